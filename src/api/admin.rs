@@ -0,0 +1,135 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, header},
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        errors::{ApiError, ApiJson, ErrorCode},
+        models::{InjectEventResponse, ModifiersResponse},
+        multiplayer_service::MultiplayerGameService,
+        openapi::{AdminActionErrors, AdminReadErrors, CancelModifierErrors, examples},
+    },
+    systems::{
+        events::EventSpec,
+        modifiers::{GlobalModifier, ModifierSpec},
+    },
+};
+
+/// Who may use the admin API: whoever presents the token the server was
+/// started with. Without one the admin API stays shut.
+#[derive(Debug, Clone, Default)]
+pub struct AdminToken(Option<String>);
+
+impl AdminToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(Some(token.into()))
+    }
+
+    /// KZRK_ADMIN_TOKEN opens the admin API
+    pub fn from_env() -> Self {
+        match std::env::var("KZRK_ADMIN_TOKEN") {
+            Ok(token) if !token.trim().is_empty() => Self::new(token.trim()),
+            _ => Self::default(),
+        }
+    }
+
+    /// Admin requests carry `Authorization: Bearer <token>`
+    pub fn authorize(&self, headers: &HeaderMap) -> Result<(), ApiError> {
+        let Some(token) = &self.0 else {
+            return Err(ApiError::new(
+                ErrorCode::AdminUnauthorized,
+                "The admin API is off: start the server with KZRK_ADMIN_TOKEN",
+            ));
+        };
+        let presented = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if presented != Some(token.as_str()) {
+            return Err(ApiError::new(
+                ErrorCode::AdminUnauthorized,
+                "Admin token missing or wrong",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Price and fee modifiers running in a room
+#[utoipa::path(
+    get,
+    path = "/admin/rooms/{room_id}/modifiers",
+    responses((status = 200, description = "Success"), AdminReadErrors)
+)]
+pub async fn get_room_modifiers(
+    State(service): State<MultiplayerGameService>,
+    Path(room_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Json<ModifiersResponse>, ApiError> {
+    service.admin().authorize(&headers)?;
+    service
+        .get_room_modifiers(room_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Adjust a room's cargo prices, fuel prices or broker fees by a percentage for some turns
+#[utoipa::path(
+    post,
+    path = "/admin/rooms/{room_id}/modifiers",
+    request_body(content = ModifierSpec, example = examples::modifier),
+    responses((status = 200, description = "Success"), AdminActionErrors)
+)]
+pub async fn apply_room_modifier(
+    State(service): State<MultiplayerGameService>,
+    Path(room_id): Path<Uuid>,
+    headers: HeaderMap,
+    ApiJson(spec): ApiJson<ModifierSpec>,
+) -> Result<Json<GlobalModifier>, ApiError> {
+    service.admin().authorize(&headers)?;
+    service
+        .apply_room_modifier(room_id, spec)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Stop a modifier early
+#[utoipa::path(
+    delete,
+    path = "/admin/rooms/{room_id}/modifiers/{modifier_id}",
+    responses((status = 200, description = "Success"), CancelModifierErrors)
+)]
+pub async fn cancel_room_modifier(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, modifier_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> Result<Json<GlobalModifier>, ApiError> {
+    service.admin().authorize(&headers)?;
+    service
+        .cancel_room_modifier(room_id, modifier_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Start a market event, labelled as the game master's in the event feed
+#[utoipa::path(
+    post,
+    path = "/admin/rooms/{room_id}/events",
+    request_body(content = EventSpec, example = examples::admin_event),
+    responses((status = 200, description = "Success"), AdminActionErrors)
+)]
+pub async fn inject_room_event(
+    State(service): State<MultiplayerGameService>,
+    Path(room_id): Path<Uuid>,
+    headers: HeaderMap,
+    ApiJson(spec): ApiJson<EventSpec>,
+) -> Result<Json<InjectEventResponse>, ApiError> {
+    service.admin().authorize(&headers)?;
+    service
+        .inject_room_event(room_id, spec)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
@@ -0,0 +1,266 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    Json,
+    body::Body,
+    extract::{Path, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures_util::{StreamExt, future};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::api::{
+    admin::AdminToken,
+    cluster::room_id_from_path,
+    errors::{ApiError, ApiJson, ErrorCode, ServiceError},
+    multiplayer_service::MultiplayerGameService,
+    openapi::{AdminActionErrors, AdminReadErrors},
+};
+
+/// Longest a request can be held back before it's handled
+pub const MAX_FAULT_DELAY_MS: u64 = 30_000;
+
+/// How badly a room's requests misbehave, so client developers can try
+/// their retry and reconnection logic against it. Rates run from 0.0
+/// (never) to 1.0 (every time).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct FaultConfig {
+    /// Share of requests answered with a 500 instead of being handled
+    pub error_rate: f64,
+    /// Share of requests held back before being handled
+    pub delay_rate: f64,
+    /// Each held request waits a random time up to this
+    pub max_delay_ms: u64,
+    /// Share of events dropped from the room's event stream
+    pub drop_event_rate: f64,
+}
+
+impl FaultConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, rate) in [
+            ("error_rate", self.error_rate),
+            ("delay_rate", self.delay_rate),
+            ("drop_event_rate", self.drop_event_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(format!("{} must be between 0.0 and 1.0", name));
+            }
+        }
+        if self.max_delay_ms > MAX_FAULT_DELAY_MS {
+            return Err(format!(
+                "max_delay_ms can be at most {}",
+                MAX_FAULT_DELAY_MS
+            ));
+        }
+        Ok(())
+    }
+}
+
+type RoomFaults = Arc<Mutex<HashMap<Uuid, FaultConfig>>>;
+
+/// Faults switched on per room through the admin API. Nothing is injected
+/// into rooms without an entry.
+#[derive(Debug, Clone, Default)]
+pub struct FaultInjector {
+    rooms: RoomFaults,
+    admin: AdminToken,
+}
+
+impl FaultInjector {
+    /// The admin API stays shut until a token is set
+    #[allow(dead_code)]
+    pub fn with_admin_token(mut self, token: impl Into<String>) -> Self {
+        self.admin = AdminToken::new(token);
+        self
+    }
+
+    /// KZRK_ADMIN_TOKEN opens the admin API
+    pub fn from_env() -> Self {
+        Self {
+            admin: AdminToken::from_env(),
+            ..Self::default()
+        }
+    }
+
+    pub fn get(&self, room_id: Uuid) -> Option<FaultConfig> {
+        self.rooms.lock().ok()?.get(&room_id).copied()
+    }
+
+    pub fn set(&self, room_id: Uuid, faults: FaultConfig) -> Result<(), ServiceError> {
+        faults.validate()?;
+        self.rooms
+            .lock()
+            .map_err(|_| ServiceError::lock("faults"))?
+            .insert(room_id, faults);
+        Ok(())
+    }
+
+    /// Whether the room had any faults to clear
+    pub fn clear(&self, room_id: Uuid) -> Result<bool, ServiceError> {
+        Ok(self
+            .rooms
+            .lock()
+            .map_err(|_| ServiceError::lock("faults"))?
+            .remove(&room_id)
+            .is_some())
+    }
+
+    /// Admin requests carry `Authorization: Bearer <token>`
+    fn authorize(&self, headers: &HeaderMap) -> Result<(), ApiError> {
+        self.admin.authorize(headers)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultsResponse {
+    pub room_id: Uuid,
+    /// False when the room behaves normally
+    pub enabled: bool,
+    pub faults: FaultConfig,
+}
+
+impl FaultsResponse {
+    fn new(room_id: Uuid, faults: Option<FaultConfig>) -> Self {
+        Self {
+            room_id,
+            enabled: faults.is_some(),
+            faults: faults.unwrap_or_default(),
+        }
+    }
+}
+
+/// Middleware that makes requests for rooms with faults misbehave: fail
+/// outright, arrive late, or (for the event stream) lose events
+pub async fn inject_faults(
+    State(service): State<MultiplayerGameService>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(room_id) = room_id_from_path(request.uri().path()) else {
+        return next.run(request).await;
+    };
+    let Some(faults) = service.faults().get(room_id) else {
+        return next.run(request).await;
+    };
+
+    let (fail, delay) = {
+        let mut rng = rand::thread_rng();
+        let fail = rng.gen_bool(faults.error_rate);
+        let delay = (faults.max_delay_ms > 0 && rng.gen_bool(faults.delay_rate))
+            .then(|| Duration::from_millis(rng.gen_range(0..=faults.max_delay_ms)));
+        (fail, delay)
+    };
+
+    if let Some(delay) = delay {
+        tokio::time::sleep(delay).await;
+    }
+    if fail {
+        return ApiError::new(
+            ErrorCode::InjectedFault,
+            "Injected fault: random server error",
+        )
+        .into_response();
+    }
+
+    let is_stream = request.uri().path().ends_with("/stream");
+    let response = next.run(request).await;
+    if !is_stream || faults.drop_event_rate == 0.0 {
+        return response;
+    }
+
+    // Each chunk of an event stream is one event
+    let (parts, body) = response.into_parts();
+    let events = body
+        .into_data_stream()
+        .filter(move |_| future::ready(!rand::thread_rng().gen_bool(faults.drop_event_rate)));
+    Response::from_parts(parts, Body::from_stream(events))
+}
+
+/// Faults switched on in a room
+#[utoipa::path(
+    get,
+    path = "/admin/rooms/{room_id}/faults",
+    responses((status = 200, description = "Success"), AdminReadErrors)
+)]
+pub async fn get_room_faults(
+    State(service): State<MultiplayerGameService>,
+    Path(room_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Json<FaultsResponse>, ApiError> {
+    service.faults().authorize(&headers)?;
+    service
+        .get_room_faults(room_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Make a room's requests fail, stall or lose stream events, for testing clients
+#[utoipa::path(
+    post,
+    path = "/admin/rooms/{room_id}/faults",
+    request_body = FaultConfig,
+    responses((status = 200, description = "Success"), AdminActionErrors)
+)]
+pub async fn set_room_faults(
+    State(service): State<MultiplayerGameService>,
+    Path(room_id): Path<Uuid>,
+    headers: HeaderMap,
+    ApiJson(faults): ApiJson<FaultConfig>,
+) -> Result<Json<FaultsResponse>, ApiError> {
+    service.faults().authorize(&headers)?;
+    service
+        .set_room_faults(room_id, faults)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Switch a room's faults off
+#[utoipa::path(
+    delete,
+    path = "/admin/rooms/{room_id}/faults",
+    responses((status = 200, description = "Success"), AdminReadErrors)
+)]
+pub async fn clear_room_faults(
+    State(service): State<MultiplayerGameService>,
+    Path(room_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Json<FaultsResponse>, ApiError> {
+    service.faults().authorize(&headers)?;
+    service
+        .clear_room_faults(room_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+impl MultiplayerGameService {
+    pub fn get_room_faults(&self, room_id: Uuid) -> Result<FaultsResponse, ServiceError> {
+        self.require_room(room_id)?;
+        Ok(FaultsResponse::new(room_id, self.faults().get(room_id)))
+    }
+
+    pub fn set_room_faults(
+        &self,
+        room_id: Uuid,
+        faults: FaultConfig,
+    ) -> Result<FaultsResponse, ServiceError> {
+        self.require_room(room_id)?;
+        self.faults().set(room_id, faults)?;
+        Ok(FaultsResponse::new(room_id, Some(faults)))
+    }
+
+    pub fn clear_room_faults(&self, room_id: Uuid) -> Result<FaultsResponse, ServiceError> {
+        self.require_room(room_id)?;
+        self.faults().clear(room_id)?;
+        Ok(FaultsResponse::new(room_id, None))
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::api::{
+    errors::{ApiError, ErrorCode},
+    multiplayer_service::MultiplayerGameService,
+};
+
+/// Response header naming the instance that served (or redirected) a request
+pub const INSTANCE_HEADER: &str = "X-Kzrk-Instance";
+
+const DEFAULT_LEASE_TTL_SECS: u64 = 30;
+
+/// Identity of this API instance when several share one database. Each room
+/// is owned by at most one instance at a time through a lease row in the
+/// database; requests for a room owned elsewhere are redirected there.
+#[derive(Debug, Clone)]
+pub struct ClusterConfig {
+    pub instance_id: String,
+    /// Base URL other instances redirect clients to, e.g. http://10.0.0.2:3000
+    pub advertise_url: String,
+    pub lease_ttl: Duration,
+}
+
+impl ClusterConfig {
+    pub fn new(instance_id: impl Into<String>, advertise_url: impl Into<String>) -> Self {
+        Self {
+            instance_id: instance_id.into(),
+            advertise_url: advertise_url.into().trim_end_matches('/').to_string(),
+            lease_ttl: Duration::from_secs(DEFAULT_LEASE_TTL_SECS),
+        }
+    }
+
+    pub fn with_lease_ttl(mut self, lease_ttl: Duration) -> Self {
+        self.lease_ttl = lease_ttl;
+        self
+    }
+
+    /// Clustering is on when KZRK_ADVERTISE_URL is set. KZRK_INSTANCE_ID
+    /// defaults to a random id and KZRK_LEASE_TTL_SECS to 30.
+    pub fn from_env() -> Option<Self> {
+        let advertise_url = std::env::var("KZRK_ADVERTISE_URL").ok()?;
+        let instance_id =
+            std::env::var("KZRK_INSTANCE_ID").unwrap_or_else(|_| Uuid::new_v4().to_string());
+        let lease_ttl = std::env::var("KZRK_LEASE_TTL_SECS")
+            .ok()
+            .and_then(|secs| secs.trim().parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(DEFAULT_LEASE_TTL_SECS));
+
+        Some(Self::new(instance_id, advertise_url).with_lease_ttl(lease_ttl))
+    }
+}
+
+/// Where requests for a room should be handled
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoomOwner {
+    Local,
+    Remote {
+        instance_id: String,
+        instance_url: String,
+    },
+}
+
+pub(crate) fn room_id_from_path(path: &str) -> Option<Uuid> {
+    let mut segments = path.trim_start_matches('/').split('/');
+    if segments.next()? != "rooms" {
+        return None;
+    }
+    Uuid::parse_str(segments.next()?).ok()
+}
+
+/// Middleware that sends requests for a room to the instance holding its
+/// lease. Rooms are redirected with 307 so the method and body are kept.
+pub async fn route_to_room_owner(
+    State(service): State<MultiplayerGameService>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(cluster) = service.cluster().cloned() else {
+        return next.run(request).await;
+    };
+
+    let owner = match room_id_from_path(request.uri().path()) {
+        Some(room_id) => service.room_owner(room_id),
+        None => Ok(RoomOwner::Local),
+    };
+
+    let mut response = match owner {
+        Ok(RoomOwner::Local) => next.run(request).await,
+        Ok(RoomOwner::Remote {
+            instance_id,
+            instance_url,
+        }) => {
+            let path_and_query = request
+                .uri()
+                .path_and_query()
+                .map(|pq| pq.as_str())
+                .unwrap_or("/");
+            let location = format!("{}{}", instance_url, path_and_query);
+
+            let mut response = StatusCode::TEMPORARY_REDIRECT.into_response();
+            if let Ok(location) = HeaderValue::from_str(&location) {
+                response.headers_mut().insert(header::LOCATION, location);
+            }
+            tracing::debug!("Redirecting {} to room owner {}", location, instance_id);
+            response
+        },
+        Err(e) => ApiError::new(
+            ErrorCode::RoomOwnershipUnavailable,
+            format!("Could not check which instance owns the room: {}", e),
+        )
+        .into_response(),
+    };
+
+    if let Ok(instance) = HeaderValue::from_str(&cluster.instance_id) {
+        response.headers_mut().insert(INSTANCE_HEADER, instance);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_room_id_from_path() {
+        let room_id = Uuid::new_v4();
+
+        assert_eq!(
+            room_id_from_path(&format!("/rooms/{}/players/x/state", room_id)),
+            Some(room_id)
+        );
+        assert_eq!(
+            room_id_from_path(&format!("/rooms/{}/join", room_id)),
+            Some(room_id)
+        );
+        assert_eq!(room_id_from_path("/rooms"), None);
+        assert_eq!(room_id_from_path("/rooms/not-a-room"), None);
+        assert_eq!(room_id_from_path(&format!("/templates/{}", room_id)), None);
+    }
+}
@@ -1,8 +1,80 @@
-use rusqlite::{Connection, Result as SqlResult, params};
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use rusqlite::{
+    Connection, OptionalExtension, Result as SqlResult, Transaction, TransactionBehavior, params,
+};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 use uuid::Uuid;
 
-use crate::systems::{GameRoom, PlayerSession};
+use crate::{
+    api::cluster::ClusterConfig,
+    models::Message,
+    systems::{
+        GameRoom, PlayerSession, RoomTemplate,
+        achievements::PilotProfile,
+        multiplayer::TurnRecord,
+        rankings::{GameResult, RankingEntry},
+        retention::ArchivedEntry,
+    },
+};
+
+/// Which instance owns a room, and until when
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoomLease {
+    pub room_id: Uuid,
+    pub instance_id: String,
+    pub instance_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LeaseOutcome {
+    /// This instance holds the lease. `renewed` is false when it has just
+    /// taken the room over, so its in-memory copy may be stale.
+    Acquired {
+        renewed: bool,
+    },
+    HeldBy(RoomLease),
+}
+
+/// How hard SQLite works to get each commit onto disk before carrying on
+/// (its `synchronous` setting)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JournalSync {
+    /// Hand writes to the OS and don't wait. A power cut can lose recent
+    /// commits or corrupt the file; a crashed server loses nothing.
+    Off,
+    /// Sync at the critical moments only
+    Normal,
+    /// Sync on every commit, SQLite's default
+    #[default]
+    Full,
+}
+
+impl JournalSync {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" => Some(JournalSync::Off),
+            "normal" => Some(JournalSync::Normal),
+            "full" => Some(JournalSync::Full),
+            _ => None,
+        }
+    }
+
+    fn pragma_value(&self) -> &'static str {
+        match self {
+            JournalSync::Off => "OFF",
+            JournalSync::Normal => "NORMAL",
+            JournalSync::Full => "FULL",
+        }
+    }
+}
+
+/// Some of a season's ranking rows, and how many pilots are ranked in all
+pub type RankingsPage = (Vec<RankingEntry>, usize);
 
 pub struct Database {
     conn: Connection,
@@ -11,6 +83,8 @@ pub struct Database {
 impl Database {
     pub fn new(db_path: &str) -> SqlResult<Self> {
         let conn = Connection::open(db_path)?;
+        // Several API instances may share the file; wait on their locks
+        conn.busy_timeout(Duration::from_secs(5))?;
         let db = Database { conn };
         db.create_tables()?;
         Ok(db)
@@ -23,6 +97,36 @@ impl Database {
         Ok(db)
     }
 
+    pub fn set_journal_sync(&self, sync: JournalSync) -> SqlResult<()> {
+        self.conn
+            .pragma_update(None, "synchronous", sync.pragma_value())
+    }
+
+    #[allow(dead_code)]
+    pub fn journal_sync(&self) -> SqlResult<JournalSync> {
+        let level: i64 = self
+            .conn
+            .pragma_query_value(None, "synchronous", |row| row.get(0))?;
+        Ok(match level {
+            0 => JournalSync::Off,
+            1 => JournalSync::Normal,
+            _ => JournalSync::Full,
+        })
+    }
+
+    /// Write a consistent copy of the whole database to `path`, replacing
+    /// any earlier snapshot there only once the new one is complete
+    pub fn snapshot_to(&self, path: &Path) -> SqlResult<()> {
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".partial");
+        let partial = PathBuf::from(partial);
+        let _ = std::fs::remove_file(&partial);
+
+        self.conn
+            .execute("VACUUM INTO ?1", params![partial.to_string_lossy()])?;
+        std::fs::rename(&partial, path).map_err(|_| rusqlite::Error::InvalidPath(path.into()))
+    }
+
     fn create_tables(&self) -> SqlResult<()> {
         // Create rooms table
         self.conn.execute(
@@ -47,12 +151,83 @@ impl Database {
             [],
         )?;
 
+        // Create room templates table
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS room_templates (
+                id TEXT PRIMARY KEY,
+                data TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )?;
+
+        // Create pilot profiles table, which outlives any one room
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS pilot_profiles (
+                pilot_name TEXT PRIMARY KEY,
+                data TEXT NOT NULL,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )?;
+
+        // Create finished game results table, which rankings are summed from
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS game_results (
+                room_id TEXT NOT NULL,
+                round INTEGER NOT NULL,
+                pilot_name TEXT NOT NULL,
+                season TEXT NOT NULL,
+                placement INTEGER NOT NULL,
+                profit INTEGER NOT NULL,
+                points INTEGER NOT NULL,
+                finished_at INTEGER NOT NULL,
+                PRIMARY KEY (room_id, round, pilot_name)
+            )",
+            [],
+        )?;
+
+        // Create room ownership leases table, shared by every API instance
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS room_leases (
+                room_id TEXT PRIMARY KEY,
+                instance_id TEXT NOT NULL,
+                instance_url TEXT NOT NULL,
+                expires_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create archive of entries trimmed out of rooms by their retention limits
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS archived_entries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                room_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                owner TEXT NOT NULL,
+                data TEXT NOT NULL,
+                archived_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )?;
+
         // Create index on player_name for quick lookups
         self.conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_sessions_player_name ON sessions(player_name)",
             [],
         )?;
 
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_game_results_season ON game_results(season)",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_archived_entries_owner
+             ON archived_entries(room_id, kind, owner)",
+            [],
+        )?;
+
         Ok(())
     }
 
@@ -78,6 +253,320 @@ impl Database {
         Ok(())
     }
 
+    pub fn save_template(&self, template: &RoomTemplate) -> SqlResult<()> {
+        let json_data = serde_json::to_string(template)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO room_templates (id, data) VALUES (?1, ?2)",
+            params![template.id.to_string(), json_data],
+        )?;
+        Ok(())
+    }
+
+    pub fn save_pilot_profile(&self, profile: &PilotProfile) -> SqlResult<()> {
+        let json_data = serde_json::to_string(profile)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO pilot_profiles (pilot_name, data, updated_at) VALUES (?1, ?2, CURRENT_TIMESTAMP)",
+            params![profile.pilot_name, json_data],
+        )?;
+        Ok(())
+    }
+
+    pub fn load_pilot_profile(&self, pilot_name: &str) -> SqlResult<Option<PilotProfile>> {
+        let data: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT data FROM pilot_profiles WHERE pilot_name = ?1",
+                params![pilot_name],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(data.and_then(|data| serde_json::from_str(&data).ok()))
+    }
+
+    /// Record how everyone did in a finished round. A round's results are
+    /// only counted once however often they're recorded.
+    pub fn record_game_results(&self, results: &[GameResult]) -> SqlResult<usize> {
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let mut recorded = 0;
+        for result in results {
+            recorded += tx.execute(
+                "INSERT OR IGNORE INTO game_results
+                 (room_id, round, pilot_name, season, placement, profit, points, finished_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    result.room_id.to_string(),
+                    result.round,
+                    result.pilot_name,
+                    result.season,
+                    result.placement,
+                    result.profit,
+                    result.points,
+                    result.finished_at.timestamp_millis()
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(recorded)
+    }
+
+    /// Keep entries a room no longer has room for, in the order given
+    pub fn archive_entries(&self, room_id: &Uuid, entries: &[ArchivedEntry]) -> SqlResult<()> {
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        for entry in entries {
+            let json_data = entry
+                .to_json()
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            tx.execute(
+                "INSERT INTO archived_entries (room_id, kind, owner, data) VALUES (?1, ?2, ?3, ?4)",
+                params![room_id.to_string(), entry.kind(), entry.owner(), json_data],
+            )?;
+        }
+        tx.commit()
+    }
+
+    /// Archived messages from an airport's board, most recent first
+    pub fn load_archived_messages(
+        &self,
+        room_id: &Uuid,
+        airport_id: &str,
+        limit: usize,
+    ) -> SqlResult<Vec<Message>> {
+        self.load_archived(room_id, "message", airport_id, limit)
+    }
+
+    /// A player's archived journal turns, oldest first
+    pub fn load_archived_turns(
+        &self,
+        room_id: &Uuid,
+        player_id: &Uuid,
+    ) -> SqlResult<Vec<TurnRecord>> {
+        let mut turns: Vec<TurnRecord> =
+            self.load_archived(room_id, "turn", &player_id.to_string(), usize::MAX)?;
+        turns.reverse();
+        Ok(turns)
+    }
+
+    /// Up to `limit` archived entries of one kind, most recent first
+    fn load_archived<T: serde::de::DeserializeOwned>(
+        &self,
+        room_id: &Uuid,
+        kind: &str,
+        owner: &str,
+        limit: usize,
+    ) -> SqlResult<Vec<T>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT data FROM archived_entries
+             WHERE room_id = ?1 AND kind = ?2 AND owner = ?3
+             ORDER BY id DESC LIMIT ?4",
+        )?;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let rows = stmt.query_map(params![room_id.to_string(), kind, owner, limit], |row| {
+            row.get::<_, String>(0)
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            if let Ok(entry) = serde_json::from_str(&row?) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// One page of a season's table, best first
+    pub fn load_season_rankings(
+        &self,
+        season: &str,
+        offset: usize,
+        limit: usize,
+    ) -> SqlResult<RankingsPage> {
+        let total: i64 = self.conn.query_row(
+            "SELECT COUNT(DISTINCT pilot_name) FROM game_results WHERE season = ?1",
+            params![season],
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT pilot_name, SUM(points) AS total_points, COUNT(*),
+                    SUM(placement = 1), SUM(profit)
+             FROM game_results WHERE season = ?1
+             GROUP BY pilot_name
+             ORDER BY total_points DESC, SUM(profit) DESC, pilot_name
+             LIMIT ?2 OFFSET ?3",
+        )?;
+        let rows = stmt.query_map(params![season, limit as i64, offset as i64], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u32>(1)?,
+                row.get::<_, u32>(2)?,
+                row.get::<_, u32>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?;
+
+        let mut entries = Vec::new();
+        for (index, row) in rows.enumerate() {
+            let (pilot_name, points, games_played, wins, total_profit) = row?;
+            entries.push(RankingEntry {
+                rank: offset + index + 1,
+                pilot_name,
+                points,
+                games_played,
+                wins,
+                total_profit,
+            });
+        }
+        Ok((entries, total as usize))
+    }
+
+    /// Every season with results, most recent first
+    pub fn list_ranked_seasons(&self) -> SqlResult<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT season FROM game_results ORDER BY season DESC")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    /// Save a room on behalf of `cluster`'s instance, taking or renewing its
+    /// lease in the same transaction. Nothing is written if another instance
+    /// holds the room.
+    pub fn save_owned_room(
+        &self,
+        room: &GameRoom,
+        cluster: &ClusterConfig,
+        now: DateTime<Utc>,
+    ) -> SqlResult<LeaseOutcome> {
+        let json_data = serde_json::to_string(room)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let outcome = Self::acquire_lease_in(&tx, &room.id, cluster, now)?;
+        if matches!(outcome, LeaseOutcome::Acquired { .. }) {
+            tx.execute(
+                "INSERT OR REPLACE INTO rooms (id, data, updated_at) VALUES (?1, ?2, CURRENT_TIMESTAMP)",
+                params![room.id.to_string(), json_data],
+            )?;
+        }
+        tx.commit()?;
+        Ok(outcome)
+    }
+
+    /// Take the lease on a room, or renew it if this instance already holds
+    /// it. Fails over to this instance once another holder's lease expires.
+    pub fn acquire_room_lease(
+        &self,
+        room_id: &Uuid,
+        cluster: &ClusterConfig,
+        now: DateTime<Utc>,
+    ) -> SqlResult<LeaseOutcome> {
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let outcome = Self::acquire_lease_in(&tx, room_id, cluster, now)?;
+        tx.commit()?;
+        Ok(outcome)
+    }
+
+    fn acquire_lease_in(
+        tx: &Transaction<'_>,
+        room_id: &Uuid,
+        cluster: &ClusterConfig,
+        now: DateTime<Utc>,
+    ) -> SqlResult<LeaseOutcome> {
+        let current = Self::query_lease(tx, room_id)?;
+        let renewed = match current {
+            Some(lease) if lease.expires_at > now => {
+                if lease.instance_id != cluster.instance_id {
+                    return Ok(LeaseOutcome::HeldBy(lease));
+                }
+                true
+            },
+            _ => false,
+        };
+
+        let ttl = chrono::Duration::from_std(cluster.lease_ttl)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        tx.execute(
+            "INSERT OR REPLACE INTO room_leases (room_id, instance_id, instance_url, expires_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                room_id.to_string(),
+                cluster.instance_id,
+                cluster.advertise_url,
+                (now + ttl).timestamp_millis()
+            ],
+        )?;
+        Ok(LeaseOutcome::Acquired { renewed })
+    }
+
+    #[allow(dead_code)]
+    pub fn room_lease(&self, room_id: &Uuid) -> SqlResult<Option<RoomLease>> {
+        Self::query_lease(&self.conn, room_id)
+    }
+
+    fn query_lease(conn: &Connection, room_id: &Uuid) -> SqlResult<Option<RoomLease>> {
+        conn.query_row(
+            "SELECT instance_id, instance_url, expires_at FROM room_leases WHERE room_id = ?1",
+            params![room_id.to_string()],
+            |row| {
+                let expires_at: i64 = row.get(2)?;
+                Ok(RoomLease {
+                    room_id: *room_id,
+                    instance_id: row.get(0)?,
+                    instance_url: row.get(1)?,
+                    expires_at: DateTime::from_timestamp_millis(expires_at).unwrap_or_default(),
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// Give up every lease held by an instance, e.g. when it shuts down
+    pub fn release_room_leases(&self, instance_id: &str) -> SqlResult<usize> {
+        self.conn.execute(
+            "DELETE FROM room_leases WHERE instance_id = ?1",
+            params![instance_id],
+        )
+    }
+
+    pub fn load_room(&self, room_id: &Uuid) -> SqlResult<Option<GameRoom>> {
+        let data: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT data FROM rooms WHERE id = ?1",
+                params![room_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(data.and_then(|data| serde_json::from_str(&data).ok()))
+    }
+
+    pub fn load_all_templates(&self) -> SqlResult<HashMap<Uuid, RoomTemplate>> {
+        let mut stmt = self.conn.prepare("SELECT id, data FROM room_templates")?;
+        let rows = stmt.query_map([], |row| {
+            let id_str: String = row.get(0)?;
+            let data: String = row.get(1)?;
+            Ok((id_str, data))
+        })?;
+
+        let mut templates = HashMap::new();
+        for row in rows {
+            let (id_str, data) = row?;
+            if let (Ok(id), Ok(template)) = (
+                Uuid::parse_str(&id_str),
+                serde_json::from_str::<RoomTemplate>(&data),
+            ) {
+                templates.insert(id, template);
+            }
+        }
+        Ok(templates)
+    }
+
     pub fn load_all_rooms(&self) -> SqlResult<HashMap<Uuid, GameRoom>> {
         let mut stmt = self.conn.prepare("SELECT id, data FROM rooms")?;
         let rows = stmt.query_map([], |row| {
@@ -120,6 +609,18 @@ impl Database {
         Ok(sessions)
     }
 
+    pub fn load_session(&self, player_id: &Uuid) -> SqlResult<Option<PlayerSession>> {
+        let data: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT data FROM sessions WHERE player_id = ?1",
+                params![player_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(data.and_then(|data| serde_json::from_str(&data).ok()))
+    }
+
     pub fn find_sessions_by_player_name(&self, player_name: &str) -> SqlResult<Vec<PlayerSession>> {
         let mut stmt = self
             .conn
@@ -145,10 +646,13 @@ impl Database {
             "DELETE FROM rooms WHERE id = ?1",
             params![room_id.to_string()],
         )?;
+        self.conn.execute(
+            "DELETE FROM archived_entries WHERE room_id = ?1",
+            params![room_id.to_string()],
+        )?;
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn delete_session(&self, player_id: &Uuid) -> SqlResult<()> {
         self.conn.execute(
             "DELETE FROM sessions WHERE player_id = ?1",
@@ -0,0 +1,61 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::{
+    api::webhooks::{CurlWebhookSender, WebhookSender},
+    systems::digest::{Digest, DigestTarget},
+};
+
+/// Delivers digests built by the summary job
+pub trait DigestSender: Send + Sync {
+    fn send(&self, target: &DigestTarget, digest: &Digest) -> Result<(), String>;
+}
+
+/// Posts webhooks with `curl` and hands email to the local `sendmail`, the
+/// same way the rest of KZRK leans on tools already on the machine.
+pub struct CommandDigestSender;
+
+impl DigestSender for CommandDigestSender {
+    fn send(&self, target: &DigestTarget, digest: &Digest) -> Result<(), String> {
+        // Addresses were validated on registration, but they come from
+        // players and end up on a command line, so check again
+        target.validate()?;
+
+        match target {
+            DigestTarget::Webhook(url) => {
+                let body = serde_json::to_value(digest)
+                    .map_err(|e| format!("Failed to serialize digest: {}", e))?;
+                CurlWebhookSender.post(url, &body)?;
+            },
+            DigestTarget::Email(address) => {
+                let message = format!(
+                    "To: {}\nSubject: {}\nContent-Type: text/plain; charset=utf-8\n\n{}",
+                    address,
+                    digest.subject(),
+                    digest.text()
+                );
+                let status = Command::new("sendmail")
+                    .args(["-i", "--"])
+                    .arg(address)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::null())
+                    .spawn()
+                    .and_then(|mut child| {
+                        if let Some(mut stdin) = child.stdin.take() {
+                            stdin.write_all(message.as_bytes())?;
+                        }
+                        child.wait()
+                    })
+                    .map_err(|e| format!("Failed to run sendmail: {}", e))?;
+
+                if !status.success() {
+                    return Err(format!("sendmail exited with {}", status));
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
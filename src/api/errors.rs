@@ -0,0 +1,491 @@
+use axum::{
+    Json,
+    extract::{FromRequest, Request, rejection::JsonRejection},
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::{Serialize, de::DeserializeOwned};
+use utoipa::{
+    PartialSchema, ToSchema,
+    openapi::{ObjectBuilder, RefOr, Schema, Type},
+};
+
+use crate::{
+    api::models::ErrorResponse,
+    models::locale::{LocalizedMessage, MessageCode},
+    systems::{errors::RoomError, trading::TradingError},
+};
+
+/// Every error the API returns. The code is sent as `ErrorResponse::error`
+/// and always comes with the same HTTP status, so clients can branch on
+/// either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    GameNotFound,
+    RoomNotFound,
+    PlayerNotInRoom,
+    SessionNotFound,
+    TemplateNotFound,
+    LossNotFound,
+    OfferNotFound,
+    ModifierNotFound,
+    MessageNotFound,
+    InvalidBody,
+    InvalidRequest,
+    ActionRejected,
+    ActionCooldown,
+    ConfirmationRequired,
+    RoomOwnershipUnavailable,
+    AdminUnauthorized,
+    PayloadTooLarge,
+    ServerBusy,
+    RequestTimedOut,
+    InjectedFault,
+    InternalError,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 21] = [
+        ErrorCode::GameNotFound,
+        ErrorCode::RoomNotFound,
+        ErrorCode::PlayerNotInRoom,
+        ErrorCode::SessionNotFound,
+        ErrorCode::TemplateNotFound,
+        ErrorCode::LossNotFound,
+        ErrorCode::OfferNotFound,
+        ErrorCode::ModifierNotFound,
+        ErrorCode::MessageNotFound,
+        ErrorCode::InvalidBody,
+        ErrorCode::InvalidRequest,
+        ErrorCode::ActionRejected,
+        ErrorCode::ActionCooldown,
+        ErrorCode::ConfirmationRequired,
+        ErrorCode::RoomOwnershipUnavailable,
+        ErrorCode::AdminUnauthorized,
+        ErrorCode::PayloadTooLarge,
+        ErrorCode::ServerBusy,
+        ErrorCode::RequestTimedOut,
+        ErrorCode::InjectedFault,
+        ErrorCode::InternalError,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::GameNotFound => "GameNotFound",
+            ErrorCode::RoomNotFound => "RoomNotFound",
+            ErrorCode::PlayerNotInRoom => "PlayerNotInRoom",
+            ErrorCode::SessionNotFound => "SessionNotFound",
+            ErrorCode::TemplateNotFound => "TemplateNotFound",
+            ErrorCode::LossNotFound => "LossNotFound",
+            ErrorCode::OfferNotFound => "OfferNotFound",
+            ErrorCode::ModifierNotFound => "ModifierNotFound",
+            ErrorCode::MessageNotFound => "MessageNotFound",
+            ErrorCode::InvalidBody => "InvalidBody",
+            ErrorCode::InvalidRequest => "InvalidRequest",
+            ErrorCode::ActionRejected => "ActionRejected",
+            ErrorCode::ActionCooldown => "ActionCooldown",
+            ErrorCode::ConfirmationRequired => "ConfirmationRequired",
+            ErrorCode::RoomOwnershipUnavailable => "RoomOwnershipUnavailable",
+            ErrorCode::AdminUnauthorized => "AdminUnauthorized",
+            ErrorCode::PayloadTooLarge => "PayloadTooLarge",
+            ErrorCode::ServerBusy => "ServerBusy",
+            ErrorCode::RequestTimedOut => "RequestTimedOut",
+            ErrorCode::InjectedFault => "InjectedFault",
+            ErrorCode::InternalError => "InternalError",
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ErrorCode::GameNotFound
+            | ErrorCode::RoomNotFound
+            | ErrorCode::PlayerNotInRoom
+            | ErrorCode::SessionNotFound
+            | ErrorCode::TemplateNotFound
+            | ErrorCode::LossNotFound
+            | ErrorCode::OfferNotFound
+            | ErrorCode::ModifierNotFound
+            | ErrorCode::MessageNotFound => StatusCode::NOT_FOUND,
+            ErrorCode::InvalidBody => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::InvalidRequest | ErrorCode::ActionRejected => StatusCode::BAD_REQUEST,
+            ErrorCode::ActionCooldown => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::ConfirmationRequired => StatusCode::CONFLICT,
+            ErrorCode::RoomOwnershipUnavailable
+            | ErrorCode::ServerBusy
+            | ErrorCode::RequestTimedOut => StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::AdminUnauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorCode::InjectedFault | ErrorCode::InternalError => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            },
+        }
+    }
+
+    /// What the message says, with placeholders for the specifics
+    pub fn message_template(&self) -> &'static str {
+        match self {
+            ErrorCode::GameNotFound => "Game session not found",
+            ErrorCode::RoomNotFound => "Room not found",
+            ErrorCode::PlayerNotInRoom => "Player not in room",
+            ErrorCode::SessionNotFound => "Session not found",
+            ErrorCode::TemplateNotFound => "Template not found",
+            ErrorCode::LossNotFound => "No unclaimed loss with that id",
+            ErrorCode::OfferNotFound => "Offer not found",
+            ErrorCode::ModifierNotFound => "Modifier not found",
+            ErrorCode::MessageNotFound => "Message not found",
+            ErrorCode::InvalidBody => "The request body could not be read: {reason}",
+            ErrorCode::InvalidRequest => "{reason}, e.g. a bad name, setting or airport",
+            ErrorCode::ActionRejected => "{reason}, e.g. not enough money, fuel or cargo space",
+            ErrorCode::ActionCooldown => "Too many actions: try again in {retry_after_ms} ms",
+            ErrorCode::ConfirmationRequired => "{reason}. Send force=true to go ahead anyway",
+            ErrorCode::RoomOwnershipUnavailable => {
+                "Could not check which instance owns the room: {reason}"
+            },
+            ErrorCode::AdminUnauthorized => "Admin token missing or wrong",
+            ErrorCode::PayloadTooLarge => "Request bodies can be at most {max_bytes} bytes",
+            ErrorCode::ServerBusy => "The server is busy: try again shortly",
+            ErrorCode::RequestTimedOut => "The server took longer than {seconds} s to answer",
+            ErrorCode::InjectedFault => "Injected fault: {reason}, on servers built for testing",
+            ErrorCode::InternalError => "Failed to acquire {resource} lock",
+        }
+    }
+}
+
+/// Codes are sent as their names, so the schema is a string that can only
+/// be one of them
+impl PartialSchema for ErrorCode {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .enum_values(Some(ErrorCode::ALL.map(|code| code.as_str())))
+            .into()
+    }
+}
+
+impl ToSchema for ErrorCode {}
+
+/// An error from the game services. Missing resources, cooldowns and
+/// internal failures carry what the API answers with; anything else is the
+/// game refusing, and the handler picks the code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceError {
+    NotFound(ErrorCode),
+    Cooldown {
+        retry_after_ms: u64,
+    },
+    Internal(String),
+    Refused(String),
+    /// A refusal the catalog has a sentence for
+    Localized(LocalizedMessage),
+    /// The action would give things up and has to be asked for again with
+    /// force, e.g. leaving a room with cargo still in the hold
+    ConfirmationRequired {
+        reason: String,
+        obligations: Vec<String>,
+    },
+}
+
+impl ServiceError {
+    pub fn lock(resource: &str) -> Self {
+        ServiceError::Internal(format!("Failed to acquire {} lock", resource))
+    }
+
+    pub fn database(error: impl std::fmt::Display) -> Self {
+        ServiceError::Internal(format!("Database error: {}", error))
+    }
+
+    /// The error as a code and parameters, when the catalog has a sentence
+    /// for it
+    pub fn localized(&self) -> Option<LocalizedMessage> {
+        match self {
+            ServiceError::NotFound(ErrorCode::RoomNotFound) => {
+                Some(LocalizedMessage::new(MessageCode::RoomNotFound))
+            },
+            ServiceError::NotFound(ErrorCode::PlayerNotInRoom) => {
+                Some(LocalizedMessage::new(MessageCode::PlayerNotInRoom))
+            },
+            ServiceError::Cooldown { retry_after_ms } => Some(
+                LocalizedMessage::new(MessageCode::ActionCooldown)
+                    .with("retry_after_ms", retry_after_ms),
+            ),
+            ServiceError::Localized(localized) => Some(localized.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServiceError::NotFound(code) => f.write_str(code.message_template()),
+            ServiceError::Cooldown { retry_after_ms } => {
+                write!(f, "Too many actions: try again in {} ms", retry_after_ms)
+            },
+            ServiceError::Internal(message) | ServiceError::Refused(message) => {
+                f.write_str(message)
+            },
+            ServiceError::Localized(localized) => write!(f, "{}", localized),
+            ServiceError::ConfirmationRequired { reason, .. } => {
+                write!(f, "{}. Send force=true to go ahead anyway", reason)
+            },
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+impl From<String> for ServiceError {
+    fn from(message: String) -> Self {
+        ServiceError::Refused(message)
+    }
+}
+
+impl From<&str> for ServiceError {
+    fn from(message: &str) -> Self {
+        ServiceError::Refused(message.to_string())
+    }
+}
+
+impl From<LocalizedMessage> for ServiceError {
+    fn from(localized: LocalizedMessage) -> Self {
+        ServiceError::Localized(localized)
+    }
+}
+
+impl From<RoomError> for ServiceError {
+    fn from(error: RoomError) -> Self {
+        match error {
+            RoomError::PlayerNotInRoom => ServiceError::NotFound(ErrorCode::PlayerNotInRoom),
+            RoomError::OfferNotFound => ServiceError::NotFound(ErrorCode::OfferNotFound),
+            RoomError::ModifierNotFound => ServiceError::NotFound(ErrorCode::ModifierNotFound),
+            RoomError::MessageNotFound => ServiceError::NotFound(ErrorCode::MessageNotFound),
+            RoomError::LossNotFound => ServiceError::NotFound(ErrorCode::LossNotFound),
+            RoomError::Cooldown { retry_after_ms } => ServiceError::Cooldown { retry_after_ms },
+            RoomError::Refused(message) => ServiceError::Refused(message),
+            RoomError::Localized(localized) => ServiceError::Localized(localized),
+        }
+    }
+}
+
+impl From<TradingError> for ServiceError {
+    fn from(error: TradingError) -> Self {
+        ServiceError::Localized(error.localized())
+    }
+}
+
+/// What an error response carries under `details`, for the codes that
+/// have more to say than their message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum ErrorDetails {
+    /// How long a cooling-down player should wait
+    RetryAfter { retry_after_ms: u64 },
+    /// What a confirmation would give up
+    Obligations { obligations: Vec<String> },
+}
+
+/// An error response: a catalog code plus the specific message.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub details: Option<ErrorDetails>,
+    /// The message as a code and parameters, when it has one
+    pub localized: Option<LocalizedMessage>,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+            localized: None,
+        }
+    }
+
+    /// Wrap a service error, using `fallback` when the game refused
+    pub fn from_service(error: impl Into<ServiceError>, fallback: ErrorCode) -> Self {
+        let error = error.into();
+        let (code, details) = match &error {
+            ServiceError::NotFound(code) => (*code, None),
+            ServiceError::Cooldown { retry_after_ms } => (
+                ErrorCode::ActionCooldown,
+                Some(ErrorDetails::RetryAfter {
+                    retry_after_ms: *retry_after_ms,
+                }),
+            ),
+            ServiceError::Internal(_) => (ErrorCode::InternalError, None),
+            ServiceError::ConfirmationRequired { obligations, .. } => (
+                ErrorCode::ConfirmationRequired,
+                Some(ErrorDetails::Obligations {
+                    obligations: obligations.clone(),
+                }),
+            ),
+            ServiceError::Refused(_) | ServiceError::Localized(_) => (fallback, None),
+        };
+        Self {
+            details,
+            localized: error.localized(),
+            ..Self::new(code, error.to_string())
+        }
+    }
+
+    /// How long a cooling-down player should wait
+    pub fn retry_after_ms(&self) -> Option<u64> {
+        match self.details {
+            Some(ErrorDetails::RetryAfter { retry_after_ms }) => Some(retry_after_ms),
+            _ => None,
+        }
+    }
+
+    /// `map_err` helper for the common case
+    pub fn or<E: Into<ServiceError>>(fallback: ErrorCode) -> impl Fn(E) -> Self {
+        move |error| Self::from_service(error, fallback)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let retry_after_ms = self.retry_after_ms();
+        let mut response = (
+            self.code.status(),
+            Json(ErrorResponse {
+                error: self.code.as_str().to_string(),
+                localized: self.localized,
+                message: self.message,
+                details: self
+                    .details
+                    .and_then(|details| serde_json::to_value(details).ok()),
+            }),
+        )
+            .into_response();
+
+        // Retry-After only takes whole seconds
+        if let Some(ms) = retry_after_ms {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(ms.div_ceil(1000)));
+        }
+        response
+    }
+}
+
+/// `Json` extractor whose rejections use the catalog's `InvalidBody` error
+/// instead of axum's plain-text responses.
+pub struct ApiJson<T>(pub T);
+
+#[axum::async_trait]
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        Json::<T>::from_request(request, state)
+            .await
+            .map(|Json(value)| ApiJson(value))
+            .map_err(|rejection: JsonRejection| {
+                let code = if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                    ErrorCode::PayloadTooLarge
+                } else {
+                    ErrorCode::InvalidBody
+                };
+                ApiError::new(code, rejection.body_text())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_errors_keep_their_code() {
+        let not_found = ApiError::from_service(
+            ServiceError::NotFound(ErrorCode::RoomNotFound),
+            ErrorCode::ActionRejected,
+        );
+        assert_eq!(not_found.code, ErrorCode::RoomNotFound);
+        assert_eq!(not_found.code.status(), StatusCode::NOT_FOUND);
+        assert_eq!(not_found.message, "Room not found");
+
+        let internal =
+            ApiError::from_service(ServiceError::lock("rooms"), ErrorCode::ActionRejected);
+        assert_eq!(internal.code, ErrorCode::InternalError);
+        assert_eq!(internal.message, "Failed to acquire rooms lock");
+
+        let rejected = ApiError::from_service("Insufficient funds", ErrorCode::ActionRejected);
+        assert_eq!(rejected.code, ErrorCode::ActionRejected);
+        assert_eq!(rejected.code.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(rejected.retry_after_ms(), None);
+
+        let cooldown = ApiError::from_service(
+            ServiceError::Cooldown {
+                retry_after_ms: 1200,
+            },
+            ErrorCode::ActionRejected,
+        );
+        assert_eq!(cooldown.code, ErrorCode::ActionCooldown);
+        assert_eq!(cooldown.retry_after_ms(), Some(1200));
+        assert_eq!(cooldown.message, "Too many actions: try again in 1200 ms");
+        let localized = cooldown.localized.unwrap();
+        assert_eq!(localized.code, MessageCode::ActionCooldown);
+        assert_eq!(localized.params["retry_after_ms"], "1200");
+    }
+
+    #[test]
+    fn test_refusals_keep_their_message_code() {
+        let refused =
+            ApiError::from_service(TradingError::InsufficientFunds, ErrorCode::ActionRejected);
+        assert_eq!(refused.code, ErrorCode::ActionRejected);
+        assert_eq!(refused.message, "Insufficient funds");
+        assert_eq!(
+            refused.localized.map(|localized| localized.code),
+            Some(MessageCode::InsufficientFunds)
+        );
+
+        // Plain refusals have no code to render from
+        let plain = ApiError::from_service(
+            "Post it on the board here instead",
+            ErrorCode::ActionRejected,
+        );
+        assert_eq!(plain.localized, None);
+    }
+
+    #[test]
+    fn test_confirmations_carry_their_obligations() {
+        let error = ApiError::from_service(
+            ServiceError::ConfirmationRequired {
+                reason: "You still have assets in this room".to_string(),
+                obligations: vec!["5 units of Food".to_string()],
+            },
+            ErrorCode::ActionRejected,
+        );
+        assert_eq!(error.code, ErrorCode::ConfirmationRequired);
+        assert_eq!(error.code.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            error.message,
+            "You still have assets in this room. Send force=true to go ahead anyway"
+        );
+        assert_eq!(error.retry_after_ms(), None);
+        assert_eq!(
+            serde_json::to_value(error.details.unwrap()).unwrap(),
+            serde_json::json!({ "obligations": ["5 units of Food"] })
+        );
+    }
+
+    #[test]
+    fn test_wording_alone_never_picks_the_code() {
+        // A refusal that happens to read like a lookup is still a refusal
+        let refused = ApiError::from_service("Room not found", ErrorCode::InvalidRequest);
+        assert_eq!(refused.code, ErrorCode::InvalidRequest);
+        let refused = ApiError::from_service(
+            "Too many actions: try again in 5 ms".to_string(),
+            ErrorCode::ActionRejected,
+        );
+        assert_eq!(refused.retry_after_ms(), None);
+    }
+}
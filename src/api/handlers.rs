@@ -2,101 +2,112 @@
 
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
-    response::Json,
+    http::HeaderMap,
+    response::{Json, Response},
 };
 use uuid::Uuid;
 
-use crate::api::{models::*, service::GameService};
+use crate::api::{
+    errors::{ApiError, ApiJson, ErrorCode},
+    models::*,
+    openapi::{BodyErrors, GameReadErrors, NoErrors, SinglePlayerActionErrors, examples},
+    service::GameService,
+    stateless_handlers,
+};
 
+/// Start a game
+#[utoipa::path(
+    post,
+    path = "/game",
+    request_body(content = CreateGameRequest, example = examples::create_game),
+    responses((status = 200, description = "Success"), BodyErrors)
+)]
 pub async fn create_game(
     State(service): State<GameService>,
-    Json(request): Json<CreateGameRequest>,
-) -> Result<Json<CreateGameResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.create_game(request) {
-        Ok(response) => Ok(Json(response)),
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "GameCreationError".to_string(),
-                message: e,
-                details: None,
-            }),
-        )),
-    }
+    ApiJson(request): ApiJson<CreateGameRequest>,
+) -> Result<Json<CreateGameResponse>, ApiError> {
+    service
+        .create_game(request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
 }
 
+/// Game state
+#[utoipa::path(
+    get,
+    path = "/game/{session_id}",
+    responses((status = 200, description = "Success"), GameReadErrors)
+)]
 pub async fn get_game_state(
     State(service): State<GameService>,
     Path(session_id): Path<Uuid>,
-) -> Result<Json<GameStateResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.get_game_state(session_id) {
-        Ok(response) => Ok(Json(response)),
-        Err(e) => Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: "GameNotFound".to_string(),
-                message: e,
-                details: None,
-            }),
-        )),
-    }
+) -> Result<Json<GameStateResponse>, ApiError> {
+    service
+        .get_game_state(session_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::GameNotFound))
 }
 
+/// Fly to another airport
+#[utoipa::path(
+    post,
+    path = "/game/{session_id}/travel",
+    request_body(content = TravelRequest, example = examples::travel),
+    responses((status = 200, description = "Success"), SinglePlayerActionErrors)
+)]
 pub async fn travel(
     State(service): State<GameService>,
     Path(session_id): Path<Uuid>,
-    Json(request): Json<TravelRequest>,
-) -> Result<Json<TravelResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.travel(session_id, request) {
-        Ok(response) => Ok(Json(response)),
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "TravelError".to_string(),
-                message: e,
-                details: None,
-            }),
-        )),
-    }
+    ApiJson(request): ApiJson<TravelRequest>,
+) -> Result<Json<TravelResponse>, ApiError> {
+    service
+        .travel(session_id, request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
 }
 
+/// Buy or sell cargo
+#[utoipa::path(
+    post,
+    path = "/game/{session_id}/trade",
+    request_body(content = TradeRequest, example = examples::trade),
+    responses((status = 200, description = "Success"), SinglePlayerActionErrors)
+)]
 pub async fn trade(
     State(service): State<GameService>,
     Path(session_id): Path<Uuid>,
-    Json(request): Json<TradeRequest>,
-) -> Result<Json<TradeResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.trade(session_id, request) {
-        Ok(response) => Ok(Json(response)),
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "TradeError".to_string(),
-                message: e,
-                details: None,
-            }),
-        )),
-    }
+    ApiJson(request): ApiJson<TradeRequest>,
+) -> Result<Json<TradeResponse>, ApiError> {
+    service
+        .trade(session_id, request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
 }
 
+/// Buy fuel
+#[utoipa::path(
+    post,
+    path = "/game/{session_id}/fuel",
+    request_body(content = FuelRequest, example = examples::fuel),
+    responses((status = 200, description = "Success"), SinglePlayerActionErrors)
+)]
 pub async fn buy_fuel(
     State(service): State<GameService>,
     Path(session_id): Path<Uuid>,
-    Json(request): Json<FuelRequest>,
-) -> Result<Json<FuelResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.buy_fuel(session_id, request) {
-        Ok(response) => Ok(Json(response)),
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "FuelPurchaseError".to_string(),
-                message: e,
-                details: None,
-            }),
-        )),
-    }
+    ApiJson(request): ApiJson<FuelRequest>,
+) -> Result<Json<FuelResponse>, ApiError> {
+    service
+        .buy_fuel(session_id, request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
 }
 
+/// Health check
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
 pub async fn health_check() -> Json<SuccessResponse> {
     Json(SuccessResponse {
         message: "KZRK Game API is running".to_string(),
@@ -104,45 +115,49 @@ pub async fn health_check() -> Json<SuccessResponse> {
     })
 }
 
+/// Airports
+#[utoipa::path(
+    get,
+    path = "/airports",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
 pub async fn get_available_airports(
     State(_service): State<GameService>,
-) -> Json<Vec<serde_json::Value>> {
-    use crate::data::airports::get_default_airports;
-
-    let airports = get_default_airports();
-    let airport_list: Vec<serde_json::Value> = airports
-        .iter()
-        .map(|(id, airport)| {
-            serde_json::json!({
-                "id": id,
-                "name": &airport.name,
-                "latitude": airport.coordinates.0,
-                "longitude": airport.coordinates.1
-            })
+    headers: HeaderMap,
+) -> Response {
+    let airports: Vec<AirportSummary> = stateless_handlers::airport_infos()
+        .into_iter()
+        .map(|airport| AirportSummary {
+            id: airport.id,
+            name: airport.name,
+            latitude: airport.coordinates.0,
+            longitude: airport.coordinates.1,
         })
         .collect();
 
-    Json(airport_list)
+    stateless_handlers::cacheable(&headers, airports)
 }
 
+/// Cargo types
+#[utoipa::path(
+    get,
+    path = "/cargo",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
 pub async fn get_available_cargo(
     State(_service): State<GameService>,
-) -> Json<Vec<serde_json::Value>> {
-    use crate::data::cargo_types::get_default_cargo_types;
-
-    let cargo_types = get_default_cargo_types();
-    let cargo_list: Vec<serde_json::Value> = cargo_types
-        .iter()
-        .map(|(id, cargo)| {
-            serde_json::json!({
-                "id": id,
-                "name": &cargo.name,
-                "base_price": cargo.base_price,
-                "weight": cargo.weight_per_unit,
-                "volatility": cargo.volatility
-            })
+    headers: HeaderMap,
+) -> Response {
+    let cargo_types: Vec<CargoSummary> = stateless_handlers::cargo_infos()
+        .into_iter()
+        .map(|cargo| CargoSummary {
+            id: cargo.id,
+            name: cargo.name,
+            base_price: cargo.base_price,
+            weight: cargo.weight_per_unit,
+            volatility: cargo.volatility,
         })
         .collect();
 
-    Json(cargo_list)
+    stateless_handlers::cacheable(&headers, cargo_types)
 }
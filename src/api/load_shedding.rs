@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use axum::{
+    BoxError, Router,
+    error_handling::HandleErrorLayer,
+    extract::{DefaultBodyLimit, Request, State},
+    http::header,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use tower::{ServiceBuilder, limit::GlobalConcurrencyLimitLayer, load_shed::error::Overloaded};
+
+use crate::api::errors::{ApiError, ErrorCode};
+
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 256;
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// How much the server takes on at once before turning requests away, so a
+/// burst of clients can't queue up behind the room locks indefinitely
+#[derive(Debug, Clone)]
+pub struct LoadPolicy {
+    /// Requests being handled at once; any more are answered 503 straight
+    /// away
+    pub max_concurrent_requests: usize,
+    /// Requests still unanswered after this long are dropped with a 503
+    pub request_timeout: Duration,
+    pub max_body_bytes: usize,
+}
+
+impl Default for LoadPolicy {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            request_timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+        }
+    }
+}
+
+impl LoadPolicy {
+    /// KZRK_MAX_CONCURRENT_REQUESTS, KZRK_REQUEST_TIMEOUT_SECS and
+    /// KZRK_MAX_BODY_BYTES, defaulting to 256, 30 and 1 MiB
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let positive = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .filter(|value| *value > 0)
+        };
+        Self {
+            max_concurrent_requests: positive("KZRK_MAX_CONCURRENT_REQUESTS")
+                .map(|requests| requests as usize)
+                .unwrap_or(default.max_concurrent_requests),
+            request_timeout: positive("KZRK_REQUEST_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.request_timeout),
+            max_body_bytes: positive("KZRK_MAX_BODY_BYTES")
+                .map(|bytes| bytes as usize)
+                .unwrap_or(default.max_body_bytes),
+        }
+    }
+}
+
+/// Wrap every route in the policy's limits. Shedding sits outermost, so a
+/// saturated server answers without touching the service.
+pub fn with_load_shedding<S>(router: Router<S>, policy: &LoadPolicy) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let timeout = policy.request_timeout;
+    router
+        .layer(DefaultBodyLimit::max(policy.max_body_bytes))
+        .layer(middleware::from_fn_with_state(
+            policy.max_body_bytes,
+            reject_large_bodies,
+        ))
+        .layer(
+            ServiceBuilder::new()
+                .layer(HandleErrorLayer::new(move |error: BoxError| async move {
+                    shed(error, timeout)
+                }))
+                .load_shed()
+                .layer(GlobalConcurrencyLimitLayer::new(
+                    policy.max_concurrent_requests,
+                ))
+                .timeout(timeout),
+        )
+}
+
+fn shed(error: BoxError, timeout: Duration) -> ApiError {
+    if error.is::<Overloaded>() {
+        ApiError::new(
+            ErrorCode::ServerBusy,
+            "The server is busy: try again shortly",
+        )
+    } else if error.is::<tower::timeout::error::Elapsed>() {
+        ApiError::new(
+            ErrorCode::RequestTimedOut,
+            format!(
+                "The server took longer than {} s to answer",
+                timeout.as_secs_f32()
+            ),
+        )
+    } else {
+        ApiError::new(ErrorCode::InternalError, error.to_string())
+    }
+}
+
+/// Turn away bodies that say up front they're too big, before reading them.
+/// Bodies that don't say are cut off by `DefaultBodyLimit` as they're read.
+async fn reject_large_bodies(
+    State(max_bytes): State<usize>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let declared = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|length| length.to_str().ok())
+        .and_then(|length| length.parse::<usize>().ok());
+    if declared.is_some_and(|length| length > max_bytes) {
+        return too_large(max_bytes).into_response();
+    }
+    next.run(request).await
+}
+
+fn too_large(max_bytes: usize) -> ApiError {
+    ApiError::new(
+        ErrorCode::PayloadTooLarge,
+        format!("Request bodies can be at most {} bytes", max_bytes),
+    )
+}
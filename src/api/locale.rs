@@ -0,0 +1,83 @@
+use axum::{
+    body::{Body, to_bytes},
+    extract::Request,
+    http::{HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+use serde_json::Value;
+
+use crate::models::locale::{Locale, LocalizedMessage};
+
+/// Answer in the language the client asked for with Accept-Language. Every
+/// JSON message that comes with a code is rewritten in that language; the
+/// code and its parameters are left alone for clients that render their own.
+pub async fn negotiate_locale(request: Request, next: Next) -> Response {
+    let locale = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(Locale::negotiate)
+        .unwrap_or_default();
+
+    let mut response = next.run(request).await;
+    if locale != Locale::English && is_json(&response) {
+        response = translate(response, locale).await;
+    }
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_LANGUAGE,
+        HeaderValue::from_static(locale.tag()),
+    );
+    headers.append(header::VARY, HeaderValue::from_static("accept-language"));
+    response
+}
+
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"))
+}
+
+async fn translate(response: Response, locale: Locale) -> Response {
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let Ok(mut value) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    localize_messages(&mut value, locale);
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(value.to_string()))
+}
+
+/// Render each `message` next to a `localized` code in `locale`, however
+/// deep in the response it is
+pub fn localize_messages(value: &mut Value, locale: Locale) {
+    match value {
+        Value::Object(object) => {
+            let rendered = object
+                .get("localized")
+                .and_then(|localized| {
+                    serde_json::from_value::<LocalizedMessage>(localized.clone()).ok()
+                })
+                .map(|localized| localized.render(locale));
+            if let Some(text) = rendered
+                && object.get("message").is_some_and(Value::is_string)
+            {
+                object.insert("message".to_string(), Value::String(text));
+            }
+            object
+                .values_mut()
+                .for_each(|value| localize_messages(value, locale));
+        },
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| localize_messages(value, locale)),
+        _ => {},
+    }
+}
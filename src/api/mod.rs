@@ -1,8 +1,21 @@
+pub mod admin;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+pub mod cluster;
 pub mod database;
+pub mod digests;
+pub mod errors;
 pub mod handlers;
+pub mod load_shedding;
+pub mod locale;
 pub mod models;
 pub mod multiplayer_handlers;
 pub mod multiplayer_service;
+pub mod openapi;
+pub mod room_stream;
 pub mod routes;
+pub mod scenario_server;
 pub mod service;
 pub mod stateless_handlers;
+pub mod tls;
+pub mod webhooks;
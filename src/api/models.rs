@@ -1,12 +1,42 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use crate::systems::GameStatus;
+use crate::api::errors::ErrorCode;
+use crate::models::{
+    Airport, BoardLimits, CargoCategory, CargoType, FuelEconomy, Message, MessageTopic,
+    Notification, PriceSnapshot, SecurityRating,
+    aircraft::AircraftUpgrade,
+    loan::Loan,
+    locale::{Localized, LocalizedMessage},
+};
+use crate::systems::achievements::{Achievement, UnlockedAchievement};
+use crate::systems::auto_refuel::RefuelReceipt;
+use crate::systems::calendar::GameCalendar;
+use crate::systems::courier::CourierLetter;
+use crate::systems::deadlines::TurnDeadline;
+use crate::systems::digest::DigestTarget;
+use crate::systems::events::EventConfig;
+use crate::systems::insurance::{CargoLoss, InsuranceClaim};
+use crate::systems::loadouts::CargoLoadout;
+use crate::systems::matchmaking::{GameMode, MatchPreferences};
+use crate::systems::modifiers::GlobalModifier;
+use crate::systems::multiplayer::GameSpeed;
+use crate::systems::offers::TradeOffer;
+use crate::systems::profile::QuantityDefaults;
+use crate::systems::rankings::RankingEntry;
+use crate::systems::route_profits::{ProfitMatrix, RouteProfit};
+use crate::systems::slots::SlotStatus;
+use crate::systems::transfers::Transfer;
+use crate::systems::travel::FlightRange;
+use crate::systems::valuation::NetWorth;
+use crate::systems::vendors::{VendorItem, VendorPurchase};
+use crate::systems::{GameStatus, RoomAward, RoomSettings, RoomWinner, RouteFuelStats, TurnRecord};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateGameRequest {
     pub player_name: String,
     pub starting_money: Option<u32>,
@@ -25,9 +55,22 @@ pub struct GameStateResponse {
     pub player: PlayerInfo,
     pub current_market: MarketInfo,
     pub available_destinations: Vec<DestinationInfo>,
+    /// How far the player can fly now and on a full tank
+    #[serde(default)]
+    pub flight_range: FlightRange,
     pub active_events: Vec<EventInfo>,
     pub statistics: StatisticsInfo,
+    /// The player's own turn
     pub turn_number: u32,
+    /// Turns taken across the whole world; always equal to `turn_number` in
+    /// single-player, ahead of it in rooms where others are playing too
+    #[serde(default)]
+    pub world_tick: u32,
+    /// The in-game date of the player's turn
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub calendar: GameCalendar,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +88,14 @@ pub struct PlayerInfo {
     pub is_online: Option<bool>,
     pub last_seen: Option<DateTime<Utc>>,
     pub is_host: Option<bool>,
+    #[serde(default)]
+    pub turn_number: Option<u32>,
+    /// One of a demo room's bot traders
+    #[serde(default)]
+    pub is_bot: bool,
+    /// Profitable sales in a row
+    #[serde(default)]
+    pub streak: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +105,43 @@ pub struct MarketInfo {
     pub fuel_price: u32,
     pub cargo_prices: HashMap<String, u32>,
     pub last_updated: std::time::SystemTime,
+    /// Fuel for sale right now; None from servers that don't track stock
+    #[serde(default)]
+    pub fuel_stock: Option<u32>,
+    #[serde(default)]
+    pub fuel_outage: bool,
+    /// The player's broker terms here; None from servers without fees
+    #[serde(default)]
+    pub broker: Option<BrokerInfo>,
+}
+
+/// A player's standing with an airport's cargo broker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokerInfo {
+    pub tier: String,
+    pub fee_basis_points: u32,
+    /// Dollars the player has traded at this airport
+    pub volume: u32,
+    #[serde(default)]
+    pub next_tier: Option<String>,
+    #[serde(default)]
+    pub next_tier_volume: Option<u32>,
+    /// Units of any one cargo the broker handles per turn at this tier;
+    /// None from servers without caps
+    #[serde(default)]
+    pub units_per_turn: Option<u32>,
+    /// Units of each cargo the player can still buy here this turn
+    #[serde(default)]
+    pub buys_left: HashMap<String, u32>,
+    /// Units of each cargo the player can still sell here this turn
+    #[serde(default)]
+    pub sales_left: HashMap<String, u32>,
+    /// Sellers get this much less than the listed price, in percent
+    #[serde(default)]
+    pub sell_spread_percent: u32,
+    /// Units of each cargo bought here too recently to sell back here
+    #[serde(default)]
+    pub resale_locked: HashMap<String, u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,16 +152,63 @@ pub struct DestinationInfo {
     pub fuel_required: u32,
     pub can_travel: bool,
     pub fuel_price: u32,
+    /// Turn the fuel price was seen on; None when fog of war hides it
+    #[serde(default)]
+    pub fuel_price_observed_at: Option<u32>,
+    /// No fuel can be bought there until the outage ends
+    #[serde(default)]
+    pub fuel_outage: bool,
+    /// Fare to get there by road instead of flying; None when it's too far
+    #[serde(default)]
+    pub ground_transfer_fare: Option<u32>,
+    /// Whether a flight landing there now gets a slot, holds or has to divert
+    #[serde(default)]
+    pub slot_status: SlotStatus,
+    /// Whether a full tank would cover the flight, holding pattern included
+    #[serde(default)]
+    pub reachable_on_full_tank: bool,
+}
+
+/// Prices a player has seen at an airport. In fog-of-war rooms these can be
+/// out of date; `stale` says the room considers them too old to trust.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownMarketInfo {
+    pub airport_id: String,
+    pub airport_name: String,
+    pub fuel_price: u32,
+    pub cargo_prices: HashMap<String, u32>,
+    pub observed_at: u32,
+    pub turns_ago: u32,
+    pub stale: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventInfo {
     pub event_type: String,
     pub affected_cargo: String,
+    /// Set when every cargo of a kind is hit
+    #[serde(default)]
+    pub affected_category: Option<CargoCategory>,
     pub affected_airport: String,
     pub price_multiplier: f32,
     pub turns_remaining: u32,
     pub description: String,
+    /// Who started the event, when a host or admin injected it rather than
+    /// it happening by itself
+    #[serde(default)]
+    pub injected_by: Option<String>,
+}
+
+impl EventInfo {
+    /// The description, marked with who started the event if it didn't
+    /// happen by itself
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
+    pub fn headline(&self) -> String {
+        match &self.injected_by {
+            Some(injected_by) => format!("{} [started by {}]", self.description, injected_by),
+            None => self.description.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,9 +223,41 @@ pub struct StatisticsInfo {
     pub best_single_trade: u32,
     pub most_profitable_cargo: String,
     pub efficiency_score: f32,
+    #[serde(default)]
+    pub fuel_spend: u32,
+    #[serde(default)]
+    pub trading_fees: u32,
+    #[serde(default)]
+    pub routes: Vec<RouteFuelStats>,
+    #[serde(default)]
+    pub best_route: Option<RouteFuelStats>,
+    #[serde(default)]
+    pub worst_route: Option<RouteFuelStats>,
+    #[serde(default)]
+    pub fuel_burned: u32,
+    /// Fuel burned per dollar of profit; None until in profit
+    #[serde(default)]
+    pub fuel_per_profit_dollar: Option<f32>,
+    /// Profitable sales in a row, and the longest run so far
+    #[serde(default)]
+    pub current_streak: u32,
+    #[serde(default)]
+    pub best_streak: u32,
+    /// Cash, cargo at local prices and aircraft as they stand now
+    #[serde(default)]
+    pub net_worth: NetWorth,
+    /// Net worth at the end of each turn still in the journal, oldest first
+    #[serde(default)]
+    pub net_worth_history: Vec<NetWorthPoint>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct NetWorthPoint {
+    pub turn_number: u32,
+    pub net_worth: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TravelRequest {
     pub destination: String,
 }
@@ -99,35 +266,67 @@ pub struct TravelRequest {
 pub struct TravelResponse {
     pub success: bool,
     pub message: String,
+    /// The message as a code and parameters, for clients that show it in
+    /// another language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedMessage>,
     pub fuel_consumed: Option<u32>,
     pub new_location: Option<String>,
+    /// Whether the action used up a turn. Only flights and ground transfers
+    /// do, for every client.
+    #[serde(default)]
+    pub advanced_turn: bool,
     pub game_state: Option<GameStateResponse>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TradeRequest {
     pub cargo_type: String,
     pub quantity: u32,
     pub action: TradeAction,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub enum TradeAction {
     Buy,
     Sell,
 }
 
+/// Trades made together: either all of them go through, in order, or
+/// none do
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TradeBatchRequest {
+    pub trades: Vec<TradeRequest>,
+}
+
+/// Load one of the pilot's saved load-outs at their current airport
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApplyLoadoutRequest {
+    pub name: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeResponse {
     pub success: bool,
     pub message: String,
+    /// The message as a code and parameters, for clients that show it in
+    /// another language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedMessage>,
     pub transaction_amount: Option<u32>,
+    /// Broker fee on top of a purchase or taken out of a sale
+    #[serde(default)]
+    pub fee: Option<u32>,
     pub new_money: Option<u32>,
     pub new_inventory: Option<HashMap<String, u32>>,
+    /// Whether the action used up a turn. Only flights and ground transfers
+    /// do, for every client.
+    #[serde(default)]
+    pub advanced_turn: bool,
     pub game_state: Option<GameStateResponse>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct FuelRequest {
     pub quantity: u32,
 }
@@ -136,16 +335,29 @@ pub struct FuelRequest {
 pub struct FuelResponse {
     pub success: bool,
     pub message: String,
+    /// The message as a code and parameters, for clients that show it in
+    /// another language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedMessage>,
     pub cost: Option<u32>,
     pub new_fuel: Option<u32>,
     pub new_money: Option<u32>,
+    /// Whether the action used up a turn. Only flights and ground transfers
+    /// do, for every client.
+    #[serde(default)]
+    pub advanced_turn: bool,
     pub game_state: Option<GameStateResponse>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
+    #[schema(value_type = ErrorCode)]
     pub error: String,
     pub message: String,
+    /// The message as a code and parameters, for clients that show it in
+    /// another language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedMessage>,
     pub details: Option<serde_json::Value>,
 }
 
@@ -155,13 +367,76 @@ pub struct SuccessResponse {
     pub data: Option<serde_json::Value>,
 }
 
+/// An airport as the single-player `/airports` lists it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AirportSummary {
+    pub id: String,
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// A cargo type as the single-player `/cargo` lists it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CargoSummary {
+    pub id: String,
+    pub name: String,
+    pub base_price: u32,
+    pub weight: u32,
+    pub volatility: f32,
+}
+
 // ===== MULTIPLAYER API MODELS =====
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// What the server is running. Clients drop their cached airports and cargo
+/// when `reference_version` changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub version: String,
+    pub reference_version: String,
+}
+
+/// An airport as `/airports` lists it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AirportInfo {
+    pub id: String,
+    pub name: String,
+    pub coordinates: (f64, f64),
+    pub base_fuel_price: u32,
+    pub fuel_economy: FuelEconomy,
+}
+
+/// A cargo type as `/cargo` lists it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CargoInfo {
+    pub id: String,
+    pub name: String,
+    pub base_price: u32,
+    pub weight_per_unit: u32,
+    pub volatility: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateRoomRequest {
     pub name: String,
     pub host_player_name: String,
     pub max_players: Option<usize>,
+    pub template_id: Option<Uuid>,
+    pub settings: Option<RoomSettings>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CloneRoomRequest {
+    pub name: Option<String>,
+    pub host_player_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateTemplateRequest {
+    pub name: String,
+    pub max_players: Option<usize>,
+    pub settings: Option<RoomSettings>,
+    pub from_room_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -172,6 +447,9 @@ pub struct CreateRoomResponse {
     pub host_player_name: String,
     pub max_players: usize,
     pub current_players: usize,
+    /// Where the host starts
+    #[serde(default)]
+    pub starting_airport: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -184,14 +462,122 @@ pub struct RoomInfo {
     pub created_at: DateTime<Utc>,
     pub game_status: GameStatus,
     pub is_joinable: bool,
+    /// A room the server runs with bot traders for newcomers to try
+    #[serde(default)]
+    pub is_demo: bool,
+    #[serde(default)]
+    pub event_rules: EventRulesInfo,
 }
 
+/// The price and fee modifiers running in a room
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifiersResponse {
+    pub room_id: Uuid,
+    pub modifiers: Vec<GlobalModifier>,
+}
+
+/// Everything a room plays by, with the choices the host left open filled
+/// in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomSettingsResponse {
+    pub room_id: Uuid,
+    pub room_name: String,
+    pub max_players: usize,
+    pub game_status: GameStatus,
+    /// As the host chose them, minus the turn deadline webhook
+    pub settings: RoomSettings,
+    /// Every airport and cargo type the room plays with, by id
+    pub airports: Vec<String>,
+    pub cargo_types: Vec<String>,
+    /// Hours each player has for a turn, when the room has deadlines
+    pub turn_deadline_hours: Option<u32>,
+    pub event_rules: EventRulesInfo,
+}
+
+/// The random events a room plays with, so players know the ruleset
+/// before they join
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventRulesInfo {
+    pub market_events: EventConfig,
+    /// Kinds of market event that can happen, e.g. "PriceSpike"
+    pub enabled_market_events: Vec<String>,
+    pub fuel_outage_chance: f32,
+    pub cargo_loss_chance: f32,
+    pub overnight_theft: bool,
+    /// Chance each turn of a sales tax holiday, fuel subsidy or tariffs
+    #[serde(default)]
+    pub modifier_event_chance: f32,
+}
+
+impl Default for EventRulesInfo {
+    fn default() -> Self {
+        Self::from(&RoomSettings::default())
+    }
+}
+
+impl EventRulesInfo {
+    /// One line for the room list, e.g. "Market events 15% (6 kinds), fuel
+    /// outages 3%, cargo losses 5%, overnight theft"
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub fn summary(&self) -> String {
+        let market_events = if self.enabled_market_events.is_empty() {
+            "No market events".to_string()
+        } else {
+            format!(
+                "Market events {:.0}% ({} kinds)",
+                self.market_events.chance * 100.0,
+                self.enabled_market_events.len()
+            )
+        };
+        let theft = if self.overnight_theft {
+            "overnight theft"
+        } else {
+            "no overnight theft"
+        };
+        let mut summary = format!(
+            "{}, fuel outages {:.0}%, cargo losses {:.0}%, {}",
+            market_events,
+            self.fuel_outage_chance * 100.0,
+            self.cargo_loss_chance * 100.0,
+            theft
+        );
+        if self.modifier_event_chance > 0.0 {
+            summary.push_str(&format!(
+                ", price modifiers {:.0}%",
+                self.modifier_event_chance * 100.0
+            ));
+        }
+        summary
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct JoinRoomRequest {
     pub player_name: String,
     pub starting_airport: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuickMatchRequest {
+    pub player_name: String,
+    #[serde(flatten)]
+    pub preferences: MatchPreferences,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickMatchResponse {
+    pub room_id: Uuid,
+    pub room_name: String,
+    pub player_id: Uuid,
+    pub player_name: String,
+    pub mode: GameMode,
+    pub max_players: usize,
+    /// No open room matched, so a new one was opened with this player as host
+    pub created: bool,
+    #[serde(default)]
+    pub starting_airport: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JoinRoomResponse {
     pub room_id: Uuid,
@@ -199,12 +585,265 @@ pub struct JoinRoomResponse {
     pub player_name: String,
     pub success: bool,
     pub message: String,
+    /// Where the player starts, or where they left off when rejoining
+    #[serde(default)]
+    pub starting_airport: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, IntoParams)]
+pub struct LeaveRoomQuery {
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+pub struct TravelPreviewQuery {
+    pub dest: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetInsuranceRequest {
+    pub insured: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BorrowRequest {
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RepayLoanRequest {
+    pub loan_id: u32,
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetGameSpeedRequest {
+    pub speed: GameSpeed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSpeedResponse {
+    pub game_speed: GameSpeed,
+    pub notified_players: usize, // 0 when the speed didn't change
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectEventResponse {
+    pub event: EventInfo,
+    pub notified_players: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetHangarParkingRequest {
+    pub hangar_parking: bool,
+}
+
+/// Security where the player is parked, and what they've paid for to keep
+/// their aircraft safe overnight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityResponse {
+    pub airport_id: String,
+    pub airport_security: SecurityRating,
+    pub theft_chance: f32,     // Tonight, parked outside
+    pub overnight_theft: bool, // Off when the room has it turned off
+    pub hangar_parking: bool,
+    pub hangar_fee: u32,
+    pub security_upgrade: bool,
+    pub security_upgrade_cost: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BuyUpgradeRequest {
+    pub upgrade: AircraftUpgrade,
+}
+
+/// One upgrade the hangar sells, and how far the player has taken it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradeInfo {
+    pub upgrade: AircraftUpgrade,
+    pub label: String,
+    pub description: String,
+    pub level: u8,
+    pub max_level: u8,
+    pub next_cost: Option<u32>, // None once it's maxed out
+}
+
+/// What the hangar can fit, and the aircraft it would fit it to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HangarResponse {
+    pub upgrades: Vec<UpgradeInfo>,
+    pub money: u32,
+    pub max_fuel: u32,
+    pub max_cargo_weight: u32,
+    pub fuel_efficiency: f32,
+    pub cargo_loss_chance: f32, // Each flight, with the engines fitted
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetPriceAlertsRequest {
+    pub threshold_percent: u32, // 0 turns alerts off
+}
+
+/// Cargo held, what it cost and what it's worth where the player is
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingInfo {
+    pub cargo_type: String,
+    pub quantity: u32,
+    pub average_cost: Option<u32>, // None for cargo that wasn't bought
+    pub price: Option<u32>,
+    pub gain_percent: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceAlertsResponse {
+    pub threshold_percent: u32,
+    pub holdings: Vec<HoldingInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FileClaimRequest {
+    pub loss_id: Uuid,
+}
+
+/// A player's cover, what it would cost on their next flight, and their
+/// losses and claims.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsuranceResponse {
+    pub insured: bool,
+    pub premium_percent: u32,
+    pub next_flight_premium: u32, // For the cargo on board now
+    pub unclaimed_losses: Vec<CargoLoss>,
+    pub claims: Vec<InsuranceClaim>, // Newest first
+}
+
+/// A player's loans and what the bank will still lend them, on its terms
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoanResponse {
+    pub loans: Vec<Loan>, // Oldest first
+    pub debt: u32,
+    pub money: u32,
+    pub credit_available: u32,
+    pub bankrupt: bool,
+    pub interest_percent: u32, // Per turn
+    pub term_turns: u32,
+    pub min_loan: u32,
+    pub bankruptcy_debt: u32,
+}
+
+/// A vendor's stall at the player's airport
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StallInfo {
+    pub id: Uuid,
+    pub vendor: String,
+    pub item: VendorItem,
+    pub description: String,
+    pub price: u32,
+    pub closes_in_turns: u32, // World ticks until the vendor packs up
+}
+
+/// What's for sale at the stalls where the player is
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VendorStallsResponse {
+    pub airport_id: String,
+    pub stalls: Vec<StallInfo>,
+    pub money: u32,
+    /// Room rules can turn the vendors off
+    pub vendors_enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StallPurchaseResponse {
+    pub message: String,
+    pub purchase: VendorPurchase,
+    pub money: u32,
+    pub fuel: u32,
+}
+
+/// Whose view of an airport's price history to give; needed in fog of war
+/// rooms, where only players at the airport see it
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct PriceHistoryQuery {
+    pub player_id: Option<Uuid>,
+}
+
+/// An airport's recent prices, one snapshot per world tick, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceHistoryResponse {
+    pub airport_id: String,
+    pub snapshots: Vec<PriceSnapshot>,
+    pub max_turns: usize,
+}
+
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+impl PriceHistoryResponse {
+    /// One cargo's prices across the snapshots
+    pub fn cargo_prices(&self, cargo_id: &str) -> Vec<u32> {
+        self.snapshots
+            .iter()
+            .filter_map(|snapshot| snapshot.cargo_prices.get(cargo_id).copied())
+            .collect()
+    }
+
+    pub fn fuel_prices(&self) -> Vec<u32> {
+        self.snapshots
+            .iter()
+            .map(|snapshot| snapshot.fuel_price)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct AcceptOfferRequest {
+    pub quantity: Option<u32>, // Defaults to everything on offer
+}
+
+/// Other players' offers at the player's airport, and all of the player's own
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffersResponse {
+    pub airport_id: String,
+    pub offers: Vec<TradeOffer>,    // Oldest first
+    pub my_offers: Vec<TradeOffer>, // Oldest first, at any airport
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfferResponse {
+    pub message: String,
+    pub offer: TradeOffer, // For an accept, just the part that traded
+    pub new_money: u32,
+    pub new_inventory: HashMap<String, u32>,
+}
+
+/// Every route a player can price, best profit per unit of fuel first, and
+/// the best of them for each pair of airports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteProfitsResponse {
+    /// Where the player is now
+    pub airport_id: String,
+    pub matrix: ProfitMatrix,
+    pub routes: Vec<RouteProfit>,
+}
+
+/// A transfer to another player, and what the sender has left
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferResponse {
+    pub message: String,
+    /// The message as a code and parameters, for clients that show it in
+    /// another language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedMessage>,
+    pub transfer: Transfer,
+    pub new_money: u32,
+    pub new_inventory: HashMap<String, u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaveRoomResponse {
     pub success: bool,
     pub message: String,
+    /// Assets the player left behind by leaving with force
+    #[serde(default)]
+    pub obligations: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -214,6 +853,124 @@ pub struct PlayerSessionInfo {
     pub room_id: Uuid,
     pub room_name: String,
     pub connected_at: DateTime<Utc>,
+    /// Last time the player joined or played in the room
+    #[serde(default = "Utc::now")]
+    pub last_active: DateTime<Utc>,
+    #[serde(default)]
+    pub idle_seconds: i64,
+}
+
+/// How long an unfinished game can go without anyone playing before the
+/// host dashboard calls it stalled
+pub const STALLED_ROOM_MINUTES: i64 = 30;
+
+/// Something about a hosted room its host may want to step in on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum RoomAlert {
+    /// Not over, but nobody has played for a while
+    Stalled { idle_minutes: i64 },
+    /// Someone reached the win condition
+    Finished { winner: String },
+}
+
+/// A player in a hosted room, as the host dashboard lists them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostedRoomPlayer {
+    pub player_id: Uuid,
+    pub player_name: String,
+    pub turn_number: u32,
+    pub is_online: bool,
+}
+
+/// A room a player hosts, with what the host dashboard needs to keep an
+/// eye on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostedRoomSummary {
+    pub room_id: Uuid,
+    pub room_name: String,
+    /// The host's own player in the room, for switching into it
+    pub host_player_id: Uuid,
+    pub game_status: GameStatus,
+    pub round: u32,
+    pub players_online: u32,
+    pub players_total: u32,
+    pub max_players: u32,
+    /// The furthest any player has got
+    pub turn_number: u32,
+    /// Furthest along first
+    pub players: Vec<HostedRoomPlayer>,
+    /// Last time anyone played in the room
+    pub last_activity: DateTime<Utc>,
+    pub idle_seconds: i64,
+    #[serde(default)]
+    pub alerts: Vec<RoomAlert>,
+}
+
+/// One logbook entry, earned or still to go
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AchievementInfo {
+    pub achievement: Achievement,
+    pub title: String,
+    pub description: String,
+    pub unlocked_at: Option<DateTime<Utc>>, // None while locked
+    pub room_name: Option<String>,          // Where it was earned
+}
+
+/// A pilot's cross-game profile: every achievement, earned ones first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PilotProfileResponse {
+    pub pilot_name: String,
+    pub unlocked: usize,
+    pub achievements: Vec<AchievementInfo>,
+    #[serde(default)]
+    pub quantity_defaults: QuantityDefaults,
+    #[serde(default)]
+    pub loadouts: Vec<CargoLoadout>,
+}
+
+/// Which page of which season's rankings to show. Missing fields mean the
+/// current season's first page of `DEFAULT_RANKINGS_PAGE_SIZE`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct RankingsQuery {
+    pub season: Option<String>,
+    pub page: Option<usize>,
+    pub per_page: Option<usize>,
+}
+
+pub const DEFAULT_RANKINGS_PAGE_SIZE: usize = 20;
+
+/// One page of a season's table of pilots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankingsResponse {
+    pub season: String,
+    /// Every season with results, most recent first
+    pub seasons: Vec<String>,
+    /// Counting from 1
+    pub page: usize,
+    pub per_page: usize,
+    /// Pilots ranked in the season altogether
+    pub total: usize,
+    pub entries: Vec<RankingEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogoutResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Where the vote for another round in the same room stands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RematchVoteResponse {
+    /// Whether this vote started the next round
+    pub restarted: bool,
+    pub round: u32,
+    pub votes: usize,
+    pub votes_needed: usize,
+    pub message: String,
+    /// Where the voter starts the new round, once it has started
+    pub starting_airport: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -223,40 +980,143 @@ pub struct MultiplayerGameStateResponse {
     pub players: Vec<PlayerInfo>,
     pub current_market: MarketInfo,
     pub available_destinations: Vec<DestinationInfo>,
+    /// How far the requesting player can fly now and on a full tank
+    #[serde(default)]
+    pub flight_range: FlightRange,
+    /// Market events in the room, fuel outages included
+    #[serde(default)]
+    pub active_events: Vec<EventInfo>,
+    /// Price and fee modifiers running across the whole room
+    #[serde(default)]
+    pub active_modifiers: Vec<GlobalModifier>,
+    #[serde(default)]
+    pub known_markets: Vec<KnownMarketInfo>,
     pub statistics: StatisticsInfo,
+    /// The requesting player's own turn
     pub turn_number: u32,
+    #[serde(default)]
+    pub world_tick: u32,
     pub world_time: DateTime<Utc>,
+    #[serde(default)]
+    pub winner: Option<RoomWinner>,
+    /// Handed out once the room is won
+    #[serde(default)]
+    pub awards: Vec<RoomAward>,
+    #[serde(default)]
+    pub rematch_room_id: Option<Uuid>,
+    /// Names of the players who've voted to play again in this room
+    #[serde(default)]
+    pub rematch_votes: Vec<String>,
+    /// Which of the room's rounds is being played
+    #[serde(default)]
+    pub round: u32,
+    /// The airports this room plays with, sorted by id
+    #[serde(default)]
+    pub airports: Vec<Airport>,
+    /// The cargo types this room trades, sorted by id
+    #[serde(default)]
+    pub cargo_types: Vec<CargoType>,
+    /// Notifications the requesting player hasn't read yet
+    #[serde(default)]
+    pub unread_notifications: usize,
+    /// Those notifications themselves, oldest first: mentions,
+    /// announcements, price alerts, arrivals and turn deadlines. They come
+    /// back with every state until marked read.
+    #[serde(default)]
+    pub notifications: Vec<Notification>,
+    /// Everything on the requesting pilot's profile, oldest first
+    #[serde(default)]
+    pub achievements: Vec<UnlockedAchievement>,
+    #[serde(default)]
+    pub game_speed: GameSpeed,
+    /// The in-game date of the requesting player's turn
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub calendar: GameCalendar,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerTravelResponse {
     pub success: bool,
     pub message: String,
+    /// The message as a code and parameters, for clients that show it in
+    /// another language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedMessage>,
     pub fuel_consumed: Option<u32>,
     pub new_location: Option<String>,
+    /// Whether the action used up a turn. Only flights and ground transfers
+    /// do, for every client.
+    #[serde(default)]
+    pub advanced_turn: bool,
+    /// Fuel the player's auto-refuel order bought on landing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_refuel: Option<RefuelReceipt>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerTradeResponse {
     pub success: bool,
     pub message: String,
+    /// The message as a code and parameters, for clients that show it in
+    /// another language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedMessage>,
     pub transaction_amount: Option<u32>,
+    /// Broker fee on top of a purchase or taken out of a sale
+    #[serde(default)]
+    pub fee: Option<u32>,
     pub new_money: Option<u32>,
     pub new_inventory: Option<HashMap<String, u32>>,
+    /// Whether the action used up a turn. Only flights and ground transfers
+    /// do, for every client.
+    #[serde(default)]
+    pub advanced_turn: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerTradeBatchResponse {
+    pub message: String,
+    /// The message as a code and parameters, for clients that show it in
+    /// another language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedMessage>,
+    /// The trades made, in order; empty when the hold already matched
+    pub trades: Vec<TradeRequest>,
+    /// Sales minus purchases, before broker fees
+    pub net_amount: i64,
+    pub fees: u32,
+    pub new_money: u32,
+    pub new_inventory: HashMap<String, u32>,
+    /// Whether the action used up a turn. Only flights and ground transfers
+    /// do, for every client.
+    #[serde(default)]
+    pub advanced_turn: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerFuelResponse {
     pub success: bool,
     pub message: String,
+    /// The message as a code and parameters, for clients that show it in
+    /// another language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedMessage>,
     pub cost: Option<u32>,
     pub new_fuel: Option<u32>,
     pub new_money: Option<u32>,
+    /// Whether the action used up a turn. Only flights and ground transfers
+    /// do, for every client.
+    #[serde(default)]
+    pub advanced_turn: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PostMessageRequest {
     pub content: String,
+    #[serde(default)]
+    pub topic: MessageTopic,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -264,6 +1124,17 @@ pub struct PostMessageResponse {
     pub success: bool,
     pub message: String,
     pub message_id: Option<Uuid>,
+    /// The board it went up on: always the poster's current airport
+    #[serde(default)]
+    pub airport_id: Option<String>,
+}
+
+/// A message taken off the board by its author or the host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteMessageResponse {
+    pub message: String,
+    pub message_id: Uuid,
+    pub airport_id: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -274,11 +1145,391 @@ pub struct MessageInfo {
     pub content: String,
     pub airport_id: String,
     pub created_at: DateTime<Utc>,
+    /// Posted by an airport local, not a player
+    #[serde(default)]
+    pub is_npc: bool,
+    /// The message @mentions the player who asked for the board
+    #[serde(default)]
+    pub mentions_you: bool,
+    /// Brought by courier from this airport
+    #[serde(default)]
+    pub couriered_from: Option<String>,
+    #[serde(default)]
+    pub topic: MessageTopic,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SendCourierRequest {
+    pub destination: String,
+    pub content: String,
+}
+
+/// What the courier charges to reach an airport from the player's
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourierRateInfo {
+    pub airport_id: String,
+    pub airport_name: String,
+    pub turns: u32,
+    pub fee: u32,
+}
+
+/// A letter the player sent, and how long it has left in the mail
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourierLetterInfo {
+    #[serde(flatten)]
+    pub letter: CourierLetter,
+    pub turns_remaining: u32,
+}
+
+impl CourierLetterInfo {
+    pub fn new(letter: &CourierLetter, world_tick: u32) -> Self {
+        Self {
+            letter: letter.clone(),
+            turns_remaining: letter.turns_remaining(world_tick),
+        }
+    }
+}
+
+/// Courier rates from the player's airport and the letters they've sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourierResponse {
+    pub airport_id: String,
+    pub world_tick: u32,
+    pub rates: Vec<CourierRateInfo>,     // Cheapest first
+    pub letters: Vec<CourierLetterInfo>, // Newest first
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendCourierResponse {
+    pub message: String,
+    pub letter: CourierLetterInfo,
+    pub new_money: u32,
+}
+
+impl MessageInfo {
+    /// A message as shown to the player named `player_name`
+    pub fn for_player(message: &Message, player_name: &str) -> Self {
+        Self {
+            id: message.id,
+            author_id: message.author_id,
+            author_name: message.author_name.clone(),
+            content: message.content.clone(),
+            airport_id: message.airport_id.clone(),
+            created_at: message.created_at,
+            is_npc: message.is_npc,
+            mentions_you: !message.is_npc && message.mentions(player_name),
+            couriered_from: message.couriered_from.clone(),
+            topic: message.topic,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetMessagesResponse {
     pub messages: Vec<MessageInfo>,
     pub airport_id: String,
+    /// Messages in the topic asked for, or at the airport when none was
     pub total_count: usize,
+    #[serde(default)]
+    pub limits: BoardLimits,
+    /// The topic asked for; None lists every topic together
+    #[serde(default)]
+    pub topic: Option<MessageTopic>,
+    /// Messages in each topic at the airport
+    #[serde(default)]
+    pub topic_counts: BTreeMap<MessageTopic, usize>,
+    /// Pass as `before_id` for the next page; None on the last one
+    #[serde(default)]
+    pub next_before_id: Option<Uuid>,
+}
+
+/// Which topic to read and which page of it. Missing means every topic,
+/// newest first, all of it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct MessagesQuery {
+    pub topic: Option<MessageTopic>,
+    /// Start after this message, the last one of the page before
+    #[serde(default)]
+    pub before_id: Option<Uuid>,
+    /// At most `MAX_MESSAGES_PAGE_SIZE`
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+pub const MAX_MESSAGES_PAGE_SIZE: usize = 100;
+
+/// How many archived messages to read. Missing means
+/// `DEFAULT_ARCHIVE_PAGE_SIZE`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct ArchivedMessagesQuery {
+    pub limit: Option<usize>,
+}
+
+/// Who wants a message board export, and in what shape. Only the host
+/// gets one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct MessageExportQuery {
+    pub player_id: Option<Uuid>,
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Markdown,
+}
+
+/// Every airport's board in a room, archived messages included, for
+/// keeping after the room is gone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageBoardExport {
+    pub room_id: Uuid,
+    pub room_name: String,
+    pub exported_at: DateTime<Utc>,
+    pub message_count: usize,
+    /// Airports with anything posted, by id
+    pub boards: Vec<AirportBoardExport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AirportBoardExport {
+    pub airport_id: String,
+    pub airport_name: String,
+    /// Oldest first
+    pub messages: Vec<Message>,
+}
+
+impl MessageBoardExport {
+    /// The export as a Markdown document: a section per airport, then a
+    /// line per message
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!(
+            "# {} message boards\n\nExported {} with {} messages.\n",
+            self.room_name,
+            self.exported_at.format("%Y-%m-%d %H:%M UTC"),
+            self.message_count
+        );
+        for board in &self.boards {
+            markdown.push_str(&format!(
+                "\n## {} ({})\n\n",
+                board.airport_name, board.airport_id
+            ));
+            for message in &board.messages {
+                let mut author = message.author_name.clone();
+                if message.is_npc {
+                    author.push_str(" (local)");
+                }
+                if let Some(from) = &message.couriered_from {
+                    author.push_str(&format!(", by courier from {}", from));
+                }
+                markdown.push_str(&format!(
+                    "- **{}** [{}] {}: {}\n",
+                    message.created_at.format("%Y-%m-%d %H:%M"),
+                    message.topic.label(),
+                    author,
+                    message.content.replace('\n', " ")
+                ));
+            }
+        }
+        markdown
+    }
+}
+
+pub const DEFAULT_ARCHIVE_PAGE_SIZE: usize = 50;
+pub const MAX_ARCHIVE_PAGE_SIZE: usize = 500;
+
+/// Messages pushed off an airport's board by its retention limit, most
+/// recent first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedMessagesResponse {
+    pub messages: Vec<MessageInfo>,
+    pub airport_id: String,
+}
+
+/// Public view of a room for spectators, streamed as JSON patches. Maps are
+/// keyed (and sorted) by id so patch paths stay stable between events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomSpectatorView {
+    pub room_id: Uuid,
+    pub room_name: String,
+    pub game_status: GameStatus,
+    pub world_tick: u32,
+    pub players: BTreeMap<Uuid, SpectatorPlayerInfo>,
+    /// Empty in fog-of-war rooms, where prices are a player's own knowledge
+    pub markets: BTreeMap<String, SpectatorMarketInfo>,
+    pub winner: Option<RoomWinner>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatorPlayerInfo {
+    pub name: String,
+    pub money: u32,
+    pub current_airport: String,
+    pub fuel: u32,
+    pub cargo_weight: u32,
+    pub turn_number: u32,
+    pub is_online: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatorMarketInfo {
+    pub fuel_price: u32,
+    pub cargo_prices: BTreeMap<String, u32>,
+}
+
+/// One pilot's turns, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PilotReplay {
+    pub player_id: Uuid,
+    pub player_name: String,
+    pub turns: Vec<TurnRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayResponse {
+    /// Whether the game is over; until then only your own turns are included
+    pub finished: bool,
+    pub pilots: Vec<PilotReplay>,
+}
+
+/// Register for a room digest: give either a webhook URL or an email address
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DigestRequest {
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestResponse {
+    /// Where digests go; none when the player isn't registered
+    pub target: Option<DigestTarget>,
+    /// The next digest covers everything after this
+    pub since: Option<DateTime<Utc>>,
+    pub period_hours: i64,
+}
+
+/// Set a room's turn deadline; leave out `webhook_url` to turn it off
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TurnDeadlineRequest {
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub hours: Option<u32>,
+    #[serde(default)]
+    pub warning_hours: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnDeadlineResponse {
+    /// None when the room has no deadline
+    pub turn_deadline: Option<TurnDeadline>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsResponse {
+    pub unread: usize,
+    /// Newest first
+    pub notifications: Vec<Notification>,
+}
+
+/// Notifications to mark read; leave `ids` out to mark them all
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct MarkNotificationsReadRequest {
+    #[serde(default)]
+    pub ids: Vec<Uuid>,
+}
+
+impl Localized for TravelResponse {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn localized(&self) -> Option<&LocalizedMessage> {
+        self.localized.as_ref()
+    }
+}
+
+impl Localized for TradeResponse {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn localized(&self) -> Option<&LocalizedMessage> {
+        self.localized.as_ref()
+    }
+}
+
+impl Localized for FuelResponse {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn localized(&self) -> Option<&LocalizedMessage> {
+        self.localized.as_ref()
+    }
+}
+
+impl Localized for ErrorResponse {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn localized(&self) -> Option<&LocalizedMessage> {
+        self.localized.as_ref()
+    }
+}
+
+impl Localized for PlayerTravelResponse {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn localized(&self) -> Option<&LocalizedMessage> {
+        self.localized.as_ref()
+    }
+}
+
+impl Localized for PlayerTradeResponse {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn localized(&self) -> Option<&LocalizedMessage> {
+        self.localized.as_ref()
+    }
+}
+
+impl Localized for PlayerTradeBatchResponse {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn localized(&self) -> Option<&LocalizedMessage> {
+        self.localized.as_ref()
+    }
+}
+
+impl Localized for PlayerFuelResponse {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn localized(&self) -> Option<&LocalizedMessage> {
+        self.localized.as_ref()
+    }
+}
+
+impl Localized for TransferResponse {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn localized(&self) -> Option<&LocalizedMessage> {
+        self.localized.as_ref()
+    }
 }
@@ -1,200 +1,1334 @@
+use std::{collections::VecDeque, convert::Infallible, time::Duration};
+
 use axum::{
-    Json as JsonExtract,
-    extract::{Path, State},
-    http::StatusCode,
-    response::Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, header},
+    response::{
+        IntoResponse, Json, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
 };
+use futures_util::{Stream, stream};
 use uuid::Uuid;
 
-use crate::api::{models::*, multiplayer_service::MultiplayerGameService};
+use crate::{
+    api::{
+        errors::{ApiError, ApiJson, ErrorCode},
+        models::*,
+        multiplayer_service::MultiplayerGameService,
+        openapi::{
+            AcceptOfferErrors, BodyErrors, CancelOfferErrors, ClaimErrors, CloneRoomErrors,
+            CreateRoomErrors, DeleteMessageErrors, GameActionErrors, JoinRoomErrors,
+            LeaveRoomErrors, LogoutErrors, MessagePageErrors, NoErrors, PlayerSettingErrors,
+            QueryErrors, RoomActionErrors, RoomErrors, RoomQueryErrors, RoomReadErrors, examples,
+        },
+        room_stream::StreamEvent,
+    },
+    systems::{
+        RoomTemplate, TravelQuote, auto_refuel::AutoRefuel, events::EventSpec,
+        insurance::InsuranceClaim, loadouts::CargoLoadout, offers::OfferTerms,
+        profile::QuantityDefaults, transfers::TransferTerms,
+    },
+};
+
+// (room_id, player_id, offer_id)
+type OfferPath = Path<(Uuid, Uuid, Uuid)>;
+// (room_id, player_id, stall_id)
+type StallPath = Path<(Uuid, Uuid, Uuid)>;
+// (room_id, player_id, message_id)
+type MessagePath = Path<(Uuid, Uuid, Uuid)>;
+
+/// Header clients set so a retried action is only applied once.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
 
+fn idempotency_key(headers: &HeaderMap) -> Option<Uuid> {
+    headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Uuid::parse_str(value).ok())
+}
+
+/// Create a room
+#[utoipa::path(
+    post,
+    path = "/rooms",
+    request_body(content = CreateRoomRequest, example = examples::create_room),
+    responses((status = 200, description = "Success"), CreateRoomErrors)
+)]
 pub async fn create_room(
     State(service): State<MultiplayerGameService>,
-    JsonExtract(request): JsonExtract<CreateRoomRequest>,
-) -> Result<Json<CreateRoomResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.create_room(request.name, request.host_player_name, request.max_players) {
-        Ok(response) => Ok(Json(response)),
-        Err(error) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "CreateRoomError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+    ApiJson(request): ApiJson<CreateRoomRequest>,
+) -> Result<Json<CreateRoomResponse>, ApiError> {
+    let result = match request.template_id {
+        Some(template_id) => {
+            service.create_room_from_template(template_id, request.name, request.host_player_name)
+        },
+        None => service.create_room_with_settings(
+            request.name,
+            request.host_player_name,
+            request.max_players,
+            request.settings.unwrap_or_default(),
+        ),
+    };
+
+    result
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
 }
 
+/// Join the best open room, or open one
+#[utoipa::path(
+    post,
+    path = "/matchmaking/quick-match",
+    request_body(content = QuickMatchRequest, example = examples::quick_match),
+    responses((status = 200, description = "Success"), BodyErrors)
+)]
+pub async fn quick_match(
+    State(service): State<MultiplayerGameService>,
+    ApiJson(request): ApiJson<QuickMatchRequest>,
+) -> Result<Json<QuickMatchResponse>, ApiError> {
+    service
+        .quick_match(request.player_name, request.preferences)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Create a room with the same settings
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/clone",
+    request_body(content = CloneRoomRequest, example = examples::clone_room),
+    responses((status = 200, description = "Success"), CloneRoomErrors)
+)]
+pub async fn clone_room(
+    State(service): State<MultiplayerGameService>,
+    Path(room_id): Path<Uuid>,
+    ApiJson(request): ApiJson<CloneRoomRequest>,
+) -> Result<Json<CreateRoomResponse>, ApiError> {
+    service
+        .clone_room(room_id, request.name, request.host_player_name)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Save a room template
+#[utoipa::path(
+    post,
+    path = "/templates",
+    request_body(content = CreateTemplateRequest, example = examples::create_template),
+    responses((status = 200, description = "Success"), BodyErrors)
+)]
+pub async fn create_template(
+    State(service): State<MultiplayerGameService>,
+    ApiJson(request): ApiJson<CreateTemplateRequest>,
+) -> Result<Json<RoomTemplate>, ApiError> {
+    service
+        .create_template(request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// List room templates
+#[utoipa::path(
+    get,
+    path = "/templates",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
+pub async fn list_templates(
+    State(service): State<MultiplayerGameService>,
+) -> Result<Json<Vec<RoomTemplate>>, ApiError> {
+    service
+        .list_templates()
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InternalError))
+}
+
+/// List rooms
+#[utoipa::path(
+    get,
+    path = "/rooms",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
 pub async fn list_rooms(
     State(service): State<MultiplayerGameService>,
-) -> Result<Json<Vec<RoomInfo>>, (StatusCode, Json<ErrorResponse>)> {
-    match service.list_rooms() {
-        Ok(rooms) => Ok(Json(rooms)),
-        Err(error) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "ListRoomsError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+) -> Result<Json<Vec<RoomInfo>>, ApiError> {
+    service
+        .list_rooms()
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InternalError))
+}
+
+/// A room's full ruleset
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/settings",
+    responses((status = 200, description = "Success"), RoomErrors)
+)]
+pub async fn get_room_settings(
+    State(service): State<MultiplayerGameService>,
+    Path(room_id): Path<Uuid>,
+) -> Result<Json<RoomSettingsResponse>, ApiError> {
+    service
+        .get_room_settings(room_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InternalError))
 }
 
+/// Join a room
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/join",
+    request_body(content = JoinRoomRequest, example = examples::join_room),
+    responses((status = 200, description = "Success"), JoinRoomErrors)
+)]
 pub async fn join_room(
     State(service): State<MultiplayerGameService>,
     Path(room_id): Path<Uuid>,
-    JsonExtract(request): JsonExtract<JoinRoomRequest>,
-) -> Result<Json<JoinRoomResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.join_room(room_id, request.player_name, request.starting_airport) {
-        Ok(response) => Ok(Json(response)),
-        Err(error) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "JoinRoomError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+    ApiJson(request): ApiJson<JoinRoomRequest>,
+) -> Result<Json<JoinRoomResponse>, ApiError> {
+    service
+        .join_room(room_id, request.player_name, request.starting_airport)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
 }
 
+/// Leave a room
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/leave",
+    params(LeaveRoomQuery),
+    responses((status = 200, description = "Success"), LeaveRoomErrors)
+)]
 pub async fn leave_room(
     State(service): State<MultiplayerGameService>,
     Path((room_id, player_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<LeaveRoomResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.leave_room(room_id, player_id) {
-        Ok(response) => Ok(Json(response)),
-        Err(error) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "LeaveRoomError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+    Query(query): Query<LeaveRoomQuery>,
+) -> Result<Json<LeaveRoomResponse>, ApiError> {
+    service
+        .leave_room(room_id, player_id, query.force)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
 }
 
+/// Room state for a player
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/state",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
 pub async fn get_room_state(
     State(service): State<MultiplayerGameService>,
     Path((room_id, player_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<MultiplayerGameStateResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.get_room_state(room_id, player_id) {
-        Ok(response) => Ok(Json(response)),
-        Err(error) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "GetRoomStateError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+) -> Result<Json<MultiplayerGameStateResponse>, ApiError> {
+    service
+        .get_room_state(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Play again after a win
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/rematch",
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn rematch(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<JoinRoomResponse>, ApiError> {
+    service
+        .rematch(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Vote to play the next round in the same room
+///
+/// The round starts once every online player votes.
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/rematch-vote",
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn vote_rematch(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<RematchVoteResponse>, ApiError> {
+    service
+        .vote_rematch(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
 }
 
+/// Fly to another airport
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/travel",
+    request_body(content = TravelRequest, example = examples::travel),
+    responses((status = 200, description = "Success"), GameActionErrors)
+)]
 pub async fn player_travel(
     State(service): State<MultiplayerGameService>,
     Path((room_id, player_id)): Path<(Uuid, Uuid)>,
-    JsonExtract(request): JsonExtract<TravelRequest>,
-) -> Result<Json<PlayerTravelResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.player_travel(room_id, player_id, request.destination) {
-        Ok(response) => Ok(Json(response)),
-        Err(error) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "PlayerTravelError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<TravelRequest>,
+) -> Result<Json<PlayerTravelResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.player_travel(room_id, player_id, request.destination)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Take the road to a nearby airport
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/ground-transfer",
+    request_body(content = TravelRequest, example = examples::ground_transfer),
+    responses((status = 200, description = "Success"), GameActionErrors)
+)]
+pub async fn player_ground_transfer(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<TravelRequest>,
+) -> Result<Json<PlayerTravelResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.player_ground_transfer(room_id, player_id, request.destination)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
 }
 
+/// Buy or sell cargo
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/trade",
+    request_body(content = TradeRequest, example = examples::trade),
+    responses((status = 200, description = "Success"), GameActionErrors)
+)]
 pub async fn player_trade(
     State(service): State<MultiplayerGameService>,
     Path((room_id, player_id)): Path<(Uuid, Uuid)>,
-    JsonExtract(request): JsonExtract<TradeRequest>,
-) -> Result<Json<PlayerTradeResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.player_trade(room_id, player_id, request) {
-        Ok(response) => Ok(Json(response)),
-        Err(error) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "PlayerTradeError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<TradeRequest>,
+) -> Result<Json<PlayerTradeResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.player_trade(room_id, player_id, request)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Make several trades at once: all of them or none
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/trades",
+    request_body(content = TradeBatchRequest, example = examples::trade_batch),
+    responses((status = 200, description = "Success"), GameActionErrors)
+)]
+pub async fn player_trade_batch(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<TradeBatchRequest>,
+) -> Result<Json<PlayerTradeBatchResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.player_trade_batch(room_id, player_id, request)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Buy and sell until the hold matches a saved load-out
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/loadout",
+    request_body(content = ApplyLoadoutRequest, example = examples::apply_loadout),
+    responses((status = 200, description = "Success"), GameActionErrors)
+)]
+pub async fn apply_loadout(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<ApplyLoadoutRequest>,
+) -> Result<Json<PlayerTradeBatchResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.apply_loadout(room_id, player_id, request)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
 }
 
+/// Buy fuel
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/fuel",
+    request_body(content = FuelRequest, example = examples::fuel),
+    responses((status = 200, description = "Success"), GameActionErrors)
+)]
 pub async fn player_buy_fuel(
     State(service): State<MultiplayerGameService>,
     Path((room_id, player_id)): Path<(Uuid, Uuid)>,
-    JsonExtract(request): JsonExtract<FuelRequest>,
-) -> Result<Json<PlayerFuelResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.player_buy_fuel(room_id, player_id, request) {
-        Ok(response) => Ok(Json(response)),
-        Err(error) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "PlayerFuelError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<FuelRequest>,
+) -> Result<Json<PlayerFuelResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.player_buy_fuel(room_id, player_id, request)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Open offers at the player's airport, and their own
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/offers",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_offers(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<OffersResponse>, ApiError> {
+    service
+        .get_offers(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Profit per unit of fuel for every route you know prices for
+///
+/// Ranked, with a heatmap matrix.
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/route-profits",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_route_profits(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<RouteProfitsResponse>, ApiError> {
+    service
+        .get_route_profits(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Give money or cargo to another player at the same airport
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/transfer",
+    request_body(content = TransferTerms, example = examples::transfer),
+    responses((status = 200, description = "Success"), GameActionErrors)
+)]
+pub async fn transfer(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    ApiJson(terms): ApiJson<TransferTerms>,
+) -> Result<Json<TransferResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.transfer(room_id, player_id, terms)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Put up a buy or sell offer for other players
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/offers",
+    request_body(content = OfferTerms, example = examples::offer),
+    responses((status = 200, description = "Success"), GameActionErrors)
+)]
+pub async fn place_offer(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    ApiJson(terms): ApiJson<OfferTerms>,
+) -> Result<Json<OfferResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.place_offer(room_id, player_id, terms)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Take another player's offer
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/offers/{offer_id}/accept",
+    request_body(content = AcceptOfferRequest, example = examples::accept_offer),
+    responses((status = 200, description = "Success"), AcceptOfferErrors)
+)]
+pub async fn accept_offer(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id, offer_id)): OfferPath,
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<AcceptOfferRequest>,
+) -> Result<Json<OfferResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.accept_offer(room_id, player_id, offer_id, request)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Cancel an offer and take back what's in escrow
+#[utoipa::path(
+    delete,
+    path = "/rooms/{room_id}/players/{player_id}/offers/{offer_id}",
+    responses((status = 200, description = "Success"), CancelOfferErrors)
+)]
+pub async fn cancel_offer(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id, offer_id)): OfferPath,
+) -> Result<Json<OfferResponse>, ApiError> {
+    service
+        .cancel_offer(room_id, player_id, offer_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
 }
 
+/// Rooms a player name is in
+#[utoipa::path(
+    get,
+    path = "/players/{player_name}/sessions",
+    responses((status = 200, description = "Success"), QueryErrors)
+)]
 pub async fn find_player_sessions(
     State(service): State<MultiplayerGameService>,
     Path(player_name): Path<String>,
-) -> Result<Json<Vec<PlayerSessionInfo>>, (StatusCode, Json<ErrorResponse>)> {
-    match service.find_player_sessions(&player_name) {
-        Ok(sessions) => Ok(Json(sessions)),
-        Err(error) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "FindPlayerSessionsError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+) -> Result<Json<Vec<PlayerSessionInfo>>, ApiError> {
+    service
+        .find_player_sessions(&player_name)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Rooms a player name hosts, with alerts for stalled and finished games
+#[utoipa::path(
+    get,
+    path = "/players/{player_name}/hosted-rooms",
+    responses((status = 200, description = "Success"), QueryErrors)
+)]
+pub async fn hosted_rooms(
+    State(service): State<MultiplayerGameService>,
+    Path(player_name): Path<String>,
+) -> Result<Json<Vec<HostedRoomSummary>>, ApiError> {
+    service
+        .hosted_rooms(&player_name, chrono::Utc::now())
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// A pilot's achievements across all their games
+#[utoipa::path(
+    get,
+    path = "/players/{player_name}/profile",
+    responses((status = 200, description = "Success"), QueryErrors)
+)]
+pub async fn get_pilot_profile(
+    State(service): State<MultiplayerGameService>,
+    Path(player_name): Path<String>,
+) -> Result<Json<PilotProfileResponse>, ApiError> {
+    service
+        .get_pilot_profile(&player_name)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// A page of a season's pilot rankings, by points for placement and profit
+#[utoipa::path(
+    get,
+    path = "/rankings",
+    params(RankingsQuery),
+    responses((status = 200, description = "Success"), QueryErrors)
+)]
+pub async fn get_rankings(
+    State(service): State<MultiplayerGameService>,
+    Query(query): Query<RankingsQuery>,
+) -> Result<Json<RankingsResponse>, ApiError> {
+    service
+        .get_rankings(query)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Set the fuel and trade quantities a pilot's clients start at
+#[utoipa::path(
+    post,
+    path = "/players/{player_name}/profile/defaults",
+    request_body(content = QuantityDefaults, example = examples::quantity_defaults),
+    responses((status = 200, description = "Success"), BodyErrors)
+)]
+pub async fn set_quantity_defaults(
+    State(service): State<MultiplayerGameService>,
+    Path(player_name): Path<String>,
+    ApiJson(request): ApiJson<QuantityDefaults>,
+) -> Result<Json<PilotProfileResponse>, ApiError> {
+    service
+        .set_quantity_defaults(&player_name, request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Save a cargo load-out, replacing any with the same name
+#[utoipa::path(
+    post,
+    path = "/players/{player_name}/profile/loadouts",
+    request_body(content = CargoLoadout, example = examples::loadout),
+    responses((status = 200, description = "Success"), BodyErrors)
+)]
+pub async fn save_loadout(
+    State(service): State<MultiplayerGameService>,
+    Path(player_name): Path<String>,
+    ApiJson(request): ApiJson<CargoLoadout>,
+) -> Result<Json<PilotProfileResponse>, ApiError> {
+    service
+        .save_loadout(&player_name, request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Forget a saved cargo load-out
+#[utoipa::path(
+    delete,
+    path = "/players/{player_name}/profile/loadouts/{name}",
+    responses((status = 200, description = "Success"), QueryErrors)
+)]
+pub async fn delete_loadout(
+    State(service): State<MultiplayerGameService>,
+    Path((player_name, name)): Path<(String, String)>,
+) -> Result<Json<PilotProfileResponse>, ApiError> {
+    service
+        .delete_loadout(&player_name, &name)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// End a player session
+#[utoipa::path(
+    post,
+    path = "/sessions/{player_id}/logout",
+    responses((status = 200, description = "Success"), LogoutErrors)
+)]
+pub async fn logout(
+    State(service): State<MultiplayerGameService>,
+    Path(player_id): Path<Uuid>,
+) -> Result<Json<LogoutResponse>, ApiError> {
+    service
+        .logout(player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
 }
 
+/// Post to the airport message board under a topic (General by default)
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/messages",
+    request_body(content = PostMessageRequest, example = examples::post_message),
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
 pub async fn post_message(
     State(service): State<MultiplayerGameService>,
     Path((room_id, player_id)): Path<(Uuid, Uuid)>,
-    JsonExtract(request): JsonExtract<PostMessageRequest>,
-) -> Result<Json<PostMessageResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.post_message(room_id, player_id, request.content) {
-        Ok(response) => Ok(Json(response)),
-        Err(error) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "PostMessageError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+    ApiJson(request): ApiJson<PostMessageRequest>,
+) -> Result<Json<PostMessageResponse>, ApiError> {
+    service
+        .post_to_topic(room_id, player_id, request.content, request.topic)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Courier rates from your airport and the letters you've sent
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/courier",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_courier(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<CourierResponse>, ApiError> {
+    service
+        .get_courier(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
 }
 
+/// Pay to post a message at another airport in a few turns
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/courier",
+    request_body(content = SendCourierRequest, example = examples::courier),
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn send_courier(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<SendCourierRequest>,
+) -> Result<Json<SendCourierResponse>, ApiError> {
+    service
+        .send_courier(room_id, player_id, request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Itemized cost of a flight
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/travel-preview",
+    params(TravelPreviewQuery),
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn travel_preview(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<TravelPreviewQuery>,
+) -> Result<Json<TravelQuote>, ApiError> {
+    service
+        .travel_preview(room_id, player_id, query.dest)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Cover, losses and claims
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/insurance",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_insurance(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<InsuranceResponse>, ApiError> {
+    service
+        .get_insurance(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Take out or cancel cover
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/insurance",
+    request_body(content = SetInsuranceRequest, example = examples::insurance),
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn set_insurance(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<SetInsuranceRequest>,
+) -> Result<Json<InsuranceResponse>, ApiError> {
+    service
+        .set_insurance(room_id, player_id, request.insured)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Loans, debt and the credit the bank will still give
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/loan",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_loans(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<LoanResponse>, ApiError> {
+    service
+        .get_loans(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Borrow money, repaid with interest over the coming turns
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/loan",
+    request_body(content = BorrowRequest, example = examples::borrow),
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn borrow(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<BorrowRequest>,
+) -> Result<Json<LoanResponse>, ApiError> {
+    service
+        .borrow(room_id, player_id, request.amount)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Pay off part or all of a loan early
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/loan/repay",
+    request_body(content = RepayLoanRequest, example = examples::repay_loan),
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn repay_loan(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<RepayLoanRequest>,
+) -> Result<Json<LoanResponse>, ApiError> {
+    service
+        .repay_loan(room_id, player_id, request.loan_id, request.amount)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Cargo held, its cost basis and what it's worth here
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/price-alerts",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_price_alerts(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<PriceAlertsResponse>, ApiError> {
+    service
+        .get_price_alerts(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Set how far above cost cargo must be worth to alert
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/price-alerts",
+    request_body(content = SetPriceAlertsRequest, example = examples::price_alerts),
+    responses((status = 200, description = "Success"), PlayerSettingErrors)
+)]
+pub async fn set_price_alerts(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<SetPriceAlertsRequest>,
+) -> Result<Json<PriceAlertsResponse>, ApiError> {
+    service
+        .set_price_alerts(room_id, player_id, request.threshold_percent)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// The fuel the player buys as soon as they land
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/auto-refuel",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_auto_refuel(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<AutoRefuel>, ApiError> {
+    service
+        .get_auto_refuel(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Top the tank up on landing when fuel is cheap enough
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/auto-refuel",
+    request_body(content = AutoRefuel, example = examples::auto_refuel),
+    responses((status = 200, description = "Success"), PlayerSettingErrors)
+)]
+pub async fn set_auto_refuel(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<AutoRefuel>,
+) -> Result<Json<AutoRefuel>, ApiError> {
+    service
+        .set_auto_refuel(room_id, player_id, request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Security at the player's airport and their overnight protection
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/security",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_security(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<SecurityResponse>, ApiError> {
+    service
+        .get_security(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Turn nightly hangar parking on or off
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/security/hangar",
+    request_body(content = SetHangarParkingRequest, example = examples::hangar_parking),
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn set_hangar_parking(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<SetHangarParkingRequest>,
+) -> Result<Json<SecurityResponse>, ApiError> {
+    service
+        .set_hangar_parking(room_id, player_id, request.hangar_parking)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Buy the aircraft security upgrade
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/security/upgrade",
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn buy_security_upgrade(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<SecurityResponse>, ApiError> {
+    service
+        .buy_security_upgrade(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Aircraft upgrades for sale and the levels fitted
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/hangar",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_hangar(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<HangarResponse>, ApiError> {
+    service
+        .get_hangar(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Buy the next level of an aircraft upgrade
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/upgrade",
+    request_body(content = BuyUpgradeRequest, example = examples::upgrade),
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn buy_upgrade(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<BuyUpgradeRequest>,
+) -> Result<Json<HangarResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.buy_upgrade(room_id, player_id, request.upgrade)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// An airport's prices over the last 20 turns
+///
+/// Fog of war rooms need the `player_id` of a player there.
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/markets/{airport_id}/history",
+    params(PriceHistoryQuery),
+    responses((status = 200, description = "Success"), RoomQueryErrors)
+)]
+pub async fn get_price_history(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, airport_id)): Path<(Uuid, String)>,
+    Query(query): Query<PriceHistoryQuery>,
+) -> Result<Json<PriceHistoryResponse>, ApiError> {
+    service
+        .get_price_history(room_id, airport_id, query.player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Vendor stalls at the player's airport
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/stalls",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_stalls(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<VendorStallsResponse>, ApiError> {
+    service
+        .get_stalls(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Buy a vendor stall's item
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/stalls/{stall_id}/buy",
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn buy_from_stall(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id, stall_id)): StallPath,
+    headers: HeaderMap,
+) -> Result<Json<StallPurchaseResponse>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.buy_from_stall(room_id, player_id, stall_id)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Change the room's game speed (host only)
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/speed",
+    request_body(content = SetGameSpeedRequest, example = examples::game_speed),
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn set_game_speed(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<SetGameSpeedRequest>,
+) -> Result<Json<GameSpeedResponse>, ApiError> {
+    service
+        .set_game_speed(room_id, player_id, request.speed)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Start a market event, labelled as yours in the event feed (host only)
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/events",
+    request_body(content = EventSpec, example = examples::host_event),
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn inject_event(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(spec): ApiJson<EventSpec>,
+) -> Result<Json<InjectEventResponse>, ApiError> {
+    service
+        .host_inject_event(room_id, player_id, spec)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// File a claim for lost cargo
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/claims",
+    request_body(content = FileClaimRequest, example = examples::claim),
+    responses((status = 200, description = "Success"), ClaimErrors)
+)]
+pub async fn file_claim(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<FileClaimRequest>,
+) -> Result<Json<InsuranceClaim>, ApiError> {
+    service
+        .with_idempotency_key(player_id, idempotency_key(&headers), || {
+            service.file_claim(room_id, player_id, request.loss_id)
+        })
+        .await
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Trading and fuel statistics
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/statistics",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_player_statistics(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<StatisticsInfo>, ApiError> {
+    service
+        .get_player_statistics(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Turn-by-turn journals for a post-game replay
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/replay",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_replay(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ReplayResponse>, ApiError> {
+    service
+        .get_replay(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Messages that @mention you, newest first
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/notifications",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_notifications(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<NotificationsResponse>, ApiError> {
+    service
+        .get_notifications(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Mark notifications read
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/notifications/read",
+    request_body(content = MarkNotificationsReadRequest, example = examples::mark_notifications_read),
+    responses((status = 200, description = "Success"), PlayerSettingErrors)
+)]
+pub async fn mark_notifications_read(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<MarkNotificationsReadRequest>,
+) -> Result<Json<NotificationsResponse>, ApiError> {
+    service
+        .mark_notifications_read(room_id, player_id, request.ids)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Get a daily digest by webhook or email
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/digest",
+    request_body(content = DigestRequest, example = examples::digest),
+    responses((status = 200, description = "Success"), PlayerSettingErrors)
+)]
+pub async fn subscribe_digest(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<DigestRequest>,
+) -> Result<Json<DigestResponse>, ApiError> {
+    service
+        .subscribe_digest(room_id, player_id, request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Stop the daily digest
+#[utoipa::path(
+    delete,
+    path = "/rooms/{room_id}/players/{player_id}/digest",
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn unsubscribe_digest(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<DigestResponse>, ApiError> {
+    service
+        .unsubscribe_digest(room_id, player_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Set or clear the room's turn deadline webhook (host only)
+#[utoipa::path(
+    post,
+    path = "/rooms/{room_id}/players/{player_id}/turn-deadline",
+    request_body(content = TurnDeadlineRequest, example = examples::turn_deadline),
+    responses((status = 200, description = "Success"), RoomActionErrors)
+)]
+pub async fn set_turn_deadline(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    ApiJson(request): ApiJson<TurnDeadlineRequest>,
+) -> Result<Json<TurnDeadlineResponse>, ApiError> {
+    service
+        .set_turn_deadline(room_id, player_id, request)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Read the airport message board, or one topic of it, a page at a time
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/messages",
+    params(MessagesQuery),
+    responses((status = 200, description = "Success"), MessagePageErrors)
+)]
 pub async fn get_messages(
     State(service): State<MultiplayerGameService>,
     Path((room_id, player_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<GetMessagesResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match service.get_messages(room_id, player_id) {
-        Ok(response) => Ok(Json(response)),
-        Err(error) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "GetMessagesError".to_string(),
-                message: error,
-                details: None,
-            }),
-        )),
-    }
+    Query(query): Query<MessagesQuery>,
+) -> Result<Json<GetMessagesResponse>, ApiError> {
+    service
+        .get_message_page(room_id, player_id, query)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Delete one of your own messages, or anyone's as the host
+#[utoipa::path(
+    delete,
+    path = "/rooms/{room_id}/players/{player_id}/messages/{message_id}",
+    responses((status = 200, description = "Success"), DeleteMessageErrors)
+)]
+pub async fn delete_message(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id, message_id)): MessagePath,
+) -> Result<Json<DeleteMessageResponse>, ApiError> {
+    service
+        .delete_message(room_id, player_id, message_id)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::ActionRejected))
+}
+
+/// Older messages the airport board no longer has room for
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/players/{player_id}/messages/archive",
+    params(ArchivedMessagesQuery),
+    responses((status = 200, description = "Success"), RoomReadErrors)
+)]
+pub async fn get_archived_messages(
+    State(service): State<MultiplayerGameService>,
+    Path((room_id, player_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<ArchivedMessagesQuery>,
+) -> Result<Json<ArchivedMessagesResponse>, ApiError> {
+    service
+        .get_archived_messages(room_id, player_id, query)
+        .map(Json)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))
+}
+
+/// Every airport's board, archive included; host only
+///
+/// The host's archive of every board in the room, as JSON or, with
+/// `?format=markdown`, a Markdown document
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/messages/export",
+    params(MessageExportQuery),
+    responses((status = 200, description = "Success"), RoomQueryErrors)
+)]
+pub async fn export_messages(
+    State(service): State<MultiplayerGameService>,
+    Path(room_id): Path<Uuid>,
+    Query(query): Query<MessageExportQuery>,
+) -> Result<Response, ApiError> {
+    let export = service
+        .export_messages(room_id, query.player_id)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))?;
+    Ok(match query.format {
+        ExportFormat::Json => Json(export).into_response(),
+        ExportFormat::Markdown => (
+            [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+            export.to_markdown(),
+        )
+            .into_response(),
+    })
+}
+
+// Comment line sent on idle streams so proxies don't time them out
+const STREAM_HEARTBEAT: Duration = Duration::from_secs(15);
+
+fn stream_event(event: StreamEvent) -> Event {
+    let (name, id, data) = match event {
+        StreamEvent::Snapshot { id, state } => ("snapshot", id, state.to_string()),
+        StreamEvent::Patch { id, ops } => {
+            ("patch", id, serde_json::to_string(&ops).unwrap_or_default())
+        },
+    };
+    Event::default().event(name).id(id).data(data)
+}
+
+/// Spectator event stream (SSE)
+///
+/// Server-sent events for spectators: a `snapshot` of the room, then
+/// `patch` events (JSON Patch) as it changes. Reconnecting clients send
+/// Last-Event-ID and pick up where they left off.
+#[utoipa::path(
+    get,
+    path = "/rooms/{room_id}/stream",
+    responses((status = 200, description = "Success"), RoomErrors)
+)]
+pub async fn stream_room(
+    State(service): State<MultiplayerGameService>,
+    Path(room_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let changes = service
+        .subscribe_room_stream(room_id)
+        .map_err(ApiError::or(ErrorCode::InvalidRequest))?;
+
+    let last_event_id = headers
+        .get("Last-Event-ID")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let events = stream::unfold(
+        (last_event_id, changes, VecDeque::new()),
+        move |(mut last_event_id, mut changes, mut pending)| {
+            let service = service.clone();
+            async move {
+                loop {
+                    if let Some(event) = pending.pop_front() {
+                        return Some((Ok(stream_event(event)), (last_event_id, changes, pending)));
+                    }
+
+                    let events = service.room_stream_events(room_id, last_event_id.as_deref());
+                    match events.last() {
+                        Some(StreamEvent::Snapshot { id, .. } | StreamEvent::Patch { id, .. }) => {
+                            last_event_id = Some(id.clone());
+                            pending.extend(events);
+                        },
+                        // Nothing new: wait for the room to change
+                        None => changes.changed().await.ok()?,
+                    }
+                }
+            }
+        },
+    );
+
+    Ok(Sse::new(events).keep_alive(
+        KeepAlive::new()
+            .interval(STREAM_HEARTBEAT)
+            .text("heartbeat"),
+    ))
 }
@@ -1,24 +1,421 @@
 use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
 };
 
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::sync::watch;
 use uuid::Uuid;
 
+#[cfg(feature = "chaos")]
+use crate::api::chaos::FaultInjector;
 use crate::{
-    api::{database::Database, models::*},
-    data::{airports::get_default_airports, cargo_types::get_default_cargo_types},
-    systems::{GameRoom, PlayerSession},
+    api::{
+        admin::AdminToken,
+        cluster::{ClusterConfig, RoomOwner},
+        database::{Database, JournalSync, LeaseOutcome},
+        digests::DigestSender,
+        errors::{ErrorCode, ServiceError},
+        load_shedding::LoadPolicy,
+        models::*,
+        room_stream::{RoomFeeds, StreamEvent},
+        webhooks::WebhookSender,
+    },
+    data::{
+        airports::{AIRPORT_RENAMES, get_default_airports},
+        cargo_types::get_default_cargo_types,
+    },
+    models::{
+        Market, MessageTopic, Player,
+        aircraft::{AircraftUpgrade, MAX_UPGRADE_LEVEL},
+        locale::{LocalizedMessage, MessageCode},
+        market::PRICE_HISTORY_TURNS,
+    },
+    systems::{
+        GameRoom, GameStatistics, GameStatus, PlayerSession, RoomSettings, RoomTemplate,
+        RouteFuelStats, TradingSystem, TravelQuote, TravelSystem, TurnAction, TurnEngine,
+        achievements::{Achievement, PilotProfile},
+        auto_refuel::AutoRefuel,
+        bank::{BANKRUPTCY_DEBT, BankSystem, INTEREST_PERCENT, LOAN_TERM_TURNS, MIN_LOAN},
+        bots::{
+            BOT_NAMES, BotTrade, BotTurn, DEFAULT_DEMO_BOTS, DEFAULT_DEMO_RESET_HOURS,
+            DEFAULT_DEMO_TURN_SECONDS, DEMO_ROOM_NAME, DemoRoom,
+        },
+        deadlines::{DEFAULT_DEADLINE_WARNING_HOURS, DEFAULT_TURN_DEADLINE_HOURS, TurnDeadline},
+        digest::{DEFAULT_DIGEST_HOURS, DigestTarget},
+        events::{EventSpec, GAME_MASTER_NAME, MarketEvent},
+        game::TurnReport,
+        hangar::HangarSystem,
+        insurance::{InsuranceAccount, InsuranceClaim, PREMIUM_PERCENT},
+        loadouts::CargoLoadout,
+        matchmaking::{GameMode, MatchPreferences, Matchmaker, QUICK_MATCH_ROOM_SIZE},
+        modifiers::{GlobalModifier, ModifierSource, ModifierSpec},
+        multiplayer::{GameSpeed, PlayerGameState},
+        npc_chatter::NPC_POST_CHANCE,
+        offers::{OfferSide, OfferTerms, TradeOffer},
+        price_alerts,
+        profile::QuantityDefaults,
+        rankings::{GameResult, MAX_RANKINGS_PAGE_SIZE, season_for},
+        route_profits::{ProfitMatrix, RouteAnalytics},
+        security::{HANGAR_FEE, SECURITY_UPGRADE_COST, SecuritySystem},
+        slots::SlotStatus,
+        trade_guards::TradeGuards,
+        trading::{TradeQuote, TradingError},
+        transfers::TransferTerms,
+        travel::{GROUND_TRANSFER_MAX_KM, TravelError},
+        valuation::NetWorth,
+    },
 };
 
 pub type GameRooms = Arc<Mutex<HashMap<Uuid, GameRoom>>>;
 pub type PlayerSessions = Arc<Mutex<HashMap<Uuid, PlayerSession>>>;
+pub type RoomTemplates = Arc<Mutex<HashMap<Uuid, RoomTemplate>>>;
+
+// (player_id, idempotency_key)
+type ActionKey = (Uuid, Uuid);
+
+// How many replayable action responses to remember before evicting the oldest
+const IDEMPOTENCY_CACHE_SIZE: usize = 1000;
+
+/// Most trades a player can make in one batch
+pub const MAX_BATCH_TRADES: usize = 50;
+
+/// Where an action with a given idempotency key has got to
+enum CachedAction {
+    /// The first request with the key is still being applied. The channel
+    /// closes once it settles, either way.
+    InFlight(watch::Receiver<()>),
+    /// It was applied, and this is what it answered
+    Done(serde_json::Value),
+}
+
+#[derive(Default)]
+struct CachedActions {
+    actions: HashMap<ActionKey, CachedAction>,
+    // Finished actions, oldest first; in-flight ones are never evicted
+    order: VecDeque<ActionKey>,
+}
+
+impl CachedActions {
+    fn finish(&mut self, key: ActionKey, response: serde_json::Value) {
+        if let Some(CachedAction::Done(_)) = self.actions.insert(key, CachedAction::Done(response))
+        {
+            return;
+        }
+        self.order.push_back(key);
+
+        while self.order.len() > IDEMPOTENCY_CACHE_SIZE {
+            if let Some(oldest) = self.order.pop_front() {
+                self.actions.remove(&oldest);
+            }
+        }
+    }
+
+    /// Forget a key whose action never finished, so it can be tried again
+    fn release(&mut self, key: &ActionKey) {
+        if let Some(CachedAction::InFlight(_)) = self.actions.get(key) {
+            self.actions.remove(key);
+        }
+    }
+}
+
+/// Responses to recently completed actions, keyed by the player and the
+/// client-supplied idempotency key, so replayed requests aren't applied twice.
+/// A key is taken as soon as its first request starts; a retry that arrives
+/// while that request is still being applied waits for its response.
+#[derive(Default)]
+pub struct IdempotencyCache {
+    actions: Mutex<CachedActions>,
+}
+
+impl IdempotencyCache {
+    fn lock(&self) -> Result<MutexGuard<'_, CachedActions>, ServiceError> {
+        self.actions
+            .lock()
+            .map_err(|_| ServiceError::lock("idempotency cache"))
+    }
+}
+
+/// A key taken for an action that's being applied. Dropping it without
+/// `finish`, when the action fails or panics, frees the key for a retry.
+struct InFlightKey<'a> {
+    cache: &'a IdempotencyCache,
+    key: ActionKey,
+    // Dropped with the key, which wakes any retries waiting on it
+    _settled: watch::Sender<()>,
+}
+
+impl InFlightKey<'_> {
+    fn finish(self, response: Option<serde_json::Value>) {
+        if let (Some(response), Ok(mut actions)) = (response, self.cache.lock()) {
+            actions.finish(self.key, response);
+        }
+    }
+}
+
+impl Drop for InFlightKey<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut actions) = self.cache.lock() {
+            actions.release(&self.key);
+        }
+    }
+}
+
+const DEFAULT_SESSION_TTL_HOURS: i64 = 24 * 7;
+const DEFAULT_SESSION_SWEEP_MINUTES: u64 = 10;
+
+/// How long a player session survives without activity, and how often the
+/// server sweeps out the expired ones.
+#[derive(Debug, Clone)]
+pub struct SessionPolicy {
+    pub ttl: chrono::Duration,
+    pub sweep_interval: Duration,
+}
+
+impl Default for SessionPolicy {
+    fn default() -> Self {
+        Self {
+            ttl: chrono::Duration::hours(DEFAULT_SESSION_TTL_HOURS),
+            sweep_interval: Duration::from_secs(DEFAULT_SESSION_SWEEP_MINUTES * 60),
+        }
+    }
+}
+
+impl SessionPolicy {
+    /// KZRK_SESSION_TTL_HOURS and KZRK_SESSION_SWEEP_MINUTES override the
+    /// defaults of a week and ten minutes
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let ttl = std::env::var("KZRK_SESSION_TTL_HOURS")
+            .ok()
+            .and_then(|hours| hours.trim().parse().ok())
+            .map(chrono::Duration::hours)
+            .unwrap_or(default.ttl);
+        let sweep_interval = std::env::var("KZRK_SESSION_SWEEP_MINUTES")
+            .ok()
+            .and_then(|minutes| minutes.trim().parse::<u64>().ok())
+            .filter(|minutes| *minutes > 0)
+            .map(|minutes| Duration::from_secs(minutes * 60))
+            .unwrap_or(default.sweep_interval);
+
+        Self {
+            ttl,
+            sweep_interval,
+        }
+    }
+}
+
+/// The demo room the server opens with bot traders while it has no other
+/// rooms, and how it's run
+#[derive(Debug, Clone)]
+pub struct DemoRoomPolicy {
+    pub bots: usize,
+    /// How long a round runs before the room starts over
+    pub reset_interval: chrono::Duration,
+    /// How often each bot takes a turn
+    pub turn_interval: Duration,
+}
+
+impl Default for DemoRoomPolicy {
+    fn default() -> Self {
+        Self {
+            bots: DEFAULT_DEMO_BOTS,
+            reset_interval: chrono::Duration::hours(DEFAULT_DEMO_RESET_HOURS),
+            turn_interval: Duration::from_secs(DEFAULT_DEMO_TURN_SECONDS),
+        }
+    }
+}
+
+impl DemoRoomPolicy {
+    /// None unless KZRK_DEMO_ROOM is on. KZRK_DEMO_BOTS,
+    /// KZRK_DEMO_RESET_HOURS and KZRK_DEMO_TURN_SECONDS override the
+    /// defaults of three bots, six hours and a minute.
+    pub fn from_env() -> Option<Self> {
+        let enabled = std::env::var("KZRK_DEMO_ROOM").is_ok_and(|value| {
+            matches!(
+                value.trim().to_lowercase().as_str(),
+                "1" | "true" | "on" | "yes"
+            )
+        });
+        if !enabled {
+            return None;
+        }
+
+        let default = Self::default();
+        let bots = std::env::var("KZRK_DEMO_BOTS")
+            .ok()
+            .and_then(|bots| bots.trim().parse::<usize>().ok())
+            .map(|bots| bots.clamp(1, BOT_NAMES.len()))
+            .unwrap_or(default.bots);
+        let reset_interval = std::env::var("KZRK_DEMO_RESET_HOURS")
+            .ok()
+            .and_then(|hours| hours.trim().parse::<i64>().ok())
+            .filter(|hours| *hours > 0)
+            .map(chrono::Duration::hours)
+            .unwrap_or(default.reset_interval);
+        let turn_interval = std::env::var("KZRK_DEMO_TURN_SECONDS")
+            .ok()
+            .and_then(|seconds| seconds.trim().parse::<u64>().ok())
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(default.turn_interval);
+
+        Some(Self {
+            bots,
+            reset_interval,
+            turn_interval,
+        })
+    }
+}
+
+/// When rooms are written to the database after a change
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SaveMode {
+    /// Before the action that changed the room answers
+    #[default]
+    EveryAction,
+    /// Changed rooms are written together on each tick of the interval. A
+    /// crash loses up to one interval of play.
+    Batched(Duration),
+}
+
+/// How much durability the server trades for throughput: when rooms are
+/// saved, how often the database is snapshotted, and how SQLite syncs.
+#[derive(Debug, Clone, Default)]
+pub struct PersistencePolicy {
+    pub save_mode: SaveMode,
+    pub snapshot_interval: Option<Duration>,
+    pub journal_sync: JournalSync,
+}
+
+impl PersistencePolicy {
+    /// KZRK_SAVE_INTERVAL_SECONDS batches room saves (0 saves on every
+    /// action, the default), KZRK_SNAPSHOT_MINUTES turns on periodic
+    /// snapshots and KZRK_DB_SYNC picks `off`, `normal` or `full` (default)
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let save_mode = std::env::var("KZRK_SAVE_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|seconds| seconds.trim().parse::<u64>().ok())
+            .filter(|seconds| *seconds > 0)
+            .map(|seconds| SaveMode::Batched(Duration::from_secs(seconds)))
+            .unwrap_or(default.save_mode);
+        let snapshot_interval = std::env::var("KZRK_SNAPSHOT_MINUTES")
+            .ok()
+            .and_then(|minutes| minutes.trim().parse::<u64>().ok())
+            .filter(|minutes| *minutes > 0)
+            .map(|minutes| Duration::from_secs(minutes * 60))
+            .or(default.snapshot_interval);
+        let journal_sync = std::env::var("KZRK_DB_SYNC")
+            .ok()
+            .and_then(|sync| JournalSync::parse(&sync))
+            .unwrap_or(default.journal_sync);
+
+        Self {
+            save_mode,
+            snapshot_interval,
+            journal_sync,
+        }
+    }
+}
+
+/// When the session's player last did anything: joining, or playing in
+/// their room
+fn session_last_active(
+    session: &PlayerSession,
+    rooms: &HashMap<Uuid, GameRoom>,
+) -> chrono::DateTime<chrono::Utc> {
+    session
+        .game_room_id
+        .and_then(|room_id| rooms.get(&room_id))
+        .and_then(|room| room.get_player(&session.player_id))
+        .map(|player| player.last_seen.max(session.connected_at))
+        .unwrap_or(session.connected_at)
+}
+
+/// Whether a player action went ahead. Actions the rules turn down still
+/// answer with a response, but leave nothing to commit; those without a
+/// `success` flag only answer when they went ahead.
+trait ActionOutcome {
+    fn succeeded(&self) -> bool {
+        true
+    }
+}
+
+impl ActionOutcome for PlayerTravelResponse {
+    fn succeeded(&self) -> bool {
+        self.success
+    }
+}
+
+impl ActionOutcome for PlayerTradeResponse {
+    fn succeeded(&self) -> bool {
+        self.success
+    }
+}
+
+impl ActionOutcome for PlayerTradeBatchResponse {}
+
+impl ActionOutcome for PlayerFuelResponse {
+    fn succeeded(&self) -> bool {
+        self.success
+    }
+}
+
+impl ActionOutcome for DeleteMessageResponse {}
+
+impl ActionOutcome for PostMessageResponse {
+    fn succeeded(&self) -> bool {
+        self.success
+    }
+}
+
+impl ActionOutcome for RematchVoteResponse {}
+
+impl ActionOutcome for InsuranceResponse {}
+
+impl ActionOutcome for LoanResponse {}
+
+impl ActionOutcome for InsuranceClaim {}
+
+impl ActionOutcome for SendCourierResponse {}
+
+impl ActionOutcome for OfferResponse {}
+
+impl ActionOutcome for TransferResponse {}
+
+impl ActionOutcome for GameSpeedResponse {}
+
+impl ActionOutcome for InjectEventResponse {}
+
+impl ActionOutcome for PriceAlertsResponse {}
+
+impl ActionOutcome for AutoRefuel {}
+
+impl ActionOutcome for SecurityResponse {}
+
+impl ActionOutcome for HangarResponse {}
+
+impl ActionOutcome for StallPurchaseResponse {}
 
 #[derive(Clone)]
 pub struct MultiplayerGameService {
     rooms: GameRooms,
     player_sessions: PlayerSessions,
+    templates: RoomTemplates,
+    idempotency_cache: Arc<IdempotencyCache>,
     db: Arc<Mutex<Database>>,
+    cluster: Option<ClusterConfig>,
+    room_feeds: Arc<Mutex<RoomFeeds>>,
+    session_policy: SessionPolicy,
+    persistence_policy: PersistencePolicy,
+    dirty_rooms: Arc<Mutex<HashSet<Uuid>>>,
+    digest_period: chrono::Duration,
+    admin: AdminToken,
+    load_policy: LoadPolicy,
+    #[cfg(feature = "chaos")]
+    faults: FaultInjector,
 }
 
 impl Default for MultiplayerGameService {
@@ -36,7 +433,19 @@ impl MultiplayerGameService {
         let mut service = Self {
             rooms: Arc::new(Mutex::new(HashMap::new())),
             player_sessions: Arc::new(Mutex::new(HashMap::new())),
+            templates: Arc::new(Mutex::new(HashMap::new())),
+            idempotency_cache: Arc::new(IdempotencyCache::default()),
             db: Arc::new(Mutex::new(db)),
+            cluster: None,
+            room_feeds: Arc::new(Mutex::new(RoomFeeds::default())),
+            session_policy: SessionPolicy::default(),
+            persistence_policy: PersistencePolicy::default(),
+            dirty_rooms: Arc::new(Mutex::new(HashSet::new())),
+            digest_period: chrono::Duration::hours(DEFAULT_DIGEST_HOURS),
+            admin: AdminToken::default(),
+            load_policy: LoadPolicy::default(),
+            #[cfg(feature = "chaos")]
+            faults: FaultInjector::default(),
         };
 
         // Load persisted rooms and sessions on startup
@@ -53,7 +462,19 @@ impl MultiplayerGameService {
         Self {
             rooms: Arc::new(Mutex::new(HashMap::new())),
             player_sessions: Arc::new(Mutex::new(HashMap::new())),
+            templates: Arc::new(Mutex::new(HashMap::new())),
+            idempotency_cache: Arc::new(IdempotencyCache::default()),
             db: Arc::new(Mutex::new(db)),
+            cluster: None,
+            room_feeds: Arc::new(Mutex::new(RoomFeeds::default())),
+            session_policy: SessionPolicy::default(),
+            persistence_policy: PersistencePolicy::default(),
+            dirty_rooms: Arc::new(Mutex::new(HashSet::new())),
+            digest_period: chrono::Duration::hours(DEFAULT_DIGEST_HOURS),
+            admin: AdminToken::default(),
+            load_policy: LoadPolicy::default(),
+            #[cfg(feature = "chaos")]
+            faults: FaultInjector::default(),
         }
     }
 
@@ -63,17 +484,264 @@ impl MultiplayerGameService {
         let mut service = Self {
             rooms: Arc::new(Mutex::new(HashMap::new())),
             player_sessions: Arc::new(Mutex::new(HashMap::new())),
+            templates: Arc::new(Mutex::new(HashMap::new())),
+            idempotency_cache: Arc::new(IdempotencyCache::default()),
             db: Arc::new(Mutex::new(db)),
+            cluster: None,
+            room_feeds: Arc::new(Mutex::new(RoomFeeds::default())),
+            session_policy: SessionPolicy::default(),
+            persistence_policy: PersistencePolicy::default(),
+            dirty_rooms: Arc::new(Mutex::new(HashSet::new())),
+            digest_period: chrono::Duration::hours(DEFAULT_DIGEST_HOURS),
+            admin: AdminToken::default(),
+            load_policy: LoadPolicy::default(),
+            #[cfg(feature = "chaos")]
+            faults: FaultInjector::default(),
         };
         // Load persisted state
         service.load_persisted_state();
         service
     }
 
+    /// Coordinate with other instances sharing this service's database:
+    /// rooms are only changed by the instance holding their lease.
+    pub fn with_cluster(mut self, cluster: ClusterConfig) -> Self {
+        self.cluster = Some(cluster);
+        self
+    }
+
+    pub fn cluster(&self) -> Option<&ClusterConfig> {
+        self.cluster.as_ref()
+    }
+
+    pub fn with_session_policy(mut self, session_policy: SessionPolicy) -> Self {
+        self.session_policy = session_policy;
+        self
+    }
+
+    pub fn session_policy(&self) -> &SessionPolicy {
+        &self.session_policy
+    }
+
+    pub fn with_persistence_policy(mut self, persistence_policy: PersistencePolicy) -> Self {
+        if let Ok(db) = self.db.lock()
+            && let Err(e) = db.set_journal_sync(persistence_policy.journal_sync)
+        {
+            tracing::warn!("Couldn't set the database sync policy: {}", e);
+        }
+        self.persistence_policy = persistence_policy;
+        self
+    }
+
+    pub fn persistence_policy(&self) -> &PersistencePolicy {
+        &self.persistence_policy
+    }
+
+    /// How often subscribed players are sent a digest of their rooms
+    pub fn with_digest_period(mut self, digest_period: chrono::Duration) -> Self {
+        self.digest_period = digest_period;
+        self
+    }
+
+    /// Limits on requests in flight, how long they take and how big they are
+    pub fn with_load_policy(mut self, load_policy: LoadPolicy) -> Self {
+        self.load_policy = load_policy;
+        self
+    }
+
+    pub fn load_policy(&self) -> &LoadPolicy {
+        &self.load_policy
+    }
+
+    /// Open the admin API to whoever holds this token
+    pub fn with_admin_token(mut self, admin: AdminToken) -> Self {
+        self.admin = admin;
+        self
+    }
+
+    pub fn admin(&self) -> &AdminToken {
+        &self.admin
+    }
+
+    /// Let the admin API switch on faults in rooms, for testing clients
+    #[cfg(feature = "chaos")]
+    pub fn with_fault_injector(mut self, faults: FaultInjector) -> Self {
+        self.faults = faults;
+        self
+    }
+
+    #[cfg(feature = "chaos")]
+    pub fn faults(&self) -> &FaultInjector {
+        &self.faults
+    }
+
+    #[cfg(feature = "chaos")]
+    pub(crate) fn require_room(&self, room_id: Uuid) -> Result<(), ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        Ok(())
+    }
+
+    /// Which instance should handle requests for a room. Takes or renews the
+    /// lease when it's free or already ours. The room is only read from the
+    /// database when this instance has no copy or has just taken the lease
+    /// over, as another instance may have changed it since; a renewal keeps
+    /// using the copy in memory. A copy with unsaved changes is written as
+    /// the lease is renewed, so they aren't lost to the next owner.
+    pub fn room_owner(&self, room_id: Uuid) -> Result<RoomOwner, ServiceError> {
+        let Some(cluster) = &self.cluster else {
+            return Ok(RoomOwner::Local);
+        };
+
+        let in_memory = self
+            .rooms
+            .lock()
+            .map_err(|_| ServiceError::lock("rooms"))?
+            .contains_key(&room_id);
+
+        let pending = if in_memory {
+            self.write_pending_room(room_id, cluster)?
+        } else {
+            None
+        };
+        let wrote_pending = pending.is_some();
+
+        // Don't hold the database lock while taking the rooms lock: saves take
+        // them the other way round
+        let (outcome, stored_room) = {
+            let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
+            let mut stored_room = None;
+            if !in_memory {
+                stored_room = db.load_room(&room_id).map_err(ServiceError::database)?;
+                if stored_room.is_none() {
+                    // Unknown room: let the handler report it
+                    return Ok(RoomOwner::Local);
+                }
+            }
+            let outcome = match pending {
+                Some(outcome) => outcome,
+                None => db
+                    .acquire_room_lease(&room_id, cluster, chrono::Utc::now())
+                    .map_err(ServiceError::database)?,
+            };
+            // Our unsaved changes were just written, so our copy is the latest
+            if in_memory
+                && !wrote_pending
+                && matches!(outcome, LeaseOutcome::Acquired { renewed: false })
+            {
+                stored_room = db.load_room(&room_id).map_err(ServiceError::database)?;
+            }
+            (outcome, stored_room)
+        };
+
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        match outcome {
+            LeaseOutcome::Acquired { .. } => {
+                if let Some(mut room) = stored_room {
+                    if Self::reconcile_loaded(&mut room)? {
+                        self.archive_overflow(&mut room);
+                        self.write_room(&room);
+                    }
+                    rooms.insert(room_id, room);
+                }
+                Ok(RoomOwner::Local)
+            },
+            LeaseOutcome::HeldBy(lease) => {
+                // Our copy goes stale as soon as the owner changes it
+                rooms.remove(&room_id);
+                if let Ok(mut dirty_rooms) = self.dirty_rooms.lock() {
+                    dirty_rooms.remove(&room_id);
+                }
+                if let Ok(mut feeds) = self.room_feeds.lock() {
+                    feeds.remove(&room_id);
+                }
+                Ok(RoomOwner::Remote {
+                    instance_id: lease.instance_id,
+                    instance_url: lease.instance_url,
+                })
+            },
+        }
+    }
+
+    /// Write a room with unsaved batched changes, renewing its lease in the
+    /// same transaction. `None` when there's nothing pending.
+    fn write_pending_room(
+        &self,
+        room_id: Uuid,
+        cluster: &ClusterConfig,
+    ) -> Result<Option<LeaseOutcome>, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let mut dirty_rooms = self
+            .dirty_rooms
+            .lock()
+            .map_err(|_| ServiceError::lock("dirty rooms"))?;
+        let Some(room) = rooms
+            .get(&room_id)
+            .filter(|_| dirty_rooms.contains(&room_id))
+        else {
+            return Ok(None);
+        };
+
+        let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
+        let outcome = db
+            .save_owned_room(room, cluster, chrono::Utc::now())
+            .map_err(ServiceError::database)?;
+        dirty_rooms.remove(&room_id);
+        if let LeaseOutcome::HeldBy(lease) = &outcome {
+            tracing::warn!(
+                "Dropping unsaved changes to room {}: instance {} took it over",
+                room_id,
+                lease.instance_id
+            );
+        }
+        Ok(Some(outcome))
+    }
+
+    /// Hand every room this instance owns back to the cluster
+    pub fn release_room_leases(&self) {
+        if let Some(cluster) = &self.cluster
+            && let Ok(db) = self.db.lock()
+        {
+            let _ = db.release_room_leases(&cluster.instance_id);
+        }
+    }
+
+    /// Fit a room read back from the database to the airports that exist
+    /// now. Returns whether anything changed, so it can be saved again.
+    fn reconcile_loaded(room: &mut GameRoom) -> Result<bool, ServiceError> {
+        let adjustments = room
+            .reconcile_airports(get_default_airports(), AIRPORT_RENAMES)
+            .map_err(|e| format!("Room {} can't be loaded: {}", room.id, e))?;
+        for adjustment in &adjustments {
+            tracing::warn!("Room {}: {}", room.id, adjustment.describe());
+        }
+        Ok(!adjustments.is_empty())
+    }
+
     fn load_persisted_state(&mut self) {
+        let mut changed_rooms = Vec::new();
         if let Ok(db) = self.db.lock() {
-            // Load rooms
+            // Load rooms, leaving out any that no longer fit the world
             if let Ok(rooms) = db.load_all_rooms() {
+                let rooms = rooms
+                    .into_iter()
+                    .filter_map(
+                        |(room_id, mut room)| match Self::reconcile_loaded(&mut room) {
+                            Ok(changed) => {
+                                if changed {
+                                    changed_rooms.push(room_id);
+                                }
+                                Some((room_id, room))
+                            },
+                            Err(e) => {
+                                tracing::warn!("{}", e);
+                                None
+                            },
+                        },
+                    )
+                    .collect();
                 *self.rooms.lock().unwrap() = rooms;
             }
 
@@ -81,12 +749,113 @@ impl MultiplayerGameService {
             if let Ok(sessions) = db.load_all_sessions() {
                 *self.player_sessions.lock().unwrap() = sessions;
             }
+
+            // Load room templates
+            if let Ok(templates) = db.load_all_templates() {
+                *self.templates.lock().unwrap() = templates;
+            }
+        }
+
+        for room_id in changed_rooms {
+            if let Some(room) = self.rooms.lock().unwrap().get_mut(&room_id) {
+                self.archive_overflow(room);
+                self.write_room(room);
+            }
+        }
+    }
+
+    /// Publish and save the room. Anything its retention limits pushed out
+    /// of memory goes to the archive straight away, whatever the save mode.
+    fn save_room(&self, room: &mut GameRoom) {
+        self.archive_overflow(room);
+        self.publish_room(room);
+
+        match self.persistence_policy.save_mode {
+            SaveMode::EveryAction => self.write_room(room),
+            SaveMode::Batched(_) => {
+                if let Ok(mut dirty_rooms) = self.dirty_rooms.lock() {
+                    dirty_rooms.insert(room.id);
+                }
+            },
+        }
+    }
+
+    /// How often `flush_dirty_rooms` needs calling, or `None` when every
+    /// action saves. Clustered, it's at most half a lease so changed rooms
+    /// are written, and their leases renewed, before the lease can lapse.
+    pub fn flush_interval(&self) -> Option<Duration> {
+        let SaveMode::Batched(save_interval) = self.persistence_policy.save_mode else {
+            return None;
+        };
+        Some(match &self.cluster {
+            Some(cluster) => save_interval.min(cluster.lease_ttl / 2),
+            None => save_interval,
+        })
+    }
+
+    /// Write every room changed since the last flush. Batched saves rely on
+    /// this being called each `flush_interval`, and on shutdown.
+    pub fn flush_dirty_rooms(&self) -> Result<usize, ServiceError> {
+        let dirty_rooms = std::mem::take(
+            &mut *self
+                .dirty_rooms
+                .lock()
+                .map_err(|_| ServiceError::lock("dirty rooms"))?,
+        );
+        if dirty_rooms.is_empty() {
+            return Ok(0);
+        }
+
+        // Rooms before the database, the same order saves take them in
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let mut written = 0;
+        for room_id in dirty_rooms {
+            // Rooms handed to another instance are theirs to save now
+            if let Some(room) = rooms.get(&room_id) {
+                self.write_room(room);
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+
+    /// Flush pending saves, then copy the database to `path`
+    pub fn snapshot_database(&self, path: &std::path::Path) -> Result<(), ServiceError> {
+        self.flush_dirty_rooms()?;
+        let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
+        db.snapshot_to(path).map_err(ServiceError::database)
+    }
+
+    fn archive_overflow(&self, room: &mut GameRoom) {
+        let overflow = room.take_overflow();
+        if overflow.is_empty() {
+            return;
+        }
+        if let Ok(db) = self.db.lock()
+            && let Err(e) = db.archive_entries(&room.id, &overflow)
+        {
+            tracing::warn!("Failed to archive entries for room {}: {}", room.id, e);
         }
     }
 
-    fn save_room(&self, room: &GameRoom) {
+    fn write_room(&self, room: &GameRoom) {
         if let Ok(db) = self.db.lock() {
-            let _ = db.save_room(room);
+            match &self.cluster {
+                None => {
+                    let _ = db.save_room(room);
+                },
+                Some(cluster) => {
+                    if let Ok(LeaseOutcome::HeldBy(lease)) =
+                        db.save_owned_room(room, cluster, chrono::Utc::now())
+                    {
+                        tracing::warn!(
+                            "Not saving room {}: it is owned by instance {}",
+                            room.id,
+                            lease.instance_id
+                        );
+                    }
+                },
+            }
         }
     }
 
@@ -96,32 +865,40 @@ impl MultiplayerGameService {
         }
     }
 
+    #[allow(dead_code)]
     pub fn create_room(
         &self,
         name: String,
         host_player_name: String,
         max_players: Option<usize>,
-    ) -> Result<CreateRoomResponse, String> {
+    ) -> Result<CreateRoomResponse, ServiceError> {
+        self.create_room_with_settings(name, host_player_name, max_players, RoomSettings::default())
+    }
+
+    pub fn create_room_with_settings(
+        &self,
+        name: String,
+        host_player_name: String,
+        max_players: Option<usize>,
+        settings: RoomSettings,
+    ) -> Result<CreateRoomResponse, ServiceError> {
         let host_player_id = Uuid::new_v4();
         let max_players = max_players.unwrap_or(4);
 
         if !(1..=8).contains(&max_players) {
-            return Err("Max players must be between 1 and 8".to_string());
+            return Err("Max players must be between 1 and 8".into());
         }
 
-        let airports = get_default_airports();
-        let cargo_types = get_default_cargo_types();
-
-        let room = GameRoom::new(
+        let mut room = GameRoom::from_settings(
             name.clone(),
             host_player_id,
             host_player_name.clone(),
             max_players,
-            airports,
-            cargo_types,
-        );
+            settings,
+        )?;
 
         let room_id = room.id;
+        let starting_airport = room.players[&host_player_id].player.current_airport.clone();
 
         // Create player session for host
         let player_session = PlayerSession {
@@ -133,10 +910,7 @@ impl MultiplayerGameService {
 
         // Store the room and session
         {
-            let mut rooms = self
-                .rooms
-                .lock()
-                .map_err(|_| "Failed to acquire rooms lock")?;
+            let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
             rooms.insert(room_id, room.clone());
         }
 
@@ -144,12 +918,12 @@ impl MultiplayerGameService {
             let mut sessions = self
                 .player_sessions
                 .lock()
-                .map_err(|_| "Failed to acquire sessions lock")?;
+                .map_err(|_| ServiceError::lock("sessions"))?;
             sessions.insert(host_player_id, player_session.clone());
         }
 
         // Save room and session to database
-        self.save_room(&room);
+        self.save_room(&mut room);
         self.save_session(&player_session);
 
         Ok(CreateRoomResponse {
@@ -159,64 +933,366 @@ impl MultiplayerGameService {
             host_player_name,
             max_players,
             current_players: 1,
+            starting_airport,
         })
     }
 
-    pub fn list_rooms(&self) -> Result<Vec<RoomInfo>, String> {
-        let rooms = self
-            .rooms
-            .lock()
-            .map_err(|_| "Failed to acquire rooms lock")?;
+    pub fn create_room_from_template(
+        &self,
+        template_id: Uuid,
+        name: String,
+        host_player_name: String,
+    ) -> Result<CreateRoomResponse, ServiceError> {
+        let template = {
+            let templates = self
+                .templates
+                .lock()
+                .map_err(|_| ServiceError::lock("templates"))?;
+            templates
+                .get(&template_id)
+                .cloned()
+                .ok_or(ServiceError::NotFound(ErrorCode::TemplateNotFound))?
+        };
 
-        let room_list = rooms
-            .values()
-            .map(|room| {
-                let host_player = room
-                    .players
-                    .get(&room.host_player_id)
-                    .map(|p| p.player_name.clone())
-                    .unwrap_or_else(|| "Unknown".to_string());
+        self.create_room_with_settings(
+            name,
+            host_player_name,
+            Some(template.max_players),
+            template.settings,
+        )
+    }
 
-                RoomInfo {
-                    id: room.id,
-                    name: room.name.clone(),
-                    host_player_name: host_player,
-                    current_players: room.players.values().filter(|p| p.is_online).count() as u32,
-                    max_players: room.max_players as u32,
-                    created_at: room.created_at,
-                    game_status: room.game_status.clone(),
-                    is_joinable: room.is_joinable(),
-                }
-            })
-            .collect();
+    /// Start a fresh room with the same rules as an existing one.
+    pub fn clone_room(
+        &self,
+        room_id: Uuid,
+        name: Option<String>,
+        host_player_name: String,
+    ) -> Result<CreateRoomResponse, ServiceError> {
+        let (source_name, max_players, settings) = {
+            let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+            let room = rooms
+                .get(&room_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+            (
+                room.name.clone(),
+                room.max_players,
+                room.settings.shareable(),
+            )
+        };
 
-        Ok(room_list)
+        let name = name.unwrap_or_else(|| format!("{} (copy)", source_name));
+        self.create_room_with_settings(name, host_player_name, Some(max_players), settings)
     }
 
-    pub fn join_room(
+    /// Start the next round after a room finishes. The first player to ask
+    /// creates a fresh room with the same rules and becomes its host; everyone
+    /// after that joins the room they created.
+    pub fn rematch(
         &self,
         room_id: Uuid,
-        player_name: String,
-        starting_airport: Option<String>,
-    ) -> Result<JoinRoomResponse, String> {
-        let mut player_id = Uuid::new_v4();
+        player_id: Uuid,
+    ) -> Result<JoinRoomResponse, ServiceError> {
+        let (room_name, player_name, rematch_room_id) = {
+            let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+            let room = rooms
+                .get(&room_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+            if !room.is_finished() {
+                return Err("A rematch is only available once the game is over"
+                    .to_string()
+                    .into());
+            }
+            let player_state = room
+                .get_player(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            (
+                room.name.clone(),
+                player_state.player_name.clone(),
+                room.rematch_room_id
+                    .filter(|rematch_id| rooms.contains_key(rematch_id)),
+            )
+        };
+
+        if let Some(rematch_room_id) = rematch_room_id {
+            return self.join_room(rematch_room_id, player_name, None);
+        }
+
+        let created = self.clone_room(
+            room_id,
+            Some(format!("{} (rematch)", room_name)),
+            player_name,
+        )?;
+
+        {
+            let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+            if let Some(room) = rooms.get_mut(&room_id) {
+                room.rematch_room_id = Some(created.room_id);
+                self.save_room(room);
+            }
+        }
+
+        Ok(JoinRoomResponse {
+            room_id: created.room_id,
+            player_id: created.host_player_id,
+            player_name: created.host_player_name,
+            success: true,
+            message: "Rematch room created".to_string(),
+            starting_airport: created.starting_airport,
+        })
+    }
+
+    /// Vote to play the next round in this same room, keeping its players
+    /// and message board. The round starts once every online player votes.
+    pub fn vote_rematch(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<RematchVoteResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            let restarted = room.vote_rematch(&player_id)?;
+            let votes_needed = room.rematch_votes_needed();
+            let (votes, message, starting_airport) = if restarted {
+                let airport_id = room
+                    .get_player(&player_id)
+                    .map(|state| state.player.current_airport.clone());
+                (
+                    votes_needed,
+                    format!("Everyone's in - round {} has started", room.round),
+                    airport_id,
+                )
+            } else {
+                let votes = room.rematch_votes.len();
+                (
+                    votes,
+                    format!("{} of {} players want a rematch", votes, votes_needed),
+                    None,
+                )
+            };
+
+            Ok(RematchVoteResponse {
+                restarted,
+                round: room.round,
+                votes,
+                votes_needed,
+                message,
+                starting_airport,
+            })
+        })
+    }
+
+    pub fn create_template(
+        &self,
+        request: CreateTemplateRequest,
+    ) -> Result<RoomTemplate, ServiceError> {
+        if request.name.trim().is_empty() {
+            return Err("Template name cannot be empty".into());
+        }
+
+        // Either snapshot an existing room's rules or take them from the request
+        let (max_players, settings) = match request.from_room_id {
+            Some(room_id) => {
+                let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+                let room = rooms
+                    .get(&room_id)
+                    .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+                (room.max_players, room.settings.shareable())
+            },
+            None => (
+                request.max_players.unwrap_or(4),
+                request.settings.unwrap_or_default(),
+            ),
+        };
+
+        if !(1..=8).contains(&max_players) {
+            return Err("Max players must be between 1 and 8".into());
+        }
+        settings.validate(&get_default_airports(), &get_default_cargo_types())?;
+
+        let template = RoomTemplate::new(request.name, max_players, settings);
 
-        // Update the room
         {
-            let mut rooms = self
-                .rooms
+            let mut templates = self
+                .templates
+                .lock()
+                .map_err(|_| ServiceError::lock("templates"))?;
+            templates.insert(template.id, template.clone());
+        }
+
+        if let Ok(db) = self.db.lock() {
+            let _ = db.save_template(&template);
+        }
+
+        Ok(template)
+    }
+
+    pub fn list_templates(&self) -> Result<Vec<RoomTemplate>, ServiceError> {
+        let templates = self
+            .templates
+            .lock()
+            .map_err(|_| ServiceError::lock("templates"))?;
+
+        let mut template_list: Vec<RoomTemplate> = templates.values().cloned().collect();
+        template_list.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(template_list)
+    }
+
+    pub fn list_rooms(&self) -> Result<Vec<RoomInfo>, ServiceError> {
+        // Other instances' rooms are only in the shared database
+        let stored = match self.cluster {
+            Some(_) => self
+                .db
                 .lock()
-                .map_err(|_| "Failed to acquire rooms lock")?;
-            let room = rooms.get_mut(&room_id).ok_or("Room not found")?;
+                .map_err(|_| ServiceError::lock("database"))?
+                .load_all_rooms()
+                .map_err(ServiceError::database)?,
+            None => HashMap::new(),
+        };
+
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+
+        let room_list = rooms
+            .values()
+            .chain(stored.values().filter(|room| !rooms.contains_key(&room.id)))
+            .map(|room| {
+                let host_player = room
+                    .players
+                    .get(&room.host_player_id)
+                    .map(|p| p.player_name.clone())
+                    .unwrap_or_else(|| "Unknown".to_string());
+
+                RoomInfo {
+                    id: room.id,
+                    name: room.name.clone(),
+                    host_player_name: host_player,
+                    current_players: room.players.values().filter(|p| p.is_online).count() as u32,
+                    max_players: room.max_players as u32,
+                    created_at: room.created_at,
+                    game_status: room.game_status.clone(),
+                    is_joinable: room.is_joinable(),
+                    is_demo: room.demo.is_some(),
+                    event_rules: EventRulesInfo::from(&room.settings),
+                }
+            })
+            .collect();
+
+        Ok(room_list)
+    }
+
+    /// Everything a room plays by, for anyone deciding whether to join
+    pub fn get_room_settings(&self, room_id: Uuid) -> Result<RoomSettingsResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        let mut airports: Vec<String> = room.shared_state.airports.keys().cloned().collect();
+        airports.sort();
+        let mut cargo_types: Vec<String> = room.shared_state.cargo_types.keys().cloned().collect();
+        cargo_types.sort();
+
+        Ok(RoomSettingsResponse {
+            room_id: room.id,
+            room_name: room.name.clone(),
+            max_players: room.max_players,
+            game_status: room.game_status.clone(),
+            settings: room.settings.shareable(),
+            airports,
+            cargo_types,
+            turn_deadline_hours: room
+                .settings
+                .turn_deadline
+                .as_ref()
+                .map(|turn_deadline| turn_deadline.hours),
+            event_rules: EventRulesInfo::from(&room.settings),
+        })
+    }
+
+    pub fn get_room_modifiers(&self, room_id: Uuid) -> Result<ModifiersResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        Ok(ModifiersResponse {
+            room_id,
+            modifiers: room.shared_state.modifiers.clone(),
+        })
+    }
+
+    /// Start a modifier in a room on an admin's say-so
+    pub fn apply_room_modifier(
+        &self,
+        room_id: Uuid,
+        spec: ModifierSpec,
+    ) -> Result<GlobalModifier, ServiceError> {
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get_mut(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        let modifier = room.apply_modifier(&spec, ModifierSource::Admin)?;
+        self.save_room(room);
+        Ok(modifier)
+    }
+
+    /// Start a market event in a room on an admin's say-so
+    pub fn inject_room_event(
+        &self,
+        room_id: Uuid,
+        spec: EventSpec,
+    ) -> Result<InjectEventResponse, ServiceError> {
+        // The game master isn't a player, so there's nobody's state to hold
+        self.transact(room_id, Uuid::nil(), |room| {
+            room.ensure_in_play()?;
+            let (event, notified_players) =
+                room.inject_event(&spec, &Uuid::nil(), GAME_MASTER_NAME)?;
+            Ok(InjectEventResponse {
+                event: EventInfo::from(&event),
+                notified_players,
+            })
+        })
+    }
+
+    /// Stop a modifier before its turns run out
+    pub fn cancel_room_modifier(
+        &self,
+        room_id: Uuid,
+        modifier_id: Uuid,
+    ) -> Result<GlobalModifier, ServiceError> {
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get_mut(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        let modifier = room.cancel_modifier(&modifier_id)?;
+        self.save_room(room);
+        Ok(modifier)
+    }
+
+    pub fn join_room(
+        &self,
+        room_id: Uuid,
+        player_name: String,
+        starting_airport: Option<String>,
+    ) -> Result<JoinRoomResponse, ServiceError> {
+        let mut player_id = Uuid::new_v4();
+
+        // Update the room
+        let starting_airport = {
+            let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+            let room = rooms
+                .get_mut(&room_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
 
             if !room.is_joinable() {
-                return Err("Room is not joinable".to_string());
+                return Err("Room is not joinable".into());
             }
 
             let actual_player_id =
                 room.add_player(player_id, player_name.clone(), starting_airport)?;
             player_id = actual_player_id;
-        }
+            self.save_room(room);
+            room.players[&player_id].player.current_airport.clone()
+        };
 
         // Create player session
         let player_session = PlayerSession {
@@ -230,7 +1306,7 @@ impl MultiplayerGameService {
             let mut sessions = self
                 .player_sessions
                 .lock()
-                .map_err(|_| "Failed to acquire sessions lock")?;
+                .map_err(|_| ServiceError::lock("sessions"))?;
             sessions.insert(player_id, player_session.clone());
         }
 
@@ -243,36 +1319,111 @@ impl MultiplayerGameService {
             player_name,
             success: true,
             message: "Successfully joined room".to_string(),
+            starting_airport,
         })
     }
 
-    pub fn leave_room(&self, room_id: Uuid, player_id: Uuid) -> Result<LeaveRoomResponse, String> {
-        // Remove player from room
+    /// Put a player in the open room that suits them best, or open a new
+    /// one for them when nothing matches. Only rooms on this instance are
+    /// considered.
+    pub fn quick_match(
+        &self,
+        player_name: String,
+        preferences: MatchPreferences,
+    ) -> Result<QuickMatchResponse, ServiceError> {
+        preferences.validate()?;
+
+        let candidate = {
+            let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+            Matchmaker::find_room(rooms.values(), &preferences)
+                .and_then(|room_id| rooms.get(&room_id))
+                .map(|room| {
+                    (
+                        room.id,
+                        room.name.clone(),
+                        GameMode::of(&room.settings),
+                        room.max_players,
+                    )
+                })
+        };
+
+        // Someone may have taken the last seat since; open a room instead
+        if let Some((room_id, room_name, mode, max_players)) = candidate
+            && let Ok(joined) = self.join_room(room_id, player_name.clone(), None)
         {
-            let mut rooms = self
-                .rooms
-                .lock()
-                .map_err(|_| "Failed to acquire rooms lock")?;
-            if let Some(room) = rooms.get_mut(&room_id) {
-                room.mark_player_offline(player_id)?;
+            return Ok(QuickMatchResponse {
+                room_id,
+                room_name,
+                player_id: joined.player_id,
+                player_name: joined.player_name,
+                mode,
+                max_players,
+                created: false,
+                starting_airport: joined.starting_airport,
+            });
+        }
 
-                // Check if all players are offline
-                let all_offline = room.players.values().all(|p| !p.is_online);
-                if all_offline {
-                    room.game_status = crate::systems::GameStatus::WaitingForPlayers;
-                }
+        let mode = preferences.mode.unwrap_or_default();
+        let created = self.create_room_with_settings(
+            format!("Quick Match ({})", mode.label()),
+            player_name,
+            Some(preferences.max_players.unwrap_or(QUICK_MATCH_ROOM_SIZE)),
+            mode.settings(),
+        )?;
 
-                // Save room state after player leaves
-                self.save_room(room);
+        Ok(QuickMatchResponse {
+            room_id: created.room_id,
+            room_name: created.room_name,
+            player_id: created.host_player_id,
+            player_name: created.host_player_name,
+            mode,
+            max_players: created.max_players,
+            created: true,
+            starting_airport: created.starting_airport,
+        })
+    }
+
+    pub fn leave_room(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        force: bool,
+    ) -> Result<LeaveRoomResponse, ServiceError> {
+        // Remove player from room
+        let obligations = {
+            let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+            let room = rooms
+                .get_mut(&room_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+            let obligations = room.leave_obligations(&player_id);
+
+            // Make the player confirm before walking away from their assets
+            if !obligations.is_empty() && !force {
+                return Err(ServiceError::ConfirmationRequired {
+                    reason: "You still have assets in this room".to_string(),
+                    obligations,
+                });
             }
-        }
+
+            room.mark_player_offline(player_id)?;
+
+            // Check if all players are offline
+            let all_offline = room.players.values().all(|p| !p.is_online);
+            if all_offline {
+                room.game_status = crate::systems::GameStatus::WaitingForPlayers;
+            }
+
+            // Save room state after player leaves
+            self.save_room(room);
+            obligations
+        };
 
         // Update player session
         {
             let mut sessions = self
                 .player_sessions
                 .lock()
-                .map_err(|_| "Failed to acquire sessions lock")?;
+                .map_err(|_| ServiceError::lock("sessions"))?;
             if let Some(session) = sessions.get_mut(&player_id) {
                 session.game_room_id = None;
             }
@@ -284,30 +1435,30 @@ impl MultiplayerGameService {
         Ok(LeaveRoomResponse {
             success: true,
             message: "Successfully left room".to_string(),
+            obligations,
         })
     }
 
     pub fn find_player_sessions(
         &self,
         player_name: &str,
-    ) -> Result<Vec<PlayerSessionInfo>, String> {
-        let rooms = self
-            .rooms
-            .lock()
-            .map_err(|_| "Failed to acquire rooms lock")?;
+    ) -> Result<Vec<PlayerSessionInfo>, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
 
-        let db = self
-            .db
-            .lock()
-            .map_err(|_| "Failed to acquire database lock")?;
+        let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
         let sessions = db
             .find_sessions_by_player_name(player_name)
-            .map_err(|e| format!("Database error: {}", e))?;
+            .map_err(ServiceError::database)?;
 
-        let matching_sessions: Vec<PlayerSessionInfo> = sessions
+        // Sessions past their TTL are only waiting for the next sweep
+        let now = chrono::Utc::now();
+        let cutoff = now - self.session_policy.ttl;
+        let mut matching_sessions: Vec<PlayerSessionInfo> = sessions
             .iter()
             .filter(|session| session.game_room_id.is_some())
-            .map(|session| {
+            .map(|session| (session, session_last_active(session, &rooms)))
+            .filter(|(_, last_active)| *last_active >= cutoff)
+            .map(|(session, last_active)| {
                 let room_name = session
                     .game_room_id
                     .and_then(|room_id| rooms.get(&room_id))
@@ -320,622 +1471,2844 @@ impl MultiplayerGameService {
                     room_id: session.game_room_id.unwrap(),
                     room_name,
                     connected_at: session.connected_at,
+                    last_active,
+                    idle_seconds: now.signed_duration_since(last_active).num_seconds().max(0),
                 }
             })
             .collect();
 
+        // Most recently played first
+        matching_sessions.sort_by_key(|session| std::cmp::Reverse(session.last_active));
+
         Ok(matching_sessions)
     }
 
-    pub fn get_room_state(
+    /// Every room a player name hosts on this server, with its players'
+    /// progress and anything the host should look at as of `now`: unfinished
+    /// games nobody has played for `STALLED_ROOM_MINUTES` and games someone
+    /// won
+    pub fn hosted_rooms(
         &self,
-        room_id: Uuid,
-        requesting_player_id: Uuid,
-    ) -> Result<MultiplayerGameStateResponse, String> {
-        let rooms = self
-            .rooms
-            .lock()
-            .map_err(|_| "Failed to acquire rooms lock")?;
-        let _room = rooms.get(&room_id).ok_or("Room not found")?;
+        player_name: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<HostedRoomSummary>, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
 
-        // Update player activity
-        drop(rooms);
-        {
-            let mut rooms = self
-                .rooms
+        let mut hosted: Vec<HostedRoomSummary> = rooms
+            .values()
+            .filter(|room| room.demo.is_none())
+            .filter(|room| {
+                room.get_player(&room.host_player_id)
+                    .is_some_and(|host| host.player_name == player_name)
+            })
+            .map(|room| {
+                let mut players: Vec<HostedRoomPlayer> = room
+                    .players
+                    .values()
+                    .map(|player| HostedRoomPlayer {
+                        player_id: player.player_id,
+                        player_name: player.player_name.clone(),
+                        turn_number: player.turn_number,
+                        is_online: player.is_online,
+                    })
+                    .collect();
+                players.sort_by(|a, b| {
+                    b.turn_number
+                        .cmp(&a.turn_number)
+                        .then_with(|| a.player_name.cmp(&b.player_name))
+                });
+
+                let last_activity = room
+                    .players
+                    .values()
+                    .map(|player| player.last_seen)
+                    .max()
+                    .unwrap_or(room.created_at);
+                let idle = now.signed_duration_since(last_activity);
+
+                let mut alerts = Vec::new();
+                if room.game_status != GameStatus::Finished
+                    && idle.num_minutes() >= STALLED_ROOM_MINUTES
+                {
+                    alerts.push(RoomAlert::Stalled {
+                        idle_minutes: idle.num_minutes(),
+                    });
+                }
+                if let Some(winner) = &room.winner {
+                    alerts.push(RoomAlert::Finished {
+                        winner: winner.player_name.clone(),
+                    });
+                }
+
+                HostedRoomSummary {
+                    room_id: room.id,
+                    room_name: room.name.clone(),
+                    host_player_id: room.host_player_id,
+                    game_status: room.game_status.clone(),
+                    round: room.round,
+                    players_online: players.iter().filter(|p| p.is_online).count() as u32,
+                    players_total: players.len() as u32,
+                    max_players: room.max_players as u32,
+                    turn_number: players.first().map_or(0, |p| p.turn_number),
+                    players,
+                    last_activity,
+                    idle_seconds: idle.num_seconds().max(0),
+                    alerts,
+                }
+            })
+            .collect();
+
+        // Rooms that need the host first, then the busiest
+        hosted.sort_by_key(|room| (room.alerts.is_empty(), room.idle_seconds));
+
+        Ok(hosted)
+    }
+
+    /// End a player's session: they go offline in their room and the
+    /// session is forgotten, so it's no longer offered for resuming.
+    pub fn logout(&self, player_id: Uuid) -> Result<LogoutResponse, ServiceError> {
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+
+        let session = {
+            let mut sessions = self
+                .player_sessions
                 .lock()
-                .map_err(|_| "Failed to acquire rooms lock")?;
-            if let Some(room) = rooms.get_mut(&room_id) {
-                room.update_player_activity(&requesting_player_id);
+                .map_err(|_| ServiceError::lock("sessions"))?;
+            let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
+            let stored = db
+                .load_session(&player_id)
+                .map_err(ServiceError::database)?;
+            let session = sessions.remove(&player_id).or(stored);
+            if session.is_some() {
+                db.delete_session(&player_id)
+                    .map_err(ServiceError::database)?;
             }
-        }
-        let rooms = self
-            .rooms
-            .lock()
-            .map_err(|_| "Failed to acquire rooms lock")?;
-        let room = rooms.get(&room_id).ok_or("Room not found")?;
+            session
+        };
+        let session = session.ok_or(ServiceError::NotFound(ErrorCode::SessionNotFound))?;
 
-        // Verify player is in room
-        if !room.players.contains_key(&requesting_player_id) {
-            return Err("Player not in room".to_string());
+        if let Some(room) = session
+            .game_room_id
+            .and_then(|room_id| rooms.get_mut(&room_id))
+            && room.get_player(&player_id).is_some_and(|p| p.is_online)
+        {
+            room.mark_player_offline(player_id)?;
+            self.save_room(room);
         }
 
-        self.build_multiplayer_game_state_response(room, requesting_player_id)
+        Ok(LogoutResponse {
+            success: true,
+            message: "Logged out".to_string(),
+        })
     }
 
-    pub fn player_travel(
+    /// Remove sessions idle for longer than the policy's TTL, taking their
+    /// players offline. Returns how many were removed.
+    pub fn expire_sessions(
         &self,
-        room_id: Uuid,
-        player_id: Uuid,
-        destination: String,
-    ) -> Result<PlayerTravelResponse, String> {
-        let mut rooms = self
-            .rooms
-            .lock()
-            .map_err(|_| "Failed to acquire rooms lock")?;
-        let room = rooms.get_mut(&room_id).ok_or("Room not found")?;
-
-        // Get necessary information before mutable borrows
-        let destination_airport_name = room
-            .shared_state
-            .airports
-            .get(&destination)
-            .ok_or("Destination airport not found")?
-            .name
-            .clone();
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, ServiceError> {
+        let cutoff = now - self.session_policy.ttl;
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
 
-        let (distance, fuel_required) = {
-            let player_state = room
-                .get_player(&player_id)
-                .ok_or("Player not found in room")?;
-            let destination_airport = room
-                .shared_state
-                .airports
-                .get(&destination)
-                .ok_or("Destination airport not found")?;
-            let current_airport = room
-                .shared_state
-                .airports
-                .get(&player_state.player.current_airport)
-                .ok_or("Current airport not found")?;
+        let expired: Vec<PlayerSession> = {
+            let mut sessions = self
+                .player_sessions
+                .lock()
+                .map_err(|_| ServiceError::lock("sessions"))?;
+            let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
 
-            let distance = current_airport.distance_to(destination_airport);
-            let fuel_required = player_state.player.fuel_needed_for_distance(distance);
-            (distance, fuel_required)
-        };
+            // The database also holds sessions created by other instances
+            let mut candidates = db.load_all_sessions().map_err(ServiceError::database)?;
+            for (player_id, session) in sessions.iter() {
+                candidates.insert(*player_id, session.clone());
+            }
 
-        // Check if travel is possible
-        let can_travel = {
-            let player_state = room
-                .get_player(&player_id)
-                .ok_or("Player not found in room")?;
-            player_state.player.can_travel_distance(distance)
+            let expired: Vec<PlayerSession> = candidates
+                .into_values()
+                .filter(|session| session_last_active(session, &rooms) < cutoff)
+                .collect();
+            for session in &expired {
+                sessions.remove(&session.player_id);
+                db.delete_session(&session.player_id)
+                    .map_err(ServiceError::database)?;
+            }
+            expired
         };
 
-        if !can_travel {
-            let current_fuel = room.get_player(&player_id).unwrap().player.fuel;
-            return Ok(PlayerTravelResponse {
-                success: false,
-                message: format!(
-                    "Insufficient fuel. Need {} units, have {}",
-                    fuel_required, current_fuel
-                ),
-                fuel_consumed: None,
-                new_location: None,
-            });
+        for session in &expired {
+            if let Some(room) = session
+                .game_room_id
+                .and_then(|room_id| rooms.get_mut(&room_id))
+                && room
+                    .get_player(&session.player_id)
+                    .is_some_and(|p| p.is_online)
+            {
+                room.mark_player_offline(session.player_id)?;
+                self.save_room(room);
+            }
         }
 
-        // Perform travel
-        {
-            let player_state = room
-                .get_player_mut(&player_id)
-                .ok_or("Player not found in room")?;
-            player_state.player.consume_fuel(fuel_required);
-            player_state.player.current_airport = destination.clone();
+        Ok(expired.len())
+    }
+
+    /// One pass of the real-time clock: in every room whose clock is due
+    /// (`interval` apart at normal speed, scaled by the room's game speed),
+    /// locals may post on the message boards. Returns how many posts were made.
+    pub fn tick_room_clocks(
+        &self,
+        interval: Duration,
+        now: Instant,
+    ) -> Result<usize, ServiceError> {
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+
+        let mut rng = rand::thread_rng();
+        let mut posted = 0;
+        for room in rooms.values_mut() {
+            if !room.clock_due(interval, now) {
+                continue;
+            }
+            let room_posts = room.post_npc_chatter(NPC_POST_CHANCE, &mut rng);
+            if room_posts > 0 {
+                self.save_room(room);
+                posted += room_posts;
+            }
         }
+        Ok(posted)
+    }
 
-        // Update statistics
-        if let Some(stats) = room.player_statistics.get_mut(&player_id) {
-            stats.record_travel(&destination, distance);
+    /// Open a demo room with bot traders when the server has no rooms at
+    /// all, so the first visitor has a game to join. Returns the new room.
+    pub fn ensure_demo_room(&self, policy: &DemoRoomPolicy) -> Result<Option<Uuid>, ServiceError> {
+        if !self.list_rooms()?.is_empty() {
+            return Ok(None);
         }
 
-        // Advance turn and potentially generate events
-        room.advance_turn();
+        let bot_names = &BOT_NAMES[..policy.bots.clamp(1, BOT_NAMES.len())];
+        let created = self.create_room_with_settings(
+            DEMO_ROOM_NAME.to_string(),
+            bot_names[0].to_string(),
+            Some(8),
+            RoomSettings::default(),
+        )?;
+        let mut bots = vec![created.host_player_id];
+        for name in &bot_names[1..] {
+            let joined = self.join_room(created.room_id, name.to_string(), None)?;
+            bots.push(joined.player_id);
+        }
 
-        // Save room state after travel
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get_mut(&created.room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        room.demo = Some(DemoRoom {
+            bots,
+            reset_at: chrono::Utc::now(),
+        });
         self.save_room(room);
-
-        Ok(PlayerTravelResponse {
-            success: true,
-            message: format!("Traveled to {} ({})", destination_airport_name, destination),
-            fuel_consumed: Some(fuel_required),
-            new_location: Some(destination),
-        })
+        Ok(Some(created.room_id))
     }
 
-    pub fn player_trade(
+    /// Start over every demo room whose round has run for `reset_interval`.
+    /// Returns how many were reset.
+    pub fn reset_demo_rooms(
         &self,
-        room_id: Uuid,
-        player_id: Uuid,
-        request: TradeRequest,
-    ) -> Result<PlayerTradeResponse, String> {
-        let mut rooms = self
-            .rooms
-            .lock()
-            .map_err(|_| "Failed to acquire rooms lock")?;
-        let room = rooms.get_mut(&room_id).ok_or("Room not found")?;
-
-        // Get trade information before mutable borrows
-        let (
-            _cargo_price,
-            transaction_amount,
-            can_afford,
-            cargo_weight_per_unit,
-            current_cargo_quantity,
-        ) = {
-            let player_state = room
-                .get_player(&player_id)
-                .ok_or("Player not found in room")?;
-            let current_market = room
-                .get_current_market(&player_state.player.current_airport)
-                .ok_or("No market available at current location")?;
-
-            let cargo_price = current_market
-                .get_cargo_price(&request.cargo_type)
-                .ok_or("Cargo type not available at this market")?;
-            let transaction_amount = cargo_price * request.quantity;
-            let can_afford = player_state.player.can_afford(transaction_amount);
-
-            let cargo_type = room
-                .shared_state
-                .cargo_types
-                .get(&request.cargo_type)
-                .ok_or("Invalid cargo type")?;
-            let cargo_weight_per_unit = cargo_type.weight_per_unit;
-            let current_cargo_quantity = player_state
-                .player
-                .cargo_inventory
-                .get_quantity(&request.cargo_type);
+        reset_interval: chrono::Duration,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, ServiceError> {
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
 
-            (
-                cargo_price,
-                transaction_amount,
-                can_afford,
-                cargo_weight_per_unit,
-                current_cargo_quantity,
-            )
+        let mut reset = 0;
+        for room in rooms.values_mut() {
+            if room
+                .demo
+                .as_ref()
+                .is_none_or(|demo| now - demo.reset_at < reset_interval)
+            {
+                continue;
+            }
+            room.reset_demo(now);
+            self.save_room(room);
+            reset += 1;
+        }
+        Ok(reset)
+    }
+
+    /// Every bot in every demo room takes a turn. Bots go through the same
+    /// actions as people, so the room's rules hold for them too. Returns
+    /// how many bots flew.
+    pub fn play_demo_bots(&self) -> Result<usize, ServiceError> {
+        let demo_rooms: Vec<(Uuid, DemoRoom)> = {
+            let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+            rooms
+                .values()
+                .filter_map(|room| Some((room.id, room.demo.clone()?)))
+                .collect()
         };
 
-        match request.action {
-            TradeAction::Buy => {
-                // Check if player can afford
-                if !can_afford {
-                    return Ok(PlayerTradeResponse {
-                        success: false,
-                        message: "Insufficient funds".to_string(),
-                        transaction_amount: None,
-                        new_money: None,
-                        new_inventory: None,
-                    });
+        let mut flown = 0;
+        for (room_id, demo) in demo_rooms {
+            for bot_id in demo.bots {
+                let turn = {
+                    let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+                    let Some(room) = rooms.get_mut(&room_id) else {
+                        break;
+                    };
+                    if room.is_finished() {
+                        break;
+                    }
+                    room.update_player_activity(&bot_id);
+                    BotTurn::plan(room, &bot_id, &mut rand::thread_rng())
+                };
+                if let Some(turn) = turn
+                    && self.play_bot_turn(room_id, bot_id, turn)
+                {
+                    flown += 1;
                 }
+            }
+        }
+        Ok(flown)
+    }
 
-                // Check cargo capacity
-                let can_carry = {
-                    let player_state = room.get_player(&player_id).unwrap();
-                    let additional_weight = cargo_weight_per_unit * request.quantity;
-                    player_state
-                        .player
-                        .can_carry_more_weight(additional_weight, &room.shared_state.cargo_types)
-                };
+    /// Whether the bot made it into the air. A step that fails, say because
+    /// prices moved, is skipped and the bot carries on with the rest.
+    fn play_bot_turn(&self, room_id: Uuid, bot_id: Uuid, turn: BotTurn) -> bool {
+        let trade = |trade: BotTrade, action: TradeAction| {
+            let request = TradeRequest {
+                cargo_type: trade.cargo_id,
+                quantity: trade.quantity,
+                action,
+            };
+            let _ = self.player_trade(room_id, bot_id, request);
+        };
+        for sale in turn.sell {
+            trade(sale, TradeAction::Sell);
+        }
+        if turn.fuel > 0 {
+            let _ = self.player_buy_fuel(
+                room_id,
+                bot_id,
+                FuelRequest {
+                    quantity: turn.fuel,
+                },
+            );
+        }
+        if let Some(purchase) = turn.buy {
+            trade(purchase, TradeAction::Buy);
+        }
+        self.player_travel(room_id, bot_id, turn.destination)
+            .is_ok_and(|travel| travel.success)
+    }
 
-                if !can_carry {
-                    return Ok(PlayerTradeResponse {
-                        success: false,
-                        message: "Insufficient cargo capacity".to_string(),
-                        transaction_amount: None,
-                        new_money: None,
-                        new_inventory: None,
-                    });
+    /// The demo room job: open the room if the server is empty, start it
+    /// over when it's due and give every bot a turn. Returns how many bots
+    /// flew.
+    pub fn run_demo_room(
+        &self,
+        policy: &DemoRoomPolicy,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, ServiceError> {
+        if let Some(room_id) = self.ensure_demo_room(policy)? {
+            tracing::info!("Opened demo room {} with {} bots", room_id, policy.bots);
+        }
+        self.reset_demo_rooms(policy.reset_interval, now)?;
+        self.play_demo_bots()
+    }
+
+    pub fn get_room_state(
+        &self,
+        room_id: Uuid,
+        requesting_player_id: Uuid,
+    ) -> Result<MultiplayerGameStateResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let _room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        // Update player activity
+        drop(rooms);
+        {
+            let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+            if let Some(room) = rooms.get_mut(&room_id) {
+                room.update_player_activity(&requesting_player_id);
+            }
+        }
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        // Verify player is in room
+        if !room.players.contains_key(&requesting_player_id) {
+            return Err(ServiceError::NotFound(ErrorCode::PlayerNotInRoom));
+        }
+
+        self.build_multiplayer_game_state_response(room, requesting_player_id)
+    }
+
+    /// Run a player action at most once per idempotency key. A replayed key
+    /// gets the original response back instead of repeating the action, and
+    /// one replayed while the original is still running waits for it without
+    /// holding up a runtime thread.
+    pub async fn with_idempotency_key<T, F>(
+        &self,
+        player_id: Uuid,
+        idempotency_key: Option<Uuid>,
+        action: F,
+    ) -> Result<T, ServiceError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Result<T, ServiceError>,
+    {
+        let Some(key) = idempotency_key else {
+            return action();
+        };
+        let cache_key = (player_id, key);
+        let cache = &*self.idempotency_cache;
+
+        let settled = loop {
+            let mut in_flight = {
+                let mut actions = cache.lock()?;
+                match actions.actions.get(&cache_key) {
+                    Some(CachedAction::Done(cached)) => {
+                        return serde_json::from_value(cached.clone()).map_err(|e| {
+                            ServiceError::Internal(format!(
+                                "Failed to replay cached response: {}",
+                                e
+                            ))
+                        });
+                    },
+                    Some(CachedAction::InFlight(in_flight)) => in_flight.clone(),
+                    None => {
+                        let (settled, in_flight) = watch::channel(());
+                        actions
+                            .actions
+                            .insert(cache_key, CachedAction::InFlight(in_flight));
+                        break settled;
+                    },
                 }
+            };
+            // Only ever closes, once the original settles
+            let _ = in_flight.changed().await;
+        };
 
-                // Execute purchase
-                let (new_money, new_inventory) = {
-                    let player_state = room
-                        .get_player_mut(&player_id)
-                        .ok_or("Player not found in room")?;
-                    player_state.player.spend_money(transaction_amount);
-                    player_state
-                        .player
-                        .cargo_inventory
-                        .add_cargo(&request.cargo_type, request.quantity);
-                    let new_money = player_state.player.money;
-                    let new_inventory = self.build_inventory_map(&player_state.player);
-                    (new_money, new_inventory)
-                };
+        let in_flight = InFlightKey {
+            cache,
+            key: cache_key,
+            _settled: settled,
+        };
+        let response = action()?;
+        in_flight.finish(serde_json::to_value(&response).ok());
+        Ok(response)
+    }
+
+    /// Run `player_id`'s action as one unit, saving the room only if the
+    /// action succeeds. An action that fails, or reports that it didn't go
+    /// ahead, has the player, their statistics and their airport's market
+    /// put back (see `GameRoom::checkpoint`), so one made of several steps
+    /// can't leave them half-applied. The one thing a failed action keeps is
+    /// its place in the action cooldown, so refused actions can't be spammed
+    /// any faster than good ones. The rooms lock is held throughout,
+    /// including for the save.
+    fn transact<T, F>(&self, room_id: Uuid, player_id: Uuid, action: F) -> Result<T, ServiceError>
+    where
+        T: ActionOutcome,
+        F: FnOnce(&mut GameRoom) -> Result<T, ServiceError>,
+    {
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get_mut(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        let checkpoint = room.checkpoint(&player_id);
+        let result = action(room);
+        match &result {
+            Ok(outcome) if outcome.succeeded() => {
+                room.record_turns();
+                self.record_achievements(room);
+                self.record_results(room);
+                self.save_room(room);
+            },
+            _ => room.restore(checkpoint),
+        }
+        result
+    }
+
+    /// Put anything players have newly earned in the room on their profiles
+    fn record_achievements(&self, room: &mut GameRoom) {
+        let earned = room.take_new_achievements();
+        if earned.is_empty() {
+            return;
+        }
 
-                // Update statistics
-                if let Some(stats) = room.player_statistics.get_mut(&player_id) {
-                    stats.record_cargo_purchase(transaction_amount);
+        if let Ok(db) = self.db.lock() {
+            for (pilot_name, achievement) in earned {
+                let mut profile = db
+                    .load_pilot_profile(&pilot_name)
+                    .ok()
+                    .flatten()
+                    .unwrap_or_else(|| PilotProfile::new(pilot_name));
+                if profile.unlock(achievement, &room.name) {
+                    let _ = db.save_pilot_profile(&profile);
                 }
+            }
+        }
+    }
 
-                // Save room state after buying cargo
-                if let Ok(rooms) = self.rooms.lock()
-                    && let Some(room) = rooms.get(&room_id)
-                {
-                    self.save_room(room);
+    /// Count a won room towards its season's rankings
+    fn record_results(&self, room: &GameRoom) {
+        if !room.is_finished() {
+            return;
+        }
+
+        let results = GameResult::for_room(room);
+        if let Ok(db) = self.db.lock() {
+            let _ = db.record_game_results(&results);
+        }
+    }
+
+    /// A page of a season's rankings, the current season unless one is named
+    pub fn get_rankings(&self, query: RankingsQuery) -> Result<RankingsResponse, ServiceError> {
+        let page = query.page.unwrap_or(1);
+        if page == 0 {
+            return Err("Pages are counted from 1".into());
+        }
+        let per_page = query.per_page.unwrap_or(DEFAULT_RANKINGS_PAGE_SIZE);
+        if per_page == 0 || per_page > MAX_RANKINGS_PAGE_SIZE {
+            return Err(
+                format!("per_page must be between 1 and {}", MAX_RANKINGS_PAGE_SIZE).into(),
+            );
+        }
+        let season = query
+            .season
+            .unwrap_or_else(|| season_for(chrono::Utc::now()));
+
+        let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
+        let (entries, total) = db
+            .load_season_rankings(&season, (page - 1) * per_page, per_page)
+            .map_err(ServiceError::database)?;
+        let seasons = db.list_ranked_seasons().map_err(ServiceError::database)?;
+
+        Ok(RankingsResponse {
+            season,
+            seasons,
+            page,
+            per_page,
+            total,
+            entries,
+        })
+    }
+
+    fn load_pilot_profile(&self, pilot_name: &str) -> Result<PilotProfile, ServiceError> {
+        let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
+        let profile = db
+            .load_pilot_profile(pilot_name)
+            .map_err(ServiceError::database)?;
+        Ok(profile.unwrap_or_else(|| PilotProfile::new(pilot_name.to_string())))
+    }
+
+    /// A pilot's achievements across every room they've played. Pilots who
+    /// haven't earned anything yet still have an empty logbook.
+    pub fn get_pilot_profile(
+        &self,
+        pilot_name: &str,
+    ) -> Result<PilotProfileResponse, ServiceError> {
+        let profile = self.load_pilot_profile(pilot_name)?;
+
+        let mut achievements: Vec<AchievementInfo> = Achievement::ALL
+            .into_iter()
+            .map(|achievement| {
+                let unlocked = profile
+                    .achievements
+                    .iter()
+                    .find(|unlocked| unlocked.achievement == achievement);
+                AchievementInfo {
+                    achievement,
+                    title: achievement.title().to_string(),
+                    description: achievement.description().to_string(),
+                    unlocked_at: unlocked.map(|unlocked| unlocked.unlocked_at),
+                    room_name: unlocked.map(|unlocked| unlocked.room_name.clone()),
                 }
+            })
+            .collect();
+        achievements.sort_by_key(|info| info.unlocked_at.is_none());
 
-                Ok(PlayerTradeResponse {
-                    success: true,
-                    message: format!(
-                        "Successfully bought {} units of {}",
-                        request.quantity, request.cargo_type
-                    ),
-                    transaction_amount: Some(transaction_amount),
-                    new_money: Some(new_money),
-                    new_inventory: Some(new_inventory),
-                })
-            },
-            TradeAction::Sell => {
-                // Check if player has enough cargo
-                if current_cargo_quantity < request.quantity {
+        Ok(PilotProfileResponse {
+            pilot_name: profile.pilot_name,
+            unlocked: profile.achievements.len(),
+            achievements,
+            quantity_defaults: profile.quantity_defaults,
+            loadouts: profile.loadouts.all().to_vec(),
+        })
+    }
+
+    /// Save the fuel and trade quantities a pilot's clients start at
+    pub fn set_quantity_defaults(
+        &self,
+        pilot_name: &str,
+        defaults: QuantityDefaults,
+    ) -> Result<PilotProfileResponse, ServiceError> {
+        defaults.validate()?;
+        let mut profile = self.load_pilot_profile(pilot_name)?;
+        profile.quantity_defaults = defaults;
+        self.save_pilot_profile(&profile)?;
+        self.get_pilot_profile(pilot_name)
+    }
+
+    /// Keep a cargo load-out on the pilot's profile, replacing any with the
+    /// same name
+    pub fn save_loadout(
+        &self,
+        pilot_name: &str,
+        loadout: CargoLoadout,
+    ) -> Result<PilotProfileResponse, ServiceError> {
+        let mut profile = self.load_pilot_profile(pilot_name)?;
+        profile.loadouts.save(loadout)?;
+        self.save_pilot_profile(&profile)?;
+        self.get_pilot_profile(pilot_name)
+    }
+
+    pub fn delete_loadout(
+        &self,
+        pilot_name: &str,
+        name: &str,
+    ) -> Result<PilotProfileResponse, ServiceError> {
+        let mut profile = self.load_pilot_profile(pilot_name)?;
+        if !profile.loadouts.remove(name) {
+            return Err(format!("No load-out called '{}'", name.trim()).into());
+        }
+        self.save_pilot_profile(&profile)?;
+        self.get_pilot_profile(pilot_name)
+    }
+
+    fn save_pilot_profile(&self, profile: &PilotProfile) -> Result<(), ServiceError> {
+        self.db
+            .lock()
+            .map_err(|_| ServiceError::lock("database"))?
+            .save_pilot_profile(profile)
+            .map_err(ServiceError::database)
+    }
+
+    pub fn player_travel(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        destination: String,
+    ) -> Result<PlayerTravelResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            room.start_action(&player_id, std::time::Instant::now())?;
+
+            // Get necessary information before mutable borrows
+            let destination_airport_name = room
+                .shared_state
+                .airports
+                .get(&destination)
+                .ok_or_else(|| LocalizedMessage::new(MessageCode::DestinationNotFound))?
+                .name
+                .clone();
+
+            // Charge exactly what the preview endpoint quotes
+            let quote = Self::quote_travel(room, &player_id, &destination)?;
+            if !quote.can_travel {
+                return Ok(PlayerTravelResponse {
+                    success: false,
+                    localized: quote.localized,
+                    message: quote.summary,
+                    fuel_consumed: None,
+                    new_location: None,
+                    advanced_turn: false,
+                    auto_refuel: None,
+                });
+            }
+            let distance = quote.distance;
+            let fuel_required = quote.total_fuel;
+            let holding = room.slot_status(&destination) == SlotStatus::Holding;
+
+            // Note the prices we're leaving behind before we go
+            room.observe_market(&player_id);
+
+            // Perform travel
+            let origin = {
+                let player_state = room
+                    .get_player_mut(&player_id)
+                    .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+                player_state.player.consume_fuel(fuel_required);
+                player_state.player.spend_money(quote.total_money);
+                std::mem::replace(
+                    &mut player_state.player.current_airport,
+                    destination.clone(),
+                )
+            };
+
+            // Advance this player's turn (and the world tick), plus one more
+            // spent circling when the hub was full
+            room.book_landing(&destination, holding);
+            let mut turns: Vec<TurnReport> =
+                TurnEngine::finish_in_room(room, &player_id, TurnAction::Fly)
+                    .into_iter()
+                    .collect();
+            if holding {
+                turns.push(room.advance_turn(&player_id));
+            }
+            room.announce_arrival(&player_id);
+            room.observe_market(&player_id);
+            let auto_refuel = room.auto_refuel(&player_id);
+
+            // Cargo may not survive the trip; claims that have finished processing pay out
+            let mut rng = rand::thread_rng();
+            let headline = LocalizedMessage::new(MessageCode::Traveled)
+                .with("airport_name", &destination_airport_name)
+                .with("airport", &destination);
+            let mut message = headline.to_string();
+            if holding {
+                message.push_str(". Held for a turn waiting for a landing slot");
+            }
+            if let Some(receipt) = &auto_refuel {
+                message.push_str(&format!(". {}", receipt.summary()));
+            }
+            if let Some(loss) = room.roll_cargo_loss(&player_id, &mut rng) {
+                message.push_str(&format!(". {} (${} lost", loss.description, loss.value));
+                message.push_str(if loss.insured {
+                    ") - file a claim at the insurance office"
+                } else {
+                    ", uninsured)"
+                });
+            }
+            for turn in &turns {
+                turn.append_to(&mut message);
+            }
+            for claim in room.resolve_claims(&player_id, &mut rng) {
+                message.push_str(&format!(
+                    ". Claim for {} settled: {} (${})",
+                    claim.loss.description, claim.note, claim.payout
+                ));
+            }
+
+            let turns_played = room
+                .get_player(&player_id)
+                .map(|p| p.turn_number)
+                .unwrap_or(1);
+
+            // Update statistics, valuing the fuel burned at the departure airport's price
+            let fuel_price = room
+                .get_current_market(&origin)
+                .map(|m| m.fuel_price)
+                .unwrap_or(0);
+            if let Some(stats) = room.player_statistics.get_mut(&player_id) {
+                stats.record_leg(RouteFuelStats::leg(
+                    &origin,
+                    &destination,
+                    distance,
+                    fuel_required,
+                    fuel_price,
+                ));
+                stats.record_travel_cost(quote.total_money);
+                stats.calculate_efficiency(turns_played);
+            }
+
+            Ok(PlayerTravelResponse {
+                success: true,
+                localized: Some(headline.leading(&message)),
+                message,
+                fuel_consumed: Some(fuel_required),
+                new_location: Some(destination),
+                advanced_turn: !turns.is_empty(),
+                auto_refuel,
+            })
+        })
+    }
+
+    /// Move to a nearby airport by road. Costs money rather than fuel and
+    /// takes a turn; nothing is flown, so there's no insurance premium and
+    /// no cargo loss roll.
+    pub fn player_ground_transfer(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        destination: String,
+    ) -> Result<PlayerTravelResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            room.start_action(&player_id, std::time::Instant::now())?;
+
+            let quote = Self::quote_ground_transfer(room, &player_id, &destination)?;
+            if !quote.can_travel {
+                return Ok(PlayerTravelResponse {
+                    success: false,
+                    localized: quote.localized,
+                    message: quote.summary,
+                    fuel_consumed: None,
+                    new_location: None,
+                    advanced_turn: false,
+                    auto_refuel: None,
+                });
+            }
+            let destination_airport_name = room.shared_state.airports[&destination].name.clone();
+
+            room.observe_market(&player_id);
+            {
+                let player_state = room
+                    .get_player_mut(&player_id)
+                    .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+                player_state.player.spend_money(quote.total_money);
+                player_state.player.current_airport = destination.clone();
+            }
+            if let Some(stats) = room.player_statistics.get_mut(&player_id) {
+                stats.record_travel_cost(quote.total_money);
+            }
+            let turn = TurnEngine::finish_in_room(room, &player_id, TurnAction::GroundTransfer);
+            room.announce_arrival(&player_id);
+            room.observe_market(&player_id);
+            let auto_refuel = room.auto_refuel(&player_id);
+
+            let headline = LocalizedMessage::new(MessageCode::GroundTransfer)
+                .with("airport_name", &destination_airport_name)
+                .with("airport", &destination)
+                .with("fare", quote.total_money);
+            let mut message = headline.to_string();
+            if let Some(receipt) = &auto_refuel {
+                message.push_str(&format!(". {}", receipt.summary()));
+            }
+            if let Some(turn) = &turn {
+                turn.append_to(&mut message);
+            }
+            for claim in room.resolve_claims(&player_id, &mut rand::thread_rng()) {
+                message.push_str(&format!(
+                    ". Claim for {} settled: {} (${})",
+                    claim.loss.description, claim.note, claim.payout
+                ));
+            }
+
+            Ok(PlayerTravelResponse {
+                success: true,
+                localized: Some(headline.leading(&message)),
+                message,
+                fuel_consumed: Some(0),
+                new_location: Some(destination),
+                advanced_turn: turn.is_some(),
+                auto_refuel,
+            })
+        })
+    }
+
+    fn quote_ground_transfer(
+        room: &GameRoom,
+        player_id: &Uuid,
+        destination: &str,
+    ) -> Result<TravelQuote, ServiceError> {
+        let player_state = room
+            .get_player(player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+        let destination_airport = room
+            .shared_state
+            .airports
+            .get(destination)
+            .ok_or_else(|| LocalizedMessage::new(MessageCode::DestinationNotFound))?;
+        let current_airport = room
+            .shared_state
+            .airports
+            .get(&player_state.player.current_airport)
+            .ok_or("Current airport not found")?;
+
+        TravelSystem::ground_transfer_quote(
+            &player_state.player,
+            current_airport,
+            destination_airport,
+        )
+        .map_err(|e| match e {
+            TravelError::SameLocation => "Already at that airport".into(),
+            _ => format!(
+                "{} is too far for a ground transfer (over {:.0} km)",
+                destination, GROUND_TRANSFER_MAX_KM
+            )
+            .into(),
+        })
+    }
+
+    /// Itemized cost of flying to a destination, without flying there.
+    pub fn travel_preview(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        destination: String,
+    ) -> Result<TravelQuote, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        Self::quote_travel(room, &player_id, &destination)
+    }
+
+    fn quote_travel(
+        room: &GameRoom,
+        player_id: &Uuid,
+        destination: &str,
+    ) -> Result<TravelQuote, ServiceError> {
+        let player_state = room
+            .get_player(player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+        let destination_airport = room
+            .shared_state
+            .airports
+            .get(destination)
+            .ok_or_else(|| LocalizedMessage::new(MessageCode::DestinationNotFound))?;
+        let current_airport = room
+            .shared_state
+            .airports
+            .get(&player_state.player.current_airport)
+            .ok_or("Current airport not found")?;
+        let fuel_price = room
+            .get_current_market(&current_airport.id)
+            .map(|m| m.fuel_price)
+            .unwrap_or(0);
+
+        let mut quote = TravelSystem::quote_with_extras(
+            &player_state.player,
+            current_airport,
+            destination_airport,
+            fuel_price,
+            room.flight_conditions(&player_state.player, &current_airport.id, destination),
+            room.flight_extras(player_id, destination),
+        );
+        if !room.slot_status(destination).can_land() {
+            quote.can_travel = false;
+            quote.set_summary(
+                LocalizedMessage::new(MessageCode::NoLandingSlots).with("airport", destination),
+            );
+        }
+        Ok(quote)
+    }
+
+    pub fn player_trade(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: TradeRequest,
+    ) -> Result<PlayerTradeResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            room.start_action(&player_id, std::time::Instant::now())?;
+
+            let quote = match trade_in_room(room, &player_id, &request)? {
+                Ok(quote) => quote,
+                Err(TradingError::CargoNotAvailable) => {
+                    return Err(TradingError::CargoNotAvailable.into());
+                },
+                Err(error) => {
                     return Ok(PlayerTradeResponse {
                         success: false,
-                        message: "Insufficient cargo to sell".to_string(),
+                        message: error.to_string(),
+                        localized: Some(error.localized()),
                         transaction_amount: None,
+                        fee: None,
                         new_money: None,
                         new_inventory: None,
+                        advanced_turn: false,
                     });
-                }
+                },
+            };
+            let player = &room
+                .players
+                .get(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?
+                .player;
+            let new_money = player.money;
+            let new_inventory = self.build_inventory_map(player, &room.shared_state.cargo_types);
 
-                // Execute sale
-                let (new_money, new_inventory) = {
-                    let player_state = room
-                        .get_player_mut(&player_id)
-                        .ok_or("Player not found in room")?;
-                    player_state
-                        .player
-                        .cargo_inventory
-                        .remove_cargo(&request.cargo_type, request.quantity);
-                    player_state.player.earn_money(transaction_amount);
-                    let new_money = player_state.player.money;
-                    let new_inventory = self.build_inventory_map(&player_state.player);
-                    (new_money, new_inventory)
-                };
+            // Selling is the only way money goes up, so this is where games end
+            let code = match request.action {
+                TradeAction::Buy => MessageCode::Bought,
+                TradeAction::Sell if room.check_for_winner(&player_id) => MessageCode::SoldAndWon,
+                TradeAction::Sell => MessageCode::Sold,
+            };
+            let localized = LocalizedMessage::new(code)
+                .with("quantity", request.quantity)
+                .with("cargo", &request.cargo_type)
+                .with("fee", quote.fee)
+                .with("money", new_money);
 
-                // Update statistics
-                if let Some(stats) = room.player_statistics.get_mut(&player_id) {
-                    stats.record_sale(&request.cargo_type, transaction_amount);
-                }
+            let advanced_turn =
+                TurnEngine::finish_in_room(room, &player_id, TurnAction::Trade).is_some();
 
-                // Save room state after selling cargo
-                if let Ok(rooms) = self.rooms.lock()
-                    && let Some(room) = rooms.get(&room_id)
-                {
-                    self.save_room(room);
-                }
+            Ok(PlayerTradeResponse {
+                success: true,
+                message: localized.to_string(),
+                localized: Some(localized),
+                transaction_amount: Some(quote.gross),
+                fee: Some(quote.fee),
+                new_money: Some(new_money),
+                new_inventory: Some(new_inventory),
+                advanced_turn,
+            })
+        })
+    }
 
-                Ok(PlayerTradeResponse {
-                    success: true,
-                    message: format!(
-                        "Successfully sold {} units of {}",
-                        request.quantity, request.cargo_type
-                    ),
-                    transaction_amount: Some(transaction_amount),
-                    new_money: Some(new_money),
-                    new_inventory: Some(new_inventory),
-                })
-            },
-        }
+    /// Make several trades as one action. They're made in order, and if
+    /// any of them can't be, none of them are.
+    pub fn player_trade_batch(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: TradeBatchRequest,
+    ) -> Result<PlayerTradeBatchResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            room.start_action(&player_id, std::time::Instant::now())?;
+            self.trade_batch_in_room(room, &player_id, request.trades)
+        })
     }
 
-    pub fn player_buy_fuel(
+    /// Buy and sell at the player's airport until their hold matches one
+    /// of their saved load-outs, all at once or not at all
+    pub fn apply_loadout(
         &self,
         room_id: Uuid,
         player_id: Uuid,
-        request: FuelRequest,
-    ) -> Result<PlayerFuelResponse, String> {
-        let mut rooms = self
-            .rooms
-            .lock()
-            .map_err(|_| "Failed to acquire rooms lock")?;
-        let room = rooms.get_mut(&room_id).ok_or("Room not found")?;
+        request: ApplyLoadoutRequest,
+    ) -> Result<PlayerTradeBatchResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            let player_state = room
+                .get_player(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            let profile = self.load_pilot_profile(&player_state.player_name)?;
+            let loadout = profile
+                .loadouts
+                .get(&request.name)
+                .ok_or_else(|| format!("No load-out called '{}'", request.name.trim()))?;
+            room.start_action(&player_id, std::time::Instant::now())?;
 
-        // Get fuel cost and check constraints before mutable borrows
-        let (fuel_cost, can_afford, space_available) = {
             let player_state = room
                 .get_player(&player_id)
-                .ok_or("Player not found in room")?;
-            let current_market = room
-                .get_current_market(&player_state.player.current_airport)
-                .ok_or("No market available at current location")?;
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            let plan = loadout.plan(&player_state.player.cargo_inventory);
+            if plan.is_empty() {
+                return Ok(PlayerTradeBatchResponse {
+                    message: format!("Your hold already matches '{}'", loadout.name),
+                    localized: None,
+                    trades: Vec::new(),
+                    net_amount: 0,
+                    fees: 0,
+                    new_money: player_state.player.money,
+                    new_inventory: self
+                        .build_inventory_map(&player_state.player, &room.shared_state.cargo_types),
+                    advanced_turn: false,
+                });
+            }
+            let sells = plan.sell.into_iter().map(|trade| TradeRequest {
+                cargo_type: trade.cargo_id,
+                quantity: trade.quantity,
+                action: TradeAction::Sell,
+            });
+            let buys = plan.buy.into_iter().map(|trade| TradeRequest {
+                cargo_type: trade.cargo_id,
+                quantity: trade.quantity,
+                action: TradeAction::Buy,
+            });
+            let mut response =
+                self.trade_batch_in_room(room, &player_id, sells.chain(buys).collect())?;
+            response.message = format!("Loaded '{}': {}", loadout.name, response.message);
+            response.localized = None;
+            Ok(response)
+        })
+    }
+
+    fn trade_batch_in_room(
+        &self,
+        room: &mut GameRoom,
+        player_id: &Uuid,
+        trades: Vec<TradeRequest>,
+    ) -> Result<PlayerTradeBatchResponse, ServiceError> {
+        if trades.len() > MAX_BATCH_TRADES {
+            return Err(format!("At most {} trades can be made at once", MAX_BATCH_TRADES).into());
+        }
+
+        // A later trade failing fails the batch, and transact puts the
+        // earlier ones back
+        let totals = make_trades(room, player_id, &trades)?;
 
-            let fuel_cost = current_market.fuel_price * request.quantity;
-            let can_afford = player_state.player.can_afford(fuel_cost);
-            let space_available = player_state.player.max_fuel - player_state.player.fuel;
+        let player = &room
+            .players
+            .get(player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?
+            .player;
+        let new_money = player.money;
+        let new_inventory = self.build_inventory_map(player, &room.shared_state.cargo_types);
 
-            (fuel_cost, can_afford, space_available)
+        let code = if totals.sold && room.check_for_winner(player_id) {
+            MessageCode::TradesMadeAndWon
+        } else {
+            MessageCode::TradesMade
         };
+        let localized = LocalizedMessage::new(code)
+            .with("count", trades.len())
+            .with("fees", totals.fees)
+            .with("money", new_money);
 
-        // Check if player can afford
-        if !can_afford {
-            return Ok(PlayerFuelResponse {
-                success: false,
-                message: "Insufficient funds for fuel purchase".to_string(),
-                cost: None,
-                new_fuel: None,
-                new_money: None,
-            });
-        }
+        // However many trades, the batch is one action
+        let advanced_turn =
+            TurnEngine::finish_in_room(room, player_id, TurnAction::Trade).is_some();
 
-        // Check if fuel tank has capacity
-        if request.quantity > space_available {
-            return Ok(PlayerFuelResponse {
-                success: false,
-                message: format!("Fuel tank can only hold {} more units", space_available),
-                cost: None,
-                new_fuel: None,
-                new_money: None,
-            });
-        }
+        Ok(PlayerTradeBatchResponse {
+            message: localized.to_string(),
+            localized: Some(localized),
+            trades,
+            net_amount: totals.net_amount,
+            fees: totals.fees,
+            new_money,
+            new_inventory,
+            advanced_turn,
+        })
+    }
 
-        // Execute fuel purchase
-        let (new_fuel, new_money) = {
-            let player_state = room
-                .get_player_mut(&player_id)
-                .ok_or("Player not found in room")?;
-            player_state.player.spend_money(fuel_cost);
-            player_state.player.add_fuel(request.quantity);
-            (player_state.player.fuel, player_state.player.money)
-        };
+    pub fn player_buy_fuel(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: FuelRequest,
+    ) -> Result<PlayerFuelResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            room.start_action(&player_id, std::time::Instant::now())?;
+
+            // Get fuel cost and check constraints before mutable borrows
+            let (airport_id, fuel_cost, can_afford, space_available, in_stock) = {
+                let player_state = room
+                    .get_player(&player_id)
+                    .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+                let current_market = room
+                    .get_current_market(&player_state.player.current_airport)
+                    .ok_or("No market available at current location")?;
+
+                // Too big to price means too big for anyone's wallet
+                let fuel_cost =
+                    TradingSystem::price_of(current_market.fuel_price, request.quantity);
+                let can_afford = fuel_cost
+                    .as_ref()
+                    .is_ok_and(|cost| player_state.player.can_afford(*cost));
+                let space_available = player_state
+                    .player
+                    .max_fuel
+                    .saturating_sub(player_state.player.fuel);
+
+                (
+                    player_state.player.current_airport.clone(),
+                    fuel_cost,
+                    can_afford,
+                    space_available,
+                    current_market.fuel_available(),
+                )
+            };
+
+            // Check the airport has the fuel
+            if request.quantity > in_stock {
+                let localized = match room.fuel_outage_at(&airport_id) {
+                    Some(outage) => LocalizedMessage::new(MessageCode::FuelOutage)
+                        .with("airport", &airport_id)
+                        .with("turns", outage.turns_remaining),
+                    None if in_stock == 0 => LocalizedMessage::new(MessageCode::FuelUnavailable),
+                    None => {
+                        LocalizedMessage::new(MessageCode::FuelLowStock).with("quantity", in_stock)
+                    },
+                };
+                return Ok(PlayerFuelResponse {
+                    success: false,
+                    message: localized.to_string(),
+                    localized: Some(localized),
+                    cost: None,
+                    new_fuel: None,
+                    new_money: None,
+                    advanced_turn: false,
+                });
+            }
+
+            // Check if player can afford
+            if !can_afford {
+                let localized = LocalizedMessage::new(MessageCode::FuelUnaffordable);
+                return Ok(PlayerFuelResponse {
+                    success: false,
+                    message: localized.to_string(),
+                    localized: Some(localized),
+                    cost: None,
+                    new_fuel: None,
+                    new_money: None,
+                    advanced_turn: false,
+                });
+            }
+
+            // Check if fuel tank has capacity
+            if request.quantity > space_available {
+                let localized =
+                    LocalizedMessage::new(MessageCode::FuelTankFull).with("space", space_available);
+                return Ok(PlayerFuelResponse {
+                    success: false,
+                    message: localized.to_string(),
+                    localized: Some(localized),
+                    cost: None,
+                    new_fuel: None,
+                    new_money: None,
+                    advanced_turn: false,
+                });
+            }
+
+            let fuel_cost = fuel_cost.map_err(|e| e.to_string())?;
+
+            // Execute fuel purchase
+            let (new_fuel, new_money) = {
+                let player_state = room
+                    .get_player_mut(&player_id)
+                    .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+                player_state.player.spend_money(fuel_cost);
+                player_state.player.add_fuel(request.quantity);
+                (player_state.player.fuel, player_state.player.money)
+            };
+            if let Some(market) = room.shared_state.markets.get_mut(&airport_id) {
+                market.draw_fuel(request.quantity);
+            }
+
+            // Update statistics
+            if let Some(stats) = room.player_statistics.get_mut(&player_id) {
+                stats.record_fuel_purchase(request.quantity, fuel_cost);
+            }
+            let advanced_turn =
+                TurnEngine::finish_in_room(room, &player_id, TurnAction::BuyFuel).is_some();
+
+            let localized = LocalizedMessage::new(MessageCode::FuelPurchased)
+                .with("quantity", request.quantity)
+                .with("cost", fuel_cost);
+            Ok(PlayerFuelResponse {
+                success: true,
+                message: localized.to_string(),
+                localized: Some(localized),
+                cost: Some(fuel_cost),
+                new_fuel: Some(new_fuel),
+                new_money: Some(new_money),
+                advanced_turn,
+            })
+        })
+    }
+
+    fn build_multiplayer_game_state_response(
+        &self,
+        room: &GameRoom,
+        requesting_player_id: Uuid,
+    ) -> Result<MultiplayerGameStateResponse, ServiceError> {
+        let requesting_player_state = room
+            .get_player(&requesting_player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+
+        let current_airport = room
+            .shared_state
+            .airports
+            .get(&requesting_player_state.player.current_airport)
+            .ok_or("Current airport not found")?;
+
+        let current_market = room
+            .get_current_market(&requesting_player_state.player.current_airport)
+            .ok_or("Current market not found")?;
+
+        // Build available destinations
+        let mut destinations = Vec::new();
+        for (airport_id, airport) in &room.shared_state.airports {
+            if airport_id != &requesting_player_state.player.current_airport {
+                let quote = Self::quote_travel(room, &requesting_player_id, airport_id)?;
+                let sighting = room.known_market(&requesting_player_id, airport_id);
+
+                destinations.push(DestinationInfo {
+                    airport_id: airport_id.clone(),
+                    airport_name: airport.name.clone(),
+                    distance: quote.distance,
+                    fuel_required: quote.total_fuel,
+                    can_travel: quote.can_travel,
+                    fuel_price: sighting.as_ref().map(|s| s.fuel_price).unwrap_or(0),
+                    fuel_price_observed_at: sighting.map(|s| s.observed_at),
+                    // Outages are news, so they show through fog of war
+                    fuel_outage: room.fuel_outage_at(airport_id).is_some(),
+                    ground_transfer_fare: TravelSystem::ground_transfer_fare(
+                        current_airport,
+                        airport,
+                    ),
+                    slot_status: room.slot_status(airport_id),
+                    reachable_on_full_tank: quote.total_fuel
+                        <= requesting_player_state.player.max_fuel
+                        && room.slot_status(airport_id).can_land(),
+                });
+            }
+        }
+
+        // Prices this player knows about - everywhere unless the room has fog of war
+        let mut airport_ids: Vec<&String> = room.shared_state.airports.keys().collect();
+        airport_ids.sort();
+        let my_turn = requesting_player_state.turn_number;
+        let known_markets = airport_ids
+            .into_iter()
+            .filter_map(|airport_id| {
+                let sighting = room.known_market(&requesting_player_id, airport_id)?;
+                Some(KnownMarketInfo {
+                    airport_id: airport_id.clone(),
+                    airport_name: room.shared_state.airports[airport_id].name.clone(),
+                    fuel_price: sighting.fuel_price,
+                    cargo_prices: sighting.cargo_prices.clone(),
+                    observed_at: sighting.observed_at,
+                    turns_ago: my_turn.saturating_sub(sighting.observed_at),
+                    stale: room.is_stale(&requesting_player_id, &sighting),
+                })
+            })
+            .collect();
+
+        // Build player list (only online players)
+        let players = room
+            .players
+            .values()
+            .filter(|player_state| player_state.is_online)
+            .map(|player_state| PlayerInfo {
+                id: Some(player_state.player_id),
+                name: player_state.player_name.clone(),
+                money: player_state.player.money,
+                current_airport: player_state.player.current_airport.clone(),
+                fuel: player_state.player.fuel,
+                max_fuel: player_state.player.max_fuel,
+                cargo_inventory: self
+                    .build_inventory_map(&player_state.player, &room.shared_state.cargo_types),
+                cargo_weight: player_state
+                    .player
+                    .current_cargo_weight(&room.shared_state.cargo_types),
+                max_cargo_weight: player_state.player.max_cargo_weight,
+                fuel_efficiency: player_state.player.fuel_efficiency,
+                is_online: Some(player_state.is_online),
+                last_seen: Some(player_state.last_seen),
+                is_host: Some(player_state.player_id == room.host_player_id),
+                turn_number: Some(player_state.turn_number),
+                is_bot: room.is_bot(&player_state.player_id),
+                streak: player_state.player.streak.current,
+            })
+            .collect();
+
+        // Get statistics for requesting player
+        let statistics = statistics_info(room, &requesting_player_id);
+
+        Ok(MultiplayerGameStateResponse {
+            room_info: RoomInfo {
+                id: room.id,
+                name: room.name.clone(),
+                host_player_name: room
+                    .players
+                    .get(&room.host_player_id)
+                    .map(|p| p.player_name.clone())
+                    .unwrap_or_else(|| "Unknown".to_string()),
+                current_players: room.players.values().filter(|p| p.is_online).count() as u32,
+                max_players: room.max_players as u32,
+                created_at: room.created_at,
+                game_status: room.game_status.clone(),
+                is_joinable: room.is_joinable(),
+                is_demo: room.demo.is_some(),
+                event_rules: EventRulesInfo::from(&room.settings),
+            },
+            my_player_id: requesting_player_id,
+            players,
+            current_market: MarketInfo {
+                airport_id: current_market.airport_id.clone(),
+                airport_name: current_airport.name.clone(),
+                fuel_price: current_market.fuel_price,
+                fuel_stock: Some(current_market.fuel_available()),
+                fuel_outage: current_market.fuel_outage,
+                broker: Some(
+                    BrokerInfo::for_player(&requesting_player_state.player, current_market)
+                        .with_trade_guards(&room.settings.trade_guards, requesting_player_state),
+                ),
+                cargo_prices: current_market.cargo_prices.clone(),
+                last_updated: current_market.last_updated,
+            },
+            available_destinations: destinations,
+            flight_range: TravelSystem::range(
+                &requesting_player_state.player,
+                room.flight_conditions(
+                    &requesting_player_state.player,
+                    &current_airport.id,
+                    &current_airport.id,
+                ),
+            ),
+            active_events: room
+                .shared_state
+                .active_events
+                .iter()
+                .map(EventInfo::from)
+                .collect(),
+            active_modifiers: room.shared_state.modifiers.clone(),
+            known_markets,
+            statistics,
+            turn_number: room
+                .get_player(&requesting_player_id)
+                .map(|p| p.turn_number)
+                .unwrap_or(1),
+            world_tick: room.shared_state.world_tick,
+            world_time: room.shared_state.world_time,
+            winner: room.winner.clone(),
+            awards: room.awards(),
+            rematch_room_id: room.rematch_room_id,
+            rematch_votes: room
+                .rematch_votes
+                .iter()
+                .filter_map(|voter| room.get_player(voter))
+                .map(|state| state.player_name.clone())
+                .collect(),
+            round: room.round,
+            airports: sorted_by_id(&room.shared_state.airports),
+            cargo_types: sorted_by_id(&room.shared_state.cargo_types),
+            unread_notifications: requesting_player_state.unread_notifications(),
+            notifications: requesting_player_state.unread_digest(),
+            achievements: self
+                .load_pilot_profile(&requesting_player_state.player_name)?
+                .achievements,
+            game_speed: room.settings.game_speed,
+            date: room
+                .settings
+                .calendar
+                .date_label(requesting_player_state.turn_number),
+            calendar: room.settings.calendar,
+        })
+    }
+
+    fn build_inventory_map(
+        &self,
+        player: &crate::models::Player,
+        cargo_types: &HashMap<String, crate::models::CargoType>,
+    ) -> HashMap<String, u32> {
+        let mut inv = HashMap::new();
+        for cargo_id in cargo_types.keys() {
+            let qty = player.cargo_inventory.get_quantity(cargo_id);
+            if qty > 0 {
+                inv.insert(cargo_id.to_string(), qty);
+            }
+        }
+        inv
+    }
+
+    /// Post under the General topic
+    #[allow(dead_code)]
+    pub fn post_message(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        content: String,
+    ) -> Result<PostMessageResponse, ServiceError> {
+        self.post_to_topic(room_id, player_id, content, MessageTopic::General)
+    }
+
+    pub fn post_to_topic(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        content: String,
+        topic: MessageTopic,
+    ) -> Result<PostMessageResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            // Verify player is in the room
+            let player_state = room
+                .players
+                .get(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+
+            let player_name = player_state.player_name.clone();
+            // Players only ever post where they're standing
+            let current_airport = player_state.player.current_airport.clone();
+
+            // Post the message to the board
+            match room.message_board.post_to_topic(
+                player_id,
+                player_name,
+                content,
+                current_airport.clone(),
+                topic,
+            ) {
+                Ok(message) => {
+                    room.notify_mentions(&message);
+                    Ok(PostMessageResponse {
+                        success: true,
+                        message: "Message posted successfully".to_string(),
+                        message_id: Some(message.id),
+                        airport_id: Some(current_airport),
+                    })
+                },
+                Err(error) => Ok(PostMessageResponse {
+                    success: false,
+                    message: error,
+                    message_id: None,
+                    airport_id: None,
+                }),
+            }
+        })
+    }
+
+    /// Courier rates from the player's airport and the letters they've sent
+    pub fn get_courier(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<CourierResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        let player_state = room
+            .get_player(&player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+        let airport_id = player_state.player.current_airport.clone();
+        let world_tick = room.shared_state.world_tick;
+
+        let mut rates: Vec<CourierRateInfo> = room
+            .shared_state
+            .airports
+            .values()
+            .filter(|airport| airport.id != airport_id)
+            .filter_map(|airport| {
+                let quote = room.courier_quote(&airport_id, &airport.id)?;
+                Some(CourierRateInfo {
+                    airport_id: airport.id.clone(),
+                    airport_name: airport.name.clone(),
+                    turns: quote.turns,
+                    fee: quote.fee,
+                })
+            })
+            .collect();
+        rates.sort_by(|a, b| {
+            a.fee
+                .cmp(&b.fee)
+                .then_with(|| a.airport_id.cmp(&b.airport_id))
+        });
+
+        let letters = room
+            .courier_letters_from(&player_id)
+            .into_iter()
+            .map(|letter| CourierLetterInfo::new(letter, world_tick))
+            .collect();
+
+        Ok(CourierResponse {
+            airport_id,
+            world_tick,
+            rates,
+            letters,
+        })
+    }
+
+    /// Pay to have a message posted at another airport a few turns from now
+    pub fn send_courier(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: SendCourierRequest,
+    ) -> Result<SendCourierResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            let letter = room.send_courier(&player_id, &request.destination, request.content)?;
+            let new_money = room
+                .get_player(&player_id)
+                .map(|player_state| player_state.player.money)
+                .unwrap_or(0);
+            let info = CourierLetterInfo::new(&letter, room.shared_state.world_tick);
+            Ok(SendCourierResponse {
+                message: format!(
+                    "Sent to {} for ${}: it goes up on the board in {} turn{}",
+                    letter.to_airport,
+                    letter.fee,
+                    info.turns_remaining,
+                    if info.turns_remaining == 1 { "" } else { "s" }
+                ),
+                letter: info,
+                new_money,
+            })
+        })
+    }
+
+    /// A player's mentions, newest first
+    pub fn get_notifications(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<NotificationsResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        let player_state = room
+            .get_player(&player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+
+        Ok(NotificationsResponse::from(player_state))
+    }
+
+    /// Mark the given notifications read, or all of them when none are given
+    pub fn mark_notifications_read(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        notification_ids: Vec<Uuid>,
+    ) -> Result<NotificationsResponse, ServiceError> {
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get_mut(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        let player_state = room
+            .get_player_mut(&player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+
+        let mut changed = false;
+        for notification in &mut player_state.notifications {
+            if !notification.read
+                && (notification_ids.is_empty() || notification_ids.contains(&notification.id))
+            {
+                notification.read = true;
+                changed = true;
+            }
+        }
+
+        let response = NotificationsResponse::from(&*player_state);
+        if changed {
+            self.save_room(room);
+        }
+        Ok(response)
+    }
+
+    pub fn get_player_statistics(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<StatisticsInfo, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        if !room.players.contains_key(&player_id) {
+            return Err(ServiceError::NotFound(ErrorCode::PlayerNotInRoom));
+        }
+
+        Ok(statistics_info(room, &player_id))
+    }
+
+    /// Turn-by-turn journals for a post-game replay. Players always see
+    /// their own; everyone else's opens up once the game is over.
+    pub fn get_replay(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<ReplayResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        if !room.players.contains_key(&player_id) {
+            return Err(ServiceError::NotFound(ErrorCode::PlayerNotInRoom));
+        }
+
+        let finished = room.game_status == GameStatus::Finished;
+        let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
+        let mut pilots: Vec<PilotReplay> = room
+            .players
+            .values()
+            .filter(|player_state| finished || player_state.player_id == player_id)
+            .map(|player_state| {
+                // Turns past the journal's retention limit are in the archive
+                let mut turns = db
+                    .load_archived_turns(&room_id, &player_state.player_id)
+                    .unwrap_or_default();
+                turns.extend(player_state.journal.iter().cloned());
+                PilotReplay {
+                    player_id: player_state.player_id,
+                    player_name: player_state.player_name.clone(),
+                    turns,
+                }
+            })
+            .collect();
+        // The requesting player first, then everyone else by name
+        pilots.sort_by_key(|pilot| (pilot.player_id != player_id, pilot.player_name.clone()));
+
+        Ok(ReplayResponse { finished, pilots })
+    }
+
+    /// Register where a player's digests for a room should go. The first
+    /// digest covers what happens from now on.
+    pub fn subscribe_digest(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: DigestRequest,
+    ) -> Result<DigestResponse, ServiceError> {
+        let target = match (request.webhook_url, request.email) {
+            (Some(url), None) => DigestTarget::Webhook(url.trim().to_string()),
+            (None, Some(address)) => DigestTarget::Email(address.trim().to_string()),
+            _ => return Err("Give either a webhook_url or an email".into()),
+        };
+
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get_mut(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        room.subscribe_digest(&player_id, target, chrono::Utc::now())?;
+        self.save_room(room);
+        Ok(self.build_digest_response(room, &player_id))
+    }
+
+    pub fn unsubscribe_digest(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<DigestResponse, ServiceError> {
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get_mut(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        let player_state = room
+            .get_player_mut(&player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+        if player_state.digest.take().is_some() {
+            self.save_room(room);
+        }
+        Ok(self.build_digest_response(room, &player_id))
+    }
+
+    /// One run of the summary job: build every digest that's due and deliver
+    /// it. Delivery happens after the rooms lock is released, so a slow
+    /// webhook doesn't hold up play; a failed one is logged and skipped
+    /// until the next period. Returns how many were delivered.
+    pub fn send_digests(
+        &self,
+        sender: &dyn DigestSender,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, ServiceError> {
+        let due = {
+            let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+
+            let mut due = Vec::new();
+            for room in rooms.values_mut() {
+                if !room.players.values().any(|p| p.digest.is_some()) {
+                    continue;
+                }
+                due.extend(room.collect_digests(now, self.digest_period));
+                self.save_room(room);
+            }
+            due
+        };
+
+        let mut sent = 0;
+        for (target, digest) in &due {
+            match sender.send(target, digest) {
+                Ok(()) => sent += 1,
+                Err(e) => tracing::warn!(
+                    "Digest for {} in room {} not delivered: {}",
+                    digest.player_name,
+                    digest.room_id,
+                    e
+                ),
+            }
+        }
+        Ok(sent)
+    }
+
+    /// The host sets the room's turn deadline, or turns it off by leaving
+    /// out the webhook
+    pub fn set_turn_deadline(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: TurnDeadlineRequest,
+    ) -> Result<TurnDeadlineResponse, ServiceError> {
+        let turn_deadline = request.webhook_url.map(|url| TurnDeadline {
+            hours: request.hours.unwrap_or(DEFAULT_TURN_DEADLINE_HOURS),
+            warning_hours: request
+                .warning_hours
+                .unwrap_or(DEFAULT_DEADLINE_WARNING_HOURS),
+            webhook_url: url.trim().to_string(),
+        });
+
+        let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get_mut(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        room.ensure_in_play()?;
+
+        room.set_turn_deadline(&player_id, turn_deadline, chrono::Utc::now())?;
+        self.save_room(room);
+        Ok(TurnDeadlineResponse {
+            turn_deadline: room.settings.turn_deadline.clone(),
+        })
+    }
+
+    /// One run of the deadline job: post every turn deadline warning and
+    /// miss that's come due to its room's webhook. Like digests, delivery
+    /// waits until the rooms lock is released and failures are logged, not
+    /// retried. Returns how many alerts were delivered.
+    pub fn send_deadline_alerts(
+        &self,
+        sender: &dyn WebhookSender,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, ServiceError> {
+        let due = {
+            let mut rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+
+            let mut due = Vec::new();
+            for room in rooms.values_mut() {
+                let alerts = room.collect_deadline_alerts(now);
+                let Some(turn_deadline) = &room.settings.turn_deadline else {
+                    continue;
+                };
+                if alerts.is_empty() {
+                    continue;
+                }
+                let webhook_url = turn_deadline.webhook_url.clone();
+                self.save_room(room);
+                due.extend(alerts.into_iter().map(|alert| (webhook_url.clone(), alert)));
+            }
+            due
+        };
+
+        let mut sent = 0;
+        for (webhook_url, alert) in &due {
+            match sender.post(webhook_url, &alert.payload()) {
+                Ok(()) => sent += 1,
+                Err(e) => tracing::warn!(
+                    "Deadline alert for {} in room {} not delivered: {}",
+                    alert.player_name,
+                    alert.room_id,
+                    e
+                ),
+            }
+        }
+        Ok(sent)
+    }
+
+    fn build_digest_response(&self, room: &GameRoom, player_id: &Uuid) -> DigestResponse {
+        let subscription = room.get_player(player_id).and_then(|p| p.digest.as_ref());
+        DigestResponse {
+            target: subscription.map(|s| s.target.clone()),
+            since: subscription.map(|s| s.last_sent_at),
+            period_hours: self.digest_period.num_hours(),
+        }
+    }
+
+    pub fn get_insurance(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<InsuranceResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        Self::build_insurance_response(room, &player_id)
+    }
+
+    /// Take out or cancel cover. Only flights taken while insured are covered.
+    pub fn set_insurance(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        insured: bool,
+    ) -> Result<InsuranceResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+
+            let player_state = room
+                .get_player_mut(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            player_state.insurance.insured = insured;
+
+            Self::build_insurance_response(room, &player_id)
+        })
+    }
+
+    pub fn get_security(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<SecurityResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        Self::build_security_response(room, &player_id)
+    }
+
+    /// Park in a hangar every night from now on, or stop. The fee is taken
+    /// each night it's used.
+    pub fn set_hangar_parking(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        hangar_parking: bool,
+    ) -> Result<SecurityResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+
+            let player_state = room
+                .get_player_mut(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            player_state.player.hangar_parking = hangar_parking;
+
+            Self::build_security_response(room, &player_id)
+        })
+    }
+
+    /// Fit the security upgrade, which cuts the overnight risk for good
+    pub fn buy_security_upgrade(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<SecurityResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+
+            let player_state = room
+                .get_player_mut(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            SecuritySystem::buy_security_upgrade(&mut player_state.player)?;
+
+            Self::build_security_response(room, &player_id)
+        })
+    }
+
+    fn build_security_response(
+        room: &GameRoom,
+        player_id: &Uuid,
+    ) -> Result<SecurityResponse, ServiceError> {
+        let player = &room
+            .get_player(player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?
+            .player;
+        let airport = room
+            .shared_state
+            .airports
+            .get(&player.current_airport)
+            .ok_or("Current airport not found")?;
+        let overnight_theft = room.settings.overnight_theft;
+
+        Ok(SecurityResponse {
+            airport_id: airport.id.clone(),
+            airport_security: airport.security,
+            theft_chance: if overnight_theft {
+                SecuritySystem::theft_chance(player, airport)
+            } else {
+                0.0
+            },
+            overnight_theft,
+            hangar_parking: player.hangar_parking,
+            hangar_fee: HANGAR_FEE,
+            security_upgrade: player.security_upgrade,
+            security_upgrade_cost: SECURITY_UPGRADE_COST,
+        })
+    }
+
+    pub fn get_hangar(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<HangarResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        Self::build_hangar_response(room, &player_id)
+    }
+
+    /// Fit the next level of an upgrade to the player's aircraft
+    pub fn buy_upgrade(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        upgrade: AircraftUpgrade,
+    ) -> Result<HangarResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+
+            let player_state = room
+                .get_player_mut(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            HangarSystem::buy_upgrade(&mut player_state.player, upgrade)?;
+
+            Self::build_hangar_response(room, &player_id)
+        })
+    }
+
+    /// An airport's recent prices, as `viewer` is allowed to see them
+    pub fn get_price_history(
+        &self,
+        room_id: Uuid,
+        airport_id: String,
+        viewer: Option<Uuid>,
+    ) -> Result<PriceHistoryResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        Ok(PriceHistoryResponse {
+            snapshots: room.price_history(&airport_id, viewer.as_ref())?,
+            airport_id,
+            max_turns: PRICE_HISTORY_TURNS,
+        })
+    }
+
+    /// The vendor stalls at the player's airport
+    pub fn get_stalls(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<VendorStallsResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        let player = &room
+            .get_player(&player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?
+            .player;
+        let world_tick = room.shared_state.world_tick;
+
+        Ok(VendorStallsResponse {
+            airport_id: player.current_airport.clone(),
+            stalls: room
+                .shared_state
+                .vendors
+                .at(&player.current_airport)
+                .into_iter()
+                .map(|stall| StallInfo {
+                    id: stall.id,
+                    vendor: stall.vendor.clone(),
+                    item: stall.item,
+                    description: stall.item.describe(),
+                    price: stall.price,
+                    closes_in_turns: stall.closes_at_tick.saturating_sub(world_tick),
+                })
+                .collect(),
+            money: player.money,
+            vendors_enabled: room.settings.vendor_stalls,
+        })
+    }
+
+    /// Buy the item at one of the stalls where the player is
+    pub fn buy_from_stall(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        stall_id: Uuid,
+    ) -> Result<StallPurchaseResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+
+            let (stall, purchase) = room.buy_from_stall(&player_id, &stall_id)?;
+            let player = &room
+                .get_player(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?
+                .player;
+            Ok(StallPurchaseResponse {
+                message: purchase.report(stall.price),
+                purchase,
+                money: player.money,
+                fuel: player.fuel,
+            })
+        })
+    }
+
+    /// The player's loans and the bank's terms
+    pub fn get_loans(&self, room_id: Uuid, player_id: Uuid) -> Result<LoanResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        Self::build_loan_response(room, &player_id)
+    }
+
+    /// Borrow `amount` from the bank, paid back over the coming turns
+    pub fn borrow(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        amount: u32,
+    ) -> Result<LoanResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+
+            let player_state = room
+                .get_player_mut(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            let turn_number = player_state.turn_number;
+            BankSystem::borrow(&mut player_state.player, amount, turn_number)?;
+
+            Self::build_loan_response(room, &player_id)
+        })
+    }
+
+    /// Pay up to `amount` off a loan ahead of its installments
+    pub fn repay_loan(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        loan_id: u32,
+        amount: u32,
+    ) -> Result<LoanResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+
+            let player_state = room
+                .get_player_mut(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            BankSystem::repay(&mut player_state.player, loan_id, amount)?;
+
+            Self::build_loan_response(room, &player_id)
+        })
+    }
+
+    fn build_loan_response(
+        room: &GameRoom,
+        player_id: &Uuid,
+    ) -> Result<LoanResponse, ServiceError> {
+        let player = &room
+            .get_player(player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?
+            .player;
+
+        Ok(LoanResponse {
+            loans: player.loans.loans.clone(),
+            debt: player.loans.debt(),
+            money: player.money,
+            credit_available: BankSystem::credit_available(&player.loans),
+            bankrupt: player.loans.bankrupt,
+            interest_percent: INTEREST_PERCENT,
+            term_turns: LOAN_TERM_TURNS,
+            min_loan: MIN_LOAN,
+            bankruptcy_debt: BANKRUPTCY_DEBT,
+        })
+    }
+
+    fn build_hangar_response(
+        room: &GameRoom,
+        player_id: &Uuid,
+    ) -> Result<HangarResponse, ServiceError> {
+        let player = &room
+            .get_player(player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?
+            .player;
+
+        Ok(HangarResponse {
+            upgrades: AircraftUpgrade::ALL
+                .iter()
+                .map(|&upgrade| UpgradeInfo {
+                    upgrade,
+                    label: upgrade.label().to_string(),
+                    description: upgrade.description().to_string(),
+                    level: player.aircraft.level(upgrade),
+                    max_level: MAX_UPGRADE_LEVEL,
+                    next_cost: player.aircraft.next_cost(upgrade),
+                })
+                .collect(),
+            money: player.money,
+            max_fuel: player.max_fuel,
+            max_cargo_weight: player.max_cargo_weight,
+            fuel_efficiency: player.fuel_efficiency,
+            cargo_loss_chance: room.settings.cargo_loss_chance * player.aircraft.transit_risk(),
+        })
+    }
+
+    pub fn get_price_alerts(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<PriceAlertsResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        Self::build_price_alerts_response(room, &player_id)
+    }
+
+    /// Change how far above cost held cargo has to be worth before the
+    /// player hears about it
+    pub fn set_price_alerts(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        threshold_percent: u32,
+    ) -> Result<PriceAlertsResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            let player_state = room
+                .get_player_mut(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            player_state.price_alerts.set_threshold(threshold_percent)?;
+
+            Self::build_price_alerts_response(room, &player_id)
+        })
+    }
+
+    pub fn get_auto_refuel(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<AutoRefuel, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+
+        room.get_player(&player_id)
+            .map(|player_state| player_state.auto_refuel)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))
+    }
+
+    /// Change the fuel the player buys on landing, or turn it off
+    pub fn set_auto_refuel(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        auto_refuel: AutoRefuel,
+    ) -> Result<AutoRefuel, ServiceError> {
+        auto_refuel.validate()?;
+        self.transact(room_id, player_id, |room| {
+            let player_state = room
+                .get_player_mut(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            player_state.auto_refuel = auto_refuel;
+            Ok(auto_refuel)
+        })
+    }
+
+    fn build_price_alerts_response(
+        room: &GameRoom,
+        player_id: &Uuid,
+    ) -> Result<PriceAlertsResponse, ServiceError> {
+        let player_state = room
+            .get_player(player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+        let inventory = &player_state.player.cargo_inventory;
+        let market = room.get_current_market(&player_state.player.current_airport);
+
+        let mut holdings: Vec<HoldingInfo> = inventory
+            .get_all_cargo()
+            .iter()
+            .filter(|(_, quantity)| **quantity > 0)
+            .map(|(cargo_type, quantity)| {
+                let average_cost = inventory.average_cost(cargo_type);
+                let price = market.and_then(|market| market.get_cargo_price(cargo_type));
+                HoldingInfo {
+                    cargo_type: cargo_type.clone(),
+                    quantity: *quantity,
+                    average_cost,
+                    price,
+                    gain_percent: average_cost
+                        .zip(price)
+                        .and_then(|(cost, price)| price_alerts::gain_percent(cost, price)),
+                }
+            })
+            .collect();
+        holdings.sort_by(|a, b| a.cargo_type.cmp(&b.cargo_type));
+
+        Ok(PriceAlertsResponse {
+            threshold_percent: player_state.price_alerts.threshold_percent,
+            holdings,
+        })
+    }
+
+    /// The host changes how fast the room's real-time clock runs
+    pub fn set_game_speed(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        speed: GameSpeed,
+    ) -> Result<GameSpeedResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            let notified_players = room.set_game_speed(&player_id, speed)?;
+            Ok(GameSpeedResponse {
+                game_speed: room.settings.game_speed,
+                notified_players,
+            })
+        })
+    }
+
+    /// The host starts a market event; everyone else in the room is told
+    pub fn host_inject_event(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        spec: EventSpec,
+    ) -> Result<InjectEventResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            let (event, notified_players) = room.host_inject_event(&player_id, &spec)?;
+            Ok(InjectEventResponse {
+                event: EventInfo::from(&event),
+                notified_players,
+            })
+        })
+    }
+
+    /// File a claim for an insured loss; it settles a few turns later.
+    pub fn file_claim(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        loss_id: Uuid,
+    ) -> Result<InsuranceClaim, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+
+            let player_state = room
+                .get_player_mut(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+            let turn = player_state.turn_number;
+            Ok(player_state.insurance.file_claim(loss_id, turn)?)
+        })
+    }
+
+    fn build_insurance_response(
+        room: &GameRoom,
+        player_id: &Uuid,
+    ) -> Result<InsuranceResponse, ServiceError> {
+        let player_state = room
+            .get_player(player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+        let insurance = &player_state.insurance;
+
+        Ok(InsuranceResponse {
+            insured: insurance.insured,
+            premium_percent: PREMIUM_PERCENT,
+            next_flight_premium: InsuranceAccount::premium(room.cargo_value(player_id)),
+            unclaimed_losses: insurance.losses.clone(),
+            claims: insurance.claims.iter().rev().cloned().collect(),
+        })
+    }
+
+    /// The order book at the player's airport, plus their own open offers
+    pub fn get_offers(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<OffersResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        let player_state = room
+            .get_player(&player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+        let airport_id = player_state.player.current_airport.clone();
+
+        let offers = room
+            .offers_at(&airport_id)
+            .into_iter()
+            .filter(|offer| offer.player_id != player_id)
+            .cloned()
+            .collect();
+        let my_offers = room
+            .offers
+            .iter()
+            .filter(|offer| offer.player_id == player_id)
+            .cloned()
+            .collect();
+
+        Ok(OffersResponse {
+            airport_id,
+            offers,
+            my_offers,
+        })
+    }
+
+    /// Put up a standing offer at the player's airport. What's offered is held
+    /// in escrow until the offer is taken or cancelled.
+    pub fn place_offer(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        terms: OfferTerms,
+    ) -> Result<OfferResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            room.start_action(&player_id, std::time::Instant::now())?;
+
+            let offer = room.place_offer(&player_id, &terms)?;
+            let message = format!(
+                "Offer posted at {}: {} {} {} at ${} each",
+                offer.airport_id,
+                offer.side.verb(),
+                offer.quantity,
+                offer.cargo_type,
+                offer.unit_price
+            );
+            self.build_offer_response(room, &player_id, message, offer)
+        })
+    }
+
+    /// Withdraw an offer. Cancelling is allowed after the game ends so
+    /// nothing stays stuck in escrow.
+    pub fn cancel_offer(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        offer_id: Uuid,
+    ) -> Result<OfferResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            let offer = room.cancel_offer(&player_id, &offer_id)?;
+            let message = match offer.side {
+                OfferSide::Sell => format!(
+                    "Offer cancelled: {} {} back in your hold",
+                    offer.quantity, offer.cargo_type
+                ),
+                OfferSide::Buy => format!(
+                    "Offer cancelled: ${} back in your account",
+                    offer.total_for(offer.quantity).unwrap_or(0)
+                ),
+            };
+            self.build_offer_response(room, &player_id, message, offer)
+        })
+    }
+
+    /// Take some or all of another player's offer
+    pub fn accept_offer(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        offer_id: Uuid,
+        request: AcceptOfferRequest,
+    ) -> Result<OfferResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            room.start_action(&player_id, std::time::Instant::now())?;
 
-        // Update statistics
-        if let Some(stats) = room.player_statistics.get_mut(&player_id) {
-            stats.record_fuel_purchase(request.quantity, fuel_cost);
-        }
+            let traded = room.accept_offer(&player_id, &offer_id, request.quantity)?;
+            let total = traded.total_for(traded.quantity).unwrap_or(0);
+            let mut message = match traded.side {
+                OfferSide::Sell => format!(
+                    "Bought {} {} from {} for ${}",
+                    traded.quantity, traded.cargo_type, traded.player_name, total
+                ),
+                OfferSide::Buy => format!(
+                    "Sold {} {} to {} for ${}",
+                    traded.quantity, traded.cargo_type, traded.player_name, total
+                ),
+            };
+            // Whoever sold may have just crossed the finish line
+            let seller_id = match traded.side {
+                OfferSide::Sell => traded.player_id,
+                OfferSide::Buy => player_id,
+            };
+            if room.check_for_winner(&seller_id) {
+                let winner = room
+                    .get_player(&seller_id)
+                    .map(|state| state.player_name.clone())
+                    .unwrap_or_default();
+                message.push_str(&format!(" - {} won the game!", winner));
+            }
+            self.build_offer_response(room, &player_id, message, traded)
+        })
+    }
 
-        // Save room state after fuel purchase
-        if let Ok(rooms) = self.rooms.lock()
-            && let Some(room) = rooms.get(&room_id)
-        {
-            self.save_room(room);
-        }
+    /// What each route the player knows prices for would make per unit of
+    /// fuel with a full hold
+    pub fn get_route_profits(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<RouteProfitsResponse, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        let player_state = room
+            .get_player(&player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
 
-        Ok(PlayerFuelResponse {
-            success: true,
-            message: format!(
-                "Purchased {} units of fuel for ${}",
-                request.quantity, fuel_cost
-            ),
-            cost: Some(fuel_cost),
-            new_fuel: Some(new_fuel),
-            new_money: Some(new_money),
+        let routes = RouteAnalytics::for_room(room, &player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+        Ok(RouteProfitsResponse {
+            airport_id: player_state.player.current_airport.clone(),
+            matrix: ProfitMatrix::from_routes(&routes),
+            routes,
         })
     }
 
-    fn build_multiplayer_game_state_response(
+    /// Hand money and/or cargo to another player at the same airport
+    pub fn transfer(
         &self,
-        room: &GameRoom,
-        requesting_player_id: Uuid,
-    ) -> Result<MultiplayerGameStateResponse, String> {
-        let requesting_player_state = room
-            .get_player(&requesting_player_id)
-            .ok_or("Requesting player not found")?;
+        room_id: Uuid,
+        player_id: Uuid,
+        terms: TransferTerms,
+    ) -> Result<TransferResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            room.ensure_in_play()?;
+            room.start_action(&player_id, std::time::Instant::now())?;
 
-        let current_airport = room
-            .shared_state
-            .airports
-            .get(&requesting_player_state.player.current_airport)
-            .ok_or("Current airport not found")?;
+            let transfer = room.transfer(&player_id, &terms)?;
+            // Money received may take the recipient over the line
+            let won = transfer.money > 0 && room.check_for_winner(&transfer.recipient_id);
+            let localized = transfer.localized(won);
 
-        let current_market = room
-            .get_current_market(&requesting_player_state.player.current_airport)
-            .ok_or("Current market not found")?;
+            let player = &room
+                .get_player(&player_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?
+                .player;
+            Ok(TransferResponse {
+                message: localized.to_string(),
+                localized: Some(localized),
+                new_money: player.money,
+                new_inventory: self.build_inventory_map(player, &room.shared_state.cargo_types),
+                transfer,
+            })
+        })
+    }
 
-        // Build available destinations
-        let mut destinations = Vec::new();
-        for (airport_id, airport) in &room.shared_state.airports {
-            if airport_id != &requesting_player_state.player.current_airport {
-                let distance = current_airport.distance_to(airport);
-                let fuel_required = requesting_player_state
-                    .player
-                    .fuel_needed_for_distance(distance);
-                let can_travel = requesting_player_state.player.can_travel_distance(distance);
-                let fuel_price = room
-                    .shared_state
-                    .markets
-                    .get(airport_id)
-                    .map(|m| m.fuel_price)
-                    .unwrap_or(50);
+    fn build_offer_response(
+        &self,
+        room: &GameRoom,
+        player_id: &Uuid,
+        message: String,
+        offer: TradeOffer,
+    ) -> Result<OfferResponse, ServiceError> {
+        let player_state = room
+            .get_player(player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
 
-                destinations.push(DestinationInfo {
-                    airport_id: airport_id.clone(),
-                    airport_name: airport.name.clone(),
-                    distance,
-                    fuel_required,
-                    can_travel,
-                    fuel_price,
-                });
-            }
-        }
+        Ok(OfferResponse {
+            message,
+            offer,
+            new_money: player_state.player.money,
+            new_inventory: self
+                .build_inventory_map(&player_state.player, &room.shared_state.cargo_types),
+        })
+    }
 
-        // Build player list (only online players)
+    /// What spectators see of a room
+    pub fn spectator_view(&self, room: &GameRoom) -> RoomSpectatorView {
         let players = room
             .players
             .values()
-            .filter(|player_state| player_state.is_online)
-            .map(|player_state| PlayerInfo {
-                id: Some(player_state.player_id),
-                name: player_state.player_name.clone(),
-                money: player_state.player.money,
-                current_airport: player_state.player.current_airport.clone(),
-                fuel: player_state.player.fuel,
-                max_fuel: player_state.player.max_fuel,
-                cargo_inventory: self.build_inventory_map(&player_state.player),
-                cargo_weight: player_state
-                    .player
-                    .current_cargo_weight(&room.shared_state.cargo_types),
-                max_cargo_weight: player_state.player.max_cargo_weight,
-                fuel_efficiency: player_state.player.fuel_efficiency,
-                is_online: Some(player_state.is_online),
-                last_seen: Some(player_state.last_seen),
-                is_host: Some(player_state.player_id == room.host_player_id),
+            .map(|player_state| {
+                (
+                    player_state.player_id,
+                    SpectatorPlayerInfo {
+                        name: player_state.player_name.clone(),
+                        money: player_state.player.money,
+                        current_airport: player_state.player.current_airport.clone(),
+                        fuel: player_state.player.fuel,
+                        cargo_weight: player_state
+                            .player
+                            .current_cargo_weight(&room.shared_state.cargo_types),
+                        turn_number: player_state.turn_number,
+                        is_online: player_state.is_online,
+                    },
+                )
             })
             .collect();
 
-        // Get statistics for requesting player
-        let statistics = room
-            .player_statistics
-            .get(&requesting_player_id)
-            .map(|stats| StatisticsInfo {
-                total_revenue: stats.total_revenue,
-                total_expenses: stats.total_expenses,
-                net_profit: stats.net_profit,
-                cargo_trades: stats.cargo_trades,
-                fuel_purchased: stats.fuel_purchased,
-                distances_traveled: stats.distances_traveled,
-                airports_visited: stats.airports_visited.clone(),
-                best_single_trade: stats.best_single_trade,
-                most_profitable_cargo: stats.most_profitable_cargo.clone(),
-                efficiency_score: stats.efficiency_score,
-            })
-            .unwrap_or_default();
+        let markets = if room.settings.fog_of_war {
+            BTreeMap::new()
+        } else {
+            room.shared_state
+                .markets
+                .iter()
+                .map(|(airport_id, market)| {
+                    (
+                        airport_id.clone(),
+                        SpectatorMarketInfo {
+                            fuel_price: market.fuel_price,
+                            cargo_prices: market
+                                .cargo_prices
+                                .iter()
+                                .map(|(cargo, price)| (cargo.clone(), *price))
+                                .collect(),
+                        },
+                    )
+                })
+                .collect()
+        };
 
-        Ok(MultiplayerGameStateResponse {
-            room_info: RoomInfo {
-                id: room.id,
-                name: room.name.clone(),
-                host_player_name: room
-                    .players
-                    .get(&room.host_player_id)
-                    .map(|p| p.player_name.clone())
-                    .unwrap_or_else(|| "Unknown".to_string()),
-                current_players: room.players.values().filter(|p| p.is_online).count() as u32,
-                max_players: room.max_players as u32,
-                created_at: room.created_at,
-                game_status: room.game_status.clone(),
-                is_joinable: room.is_joinable(),
-            },
-            my_player_id: requesting_player_id,
+        RoomSpectatorView {
+            room_id: room.id,
+            room_name: room.name.clone(),
+            game_status: room.game_status.clone(),
+            world_tick: room.shared_state.world_tick,
             players,
-            current_market: MarketInfo {
-                airport_id: current_market.airport_id.clone(),
-                airport_name: current_airport.name.clone(),
-                fuel_price: current_market.fuel_price,
-                cargo_prices: current_market.cargo_prices.clone(),
-                last_updated: current_market.last_updated,
-            },
-            available_destinations: destinations,
-            statistics,
-            turn_number: room.shared_state.turn_number,
-            world_time: room.shared_state.world_time,
-        })
+            markets,
+            winner: room.winner.clone(),
+        }
     }
 
-    fn build_inventory_map(&self, player: &crate::models::Player) -> HashMap<String, u32> {
-        let mut inv = HashMap::new();
-        for cargo_id in &[
-            "electronics",
-            "food",
-            "textiles",
-            "industrial",
-            "luxury",
-            "materials",
-        ] {
-            let qty = player.cargo_inventory.get_quantity(cargo_id);
-            if qty > 0 {
-                inv.insert(cargo_id.to_string(), qty);
-            }
+    fn spectator_json(&self, room: &GameRoom) -> serde_json::Value {
+        serde_json::to_value(self.spectator_view(room)).unwrap_or_default()
+    }
+
+    /// Push a room's latest state to anyone streaming it
+    fn publish_room(&self, room: &GameRoom) {
+        if let Ok(mut feeds) = self.room_feeds.lock()
+            && feeds.is_followed(&room.id)
+        {
+            feeds.publish(room.id, self.spectator_json(room));
         }
-        inv
     }
 
-    pub fn post_message(
+    /// Start streaming a room. The receiver ticks whenever there is
+    /// something new for `room_stream_events`.
+    pub fn subscribe_room_stream(
         &self,
         room_id: Uuid,
-        player_id: Uuid,
-        content: String,
-    ) -> Result<PostMessageResponse, String> {
-        let mut rooms = self
-            .rooms
-            .lock()
-            .map_err(|_| "Failed to acquire rooms lock")?;
+    ) -> Result<tokio::sync::watch::Receiver<u64>, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
 
-        let room = rooms.get_mut(&room_id).ok_or("Room not found")?;
-
-        // Verify player is in the room
-        let player_state = room
-            .players
-            .get(&player_id)
-            .ok_or("Player not in this room")?;
+        let mut feeds = self
+            .room_feeds
+            .lock()
+            .map_err(|_| ServiceError::lock("room feeds"))?;
+        Ok(feeds.subscribe(room_id, || self.spectator_json(room)))
+    }
 
-        let player_name = player_state.player_name.clone();
-        let current_airport = player_state.player.current_airport.clone();
+    /// Events a streaming client hasn't seen, given the last event id it got
+    pub fn room_stream_events(
+        &self,
+        room_id: Uuid,
+        last_event_id: Option<&str>,
+    ) -> Vec<StreamEvent> {
+        self.room_feeds
+            .lock()
+            .map(|feeds| feeds.events_since(&room_id, last_event_id))
+            .unwrap_or_default()
+    }
 
-        // Post the message to the board
-        match room
-            .message_board
-            .post_message(player_id, player_name, content, current_airport)
-        {
-            Ok(message) => {
-                // Save the room with the new message
-                self.save_room(room);
+    /// Every topic at the player's airport
+    #[allow(dead_code)]
+    pub fn get_messages(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<GetMessagesResponse, ServiceError> {
+        self.get_topic_messages(room_id, player_id, None)
+    }
 
-                Ok(PostMessageResponse {
-                    success: true,
-                    message: "Message posted successfully".to_string(),
-                    message_id: Some(message.id),
-                })
+    /// One topic at the player's airport, or every topic when `topic` is None
+    pub fn get_topic_messages(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        topic: Option<MessageTopic>,
+    ) -> Result<GetMessagesResponse, ServiceError> {
+        self.get_message_page(
+            room_id,
+            player_id,
+            MessagesQuery {
+                topic,
+                ..MessagesQuery::default()
             },
-            Err(error) => Ok(PostMessageResponse {
-                success: false,
-                message: error,
-                message_id: None,
-            }),
-        }
+        )
     }
 
-    pub fn get_messages(
+    /// A page of the board at the player's airport, newest first
+    pub fn get_message_page(
         &self,
         room_id: Uuid,
         player_id: Uuid,
-    ) -> Result<GetMessagesResponse, String> {
-        let rooms = self
-            .rooms
-            .lock()
-            .map_err(|_| "Failed to acquire rooms lock")?;
+        query: MessagesQuery,
+    ) -> Result<GetMessagesResponse, ServiceError> {
+        if let Some(limit) = query.limit
+            && (limit == 0 || limit > MAX_MESSAGES_PAGE_SIZE)
+        {
+            return Err(format!("limit must be between 1 and {}", MAX_MESSAGES_PAGE_SIZE).into());
+        }
 
-        let room = rooms.get(&room_id).ok_or("Room not found")?;
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
 
         // Verify player is in the room
         let player_state = room
             .players
             .get(&player_id)
-            .ok_or("Player not in this room")?;
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
 
         let current_airport = player_state.player.current_airport.clone();
 
         // Get messages for the player's current airport
-        let messages = room.message_board.get_messages(&current_airport, Some(20));
-        let total_count = room.message_board.message_count(Some(&current_airport));
+        let limits = room.message_board.limits().clone();
+        let topic_counts = room.message_board.topic_counts(&current_airport);
+        let total_count = match query.topic {
+            Some(topic) => topic_counts.get(&topic).copied().unwrap_or(0),
+            None => room.message_board.message_count(Some(&current_airport)),
+        };
+        let page_size = query.limit.unwrap_or(usize::MAX);
+        // One more than asked for says whether there's another page
+        let mut messages = room.message_board.get_page(
+            &current_airport,
+            query.topic,
+            query.before_id,
+            page_size.saturating_add(1),
+        )?;
+        let next_before_id = if messages.len() > page_size {
+            messages.truncate(page_size);
+            messages.last().map(|message| message.id)
+        } else {
+            None
+        };
 
         // Convert messages to MessageInfo
         let message_infos: Vec<MessageInfo> = messages
             .into_iter()
-            .map(|msg| MessageInfo {
-                id: msg.id,
-                author_id: msg.author_id,
-                author_name: msg.author_name.clone(),
-                content: msg.content.clone(),
-                airport_id: msg.airport_id.clone(),
-                created_at: msg.created_at,
-            })
+            .map(|msg| MessageInfo::for_player(msg, &player_state.player_name))
             .collect();
 
         Ok(GetMessagesResponse {
             messages: message_infos,
             airport_id: current_airport,
             total_count,
+            limits,
+            topic: query.topic,
+            topic_counts,
+            next_before_id,
+        })
+    }
+
+    /// Take a message down. Authors can delete their own, wherever they
+    /// are, and the host can delete anyone's.
+    pub fn delete_message(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<DeleteMessageResponse, ServiceError> {
+        self.transact(room_id, player_id, |room| {
+            if !room.players.contains_key(&player_id) {
+                return Err(ServiceError::NotFound(ErrorCode::PlayerNotInRoom));
+            }
+            let message = room
+                .message_board
+                .find_message(&message_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::MessageNotFound))?;
+            if message.author_id != player_id && room.host_player_id != player_id {
+                return Err("Only the author or the host can delete a message"
+                    .to_string()
+                    .into());
+            }
+
+            let message = room
+                .message_board
+                .remove_message(&message_id)
+                .ok_or(ServiceError::NotFound(ErrorCode::MessageNotFound))?;
+            Ok(DeleteMessageResponse {
+                message: "Message deleted".to_string(),
+                message_id,
+                airport_id: message.airport_id,
+            })
+        })
+    }
+
+    /// Messages the board at the player's airport no longer has room for,
+    /// most recent first
+    pub fn get_archived_messages(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        query: ArchivedMessagesQuery,
+    ) -> Result<ArchivedMessagesResponse, ServiceError> {
+        let limit = query.limit.unwrap_or(DEFAULT_ARCHIVE_PAGE_SIZE);
+        if limit == 0 || limit > MAX_ARCHIVE_PAGE_SIZE {
+            return Err(format!("limit must be between 1 and {}", MAX_ARCHIVE_PAGE_SIZE).into());
+        }
+
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        let player_state = room
+            .players
+            .get(&player_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+        let airport_id = player_state.player.current_airport.clone();
+
+        let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
+        let messages = db
+            .load_archived_messages(&room_id, &airport_id, limit)
+            .map_err(ServiceError::database)?;
+
+        Ok(ArchivedMessagesResponse {
+            messages: messages
+                .iter()
+                .map(|msg| MessageInfo::for_player(msg, &player_state.player_name))
+                .collect(),
+            airport_id,
+        })
+    }
+
+    /// Every airport's board, archived messages and all, for the host to
+    /// keep once the room is gone
+    pub fn export_messages(
+        &self,
+        room_id: Uuid,
+        player_id: Option<Uuid>,
+    ) -> Result<MessageBoardExport, ServiceError> {
+        let rooms = self.rooms.lock().map_err(|_| ServiceError::lock("rooms"))?;
+        let room = rooms
+            .get(&room_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::RoomNotFound))?;
+        if player_id != Some(room.host_player_id) {
+            return Err("Only the host can export the message boards"
+                .to_string()
+                .into());
+        }
+
+        let mut airport_ids: Vec<String> = room
+            .shared_state
+            .airports
+            .keys()
+            .cloned()
+            .chain(room.message_board.airport_ids().map(str::to_string))
+            .collect();
+        airport_ids.sort();
+        airport_ids.dedup();
+
+        let db = self.db.lock().map_err(|_| ServiceError::lock("database"))?;
+        let mut boards = Vec::new();
+        for airport_id in airport_ids {
+            let mut messages = db
+                .load_archived_messages(&room_id, &airport_id, usize::MAX)
+                .map_err(ServiceError::database)?;
+            // Archived ones come most recent first, and all predate the board
+            messages.reverse();
+            messages.extend(
+                room.message_board
+                    .get_messages(&airport_id, None)
+                    .into_iter()
+                    .rev()
+                    .cloned(),
+            );
+            if messages.is_empty() {
+                continue;
+            }
+            boards.push(AirportBoardExport {
+                airport_name: room
+                    .shared_state
+                    .airports
+                    .get(&airport_id)
+                    .map_or_else(|| airport_id.clone(), |airport| airport.name.clone()),
+                airport_id,
+                messages,
+            });
+        }
+
+        Ok(MessageBoardExport {
+            room_id,
+            room_name: room.name.clone(),
+            exported_at: chrono::Utc::now(),
+            message_count: boards.iter().map(|board| board.messages.len()).sum(),
+            boards,
         })
     }
 }
@@ -953,6 +4326,290 @@ impl Default for StatisticsInfo {
             best_single_trade: 0,
             most_profitable_cargo: String::new(),
             efficiency_score: 0.0,
+            fuel_spend: 0,
+            trading_fees: 0,
+            routes: vec![],
+            best_route: None,
+            worst_route: None,
+            fuel_burned: 0,
+            fuel_per_profit_dollar: None,
+            current_streak: 0,
+            best_streak: 0,
+            net_worth: NetWorth::default(),
+            net_worth_history: vec![],
+        }
+    }
+}
+
+/// A player's statistics with their net worth now and over the turns in
+/// their journal
+fn statistics_info(room: &GameRoom, player_id: &Uuid) -> StatisticsInfo {
+    let mut statistics = room
+        .player_statistics
+        .get(player_id)
+        .map(StatisticsInfo::from)
+        .unwrap_or_default();
+    statistics.net_worth = room.net_worth(player_id).unwrap_or_default();
+    if let Some(player_state) = room.players.get(player_id) {
+        statistics.current_streak = player_state.player.streak.current;
+        statistics.best_streak = player_state.player.streak.best;
+        statistics.net_worth_history = player_state
+            .journal
+            .iter()
+            .map(|record| NetWorthPoint {
+                turn_number: record.turn_number,
+                net_worth: record.net_worth,
+            })
+            .collect();
+    }
+    statistics
+}
+
+impl From<&GameStatistics> for StatisticsInfo {
+    fn from(stats: &GameStatistics) -> Self {
+        Self {
+            total_revenue: stats.total_revenue,
+            total_expenses: stats.total_expenses,
+            net_profit: stats.net_profit,
+            cargo_trades: stats.cargo_trades,
+            fuel_purchased: stats.fuel_purchased,
+            distances_traveled: stats.distances_traveled,
+            airports_visited: stats.airports_visited.clone(),
+            best_single_trade: stats.best_single_trade,
+            most_profitable_cargo: stats.most_profitable_cargo.clone(),
+            efficiency_score: stats.efficiency_score,
+            fuel_spend: stats.fuel_spend,
+            trading_fees: stats.trading_fees,
+            routes: stats.routes.clone(),
+            best_route: stats.best_route().cloned(),
+            worst_route: stats.worst_route().cloned(),
+            fuel_burned: stats.fuel_burned,
+            fuel_per_profit_dollar: stats.fuel_per_profit_dollar(),
+            current_streak: 0,
+            best_streak: 0,
+            net_worth: NetWorth::default(),
+            net_worth_history: vec![],
+        }
+    }
+}
+
+impl From<&RoomSettings> for EventRulesInfo {
+    fn from(settings: &RoomSettings) -> Self {
+        Self {
+            market_events: settings.events.clone(),
+            enabled_market_events: settings
+                .events
+                .enabled_events()
+                .iter()
+                .map(|event_type| format!("{:?}", event_type))
+                .collect(),
+            fuel_outage_chance: settings.fuel_outage_chance,
+            cargo_loss_chance: settings.cargo_loss_chance,
+            overnight_theft: settings.overnight_theft,
+            modifier_event_chance: settings.modifier_event_chance,
+        }
+    }
+}
+
+impl From<&MarketEvent> for EventInfo {
+    fn from(event: &MarketEvent) -> Self {
+        Self {
+            event_type: format!("{:?}", event.event_type),
+            affected_cargo: event.affected_cargo.clone(),
+            affected_category: event.affected_category,
+            affected_airport: event.affected_airport.clone(),
+            price_multiplier: event.price_multiplier,
+            turns_remaining: event.turns_remaining,
+            description: event.description.clone(),
+            injected_by: event.injected_by.clone(),
+        }
+    }
+}
+
+impl From<&PlayerGameState> for NotificationsResponse {
+    fn from(player_state: &PlayerGameState) -> Self {
+        Self {
+            unread: player_state.unread_notifications(),
+            notifications: player_state.notifications.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+impl BrokerInfo {
+    /// The player's terms at this market and what's left of this turn's caps
+    pub fn for_player(player: &Player, market: &Market) -> Self {
+        let volume = player.broker_volume_at(&market.airport_id);
+        let tier = TradingSystem::broker_tier(volume);
+        let next = TradingSystem::next_broker_tier(volume);
+        let mut buys_left = HashMap::new();
+        let mut sales_left = HashMap::new();
+        for cargo_id in market.cargo_prices.keys() {
+            buys_left.insert(
+                cargo_id.clone(),
+                TradingSystem::purchases_left(player, market, cargo_id),
+            );
+            sales_left.insert(
+                cargo_id.clone(),
+                TradingSystem::sales_left(player, market, cargo_id),
+            );
         }
+        Self {
+            tier: tier.name.to_string(),
+            fee_basis_points: tier.fee_basis_points,
+            volume,
+            next_tier: next.map(|next| next.name.to_string()),
+            next_tier_volume: next.map(|next| next.min_volume),
+            units_per_turn: Some(tier.units_per_turn),
+            buys_left,
+            sales_left,
+            sell_spread_percent: 0,
+            resale_locked: HashMap::new(),
+        }
+    }
+
+    /// The same terms under a room's trade guards
+    pub fn with_trade_guards(
+        mut self,
+        guards: &TradeGuards,
+        player_state: &PlayerGameState,
+    ) -> Self {
+        self.sell_spread_percent = guards.sell_spread_percent;
+        for cargo_id in self.sales_left.keys() {
+            let (locked, _) = player_state.purchase_log.locked(
+                &player_state.player.current_airport,
+                cargo_id,
+                player_state.turn_number,
+                guards.resale_cooldown_turns,
+            );
+            if locked > 0 {
+                self.resale_locked.insert(cargo_id.clone(), locked);
+            }
+        }
+        self
+    }
+}
+
+/// What a run of trades added up to
+#[derive(Default)]
+struct TradeTotals {
+    /// Money the trades brought in less what they spent
+    net_amount: i64,
+    fees: u32,
+    sold: bool,
+}
+
+/// Make each trade in order, stopping at the first that can't be made
+fn make_trades(
+    room: &mut GameRoom,
+    player_id: &Uuid,
+    trades: &[TradeRequest],
+) -> Result<TradeTotals, ServiceError> {
+    let mut totals = TradeTotals::default();
+    for (index, request) in trades.iter().enumerate() {
+        let quote = trade_in_room(room, player_id, request)?.map_err(|error| {
+            format!(
+                "Trade {} ({} {} {}) failed, so none were made: {}",
+                index + 1,
+                match request.action {
+                    TradeAction::Buy => "buy",
+                    TradeAction::Sell => "sell",
+                },
+                request.quantity,
+                request.cargo_type,
+                error
+            )
+        })?;
+        match request.action {
+            TradeAction::Buy => totals.net_amount -= i64::from(quote.gross),
+            TradeAction::Sell => {
+                totals.net_amount += i64::from(quote.gross);
+                totals.sold = true;
+            },
+        }
+        totals.fees = totals.fees.saturating_add(quote.fee);
     }
+    Ok(totals)
+}
+
+/// A trade that was made, or why it was turned down
+type TradeOutcome = Result<TradeQuote, TradingError>;
+
+/// Make one trade at the player's current airport and count it in their
+/// statistics. The outer error means there's no such player or market; the
+/// inner one is the trade itself being turned down.
+fn trade_in_room(
+    room: &mut GameRoom,
+    player_id: &Uuid,
+    request: &TradeRequest,
+) -> Result<TradeOutcome, ServiceError> {
+    let guards = room.settings.trade_guards;
+    let player_state = room
+        .players
+        .get_mut(player_id)
+        .ok_or(ServiceError::NotFound(ErrorCode::PlayerNotInRoom))?;
+    let current_market = room
+        .shared_state
+        .markets
+        .get(&player_state.player.current_airport)
+        .ok_or("No market available at current location")?;
+    let turn = player_state.turn_number;
+    player_state
+        .purchase_log
+        .prune(turn, guards.resale_cooldown_turns);
+
+    let result = match request.action {
+        TradeAction::Buy => TradingSystem::buy_cargo(
+            &mut player_state.player,
+            current_market,
+            &room.shared_state.cargo_types,
+            &request.cargo_type,
+            request.quantity,
+        ),
+        TradeAction::Sell => {
+            let (locked, turns) = player_state.purchase_log.locked(
+                &current_market.airport_id,
+                &request.cargo_type,
+                turn,
+                guards.resale_cooldown_turns,
+            );
+            let held = player_state
+                .player
+                .cargo_inventory
+                .get_quantity(&request.cargo_type);
+            let sellable = held.saturating_sub(locked);
+            if request.quantity > sellable && request.quantity <= held {
+                return Ok(Err(TradingError::ResaleCooldown { sellable, turns }));
+            }
+            TradingSystem::sell_cargo_at_spread(
+                &mut player_state.player,
+                current_market,
+                &request.cargo_type,
+                request.quantity,
+                guards.sell_spread_percent,
+            )
+        },
+    };
+    if let (TradeAction::Buy, Ok(_), 1..) = (&request.action, &result, guards.resale_cooldown_turns)
+    {
+        player_state.purchase_log.record(
+            &current_market.airport_id,
+            &request.cargo_type,
+            request.quantity,
+            turn,
+        );
+    }
+    if let (Ok(quote), Some(stats)) = (&result, room.player_statistics.get_mut(player_id)) {
+        match request.action {
+            TradeAction::Buy => stats.record_cargo_purchase(quote.gross),
+            TradeAction::Sell => stats.record_sale(&request.cargo_type, quote.gross),
+        }
+        stats.record_trading_fee(quote.fee);
+    }
+    Ok(result)
+}
+
+fn sorted_by_id<T: Clone>(items: &HashMap<String, T>) -> Vec<T> {
+    let mut ids: Vec<&String> = items.keys().collect();
+    ids.sort();
+    ids.into_iter().map(|id| items[id].clone()).collect()
 }
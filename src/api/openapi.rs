@@ -0,0 +1,628 @@
+use std::collections::BTreeMap;
+
+use axum::{Router, response::Json};
+use serde_json::{Map, Value, json};
+use utoipa::{
+    IntoResponses, Modify, OpenApi,
+    openapi::{
+        AllOfBuilder, Info, ObjectBuilder, Ref, RefOr, Response, ResponseBuilder, Type,
+        content::ContentBuilder, path::Operation,
+    },
+};
+
+use crate::{
+    api::{admin, errors::ErrorCode, handlers, multiplayer_handlers, stateless_handlers},
+    models::locale::{Locale, MessageCode},
+};
+
+use ErrorCode::*;
+
+/// Error responses for `codes` plus the catalog-wide InternalError, one per
+/// status, each listing which codes it can carry
+fn error_responses(codes: &[ErrorCode]) -> BTreeMap<String, RefOr<Response>> {
+    let mut codes = codes.to_vec();
+    codes.push(InternalError);
+
+    let mut responses = BTreeMap::new();
+    for code in &codes {
+        let same_status: Vec<&str> = codes
+            .iter()
+            .filter(|other| other.status() == code.status())
+            .map(|other| other.as_str())
+            .collect();
+        let schema = AllOfBuilder::new()
+            .item(Ref::from_schema_name("ErrorResponse"))
+            .item(
+                ObjectBuilder::new().property(
+                    "error",
+                    ObjectBuilder::new()
+                        .schema_type(Type::String)
+                        .enum_values(Some(same_status.clone())),
+                ),
+            );
+        let response = ResponseBuilder::new()
+            .description(same_status.join(", "))
+            .content(
+                "application/json",
+                ContentBuilder::new().schema(Some(schema)).build(),
+            );
+        responses.insert(
+            code.status().as_u16().to_string(),
+            RefOr::T(response.build()),
+        );
+    }
+    responses
+}
+
+/// Declares a set of errors an endpoint can answer with, for the
+/// `responses(...)` of its `#[utoipa::path]`
+macro_rules! errors {
+    ($($(#[$doc:meta])* $name:ident = [$($code:ident),* $(,)?];)*) => {
+        $(
+            $(#[$doc])*
+            pub struct $name;
+
+            impl IntoResponses for $name {
+                fn responses() -> BTreeMap<String, RefOr<Response>> {
+                    error_responses(&[$($code),*])
+                }
+            }
+        )*
+    };
+}
+
+errors! {
+    /// Nothing beyond a server fault
+    NoErrors = [];
+    QueryErrors = [InvalidRequest];
+    BodyErrors = [InvalidBody, InvalidRequest];
+    LogoutErrors = [SessionNotFound, InvalidRequest];
+
+    CreateRoomErrors = [InvalidBody, InvalidRequest, TemplateNotFound];
+    RoomErrors = [RoomNotFound];
+    RoomQueryErrors = [RoomNotFound, InvalidRequest];
+    JoinRoomErrors = [RoomNotFound, InvalidBody, ActionRejected];
+    CloneRoomErrors = [RoomNotFound, InvalidBody, InvalidRequest];
+
+    RoomReadErrors = [RoomNotFound, PlayerNotInRoom, InvalidRequest];
+    RoomActionErrors = [RoomNotFound, PlayerNotInRoom, InvalidBody, ActionRejected];
+    /// Leaving with assets still in the room has to be confirmed with force
+    LeaveRoomErrors = [RoomNotFound, PlayerNotInRoom, ActionRejected, ConfirmationRequired];
+    /// Trades, fuel and trips, which are held to the room's action cooldown
+    GameActionErrors = [RoomNotFound, PlayerNotInRoom, InvalidBody, ActionRejected, ActionCooldown];
+    /// A player's own preferences, which are checked rather than refused
+    PlayerSettingErrors = [RoomNotFound, PlayerNotInRoom, InvalidBody, InvalidRequest];
+    AcceptOfferErrors = [
+        RoomNotFound,
+        PlayerNotInRoom,
+        OfferNotFound,
+        InvalidBody,
+        ActionRejected,
+        ActionCooldown,
+    ];
+    CancelOfferErrors = [RoomNotFound, PlayerNotInRoom, OfferNotFound, ActionRejected];
+    ClaimErrors = [RoomNotFound, PlayerNotInRoom, LossNotFound, InvalidBody, ActionRejected];
+    MessagePageErrors = [RoomNotFound, PlayerNotInRoom, MessageNotFound, InvalidRequest];
+    DeleteMessageErrors = [RoomNotFound, PlayerNotInRoom, MessageNotFound, ActionRejected];
+
+    AdminReadErrors = [AdminUnauthorized, RoomNotFound];
+    AdminActionErrors = [AdminUnauthorized, RoomNotFound, InvalidBody, InvalidRequest];
+    CancelModifierErrors = [AdminUnauthorized, RoomNotFound, ModifierNotFound];
+
+    SinglePlayerActionErrors = [GameNotFound, InvalidBody, ActionRejected];
+    GameReadErrors = [GameNotFound];
+}
+
+/// Request bodies shown in the spec, built from the types the handlers
+/// take so the two can't drift apart
+pub(crate) mod examples {
+    use std::collections::BTreeMap;
+
+    use uuid::Uuid;
+
+    use crate::{
+        api::models::{
+            AcceptOfferRequest, ApplyLoadoutRequest, BorrowRequest, BuyUpgradeRequest,
+            CloneRoomRequest, CreateGameRequest, CreateRoomRequest, CreateTemplateRequest,
+            DigestRequest, FileClaimRequest, FuelRequest, JoinRoomRequest,
+            MarkNotificationsReadRequest, PostMessageRequest, QuickMatchRequest, RepayLoanRequest,
+            SendCourierRequest, SetGameSpeedRequest, SetHangarParkingRequest, SetInsuranceRequest,
+            SetPriceAlertsRequest, TradeAction, TradeBatchRequest, TradeRequest, TravelRequest,
+            TurnDeadlineRequest,
+        },
+        models::{MessageTopic, aircraft::AircraftUpgrade},
+        systems::{
+            RoomSettings,
+            auto_refuel::AutoRefuel,
+            events::{EventSpec, MarketEventType},
+            loadouts::CargoLoadout,
+            matchmaking::{GameMode, MatchPreferences},
+            modifiers::ModifierSpec,
+            multiplayer::GameSpeed,
+            offers::{OfferSide, OfferTerms},
+            profile::QuantityDefaults,
+            transfers::TransferTerms,
+        },
+    };
+
+    fn trade_of(action: TradeAction) -> TradeRequest {
+        TradeRequest {
+            cargo_type: "electronics".to_string(),
+            quantity: 10,
+            action,
+        }
+    }
+
+    pub fn create_room() -> CreateRoomRequest {
+        CreateRoomRequest {
+            name: "Friday Night Freight".to_string(),
+            host_player_name: "Amelia".to_string(),
+            max_players: Some(4),
+            template_id: None,
+            settings: Some(RoomSettings::default()),
+        }
+    }
+
+    pub fn join_room() -> JoinRoomRequest {
+        JoinRoomRequest {
+            player_name: "Bessie".to_string(),
+            starting_airport: Some("ORD".to_string()),
+        }
+    }
+
+    pub fn quick_match() -> QuickMatchRequest {
+        QuickMatchRequest {
+            player_name: "Bessie".to_string(),
+            preferences: MatchPreferences {
+                max_players: Some(4),
+                mode: Some(GameMode::Classic),
+            },
+        }
+    }
+
+    pub fn clone_room() -> CloneRoomRequest {
+        CloneRoomRequest {
+            name: Some("Friday Night Freight II".to_string()),
+            host_player_name: "Amelia".to_string(),
+        }
+    }
+
+    pub fn create_template() -> CreateTemplateRequest {
+        CreateTemplateRequest {
+            name: "Short Hops".to_string(),
+            max_players: Some(4),
+            settings: Some(RoomSettings::default()),
+            from_room_id: None,
+        }
+    }
+
+    pub fn quantity_defaults() -> QuantityDefaults {
+        QuantityDefaults::default()
+    }
+
+    pub fn loadout() -> CargoLoadout {
+        CargoLoadout {
+            name: "Tech run".to_string(),
+            cargo: BTreeMap::from([("electronics".to_string(), 20)]),
+        }
+    }
+
+    pub fn modifier() -> ModifierSpec {
+        ModifierSpec::sales_tax_holiday(3)
+    }
+
+    pub fn admin_event() -> EventSpec {
+        EventSpec::luxury_boom("JFK")
+    }
+
+    pub fn host_event() -> EventSpec {
+        EventSpec {
+            event_type: MarketEventType::FuelOutage,
+            airport: "DEN".to_string(),
+            cargo: None,
+            category: None,
+            percent: None,
+            turns: 3,
+            description: Some(
+                "⛈️ STORM: A storm front grounds the fuel trucks at Denver".to_string(),
+            ),
+        }
+    }
+
+    pub fn game_speed() -> SetGameSpeedRequest {
+        SetGameSpeedRequest {
+            speed: GameSpeed::Fast,
+        }
+    }
+
+    pub fn travel() -> TravelRequest {
+        TravelRequest {
+            destination: "LAX".to_string(),
+        }
+    }
+
+    pub fn ground_transfer() -> TravelRequest {
+        TravelRequest {
+            destination: "EWR".to_string(),
+        }
+    }
+
+    pub fn trade() -> TradeRequest {
+        trade_of(TradeAction::Buy)
+    }
+
+    pub fn trade_batch() -> TradeBatchRequest {
+        TradeBatchRequest {
+            trades: vec![trade_of(TradeAction::Sell)],
+        }
+    }
+
+    pub fn apply_loadout() -> ApplyLoadoutRequest {
+        ApplyLoadoutRequest {
+            name: "Tech run".to_string(),
+        }
+    }
+
+    pub fn fuel() -> FuelRequest {
+        FuelRequest { quantity: 50 }
+    }
+
+    pub fn offer() -> OfferTerms {
+        OfferTerms {
+            side: OfferSide::Sell,
+            cargo_type: "electronics".to_string(),
+            quantity: 10,
+            unit_price: 450,
+        }
+    }
+
+    pub fn accept_offer() -> AcceptOfferRequest {
+        AcceptOfferRequest { quantity: Some(5) }
+    }
+
+    pub fn transfer() -> TransferTerms {
+        TransferTerms {
+            recipient_id: Uuid::nil(),
+            money: 500,
+            cargo_type: Some("food".to_string()),
+            quantity: 5,
+        }
+    }
+
+    pub fn insurance() -> SetInsuranceRequest {
+        SetInsuranceRequest { insured: true }
+    }
+
+    pub fn claim() -> FileClaimRequest {
+        FileClaimRequest {
+            loss_id: Uuid::nil(),
+        }
+    }
+
+    pub fn price_alerts() -> SetPriceAlertsRequest {
+        SetPriceAlertsRequest {
+            threshold_percent: 25,
+        }
+    }
+
+    pub fn auto_refuel() -> AutoRefuel {
+        AutoRefuel {
+            enabled: true,
+            target_percent: 60,
+            max_price: Some(80),
+        }
+    }
+
+    pub fn hangar_parking() -> SetHangarParkingRequest {
+        SetHangarParkingRequest {
+            hangar_parking: true,
+        }
+    }
+
+    pub fn upgrade() -> BuyUpgradeRequest {
+        BuyUpgradeRequest {
+            upgrade: AircraftUpgrade::FuelTank,
+        }
+    }
+
+    pub fn borrow() -> BorrowRequest {
+        BorrowRequest { amount: 2000 }
+    }
+
+    pub fn repay_loan() -> RepayLoanRequest {
+        RepayLoanRequest {
+            loan_id: 1,
+            amount: 500,
+        }
+    }
+
+    pub fn digest() -> DigestRequest {
+        DigestRequest {
+            webhook_url: Some("https://hooks.example.com/kzrk".to_string()),
+            email: None,
+        }
+    }
+
+    pub fn turn_deadline() -> TurnDeadlineRequest {
+        TurnDeadlineRequest {
+            webhook_url: Some("https://hooks.example.com/kzrk".to_string()),
+            hours: Some(48),
+            warning_hours: Some(4),
+        }
+    }
+
+    pub fn post_message() -> PostMessageRequest {
+        PostMessageRequest {
+            content: "Fuel is cheap at DEN today".to_string(),
+            topic: MessageTopic::TradeTips,
+        }
+    }
+
+    pub fn courier() -> SendCourierRequest {
+        SendCourierRequest {
+            destination: "LAX".to_string(),
+            content: "Meet me at the trading desk".to_string(),
+        }
+    }
+
+    pub fn mark_notifications_read() -> MarkNotificationsReadRequest {
+        MarkNotificationsReadRequest {
+            ids: vec![Uuid::nil()],
+        }
+    }
+
+    pub fn create_game() -> CreateGameRequest {
+        CreateGameRequest {
+            player_name: "Amelia".to_string(),
+            starting_money: Some(5000),
+            starting_airport: Some("ORD".to_string()),
+        }
+    }
+}
+
+/// Every coded message, in each language; `message` fields are sent in
+/// the one asked for with Accept-Language
+fn message_catalog() -> Value {
+    Value::Array(
+        MessageCode::ALL
+            .iter()
+            .map(|code| {
+                let templates: Map<String, Value> = Locale::ALL
+                    .iter()
+                    .map(|locale| (locale.tag().to_string(), json!(code.template(*locale))))
+                    .collect();
+                json!({"code": code, "templates": templates})
+            })
+            .collect(),
+    )
+}
+
+fn error_catalog() -> Value {
+    Value::Array(
+        ErrorCode::ALL
+            .iter()
+            .map(|code| {
+                json!({
+                    "code": code.as_str(),
+                    "status": code.status().as_u16(),
+                    "message": code.message_template(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Puts both catalogs at the top of the document, as `x-error-catalog` and
+/// `x-message-catalog`
+struct Catalogs;
+
+impl Modify for Catalogs {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let extensions = openapi.extensions.get_or_insert_default();
+        extensions.insert("x-error-catalog".to_string(), error_catalog());
+        extensions.insert("x-message-catalog".to_string(), message_catalog());
+    }
+}
+
+/// Errors any multiplayer request can meet before it reaches its handler:
+/// shed when the server is saturated, or turned away for its size
+struct SheddableRequests;
+
+impl Modify for SheddableRequests {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let shared = error_responses(&[PayloadTooLarge, ServerBusy, RequestTimedOut]);
+        for item in openapi.paths.paths.values_mut() {
+            for operation in operations(item) {
+                for (status, response) in &shared {
+                    // No endpoint answers with these statuses itself
+                    operation
+                        .responses
+                        .responses
+                        .entry(status.clone())
+                        .or_insert_with(|| response.clone());
+                }
+            }
+        }
+    }
+}
+
+fn operations(item: &mut utoipa::openapi::PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        item.get.as_mut(),
+        item.post.as_mut(),
+        item.put.as_mut(),
+        item.delete.as_mut(),
+        item.patch.as_mut(),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        stateless_handlers::health_check,
+        get_multiplayer_spec,
+        multiplayer_handlers::create_room,
+        multiplayer_handlers::list_rooms,
+        multiplayer_handlers::get_room_settings,
+        multiplayer_handlers::join_room,
+        multiplayer_handlers::quick_match,
+        multiplayer_handlers::clone_room,
+        multiplayer_handlers::leave_room,
+        multiplayer_handlers::rematch,
+        multiplayer_handlers::vote_rematch,
+        multiplayer_handlers::set_game_speed,
+        multiplayer_handlers::inject_event,
+        multiplayer_handlers::stream_room,
+        multiplayer_handlers::get_room_state,
+        multiplayer_handlers::travel_preview,
+        multiplayer_handlers::get_player_statistics,
+        multiplayer_handlers::get_replay,
+        multiplayer_handlers::player_travel,
+        multiplayer_handlers::player_ground_transfer,
+        multiplayer_handlers::player_trade,
+        multiplayer_handlers::player_trade_batch,
+        multiplayer_handlers::apply_loadout,
+        multiplayer_handlers::player_buy_fuel,
+        multiplayer_handlers::get_offers,
+        multiplayer_handlers::place_offer,
+        multiplayer_handlers::accept_offer,
+        multiplayer_handlers::cancel_offer,
+        multiplayer_handlers::transfer,
+        multiplayer_handlers::get_route_profits,
+        multiplayer_handlers::get_price_history,
+        multiplayer_handlers::get_insurance,
+        multiplayer_handlers::set_insurance,
+        multiplayer_handlers::file_claim,
+        multiplayer_handlers::get_price_alerts,
+        multiplayer_handlers::set_price_alerts,
+        multiplayer_handlers::get_auto_refuel,
+        multiplayer_handlers::set_auto_refuel,
+        multiplayer_handlers::get_security,
+        multiplayer_handlers::set_hangar_parking,
+        multiplayer_handlers::buy_security_upgrade,
+        multiplayer_handlers::get_hangar,
+        multiplayer_handlers::buy_upgrade,
+        multiplayer_handlers::get_stalls,
+        multiplayer_handlers::buy_from_stall,
+        multiplayer_handlers::get_loans,
+        multiplayer_handlers::borrow,
+        multiplayer_handlers::repay_loan,
+        multiplayer_handlers::subscribe_digest,
+        multiplayer_handlers::unsubscribe_digest,
+        multiplayer_handlers::set_turn_deadline,
+        multiplayer_handlers::create_template,
+        multiplayer_handlers::list_templates,
+        multiplayer_handlers::find_player_sessions,
+        multiplayer_handlers::hosted_rooms,
+        multiplayer_handlers::get_pilot_profile,
+        multiplayer_handlers::set_quantity_defaults,
+        multiplayer_handlers::save_loadout,
+        multiplayer_handlers::delete_loadout,
+        multiplayer_handlers::logout,
+        multiplayer_handlers::get_rankings,
+        admin::get_room_modifiers,
+        admin::apply_room_modifier,
+        admin::cancel_room_modifier,
+        admin::inject_room_event,
+        multiplayer_handlers::post_message,
+        multiplayer_handlers::get_messages,
+        multiplayer_handlers::get_archived_messages,
+        multiplayer_handlers::delete_message,
+        multiplayer_handlers::export_messages,
+        multiplayer_handlers::get_courier,
+        multiplayer_handlers::send_courier,
+        multiplayer_handlers::get_notifications,
+        multiplayer_handlers::mark_notifications_read,
+        stateless_handlers::get_server_info,
+        stateless_handlers::get_available_airports,
+        stateless_handlers::get_available_cargo,
+    ),
+    components(schemas(crate::api::models::ErrorResponse)),
+    modifiers(&Catalogs)
+)]
+struct MultiplayerApi;
+
+/// Endpoints for switching faults on in a room, in builds with the chaos
+/// feature
+#[cfg(feature = "chaos")]
+#[derive(OpenApi)]
+#[openapi(paths(
+    crate::api::chaos::get_room_faults,
+    crate::api::chaos::set_room_faults,
+    crate::api::chaos::clear_room_faults,
+))]
+struct FaultsApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        handlers::health_check,
+        get_single_player_spec,
+        handlers::create_game,
+        handlers::get_game_state,
+        handlers::travel,
+        handlers::trade,
+        handlers::buy_fuel,
+        stateless_handlers::get_server_info,
+        handlers::get_available_airports,
+        handlers::get_available_cargo,
+    ),
+    components(schemas(crate::api::models::ErrorResponse)),
+    modifiers(&Catalogs)
+)]
+struct SinglePlayerApi;
+
+pub fn multiplayer_spec() -> utoipa::openapi::OpenApi {
+    let mut spec = MultiplayerApi::openapi();
+    #[cfg(feature = "chaos")]
+    spec.merge(FaultsApi::openapi());
+    SheddableRequests.modify(&mut spec);
+    spec.info = Info::new("KZRK Multiplayer API", env!("CARGO_PKG_VERSION"));
+    spec
+}
+
+pub fn single_player_spec() -> utoipa::openapi::OpenApi {
+    let mut spec = SinglePlayerApi::openapi();
+    spec.info = Info::new("KZRK Game API", env!("CARGO_PKG_VERSION"));
+    spec
+}
+
+/// This document
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
+pub async fn get_multiplayer_spec() -> Json<utoipa::openapi::OpenApi> {
+    Json(multiplayer_spec())
+}
+
+/// This document
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
+pub async fn get_single_player_spec() -> Json<utoipa::openapi::OpenApi> {
+    Json(single_player_spec())
+}
+
+/// Swagger UI for `/openapi.json` at `/docs`, in builds with the swagger-ui
+/// feature. Its assets are built into the server, so the page works
+/// without reaching a CDN.
+#[cfg(feature = "swagger-ui")]
+pub fn with_docs<S>(router: Router<S>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    use utoipa_swagger_ui::{Config, SwaggerUi};
+
+    router.merge(SwaggerUi::new("/docs").config(Config::from("/openapi.json")))
+}
+
+#[cfg(not(feature = "swagger-ui"))]
+pub fn with_docs<S>(router: Router<S>) -> Router<S> {
+    router
+}
@@ -0,0 +1,284 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+// How many patches a feed keeps for clients resuming with Last-Event-ID.
+// Anyone further behind gets a fresh snapshot instead.
+const PATCH_HISTORY: usize = 64;
+
+// (version, ops)
+type VersionedPatch = (u64, Vec<PatchOp>);
+
+/// One JSON Patch (RFC 6902) operation. Only the operations a diff of two
+/// documents needs are produced.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+/// Patches turning `old` into `new`. Objects are compared key by key; arrays
+/// and scalars are replaced whole.
+pub fn diff(old: &Value, new: &Value) -> Vec<PatchOp> {
+    let mut ops = Vec::new();
+    diff_into(old, new, String::new(), &mut ops);
+    ops
+}
+
+fn diff_into(old: &Value, new: &Value, path: String, ops: &mut Vec<PatchOp>) {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_value) in old_map {
+                let child = format!("{}/{}", path, escape_pointer(key));
+                match new_map.get(key) {
+                    Some(new_value) => diff_into(old_value, new_value, child, ops),
+                    None => ops.push(PatchOp::Remove { path: child }),
+                }
+            }
+            for (key, new_value) in new_map {
+                if !old_map.contains_key(key) {
+                    ops.push(PatchOp::Add {
+                        path: format!("{}/{}", path, escape_pointer(key)),
+                        value: new_value.clone(),
+                    });
+                }
+            }
+        },
+        _ if old != new => ops.push(PatchOp::Replace {
+            path,
+            value: new.clone(),
+        }),
+        _ => {},
+    }
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// What a stream sends next: the whole document, or a patch on top of the
+/// previous event. `id` is the SSE event id clients resume from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    Snapshot { id: String, state: Value },
+    Patch { id: String, ops: Vec<PatchOp> },
+}
+
+/// The published state of one room and its recent patches. Event ids are
+/// `<epoch>-<version>`; the epoch changes whenever a feed is recreated
+/// (e.g. on another instance) so stale ids fall back to a snapshot.
+struct RoomFeed {
+    epoch: String,
+    version: u64,
+    state: Value,
+    patches: VecDeque<VersionedPatch>,
+    notify: watch::Sender<u64>,
+}
+
+impl RoomFeed {
+    fn new(state: Value) -> Self {
+        let (notify, _) = watch::channel(1);
+        Self {
+            epoch: Uuid::new_v4().simple().to_string()[..8].to_string(),
+            version: 1,
+            state,
+            patches: VecDeque::new(),
+            notify,
+        }
+    }
+
+    fn event_id(&self, version: u64) -> String {
+        format!("{}-{}", self.epoch, version)
+    }
+
+    fn publish(&mut self, state: Value) {
+        let ops = diff(&self.state, &state);
+        if ops.is_empty() {
+            return;
+        }
+
+        self.version += 1;
+        self.state = state;
+        self.patches.push_back((self.version, ops));
+        while self.patches.len() > PATCH_HISTORY {
+            self.patches.pop_front();
+        }
+        self.notify.send_replace(self.version);
+    }
+
+    fn events_since(&self, last_event_id: Option<&str>) -> Vec<StreamEvent> {
+        let last_version = last_event_id
+            .and_then(|id| id.split_once('-'))
+            .filter(|(epoch, _)| *epoch == self.epoch)
+            .and_then(|(_, version)| version.parse::<u64>().ok());
+
+        let oldest_patch = self
+            .patches
+            .front()
+            .map(|(version, _)| *version)
+            .unwrap_or(self.version + 1);
+
+        match last_version {
+            Some(last) if last == self.version => Vec::new(),
+            Some(last) if last < self.version && last + 1 >= oldest_patch => self
+                .patches
+                .iter()
+                .filter(|(version, _)| *version > last)
+                .map(|(version, ops)| StreamEvent::Patch {
+                    id: self.event_id(*version),
+                    ops: ops.clone(),
+                })
+                .collect(),
+            _ => vec![StreamEvent::Snapshot {
+                id: self.event_id(self.version),
+                state: self.state.clone(),
+            }],
+        }
+    }
+}
+
+/// Live feeds for rooms someone has streamed. Rooms nobody has asked for
+/// aren't tracked, so publishing to them is free.
+#[derive(Default)]
+pub struct RoomFeeds {
+    feeds: HashMap<Uuid, RoomFeed>,
+}
+
+impl RoomFeeds {
+    /// Start following a room, creating its feed from `state` if needed.
+    /// The receiver ticks whenever a new patch is published.
+    pub fn subscribe(
+        &mut self,
+        room_id: Uuid,
+        state: impl FnOnce() -> Value,
+    ) -> watch::Receiver<u64> {
+        self.feeds
+            .entry(room_id)
+            .or_insert_with(|| RoomFeed::new(state()))
+            .notify
+            .subscribe()
+    }
+
+    pub fn is_followed(&self, room_id: &Uuid) -> bool {
+        self.feeds.contains_key(room_id)
+    }
+
+    pub fn publish(&mut self, room_id: Uuid, state: Value) {
+        if let Some(feed) = self.feeds.get_mut(&room_id) {
+            feed.publish(state);
+        }
+    }
+
+    /// Stop following a room that has left this instance. Dropping the feed
+    /// ends its streams, so clients reconnect to wherever the room went.
+    pub fn remove(&mut self, room_id: &Uuid) {
+        self.feeds.remove(room_id);
+    }
+
+    pub fn events_since(&self, room_id: &Uuid, last_event_id: Option<&str>) -> Vec<StreamEvent> {
+        self.feeds
+            .get(room_id)
+            .map(|feed| feed.events_since(last_event_id))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_produces_minimal_patches() {
+        let old = json!({"status": "InProgress", "players": {"a": {"money": 5000}, "b/c": {}}});
+        let new = json!({"status": "InProgress", "players": {"a": {"money": 4200}, "d": {}}});
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                PatchOp::Replace {
+                    path: "/players/a/money".to_string(),
+                    value: json!(4200)
+                },
+                PatchOp::Remove {
+                    path: "/players/b~1c".to_string()
+                },
+                PatchOp::Add {
+                    path: "/players/d".to_string(),
+                    value: json!({})
+                },
+            ]
+        );
+        assert!(diff(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_resume_from_last_event_id() {
+        let room_id = Uuid::new_v4();
+        let mut feeds = RoomFeeds::default();
+        feeds.subscribe(room_id, || json!({"tick": 0}));
+
+        let first = feeds.events_since(&room_id, None);
+        let StreamEvent::Snapshot {
+            id: snapshot_id, ..
+        } = &first[0]
+        else {
+            panic!("New clients start with a snapshot");
+        };
+
+        feeds.publish(room_id, json!({"tick": 1}));
+        feeds.publish(room_id, json!({"tick": 1})); // No change, no event
+        feeds.publish(room_id, json!({"tick": 2}));
+
+        let resumed = feeds.events_since(&room_id, Some(snapshot_id));
+        assert_eq!(resumed.len(), 2);
+        let StreamEvent::Patch { id: latest, .. } = &resumed[1] else {
+            panic!("Resuming replays patches");
+        };
+        assert!(feeds.events_since(&room_id, Some(latest)).is_empty());
+
+        // Ids from another feed, or from too far back, get a snapshot
+        for stale in ["deadbeef-1", "garbage"] {
+            assert!(matches!(
+                feeds.events_since(&room_id, Some(stale))[0],
+                StreamEvent::Snapshot { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_removing_a_feed_ends_its_streams() {
+        let room_id = Uuid::new_v4();
+        let mut feeds = RoomFeeds::default();
+        let changes = feeds.subscribe(room_id, || json!({"tick": 0}));
+
+        feeds.remove(&room_id);
+        assert!(changes.has_changed().is_err());
+        assert!(!feeds.is_followed(&room_id));
+        assert!(feeds.events_since(&room_id, None).is_empty());
+    }
+
+    #[test]
+    fn test_old_patches_are_dropped() {
+        let room_id = Uuid::new_v4();
+        let mut feeds = RoomFeeds::default();
+        feeds.subscribe(room_id, || json!({"tick": 0}));
+        let StreamEvent::Snapshot { id, .. } = feeds.events_since(&room_id, None).remove(0) else {
+            panic!("Expected a snapshot");
+        };
+
+        for tick in 1..=(PATCH_HISTORY as u64 + 1) {
+            feeds.publish(room_id, json!({ "tick": tick }));
+        }
+
+        assert!(matches!(
+            feeds.events_since(&room_id, Some(&id))[0],
+            StreamEvent::Snapshot { .. }
+        ));
+    }
+}
@@ -1,19 +1,20 @@
 #![allow(dead_code)]
 
 use axum::{
-    Router,
-    routing::{get, post},
+    Router, middleware,
+    routing::{delete, get, post},
 };
 
 use crate::api::{
-    handlers, multiplayer_handlers, multiplayer_service::MultiplayerGameService,
-    service::GameService, stateless_handlers,
+    admin, cluster, handlers, load_shedding, locale, multiplayer_handlers,
+    multiplayer_service::MultiplayerGameService, openapi, service::GameService, stateless_handlers,
 };
 
 pub fn create_router(service: GameService) -> Router {
-    Router::new()
+    openapi::with_docs(Router::new())
         // Health check
         .route("/health", get(handlers::health_check))
+        .route("/openapi.json", get(openapi::get_single_player_spec))
 
         // Single-player game management (backwards compatibility)
         .route("/game", post(handlers::create_game))
@@ -25,43 +26,172 @@ pub fn create_router(service: GameService) -> Router {
         .route("/game/:session_id/fuel", post(handlers::buy_fuel))
 
         // Reference data
+        .route("/server-info", get(stateless_handlers::get_server_info))
         .route("/airports", get(handlers::get_available_airports))
         .route("/cargo", get(handlers::get_available_cargo))
 
+        // Answer in the client's language
+        .layer(middleware::from_fn(locale::negotiate_locale))
+
         // Add the service as state
         .with_state(service)
 }
 
 pub fn create_multiplayer_router(service: MultiplayerGameService) -> Router {
-    Router::new()
+    let router = openapi::with_docs(Router::new())
         // Health check
         .route("/health", get(stateless_handlers::health_check))
+        .route("/openapi.json", get(openapi::get_multiplayer_spec))
 
         // Multiplayer room management
         .route("/rooms", post(multiplayer_handlers::create_room))
         .route("/rooms", get(multiplayer_handlers::list_rooms))
+        .route("/rooms/:room_id/settings", get(multiplayer_handlers::get_room_settings))
         .route("/rooms/:room_id/join", post(multiplayer_handlers::join_room))
+        .route("/matchmaking/quick-match", post(multiplayer_handlers::quick_match))
+        .route("/rooms/:room_id/clone", post(multiplayer_handlers::clone_room))
         .route("/rooms/:room_id/players/:player_id/leave", post(multiplayer_handlers::leave_room))
+        .route("/rooms/:room_id/players/:player_id/rematch", post(multiplayer_handlers::rematch))
+        .route("/rooms/:room_id/players/:player_id/rematch-vote", post(multiplayer_handlers::vote_rematch))
+        .route("/rooms/:room_id/players/:player_id/speed", post(multiplayer_handlers::set_game_speed))
+        .route("/rooms/:room_id/players/:player_id/events", post(multiplayer_handlers::inject_event))
 
         // Multiplayer game state
+        .route("/rooms/:room_id/stream", get(multiplayer_handlers::stream_room))
         .route("/rooms/:room_id/players/:player_id/state", get(multiplayer_handlers::get_room_state))
+        .route("/rooms/:room_id/players/:player_id/travel-preview", get(multiplayer_handlers::travel_preview))
+        .route("/rooms/:room_id/players/:player_id/statistics", get(multiplayer_handlers::get_player_statistics))
+        .route("/rooms/:room_id/players/:player_id/replay", get(multiplayer_handlers::get_replay))
 
         // Multiplayer player actions
         .route("/rooms/:room_id/players/:player_id/travel", post(multiplayer_handlers::player_travel))
+        .route("/rooms/:room_id/players/:player_id/ground-transfer", post(multiplayer_handlers::player_ground_transfer))
         .route("/rooms/:room_id/players/:player_id/trade", post(multiplayer_handlers::player_trade))
+        .route("/rooms/:room_id/players/:player_id/trades", post(multiplayer_handlers::player_trade_batch))
+        .route("/rooms/:room_id/players/:player_id/loadout", post(multiplayer_handlers::apply_loadout))
         .route("/rooms/:room_id/players/:player_id/fuel", post(multiplayer_handlers::player_buy_fuel))
 
+        // Order book between players
+        .route("/rooms/:room_id/players/:player_id/offers", get(multiplayer_handlers::get_offers))
+        .route("/rooms/:room_id/players/:player_id/offers", post(multiplayer_handlers::place_offer))
+        .route("/rooms/:room_id/players/:player_id/offers/:offer_id/accept", post(multiplayer_handlers::accept_offer))
+        .route("/rooms/:room_id/players/:player_id/offers/:offer_id", delete(multiplayer_handlers::cancel_offer))
+        .route("/rooms/:room_id/players/:player_id/transfer", post(multiplayer_handlers::transfer))
+        .route("/rooms/:room_id/players/:player_id/route-profits", get(multiplayer_handlers::get_route_profits))
+        .route("/rooms/:room_id/markets/:airport_id/history", get(multiplayer_handlers::get_price_history))
+
+        // Cargo insurance
+        .route("/rooms/:room_id/players/:player_id/insurance", get(multiplayer_handlers::get_insurance))
+        .route("/rooms/:room_id/players/:player_id/insurance", post(multiplayer_handlers::set_insurance))
+        .route("/rooms/:room_id/players/:player_id/claims", post(multiplayer_handlers::file_claim))
+
+        // Alerts on what held cargo is worth
+        .route("/rooms/:room_id/players/:player_id/price-alerts", get(multiplayer_handlers::get_price_alerts))
+        .route("/rooms/:room_id/players/:player_id/price-alerts", post(multiplayer_handlers::set_price_alerts))
+        .route("/rooms/:room_id/players/:player_id/auto-refuel", get(multiplayer_handlers::get_auto_refuel))
+        .route("/rooms/:room_id/players/:player_id/auto-refuel", post(multiplayer_handlers::set_auto_refuel))
+
+        // Overnight security
+        .route("/rooms/:room_id/players/:player_id/security", get(multiplayer_handlers::get_security))
+        .route("/rooms/:room_id/players/:player_id/security/hangar", post(multiplayer_handlers::set_hangar_parking))
+        .route("/rooms/:room_id/players/:player_id/security/upgrade", post(multiplayer_handlers::buy_security_upgrade))
+        // Aircraft upgrades
+        .route("/rooms/:room_id/players/:player_id/hangar", get(multiplayer_handlers::get_hangar))
+        .route("/rooms/:room_id/players/:player_id/upgrade", post(multiplayer_handlers::buy_upgrade))
+        // Vendor stalls
+        .route("/rooms/:room_id/players/:player_id/stalls", get(multiplayer_handlers::get_stalls))
+        .route("/rooms/:room_id/players/:player_id/stalls/:stall_id/buy", post(multiplayer_handlers::buy_from_stall))
+        // Bank loans
+        .route("/rooms/:room_id/players/:player_id/loan", get(multiplayer_handlers::get_loans))
+        .route("/rooms/:room_id/players/:player_id/loan", post(multiplayer_handlers::borrow))
+        .route("/rooms/:room_id/players/:player_id/loan/repay", post(multiplayer_handlers::repay_loan))
+
+        // Daily digests for asynchronous play
+        .route("/rooms/:room_id/players/:player_id/digest", post(multiplayer_handlers::subscribe_digest))
+        .route("/rooms/:room_id/players/:player_id/digest", delete(multiplayer_handlers::unsubscribe_digest))
+        .route("/rooms/:room_id/players/:player_id/turn-deadline", post(multiplayer_handlers::set_turn_deadline))
+
+        // Room templates
+        .route("/templates", post(multiplayer_handlers::create_template))
+        .route("/templates", get(multiplayer_handlers::list_templates))
+
         // Session management
         .route("/players/:player_name/sessions", get(multiplayer_handlers::find_player_sessions))
+        .route("/players/:player_name/hosted-rooms", get(multiplayer_handlers::hosted_rooms))
+        .route("/players/:player_name/profile", get(multiplayer_handlers::get_pilot_profile))
+        .route("/players/:player_name/profile/defaults", post(multiplayer_handlers::set_quantity_defaults))
+        .route("/players/:player_name/profile/loadouts", post(multiplayer_handlers::save_loadout))
+        .route("/players/:player_name/profile/loadouts/:name", delete(multiplayer_handlers::delete_loadout))
+        .route("/sessions/:player_id/logout", post(multiplayer_handlers::logout))
+
+        // Seasonal rankings across every finished game
+        .route("/rankings", get(multiplayer_handlers::get_rankings))
+
+        // Global price and fee modifiers, for admins
+        .route("/admin/rooms/:room_id/modifiers", get(admin::get_room_modifiers))
+        .route("/admin/rooms/:room_id/modifiers", post(admin::apply_room_modifier))
+        .route("/admin/rooms/:room_id/modifiers/:modifier_id", delete(admin::cancel_room_modifier))
+
+        // Market events started by hand, for story-driven sessions
+        .route("/admin/rooms/:room_id/events", post(admin::inject_room_event))
 
         // Message board endpoints
         .route("/rooms/:room_id/players/:player_id/messages", post(multiplayer_handlers::post_message))
         .route("/rooms/:room_id/players/:player_id/messages", get(multiplayer_handlers::get_messages))
+        .route("/rooms/:room_id/players/:player_id/messages/archive", get(multiplayer_handlers::get_archived_messages))
+        .route("/rooms/:room_id/players/:player_id/messages/:message_id", delete(multiplayer_handlers::delete_message))
+        .route("/rooms/:room_id/messages/export", get(multiplayer_handlers::export_messages))
+        .route("/rooms/:room_id/players/:player_id/courier", get(multiplayer_handlers::get_courier))
+        .route("/rooms/:room_id/players/:player_id/courier", post(multiplayer_handlers::send_courier))
+        .route("/rooms/:room_id/players/:player_id/notifications", get(multiplayer_handlers::get_notifications))
+        .route("/rooms/:room_id/players/:player_id/notifications/read", post(multiplayer_handlers::mark_notifications_read))
 
         // Reference data (stateless handlers)
+        .route("/server-info", get(stateless_handlers::get_server_info))
         .route("/airports", get(stateless_handlers::get_available_airports))
-        .route("/cargo", get(stateless_handlers::get_available_cargo))
+        .route("/cargo", get(stateless_handlers::get_available_cargo));
+
+    // Fault injection for testing clients, in builds with the chaos feature
+    let router = with_fault_injection(router, &service)
+        // Send room requests to the instance that owns the room
+        .layer(middleware::from_fn_with_state(service.clone(), cluster::route_to_room_owner))
 
+        // Answer in the client's language
+        .layer(middleware::from_fn(locale::negotiate_locale));
+
+    // Turn requests away with a 503 when the server is saturated
+    load_shedding::with_load_shedding(router, service.load_policy())
         // Add the service as state
         .with_state(service)
 }
+
+/// Admin endpoints for switching faults on in a room, and the layer that
+/// injects them. Sits inside the owner redirect so only rooms served here
+/// misbehave.
+#[cfg(feature = "chaos")]
+fn with_fault_injection(
+    router: Router<MultiplayerGameService>,
+    service: &MultiplayerGameService,
+) -> Router<MultiplayerGameService> {
+    use crate::api::chaos;
+
+    router
+        .route("/admin/rooms/:room_id/faults", get(chaos::get_room_faults))
+        .route("/admin/rooms/:room_id/faults", post(chaos::set_room_faults))
+        .route(
+            "/admin/rooms/:room_id/faults",
+            delete(chaos::clear_room_faults),
+        )
+        .layer(middleware::from_fn_with_state(
+            service.clone(),
+            chaos::inject_faults,
+        ))
+}
+
+#[cfg(not(feature = "chaos"))]
+fn with_fault_injection(
+    router: Router<MultiplayerGameService>,
+    _service: &MultiplayerGameService,
+) -> Router<MultiplayerGameService> {
+    router
+}
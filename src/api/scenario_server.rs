@@ -0,0 +1,255 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use crate::{
+    api::models::{
+        CreateRoomRequest, CreateRoomResponse, ErrorResponse, FuelRequest, GetMessagesResponse,
+        MultiplayerGameStateResponse, PlayerFuelResponse, PlayerTradeResponse,
+        PlayerTravelResponse, PostMessageRequest, PostMessageResponse, TradeAction, TradeRequest,
+        TravelRequest,
+    },
+    models::MessageTopic,
+    systems::{
+        RoomSettings,
+        events::EventConfig,
+        multiplayer::SpawnRule,
+        scenario::{Outcome, Scenario, ScenarioAction, ScenarioTarget, Snapshot},
+    },
+};
+
+/// What came back from the server: the response, or the error it sent
+enum Reply<T> {
+    Ok(T),
+    Refused(String),
+}
+
+/// Plays scenarios in a fresh room on a running multiplayer server, over
+/// HTTP with `curl`. The room turns off theft, cargo loss, outages, NPC
+/// posts, landing slots and market events so runs repeat.
+pub struct ServerTarget {
+    base_url: String,
+    room_id: Uuid,
+    player_id: Uuid,
+}
+
+impl ServerTarget {
+    pub fn new(base_url: &str) -> Self {
+        let base_url = if base_url.starts_with("http") {
+            base_url.trim_end_matches('/').to_string()
+        } else {
+            format!("http://{}", base_url.trim_end_matches('/'))
+        };
+        Self {
+            base_url,
+            room_id: Uuid::nil(),
+            player_id: Uuid::nil(),
+        }
+    }
+
+    fn player_path(&self, path: &str) -> String {
+        format!(
+            "/rooms/{}/players/{}/{}",
+            self.room_id, self.player_id, path
+        )
+    }
+
+    fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<String>,
+    ) -> Result<Reply<T>, String> {
+        let mut command = Command::new("curl");
+        command
+            .args(["-sS", "-L", "--max-time", "10", "-X", method])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if body.is_some() {
+            command
+                .args(["-H", "Content-Type: application/json"])
+                .args(["--data-binary", "@-"]);
+        }
+        let output = command
+            .arg("--")
+            .arg(format!("{}{}", self.base_url, path))
+            .spawn()
+            .and_then(|mut child| {
+                if let (Some(mut stdin), Some(body)) = (child.stdin.take(), &body) {
+                    stdin.write_all(body.as_bytes())?;
+                }
+                child.wait_with_output()
+            })
+            .map_err(|e| format!("Failed to run curl: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "{} {} failed: {}",
+                method,
+                path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        if let Ok(value) = serde_json::from_str::<T>(&text) {
+            return Ok(Reply::Ok(value));
+        }
+        match serde_json::from_str::<ErrorResponse>(&text) {
+            Ok(error) => Ok(Reply::Refused(error.message)),
+            Err(_) => Err(format!(
+                "{} {}: unexpected response '{}'",
+                method, path, text
+            )),
+        }
+    }
+
+    fn post<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl serde::Serialize,
+    ) -> Result<Reply<T>, String> {
+        let json = serde_json::to_string(body).map_err(|e| e.to_string())?;
+        self.request("POST", path, Some(json))
+    }
+
+    fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, String> {
+        match self.request("GET", path, None)? {
+            Reply::Ok(value) => Ok(value),
+            Reply::Refused(reason) => Err(format!("GET {}: {}", path, reason)),
+        }
+    }
+}
+
+/// An action's reply as an outcome, using its success flag and message
+fn outcome<T>(reply: Reply<T>, succeeded: impl Fn(&T) -> Result<(), String>) -> Outcome {
+    match reply {
+        Reply::Ok(response) => match succeeded(&response) {
+            Ok(()) => Outcome::Done,
+            Err(reason) => Outcome::Refused(reason),
+        },
+        Reply::Refused(reason) => Outcome::Refused(reason),
+    }
+}
+
+impl ScenarioTarget for ServerTarget {
+    fn start(&mut self, scenario: &Scenario) -> Result<(), String> {
+        let request = CreateRoomRequest {
+            name: format!("Scenario: {}", scenario.name),
+            host_player_name: "Pilot".to_string(),
+            max_players: Some(1),
+            template_id: None,
+            settings: Some(RoomSettings {
+                starting_money: scenario.starting_money,
+                spawn: SpawnRule::HostChosen(scenario.starting_airport.clone()),
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                events: EventConfig {
+                    chance: 0.0,
+                    ..EventConfig::default()
+                },
+                npc_chatter: false,
+                landing_slots: false,
+                overnight_theft: false,
+                ..RoomSettings::default()
+            }),
+        };
+        match self.post::<CreateRoomResponse>("/rooms", &request)? {
+            Reply::Ok(room) => {
+                self.room_id = room.room_id;
+                self.player_id = room.host_player_id;
+                Ok(())
+            },
+            Reply::Refused(reason) => Err(format!("Couldn't create a room: {}", reason)),
+        }
+    }
+
+    fn perform(&mut self, action: &ScenarioAction) -> Result<Outcome, String> {
+        Ok(match action {
+            ScenarioAction::Buy { cargo, quantity } | ScenarioAction::Sell { cargo, quantity } => {
+                let request = TradeRequest {
+                    cargo_type: cargo.clone(),
+                    quantity: *quantity,
+                    action: if matches!(action, ScenarioAction::Buy { .. }) {
+                        TradeAction::Buy
+                    } else {
+                        TradeAction::Sell
+                    },
+                };
+                let reply = self.post(&self.player_path("trade"), &request)?;
+                outcome(reply, |response: &PlayerTradeResponse| {
+                    response
+                        .success
+                        .then_some(())
+                        .ok_or(response.message.clone())
+                })
+            },
+            ScenarioAction::BuyFuel(quantity) => {
+                let request = FuelRequest {
+                    quantity: *quantity,
+                };
+                let reply = self.post(&self.player_path("fuel"), &request)?;
+                outcome(reply, |response: &PlayerFuelResponse| {
+                    response
+                        .success
+                        .then_some(())
+                        .ok_or(response.message.clone())
+                })
+            },
+            ScenarioAction::Fly(destination) => {
+                let request = TravelRequest {
+                    destination: destination.clone(),
+                };
+                let reply = self.post(&self.player_path("travel"), &request)?;
+                outcome(reply, |response: &PlayerTravelResponse| {
+                    response
+                        .success
+                        .then_some(())
+                        .ok_or(response.message.clone())
+                })
+            },
+            ScenarioAction::PostMessage(content) => {
+                let request = PostMessageRequest {
+                    content: content.clone(),
+                    topic: MessageTopic::General,
+                };
+                let reply = self.post(&self.player_path("messages"), &request)?;
+                outcome(reply, |response: &PostMessageResponse| {
+                    response
+                        .success
+                        .then_some(())
+                        .ok_or(response.message.clone())
+                })
+            },
+            ScenarioAction::Check => Outcome::Done,
+        })
+    }
+
+    fn snapshot(&mut self) -> Result<Snapshot, String> {
+        let state: MultiplayerGameStateResponse = self.get(&self.player_path("state"))?;
+        let player = state
+            .players
+            .into_iter()
+            .find(|player| player.id == Some(self.player_id))
+            .ok_or("The room doesn't list the scenario's player")?;
+        let board: GetMessagesResponse = self.get(&self.player_path("messages"))?;
+
+        Ok(Snapshot {
+            location: player.current_airport,
+            turn: player.turn_number.unwrap_or(1),
+            fuel: player.fuel,
+            money: player.money,
+            cargo: player.cargo_inventory,
+            messages: board
+                .messages
+                .into_iter()
+                .map(|message| message.content)
+                .collect(),
+        })
+    }
+}
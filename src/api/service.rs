@@ -8,10 +8,22 @@ use std::{
 use uuid::Uuid;
 
 use crate::{
-    api::models::*,
+    api::{
+        errors::{ErrorCode, ServiceError},
+        models::*,
+    },
     data::{airports::get_default_airports, cargo_types::get_default_cargo_types},
-    models::Player,
-    systems::{GameState, GameStatistics},
+    models::{
+        Player,
+        locale::{LocalizedMessage, MessageCode},
+    },
+    systems::{
+        GameState, GameStatistics, RouteFuelStats, TradingSystem, TravelSystem, TurnAction,
+        TurnEngine,
+        game::{Completed, TurnReport},
+        slots::SlotStatus,
+        trading::TradingError,
+    },
 };
 
 pub type GameSessions = Arc<Mutex<HashMap<Uuid, GameState>>>;
@@ -37,7 +49,10 @@ impl GameService {
         }
     }
 
-    pub fn create_game(&self, request: CreateGameRequest) -> Result<CreateGameResponse, String> {
+    pub fn create_game(
+        &self,
+        request: CreateGameRequest,
+    ) -> Result<CreateGameResponse, ServiceError> {
         let session_id = Uuid::new_v4();
 
         let starting_money = request.starting_money.unwrap_or(5000);
@@ -58,7 +73,7 @@ impl GameService {
             let mut sessions = self
                 .sessions
                 .lock()
-                .map_err(|_| "Failed to acquire session lock")?;
+                .map_err(|_| ServiceError::lock("session"))?;
             sessions.insert(session_id, game_state);
         }
 
@@ -67,7 +82,7 @@ impl GameService {
             let mut stats = self
                 .statistics
                 .lock()
-                .map_err(|_| "Failed to acquire statistics lock")?;
+                .map_err(|_| ServiceError::lock("statistics"))?;
             stats.insert(session_id, GameStatistics::new());
         }
 
@@ -78,12 +93,14 @@ impl GameService {
         })
     }
 
-    pub fn get_game_state(&self, session_id: Uuid) -> Result<GameStateResponse, String> {
+    pub fn get_game_state(&self, session_id: Uuid) -> Result<GameStateResponse, ServiceError> {
         let sessions = self
             .sessions
             .lock()
-            .map_err(|_| "Failed to acquire session lock")?;
-        let game_state = sessions.get(&session_id).ok_or("Game session not found")?;
+            .map_err(|_| ServiceError::lock("session"))?;
+        let game_state = sessions
+            .get(&session_id)
+            .ok_or(ServiceError::NotFound(ErrorCode::GameNotFound))?;
 
         self.build_game_state_response(game_state, session_id)
     }
@@ -92,20 +109,20 @@ impl GameService {
         &self,
         session_id: Uuid,
         request: TravelRequest,
-    ) -> Result<TravelResponse, String> {
+    ) -> Result<TravelResponse, ServiceError> {
         let mut sessions = self
             .sessions
             .lock()
-            .map_err(|_| "Failed to acquire session lock")?;
+            .map_err(|_| ServiceError::lock("session"))?;
         let game_state = sessions
             .get_mut(&session_id)
-            .ok_or("Game session not found")?;
+            .ok_or(ServiceError::NotFound(ErrorCode::GameNotFound))?;
 
         // Get destination airport
         let destination_airport = game_state
             .airports
             .get(&request.destination)
-            .ok_or("Destination airport not found")?;
+            .ok_or_else(|| LocalizedMessage::new(MessageCode::DestinationNotFound))?;
 
         // Calculate distance and fuel required
         let current_airport = game_state
@@ -113,19 +130,25 @@ impl GameService {
             .get(&game_state.player.current_airport)
             .ok_or("Current airport not found")?;
 
-        let distance = current_airport.distance_to(destination_airport);
-        let fuel_required = game_state.player.fuel_needed_for_distance(distance);
+        let distance = game_state
+            .distances
+            .between(current_airport, destination_airport);
+        let conditions = game_state.flight_conditions(&current_airport.id, &request.destination);
+        let fuel_required =
+            TravelSystem::calculate_fuel_needed(&game_state.player, distance, conditions);
 
         // Check if travel is possible
-        if !game_state.player.can_travel_distance(distance) {
+        if game_state.player.fuel < fuel_required {
+            let localized = LocalizedMessage::new(MessageCode::InsufficientFuel)
+                .with("needed", fuel_required)
+                .with("have", game_state.player.fuel);
             return Ok(TravelResponse {
                 success: false,
-                message: format!(
-                    "Insufficient fuel. Need {} units, have {}",
-                    fuel_required, game_state.player.fuel
-                ),
+                message: localized.to_string(),
+                localized: Some(localized),
                 fuel_consumed: None,
                 new_location: None,
+                advanced_turn: false,
                 game_state: None,
             });
         }
@@ -133,145 +156,122 @@ impl GameService {
         // Get destination name for response message
         let destination_name = destination_airport.name.clone();
 
-        // Perform travel
-        game_state.player.consume_fuel(fuel_required);
-        game_state.player.current_airport = request.destination.clone();
+        // Fuel burned on this leg is valued at the departure airport's price
+        let origin = game_state.player.current_airport.clone();
+        let fuel_price = game_state
+            .markets
+            .get(&origin)
+            .map(|m| m.fuel_price)
+            .unwrap_or(0);
+
+        // Prices have moved by the time we land, and the flight takes a
+        // turn the same way it does in the terminal game
+        let travel_info = game_state
+            .fly_to(&request.destination)
+            .map_err(|error| format!("Travel failed: {:?}", error))?;
+        let turn = TurnReport {
+            night: travel_info.night,
+            price_alerts: travel_info.price_alerts,
+            bank: travel_info.bank,
+        };
 
         // Update statistics
         {
             let mut stats = self
                 .statistics
                 .lock()
-                .map_err(|_| "Failed to acquire statistics lock")?;
+                .map_err(|_| ServiceError::lock("statistics"))?;
             if let Some(game_stats) = stats.get_mut(&session_id) {
-                game_stats.record_travel(&request.destination, distance);
+                game_stats.record_leg(RouteFuelStats::leg(
+                    &origin,
+                    &request.destination,
+                    distance,
+                    travel_info.fuel_consumed,
+                    fuel_price,
+                ));
+                game_stats.calculate_efficiency(game_state.turn_number);
             }
         }
 
-        // Advance turn and potentially generate events
-        self.advance_turn(game_state);
-
         let new_game_state = self.build_game_state_response(game_state, session_id)?;
 
+        let headline = LocalizedMessage::new(MessageCode::Traveled)
+            .with("airport_name", &destination_name)
+            .with("airport", &request.destination);
+        let mut message = headline.to_string();
+        turn.append_to(&mut message);
         Ok(TravelResponse {
             success: true,
-            message: format!("Traveled to {} ({})", destination_name, request.destination),
-            fuel_consumed: Some(fuel_required),
+            localized: Some(headline.leading(&message)),
+            message,
+            fuel_consumed: Some(travel_info.fuel_consumed),
             new_location: Some(request.destination),
+            advanced_turn: TurnEngine::costs_turn(TurnAction::Fly),
             game_state: Some(new_game_state),
         })
     }
 
-    pub fn trade(&self, session_id: Uuid, request: TradeRequest) -> Result<TradeResponse, String> {
+    pub fn trade(
+        &self,
+        session_id: Uuid,
+        request: TradeRequest,
+    ) -> Result<TradeResponse, ServiceError> {
         let mut sessions = self
             .sessions
             .lock()
-            .map_err(|_| "Failed to acquire session lock")?;
+            .map_err(|_| ServiceError::lock("session"))?;
         let game_state = sessions
             .get_mut(&session_id)
-            .ok_or("Game session not found")?;
-
-        let current_market = game_state
-            .get_current_market()
-            .ok_or("No market available at current location")?;
-
-        let cargo_price = current_market
-            .get_cargo_price(&request.cargo_type)
-            .ok_or("Cargo type not available at this market")?;
-
-        let transaction_amount = cargo_price * request.quantity;
-
-        match request.action {
-            TradeAction::Buy => {
-                // Check if player can afford
-                if !game_state.player.can_afford(transaction_amount) {
-                    return Ok(TradeResponse {
-                        success: false,
-                        message: "Insufficient funds".to_string(),
-                        transaction_amount: None,
-                        new_money: None,
-                        new_inventory: None,
-                        game_state: None,
-                    });
-                }
-
-                // Check cargo capacity
-                let cargo_type = game_state
-                    .cargo_types
-                    .get(&request.cargo_type)
-                    .ok_or("Invalid cargo type")?;
-                let additional_weight = cargo_type.weight_per_unit * request.quantity;
+            .ok_or(ServiceError::NotFound(ErrorCode::GameNotFound))?;
 
-                if !game_state
-                    .player
-                    .can_carry_more_weight(additional_weight, &game_state.cargo_types)
-                {
-                    return Ok(TradeResponse {
-                        success: false,
-                        message: "Insufficient cargo capacity".to_string(),
-                        transaction_amount: None,
-                        new_money: None,
-                        new_inventory: None,
-                        game_state: None,
-                    });
-                }
+        if game_state.get_current_market().is_none() {
+            return Err("No market available at current location".into());
+        }
 
-                // Execute purchase
-                game_state.player.spend_money(transaction_amount);
-                game_state
-                    .player
-                    .cargo_inventory
-                    .add_cargo(&request.cargo_type, request.quantity);
-
-                // Update statistics
-                {
-                    let mut stats = self
-                        .statistics
-                        .lock()
-                        .map_err(|_| "Failed to acquire statistics lock")?;
-                    if let Some(game_stats) = stats.get_mut(&session_id) {
-                        game_stats.record_cargo_purchase(transaction_amount);
-                    }
-                }
+        let result = match request.action {
+            TradeAction::Buy => game_state.buy_cargo(&request.cargo_type, request.quantity),
+            TradeAction::Sell => game_state.sell_cargo(&request.cargo_type, request.quantity),
+        };
+        let Completed {
+            result: quote,
+            turn,
+        } = match result {
+            Ok(completed) => completed,
+            Err(TradingError::CargoNotAvailable) => {
+                return Err(TradingError::CargoNotAvailable.into());
             },
-            TradeAction::Sell => {
-                // Check if player has enough cargo
-                if game_state
-                    .player
-                    .cargo_inventory
-                    .get_quantity(&request.cargo_type)
-                    < request.quantity
-                {
-                    return Ok(TradeResponse {
-                        success: false,
-                        message: "Insufficient cargo to sell".to_string(),
-                        transaction_amount: None,
-                        new_money: None,
-                        new_inventory: None,
-                        game_state: None,
-                    });
-                }
+            Err(error) => {
+                return Ok(TradeResponse {
+                    success: false,
+                    message: error.to_string(),
+                    localized: Some(error.localized()),
+                    transaction_amount: None,
+                    fee: None,
+                    new_money: None,
+                    new_inventory: None,
+                    advanced_turn: false,
+                    game_state: None,
+                });
+            },
+        };
 
-                // Execute sale
-                game_state
-                    .player
-                    .cargo_inventory
-                    .remove_cargo(&request.cargo_type, request.quantity);
-                game_state.player.earn_money(transaction_amount);
-
-                // Update statistics
-                {
-                    let mut stats = self
-                        .statistics
-                        .lock()
-                        .map_err(|_| "Failed to acquire statistics lock")?;
-                    if let Some(game_stats) = stats.get_mut(&session_id) {
-                        game_stats.record_sale(&request.cargo_type, transaction_amount);
-                    }
+        // Update statistics
+        {
+            let mut stats = self
+                .statistics
+                .lock()
+                .map_err(|_| ServiceError::lock("statistics"))?;
+            if let Some(game_stats) = stats.get_mut(&session_id) {
+                match request.action {
+                    TradeAction::Buy => game_stats.record_cargo_purchase(quote.gross),
+                    TradeAction::Sell => game_stats.record_sale(&request.cargo_type, quote.gross),
                 }
-            },
+                game_stats.record_trading_fee(quote.fee);
+            }
         }
 
+        let advanced_turn = turn.is_some();
+
         let new_inventory = {
             let mut inv = HashMap::new();
             for cargo_id in &[
@@ -297,79 +297,124 @@ impl GameService {
                 "Successfully {:?}ed {} units of {}",
                 request.action, request.quantity, request.cargo_type
             ),
-            transaction_amount: Some(transaction_amount),
+            localized: None,
+            transaction_amount: Some(quote.gross),
+            fee: Some(quote.fee),
             new_money: Some(game_state.player.money),
             new_inventory: Some(new_inventory),
+            advanced_turn,
             game_state: Some(new_game_state),
         })
     }
 
-    pub fn buy_fuel(&self, session_id: Uuid, request: FuelRequest) -> Result<FuelResponse, String> {
+    pub fn buy_fuel(
+        &self,
+        session_id: Uuid,
+        request: FuelRequest,
+    ) -> Result<FuelResponse, ServiceError> {
         let mut sessions = self
             .sessions
             .lock()
-            .map_err(|_| "Failed to acquire session lock")?;
+            .map_err(|_| ServiceError::lock("session"))?;
         let game_state = sessions
             .get_mut(&session_id)
-            .ok_or("Game session not found")?;
+            .ok_or(ServiceError::NotFound(ErrorCode::GameNotFound))?;
 
         let current_market = game_state
             .get_current_market()
             .ok_or("No market available at current location")?;
 
-        let fuel_cost = current_market.fuel_price * request.quantity;
+        // Too big to price means too big for anyone's wallet
+        let fuel_cost = TradingSystem::price_of(current_market.fuel_price, request.quantity);
+
+        // Check the airport has the fuel
+        let in_stock = current_market.fuel_available();
+        if request.quantity > in_stock {
+            let localized = if in_stock == 0 {
+                LocalizedMessage::new(MessageCode::FuelUnavailable)
+            } else {
+                LocalizedMessage::new(MessageCode::FuelLowStock).with("quantity", in_stock)
+            };
+            return Ok(FuelResponse {
+                success: false,
+                message: localized.to_string(),
+                localized: Some(localized),
+                cost: None,
+                new_fuel: None,
+                new_money: None,
+                advanced_turn: false,
+                game_state: None,
+            });
+        }
 
         // Check if player can afford
-        if !game_state.player.can_afford(fuel_cost) {
+        if !fuel_cost.is_ok_and(|cost| game_state.player.can_afford(cost)) {
+            let localized = LocalizedMessage::new(MessageCode::FuelUnaffordable);
             return Ok(FuelResponse {
                 success: false,
-                message: "Insufficient funds for fuel purchase".to_string(),
+                message: localized.to_string(),
+                localized: Some(localized),
                 cost: None,
                 new_fuel: None,
                 new_money: None,
+                advanced_turn: false,
                 game_state: None,
             });
         }
 
         // Check if fuel tank has capacity
-        let space_available = game_state.player.max_fuel - game_state.player.fuel;
+        let space_available = game_state
+            .player
+            .max_fuel
+            .saturating_sub(game_state.player.fuel);
         if request.quantity > space_available {
+            let localized =
+                LocalizedMessage::new(MessageCode::FuelTankFull).with("space", space_available);
             return Ok(FuelResponse {
                 success: false,
-                message: format!("Fuel tank can only hold {} more units", space_available),
+                message: localized.to_string(),
+                localized: Some(localized),
                 cost: None,
                 new_fuel: None,
                 new_money: None,
+                advanced_turn: false,
                 game_state: None,
             });
         }
 
         // Execute fuel purchase
-        game_state.player.spend_money(fuel_cost);
-        game_state.player.add_fuel(request.quantity);
+        let Completed {
+            result: purchase,
+            turn,
+        } = game_state
+            .buy_fuel(request.quantity)
+            .map_err(|error| error.to_string())?;
 
         // Update statistics
         {
             let mut stats = self
                 .statistics
                 .lock()
-                .map_err(|_| "Failed to acquire statistics lock")?;
+                .map_err(|_| ServiceError::lock("statistics"))?;
             if let Some(game_stats) = stats.get_mut(&session_id) {
-                game_stats.record_fuel_purchase(request.quantity, fuel_cost);
+                game_stats.record_fuel_purchase(purchase.quantity, purchase.cost);
             }
         }
 
+        let advanced_turn = turn.is_some();
         let new_game_state = self.build_game_state_response(game_state, session_id)?;
 
+        let localized = LocalizedMessage::new(MessageCode::FuelPurchased)
+            .with("quantity", purchase.quantity)
+            .with("cost", purchase.cost);
         Ok(FuelResponse {
             success: true,
-            message: format!(
-                "Purchased {} units of fuel for ${}",
-                request.quantity, fuel_cost
-            ),
-            cost: Some(fuel_cost),
+            message: localized.to_string(),
+            localized: Some(localized),
+            cost: Some(purchase.cost),
             new_fuel: Some(game_state.player.fuel),
             new_money: Some(game_state.player.money),
+            advanced_turn,
             game_state: Some(new_game_state),
         })
     }
@@ -378,7 +423,7 @@ impl GameService {
         &self,
         game_state: &GameState,
         session_id: Uuid,
-    ) -> Result<GameStateResponse, String> {
+    ) -> Result<GameStateResponse, ServiceError> {
         let current_airport = game_state
             .airports
             .get(&game_state.player.current_airport)
@@ -392,9 +437,11 @@ impl GameService {
         let mut destinations = Vec::new();
         for (airport_id, airport) in &game_state.airports {
             if airport_id != &game_state.player.current_airport {
-                let distance = current_airport.distance_to(airport);
-                let fuel_required = game_state.player.fuel_needed_for_distance(distance);
-                let can_travel = game_state.player.can_travel_distance(distance);
+                let distance = game_state.distances.between(current_airport, airport);
+                let conditions = game_state.flight_conditions(&current_airport.id, airport_id);
+                let fuel_required =
+                    TravelSystem::calculate_fuel_needed(&game_state.player, distance, conditions);
+                let can_travel = game_state.player.fuel >= fuel_required;
                 let fuel_price = game_state
                     .markets
                     .get(airport_id)
@@ -408,46 +455,40 @@ impl GameService {
                     fuel_required,
                     can_travel,
                     fuel_price,
+                    fuel_price_observed_at: Some(game_state.turn_number),
+                    fuel_outage: game_state
+                        .markets
+                        .get(airport_id)
+                        .is_some_and(|m| m.fuel_outage),
+                    ground_transfer_fare: TravelSystem::ground_transfer_fare(
+                        current_airport,
+                        airport,
+                    ),
+                    slot_status: SlotStatus::Unrestricted,
+                    reachable_on_full_tank: fuel_required <= game_state.player.max_fuel,
                 });
             }
         }
 
-        // Build active events - for now, return empty since we haven't added events to GameState
-        let active_events: Vec<EventInfo> = vec![];
+        let active_events: Vec<EventInfo> = game_state
+            .active_events
+            .iter()
+            .map(EventInfo::from)
+            .collect();
 
         // Get statistics
         let statistics = {
             let stats = self
                 .statistics
                 .lock()
-                .map_err(|_| "Failed to acquire statistics lock")?;
-            if let Some(game_stats) = stats.get(&session_id) {
-                StatisticsInfo {
-                    total_revenue: game_stats.total_revenue,
-                    total_expenses: game_stats.total_expenses,
-                    net_profit: game_stats.net_profit,
-                    cargo_trades: game_stats.cargo_trades,
-                    fuel_purchased: game_stats.fuel_purchased,
-                    distances_traveled: game_stats.distances_traveled,
-                    airports_visited: game_stats.airports_visited.clone(),
-                    best_single_trade: game_stats.best_single_trade,
-                    most_profitable_cargo: game_stats.most_profitable_cargo.clone(),
-                    efficiency_score: game_stats.efficiency_score,
-                }
-            } else {
-                StatisticsInfo {
-                    total_revenue: 0,
-                    total_expenses: 0,
-                    net_profit: 0,
-                    cargo_trades: 0,
-                    fuel_purchased: 0,
-                    distances_traveled: 0.0,
-                    airports_visited: vec![],
-                    best_single_trade: 0,
-                    most_profitable_cargo: String::new(),
-                    efficiency_score: 0.0,
-                }
-            }
+                .map_err(|_| ServiceError::lock("statistics"))?;
+            let mut statistics = stats
+                .get(&session_id)
+                .map(StatisticsInfo::from)
+                .unwrap_or_default();
+            statistics.current_streak = game_state.player.streak.current;
+            statistics.best_streak = game_state.player.streak.best;
+            statistics
         };
 
         Ok(GameStateResponse {
@@ -483,6 +524,9 @@ impl GameService {
                 is_online: None,
                 last_seen: None,
                 is_host: None,
+                turn_number: Some(game_state.turn_number),
+                is_bot: false,
+                streak: game_state.player.streak.current,
             },
             current_market: MarketInfo {
                 airport_id: current_market.airport_id.clone(),
@@ -490,18 +534,21 @@ impl GameService {
                 fuel_price: current_market.fuel_price,
                 cargo_prices: current_market.cargo_prices.clone(),
                 last_updated: current_market.last_updated,
+                fuel_stock: Some(current_market.fuel_available()),
+                fuel_outage: current_market.fuel_outage,
+                broker: Some(BrokerInfo::for_player(&game_state.player, current_market)),
             },
             available_destinations: destinations,
+            flight_range: TravelSystem::range(
+                &game_state.player,
+                game_state.flight_conditions(&current_airport.id, &current_airport.id),
+            ),
             active_events,
             statistics,
             turn_number: game_state.turn_number,
+            world_tick: game_state.turn_number,
+            date: game_state.date(),
+            calendar: game_state.calendar,
         })
     }
-
-    fn advance_turn(&self, game_state: &mut GameState) {
-        game_state.turn_number += 1;
-
-        // For now, just advance the turn - we can add events later
-        // TODO: Add event system integration
-    }
 }
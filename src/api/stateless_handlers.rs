@@ -1,10 +1,31 @@
-use axum::{http::StatusCode, response::Json};
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::OnceLock,
+};
+
+use axum::{
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
 
 use crate::{
-    api::models::{ErrorResponse, SuccessResponse},
+    api::{
+        models::{AirportInfo, CargoInfo, ErrorResponse, ServerInfo, SuccessResponse},
+        openapi::NoErrors,
+    },
     data::{get_default_airports, get_default_cargo_types},
 };
 
+/// How long clients may reuse airports and cargo before asking again
+pub const REFERENCE_MAX_AGE_SECS: u64 = 3600;
+
+/// Health check
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
 pub async fn health_check() -> Result<Json<SuccessResponse>, (StatusCode, Json<ErrorResponse>)> {
     Ok(Json(SuccessResponse {
         message: "KZRK Game API is running".to_string(),
@@ -12,41 +33,109 @@ pub async fn health_check() -> Result<Json<SuccessResponse>, (StatusCode, Json<E
     }))
 }
 
-pub async fn get_available_airports()
--> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let airports = get_default_airports();
+/// Server and reference data versions
+#[utoipa::path(
+    get,
+    path = "/server-info",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
+pub async fn get_server_info() -> Json<ServerInfo> {
+    Json(ServerInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        reference_version: reference_version().to_string(),
+    })
+}
 
-    let airport_list: Vec<serde_json::Value> = airports
-        .values()
-        .map(|airport| {
-            serde_json::json!({
-                "id": airport.id,
-                "name": airport.name,
-                "coordinates": airport.coordinates,
-                "base_fuel_price": airport.base_fuel_price
-            })
-        })
-        .collect();
+/// Airports and their fuel economies
+#[utoipa::path(
+    get,
+    path = "/airports",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
+pub async fn get_available_airports(headers: HeaderMap) -> Response {
+    cacheable(&headers, airport_infos())
+}
 
-    Ok(Json(serde_json::json!(airport_list)))
+/// Cargo types
+#[utoipa::path(
+    get,
+    path = "/cargo",
+    responses((status = 200, description = "Success"), NoErrors)
+)]
+pub async fn get_available_cargo(headers: HeaderMap) -> Response {
+    cacheable(&headers, cargo_infos())
 }
 
-pub async fn get_available_cargo()
--> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let cargo_types = get_default_cargo_types();
+/// The airports `/airports` serves, by id
+pub fn airport_infos() -> Vec<AirportInfo> {
+    let mut airports: Vec<AirportInfo> = get_default_airports()
+        .into_values()
+        .map(|airport| AirportInfo {
+            fuel_economy: airport.fuel_economy(),
+            id: airport.id,
+            name: airport.name,
+            coordinates: airport.coordinates,
+            base_fuel_price: airport.base_fuel_price,
+        })
+        .collect();
+    airports.sort_by(|a, b| a.id.cmp(&b.id));
+    airports
+}
 
-    let cargo_list: Vec<serde_json::Value> = cargo_types
-        .values()
-        .map(|cargo_type| {
-            serde_json::json!({
-                "id": cargo_type.id,
-                "name": cargo_type.name,
-                "base_price": cargo_type.base_price,
-                "weight_per_unit": cargo_type.weight_per_unit,
-                "volatility": cargo_type.volatility
-            })
+/// The cargo types `/cargo` serves, by id
+pub fn cargo_infos() -> Vec<CargoInfo> {
+    let mut cargo_types: Vec<CargoInfo> = get_default_cargo_types()
+        .into_values()
+        .map(|cargo_type| CargoInfo {
+            id: cargo_type.id,
+            name: cargo_type.name,
+            base_price: cargo_type.base_price,
+            weight_per_unit: cargo_type.weight_per_unit,
+            volatility: cargo_type.volatility,
         })
         .collect();
+    cargo_types.sort_by(|a, b| a.id.cmp(&b.id));
+    cargo_types
+}
+
+/// A fingerprint of the airports and cargo the server hands out. It changes
+/// whenever either does, so it doubles as their ETag.
+pub fn reference_version() -> &'static str {
+    static VERSION: OnceLock<String> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_string(&airport_infos())
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        serde_json::to_string(&cargo_infos())
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    })
+}
+
+/// Reference data with headers that let clients and proxies keep it. A
+/// client that already has this version gets 304 Not Modified and no body.
+pub fn cacheable<T: Serialize>(request_headers: &HeaderMap, body: T) -> Response {
+    let etag = format!("\"{}\"", reference_version());
+    let fresh = request_headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|tags| tags.split(',').any(|tag| tag.trim() == etag));
 
-    Ok(Json(serde_json::json!(cargo_list)))
+    let mut response = if fresh {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        Json(body).into_response()
+    };
+    let headers = response.headers_mut();
+    if let Ok(etag) = HeaderValue::from_str(&etag) {
+        headers.insert(header::ETAG, etag);
+    }
+    if let Ok(cache_control) =
+        HeaderValue::from_str(&format!("public, max-age={}", REFERENCE_MAX_AGE_SECS))
+    {
+        headers.insert(header::CACHE_CONTROL, cache_control);
+    }
+    response
 }
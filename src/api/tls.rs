@@ -0,0 +1,305 @@
+use std::path::PathBuf;
+
+#[cfg(feature = "tls")]
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
+
+#[cfg(feature = "tls")]
+use axum::{
+    Router,
+    extract::Request,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+#[cfg(feature = "tls")]
+use rustls::{
+    ServerConfig,
+    pki_types::{CertificateDer, PrivateKeyDer},
+    server::{ClientHello, ResolvesServerCert},
+    sign::CertifiedKey,
+};
+#[cfg(feature = "tls")]
+use tracing::info;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CertificatePaths {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+/// A hostname and the certificate to serve for it
+pub type SniCertificate = (String, CertificatePaths);
+
+/// HTTPS settings for the API server. The default certificate is served to
+/// clients whose SNI hostname has no certificate of its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TlsConfig {
+    pub default_certificate: CertificatePaths,
+    pub sni_certificates: Vec<SniCertificate>,
+    /// Where to listen for plain HTTP and redirect it to HTTPS, e.g. 0.0.0.0:80
+    pub http_redirect_addr: Option<String>,
+}
+
+impl TlsConfig {
+    pub fn new(cert: impl Into<PathBuf>, key: impl Into<PathBuf>) -> Self {
+        Self {
+            default_certificate: CertificatePaths {
+                cert: cert.into(),
+                key: key.into(),
+            },
+            sni_certificates: Vec::new(),
+            http_redirect_addr: None,
+        }
+    }
+
+    pub fn with_sni_certificate(
+        mut self,
+        hostname: &str,
+        cert: impl Into<PathBuf>,
+        key: impl Into<PathBuf>,
+    ) -> Self {
+        self.sni_certificates.push((
+            hostname.to_ascii_lowercase(),
+            CertificatePaths {
+                cert: cert.into(),
+                key: key.into(),
+            },
+        ));
+        self
+    }
+
+    pub fn with_http_redirect(mut self, addr: impl Into<String>) -> Self {
+        self.http_redirect_addr = Some(addr.into());
+        self
+    }
+
+    /// HTTPS is on when KZRK_TLS_CERT and KZRK_TLS_KEY are set. Extra
+    /// hostnames go in KZRK_TLS_SNI as `host=cert.pem,key.pem;host2=...`, and
+    /// KZRK_HTTP_REDIRECT_ADDR turns on the plain HTTP redirect listener.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let cert = std::env::var("KZRK_TLS_CERT").ok();
+        let key = std::env::var("KZRK_TLS_KEY").ok();
+        let mut config = match (cert, key) {
+            (None, None) => return Ok(None),
+            (Some(cert), Some(key)) => Self::new(cert, key),
+            _ => return Err("Set both KZRK_TLS_CERT and KZRK_TLS_KEY to enable HTTPS".to_string()),
+        };
+
+        if let Ok(spec) = std::env::var("KZRK_TLS_SNI") {
+            for (hostname, paths) in Self::parse_sni(&spec)? {
+                config = config.with_sni_certificate(&hostname, paths.cert, paths.key);
+            }
+        }
+        if let Ok(addr) = std::env::var("KZRK_HTTP_REDIRECT_ADDR") {
+            config = config.with_http_redirect(addr);
+        }
+
+        Ok(Some(config))
+    }
+
+    pub fn parse_sni(spec: &str) -> Result<Vec<SniCertificate>, String> {
+        spec.split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let invalid = || format!("Invalid SNI entry '{}', expected host=cert,key", entry);
+                let (hostname, paths) = entry.split_once('=').ok_or_else(invalid)?;
+                let (cert, key) = paths.split_once(',').ok_or_else(invalid)?;
+                if hostname.trim().is_empty() || cert.trim().is_empty() || key.trim().is_empty() {
+                    return Err(invalid());
+                }
+                Ok((
+                    hostname.trim().to_ascii_lowercase(),
+                    CertificatePaths {
+                        cert: PathBuf::from(cert.trim()),
+                        key: PathBuf::from(key.trim()),
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Load the certificates and build the rustls configuration
+    #[cfg(feature = "tls")]
+    pub fn server_config(&self) -> Result<ServerConfig, String> {
+        let mut by_hostname = HashMap::new();
+        for (hostname, paths) in &self.sni_certificates {
+            by_hostname.insert(hostname.clone(), load_certified_key(paths)?);
+        }
+        let resolver = SniResolver {
+            by_hostname,
+            default: load_certified_key(&self.default_certificate)?,
+        };
+
+        let mut config =
+            ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .map_err(|e| format!("TLS configuration error: {}", e))?
+                .with_no_client_auth()
+                .with_cert_resolver(Arc::new(resolver));
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+}
+
+/// Where to send a plain HTTP request on the HTTPS server. None without a
+/// usable Host header.
+#[cfg_attr(not(feature = "tls"), allow(dead_code))]
+pub fn https_redirect_location(
+    host_header: Option<&str>,
+    https_port: u16,
+    path_and_query: &str,
+) -> Option<String> {
+    let host = host_header?.trim();
+    // Drop any port, keeping IPv6 literals like [::1] intact
+    let hostname = match host.rsplit_once(':') {
+        Some((name, port)) if !name.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    };
+    if hostname.is_empty() {
+        return None;
+    }
+
+    let authority = if https_port == 443 {
+        hostname.to_string()
+    } else {
+        format!("{}:{}", hostname, https_port)
+    };
+    Some(format!("https://{}{}", authority, path_and_query))
+}
+
+#[cfg(feature = "tls")]
+fn load_certified_key(paths: &CertificatePaths) -> Result<Arc<CertifiedKey>, String> {
+    let read = |path: &PathBuf| {
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    };
+
+    let cert_pem = read(&paths.cert)?;
+    let certs: Vec<CertificateDer<'static>> = rustls_pemfile::certs(&mut cert_pem.as_slice())
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Invalid certificate in {}: {}", paths.cert.display(), e))?;
+    if certs.is_empty() {
+        return Err(format!("No certificates found in {}", paths.cert.display()));
+    }
+
+    let key_pem = read(&paths.key)?;
+    let key: PrivateKeyDer<'static> = rustls_pemfile::private_key(&mut key_pem.as_slice())
+        .map_err(|e| format!("Invalid private key in {}: {}", paths.key.display(), e))?
+        .ok_or_else(|| format!("No private key found in {}", paths.key.display()))?;
+    let signing_key = rustls::crypto::ring::sign::any_supported_type(&key)
+        .map_err(|e| format!("Unsupported private key in {}: {}", paths.key.display(), e))?;
+
+    Ok(Arc::new(CertifiedKey::new(certs, signing_key)))
+}
+
+/// Picks a certificate by the SNI hostname the client asked for
+#[cfg(feature = "tls")]
+#[derive(Debug)]
+struct SniResolver {
+    by_hostname: HashMap<String, Arc<CertifiedKey>>,
+    default: Arc<CertifiedKey>,
+}
+
+#[cfg(feature = "tls")]
+impl ResolvesServerCert for SniResolver {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        let certificate = client_hello
+            .server_name()
+            .and_then(|name| self.by_hostname.get(&name.to_ascii_lowercase()))
+            .unwrap_or(&self.default);
+        Some(certificate.clone())
+    }
+}
+
+#[cfg(feature = "tls")]
+fn redirect_router(https_port: u16) -> Router {
+    Router::new().fallback(move |request: Request| async move {
+        let path_and_query = request
+            .uri()
+            .path_and_query()
+            .map(|pq| pq.as_str())
+            .unwrap_or("/");
+        let host = request
+            .headers()
+            .get(header::HOST)
+            .and_then(|value| value.to_str().ok());
+
+        match https_redirect_location(host, https_port, path_and_query) {
+            // 308 keeps the method and body, unlike 301
+            Some(location) => (
+                StatusCode::PERMANENT_REDIRECT,
+                [(header::LOCATION, location)],
+            )
+                .into_response(),
+            None => Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body("Missing Host header".into())
+                .unwrap_or_default(),
+        }
+    })
+}
+
+/// Serve `app` over HTTPS until Ctrl-C, plus the HTTP redirect listener if
+/// configured.
+#[cfg(feature = "tls")]
+pub async fn serve(app: Router, addr: SocketAddr, config: &TlsConfig) -> Result<(), String> {
+    let rustls_config =
+        axum_server::tls_rustls::RustlsConfig::from_config(Arc::new(config.server_config()?));
+
+    if let Some(redirect_addr) = &config.http_redirect_addr {
+        let listener = tokio::net::TcpListener::bind(redirect_addr)
+            .await
+            .map_err(|e| format!("Failed to bind {}: {}", redirect_addr, e))?;
+        info!("Redirecting http://{} to HTTPS", redirect_addr);
+        let redirect = redirect_router(addr.port());
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, redirect).await;
+        });
+    }
+
+    let handle = axum_server::Handle::new();
+    let shutdown = handle.clone();
+    tokio::spawn(async move {
+        let _ = tokio::signal::ctrl_c().await;
+        shutdown.graceful_shutdown(Some(Duration::from_secs(10)));
+    });
+
+    axum_server::bind_rustls(addr, rustls_config)
+        .handle(handle)
+        .serve(app.into_make_service())
+        .await
+        .map_err(|e| format!("HTTPS server error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sni() {
+        let entries =
+            TlsConfig::parse_sni("Play.Example.com=/certs/play.pem,/certs/play.key; ").unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, "play.example.com");
+        assert_eq!(entries[0].1.key, PathBuf::from("/certs/play.key"));
+
+        assert!(TlsConfig::parse_sni("play.example.com=/certs/play.pem").is_err());
+        assert!(TlsConfig::parse_sni("=/a.pem,/a.key").is_err());
+    }
+
+    #[test]
+    fn test_https_redirect_location() {
+        assert_eq!(
+            https_redirect_location(Some("play.example.com:80"), 443, "/rooms?x=1"),
+            Some("https://play.example.com/rooms?x=1".to_string())
+        );
+        assert_eq!(
+            https_redirect_location(Some("localhost:8080"), 3443, "/health"),
+            Some("https://localhost:3443/health".to_string())
+        );
+        assert_eq!(
+            https_redirect_location(Some("[::1]:80"), 443, "/"),
+            Some("https://[::1]/".to_string())
+        );
+        assert_eq!(https_redirect_location(None, 443, "/"), None);
+    }
+}
@@ -0,0 +1,45 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// Posts JSON to a URL someone gave the server, such as a digest webhook
+/// or a room's turn deadline channel
+pub trait WebhookSender: Send + Sync {
+    fn post(&self, url: &str, body: &serde_json::Value) -> Result<(), String>;
+}
+
+/// Posts with `curl`, like the rest of KZRK leans on tools already on the
+/// machine
+pub struct CurlWebhookSender;
+
+impl WebhookSender for CurlWebhookSender {
+    fn post(&self, url: &str, body: &serde_json::Value) -> Result<(), String> {
+        let json = serde_json::to_string(body)
+            .map_err(|e| format!("Failed to serialize webhook body: {}", e))?;
+        let output = Command::new("curl")
+            .args(["-sS", "--fail", "--max-time", "10", "-X", "POST"])
+            .args(["-H", "Content-Type: application/json"])
+            .args(["--data-binary", "@-", "--"])
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(mut stdin) = child.stdin.take() {
+                    stdin.write_all(json.as_bytes())?;
+                }
+                child.wait_with_output()
+            })
+            .map_err(|e| format!("Failed to run curl: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "Webhook failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+}
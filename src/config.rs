@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::systems::calendar::GameCalendar;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameConfig {
     pub starting_money: u32,
@@ -11,6 +13,9 @@ pub struct GameConfig {
     pub fuel_efficiency: f32,
     pub price_volatility_multiplier: f32,
     pub fuel_price_multiplier: f32,
+    /// The in-game date each turn falls on
+    #[serde(default)]
+    pub calendar: GameCalendar,
 }
 
 impl Default for GameConfig {
@@ -25,6 +30,7 @@ impl Default for GameConfig {
             fuel_efficiency: 10.0,
             price_volatility_multiplier: 1.0,
             fuel_price_multiplier: 1.0,
+            calendar: GameCalendar::default(),
         }
     }
 }
@@ -55,3 +61,34 @@ impl GameConfig {
         }
     }
 }
+
+/// The difficulty presets by name. Profiles store this rather than a full
+/// GameConfig so they pick up any rebalancing of the presets.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    #[allow(dead_code)]
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn config(&self) -> GameConfig {
+        match self {
+            Difficulty::Easy => GameConfig::easy(),
+            Difficulty::Normal => GameConfig::normal(),
+            Difficulty::Hard => GameConfig::hard(),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
@@ -1,8 +1,22 @@
 use std::collections::HashMap;
 
-use crate::models::Airport;
+use crate::models::{Airport, FuelEconomy, SecurityRating};
 
+/// Airports whose id has changed, old id first, so games saved before the
+/// change still load
+pub const AIRPORT_RENAMES: &[(&str, &str)] = &[];
+
+/// The airports games are played between: the world loaded with `--world`,
+/// or the built-in ones when there isn't one
 pub fn get_default_airports() -> HashMap<String, Airport> {
+    match super::world::installed() {
+        Some(world) => world.airport_map(),
+        None => builtin_airports(),
+    }
+}
+
+/// The airports compiled into the game
+pub fn builtin_airports() -> HashMap<String, Airport> {
     let mut airports = HashMap::new();
 
     airports.insert(
@@ -15,7 +29,9 @@ pub fn get_default_airports() -> HashMap<String, Airport> {
             vec!["electronics".to_string(), "luxury".to_string()],
             vec!["food".to_string(), "materials".to_string()],
             1.2,
-        ),
+        )
+        .with_fuel_economy(FuelEconomy::new(95, 0.15, 80, 115))
+        .with_landing_slots(2),
     );
 
     airports.insert(
@@ -28,7 +44,9 @@ pub fn get_default_airports() -> HashMap<String, Airport> {
             vec!["electronics".to_string(), "textiles".to_string()],
             vec!["industrial".to_string(), "materials".to_string()],
             1.1,
-        ),
+        )
+        .with_fuel_economy(FuelEconomy::new(85, 0.15, 70, 100))
+        .with_landing_slots(2),
     );
 
     airports.insert(
@@ -41,7 +59,9 @@ pub fn get_default_airports() -> HashMap<String, Airport> {
             vec!["food".to_string(), "luxury".to_string()],
             vec!["electronics".to_string(), "textiles".to_string()],
             0.9,
-        ),
+        )
+        .with_fuel_economy(FuelEconomy::new(65, 0.15, 55, 80))
+        .with_security(SecurityRating::Low),
     );
 
     airports.insert(
@@ -54,7 +74,9 @@ pub fn get_default_airports() -> HashMap<String, Airport> {
             vec!["industrial".to_string(), "food".to_string()],
             vec!["luxury".to_string(), "electronics".to_string()],
             1.0,
-        ),
+        )
+        .with_fuel_economy(FuelEconomy::new(65, 0.12, 55, 75))
+        .with_landing_slots(2),
     );
 
     airports.insert(
@@ -67,9 +89,11 @@ pub fn get_default_airports() -> HashMap<String, Airport> {
             vec!["materials".to_string(), "industrial".to_string()],
             vec!["luxury".to_string(), "food".to_string()],
             0.8,
-        ),
+        )
+        .with_fuel_economy(FuelEconomy::new(50, 0.10, 45, 60)),
     );
 
+    // Far from the refineries, so fuel is dear and swings widely
     airports.insert(
         "SEA".to_string(),
         Airport::new(
@@ -80,7 +104,8 @@ pub fn get_default_airports() -> HashMap<String, Airport> {
             vec!["electronics".to_string(), "food".to_string()],
             vec!["textiles".to_string(), "materials".to_string()],
             1.3,
-        ),
+        )
+        .with_fuel_economy(FuelEconomy::new(110, 0.25, 85, 150)),
     );
 
     airports
@@ -1,38 +1,53 @@
 use std::collections::HashMap;
 
-use crate::models::CargoType;
+use crate::models::{CargoCategory, CargoType};
 
+/// The cargo types traded: the loaded world's, or the built-in ones when
+/// there's no world or it brings none of its own
 pub fn get_default_cargo_types() -> HashMap<String, CargoType> {
+    match super::world::installed() {
+        Some(world) => world.cargo_type_map(),
+        None => builtin_cargo_types(),
+    }
+}
+
+/// The cargo types compiled into the game
+pub fn builtin_cargo_types() -> HashMap<String, CargoType> {
     let mut cargo_types = HashMap::new();
 
     cargo_types.insert(
         "electronics".to_string(),
-        CargoType::new("electronics", "Electronics", 500, 1, 0.4),
+        CargoType::new("electronics", "Electronics", 500, 1, 0.4)
+            .with_category(CargoCategory::Luxury),
     );
 
     cargo_types.insert(
         "food".to_string(),
-        CargoType::new("food", "Food & Beverages", 100, 2, 0.2),
+        CargoType::new("food", "Food & Beverages", 100, 2, 0.2)
+            .with_category(CargoCategory::Perishable),
     );
 
     cargo_types.insert(
         "textiles".to_string(),
-        CargoType::new("textiles", "Textiles", 200, 3, 0.25),
+        CargoType::new("textiles", "Textiles", 200, 3, 0.25)
+            .with_category(CargoCategory::Industrial),
     );
 
     cargo_types.insert(
         "industrial".to_string(),
-        CargoType::new("industrial", "Industrial Parts", 300, 5, 0.3),
+        CargoType::new("industrial", "Industrial Parts", 300, 5, 0.3)
+            .with_category(CargoCategory::Industrial),
     );
 
     cargo_types.insert(
         "luxury".to_string(),
-        CargoType::new("luxury", "Luxury Goods", 1000, 1, 0.5),
+        CargoType::new("luxury", "Luxury Goods", 1000, 1, 0.5).with_category(CargoCategory::Luxury),
     );
 
     cargo_types.insert(
         "materials".to_string(),
-        CargoType::new("materials", "Raw Materials", 50, 4, 0.15),
+        CargoType::new("materials", "Raw Materials", 50, 4, 0.15)
+            .with_category(CargoCategory::Industrial),
     );
 
     cargo_types
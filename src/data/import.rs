@@ -0,0 +1,198 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+use crate::models::{Airport, CargoType};
+
+/// Cargo types an imported airport produces, and as many again that it
+/// consumes
+const SPECIALIZATIONS: usize = 2;
+
+/// What to keep from an airports CSV
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Airport types to keep when the file has a `type` column, like
+    /// OurAirports' `large_airport`; empty keeps them all
+    pub types: Vec<String>,
+    /// Stop after this many airports
+    pub limit: Option<usize>,
+}
+
+/// Columns we know how to read, by header. Picks the first header present
+/// from each list, so both OurAirports exports and a plain
+/// `icao,name,lat,lon` file work.
+struct Columns {
+    id: usize,
+    name: usize,
+    lat: usize,
+    lon: usize,
+    kind: Option<usize>,
+}
+
+impl Columns {
+    fn find(header: &[String]) -> Result<Self, String> {
+        let position = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                header
+                    .iter()
+                    .position(|column| column.trim().eq_ignore_ascii_case(name))
+            })
+        };
+        let required = |names: &[&str]| {
+            position(names).ok_or_else(|| format!("The CSV needs a {} column", names.join("/")))
+        };
+
+        Ok(Self {
+            id: required(&[
+                "icao",
+                "icao_code",
+                "ident",
+                "gps_code",
+                "iata",
+                "iata_code",
+            ])?,
+            name: required(&["name"])?,
+            lat: required(&["latitude_deg", "latitude", "lat"])?,
+            lon: required(&["longitude_deg", "longitude", "lon", "lng"])?,
+            kind: position(&["type"]),
+        })
+    }
+}
+
+/// Split one CSV line, honoring double quotes (and `""` inside them)
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            },
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// A hash of the airport id that stays the same from run to run, so
+/// importing the same file twice gives the same world
+fn stable_seed(id: &str) -> u64 {
+    id.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Turn a CSV of real airports into airports for the game. Each gets a
+/// couple of cargo types it produces and a couple it consumes, and fuel
+/// that's dearer the farther it is from its nearest neighbor. Every
+/// airport is measured against every other, so keep big files filtered.
+pub fn airports_from_csv(
+    csv: &str,
+    options: &ImportOptions,
+    cargo_types: &HashMap<String, CargoType>,
+) -> Result<Vec<Airport>, String> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+    let header = split_csv_line(lines.next().ok_or("The CSV is empty")?);
+    let columns = Columns::find(&header)?;
+
+    let mut cargo_ids: Vec<&String> = cargo_types.keys().collect();
+    cargo_ids.sort();
+    if cargo_ids.len() < SPECIALIZATIONS * 2 {
+        return Err(format!(
+            "Generating markets needs at least {} cargo types",
+            SPECIALIZATIONS * 2
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let mut rows = Vec::new();
+    for (line_number, line) in lines.enumerate() {
+        let fields = split_csv_line(line);
+        let field = |index: usize| fields.get(index).map(|field| field.trim()).unwrap_or("");
+
+        if let Some(kind) = columns.kind
+            && !options.types.is_empty()
+            && !options.types.iter().any(|wanted| wanted == field(kind))
+        {
+            continue;
+        }
+
+        let id = field(columns.id).to_uppercase();
+        let name = field(columns.name);
+        if id.is_empty() || name.is_empty() || !seen.insert(id.clone()) {
+            continue;
+        }
+        let (Ok(lat), Ok(lon)) = (field(columns.lat).parse(), field(columns.lon).parse()) else {
+            return Err(format!(
+                "Line {}: {} has unreadable coordinates",
+                line_number + 2,
+                id
+            ));
+        };
+        rows.push((id, name.to_string(), (lat, lon)));
+
+        if options.limit.is_some_and(|limit| rows.len() >= limit) {
+            break;
+        }
+    }
+
+    let mut airports: Vec<Airport> = rows
+        .into_iter()
+        .map(|(id, name, coordinates)| {
+            let mut rng = StdRng::seed_from_u64(stable_seed(&id));
+            let mut picks = cargo_ids.clone();
+            picks.shuffle(&mut rng);
+            let produces = picks[..SPECIALIZATIONS]
+                .iter()
+                .map(|cargo_id| cargo_id.to_string())
+                .collect();
+            let consumes = picks[SPECIALIZATIONS..SPECIALIZATIONS * 2]
+                .iter()
+                .map(|cargo_id| cargo_id.to_string())
+                .collect();
+            let base_fuel_price = rng.gen_range(55..=65);
+            let fuel_modifier = rng.gen_range(80..=130) as f32 / 100.0;
+            Airport::new(
+                &id,
+                &name,
+                coordinates,
+                base_fuel_price,
+                produces,
+                consumes,
+                fuel_modifier,
+            )
+        })
+        .collect();
+
+    // Remote airports pay more to have fuel brought in, like SEA
+    let nearest_km: Vec<f64> = airports
+        .iter()
+        .enumerate()
+        .map(|(index, airport)| {
+            airports
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(_, other)| airport.distance_to(other))
+                .fold(f64::INFINITY, f64::min)
+        })
+        .collect();
+    for (airport, nearest_km) in airports.iter_mut().zip(nearest_km) {
+        if nearest_km.is_finite() {
+            airport.base_fuel_price += (nearest_km / 40.0).min(50.0) as u32;
+        }
+        airport.validate(cargo_types)?;
+    }
+
+    if airports.is_empty() {
+        return Err("No airports in the CSV matched".to_string());
+    }
+    Ok(airports)
+}
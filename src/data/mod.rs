@@ -1,5 +1,7 @@
 pub mod airports;
 pub mod cargo_types;
+pub mod import;
+pub mod world;
 
 pub use airports::get_default_airports;
 pub use cargo_types::get_default_cargo_types;
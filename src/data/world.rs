@@ -0,0 +1,150 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::OnceLock,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::cargo_types::builtin_cargo_types;
+use crate::models::{Airport, CargoType};
+
+/// The world every game in this process plays in, once one is loaded
+static WORLD: OnceLock<World> = OnceLock::new();
+
+/// How a world file is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldFormat {
+    Toml,
+    Json,
+}
+
+impl WorldFormat {
+    /// Picked from the file's extension
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Ok(WorldFormat::Toml),
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Ok(WorldFormat::Json),
+            _ => Err(format!(
+                "{}: world files must end in .toml or .json",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// A map shipped as a data file: the airports, and optionally the cargo
+/// types, games are played with instead of the built-in ones. Airports and
+/// cargo types have the same shape as a room's `custom_airports` and
+/// `custom_cargo_types`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct World {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub airports: Vec<Airport>,
+    /// Empty keeps the built-in cargo types
+    #[serde(default)]
+    pub cargo_types: Vec<CargoType>,
+}
+
+impl World {
+    pub fn parse(contents: &str, format: WorldFormat) -> Result<Self, String> {
+        let world: Self = match format {
+            WorldFormat::Toml => toml::from_str(contents).map_err(|e| e.to_string()),
+            WorldFormat::Json => serde_json::from_str(contents).map_err(|e| e.to_string()),
+        }
+        .map_err(|e| format!("Invalid world: {}", e))?;
+        world.validate()?;
+        Ok(world)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let format = WorldFormat::from_path(path)?;
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&contents, format).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// The same checks rooms make of uploaded worlds: at least two airports,
+    /// no duplicate ids and no airport trading cargo the world doesn't have
+    pub fn validate(&self) -> Result<(), String> {
+        if self.airports.len() < 2 {
+            return Err("A world needs at least two airports".to_string());
+        }
+        if let Some(id) = first_duplicate(self.airports.iter().map(|a| a.id.as_str())) {
+            return Err(format!("Airport {} is in the world twice", id));
+        }
+        if let Some(id) = first_duplicate(self.cargo_types.iter().map(|c| c.id.as_str())) {
+            return Err(format!("Cargo type {} is in the world twice", id));
+        }
+
+        for cargo_type in &self.cargo_types {
+            cargo_type.validate()?;
+        }
+        let cargo_types = self.cargo_type_map();
+        for airport in &self.airports {
+            airport.validate(&cargo_types)?;
+        }
+
+        Ok(())
+    }
+
+    /// What to call the world in logs and menus
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or("Custom world")
+    }
+
+    pub fn airport_map(&self) -> HashMap<String, Airport> {
+        self.airports
+            .iter()
+            .map(|airport| (airport.id.clone(), airport.clone()))
+            .collect()
+    }
+
+    pub fn cargo_type_map(&self) -> HashMap<String, CargoType> {
+        if self.cargo_types.is_empty() {
+            return builtin_cargo_types();
+        }
+
+        self.cargo_types
+            .iter()
+            .map(|cargo_type| (cargo_type.id.clone(), cargo_type.clone()))
+            .collect()
+    }
+}
+
+fn first_duplicate<'a>(ids: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let mut seen = HashSet::new();
+    ids.into_iter().find(|id| !seen.insert(*id))
+}
+
+/// Make `world` the one new games are played in, in place of the built-in
+/// airports and cargo types. A process gets one world.
+pub fn install(world: World) -> Result<&'static World, String> {
+    world.validate()?;
+    WORLD
+        .set(world)
+        .map_err(|_| "A world has already been loaded".to_string())?;
+    Ok(WORLD.get().expect("the world was just set"))
+}
+
+/// The world loaded with `--world`, if there is one
+pub fn installed() -> Option<&'static World> {
+    WORLD.get()
+}
+
+/// Load the world named by `--world <file>`, taking the flag out of `args`
+/// so commands see the arguments they expect. Returns None when there's no
+/// flag.
+pub fn init(args: &mut Vec<String>) -> Result<Option<&'static World>, String> {
+    let Some(index) = args.iter().position(|arg| arg == "--world") else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        return Err("Usage: kzrk --world <world.toml|world.json> [command]".to_string());
+    }
+
+    let path = args.remove(index + 1);
+    args.remove(index);
+    install(World::load(Path::new(&path))?).map(Some)
+}
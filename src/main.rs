@@ -7,7 +7,17 @@ mod ui;
 
 use std::env;
 
-use api::{multiplayer_service::MultiplayerGameService, routes::create_multiplayer_router};
+use api::{
+    cluster::ClusterConfig,
+    digests::CommandDigestSender,
+    multiplayer_service::{
+        DemoRoomPolicy, MultiplayerGameService, PersistencePolicy, SessionPolicy,
+    },
+    routes::create_multiplayer_router,
+    tls::TlsConfig,
+    webhooks::CurlWebhookSender,
+};
+use systems::{digest::DEFAULT_DIGEST_HOURS, multiplayer::GameSpeed};
 use tower_http::cors::CorsLayer;
 use tracing::{Level, info};
 use ui::TerminalUI;
@@ -20,12 +30,31 @@ async fn main() {
     // Initialize tracing
     tracing_subscriber::fmt().with_max_level(Level::INFO).init();
 
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    ui::style::init(&args);
+    match data::world::init(&mut args) {
+        Ok(Some(world)) => info!(
+            "Playing in {} ({} airports)",
+            world.label(),
+            world.airports.len()
+        ),
+        Ok(None) => {},
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        },
+    }
 
     if args.len() > 1 && args[1] == "api" {
         run_api_server().await;
     } else if args.len() > 1 && args[1] == "gui" {
         run_egui_game();
+    } else if args.len() > 1 && args[1] == "tui" {
+        run_tui(&args);
+    } else if args.len() > 1 && args[1] == "import-airports" {
+        run_import_airports(&args);
+    } else if args.len() > 1 && args[1] == "scenario" {
+        run_scenarios(&args);
     } else {
         run_cli_game();
     }
@@ -34,30 +63,312 @@ async fn main() {
 async fn run_api_server() {
     info!("Starting KZRK Multiplayer Game API server...");
 
-    let service = MultiplayerGameService::new();
-    let app = create_multiplayer_router(service).layer(CorsLayer::permissive());
+    // Instances that share a database (KZRK_DB_PATH) coordinate room
+    // ownership when KZRK_ADVERTISE_URL is set
+    let db_path = env::var("KZRK_DB_PATH").ok();
+    let mut service = match &db_path {
+        Some(db_path) => MultiplayerGameService::new_with_db_path(db_path),
+        None => MultiplayerGameService::new(),
+    };
+    if let Some(cluster) = ClusterConfig::from_env() {
+        info!(
+            "Clustering as instance {} ({}), room leases last {}s",
+            cluster.instance_id,
+            cluster.advertise_url,
+            cluster.lease_ttl.as_secs()
+        );
+        service = service.with_cluster(cluster);
+    }
+    service = service.with_admin_token(api::admin::AdminToken::from_env());
+    #[cfg(feature = "chaos")]
+    {
+        let faults = api::chaos::FaultInjector::from_env();
+        info!("Fault injection is built in: POST /admin/rooms/:room_id/faults to misbehave");
+        service = service.with_fault_injector(faults);
+    }
+    service = service.with_session_policy(SessionPolicy::from_env());
+    service = service.with_persistence_policy(PersistencePolicy::from_env());
+    let load_policy = api::load_shedding::LoadPolicy::from_env();
+    info!(
+        "Handling up to {} requests at once, each for up to {}s and {} bytes",
+        load_policy.max_concurrent_requests,
+        load_policy.request_timeout.as_secs(),
+        load_policy.max_body_bytes
+    );
+    service = service.with_load_policy(load_policy);
+
+    // Write changed rooms together instead of on every action
+    if let Some(save_interval) = service.flush_interval() {
+        info!("Saving changed rooms every {}s", save_interval.as_secs());
+        let saver = service.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(save_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = saver.flush_dirty_rooms() {
+                    tracing::warn!("Saving rooms failed: {}", e);
+                }
+            }
+        });
+    }
+
+    // Keep a recent copy of the database to restore from
+    if let Some(snapshot_interval) = service.persistence_policy().snapshot_interval {
+        let snapshot_path = env::var("KZRK_SNAPSHOT_PATH").unwrap_or_else(|_| {
+            format!(
+                "{}.snapshot",
+                db_path.as_deref().unwrap_or("kzrk_multiplayer.db")
+            )
+        });
+        info!(
+            "Snapshotting the database to {} every {} minutes",
+            snapshot_path,
+            snapshot_interval.as_secs() / 60
+        );
+        let snapshotter = service.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(snapshot_interval);
+            // The first tick is immediate; there's nothing new to copy yet
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let snapshotter = snapshotter.clone();
+                let snapshot_path = snapshot_path.clone();
+                let taken = tokio::task::spawn_blocking(move || {
+                    snapshotter.snapshot_database(std::path::Path::new(&snapshot_path))
+                })
+                .await;
+                match taken {
+                    Ok(Ok(())) => {},
+                    Ok(Err(e)) => tracing::warn!("Database snapshot failed: {}", e),
+                    Err(e) => tracing::warn!("Database snapshot panicked: {}", e),
+                }
+            }
+        });
+    }
+
+    // At most one digest per subscriber per period
+    let digest_hours = env::var("KZRK_DIGEST_HOURS")
+        .ok()
+        .and_then(|hours| hours.parse().ok())
+        .filter(|hours| *hours > 0)
+        .unwrap_or(DEFAULT_DIGEST_HOURS);
+    service = service.with_digest_period(chrono::Duration::hours(digest_hours));
+
+    // Sweep out sessions nobody has used within the TTL
+    let sweeper = service.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(sweeper.session_policy().sweep_interval);
+        loop {
+            interval.tick().await;
+            match sweeper.expire_sessions(chrono::Utc::now()) {
+                Ok(0) => {},
+                Ok(expired) => info!("Expired {} idle player sessions", expired),
+                Err(e) => tracing::warn!("Session sweep failed: {}", e),
+            }
+        }
+    });
+
+    // Rooms' real-time clocks, which have airport locals chat on quiet
+    // message boards. The interval is for normal speed; the loop runs at
+    // the fast speed's pace and each room ticks when its own speed is due.
+    let clocks = service.clone();
+    let chatter_interval = env::var("KZRK_NPC_CHATTER_SECONDS")
+        .ok()
+        .and_then(|seconds| seconds.parse().ok())
+        .filter(|seconds| *seconds > 0)
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(std::time::Duration::from_secs(300));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(GameSpeed::Fast.scale(chatter_interval));
+        loop {
+            // The scheduled time, so rooms tick on whole multiples of it
+            let now = interval.tick().await.into_std();
+            if let Err(e) = clocks.tick_room_clocks(chatter_interval, now) {
+                tracing::warn!("NPC chatter failed: {}", e);
+            }
+        }
+    });
+
+    // Daily digests for players following rooms from afar, checked hourly
+    let digests = service.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            let digests = digests.clone();
+            let sent = tokio::task::spawn_blocking(move || {
+                digests.send_digests(&CommandDigestSender, chrono::Utc::now())
+            })
+            .await;
+            match sent {
+                Ok(Ok(0)) => {},
+                Ok(Ok(sent)) => info!("Sent {} room digests", sent),
+                Ok(Err(e)) => tracing::warn!("Digest job failed: {}", e),
+                Err(e) => tracing::warn!("Digest job panicked: {}", e),
+            }
+        }
+    });
+
+    // Turn deadline warnings and misses for play-by-post rooms
+    let deadlines = service.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(5 * 60));
+        loop {
+            interval.tick().await;
+            let deadlines = deadlines.clone();
+            let sent = tokio::task::spawn_blocking(move || {
+                deadlines.send_deadline_alerts(&CurlWebhookSender, chrono::Utc::now())
+            })
+            .await;
+            match sent {
+                Ok(Ok(0)) => {},
+                Ok(Ok(sent)) => info!("Sent {} turn deadline alerts", sent),
+                Ok(Err(e)) => tracing::warn!("Deadline job failed: {}", e),
+                Err(e) => tracing::warn!("Deadline job panicked: {}", e),
+            }
+        }
+    });
+
+    // A demo room with bot traders, so the first visitor to an empty
+    // server has someone to play against
+    if let Some(demo) = DemoRoomPolicy::from_env() {
+        info!(
+            "Demo room on: {} bots taking a turn every {}s, starting over every {}h",
+            demo.bots,
+            demo.turn_interval.as_secs(),
+            demo.reset_interval.num_hours()
+        );
+        let demos = service.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(demo.turn_interval);
+            loop {
+                interval.tick().await;
+                let demos = demos.clone();
+                let demo = demo.clone();
+                let played = tokio::task::spawn_blocking(move || {
+                    demos.run_demo_room(&demo, chrono::Utc::now())
+                })
+                .await;
+                match played {
+                    Ok(Ok(_)) => {},
+                    Ok(Err(e)) => tracing::warn!("Demo room job failed: {}", e),
+                    Err(e) => tracing::warn!("Demo room job panicked: {}", e),
+                }
+            }
+        });
+    }
+
+    let app = create_multiplayer_router(service.clone()).layer(CorsLayer::permissive());
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
-        .await
-        .expect("Failed to bind to address");
+    // HTTPS when KZRK_TLS_CERT and KZRK_TLS_KEY are set
+    let tls = TlsConfig::from_env().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+    let scheme = if tls.is_some() { "https" } else { "http" };
 
-    info!("Multiplayer API server running on http://127.0.0.1:3000");
+    let bind_addr = env::var("KZRK_BIND_ADDR").unwrap_or_else(|_| "127.0.0.1:3000".to_string());
+    info!(
+        "Multiplayer API server running on {}://{}",
+        scheme, bind_addr
+    );
     info!("Endpoints:");
     info!("  GET  /health - Health check");
+    info!("  GET  /openapi.json - OpenAPI spec, including the error catalog");
+    #[cfg(feature = "swagger-ui")]
+    info!("  GET  /docs - Swagger UI for the spec");
     info!("  POST /rooms - Create new game room");
     info!("  GET  /rooms - List available rooms");
+    info!("  GET  /rooms/:room_id/settings - Full ruleset of a room");
     info!("  POST /rooms/:room_id/join - Join a room");
-    info!("  POST /rooms/:room_id/players/:player_id/leave - Leave room");
+    info!("  POST /rooms/:room_id/clone - Create a new room with the same settings");
+    info!("  POST /rooms/:room_id/players/:player_id/leave?force=true - Leave room");
+    info!("  POST /rooms/:room_id/players/:player_id/rematch - Play again after a win");
+    info!(
+        "  POST /rooms/:room_id/players/:player_id/rematch-vote - Vote to replay in the same room"
+    );
+    info!("  GET  /rooms/:room_id/stream - Spectator event stream (SSE, JSON patches)");
     info!("  GET  /rooms/:room_id/players/:player_id/state - Get room state");
+    info!(
+        "  GET  /rooms/:room_id/players/:player_id/travel-preview?dest=X - Itemized cost of a flight"
+    );
+    info!("  GET  /rooms/:room_id/players/:player_id/statistics - Trading and fuel economy stats");
     info!("  POST /rooms/:room_id/players/:player_id/travel - Travel to destination");
     info!("  POST /rooms/:room_id/players/:player_id/trade - Buy/sell cargo");
+    info!("  POST /rooms/:room_id/players/:player_id/trades - Several trades, all or none");
+    info!("  POST /rooms/:room_id/players/:player_id/transfer - Give money or cargo to a player");
+    info!(
+        "  GET  /rooms/:room_id/players/:player_id/route-profits - Routes ranked by profit per fuel"
+    );
+    info!("  GET  /rooms/:room_id/markets/:airport_id/history - An airport's recent prices");
+    info!("  POST /rooms/:room_id/players/:player_id/loadout - Load a saved cargo load-out");
     info!("  POST /rooms/:room_id/players/:player_id/fuel - Buy fuel");
+    info!("  GET  /rooms/:room_id/players/:player_id/insurance - Cover, losses and claims");
+    info!("  POST /rooms/:room_id/players/:player_id/insurance - Take out or cancel cover");
+    info!("  POST /rooms/:room_id/players/:player_id/claims - File a claim for lost cargo");
+    info!("  GET  /rooms/:room_id/players/:player_id/hangar - Aircraft upgrades for sale");
+    info!("  POST /rooms/:room_id/players/:player_id/upgrade - Buy an aircraft upgrade");
+    info!("  GET  /rooms/:room_id/players/:player_id/stalls - Vendor stalls at your airport");
+    info!("  POST /rooms/:room_id/players/:player_id/stalls/:stall_id/buy - Buy from a stall");
+    info!("  GET  /rooms/:room_id/players/:player_id/loan - Loans and credit at the bank");
+    info!("  POST /rooms/:room_id/players/:player_id/loan - Borrow from the bank");
+    info!("  POST /rooms/:room_id/players/:player_id/loan/repay - Pay a loan off early");
+    info!("  POST /rooms/:room_id/players/:player_id/digest - Daily digest by webhook or email");
+    info!("  DELETE /rooms/:room_id/players/:player_id/digest - Stop the daily digest");
+    info!("  GET  /rooms/:room_id/players/:player_id/messages/archive - Older board messages");
+    info!(
+        "  POST /rooms/:room_id/players/:player_id/turn-deadline - Turn deadline webhook (host only)"
+    );
+    info!("  POST /players/:player_name/profile/loadouts - Save a cargo load-out");
+    info!("  DELETE /players/:player_name/profile/loadouts/:name - Forget a cargo load-out");
+    info!("  GET  /admin/rooms/:room_id/modifiers - Price and fee modifiers in a room");
+    info!("  POST /admin/rooms/:room_id/modifiers - Start a price or fee modifier");
+    info!("  DELETE /admin/rooms/:room_id/modifiers/:modifier_id - Stop a modifier early");
+    info!("  POST /sessions/:player_id/logout - End a player session");
+    info!("  POST /templates - Save a room template");
+    info!("  GET  /templates - List room templates");
+    info!("  GET  /server-info - Server and reference data versions");
     info!("  GET  /airports - List available airports");
     info!("  GET  /cargo - List available cargo types");
 
-    axum::serve(listener, app)
-        .await
-        .expect("Failed to start server");
+    if let Some(tls) = tls {
+        serve_https(app, &bind_addr, tls).await;
+    } else {
+        let listener = tokio::net::TcpListener::bind(&bind_addr)
+            .await
+            .expect("Failed to bind to address");
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await
+            .expect("Failed to start server");
+    }
+
+    // Don't lose batched saves, and let other instances pick up our rooms
+    // straight away
+    if let Err(e) = service.flush_dirty_rooms() {
+        tracing::warn!("Saving rooms on shutdown failed: {}", e);
+    }
+    service.release_room_leases();
+}
+
+#[cfg(feature = "tls")]
+async fn serve_https(app: axum::Router, bind_addr: &str, tls: TlsConfig) {
+    let addr = bind_addr
+        .parse()
+        .expect("KZRK_BIND_ADDR must be an ip:port address when serving HTTPS");
+    if let Err(e) = api::tls::serve(app, addr, &tls).await {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "tls"))]
+async fn serve_https(_app: axum::Router, _bind_addr: &str, _tls: TlsConfig) {
+    eprintln!("HTTPS is configured but TLS support isn't built in. Compile with --features tls");
+    std::process::exit(1);
 }
 
 #[cfg(feature = "gui")]
@@ -85,6 +396,216 @@ fn run_egui_game() {
     std::process::exit(1);
 }
 
+/// `kzrk tui` plays locally, `kzrk tui --real-time 30` with a turn passing
+/// every 30 seconds; `kzrk tui <server> <room_id> <player_id>` takes a seat
+/// in a multiplayer room
+#[cfg(feature = "tui")]
+fn run_tui(args: &[String]) {
+    use ui::tui::{LocalBackend, ServerBackend};
+
+    let run_local = |real_time: Option<u32>| {
+        let save_dir = systems::SaveSystem::get_save_directory()
+            .unwrap_or_else(|_| std::env::temp_dir().join("kzrk_saves"));
+        let mut backend = LocalBackend::resume_or_new(save_dir);
+        if let Some(seconds) = real_time {
+            backend = backend.with_real_time(seconds);
+        }
+        ui::tui::run(backend)
+    };
+    let result = match &args[2..] {
+        [] => run_local(None),
+        [flag, seconds] if flag == "--real-time" => {
+            match systems::realtime::parse_turn_seconds(seconds) {
+                Ok(seconds) => run_local(Some(seconds)),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                },
+            }
+        },
+        [server, room_id, player_id, ..] => {
+            let (Ok(room_id), Ok(player_id)) = (
+                uuid::Uuid::parse_str(room_id),
+                uuid::Uuid::parse_str(player_id),
+            ) else {
+                eprintln!("Room and player IDs must be UUIDs");
+                std::process::exit(1);
+            };
+            ui::tui::run(ServerBackend::new(server.clone(), room_id, player_id))
+        },
+        _ => {
+            eprintln!(
+                "Usage: kzrk tui [--real-time <seconds>] | kzrk tui <server> <room_id> <player_id>"
+            );
+            std::process::exit(1);
+        },
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to run the TUI: {}", e);
+    }
+}
+
+#[cfg(not(feature = "tui"))]
+fn run_tui(_args: &[String]) {
+    eprintln!("TUI feature not enabled. Compile with --features tui");
+    std::process::exit(1);
+}
+
+const IMPORT_USAGE: &str = "Usage: kzrk import-airports <airports.csv> [--out <airports.json>] \
+     [--types <type,...>|--all-types] [--limit <n>]";
+
+/// `kzrk import-airports airports.csv` turns a CSV of real airports (an
+/// OurAirports export, or just icao,name,lat,lon) into JSON airports a host
+/// can use as a room's `custom_airports`
+fn run_import_airports(args: &[String]) {
+    use data::import::{ImportOptions, airports_from_csv};
+
+    let fail = |message: &str| -> ! {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    };
+
+    let Some(csv_path) = args.get(2).filter(|arg| !arg.starts_with("--")) else {
+        fail(IMPORT_USAGE);
+    };
+    // Only the big airports by default; OurAirports lists every airstrip
+    let mut options = ImportOptions {
+        types: vec!["large_airport".to_string()],
+        limit: Some(systems::multiplayer::MAX_ROOM_AIRPORTS),
+    };
+    let mut out_path = None;
+    let mut index = 3;
+    while index < args.len() {
+        if args[index] == "--all-types" {
+            options.types.clear();
+            index += 1;
+            continue;
+        }
+        match (args[index].as_str(), args.get(index + 1)) {
+            ("--out", Some(path)) => out_path = Some(path.clone()),
+            ("--types", Some(types)) => {
+                options.types = types.split(',').map(|t| t.trim().to_string()).collect();
+            },
+            ("--limit", Some(limit)) => match limit.parse() {
+                Ok(0) => options.limit = None,
+                Ok(limit) => options.limit = Some(limit),
+                Err(_) => fail("--limit takes a number (0 for no limit)"),
+            },
+            _ => fail(IMPORT_USAGE),
+        }
+        index += 2;
+    }
+
+    let csv = std::fs::read_to_string(csv_path)
+        .unwrap_or_else(|e| fail(&format!("Can't read {}: {}", csv_path, e)));
+    let airports = airports_from_csv(&csv, &options, &data::get_default_cargo_types())
+        .unwrap_or_else(|e| fail(&format!("Import failed: {}", e)));
+    let json = serde_json::to_string_pretty(&airports)
+        .unwrap_or_else(|e| fail(&format!("Couldn't write the airports: {}", e)));
+
+    match out_path {
+        Some(path) => {
+            std::fs::write(&path, json)
+                .unwrap_or_else(|e| fail(&format!("Can't write {}: {}", path, e)));
+            eprintln!("Imported {} airports into {}", airports.len(), path);
+        },
+        None => println!("{}", json),
+    }
+    if airports.len() > systems::multiplayer::MAX_ROOM_AIRPORTS {
+        eprintln!(
+            "Rooms take at most {} custom airports; import fewer with --limit to use these in one",
+            systems::multiplayer::MAX_ROOM_AIRPORTS
+        );
+    }
+}
+
+const SCENARIO_USAGE: &str = "Usage: kzrk scenario <scenario.json|directory>... [--server <url>]";
+
+/// `kzrk scenario tests/scenarios` plays scripted scenarios on a local game,
+/// or with `--server` in a fresh room on a running multiplayer server, and
+/// exits non-zero if any step didn't go as its file expects
+fn run_scenarios(args: &[String]) {
+    use systems::scenario::{self, LocalTarget, Scenario, ScenarioTarget};
+
+    let fail = |message: &str| -> ! {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    };
+
+    let mut server = None;
+    let mut paths = Vec::new();
+    let mut index = 2;
+    while index < args.len() {
+        match (args[index].as_str(), args.get(index + 1)) {
+            ("--server", Some(url)) => {
+                server = Some(url.clone());
+                index += 1;
+            },
+            (arg, _) if arg.starts_with("--") => fail(SCENARIO_USAGE),
+            (path, _) => paths.push(std::path::PathBuf::from(path)),
+        }
+        index += 1;
+    }
+    if paths.is_empty() {
+        fail(SCENARIO_USAGE);
+    }
+
+    // A directory stands for every .json file in it
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut found: Vec<_> = std::fs::read_dir(&path)
+                .unwrap_or_else(|e| fail(&format!("Can't read {}: {}", path.display(), e)))
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|file| file.extension().is_some_and(|ext| ext == "json"))
+                .collect();
+            found.sort();
+            files.extend(found);
+        } else {
+            files.push(path);
+        }
+    }
+
+    let mut failed = 0;
+    for file in &files {
+        let scenario = Scenario::load(file).unwrap_or_else(|e| fail(&e));
+        let mut target: Box<dyn ScenarioTarget> = match &server {
+            Some(url) => Box::new(api::scenario_server::ServerTarget::new(url)),
+            None => Box::new(LocalTarget::new()),
+        };
+        match scenario::run(&scenario, target.as_mut()) {
+            Ok(report) if report.passed() => {
+                println!("PASS {} ({} steps)", report.name, report.steps_run)
+            },
+            Ok(report) => {
+                failed += 1;
+                if let Some(failure) = report.failure {
+                    println!(
+                        "FAIL {} at step {} ({:?}): {}",
+                        report.name,
+                        failure.step,
+                        failure.action,
+                        failure.problems.join("; ")
+                    );
+                }
+            },
+            Err(e) => {
+                failed += 1;
+                println!("ERROR {}: {}", scenario.name, e);
+            },
+        }
+    }
+
+    println!(
+        "{} of {} scenarios passed",
+        files.len() - failed,
+        files.len()
+    );
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
 fn run_cli_game() {
     println!("Starting KZRK CLI game...");
     TerminalUI::run_game_loop();
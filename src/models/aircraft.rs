@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Times each upgrade can be bought
+pub const MAX_UPGRADE_LEVEL: u8 = 3;
+
+/// Something the hangar can fit to an aircraft, a level at a time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum AircraftUpgrade {
+    /// 50 more units of fuel a level
+    FuelTank,
+    /// 250 kg more cargo a level
+    CargoHold,
+    /// 1.5 km more per unit of fuel a level
+    FuelEfficiency,
+    /// Less time in the air, so a quarter less chance a level of losing
+    /// cargo in flight
+    Engines,
+}
+
+impl AircraftUpgrade {
+    pub const ALL: [AircraftUpgrade; 4] = [
+        AircraftUpgrade::FuelTank,
+        AircraftUpgrade::CargoHold,
+        AircraftUpgrade::FuelEfficiency,
+        AircraftUpgrade::Engines,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AircraftUpgrade::FuelTank => "Bigger fuel tank",
+            AircraftUpgrade::CargoHold => "Larger cargo hold",
+            AircraftUpgrade::FuelEfficiency => "Efficient engines",
+            AircraftUpgrade::Engines => "Faster engines",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            AircraftUpgrade::FuelTank => "+50 fuel capacity",
+            AircraftUpgrade::CargoHold => "+250 kg cargo capacity",
+            AircraftUpgrade::FuelEfficiency => "+1.5 km per unit of fuel",
+            AircraftUpgrade::Engines => "-25% chance of losing cargo in flight",
+        }
+    }
+
+    /// What the first level costs; each level after costs that much again
+    pub fn base_cost(self) -> u32 {
+        match self {
+            AircraftUpgrade::FuelTank => 4_000,
+            AircraftUpgrade::CargoHold => 6_000,
+            AircraftUpgrade::FuelEfficiency => 8_000,
+            AircraftUpgrade::Engines => 5_000,
+        }
+    }
+
+    /// Price of taking the upgrade to `level`
+    pub fn cost(self, level: u8) -> u32 {
+        self.base_cost() * level as u32
+    }
+}
+
+/// The upgrades fitted to a pilot's aircraft, by level
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Aircraft {
+    #[serde(default)]
+    pub fuel_tank: u8,
+    #[serde(default)]
+    pub cargo_hold: u8,
+    #[serde(default)]
+    pub fuel_efficiency: u8,
+    #[serde(default)]
+    pub engines: u8,
+}
+
+impl Aircraft {
+    pub fn level(&self, upgrade: AircraftUpgrade) -> u8 {
+        match upgrade {
+            AircraftUpgrade::FuelTank => self.fuel_tank,
+            AircraftUpgrade::CargoHold => self.cargo_hold,
+            AircraftUpgrade::FuelEfficiency => self.fuel_efficiency,
+            AircraftUpgrade::Engines => self.engines,
+        }
+    }
+
+    fn level_mut(&mut self, upgrade: AircraftUpgrade) -> &mut u8 {
+        match upgrade {
+            AircraftUpgrade::FuelTank => &mut self.fuel_tank,
+            AircraftUpgrade::CargoHold => &mut self.cargo_hold,
+            AircraftUpgrade::FuelEfficiency => &mut self.fuel_efficiency,
+            AircraftUpgrade::Engines => &mut self.engines,
+        }
+    }
+
+    /// What the next level of `upgrade` costs, or None once it's maxed out
+    pub fn next_cost(&self, upgrade: AircraftUpgrade) -> Option<u32> {
+        let level = self.level(upgrade);
+        (level < MAX_UPGRADE_LEVEL).then(|| upgrade.cost(level + 1))
+    }
+
+    /// Count a level of `upgrade` as fitted, returning the new level
+    pub fn fit(&mut self, upgrade: AircraftUpgrade) -> u8 {
+        let level = self.level_mut(upgrade);
+        *level = (*level + 1).min(MAX_UPGRADE_LEVEL);
+        *level
+    }
+
+    /// What every level fitted so far cost in all
+    pub fn invested(&self) -> u32 {
+        AircraftUpgrade::ALL
+            .iter()
+            .flat_map(|upgrade| (1..=self.level(*upgrade)).map(|level| upgrade.cost(level)))
+            .fold(0, u32::saturating_add)
+    }
+
+    /// How much of the usual chance of losing cargo in flight remains
+    pub fn transit_risk(&self) -> f32 {
+        1.0 - 0.25 * self.engines.min(MAX_UPGRADE_LEVEL) as f32
+    }
+}
@@ -1,19 +1,142 @@
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::models::CargoType;
+
+/// Fuel pricing at one airport. Each market refresh picks a price within
+/// `volatility` of `base_price`, never leaving `min_price..=max_price`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct FuelEconomy {
+    pub base_price: u32,
+    pub volatility: f32, // 0.15 = up to 15% either side of the base price
+    pub min_price: u32,
+    pub max_price: u32,
+}
+
+/// How well an airport's apron is watched at night
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum SecurityRating {
+    /// Aircraft parked outside overnight get robbed now and then
+    Low,
+    #[default]
+    Standard,
+}
+
+impl SecurityRating {
+    /// Chance that an aircraft parked outside here overnight is robbed
+    pub fn theft_chance(self) -> f32 {
+        match self {
+            SecurityRating::Low => 0.2,
+            SecurityRating::Standard => 0.0,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SecurityRating::Low => "Low",
+            SecurityRating::Standard => "Standard",
+        }
+    }
+}
+
+/// Where a fuel price sits within an airport's range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FuelPriceLevel {
+    Cheap,
+    Normal,
+    Expensive,
+}
+
+impl FuelPriceLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FuelPriceLevel::Cheap => "Cheap",
+            FuelPriceLevel::Normal => "Normal",
+            FuelPriceLevel::Expensive => "Expensive",
+        }
+    }
+}
+
+impl FuelEconomy {
+    pub fn new(base_price: u32, volatility: f32, min_price: u32, max_price: u32) -> Self {
+        Self {
+            base_price,
+            volatility,
+            min_price,
+            max_price,
+        }
+    }
+
+    /// The economy airports without explicit fuel settings have always had:
+    /// the base price times the fuel modifier, give or take 15%.
+    pub fn from_base_and_modifier(base_fuel_price: u32, fuel_modifier: f32) -> Self {
+        let base_price = (base_fuel_price as f32 * fuel_modifier) as u32;
+        let volatility = 0.15;
+        Self {
+            base_price,
+            volatility,
+            min_price: ((base_price as f32 * (1.0 - volatility)) as u32).max(1),
+            max_price: (base_price as f32 * (1.0 + volatility)).ceil() as u32,
+        }
+    }
+
+    pub fn clamp(&self, price: u32) -> u32 {
+        price.clamp(self.min_price, self.max_price).max(1)
+    }
+
+    pub fn sample_price(&self, rng: &mut impl Rng) -> u32 {
+        let modifier = if self.volatility > 0.0 {
+            1.0 + rng.gen_range(-self.volatility..self.volatility)
+        } else {
+            1.0
+        };
+        self.clamp((self.base_price as f32 * modifier) as u32)
+    }
+
+    /// Cheap in the bottom third of the range, expensive in the top third
+    pub fn level(&self, price: u32) -> FuelPriceLevel {
+        let span = self.max_price.saturating_sub(self.min_price);
+        if price < self.min_price + span / 3 {
+            FuelPriceLevel::Cheap
+        } else if price > self.max_price - span / 3 {
+            FuelPriceLevel::Expensive
+        } else {
+            FuelPriceLevel::Normal
+        }
+    }
+
+    pub fn range_label(&self) -> String {
+        format!("${}-${}", self.min_price, self.max_price)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct MarketProfile {
     pub produces: Vec<String>, // Cargo types with lower buy prices
     pub consumes: Vec<String>, // Cargo types with higher sell prices
     pub fuel_modifier: f32,    // Multiplier for base fuel price (1.0 = normal)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct Airport {
     pub id: String,
     pub name: String,
     pub coordinates: (f64, f64),
     pub base_fuel_price: u32,
     pub market_profile: MarketProfile,
+    /// Explicit fuel pricing; airports without one derive it from
+    /// `base_fuel_price` and the market profile's fuel modifier.
+    #[serde(default)]
+    pub fuel_economy: Option<FuelEconomy>,
+    /// Landings allowed per slot period at a busy hub; None for airports
+    /// anyone can land at whenever they like
+    #[serde(default)]
+    pub landing_slots: Option<u32>,
+    /// How likely aircraft parked outside overnight are to be robbed
+    #[serde(default)]
+    pub security: SecurityRating,
 }
 
 pub struct AirportConfig {
@@ -24,6 +147,7 @@ pub struct AirportConfig {
     pub produces: Vec<String>,
     pub consumes: Vec<String>,
     pub fuel_modifier: f32,
+    pub fuel_economy: Option<FuelEconomy>,
 }
 
 impl Airport {
@@ -45,10 +169,83 @@ impl Airport {
             produces,
             consumes,
             fuel_modifier,
+            fuel_economy: None,
         };
         Self::from_config(config)
     }
 
+    pub fn with_fuel_economy(mut self, fuel_economy: FuelEconomy) -> Self {
+        self.fuel_economy = Some(fuel_economy);
+        self
+    }
+
+    pub fn with_landing_slots(mut self, landing_slots: u32) -> Self {
+        self.landing_slots = Some(landing_slots);
+        self
+    }
+
+    pub fn with_security(mut self, security: SecurityRating) -> Self {
+        self.security = security;
+        self
+    }
+
+    pub fn fuel_economy(&self) -> FuelEconomy {
+        self.fuel_economy.unwrap_or_else(|| {
+            FuelEconomy::from_base_and_modifier(
+                self.base_fuel_price,
+                self.market_profile.fuel_modifier,
+            )
+        })
+    }
+
+    /// Sanity checks for host-uploaded airports. Produced and consumed cargo
+    /// must be among `cargo_types`.
+    pub fn validate(&self, cargo_types: &HashMap<String, CargoType>) -> Result<(), String> {
+        if self.id.trim().is_empty() || self.name.trim().is_empty() {
+            return Err("Airports need an id and a name".to_string());
+        }
+
+        let (lat, lon) = self.coordinates;
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            return Err(format!("Airport {} has invalid coordinates", self.id));
+        }
+
+        if self.base_fuel_price == 0 || self.market_profile.fuel_modifier <= 0.0 {
+            return Err(format!("Airport {} needs a fuel price above zero", self.id));
+        }
+
+        if let Some(economy) = &self.fuel_economy
+            && (economy.min_price == 0
+                || economy.min_price > economy.base_price
+                || economy.base_price > economy.max_price
+                || !(0.0..=1.0).contains(&economy.volatility))
+        {
+            return Err(format!("Airport {} has an invalid fuel economy", self.id));
+        }
+
+        if self.landing_slots == Some(0) {
+            return Err(format!(
+                "Airport {} needs at least one landing slot",
+                self.id
+            ));
+        }
+
+        if let Some(unknown) = self
+            .market_profile
+            .produces
+            .iter()
+            .chain(&self.market_profile.consumes)
+            .find(|cargo_id| !cargo_types.contains_key(*cargo_id))
+        {
+            return Err(format!(
+                "Airport {} trades unknown cargo type: {}",
+                self.id, unknown
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn from_config(config: AirportConfig) -> Self {
         Self {
             id: config.id,
@@ -60,23 +257,30 @@ impl Airport {
                 consumes: config.consumes,
                 fuel_modifier: config.fuel_modifier,
             },
+            fuel_economy: config.fuel_economy,
+            landing_slots: None,
+            security: SecurityRating::default(),
         }
     }
 
+    /// Great-circle kilometers to `other`
     pub fn distance_to(&self, other: &Airport) -> f64 {
-        let (lat1, lon1) = self.coordinates;
-        let (lat2, lon2) = other.coordinates;
+        great_circle_km(self.coordinates, other.coordinates)
+    }
+}
 
-        // Haversine formula for distance between two points on Earth
-        let r = 6371.0; // Earth's radius in kilometers
-        let dlat = (lat2 - lat1).to_radians();
-        let dlon = (lon2 - lon1).to_radians();
-        let lat1 = lat1.to_radians();
-        let lat2 = lat2.to_radians();
+/// Kilometers between two (latitude, longitude) points along the Earth's
+/// surface, by the haversine formula. Every distance in the game, on the
+/// server and in the clients, comes from here.
+pub fn great_circle_km((lat1, lon1): (f64, f64), (lat2, lon2): (f64, f64)) -> f64 {
+    let r = 6371.0; // Earth's radius in kilometers
+    let dlat = (lat2 - lat1).to_radians();
+    let dlon = (lon2 - lon1).to_radians();
+    let lat1 = lat1.to_radians();
+    let lat2 = lat2.to_radians();
 
-        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
-        let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
 
-        r * c
-    }
+    r * c
 }
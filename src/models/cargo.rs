@@ -1,14 +1,62 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Broad kind of goods. Market events can hit a whole category at once,
+/// and the trading screens filter and sort by it.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
+pub enum CargoCategory {
+    Perishable,
+    Industrial,
+    Luxury,
+    /// Anything not put in a category, such as custom cargo uploaded
+    /// without one
+    #[default]
+    General,
+}
+
+impl CargoCategory {
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub const ALL: [CargoCategory; 4] = [
+        CargoCategory::Perishable,
+        CargoCategory::Industrial,
+        CargoCategory::Luxury,
+        CargoCategory::General,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CargoCategory::Perishable => "Perishable",
+            CargoCategory::Industrial => "Industrial",
+            CargoCategory::Luxury => "Luxury",
+            CargoCategory::General => "General",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct CargoType {
     pub id: String,
     pub name: String,
     pub base_price: u32,
     pub weight_per_unit: u32,
     pub volatility: f32,
+    #[serde(default)]
+    pub category: CargoCategory,
 }
 
 impl CargoType {
@@ -25,19 +73,53 @@ impl CargoType {
             base_price,
             weight_per_unit,
             volatility,
+            category: CargoCategory::default(),
+        }
+    }
+
+    pub fn with_category(mut self, category: CargoCategory) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Sanity checks for host-uploaded cargo types
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() || self.name.trim().is_empty() {
+            return Err("Cargo types need an id and a name".to_string());
+        }
+
+        if self.base_price == 0 || self.weight_per_unit == 0 {
+            return Err(format!(
+                "Cargo type {} needs a price and a weight greater than zero",
+                self.id
+            ));
         }
+
+        if !(0.0..=1.0).contains(&self.volatility) {
+            return Err(format!(
+                "Cargo type {} volatility must be between 0 and 1",
+                self.id
+            ));
+        }
+
+        Ok(())
     }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct CargoInventory {
     inventory: HashMap<String, u32>,
+    /// Average price paid per unit of the cargo held, for cargo that was
+    /// bought rather than handed over
+    #[serde(default)]
+    cost_basis: HashMap<String, u32>,
 }
 
 impl CargoInventory {
     pub fn new() -> Self {
         Self {
             inventory: HashMap::new(),
+            cost_basis: HashMap::new(),
         }
     }
 
@@ -46,7 +128,31 @@ impl CargoInventory {
     }
 
     pub fn add_cargo(&mut self, cargo_id: &str, quantity: u32) {
-        *self.inventory.entry(cargo_id.to_string()).or_insert(0) += quantity;
+        let current = self.inventory.entry(cargo_id.to_string()).or_insert(0);
+        *current = current.saturating_add(quantity);
+    }
+
+    /// Add cargo bought at `unit_cost`, folding it into the average cost of
+    /// what's already held
+    pub fn add_cargo_at(&mut self, cargo_id: &str, quantity: u32, unit_cost: u32) {
+        if quantity == 0 {
+            return;
+        }
+        let average = match self.cost_basis.get(cargo_id) {
+            Some(&average) => {
+                let held = self.get_quantity(cargo_id) as u64;
+                let total = average as u64 * held + unit_cost as u64 * quantity as u64;
+                (total / (held + quantity as u64)) as u32
+            },
+            None => unit_cost,
+        };
+        self.cost_basis.insert(cargo_id.to_string(), average);
+        self.add_cargo(cargo_id, quantity);
+    }
+
+    /// What the cargo held cost per unit on average, if it was bought
+    pub fn average_cost(&self, cargo_id: &str) -> Option<u32> {
+        self.cost_basis.get(cargo_id).copied()
     }
 
     pub fn remove_cargo(&mut self, cargo_id: &str, quantity: u32) -> bool {
@@ -56,6 +162,7 @@ impl CargoInventory {
             *current -= quantity;
             if *current == 0 {
                 self.inventory.remove(cargo_id);
+                self.cost_basis.remove(cargo_id);
             }
             return true;
         }
@@ -68,10 +175,10 @@ impl CargoInventory {
             .map(|(cargo_id, quantity)| {
                 cargo_types
                     .get(cargo_id)
-                    .map(|cargo_type| cargo_type.weight_per_unit * quantity)
+                    .map(|cargo_type| cargo_type.weight_per_unit.saturating_mul(*quantity))
                     .unwrap_or(0)
             })
-            .sum()
+            .fold(0, u32::saturating_add)
     }
 
     pub fn get_all_cargo(&self) -> &HashMap<String, u32> {
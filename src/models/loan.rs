@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Money borrowed from the bank, paid back an installment a turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Loan {
+    pub id: u32,
+    /// What was borrowed
+    pub principal: u32,
+    /// Still owed, interest and late fees included
+    pub balance: u32,
+    /// Taken from cash at the end of each turn
+    pub installment: u32,
+    pub taken_at_turn: u32,
+    /// Turns the installment couldn't be paid in full
+    #[serde(default)]
+    pub missed_payments: u32,
+}
+
+/// A pilot's loans, and whether the bank has already foreclosed on them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoanAccount {
+    #[serde(default)]
+    pub loans: Vec<Loan>,
+    #[serde(default)]
+    pub next_id: u32,
+    /// Declared bankrupt; the bank won't lend again
+    #[serde(default)]
+    pub bankrupt: bool,
+}
+
+impl LoanAccount {
+    /// Everything owed across all loans
+    pub fn debt(&self) -> u32 {
+        self.loans
+            .iter()
+            .map(|loan| loan.balance)
+            .fold(0, u32::saturating_add)
+    }
+
+    pub fn get(&self, loan_id: u32) -> Option<&Loan> {
+        self.loans.iter().find(|loan| loan.id == loan_id)
+    }
+
+    pub fn get_mut(&mut self, loan_id: u32) -> Option<&mut Loan> {
+        self.loans.iter_mut().find(|loan| loan.id == loan_id)
+    }
+}
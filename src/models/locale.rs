@@ -0,0 +1,409 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A language the server and clients can render messages in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::Spanish];
+
+    /// The language tag sent in Accept-Language and Content-Language
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+        }
+    }
+
+    /// The locale for a language tag or locale name like `es`, `es-MX` or
+    /// `es_ES.UTF-8`
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.tag() == language)
+    }
+
+    /// The best locale for an Accept-Language header: the supported
+    /// language with the highest weight, or English
+    pub fn negotiate(accept_language: &str) -> Locale {
+        let mut best: Option<(Locale, f32)> = None;
+        for range in accept_language.split(',') {
+            let mut parts = range.split(';');
+            let tag = parts.next().unwrap_or_default().trim();
+            let weight = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())
+                .unwrap_or(0.0);
+            let locale = if tag == "*" {
+                Some(Locale::English)
+            } else {
+                Locale::from_tag(tag)
+            };
+            if let Some(locale) = locale
+                && weight > 0.0
+                && best.is_none_or(|(_, best_weight)| weight > best_weight)
+            {
+                best = Some((locale, weight));
+            }
+        }
+        best.map(|(locale, _)| locale).unwrap_or_default()
+    }
+
+    /// The player's locale: KZRK_LANG, or else the system's LC_ALL,
+    /// LC_MESSAGES or LANG
+    #[cfg_attr(not(any(feature = "gui", feature = "tui")), allow(dead_code))]
+    pub fn current() -> Locale {
+        ["KZRK_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.trim().is_empty())
+            .and_then(|value| Locale::from_tag(&value))
+            .unwrap_or_default()
+    }
+}
+
+/// A sentence the server can send with a code, so clients can show it in
+/// their own language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum MessageCode {
+    RoomNotFound,
+    PlayerNotInRoom,
+    DestinationNotFound,
+    GameOver,
+    GameWon,
+    ActionCooldown,
+    InsufficientFunds,
+    InsufficientCargo,
+    InsufficientCapacity,
+    CargoNotAvailable,
+    InvalidQuantity,
+    FuelUnavailable,
+    AmountTooLarge,
+    TurnCapReached,
+    ResaleCooldown,
+    InsufficientFuel,
+    FuelOutage,
+    FuelLowStock,
+    FuelUnaffordable,
+    FuelTankFull,
+    ReadyToFly,
+    ReadyByRoad,
+    MoneyShort,
+    FuelShort,
+    FuelAndMoneyShort,
+    NoLandingSlots,
+    Bought,
+    Sold,
+    SoldAndWon,
+    TradesMade,
+    TradesMadeAndWon,
+    FuelPurchased,
+    Traveled,
+    GroundTransfer,
+    GaveMoney,
+    GaveCargo,
+    GaveMoneyAndCargo,
+    GaveMoneyAndWon,
+    GaveMoneyAndCargoAndWon,
+}
+
+impl MessageCode {
+    pub const ALL: [MessageCode; 39] = [
+        MessageCode::RoomNotFound,
+        MessageCode::PlayerNotInRoom,
+        MessageCode::DestinationNotFound,
+        MessageCode::GameOver,
+        MessageCode::GameWon,
+        MessageCode::ActionCooldown,
+        MessageCode::InsufficientFunds,
+        MessageCode::InsufficientCargo,
+        MessageCode::InsufficientCapacity,
+        MessageCode::CargoNotAvailable,
+        MessageCode::InvalidQuantity,
+        MessageCode::FuelUnavailable,
+        MessageCode::AmountTooLarge,
+        MessageCode::TurnCapReached,
+        MessageCode::ResaleCooldown,
+        MessageCode::InsufficientFuel,
+        MessageCode::FuelOutage,
+        MessageCode::FuelLowStock,
+        MessageCode::FuelUnaffordable,
+        MessageCode::FuelTankFull,
+        MessageCode::ReadyToFly,
+        MessageCode::ReadyByRoad,
+        MessageCode::MoneyShort,
+        MessageCode::FuelShort,
+        MessageCode::FuelAndMoneyShort,
+        MessageCode::NoLandingSlots,
+        MessageCode::Bought,
+        MessageCode::Sold,
+        MessageCode::SoldAndWon,
+        MessageCode::TradesMade,
+        MessageCode::TradesMadeAndWon,
+        MessageCode::FuelPurchased,
+        MessageCode::Traveled,
+        MessageCode::GroundTransfer,
+        MessageCode::GaveMoney,
+        MessageCode::GaveCargo,
+        MessageCode::GaveMoneyAndCargo,
+        MessageCode::GaveMoneyAndWon,
+        MessageCode::GaveMoneyAndCargoAndWon,
+    ];
+
+    /// The sentence in `locale`, with `{name}` where each parameter goes
+    pub fn template(self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::English => self.english(),
+            Locale::Spanish => self.spanish(),
+        }
+    }
+
+    fn english(self) -> &'static str {
+        match self {
+            MessageCode::RoomNotFound => "Room not found",
+            MessageCode::PlayerNotInRoom => "Player not in room",
+            MessageCode::DestinationNotFound => "Destination airport not found",
+            MessageCode::GameOver => "Game over",
+            MessageCode::GameWon => "Game over - {winner} won with ${money}",
+            MessageCode::ActionCooldown => "Too many actions: try again in {retry_after_ms} ms",
+            MessageCode::InsufficientFunds => "Insufficient funds",
+            MessageCode::InsufficientCargo => "Insufficient cargo to sell",
+            MessageCode::InsufficientCapacity => "Insufficient cargo capacity",
+            MessageCode::CargoNotAvailable => "Cargo type not available at this market",
+            MessageCode::InvalidQuantity => "Quantity must be greater than zero",
+            MessageCode::FuelUnavailable => "No fuel for sale here right now",
+            MessageCode::AmountTooLarge => "That trade is too large to price",
+            MessageCode::TurnCapReached => {
+                "The broker will only handle {remaining} more units of that cargo this turn"
+            },
+            MessageCode::ResaleCooldown => {
+                "Cargo bought here can't be sold back here for {turns} more turn(s); {sellable} units can be sold now"
+            },
+            MessageCode::InsufficientFuel => "Insufficient fuel. Need {needed} units, have {have}",
+            MessageCode::FuelOutage => "No fuel at {airport} for {turns} more turn(s)",
+            MessageCode::FuelLowStock => "Only {quantity} units of fuel in stock",
+            MessageCode::FuelUnaffordable => "Insufficient funds for fuel purchase",
+            MessageCode::FuelTankFull => "Fuel tank can only hold {space} more units",
+            MessageCode::ReadyToFly => "Ready to fly: {fuel} fuel left on arrival",
+            MessageCode::ReadyByRoad => "Ready to go by road: ${money} left on arrival",
+            MessageCode::MoneyShort => "Not enough money: ${money} short",
+            MessageCode::FuelShort => "Not enough fuel: {fuel} units short",
+            MessageCode::FuelAndMoneyShort => {
+                "Not enough fuel or money: {fuel} units and ${money} short"
+            },
+            MessageCode::NoLandingSlots => {
+                "No landing slots at {airport} this period or next: divert elsewhere"
+            },
+            MessageCode::Bought => {
+                "Successfully bought {quantity} units of {cargo} (${fee} broker fee)"
+            },
+            MessageCode::Sold => {
+                "Successfully sold {quantity} units of {cargo} (${fee} broker fee)"
+            },
+            MessageCode::SoldAndWon => {
+                "Successfully sold {quantity} units of {cargo} (${fee} broker fee) - ${money} reached, you won the game!"
+            },
+            MessageCode::TradesMade => "Made {count} trades (${fees} in broker fees)",
+            MessageCode::TradesMadeAndWon => {
+                "Made {count} trades (${fees} in broker fees) - ${money} reached, you won the game!"
+            },
+            MessageCode::FuelPurchased => "Purchased {quantity} units of fuel for ${cost}",
+            MessageCode::Traveled => "Traveled to {airport_name} ({airport})",
+            MessageCode::GroundTransfer => {
+                "Took the ground transfer to {airport_name} ({airport}) for ${fare}"
+            },
+            MessageCode::GaveMoney => "Gave ${money} to {recipient} at {airport}",
+            MessageCode::GaveCargo => "Gave {quantity} {cargo} to {recipient} at {airport}",
+            MessageCode::GaveMoneyAndCargo => {
+                "Gave ${money} and {quantity} {cargo} to {recipient} at {airport}"
+            },
+            MessageCode::GaveMoneyAndWon => {
+                "Gave ${money} to {recipient} at {airport} - {recipient} won the game!"
+            },
+            MessageCode::GaveMoneyAndCargoAndWon => {
+                "Gave ${money} and {quantity} {cargo} to {recipient} at {airport} - {recipient} won the game!"
+            },
+        }
+    }
+
+    fn spanish(self) -> &'static str {
+        match self {
+            MessageCode::RoomNotFound => "Sala no encontrada",
+            MessageCode::PlayerNotInRoom => "El jugador no está en la sala",
+            MessageCode::DestinationNotFound => "Aeropuerto de destino no encontrado",
+            MessageCode::GameOver => "Fin de la partida",
+            MessageCode::GameWon => "Fin de la partida: {winner} ganó con ${money}",
+            MessageCode::ActionCooldown => {
+                "Demasiadas acciones: inténtalo de nuevo en {retry_after_ms} ms"
+            },
+            MessageCode::InsufficientFunds => "Fondos insuficientes",
+            MessageCode::InsufficientCargo => "No tienes tanta carga para vender",
+            MessageCode::InsufficientCapacity => "No cabe tanta carga en la bodega",
+            MessageCode::CargoNotAvailable => "Esa carga no se vende en este mercado",
+            MessageCode::InvalidQuantity => "La cantidad debe ser mayor que cero",
+            MessageCode::FuelUnavailable => "Ahora mismo no hay combustible a la venta aquí",
+            MessageCode::AmountTooLarge => "Esa operación es demasiado grande para cotizarla",
+            MessageCode::TurnCapReached => {
+                "El corredor solo acepta {remaining} unidades más de esa carga este turno"
+            },
+            MessageCode::ResaleCooldown => {
+                "La carga comprada aquí no se puede revender aquí durante {turns} turno(s) más; ahora puedes vender {sellable} unidades"
+            },
+            MessageCode::InsufficientFuel => {
+                "Combustible insuficiente. Necesitas {needed} unidades y tienes {have}"
+            },
+            MessageCode::FuelOutage => {
+                "No hay combustible en {airport} durante {turns} turno(s) más"
+            },
+            MessageCode::FuelLowStock => "Solo quedan {quantity} unidades de combustible",
+            MessageCode::FuelUnaffordable => "Fondos insuficientes para comprar combustible",
+            MessageCode::FuelTankFull => "En el depósito solo caben {space} unidades más",
+            MessageCode::ReadyToFly => "Listo para volar: llegarás con {fuel} de combustible",
+            MessageCode::ReadyByRoad => "Listo para ir por carretera: llegarás con ${money}",
+            MessageCode::MoneyShort => "Dinero insuficiente: faltan ${money}",
+            MessageCode::FuelShort => "Combustible insuficiente: faltan {fuel} unidades",
+            MessageCode::FuelAndMoneyShort => {
+                "Combustible y dinero insuficientes: faltan {fuel} unidades y ${money}"
+            },
+            MessageCode::NoLandingSlots => {
+                "No hay franjas de aterrizaje en {airport} ni en este periodo ni en el siguiente: desvíate a otro aeropuerto"
+            },
+            MessageCode::Bought => "Compraste {quantity} unidades de {cargo} (${fee} de comisión)",
+            MessageCode::Sold => "Vendiste {quantity} unidades de {cargo} (${fee} de comisión)",
+            MessageCode::SoldAndWon => {
+                "Vendiste {quantity} unidades de {cargo} (${fee} de comisión): ¡llegaste a ${money} y ganaste la partida!"
+            },
+            MessageCode::TradesMade => "Hiciste {count} operaciones (${fees} en comisiones)",
+            MessageCode::TradesMadeAndWon => {
+                "Hiciste {count} operaciones (${fees} en comisiones): ¡llegaste a ${money} y ganaste la partida!"
+            },
+            MessageCode::FuelPurchased => {
+                "Compraste {quantity} unidades de combustible por ${cost}"
+            },
+            MessageCode::Traveled => "Volaste a {airport_name} ({airport})",
+            MessageCode::GroundTransfer => {
+                "Fuiste por tierra a {airport_name} ({airport}) por ${fare}"
+            },
+            MessageCode::GaveMoney => "Diste ${money} a {recipient} en {airport}",
+            MessageCode::GaveCargo => "Diste {quantity} {cargo} a {recipient} en {airport}",
+            MessageCode::GaveMoneyAndCargo => {
+                "Diste ${money} y {quantity} {cargo} a {recipient} en {airport}"
+            },
+            MessageCode::GaveMoneyAndWon => {
+                "Diste ${money} a {recipient} en {airport}: ¡{recipient} ganó la partida!"
+            },
+            MessageCode::GaveMoneyAndCargoAndWon => {
+                "Diste ${money} y {quantity} {cargo} a {recipient} en {airport}: ¡{recipient} ganó la partida!"
+            },
+        }
+    }
+}
+
+/// A coded sentence and its parameters. `detail` is any text that followed
+/// it in the message without a code of its own, such as the night's
+/// report, and is shown as sent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LocalizedMessage {
+    pub code: MessageCode,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub detail: String,
+}
+
+impl LocalizedMessage {
+    pub fn new(code: MessageCode) -> Self {
+        Self {
+            code,
+            params: BTreeMap::new(),
+            detail: String::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// This sentence as the start of `message`, keeping the rest as detail
+    pub fn leading(mut self, message: &str) -> Self {
+        let sentence = self.render(Locale::English);
+        self.detail = message
+            .strip_prefix(sentence.as_str())
+            .unwrap_or_default()
+            .to_string();
+        self
+    }
+
+    /// The sentence in `locale`, followed by any detail
+    pub fn render(&self, locale: Locale) -> String {
+        let mut text = String::new();
+        for segment in segments(self.code.template(locale)) {
+            match segment {
+                Segment::Text(literal) => text.push_str(literal),
+                Segment::Param(name) => {
+                    text.push_str(self.params.get(name).map_or("", String::as_str))
+                },
+            }
+        }
+        text.push_str(&self.detail);
+        text
+    }
+}
+
+impl std::fmt::Display for LocalizedMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(Locale::English))
+    }
+}
+
+/// A response message that may come with a code to render it from
+#[cfg_attr(not(any(feature = "gui", feature = "tui")), allow(dead_code))]
+pub trait Localized {
+    fn message(&self) -> &str;
+    fn localized(&self) -> Option<&LocalizedMessage>;
+
+    /// The message in `locale`, or as sent when it has no code
+    fn message_in(&self, locale: Locale) -> String {
+        self.localized()
+            .map(|localized| localized.render(locale))
+            .unwrap_or_else(|| self.message().to_string())
+    }
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Param(&'a str),
+}
+
+fn segments(template: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(length) = rest[start..].find('}') else {
+            break;
+        };
+        if start > 0 {
+            segments.push(Segment::Text(&rest[..start]));
+        }
+        segments.push(Segment::Param(&rest[start + 1..start + length]));
+        rest = &rest[start + length + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    segments
+}
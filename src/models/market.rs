@@ -1,13 +1,51 @@
-use std::{collections::HashMap, time::SystemTime};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::SystemTime,
+};
 
 use serde::{Deserialize, Serialize};
 
+/// Fuel an airport keeps on hand, and how much arrives each turn
+pub const FUEL_STOCK_CAPACITY: u32 = 1000;
+pub const FUEL_RESTOCK_PER_TURN: u32 = 100;
+
+/// Turns of prices a market remembers
+pub const PRICE_HISTORY_TURNS: usize = 20;
+
+fn full_fuel_stock() -> u32 {
+    FUEL_STOCK_CAPACITY
+}
+
+fn no_fee_adjustment() -> f64 {
+    1.0
+}
+
+/// A market's prices as they stood at the end of one world tick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceSnapshot {
+    pub tick: u32,
+    pub fuel_price: u32,
+    pub cargo_prices: HashMap<String, u32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Market {
     pub airport_id: String,
     pub fuel_price: u32,
     pub cargo_prices: HashMap<String, u32>,
     pub last_updated: SystemTime,
+    /// Purchases draw this down; deliveries top it up every turn
+    #[serde(default = "full_fuel_stock")]
+    pub fuel_stock: u32,
+    /// Deliveries have stopped and the pumps are dry until the outage ends
+    #[serde(default)]
+    pub fuel_outage: bool,
+    /// Broker fees here are scaled by this while a global modifier runs
+    #[serde(default = "no_fee_adjustment")]
+    pub fee_multiplier: f64,
+    /// The last `PRICE_HISTORY_TURNS` snapshots, oldest first
+    #[serde(default)]
+    pub price_history: VecDeque<PriceSnapshot>,
 }
 
 impl Market {
@@ -17,9 +55,45 @@ impl Market {
             fuel_price,
             cargo_prices: HashMap::new(),
             last_updated: SystemTime::now(),
+            fuel_stock: FUEL_STOCK_CAPACITY,
+            fuel_outage: false,
+            fee_multiplier: no_fee_adjustment(),
+            price_history: VecDeque::new(),
         }
     }
 
+    /// How much fuel can be bought here right now
+    pub fn fuel_available(&self) -> u32 {
+        if self.fuel_outage { 0 } else { self.fuel_stock }
+    }
+
+    /// Take fuel out of stock for a purchase, returning how much was taken
+    pub fn draw_fuel(&mut self, quantity: u32) -> u32 {
+        let drawn = quantity.min(self.fuel_available());
+        self.fuel_stock -= drawn;
+        drawn
+    }
+
+    /// One turn's delivery, unless an outage has cut supply off
+    pub fn restock_fuel(&mut self) {
+        if !self.fuel_outage {
+            self.fuel_stock = (self.fuel_stock + FUEL_RESTOCK_PER_TURN).min(FUEL_STOCK_CAPACITY);
+        }
+    }
+
+    pub fn start_fuel_outage(&mut self) {
+        self.fuel_outage = true;
+        self.fuel_stock = 0;
+        self.last_updated = SystemTime::now();
+    }
+
+    /// Supply is back: the first delivery refills the tanks
+    pub fn end_fuel_outage(&mut self) {
+        self.fuel_outage = false;
+        self.fuel_stock = FUEL_STOCK_CAPACITY;
+        self.last_updated = SystemTime::now();
+    }
+
     pub fn set_cargo_price(&mut self, cargo_id: &str, price: u32) {
         self.cargo_prices.insert(cargo_id.to_string(), price);
         self.last_updated = SystemTime::now();
@@ -37,6 +111,26 @@ impl Market {
     pub fn get_all_cargo_prices(&self) -> &HashMap<String, u32> {
         &self.cargo_prices
     }
+
+    /// Remember today's prices as `tick`'s, dropping the oldest once the
+    /// history is full. Recording the same tick twice keeps the later one.
+    pub fn record_prices(&mut self, tick: u32) {
+        if self
+            .price_history
+            .back()
+            .is_some_and(|snapshot| snapshot.tick == tick)
+        {
+            self.price_history.pop_back();
+        }
+        self.price_history.push_back(PriceSnapshot {
+            tick,
+            fuel_price: self.fuel_price,
+            cargo_prices: self.cargo_prices.clone(),
+        });
+        while self.price_history.len() > PRICE_HISTORY_TURNS {
+            self.price_history.pop_front();
+        }
+    }
 }
 
 #[cfg(test)]
@@ -79,6 +173,45 @@ mod tests {
         assert_eq!(all_prices.get("textiles"), Some(&300));
     }
 
+    #[test]
+    fn test_price_history_keeps_the_latest_turns() {
+        let mut market = Market::new("JFK", 100);
+        for tick in 1..=(PRICE_HISTORY_TURNS as u32 + 5) {
+            market.set_cargo_price("electronics", tick * 10);
+            market.record_prices(tick);
+        }
+        assert_eq!(market.price_history.len(), PRICE_HISTORY_TURNS);
+        assert_eq!(market.price_history.front().unwrap().tick, 6);
+
+        // A second recording of a tick replaces the first
+        market.set_cargo_price("electronics", 1);
+        market.record_prices(PRICE_HISTORY_TURNS as u32 + 5);
+        assert_eq!(market.price_history.len(), PRICE_HISTORY_TURNS);
+        let latest = market.price_history.back().unwrap();
+        assert_eq!(latest.cargo_prices["electronics"], 1);
+    }
+
+    #[test]
+    fn test_fuel_stock_and_outages() {
+        let mut market = Market::new("JFK", 100);
+        assert_eq!(
+            market.draw_fuel(FUEL_STOCK_CAPACITY + 50),
+            FUEL_STOCK_CAPACITY
+        );
+        assert_eq!(market.fuel_available(), 0);
+
+        market.restock_fuel();
+        assert_eq!(market.fuel_available(), FUEL_RESTOCK_PER_TURN);
+
+        market.start_fuel_outage();
+        market.restock_fuel();
+        assert_eq!(market.fuel_available(), 0);
+        assert_eq!(market.draw_fuel(10), 0);
+
+        market.end_fuel_outage();
+        assert_eq!(market.fuel_available(), FUEL_STOCK_CAPACITY);
+    }
+
     #[test]
     fn test_last_updated_changes() {
         let mut market = Market::new("JFK", 100);
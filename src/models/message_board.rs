@@ -1,6 +1,11 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::systems::{courier::CourierLetter, errors::RoomError};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub id: Uuid,
@@ -9,22 +14,410 @@ pub struct Message {
     pub content: String,
     pub airport_id: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Posted by one of the airport's locals rather than a player
+    #[serde(default)]
+    pub is_npc: bool,
+    /// Airport a courier carried the message from, when it wasn't posted here
+    #[serde(default)]
+    pub couriered_from: Option<String>,
+    /// Older saves' messages are all general chat
+    #[serde(default)]
+    pub topic: MessageTopic,
+}
+
+/// The channels each airport's board is split into
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
+pub enum MessageTopic {
+    #[default]
+    General,
+    TradeTips,
+    Warnings,
+}
+
+impl MessageTopic {
+    pub const ALL: [MessageTopic; 3] = [
+        MessageTopic::General,
+        MessageTopic::TradeTips,
+        MessageTopic::Warnings,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MessageTopic::General => "General",
+            MessageTopic::TradeTips => "Trade Tips",
+            MessageTopic::Warnings => "Warnings",
+        }
+    }
+}
+
+impl Message {
+    /// Whether the message @mentions a player: an @ followed by their name,
+    /// in any case, and not running on into a longer word
+    pub fn mentions(&self, player_name: &str) -> bool {
+        let content = self.content.to_lowercase();
+        let mention = format!("@{}", player_name.trim().to_lowercase());
+        if mention.len() == 1 {
+            return false;
+        }
+
+        content.match_indices(&mention).any(|(start, _)| {
+            !content[start + mention.len()..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_')
+        })
+    }
+}
+
+/// What a notification in a player's feed is about
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationKind {
+    /// A board post that @mentions the player
+    #[default]
+    Mention,
+    /// A notice to the whole room
+    Announcement,
+    /// Cargo the player holds is now worth well over what they paid
+    PriceAlert,
+    /// Another pilot arrived where the player is
+    PlayerArrived,
+    /// The player's turn deadline is close or has gone by
+    TurnDeadline,
+}
+
+/// Something in a player's feed: a board post that @mentions them, a room
+/// announcement, a price alert, another pilot's arrival or a turn deadline.
+/// Each waits there, wherever the player happens to be, until marked read.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "StoredNotification")]
+pub struct Notification {
+    pub id: Uuid,
+    pub message_id: Uuid,
+    pub author_id: Uuid,
+    pub author_name: String,
+    pub airport_id: String,
+    pub content: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub read: bool,
+    pub kind: NotificationKind,
+    /// The player's own turn when it arrived
+    pub turn_number: Option<u32>,
+}
+
+/// How a notification is loaded. Ones saved before they had a kind marked
+/// room-wide notices with an `announcement` flag instead.
+#[derive(Deserialize)]
+struct StoredNotification {
+    id: Uuid,
+    message_id: Uuid,
+    author_id: Uuid,
+    author_name: String,
+    airport_id: String,
+    content: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    read: bool,
+    #[serde(default)]
+    announcement: bool,
+    #[serde(default)]
+    kind: Option<NotificationKind>,
+    #[serde(default)]
+    turn_number: Option<u32>,
+}
+
+impl From<StoredNotification> for Notification {
+    fn from(stored: StoredNotification) -> Self {
+        let kind = stored.kind.unwrap_or(if stored.announcement {
+            NotificationKind::Announcement
+        } else {
+            NotificationKind::Mention
+        });
+        Self {
+            id: stored.id,
+            message_id: stored.message_id,
+            author_id: stored.author_id,
+            author_name: stored.author_name,
+            airport_id: stored.airport_id,
+            content: stored.content,
+            created_at: stored.created_at,
+            read: stored.read,
+            kind,
+            turn_number: stored.turn_number,
+        }
+    }
+}
+
+impl Notification {
+    pub fn for_message(message: &Message) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            message_id: message.id,
+            author_id: message.author_id,
+            author_name: message.author_name.clone(),
+            airport_id: message.airport_id.clone(),
+            content: message.content.clone(),
+            created_at: message.created_at,
+            read: false,
+            kind: NotificationKind::Mention,
+            turn_number: None,
+        }
+    }
+
+    /// A notice from `author_id` to everyone in the room; it isn't tied to
+    /// a message or an airport
+    pub fn announcement(author_id: Uuid, author_name: String, content: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            message_id: Uuid::nil(),
+            author_id,
+            author_name,
+            airport_id: String::new(),
+            content,
+            created_at: chrono::Utc::now(),
+            read: false,
+            kind: NotificationKind::Announcement,
+            turn_number: None,
+        }
+    }
+
+    /// Word from Air Traffic Control about something that happened to the
+    /// player, at `airport_id`
+    pub fn notice(kind: NotificationKind, airport_id: &str, content: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            message_id: Uuid::nil(),
+            author_id: Uuid::nil(),
+            author_name: "Air Traffic Control".to_string(),
+            airport_id: airport_id.to_string(),
+            content,
+            created_at: chrono::Utc::now(),
+            read: false,
+            kind,
+            turn_number: None,
+        }
+    }
+}
+
+/// How much a board accepts and keeps. Rooms choose these at creation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(default)]
+pub struct BoardLimits {
+    pub max_message_length: usize,
+    /// Messages each topic keeps at an airport, unless `topic_limits` says
+    /// otherwise for it
+    pub max_messages_per_airport: usize,
+    pub post_cooldown_seconds: u64, // 0 disables the cooldown
+    pub topic_limits: BTreeMap<MessageTopic, usize>,
+    /// Messages are taken down this many days after they were posted; None
+    /// keeps them until newer ones push them out
+    pub max_message_age_days: Option<u32>,
+}
+
+impl Default for BoardLimits {
+    fn default() -> Self {
+        Self {
+            max_message_length: 500,
+            max_messages_per_airport: 50,
+            post_cooldown_seconds: 0,
+            topic_limits: BTreeMap::new(),
+            max_message_age_days: None,
+        }
+    }
 }
 
+impl BoardLimits {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_message_length == 0 {
+            return Err("Maximum message length must be greater than zero".to_string());
+        }
+
+        if self.max_messages_per_airport == 0 {
+            return Err("Message boards must keep at least one message per airport".to_string());
+        }
+
+        if let Some((topic, _)) = self.topic_limits.iter().find(|(_, limit)| **limit == 0) {
+            return Err(format!(
+                "The {} topic must keep at least one message per airport",
+                topic.label()
+            ));
+        }
+
+        if self.max_message_age_days == Some(0) {
+            return Err("Messages must stay up for at least a day".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Messages `topic` keeps at each airport
+    pub fn limit_for(&self, topic: MessageTopic) -> usize {
+        self.topic_limits
+            .get(&topic)
+            .copied()
+            .unwrap_or(self.max_messages_per_airport)
+    }
+
+    /// Whether a message has been up longer than boards keep them
+    pub fn is_expired(&self, message: &Message, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.max_message_age_days.is_some_and(|days| {
+            now.signed_duration_since(message.created_at) >= chrono::Duration::days(days as i64)
+        })
+    }
+}
+
+/// Each airport's board, kept apart so reading, counting and trimming one
+/// never touches the others. Saved as a single list, as boards always were.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(from = "StoredBoard", into = "StoredBoard")]
 pub struct MessageBoard {
+    /// Messages at each airport, oldest first
+    airports: BTreeMap<String, Vec<Message>>,
+    limits: BoardLimits,
+    /// Messages trimmed off since the last `take_evicted`, oldest first
+    evicted: Vec<Message>,
+}
+
+/// How a board is saved
+#[derive(Serialize, Deserialize)]
+struct StoredBoard {
     messages: Vec<Message>,
-    max_messages: usize,
+    #[serde(default)]
+    limits: BoardLimits,
+}
+
+impl From<StoredBoard> for MessageBoard {
+    fn from(stored: StoredBoard) -> Self {
+        let mut board = MessageBoard::with_limits(stored.limits);
+        for message in stored.messages {
+            board
+                .airports
+                .entry(message.airport_id.clone())
+                .or_default()
+                .push(message);
+        }
+        for messages in board.airports.values_mut() {
+            messages.sort_by_key(|message| message.created_at);
+        }
+        board
+    }
+}
+
+impl From<MessageBoard> for StoredBoard {
+    fn from(board: MessageBoard) -> Self {
+        let mut messages: Vec<Message> = board.airports.into_values().flatten().collect();
+        messages.sort_by_key(|message| message.created_at);
+        StoredBoard {
+            messages,
+            limits: board.limits,
+        }
+    }
 }
 
 impl MessageBoard {
-    pub fn new(max_messages: usize) -> Self {
+    pub fn new(max_messages_per_airport: usize) -> Self {
+        Self::with_limits(BoardLimits {
+            max_messages_per_airport,
+            ..BoardLimits::default()
+        })
+    }
+
+    pub fn with_limits(limits: BoardLimits) -> Self {
         Self {
-            messages: Vec::new(),
-            max_messages,
+            airports: BTreeMap::new(),
+            limits,
+            evicted: Vec::new(),
+        }
+    }
+
+    /// Messages the retention limit has pushed off the board since this was
+    /// last called, oldest first
+    pub fn take_evicted(&mut self) -> Vec<Message> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    pub fn limits(&self) -> &BoardLimits {
+        &self.limits
+    }
+
+    /// Change the limits of an existing board, dropping anything the new
+    /// retention limit no longer has room for.
+    pub fn set_limits(&mut self, limits: BoardLimits) {
+        self.limits = limits;
+
+        let airports: Vec<String> = self.airports.keys().cloned().collect();
+        for airport_id in airports {
+            self.trim_airport(&airport_id);
         }
+        self.expire(chrono::Utc::now());
     }
 
+    /// Take down every message older than the board's age limit, archiving
+    /// it like one pushed out by newer posts
+    pub fn expire(&mut self, now: chrono::DateTime<chrono::Utc>) {
+        if self.limits.max_message_age_days.is_none() {
+            return;
+        }
+        for messages in self.airports.values_mut() {
+            // Oldest first, so the expired ones are at the front
+            let expired = messages
+                .iter()
+                .take_while(|message| self.limits.is_expired(message, now))
+                .count();
+            self.evicted.extend(messages.drain(..expired));
+        }
+        self.airports.retain(|_, messages| !messages.is_empty());
+    }
+
+    /// Take a message off whichever board it's on
+    pub fn remove_message(&mut self, message_id: &Uuid) -> Option<Message> {
+        let (airport_id, index) = self.airports.iter().find_map(|(airport_id, messages)| {
+            let index = messages.iter().position(|m| m.id == *message_id)?;
+            Some((airport_id.clone(), index))
+        })?;
+        let messages = self.airports.get_mut(&airport_id)?;
+        let message = messages.remove(index);
+        if messages.is_empty() {
+            self.airports.remove(&airport_id);
+        }
+        Some(message)
+    }
+
+    pub fn find_message(&self, message_id: &Uuid) -> Option<&Message> {
+        self.airports
+            .values()
+            .flatten()
+            .find(|message| message.id == *message_id)
+    }
+
+    /// Whether `content` fits on the board
+    pub fn check_content(&self, content: &str) -> Result<(), String> {
+        if content.is_empty() {
+            return Err("Message content cannot be empty".to_string());
+        }
+
+        if content.chars().count() > self.limits.max_message_length {
+            return Err(format!(
+                "Message content cannot exceed {} characters",
+                self.limits.max_message_length
+            ));
+        }
+        Ok(())
+    }
+
+    /// Post under the General topic
     pub fn post_message(
         &mut self,
         author_id: Uuid,
@@ -32,12 +425,36 @@ impl MessageBoard {
         content: String,
         airport_id: String,
     ) -> Result<Message, String> {
-        if content.is_empty() {
-            return Err("Message content cannot be empty".to_string());
+        self.post_to_topic(
+            author_id,
+            author_name,
+            content,
+            airport_id,
+            MessageTopic::General,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn post_to_topic(
+        &mut self,
+        author_id: Uuid,
+        author_name: String,
+        content: String,
+        airport_id: String,
+        topic: MessageTopic,
+    ) -> Result<Message, String> {
+        self.check_content(&content)?;
+        if airport_id.is_empty() {
+            return Err("Messages have to be posted at an airport".to_string());
         }
 
-        if content.len() > 500 {
-            return Err("Message content cannot exceed 500 characters".to_string());
+        let now = chrono::Utc::now();
+        if let Some(wait) = self.cooldown_remaining(&author_id, now) {
+            return Err(format!(
+                "You can post again in {} second{}",
+                wait,
+                if wait == 1 { "" } else { "s" }
+            ));
         }
 
         let message = Message {
@@ -46,41 +463,200 @@ impl MessageBoard {
             author_name,
             content,
             airport_id,
+            created_at: now,
+            is_npc: false,
+            couriered_from: None,
+            topic,
+        };
+
+        self.pin(message.clone());
+        Ok(message)
+    }
+
+    /// Post on behalf of an airport local. These skip the player limits on
+    /// length and cooldown but still count towards retention.
+    pub fn post_npc_message(
+        &mut self,
+        author_name: String,
+        content: String,
+        airport_id: String,
+        topic: MessageTopic,
+    ) -> Message {
+        let message = Message {
+            id: Uuid::new_v4(),
+            author_id: Uuid::nil(),
+            author_name,
+            content,
+            airport_id,
+            created_at: chrono::Utc::now(),
+            is_npc: true,
+            couriered_from: None,
+            topic,
+        };
+
+        self.pin(message.clone());
+        message
+    }
+
+    /// Post a letter a courier has brought from another airport. It was
+    /// checked against the limits when sent, so it isn't held to them again.
+    pub fn deliver_courier(&mut self, letter: &CourierLetter) -> Message {
+        let message = Message {
+            id: Uuid::new_v4(),
+            author_id: letter.sender_id,
+            author_name: letter.sender_name.clone(),
+            content: letter.content.clone(),
+            airport_id: letter.to_airport.clone(),
             created_at: chrono::Utc::now(),
+            is_npc: false,
+            couriered_from: Some(letter.from_airport.clone()),
+            topic: MessageTopic::General,
         };
 
-        self.messages.push(message.clone());
+        self.pin(message.clone());
+        message
+    }
+
+    /// Put a message up on its airport's board, making room if it's full
+    fn pin(&mut self, message: Message) {
+        self.expire(message.created_at);
+        let airport_id = message.airport_id.clone();
+        self.airports
+            .entry(airport_id.clone())
+            .or_default()
+            .push(message);
+        self.trim_airport(&airport_id);
+    }
 
-        // Keep only the most recent messages if we exceed the limit
-        if self.messages.len() > self.max_messages {
-            self.messages
-                .drain(0..self.messages.len() - self.max_messages);
+    /// Seconds until the author may post again, if they're still cooling down.
+    /// The cooldown covers every airport, not just the last one posted at.
+    fn cooldown_remaining(
+        &self,
+        author_id: &Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<u64> {
+        if self.limits.post_cooldown_seconds == 0 {
+            return None;
         }
 
-        Ok(message)
+        let last_post = self
+            .airports
+            .values()
+            .flatten()
+            .filter(|msg| msg.author_id == *author_id)
+            .map(|msg| msg.created_at)
+            .max()?;
+
+        let elapsed = now.signed_duration_since(last_post).num_seconds().max(0) as u64;
+        self.limits
+            .post_cooldown_seconds
+            .checked_sub(elapsed)
+            .filter(|wait| *wait > 0)
     }
 
-    pub fn get_messages(&self, airport_id: &str, limit: Option<usize>) -> Vec<&Message> {
-        let mut messages: Vec<&Message> = self
-            .messages
-            .iter()
-            .filter(|msg| msg.airport_id == airport_id)
+    /// Keep only the most recent messages in each topic at an airport
+    fn trim_airport(&mut self, airport_id: &str) {
+        let Some(messages) = self.airports.get_mut(airport_id) else {
+            return;
+        };
+        let mut excess: BTreeMap<MessageTopic, usize> = MessageTopic::ALL
+            .into_iter()
+            .map(|topic| {
+                let count = messages.iter().filter(|m| m.topic == topic).count();
+                (topic, count.saturating_sub(self.limits.limit_for(topic)))
+            })
             .collect();
 
-        // Sort by creation time (most recent first)
-        messages.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        // Messages are stored oldest first, so the first ones over go
+        for message in std::mem::take(messages) {
+            match excess.get_mut(&message.topic) {
+                Some(over) if *over > 0 => {
+                    *over -= 1;
+                    self.evicted.push(message);
+                },
+                _ => messages.push(message),
+            }
+        }
+        if messages.is_empty() {
+            self.airports.remove(airport_id);
+        }
+    }
 
-        if let Some(limit) = limit {
-            messages.truncate(limit);
+    /// Messages at one airport that are still up, oldest first. Expired
+    /// ones stay hidden until the next post takes them down.
+    fn live(&self, airport_id: &str) -> impl DoubleEndedIterator<Item = &Message> {
+        let now = chrono::Utc::now();
+        self.airports
+            .get(airport_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter(move |message| !self.limits.is_expired(message, now))
+    }
+
+    /// Messages at one airport, most recent first
+    pub fn get_messages(&self, airport_id: &str, limit: Option<usize>) -> Vec<&Message> {
+        self.live(airport_id)
+            .rev()
+            .take(limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    /// A page of an airport's board, most recent first: up to `limit`
+    /// messages older than `before_id`, or the newest when that's None.
+    /// Only `topic`'s are counted when one is given.
+    pub fn get_page(
+        &self,
+        airport_id: &str,
+        topic: Option<MessageTopic>,
+        before_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<&Message>, RoomError> {
+        let mut messages = self
+            .live(airport_id)
+            .rev()
+            .filter(|message| topic.is_none_or(|topic| message.topic == topic));
+        if let Some(before_id) = before_id {
+            // Walk past the newer ones, the message itself included
+            if !messages.any(|message| message.id == before_id) {
+                return Err(RoomError::MessageNotFound);
+            }
         }
+        Ok(messages.take(limit).collect())
+    }
 
+    /// Messages in one topic at an airport, most recent first
+    #[allow(dead_code)]
+    pub fn get_topic_messages(
+        &self,
+        airport_id: &str,
+        topic: MessageTopic,
+        limit: Option<usize>,
+    ) -> Vec<&Message> {
+        let mut messages = self.get_messages(airport_id, None);
+        messages.retain(|message| message.topic == topic);
+        messages.truncate(limit.unwrap_or(usize::MAX));
         messages
     }
 
-    #[allow(dead_code)]
+    /// How many messages each topic has at an airport, empty topics included
+    pub fn topic_counts(&self, airport_id: &str) -> BTreeMap<MessageTopic, usize> {
+        let mut counts: BTreeMap<MessageTopic, usize> =
+            MessageTopic::ALL.iter().map(|topic| (*topic, 0)).collect();
+        for message in self.get_messages(airport_id, None) {
+            *counts.entry(message.topic).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Messages at every airport, most recent first
     pub fn get_all_messages(&self, limit: Option<usize>) -> Vec<&Message> {
-        let mut messages: Vec<&Message> = self.messages.iter().collect();
-        messages.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        let mut messages: Vec<&Message> = self
+            .airports
+            .keys()
+            .flat_map(|airport_id| self.live(airport_id))
+            .collect();
+        messages.sort_by_key(|m| std::cmp::Reverse(m.created_at));
 
         if let Some(limit) = limit {
             messages.truncate(limit);
@@ -89,19 +665,20 @@ impl MessageBoard {
         messages
     }
 
+    /// Airports with anything on their board
+    pub fn airport_ids(&self) -> impl Iterator<Item = &str> {
+        self.airports.keys().map(String::as_str)
+    }
+
     #[allow(dead_code)]
     pub fn clear_airport_messages(&mut self, airport_id: &str) {
-        self.messages.retain(|msg| msg.airport_id != airport_id);
+        self.airports.remove(airport_id);
     }
 
     pub fn message_count(&self, airport_id: Option<&str>) -> usize {
         match airport_id {
-            Some(id) => self
-                .messages
-                .iter()
-                .filter(|msg| msg.airport_id == id)
-                .count(),
-            None => self.messages.len(),
+            Some(id) => self.live(id).count(),
+            None => self.airports.keys().map(|id| self.live(id).count()).sum(),
         }
     }
 }
@@ -132,6 +709,26 @@ mod tests {
         assert_eq!(messages[0].content, "Hello world!");
     }
 
+    #[test]
+    fn test_mentions() {
+        let mut board = MessageBoard::new(10);
+        let message = board
+            .post_message(
+                Uuid::new_v4(),
+                "Alice".to_string(),
+                "@bob and @Old Pete: fuel is cheap. Ask @bobby too".to_string(),
+                "JFK".to_string(),
+            )
+            .unwrap();
+
+        assert!(message.mentions("Bob"));
+        assert!(message.mentions("Old Pete"));
+        assert!(message.mentions("Bobby"));
+        assert!(!message.mentions("Bo"));
+        assert!(!message.mentions("Alice"));
+        assert!(!message.mentions(""));
+    }
+
     #[test]
     fn test_message_limit() {
         let mut board = MessageBoard::new(3);
@@ -153,6 +750,118 @@ mod tests {
         assert_eq!(messages[0].content, "Message 5");
     }
 
+    #[test]
+    fn test_message_limit_is_per_airport() {
+        let mut board = MessageBoard::new(2);
+        let author_id = Uuid::new_v4();
+
+        board
+            .post_message(
+                author_id,
+                "TestPlayer".to_string(),
+                "Quiet airport".to_string(),
+                "LAX".to_string(),
+            )
+            .unwrap();
+        for i in 1..=4 {
+            board
+                .post_message(
+                    author_id,
+                    "TestPlayer".to_string(),
+                    format!("Message {}", i),
+                    "JFK".to_string(),
+                )
+                .unwrap();
+        }
+
+        // A busy airport doesn't push out messages elsewhere
+        assert_eq!(board.message_count(Some("JFK")), 2);
+        assert_eq!(board.message_count(Some("LAX")), 1);
+    }
+
+    #[test]
+    fn test_custom_length_limit() {
+        let mut board = MessageBoard::with_limits(BoardLimits {
+            max_message_length: 10,
+            ..BoardLimits::default()
+        });
+
+        let result = board.post_message(
+            Uuid::new_v4(),
+            "TestPlayer".to_string(),
+            "This is far too long".to_string(),
+            "JFK".to_string(),
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            "Message content cannot exceed 10 characters"
+        );
+    }
+
+    #[test]
+    fn test_post_cooldown() {
+        let mut board = MessageBoard::with_limits(BoardLimits {
+            post_cooldown_seconds: 60,
+            ..BoardLimits::default()
+        });
+        let author_id = Uuid::new_v4();
+
+        board
+            .post_message(
+                author_id,
+                "TestPlayer".to_string(),
+                "First".to_string(),
+                "JFK".to_string(),
+            )
+            .unwrap();
+
+        let result = board.post_message(
+            author_id,
+            "TestPlayer".to_string(),
+            "Second".to_string(),
+            "ORD".to_string(),
+        );
+        assert!(result.unwrap_err().starts_with("You can post again in"));
+
+        // Other pilots aren't held up
+        assert!(
+            board
+                .post_message(
+                    Uuid::new_v4(),
+                    "Other".to_string(),
+                    "Hello".to_string(),
+                    "JFK".to_string(),
+                )
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_npc_posts_are_marked() {
+        let mut board = MessageBoard::with_limits(BoardLimits {
+            max_message_length: 10,
+            post_cooldown_seconds: 60,
+            ..BoardLimits::default()
+        });
+
+        board.post_npc_message(
+            "Gus (ramp agent)".to_string(),
+            "Crosswinds again, hold onto your coffee".to_string(),
+            "JFK".to_string(),
+            MessageTopic::Warnings,
+        );
+        board.post_npc_message(
+            "Gus (ramp agent)".to_string(),
+            "Fog this morning".to_string(),
+            "JFK".to_string(),
+            MessageTopic::Warnings,
+        );
+
+        let messages = board.get_messages("JFK", None);
+        assert_eq!(messages.len(), 2);
+        assert!(messages.iter().all(|message| message.is_npc));
+    }
+
     #[test]
     fn test_empty_message_error() {
         let mut board = MessageBoard::new(100);
@@ -188,4 +897,27 @@ mod tests {
             "Message content cannot exceed 500 characters"
         );
     }
+
+    #[test]
+    fn test_old_announcements_load_with_their_kind() {
+        let notice = Notification::announcement(
+            Uuid::new_v4(),
+            "Host".to_string(),
+            "Speed is now Fast".to_string(),
+        );
+        let mut saved = serde_json::to_value(&notice).unwrap();
+        let fields = saved.as_object_mut().unwrap();
+        assert!(!fields.contains_key("announcement"));
+
+        // Saved before notifications had a kind
+        fields.remove("kind");
+        fields.insert("announcement".to_string(), serde_json::Value::Bool(true));
+        let loaded: Notification = serde_json::from_value(saved.clone()).unwrap();
+        assert_eq!(loaded.kind, NotificationKind::Announcement);
+
+        let fields = saved.as_object_mut().unwrap();
+        fields.remove("announcement");
+        let loaded: Notification = serde_json::from_value(saved).unwrap();
+        assert_eq!(loaded.kind, NotificationKind::Mention);
+    }
 }
@@ -1,15 +1,23 @@
+pub mod aircraft;
 pub mod airport;
 pub mod cargo;
+pub mod loan;
+pub mod locale;
 pub mod market;
 pub mod message_board;
 pub mod player;
+pub mod quota;
 pub mod stats;
+pub mod streak;
 
-pub use airport::Airport;
-pub use cargo::CargoType;
-pub use market::Market;
+#[allow(unused_imports)]
+pub use airport::FuelPriceLevel;
+pub use airport::{Airport, FuelEconomy, SecurityRating};
+pub use cargo::{CargoCategory, CargoType};
+pub use market::{Market, PriceSnapshot};
+pub use message_board::BoardLimits;
 #[allow(unused_imports)]
 pub use message_board::Message;
-pub use message_board::MessageBoard;
+pub use message_board::{MessageBoard, MessageTopic, Notification, NotificationKind};
 pub use player::Player;
 pub use stats::GameStats;
@@ -1,6 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-use super::cargo::CargoInventory;
+use super::{
+    aircraft::Aircraft, cargo::CargoInventory, loan::LoanAccount, quota::TurnTrades,
+    streak::TradeStreak,
+};
+
+/// Extra fuel burned with the hold full, in percent; a part-full hold burns
+/// its share of it
+pub const FULL_HOLD_BURN_PERCENT: u32 = 20;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
@@ -11,6 +18,27 @@ pub struct Player {
     pub cargo_inventory: CargoInventory,
     pub max_cargo_weight: u32,
     pub fuel_efficiency: f32,
+    /// Dollars traded at each airport, which earns cheaper broker tiers
+    #[serde(default)]
+    pub broker_volume: std::collections::HashMap<String, u32>,
+    /// Pay for a hangar each night instead of parking outside
+    #[serde(default)]
+    pub hangar_parking: bool,
+    /// Bought once; makes overnight theft much less likely
+    #[serde(default)]
+    pub security_upgrade: bool,
+    /// Upgrades bought at the hangar
+    #[serde(default)]
+    pub aircraft: Aircraft,
+    /// Money owed to the bank
+    #[serde(default)]
+    pub loans: LoanAccount,
+    /// Profitable sales in a row, which earn a small bonus on the next
+    #[serde(default)]
+    pub streak: TradeStreak,
+    /// Cargo bought and sold so far this turn, against the broker's caps
+    #[serde(default)]
+    pub turn_trades: TurnTrades,
 }
 
 impl Player {
@@ -29,6 +57,13 @@ impl Player {
             cargo_inventory: CargoInventory::new(),
             max_cargo_weight,
             fuel_efficiency,
+            broker_volume: std::collections::HashMap::new(),
+            hangar_parking: false,
+            security_upgrade: false,
+            aircraft: Aircraft::default(),
+            loans: LoanAccount::default(),
+            streak: TradeStreak::default(),
+            turn_trades: TurnTrades::default(),
         }
     }
 
@@ -45,8 +80,10 @@ impl Player {
         }
     }
 
+    /// Money tops out at u32::MAX; trades that would pass it are refused
+    /// before they get here
     pub fn earn_money(&mut self, amount: u32) {
-        self.money += amount;
+        self.money = self.money.saturating_add(amount);
     }
 
     pub fn can_carry_more_weight(
@@ -55,7 +92,9 @@ impl Player {
         cargo_types: &std::collections::HashMap<String, super::cargo::CargoType>,
     ) -> bool {
         let current_weight = self.cargo_inventory.total_weight(cargo_types);
-        current_weight + additional_weight <= self.max_cargo_weight
+        current_weight
+            .checked_add(additional_weight)
+            .is_some_and(|total| total <= self.max_cargo_weight)
     }
 
     pub fn consume_fuel(&mut self, amount: u32) -> bool {
@@ -68,18 +107,55 @@ impl Player {
     }
 
     pub fn add_fuel(&mut self, amount: u32) {
-        self.fuel = (self.fuel + amount).min(self.max_fuel);
+        self.fuel = self.fuel.saturating_add(amount).min(self.max_fuel);
     }
 
     pub fn fuel_needed_for_distance(&self, distance: f64) -> u32 {
-        (distance / self.fuel_efficiency as f64).ceil() as u32
+        self.fuel_needed_at_burn(distance, 1.0)
+    }
+
+    /// Fuel for `distance` km with the engines burning `burn` times their
+    /// usual rate, as they do under a heavy load or in bad weather
+    pub fn fuel_needed_at_burn(&self, distance: f64, burn: f64) -> u32 {
+        (distance * burn / self.fuel_efficiency as f64).ceil() as u32
+    }
+
+    /// Furthest a flight can go on `fuel` units burning `burn` times the
+    /// usual rate
+    pub fn range_at_burn(&self, fuel: u32, burn: f64) -> f64 {
+        fuel as f64 * self.fuel_efficiency as f64 / burn
+    }
+
+    /// How much harder the engines work with `cargo_weight` aboard: 1.0 with
+    /// an empty hold, up to `FULL_HOLD_BURN_PERCENT` more with a full one
+    pub fn load_factor(&self, cargo_weight: u32) -> f64 {
+        if self.max_cargo_weight == 0 {
+            return 1.0;
+        }
+        let share = (cargo_weight as f64 / self.max_cargo_weight as f64).min(1.0);
+        1.0 + share * FULL_HOLD_BURN_PERCENT as f64 / 100.0
     }
 
+    /// Whether the fuel aboard covers `distance` km with an empty hold in
+    /// clear skies
+    #[allow(dead_code)]
     pub fn can_travel_distance(&self, distance: f64) -> bool {
         let fuel_needed = self.fuel_needed_for_distance(distance);
         self.fuel >= fuel_needed
     }
 
+    pub fn broker_volume_at(&self, airport_id: &str) -> u32 {
+        self.broker_volume.get(airport_id).copied().unwrap_or(0)
+    }
+
+    pub fn record_broker_volume(&mut self, airport_id: &str, amount: u32) {
+        let volume = self
+            .broker_volume
+            .entry(airport_id.to_string())
+            .or_insert(0);
+        *volume = volume.saturating_add(amount);
+    }
+
     pub fn current_cargo_weight(
         &self,
         cargo_types: &std::collections::HashMap<String, super::cargo::CargoType>,
@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Units of each cargo a player has bought and sold this turn. Brokers
+/// won't handle more than their tier allows of any one cargo per turn, so
+/// no one can corner a market in a single go.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TurnTrades {
+    pub bought: HashMap<String, u32>,
+    pub sold: HashMap<String, u32>,
+}
+
+impl TurnTrades {
+    pub fn bought(&self, cargo_id: &str) -> u32 {
+        self.bought.get(cargo_id).copied().unwrap_or(0)
+    }
+
+    pub fn sold(&self, cargo_id: &str) -> u32 {
+        self.sold.get(cargo_id).copied().unwrap_or(0)
+    }
+
+    pub fn record_purchase(&mut self, cargo_id: &str, quantity: u32) {
+        let bought = self.bought.entry(cargo_id.to_string()).or_insert(0);
+        *bought = bought.saturating_add(quantity);
+    }
+
+    pub fn record_sale(&mut self, cargo_id: &str, quantity: u32) {
+        let sold = self.sold.entry(cargo_id.to_string()).or_insert(0);
+        *sold = sold.saturating_add(quantity);
+    }
+
+    /// Start the count again for a new turn
+    pub fn reset(&mut self) {
+        self.bought.clear();
+        self.sold.clear();
+    }
+}
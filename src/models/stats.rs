@@ -19,6 +19,12 @@ pub struct GameStats {
     pub peak_money: u32,
     pub lowest_money: u32,
     pub times_went_broke: u32,
+    #[serde(default)]
+    pub ironman: bool, // No manual saves, and bankruptcy deletes the save
+    #[serde(default)]
+    pub real_time_turn_secs: Option<u32>, // Turns pass on their own this often
+    #[serde(default)]
+    pub best_streak: u32, // Most profitable sales in a row
 }
 
 impl GameStats {
@@ -41,6 +47,9 @@ impl GameStats {
             peak_money: starting_money,
             lowest_money: starting_money,
             times_went_broke: 0,
+            ironman: false,
+            real_time_turn_secs: None,
+            best_streak: 0,
         }
     }
 
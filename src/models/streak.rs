@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Sale price bonus each profitable sale in a row adds, in percent
+pub const STREAK_BONUS_PERCENT: u32 = 1;
+
+/// Most a streak can add to a sale, in percent
+pub const MAX_STREAK_BONUS_PERCENT: u32 = 5;
+
+/// Profitable sales in a row. Buyers pay a little more to a pilot with a
+/// reputation for delivering, until a sale at a loss breaks the run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradeStreak {
+    #[serde(default)]
+    pub current: u32,
+    #[serde(default)]
+    pub best: u32,
+}
+
+impl TradeStreak {
+    /// What the streak adds to the next sale, in percent
+    pub fn bonus_percent(&self) -> u32 {
+        (self.current * STREAK_BONUS_PERCENT).min(MAX_STREAK_BONUS_PERCENT)
+    }
+
+    /// The bonus on a sale worth `gross`, rounded down
+    pub fn bonus_on(&self, gross: u32) -> u32 {
+        (gross as u64 * self.bonus_percent() as u64 / 100) as u32
+    }
+
+    /// Extend the streak with a profitable sale, or end it
+    pub fn record_sale(&mut self, profitable: bool) {
+        if profitable {
+            self.current += 1;
+            self.best = self.best.max(self.current);
+        } else {
+            self.current = 0;
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::systems::{GameRoom, loadouts::CargoLoadouts, profile::QuantityDefaults};
+
+/// Money a pilot has to hold at once for First Million
+pub const FIRST_MILLION: u32 = 1_000_000;
+
+/// Logbook entries a pilot can earn in any multiplayer room. Once earned
+/// they stay on the pilot's profile for good, whatever happens to the room.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Achievement {
+    /// Stood on the ramp at every airport in a room
+    Globetrotter,
+    /// Brought a flight home after losing cargo on the way. There's no
+    /// weather yet, so a loss in transit is the storm.
+    StormSurvivor,
+    /// Held FIRST_MILLION dollars at once
+    FirstMillion,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 3] = [
+        Achievement::Globetrotter,
+        Achievement::StormSurvivor,
+        Achievement::FirstMillion,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Achievement::Globetrotter => "Globetrotter",
+            Achievement::StormSurvivor => "Weathered the Storm",
+            Achievement::FirstMillion => "First Million",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::Globetrotter => "Land at every airport in a room",
+            Achievement::StormSurvivor => "Make it down after losing cargo in transit",
+            Achievement::FirstMillion => "Hold $1,000,000 at once",
+        }
+    }
+
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Achievement::Globetrotter => "🌍",
+            Achievement::StormSurvivor => "⛈",
+            Achievement::FirstMillion => "💰",
+        }
+    }
+
+    /// Whether the player has done it in this room
+    pub fn earned_in(&self, room: &GameRoom, player_id: &Uuid) -> bool {
+        let Some(player_state) = room.players.get(player_id) else {
+            return false;
+        };
+
+        match self {
+            Achievement::Globetrotter => {
+                // The journal has where they started; the statistics keep
+                // every landing even once the journal's dropped old turns
+                let mut visited: HashSet<&str> = player_state
+                    .journal
+                    .iter()
+                    .map(|record| record.airport_id.as_str())
+                    .collect();
+                if let Some(stats) = room.player_statistics.get(player_id) {
+                    visited.extend(stats.airports_visited.iter().map(String::as_str));
+                }
+                room.shared_state.airports.len() > 1
+                    && room
+                        .shared_state
+                        .airports
+                        .keys()
+                        .all(|airport_id| visited.contains(airport_id.as_str()))
+            },
+            Achievement::StormSurvivor => {
+                let insurance = &player_state.insurance;
+                !insurance.losses.is_empty() || !insurance.claims.is_empty()
+            },
+            Achievement::FirstMillion => player_state.player.money >= FIRST_MILLION,
+        }
+    }
+}
+
+/// An achievement on a pilot's profile, and the room it was earned in
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnlockedAchievement {
+    pub achievement: Achievement,
+    pub room_name: String,
+    pub unlocked_at: DateTime<Utc>,
+}
+
+/// What the server remembers about a pilot across games. Pilots are known
+/// by the name they play under, the same way their sessions are found.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PilotProfile {
+    pub pilot_name: String,
+    #[serde(default)]
+    pub achievements: Vec<UnlockedAchievement>, // Oldest first
+    /// Fuel and trade quantities clients start this pilot's sliders at
+    #[serde(default)]
+    pub quantity_defaults: QuantityDefaults,
+    /// Cargo holds the pilot can load with one click
+    #[serde(default)]
+    pub loadouts: CargoLoadouts,
+}
+
+impl PilotProfile {
+    pub fn new(pilot_name: String) -> Self {
+        Self {
+            pilot_name,
+            achievements: Vec::new(),
+            quantity_defaults: QuantityDefaults::default(),
+            loadouts: CargoLoadouts::default(),
+        }
+    }
+
+    pub fn has(&self, achievement: Achievement) -> bool {
+        self.achievements
+            .iter()
+            .any(|unlocked| unlocked.achievement == achievement)
+    }
+
+    /// Put an achievement on the profile unless it's already there
+    pub fn unlock(&mut self, achievement: Achievement, room_name: &str) -> bool {
+        if self.has(achievement) {
+            return false;
+        }
+        self.achievements.push(UnlockedAchievement {
+            achievement,
+            room_name: room_name.to_string(),
+            unlocked_at: Utc::now(),
+        });
+        true
+    }
+}
@@ -0,0 +1,184 @@
+use crate::systems::{
+    GameState,
+    route_profits::{RouteAnalytics, RouteProfit},
+};
+
+/// What the advisor thinks the player should do next
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdvisedAction {
+    Buy { cargo_type: String, units: u32 },
+    Sell { cargo_type: String },
+    BuyFuel { units: u32 },
+    Fly { airport_id: String },
+}
+
+/// One suggestion and why, for players still learning the buy-fly-sell loop
+#[derive(Debug, Clone, PartialEq)]
+pub struct Advice {
+    pub action: AdvisedAction,
+    /// e.g. "Buy 40 Electronics"
+    pub summary: String,
+    pub reason: String,
+}
+
+pub struct Advisor;
+
+impl Advisor {
+    /// The single most useful thing to do from here, weighed the way the
+    /// route planner ranks routes. None when no trade anywhere turns a
+    /// profit.
+    pub fn advise(game_state: &GameState) -> Option<Advice> {
+        let routes: Vec<RouteProfit> = RouteAnalytics::for_game(game_state)
+            .into_iter()
+            .filter(|route| route.profit > 0)
+            .collect();
+
+        let carrying = game_state
+            .player
+            .cargo_inventory
+            .get_all_cargo()
+            .iter()
+            .any(|(_, &quantity)| quantity > 0);
+        if carrying {
+            Self::advise_sale(game_state, &routes)
+        } else {
+            Self::advise_purchase(game_state, &routes)
+        }
+    }
+
+    /// Take what's in the hold wherever it gains the most over selling here
+    fn advise_sale(game_state: &GameState, routes: &[RouteProfit]) -> Option<Advice> {
+        let player = &game_state.player;
+        let market = game_state.get_current_market()?;
+
+        let mut held: Vec<(&String, u32)> = player
+            .cargo_inventory
+            .get_all_cargo()
+            .iter()
+            .filter(|(_, quantity)| **quantity > 0)
+            .map(|(cargo_id, quantity)| (cargo_id, *quantity))
+            .collect();
+        held.sort();
+
+        let best_trip = held
+            .iter()
+            .filter_map(|(cargo_id, quantity)| {
+                let route = routes.iter().find(|route| {
+                    route.buy_at == player.current_airport && &route.cargo_type == *cargo_id
+                })?;
+                let gain = (route.sell_price - route.buy_price) as u64 * *quantity as u64;
+                Some((route, *quantity, gain))
+            })
+            .max_by_key(|(_, _, gain)| *gain);
+
+        if let Some((route, quantity, gain)) = best_trip {
+            let reason = format!(
+                "Your {} {} sell for ${} each at {}, against ${} here: ${} more for the load",
+                quantity,
+                Self::cargo_name(game_state, &route.cargo_type),
+                route.sell_price,
+                route.sell_at,
+                route.buy_price,
+                gain
+            );
+            return Some(Self::fly(
+                game_state,
+                &route.sell_at,
+                route.fuel_needed,
+                reason,
+            ));
+        }
+
+        let (cargo_id, quantity) = held.into_iter().max_by_key(|(cargo_id, quantity)| {
+            market.get_cargo_price(cargo_id).unwrap_or(0) as u64 * *quantity as u64
+        })?;
+        let price = market.get_cargo_price(cargo_id)?;
+        let name = Self::cargo_name(game_state, cargo_id);
+        Some(Advice {
+            action: AdvisedAction::Sell {
+                cargo_type: cargo_id.clone(),
+            },
+            summary: format!("Sell your {} {}", quantity, name),
+            reason: format!(
+                "No airport you know pays more for {} than the ${} here",
+                name, price
+            ),
+        })
+    }
+
+    /// Fill the hold with the cargo that pays best per unit of fuel from
+    /// here, or head to where the best run starts
+    fn advise_purchase(game_state: &GameState, routes: &[RouteProfit]) -> Option<Advice> {
+        let player = &game_state.player;
+        let from_here = routes.iter().find(|route| {
+            route.buy_at == player.current_airport && route.buy_price <= player.money
+        });
+
+        let Some(route) = from_here else {
+            let route = routes.first()?;
+            let fuel_needed = game_state
+                .distances
+                .between_ids(&game_state.airports, &player.current_airport, &route.buy_at)
+                .map(|distance| player.fuel_needed_for_distance(distance))?;
+            let reason = format!(
+                "Nothing here sells for more elsewhere. The best run starts at {}: {} from ${} there to ${} at {}",
+                route.buy_at,
+                Self::cargo_name(game_state, &route.cargo_type),
+                route.buy_price,
+                route.sell_price,
+                route.sell_at
+            );
+            return Some(Self::fly(game_state, &route.buy_at, fuel_needed, reason));
+        };
+
+        let units = route.units.min(player.money / route.buy_price.max(1));
+        let name = Self::cargo_name(game_state, &route.cargo_type);
+        Some(Advice {
+            action: AdvisedAction::Buy {
+                cargo_type: route.cargo_type.clone(),
+                units,
+            },
+            summary: format!("Buy {} {}", units, name),
+            reason: format!(
+                "{} costs ${} here and sells for ${} at {}: about ${} for {} units, the best return on fuel from here",
+                name,
+                route.buy_price,
+                route.sell_price,
+                route.sell_at,
+                (route.sell_price - route.buy_price) as u64 * units as u64,
+                units
+            ),
+        })
+    }
+
+    /// Fly somewhere, topping up first if the tank won't get us there
+    fn fly(game_state: &GameState, airport_id: &str, fuel_needed: u32, reason: String) -> Advice {
+        let player = &game_state.player;
+        if !game_state.cheat_mode && player.fuel < fuel_needed {
+            let units = fuel_needed - player.fuel;
+            return Advice {
+                action: AdvisedAction::BuyFuel { units },
+                summary: format!("Buy {} fuel", units),
+                reason: format!(
+                    "{}. Getting to {} takes {} fuel and you have {}",
+                    reason, airport_id, fuel_needed, player.fuel
+                ),
+            };
+        }
+        Advice {
+            action: AdvisedAction::Fly {
+                airport_id: airport_id.to_string(),
+            },
+            summary: format!("Fly to {}", airport_id),
+            reason,
+        }
+    }
+
+    fn cargo_name(game_state: &GameState, cargo_id: &str) -> String {
+        game_state
+            .cargo_types
+            .get(cargo_id)
+            .map(|cargo_type| cargo_type.name.clone())
+            .unwrap_or_else(|| cargo_id.to_string())
+    }
+}
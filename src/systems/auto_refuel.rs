@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::Player;
+
+/// Tank share auto-refuel tops up to unless the player picks another
+pub const DEFAULT_AUTO_REFUEL_PERCENT: u32 = 60;
+
+/// A player's standing order for the fuel pump, carried out as soon as they
+/// land: top the tank up to `target_percent` when fuel costs less than
+/// `max_price` a unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AutoRefuel {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_target_percent")]
+    pub target_percent: u32,
+    /// Fuel has to cost less than this; None buys at any price
+    #[serde(default)]
+    pub max_price: Option<u32>,
+}
+
+fn default_target_percent() -> u32 {
+    DEFAULT_AUTO_REFUEL_PERCENT
+}
+
+impl Default for AutoRefuel {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_percent: DEFAULT_AUTO_REFUEL_PERCENT,
+            max_price: None,
+        }
+    }
+}
+
+/// What auto-refuel did on landing. `quantity` is 0 and `note` says why when
+/// nothing was bought.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefuelReceipt {
+    pub quantity: u32,
+    pub price_per_unit: u32,
+    pub cost: u32,
+    pub new_fuel: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl RefuelReceipt {
+    fn skipped(price_per_unit: u32, fuel: u32, note: String) -> Self {
+        Self {
+            quantity: 0,
+            price_per_unit,
+            cost: 0,
+            new_fuel: fuel,
+            note: Some(note),
+        }
+    }
+
+    /// One line for the travel message
+    pub fn summary(&self) -> String {
+        match (self.quantity, &self.note) {
+            (0, Some(note)) => format!("Auto-refuel skipped: {}", note),
+            (_, Some(note)) => format!(
+                "Auto-refuel bought {} fuel at ${} (${}); {}",
+                self.quantity, self.price_per_unit, self.cost, note
+            ),
+            _ => format!(
+                "Auto-refuel bought {} fuel at ${} (${})",
+                self.quantity, self.price_per_unit, self.cost
+            ),
+        }
+    }
+}
+
+impl AutoRefuel {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=100).contains(&self.target_percent) {
+            return Err("Auto-refuel tops up to between 1% and 100% of the tank".to_string());
+        }
+        if self.max_price == Some(0) {
+            return Err("Auto-refuel's price limit has to be above $0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Units short of the target level
+    pub fn shortfall(&self, fuel: u32, max_fuel: u32) -> u32 {
+        let target = (max_fuel as u64 * self.target_percent.min(100) as u64).div_ceil(100) as u32;
+        target.saturating_sub(fuel)
+    }
+
+    /// What `player` should buy on landing where `in_stock` units are for
+    /// sale at `price` each. None when it's off or the tank is already at
+    /// the target.
+    pub fn plan(&self, player: &Player, price: u32, in_stock: u32) -> Option<RefuelReceipt> {
+        let fuel = player.fuel;
+        let wanted = self.shortfall(fuel, player.max_fuel);
+        if !self.enabled || wanted == 0 {
+            return None;
+        }
+
+        if let Some(limit) = self.max_price
+            && price >= limit
+        {
+            return Some(RefuelReceipt::skipped(
+                price,
+                fuel,
+                format!("fuel costs ${}, your limit is under ${}", price, limit),
+            ));
+        }
+        if in_stock == 0 {
+            return Some(RefuelReceipt::skipped(
+                price,
+                fuel,
+                "no fuel for sale here".to_string(),
+            ));
+        }
+
+        let affordable = player.money.checked_div(price).unwrap_or(wanted);
+        let quantity = wanted.min(in_stock).min(affordable);
+        if quantity == 0 {
+            return Some(RefuelReceipt::skipped(
+                price,
+                fuel,
+                "not enough money".to_string(),
+            ));
+        }
+
+        let note = (quantity < wanted).then(|| {
+            if quantity == in_stock {
+                format!("only {} units in stock", in_stock)
+            } else {
+                "all you could afford".to_string()
+            }
+        });
+        Some(RefuelReceipt {
+            quantity,
+            price_per_unit: price,
+            cost: quantity * price,
+            new_fuel: fuel + quantity,
+            note,
+        })
+    }
+}
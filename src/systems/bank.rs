@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    Player,
+    cargo::CargoInventory,
+    loan::{Loan, LoanAccount},
+};
+
+/// Interest added to each loan's balance every turn
+pub const INTEREST_PERCENT: u32 = 2;
+
+/// Turns a loan is meant to be paid off over
+pub const LOAN_TERM_TURNS: u32 = 10;
+
+/// Smallest amount the bank will lend
+pub const MIN_LOAN: u32 = 500;
+
+/// Most a pilot can owe the bank before it stops lending
+pub const CREDIT_LIMIT: u32 = 10_000;
+
+/// Added to a loan, as a share of its installment, each turn it isn't paid
+pub const LATE_FEE_PERCENT: u32 = 10;
+
+/// Debt past which the bank forecloses
+pub const BANKRUPTCY_DEBT: u32 = 15_000;
+
+/// What the bank took when it foreclosed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bankruptcy {
+    pub debt_written_off: u32,
+    pub cash_seized: u32,
+    /// Units of cargo taken from the hold
+    pub cargo_seized: u32,
+}
+
+/// How the bank settled a turn's loan payments
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BankStatement {
+    pub interest: u32,
+    pub paid: u32,
+    pub late_fees: u32,
+    /// Loans this turn's payment cleared
+    pub paid_off: Vec<u32>,
+    pub bankruptcy: Option<Bankruptcy>,
+}
+
+impl BankStatement {
+    /// A line for the player
+    pub fn report(&self) -> String {
+        if let Some(bankruptcy) = &self.bankruptcy {
+            return format!(
+                "Declared bankrupt: the bank seized ${} and {} units of cargo, writing off ${} of debt",
+                bankruptcy.cash_seized, bankruptcy.cargo_seized, bankruptcy.debt_written_off
+            );
+        }
+
+        let mut report = format!("Paid ${} on loans (${} interest)", self.paid, self.interest);
+        if self.late_fees > 0 {
+            report.push_str(&format!(
+                ", missed a payment for ${} in late fees",
+                self.late_fees
+            ));
+        }
+        if !self.paid_off.is_empty() {
+            report.push_str(", a loan is paid off");
+        }
+        report
+    }
+}
+
+pub struct BankSystem;
+
+impl BankSystem {
+    /// How much more the bank will lend this pilot
+    pub fn credit_available(account: &LoanAccount) -> u32 {
+        if account.bankrupt {
+            return 0;
+        }
+        CREDIT_LIMIT.saturating_sub(account.debt())
+    }
+
+    /// Paid each turn so a loan of `principal` clears within the term
+    pub fn installment(principal: u32) -> u32 {
+        let owed = principal as u64 * (100 + INTEREST_PERCENT * LOAN_TERM_TURNS) as u64 / 100;
+        owed.div_ceil(LOAN_TERM_TURNS as u64) as u32
+    }
+
+    /// Take out a loan of `amount`, paid straight into the pilot's cash.
+    /// Returns the new loan's id.
+    pub fn borrow(player: &mut Player, amount: u32, turn_number: u32) -> Result<u32, String> {
+        if player.loans.bankrupt {
+            return Err("The bank won't lend to a pilot who has gone bankrupt".to_string());
+        }
+        if amount < MIN_LOAN {
+            return Err(format!("The smallest loan the bank makes is ${}", MIN_LOAN));
+        }
+        let available = Self::credit_available(&player.loans);
+        if amount > available {
+            return Err(format!(
+                "The bank will only lend you another ${}",
+                available
+            ));
+        }
+
+        let account = &mut player.loans;
+        account.next_id += 1;
+        let id = account.next_id;
+        account.loans.push(Loan {
+            id,
+            principal: amount,
+            balance: amount,
+            installment: Self::installment(amount),
+            taken_at_turn: turn_number,
+            missed_payments: 0,
+        });
+        player.earn_money(amount);
+        Ok(id)
+    }
+
+    /// Pay up to `amount` off a loan early. Returns what was paid.
+    pub fn repay(player: &mut Player, loan_id: u32, amount: u32) -> Result<u32, String> {
+        if amount == 0 {
+            return Err("Repay at least $1".to_string());
+        }
+        let balance = player.loans.get(loan_id).ok_or("Loan not found")?.balance;
+        let payment = amount.min(balance);
+        if !player.spend_money(payment) {
+            return Err(format!("You don't have ${} to repay", payment));
+        }
+
+        if let Some(loan) = player.loans.get_mut(loan_id) {
+            loan.balance -= payment;
+        }
+        player.loans.loans.retain(|loan| loan.balance > 0);
+        Ok(payment)
+    }
+
+    /// Charge interest and take each loan's installment at the end of a
+    /// turn. A pilot who can't pay is charged a late fee, and one whose
+    /// debt passes BANKRUPTCY_DEBT loses their cash and cargo to the bank.
+    /// None when there are no loans.
+    pub fn charge_turn(player: &mut Player) -> Option<BankStatement> {
+        if player.loans.loans.is_empty() {
+            return None;
+        }
+
+        let mut statement = BankStatement::default();
+        for loan in &mut player.loans.loans {
+            let interest = (loan.balance as u64 * INTEREST_PERCENT as u64).div_ceil(100) as u32;
+            loan.balance = loan.balance.saturating_add(interest);
+            statement.interest += interest;
+
+            let due = loan.installment.min(loan.balance);
+            let payment = due.min(player.money);
+            player.money -= payment;
+            loan.balance -= payment;
+            statement.paid += payment;
+
+            if payment < due {
+                let fee = (loan.installment as u64 * LATE_FEE_PERCENT as u64).div_ceil(100) as u32;
+                loan.balance = loan.balance.saturating_add(fee);
+                loan.missed_payments += 1;
+                statement.late_fees += fee;
+            }
+            if loan.balance == 0 {
+                statement.paid_off.push(loan.id);
+            }
+        }
+        player.loans.loans.retain(|loan| loan.balance > 0);
+
+        let debt = player.loans.debt();
+        if debt > BANKRUPTCY_DEBT {
+            let cargo_seized = player
+                .cargo_inventory
+                .get_all_cargo()
+                .values()
+                .fold(0, |total: u32, quantity| total.saturating_add(*quantity));
+            statement.bankruptcy = Some(Bankruptcy {
+                debt_written_off: debt,
+                cash_seized: std::mem::take(&mut player.money),
+                cargo_seized,
+            });
+            player.cargo_inventory = CargoInventory::new();
+            player.loans.loans.clear();
+            player.loans.bankrupt = true;
+        }
+        Some(statement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(money: u32) -> Player {
+        Player::new(money, "JFK", 100, 1000, 5.0)
+    }
+
+    #[test]
+    fn test_installments_clear_a_loan_within_the_term() {
+        let mut player = player(0);
+        BankSystem::borrow(&mut player, 1_000, 1).unwrap();
+        player.money = 10_000;
+
+        let mut turns = 0;
+        while !player.loans.loans.is_empty() {
+            BankSystem::charge_turn(&mut player).unwrap();
+            turns += 1;
+        }
+        assert!(turns <= LOAN_TERM_TURNS);
+        assert!(player.money < 10_000 - 1_000);
+    }
+
+    #[test]
+    fn test_missed_payments_add_late_fees() {
+        let mut player = player(0);
+        BankSystem::borrow(&mut player, 1_000, 1).unwrap();
+        player.money = 0;
+
+        let statement = BankSystem::charge_turn(&mut player).unwrap();
+        assert_eq!(statement.paid, 0);
+        assert_eq!(statement.interest, 20);
+        assert!(statement.late_fees > 0);
+        assert_eq!(player.loans.loans[0].missed_payments, 1);
+        assert_eq!(player.loans.debt(), 1_020 + statement.late_fees);
+    }
+}
@@ -0,0 +1,168 @@
+use rand::{Rng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::systems::GameRoom;
+
+/// Names the demo room's bot traders fly under
+pub const BOT_NAMES: [&str; 5] = [
+    "Autopilot Ada",
+    "Autopilot Bix",
+    "Autopilot Cho",
+    "Autopilot Dov",
+    "Autopilot Eun",
+];
+
+/// What the server calls its demo room in the lobby
+pub const DEMO_ROOM_NAME: &str = "🤖 Demo Room";
+
+/// Bot traders in a demo room unless the server says otherwise
+pub const DEFAULT_DEMO_BOTS: usize = 3;
+
+/// How often a demo room starts over unless the server says otherwise
+pub const DEFAULT_DEMO_RESET_HOURS: i64 = 6;
+
+/// How often each bot takes a turn unless the server says otherwise
+pub const DEFAULT_DEMO_TURN_SECONDS: u64 = 60;
+
+/// A room the server runs itself so newcomers to an empty server have
+/// someone to play against
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DemoRoom {
+    /// The room's bot traders; everyone else is a person
+    pub bots: Vec<Uuid>,
+    /// When the room last started over
+    pub reset_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl DemoRoom {
+    pub fn is_bot(&self, player_id: &Uuid) -> bool {
+        self.bots.contains(player_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BotTrade {
+    pub cargo_id: String,
+    pub quantity: u32,
+}
+
+/// What a bot trader does with a turn, in order: sell, refuel, buy, fly
+#[derive(Debug, Clone, PartialEq)]
+pub struct BotTurn {
+    pub sell: Vec<BotTrade>,
+    pub fuel: u32,
+    pub buy: Option<BotTrade>,
+    pub destination: String,
+}
+
+impl BotTurn {
+    /// A greedy trader: sell the whole hold, fill the tank, then buy the
+    /// cargo that sells for the most above its price here at an airport a
+    /// full tank reaches, and fly there. Without a profitable run it flies
+    /// somewhere in range at random.
+    pub fn plan(room: &GameRoom, bot_id: &Uuid, rng: &mut impl Rng) -> Option<Self> {
+        let player = &room.get_player(bot_id)?.player;
+        let here = player.current_airport.as_str();
+        let market = room.get_current_market(here)?;
+        let shared = &room.shared_state;
+
+        let sell: Vec<BotTrade> = player
+            .cargo_inventory
+            .get_all_cargo()
+            .iter()
+            .filter(|(cargo_id, quantity)| {
+                **quantity > 0 && market.get_cargo_price(cargo_id).is_some()
+            })
+            .map(|(cargo_id, quantity)| BotTrade {
+                cargo_id: cargo_id.clone(),
+                quantity: *quantity,
+            })
+            .collect();
+        let mut money = player.money
+            + sell
+                .iter()
+                .map(|trade| trade.quantity * market.get_cargo_price(&trade.cargo_id).unwrap_or(0))
+                .sum::<u32>();
+
+        let fuel = (player.max_fuel - player.fuel)
+            .min(money / market.fuel_price.max(1))
+            .min(market.fuel_available());
+        money -= fuel * market.fuel_price;
+
+        let mut in_range: Vec<&str> = shared
+            .airports
+            .keys()
+            .map(String::as_str)
+            .filter(|airport_id| *airport_id != here)
+            .filter(|airport_id| {
+                shared
+                    .distances
+                    .between_ids(&shared.airports, here, airport_id)
+                    .is_some_and(|distance| {
+                        player.fuel_needed_for_distance(distance) <= player.fuel + fuel
+                    })
+            })
+            .collect();
+        in_range.sort_unstable();
+
+        struct Run<'a> {
+            /// Profit per unit of weight
+            margin: f64,
+            cargo_id: &'a str,
+            destination: &'a str,
+        }
+        let mut best: Option<Run> = None;
+        for destination in &in_range {
+            let Some(there) = room.get_current_market(destination) else {
+                continue;
+            };
+            for (cargo_id, price) in market.get_all_cargo_prices() {
+                let (Some(cargo_type), Some(sale)) = (
+                    shared.cargo_types.get(cargo_id),
+                    there.get_cargo_price(cargo_id),
+                ) else {
+                    continue;
+                };
+                if sale <= *price {
+                    continue;
+                }
+                let margin = (sale - price) as f64 / cargo_type.weight_per_unit.max(1) as f64;
+                if best.as_ref().is_none_or(|run| margin > run.margin) {
+                    best = Some(Run {
+                        margin,
+                        cargo_id,
+                        destination,
+                    });
+                }
+            }
+        }
+
+        let (buy, destination) = match best {
+            Some(Run {
+                cargo_id,
+                destination,
+                ..
+            }) => {
+                let price = market.get_cargo_price(cargo_id).unwrap_or(0).max(1);
+                let weight = shared.cargo_types[cargo_id].weight_per_unit.max(1);
+                // Keep a little back for the broker's fee
+                let budget = money - money / 10;
+                let quantity = (budget / price).min(player.max_cargo_weight / weight);
+                let buy = (quantity > 0).then(|| BotTrade {
+                    cargo_id: cargo_id.to_string(),
+                    quantity,
+                });
+                (buy, destination.to_string())
+            },
+            None => (None, in_range.choose(rng)?.to_string()),
+        };
+
+        Some(Self {
+            sell,
+            fuel,
+            buy,
+            destination,
+        })
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Longest a host may make a single turn
+pub const MAX_HOURS_PER_TURN: u32 = 24 * 7;
+
+/// Time of year at an in-game date, by the northern hemisphere's months
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn of(date: NaiveDateTime) -> Self {
+        match date.month() {
+            3..=5 => Season::Spring,
+            6..=8 => Season::Summer,
+            9..=11 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+}
+
+/// The game's clock: turn 1 starts at `start` and each turn after moves it
+/// on by `hours_per_turn`. Anything that runs on in-game time asks this
+/// rather than counting turns itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct GameCalendar {
+    pub start: NaiveDateTime,
+    pub hours_per_turn: u32,
+}
+
+impl Default for GameCalendar {
+    fn default() -> Self {
+        Self {
+            start: NaiveDate::from_ymd_opt(1985, 6, 1)
+                .and_then(|date| date.and_hms_opt(6, 0, 0))
+                .unwrap_or_default(),
+            hours_per_turn: 6,
+        }
+    }
+}
+
+impl GameCalendar {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_HOURS_PER_TURN).contains(&self.hours_per_turn) {
+            return Err(format!(
+                "A turn must last between 1 and {} hours",
+                MAX_HOURS_PER_TURN
+            ));
+        }
+        Ok(())
+    }
+
+    /// When `turn` starts
+    pub fn time_at(&self, turn: u32) -> NaiveDateTime {
+        let hours = turn.saturating_sub(1) as i64 * self.hours_per_turn as i64;
+        self.start + Duration::hours(hours)
+    }
+
+    /// In-game hours from the start of `from` to the start of `to`
+    pub fn hours_between(&self, from: u32, to: u32) -> u32 {
+        to.saturating_sub(from).saturating_mul(self.hours_per_turn)
+    }
+
+    pub fn season_at(&self, turn: u32) -> Season {
+        Season::of(self.time_at(turn))
+    }
+
+    /// `turn` as a date for the player, like "Sat 1 Jun 1985, 06:00"
+    pub fn date_label(&self, turn: u32) -> String {
+        self.time_at(turn).format("%a %-d %b %Y, %H:%M").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns_move_the_clock_on() {
+        let calendar = GameCalendar::default();
+        assert_eq!(calendar.date_label(1), "Sat 1 Jun 1985, 06:00");
+        assert_eq!(calendar.date_label(4), "Sun 2 Jun 1985, 00:00");
+        assert_eq!(calendar.hours_between(1, 5), 24);
+        assert_eq!(calendar.season_at(1), Season::Summer);
+    }
+
+    #[test]
+    fn test_longer_turns_cross_into_the_next_season() {
+        let calendar = GameCalendar {
+            hours_per_turn: 24 * 7,
+            ..GameCalendar::default()
+        };
+        assert_eq!(calendar.date_label(14), "Sat 31 Aug 1985, 06:00");
+        assert_eq!(calendar.season_at(14), Season::Summer);
+        assert_eq!(calendar.season_at(15), Season::Autumn);
+    }
+}
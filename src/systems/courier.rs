@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Flat charge for sending a letter by courier
+pub const COURIER_BASE_FEE: u32 = 50;
+
+/// Charge for each turn the letter spends in the mail
+pub const COURIER_FEE_PER_TURN: u32 = 25;
+
+/// How far the mail flights get in a turn
+pub const COURIER_KM_PER_TURN: f64 = 1500.0;
+
+/// Most letters one player may have in the mail at once
+pub const MAX_LETTERS_IN_TRANSIT: usize = 5;
+
+/// What sending a letter between two airports costs, and how long it takes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CourierQuote {
+    /// Turns of the room's world clock before the letter is posted
+    pub turns: u32,
+    pub fee: u32,
+}
+
+impl CourierQuote {
+    /// Letters ride the mail flights, a turn for every
+    /// `COURIER_KM_PER_TURN` km and at least one
+    pub fn for_distance(distance_km: f64) -> Self {
+        let turns = ((distance_km / COURIER_KM_PER_TURN).ceil() as u32).max(1);
+        Self {
+            turns,
+            fee: COURIER_BASE_FEE + COURIER_FEE_PER_TURN * turns,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CourierStatus {
+    InTransit,
+    Delivered,
+}
+
+/// A message on its way to another airport's board. The sender pays up
+/// front; the room posts it once the world clock reaches `deliver_at`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CourierLetter {
+    pub id: Uuid,
+    pub sender_id: Uuid,
+    pub sender_name: String,
+    pub from_airport: String,
+    pub to_airport: String,
+    pub content: String,
+    pub fee: u32,
+    /// World tick when it was sent
+    pub sent_at: u32,
+    /// World tick it's posted on
+    pub deliver_at: u32,
+    pub status: CourierStatus,
+    /// The board message it became, once delivered
+    #[serde(default)]
+    pub message_id: Option<Uuid>,
+}
+
+impl CourierLetter {
+    /// Turns until delivery at `world_tick`; zero once it's due
+    pub fn turns_remaining(&self, world_tick: u32) -> u32 {
+        match self.status {
+            CourierStatus::InTransit => self.deliver_at.saturating_sub(world_tick),
+            CourierStatus::Delivered => 0,
+        }
+    }
+
+    pub fn is_due(&self, world_tick: u32) -> bool {
+        self.status == CourierStatus::InTransit && world_tick >= self.deliver_at
+    }
+}
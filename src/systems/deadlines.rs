@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::systems::digest::DigestTarget;
+
+/// Shortest and longest a host may give each player for a turn
+pub const MIN_TURN_DEADLINE_HOURS: u32 = 1;
+pub const MAX_TURN_DEADLINE_HOURS: u32 = 24 * 14;
+
+/// Given to each turn when the host doesn't pick a length
+pub const DEFAULT_TURN_DEADLINE_HOURS: u32 = 48;
+
+/// How long before the deadline the warning goes out unless the host says
+pub const DEFAULT_DEADLINE_WARNING_HOURS: u32 = 4;
+
+/// A play-by-post room's turn deadline: each player has `hours` from their
+/// last turn to take the next one, and the room's webhook hears when a
+/// deadline is getting close or has gone by
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TurnDeadline {
+    pub hours: u32,
+    /// Warn this long before the deadline; 0 only reports missed ones
+    pub warning_hours: u32,
+    /// Where alerts go, e.g. a Discord or Slack incoming webhook
+    pub webhook_url: String,
+}
+
+impl TurnDeadline {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_TURN_DEADLINE_HOURS..=MAX_TURN_DEADLINE_HOURS).contains(&self.hours) {
+            return Err(format!(
+                "Turn deadlines must be between {} and {} hours",
+                MIN_TURN_DEADLINE_HOURS, MAX_TURN_DEADLINE_HOURS
+            ));
+        }
+        if self.warning_hours >= self.hours {
+            return Err("The warning must come before the deadline".to_string());
+        }
+        DigestTarget::Webhook(self.webhook_url.clone()).validate()
+    }
+
+    /// When a turn that started at `started_at` is due
+    pub fn due_at(
+        &self,
+        started_at: chrono::DateTime<chrono::Utc>,
+    ) -> chrono::DateTime<chrono::Utc> {
+        started_at + chrono::Duration::hours(self.hours.into())
+    }
+
+    /// What, if anything, a player whose turn is due at `due_at` should
+    /// be alerted about now, given the last alert they got this turn
+    pub fn alert_due(
+        &self,
+        due_at: chrono::DateTime<chrono::Utc>,
+        already_sent: Option<DeadlineAlertKind>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<DeadlineAlertKind> {
+        let warn_at = due_at - chrono::Duration::hours(self.warning_hours.into());
+        match already_sent {
+            Some(DeadlineAlertKind::Missed) => None,
+            _ if now >= due_at => Some(DeadlineAlertKind::Missed),
+            None if self.warning_hours > 0 && now >= warn_at => {
+                Some(DeadlineAlertKind::Approaching)
+            },
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DeadlineAlertKind {
+    Approaching,
+    Missed,
+}
+
+/// One turn deadline notice for a room's webhook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeadlineAlert {
+    pub room_id: Uuid,
+    pub room_name: String,
+    pub player_id: Uuid,
+    pub player_name: String,
+    pub kind: DeadlineAlertKind,
+    pub turn_number: u32,
+    pub due_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl DeadlineAlert {
+    pub fn text(&self) -> String {
+        let due = self.due_at.format("%Y-%m-%d %H:%M UTC");
+        match self.kind {
+            DeadlineAlertKind::Approaching => format!(
+                "⏰ {} has until {} to take turn {} in {}",
+                self.player_name, due, self.turn_number, self.room_name
+            ),
+            DeadlineAlertKind::Missed => format!(
+                "⌛ {} missed the {} deadline for turn {} in {}",
+                self.player_name, due, self.turn_number, self.room_name
+            ),
+        }
+    }
+
+    /// The same news told to the player it's about, for their own
+    /// notifications
+    pub fn personal_text(&self) -> String {
+        let due = self.due_at.format("%Y-%m-%d %H:%M UTC");
+        match self.kind {
+            DeadlineAlertKind::Approaching => format!(
+                "⏰ You have until {} to take turn {}",
+                due, self.turn_number
+            ),
+            DeadlineAlertKind::Missed => format!(
+                "⌛ You missed the {} deadline for turn {}",
+                due, self.turn_number
+            ),
+        }
+    }
+
+    /// The webhook body. Discord reads `content` and Slack reads `text`,
+    /// so both carry the message; anything else can use `alert`.
+    pub fn payload(&self) -> serde_json::Value {
+        let text = self.text();
+        serde_json::json!({
+            "content": text,
+            "text": text,
+            "alert": self,
+        })
+    }
+}
@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::systems::{RoomWinner, multiplayer::FUEL_EFFICIENCY_PROFIT_UNIT};
+
+/// How often a player hears about a room unless the server says otherwise
+pub const DEFAULT_DIGEST_HOURS: i64 = 24;
+
+/// Mentions included in one digest; the rest are only counted
+pub const MAX_DIGEST_MENTIONS: usize = 10;
+
+/// Where a player's digests are delivered
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DigestTarget {
+    Webhook(String),
+    Email(String),
+}
+
+impl DigestTarget {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            DigestTarget::Webhook(url) => {
+                let valid = (url.starts_with("https://") || url.starts_with("http://"))
+                    && url.len() <= 2048
+                    && !url.chars().any(|c| c.is_whitespace() || c.is_control());
+                if !valid {
+                    return Err("Webhook must be an http:// or https:// URL".to_string());
+                }
+            },
+            DigestTarget::Email(address) => {
+                // Strict on purpose: the address ends up in mail headers
+                let valid = address.len() <= 254
+                    && address
+                        .chars()
+                        .all(|c| c.is_ascii_graphic() && !"<>(),;:\"[]\\".contains(c))
+                    && matches!(address.split_once('@'), Some((local, domain))
+                        if !local.is_empty()
+                            && !local.starts_with('-')
+                            && domain.contains('.')
+                            && !domain.contains('@')
+                            && !domain.starts_with('.')
+                            && !domain.ends_with('.'));
+                if !valid {
+                    return Err(format!("Invalid email address: {}", address));
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+/// A player's digest registration, and where the last digest left off
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DigestSubscription {
+    pub target: DigestTarget,
+    pub last_sent_at: chrono::DateTime<chrono::Utc>,
+    pub world_tick: u32,
+    pub turn_number: u32,
+    pub rank: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LeaderboardEntry {
+    pub player_name: String,
+    pub money: u32,
+    /// Money, cargo and aircraft together; what the leaderboard ranks by
+    #[serde(default)]
+    pub net_worth: u32,
+    /// Fuel burned per dollar of profit, lower being greener
+    #[serde(default)]
+    pub fuel_per_profit_dollar: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DigestMention {
+    pub author_name: String,
+    pub airport_id: String,
+    pub content: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// What happened in a room since a player's last digest. Webhooks get this
+/// as JSON; email gets `text()`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Digest {
+    pub room_id: Uuid,
+    pub room_name: String,
+    pub player_id: Uuid,
+    pub player_name: String,
+    pub since: chrono::DateTime<chrono::Utc>,
+    pub until: chrono::DateTime<chrono::Utc>,
+    /// Actions taken by anyone in the room
+    pub world_turns: u32,
+    /// Turns this player flew
+    pub your_turns: u32,
+    pub rank: usize,
+    pub previous_rank: usize,
+    pub leaderboard: Vec<LeaderboardEntry>,
+    pub mentions: Vec<DigestMention>,
+    pub more_mentions: usize,
+    pub winner: Option<RoomWinner>,
+}
+
+impl Digest {
+    /// Nothing worth interrupting anyone for
+    pub fn is_quiet(&self) -> bool {
+        self.world_turns == 0 && self.mentions.is_empty() && self.winner.is_none()
+    }
+
+    pub fn subject(&self) -> String {
+        let room_name: String = self.room_name.chars().filter(|c| !c.is_control()).collect();
+        format!("KZRK - {}: your daily summary", room_name)
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = format!(
+            "Here's what happened in {} since {}.\n\n",
+            self.room_name,
+            self.since.format("%Y-%m-%d %H:%M UTC")
+        );
+
+        if let Some(winner) = &self.winner {
+            text.push_str(&format!(
+                "Game over: {} won with ${} on turn {}.\n\n",
+                winner.player_name, winner.money, winner.turn_number
+            ));
+        }
+
+        text.push_str(&format!(
+            "{} turns were played in the room, {} of them yours.\n",
+            self.world_turns, self.your_turns
+        ));
+        text.push_str(&match self.rank.cmp(&self.previous_rank) {
+            std::cmp::Ordering::Less => format!(
+                "You climbed from #{} to #{}.\n",
+                self.previous_rank, self.rank
+            ),
+            std::cmp::Ordering::Greater => format!(
+                "You slipped from #{} to #{}.\n",
+                self.previous_rank, self.rank
+            ),
+            std::cmp::Ordering::Equal => format!("You're holding at #{}.\n", self.rank),
+        });
+
+        text.push_str("\nLeaderboard:\n");
+        for (index, entry) in self.leaderboard.iter().enumerate() {
+            text.push_str(&format!(
+                "  {}. {} - worth ${} (${} cash)",
+                index + 1,
+                entry.player_name,
+                entry.net_worth,
+                entry.money
+            ));
+            if let Some(ratio) = entry.fuel_per_profit_dollar {
+                text.push_str(&format!(
+                    " ({:.1} fuel per ${} profit)",
+                    ratio * FUEL_EFFICIENCY_PROFIT_UNIT as f32,
+                    FUEL_EFFICIENCY_PROFIT_UNIT
+                ));
+            }
+            text.push('\n');
+        }
+
+        if !self.mentions.is_empty() {
+            text.push_str("\nMentions:\n");
+            for mention in &self.mentions {
+                text.push_str(&format!(
+                    "  {} at {}: {}\n",
+                    mention.author_name, mention.airport_id, mention.content
+                ));
+            }
+            if self.more_mentions > 0 {
+                text.push_str(&format!("  ...and {} more\n", self.more_mentions));
+            }
+        }
+
+        text
+    }
+}
+
+/// Whether a message mentions a player: their name as a whole word, any case,
+/// with or without an @
+pub fn mentions(content: &str, player_name: &str) -> bool {
+    let content = content.to_lowercase();
+    let name = player_name.trim().to_lowercase();
+    if name.is_empty() {
+        return false;
+    }
+
+    content.match_indices(&name).any(|(start, _)| {
+        let before = content[..start].chars().next_back();
+        let after = content[start + name.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions_match_whole_names_only() {
+        assert!(mentions("@Bo you still flying to ORD?", "Bo"));
+        assert!(mentions(
+            "watch out, old pete is buying everything",
+            "Old Pete"
+        ));
+        assert!(mentions("thanks bo!", "Bo"));
+        assert!(!mentions("bought a boat", "Bo"));
+        assert!(!mentions("anyone?", " "));
+    }
+
+    #[test]
+    fn test_targets_are_validated() {
+        assert!(
+            DigestTarget::Webhook("https://example.com/hook".to_string())
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            DigestTarget::Webhook("ftp://example.com".to_string())
+                .validate()
+                .is_err()
+        );
+        assert!(
+            DigestTarget::Webhook("https://example.com/a b".to_string())
+                .validate()
+                .is_err()
+        );
+
+        assert!(
+            DigestTarget::Email("pilot@example.com".to_string())
+                .validate()
+                .is_ok()
+        );
+        for bad in [
+            "pilot",
+            "pilot@localhost",
+            "-oQ@example.com",
+            "pilot@example.com\nBcc: x@example.com",
+            "a@b@example.com",
+        ] {
+            assert!(
+                DigestTarget::Email(bad.to_string()).validate().is_err(),
+                "{}",
+                bad
+            );
+        }
+    }
+}
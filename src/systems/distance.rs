@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::models::Airport;
+
+/// Airport ids, alphabetically
+type Pair = (String, String);
+
+/// A measured leg, with the coordinates it was measured between so a
+/// moved or replaced airport is noticed rather than served stale
+#[derive(Debug, Clone, Copy)]
+struct Measured {
+    from: (f64, f64),
+    to: (f64, f64),
+    km: f64,
+}
+
+/// Great-circle distances between airports, worked out the first time a
+/// leg is asked for and remembered after that. Legs are stored once per
+/// pair, so A to B and B to A share an entry. Safe to share between
+/// threads, and never saved: it's rebuilt on demand after a load.
+#[derive(Debug, Default)]
+pub struct DistanceCache {
+    legs: Mutex<HashMap<Pair, Measured>>,
+}
+
+impl Clone for DistanceCache {
+    fn clone(&self) -> Self {
+        Self {
+            legs: Mutex::new(self.legs.lock().unwrap().clone()),
+        }
+    }
+}
+
+impl DistanceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Kilometers from one airport to another
+    pub fn between(&self, from: &Airport, to: &Airport) -> f64 {
+        if from.id == to.id {
+            return 0.0;
+        }
+        let (first, second) = if from.id < to.id {
+            (from, to)
+        } else {
+            (to, from)
+        };
+        let key = (first.id.clone(), second.id.clone());
+
+        let mut legs = self.legs.lock().unwrap();
+        if let Some(leg) = legs.get(&key)
+            && leg.from == first.coordinates
+            && leg.to == second.coordinates
+        {
+            return leg.km;
+        }
+        let km = first.distance_to(second);
+        legs.insert(
+            key,
+            Measured {
+                from: first.coordinates,
+                to: second.coordinates,
+                km,
+            },
+        );
+        km
+    }
+
+    /// Kilometers between two airports in `airports`, by id
+    pub fn between_ids(
+        &self,
+        airports: &HashMap<String, Airport>,
+        from: &str,
+        to: &str,
+    ) -> Option<f64> {
+        Some(self.between(airports.get(from)?, airports.get(to)?))
+    }
+
+    /// Forget legs touching airports that are no longer in `airports`
+    #[allow(dead_code)]
+    pub fn retain_airports(&self, airports: &HashMap<String, Airport>) {
+        self.legs
+            .lock()
+            .unwrap()
+            .retain(|(from, to), _| airports.contains_key(from) && airports.contains_key(to));
+    }
+
+    /// How many legs have been measured so far
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.legs.lock().unwrap().len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
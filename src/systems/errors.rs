@@ -0,0 +1,56 @@
+use crate::models::locale::LocalizedMessage;
+
+/// Why the game refused something in a room. Lookups that miss and
+/// cooldowns are kept apart from plain refusals so the API can answer them
+/// with their own status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoomError {
+    PlayerNotInRoom,
+    OfferNotFound,
+    ModifierNotFound,
+    MessageNotFound,
+    LossNotFound,
+    Cooldown {
+        retry_after_ms: u64,
+    },
+    Refused(String),
+    /// A refusal the catalog has a sentence for
+    Localized(LocalizedMessage),
+}
+
+impl std::fmt::Display for RoomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RoomError::PlayerNotInRoom => f.write_str("Player not in room"),
+            RoomError::OfferNotFound => f.write_str("Offer not found"),
+            RoomError::ModifierNotFound => f.write_str("Modifier not found"),
+            RoomError::MessageNotFound => f.write_str("Message not found"),
+            RoomError::LossNotFound => f.write_str("No unclaimed loss with that id"),
+            RoomError::Cooldown { retry_after_ms } => {
+                write!(f, "Too many actions: try again in {} ms", retry_after_ms)
+            },
+            RoomError::Refused(message) => f.write_str(message),
+            RoomError::Localized(localized) => write!(f, "{}", localized),
+        }
+    }
+}
+
+impl std::error::Error for RoomError {}
+
+impl From<String> for RoomError {
+    fn from(message: String) -> Self {
+        RoomError::Refused(message)
+    }
+}
+
+impl From<&str> for RoomError {
+    fn from(message: &str) -> Self {
+        RoomError::Refused(message.to_string())
+    }
+}
+
+impl From<LocalizedMessage> for RoomError {
+    fn from(localized: LocalizedMessage) -> Self {
+        RoomError::Localized(localized)
+    }
+}
@@ -2,28 +2,400 @@ use std::collections::HashMap;
 
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-use crate::models::{Airport, CargoType, Market};
+use crate::models::{Airport, CargoCategory, CargoType, Market};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketEvent {
     pub event_type: MarketEventType,
+    /// Empty for events that hit a whole category, and for fuel outages
+    /// and storms
     pub affected_cargo: String,
+    /// Every cargo of this kind is hit, rather than just `affected_cargo`
+    #[serde(default)]
+    pub affected_category: Option<CargoCategory>,
     pub affected_airport: String,
+    /// For a storm, how much more fuel flights in and out burn
     pub price_multiplier: f32,
     pub duration_turns: u32,
     pub turns_remaining: u32,
     pub description: String,
+    /// Who started the event by hand, for events a host or admin injected
+    #[serde(default)]
+    pub injected_by: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl MarketEvent {
+    /// Whether the event moves this cargo's price
+    pub fn affects_cargo(&self, cargo_id: &str, cargo_types: &HashMap<String, CargoType>) -> bool {
+        match self.affected_category {
+            Some(category) => cargo_types
+                .get(cargo_id)
+                .is_some_and(|cargo_type| cargo_type.category == category),
+            None => self.affected_cargo == cargo_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum MarketEventType {
     PriceSpike, // Sudden high demand increases prices
     PriceCrash, // Market oversupply crashes prices
     Shortage,   // Limited supply reduces availability
-    Boom,       // Regional economic boom affects multiple goods
-    Recession,  // Economic downturn lowers all prices
+    Boom,       // Buyers flock to a whole category of goods
+    Recession,  // A whole category of goods falls out of favor
     NewsEvent,  // External news affects specific cargo
+    FuelOutage, // An airport runs out of fuel until supply resumes
+    Storm,      // Bad weather makes flights in and out burn more fuel
+}
+
+/// Longest an injected event can run, in world turns
+pub const MAX_INJECTED_EVENT_TURNS: u32 = 20;
+
+/// Most injected events a room can have running at once
+pub const MAX_INJECTED_EVENTS: usize = 6;
+
+/// Furthest an injected event can move prices, in percent
+pub const MIN_INJECTED_PERCENT: i32 = -90;
+pub const MAX_INJECTED_PERCENT: i32 = 300;
+
+/// Longest description a host can write for an injected event
+pub const MAX_EVENT_DESCRIPTION_LENGTH: usize = 200;
+
+/// Who events an admin injects are credited to in the event feed
+pub const GAME_MASTER_NAME: &str = "Game master";
+
+/// An injected event and how many players were told about it
+pub type InjectedEvent = (MarketEvent, usize);
+
+/// A market event as a host or admin asks for it, for a story they're
+/// telling, before it's running
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct EventSpec {
+    pub event_type: MarketEventType,
+    pub airport: String,
+    /// The cargo a spike, crash, shortage or news event hits
+    #[serde(default)]
+    pub cargo: Option<String>,
+    /// The kind of cargo a boom or recession hits
+    #[serde(default)]
+    pub category: Option<CargoCategory>,
+    /// How far prices move, e.g. 50 for half as much again, or for a
+    /// storm how much more fuel flights burn. Each kind of event has a
+    /// default; fuel outages ignore it.
+    #[serde(default)]
+    pub percent: Option<i32>,
+    pub turns: u32,
+    /// What players read; one is written when it's left out
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl EventSpec {
+    /// Luxury goods up 40% at an airport for three turns
+    pub fn luxury_boom(airport: &str) -> Self {
+        Self {
+            event_type: MarketEventType::Boom,
+            airport: airport.to_string(),
+            cargo: None,
+            category: Some(CargoCategory::Luxury),
+            percent: Some(40),
+            turns: 3,
+            description: None,
+        }
+    }
+
+    /// The percent this event moves prices by
+    pub fn effective_percent(&self) -> i32 {
+        self.percent.unwrap_or(match self.event_type {
+            MarketEventType::PriceSpike => 100,
+            MarketEventType::PriceCrash => -50,
+            MarketEventType::Shortage => 140,
+            MarketEventType::Boom => 45,
+            MarketEventType::Recession => -30,
+            MarketEventType::NewsEvent => 50,
+            MarketEventType::FuelOutage => 0,
+            MarketEventType::Storm => 40,
+        })
+    }
+
+    /// Check the event makes sense in this world and build it, labelled
+    /// with who started it
+    pub fn build(
+        &self,
+        airports: &HashMap<String, Airport>,
+        cargo_types: &HashMap<String, CargoType>,
+        injected_by: &str,
+    ) -> Result<MarketEvent, String> {
+        if !(1..=MAX_INJECTED_EVENT_TURNS).contains(&self.turns) {
+            return Err(format!(
+                "An event must run for 1 to {} turns",
+                MAX_INJECTED_EVENT_TURNS
+            ));
+        }
+        let airport = self.airport.trim();
+        if !airports.contains_key(airport) {
+            return Err(format!("Airport {} is not part of this room", airport));
+        }
+
+        let (affected_cargo, affected_category) = match self.event_type {
+            MarketEventType::FuelOutage | MarketEventType::Storm => (String::new(), None),
+            MarketEventType::Boom | MarketEventType::Recession => {
+                let category = self
+                    .category
+                    .ok_or("A boom or recession needs a cargo category")?;
+                if !cargo_types
+                    .values()
+                    .any(|cargo_type| cargo_type.category == category)
+                {
+                    return Err(format!(
+                        "This room has no {} cargo",
+                        category.label().to_lowercase()
+                    ));
+                }
+                (String::new(), Some(category))
+            },
+            _ => {
+                let cargo = self
+                    .cargo
+                    .as_deref()
+                    .map(str::trim)
+                    .ok_or("This kind of event needs a cargo")?;
+                if !cargo_types.contains_key(cargo) {
+                    return Err(format!("Unknown cargo type {}", cargo));
+                }
+                (cargo.to_string(), None)
+            },
+        };
+
+        let percent = self.effective_percent();
+        let price_multiplier = if self.event_type == MarketEventType::FuelOutage {
+            1.0
+        } else {
+            if percent == 0 || !(MIN_INJECTED_PERCENT..=MAX_INJECTED_PERCENT).contains(&percent) {
+                return Err(format!(
+                    "An event's percent must be between {} and {}, and not 0",
+                    MIN_INJECTED_PERCENT, MAX_INJECTED_PERCENT
+                ));
+            }
+            let raises = matches!(
+                self.event_type,
+                MarketEventType::PriceSpike
+                    | MarketEventType::Shortage
+                    | MarketEventType::Boom
+                    | MarketEventType::Storm
+            );
+            let lowers = matches!(
+                self.event_type,
+                MarketEventType::PriceCrash | MarketEventType::Recession
+            );
+            if (raises && percent < 0) || (lowers && percent > 0) {
+                return Err(format!(
+                    "A {:?} can't move prices that way",
+                    self.event_type
+                ));
+            }
+            1.0 + percent as f32 / 100.0
+        };
+
+        let description = match self.description.as_deref().map(str::trim) {
+            Some(description) if description.chars().count() > MAX_EVENT_DESCRIPTION_LENGTH => {
+                return Err(format!(
+                    "An event's description can be at most {} characters",
+                    MAX_EVENT_DESCRIPTION_LENGTH
+                ));
+            },
+            Some(description) if !description.is_empty() => description.to_string(),
+            _ => self.describe(&affected_cargo, airport, airports, cargo_types),
+        };
+
+        Ok(MarketEvent {
+            event_type: self.event_type,
+            affected_cargo,
+            affected_category,
+            affected_airport: airport.to_string(),
+            price_multiplier,
+            duration_turns: self.turns,
+            turns_remaining: self.turns,
+            description,
+            injected_by: Some(injected_by.to_string()),
+        })
+    }
+
+    /// The headline an event of this kind would have had if it had happened
+    /// by itself
+    fn describe(
+        &self,
+        cargo: &str,
+        airport: &str,
+        airports: &HashMap<String, Airport>,
+        cargo_types: &HashMap<String, CargoType>,
+    ) -> String {
+        match self.event_type {
+            MarketEventType::PriceSpike => {
+                EventSystem::generate_spike_description(cargo, airport, airports, cargo_types)
+            },
+            MarketEventType::PriceCrash => {
+                EventSystem::generate_crash_description(cargo, airport, airports, cargo_types)
+            },
+            MarketEventType::Shortage => {
+                EventSystem::generate_shortage_description(cargo, airport, airports, cargo_types)
+            },
+            MarketEventType::NewsEvent => {
+                EventSystem::generate_news_description(cargo, self.effective_percent() > 0)
+            },
+            MarketEventType::Boom | MarketEventType::Recession => {
+                EventSystem::generate_category_description(
+                    self.category.unwrap_or_default(),
+                    self.event_type == MarketEventType::Boom,
+                    1.0 + self.effective_percent() as f32 / 100.0,
+                    airport,
+                    airports,
+                )
+            },
+            MarketEventType::FuelOutage => format!(
+                "⛽ FUEL OUTAGE: Fuel supply to {} is cut off",
+                airports
+                    .get(airport)
+                    .map_or(airport, |airport| airport.name.as_str())
+            ),
+            MarketEventType::Storm => format!(
+                "⛈️ STORM: Headwinds over {} make every flight in or out burn {}% more fuel",
+                airports
+                    .get(airport)
+                    .map_or(airport, |airport| airport.name.as_str()),
+                self.effective_percent()
+            ),
+        }
+    }
+}
+
+/// Chance per turn that some airport runs out of fuel
+pub const FUEL_OUTAGE_CHANCE: f32 = 0.03;
+
+/// Chance per turn of a market event
+pub const MARKET_EVENT_CHANCE: f32 = 0.15;
+
+/// Highest weight a host may give one kind of market event
+pub const MAX_EVENT_WEIGHT: f32 = 10.0;
+
+/// How often market events happen and which kinds turn up. Rooms
+/// persisted before it existed load with the defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct EventConfig {
+    /// Chance per turn of a market event; 0 turns them off
+    #[serde(default = "default_market_event_chance")]
+    pub chance: f32,
+    #[serde(default)]
+    pub weights: EventWeights,
+}
+
+/// How likely each kind of market event is relative to the others. 0
+/// turns a kind off, and 2 makes it twice as likely as one left at 1.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(default)]
+pub struct EventWeights {
+    pub price_spike: f32,
+    pub price_crash: f32,
+    pub shortage: f32,
+    pub news: f32,
+    pub boom: f32,
+    pub recession: f32,
+}
+
+fn default_market_event_chance() -> f32 {
+    MARKET_EVENT_CHANCE
+}
+
+impl Default for EventConfig {
+    fn default() -> Self {
+        Self {
+            chance: default_market_event_chance(),
+            weights: EventWeights::default(),
+        }
+    }
+}
+
+impl Default for EventWeights {
+    fn default() -> Self {
+        Self {
+            price_spike: 1.0,
+            price_crash: 1.0,
+            shortage: 1.0,
+            news: 1.0,
+            boom: 1.0,
+            recession: 1.0,
+        }
+    }
+}
+
+impl EventConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.chance) {
+            return Err("Market event chance must be between 0 and 1".to_string());
+        }
+        for (event_type, weight) in self.weights.all() {
+            if !(0.0..=MAX_EVENT_WEIGHT).contains(&weight) {
+                return Err(format!(
+                    "{:?} weight must be between 0 and {}",
+                    event_type, MAX_EVENT_WEIGHT
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The kinds of market event that can happen, none when they're off
+    pub fn enabled_events(&self) -> Vec<MarketEventType> {
+        if self.chance <= 0.0 {
+            return Vec::new();
+        }
+        self.weights
+            .all()
+            .into_iter()
+            .filter(|(_, weight)| *weight > 0.0)
+            .map(|(event_type, _)| event_type)
+            .collect()
+    }
+}
+
+impl EventWeights {
+    /// Every kind of market event with its weight, in a fixed order
+    pub fn all(&self) -> [(MarketEventType, f32); 6] {
+        [
+            (MarketEventType::PriceSpike, self.price_spike),
+            (MarketEventType::PriceCrash, self.price_crash),
+            (MarketEventType::Shortage, self.shortage),
+            (MarketEventType::NewsEvent, self.news),
+            (MarketEventType::Boom, self.boom),
+            (MarketEventType::Recession, self.recession),
+        ]
+    }
+
+    /// Pick a kind of event by weight; None when every kind is off
+    pub fn pick(&self, rng: &mut impl Rng) -> Option<MarketEventType> {
+        let weights = self.all();
+        let total: f32 = weights.iter().map(|(_, weight)| weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut roll = rng.gen_range(0.0..total);
+        let mut picked = None;
+        for (event_type, weight) in weights {
+            if weight <= 0.0 {
+                continue;
+            }
+            // Rounding can leave a sliver past the last weight, so hold on
+            // to the last kind that's on
+            picked = Some(event_type);
+            if roll < weight {
+                break;
+            }
+            roll -= weight;
+        }
+        picked
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +410,63 @@ pub struct GameStatistics {
     pub best_single_trade: u32,
     pub most_profitable_cargo: String,
     pub efficiency_score: f32,
+    #[serde(default)]
+    pub fuel_spend: u32,
+    /// Broker fees paid on cargo trades, part of `total_expenses`
+    #[serde(default)]
+    pub trading_fees: u32,
+    #[serde(default)]
+    pub routes: Vec<RouteFuelStats>,
+    /// Fuel burned in flight, time spent holding for a slot included
+    #[serde(default)]
+    pub fuel_burned: u32,
+    /// Index into `routes` of the leg that brought the player to where they
+    /// are now; sales made on the ground are credited to it.
+    #[serde(default)]
+    current_route: Option<usize>,
+}
+
+/// Fuel economy for one directed route, accumulated over every time it was
+/// flown. Revenue is whatever the player sold after landing, before taking
+/// off again.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RouteFuelStats {
+    pub from: String,
+    pub to: String,
+    pub legs_flown: u32,
+    pub distance: f64,
+    pub fuel_used: u32,
+    pub fuel_cost: u32,
+    pub revenue: u32,
+}
+
+impl RouteFuelStats {
+    /// A single flight; fuel is valued at the departure airport's price
+    pub fn leg(from: &str, to: &str, distance: f64, fuel_used: u32, fuel_price: u32) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            legs_flown: 1,
+            distance,
+            fuel_used,
+            fuel_cost: fuel_used.saturating_mul(fuel_price),
+            revenue: 0,
+        }
+    }
+
+    pub fn net_result(&self) -> i64 {
+        self.revenue as i64 - self.fuel_cost as i64
+    }
+
+    /// Dollars of sales earned per dollar of fuel burned
+    #[allow(dead_code)]
+    pub fn revenue_per_fuel_dollar(&self) -> Option<f32> {
+        if self.fuel_cost == 0 {
+            None
+        } else {
+            Some(self.revenue as f32 / self.fuel_cost as f32)
+        }
+    }
 }
 
 impl Default for GameStatistics {
@@ -59,11 +488,19 @@ impl GameStatistics {
             best_single_trade: 0,
             most_profitable_cargo: String::new(),
             efficiency_score: 0.0,
+            fuel_spend: 0,
+            trading_fees: 0,
+            routes: Vec::new(),
+            fuel_burned: 0,
+            current_route: None,
         }
     }
 
     pub fn record_sale(&mut self, cargo_type: &str, revenue: u32) {
-        self.total_revenue += revenue;
+        self.total_revenue = self.total_revenue.saturating_add(revenue);
+        if let Some(route) = self.current_route.and_then(|i| self.routes.get_mut(i)) {
+            route.revenue = route.revenue.saturating_add(revenue);
+        }
         self.net_profit = self.total_revenue.saturating_sub(self.total_expenses);
         self.cargo_trades += 1;
 
@@ -74,7 +511,7 @@ impl GameStatistics {
     }
 
     pub fn record_purchase(&mut self, expense: u32) {
-        self.total_expenses += expense;
+        self.total_expenses = self.total_expenses.saturating_add(expense);
         self.net_profit = self.total_revenue.saturating_sub(self.total_expenses);
     }
 
@@ -83,11 +520,61 @@ impl GameStatistics {
         self.cargo_trades += 1;
     }
 
+    pub fn record_trading_fee(&mut self, fee: u32) {
+        self.trading_fees = self.trading_fees.saturating_add(fee);
+        self.record_purchase(fee);
+    }
+
     pub fn record_fuel_purchase(&mut self, fuel_amount: u32, cost: u32) {
-        self.fuel_purchased += fuel_amount;
+        self.fuel_purchased = self.fuel_purchased.saturating_add(fuel_amount);
+        self.fuel_spend = self.fuel_spend.saturating_add(cost);
+        self.record_purchase(cost);
+    }
+
+    /// Landing fees, ground transfer fares and anything else paid to get
+    /// somewhere other than fuel
+    pub fn record_travel_cost(&mut self, cost: u32) {
         self.record_purchase(cost);
     }
 
+    pub fn record_leg(&mut self, leg: RouteFuelStats) {
+        self.record_travel(&leg.to, leg.distance);
+        self.fuel_burned = self.fuel_burned.saturating_add(leg.fuel_used);
+
+        let index = match self
+            .routes
+            .iter()
+            .position(|route| route.from == leg.from && route.to == leg.to)
+        {
+            Some(index) => {
+                let route = &mut self.routes[index];
+                route.legs_flown += leg.legs_flown;
+                route.distance += leg.distance;
+                route.fuel_used += leg.fuel_used;
+                route.fuel_cost = route.fuel_cost.saturating_add(leg.fuel_cost);
+                route.revenue = route.revenue.saturating_add(leg.revenue);
+                index
+            },
+            None => {
+                self.routes.push(leg);
+                self.routes.len() - 1
+            },
+        };
+        self.current_route = Some(index);
+    }
+
+    pub fn best_route(&self) -> Option<&RouteFuelStats> {
+        self.routes.iter().max_by_key(|route| route.net_result())
+    }
+
+    /// Only meaningful once there is more than one route to compare
+    pub fn worst_route(&self) -> Option<&RouteFuelStats> {
+        if self.routes.len() < 2 {
+            return None;
+        }
+        self.routes.iter().min_by_key(|route| route.net_result())
+    }
+
     pub fn record_travel(&mut self, airport: &str, distance: f64) {
         self.distances_traveled += distance;
         if !self.airports_visited.contains(&airport.to_string()) {
@@ -95,7 +582,23 @@ impl GameStatistics {
         }
     }
 
-    #[allow(dead_code)]
+    /// Everything earned less everything spent; unlike `net_profit` this
+    /// can go negative
+    pub fn profit(&self) -> i64 {
+        self.total_revenue as i64 - self.total_expenses as i64
+    }
+
+    /// Units of fuel burned for each dollar of profit, lower being greener.
+    /// None until the player is in profit.
+    pub fn fuel_per_profit_dollar(&self) -> Option<f32> {
+        let profit = self.profit();
+        if profit <= 0 {
+            None
+        } else {
+            Some(self.fuel_burned as f32 / profit as f32)
+        }
+    }
+
     pub fn calculate_efficiency(&mut self, turns: u32) {
         if turns > 0 {
             self.efficiency_score = self.net_profit as f32 / turns as f32;
@@ -111,29 +614,41 @@ impl EventSystem {
     pub fn generate_random_event(
         airports: &HashMap<String, Airport>,
         cargo_types: &HashMap<String, CargoType>,
+        config: &EventConfig,
         rng: &mut impl Rng,
     ) -> Option<MarketEvent> {
-        // 15% chance of generating an event each turn
-        if rng.gen_range(0.0..1.0) > 0.15 {
+        if rng.gen_range(0.0..1.0) >= config.chance {
             return None;
         }
 
-        let event_types = [
-            MarketEventType::PriceSpike,
-            MarketEventType::PriceCrash,
-            MarketEventType::Shortage,
-            MarketEventType::NewsEvent,
-        ];
-
-        let event_type = event_types[rng.gen_range(0..event_types.len())].clone();
+        let event_type = config.weights.pick(rng)?;
 
-        // Pick random cargo and airport
-        let cargo_ids: Vec<_> = cargo_types.keys().collect();
-        let airport_ids: Vec<_> = airports.keys().collect();
+        // Pick random cargo and airport; HashMap order isn't stable, so sort
+        // before picking
+        let mut cargo_ids: Vec<_> = cargo_types.keys().collect();
+        let mut airport_ids: Vec<_> = airports.keys().collect();
+        if cargo_ids.is_empty() || airport_ids.is_empty() {
+            return None;
+        }
+        cargo_ids.sort();
+        airport_ids.sort();
 
         let affected_cargo = cargo_ids[rng.gen_range(0..cargo_ids.len())].clone();
         let affected_airport = airport_ids[rng.gen_range(0..airport_ids.len())].clone();
 
+        if matches!(
+            event_type,
+            MarketEventType::Boom | MarketEventType::Recession
+        ) {
+            return Self::generate_category_event(
+                event_type,
+                affected_airport,
+                airports,
+                cargo_types,
+                rng,
+            );
+        }
+
         let (multiplier, duration, description) = match event_type {
             MarketEventType::PriceSpike => {
                 let mult = rng.gen_range(1.5..2.5);
@@ -180,14 +695,114 @@ impl EventSystem {
         Some(MarketEvent {
             event_type,
             affected_cargo,
+            affected_category: None,
+            affected_airport,
+            price_multiplier: multiplier,
+            duration_turns: duration,
+            turns_remaining: duration,
+            description,
+            injected_by: None,
+        })
+    }
+
+    /// A boom or recession in one category of the cargo the world trades
+    fn generate_category_event(
+        event_type: MarketEventType,
+        affected_airport: String,
+        airports: &HashMap<String, Airport>,
+        cargo_types: &HashMap<String, CargoType>,
+        rng: &mut impl Rng,
+    ) -> Option<MarketEvent> {
+        let mut categories: Vec<CargoCategory> = cargo_types
+            .values()
+            .map(|cargo_type| cargo_type.category)
+            .collect();
+        // HashMap order isn't stable, so sort before picking
+        categories.sort();
+        categories.dedup();
+        if categories.is_empty() {
+            return None;
+        }
+        let category = categories[rng.gen_range(0..categories.len())];
+
+        let boom = matches!(event_type, MarketEventType::Boom);
+        let (multiplier, duration) = if boom {
+            (rng.gen_range(1.3..1.6), rng.gen_range(3..7))
+        } else {
+            (rng.gen_range(0.6..0.8), rng.gen_range(4..9))
+        };
+        let description = Self::generate_category_description(
+            category,
+            boom,
+            multiplier,
+            &affected_airport,
+            airports,
+        );
+
+        Some(MarketEvent {
+            event_type,
+            affected_cargo: String::new(),
+            affected_category: Some(category),
             affected_airport,
             price_multiplier: multiplier,
             duration_turns: duration,
             turns_remaining: duration,
             description,
+            injected_by: None,
         })
     }
 
+    fn generate_category_description(
+        category: CargoCategory,
+        boom: bool,
+        multiplier: f32,
+        airport: &str,
+        airports: &HashMap<String, Airport>,
+    ) -> String {
+        let airport_name = airports
+            .get(airport)
+            .map(|a| a.name.as_str())
+            .unwrap_or(airport);
+        let label = category.label();
+        let percent = ((multiplier - 1.0).abs() * 100.0).round();
+
+        let scenarios = if boom {
+            [
+                format!(
+                    "🛍️ {} BOOM: Buyers flood {} - {} goods up {}%",
+                    label.to_uppercase(),
+                    airport_name,
+                    label.to_lowercase(),
+                    percent
+                ),
+                format!(
+                    "📈 SECTOR RALLY: Every {} line at {} is in demand, prices up {}%",
+                    label.to_lowercase(),
+                    airport_name,
+                    percent
+                ),
+            ]
+        } else {
+            [
+                format!(
+                    "🚔 {} CRACKDOWN: Customs clampdown at {} knocks {} prices down {}%",
+                    label.to_uppercase(),
+                    airport_name,
+                    label.to_lowercase(),
+                    percent
+                ),
+                format!(
+                    "📉 SECTOR SLUMP: Nobody at {} wants {} goods - prices down {}%",
+                    airport_name,
+                    label.to_lowercase(),
+                    percent
+                ),
+            ]
+        };
+
+        scenarios[rand::thread_rng().gen_range(0..scenarios.len())].clone()
+    }
+
     fn generate_spike_description(
         cargo: &str,
         airport: &str,
@@ -345,24 +960,136 @@ impl EventSystem {
         }
     }
 
-    pub fn apply_event_to_market(event: &MarketEvent, market: &mut Market) {
-        if let Some(current_price) = market.get_cargo_price(&event.affected_cargo) {
-            let new_price = (current_price as f32 * event.price_multiplier) as u32;
-            let new_price = new_price.max(1); // Ensure minimum price of $1
-            market.set_cargo_price(&event.affected_cargo, new_price);
+    /// Maybe cut off fuel supply at an airport that still has it, for a few
+    /// turns
+    pub fn generate_fuel_outage(
+        airports: &HashMap<String, Airport>,
+        active_events: &[MarketEvent],
+        chance: f32,
+        rng: &mut impl Rng,
+    ) -> Option<MarketEvent> {
+        if rng.gen_range(0.0..1.0) >= chance {
+            return None;
+        }
+
+        let mut candidates: Vec<&Airport> = airports
+            .values()
+            .filter(|airport| Self::fuel_outage_at(active_events, &airport.id).is_none())
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        // HashMap order isn't stable, so sort before picking
+        candidates.sort_by(|a, b| a.id.cmp(&b.id));
+        let airport = candidates[rng.gen_range(0..candidates.len())];
+
+        let duration = rng.gen_range(2..=4);
+        let scenarios = [
+            format!(
+                "⛽ FUEL OUTAGE: Supply truck strike leaves {} without fuel",
+                airport.name
+            ),
+            format!(
+                "⛽ FUEL OUTAGE: Contaminated delivery grounds the fuel farm at {}",
+                airport.name
+            ),
+            format!(
+                "⛽ FUEL OUTAGE: Pipeline repairs cut off fuel supply to {}",
+                airport.name
+            ),
+        ];
+
+        Some(MarketEvent {
+            event_type: MarketEventType::FuelOutage,
+            affected_cargo: String::new(),
+            affected_category: None,
+            affected_airport: airport.id.clone(),
+            price_multiplier: 1.0,
+            duration_turns: duration,
+            turns_remaining: duration,
+            description: scenarios[rng.gen_range(0..scenarios.len())].clone(),
+            injected_by: None,
+        })
+    }
+
+    /// The outage affecting an airport, if there is one
+    pub fn fuel_outage_at<'a>(
+        events: &'a [MarketEvent],
+        airport_id: &str,
+    ) -> Option<&'a MarketEvent> {
+        events.iter().find(|event| {
+            matches!(event.event_type, MarketEventType::FuelOutage)
+                && event.affected_airport == airport_id
+        })
+    }
+
+    /// How much more fuel a flight between two airports burns for the
+    /// weather at either end: the worse storm counts, 1.0 in clear skies
+    pub fn weather_multiplier(events: &[MarketEvent], from: &str, to: &str) -> f64 {
+        events
+            .iter()
+            .filter(|event| {
+                event.event_type == MarketEventType::Storm
+                    && (event.affected_airport == from || event.affected_airport == to)
+            })
+            .map(|event| event.price_multiplier as f64)
+            .fold(1.0, f64::max)
+    }
+
+    pub fn apply_event_to_market(
+        event: &MarketEvent,
+        market: &mut Market,
+        cargo_types: &HashMap<String, CargoType>,
+    ) {
+        if matches!(event.event_type, MarketEventType::FuelOutage) {
+            market.start_fuel_outage();
+            return;
+        }
+
+        let affected: Vec<String> = market
+            .cargo_prices
+            .keys()
+            .filter(|cargo_id| event.affects_cargo(cargo_id, cargo_types))
+            .cloned()
+            .collect();
+        for cargo_id in affected {
+            if let Some(current_price) = market.get_cargo_price(&cargo_id) {
+                let new_price =
+                    (current_price as f64 * event.price_multiplier as f64).round() as u32;
+                let new_price = new_price.max(1); // Ensure minimum price of $1
+                market.set_cargo_price(&cargo_id, new_price);
+            }
+        }
+    }
+
+    /// Undo what an event did to a market once it has run its course
+    pub fn clear_event_from_market(event: &MarketEvent, market: &mut Market) {
+        if matches!(event.event_type, MarketEventType::FuelOutage) {
+            market.end_fuel_outage();
         }
     }
 
     pub fn update_events(events: &mut Vec<MarketEvent>) -> Vec<String> {
+        Self::expire_events(events)
+            .iter()
+            .map(|event| {
+                format!(
+                    "Market conditions normalize: {} effects have ended",
+                    event.description.split(':').next().unwrap_or("Event")
+                )
+            })
+            .collect()
+    }
+
+    /// Count a turn off every event, removing and returning the ones that
+    /// have ended
+    pub fn expire_events(events: &mut Vec<MarketEvent>) -> Vec<MarketEvent> {
         let mut expired_events = Vec::new();
 
         events.retain_mut(|event| {
             event.turns_remaining = event.turns_remaining.saturating_sub(1);
             if event.turns_remaining == 0 {
-                expired_events.push(format!(
-                    "Market conditions normalize: {} effects have ended",
-                    event.description.split(':').next().unwrap_or("Event")
-                ));
+                expired_events.push(event.clone());
                 false
             } else {
                 true
@@ -7,26 +7,78 @@ use crate::{
     models::{Airport, CargoType, GameStats, Market, MessageBoard, Player},
     systems::{
         MarketSystem,
-        events::{EventSystem, MarketEvent},
+        bank::{BankStatement, BankSystem},
+        calendar::{GameCalendar, Season},
+        distance::DistanceCache,
+        events::{EventConfig, EventSystem, FUEL_OUTAGE_CHANCE, MarketEvent},
+        price_alerts::{PriceAlert, PriceAlerts},
+        reconcile::{AirportReconciler, ReconciledWorld, WorldAdjustment},
+        security::{Night, SecuritySystem},
+        trading::{TradeQuote, TradingError, TradingSystem},
+        travel::{FlightConditions, TravelError, TravelInfo, TravelSystem},
+        turns::{TurnAction, TurnEngine},
     },
 };
 
-// Use a string key for JSON serialization compatibility
-pub type DistanceCache = HashMap<String, f64>;
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
     pub player: Player,
     pub airports: HashMap<String, Airport>,
     pub cargo_types: HashMap<String, CargoType>,
     pub markets: HashMap<String, Market>,
-    pub distance_cache: DistanceCache,
+    /// Worked out as legs are flown; older saves' `distance_cache` is ignored
+    #[serde(skip)]
+    pub distances: DistanceCache,
     pub turn_number: u32,
     pub cheat_mode: bool,
     pub stats: GameStats,
     pub win_condition_money: u32,
     pub active_events: Vec<MarketEvent>,
     pub message_board: MessageBoard,
+    #[serde(default)]
+    pub price_alerts: PriceAlerts,
+    #[serde(default)]
+    pub calendar: GameCalendar,
+}
+
+/// How a turn ended for the player
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TurnReport {
+    pub night: Night,
+    /// Held cargo that's now worth well over its cost here
+    pub price_alerts: Vec<PriceAlert>,
+    /// Loan payments taken at the end of the turn, if the player owes any
+    pub bank: Option<BankStatement>,
+}
+
+impl TurnReport {
+    /// The night, loan payments and any price alerts, after an action's
+    /// message
+    pub fn append_to(&self, message: &mut String) {
+        if let Some(report) = self.night.report() {
+            message.push_str(&format!(". {}", report));
+        }
+        if let Some(statement) = &self.bank {
+            message.push_str(&format!(". {}", statement.report()));
+        }
+        for alert in &self.price_alerts {
+            message.push_str(&format!(". {}", alert.message()));
+        }
+    }
+}
+
+/// What a trade or refuel came to, and the turn it took if it cost one
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completed<T> {
+    pub result: T,
+    pub turn: Option<TurnReport>,
+}
+
+/// Fuel that went into the tank
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuelPurchase {
+    pub quantity: u32,
+    pub cost: u32,
 }
 
 impl GameState {
@@ -48,10 +100,21 @@ impl GameState {
             .map(|v| v == "1" || v == "true" || v == "on")
             .unwrap_or(false);
 
+        // A world loaded from a file may not have the usual first airport
+        let starting_airport = if airports.contains_key(&config.starting_airport) {
+            config.starting_airport.clone()
+        } else {
+            airports
+                .keys()
+                .min()
+                .cloned()
+                .unwrap_or_else(|| config.starting_airport.clone())
+        };
+
         let mut game_state = Self {
             player: Player::new(
                 config.starting_money,
-                &config.starting_airport,
+                &starting_airport,
                 config.max_fuel,
                 config.max_cargo_weight,
                 config.fuel_efficiency,
@@ -59,67 +122,90 @@ impl GameState {
             airports: airports.clone(),
             cargo_types,
             markets: HashMap::new(),
-            distance_cache: HashMap::new(),
+            distances: DistanceCache::new(),
             turn_number: 1,
             cheat_mode,
             stats: GameStats::new(config.starting_money),
             win_condition_money: config.win_condition_money,
             active_events: Vec::new(),
             message_board: MessageBoard::new(50),
+            price_alerts: PriceAlerts::default(),
+            calendar: config.calendar,
         };
 
         // Initialize starting airport in stats
-        game_state
-            .stats
-            .airports_visited
-            .push(config.starting_airport.clone());
+        game_state.stats.airports_visited.push(starting_airport);
 
         // Apply starting fuel percentage
         game_state.player.fuel = (config.max_fuel as f32 * config.starting_fuel_percentage) as u32;
 
-        // Pre-calculate all distances and initialize markets
-        game_state.initialize_distance_cache();
         game_state.initialize_markets();
 
         game_state
     }
 
-    fn initialize_distance_cache(&mut self) {
-        let airport_ids: Vec<String> = self.airports.keys().cloned().collect();
-
-        for i in 0..airport_ids.len() {
-            for j in i..airport_ids.len() {
-                let id1 = &airport_ids[i];
-                let id2 = &airport_ids[j];
-
-                if i == j {
-                    let key = format!("{}-{}", id1, id2);
-                    self.distance_cache.insert(key, 0.0);
-                } else {
-                    let airport1 = &self.airports[id1];
-                    let airport2 = &self.airports[id2];
-                    let distance = airport1.distance_to(airport2);
-
-                    // Store both directions
-                    let key1 = format!("{}-{}", id1, id2);
-                    let key2 = format!("{}-{}", id2, id1);
-                    self.distance_cache.insert(key1, distance);
-                    self.distance_cache.insert(key2, distance);
-                }
-            }
-        }
-    }
-
     fn initialize_markets(&mut self) {
         let mut rng = rand::thread_rng();
         self.markets =
             MarketSystem::initialize_all_markets(&self.airports, &self.cargo_types, &mut rng);
     }
 
-    #[allow(dead_code)]
+    /// Fit a loaded game to the airports that exist now: renamed airports
+    /// follow their new id, removed ones are dropped, and the player is
+    /// moved off one that's gone.
+    pub fn reconcile_airports(
+        &mut self,
+        reconciler: &AirportReconciler,
+    ) -> Result<Vec<WorldAdjustment>, String> {
+        let ReconciledWorld {
+            mut adjustments,
+            removed,
+        } = reconciler.reconcile_world(
+            &mut self.airports,
+            &mut self.markets,
+            &mut self.active_events,
+        )?;
+        adjustments.extend(reconciler.place_player(
+            &mut self.player,
+            "Your aircraft",
+            &self.airports,
+            &removed,
+        ));
+
+        // Visits to airports that have closed still count
+        for airport_id in &mut self.stats.airports_visited {
+            if let Some(new_id) = reconciler.resolve(airport_id) {
+                *airport_id = new_id;
+            }
+        }
+        let mut seen = std::collections::HashSet::new();
+        self.stats
+            .airports_visited
+            .retain(|airport_id| seen.insert(airport_id.clone()));
+
+        Ok(adjustments)
+    }
+
+    /// Kilometers between two airports, by id
     pub fn get_distance(&self, from: &str, to: &str) -> Option<f64> {
-        let key = format!("{}-{}", from, to);
-        self.distance_cache.get(&key).copied()
+        self.distances.between_ids(&self.airports, from, to)
+    }
+
+    /// Swap in a new set of airports, forgetting distances to any that
+    /// went away. Airports that moved are re-measured when next asked.
+    #[allow(dead_code)]
+    pub fn set_airports(&mut self, airports: HashMap<String, Airport>) {
+        self.distances.retain_airports(&airports);
+        self.airports = airports;
+    }
+
+    /// The in-game date of the current turn
+    pub fn date(&self) -> String {
+        self.calendar.date_label(self.turn_number)
+    }
+
+    pub fn season(&self) -> Season {
+        self.calendar.season_at(self.turn_number)
     }
 
     pub fn get_current_airport(&self) -> Option<&Airport> {
@@ -130,11 +216,6 @@ impl GameState {
         self.markets.get(&self.player.current_airport)
     }
 
-    #[allow(dead_code)]
-    pub fn get_current_market_mut(&mut self) -> Option<&mut Market> {
-        self.markets.get_mut(&self.player.current_airport)
-    }
-
     pub fn get_available_destinations(&self) -> Vec<&Airport> {
         self.airports
             .values()
@@ -142,47 +223,204 @@ impl GameState {
             .collect()
     }
 
-    pub fn advance_turn(&mut self) {
+    /// Move on a turn. Every turn ends with a night parked at the player's
+    /// current airport, then a look at what the cargo held is worth there.
+    pub fn advance_turn(&mut self) -> TurnReport {
         self.turn_number += 1;
+        self.player.turn_trades.reset();
 
         // Process market events
         self.process_market_events();
 
         // Chance to generate new event
         self.maybe_generate_event();
+
+        let night = self.spend_night(&mut rand::thread_rng());
+        TurnReport {
+            night,
+            bank: BankSystem::charge_turn(&mut self.player),
+            price_alerts: self.check_price_alerts(),
+        }
+    }
+
+    /// A turn that passes while the player stays put, in a real-time game:
+    /// prices drift everywhere and the night still has to be paid for
+    pub fn pass_idle_turn(&mut self) -> TurnReport {
+        self.refresh_all_markets();
+        self.advance_turn()
+    }
+
+    pub fn check_price_alerts(&mut self) -> Vec<PriceAlert> {
+        let Some(market) = self.markets.get(&self.player.current_airport) else {
+            return Vec::new();
+        };
+        self.price_alerts
+            .check(&self.player.cargo_inventory, market, &self.cargo_types)
+    }
+
+    pub fn spend_night(&mut self, rng: &mut impl rand::Rng) -> Night {
+        let Some(airport) = self.airports.get(&self.player.current_airport) else {
+            return Night::Quiet;
+        };
+        SecuritySystem::spend_night(&mut self.player, airport, &self.cargo_types, rng)
     }
 
     fn process_market_events(&mut self) {
-        // Update event durations
-        let expired_messages = EventSystem::update_events(&mut self.active_events);
+        // Update event durations, undoing the ones that have ended
+        for event in EventSystem::expire_events(&mut self.active_events) {
+            if let Some(market) = self.markets.get_mut(&event.affected_airport) {
+                EventSystem::clear_event_from_market(&event, market);
+            }
+        }
+
+        // A turn's worth of fuel deliveries
+        for market in self.markets.values_mut() {
+            market.restock_fuel();
+        }
 
         // Re-apply active events to markets
         for event in &self.active_events {
             if let Some(market) = self.markets.get_mut(&event.affected_airport) {
-                EventSystem::apply_event_to_market(event, market);
+                EventSystem::apply_event_to_market(event, market, &self.cargo_types);
             }
         }
-
-        // Could store expired messages for display if needed
-        for _ in expired_messages {
-            // Events expired silently for now
-        }
     }
 
     fn maybe_generate_event(&mut self) {
         let mut rng = rand::thread_rng();
-        if let Some(new_event) =
-            EventSystem::generate_random_event(&self.airports, &self.cargo_types, &mut rng)
-        {
+        let new_events = [
+            EventSystem::generate_random_event(
+                &self.airports,
+                &self.cargo_types,
+                &EventConfig::default(),
+                &mut rng,
+            ),
+            EventSystem::generate_fuel_outage(
+                &self.airports,
+                &self.active_events,
+                FUEL_OUTAGE_CHANCE,
+                &mut rng,
+            ),
+        ];
+
+        for new_event in new_events.into_iter().flatten() {
             // Apply the event to the affected market immediately
             if let Some(market) = self.markets.get_mut(&new_event.affected_airport) {
-                EventSystem::apply_event_to_market(&new_event, market);
+                EventSystem::apply_event_to_market(&new_event, market, &self.cargo_types);
             }
 
             self.active_events.push(new_event);
         }
     }
 
+    /// Buy cargo at the current airport. Trades, refuels and flights all go
+    /// through these few methods, so every front end records the same
+    /// statistics and takes turns the same way.
+    pub fn buy_cargo(
+        &mut self,
+        cargo_id: &str,
+        quantity: u32,
+    ) -> Result<Completed<TradeQuote>, TradingError> {
+        let market = self
+            .markets
+            .get(&self.player.current_airport)
+            .ok_or(TradingError::CargoNotAvailable)?;
+        let quote = TradingSystem::buy_cargo(
+            &mut self.player,
+            market,
+            &self.cargo_types,
+            cargo_id,
+            quantity,
+        )?;
+        self.stats.record_trade(
+            -(quote.total_cost() as i64),
+            cargo_id.to_string(),
+            quantity,
+            true,
+        );
+        Ok(self.complete(quote, TurnAction::Trade))
+    }
+
+    /// Sell cargo at the current airport. Profit is measured against the
+    /// cargo's usual price, since what the player paid isn't tracked.
+    pub fn sell_cargo(
+        &mut self,
+        cargo_id: &str,
+        quantity: u32,
+    ) -> Result<Completed<TradeQuote>, TradingError> {
+        let market = self
+            .markets
+            .get(&self.player.current_airport)
+            .ok_or(TradingError::CargoNotAvailable)?;
+        let quote = TradingSystem::sell_cargo(&mut self.player, market, cargo_id, quantity)?;
+        let usual_price = self
+            .cargo_types
+            .get(cargo_id)
+            .map(|cargo_type| cargo_type.base_price)
+            .unwrap_or(0);
+        let profit = quote.proceeds() as i64 - usual_price as i64 * quantity as i64;
+        self.stats
+            .record_trade(profit, cargo_id.to_string(), quantity, false);
+        self.stats.best_streak = self.stats.best_streak.max(self.player.streak.best);
+        Ok(self.complete(quote, TurnAction::Trade))
+    }
+
+    /// Buy up to `quantity` fuel at the current airport, as much as the tank
+    /// and the pumps allow
+    pub fn buy_fuel(&mut self, quantity: u32) -> Result<Completed<FuelPurchase>, TradingError> {
+        let market = self
+            .markets
+            .get_mut(&self.player.current_airport)
+            .ok_or(TradingError::FuelUnavailable)?;
+        let fuel_before = self.player.fuel;
+        let cost = TradingSystem::buy_fuel(&mut self.player, market, quantity)?;
+        let purchase = FuelPurchase {
+            quantity: self.player.fuel - fuel_before,
+            cost,
+        };
+        self.stats.record_fuel_purchase(purchase.quantity);
+        Ok(self.complete(purchase, TurnAction::BuyFuel))
+    }
+
+    /// Fly to another airport, taking the turn the flight costs
+    pub fn fly_to(&mut self, destination_id: &str) -> Result<TravelInfo, TravelError> {
+        let origin = self.player.current_airport.clone();
+        let travel_info = TravelSystem::travel_to(self, destination_id)?;
+        self.stats.record_travel(
+            travel_info.distance_km,
+            travel_info.fuel_consumed,
+            origin,
+            destination_id.to_string(),
+        );
+        self.stats.update_money_stats(self.player.money);
+        Ok(travel_info)
+    }
+
+    fn complete<T>(&mut self, result: T, action: TurnAction) -> Completed<T> {
+        self.stats.update_money_stats(self.player.money);
+        Completed {
+            result,
+            turn: TurnEngine::finish(self, action),
+        }
+    }
+
+    /// The outage keeping fuel from an airport, if any
+    pub fn fuel_outage_at(&self, airport_id: &str) -> Option<&MarketEvent> {
+        EventSystem::fuel_outage_at(&self.active_events, airport_id)
+    }
+
+    /// The load aboard and the weather between two airports, for the fuel
+    /// a flight between them burns
+    pub fn flight_conditions(&self, from: &str, to: &str) -> FlightConditions {
+        FlightConditions::for_route(
+            &self.player,
+            &self.cargo_types,
+            &self.active_events,
+            from,
+            to,
+        )
+    }
+
     pub fn is_game_won(&self) -> bool {
         self.player.money >= self.win_condition_money
     }
@@ -210,7 +448,6 @@ impl GameState {
         }
     }
 
-    #[allow(dead_code)]
     pub fn refresh_all_markets(&mut self) {
         let mut rng = rand::thread_rng();
         for (airport_id, market) in self.markets.iter_mut() {
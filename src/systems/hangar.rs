@@ -0,0 +1,41 @@
+use crate::models::{Player, aircraft::AircraftUpgrade};
+
+/// Fuel capacity each fuel tank level adds
+pub const FUEL_TANK_STEP: u32 = 50;
+
+/// Cargo capacity each cargo hold level adds, in kg
+pub const CARGO_HOLD_STEP: u32 = 250;
+
+/// Range each efficiency level adds per unit of fuel, in km
+pub const FUEL_EFFICIENCY_STEP: f32 = 1.5;
+
+pub struct HangarSystem;
+
+impl HangarSystem {
+    /// Fit the next level of `upgrade`, paying for it. Returns what it cost.
+    pub fn buy_upgrade(player: &mut Player, upgrade: AircraftUpgrade) -> Result<u32, String> {
+        let cost = player.aircraft.next_cost(upgrade).ok_or_else(|| {
+            format!(
+                "Your aircraft already has the best {} there is",
+                upgrade.label().to_lowercase()
+            )
+        })?;
+        if !player.spend_money(cost) {
+            return Err(format!(
+                "The next {} costs ${}",
+                upgrade.label().to_lowercase(),
+                cost
+            ));
+        }
+
+        player.aircraft.fit(upgrade);
+        match upgrade {
+            AircraftUpgrade::FuelTank => player.max_fuel += FUEL_TANK_STEP,
+            AircraftUpgrade::CargoHold => player.max_cargo_weight += CARGO_HOLD_STEP,
+            AircraftUpgrade::FuelEfficiency => player.fuel_efficiency += FUEL_EFFICIENCY_STEP,
+            // Checked each flight, see Aircraft::transit_risk
+            AircraftUpgrade::Engines => {},
+        }
+        Ok(cost)
+    }
+}
@@ -0,0 +1,244 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::systems::errors::RoomError;
+
+/// Premium charged per flight, as a percentage of the cargo's value at the
+/// departure airport
+pub const PREMIUM_PERCENT: u32 = 2;
+pub const MINIMUM_PREMIUM: u32 = 10;
+
+/// How many of the player's own turns a claim takes to settle
+pub const CLAIM_PROCESSING_TURNS: u32 = 3;
+
+// Settlement odds: the rest of the time a claim is denied
+const FULL_PAYOUT_CHANCE: f64 = 0.6;
+const PARTIAL_PAYOUT_CHANCE: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum CargoLossCause {
+    Spoilage,
+    Theft,
+}
+
+impl CargoLossCause {
+    #[allow(dead_code)]
+    pub fn label(&self) -> &'static str {
+        match self {
+            CargoLossCause::Spoilage => "Spoilage",
+            CargoLossCause::Theft => "Theft",
+        }
+    }
+}
+
+/// Cargo destroyed or stolen on a flight.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CargoLoss {
+    pub id: Uuid,
+    pub cause: CargoLossCause,
+    pub cargo_type: String,
+    pub quantity: u32,
+    pub value: u32,         // At the prices where the loss was discovered
+    pub airport_id: String, // Where the loss was discovered
+    pub turn: u32,          // The player's own turn
+    pub insured: bool,      // Whether cover was in force at the time
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ClaimStatus {
+    Pending,
+    Paid,
+    PartiallyPaid,
+    Denied,
+}
+
+impl ClaimStatus {
+    #[allow(dead_code)]
+    pub fn label(&self) -> &'static str {
+        match self {
+            ClaimStatus::Pending => "Pending",
+            ClaimStatus::Paid => "Paid",
+            ClaimStatus::PartiallyPaid => "Partially paid",
+            ClaimStatus::Denied => "Denied",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InsuranceClaim {
+    pub id: Uuid,
+    pub loss: CargoLoss,
+    pub filed_at_turn: u32,
+    pub resolves_at_turn: u32,
+    pub status: ClaimStatus,
+    pub payout: u32,
+    pub note: String,
+}
+
+/// A player's cover, the losses they haven't claimed for yet and their
+/// claims queue.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct InsuranceAccount {
+    pub insured: bool,
+    pub losses: Vec<CargoLoss>,
+    pub claims: Vec<InsuranceClaim>,
+}
+
+impl InsuranceAccount {
+    /// Premium for a flight carrying cargo worth `cargo_value`
+    pub fn premium(cargo_value: u32) -> u32 {
+        if cargo_value == 0 {
+            return 0;
+        }
+        let premium = cargo_value as u64 * PREMIUM_PERCENT as u64 / 100;
+        (premium as u32).max(MINIMUM_PREMIUM)
+    }
+
+    pub fn record_loss(&mut self, loss: CargoLoss) {
+        self.losses.push(loss);
+    }
+
+    /// Turn an unclaimed loss into a pending claim
+    pub fn file_claim(
+        &mut self,
+        loss_id: Uuid,
+        current_turn: u32,
+    ) -> Result<InsuranceClaim, RoomError> {
+        let index = self
+            .losses
+            .iter()
+            .position(|loss| loss.id == loss_id)
+            .ok_or(RoomError::LossNotFound)?;
+
+        if !self.losses[index].insured {
+            return Err("That cargo wasn't insured when it was lost"
+                .to_string()
+                .into());
+        }
+
+        let loss = self.losses.remove(index);
+        let claim = InsuranceClaim {
+            id: Uuid::new_v4(),
+            loss,
+            filed_at_turn: current_turn,
+            resolves_at_turn: current_turn + CLAIM_PROCESSING_TURNS,
+            status: ClaimStatus::Pending,
+            payout: 0,
+            note: format!(
+                "Under review until turn {}",
+                current_turn + CLAIM_PROCESSING_TURNS
+            ),
+        };
+        self.claims.push(claim.clone());
+        Ok(claim)
+    }
+
+    /// Settle every pending claim that's due by `current_turn`. Returns the
+    /// claims settled this time; the caller pays out.
+    pub fn resolve_due(&mut self, current_turn: u32, rng: &mut impl Rng) -> Vec<InsuranceClaim> {
+        let mut settled = Vec::new();
+
+        for claim in &mut self.claims {
+            if claim.status != ClaimStatus::Pending || claim.resolves_at_turn > current_turn {
+                continue;
+            }
+
+            let roll = rng.gen_range(0.0..1.0);
+            if roll < FULL_PAYOUT_CHANCE {
+                claim.status = ClaimStatus::Paid;
+                claim.payout = claim.loss.value;
+                claim.note = "Paid in full".to_string();
+            } else if roll < FULL_PAYOUT_CHANCE + PARTIAL_PAYOUT_CHANCE {
+                let share = rng.gen_range(40..=70);
+                claim.status = ClaimStatus::PartiallyPaid;
+                claim.payout = (claim.loss.value as u64 * share / 100) as u32;
+                claim.note = format!("Adjuster paid {}% of the claimed value", share);
+            } else {
+                claim.status = ClaimStatus::Denied;
+                claim.payout = 0;
+                claim.note = match claim.loss.cause {
+                    CargoLossCause::Spoilage => "Denied: cargo was not stored to policy terms",
+                    CargoLossCause::Theft => "Denied: no evidence of forced entry",
+                }
+                .to_string();
+            }
+            settled.push(claim.clone());
+        }
+
+        settled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn loss(insured: bool) -> CargoLoss {
+        CargoLoss {
+            id: Uuid::new_v4(),
+            cause: CargoLossCause::Theft,
+            cargo_type: "electronics".to_string(),
+            quantity: 5,
+            value: 1000,
+            airport_id: "ORD".to_string(),
+            turn: 2,
+            insured,
+            description: "Stolen".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_premium() {
+        assert_eq!(InsuranceAccount::premium(0), 0);
+        assert_eq!(InsuranceAccount::premium(100), MINIMUM_PREMIUM);
+        assert_eq!(InsuranceAccount::premium(10_000), 200);
+    }
+
+    #[test]
+    fn test_only_insured_losses_can_be_claimed() {
+        let mut account = InsuranceAccount::default();
+        let uninsured = loss(false);
+        account.record_loss(uninsured.clone());
+
+        assert_eq!(
+            account.file_claim(uninsured.id, 2).unwrap_err(),
+            "That cargo wasn't insured when it was lost".into()
+        );
+        assert_eq!(
+            account.file_claim(Uuid::new_v4(), 2).unwrap_err(),
+            RoomError::LossNotFound
+        );
+        assert_eq!(account.losses.len(), 1);
+    }
+
+    #[test]
+    fn test_claims_settle_after_the_processing_delay() {
+        let mut account = InsuranceAccount::default();
+        let insured = loss(true);
+        account.record_loss(insured.clone());
+
+        let claim = account.file_claim(insured.id, 2).unwrap();
+        assert_eq!(claim.status, ClaimStatus::Pending);
+        assert!(account.losses.is_empty());
+
+        let mut rng = StdRng::seed_from_u64(7);
+        assert!(
+            account
+                .resolve_due(2 + CLAIM_PROCESSING_TURNS - 1, &mut rng)
+                .is_empty()
+        );
+        assert_eq!(account.claims[0].status, ClaimStatus::Pending);
+
+        let settled = account.resolve_due(2 + CLAIM_PROCESSING_TURNS, &mut rng);
+        assert_eq!(settled.len(), 1);
+        assert_ne!(settled[0].status, ClaimStatus::Pending);
+        assert!(settled[0].payout <= insured.value);
+
+        // Settled claims aren't paid twice
+        assert!(account.resolve_due(100, &mut rng).is_empty());
+    }
+}
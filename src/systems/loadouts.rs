@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::cargo::CargoInventory;
+
+/// Most load-outs a pilot may keep
+pub const MAX_LOADOUTS: usize = 20;
+
+/// Longest a load-out's name may be
+pub const MAX_LOADOUT_NAME_LENGTH: usize = 40;
+
+/// A named cargo hold ("ORD electronics run") a pilot can load with one
+/// click: whatever the hold has too much of is sold and whatever it's short
+/// of is bought, at the market where the pilot is standing
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct CargoLoadout {
+    pub name: String,
+    /// Units of each cargo the hold should end up with. Cargo left out is
+    /// sold off.
+    pub cargo: BTreeMap<String, u32>,
+}
+
+/// One side of a load-out's trades
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadoutTrade {
+    pub cargo_id: String,
+    pub quantity: u32,
+}
+
+/// The trades that turn a hold into a load-out. Sales come first so their
+/// proceeds can pay for the purchases.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadoutPlan {
+    pub sell: Vec<LoadoutTrade>,
+    pub buy: Vec<LoadoutTrade>,
+}
+
+impl LoadoutPlan {
+    /// The hold already matches
+    pub fn is_empty(&self) -> bool {
+        self.sell.is_empty() && self.buy.is_empty()
+    }
+}
+
+impl CargoLoadout {
+    /// A load-out of what's in the hold right now
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub fn from_hold(name: String, inventory: &CargoInventory) -> Self {
+        let cargo = inventory
+            .get_all_cargo()
+            .iter()
+            .filter(|(_, quantity)| **quantity > 0)
+            .map(|(cargo_id, quantity)| (cargo_id.clone(), *quantity))
+            .collect();
+        Self { name, cargo }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("Load-outs need a name".to_string());
+        }
+        if name.chars().count() > MAX_LOADOUT_NAME_LENGTH {
+            return Err(format!(
+                "Load-out names can be at most {} characters",
+                MAX_LOADOUT_NAME_LENGTH
+            ));
+        }
+        Ok(())
+    }
+
+    /// What to sell and buy so `inventory` matches the load-out
+    pub fn plan(&self, inventory: &CargoInventory) -> LoadoutPlan {
+        let held = inventory.get_all_cargo();
+        let mut cargo_ids: Vec<&String> = held.keys().chain(self.cargo.keys()).collect();
+        cargo_ids.sort_unstable();
+        cargo_ids.dedup();
+
+        let mut plan = LoadoutPlan::default();
+        for cargo_id in cargo_ids {
+            let have = held.get(cargo_id).copied().unwrap_or(0);
+            let want = self.cargo.get(cargo_id).copied().unwrap_or(0);
+            if have > want {
+                plan.sell.push(LoadoutTrade {
+                    cargo_id: cargo_id.clone(),
+                    quantity: have - want,
+                });
+            } else if want > have {
+                plan.buy.push(LoadoutTrade {
+                    cargo_id: cargo_id.clone(),
+                    quantity: want - have,
+                });
+            }
+        }
+        plan
+    }
+}
+
+/// A pilot's saved load-outs, looked up by name regardless of case
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct CargoLoadouts(Vec<CargoLoadout>);
+
+impl CargoLoadouts {
+    pub fn all(&self) -> &[CargoLoadout] {
+        &self.0
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CargoLoadout> {
+        let name = name.trim();
+        self.0
+            .iter()
+            .find(|loadout| loadout.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Keep `loadout`, replacing any with the same name
+    pub fn save(&mut self, mut loadout: CargoLoadout) -> Result<(), String> {
+        loadout.validate()?;
+        loadout.name = loadout.name.trim().to_string();
+        loadout.cargo.retain(|_, quantity| *quantity > 0);
+
+        let saved = self
+            .0
+            .iter()
+            .position(|saved| saved.name.eq_ignore_ascii_case(&loadout.name));
+        match saved {
+            Some(index) => self.0[index] = loadout,
+            None if self.0.len() >= MAX_LOADOUTS => {
+                return Err(format!(
+                    "Pilots can keep at most {} load-outs",
+                    MAX_LOADOUTS
+                ));
+            },
+            None => self.0.push(loadout),
+        }
+        Ok(())
+    }
+
+    /// Forget a load-out; false when there was none by that name
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.0.len();
+        let name = name.trim();
+        self.0
+            .retain(|loadout| !loadout.name.eq_ignore_ascii_case(name));
+        self.0.len() != before
+    }
+}
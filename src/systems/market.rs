@@ -11,15 +11,31 @@ impl MarketSystem {
         airport: &Airport,
         cargo_types: &HashMap<String, CargoType>,
         rng: &mut impl Rng,
+    ) -> HashMap<String, u32> {
+        Self::generate_scaled_market_prices(airport, cargo_types, 1.0, rng)
+    }
+
+    /// Prices with every cargo's volatility multiplied by
+    /// `volatility_scale`, for rooms that want calmer or wilder markets
+    pub fn generate_scaled_market_prices(
+        airport: &Airport,
+        cargo_types: &HashMap<String, CargoType>,
+        volatility_scale: f32,
+        rng: &mut impl Rng,
     ) -> HashMap<String, u32> {
         let mut prices = HashMap::new();
 
         for (cargo_id, cargo_type) in cargo_types {
             let base_price = cargo_type.base_price;
-            let volatility = cargo_type.volatility;
+            let volatility = cargo_type.volatility * volatility_scale;
 
-            // Base price fluctuation (-volatility to +volatility)
-            let price_modifier = 1.0 + rng.gen_range(-volatility..volatility);
+            // Base price fluctuation (-volatility to +volatility); a cargo
+            // that doesn't fluctuate stays at its base
+            let price_modifier = if volatility > 0.0 {
+                1.0 + rng.gen_range(-volatility..volatility)
+            } else {
+                1.0
+            };
 
             // Apply airport market profile modifiers
             let profile_modifier = if airport.market_profile.produces.contains(cargo_id) {
@@ -33,7 +49,9 @@ impl MarketSystem {
                 rng.gen_range(0.9..1.1)
             };
 
-            let final_price = (base_price as f32 * price_modifier * profile_modifier) as u32;
+            // Round to the nearest dollar; the cast saturates for huge custom prices
+            let final_price =
+                (base_price as f64 * price_modifier as f64 * profile_modifier).round() as u32;
             let final_price = final_price.max(1); // Ensure price is at least $1
 
             prices.insert(cargo_id.clone(), final_price);
@@ -43,14 +61,7 @@ impl MarketSystem {
     }
 
     pub fn generate_fuel_price(airport: &Airport, rng: &mut impl Rng) -> u32 {
-        let base_price = airport.base_fuel_price;
-        let modifier = airport.market_profile.fuel_modifier;
-
-        // Add some randomness (±15%)
-        let random_modifier = rng.gen_range(0.85..1.15);
-
-        let final_price = (base_price as f32 * modifier * random_modifier) as u32;
-        final_price.max(1) // Ensure price is at least $1
+        airport.fuel_economy().sample_price(rng)
     }
 
     pub fn update_market_prices(
@@ -58,9 +69,22 @@ impl MarketSystem {
         airport: &Airport,
         cargo_types: &HashMap<String, CargoType>,
         rng: &mut impl Rng,
+    ) {
+        Self::update_scaled_market_prices(market, airport, cargo_types, 1.0, rng);
+    }
+
+    /// As `update_market_prices`, with every cargo's volatility multiplied
+    /// by `volatility_scale`
+    pub fn update_scaled_market_prices(
+        market: &mut Market,
+        airport: &Airport,
+        cargo_types: &HashMap<String, CargoType>,
+        volatility_scale: f32,
+        rng: &mut impl Rng,
     ) {
         // Update cargo prices
-        let new_cargo_prices = Self::generate_market_prices(airport, cargo_types, rng);
+        let new_cargo_prices =
+            Self::generate_scaled_market_prices(airport, cargo_types, volatility_scale, rng);
         for (cargo_id, price) in new_cargo_prices {
             market.set_cargo_price(&cargo_id, price);
         }
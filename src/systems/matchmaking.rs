@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::systems::{GameRoom, RoomSettings};
+
+/// Players per room when quick match has to open one and the player didn't
+/// ask for a size
+pub const QUICK_MATCH_ROOM_SIZE: usize = 4;
+
+/// The kinds of game quick match can find or open
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    FogOfWar,
+}
+
+impl GameMode {
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub const ALL: [GameMode; 2] = [GameMode::Classic, GameMode::FogOfWar];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::FogOfWar => "Fog of War",
+        }
+    }
+
+    /// The mode a room is being played in
+    pub fn of(settings: &RoomSettings) -> Self {
+        if settings.fog_of_war {
+            GameMode::FogOfWar
+        } else {
+            GameMode::Classic
+        }
+    }
+
+    /// Settings for a room quick match opens in this mode
+    pub fn settings(&self) -> RoomSettings {
+        RoomSettings {
+            fog_of_war: *self == GameMode::FogOfWar,
+            ..RoomSettings::default()
+        }
+    }
+}
+
+/// What a player asked quick match for. Anything left out matches any room.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MatchPreferences {
+    #[serde(default)]
+    pub max_players: Option<usize>,
+    #[serde(default)]
+    pub mode: Option<GameMode>,
+}
+
+impl MatchPreferences {
+    pub fn validate(&self) -> Result<(), String> {
+        match self.max_players {
+            Some(max_players) if !(1..=8).contains(&max_players) => {
+                Err("Max players must be between 1 and 8".to_string())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    pub fn accepts(&self, room: &GameRoom) -> bool {
+        room.is_joinable()
+            && self
+                .max_players
+                .is_none_or(|max_players| room.max_players == max_players)
+            && self
+                .mode
+                .is_none_or(|mode| GameMode::of(&room.settings) == mode)
+    }
+}
+
+pub struct Matchmaker;
+
+impl Matchmaker {
+    /// The open room that suits the preferences best: the one closest to
+    /// full, so games get going sooner, then the longest waiting.
+    pub fn find_room<'a>(
+        rooms: impl IntoIterator<Item = &'a GameRoom>,
+        preferences: &MatchPreferences,
+    ) -> Option<Uuid> {
+        rooms
+            .into_iter()
+            .filter(|room| preferences.accepts(room))
+            .min_by_key(|room| {
+                let online = room.players.values().filter(|p| p.is_online).count();
+                (room.max_players - online, room.created_at)
+            })
+            .map(|room| room.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+
+    fn room(max_players: usize, players: usize, mode: GameMode) -> GameRoom {
+        let mut room = GameRoom::new(
+            "Room".to_string(),
+            Uuid::new_v4(),
+            "Host".to_string(),
+            max_players,
+            get_default_airports(),
+            get_default_cargo_types(),
+        );
+        room.apply_settings(mode.settings());
+        for index in 1..players {
+            room.add_player(Uuid::new_v4(), format!("Pilot {}", index), None)
+                .unwrap();
+        }
+        room
+    }
+
+    #[test]
+    fn test_prefers_the_fullest_matching_room() {
+        let quiet = room(4, 1, GameMode::Classic);
+        let busy = room(4, 3, GameMode::Classic);
+        let full = room(2, 2, GameMode::Classic);
+        let foggy = room(4, 3, GameMode::FogOfWar);
+        let rooms = [&quiet, &busy, &full, &foggy];
+
+        let any = MatchPreferences::default();
+        let found = Matchmaker::find_room(rooms, &any);
+        assert!(found == Some(busy.id) || found == Some(foggy.id));
+
+        let classic = MatchPreferences {
+            mode: Some(GameMode::Classic),
+            ..Default::default()
+        };
+        assert_eq!(Matchmaker::find_room(rooms, &classic), Some(busy.id));
+
+        let pairs = MatchPreferences {
+            max_players: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(Matchmaker::find_room(rooms, &pairs), None);
+
+        let foggy_four = MatchPreferences {
+            max_players: Some(4),
+            mode: Some(GameMode::FogOfWar),
+        };
+        assert_eq!(Matchmaker::find_room(rooms, &foggy_four), Some(foggy.id));
+    }
+
+    #[test]
+    fn test_rejects_impossible_room_sizes() {
+        let preferences = MatchPreferences {
+            max_players: Some(9),
+            ..Default::default()
+        };
+        assert!(preferences.validate().is_err());
+        assert!(MatchPreferences::default().validate().is_ok());
+    }
+}
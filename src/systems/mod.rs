@@ -1,15 +1,53 @@
+pub mod achievements;
+pub mod advisor;
+pub mod auto_refuel;
+pub mod bank;
+pub mod bots;
+pub mod calendar;
+pub mod courier;
+pub mod deadlines;
+pub mod digest;
+pub mod distance;
+pub mod errors;
 pub mod events;
 pub mod game;
+pub mod hangar;
+pub mod insurance;
+pub mod loadouts;
 pub mod market;
+pub mod matchmaking;
+pub mod modifiers;
 pub mod multiplayer;
+pub mod npc_chatter;
+pub mod offers;
+pub mod price_alerts;
+pub mod profile;
+pub mod rankings;
+pub mod realtime;
+pub mod reconcile;
+pub mod retention;
+pub mod route_profits;
 pub mod save;
+pub mod scenario;
+pub mod security;
+pub mod slots;
+pub mod trade_guards;
 pub mod trading;
+pub mod transfers;
 pub mod travel;
+pub mod turns;
+pub mod valuation;
+pub mod vendors;
 
-pub use events::GameStatistics;
+pub use events::{GameStatistics, RouteFuelStats};
 pub use game::GameState;
 pub use market::MarketSystem;
-pub use multiplayer::{GameRoom, GameStatus, PlayerSession};
-pub use save::SaveSystem;
+pub use multiplayer::{
+    GameRoom, GameStatus, PlayerSession, RoomAward, RoomSettings, RoomTemplate, RoomWinner,
+    TurnRecord,
+};
+pub use profile::{Profile, ProfileSettings};
+pub use save::{AutosavePolicy, AutosaveTimer, SaveSystem};
 pub use trading::TradingSystem;
-pub use travel::TravelSystem;
+pub use travel::{TravelQuote, TravelSystem};
+pub use turns::{TurnAction, TurnEngine};
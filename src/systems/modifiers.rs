@@ -0,0 +1,203 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::Market;
+
+/// Furthest a modifier can move what it targets, up or down, in percent
+pub const MAX_MODIFIER_PERCENT: i32 = 100;
+
+/// Longest a modifier can run, in world turns
+pub const MAX_MODIFIER_TURNS: u32 = 50;
+
+/// Most modifiers a room can have running at once
+pub const MAX_ACTIVE_MODIFIERS: usize = 8;
+
+/// What a global modifier adjusts, at every airport in the room
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ModifierTarget {
+    CargoPrices,
+    FuelPrices,
+    /// The broker's cut of each cargo trade
+    TradingFees,
+}
+
+impl ModifierTarget {
+    #[cfg_attr(not(any(feature = "gui", feature = "tui")), allow(dead_code))]
+    pub fn label(self) -> &'static str {
+        match self {
+            ModifierTarget::CargoPrices => "cargo prices",
+            ModifierTarget::FuelPrices => "fuel prices",
+            ModifierTarget::TradingFees => "broker fees",
+        }
+    }
+}
+
+/// Who started a modifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModifierSource {
+    /// Rolled like a market event
+    Event,
+    /// Applied through the admin API
+    Admin,
+    /// Part of the room's settings, running from the start of each round
+    Scenario,
+}
+
+/// A modifier as a host or admin asks for it, before it's running
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ModifierSpec {
+    pub name: String,
+    pub target: ModifierTarget,
+    /// e.g. -100 for no fees at all, 25 for prices a quarter higher
+    pub percent: i32,
+    pub turns: u32,
+}
+
+impl ModifierSpec {
+    /// Broker fees waived for a few turns
+    pub fn sales_tax_holiday(turns: u32) -> Self {
+        Self {
+            name: "Sales tax holiday".to_string(),
+            target: ModifierTarget::TradingFees,
+            percent: -100,
+            turns,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("A modifier needs a name".to_string());
+        }
+        if self.percent == 0 || self.percent.abs() > MAX_MODIFIER_PERCENT {
+            return Err(format!(
+                "A modifier's percent must be between -{} and {}, and not 0",
+                MAX_MODIFIER_PERCENT, MAX_MODIFIER_PERCENT
+            ));
+        }
+        if !(1..=MAX_MODIFIER_TURNS).contains(&self.turns) {
+            return Err(format!(
+                "A modifier must run for 1 to {} turns",
+                MAX_MODIFIER_TURNS
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn start(&self, source: ModifierSource) -> GlobalModifier {
+        GlobalModifier {
+            id: Uuid::new_v4(),
+            name: self.name.trim().to_string(),
+            target: self.target,
+            percent: self.percent,
+            turns_remaining: self.turns,
+            source,
+        }
+    }
+}
+
+/// A percentage adjustment to prices or fees across a whole room, for a
+/// bounded number of turns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlobalModifier {
+    pub id: Uuid,
+    pub name: String,
+    pub target: ModifierTarget,
+    pub percent: i32,
+    pub turns_remaining: u32,
+    pub source: ModifierSource,
+}
+
+impl GlobalModifier {
+    /// One line for banners, e.g. "Sales tax holiday: broker fees -100% for
+    /// 3 more turns"
+    #[cfg_attr(not(any(feature = "gui", feature = "tui")), allow(dead_code))]
+    pub fn describe(&self) -> String {
+        let turns = if self.turns_remaining == 1 {
+            "1 more turn".to_string()
+        } else {
+            format!("{} more turns", self.turns_remaining)
+        };
+        format!(
+            "{}: {} {:+}% for {}",
+            self.name,
+            self.target.label(),
+            self.percent,
+            turns
+        )
+    }
+}
+
+pub struct ModifierSystem;
+
+impl ModifierSystem {
+    /// How much `target` is scaled by, with every modifier on it applied
+    /// one after the other
+    pub fn multiplier(modifiers: &[GlobalModifier], target: ModifierTarget) -> f64 {
+        modifiers
+            .iter()
+            .filter(|modifier| modifier.target == target)
+            .map(|modifier| (100 + modifier.percent) as f64 / 100.0)
+            .product()
+    }
+
+    /// Lay modifiers over a market's prices and fees. Prices never drop
+    /// below $1; fees can go to nothing.
+    pub fn apply_to_market(modifiers: &[GlobalModifier], market: &mut Market) {
+        let cargo = Self::multiplier(modifiers, ModifierTarget::CargoPrices);
+        if cargo != 1.0 {
+            for price in market.cargo_prices.values_mut() {
+                *price = scale(*price, cargo).max(1);
+            }
+        }
+        let fuel = Self::multiplier(modifiers, ModifierTarget::FuelPrices);
+        if fuel != 1.0 {
+            market.fuel_price = scale(market.fuel_price, fuel).max(1);
+        }
+        market.fee_multiplier *= Self::multiplier(modifiers, ModifierTarget::TradingFees);
+    }
+
+    /// Count a turn off every modifier, removing and returning the ones
+    /// that have run their course
+    pub fn expire(modifiers: &mut Vec<GlobalModifier>) -> Vec<GlobalModifier> {
+        let mut expired = Vec::new();
+        modifiers.retain_mut(|modifier| {
+            modifier.turns_remaining = modifier.turns_remaining.saturating_sub(1);
+            if modifier.turns_remaining == 0 {
+                expired.push(modifier.clone());
+                false
+            } else {
+                true
+            }
+        });
+        expired
+    }
+
+    /// Maybe start one of the stock modifiers, with `chance` per turn
+    pub fn roll_event(chance: f32, rng: &mut impl Rng) -> Option<GlobalModifier> {
+        if chance <= 0.0 || rng.gen_range(0.0..1.0) >= chance {
+            return None;
+        }
+        let stock = [
+            ModifierSpec::sales_tax_holiday(3),
+            ModifierSpec {
+                name: "Fuel subsidy".to_string(),
+                target: ModifierTarget::FuelPrices,
+                percent: -20,
+                turns: 5,
+            },
+            ModifierSpec {
+                name: "Import tariffs".to_string(),
+                target: ModifierTarget::CargoPrices,
+                percent: 10,
+                turns: 5,
+            },
+        ];
+        Some(stock[rng.gen_range(0..stock.len())].start(ModifierSource::Event))
+    }
+}
+
+fn scale(amount: u32, multiplier: f64) -> u32 {
+    (amount as f64 * multiplier).round().min(u32::MAX as f64) as u32
+}
@@ -1,10 +1,54 @@
+use rand::{Rng, seq::SliceRandom};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
-    models::{Airport, CargoType, Market, MessageBoard, Player},
-    systems::GameStatistics,
+    data::{airports::get_default_airports, cargo_types::get_default_cargo_types},
+    models::{
+        Airport, BoardLimits, CargoType, Market, Message, MessageBoard, Notification,
+        NotificationKind, Player, PriceSnapshot,
+        locale::{LocalizedMessage, MessageCode},
+    },
+    systems::{
+        GameStatistics, MarketSystem,
+        achievements::Achievement,
+        auto_refuel::{AutoRefuel, RefuelReceipt},
+        bank::BankSystem,
+        bots::DemoRoom,
+        calendar::GameCalendar,
+        courier::{CourierLetter, CourierQuote, CourierStatus, MAX_LETTERS_IN_TRANSIT},
+        deadlines::{DeadlineAlert, DeadlineAlertKind, TurnDeadline},
+        digest::{self, Digest, DigestMention, DigestSubscription, DigestTarget, LeaderboardEntry},
+        distance::DistanceCache,
+        errors::RoomError,
+        events::{
+            EventConfig, EventSpec, EventSystem, FUEL_OUTAGE_CHANCE, InjectedEvent,
+            MAX_INJECTED_EVENTS, MarketEvent, MarketEventType,
+        },
+        game::TurnReport,
+        insurance::{CargoLoss, CargoLossCause, ClaimStatus, InsuranceAccount, InsuranceClaim},
+        modifiers::{
+            GlobalModifier, MAX_ACTIVE_MODIFIERS, ModifierSource, ModifierSpec, ModifierSystem,
+            ModifierTarget,
+        },
+        npc_chatter::{NPC_MAX_UNANSWERED, NpcChatter},
+        offers::{MAX_OPEN_OFFERS, OfferSide, OfferTerms, TradeOffer},
+        price_alerts::{PriceAlert, PriceAlerts},
+        reconcile::{AirportReconciler, ReconciledWorld, WorldAdjustment},
+        retention::{ArchivedEntry, RetentionLimits, evict_oldest},
+        security::{Night, SecuritySystem, Stolen},
+        slots::{HOLDING_FUEL, SlotBook, SlotStatus},
+        trade_guards::{PurchaseLog, TradeGuards},
+        transfers::{Transfer, TransferTerms},
+        travel::{FlightConditions, TravelCostItem},
+        valuation::{NetWorth, ValuationSystem},
+        vendors::{INTEL_AIRPORTS, StallSale, VendorItem, VendorPurchase, VendorStalls},
+    },
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +63,514 @@ pub struct GameRoom {
     pub players: HashMap<Uuid, PlayerGameState>,
     pub player_statistics: HashMap<Uuid, GameStatistics>,
     pub message_board: MessageBoard,
+    #[serde(default)]
+    pub settings: RoomSettings,
+    #[serde(default)]
+    pub winner: Option<RoomWinner>,
+    /// Room created for the next round once this one finished
+    #[serde(default)]
+    pub rematch_room_id: Option<Uuid>,
+    /// Players who've voted to play the next round right here, in the
+    /// order they voted
+    #[serde(default)]
+    pub rematch_votes: Vec<Uuid>,
+    /// Rounds played in this room, counting the one in progress
+    #[serde(default = "first_round")]
+    pub round: u32,
+    /// Standing offers between players, oldest first
+    #[serde(default)]
+    pub offers: Vec<TradeOffer>,
+    /// Letters sent by courier, delivered or not, oldest first
+    #[serde(default)]
+    pub courier_mail: Vec<CourierLetter>,
+    /// Set when the server runs this room as a demo with bot traders
+    #[serde(default)]
+    pub demo: Option<DemoRoom>,
+    /// Notifications, turns and letters pushed out by the retention limits,
+    /// until
+    /// the server archives them
+    #[serde(skip)]
+    pub overflow: Vec<ArchivedEntry>,
+}
+
+/// What an action can change about the player taking it, as it stood
+/// before the action, from `GameRoom::checkpoint`
+#[derive(Debug)]
+pub struct ActionCheckpoint {
+    player_id: Uuid,
+    player_state: Option<Box<PlayerGameState>>,
+    statistics: Option<GameStatistics>,
+    /// The market at the player's airport
+    market: Option<Market>,
+}
+
+/// The first player to reach the room's win condition.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoomWinner {
+    pub player_id: Uuid,
+    pub player_name: String,
+    pub money: u32,
+    pub turn_number: u32,
+    pub won_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Profit the fuel-efficiency award is quoted per, so the figure isn't a
+/// long run of zeros
+pub const FUEL_EFFICIENCY_PROFIT_UNIT: u32 = 1000;
+
+/// An end-of-game honour for one player, shown alongside the winner
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoomAward {
+    pub title: String,
+    pub player_id: Uuid,
+    pub player_name: String,
+    pub detail: String,
+}
+
+/// Host-configurable rules for a room. Rooms persisted before settings
+/// existed load with the defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct RoomSettings {
+    pub starting_money: u32,
+    /// Fuel every pilot starts with; None gives them two thirds of a tank
+    #[serde(default)]
+    pub starting_fuel: Option<u32>,
+    /// Scales how far every cargo's price swings from turn to turn: 0 for
+    /// steady prices, 2 for twice the usual swings
+    #[serde(default = "default_price_volatility")]
+    pub price_volatility: f32,
+    pub win_condition_money: u32,
+    pub airports: Vec<String>, // Empty means every airport in the world
+    #[serde(default)]
+    pub message_board: BoardLimits,
+    /// Players only know prices at airports they have been to
+    #[serde(default)]
+    pub fog_of_war: bool,
+    /// How many of a player's own turns a sighting stays fresh for
+    #[serde(default = "default_fog_stale_after_turns")]
+    pub fog_stale_after_turns: u32,
+    /// Chance per flight that some of the cargo spoils or is stolen
+    #[serde(default = "default_cargo_loss_chance")]
+    pub cargo_loss_chance: f32,
+    /// Chance per turn that an airport runs out of fuel for a few turns
+    #[serde(default = "default_fuel_outage_chance")]
+    pub fuel_outage_chance: f32,
+    /// How often price spikes, crashes, booms and the like hit the room's
+    /// markets, and which kinds can happen
+    #[serde(default)]
+    pub events: EventConfig,
+    /// Chance per turn of a room-wide modifier such as a sales tax holiday;
+    /// off unless the host sets it
+    #[serde(default)]
+    pub modifier_event_chance: f32,
+    /// Modifiers that run from the start of every round, so a room can be
+    /// set up as a scenario
+    #[serde(default)]
+    pub scenario_modifiers: Vec<ModifierSpec>,
+    /// Airport locals post weather gripes, price rumors and lore on the
+    /// message boards
+    #[serde(default = "default_npc_chatter")]
+    pub npc_chatter: bool,
+    /// Cargo types traded in this room; empty means all of them
+    #[serde(default)]
+    pub cargo_types: Vec<String>,
+    /// Host-uploaded airports that replace the built-in ones. `airports`
+    /// then picks from these.
+    #[serde(default)]
+    pub custom_airports: Vec<Airport>,
+    /// Host-uploaded cargo types that replace the built-in ones.
+    /// `cargo_types` then picks from these.
+    #[serde(default)]
+    pub custom_cargo_types: Vec<CargoType>,
+    /// Where players start when they don't ask for an airport
+    #[serde(default)]
+    pub spawn: SpawnRule,
+    /// Least time between one player's trades, fuel purchases and trips, so
+    /// scripted clients can't swamp the shared market; 0 turns it off
+    #[serde(default)]
+    pub action_cooldown_ms: u64,
+    /// Busy hubs only take so many landings per slot period; arrivals hold
+    /// or divert when they're full
+    #[serde(default = "default_landing_slots")]
+    pub landing_slots: bool,
+    /// How fast the room's real-time clock runs. The host can change it
+    /// mid-game.
+    #[serde(default)]
+    pub game_speed: GameSpeed,
+    /// Aircraft parked outside overnight at low-security airports can lose
+    /// fuel or cargo to thieves
+    #[serde(default = "default_overnight_theft")]
+    pub overnight_theft: bool,
+    /// Vendors set up stalls selling fuel vouchers, cargo crates and map
+    /// intel at a few airports, moving on every few turns
+    #[serde(default = "default_vendor_stalls")]
+    pub vendor_stalls: bool,
+    /// Play-by-post rooms can give players a deadline for each turn and
+    /// have a webhook told when one is close or missed
+    #[serde(default)]
+    pub turn_deadline: Option<TurnDeadline>,
+    /// How many notifications and journal turns each player keeps before
+    /// the oldest are archived
+    #[serde(default)]
+    pub retention: RetentionLimits,
+    /// The in-game date each player's turns fall on
+    #[serde(default)]
+    pub calendar: GameCalendar,
+    /// The sell spread and resale cooldown that stop players buying and
+    /// selling the same cargo back and forth at one airport
+    #[serde(default)]
+    pub trade_guards: TradeGuards,
+}
+
+/// Scales every interval the server runs a room's real-time clock on, such
+/// as the locals' chatter. Turn-based play isn't affected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum GameSpeed {
+    /// Twice as long between ticks
+    Slow,
+    #[default]
+    Normal,
+    /// Half as long between ticks
+    Fast,
+}
+
+impl GameSpeed {
+    /// How long `interval` (at normal speed) lasts at this speed
+    pub fn scale(self, interval: Duration) -> Duration {
+        match self {
+            GameSpeed::Slow => interval * 2,
+            GameSpeed::Normal => interval,
+            GameSpeed::Fast => interval / 2,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GameSpeed::Slow => "slow",
+            GameSpeed::Normal => "normal",
+            GameSpeed::Fast => "fast",
+        }
+    }
+}
+
+/// How a room picks starting airports, so players don't all pile up at
+/// the same one.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum SpawnRule {
+    /// The room's default airport: JFK when the room has it
+    #[default]
+    DefaultAirport,
+    /// A different random airport for each player
+    Random,
+    /// The airport farthest from the pilots already in the room
+    SpreadOut,
+    /// An airport the host picked
+    HostChosen(String),
+}
+
+/// Most airports a custom room world may have
+pub const MAX_ROOM_AIRPORTS: usize = 50;
+
+/// Most cargo types a custom room world may have
+pub const MAX_ROOM_CARGO_TYPES: usize = 20;
+
+/// Longest action cooldown a host may set
+pub const MAX_ACTION_COOLDOWN_MS: u64 = 60_000;
+
+/// Fuel tank every pilot starts with
+pub const STARTING_MAX_FUEL: u32 = 200;
+
+/// Most a room can scale its price swings by
+pub const MAX_PRICE_VOLATILITY: f32 = 3.0;
+
+fn default_fog_stale_after_turns() -> u32 {
+    5
+}
+
+fn default_cargo_loss_chance() -> f32 {
+    0.05
+}
+
+fn default_fuel_outage_chance() -> f32 {
+    FUEL_OUTAGE_CHANCE
+}
+
+fn default_price_volatility() -> f32 {
+    1.0
+}
+
+fn default_npc_chatter() -> bool {
+    true
+}
+
+fn default_landing_slots() -> bool {
+    true
+}
+
+fn default_vendor_stalls() -> bool {
+    true
+}
+
+fn default_overnight_theft() -> bool {
+    true
+}
+
+impl Default for RoomSettings {
+    fn default() -> Self {
+        Self {
+            starting_money: 5000,
+            starting_fuel: None,
+            price_volatility: default_price_volatility(),
+            win_condition_money: 100000,
+            airports: Vec::new(),
+            message_board: BoardLimits::default(),
+            fog_of_war: false,
+            fog_stale_after_turns: default_fog_stale_after_turns(),
+            cargo_loss_chance: default_cargo_loss_chance(),
+            fuel_outage_chance: default_fuel_outage_chance(),
+            events: EventConfig::default(),
+            modifier_event_chance: 0.0,
+            scenario_modifiers: Vec::new(),
+            npc_chatter: default_npc_chatter(),
+            cargo_types: Vec::new(),
+            custom_airports: Vec::new(),
+            custom_cargo_types: Vec::new(),
+            spawn: SpawnRule::default(),
+            action_cooldown_ms: 0,
+            landing_slots: default_landing_slots(),
+            game_speed: GameSpeed::default(),
+            overnight_theft: default_overnight_theft(),
+            vendor_stalls: default_vendor_stalls(),
+            turn_deadline: None,
+            retention: RetentionLimits::default(),
+            calendar: GameCalendar::default(),
+            trade_guards: TradeGuards::default(),
+        }
+    }
+}
+
+impl RoomSettings {
+    /// The rules minus the host's private turn deadline webhook, for
+    /// copying into another room or a template anyone can list
+    pub fn shareable(&self) -> Self {
+        Self {
+            turn_deadline: None,
+            ..self.clone()
+        }
+    }
+
+    pub fn validate(
+        &self,
+        available_airports: &HashMap<String, Airport>,
+        available_cargo_types: &HashMap<String, CargoType>,
+    ) -> Result<(), String> {
+        if self.starting_money == 0 {
+            return Err("Starting money must be greater than zero".to_string());
+        }
+
+        if self.win_condition_money <= self.starting_money {
+            return Err("Win condition must be greater than starting money".to_string());
+        }
+
+        if self
+            .starting_fuel
+            .is_some_and(|fuel| fuel > STARTING_MAX_FUEL)
+        {
+            return Err(format!(
+                "Starting fuel can be at most {} units, a full tank",
+                STARTING_MAX_FUEL
+            ));
+        }
+
+        if !(0.0..=MAX_PRICE_VOLATILITY).contains(&self.price_volatility) {
+            return Err(format!(
+                "Price volatility must be between 0 and {}",
+                MAX_PRICE_VOLATILITY
+            ));
+        }
+
+        if self.action_cooldown_ms > MAX_ACTION_COOLDOWN_MS {
+            return Err(format!(
+                "Action cooldown can be at most {} ms",
+                MAX_ACTION_COOLDOWN_MS
+            ));
+        }
+
+        if self.custom_cargo_types.len() > MAX_ROOM_CARGO_TYPES {
+            return Err(format!(
+                "A room can have at most {} cargo types",
+                MAX_ROOM_CARGO_TYPES
+            ));
+        }
+        for cargo_type in &self.custom_cargo_types {
+            cargo_type.validate()?;
+        }
+        if has_duplicates(self.custom_cargo_types.iter().map(|c| c.id.as_str())) {
+            return Err("Custom cargo type ids must be unique".to_string());
+        }
+
+        let world_cargo_types = self.world_cargo_types(available_cargo_types.clone());
+        if let Some(unknown) = self
+            .cargo_types
+            .iter()
+            .find(|id| !world_cargo_types.contains_key(*id))
+        {
+            return Err(format!("Unknown cargo type: {}", unknown));
+        }
+
+        if self.custom_airports.len() > MAX_ROOM_AIRPORTS {
+            return Err(format!(
+                "A room can have at most {} airports",
+                MAX_ROOM_AIRPORTS
+            ));
+        }
+        // Custom airports may produce or consume any cargo in the room's
+        // world, including ones `cargo_types` leaves out
+        for airport in &self.custom_airports {
+            airport.validate(&world_cargo_types)?;
+        }
+
+        if let Some(turn_deadline) = &self.turn_deadline {
+            turn_deadline.validate()?;
+        }
+        if has_duplicates(self.custom_airports.iter().map(|a| a.id.as_str())) {
+            return Err("Custom airport ids must be unique".to_string());
+        }
+
+        let world_airports = self.world_airports(available_airports.clone());
+        if let Some(unknown) = self
+            .airports
+            .iter()
+            .find(|id| !world_airports.contains_key(*id))
+        {
+            return Err(format!("Unknown airport: {}", unknown));
+        }
+
+        let room_airports = self.room_airports(world_airports);
+        if room_airports.len() < 2 {
+            return Err("A room needs at least two airports".to_string());
+        }
+        if let SpawnRule::HostChosen(airport_id) = &self.spawn
+            && !room_airports.contains_key(airport_id)
+        {
+            return Err(format!(
+                "Spawn airport {} is not part of this room",
+                airport_id
+            ));
+        }
+
+        self.message_board.validate()?;
+        self.retention.validate()?;
+        self.calendar.validate()?;
+        self.trade_guards.validate()?;
+
+        if self.fog_of_war && self.fog_stale_after_turns == 0 {
+            return Err("Market sightings must stay fresh for at least one turn".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.cargo_loss_chance) {
+            return Err("Cargo loss chance must be between 0 and 1".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.fuel_outage_chance) {
+            return Err("Fuel outage chance must be between 0 and 1".to_string());
+        }
+
+        self.events.validate()?;
+
+        if !(0.0..=1.0).contains(&self.modifier_event_chance) {
+            return Err("Modifier event chance must be between 0 and 1".to_string());
+        }
+        if self.scenario_modifiers.len() > MAX_ACTIVE_MODIFIERS {
+            return Err(format!(
+                "A room can start with at most {} modifiers",
+                MAX_ACTIVE_MODIFIERS
+            ));
+        }
+        for spec in &self.scenario_modifiers {
+            spec.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// The airports this room plays with: the host's custom set if there
+    /// is one, otherwise the built-in ones, narrowed to `airports`.
+    pub fn room_airports(&self, airports: HashMap<String, Airport>) -> HashMap<String, Airport> {
+        narrow(self.world_airports(airports), &self.airports)
+    }
+
+    /// The cargo types this room trades, picked the same way as airports.
+    pub fn room_cargo_types(
+        &self,
+        cargo_types: HashMap<String, CargoType>,
+    ) -> HashMap<String, CargoType> {
+        narrow(self.world_cargo_types(cargo_types), &self.cargo_types)
+    }
+
+    fn world_airports(&self, airports: HashMap<String, Airport>) -> HashMap<String, Airport> {
+        if self.custom_airports.is_empty() {
+            return airports;
+        }
+
+        self.custom_airports
+            .iter()
+            .map(|airport| (airport.id.clone(), airport.clone()))
+            .collect()
+    }
+
+    fn world_cargo_types(
+        &self,
+        cargo_types: HashMap<String, CargoType>,
+    ) -> HashMap<String, CargoType> {
+        if self.custom_cargo_types.is_empty() {
+            return cargo_types;
+        }
+
+        self.custom_cargo_types
+            .iter()
+            .map(|cargo_type| (cargo_type.id.clone(), cargo_type.clone()))
+            .collect()
+    }
+}
+
+/// Keep only the `chosen` ids, or everything when nothing was chosen.
+fn narrow<T>(items: HashMap<String, T>, chosen: &[String]) -> HashMap<String, T> {
+    if chosen.is_empty() {
+        return items;
+    }
+
+    items
+        .into_iter()
+        .filter(|(id, _)| chosen.contains(id))
+        .collect()
+}
+
+fn has_duplicates<'a>(mut ids: impl Iterator<Item = &'a str>) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    ids.any(|id| !seen.insert(id))
+}
+
+/// A reusable bundle of room settings that hosts can create rooms from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub max_players: usize,
+    pub settings: RoomSettings,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RoomTemplate {
+    pub fn new(name: String, max_players: usize, settings: RoomSettings) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            max_players,
+            settings,
+            created_at: chrono::Utc::now(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -31,12 +583,33 @@ pub enum GameStatus {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SharedGameState {
-    pub turn_number: u32,
+    /// Counts every action taken by anyone in the room. Each player's own
+    /// progress is tracked separately in `PlayerGameState::turn_number`.
+    #[serde(alias = "turn_number")]
+    pub world_tick: u32,
     pub markets: HashMap<String, Market>,
     pub airports: HashMap<String, Airport>,
     pub cargo_types: HashMap<String, CargoType>,
     pub world_time: chrono::DateTime<chrono::Utc>,
     pub last_market_update: chrono::DateTime<chrono::Utc>,
+    /// Market events in progress, e.g. fuel outages
+    #[serde(default)]
+    pub active_events: Vec<MarketEvent>,
+    /// Price and fee adjustments running across every airport
+    #[serde(default)]
+    pub modifiers: Vec<GlobalModifier>,
+    /// Landings booked at slot-restricted hubs
+    #[serde(default)]
+    pub slot_book: SlotBook,
+    /// Vendors selling one-off items at the airports this rotation
+    #[serde(default)]
+    pub vendors: VendorStalls,
+    /// When the room's real-time clock last ticked
+    #[serde(skip)]
+    pub last_clock_tick: Option<Instant>,
+    /// Distances between the room's airports, measured as they're needed
+    #[serde(skip)]
+    pub distances: DistanceCache,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +620,124 @@ pub struct PlayerGameState {
     pub is_online: bool,
     pub last_seen: chrono::DateTime<chrono::Utc>,
     pub joined_at: chrono::DateTime<chrono::Utc>,
+    /// This player's own turn, advanced by their flights just like the
+    /// single-player turn counter
+    #[serde(default = "first_turn")]
+    pub turn_number: u32,
+    /// Last prices this player saw at each airport they've visited
+    #[serde(default)]
+    pub market_knowledge: HashMap<String, MarketSighting>,
+    #[serde(default)]
+    pub insurance: InsuranceAccount,
+    /// How each of this player's turns ended, oldest first
+    #[serde(default)]
+    pub journal: Vec<TurnRecord>,
+    /// Where to send this player's periodic summary of the room, if anywhere
+    #[serde(default)]
+    pub digest: Option<DigestSubscription>,
+    /// Board messages that @mentioned this player, oldest first
+    #[serde(default)]
+    pub notifications: Vec<Notification>,
+    /// When this player's last action went through, for the room's cooldown
+    #[serde(skip)]
+    pub last_action_at: Option<Instant>,
+    /// Achievements earned here that have gone on the pilot's profile
+    #[serde(default)]
+    pub achievements: Vec<Achievement>,
+    #[serde(default)]
+    pub price_alerts: PriceAlerts,
+    /// Fuel to buy as soon as this player lands
+    #[serde(default)]
+    pub auto_refuel: AutoRefuel,
+    /// Cargo this player bought too recently to sell back where they got it
+    #[serde(default)]
+    pub purchase_log: PurchaseLog,
+    /// When this player's current turn began, for the room's turn deadline
+    #[serde(default)]
+    pub turn_started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The last deadline alert sent about the current turn
+    #[serde(default)]
+    pub deadline_alert: Option<DeadlineAlertKind>,
+}
+
+impl PlayerGameState {
+    pub fn unread_notifications(&self) -> usize {
+        self.notifications.iter().filter(|n| !n.read).count()
+    }
+
+    /// Everything the player hasn't marked read yet, oldest first. It stays
+    /// here until they do, so no client or reconnect can lose any of it.
+    pub fn unread_digest(&self) -> Vec<Notification> {
+        self.notifications
+            .iter()
+            .filter(|n| !n.read)
+            .cloned()
+            .collect()
+    }
+
+    /// Add a notification, returning any that no longer fit in the
+    /// player's `max_feed_length`, oldest first
+    pub fn notify(
+        &mut self,
+        mut notification: Notification,
+        max_feed_length: usize,
+    ) -> Vec<Notification> {
+        notification.turn_number = Some(self.turn_number);
+        self.notifications.push(notification);
+        evict_oldest(&mut self.notifications, max_feed_length)
+    }
+}
+
+/// Where a player stood at the end of one of their turns, for replays.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TurnRecord {
+    pub turn_number: u32,
+    pub money: u32,
+    pub fuel: u32,
+    pub airport_id: String,
+    pub cargo: HashMap<String, u32>,
+    /// Prices at `airport_id` as the turn ended
+    pub market: MarketSighting,
+    /// Money, cargo and aircraft together as the turn ended
+    #[serde(default)]
+    pub net_worth: u32,
+}
+
+/// A player's snapshot of one airport's market.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarketSighting {
+    pub fuel_price: u32,
+    pub cargo_prices: HashMap<String, u32>,
+    pub observed_at: u32, // The player's own turn number
+}
+
+fn first_turn() -> u32 {
+    1
+}
+
+fn first_round() -> u32 {
+    1
+}
+
+/// Fuel price every market opens with
+const OPENING_FUEL_PRICE: u32 = 50;
+
+/// Every airport's market as it stands before anyone has traded
+fn opening_markets(
+    airports: &HashMap<String, Airport>,
+    cargo_types: &HashMap<String, CargoType>,
+) -> HashMap<String, Market> {
+    let mut markets = HashMap::new();
+    for airport_id in airports.keys() {
+        let mut market = Market::new(airport_id, OPENING_FUEL_PRICE);
+        for (cargo_type_id, cargo_type) in cargo_types {
+            market.set_cargo_price(cargo_type_id, cargo_type.base_price);
+        }
+        // Rooms open on tick 1
+        market.record_prices(1);
+        markets.insert(airport_id.clone(), market);
+    }
+    markets
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,27 +762,23 @@ impl GameRoom {
         let now = chrono::Utc::now();
 
         // Create initial shared state
-        let mut markets = HashMap::new();
-        for airport_id in airports.keys() {
-            let mut market = Market::new(airport_id, 50); // Default fuel price
-            // Set initial cargo prices
-            for (cargo_type_id, cargo_type) in &cargo_types {
-                market.set_cargo_price(cargo_type_id, cargo_type.base_price);
-            }
-            markets.insert(airport_id.clone(), market);
-        }
-
         let shared_state = SharedGameState {
-            turn_number: 1,
-            markets,
+            world_tick: 1,
+            markets: opening_markets(&airports, &cargo_types),
             airports,
             cargo_types,
             world_time: now,
             last_market_update: now,
+            active_events: Vec::new(),
+            modifiers: Vec::new(),
+            slot_book: SlotBook::default(),
+            vendors: VendorStalls::default(),
+            last_clock_tick: None,
+            distances: DistanceCache::new(),
         };
 
         // Create host player state
-        let host_player = Player::new(5000, "JFK", 200, 1000, 15.0);
+        let host_player = Player::new(5000, "JFK", STARTING_MAX_FUEL, 1000, 15.0);
         let host_player_state = PlayerGameState {
             player_id: host_player_id,
             player_name: host_player_name,
@@ -99,6 +786,19 @@ impl GameRoom {
             is_online: true,
             last_seen: now,
             joined_at: now,
+            turn_number: first_turn(),
+            market_knowledge: HashMap::new(),
+            insurance: InsuranceAccount::default(),
+            journal: Vec::new(),
+            digest: None,
+            notifications: Vec::new(),
+            last_action_at: None,
+            achievements: Vec::new(),
+            price_alerts: PriceAlerts::default(),
+            auto_refuel: AutoRefuel::default(),
+            purchase_log: PurchaseLog::default(),
+            turn_started_at: None,
+            deadline_alert: None,
         };
 
         let mut players = HashMap::new();
@@ -107,7 +807,7 @@ impl GameRoom {
         let mut player_statistics = HashMap::new();
         player_statistics.insert(host_player_id, GameStatistics::new());
 
-        Self {
+        let mut room = Self {
             id: room_id,
             name,
             host_player_id,
@@ -117,8 +817,150 @@ impl GameRoom {
             shared_state,
             players,
             player_statistics,
-            message_board: MessageBoard::new(50), // Keep last 50 messages per airport
+            message_board: MessageBoard::with_limits(BoardLimits::default()),
+            settings: RoomSettings::default(),
+            winner: None,
+            rematch_room_id: None,
+            rematch_votes: Vec::new(),
+            round: first_round(),
+            offers: Vec::new(),
+            courier_mail: Vec::new(),
+            demo: None,
+            overflow: Vec::new(),
+        };
+        room.observe_market(&host_player_id);
+        room.record_turn(&host_player_id);
+        room
+    }
+
+    /// A room whose world is built from its settings: the built-in or
+    /// host-uploaded airports and cargo narrowed to the ones it plays with,
+    /// and everyone starting on its money and fuel
+    pub fn from_settings(
+        name: String,
+        host_player_id: Uuid,
+        host_player_name: String,
+        max_players: usize,
+        settings: RoomSettings,
+    ) -> Result<Self, String> {
+        let airports = get_default_airports();
+        let cargo_types = get_default_cargo_types();
+        settings.validate(&airports, &cargo_types)?;
+
+        let mut room = Self::new(
+            name,
+            host_player_id,
+            host_player_name,
+            max_players,
+            settings.room_airports(airports),
+            settings.room_cargo_types(cargo_types),
+        );
+        room.apply_settings(settings);
+        Ok(room)
+    }
+
+    /// Apply host-chosen settings to a freshly created room. The airports in
+    /// shared_state are expected to already be filtered to the settings.
+    pub fn apply_settings(&mut self, settings: RoomSettings) {
+        self.settings = settings;
+        self.message_board
+            .set_limits(self.settings.message_board.clone());
+        self.start_scenario_modifiers();
+
+        // Settings are chosen before anyone has played, so everyone starts
+        // over under the new rules, in the order they joined
+        let mut player_ids: Vec<Uuid> = self.players.keys().copied().collect();
+        player_ids.sort_by_key(|player_id| (self.players[player_id].joined_at, *player_id));
+        let mut rng = rand::thread_rng();
+        for player_id in &player_ids {
+            let airport_id = self.spawn_airport(player_id, &mut rng);
+            let player = self.new_pilot(&airport_id);
+            if let Some(player_state) = self.players.get_mut(player_id) {
+                player_state.player = player;
+            }
+            self.observe_market(player_id);
+        }
+        self.record_turns();
+    }
+
+    /// Where a player should start under the room's spawn rule. The player
+    /// themselves is ignored when looking at where everyone else is.
+    pub fn spawn_airport(&self, player_id: &Uuid, rng: &mut impl Rng) -> String {
+        let mut airport_ids: Vec<&String> = self.shared_state.airports.keys().collect();
+        airport_ids.sort();
+
+        match &self.settings.spawn {
+            SpawnRule::DefaultAirport => self.default_airport(),
+            SpawnRule::Random => {
+                if airport_ids.is_empty() {
+                    return self.default_airport();
+                }
+                airport_ids[rng.gen_range(0..airport_ids.len())].clone()
+            },
+            SpawnRule::SpreadOut => {
+                let occupied: Vec<&Airport> = self
+                    .players
+                    .values()
+                    .filter(|p| p.is_online && p.player_id != *player_id)
+                    .filter_map(|p| self.shared_state.airports.get(&p.player.current_airport))
+                    .collect();
+                if occupied.is_empty() {
+                    return self.default_airport();
+                }
+
+                // Farthest from the nearest pilot; ties go to the first id
+                let mut best: Option<(&String, f64)> = None;
+                for airport_id in airport_ids {
+                    let airport = &self.shared_state.airports[airport_id];
+                    let nearest = occupied
+                        .iter()
+                        .map(|other| self.shared_state.distances.between(airport, other))
+                        .fold(f64::INFINITY, f64::min);
+                    if best.is_none_or(|(_, distance)| nearest > distance) {
+                        best = Some((airport_id, nearest));
+                    }
+                }
+                best.map(|(airport_id, _)| airport_id.clone())
+                    .unwrap_or_else(|| self.default_airport())
+            },
+            SpawnRule::HostChosen(airport_id) => {
+                if self.shared_state.airports.contains_key(airport_id) {
+                    airport_id.clone()
+                } else {
+                    self.default_airport()
+                }
+            },
+        }
+    }
+
+    /// A pilot fresh to the room, with the money and fuel it starts
+    /// everyone on
+    fn new_pilot(&self, airport_id: &str) -> Player {
+        let mut player = Player::new(
+            self.settings.starting_money,
+            airport_id,
+            STARTING_MAX_FUEL,
+            1000,
+            15.0,
+        );
+        if let Some(fuel) = self.settings.starting_fuel {
+            player.fuel = fuel.min(player.max_fuel);
         }
+        player
+    }
+
+    /// Airport players spawn at unless the room's spawn rule says otherwise
+    pub fn default_airport(&self) -> String {
+        if self.shared_state.airports.contains_key("JFK") {
+            return "JFK".to_string();
+        }
+
+        self.shared_state
+            .airports
+            .keys()
+            .min()
+            .cloned()
+            .unwrap_or_else(|| "JFK".to_string())
     }
 
     #[allow(dead_code)]
@@ -181,9 +1023,18 @@ impl GameRoom {
                 return Err("Player already in room".to_string());
             }
 
-            // New player joining
-            let starting_airport = starting_airport.unwrap_or_else(|| "JFK".to_string());
-            let player = Player::new(5000, &starting_airport, 200, 1000, 15.0);
+            // New player joining. A blank request leaves it to the spawn rule.
+            let starting_airport = starting_airport
+                .map(|airport_id| airport_id.trim().to_string())
+                .filter(|airport_id| !airport_id.is_empty())
+                .unwrap_or_else(|| self.spawn_airport(&player_id, &mut rand::thread_rng()));
+            if !self.shared_state.airports.contains_key(&starting_airport) {
+                return Err(format!(
+                    "Airport {} is not part of this room",
+                    starting_airport
+                ));
+            }
+            let player = self.new_pilot(&starting_airport);
 
             let player_state = PlayerGameState {
                 player_id,
@@ -192,9 +1043,24 @@ impl GameRoom {
                 is_online: true,
                 last_seen: now,
                 joined_at: now,
+                turn_number: first_turn(),
+                market_knowledge: HashMap::new(),
+                insurance: InsuranceAccount::default(),
+                journal: Vec::new(),
+                digest: None,
+                notifications: Vec::new(),
+                last_action_at: None,
+                achievements: Vec::new(),
+                price_alerts: PriceAlerts::default(),
+                auto_refuel: AutoRefuel::default(),
+                purchase_log: PurchaseLog::default(),
+                turn_started_at: None,
+                deadline_alert: None,
             };
 
             self.players.insert(player_id, player_state);
+            self.observe_market(&player_id);
+            self.record_turn(&player_id);
             self.player_statistics
                 .insert(player_id, GameStatistics::new());
             actual_player_id = player_id;
@@ -203,20 +1069,92 @@ impl GameRoom {
         Ok(actual_player_id)
     }
 
-    pub fn mark_player_offline(&mut self, player_id: Uuid) -> Result<(), String> {
+    pub fn mark_player_offline(&mut self, player_id: Uuid) -> Result<(), RoomError> {
         if let Some(player_state) = self.players.get_mut(&player_id) {
             player_state.is_online = false;
             player_state.last_seen = chrono::Utc::now();
             Ok(())
         } else {
-            Err("Player not in room".to_string())
+            Err(RoomError::PlayerNotInRoom)
+        }
+    }
+
+    /// Describe what a player would be walking away from if they left now.
+    /// An empty list means the player can leave without confirmation.
+    pub fn leave_obligations(&self, player_id: &Uuid) -> Vec<String> {
+        let mut obligations = Vec::new();
+
+        let Some(player_state) = self.players.get(player_id) else {
+            return obligations;
+        };
+
+        let market = self.get_current_market(&player_state.player.current_airport);
+        let mut cargo: Vec<_> = player_state
+            .player
+            .cargo_inventory
+            .get_all_cargo()
+            .iter()
+            .collect();
+        cargo.sort();
+
+        for (cargo_id, quantity) in cargo {
+            let cargo_name = self
+                .shared_state
+                .cargo_types
+                .get(cargo_id)
+                .map(|c| c.name.as_str())
+                .unwrap_or(cargo_id);
+            let value = market
+                .and_then(|m| m.get_cargo_price(cargo_id))
+                .map(|price| price.saturating_mul(*quantity))
+                .unwrap_or(0);
+            obligations.push(format!(
+                "{} units of {} (worth ~${} here) will sit in your hold until you rejoin",
+                quantity, cargo_name, value
+            ));
+        }
+
+        for loan in &player_state.player.loans.loans {
+            obligations.push(format!(
+                "Loan #{} still owes ${}; its ${} installments resume when you rejoin",
+                loan.id, loan.balance, loan.installment
+            ));
+        }
+
+        for offer in self.offers.iter().filter(|o| o.player_id == *player_id) {
+            let escrow = match offer.side {
+                OfferSide::Sell => format!("{} units of {}", offer.quantity, offer.cargo_type),
+                OfferSide::Buy => format!("${}", offer.unit_price.saturating_mul(offer.quantity)),
+            };
+            obligations.push(format!(
+                "Your offer {} {} {} at {} stays open, with {} held in escrow",
+                offer.side.verb(),
+                offer.quantity,
+                offer.cargo_type,
+                offer.airport_id,
+                escrow
+            ));
+        }
+
+        for claim in player_state
+            .insurance
+            .claims
+            .iter()
+            .filter(|claim| claim.status == ClaimStatus::Pending)
+        {
+            obligations.push(format!(
+                "Your insurance claim for ${} is under review until turn {}",
+                claim.loss.value, claim.resolves_at_turn
+            ));
         }
+
+        obligations
     }
 
     #[allow(dead_code)]
-    pub fn remove_player(&mut self, player_id: Uuid) -> Result<(), String> {
+    pub fn remove_player(&mut self, player_id: Uuid) -> Result<(), RoomError> {
         if !self.players.contains_key(&player_id) {
-            return Err("Player not in room".to_string());
+            return Err(RoomError::PlayerNotInRoom);
         }
 
         self.players.remove(&player_id);
@@ -249,22 +1187,2166 @@ impl GameRoom {
         self.shared_state.markets.get(airport_id)
     }
 
-    pub fn advance_turn(&mut self) {
-        self.shared_state.turn_number += 1;
+    /// A player finished a turn: their own counter moves on, and so does the
+    /// room's world tick.
+    pub fn advance_turn(&mut self, player_id: &Uuid) -> TurnReport {
+        if let Some(player_state) = self.players.get_mut(player_id) {
+            player_state.turn_number += 1;
+            player_state.player.turn_trades.reset();
+        }
+        self.shared_state.world_tick += 1;
         self.shared_state.world_time = chrono::Utc::now();
+        self.deliver_courier_mail();
 
-        // TODO: Add event system integration
-        // TODO: Update market prices based on global player activity
+        let mut rng = rand::thread_rng();
+        self.process_market_events(&mut rng);
+        self.roll_market_event(&mut rng);
+        self.roll_modifier_event(&mut rng);
+        let chance = self.settings.fuel_outage_chance;
+        self.roll_fuel_outage(chance, &mut rng);
+        self.rotate_vendor_stalls(&mut rng);
+        self.record_price_history();
+
+        let night = self.spend_night(player_id, &mut rng);
+        let bank = self
+            .players
+            .get_mut(player_id)
+            .and_then(|player_state| BankSystem::charge_turn(&mut player_state.player));
+        let price_alerts = self.check_price_alerts(player_id);
+        for alert in &price_alerts {
+            self.deliver_notification(
+                player_id,
+                Notification::notice(
+                    NotificationKind::PriceAlert,
+                    &alert.airport_id,
+                    alert.message(),
+                ),
+            );
+        }
+        TurnReport {
+            night,
+            bank,
+            price_alerts,
+        }
     }
 
-    #[allow(dead_code)]
-    pub fn start_game(&mut self) -> Result<(), String> {
-        if self.players.is_empty() {
-            return Err("Need at least 1 player to start".to_string());
+    /// Snapshot every market's prices as they stand at the end of the tick
+    fn record_price_history(&mut self) {
+        let tick = self.shared_state.world_tick;
+        for market in self.shared_state.markets.values_mut() {
+            market.record_prices(tick);
         }
+    }
 
-        self.game_status = GameStatus::InProgress;
-        Ok(())
+    /// An airport's recent prices, oldest first. In fog of war rooms only
+    /// a player standing at the airport gets to see them.
+    pub fn price_history(
+        &self,
+        airport_id: &str,
+        viewer: Option<&Uuid>,
+    ) -> Result<Vec<PriceSnapshot>, String> {
+        let market = self
+            .shared_state
+            .markets
+            .get(airport_id)
+            .ok_or_else(|| format!("Unknown airport: {}", airport_id))?;
+        if self.settings.fog_of_war {
+            let at_airport = viewer
+                .and_then(|player_id| self.players.get(player_id))
+                .is_some_and(|player_state| player_state.player.current_airport == airport_id);
+            if !at_airport {
+                return Err(
+                    "Price history is only posted at the airport itself in this room".to_string(),
+                );
+            }
+        }
+        Ok(market.price_history.iter().cloned().collect())
+    }
+
+    /// Compare the player's cargo with prices where they've ended the turn
+    pub fn check_price_alerts(&mut self, player_id: &Uuid) -> Vec<PriceAlert> {
+        let Some(player_state) = self.players.get_mut(player_id) else {
+            return Vec::new();
+        };
+        let Some(market) = self
+            .shared_state
+            .markets
+            .get(&player_state.player.current_airport)
+        else {
+            return Vec::new();
+        };
+        player_state.price_alerts.check(
+            &player_state.player.cargo_inventory,
+            market,
+            &self.shared_state.cargo_types,
+        )
+    }
+
+    /// The player's aircraft spends the night at their current airport.
+    /// Cargo taken by thieves is recorded as a theft loss, so insured
+    /// players can claim for it.
+    pub fn spend_night(&mut self, player_id: &Uuid, rng: &mut impl Rng) -> Night {
+        if !self.settings.overnight_theft {
+            return Night::Quiet;
+        }
+        let Some(player_state) = self.players.get_mut(player_id) else {
+            return Night::Quiet;
+        };
+        let Some(airport) = self
+            .shared_state
+            .airports
+            .get(&player_state.player.current_airport)
+        else {
+            return Night::Quiet;
+        };
+
+        let night = SecuritySystem::spend_night(
+            &mut player_state.player,
+            airport,
+            &self.shared_state.cargo_types,
+            rng,
+        );
+        if let Night::Robbed(theft) = &night
+            && let Stolen::Cargo {
+                cargo_type,
+                quantity,
+            } = &theft.stolen
+        {
+            let price = self
+                .shared_state
+                .markets
+                .get(&theft.airport_id)
+                .and_then(|market| market.get_cargo_price(cargo_type))
+                .unwrap_or(0);
+            player_state.insurance.record_loss(CargoLoss {
+                id: Uuid::new_v4(),
+                cause: CargoLossCause::Theft,
+                cargo_type: cargo_type.clone(),
+                quantity: *quantity,
+                value: price.saturating_mul(*quantity),
+                airport_id: theft.airport_id.clone(),
+                turn: player_state.turn_number,
+                insured: player_state.insurance.insured,
+                description: theft.description.clone(),
+            });
+        }
+        night
+    }
+
+    fn process_market_events(&mut self, rng: &mut impl Rng) {
+        let shared = &mut self.shared_state;
+        for event in EventSystem::expire_events(&mut shared.active_events) {
+            if let Some(market) = shared.markets.get_mut(&event.affected_airport) {
+                EventSystem::clear_event_from_market(&event, market);
+            }
+        }
+        ModifierSystem::expire(&mut shared.modifiers);
+
+        // Prices move everywhere each turn, then events still running are
+        // laid over the new prices
+        self.refresh_markets(rng);
+        let shared = &mut self.shared_state;
+
+        for market in shared.markets.values_mut() {
+            market.restock_fuel();
+        }
+
+        for event in &shared.active_events {
+            if let Some(market) = shared.markets.get_mut(&event.affected_airport) {
+                EventSystem::apply_event_to_market(event, market, &shared.cargo_types);
+            }
+        }
+
+        // Global modifiers go on last, over the whole room
+        for market in shared.markets.values_mut() {
+            market.fee_multiplier = 1.0;
+            ModifierSystem::apply_to_market(&shared.modifiers, market);
+        }
+    }
+
+    /// Start a global modifier now, laying it over every market
+    pub fn apply_modifier(
+        &mut self,
+        spec: &ModifierSpec,
+        source: ModifierSource,
+    ) -> Result<GlobalModifier, String> {
+        spec.validate()?;
+        let shared = &mut self.shared_state;
+        if shared.modifiers.len() >= MAX_ACTIVE_MODIFIERS {
+            return Err(format!(
+                "A room can have at most {} modifiers running",
+                MAX_ACTIVE_MODIFIERS
+            ));
+        }
+
+        Ok(self.lay_modifier(spec.start(source)))
+    }
+
+    fn lay_modifier(&mut self, modifier: GlobalModifier) -> GlobalModifier {
+        let shared = &mut self.shared_state;
+        for market in shared.markets.values_mut() {
+            ModifierSystem::apply_to_market(std::slice::from_ref(&modifier), market);
+        }
+        shared.modifiers.push(modifier.clone());
+        modifier
+    }
+
+    /// Stop a modifier early. Fees go back at once; prices go back when
+    /// the markets next move.
+    pub fn cancel_modifier(&mut self, modifier_id: &Uuid) -> Result<GlobalModifier, RoomError> {
+        let shared = &mut self.shared_state;
+        let index = shared
+            .modifiers
+            .iter()
+            .position(|modifier| modifier.id == *modifier_id)
+            .ok_or(RoomError::ModifierNotFound)?;
+        let cancelled = shared.modifiers.remove(index);
+
+        let fee_multiplier =
+            ModifierSystem::multiplier(&shared.modifiers, ModifierTarget::TradingFees);
+        for market in shared.markets.values_mut() {
+            market.fee_multiplier = fee_multiplier;
+        }
+        Ok(cancelled)
+    }
+
+    /// Maybe start a modifier, as the room's settings allow
+    pub fn roll_modifier_event(&mut self, rng: &mut impl Rng) -> Option<GlobalModifier> {
+        if self.shared_state.modifiers.len() >= MAX_ACTIVE_MODIFIERS {
+            return None;
+        }
+        let modifier = ModifierSystem::roll_event(self.settings.modifier_event_chance, rng)?;
+        Some(self.lay_modifier(modifier))
+    }
+
+    /// Replace any scenario modifiers with fresh ones from the settings
+    fn start_scenario_modifiers(&mut self) {
+        self.shared_state
+            .modifiers
+            .retain(|modifier| modifier.source != ModifierSource::Scenario);
+        // Validated with the settings, so they all fit
+        for spec in self.settings.scenario_modifiers.clone() {
+            let _ = self.apply_modifier(&spec, ModifierSource::Scenario);
+        }
+    }
+
+    /// Re-roll every airport's cargo and fuel prices from its supply and
+    /// demand and each cargo's volatility, as single player does
+    pub fn refresh_markets(&mut self, rng: &mut impl Rng) {
+        let shared = &mut self.shared_state;
+        for (airport_id, market) in shared.markets.iter_mut() {
+            if let Some(airport) = shared.airports.get(airport_id) {
+                MarketSystem::update_scaled_market_prices(
+                    market,
+                    airport,
+                    &shared.cargo_types,
+                    self.settings.price_volatility,
+                    rng,
+                );
+            }
+        }
+        shared.last_market_update = chrono::Utc::now();
+    }
+
+    /// Maybe start a market event, as the room's event settings allow
+    pub fn roll_market_event(&mut self, rng: &mut impl Rng) -> Option<&MarketEvent> {
+        let shared = &mut self.shared_state;
+        let event = EventSystem::generate_random_event(
+            &shared.airports,
+            &shared.cargo_types,
+            &self.settings.events,
+            rng,
+        )?;
+        if let Some(market) = shared.markets.get_mut(&event.affected_airport) {
+            EventSystem::apply_event_to_market(&event, market, &shared.cargo_types);
+        }
+        shared.active_events.push(event);
+        shared.active_events.last()
+    }
+
+    /// Maybe start a fuel outage at one of the room's airports
+    pub fn roll_fuel_outage(&mut self, chance: f32, rng: &mut impl Rng) -> Option<&MarketEvent> {
+        let shared = &mut self.shared_state;
+        let outage = EventSystem::generate_fuel_outage(
+            &shared.airports,
+            &shared.active_events,
+            chance,
+            rng,
+        )?;
+        if let Some(market) = shared.markets.get_mut(&outage.affected_airport) {
+            EventSystem::apply_event_to_market(&outage, market, &shared.cargo_types);
+        }
+        shared.active_events.push(outage);
+        shared.active_events.last()
+    }
+
+    /// The outage keeping fuel from an airport, if any
+    pub fn fuel_outage_at(&self, airport_id: &str) -> Option<&MarketEvent> {
+        EventSystem::fuel_outage_at(&self.shared_state.active_events, airport_id)
+    }
+
+    /// The player's load and the weather between two airports, for the fuel
+    /// a flight between them burns
+    pub fn flight_conditions(&self, player: &Player, from: &str, to: &str) -> FlightConditions {
+        FlightConditions::for_route(
+            player,
+            &self.shared_state.cargo_types,
+            &self.shared_state.active_events,
+            from,
+            to,
+        )
+    }
+
+    /// Start an event by hand, for a story the host or an admin is telling.
+    /// It's laid over the market at once, labelled with who started it, and
+    /// everyone else in the room hears about it. Returns the event and how
+    /// many players were told.
+    pub fn inject_event(
+        &mut self,
+        spec: &EventSpec,
+        author_id: &Uuid,
+        author_name: &str,
+    ) -> Result<InjectedEvent, String> {
+        let shared = &mut self.shared_state;
+        let injected = shared
+            .active_events
+            .iter()
+            .filter(|event| event.injected_by.is_some())
+            .count();
+        if injected >= MAX_INJECTED_EVENTS {
+            return Err(format!(
+                "A room can have at most {} injected events running",
+                MAX_INJECTED_EVENTS
+            ));
+        }
+        let event = spec.build(&shared.airports, &shared.cargo_types, author_name)?;
+        if event.event_type == MarketEventType::FuelOutage
+            && EventSystem::fuel_outage_at(&shared.active_events, &event.affected_airport).is_some()
+        {
+            return Err(format!("{} is already out of fuel", event.affected_airport));
+        }
+
+        if let Some(market) = shared.markets.get_mut(&event.affected_airport) {
+            EventSystem::apply_event_to_market(&event, market, &shared.cargo_types);
+        }
+        shared.active_events.push(event.clone());
+
+        let content = format!("{} started an event: {}", author_name, event.description);
+        let notified = self.announce(author_id, author_name, &content);
+        Ok((event, notified))
+    }
+
+    /// The host starts an event in their own room
+    pub fn host_inject_event(
+        &mut self,
+        player_id: &Uuid,
+        spec: &EventSpec,
+    ) -> Result<InjectedEvent, String> {
+        if *player_id != self.host_player_id {
+            return Err("Only the host can start events".to_string());
+        }
+        let host_name = self
+            .players
+            .get(player_id)
+            .map(|player_state| player_state.player_name.clone())
+            .unwrap_or_default();
+        self.inject_event(spec, player_id, &host_name)
+    }
+
+    /// Whether the room's real-time clock is due to tick at `now`, `interval`
+    /// being how often it ticks at normal speed. Starts a new tick when it is.
+    pub fn clock_due(&mut self, interval: Duration, now: Instant) -> bool {
+        let interval = self.settings.game_speed.scale(interval);
+        let shared = &mut self.shared_state;
+        if let Some(last) = shared.last_clock_tick
+            && now.saturating_duration_since(last) < interval
+        {
+            return false;
+        }
+        shared.last_clock_tick = Some(now);
+        true
+    }
+
+    /// The host speeds the room up or slows it down, and everyone else is
+    /// told. Returns how many players were notified.
+    pub fn set_game_speed(&mut self, player_id: &Uuid, speed: GameSpeed) -> Result<usize, String> {
+        if *player_id != self.host_player_id {
+            return Err("Only the host can change the game speed".to_string());
+        }
+        if self.settings.game_speed == speed {
+            return Ok(0);
+        }
+        self.settings.game_speed = speed;
+
+        let host_name = self
+            .players
+            .get(player_id)
+            .map(|player_state| player_state.player_name.clone())
+            .unwrap_or_default();
+        let content = format!("{} set the game speed to {}", host_name, speed.label());
+        Ok(self.announce(player_id, &host_name, &content))
+    }
+
+    /// Put a notification in one player's feed, archiving any it pushes out
+    fn deliver_notification(&mut self, player_id: &Uuid, notification: Notification) {
+        let Some(player_state) = self.players.get_mut(player_id) else {
+            return;
+        };
+        let evicted = player_state.notify(notification, self.settings.retention.max_feed_length);
+        self.overflow.extend(
+            evicted
+                .into_iter()
+                .map(|notification| ArchivedEntry::Notification {
+                    player_id: *player_id,
+                    notification,
+                }),
+        );
+    }
+
+    /// Tell the other pilots at the airport a player just reached that
+    /// they've arrived. Returns how many were told.
+    pub fn announce_arrival(&mut self, player_id: &Uuid) -> usize {
+        let Some(arriving) = self.players.get(player_id) else {
+            return 0;
+        };
+        let airport_id = arriving.player.current_airport.clone();
+        let airport_name = self
+            .shared_state
+            .airports
+            .get(&airport_id)
+            .map_or(airport_id.clone(), |airport| airport.name.clone());
+        let content = format!("{} arrived at {}", arriving.player_name, airport_name);
+
+        let waiting: Vec<Uuid> = self
+            .players
+            .values()
+            .filter(|player_state| {
+                player_state.player_id != *player_id
+                    && !self.is_bot(&player_state.player_id)
+                    && player_state.player.current_airport == airport_id
+            })
+            .map(|player_state| player_state.player_id)
+            .collect();
+        for waiting_id in &waiting {
+            self.deliver_notification(
+                waiting_id,
+                Notification::notice(
+                    NotificationKind::PlayerArrived,
+                    &airport_id,
+                    content.clone(),
+                ),
+            );
+        }
+        waiting.len()
+    }
+
+    /// Tell everyone in the room but the author something. Returns how many
+    /// players were told.
+    fn announce(&mut self, author_id: &Uuid, author_name: &str, content: &str) -> usize {
+        let mut notified = 0;
+        for player_state in self.players.values_mut() {
+            if player_state.player_id == *author_id {
+                continue;
+            }
+            let evicted = player_state.notify(
+                Notification::announcement(
+                    *author_id,
+                    author_name.to_string(),
+                    content.to_string(),
+                ),
+                self.settings.retention.max_feed_length,
+            );
+            self.overflow
+                .extend(
+                    evicted
+                        .into_iter()
+                        .map(|notification| ArchivedEntry::Notification {
+                            player_id: player_state.player_id,
+                            notification,
+                        }),
+                );
+            notified += 1;
+        }
+        notified
+    }
+
+    /// Fit a room read back from the database to the airports that exist
+    /// now: renamed airports follow their new id, removed ones are dropped
+    /// and anyone stranded at one is moved to the nearest airport left.
+    /// Every player is told what changed.
+    pub fn reconcile_airports(
+        &mut self,
+        available_airports: HashMap<String, Airport>,
+        renames: &[(&str, &str)],
+    ) -> Result<Vec<WorldAdjustment>, String> {
+        let registry = self.settings.world_airports(available_airports);
+        let reconciler = AirportReconciler::new(&registry, renames);
+        let shared = &mut self.shared_state;
+        let ReconciledWorld {
+            mut adjustments,
+            removed,
+        } = reconciler.reconcile_world(
+            &mut shared.airports,
+            &mut shared.markets,
+            &mut shared.active_events,
+        )?;
+
+        reconciler.resolve_ids(&mut self.settings.airports);
+        if let SpawnRule::HostChosen(airport_id) = &self.settings.spawn {
+            self.settings.spawn = match reconciler.resolve(airport_id) {
+                Some(airport_id) => SpawnRule::HostChosen(airport_id),
+                None => SpawnRule::DefaultAirport,
+            };
+        }
+
+        let mut player_ids: Vec<Uuid> = self.players.keys().copied().collect();
+        player_ids.sort();
+        for player_id in &player_ids {
+            let Some(player_state) = self.players.get_mut(player_id) else {
+                continue;
+            };
+            adjustments.extend(reconciler.place_player(
+                &mut player_state.player,
+                &player_state.player_name,
+                &shared.airports,
+                &removed,
+            ));
+            player_state.market_knowledge = std::mem::take(&mut player_state.market_knowledge)
+                .into_iter()
+                .filter_map(|(airport_id, sighting)| {
+                    reconciler
+                        .resolve(&airport_id)
+                        .map(|airport_id| (airport_id, sighting))
+                })
+                .collect();
+        }
+
+        if adjustments.is_empty() {
+            return Ok(adjustments);
+        }
+        let changes: Vec<String> = adjustments
+            .iter()
+            .map(|adjustment| adjustment.describe())
+            .collect();
+        let content = format!(
+            "The airports changed while this room was saved: {}",
+            changes.join("; ")
+        );
+        for player_id in &player_ids {
+            let Some(player_state) = self.players.get_mut(player_id) else {
+                continue;
+            };
+            let evicted = player_state.notify(
+                Notification::announcement(
+                    Uuid::nil(),
+                    "Air Traffic Control".to_string(),
+                    content.clone(),
+                ),
+                self.settings.retention.max_feed_length,
+            );
+            self.overflow
+                .extend(
+                    evicted
+                        .into_iter()
+                        .map(|notification| ArchivedEntry::Notification {
+                            player_id: *player_id,
+                            notification,
+                        }),
+                );
+        }
+        Ok(adjustments)
+    }
+
+    /// The host sets or clears the room's turn deadline. Everyone's current
+    /// turn counts from now, so nobody is late the moment it's switched on.
+    pub fn set_turn_deadline(
+        &mut self,
+        player_id: &Uuid,
+        turn_deadline: Option<TurnDeadline>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), String> {
+        if *player_id != self.host_player_id {
+            return Err("Only the host can set a turn deadline".to_string());
+        }
+        if let Some(turn_deadline) = &turn_deadline {
+            turn_deadline.validate()?;
+        }
+        self.settings.turn_deadline = turn_deadline;
+        for player_state in self.players.values_mut() {
+            player_state.turn_started_at = Some(now);
+            player_state.deadline_alert = None;
+        }
+        Ok(())
+    }
+
+    /// Build the turn deadline alerts that are due, at most one warning and
+    /// one miss per player per turn, and mark them sent. They go to the
+    /// webhook in the room's `turn_deadline`, and each player finds theirs
+    /// in their notifications.
+    pub fn collect_deadline_alerts(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<DeadlineAlert> {
+        let Some(turn_deadline) = self.settings.turn_deadline.clone() else {
+            return Vec::new();
+        };
+        if self.is_finished() {
+            return Vec::new();
+        }
+
+        let mut alerts = Vec::new();
+        for player_state in self.players.values_mut() {
+            let started_at = player_state
+                .turn_started_at
+                .unwrap_or(player_state.joined_at);
+            let due_at = turn_deadline.due_at(started_at);
+            let Some(kind) = turn_deadline.alert_due(due_at, player_state.deadline_alert, now)
+            else {
+                continue;
+            };
+            player_state.deadline_alert = Some(kind);
+            alerts.push(DeadlineAlert {
+                room_id: self.id,
+                room_name: self.name.clone(),
+                player_id: player_state.player_id,
+                player_name: player_state.player_name.clone(),
+                kind,
+                turn_number: player_state.turn_number,
+                due_at,
+            });
+        }
+        alerts.sort_by_key(|alert| alert.due_at);
+        for alert in &alerts {
+            let airport_id = self
+                .players
+                .get(&alert.player_id)
+                .map(|player_state| player_state.player.current_airport.clone())
+                .unwrap_or_default();
+            self.deliver_notification(
+                &alert.player_id,
+                Notification::notice(
+                    NotificationKind::TurnDeadline,
+                    &airport_id,
+                    alert.personal_text(),
+                ),
+            );
+        }
+        alerts
+    }
+
+    /// Let the airport locals have their say, if the room wants them.
+    /// Returns how many posts were made.
+    pub fn post_npc_chatter(&mut self, chance: f32, rng: &mut impl Rng) -> usize {
+        if !self.settings.npc_chatter || self.is_finished() {
+            return 0;
+        }
+
+        let mut airport_ids: Vec<String> = self.shared_state.airports.keys().cloned().collect();
+        airport_ids.sort();
+
+        let mut posted = 0;
+        for airport_id in airport_ids {
+            if rng.gen_range(0.0..1.0) >= chance {
+                continue;
+            }
+            let recent = self
+                .message_board
+                .get_messages(&airport_id, Some(NPC_MAX_UNANSWERED));
+            if NpcChatter::is_talked_out(&recent) {
+                continue;
+            }
+            let (Some(airport), Some(market)) = (
+                self.shared_state.airports.get(&airport_id),
+                self.shared_state.markets.get(&airport_id),
+            ) else {
+                continue;
+            };
+
+            let post = NpcChatter::compose(airport, market, &self.shared_state.cargo_types, rng);
+            self.message_board.post_npc_message(
+                post.author_name,
+                post.content,
+                airport_id,
+                post.topic,
+            );
+            posted += 1;
+        }
+        posted
+    }
+
+    /// What the player's cargo is worth at their current airport's prices
+    pub fn cargo_value(&self, player_id: &Uuid) -> u32 {
+        let Some(player_state) = self.players.get(player_id) else {
+            return 0;
+        };
+        let Some(market) = self.get_current_market(&player_state.player.current_airport) else {
+            return 0;
+        };
+
+        player_state
+            .player
+            .cargo_inventory
+            .get_all_cargo()
+            .iter()
+            .map(|(cargo_id, quantity)| {
+                market
+                    .get_cargo_price(cargo_id)
+                    .unwrap_or(0)
+                    .saturating_mul(*quantity)
+            })
+            .fold(0, u32::saturating_add)
+    }
+
+    /// The premium line for a flight's cost breakdown, if the player is
+    /// insured and carrying anything
+    pub fn insurance_premium_item(&self, player_id: &Uuid) -> Option<TravelCostItem> {
+        let player_state = self.players.get(player_id)?;
+        if !player_state.insurance.insured {
+            return None;
+        }
+
+        let cargo_value = self.cargo_value(player_id);
+        let premium = InsuranceAccount::premium(cargo_value);
+        if premium == 0 {
+            return None;
+        }
+
+        Some(TravelCostItem {
+            label: format!("Cargo insurance (on ${} of cargo)", cargo_value),
+            fuel: 0,
+            money: premium,
+        })
+    }
+
+    /// Whether a flight landing at `airport_id` now would get a slot
+    pub fn slot_status(&self, airport_id: &str) -> SlotStatus {
+        match self.shared_state.airports.get(airport_id) {
+            Some(airport) if self.settings.landing_slots => self
+                .shared_state
+                .slot_book
+                .status(airport, self.shared_state.world_tick),
+            _ => SlotStatus::Unrestricted,
+        }
+    }
+
+    /// Everything a flight to `destination` costs on top of the fuel burn:
+    /// the insurance premium, and circling while a full hub frees up a slot
+    pub fn flight_extras(&self, player_id: &Uuid, destination: &str) -> Vec<TravelCostItem> {
+        let mut extras: Vec<TravelCostItem> =
+            self.insurance_premium_item(player_id).into_iter().collect();
+        if self.slot_status(destination) == SlotStatus::Holding {
+            extras.push(TravelCostItem {
+                label: format!(
+                    "Holding pattern at {} (a turn waiting for a slot)",
+                    destination
+                ),
+                fuel: HOLDING_FUEL,
+                money: 0,
+            });
+        }
+        extras
+    }
+
+    /// Take a landing slot for a flight that's arriving at `airport_id`,
+    /// before the world tick moves on
+    pub fn book_landing(&mut self, airport_id: &str, holding: bool) {
+        if !self.settings.landing_slots {
+            return;
+        }
+        let shared = &mut self.shared_state;
+        if let Some(airport) = shared.airports.get(airport_id) {
+            shared.slot_book.prune(shared.world_tick);
+            shared.slot_book.book(airport, shared.world_tick, holding);
+        }
+    }
+
+    /// After a flight, some of the player's cargo may have spoiled or been
+    /// stolen. The loss is taken out of their hold and recorded so they can
+    /// claim for it if they were insured.
+    pub fn roll_cargo_loss(&mut self, player_id: &Uuid, rng: &mut impl Rng) -> Option<CargoLoss> {
+        let transit_risk = self.players.get(player_id).map_or(1.0, |player_state| {
+            player_state.player.aircraft.transit_risk()
+        });
+        if rng.gen_range(0.0..1.0) >= self.settings.cargo_loss_chance * transit_risk {
+            return None;
+        }
+
+        let player_state = self.players.get(player_id)?;
+        let mut carried: Vec<(&String, &u32)> = player_state
+            .player
+            .cargo_inventory
+            .get_all_cargo()
+            .iter()
+            .filter(|(_, quantity)| **quantity > 0)
+            .collect();
+        if carried.is_empty() {
+            return None;
+        }
+        carried.sort();
+
+        let (cargo_id, carried_quantity) = carried[rng.gen_range(0..carried.len())];
+        let cargo_id = cargo_id.clone();
+        let quantity = rng.gen_range(1..=*carried_quantity);
+        let cause = if rng.gen_bool(0.5) {
+            CargoLossCause::Spoilage
+        } else {
+            CargoLossCause::Theft
+        };
+
+        let airport_id = player_state.player.current_airport.clone();
+        let price = self
+            .get_current_market(&airport_id)
+            .and_then(|market| market.get_cargo_price(&cargo_id))
+            .unwrap_or(0);
+        let cargo_name = self
+            .shared_state
+            .cargo_types
+            .get(&cargo_id)
+            .map(|cargo| cargo.name.clone())
+            .unwrap_or_else(|| cargo_id.clone());
+        let description = match cause {
+            CargoLossCause::Spoilage => {
+                format!("{} units of {} spoiled in flight", quantity, cargo_name)
+            },
+            CargoLossCause::Theft => {
+                format!(
+                    "{} units of {} were stolen on arrival",
+                    quantity, cargo_name
+                )
+            },
+        };
+
+        let player_state = self.players.get_mut(player_id)?;
+        player_state
+            .player
+            .cargo_inventory
+            .remove_cargo(&cargo_id, quantity);
+
+        let loss = CargoLoss {
+            id: Uuid::new_v4(),
+            cause,
+            cargo_type: cargo_id,
+            quantity,
+            value: price.saturating_mul(quantity),
+            airport_id,
+            turn: player_state.turn_number,
+            insured: player_state.insurance.insured,
+            description,
+        };
+        player_state.insurance.record_loss(loss.clone());
+        Some(loss)
+    }
+
+    /// Settle the player's claims that are due and pay them out
+    pub fn resolve_claims(&mut self, player_id: &Uuid, rng: &mut impl Rng) -> Vec<InsuranceClaim> {
+        let Some(player_state) = self.players.get_mut(player_id) else {
+            return Vec::new();
+        };
+
+        let settled = player_state
+            .insurance
+            .resolve_due(player_state.turn_number, rng);
+        for claim in &settled {
+            player_state.player.earn_money(claim.payout);
+        }
+        settled
+    }
+
+    /// Carry out the player's auto-refuel order where they've just landed.
+    /// The fuel is paid for like any other, but doesn't take a turn.
+    pub fn auto_refuel(&mut self, player_id: &Uuid) -> Option<RefuelReceipt> {
+        let player_state = self.players.get_mut(player_id)?;
+        let airport_id = &player_state.player.current_airport;
+        let market = self.shared_state.markets.get_mut(airport_id)?;
+        let receipt = player_state.auto_refuel.plan(
+            &player_state.player,
+            market.fuel_price,
+            market.fuel_available(),
+        )?;
+        if receipt.quantity == 0 {
+            return Some(receipt);
+        }
+
+        player_state.player.spend_money(receipt.cost);
+        player_state.player.add_fuel(receipt.quantity);
+        market.draw_fuel(receipt.quantity);
+        if let Some(stats) = self.player_statistics.get_mut(player_id) {
+            stats.record_fuel_purchase(receipt.quantity, receipt.cost);
+        }
+        Some(receipt)
+    }
+
+    /// Remember the prices at the player's current airport as of their
+    /// current turn.
+    pub fn observe_market(&mut self, player_id: &Uuid) {
+        let Some(player_state) = self.players.get_mut(player_id) else {
+            return;
+        };
+        let airport_id = player_state.player.current_airport.clone();
+        let Some(market) = self.shared_state.markets.get(&airport_id) else {
+            return;
+        };
+
+        player_state.market_knowledge.insert(
+            airport_id,
+            MarketSighting {
+                fuel_price: market.fuel_price,
+                cargo_prices: market.cargo_prices.clone(),
+                observed_at: player_state.turn_number,
+            },
+        );
+    }
+
+    /// Write down where a player stands on their current turn, replacing
+    /// anything already noted for that turn.
+    pub fn record_turn(&mut self, player_id: &Uuid) {
+        let net_worth = self
+            .net_worth(player_id)
+            .map(|worth| worth.total())
+            .unwrap_or(0);
+        let Some(player_state) = self.players.get_mut(player_id) else {
+            return;
+        };
+        let player = &player_state.player;
+        let Some(market) = self.shared_state.markets.get(&player.current_airport) else {
+            return;
+        };
+
+        let record = TurnRecord {
+            turn_number: player_state.turn_number,
+            money: player.money,
+            fuel: player.fuel,
+            airport_id: player.current_airport.clone(),
+            cargo: player.cargo_inventory.get_all_cargo().clone(),
+            market: MarketSighting {
+                fuel_price: market.fuel_price,
+                cargo_prices: market.cargo_prices.clone(),
+                observed_at: player_state.turn_number,
+            },
+            net_worth,
+        };
+
+        let journal = &mut player_state.journal;
+        match journal.last_mut() {
+            Some(last) if last.turn_number == record.turn_number => *last = record,
+            _ => {
+                journal.push(record);
+                // A new turn, so a new deadline
+                player_state.turn_started_at = Some(chrono::Utc::now());
+                player_state.deadline_alert = None;
+            },
+        }
+        let evicted = evict_oldest(journal, self.settings.retention.max_journal_turns);
+        self.overflow
+            .extend(evicted.into_iter().map(|record| ArchivedEntry::Turn {
+                player_id: *player_id,
+                record,
+            }));
+    }
+
+    /// Bring every player's journal up to date
+    pub fn record_turns(&mut self) {
+        let player_ids: Vec<Uuid> = self.players.keys().copied().collect();
+        for player_id in &player_ids {
+            self.record_turn(player_id);
+        }
+    }
+
+    /// What a letter from `from` to `to` would cost and how long it'd take
+    pub fn courier_quote(&self, from: &str, to: &str) -> Option<CourierQuote> {
+        let airports = &self.shared_state.airports;
+        let distance = self
+            .shared_state
+            .distances
+            .between_ids(airports, from, to)?;
+        Some(CourierQuote::for_distance(distance))
+    }
+
+    /// Pay to have a message posted on another airport's board a few turns
+    /// from now, as if it went out on the mail flights
+    pub fn send_courier(
+        &mut self,
+        player_id: &Uuid,
+        to_airport: &str,
+        content: String,
+    ) -> Result<CourierLetter, RoomError> {
+        let player_state = self
+            .players
+            .get(player_id)
+            .ok_or(RoomError::PlayerNotInRoom)?;
+        let from_airport = player_state.player.current_airport.clone();
+        if to_airport == from_airport {
+            return Err("Post it on the board here instead".into());
+        }
+        let quote = self
+            .courier_quote(&from_airport, to_airport)
+            .ok_or_else(|| format!("Unknown airport: {}", to_airport))?;
+        self.message_board.check_content(&content)?;
+        let in_transit = self
+            .courier_mail
+            .iter()
+            .filter(|letter| {
+                letter.sender_id == *player_id && letter.status == CourierStatus::InTransit
+            })
+            .count();
+        if in_transit >= MAX_LETTERS_IN_TRANSIT {
+            return Err(format!(
+                "You already have {} letters in the mail; wait for one to arrive",
+                MAX_LETTERS_IN_TRANSIT
+            )
+            .into());
+        }
+
+        let player_state = self
+            .players
+            .get_mut(player_id)
+            .ok_or(RoomError::PlayerNotInRoom)?;
+        if !player_state.player.spend_money(quote.fee) {
+            return Err(format!("The courier charges ${}", quote.fee).into());
+        }
+
+        let world_tick = self.shared_state.world_tick;
+        let letter = CourierLetter {
+            id: Uuid::new_v4(),
+            sender_id: *player_id,
+            sender_name: player_state.player_name.clone(),
+            from_airport,
+            to_airport: to_airport.to_string(),
+            content,
+            fee: quote.fee,
+            sent_at: world_tick,
+            deliver_at: world_tick + quote.turns,
+            status: CourierStatus::InTransit,
+            message_id: None,
+        };
+        self.courier_mail.push(letter.clone());
+        Ok(letter)
+    }
+
+    /// Post every letter that's due by the current world tick. Returns how
+    /// many were delivered.
+    pub fn deliver_courier_mail(&mut self) -> usize {
+        let world_tick = self.shared_state.world_tick;
+        let mut delivered = Vec::new();
+        for letter in &mut self.courier_mail {
+            if !letter.is_due(world_tick) {
+                continue;
+            }
+            let message = self.message_board.deliver_courier(letter);
+            letter.status = CourierStatus::Delivered;
+            letter.message_id = Some(message.id);
+            delivered.push(message);
+        }
+
+        for message in &delivered {
+            self.notify_mentions(message);
+        }
+        if !delivered.is_empty() {
+            self.archive_delivered_letters();
+        }
+        delivered.len()
+    }
+
+    /// Move each sender's delivered letters beyond the retention limit,
+    /// oldest first, out to the archive
+    fn archive_delivered_letters(&mut self) {
+        let limit = self.settings.retention.max_delivered_letters;
+        let mut delivered_per_sender: HashMap<Uuid, usize> = HashMap::new();
+        // Counted newest first, so each sender keeps their latest letters
+        let mut keep: Vec<bool> = self
+            .courier_mail
+            .iter()
+            .rev()
+            .map(|letter| {
+                if letter.status == CourierStatus::InTransit {
+                    return true;
+                }
+                let delivered = delivered_per_sender.entry(letter.sender_id).or_default();
+                *delivered += 1;
+                *delivered <= limit
+            })
+            .collect();
+        keep.reverse();
+
+        let (kept, archived): (Vec<_>, Vec<_>) = std::mem::take(&mut self.courier_mail)
+            .into_iter()
+            .zip(keep)
+            .partition(|(_, keep)| *keep);
+        self.courier_mail = kept.into_iter().map(|(letter, _)| letter).collect();
+        self.overflow.extend(
+            archived
+                .into_iter()
+                .map(|(letter, _)| ArchivedEntry::Letter(letter)),
+        );
+    }
+
+    /// Letters a player has sent, newest first
+    pub fn courier_letters_from(&self, player_id: &Uuid) -> Vec<&CourierLetter> {
+        self.courier_mail
+            .iter()
+            .rev()
+            .filter(|letter| letter.sender_id == *player_id)
+            .collect()
+    }
+
+    /// Everything the retention limits have pushed out of the room since
+    /// this was last called, board messages included, oldest first
+    pub fn take_overflow(&mut self) -> Vec<ArchivedEntry> {
+        let mut overflow: Vec<ArchivedEntry> = self
+            .message_board
+            .take_evicted()
+            .into_iter()
+            .map(ArchivedEntry::Message)
+            .collect();
+        overflow.append(&mut self.overflow);
+        overflow
+    }
+
+    /// Notify everyone a player's message @mentions, other than its author.
+    /// Returns how many were notified.
+    pub fn notify_mentions(&mut self, message: &Message) -> usize {
+        let mut notified = 0;
+        for player_state in self.players.values_mut() {
+            if player_state.player_id == message.author_id
+                || !message.mentions(&player_state.player_name)
+            {
+                continue;
+            }
+
+            let evicted = player_state.notify(
+                Notification::for_message(message),
+                self.settings.retention.max_feed_length,
+            );
+            self.overflow
+                .extend(
+                    evicted
+                        .into_iter()
+                        .map(|notification| ArchivedEntry::Notification {
+                            player_id: player_state.player_id,
+                            notification,
+                        }),
+                );
+            notified += 1;
+        }
+        notified
+    }
+
+    /// Players ordered by net worth, richest first
+    pub fn standings(&self) -> Vec<&PlayerGameState> {
+        let mut standings: Vec<(&PlayerGameState, u32)> = self
+            .players
+            .values()
+            .map(|player_state| {
+                let worth = self
+                    .net_worth(&player_state.player_id)
+                    .map(|worth| worth.total())
+                    .unwrap_or(0);
+                (player_state, worth)
+            })
+            .collect();
+        standings.sort_by(|(a, a_worth), (b, b_worth)| {
+            b_worth
+                .cmp(a_worth)
+                .then_with(|| a.player_name.cmp(&b.player_name))
+        });
+        standings
+            .into_iter()
+            .map(|(player_state, _)| player_state)
+            .collect()
+    }
+
+    /// What a player is worth, valuing their hold at their airport's prices
+    pub fn net_worth(&self, player_id: &Uuid) -> Option<NetWorth> {
+        let player = &self.players.get(player_id)?.player;
+        Some(ValuationSystem::value(
+            player,
+            self.shared_state.markets.get(&player.current_airport),
+            &self.shared_state.cargo_types,
+        ))
+    }
+
+    /// A player's fuel burned per dollar of profit, if they're in profit
+    pub fn fuel_per_profit_dollar(&self, player_id: &Uuid) -> Option<f32> {
+        self.player_statistics
+            .get(player_id)?
+            .fuel_per_profit_dollar()
+    }
+
+    /// Honours for the room's finished game; none while it's still in play.
+    /// The Most Efficient Pilot burned the least fuel per dollar of profit.
+    pub fn awards(&self) -> Vec<RoomAward> {
+        if self.winner.is_none() {
+            return Vec::new();
+        }
+
+        let greenest = self
+            .standings()
+            .into_iter()
+            .filter_map(|player_state| {
+                let ratio = self.fuel_per_profit_dollar(&player_state.player_id)?;
+                Some((player_state, ratio))
+            })
+            .min_by(|(a, a_ratio), (b, b_ratio)| {
+                a_ratio
+                    .total_cmp(b_ratio)
+                    .then_with(|| a.player_name.cmp(&b.player_name))
+            });
+
+        greenest
+            .map(|(player_state, ratio)| RoomAward {
+                title: "🌱 Most Efficient Pilot".to_string(),
+                player_id: player_state.player_id,
+                player_name: player_state.player_name.clone(),
+                detail: format!(
+                    "{:.1} fuel per ${} of profit",
+                    ratio * FUEL_EFFICIENCY_PROFIT_UNIT as f32,
+                    FUEL_EFFICIENCY_PROFIT_UNIT
+                ),
+            })
+            .into_iter()
+            .collect()
+    }
+
+    /// Start sending a player digests, counting from now
+    pub fn subscribe_digest(
+        &mut self,
+        player_id: &Uuid,
+        target: DigestTarget,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), RoomError> {
+        target.validate()?;
+        let rank = self.rank_of(player_id).ok_or(RoomError::PlayerNotInRoom)?;
+        let world_tick = self.shared_state.world_tick;
+        let player_state = self
+            .players
+            .get_mut(player_id)
+            .ok_or(RoomError::PlayerNotInRoom)?;
+
+        player_state.digest = Some(DigestSubscription {
+            target,
+            last_sent_at: now,
+            world_tick,
+            turn_number: player_state.turn_number,
+            rank,
+        });
+        Ok(())
+    }
+
+    /// Build the digests that are due, at most one per player per `period`,
+    /// and move each subscriber's marks up to now. Quiet periods are skipped
+    /// but still count as sent.
+    pub fn collect_digests(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+        period: chrono::Duration,
+    ) -> Vec<(DigestTarget, Digest)> {
+        let leaderboard: Vec<LeaderboardEntry> = self
+            .standings()
+            .into_iter()
+            .map(|player_state| LeaderboardEntry {
+                player_name: player_state.player_name.clone(),
+                money: player_state.player.money,
+                net_worth: self
+                    .net_worth(&player_state.player_id)
+                    .map(|worth| worth.total())
+                    .unwrap_or(0),
+                fuel_per_profit_dollar: self.fuel_per_profit_dollar(&player_state.player_id),
+            })
+            .collect();
+        let due: Vec<Uuid> = self
+            .players
+            .values()
+            .filter(|p| {
+                p.digest
+                    .as_ref()
+                    .is_some_and(|d| now - d.last_sent_at >= period)
+            })
+            .map(|p| p.player_id)
+            .collect();
+
+        let mut digests = Vec::new();
+        for player_id in due {
+            let rank = self.rank_of(&player_id).unwrap_or(leaderboard.len());
+            let world_tick = self.shared_state.world_tick;
+            let player_state = &self.players[&player_id];
+            let Some(subscription) = player_state.digest.clone() else {
+                continue;
+            };
+
+            let mut mentions: Vec<DigestMention> = self
+                .message_board
+                .get_all_messages(None)
+                .into_iter()
+                .filter(|message| {
+                    !message.is_npc
+                        && message.author_id != player_id
+                        && message.created_at > subscription.last_sent_at
+                        && digest::mentions(&message.content, &player_state.player_name)
+                })
+                .map(|message| DigestMention {
+                    author_name: message.author_name.clone(),
+                    airport_id: message.airport_id.clone(),
+                    content: message.content.clone(),
+                    created_at: message.created_at,
+                })
+                .collect();
+            let more_mentions = mentions.len().saturating_sub(digest::MAX_DIGEST_MENTIONS);
+            mentions.truncate(digest::MAX_DIGEST_MENTIONS);
+
+            let turn_number = player_state.turn_number;
+            let digest = Digest {
+                room_id: self.id,
+                room_name: self.name.clone(),
+                player_id,
+                player_name: player_state.player_name.clone(),
+                since: subscription.last_sent_at,
+                until: now,
+                world_turns: world_tick.saturating_sub(subscription.world_tick),
+                your_turns: turn_number.saturating_sub(subscription.turn_number),
+                rank,
+                previous_rank: subscription.rank,
+                leaderboard: leaderboard.clone(),
+                mentions,
+                more_mentions,
+                winner: self
+                    .winner
+                    .clone()
+                    .filter(|winner| winner.won_at > subscription.last_sent_at),
+            };
+
+            if let Some(player_state) = self.players.get_mut(&player_id) {
+                player_state.digest = Some(DigestSubscription {
+                    last_sent_at: now,
+                    world_tick,
+                    turn_number,
+                    rank,
+                    ..subscription.clone()
+                });
+            }
+
+            if !digest.is_quiet() {
+                digests.push((subscription.target, digest));
+            }
+        }
+
+        digests
+    }
+
+    fn rank_of(&self, player_id: &Uuid) -> Option<usize> {
+        self.standings()
+            .iter()
+            .position(|p| p.player_id == *player_id)
+            .map(|index| index + 1)
+    }
+
+    /// What a player knows about an airport's prices. Without fog of war,
+    /// and at the airport they're standing in, that's the live market;
+    /// otherwise it's their last sighting, if they have one.
+    pub fn known_market(&self, player_id: &Uuid, airport_id: &str) -> Option<MarketSighting> {
+        let player_state = self.players.get(player_id)?;
+
+        if !self.settings.fog_of_war || player_state.player.current_airport == airport_id {
+            let market = self.shared_state.markets.get(airport_id)?;
+            return Some(MarketSighting {
+                fuel_price: market.fuel_price,
+                cargo_prices: market.cargo_prices.clone(),
+                observed_at: player_state.turn_number,
+            });
+        }
+
+        player_state.market_knowledge.get(airport_id).cloned()
+    }
+
+    /// A sighting is stale once the player has taken more turns since than
+    /// the room allows.
+    pub fn is_stale(&self, player_id: &Uuid, sighting: &MarketSighting) -> bool {
+        let current_turn = self
+            .players
+            .get(player_id)
+            .map(|p| p.turn_number)
+            .unwrap_or(sighting.observed_at);
+        current_turn.saturating_sub(sighting.observed_at) > self.settings.fog_stale_after_turns
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.game_status == GameStatus::Finished
+    }
+
+    /// Open offers at an airport, oldest first
+    pub fn offers_at(&self, airport_id: &str) -> Vec<&TradeOffer> {
+        self.offers
+            .iter()
+            .filter(|offer| offer.airport_id == airport_id)
+            .collect()
+    }
+
+    /// Put up a standing offer at the player's airport, moving the cargo
+    /// (selling) or the money (buying) into escrow
+    pub fn place_offer(
+        &mut self,
+        player_id: &Uuid,
+        terms: &OfferTerms,
+    ) -> Result<TradeOffer, RoomError> {
+        let OfferTerms {
+            side,
+            ref cargo_type,
+            quantity,
+            unit_price,
+        } = *terms;
+        if quantity == 0 {
+            return Err("Quantity must be greater than zero".into());
+        }
+        if unit_price == 0 {
+            return Err("Price must be greater than zero".into());
+        }
+        if !self
+            .shared_state
+            .cargo_types
+            .contains_key(cargo_type.as_str())
+        {
+            return Err(format!("Unknown cargo type: {}", cargo_type).into());
+        }
+        let total = unit_price
+            .checked_mul(quantity)
+            .ok_or("That offer is too large to price")?;
+        let open = self
+            .offers
+            .iter()
+            .filter(|offer| offer.player_id == *player_id)
+            .count();
+        if open >= MAX_OPEN_OFFERS {
+            return Err(format!(
+                "You already have {} open offers; cancel one first",
+                MAX_OPEN_OFFERS
+            )
+            .into());
+        }
+
+        let player_state = self
+            .players
+            .get_mut(player_id)
+            .ok_or(RoomError::PlayerNotInRoom)?;
+        let player = &mut player_state.player;
+        match side {
+            OfferSide::Sell => {
+                if !player.cargo_inventory.remove_cargo(cargo_type, quantity) {
+                    return Err(
+                        format!("You don't have {} {} to sell", quantity, cargo_type).into(),
+                    );
+                }
+            },
+            OfferSide::Buy => {
+                if !player.spend_money(total) {
+                    return Err(format!("You need ${} to back that offer", total).into());
+                }
+            },
+        }
+
+        let offer = TradeOffer {
+            id: Uuid::new_v4(),
+            player_id: *player_id,
+            player_name: player_state.player_name.clone(),
+            airport_id: player.current_airport.clone(),
+            side,
+            cargo_type: cargo_type.clone(),
+            quantity,
+            unit_price,
+            created_at: chrono::Utc::now(),
+        };
+        self.offers.push(offer.clone());
+        Ok(offer)
+    }
+
+    /// Withdraw one of the player's offers and take back what's in escrow.
+    /// Escrowed cargo has to be picked up at the offer's airport.
+    pub fn cancel_offer(
+        &mut self,
+        player_id: &Uuid,
+        offer_id: &Uuid,
+    ) -> Result<TradeOffer, RoomError> {
+        let index = self
+            .offers
+            .iter()
+            .position(|offer| offer.id == *offer_id)
+            .ok_or(RoomError::OfferNotFound)?;
+        let offer = &self.offers[index];
+        if offer.player_id != *player_id {
+            return Err("That offer isn't yours".into());
+        }
+
+        let cargo_types = &self.shared_state.cargo_types;
+        let player = &mut self
+            .players
+            .get_mut(player_id)
+            .ok_or(RoomError::PlayerNotInRoom)?
+            .player;
+        match offer.side {
+            OfferSide::Sell => {
+                if player.current_airport != offer.airport_id {
+                    return Err(format!(
+                        "Your {} is waiting at {}; cancel the offer there",
+                        offer.cargo_type, offer.airport_id
+                    )
+                    .into());
+                }
+                let weight = cargo_types
+                    .get(&offer.cargo_type)
+                    .map(|cargo_type| cargo_type.weight_per_unit.saturating_mul(offer.quantity))
+                    .unwrap_or(0);
+                if !player.can_carry_more_weight(weight, cargo_types) {
+                    return Err("Not enough cargo space to take it back".into());
+                }
+                player
+                    .cargo_inventory
+                    .add_cargo(&offer.cargo_type, offer.quantity);
+            },
+            OfferSide::Buy => {
+                // Escrow came out of this player's money, so it fits back in
+                player.earn_money(offer.total_for(offer.quantity).unwrap_or(u32::MAX));
+            },
+        }
+
+        Ok(self.offers.remove(index))
+    }
+
+    /// Move the vendors on to new airports once their stalls have been up
+    /// long enough
+    pub fn rotate_vendor_stalls(&mut self, rng: &mut impl Rng) {
+        let shared_state = &mut self.shared_state;
+        if !self.settings.vendor_stalls || !shared_state.vendors.due(shared_state.world_tick) {
+            return;
+        }
+        shared_state.vendors.restock(
+            &shared_state.markets,
+            &shared_state.cargo_types,
+            shared_state.world_tick,
+            self.settings.fog_of_war,
+            rng,
+        );
+    }
+
+    /// Buy a stall's item at the airport the player is at. The stall comes
+    /// down once it's sold.
+    pub fn buy_from_stall(
+        &mut self,
+        player_id: &Uuid,
+        stall_id: &Uuid,
+    ) -> Result<StallSale, RoomError> {
+        let player_state = self
+            .players
+            .get(player_id)
+            .ok_or(RoomError::PlayerNotInRoom)?;
+        let player = &player_state.player;
+        let stall = self
+            .shared_state
+            .vendors
+            .stalls
+            .iter()
+            .find(|stall| stall.id == *stall_id)
+            .ok_or("That stall has packed up or sold out")?
+            .clone();
+        if stall.airport_id != player.current_airport {
+            return Err(format!("That stall is at {}", stall.airport_id).into());
+        }
+        if !player.can_afford(stall.price) {
+            return Err(format!("{} wants ${}", stall.vendor, stall.price).into());
+        }
+
+        let mut rng = rand::thread_rng();
+        let purchase = match stall.item {
+            VendorItem::FuelVoucher { fuel } => {
+                let room = player.max_fuel.saturating_sub(player.fuel);
+                if room == 0 {
+                    return Err("Your tank is already full".into());
+                }
+                VendorPurchase::Fuel {
+                    added: fuel.min(room),
+                }
+            },
+            VendorItem::CargoCrate { units } => {
+                let mut cargo_ids: Vec<&String> = self.shared_state.cargo_types.keys().collect();
+                cargo_ids.sort();
+                let cargo_id = cargo_ids
+                    .choose(&mut rng)
+                    .ok_or("This room has no cargo to crate")?;
+                let weight = self.shared_state.cargo_types[*cargo_id]
+                    .weight_per_unit
+                    .saturating_mul(units);
+                if !player.can_carry_more_weight(weight, &self.shared_state.cargo_types) {
+                    return Err(format!(
+                        "The crate weighs {}kg; make room in the hold first",
+                        weight
+                    )
+                    .into());
+                }
+                VendorPurchase::Cargo {
+                    cargo_type: cargo_id.to_string(),
+                    units,
+                }
+            },
+            VendorItem::MapIntel => {
+                // Airports never seen come first, then the stalest sightings
+                let mut airports: Vec<&String> = self
+                    .shared_state
+                    .markets
+                    .keys()
+                    .filter(|airport_id| **airport_id != player.current_airport)
+                    .collect();
+                airports.sort_by_key(|airport_id| {
+                    let seen = player_state
+                        .market_knowledge
+                        .get(*airport_id)
+                        .map(|sighting| sighting.observed_at);
+                    (seen, *airport_id)
+                });
+                VendorPurchase::Intel {
+                    airports: airports.into_iter().take(INTEL_AIRPORTS).cloned().collect(),
+                }
+            },
+        };
+
+        let unit_cost = match &purchase {
+            VendorPurchase::Cargo { units, .. } => stall.price / units,
+            _ => 0,
+        };
+        let markets = &self.shared_state.markets;
+        let player_state = self
+            .players
+            .get_mut(player_id)
+            .ok_or(RoomError::PlayerNotInRoom)?;
+        player_state.player.spend_money(stall.price);
+        match &purchase {
+            VendorPurchase::Fuel { added } => player_state.player.add_fuel(*added),
+            VendorPurchase::Cargo { cargo_type, units } => player_state
+                .player
+                .cargo_inventory
+                .add_cargo_at(cargo_type, *units, unit_cost),
+            VendorPurchase::Intel { airports } => {
+                for airport_id in airports {
+                    if let Some(market) = markets.get(airport_id) {
+                        player_state.market_knowledge.insert(
+                            airport_id.clone(),
+                            MarketSighting {
+                                fuel_price: market.fuel_price,
+                                cargo_prices: market.cargo_prices.clone(),
+                                observed_at: player_state.turn_number,
+                            },
+                        );
+                    }
+                }
+            },
+        }
+        self.shared_state.vendors.take(stall_id);
+        Ok((stall, purchase))
+    }
+
+    /// Take `quantity` units (all of them by default) of another player's
+    /// offer at the airport the player is at. Returns the part that traded.
+    pub fn accept_offer(
+        &mut self,
+        player_id: &Uuid,
+        offer_id: &Uuid,
+        quantity: Option<u32>,
+    ) -> Result<TradeOffer, RoomError> {
+        let index = self
+            .offers
+            .iter()
+            .position(|offer| offer.id == *offer_id)
+            .ok_or(RoomError::OfferNotFound)?;
+        let offer = self.offers[index].clone();
+        if offer.player_id == *player_id {
+            return Err("You can't accept your own offer".into());
+        }
+
+        let quantity = quantity.unwrap_or(offer.quantity);
+        if quantity == 0 || quantity > offer.quantity {
+            return Err(format!(
+                "Choose between 1 and {} {}",
+                offer.quantity, offer.cargo_type
+            )
+            .into());
+        }
+        let total = offer
+            .total_for(quantity)
+            .ok_or("That offer is too large to price")?;
+        let weight = self
+            .shared_state
+            .cargo_types
+            .get(&offer.cargo_type)
+            .map(|cargo_type| cargo_type.weight_per_unit.saturating_mul(quantity))
+            .unwrap_or(0);
+
+        let taker = &self
+            .players
+            .get(player_id)
+            .ok_or(RoomError::PlayerNotInRoom)?
+            .player;
+        let poster = &self
+            .players
+            .get(&offer.player_id)
+            .ok_or("The player who made that offer has gone")?
+            .player;
+        if taker.current_airport != offer.airport_id {
+            return Err(format!("That offer can only be taken at {}", offer.airport_id).into());
+        }
+
+        let cargo_types = &self.shared_state.cargo_types;
+        // Whoever ends up with the cargo and whoever ends up with the money
+        let (buyer_id, seller_id) = match offer.side {
+            OfferSide::Sell => {
+                if !taker.can_afford(total) {
+                    return Err(format!("You need ${} for that", total).into());
+                }
+                if !taker.can_carry_more_weight(weight, cargo_types) {
+                    return Err("Not enough cargo space".into());
+                }
+                if poster.money.checked_add(total).is_none() {
+                    return Err("That offer is too large to price".into());
+                }
+                (*player_id, offer.player_id)
+            },
+            OfferSide::Buy => {
+                if taker.cargo_inventory.get_quantity(&offer.cargo_type) < quantity {
+                    return Err(format!(
+                        "You don't have {} {} to sell",
+                        quantity, offer.cargo_type
+                    )
+                    .into());
+                }
+                if !poster.can_carry_more_weight(weight, cargo_types) {
+                    return Err(
+                        format!("{} has no room left for that cargo", offer.player_name).into(),
+                    );
+                }
+                if taker.money.checked_add(total).is_none() {
+                    return Err("That offer is too large to price".into());
+                }
+                (offer.player_id, *player_id)
+            },
+        };
+
+        // Each side gives up what isn't already in escrow
+        if offer.side == OfferSide::Sell {
+            let buyer = &mut self.players.get_mut(&buyer_id).unwrap().player;
+            buyer.spend_money(total);
+            buyer
+                .cargo_inventory
+                .add_cargo_at(&offer.cargo_type, quantity, offer.unit_price);
+        } else {
+            self.players
+                .get_mut(&seller_id)
+                .unwrap()
+                .player
+                .cargo_inventory
+                .remove_cargo(&offer.cargo_type, quantity);
+            self.players
+                .get_mut(&buyer_id)
+                .unwrap()
+                .player
+                .cargo_inventory
+                .add_cargo_at(&offer.cargo_type, quantity, offer.unit_price);
+        }
+        if offer.side == OfferSide::Buy || seller_id != buyer_id {
+            self.players
+                .get_mut(&seller_id)
+                .unwrap()
+                .player
+                .earn_money(total);
+        }
+
+        if let Some(stats) = self.player_statistics.get_mut(&buyer_id) {
+            stats.record_cargo_purchase(total);
+        }
+        if let Some(stats) = self.player_statistics.get_mut(&seller_id) {
+            stats.record_sale(&offer.cargo_type, total);
+        }
+
+        let remaining = &mut self.offers[index];
+        remaining.quantity -= quantity;
+        if remaining.quantity == 0 {
+            self.offers.remove(index);
+        }
+
+        Ok(TradeOffer { quantity, ..offer })
+    }
+
+    /// Hand money and/or cargo straight to another player at the same
+    /// airport. Everything is checked before either player changes, so the
+    /// transfer happens in full or not at all.
+    pub fn transfer(
+        &mut self,
+        sender_id: &Uuid,
+        terms: &TransferTerms,
+    ) -> Result<Transfer, RoomError> {
+        let recipient_id = terms.recipient_id;
+        if recipient_id == *sender_id {
+            return Err("You can't send things to yourself".into());
+        }
+        let cargo = terms.cargo();
+        if terms.money == 0 && cargo.is_none() {
+            return Err("Send some money or cargo".into());
+        }
+
+        let sender_state = self
+            .players
+            .get(sender_id)
+            .ok_or(RoomError::PlayerNotInRoom)?;
+        let recipient_state = self
+            .players
+            .get(&recipient_id)
+            .ok_or(RoomError::PlayerNotInRoom)?;
+        let (sender, recipient) = (&sender_state.player, &recipient_state.player);
+        if sender.current_airport != recipient.current_airport {
+            return Err(format!(
+                "{} is at {}; you can only hand things over at the same airport",
+                recipient_state.player_name, recipient.current_airport
+            )
+            .into());
+        }
+
+        if !sender.can_afford(terms.money) {
+            return Err(format!("You don't have ${} to send", terms.money).into());
+        }
+        if recipient.money.checked_add(terms.money).is_none() {
+            return Err("That's more money than they can hold".into());
+        }
+        let mut unit_cost = None;
+        if let Some((cargo_type, quantity)) = cargo {
+            let weight_per_unit = self
+                .shared_state
+                .cargo_types
+                .get(cargo_type)
+                .map(|cargo_type| cargo_type.weight_per_unit)
+                .ok_or_else(|| format!("Unknown cargo type: {}", cargo_type))?;
+            if sender.cargo_inventory.get_quantity(cargo_type) < quantity {
+                return Err(format!("You don't have {} {} to send", quantity, cargo_type).into());
+            }
+            if !recipient.can_carry_more_weight(
+                weight_per_unit.saturating_mul(quantity),
+                &self.shared_state.cargo_types,
+            ) {
+                return Err(format!(
+                    "{} has no room left for that cargo",
+                    recipient_state.player_name
+                )
+                .into());
+            }
+            // The recipient takes it on at what the sender paid
+            unit_cost = sender.cargo_inventory.average_cost(cargo_type);
+        }
+
+        let transfer = Transfer {
+            sender_id: *sender_id,
+            sender_name: sender_state.player_name.clone(),
+            recipient_id,
+            recipient_name: recipient_state.player_name.clone(),
+            airport_id: sender.current_airport.clone(),
+            money: terms.money,
+            cargo_type: cargo.map(|(cargo_type, _)| cargo_type.to_string()),
+            quantity: cargo.map(|(_, quantity)| quantity).unwrap_or(0),
+        };
+
+        let sender = &mut self
+            .players
+            .get_mut(sender_id)
+            .ok_or(RoomError::PlayerNotInRoom)?
+            .player;
+        sender.spend_money(transfer.money);
+        if let Some((cargo_type, quantity)) = cargo {
+            sender.cargo_inventory.remove_cargo(cargo_type, quantity);
+        }
+        let recipient = &mut self
+            .players
+            .get_mut(&recipient_id)
+            .ok_or(RoomError::PlayerNotInRoom)?
+            .player;
+        recipient.earn_money(transfer.money);
+        match (cargo, unit_cost) {
+            (Some((cargo_type, quantity)), Some(unit_cost)) => recipient
+                .cargo_inventory
+                .add_cargo_at(cargo_type, quantity, unit_cost),
+            (Some((cargo_type, quantity)), None) => {
+                recipient.cargo_inventory.add_cargo(cargo_type, quantity)
+            },
+            (None, _) => {},
+        }
+
+        Ok(transfer)
+    }
+
+    /// Achievements players have earned since the last look, as (pilot
+    /// name, achievement). Each is only reported once per room.
+    pub fn take_new_achievements(&mut self) -> Vec<(String, Achievement)> {
+        let mut player_ids: Vec<Uuid> = self.players.keys().copied().collect();
+        player_ids.sort();
+
+        let mut earned = Vec::new();
+        for player_id in &player_ids {
+            let new: Vec<Achievement> = Achievement::ALL
+                .into_iter()
+                .filter(|achievement| {
+                    !self.players[player_id].achievements.contains(achievement)
+                        && achievement.earned_in(self, player_id)
+                })
+                .collect();
+            let player_state = self.players.get_mut(player_id).unwrap();
+            for achievement in new {
+                player_state.achievements.push(achievement);
+                earned.push((player_state.player_name.clone(), achievement));
+            }
+        }
+        earned
+    }
+
+    /// What an action by `player_id` can change part-way before failing:
+    /// the player, their statistics and the market where they are. Anything
+    /// else an action touches it checks before changing. Their journal and
+    /// feed only grow once an action has gone through, so they're left out
+    /// of the copy.
+    pub fn checkpoint(&mut self, player_id: &Uuid) -> ActionCheckpoint {
+        let player_state = self.players.get_mut(player_id).map(|player_state| {
+            let journal = std::mem::take(&mut player_state.journal);
+            let notifications = std::mem::take(&mut player_state.notifications);
+            let copy = player_state.clone();
+            player_state.journal = journal;
+            player_state.notifications = notifications;
+            Box::new(copy)
+        });
+        let market = player_state
+            .as_ref()
+            .and_then(|player_state| {
+                self.shared_state
+                    .markets
+                    .get(&player_state.player.current_airport)
+            })
+            .cloned();
+        ActionCheckpoint {
+            player_id: *player_id,
+            player_state,
+            statistics: self.player_statistics.get(player_id).cloned(),
+            market,
+        }
+    }
+
+    /// Put back what `checkpoint` kept. Cooldowns started since are kept, so
+    /// a refused action still counts against its player.
+    pub fn restore(&mut self, checkpoint: ActionCheckpoint) {
+        let player_id = checkpoint.player_id;
+        if let Some(mut saved) = checkpoint.player_state
+            && let Some(live) = self.players.get_mut(&player_id)
+        {
+            saved.journal = std::mem::take(&mut live.journal);
+            saved.notifications = std::mem::take(&mut live.notifications);
+            saved.last_action_at = live.last_action_at;
+            *live = *saved;
+        }
+        if let Some(statistics) = checkpoint.statistics {
+            self.player_statistics.insert(player_id, statistics);
+        }
+        if let Some(market) = checkpoint.market {
+            self.shared_state
+                .markets
+                .insert(market.airport_id.clone(), market);
+        }
+    }
+
+    /// Hold a player to the room's action cooldown, starting a new one when
+    /// the action may go ahead
+    pub fn start_action(&mut self, player_id: &Uuid, now: Instant) -> Result<(), RoomError> {
+        let cooldown = std::time::Duration::from_millis(self.settings.action_cooldown_ms);
+        let player_state = self
+            .players
+            .get_mut(player_id)
+            .ok_or(RoomError::PlayerNotInRoom)?;
+
+        if let Some(last) = player_state.last_action_at {
+            let ready_at = last + cooldown;
+            if now < ready_at {
+                let wait = ready_at - now;
+                return Err(RoomError::Cooldown {
+                    retry_after_ms: wait.as_millis().max(1) as u64,
+                });
+            }
+        }
+        player_state.last_action_at = Some(now);
+        Ok(())
+    }
+
+    /// Refuse game actions once someone has won
+    pub fn ensure_in_play(&self) -> Result<(), RoomError> {
+        if !self.is_finished() {
+            return Ok(());
+        }
+
+        let localized = match &self.winner {
+            Some(winner) => LocalizedMessage::new(MessageCode::GameWon)
+                .with("winner", &winner.player_name)
+                .with("money", winner.money),
+            None => LocalizedMessage::new(MessageCode::GameOver),
+        };
+        Err(localized.into())
+    }
+
+    /// Evaluate the win condition for a player after they act. The first
+    /// player to reach it wins and the room moves to Finished. Returns true
+    /// if this call decided the game.
+    pub fn check_for_winner(&mut self, player_id: &Uuid) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        let Some(player_state) = self.players.get(player_id) else {
+            return false;
+        };
+        // Borrowed money doesn't count towards winning
+        let money = player_state
+            .player
+            .money
+            .saturating_sub(player_state.player.loans.debt());
+        if money < self.settings.win_condition_money {
+            return false;
+        }
+
+        self.winner = Some(RoomWinner {
+            player_id: *player_id,
+            player_name: player_state.player_name.clone(),
+            money: player_state.player.money,
+            turn_number: player_state.turn_number,
+            won_at: chrono::Utc::now(),
+        });
+        self.game_status = GameStatus::Finished;
+        true
+    }
+
+    /// Players whose votes it takes to start the next round: everyone still
+    /// online
+    pub fn rematch_votes_needed(&self) -> usize {
+        self.players.values().filter(|p| p.is_online).count()
+    }
+
+    /// Vote to play the next round in this room once the game is over.
+    /// When every online player has voted the round starts straight away.
+    /// Returns true if this vote started it.
+    pub fn vote_rematch(&mut self, player_id: &Uuid) -> Result<bool, RoomError> {
+        if !self.is_finished() {
+            return Err("A rematch is only available once the game is over"
+                .to_string()
+                .into());
+        }
+        if !self.players.contains_key(player_id) {
+            return Err(RoomError::PlayerNotInRoom);
+        }
+
+        if !self.rematch_votes.contains(player_id) {
+            self.rematch_votes.push(*player_id);
+        }
+        let online_votes = self
+            .rematch_votes
+            .iter()
+            .filter(|voter| self.players.get(voter).is_some_and(|p| p.is_online))
+            .count();
+        if online_votes < self.rematch_votes_needed() {
+            return Ok(false);
+        }
+
+        self.start_next_round();
+        Ok(true)
+    }
+
+    /// Put the room back to how it opened, under the same settings and with
+    /// the same players. The message board, mentions and everyone's digest
+    /// and alert preferences carry over; markets, money, cargo, turns and
+    /// the winner don't.
+    pub fn start_next_round(&mut self) {
+        let now = chrono::Utc::now();
+        let shared_state = &mut self.shared_state;
+        shared_state.world_tick = 1;
+        shared_state.markets = opening_markets(&shared_state.airports, &shared_state.cargo_types);
+        shared_state.world_time = now;
+        shared_state.last_market_update = now;
+        shared_state.active_events.clear();
+        shared_state.modifiers.clear();
+        shared_state.slot_book = SlotBook::default();
+        shared_state.vendors = VendorStalls::default();
+        shared_state.last_clock_tick = None;
+        self.start_scenario_modifiers();
+
+        // Escrowed cargo and money go with the old round, as does mail the
+        // new round's clock would never reach
+        self.offers.clear();
+        self.courier_mail
+            .retain(|letter| letter.status == CourierStatus::Delivered);
+
+        let mut player_ids: Vec<Uuid> = self.players.keys().copied().collect();
+        player_ids.sort_by_key(|player_id| (self.players[player_id].joined_at, *player_id));
+        let mut rng = rand::thread_rng();
+        for player_id in &player_ids {
+            let airport_id = self.spawn_airport(player_id, &mut rng);
+            let player = self.new_pilot(&airport_id);
+            if let Some(player_state) = self.players.get_mut(player_id) {
+                player_state.player = player;
+                player_state.turn_number = first_turn();
+                player_state.market_knowledge.clear();
+                player_state.insurance = InsuranceAccount::default();
+                player_state.journal.clear();
+                player_state.last_action_at = None;
+            }
+            self.player_statistics
+                .insert(*player_id, GameStatistics::new());
+            self.observe_market(player_id);
+        }
+        self.record_turns();
+
+        self.winner = None;
+        self.rematch_room_id = None;
+        self.rematch_votes.clear();
+        self.round += 1;
+        self.game_status = GameStatus::WaitingForPlayers;
+    }
+
+    #[allow(dead_code)]
+    pub fn start_game(&mut self) -> Result<(), String> {
+        if self.players.is_empty() {
+            return Err("Need at least 1 player to start".to_string());
+        }
+
+        self.game_status = GameStatus::InProgress;
+        Ok(())
+    }
+
+    pub fn is_bot(&self, player_id: &Uuid) -> bool {
+        self.demo
+            .as_ref()
+            .is_some_and(|demo| demo.is_bot(player_id))
+    }
+
+    /// Start a demo room over: people who have gone offline make room for
+    /// newcomers, and everyone left begins a fresh round
+    pub fn reset_demo(&mut self, now: chrono::DateTime<chrono::Utc>) {
+        let Some(demo) = &self.demo else {
+            return;
+        };
+        let gone: Vec<Uuid> = self
+            .players
+            .values()
+            .filter(|p| !p.is_online && !demo.is_bot(&p.player_id))
+            .map(|p| p.player_id)
+            .collect();
+        let bots = demo.bots.clone();
+        for player_id in gone {
+            let _ = self.remove_player(player_id);
+        }
+
+        self.start_next_round();
+        for bot_id in &bots {
+            self.update_player_activity(bot_id);
+        }
+        if let Some(bot_id) = bots.first() {
+            self.host_player_id = *bot_id;
+        }
+        if let Some(demo) = &mut self.demo {
+            demo.reset_at = now;
+        }
     }
 
     pub fn is_joinable(&self) -> bool {
@@ -0,0 +1,185 @@
+use rand::{Rng, seq::SliceRandom};
+use std::collections::HashMap;
+
+use crate::models::{Airport, CargoType, Market, Message, MessageTopic};
+
+/// Chance per airport, per chatter tick, that a local posts something
+pub const NPC_POST_CHANCE: f32 = 0.15;
+
+/// Locals stop talking once this many of the newest posts at an airport
+/// are theirs, so they never bury what the players wrote
+pub const NPC_MAX_UNANSWERED: usize = 3;
+
+const NPC_NAMES: [&str; 5] = [
+    "Gus (ramp agent)",
+    "Marge (fuel desk)",
+    "Old Pete (hangar regular)",
+    "Tower Tina",
+    "Dispatch",
+];
+
+const LORE: [&str; 5] = [
+    "They say the first cargo run out of here was a DC-3 full of pineapples. Nobody knows where it was going.",
+    "Somebody once tried to haul a grand piano in a Cessna. The piano made it. The Cessna didn't.",
+    "If you hear a bell in the hangar at night, that's just the old maintenance chief. Or the wind. Probably the wind.",
+    "Rule one of freight flying: never trust a manifest that says \"assorted\".",
+    "The vending machine by gate 4 has eaten more quarters than the airline has flown passengers.",
+];
+
+/// What a local has to say
+#[derive(Debug, Clone, PartialEq)]
+pub struct NpcPost {
+    pub author_name: String,
+    pub content: String,
+    pub topic: MessageTopic,
+}
+
+pub struct NpcChatter;
+
+impl NpcChatter {
+    /// A weather gripe, a rumor about this market's real prices, or a bit of
+    /// airport lore, posted under Warnings, Trade Tips and General. Rumors
+    /// of dry tanks are warnings too.
+    pub fn compose(
+        airport: &Airport,
+        market: &Market,
+        cargo_types: &HashMap<String, CargoType>,
+        rng: &mut impl Rng,
+    ) -> NpcPost {
+        let (content, topic) = match rng.gen_range(0..3) {
+            0 => (Self::weather_gripe(airport, rng), MessageTopic::Warnings),
+            1 if market.fuel_outage => (
+                Self::price_rumor(airport, market, cargo_types),
+                MessageTopic::Warnings,
+            ),
+            1 => (
+                Self::price_rumor(airport, market, cargo_types),
+                MessageTopic::TradeTips,
+            ),
+            _ => (
+                LORE.choose(rng).unwrap_or(&LORE[0]).to_string(),
+                MessageTopic::General,
+            ),
+        };
+
+        NpcPost {
+            author_name: NPC_NAMES.choose(rng).unwrap_or(&NPC_NAMES[0]).to_string(),
+            content,
+            topic,
+        }
+    }
+
+    /// Whether the newest posts at an airport are all from locals already
+    pub fn is_talked_out(recent: &[&Message]) -> bool {
+        recent.len() >= NPC_MAX_UNANSWERED
+            && recent
+                .iter()
+                .take(NPC_MAX_UNANSWERED)
+                .all(|message| message.is_npc)
+    }
+
+    fn weather_gripe(airport: &Airport, rng: &mut impl Rng) -> String {
+        let gripes = [
+            format!(
+                "Crosswinds at {} again. Hold onto your coffee on final.",
+                airport.name
+            ),
+            format!(
+                "Fog rolled into {} this morning and hasn't left. Bring your instrument rating.",
+                airport.name
+            ),
+            "De-icing line is twenty planes long. Wonderful.".to_string(),
+            format!(
+                "Thunderstorms forecast around {} all afternoon. Plan your fuel.",
+                airport.name
+            ),
+            "Hottest day of the year on the apron. The tarmac is practically soup.".to_string(),
+        ];
+        gripes.choose(rng).cloned().unwrap_or_default()
+    }
+
+    /// Rumors are always true: they come from the market as it is right now
+    fn price_rumor(
+        airport: &Airport,
+        market: &Market,
+        cargo_types: &HashMap<String, CargoType>,
+    ) -> String {
+        if market.fuel_outage {
+            return format!(
+                "Tanks are dry at {}. Don't count on filling up here for a while.",
+                airport.name
+            );
+        }
+
+        // The cargo furthest from its usual price makes the best gossip
+        let mut cargo_ids: Vec<&String> = market.cargo_prices.keys().collect();
+        cargo_ids.sort();
+        let standout = cargo_ids
+            .into_iter()
+            .filter_map(|cargo_id| {
+                let cargo = cargo_types.get(cargo_id)?;
+                let price = market.get_cargo_price(cargo_id)?;
+                let swing = price as f32 / cargo.base_price.max(1) as f32 - 1.0;
+                Some((cargo, price, swing))
+            })
+            .max_by(|a, b| a.2.abs().total_cmp(&b.2.abs()));
+
+        match standout {
+            Some((cargo, price, swing)) if swing >= 0.2 => format!(
+                "Word is buyers here are paying ${} for {}. That's well over the usual ${}.",
+                price, cargo.name, cargo.base_price
+            ),
+            Some((cargo, price, swing)) if swing <= -0.2 => format!(
+                "Warehouse is overflowing with {}. It's going for ${}, cheap as it gets.",
+                cargo.name, price
+            ),
+            _ => format!(
+                "Nothing much moving at {}. Prices are about where they always are, fuel's ${}.",
+                airport.name, market.fuel_price
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+
+    #[test]
+    fn test_rumors_follow_the_market() {
+        let airports = get_default_airports();
+        let cargo_types = get_default_cargo_types();
+        let airport = &airports["JFK"];
+        let mut market = Market::new("JFK", 50);
+        for (cargo_id, cargo) in &cargo_types {
+            market.set_cargo_price(cargo_id, cargo.base_price);
+        }
+
+        let electronics = &cargo_types["electronics"];
+        market.set_cargo_price("electronics", electronics.base_price * 2);
+        let rumor = NpcChatter::price_rumor(airport, &market, &cargo_types);
+        assert!(rumor.contains(&electronics.name), "{}", rumor);
+        assert!(rumor.contains(&format!("${}", electronics.base_price * 2)));
+
+        market.start_fuel_outage();
+        let rumor = NpcChatter::price_rumor(airport, &market, &cargo_types);
+        assert!(rumor.starts_with("Tanks are dry"));
+    }
+
+    #[test]
+    fn test_posts_have_an_author_and_content() {
+        let airports = get_default_airports();
+        let cargo_types = get_default_cargo_types();
+        let market = Market::new("ORD", 50);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for _ in 0..20 {
+            let post = NpcChatter::compose(&airports["ORD"], &market, &cargo_types, &mut rng);
+            assert!(NPC_NAMES.contains(&post.author_name.as_str()));
+            assert!(!post.content.is_empty());
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Most standing offers one player may have open at a time
+pub const MAX_OPEN_OFFERS: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, ToSchema)]
+pub enum OfferSide {
+    /// The player is selling cargo they've handed over to escrow
+    #[default]
+    Sell,
+    /// The player is buying with money they've handed over to escrow
+    Buy,
+}
+
+impl OfferSide {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            OfferSide::Sell => "selling",
+            OfferSide::Buy => "buying",
+        }
+    }
+}
+
+/// What a player puts up when placing an offer
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OfferTerms {
+    pub side: OfferSide,
+    pub cargo_type: String,
+    pub quantity: u32,
+    pub unit_price: u32,
+}
+
+/// A standing offer between players at an airport. The goods are held in
+/// escrow while it's open: the cargo for a sell offer, the money for a buy
+/// offer. Another player at the airport can take some or all of it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TradeOffer {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub player_name: String,
+    pub airport_id: String,
+    pub side: OfferSide,
+    pub cargo_type: String,
+    /// Units still on offer
+    pub quantity: u32,
+    pub unit_price: u32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TradeOffer {
+    /// What `quantity` units of this offer come to, if that fits in a u32
+    pub fn total_for(&self, quantity: u32) -> Option<u32> {
+        self.unit_price.checked_mul(quantity)
+    }
+
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub fn describe(&self) -> String {
+        format!(
+            "{} is {} {} {} at ${} each",
+            self.player_name,
+            self.side.verb(),
+            self.quantity,
+            self.cargo_type,
+            self.unit_price
+        )
+    }
+}
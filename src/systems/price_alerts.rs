@@ -0,0 +1,141 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{CargoType, Market, cargo::CargoInventory};
+
+/// How far above its cost held cargo has to be worth before the trading
+/// desk says so, unless the player picks another threshold
+pub const DEFAULT_ALERT_THRESHOLD_PERCENT: u32 = 25;
+
+/// Highest threshold a player may set
+pub const MAX_ALERT_THRESHOLD_PERCENT: u32 = 1000;
+
+/// Held cargo that's now worth well over what was paid for it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceAlert {
+    pub cargo_type: String,
+    pub cargo_name: String,
+    pub airport_id: String,
+    pub average_cost: u32,
+    pub price: u32, // At `airport_id`
+    pub gain_percent: u32,
+}
+
+impl PriceAlert {
+    pub fn message(&self) -> String {
+        format!(
+            "Your {} are now +{}% vs purchase at {} (${} a unit, bought at ${})",
+            self.cargo_name.to_lowercase(),
+            self.gain_percent,
+            self.airport_id,
+            self.price,
+            self.average_cost
+        )
+    }
+}
+
+/// How much more than its cost cargo sells for at `price`, in percent;
+/// None when the cargo cost nothing
+pub fn gain_percent(average_cost: u32, price: u32) -> Option<i64> {
+    if average_cost == 0 {
+        return None;
+    }
+    Some((price as i64 - average_cost as i64) * 100 / average_cost as i64)
+}
+
+/// A player's alert threshold, and the cargo they've already been told
+/// about so the same alert doesn't come every turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceAlerts {
+    /// 0 turns alerts off
+    #[serde(default = "default_threshold_percent")]
+    pub threshold_percent: u32,
+    /// Cargo that was over the threshold when last checked
+    #[serde(default)]
+    above: HashSet<String>,
+}
+
+fn default_threshold_percent() -> u32 {
+    DEFAULT_ALERT_THRESHOLD_PERCENT
+}
+
+impl Default for PriceAlerts {
+    fn default() -> Self {
+        Self {
+            threshold_percent: default_threshold_percent(),
+            above: HashSet::new(),
+        }
+    }
+}
+
+impl PriceAlerts {
+    pub fn set_threshold(&mut self, percent: u32) -> Result<(), String> {
+        if percent > MAX_ALERT_THRESHOLD_PERCENT {
+            return Err(format!(
+                "The alert threshold can be at most {}%",
+                MAX_ALERT_THRESHOLD_PERCENT
+            ));
+        }
+        self.threshold_percent = percent;
+        self.above.clear();
+        Ok(())
+    }
+
+    /// Compare the cargo held against `market`, once a turn. Cargo alerts
+    /// when it first goes over the threshold, and again only after it has
+    /// dropped back under (or been sold) in between.
+    pub fn check(
+        &mut self,
+        inventory: &CargoInventory,
+        market: &Market,
+        cargo_types: &HashMap<String, CargoType>,
+    ) -> Vec<PriceAlert> {
+        if self.threshold_percent == 0 {
+            self.above.clear();
+            return Vec::new();
+        }
+
+        let mut held: Vec<&String> = inventory
+            .get_all_cargo()
+            .iter()
+            .filter(|(_, quantity)| **quantity > 0)
+            .map(|(cargo_id, _)| cargo_id)
+            .collect();
+        held.sort();
+
+        let mut alerts = Vec::new();
+        let mut above = HashSet::new();
+        for cargo_id in held {
+            let (Some(average_cost), Some(price)) = (
+                inventory.average_cost(cargo_id),
+                market.get_cargo_price(cargo_id),
+            ) else {
+                continue;
+            };
+            let Some(gain) = gain_percent(average_cost, price) else {
+                continue;
+            };
+            if gain < self.threshold_percent as i64 {
+                continue;
+            }
+
+            above.insert(cargo_id.clone());
+            if !self.above.contains(cargo_id) {
+                alerts.push(PriceAlert {
+                    cargo_type: cargo_id.clone(),
+                    cargo_name: cargo_types
+                        .get(cargo_id)
+                        .map(|cargo| cargo.name.clone())
+                        .unwrap_or_else(|| cargo_id.clone()),
+                    airport_id: market.airport_id.clone(),
+                    average_cost,
+                    price,
+                    gain_percent: gain as u32,
+                });
+            }
+        }
+        self.above = above;
+        alerts
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{config::Difficulty, systems::GameState};
+
+/// Settings every game started under a profile uses.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ProfileSettings {
+    pub difficulty: Difficulty,
+    #[serde(default)]
+    pub ironman: bool,
+}
+
+/// Lifetime record for a profile, across all of its games.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct ProfileStats {
+    pub games_started: u32,
+    pub games_won: u32,
+    pub games_lost: u32,
+    pub turns_played: u32,
+    pub best_money: u32,
+}
+
+impl ProfileStats {
+    pub fn record_game_started(&mut self) {
+        self.games_started += 1;
+    }
+
+    /// Fold a finished game into the record
+    pub fn record_game_finished(&mut self, game_state: &GameState, won: bool) {
+        if won {
+            self.games_won += 1;
+        } else {
+            self.games_lost += 1;
+        }
+        self.turns_played += game_state.turn_number;
+        self.best_money = self
+            .best_money
+            .max(game_state.stats.peak_money)
+            .max(game_state.player.money);
+    }
+}
+
+/// A named save slot ("Ironman run", "Casual") with its own saves,
+/// settings and statistics. Each profile lives in its own directory under
+/// the save directory; see `SaveSystem::profile_directory`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub settings: ProfileSettings,
+    #[serde(default)]
+    pub stats: ProfileStats,
+    pub created_at: DateTime<Local>,
+    #[serde(default)]
+    pub last_played: Option<DateTime<Local>>,
+}
+
+impl Profile {
+    pub fn new(name: String, settings: ProfileSettings) -> Self {
+        Self {
+            name,
+            settings,
+            stats: ProfileStats::default(),
+            created_at: Local::now(),
+            last_played: None,
+        }
+    }
+
+    /// Directory-safe version of the profile name
+    pub fn slug(name: &str) -> String {
+        let mut slug = String::new();
+        for c in name.trim().chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        slug.trim_end_matches('-').to_string()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{}{} ({}) - {} games, {} won, best ${}",
+            self.name,
+            if self.settings.ironman {
+                " [IRONMAN]"
+            } else {
+                ""
+            },
+            self.settings.difficulty.label(),
+            self.stats.games_started,
+            self.stats.games_won,
+            self.stats.best_money
+        )
+    }
+}
+
+/// Largest default quantity a player may set, for fuel or cargo
+pub const MAX_DEFAULT_QUANTITY: u32 = 10_000;
+
+/// Quantities the fuel pump and trading desk start at, so players who
+/// always buy the same amounts don't have to drag a slider every time
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(default)]
+pub struct QuantityDefaults {
+    /// Fuel units to buy at the pump
+    pub fuel_quantity: u32,
+    /// Units of cargo to buy or sell at the trading desk
+    pub trade_quantity: u32,
+    /// Start the pump at a full tank after every landing instead
+    pub auto_fill_on_arrival: bool,
+}
+
+impl Default for QuantityDefaults {
+    fn default() -> Self {
+        Self {
+            fuel_quantity: 10,
+            trade_quantity: 1,
+            auto_fill_on_arrival: false,
+        }
+    }
+}
+
+impl QuantityDefaults {
+    pub fn validate(&self) -> Result<(), String> {
+        for (what, quantity) in [("fuel", self.fuel_quantity), ("trade", self.trade_quantity)] {
+            if quantity == 0 || quantity > MAX_DEFAULT_QUANTITY {
+                return Err(format!(
+                    "The default {} quantity must be between 1 and {}",
+                    what, MAX_DEFAULT_QUANTITY
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fuel to offer at the pump with `space` units free in the tank.
+    /// `just_landed` is the first visit to the pump since arriving.
+    #[allow(dead_code)]
+    pub fn fuel_quantity_for(&self, space: u32, just_landed: bool) -> u32 {
+        if just_landed && self.auto_fill_on_arrival {
+            space
+        } else {
+            self.fuel_quantity.min(space)
+        }
+    }
+}
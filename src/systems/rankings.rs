@@ -0,0 +1,106 @@
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::systems::GameRoom;
+
+/// Points for finishing first, second, third and so on. Anyone further
+/// down still gets the last entry for seeing the game through.
+pub const PLACEMENT_POINTS: [u32; 5] = [10, 6, 4, 2, 1];
+
+/// Profit it takes to earn a bonus point on top of placement
+pub const PROFIT_PER_POINT: u32 = 10_000;
+
+/// Most ranking rows a single page may hold
+pub const MAX_RANKINGS_PAGE_SIZE: usize = 100;
+
+/// The season a moment falls in. Seasons run a calendar quarter each, so
+/// rankings start over four times a year, e.g. "2026-Q4".
+pub fn season_for(at: DateTime<Utc>) -> String {
+    format!("{}-Q{}", at.year(), at.month0() / 3 + 1)
+}
+
+/// Points earned for a placement and the profit made getting there
+pub fn points_for(placement: u32, profit: i64) -> u32 {
+    let index = (placement.max(1) as usize - 1).min(PLACEMENT_POINTS.len() - 1);
+    let bonus = profit.max(0) / PROFIT_PER_POINT as i64;
+    PLACEMENT_POINTS[index] + bonus as u32
+}
+
+/// How one pilot did in one finished room
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameResult {
+    pub room_id: Uuid,
+    /// Which of the room's rounds it was
+    #[serde(default)]
+    pub round: u32,
+    pub pilot_name: String,
+    pub season: String,
+    /// 1 for the winner
+    pub placement: u32,
+    /// Money at the end less the room's starting money
+    pub profit: i64,
+    pub points: u32,
+    pub finished_at: DateTime<Utc>,
+}
+
+impl GameResult {
+    /// Everyone's result in a room that has been won, the winner first and
+    /// the rest by the money they ended with. Empty while still in play.
+    pub fn for_room(room: &GameRoom) -> Vec<GameResult> {
+        let Some(winner) = &room.winner else {
+            return Vec::new();
+        };
+
+        let mut finishers: Vec<_> = room
+            .players
+            .iter()
+            .map(|(player_id, state)| {
+                (
+                    *player_id == winner.player_id,
+                    state.player_name.as_str(),
+                    state.player.money,
+                )
+            })
+            .collect();
+        finishers.sort_by(|(a_won, a_name, a_money), (b_won, b_name, b_money)| {
+            b_won
+                .cmp(a_won)
+                .then(b_money.cmp(a_money))
+                .then(a_name.cmp(b_name))
+        });
+
+        let starting_money = room.settings.starting_money as i64;
+        let season = season_for(winner.won_at);
+        finishers
+            .into_iter()
+            .enumerate()
+            .map(|(index, (_, pilot_name, money))| {
+                let placement = index as u32 + 1;
+                let profit = money as i64 - starting_money;
+                GameResult {
+                    room_id: room.id,
+                    round: room.round,
+                    pilot_name: pilot_name.to_string(),
+                    season: season.clone(),
+                    placement,
+                    profit,
+                    points: points_for(placement, profit),
+                    finished_at: winner.won_at,
+                }
+            })
+            .collect()
+    }
+}
+
+/// A pilot's standing in a season, summed over their finished games
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankingEntry {
+    /// 1 for the top of the table
+    pub rank: usize,
+    pub pilot_name: String,
+    pub points: u32,
+    pub games_played: u32,
+    pub wins: u32,
+    pub total_profit: i64,
+}
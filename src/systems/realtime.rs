@@ -0,0 +1,127 @@
+use std::time::{Duration, Instant};
+
+use crate::systems::game::{GameState, TurnReport};
+
+/// Shortest and longest turn a real-time game may be set to
+pub const MIN_TURN_SECONDS: u32 = 5;
+pub const MAX_TURN_SECONDS: u32 = 600;
+
+/// Offered when the player asks for real-time without picking a length
+pub const DEFAULT_TURN_SECONDS: u32 = 30;
+
+/// Seconds per turn as typed by the player, e.g. "30"
+pub fn parse_turn_seconds(input: &str) -> Result<u32, String> {
+    let seconds: u32 = input
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{}' isn't a number of seconds", input.trim()))?;
+    if !(MIN_TURN_SECONDS..=MAX_TURN_SECONDS).contains(&seconds) {
+        return Err(format!(
+            "Turns must last between {} and {} seconds",
+            MIN_TURN_SECONDS, MAX_TURN_SECONDS
+        ));
+    }
+    Ok(seconds)
+}
+
+/// Counts down to the next turn of a real-time game. Taking a turn by
+/// hand starts the countdown again; pausing freezes it.
+#[derive(Debug, Clone)]
+pub struct TurnClock {
+    interval: Duration,
+    /// When the current countdown started, or None while paused
+    running_since: Option<Instant>,
+    /// Countdown used up before the last pause
+    banked: Duration,
+}
+
+impl TurnClock {
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self {
+            interval,
+            running_since: Some(now),
+            banked: Duration::ZERO,
+        }
+    }
+
+    /// A clock for the game, if it's being played in real time
+    pub fn for_game(game_state: &GameState, now: Instant) -> Option<Self> {
+        let seconds = game_state.stats.real_time_turn_secs?;
+        Some(Self::new(Duration::from_secs(seconds.into()), now))
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.running_since.is_none()
+    }
+
+    /// Pause a running clock or resume a paused one. Returns whether it's
+    /// paused now.
+    pub fn toggle_pause(&mut self, now: Instant) -> bool {
+        match self.running_since.take() {
+            Some(since) => self.banked += now.saturating_duration_since(since),
+            None => self.running_since = Some(now),
+        }
+        self.is_paused()
+    }
+
+    fn elapsed(&self, now: Instant) -> Duration {
+        let running = self
+            .running_since
+            .map(|since| now.saturating_duration_since(since))
+            .unwrap_or_default();
+        self.banked + running
+    }
+
+    /// Turns that have run out since the last call, keeping whatever is
+    /// left of the current one
+    pub fn turns_due(&mut self, now: Instant) -> u32 {
+        let elapsed = self.elapsed(now);
+        let turns = (elapsed.as_millis() / self.interval.as_millis().max(1)) as u32;
+        if turns > 0 {
+            let leftover = elapsed - self.interval * turns;
+            self.banked = leftover;
+            if self.running_since.is_some() {
+                self.running_since = Some(now);
+            }
+        }
+        turns
+    }
+
+    /// The player took a turn themselves: a full turn until the next one
+    pub fn restart(&mut self, now: Instant) {
+        self.banked = Duration::ZERO;
+        if self.running_since.is_some() {
+            self.running_since = Some(now);
+        }
+    }
+
+    /// Time until the next turn passes on its own
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.interval.saturating_sub(self.elapsed(now))
+    }
+
+    /// A line for the status display
+    pub fn status(&self, now: Instant) -> String {
+        if self.is_paused() {
+            "⏸️  Clock paused".to_string()
+        } else {
+            format!(
+                "⏱️  Next turn in {}s",
+                self.remaining(now).as_secs_f32().ceil() as u32
+            )
+        }
+    }
+
+    /// Pass every turn that has run out while the player idled, stopping
+    /// early if the game ends. Returns what each turn brought.
+    pub fn pass_due_turns(&mut self, game_state: &mut GameState, now: Instant) -> Vec<TurnReport> {
+        let mut reports = Vec::new();
+        for _ in 0..self.turns_due(now) {
+            if game_state.is_game_won() || !game_state.can_player_continue() {
+                break;
+            }
+            reports.push(game_state.pass_idle_turn());
+        }
+        reports
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::{Airport, Market, Player},
+    systems::events::MarketEvent,
+};
+
+/// Something loading a saved game or room changed so it fits the airports
+/// the game knows today
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WorldAdjustment {
+    /// An airport's id changed; its market and anyone there followed it
+    Renamed { from: String, to: String },
+    /// An airport is no longer in the game
+    Removed { airport_id: String },
+    /// A pilot was at a removed airport and was moved to the nearest one
+    /// left
+    Relocated {
+        player_name: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl WorldAdjustment {
+    pub fn describe(&self) -> String {
+        match self {
+            WorldAdjustment::Renamed { from, to } => {
+                format!("Airport {} is now {}", from, to)
+            },
+            WorldAdjustment::Removed { airport_id } => {
+                format!("Airport {} has closed", airport_id)
+            },
+            WorldAdjustment::Relocated {
+                player_name,
+                from,
+                to,
+            } => format!("{} was moved from {} to {}", player_name, from, to),
+        }
+    }
+}
+
+/// What fitting a world's airports changed, and the airports removed, so
+/// pilots stranded at them can be moved
+#[derive(Debug, Clone, Default)]
+pub struct ReconciledWorld {
+    pub adjustments: Vec<WorldAdjustment>,
+    pub removed: HashMap<String, Airport>,
+}
+
+/// Brings a persisted world's airports in line with a registry of the ones
+/// that exist now, following any renames
+pub struct AirportReconciler<'a> {
+    registry: &'a HashMap<String, Airport>,
+    renames: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> AirportReconciler<'a> {
+    pub fn new(registry: &'a HashMap<String, Airport>, renames: &[(&'a str, &'a str)]) -> Self {
+        Self {
+            registry,
+            renames: renames.iter().copied().collect(),
+        }
+    }
+
+    /// Where an airport id points now: itself, its new id, or None when
+    /// it's gone
+    pub fn resolve(&self, airport_id: &str) -> Option<String> {
+        if self.registry.contains_key(airport_id) {
+            return Some(airport_id.to_string());
+        }
+        self.renames
+            .get(airport_id)
+            .filter(|new_id| self.registry.contains_key(**new_id))
+            .map(|new_id| new_id.to_string())
+    }
+
+    /// Rename or drop airports the registry doesn't have, along with their
+    /// markets and events
+    pub fn reconcile_world(
+        &self,
+        airports: &mut HashMap<String, Airport>,
+        markets: &mut HashMap<String, Market>,
+        events: &mut Vec<MarketEvent>,
+    ) -> Result<ReconciledWorld, String> {
+        let mut stale: Vec<String> = airports
+            .keys()
+            .filter(|airport_id| !self.registry.contains_key(*airport_id))
+            .cloned()
+            .collect();
+        // HashMap order isn't stable, so sort to report in the same order
+        stale.sort();
+
+        let mut adjustments = Vec::new();
+        let mut removed = HashMap::new();
+        for old_id in stale {
+            let Some(airport) = airports.remove(&old_id) else {
+                continue;
+            };
+            let market = markets.remove(&old_id);
+            match self.resolve(&old_id) {
+                Some(new_id) => {
+                    // A game that already has the new airport keeps its
+                    // own copy and market
+                    if !airports.contains_key(&new_id) {
+                        airports.insert(new_id.clone(), self.registry[&new_id].clone());
+                        if let Some(mut market) = market {
+                            market.airport_id = new_id.clone();
+                            markets.insert(new_id.clone(), market);
+                        }
+                    }
+                    adjustments.push(WorldAdjustment::Renamed {
+                        from: old_id,
+                        to: new_id,
+                    });
+                },
+                None => {
+                    adjustments.push(WorldAdjustment::Removed {
+                        airport_id: old_id.clone(),
+                    });
+                    removed.insert(old_id, airport);
+                },
+            }
+        }
+
+        if airports.is_empty() {
+            return Err("None of this game's airports exist any more".to_string());
+        }
+
+        events.retain_mut(|event| match self.resolve(&event.affected_airport) {
+            Some(airport_id) => {
+                event.affected_airport = airport_id;
+                true
+            },
+            None => false,
+        });
+
+        Ok(ReconciledWorld {
+            adjustments,
+            removed,
+        })
+    }
+
+    /// Put a pilot somewhere that exists. Pilots at a renamed airport
+    /// follow it; pilots at a removed one go to the nearest airport left,
+    /// or the first by id when where they were isn't known.
+    pub fn place_player(
+        &self,
+        player: &mut Player,
+        player_name: &str,
+        airports: &HashMap<String, Airport>,
+        removed: &HashMap<String, Airport>,
+    ) -> Option<WorldAdjustment> {
+        let from = player.current_airport.clone();
+        if airports.contains_key(&from) {
+            return None;
+        }
+        if let Some(new_id) = self.resolve(&from).filter(|id| airports.contains_key(id)) {
+            player.current_airport = new_id;
+            return None;
+        }
+
+        let to = match removed.get(&from) {
+            Some(stranded_at) => airports.values().min_by(|a, b| {
+                stranded_at
+                    .distance_to(a)
+                    .total_cmp(&stranded_at.distance_to(b))
+                    .then_with(|| a.id.cmp(&b.id))
+            }),
+            None => airports.values().min_by(|a, b| a.id.cmp(&b.id)),
+        }?;
+        player.current_airport = to.id.clone();
+        Some(WorldAdjustment::Relocated {
+            player_name: player_name.to_string(),
+            from,
+            to: to.id.clone(),
+        })
+    }
+
+    /// An airport id list with renames followed and gone airports dropped
+    pub fn resolve_ids(&self, airport_ids: &mut Vec<String>) {
+        *airport_ids = airport_ids
+            .iter()
+            .filter_map(|airport_id| self.resolve(airport_id))
+            .collect();
+        let mut seen = std::collections::HashSet::new();
+        airport_ids.retain(|airport_id| seen.insert(airport_id.clone()));
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    models::{Notification, message_board::Message},
+    systems::{courier::CourierLetter, multiplayer::TurnRecord},
+};
+
+/// Default for how many notifications each player keeps
+pub const DEFAULT_MAX_FEED_LENGTH: usize = 100;
+
+/// Default for how many turns each player's journal keeps
+pub const DEFAULT_MAX_JOURNAL_TURNS: usize = 500;
+
+/// Default for how many delivered courier letters each sender keeps
+pub const DEFAULT_MAX_DELIVERED_LETTERS: usize = 20;
+
+/// How long each player's lists in a room may grow before the oldest
+/// entries move to the database archive. Board messages are kept to
+/// `BoardLimits::max_messages_per_airport` the same way.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(default)]
+pub struct RetentionLimits {
+    /// Notifications kept per player
+    pub max_feed_length: usize,
+    /// Turns kept in each player's replay journal
+    pub max_journal_turns: usize,
+    /// Delivered courier letters kept per sender. Letters still in the mail
+    /// always stay.
+    pub max_delivered_letters: usize,
+}
+
+impl Default for RetentionLimits {
+    fn default() -> Self {
+        Self {
+            max_feed_length: DEFAULT_MAX_FEED_LENGTH,
+            max_journal_turns: DEFAULT_MAX_JOURNAL_TURNS,
+            max_delivered_letters: DEFAULT_MAX_DELIVERED_LETTERS,
+        }
+    }
+}
+
+impl RetentionLimits {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_feed_length == 0 {
+            return Err("Players must keep at least one notification".to_string());
+        }
+        if self.max_journal_turns == 0 {
+            return Err("Journals must keep at least one turn".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Something pushed out of a room's memory, waiting to be archived
+#[derive(Debug, Clone)]
+pub enum ArchivedEntry {
+    Message(Message),
+    Notification {
+        player_id: Uuid,
+        notification: Notification,
+    },
+    Turn {
+        player_id: Uuid,
+        record: TurnRecord,
+    },
+    Letter(CourierLetter),
+}
+
+impl ArchivedEntry {
+    /// What kind of entry this is, as stored in the archive
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ArchivedEntry::Message(_) => "message",
+            ArchivedEntry::Notification { .. } => "notification",
+            ArchivedEntry::Turn { .. } => "turn",
+            ArchivedEntry::Letter(_) => "letter",
+        }
+    }
+
+    /// The airport a message was posted at, or the player a notification,
+    /// turn or letter belongs to
+    pub fn owner(&self) -> String {
+        match self {
+            ArchivedEntry::Message(message) => message.airport_id.clone(),
+            ArchivedEntry::Notification { player_id, .. }
+            | ArchivedEntry::Turn { player_id, .. } => player_id.to_string(),
+            ArchivedEntry::Letter(letter) => letter.sender_id.to_string(),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        match self {
+            ArchivedEntry::Message(message) => serde_json::to_string(message),
+            ArchivedEntry::Notification { notification, .. } => serde_json::to_string(notification),
+            ArchivedEntry::Turn { record, .. } => serde_json::to_string(record),
+            ArchivedEntry::Letter(letter) => serde_json::to_string(letter),
+        }
+    }
+}
+
+/// Drop the oldest of `entries` (kept oldest first) beyond `limit`,
+/// returning them
+pub fn evict_oldest<T>(entries: &mut Vec<T>, limit: usize) -> Vec<T> {
+    let excess = entries.len().saturating_sub(limit);
+    entries.drain(..excess).collect()
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    models::{Airport, CargoType, Player},
+    systems::{GameRoom, GameState, distance::DistanceCache},
+};
+
+/// The cargo prices a pilot knows at one airport
+#[derive(Debug, Clone)]
+pub struct KnownPrices {
+    pub airport_id: String,
+    pub cargo_prices: HashMap<String, u32>,
+    /// Seen too many turns ago to trust
+    pub stale: bool,
+}
+
+/// Buying one cargo at one airport and selling it at another, priced for a
+/// full hold
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RouteProfit {
+    pub cargo_type: String,
+    pub buy_at: String,
+    pub sell_at: String,
+    pub buy_price: u32,
+    pub sell_price: u32,
+    /// Units of the cargo a full hold carries
+    pub units: u32,
+    /// What selling a full hold makes over buying it, and may be negative
+    pub profit: i64,
+    pub fuel_needed: u32,
+    pub profit_per_fuel: f64,
+    /// Either price comes from a stale sighting
+    pub stale: bool,
+}
+
+pub struct RouteAnalytics;
+
+impl RouteAnalytics {
+    /// Every buy-here, sell-there pair the pilot has prices for, best profit
+    /// per unit of fuel first
+    pub fn rank(
+        player: &Player,
+        prices: &[KnownPrices],
+        airports: &HashMap<String, Airport>,
+        cargo_types: &HashMap<String, CargoType>,
+        distances: &DistanceCache,
+    ) -> Vec<RouteProfit> {
+        let mut routes = Vec::new();
+        for from in prices {
+            for to in prices {
+                if from.airport_id == to.airport_id {
+                    continue;
+                }
+                let Some(distance) =
+                    distances.between_ids(airports, &from.airport_id, &to.airport_id)
+                else {
+                    continue;
+                };
+                let fuel_needed = player.fuel_needed_for_distance(distance).max(1);
+
+                for (cargo_id, &buy_price) in &from.cargo_prices {
+                    let (Some(&sell_price), Some(cargo_type)) =
+                        (to.cargo_prices.get(cargo_id), cargo_types.get(cargo_id))
+                    else {
+                        continue;
+                    };
+                    let units = player.max_cargo_weight / cargo_type.weight_per_unit.max(1);
+                    let profit = (sell_price as i64 - buy_price as i64) * units as i64;
+                    routes.push(RouteProfit {
+                        cargo_type: cargo_id.clone(),
+                        buy_at: from.airport_id.clone(),
+                        sell_at: to.airport_id.clone(),
+                        buy_price,
+                        sell_price,
+                        units,
+                        profit,
+                        fuel_needed,
+                        profit_per_fuel: profit as f64 / fuel_needed as f64,
+                        stale: from.stale || to.stale,
+                    });
+                }
+            }
+        }
+
+        routes.sort_by(|a, b| {
+            b.profit_per_fuel
+                .total_cmp(&a.profit_per_fuel)
+                .then_with(|| a.buy_at.cmp(&b.buy_at))
+                .then_with(|| a.sell_at.cmp(&b.sell_at))
+                .then_with(|| a.cargo_type.cmp(&b.cargo_type))
+        });
+        routes
+    }
+
+    /// Routes a player in a room can price, from what they know of each
+    /// market. Under fog of war that's only airports they've seen.
+    pub fn for_room(room: &GameRoom, player_id: &Uuid) -> Option<Vec<RouteProfit>> {
+        let player = &room.players.get(player_id)?.player;
+        let mut airport_ids: Vec<&String> = room.shared_state.airports.keys().collect();
+        airport_ids.sort();
+        let prices: Vec<KnownPrices> = airport_ids
+            .into_iter()
+            .filter_map(|airport_id| {
+                let sighting = room.known_market(player_id, airport_id)?;
+                Some(KnownPrices {
+                    airport_id: airport_id.clone(),
+                    stale: room.is_stale(player_id, &sighting),
+                    cargo_prices: sighting.cargo_prices,
+                })
+            })
+            .collect();
+
+        Some(Self::rank(
+            player,
+            &prices,
+            &room.shared_state.airports,
+            &room.shared_state.cargo_types,
+            &room.shared_state.distances,
+        ))
+    }
+
+    /// Routes in a single-player game, where every market is on show
+    pub fn for_game(game_state: &GameState) -> Vec<RouteProfit> {
+        let mut prices: Vec<KnownPrices> = game_state
+            .markets
+            .iter()
+            .map(|(airport_id, market)| KnownPrices {
+                airport_id: airport_id.clone(),
+                cargo_prices: market.cargo_prices.clone(),
+                stale: false,
+            })
+            .collect();
+        prices.sort_by(|a, b| a.airport_id.cmp(&b.airport_id));
+
+        Self::rank(
+            &game_state.player,
+            &prices,
+            &game_state.airports,
+            &game_state.cargo_types,
+            &game_state.distances,
+        )
+    }
+}
+
+/// The best profit per unit of fuel from each airport (rows) to each other
+/// airport (columns), over every cargo, for drawing as a heatmap
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProfitMatrix {
+    /// Row and column order, by id
+    pub airports: Vec<String>,
+    /// None on the diagonal and where no cargo is priced at both ends
+    pub cells: Vec<Vec<Option<f64>>>,
+}
+
+impl ProfitMatrix {
+    pub fn from_routes(routes: &[RouteProfit]) -> Self {
+        let mut airports: Vec<String> = routes
+            .iter()
+            .flat_map(|route| [route.buy_at.clone(), route.sell_at.clone()])
+            .collect();
+        airports.sort();
+        airports.dedup();
+
+        let index: HashMap<&str, usize> = airports
+            .iter()
+            .enumerate()
+            .map(|(i, airport_id)| (airport_id.as_str(), i))
+            .collect();
+        let mut cells = vec![vec![None; airports.len()]; airports.len()];
+        for route in routes {
+            let cell: &mut Option<f64> =
+                &mut cells[index[route.buy_at.as_str()]][index[route.sell_at.as_str()]];
+            if cell.is_none_or(|best| route.profit_per_fuel > best) {
+                *cell = Some(route.profit_per_fuel);
+            }
+        }
+
+        Self { airports, cells }
+    }
+}
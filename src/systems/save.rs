@@ -1,10 +1,15 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 
-use crate::systems::GameState;
+use crate::data::airports::{AIRPORT_RENAMES, get_default_airports};
+use crate::systems::{
+    GameState, Profile, ProfileSettings,
+    reconcile::{AirportReconciler, WorldAdjustment},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveGame {
@@ -14,13 +19,22 @@ pub struct SaveGame {
     pub version: String,
 }
 
+/// A loaded game and what had to change for it to fit today's airports
+#[derive(Debug, Clone)]
+pub struct LoadedGame {
+    pub game_state: GameState,
+    pub adjustments: Vec<WorldAdjustment>,
+}
+
 #[derive(Debug)]
 pub enum SaveError {
     IoError(String),
     SerializationError(String),
-    #[allow(dead_code)]
     InvalidSaveFile,
     SaveNotFound,
+    InvalidProfileName,
+    ProfileExists(String),
+    IronmanManualSave,
 }
 
 impl std::fmt::Display for SaveError {
@@ -30,8 +44,147 @@ impl std::fmt::Display for SaveError {
             SaveError::SerializationError(e) => write!(f, "Serialization error: {}", e),
             SaveError::InvalidSaveFile => write!(f, "Invalid save file format"),
             SaveError::SaveNotFound => write!(f, "Save file not found"),
+            SaveError::InvalidProfileName => {
+                write!(f, "Profile names need at least one letter or digit")
+            },
+            SaveError::ProfileExists(name) => write!(f, "A profile named {} already exists", name),
+            SaveError::IronmanManualSave => {
+                write!(f, "Ironman games can't be saved manually; they autosave")
+            },
+        }
+    }
+}
+
+const AUTOSAVE_NAME: &str = "autosave";
+const PROFILES_DIR: &str = "profiles";
+const PROFILE_FILE: &str = "profile.json";
+
+/// When to autosave and how many autosaves to keep. Configurable through
+/// KZRK_AUTOSAVE_TURNS, KZRK_AUTOSAVE_MINUTES and KZRK_AUTOSAVE_KEEP; a
+/// value of 0 turns that trigger off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutosavePolicy {
+    pub every_turns: u32,
+    pub every_minutes: u64,
+    pub keep: usize,
+}
+
+impl Default for AutosavePolicy {
+    fn default() -> Self {
+        Self {
+            every_turns: 1,
+            every_minutes: 5,
+            keep: 3,
+        }
+    }
+}
+
+impl AutosavePolicy {
+    /// Ironman games save after every action and keep a single autosave,
+    /// so there is never an older state to fall back to.
+    pub fn ironman() -> Self {
+        Self {
+            every_turns: 1,
+            every_minutes: 0,
+            keep: 1,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok()?.trim().parse().ok()
+        }
+
+        let default = Self::default();
+        Self {
+            every_turns: var("KZRK_AUTOSAVE_TURNS").unwrap_or(default.every_turns),
+            every_minutes: var("KZRK_AUTOSAVE_MINUTES").unwrap_or(default.every_minutes),
+            keep: var::<usize>("KZRK_AUTOSAVE_KEEP")
+                .unwrap_or(default.keep)
+                .max(1),
+        }
+    }
+}
+
+/// Tracks when the last autosave happened so the game loop can ask whether
+/// another one is due.
+#[derive(Debug, Clone)]
+pub struct AutosaveTimer {
+    pub policy: AutosavePolicy,
+    save_dir: Option<PathBuf>, // None means the default save directory
+    last_turn: u32,
+    last_saved_at: Instant,
+    // The file this game was last autosaved to
+    last_save: Option<PathBuf>,
+}
+
+impl AutosaveTimer {
+    pub fn new(policy: AutosavePolicy, current_turn: u32) -> Self {
+        Self {
+            policy,
+            save_dir: None,
+            last_turn: current_turn,
+            last_saved_at: Instant::now(),
+            last_save: None,
         }
     }
+
+    /// Autosave into a profile's directory instead of the default one
+    pub fn with_save_dir(mut self, save_dir: PathBuf) -> Self {
+        self.save_dir = Some(save_dir);
+        self
+    }
+
+    /// Count `path` as this game's latest autosave, for a game resumed from it
+    pub fn resumed_from(mut self, path: PathBuf) -> Self {
+        self.last_save = Some(path);
+        self
+    }
+
+    /// The file this game was last autosaved to, if it has been
+    pub fn last_save(&self) -> Option<&Path> {
+        self.last_save.as_deref()
+    }
+
+    /// Whether enough turns or enough time have passed since the last save
+    pub fn is_due(&self, current_turn: u32, now: Instant) -> bool {
+        let turns_due =
+            self.policy.every_turns > 0 && current_turn >= self.last_turn + self.policy.every_turns;
+        let time_due = self.policy.every_minutes > 0
+            && now.duration_since(self.last_saved_at)
+                >= Duration::from_secs(self.policy.every_minutes * 60);
+
+        turns_due || time_due
+    }
+
+    pub fn mark_saved(&mut self, current_turn: u32, now: Instant) {
+        self.last_turn = current_turn;
+        self.last_saved_at = now;
+    }
+
+    /// Autosave if one is due. Returns the result of the save, or None when
+    /// it wasn't time yet.
+    pub fn tick(&mut self, game_state: &GameState) -> Option<Result<PathBuf, SaveError>> {
+        if !self.is_due(game_state.turn_number, Instant::now()) {
+            return None;
+        }
+
+        Some(self.save_now(game_state))
+    }
+
+    /// Autosave regardless of the policy's triggers
+    pub fn save_now(&mut self, game_state: &GameState) -> Result<PathBuf, SaveError> {
+        let now = Instant::now();
+        let result = match &self.save_dir {
+            Some(save_dir) => SaveSystem::autosave_to_dir(game_state, self.policy.keep, save_dir),
+            None => SaveSystem::autosave(game_state, self.policy.keep),
+        };
+        if let Ok(path) = &result {
+            self.mark_saved(game_state.turn_number, now);
+            self.last_save = Some(path.clone());
+        }
+        result
+    }
 }
 
 pub struct SaveSystem;
@@ -84,37 +237,16 @@ impl SaveSystem {
     }
 
     /// Save the game state to a file
+    #[allow(dead_code)]
     pub fn save_game(
         game_state: &GameState,
         save_name: Option<String>,
     ) -> Result<PathBuf, SaveError> {
-        let save_dir = Self::get_save_directory()?;
-
-        // Generate save name if not provided
-        let save_name =
-            save_name.unwrap_or_else(|| format!("save_{}", Local::now().format("%Y%m%d_%H%M%S")));
-
-        let save_file = SaveGame {
-            game_state: game_state.clone(),
-            save_name: save_name.clone(),
-            timestamp: Local::now(),
-            version: env!("CARGO_PKG_VERSION").to_string(),
-        };
-
-        let file_path = save_dir.join(format!("{}.json", save_name));
-
-        // Serialize to JSON
-        let json = serde_json::to_string_pretty(&save_file)
-            .map_err(|e| SaveError::SerializationError(e.to_string()))?;
-
-        // Write to file
-        fs::write(&file_path, json)
-            .map_err(|e| SaveError::IoError(format!("Failed to write save file: {}", e)))?;
-
-        Ok(file_path)
+        Self::save_game_to_dir(game_state, save_name, &Self::get_save_directory()?)
     }
 
     /// Load a game state from a file
+    #[allow(dead_code)]
     pub fn load_game(save_name: &str) -> Result<GameState, SaveError> {
         let save_dir = Self::get_save_directory()?;
         let file_path = save_dir.join(format!("{}.json", save_name));
@@ -123,15 +255,7 @@ impl SaveSystem {
             return Err(SaveError::SaveNotFound);
         }
 
-        // Read file
-        let json = fs::read_to_string(&file_path)
-            .map_err(|e| SaveError::IoError(format!("Failed to read save file: {}", e)))?;
-
-        // Deserialize from JSON
-        let save_file: SaveGame = serde_json::from_str(&json)
-            .map_err(|e| SaveError::SerializationError(e.to_string()))?;
-
-        Ok(save_file.game_state)
+        Self::read_save(&file_path).map(|loaded| loaded.game_state)
     }
 
     /// Load a game from a specific path
@@ -141,18 +265,43 @@ impl SaveSystem {
             return Err(SaveError::SaveNotFound);
         }
 
-        // Read file
+        Self::read_save(path).map(|loaded| loaded.game_state)
+    }
+
+    /// Load a game from a directory, reporting any airports that had to be
+    /// renamed or removed since it was saved
+    pub fn load_game_from_dir_reporting(
+        save_name: &str,
+        save_dir: &Path,
+    ) -> Result<LoadedGame, SaveError> {
+        let file_path = save_dir.join(format!("{}.json", save_name));
+        if !file_path.exists() {
+            return Err(SaveError::SaveNotFound);
+        }
+        Self::read_save(&file_path)
+    }
+
+    /// Read and deserialize a save, then fit it to the airports that exist
+    /// now
+    fn read_save(path: &Path) -> Result<LoadedGame, SaveError> {
         let json = fs::read_to_string(path)
             .map_err(|e| SaveError::IoError(format!("Failed to read save file: {}", e)))?;
-
-        // Deserialize from JSON
         let save_file: SaveGame = serde_json::from_str(&json)
             .map_err(|e| SaveError::SerializationError(e.to_string()))?;
 
-        Ok(save_file.game_state)
+        let mut game_state = save_file.game_state;
+        let registry = get_default_airports();
+        let adjustments = game_state
+            .reconcile_airports(&AirportReconciler::new(&registry, AIRPORT_RENAMES))
+            .map_err(|_| SaveError::InvalidSaveFile)?;
+        Ok(LoadedGame {
+            game_state,
+            adjustments,
+        })
     }
 
     /// List all available save files
+    #[allow(dead_code)]
     pub fn list_saves() -> Result<Vec<SaveInfo>, SaveError> {
         let save_dir = Self::get_save_directory()?;
         let mut saves = Vec::new();
@@ -175,6 +324,7 @@ impl SaveSystem {
                         turn: save_file.game_state.turn_number,
                         money: save_file.game_state.player.money,
                         location: save_file.game_state.player.current_airport.clone(),
+                        ironman: save_file.game_state.stats.ironman,
                         file_name: path
                             .file_stem()
                             .and_then(|s| s.to_str())
@@ -186,7 +336,7 @@ impl SaveSystem {
         }
 
         // Sort by timestamp, newest first
-        saves.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        saves.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
 
         Ok(saves)
     }
@@ -207,23 +357,210 @@ impl SaveSystem {
         Ok(())
     }
 
-    /// Create an autosave
-    pub fn autosave(game_state: &GameState) -> Result<PathBuf, SaveError> {
-        Self::save_game(game_state, Some("autosave".to_string()))
+    /// Create an autosave, keeping the previous `keep - 1` autosaves as
+    /// autosave_1, autosave_2, ... (newest first)
+    pub fn autosave(game_state: &GameState, keep: usize) -> Result<PathBuf, SaveError> {
+        let save_dir = Self::get_save_directory()?;
+        Self::autosave_to_dir(game_state, keep, &save_dir)
+    }
+
+    #[allow(dead_code)]
+    pub fn autosave_to_dir(
+        game_state: &GameState,
+        keep: usize,
+        save_dir: &Path,
+    ) -> Result<PathBuf, SaveError> {
+        Self::rotate_autosaves(save_dir, keep)?;
+        Self::write_save_file(game_state, Some(AUTOSAVE_NAME.to_string()), save_dir)
+    }
+
+    /// Remove one save file by its path. Ironman games call this on their
+    /// own autosave when the pilot goes bankrupt, so there is nothing left to
+    /// reload; other games' saves in the same directory are left alone.
+    pub fn delete_save_file(path: &Path) -> Result<(), SaveError> {
+        if !path.exists() {
+            return Err(SaveError::SaveNotFound);
+        }
+
+        fs::remove_file(path)
+            .map_err(|e| SaveError::IoError(format!("Failed to delete save file: {}", e)))
+    }
+
+    fn autosave_path(save_dir: &Path, generation: usize) -> PathBuf {
+        if generation == 0 {
+            save_dir.join(format!("{}.json", AUTOSAVE_NAME))
+        } else {
+            save_dir.join(format!("{}_{}.json", AUTOSAVE_NAME, generation))
+        }
+    }
+
+    /// Shift every autosave back one generation; the oldest one we're not
+    /// keeping falls off the end
+    fn rotate_autosaves(save_dir: &Path, keep: usize) -> Result<(), SaveError> {
+        if keep <= 1 {
+            return Ok(());
+        }
+
+        let oldest = Self::autosave_path(save_dir, keep - 1);
+        if oldest.exists() {
+            fs::remove_file(&oldest)
+                .map_err(|e| SaveError::IoError(format!("Failed to remove old autosave: {}", e)))?;
+        }
+
+        for generation in (0..keep - 1).rev() {
+            let from = Self::autosave_path(save_dir, generation);
+            if from.exists() {
+                fs::rename(&from, Self::autosave_path(save_dir, generation + 1))
+                    .map_err(|e| SaveError::IoError(format!("Failed to rotate autosave: {}", e)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The most recent autosave in `save_dir`, if it was written after
+    /// `than`. Used to offer recovery when someone loads an older manual
+    /// save.
+    pub fn newer_autosave_in_dir(than: DateTime<Local>, save_dir: &Path) -> Option<SaveInfo> {
+        Self::list_saves_in_dir(save_dir)
+            .ok()?
+            .into_iter()
+            .filter(|save| save.is_autosave())
+            .find(|save| save.timestamp > than)
     }
 
     /// Check if an autosave exists
+    #[allow(dead_code)]
     pub fn has_autosave() -> bool {
         if let Ok(save_dir) = Self::get_save_directory() {
-            save_dir.join("autosave.json").exists()
+            Self::has_autosave_in_dir(&save_dir)
         } else {
             false
         }
     }
 
+    pub fn has_autosave_in_dir(save_dir: &Path) -> bool {
+        Self::autosave_path_in_dir(save_dir).exists()
+    }
+
+    /// Where the newest autosave in a directory is kept
+    pub fn autosave_path_in_dir(save_dir: &Path) -> PathBuf {
+        Self::autosave_path(save_dir, 0)
+    }
+
     /// Load the autosave
+    #[allow(dead_code)]
     pub fn load_autosave() -> Result<GameState, SaveError> {
-        Self::load_game("autosave")
+        Self::load_game(AUTOSAVE_NAME)
+    }
+
+    /// Directory every profile lives under
+    pub fn profiles_directory() -> Result<PathBuf, SaveError> {
+        Ok(Self::get_save_directory()?.join(PROFILES_DIR))
+    }
+
+    /// A profile's own directory; its saves and autosaves go here too
+    pub fn profile_directory(name: &str) -> Result<PathBuf, SaveError> {
+        Self::profile_directory_in(name, &Self::profiles_directory()?)
+    }
+
+    pub fn profile_directory_in(name: &str, profiles_dir: &Path) -> Result<PathBuf, SaveError> {
+        let slug = Profile::slug(name);
+        if slug.is_empty() {
+            return Err(SaveError::InvalidProfileName);
+        }
+        Ok(profiles_dir.join(slug))
+    }
+
+    pub fn create_profile(name: &str, settings: ProfileSettings) -> Result<Profile, SaveError> {
+        Self::create_profile_in_dir(name, settings, &Self::profiles_directory()?)
+    }
+
+    pub fn create_profile_in_dir(
+        name: &str,
+        settings: ProfileSettings,
+        profiles_dir: &Path,
+    ) -> Result<Profile, SaveError> {
+        let profile_dir = Self::profile_directory_in(name, profiles_dir)?;
+        if profile_dir.join(PROFILE_FILE).exists() {
+            return Err(SaveError::ProfileExists(name.trim().to_string()));
+        }
+
+        let profile = Profile::new(name.trim().to_string(), settings);
+        Self::save_profile_in_dir(&profile, profiles_dir)?;
+        Ok(profile)
+    }
+
+    pub fn save_profile(profile: &Profile) -> Result<(), SaveError> {
+        Self::save_profile_in_dir(profile, &Self::profiles_directory()?)
+    }
+
+    pub fn save_profile_in_dir(profile: &Profile, profiles_dir: &Path) -> Result<(), SaveError> {
+        let profile_dir = Self::profile_directory_in(&profile.name, profiles_dir)?;
+        fs::create_dir_all(&profile_dir).map_err(|e| {
+            SaveError::IoError(format!("Failed to create profile directory: {}", e))
+        })?;
+
+        let json = serde_json::to_string_pretty(profile)
+            .map_err(|e| SaveError::SerializationError(e.to_string()))?;
+        fs::write(profile_dir.join(PROFILE_FILE), json)
+            .map_err(|e| SaveError::IoError(format!("Failed to write profile: {}", e)))
+    }
+
+    pub fn load_profile_in_dir(name: &str, profiles_dir: &Path) -> Result<Profile, SaveError> {
+        let path = Self::profile_directory_in(name, profiles_dir)?.join(PROFILE_FILE);
+        if !path.exists() {
+            return Err(SaveError::SaveNotFound);
+        }
+
+        let json = fs::read_to_string(&path)
+            .map_err(|e| SaveError::IoError(format!("Failed to read profile: {}", e)))?;
+        serde_json::from_str(&json).map_err(|e| SaveError::SerializationError(e.to_string()))
+    }
+
+    /// All profiles, most recently played first
+    pub fn list_profiles() -> Result<Vec<Profile>, SaveError> {
+        Self::list_profiles_in_dir(&Self::profiles_directory()?)
+    }
+
+    pub fn list_profiles_in_dir(profiles_dir: &Path) -> Result<Vec<Profile>, SaveError> {
+        if !profiles_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = fs::read_dir(profiles_dir)
+            .map_err(|e| SaveError::IoError(format!("Failed to read profiles: {}", e)))?;
+
+        let mut profiles = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| SaveError::IoError(e.to_string()))?;
+            // Profile directories are named by slug, which slugs to itself
+            if let Some(slug) = entry.file_name().to_str()
+                && let Ok(profile) = Self::load_profile_in_dir(slug, profiles_dir)
+            {
+                profiles.push(profile);
+            }
+        }
+
+        profiles.sort_by_key(|p| std::cmp::Reverse(p.last_played.unwrap_or(p.created_at)));
+        Ok(profiles)
+    }
+
+    /// Remove a profile along with all of its saves
+    #[allow(dead_code)]
+    pub fn delete_profile(name: &str) -> Result<(), SaveError> {
+        Self::delete_profile_in_dir(name, &Self::profiles_directory()?)
+    }
+
+    #[allow(dead_code)]
+    pub fn delete_profile_in_dir(name: &str, profiles_dir: &Path) -> Result<(), SaveError> {
+        let profile_dir = Self::profile_directory_in(name, profiles_dir)?;
+        if !profile_dir.join(PROFILE_FILE).exists() {
+            return Err(SaveError::SaveNotFound);
+        }
+
+        fs::remove_dir_all(&profile_dir)
+            .map_err(|e| SaveError::IoError(format!("Failed to delete profile: {}", e)))
     }
 
     // Test-specific methods that accept custom directories
@@ -232,6 +569,18 @@ impl SaveSystem {
         game_state: &GameState,
         save_name: Option<String>,
         save_dir: &Path,
+    ) -> Result<PathBuf, SaveError> {
+        if game_state.stats.ironman {
+            return Err(SaveError::IronmanManualSave);
+        }
+
+        Self::write_save_file(game_state, save_name, save_dir)
+    }
+
+    fn write_save_file(
+        game_state: &GameState,
+        save_name: Option<String>,
+        save_dir: &Path,
     ) -> Result<PathBuf, SaveError> {
         // Create directory if it doesn't exist
         fs::create_dir_all(save_dir)
@@ -269,15 +618,7 @@ impl SaveSystem {
             return Err(SaveError::SaveNotFound);
         }
 
-        // Read file
-        let json = fs::read_to_string(&file_path)
-            .map_err(|e| SaveError::IoError(format!("Failed to read save file: {}", e)))?;
-
-        // Deserialize from JSON
-        let save_file: SaveGame = serde_json::from_str(&json)
-            .map_err(|e| SaveError::SerializationError(e.to_string()))?;
-
-        Ok(save_file.game_state)
+        Self::read_save(&file_path).map(|loaded| loaded.game_state)
     }
 
     #[allow(dead_code)]
@@ -302,6 +643,7 @@ impl SaveSystem {
                         turn: save_file.game_state.turn_number,
                         money: save_file.game_state.player.money,
                         location: save_file.game_state.player.current_airport.clone(),
+                        ironman: save_file.game_state.stats.ironman,
                         file_name: path
                             .file_stem()
                             .and_then(|s| s.to_str())
@@ -313,7 +655,7 @@ impl SaveSystem {
         }
 
         // Sort by timestamp, newest first
-        saves.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        saves.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
 
         Ok(saves)
     }
@@ -327,4 +669,17 @@ pub struct SaveInfo {
     pub money: u32,
     pub location: String,
     pub file_name: String,
+    #[serde(default)]
+    pub ironman: bool,
+}
+
+impl SaveInfo {
+    pub fn is_autosave(&self) -> bool {
+        self.file_name == AUTOSAVE_NAME
+            || self
+                .file_name
+                .strip_prefix(AUTOSAVE_NAME)
+                .and_then(|rest| rest.strip_prefix('_'))
+                .is_some_and(|generation| generation.parse::<usize>().is_ok())
+    }
 }
@@ -0,0 +1,325 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    config::GameConfig,
+    data::{airports::get_default_airports, cargo_types::get_default_cargo_types},
+    systems::GameState,
+};
+
+fn default_airport() -> String {
+    "JFK".to_string()
+}
+
+fn default_money() -> u32 {
+    5000
+}
+
+/// A scripted game: actions to take in order, each with what should
+/// follow. Loaded from JSON, so a regression can be kept as a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_airport")]
+    pub starting_airport: String,
+    #[serde(default = "default_money")]
+    pub starting_money: u32,
+    pub steps: Vec<ScenarioStep>,
+}
+
+impl Scenario {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid scenario: {}", e))
+    }
+
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_json(&json).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioStep {
+    pub action: ScenarioAction,
+    #[serde(default)]
+    pub expect: Expectation,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScenarioAction {
+    Buy {
+        cargo: String,
+        quantity: u32,
+    },
+    Sell {
+        cargo: String,
+        quantity: u32,
+    },
+    BuyFuel(u32),
+    Fly(String),
+    PostMessage(String),
+    /// Change nothing; just check the expectations
+    Check,
+}
+
+/// What should be true after a step. Anything left out isn't checked.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expectation {
+    /// The action should be refused
+    #[serde(default)]
+    pub fails: bool,
+    /// Part of the reason it was refused
+    pub error_contains: Option<String>,
+    pub location: Option<String>,
+    pub turn: Option<u32>,
+    pub fuel: Option<u32>,
+    pub money_at_least: Option<u32>,
+    pub money_at_most: Option<u32>,
+    /// Units held of each cargo listed
+    #[serde(default)]
+    pub cargo: BTreeMap<String, u32>,
+    /// The board at the player's airport, newest first
+    pub messages: Option<Vec<String>>,
+}
+
+/// How an action went, when the target could be asked at all
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Done,
+    Refused(String),
+}
+
+/// The player as a target sees them after a step
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    pub location: String,
+    pub turn: u32,
+    pub fuel: u32,
+    pub money: u32,
+    pub cargo: HashMap<String, u32>,
+    /// Board at `location`, newest first
+    pub messages: Vec<String>,
+}
+
+/// Somewhere a scenario can be played. Err is for the target itself
+/// failing, not an action being refused.
+pub trait ScenarioTarget {
+    fn start(&mut self, scenario: &Scenario) -> Result<(), String>;
+
+    fn perform(&mut self, action: &ScenarioAction) -> Result<Outcome, String>;
+
+    fn snapshot(&mut self) -> Result<Snapshot, String>;
+}
+
+/// The first step that didn't go as expected
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepFailure {
+    /// Counted from 1
+    pub step: usize,
+    pub action: ScenarioAction,
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioReport {
+    pub name: String,
+    pub steps_run: usize,
+    pub failure: Option<StepFailure>,
+}
+
+impl ScenarioReport {
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Play `scenario` against `target`, stopping at the first step that
+/// doesn't meet its expectations
+pub fn run(scenario: &Scenario, target: &mut dyn ScenarioTarget) -> Result<ScenarioReport, String> {
+    target.start(scenario)?;
+
+    for (index, step) in scenario.steps.iter().enumerate() {
+        let outcome = target.perform(&step.action)?;
+        let snapshot = target.snapshot()?;
+        let problems = check(&step.expect, &outcome, &snapshot);
+        if !problems.is_empty() {
+            return Ok(ScenarioReport {
+                name: scenario.name.clone(),
+                steps_run: index + 1,
+                failure: Some(StepFailure {
+                    step: index + 1,
+                    action: step.action.clone(),
+                    problems,
+                }),
+            });
+        }
+    }
+
+    Ok(ScenarioReport {
+        name: scenario.name.clone(),
+        steps_run: scenario.steps.len(),
+        failure: None,
+    })
+}
+
+/// Everything about a step that didn't match what was expected
+pub fn check(expect: &Expectation, outcome: &Outcome, snapshot: &Snapshot) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    match (outcome, expect.fails) {
+        (Outcome::Done, true) => problems.push("expected the action to be refused".to_string()),
+        (Outcome::Refused(reason), false) => problems.push(format!("refused: {}", reason)),
+        (Outcome::Refused(reason), true) => {
+            if let Some(part) = &expect.error_contains
+                && !reason.contains(part.as_str())
+            {
+                problems.push(format!("refused with '{}', not '{}'", reason, part));
+            }
+        },
+        (Outcome::Done, false) => {},
+    }
+
+    if let Some(location) = &expect.location
+        && &snapshot.location != location
+    {
+        problems.push(format!("at {}, not {}", snapshot.location, location));
+    }
+    if let Some(turn) = expect.turn
+        && snapshot.turn != turn
+    {
+        problems.push(format!("on turn {}, not {}", snapshot.turn, turn));
+    }
+    if let Some(fuel) = expect.fuel
+        && snapshot.fuel != fuel
+    {
+        problems.push(format!("{} fuel, not {}", snapshot.fuel, fuel));
+    }
+    if let Some(least) = expect.money_at_least
+        && snapshot.money < least
+    {
+        problems.push(format!("${}, under ${}", snapshot.money, least));
+    }
+    if let Some(most) = expect.money_at_most
+        && snapshot.money > most
+    {
+        problems.push(format!("${}, over ${}", snapshot.money, most));
+    }
+    for (cargo, quantity) in &expect.cargo {
+        let held = snapshot.cargo.get(cargo).copied().unwrap_or(0);
+        if held != *quantity {
+            problems.push(format!("{} {}, not {}", held, cargo, quantity));
+        }
+    }
+    if let Some(messages) = &expect.messages
+        && &snapshot.messages != messages
+    {
+        problems.push(format!(
+            "board reads {:?}, not {:?}",
+            snapshot.messages, messages
+        ));
+    }
+
+    problems
+}
+
+/// Plays scenarios on a GameState in this process, the way the terminal
+/// game does
+#[derive(Default)]
+pub struct LocalTarget {
+    game_state: Option<GameState>,
+    player_id: Uuid,
+}
+
+impl LocalTarget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn game_state(&mut self) -> Result<&mut GameState, String> {
+        self.game_state
+            .as_mut()
+            .ok_or_else(|| "Scenario hasn't started".to_string())
+    }
+}
+
+impl ScenarioTarget for LocalTarget {
+    fn start(&mut self, scenario: &Scenario) -> Result<(), String> {
+        let airports = get_default_airports();
+        if !airports.contains_key(&scenario.starting_airport) {
+            return Err(format!("Unknown airport {}", scenario.starting_airport));
+        }
+
+        let config = GameConfig {
+            starting_money: scenario.starting_money,
+            starting_airport: scenario.starting_airport.clone(),
+            ..GameConfig::default()
+        };
+        let mut game_state =
+            GameState::new_with_config(airports, get_default_cargo_types(), config);
+        game_state.cheat_mode = false;
+        self.game_state = Some(game_state);
+        self.player_id = Uuid::new_v4();
+        Ok(())
+    }
+
+    fn perform(&mut self, action: &ScenarioAction) -> Result<Outcome, String> {
+        let player_id = self.player_id;
+        let game_state = self.game_state()?;
+        let result = match action {
+            ScenarioAction::Buy { cargo, quantity } => game_state
+                .buy_cargo(cargo, *quantity)
+                .map(|_| ())
+                .map_err(|e| format!("{:?}", e)),
+            ScenarioAction::Sell { cargo, quantity } => game_state
+                .sell_cargo(cargo, *quantity)
+                .map(|_| ())
+                .map_err(|e| format!("{:?}", e)),
+            ScenarioAction::BuyFuel(quantity) => game_state
+                .buy_fuel(*quantity)
+                .map(|_| ())
+                .map_err(|e| format!("{:?}", e)),
+            ScenarioAction::Fly(destination) => game_state
+                .fly_to(destination)
+                .map(|_| ())
+                .map_err(|e| format!("{:?}", e)),
+            ScenarioAction::PostMessage(content) => {
+                let airport_id = game_state.player.current_airport.clone();
+                game_state
+                    .message_board
+                    .post_message(player_id, "Pilot".to_string(), content.clone(), airport_id)
+                    .map(|_| ())
+            },
+            ScenarioAction::Check => Ok(()),
+        };
+
+        Ok(match result {
+            Ok(()) => Outcome::Done,
+            Err(reason) => Outcome::Refused(reason),
+        })
+    }
+
+    fn snapshot(&mut self) -> Result<Snapshot, String> {
+        let game_state = self.game_state()?;
+        let player = &game_state.player;
+        Ok(Snapshot {
+            location: player.current_airport.clone(),
+            turn: game_state.turn_number,
+            fuel: player.fuel,
+            money: player.money,
+            cargo: player.cargo_inventory.get_all_cargo().clone(),
+            messages: game_state
+                .message_board
+                .get_messages(&player.current_airport, None)
+                .into_iter()
+                .map(|message| message.content.clone())
+                .collect(),
+        })
+    }
+}
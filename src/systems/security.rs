@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Airport, CargoType, Player};
+
+/// What a night in a hangar costs, wherever it is
+pub const HANGAR_FEE: u32 = 120;
+
+/// One-off price of locks, alarms and a tracker for the aircraft
+pub const SECURITY_UPGRADE_COST: u32 = 3000;
+
+/// How much of the usual risk an upgraded aircraft still runs
+pub const UPGRADE_RISK_FACTOR: f32 = 0.25;
+
+/// What was taken from an aircraft parked outside overnight
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Stolen {
+    Fuel { quantity: u32 },
+    Cargo { cargo_type: String, quantity: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OvernightLoss {
+    pub airport_id: String,
+    pub stolen: Stolen,
+    pub description: String,
+}
+
+/// How the aircraft spent the night at the end of a turn
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum Night {
+    /// Parked outside and nothing happened
+    #[default]
+    Quiet,
+    /// Locked in a hangar for the fee
+    Hangar {
+        fee: u32,
+    },
+    Robbed(OvernightLoss),
+}
+
+impl Night {
+    /// A line for the player, or None when there's nothing worth saying
+    pub fn report(&self) -> Option<String> {
+        match self {
+            Night::Quiet => None,
+            Night::Hangar { fee } => Some(format!("Hangared overnight for ${}", fee)),
+            Night::Robbed(loss) => Some(loss.description.clone()),
+        }
+    }
+}
+
+pub struct SecuritySystem;
+
+impl SecuritySystem {
+    /// Chance this player's aircraft is robbed parked outside at `airport`
+    pub fn theft_chance(player: &Player, airport: &Airport) -> f32 {
+        let chance = airport.security.theft_chance();
+        if player.security_upgrade {
+            chance * UPGRADE_RISK_FACTOR
+        } else {
+            chance
+        }
+    }
+
+    pub fn buy_security_upgrade(player: &mut Player) -> Result<u32, String> {
+        if player.security_upgrade {
+            return Err("Your aircraft already has the security upgrade".to_string());
+        }
+        if !player.spend_money(SECURITY_UPGRADE_COST) {
+            return Err(format!(
+                "The security upgrade costs ${}",
+                SECURITY_UPGRADE_COST
+            ));
+        }
+        player.security_upgrade = true;
+        Ok(SECURITY_UPGRADE_COST)
+    }
+
+    /// The night at the end of a turn, parked at the player's current
+    /// airport. Pilots who asked for hangar parking get it when they can
+    /// pay; everyone else takes their chances outside.
+    pub fn spend_night(
+        player: &mut Player,
+        airport: &Airport,
+        cargo_types: &HashMap<String, CargoType>,
+        rng: &mut impl Rng,
+    ) -> Night {
+        if player.hangar_parking && player.spend_money(HANGAR_FEE) {
+            return Night::Hangar { fee: HANGAR_FEE };
+        }
+        if rng.gen_range(0.0..1.0) >= Self::theft_chance(player, airport) {
+            return Night::Quiet;
+        }
+
+        let mut carried: Vec<(&String, &u32)> = player
+            .cargo_inventory
+            .get_all_cargo()
+            .iter()
+            .filter(|(_, quantity)| **quantity > 0)
+            .collect();
+        carried.sort();
+
+        // Thieves go for the hold when there's something in it, half the time
+        let stolen = if !carried.is_empty() && rng.gen_bool(0.5) {
+            let (cargo_id, carried_quantity) = carried[rng.gen_range(0..carried.len())];
+            Stolen::Cargo {
+                cargo_type: cargo_id.clone(),
+                quantity: rng.gen_range(1..=(*carried_quantity / 4).max(1)),
+            }
+        } else if player.fuel > 0 {
+            let percent = rng.gen_range(10..=25);
+            Stolen::Fuel {
+                quantity: (player.fuel * percent / 100).max(1),
+            }
+        } else {
+            return Night::Quiet;
+        };
+
+        let description = match &stolen {
+            Stolen::Fuel { quantity } => {
+                player.fuel -= quantity;
+                format!(
+                    "Thieves siphoned {} fuel from your aircraft overnight at {}",
+                    quantity, airport.name
+                )
+            },
+            Stolen::Cargo {
+                cargo_type,
+                quantity,
+            } => {
+                player.cargo_inventory.remove_cargo(cargo_type, *quantity);
+                let cargo_name = cargo_types
+                    .get(cargo_type)
+                    .map(|cargo| cargo.name.as_str())
+                    .unwrap_or(cargo_type);
+                format!(
+                    "{} units of {} were stolen from your hold overnight at {}",
+                    quantity, cargo_name, airport.name
+                )
+            },
+        };
+
+        Night::Robbed(OvernightLoss {
+            airport_id: airport.id.clone(),
+            stolen,
+            description,
+        })
+    }
+}
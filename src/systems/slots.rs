@@ -0,0 +1,108 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::Airport;
+
+/// World ticks in each slot period. Every turn anyone takes is a tick, so a
+/// period is a handful of moves around the room.
+pub const SLOT_PERIOD_TICKS: u32 = 4;
+
+/// Fuel burned circling a hub for a period while waiting for a slot
+pub const HOLDING_FUEL: u32 = 10;
+
+/// What a flight arriving at an airport right now would have to do
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SlotStatus {
+    /// The airport doesn't restrict landings
+    #[default]
+    Unrestricted,
+    /// Slots left this period
+    Open { free: u32 },
+    /// Full this period, with a slot free in the next: arrivals hold for a
+    /// turn and then land
+    Holding,
+    /// Full this period and the next; arrivals have to divert elsewhere
+    Full,
+}
+
+impl SlotStatus {
+    pub fn can_land(&self) -> bool {
+        !matches!(self, SlotStatus::Full)
+    }
+
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub fn label(&self) -> String {
+        match self {
+            SlotStatus::Unrestricted => "No slot restrictions".to_string(),
+            SlotStatus::Open { free: 1 } => "1 landing slot left".to_string(),
+            SlotStatus::Open { free } => format!("{} landing slots left", free),
+            SlotStatus::Holding => "Slots full: expect to hold for a turn".to_string(),
+            SlotStatus::Full => "Slots full: divert elsewhere".to_string(),
+        }
+    }
+}
+
+/// Landings booked at one airport, by slot period
+type PeriodBookings = BTreeMap<u32, u32>;
+
+/// Landings booked at slot-restricted airports, per slot period
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SlotBook {
+    landings: HashMap<String, PeriodBookings>,
+}
+
+impl SlotBook {
+    pub fn period(world_tick: u32) -> u32 {
+        world_tick / SLOT_PERIOD_TICKS
+    }
+
+    fn booked(&self, airport_id: &str, period: u32) -> u32 {
+        self.landings
+            .get(airport_id)
+            .and_then(|periods| periods.get(&period))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn status(&self, airport: &Airport, world_tick: u32) -> SlotStatus {
+        let Some(slots) = airport.landing_slots else {
+            return SlotStatus::Unrestricted;
+        };
+
+        let period = Self::period(world_tick);
+        let free = slots.saturating_sub(self.booked(&airport.id, period));
+        if free > 0 {
+            SlotStatus::Open { free }
+        } else if self.booked(&airport.id, period + 1) < slots {
+            SlotStatus::Holding
+        } else {
+            SlotStatus::Full
+        }
+    }
+
+    /// Take a slot at `world_tick`'s period. Holding flights take theirs in
+    /// the next one.
+    pub fn book(&mut self, airport: &Airport, world_tick: u32, holding: bool) {
+        if airport.landing_slots.is_none() {
+            return;
+        }
+
+        let period = Self::period(world_tick) + u32::from(holding);
+        *self
+            .landings
+            .entry(airport.id.clone())
+            .or_default()
+            .entry(period)
+            .or_default() += 1;
+    }
+
+    /// Forget periods that are over
+    pub fn prune(&mut self, world_tick: u32) {
+        let current = Self::period(world_tick);
+        for periods in self.landings.values_mut() {
+            periods.retain(|period, _| *period >= current);
+        }
+        self.landings.retain(|_, periods| !periods.is_empty());
+    }
+}
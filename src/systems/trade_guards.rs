@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Widest gap a room may put between buying and selling prices
+pub const MAX_SELL_SPREAD_PERCENT: u32 = 50;
+
+/// Longest a room may make players wait to sell cargo back where they
+/// bought it
+pub const MAX_RESALE_COOLDOWN_TURNS: u32 = 20;
+
+/// A room's guards against buying and selling the same cargo back and
+/// forth at one airport for money the market never meant to pay. Both are
+/// off unless the host turns them on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TradeGuards {
+    /// Sellers get this much less than the listed price, in percent
+    #[serde(default)]
+    pub sell_spread_percent: u32,
+    /// Cargo bought at an airport can't be sold back there until this many
+    /// of the buyer's turns have passed; 0 turns the guard off
+    #[serde(default)]
+    pub resale_cooldown_turns: u32,
+}
+
+impl TradeGuards {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sell_spread_percent > MAX_SELL_SPREAD_PERCENT {
+            return Err(format!(
+                "The sell spread can be at most {}%",
+                MAX_SELL_SPREAD_PERCENT
+            ));
+        }
+        if self.resale_cooldown_turns > MAX_RESALE_COOLDOWN_TURNS {
+            return Err(format!(
+                "The resale cooldown can be at most {} turns",
+                MAX_RESALE_COOLDOWN_TURNS
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Cargo bought at one airport on one turn
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentPurchase {
+    pub airport_id: String,
+    pub cargo_id: String,
+    pub quantity: u32,
+    pub turn: u32,
+}
+
+/// A player's purchases still inside the room's resale cooldown, oldest
+/// first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PurchaseLog {
+    purchases: Vec<RecentPurchase>,
+}
+
+impl PurchaseLog {
+    pub fn record(&mut self, airport_id: &str, cargo_id: &str, quantity: u32, turn: u32) {
+        if let Some(last) = self.purchases.last_mut()
+            && last.airport_id == airport_id
+            && last.cargo_id == cargo_id
+            && last.turn == turn
+        {
+            last.quantity = last.quantity.saturating_add(quantity);
+            return;
+        }
+        self.purchases.push(RecentPurchase {
+            airport_id: airport_id.to_string(),
+            cargo_id: cargo_id.to_string(),
+            quantity,
+            turn,
+        });
+    }
+
+    /// Forget purchases whose cooldown is over
+    pub fn prune(&mut self, turn: u32, cooldown_turns: u32) {
+        self.purchases
+            .retain(|purchase| purchase.turn.saturating_add(cooldown_turns) > turn);
+    }
+
+    /// Units of a cargo bought at an airport that can't be sold back there
+    /// on `turn`, and how many turns until the last of them can
+    pub fn locked(
+        &self,
+        airport_id: &str,
+        cargo_id: &str,
+        turn: u32,
+        cooldown_turns: u32,
+    ) -> (u32, u32) {
+        self.purchases
+            .iter()
+            .filter(|purchase| purchase.airport_id == airport_id && purchase.cargo_id == cargo_id)
+            .filter_map(|purchase| {
+                let free_on = purchase.turn.saturating_add(cooldown_turns);
+                (free_on > turn).then(|| (purchase.quantity, free_on - turn))
+            })
+            .fold((0, 0), |(units, wait), (quantity, turns)| {
+                (units.saturating_add(quantity), wait.max(turns))
+            })
+    }
+}
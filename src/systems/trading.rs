@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use crate::models::{CargoType, Market, Player};
+use crate::models::{
+    CargoType, Market, Player,
+    locale::{LocalizedMessage, MessageCode},
+};
 
 #[derive(Debug, Clone)]
 pub enum TradingError {
@@ -9,35 +12,282 @@ pub enum TradingError {
     InsufficientCapacity,
     CargoNotAvailable,
     InvalidQuantity,
+    FuelUnavailable,
+    AmountTooLarge,
+    /// More than the broker will handle of one cargo in a turn
+    TurnCapReached {
+        remaining: u32,
+    },
+    /// Cargo bought here too recently to be sold back here
+    ResaleCooldown {
+        sellable: u32,
+        turns: u32,
+    },
+}
+
+/// A broker's terms at an airport. Players who trade more there get a
+/// smaller cut taken from each trade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BrokerTier {
+    pub name: &'static str,
+    /// Dollars a player must have traded at the airport to qualify
+    pub min_volume: u32,
+    pub fee_basis_points: u32,
+    /// Units of any one cargo the broker will buy from or sell to the
+    /// player each turn
+    pub units_per_turn: u32,
+}
+
+pub const BROKER_TIERS: [BrokerTier; 4] = [
+    BrokerTier {
+        name: "Walk-in",
+        min_volume: 0,
+        fee_basis_points: 300,
+        units_per_turn: 150,
+    },
+    BrokerTier {
+        name: "Regular",
+        min_volume: 10_000,
+        fee_basis_points: 200,
+        units_per_turn: 250,
+    },
+    BrokerTier {
+        name: "Preferred",
+        min_volume: 50_000,
+        fee_basis_points: 100,
+        units_per_turn: 400,
+    },
+    BrokerTier {
+        name: "Partner",
+        min_volume: 200_000,
+        fee_basis_points: 50,
+        units_per_turn: 600,
+    },
+];
+
+/// A trade priced at the market, with the broker's fee
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeQuote {
+    pub unit_price: u32,
+    pub quantity: u32,
+    pub gross: u32,
+    pub fee: u32,
+    pub tier: BrokerTier,
+    /// Added to a sale by the seller's streak
+    pub bonus: u32,
+}
+
+impl BrokerTier {
+    pub fn fee_percent(&self) -> f32 {
+        self.fee_basis_points as f32 / 100.0
+    }
+}
+
+impl TradeQuote {
+    /// What a buyer pays. Quotes from `quote_trade` always fit in a u32.
+    pub fn total_cost(&self) -> u32 {
+        self.gross.saturating_add(self.fee)
+    }
+
+    /// What a seller receives, streak bonus included
+    pub fn proceeds(&self) -> u32 {
+        self.gross
+            .saturating_sub(self.fee)
+            .saturating_add(self.bonus)
+    }
+}
+
+impl TradingError {
+    /// The refusal as a code and parameters
+    pub fn localized(&self) -> LocalizedMessage {
+        match *self {
+            TradingError::InsufficientFunds => {
+                LocalizedMessage::new(MessageCode::InsufficientFunds)
+            },
+            TradingError::InsufficientCargo => {
+                LocalizedMessage::new(MessageCode::InsufficientCargo)
+            },
+            TradingError::InsufficientCapacity => {
+                LocalizedMessage::new(MessageCode::InsufficientCapacity)
+            },
+            TradingError::CargoNotAvailable => {
+                LocalizedMessage::new(MessageCode::CargoNotAvailable)
+            },
+            TradingError::InvalidQuantity => LocalizedMessage::new(MessageCode::InvalidQuantity),
+            TradingError::FuelUnavailable => LocalizedMessage::new(MessageCode::FuelUnavailable),
+            TradingError::AmountTooLarge => LocalizedMessage::new(MessageCode::AmountTooLarge),
+            TradingError::TurnCapReached { remaining } => {
+                LocalizedMessage::new(MessageCode::TurnCapReached).with("remaining", remaining)
+            },
+            TradingError::ResaleCooldown { sellable, turns } => {
+                LocalizedMessage::new(MessageCode::ResaleCooldown)
+                    .with("sellable", sellable)
+                    .with("turns", turns)
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for TradingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.localized())
+    }
 }
 
 pub struct TradingSystem;
 
 impl TradingSystem {
-    pub fn buy_cargo(
-        player: &mut Player,
+    /// The broker tier a player has earned at an airport
+    pub fn broker_tier(volume: u32) -> BrokerTier {
+        BROKER_TIERS
+            .iter()
+            .rev()
+            .find(|tier| volume >= tier.min_volume)
+            .copied()
+            .unwrap_or(BROKER_TIERS[0])
+    }
+
+    /// The tier after the one a player is on, if there is one
+    pub fn next_broker_tier(volume: u32) -> Option<BrokerTier> {
+        BROKER_TIERS
+            .iter()
+            .find(|tier| tier.min_volume > volume)
+            .copied()
+    }
+
+    /// `quantity` units at `unit_price`, or an error when the total doesn't fit
+    /// in a u32 - no pilot could pay it or hold the proceeds
+    pub fn price_of(unit_price: u32, quantity: u32) -> Result<u32, TradingError> {
+        unit_price
+            .checked_mul(quantity)
+            .ok_or(TradingError::AmountTooLarge)
+    }
+
+    /// The broker's cut of a trade worth `gross`, rounded to the nearest dollar
+    pub fn trading_fee(gross: u32, tier: &BrokerTier) -> u32 {
+        let fee = (gross as u64 * tier.fee_basis_points as u64 + 5_000) / 10_000;
+        fee.min(u32::MAX as u64) as u32
+    }
+
+    /// The broker's cut at this market, after any global modifier on fees
+    pub fn market_fee(gross: u32, tier: &BrokerTier, market: &Market) -> u32 {
+        let fee = Self::trading_fee(gross, tier);
+        if market.fee_multiplier == 1.0 {
+            return fee;
+        }
+        (fee as f64 * market.fee_multiplier)
+            .round()
+            .clamp(0.0, u32::MAX as f64) as u32
+    }
+
+    /// Units of a cargo the player can still buy here this turn
+    pub fn purchases_left(player: &Player, market: &Market, cargo_id: &str) -> u32 {
+        let tier = Self::broker_tier(player.broker_volume_at(&market.airport_id));
+        tier.units_per_turn
+            .saturating_sub(player.turn_trades.bought(cargo_id))
+    }
+
+    /// Units of a cargo the player can still sell here this turn
+    pub fn sales_left(player: &Player, market: &Market, cargo_id: &str) -> u32 {
+        let tier = Self::broker_tier(player.broker_volume_at(&market.airport_id));
+        tier.units_per_turn
+            .saturating_sub(player.turn_trades.sold(cargo_id))
+    }
+
+    /// What trading `quantity` units at this market would come to for the
+    /// player, fee included
+    pub fn quote_trade(
+        player: &Player,
         market: &Market,
-        cargo_types: &HashMap<String, CargoType>,
         cargo_id: &str,
         quantity: u32,
-    ) -> Result<u32, TradingError> {
+    ) -> Result<TradeQuote, TradingError> {
+        let unit_price = market
+            .get_cargo_price(cargo_id)
+            .ok_or(TradingError::CargoNotAvailable)?;
+        Self::quote_at(player, market, unit_price, quantity)
+    }
+
+    /// The price a seller gets for a cargo listed at `price` when the
+    /// market keeps `spread_percent` of it
+    pub fn sell_price(price: u32, spread_percent: u32) -> u32 {
+        let kept = 100u64.saturating_sub(spread_percent as u64);
+        (price as u64 * kept / 100) as u32
+    }
+
+    fn quote_at(
+        player: &Player,
+        market: &Market,
+        unit_price: u32,
+        quantity: u32,
+    ) -> Result<TradeQuote, TradingError> {
         if quantity == 0 {
             return Err(TradingError::InvalidQuantity);
         }
 
+        let gross = Self::price_of(unit_price, quantity)?;
+        let tier = Self::broker_tier(player.broker_volume_at(&market.airport_id));
+        let fee = Self::market_fee(gross, &tier, market);
+        gross.checked_add(fee).ok_or(TradingError::AmountTooLarge)?;
+
+        Ok(TradeQuote {
+            unit_price,
+            quantity,
+            gross,
+            fee,
+            tier,
+            bonus: 0,
+        })
+    }
+
+    /// What selling `quantity` units here would pay the player, with the
+    /// bonus their streak earns
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub fn quote_sale(
+        player: &Player,
+        market: &Market,
+        cargo_id: &str,
+        quantity: u32,
+    ) -> Result<TradeQuote, TradingError> {
+        Self::quote_sale_at_spread(player, market, cargo_id, quantity, 0)
+    }
+
+    /// A sale at a market that pays `spread_percent` under its listed price
+    pub fn quote_sale_at_spread(
+        player: &Player,
+        market: &Market,
+        cargo_id: &str,
+        quantity: u32,
+        spread_percent: u32,
+    ) -> Result<TradeQuote, TradingError> {
+        let price = market
+            .get_cargo_price(cargo_id)
+            .ok_or(TradingError::CargoNotAvailable)?;
+        let unit_price = Self::sell_price(price, spread_percent);
+        let mut quote = Self::quote_at(player, market, unit_price, quantity)?;
+        quote.bonus = player.streak.bonus_on(quote.gross);
+        Ok(quote)
+    }
+
+    pub fn buy_cargo(
+        player: &mut Player,
+        market: &Market,
+        cargo_types: &HashMap<String, CargoType>,
+        cargo_id: &str,
+        quantity: u32,
+    ) -> Result<TradeQuote, TradingError> {
         // Check if cargo type exists and is available in market
         let cargo_type = cargo_types
             .get(cargo_id)
             .ok_or(TradingError::CargoNotAvailable)?;
-        let unit_price = market
-            .get_cargo_price(cargo_id)
-            .ok_or(TradingError::CargoNotAvailable)?;
-
-        let total_cost = unit_price * quantity;
-        let total_weight = cargo_type.weight_per_unit * quantity;
+        let quote = Self::quote_trade(player, market, cargo_id, quantity)?;
+        let total_weight = cargo_type
+            .weight_per_unit
+            .checked_mul(quantity)
+            .ok_or(TradingError::InsufficientCapacity)?;
 
         // Check if player can afford it
-        if !player.can_afford(total_cost) {
+        if !player.can_afford(quote.total_cost()) {
             return Err(TradingError::InsufficientFunds);
         }
 
@@ -46,10 +296,20 @@ impl TradingSystem {
             return Err(TradingError::InsufficientCapacity);
         }
 
+        // Check the broker will take that much this turn
+        let remaining = Self::purchases_left(player, market, cargo_id);
+        if quantity > remaining {
+            return Err(TradingError::TurnCapReached { remaining });
+        }
+
         // Execute the purchase
-        if player.spend_money(total_cost) {
-            player.cargo_inventory.add_cargo(cargo_id, quantity);
-            Ok(total_cost)
+        if player.spend_money(quote.total_cost()) {
+            player
+                .cargo_inventory
+                .add_cargo_at(cargo_id, quantity, quote.total_cost() / quantity);
+            player.record_broker_volume(&market.airport_id, quote.gross);
+            player.turn_trades.record_purchase(cargo_id, quantity);
+            Ok(quote)
         } else {
             Err(TradingError::InsufficientFunds)
         }
@@ -60,44 +320,68 @@ impl TradingSystem {
         market: &Market,
         cargo_id: &str,
         quantity: u32,
-    ) -> Result<u32, TradingError> {
-        if quantity == 0 {
-            return Err(TradingError::InvalidQuantity);
-        }
+    ) -> Result<TradeQuote, TradingError> {
+        Self::sell_cargo_at_spread(player, market, cargo_id, quantity, 0)
+    }
+
+    /// Sell at a market that pays `spread_percent` under its listed price
+    pub fn sell_cargo_at_spread(
+        player: &mut Player,
+        market: &Market,
+        cargo_id: &str,
+        quantity: u32,
+        spread_percent: u32,
+    ) -> Result<TradeQuote, TradingError> {
+        let quote = Self::quote_sale_at_spread(player, market, cargo_id, quantity, spread_percent)?;
 
         // Check if player has enough cargo
         let player_quantity = player.cargo_inventory.get_quantity(cargo_id);
         if player_quantity < quantity {
             return Err(TradingError::InsufficientCargo);
         }
+        let remaining = Self::sales_left(player, market, cargo_id);
+        if quantity > remaining {
+            return Err(TradingError::TurnCapReached { remaining });
+        }
+        // Refuse rather than pay out less than the sale is worth
+        if player.money.checked_add(quote.proceeds()).is_none() {
+            return Err(TradingError::AmountTooLarge);
+        }
 
-        // Check if market has a price for this cargo
-        let unit_price = market
-            .get_cargo_price(cargo_id)
-            .ok_or(TradingError::CargoNotAvailable)?;
-
-        let total_revenue = unit_price * quantity;
-
-        // Execute the sale
+        // Execute the sale. Cargo with no known cost leaves the streak alone.
+        let cost = player
+            .cargo_inventory
+            .average_cost(cargo_id)
+            .map(|unit_cost| unit_cost as u64 * quantity as u64);
         if player.cargo_inventory.remove_cargo(cargo_id, quantity) {
-            player.earn_money(total_revenue);
-            Ok(total_revenue)
+            if let Some(cost) = cost {
+                player.streak.record_sale(quote.proceeds() as u64 > cost);
+            }
+            player.earn_money(quote.proceeds());
+            player.record_broker_volume(&market.airport_id, quote.gross);
+            player.turn_trades.record_sale(cargo_id, quantity);
+            Ok(quote)
         } else {
             Err(TradingError::InsufficientCargo)
         }
     }
 
+    /// Buy up to `quantity` fuel, limited by the tank and by what the
+    /// airport has in stock
     pub fn buy_fuel(
         player: &mut Player,
-        market: &Market,
+        market: &mut Market,
         quantity: u32,
     ) -> Result<u32, TradingError> {
         if quantity == 0 {
             return Err(TradingError::InvalidQuantity);
         }
+        if market.fuel_available() == 0 {
+            return Err(TradingError::FuelUnavailable);
+        }
 
         let unit_price = market.fuel_price;
-        let total_cost = unit_price * quantity;
+        let total_cost = Self::price_of(unit_price, quantity)?;
 
         // Check if player can afford it
         if !player.can_afford(total_cost) {
@@ -105,17 +389,20 @@ impl TradingSystem {
         }
 
         // Check if fuel tank has capacity
-        let available_capacity = player.max_fuel - player.fuel;
-        let quantity_to_buy = quantity.min(available_capacity);
+        let available_capacity = player.max_fuel.saturating_sub(player.fuel);
+        let quantity_to_buy = quantity
+            .min(available_capacity)
+            .min(market.fuel_available());
 
         if quantity_to_buy == 0 {
             return Err(TradingError::InsufficientCapacity);
         }
 
-        let actual_cost = unit_price * quantity_to_buy;
+        let actual_cost = Self::price_of(unit_price, quantity_to_buy)?;
 
         // Execute the purchase
         if player.spend_money(actual_cost) {
+            market.draw_fuel(quantity_to_buy);
             player.add_fuel(quantity_to_buy);
             Ok(actual_cost)
         } else {
@@ -143,19 +430,29 @@ impl TradingSystem {
             return 0;
         }
 
-        // Calculate maximum based on money
-        let max_by_money = player.money / unit_price;
+        // Calculate maximum based on money, leaving room for the fee
+        let tier = Self::broker_tier(player.broker_volume_at(&market.airport_id));
+        let mut max_by_money = player.money / unit_price;
+        while max_by_money > 0 {
+            let gross = unit_price * max_by_money;
+            let total = gross.checked_add(Self::market_fee(gross, &tier, market));
+            if total.is_some_and(|total| total <= player.money) {
+                break;
+            }
+            max_by_money -= 1;
+        }
 
         // Calculate maximum based on weight capacity
         let current_weight = player.current_cargo_weight(cargo_types);
         let available_weight = player.max_cargo_weight.saturating_sub(current_weight);
-        let max_by_weight = if cargo_type.weight_per_unit > 0 {
-            available_weight / cargo_type.weight_per_unit
-        } else {
-            max_by_money // If weight is 0, no weight constraint
-        };
+        // If weight is 0, no weight constraint
+        let max_by_weight = available_weight
+            .checked_div(cargo_type.weight_per_unit)
+            .unwrap_or(max_by_money);
 
-        max_by_money.min(max_by_weight)
+        max_by_money
+            .min(max_by_weight)
+            .min(Self::purchases_left(player, market, cargo_id))
     }
 
     pub fn get_max_fuel_buyable(player: &Player, market: &Market) -> u32 {
@@ -165,8 +462,10 @@ impl TradingSystem {
         }
 
         let max_by_money = player.money / unit_price;
-        let max_by_capacity = player.max_fuel - player.fuel;
+        let max_by_capacity = player.max_fuel.saturating_sub(player.fuel);
 
-        max_by_money.min(max_by_capacity)
+        max_by_money
+            .min(max_by_capacity)
+            .min(market.fuel_available())
     }
 }
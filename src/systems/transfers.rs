@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::locale::{LocalizedMessage, MessageCode};
+
+/// What a player hands over to another player at the same airport. Money,
+/// cargo or both may be sent at once.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferTerms {
+    pub recipient_id: Uuid,
+    #[serde(default)]
+    pub money: u32,
+    #[serde(default)]
+    pub cargo_type: Option<String>,
+    #[serde(default)]
+    pub quantity: u32,
+}
+
+impl TransferTerms {
+    /// The cargo being sent, if any
+    pub fn cargo(&self) -> Option<(&str, u32)> {
+        self.cargo_type
+            .as_deref()
+            .filter(|_| self.quantity > 0)
+            .map(|cargo_type| (cargo_type, self.quantity))
+    }
+}
+
+/// A transfer that went through
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Transfer {
+    pub sender_id: Uuid,
+    pub sender_name: String,
+    pub recipient_id: Uuid,
+    pub recipient_name: String,
+    pub airport_id: String,
+    pub money: u32,
+    pub cargo_type: Option<String>,
+    pub quantity: u32,
+}
+
+impl Transfer {
+    /// What the sender is told. Money can take the recipient over the
+    /// room's win condition, so `won` says when it did.
+    pub fn localized(&self, won: bool) -> LocalizedMessage {
+        let cargo = self
+            .cargo_type
+            .as_ref()
+            .filter(|_| self.quantity > 0)
+            .map(|cargo_type| (cargo_type, self.quantity));
+        let code = match (cargo.is_some(), won) {
+            (false, false) => MessageCode::GaveMoney,
+            (false, true) => MessageCode::GaveMoneyAndWon,
+            (true, _) if self.money == 0 => MessageCode::GaveCargo,
+            (true, false) => MessageCode::GaveMoneyAndCargo,
+            (true, true) => MessageCode::GaveMoneyAndCargoAndWon,
+        };
+        let mut localized = LocalizedMessage::new(code)
+            .with("recipient", &self.recipient_name)
+            .with("airport", &self.airport_id);
+        if self.money > 0 {
+            localized = localized.with("money", self.money);
+        }
+        if let Some((cargo_type, quantity)) = cargo {
+            localized = localized
+                .with("quantity", quantity)
+                .with("cargo", cargo_type);
+        }
+        localized
+    }
+}
@@ -1,6 +1,20 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    models::{Airport, Player},
-    systems::GameState,
+    models::{
+        Airport, CargoType, Player,
+        locale::{Localized, LocalizedMessage, MessageCode},
+    },
+    systems::{
+        GameState, TurnAction, TurnEngine,
+        bank::BankStatement,
+        events::{EventSystem, MarketEvent},
+        game::TurnReport,
+        price_alerts::PriceAlert,
+        security::Night,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -9,14 +23,29 @@ pub enum TravelError {
     InvalidDestination,
     SameLocation,
     DestinationNotFound,
+    TooFarForGroundTransfer,
+    InsufficientFunds,
 }
 
+/// Airports this close together share a metro area and can be reached by road
+pub const GROUND_TRANSFER_MAX_KM: f64 = 150.0;
+
+/// Flat part of a ground transfer fare
+pub const GROUND_TRANSFER_BASE_FARE: u32 = 50;
+
+/// Per-km part of a ground transfer fare
+pub const GROUND_TRANSFER_FARE_PER_KM: f64 = 1.5;
+
 pub struct TravelSystem;
 
 impl TravelSystem {
     /// Calculate fuel needed to travel between two airports
-    pub fn calculate_fuel_needed(player: &Player, distance: f64) -> u32 {
-        player.fuel_needed_for_distance(distance)
+    pub fn calculate_fuel_needed(
+        player: &Player,
+        distance: f64,
+        conditions: FlightConditions,
+    ) -> u32 {
+        player.fuel_needed_at_burn(distance, conditions.burn(player))
     }
 
     /// Check if player can travel to a destination
@@ -24,6 +53,7 @@ impl TravelSystem {
         player: &Player,
         from_airport: &Airport,
         to_airport: &Airport,
+        conditions: FlightConditions,
     ) -> Result<u32, TravelError> {
         // Check if trying to travel to same location
         if from_airport.id == to_airport.id {
@@ -31,7 +61,7 @@ impl TravelSystem {
         }
 
         let distance = from_airport.distance_to(to_airport);
-        let fuel_needed = Self::calculate_fuel_needed(player, distance);
+        let fuel_needed = Self::calculate_fuel_needed(player, distance, conditions);
 
         if player.fuel < fuel_needed {
             return Err(TravelError::InsufficientFuel);
@@ -62,11 +92,19 @@ impl TravelSystem {
         let fuel_needed = if game_state.cheat_mode {
             0 // Always allow travel in cheat mode
         } else {
-            Self::can_travel_to(&game_state.player, &current_airport, &destination_airport)?
+            let conditions = game_state.flight_conditions(&current_airport.id, destination_id);
+            Self::can_travel_to(
+                &game_state.player,
+                &current_airport,
+                &destination_airport,
+                conditions,
+            )?
         };
 
         // Calculate distance for travel info
-        let distance = current_airport.distance_to(&destination_airport);
+        let distance = game_state
+            .distances
+            .between(&current_airport, &destination_airport);
 
         // Execute the travel
         let actual_fuel_consumed = if game_state.cheat_mode {
@@ -80,8 +118,12 @@ impl TravelSystem {
         // Refresh market prices at new location (simulate market changes over time)
         game_state.refresh_current_market();
 
-        // Advance turn
-        game_state.advance_turn();
+        // A flight takes a turn, ending with a night parked at the destination
+        let TurnReport {
+            night,
+            price_alerts,
+            bank,
+        } = TurnEngine::finish(game_state, TurnAction::Fly).unwrap_or_default();
 
         Ok(TravelInfo {
             from: current_airport.name.clone(),
@@ -89,17 +131,106 @@ impl TravelSystem {
             distance_km: distance,
             fuel_consumed: actual_fuel_consumed,
             remaining_fuel: game_state.player.fuel,
+            night,
+            price_alerts,
+            bank,
+        })
+    }
+
+    /// Fare for moving between two airports by road, or None when they are too
+    /// far apart to drive
+    pub fn ground_transfer_fare(from: &Airport, to: &Airport) -> Option<u32> {
+        if from.id == to.id {
+            return None;
+        }
+
+        let distance = from.distance_to(to);
+        (distance <= GROUND_TRANSFER_MAX_KM).then(|| {
+            GROUND_TRANSFER_BASE_FARE + (distance * GROUND_TRANSFER_FARE_PER_KM).round() as u32
+        })
+    }
+
+    /// Move to a nearby airport by road: costs money instead of fuel, and
+    /// takes a turn like a flight
+    pub fn ground_transfer(
+        game_state: &mut GameState,
+        destination_id: &str,
+    ) -> Result<TravelInfo, TravelError> {
+        let destination_airport = game_state
+            .airports
+            .get(destination_id)
+            .ok_or(TravelError::DestinationNotFound)?
+            .clone();
+        let current_airport = game_state
+            .get_current_airport()
+            .ok_or(TravelError::InvalidDestination)?
+            .clone();
+
+        let quote = Self::ground_transfer_quote(
+            &game_state.player,
+            &current_airport,
+            &destination_airport,
+        )?;
+        if !game_state.player.spend_money(quote.total_money) {
+            return Err(TravelError::InsufficientFunds);
+        }
+        game_state.player.current_airport = destination_id.to_string();
+
+        game_state.refresh_current_market();
+        let TurnReport {
+            night,
+            price_alerts,
+            bank,
+        } = TurnEngine::finish(game_state, TurnAction::GroundTransfer).unwrap_or_default();
+
+        Ok(TravelInfo {
+            from: current_airport.name.clone(),
+            to: destination_airport.name.clone(),
+            distance_km: quote.distance,
+            fuel_consumed: 0,
+            remaining_fuel: game_state.player.fuel,
+            night,
+            price_alerts,
+            bank,
         })
     }
 
+    /// Itemized cost of a ground transfer, in the same shape as a flight quote
+    pub fn ground_transfer_quote(
+        player: &Player,
+        from: &Airport,
+        to: &Airport,
+    ) -> Result<TravelQuote, TravelError> {
+        if from.id == to.id {
+            return Err(TravelError::SameLocation);
+        }
+        let fare =
+            Self::ground_transfer_fare(from, to).ok_or(TravelError::TooFarForGroundTransfer)?;
+
+        let items = vec![TravelCostItem {
+            label: format!("Ground transfer ({:.0} km by road)", from.distance_to(to)),
+            fuel: 0,
+            money: fare,
+        }];
+        let mut quote = TravelQuote::new(player, from, to, items, 0);
+        if quote.can_travel {
+            quote.set_summary(
+                LocalizedMessage::new(MessageCode::ReadyByRoad).with("money", player.money - fare),
+            );
+        }
+        Ok(quote)
+    }
+
     /// Get all possible destinations from current location
     pub fn get_reachable_destinations(game_state: &GameState) -> Vec<DestinationInfo> {
         let mut destinations = Vec::new();
 
         if let Some(current_airport) = game_state.get_current_airport() {
             for destination in game_state.get_available_destinations() {
-                let distance = current_airport.distance_to(destination);
-                let fuel_needed = Self::calculate_fuel_needed(&game_state.player, distance);
+                let distance = game_state.distances.between(current_airport, destination);
+                let conditions = game_state.flight_conditions(&current_airport.id, &destination.id);
+                let fuel_needed =
+                    Self::calculate_fuel_needed(&game_state.player, distance, conditions);
                 let can_afford = game_state.cheat_mode || game_state.player.fuel >= fuel_needed;
 
                 destinations.push(DestinationInfo {
@@ -108,6 +239,7 @@ impl TravelSystem {
                     distance_km: distance,
                     fuel_needed,
                     can_afford,
+                    ground_transfer_fare: Self::ground_transfer_fare(current_airport, destination),
                 });
             }
         }
@@ -117,14 +249,87 @@ impl TravelSystem {
         destinations
     }
 
+    /// How far the player's aircraft can fly now and on a full tank, by the
+    /// same burn flights are charged at under `conditions`
+    pub fn range(player: &Player, conditions: FlightConditions) -> FlightRange {
+        let burn = conditions.burn(player);
+        FlightRange {
+            current_fuel_km: player.range_at_burn(player.fuel, burn),
+            full_tank_km: player.range_at_burn(player.max_fuel, burn),
+        }
+    }
+
+    /// Itemized cost of flying a route. Servers use this both to preview a
+    /// flight and to charge for it, so clients can show the lines as-is
+    /// instead of repeating the arithmetic.
+    pub fn quote(
+        player: &Player,
+        from: &Airport,
+        to: &Airport,
+        fuel_price: u32,
+        conditions: FlightConditions,
+    ) -> TravelQuote {
+        Self::quote_with_extras(player, from, to, fuel_price, conditions, Vec::new())
+    }
+
+    /// A quote with extra charges (such as an insurance premium) after the fuel burn
+    #[allow(clippy::too_many_arguments)]
+    pub fn quote_with_extras(
+        player: &Player,
+        from: &Airport,
+        to: &Airport,
+        fuel_price: u32,
+        conditions: FlightConditions,
+        extras: Vec<TravelCostItem>,
+    ) -> TravelQuote {
+        let distance = from.distance_to(to);
+        let base_burn = player.fuel_needed_for_distance(distance);
+        let load_burn =
+            player.fuel_needed_at_burn(distance, player.load_factor(conditions.cargo_weight));
+        let fuel_burn = Self::calculate_fuel_needed(player, distance, conditions);
+
+        let mut items = vec![TravelCostItem {
+            label: format!(
+                "Fuel burn ({:.0} km at {:.0} km/unit)",
+                distance, player.fuel_efficiency
+            ),
+            fuel: base_burn,
+            money: 0,
+        }];
+        if load_burn > base_burn {
+            items.push(TravelCostItem {
+                label: format!("Cargo weight ({} kg aboard)", conditions.cargo_weight),
+                fuel: load_burn - base_burn,
+                money: 0,
+            });
+        }
+        if fuel_burn > load_burn {
+            items.push(TravelCostItem {
+                label: format!(
+                    "Storm on the route ({:.0}% more burn)",
+                    (conditions.weather - 1.0) * 100.0
+                ),
+                fuel: fuel_burn - load_burn,
+                money: 0,
+            });
+        }
+        items.extend(extras);
+
+        TravelQuote::new(player, from, to, items, fuel_price)
+    }
+
     /// Calculate travel cost in fuel for a given route
     #[allow(dead_code)]
     pub fn calculate_travel_cost(game_state: &GameState, destination_id: &str) -> Option<u32> {
-        let destination = game_state.airports.get(destination_id)?;
-        let current_airport = game_state.get_current_airport()?;
-
-        let distance = current_airport.distance_to(destination);
-        Some(Self::calculate_fuel_needed(&game_state.player, distance))
+        let distance =
+            game_state.get_distance(&game_state.player.current_airport, destination_id)?;
+        let conditions =
+            game_state.flight_conditions(&game_state.player.current_airport, destination_id);
+        Some(Self::calculate_fuel_needed(
+            &game_state.player,
+            distance,
+            conditions,
+        ))
     }
 }
 
@@ -136,6 +341,172 @@ pub struct TravelInfo {
     pub fuel_consumed: u32,
     #[allow(dead_code)]
     pub remaining_fuel: u32,
+    /// How the aircraft spent the night after arriving
+    pub night: Night,
+    /// Held cargo now worth well over its cost at the destination
+    pub price_alerts: Vec<PriceAlert>,
+    /// Loan payments taken at the end of the flight's turn
+    pub bank: Option<BankStatement>,
+}
+
+/// One line of a travel cost breakdown.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TravelCostItem {
+    pub label: String,
+    pub fuel: u32,
+    pub money: u32,
+}
+
+/// What a flight's burn depends on besides its length
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlightConditions {
+    /// Weight of the cargo aboard
+    pub cargo_weight: u32,
+    /// How much more fuel the weather on the route makes the flight burn,
+    /// 1.0 in clear skies
+    pub weather: f64,
+}
+
+impl Default for FlightConditions {
+    fn default() -> Self {
+        Self {
+            cargo_weight: 0,
+            weather: 1.0,
+        }
+    }
+}
+
+impl FlightConditions {
+    /// Flying `player`'s hold from one airport to another with `events`
+    /// running
+    pub fn for_route(
+        player: &Player,
+        cargo_types: &HashMap<String, CargoType>,
+        events: &[MarketEvent],
+        from: &str,
+        to: &str,
+    ) -> Self {
+        Self {
+            cargo_weight: player.current_cargo_weight(cargo_types),
+            weather: EventSystem::weather_multiplier(events, from, to),
+        }
+    }
+
+    /// Times the usual rate `player`'s aircraft burns fuel at
+    pub fn burn(&self, player: &Player) -> f64 {
+        player.load_factor(self.cargo_weight) * self.weather
+    }
+}
+
+/// Furthest the aircraft can fly, in km, with the cargo aboard now and in
+/// the weather where it stands. A heavier hold or a storm shortens it.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct FlightRange {
+    /// On the fuel on board now
+    pub current_fuel_km: f64,
+    /// After filling the tank
+    pub full_tank_km: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TravelQuote {
+    pub from: String,
+    pub to: String,
+    pub distance: f64,
+    pub items: Vec<TravelCostItem>,
+    pub total_fuel: u32,
+    pub total_money: u32,
+    pub fuel_on_hand: u32,
+    pub money_on_hand: u32,
+    pub refill_cost: u32, // Buying the burned fuel back at the departure airport
+    pub can_travel: bool,
+    pub summary: String,
+    /// The summary as a code and parameters, for clients that show it in
+    /// another language
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedMessage>,
+}
+
+impl TravelQuote {
+    fn new(
+        player: &Player,
+        from: &Airport,
+        to: &Airport,
+        items: Vec<TravelCostItem>,
+        fuel_price: u32,
+    ) -> Self {
+        let total_fuel = items
+            .iter()
+            .map(|item| item.fuel)
+            .fold(0, u32::saturating_add);
+        let total_money = items
+            .iter()
+            .map(|item| item.money)
+            .fold(0, u32::saturating_add);
+
+        let fuel_short = total_fuel.saturating_sub(player.fuel);
+        let money_short = total_money.saturating_sub(player.money);
+        let summary = match (fuel_short, money_short) {
+            (0, 0) => LocalizedMessage::new(MessageCode::ReadyToFly)
+                .with("fuel", player.fuel - total_fuel),
+            (0, money) => LocalizedMessage::new(MessageCode::MoneyShort).with("money", money),
+            (fuel, 0) => LocalizedMessage::new(MessageCode::FuelShort).with("fuel", fuel),
+            (fuel, money) => LocalizedMessage::new(MessageCode::FuelAndMoneyShort)
+                .with("fuel", fuel)
+                .with("money", money),
+        };
+
+        Self {
+            from: from.id.clone(),
+            to: to.id.clone(),
+            distance: from.distance_to(to),
+            items,
+            total_fuel,
+            total_money,
+            fuel_on_hand: player.fuel,
+            money_on_hand: player.money,
+            refill_cost: total_fuel.saturating_mul(fuel_price),
+            can_travel: fuel_short == 0 && money_short == 0,
+            summary: summary.to_string(),
+            localized: Some(summary),
+        }
+    }
+
+    /// Replace the summary, keeping its code alongside
+    pub fn set_summary(&mut self, localized: LocalizedMessage) {
+        self.summary = localized.to_string();
+        self.localized = Some(localized);
+    }
+
+    /// The breakdown as plain text lines, for clients without a table
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .items
+            .iter()
+            .map(|item| match (item.fuel, item.money) {
+                (fuel, 0) => format!("{}: {} fuel", item.label, fuel),
+                (0, money) => format!("{}: ${}", item.label, money),
+                (fuel, money) => format!("{}: {} fuel + ${}", item.label, fuel, money),
+            })
+            .collect();
+
+        lines.push(format!(
+            "Total: {} fuel, ${} (refilling costs ~${})",
+            self.total_fuel, self.total_money, self.refill_cost
+        ));
+        lines.push(self.summary.clone());
+        lines
+    }
+}
+
+impl Localized for TravelQuote {
+    fn message(&self) -> &str {
+        &self.summary
+    }
+
+    fn localized(&self) -> Option<&LocalizedMessage> {
+        self.localized.as_ref()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -145,4 +516,5 @@ pub struct DestinationInfo {
     pub distance_km: f64,
     pub fuel_needed: u32,
     pub can_afford: bool,
+    pub ground_transfer_fare: Option<u32>,
 }
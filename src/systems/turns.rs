@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::systems::{GameRoom, GameState, game::TurnReport};
+
+/// Something a pilot does that the API answers on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TurnAction {
+    Trade,
+    BuyFuel,
+    Fly,
+    GroundTransfer,
+}
+
+/// The one place that decides what an action costs in turns, so the
+/// terminal game, the single-player API and multiplayer rooms charge the
+/// same nights, fees and market moves for the same thing
+pub struct TurnEngine;
+
+impl TurnEngine {
+    /// Whether `action` uses up a turn. Only getting to another airport
+    /// does; trading and refueling happen on the ground within the turn.
+    pub fn costs_turn(action: TurnAction) -> bool {
+        match action {
+            TurnAction::Fly | TurnAction::GroundTransfer => true,
+            TurnAction::Trade | TurnAction::BuyFuel => false,
+        }
+    }
+
+    /// Finish `action` in a single-player game, taking the turn it costs
+    pub fn finish(game_state: &mut GameState, action: TurnAction) -> Option<TurnReport> {
+        Self::costs_turn(action).then(|| game_state.advance_turn())
+    }
+
+    /// Finish one player's `action` in a multiplayer room
+    pub fn finish_in_room(
+        room: &mut GameRoom,
+        player_id: &Uuid,
+        action: TurnAction,
+    ) -> Option<TurnReport> {
+        Self::costs_turn(action).then(|| room.advance_turn(player_id))
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{CargoType, Market, Player};
+
+/// Share of what its upgrades cost that a fitted-out aircraft is worth
+pub const AIRCRAFT_RESALE_PERCENT: u32 = 50;
+
+/// What a pilot is worth, piece by piece, less what they owe the bank
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetWorth {
+    pub cash: u32,
+    /// The hold sold at the prices where the pilot is standing
+    pub cargo: u32,
+    /// Upgrades fitted at the hangar, at resale
+    pub aircraft: u32,
+    /// Owed on loans, taken off the total
+    #[serde(default)]
+    pub debt: u32,
+}
+
+impl NetWorth {
+    pub fn total(&self) -> u32 {
+        self.cash
+            .saturating_add(self.cargo)
+            .saturating_add(self.aircraft)
+            .saturating_sub(self.debt)
+    }
+}
+
+pub struct ValuationSystem;
+
+impl ValuationSystem {
+    /// Value a pilot against `market`, their airport's. Cargo that market
+    /// doesn't price counts at its base price.
+    pub fn value(
+        player: &Player,
+        market: Option<&Market>,
+        cargo_types: &HashMap<String, CargoType>,
+    ) -> NetWorth {
+        let cargo = player
+            .cargo_inventory
+            .get_all_cargo()
+            .iter()
+            .map(|(cargo_id, quantity)| {
+                let price = market
+                    .and_then(|market| market.get_cargo_price(cargo_id))
+                    .or_else(|| cargo_types.get(cargo_id).map(|cargo| cargo.base_price))
+                    .unwrap_or(0);
+                price.saturating_mul(*quantity)
+            })
+            .fold(0, u32::saturating_add);
+
+        NetWorth {
+            cash: player.money,
+            cargo,
+            aircraft: (player.aircraft.invested() as u64 * AIRCRAFT_RESALE_PERCENT as u64 / 100)
+                as u32,
+            debt: player.loans.debt(),
+        }
+    }
+}
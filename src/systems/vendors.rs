@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use rand::{Rng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::{CargoType, Market};
+
+/// World ticks the vendors stay put before packing up and setting out
+/// somewhere else
+pub const STALL_ROTATION_TICKS: u32 = 5;
+
+/// Chance an airport gets a stall each time the vendors move on
+pub const STALL_CHANCE: f64 = 0.4;
+
+/// Most stalls one airport can have up at once
+pub const MAX_STALLS_PER_AIRPORT: usize = 2;
+
+/// Airports a piece of map intel has prices for
+pub const INTEL_AIRPORTS: usize = 3;
+
+/// What map intel costs, wherever it's sold
+pub const INTEL_PRICE: u32 = 150;
+
+/// What a fuel voucher costs, as a share of the pump price
+pub const VOUCHER_PRICE_PERCENT: u64 = 60;
+
+/// What a cargo crate costs, as a share of the average cargo price
+pub const CRATE_PRICE_PERCENT: u64 = 70;
+
+const VENDOR_NAMES: &[&str] = &[
+    "Rosa's Ramp Supplies",
+    "Tarmac Tony",
+    "The Fuel Truck Guy",
+    "Hangar 9 Salvage",
+    "Old Pete's Charts",
+    "Crosswind Traders",
+];
+
+/// Something a vendor sells once, to whoever gets there first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VendorItem {
+    /// Fuel pumped straight into the tank for well under the pump price
+    FuelVoucher { fuel: u32 },
+    /// A sealed crate of one kind of cargo; which kind isn't known until
+    /// it's opened
+    CargoCrate { units: u32 },
+    /// Today's prices at a few airports the buyer hasn't seen lately.
+    /// Only sold in fog of war rooms.
+    MapIntel,
+}
+
+impl VendorItem {
+    /// What a voucher for `fuel` units sells for where fuel costs
+    /// `pump_price`: at least $1, and at most what a price can hold
+    pub fn voucher_price(fuel: u32, pump_price: u32) -> u32 {
+        let price = fuel as u64 * pump_price as u64 * VOUCHER_PRICE_PERCENT / 100;
+        price.clamp(1, u32::MAX as u64) as u32
+    }
+
+    /// What a crate of `units` sells for when cargo averages
+    /// `average_cargo_price`, kept within the same bounds
+    pub fn crate_price(units: u32, average_cargo_price: u64) -> u32 {
+        let price = (units as u64)
+            .saturating_mul(average_cargo_price)
+            .saturating_mul(CRATE_PRICE_PERCENT)
+            / 100;
+        price.clamp(1, u32::MAX as u64) as u32
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            VendorItem::FuelVoucher { fuel } => format!("Voucher for {} fuel", fuel),
+            VendorItem::CargoCrate { units } => {
+                format!("Sealed crate of {} units of something", units)
+            },
+            VendorItem::MapIntel => {
+                format!(
+                    "Prices at {} airports you haven't seen lately",
+                    INTEL_AIRPORTS
+                )
+            },
+        }
+    }
+}
+
+/// A vendor's stall at an airport
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VendorStall {
+    pub id: Uuid,
+    pub airport_id: String,
+    pub vendor: String,
+    pub item: VendorItem,
+    pub price: u32,
+    /// The world tick the vendor packs up at
+    pub closes_at_tick: u32,
+}
+
+/// The stalls up across a room's airports
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VendorStalls {
+    pub stalls: Vec<VendorStall>,
+    /// The world tick the current stalls went up
+    pub stocked_at_tick: u32,
+}
+
+/// What a stall sold the player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum VendorPurchase {
+    Fuel { added: u32 },
+    Cargo { cargo_type: String, units: u32 },
+    Intel { airports: Vec<String> },
+}
+
+/// A stall that's been sold and what it sold
+pub type StallSale = (VendorStall, VendorPurchase);
+
+impl VendorPurchase {
+    /// A line for the player
+    pub fn report(&self, price: u32) -> String {
+        match self {
+            VendorPurchase::Fuel { added } => {
+                format!("Paid ${} and pumped {} fuel into the tank", price, added)
+            },
+            VendorPurchase::Cargo { cargo_type, units } => format!(
+                "Paid ${} and opened the crate: {} units of {}",
+                price, units, cargo_type
+            ),
+            VendorPurchase::Intel { airports } => format!(
+                "Paid ${} for the latest prices at {}",
+                price,
+                airports.join(", ")
+            ),
+        }
+    }
+}
+
+impl VendorStalls {
+    /// Stalls at an airport, in the order they went up
+    pub fn at(&self, airport_id: &str) -> Vec<&VendorStall> {
+        self.stalls
+            .iter()
+            .filter(|stall| stall.airport_id == airport_id)
+            .collect()
+    }
+
+    /// Whether the vendors move on at `world_tick`. A room's first stalls
+    /// go up on its first turn.
+    pub fn due(&self, world_tick: u32) -> bool {
+        self.stocked_at_tick == 0 || world_tick >= self.stocked_at_tick + STALL_ROTATION_TICKS
+    }
+
+    /// Take a stall down once its item is sold
+    pub fn take(&mut self, stall_id: &Uuid) -> Option<VendorStall> {
+        let index = self.stalls.iter().position(|stall| stall.id == *stall_id)?;
+        Some(self.stalls.remove(index))
+    }
+
+    /// Clear the stalls and put up a new set at random airports. Vouchers
+    /// are priced off the airport's fuel and crates off the room's cargo.
+    #[allow(clippy::too_many_arguments)]
+    pub fn restock(
+        &mut self,
+        markets: &HashMap<String, Market>,
+        cargo_types: &HashMap<String, CargoType>,
+        world_tick: u32,
+        intel: bool,
+        rng: &mut impl Rng,
+    ) {
+        let mut airport_ids: Vec<&String> = markets.keys().collect();
+        airport_ids.sort();
+
+        let average_cargo_price = if cargo_types.is_empty() {
+            0
+        } else {
+            cargo_types
+                .values()
+                .map(|cargo_type| cargo_type.base_price as u64)
+                .sum::<u64>()
+                / cargo_types.len() as u64
+        };
+
+        self.stalls.clear();
+        self.stocked_at_tick = world_tick;
+        for airport_id in airport_ids {
+            if !rng.gen_bool(STALL_CHANCE) {
+                continue;
+            }
+            let count = rng.gen_range(1..=MAX_STALLS_PER_AIRPORT);
+            for _ in 0..count {
+                let (item, price) = match rng.gen_range(0..3) {
+                    0 => {
+                        let fuel = rng.gen_range(40..=80);
+                        let pump_price = markets[airport_id].fuel_price;
+                        (
+                            VendorItem::FuelVoucher { fuel },
+                            VendorItem::voucher_price(fuel, pump_price),
+                        )
+                    },
+                    1 if average_cargo_price > 0 => {
+                        let units = rng.gen_range(5..=15);
+                        (
+                            VendorItem::CargoCrate { units },
+                            VendorItem::crate_price(units, average_cargo_price),
+                        )
+                    },
+                    2 if intel => (VendorItem::MapIntel, INTEL_PRICE),
+                    _ => continue,
+                };
+                self.stalls.push(VendorStall {
+                    id: Uuid::new_v4(),
+                    airport_id: airport_id.clone(),
+                    vendor: VENDOR_NAMES
+                        .choose(rng)
+                        .copied()
+                        .unwrap_or("A vendor")
+                        .to_string(),
+                    item,
+                    price,
+                    closes_at_tick: world_tick + STALL_ROTATION_TICKS,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::VecDeque;
+
+use uuid::Uuid;
+
+use crate::{
+    api::models::{ApplyLoadoutRequest, FuelRequest, TradeAction, TradeRequest, TravelRequest},
+    models::locale::{Locale, Localized},
+    ui::{
+        game_api_client::{ApiError, GameApiClient},
+        scenes::room_lobby::GameSession,
+    },
+};
+
+/// An action the player took that still has to reach the server. Nothing
+/// changes on screen until the server has answered it and the room state
+/// is fetched again.
+#[derive(Debug, Clone)]
+pub enum ActionKind {
+    Trade(TradeRequest),
+    ApplyLoadout(ApplyLoadoutRequest),
+    BuyFuel(FuelRequest),
+    Travel(TravelRequest),
+    GroundTransfer(TravelRequest),
+}
+
+impl ActionKind {
+    pub fn describe(&self) -> String {
+        match self {
+            ActionKind::Trade(request) => {
+                let verb = match request.action {
+                    TradeAction::Buy => "Buy",
+                    TradeAction::Sell => "Sell",
+                };
+                format!("{} {} {}", verb, request.quantity, request.cargo_type)
+            },
+            ActionKind::ApplyLoadout(request) => format!("Load {}", request.name),
+            ActionKind::BuyFuel(request) => format!("Buy {} fuel", request.quantity),
+            ActionKind::Travel(request) => format!("Fly to {}", request.destination),
+            ActionKind::GroundTransfer(request) => {
+                format!("Ground transfer to {}", request.destination)
+            },
+        }
+    }
+}
+
+/// The server's answer to a delivered action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionResult {
+    pub message: String,
+    /// Whether the action went ahead, rather than being turned down
+    pub succeeded: bool,
+}
+
+impl ActionResult {
+    fn answered(succeeded: bool, message: String) -> Self {
+        Self { message, succeeded }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QueuedAction {
+    pub idempotency_key: Uuid,
+    pub kind: ActionKind,
+    pub queued_at: std::time::Instant,
+}
+
+/// Outgoing actions waiting for the server. When the connection drops the
+/// queue holds on to them and replays them in order once it comes back; the
+/// idempotency key makes a replay of an already-applied action harmless.
+#[derive(Debug, Default)]
+pub struct ActionQueue {
+    pending: VecDeque<QueuedAction>,
+    pub offline: bool,
+    pub last_error: Option<String>,
+}
+
+impl ActionQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enqueue(&mut self, kind: ActionKind) -> Uuid {
+        let idempotency_key = Uuid::new_v4();
+        self.pending.push_back(QueuedAction {
+            idempotency_key,
+            kind,
+            queued_at: std::time::Instant::now(),
+        });
+        idempotency_key
+    }
+
+    pub fn cancel(&mut self, idempotency_key: Uuid) -> bool {
+        let before = self.pending.len();
+        self.pending
+            .retain(|action| action.idempotency_key != idempotency_key);
+        self.pending.len() != before
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &QueuedAction> {
+        self.pending.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Send queued actions in order. Stops at the first network failure and
+    /// keeps the remaining actions for the next attempt. Returns the server's
+    /// answer to each action that was delivered.
+    pub fn flush(&mut self, client: &GameApiClient, session: &GameSession) -> Vec<ActionResult> {
+        let mut results = Vec::new();
+
+        while let Some(action) = self.pending.front() {
+            match Self::send(client, session, action) {
+                Ok(result) => {
+                    self.offline = false;
+                    self.last_error = None;
+                    results.push(result);
+                    self.pending.pop_front();
+                },
+                Err(ApiError::NetworkError(e)) => {
+                    // Connection is down - keep the action and try again later
+                    self.offline = true;
+                    self.last_error = Some(e);
+                    break;
+                },
+                Err(ApiError::Cooldown(message)) => {
+                    // The room wants us to slow down - keep this and the rest
+                    // for the next attempt
+                    self.last_error = Some(message);
+                    break;
+                },
+                Err(e) => {
+                    // The server saw the action and rejected it; retrying won't help
+                    self.offline = false;
+                    results.push(ActionResult::answered(
+                        false,
+                        format!("{} failed: {}", action.kind.describe(), e),
+                    ));
+                    self.pending.pop_front();
+                },
+            }
+        }
+
+        results
+    }
+
+    fn send(
+        client: &GameApiClient,
+        session: &GameSession,
+        action: &QueuedAction,
+    ) -> Result<ActionResult, ApiError> {
+        let (room_id, player_id) = (session.room_id, session.player_id);
+        let key = action.idempotency_key;
+
+        match &action.kind {
+            ActionKind::Trade(request) => client
+                .player_trade_sync(room_id, player_id, request, key)
+                .map(|response| {
+                    ActionResult::answered(response.success, response.message_in(Locale::current()))
+                }),
+            ActionKind::ApplyLoadout(request) => client
+                .apply_loadout_sync(room_id, player_id, request, key)
+                .map(|response| {
+                    ActionResult::answered(true, response.message_in(Locale::current()))
+                }),
+            ActionKind::BuyFuel(request) => client
+                .player_buy_fuel_sync(room_id, player_id, request, key)
+                .map(|response| {
+                    ActionResult::answered(response.success, response.message_in(Locale::current()))
+                }),
+            ActionKind::Travel(request) => client
+                .player_travel_sync(room_id, player_id, request, key)
+                .map(|response| {
+                    ActionResult::answered(response.success, response.message_in(Locale::current()))
+                }),
+            ActionKind::GroundTransfer(request) => client
+                .player_ground_transfer_sync(room_id, player_id, request, key)
+                .map(|response| {
+                    ActionResult::answered(response.success, response.message_in(Locale::current()))
+                }),
+        }
+    }
+}
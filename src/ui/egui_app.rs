@@ -1,20 +1,44 @@
 use crate::{
-    api::models::MultiplayerGameStateResponse,
+    api::models::{MarketInfo, MultiplayerGameStateResponse},
+    models::NotificationKind,
+    systems::{RoomAward, RoomWinner, distance::DistanceCache},
     ui::{
+        action_queue::ActionQueue,
         game_api_client::GameApiClient,
+        notifications::{Notification, NotificationWatcher, Notifier},
+        recorder::{MomentRecorder, moments_dir, save_moment},
+        refresh::{GuiSettings, RefreshMode},
         scenes::{
             Scene, SceneState,
-            room_lobby::{GameSession, RoomLobbyScene},
+            airport::drafts,
+            host_dashboard::{DashboardAction, HostDashboardScene},
+            profile_manager::ProfileManagerScene,
+            replay::ReplayScene,
+            room_info::RoomInfoDialog,
+            room_lobby::{GameSession, RoomLobbyScene, starting_airport},
             server_connection::ServerConnectionScene,
         },
+        toasts::{ToastKind, Toasts},
     },
 };
 use eframe::egui;
 
+/// How long an achievement unlock stays on screen
+const ACHIEVEMENT_TOAST_DURATION: std::time::Duration = std::time::Duration::from_secs(6);
+
+/// How long "Saved moment to ..." stays on screen
+const MOMENT_MESSAGE_DURATION: std::time::Duration = std::time::Duration::from_secs(8);
+
+/// A moment being written to disk, off the UI thread
+type MomentSave = std::sync::mpsc::Receiver<Result<std::path::PathBuf, String>>;
+
 #[derive(Debug, Clone)]
 pub enum AppState {
     ServerConnection,
     RoomLobby,
+    ProfileManager,
+    /// Every room the player hosts, to watch and switch between
+    HostDashboard,
     InGame(GameSession),
 }
 
@@ -24,10 +48,39 @@ pub struct KzrkEguiApp {
     api_client: GameApiClient,
     game_state: Option<MultiplayerGameStateResponse>,
     converted_game_state: Option<crate::systems::game::GameState>, // Cache converted state
-    last_local_action: Option<std::time::Instant>,                 // Track recent local actions
+    action_queue: ActionQueue,
+    /// What the server said about the player's actions, waiting to be shown
+    /// or on screen
+    toasts: Toasts,
+    notifier: Notifier,
+    notification_watcher: NotificationWatcher,
+    unread_notifications: usize,
+    /// Mentions shown in the notifications window while it's open
+    mentions_inbox: Option<Vec<crate::models::Notification>>,
+    /// Achievement unlocks on screen, and when each appeared
+    achievement_toasts: Vec<(Notification, std::time::Instant)>,
+    room_winner: Option<RoomWinner>,
+    room_awards: Vec<RoomAward>,
+    /// Who has voted to play the next round in this room
+    rematch_votes: Vec<String>,
+    game_over_error: Option<String>,
+    replay_scene: Option<ReplayScene>,
+    /// The room's ruleset, shown once on joining
+    room_info: Option<RoomInfoDialog>,
     server_connection_scene: ServerConnectionScene,
     room_lobby_scene: RoomLobbyScene,
+    host_dashboard_scene: HostDashboardScene,
+    profile_manager_scene: ProfileManagerScene,
     last_state_refresh: std::time::Instant,
+    gui_settings: GuiSettings,
+    /// The last few seconds of the window, while recording moments
+    moment_recorder: Option<MomentRecorder>,
+    moment_save: Option<MomentSave>,
+    /// How saving the last moment went, and when
+    moment_message: Option<(String, std::time::Instant)>,
+    /// What the title bar says, so it's only sent when it changes: every
+    /// viewport command costs a repaint
+    window_title: String,
 }
 
 impl Default for KzrkEguiApp {
@@ -44,93 +97,166 @@ impl KzrkEguiApp {
             api_client: GameApiClient::new("127.0.0.1:3000".to_string()),
             game_state: None,
             converted_game_state: None,
-            last_local_action: None,
+            action_queue: ActionQueue::new(),
+            toasts: Toasts::new(),
+            notifier: Notifier::desktop_from_env(),
+            notification_watcher: NotificationWatcher::new(),
+            unread_notifications: 0,
+            mentions_inbox: None,
+            room_info: None,
+            achievement_toasts: Vec::new(),
+            room_winner: None,
+            room_awards: Vec::new(),
+            rematch_votes: Vec::new(),
+            game_over_error: None,
+            replay_scene: None,
             server_connection_scene: ServerConnectionScene::default(),
             room_lobby_scene: RoomLobbyScene::default(),
+            host_dashboard_scene: HostDashboardScene::default(),
+            profile_manager_scene: ProfileManagerScene::default(),
             last_state_refresh: std::time::Instant::now(),
+            gui_settings: GuiSettings::load(),
+            moment_recorder: None,
+            moment_save: None,
+            moment_message: None,
+            window_title: String::new(),
         }
+        .with_moment_recorder()
+    }
+
+    /// Start recording moments if the saved settings say to
+    fn with_moment_recorder(mut self) -> Self {
+        if self.gui_settings.record_moments {
+            self.moment_recorder = Some(MomentRecorder::from_env());
+        }
+        self
     }
 }
 
 impl eframe::App for KzrkEguiApp {
     fn update(&mut self, ctx: &eframe::egui::Context, _frame: &mut eframe::Frame) {
         // Debug: Show current app state in title bar
-        ctx.send_viewport_cmd(egui::ViewportCommand::Title(format!(
-            "KZRK - State: {:?}",
-            self.app_state
-        )));
+        let title = format!("KZRK - State: {:?}", self.app_state);
+        if title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = title;
+        }
+
+        let refresh_mode = self.gui_settings.refresh_mode;
+        ctx.style_mut(|style| {
+            style.animation_time = if refresh_mode.animates() {
+                egui::Style::default().animation_time
+            } else {
+                0.0
+            };
+        });
+        self.capture_moment_frame(ctx);
+        self.render_power_saver_toggle(ctx);
 
         match &self.app_state.clone() {
             AppState::ServerConnection => {
                 if let Some((scene, client)) = self.server_connection_scene.render(ctx) {
                     self.api_client = client;
-                    if scene == Scene::RoomLobby {
-                        self.app_state = AppState::RoomLobby
+                    match scene {
+                        Scene::RoomLobby => self.app_state = AppState::RoomLobby,
+                        Scene::ProfileManager => self.app_state = AppState::ProfileManager,
+                        _ => {},
                     }
                 }
             },
+            AppState::ProfileManager => {
+                if self.profile_manager_scene.render(ctx) {
+                    self.app_state = AppState::ServerConnection;
+                }
+            },
             AppState::RoomLobby => {
                 // API client is always available
+                self.room_lobby_scene.refresh_every = refresh_mode.lobby_poll_interval();
                 if let Some((scene, session)) = self.room_lobby_scene.render(ctx, &self.api_client)
                 {
-                    self.app_state = AppState::InGame(session);
-                    if let Scene::Airport(airport_id) = scene {
-                        self.scene_state.travel_to_airport(airport_id);
-                    }
+                    self.enter_room(scene, session);
+                } else if self.room_lobby_scene.open_host_dashboard {
+                    self.room_lobby_scene.open_host_dashboard = false;
+                    let host_name = self.room_lobby_scene.player_name.trim().to_string();
+                    self.host_dashboard_scene.open(&host_name);
+                    self.app_state = AppState::HostDashboard;
+                }
+            },
+            AppState::HostDashboard => {
+                self.host_dashboard_scene.refresh_every = refresh_mode.lobby_poll_interval();
+                match self.host_dashboard_scene.render(ctx, &self.api_client) {
+                    Some(DashboardAction::Switch(scene, session)) => {
+                        self.reset_room_state();
+                        self.enter_room(scene, session);
+                    },
+                    Some(DashboardAction::Close) => self.app_state = AppState::RoomLobby,
+                    None => {},
                 }
             },
             AppState::InGame(session) => {
-                // Refresh game state periodically
-                if self.last_state_refresh.elapsed().as_secs() >= 2 {
-                    self.refresh_game_state(session);
+                // Refresh game state periodically, retrying any queued actions first
+                let focused = ctx.input(|i| i.viewport().focused).unwrap_or(true);
+                let poll_every = refresh_mode.state_poll_interval(focused);
+                if self.last_state_refresh.elapsed() >= poll_every {
+                    if !self.deliver_actions(session) {
+                        self.refresh_game_state(session);
+                    }
+                    self.poll_notifications(ctx, session);
+                }
+                // Wake up for the next poll even if the player is away;
+                // input wakes us sooner
+                ctx.request_repaint_after(
+                    poll_every.saturating_sub(self.last_state_refresh.elapsed()),
+                );
+
+                // Must be shown before the airport scene claims the central panel
+                self.render_pending_actions_tray(ctx, session);
+                self.render_modifier_banner(ctx);
+                self.render_notification_bell(ctx, session);
+                self.render_achievement_toasts(ctx);
+                self.render_action_toasts(ctx);
+                self.render_game_over(ctx, session);
+                if let Some(replay) = &mut self.replay_scene
+                    && replay.render(ctx)
+                {
+                    self.replay_scene = None;
+                }
+                if let Some(dialog) = &self.room_info
+                    && dialog.render(ctx)
+                {
+                    self.room_info = None;
                 }
 
                 // Render airport scene with multiplayer data
                 match &self.scene_state.current_scene {
                     Scene::Airport(_airport) => {
-                        if let Some(multiplayer_state) = &self.game_state {
-                            // Only convert/update the cached state if needed
-                            if self.converted_game_state.is_none() {
-                                self.converted_game_state = self
-                                    .convert_multiplayer_to_game_state(multiplayer_state, session);
-                            }
+                        if let Some(converted_state) = &mut self.converted_game_state {
+                            // Buttons only queue actions; what they do comes
+                            // back from the server
+                            crate::ui::scenes::airport::AirportScene::render(
+                                converted_state,
+                                &mut self.scene_state,
+                                ctx,
+                                &self.api_client,
+                                session,
+                            );
 
-                            // Clone the multiplayer state and session for the update
-                            let multiplayer_state_clone = multiplayer_state.clone();
-                            let session_clone = session.clone();
-
-                            // Use the cached converted state
-                            if let Some(converted_state) = &mut self.converted_game_state {
-                                // Update only the player data from multiplayer state (dynamic data)
-                                // Skip update if there was a recent local action (within 5 seconds)
-                                let skip_update = if let Some(last_action) = self.last_local_action
-                                {
-                                    last_action.elapsed().as_secs() < 5
-                                } else {
-                                    false
-                                };
-
-                                if !skip_update {
-                                    Self::update_converted_state_player_static(
-                                        converted_state,
-                                        &multiplayer_state_clone,
-                                        &session_clone,
-                                    );
+                            // Hand this frame's actions to the queue and try to deliver them
+                            if !self.scene_state.outbox.is_empty() {
+                                for action in self.scene_state.outbox.drain(..) {
+                                    self.action_queue.enqueue(action);
                                 }
-
-                                // Use a custom multiplayer-aware render that handles API calls
-                                if let Some(action_time) =
-                                    Self::render_multiplayer_airport_scene_static(
-                                        converted_state,
-                                        &mut self.scene_state,
-                                        &session_clone,
-                                        ctx,
-                                        &self.api_client,
-                                    )
-                                {
-                                    self.last_local_action = Some(action_time);
+                                if !self.action_queue.offline {
+                                    self.deliver_actions(session);
                                 }
                             }
+
+                            // The player confirmed leaving - head back to the lobby
+                            if self.scene_state.left_room {
+                                drafts::clear();
+                                self.reset_room_state();
+                                self.app_state = AppState::RoomLobby;
+                            }
                         } else {
                             // Loading state
                             egui::CentralPanel::default().show(ctx, |ui| {
@@ -144,8 +270,15 @@ impl eframe::App for KzrkEguiApp {
                         }
                     },
                     _other_scene => {
-                        // Default to airport if we're in an unexpected scene
-                        self.scene_state.travel_to_airport("JFK".to_string());
+                        // Default to where the player is if we're in an
+                        // unexpected scene
+                        let airport_id = self
+                            .converted_game_state
+                            .as_ref()
+                            .map(|state| state.player.current_airport.clone())
+                            .unwrap_or_default();
+                        self.scene_state
+                            .travel_to_airport(starting_airport(airport_id));
                     },
                 }
             },
@@ -154,139 +287,716 @@ impl eframe::App for KzrkEguiApp {
 }
 
 impl KzrkEguiApp {
-    fn refresh_game_state(&mut self, session: &GameSession) {
-        self.last_state_refresh = std::time::Instant::now();
+    /// Start playing in a room the lobby or host dashboard put us in
+    fn enter_room(&mut self, scene: Scene, session: GameSession) {
+        self.room_info = self
+            .api_client
+            .get_room_settings_sync(session.room_id)
+            .ok()
+            .map(RoomInfoDialog::new);
+        if let Scene::Airport(airport_id) = scene {
+            self.scene_state.travel_to_airport(airport_id);
+        }
+        // Pick up where we were if the last run died mid-draft
+        if let Some(draft) = drafts::load() {
+            draft.restore(&mut self.scene_state, &session);
+        }
+        self.app_state = AppState::InGame(session);
+    }
 
-        // TODO: Implement proper async state fetching
-        // For now, we'll create a mock state to avoid the tokio runtime crash
-        // In a production app, you'd use proper async channels or polling_promise
+    /// Forget everything tied to the room we were playing in
+    fn reset_room_state(&mut self) {
+        self.scene_state = SceneState::new();
+        self.game_state = None;
+        self.converted_game_state = None;
+        self.action_queue = ActionQueue::new();
+        self.toasts.clear();
+        self.notification_watcher = NotificationWatcher::new();
+        self.unread_notifications = 0;
+        self.mentions_inbox = None;
+        self.room_winner = None;
+        self.room_awards.clear();
+        self.rematch_votes.clear();
+        self.game_over_error = None;
+        self.replay_scene = None;
+        self.room_info = None;
+    }
 
-        // Always refresh state, but preserve local changes during action protection window
-        // Create/update mock state for testing
-        use crate::api::models::*;
-        use crate::systems::GameStatus;
-        use chrono::Utc;
+    /// The room has started its next round: start over where the server
+    /// put us, in the same room
+    fn start_next_round(&mut self, airport_id: String) {
+        self.reset_room_state();
+        self.scene_state.travel_to_airport(airport_id);
+    }
 
-        // Get the current location from converted state if available (to preserve travel)
-        let current_location = if let Some(converted_state) = &self.converted_game_state {
-            converted_state.player.current_airport.clone()
-        } else {
-            "JFK".to_string()
+    fn render_game_over(&mut self, ctx: &egui::Context, session: &GameSession) {
+        let Some(winner) = self.room_winner.clone() else {
+            return;
         };
 
-        // Get current player state to preserve changes during local action window
-        let (current_money, current_fuel, current_cargo) =
-            if let Some(converted_state) = &self.converted_game_state {
-                (
-                    converted_state.player.money,
-                    converted_state.player.fuel,
-                    converted_state.player.cargo_inventory.clone(),
-                )
-            } else {
-                use crate::models::cargo::CargoInventory;
-                (5000, 200, CargoInventory::new())
-            };
+        let mut rematch = false;
+        let mut vote_rematch = false;
+        let mut replay = false;
+        let mut back_to_lobby = false;
 
-        let mock_state = MultiplayerGameStateResponse {
-            room_info: RoomInfo {
-                id: session.room_id,
-                name: "Test Room".to_string(),
-                host_player_name: session.player_name.clone(),
-                current_players: 1,
-                max_players: 4,
-                created_at: Utc::now(),
-                game_status: GameStatus::WaitingForPlayers,
-                is_joinable: true,
-            },
-            my_player_id: session.player_id,
-            players: vec![PlayerInfo {
-                id: Some(session.player_id),
-                name: session.player_name.clone(),
-                money: current_money,
-                current_airport: current_location.clone(),
-                fuel: current_fuel,
-                max_fuel: 200,
-                cargo_inventory: current_cargo
-                    .get_all_cargo()
-                    .iter()
-                    .map(|(k, v)| (k.clone(), *v))
-                    .collect(),
-                cargo_weight: current_cargo.get_all_cargo().values().sum::<u32>(),
-                max_cargo_weight: 1000,
-                fuel_efficiency: 15.0,
-                is_online: Some(true),
-                last_seen: Some(Utc::now()),
-                is_host: Some(true),
-            }],
-            current_market: MarketInfo {
-                airport_id: current_location.clone(),
-                airport_name: match current_location.as_str() {
-                    "JFK" => "New York JFK".to_string(),
-                    "LAX" => "Los Angeles LAX".to_string(),
-                    "MIA" => "Miami MIA".to_string(),
-                    "ORD" => "Chicago O'Hare".to_string(),
-                    "DEN" => "Denver DEN".to_string(),
-                    "SEA" => "Seattle SEA".to_string(),
-                    _ => "Unknown Airport".to_string(),
+        egui::Window::new("🏆 Game Over")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if winner.player_id == session.player_id {
+                    ui.heading("You won!");
+                } else {
+                    ui.heading(format!("{} won!", winner.player_name));
+                }
+                ui.label(format!(
+                    "Reached ${} on turn {}.",
+                    winner.money, winner.turn_number
+                ));
+                for award in &self.room_awards {
+                    ui.label(format!(
+                        "{}: {} ({})",
+                        award.title, award.player_name, award.detail
+                    ));
+                }
+                ui.label("Trading in this room is closed.");
+                if !self.rematch_votes.is_empty() {
+                    ui.label(format!(
+                        "Ready to play again here: {}",
+                        self.rematch_votes.join(", ")
+                    ));
+                }
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    let voted = self.rematch_votes.contains(&session.player_name);
+                    if ui
+                        .add_enabled(!voted, egui::Button::new("🗳 Play Again Here"))
+                        .on_hover_text("Starts once everyone in the room votes")
+                        .clicked()
+                    {
+                        vote_rematch = true;
+                    }
+                    if ui
+                        .button("🔁 Rematch")
+                        .on_hover_text("Start over in a new room")
+                        .clicked()
+                    {
+                        rematch = true;
+                    }
+                    if ui.button("📼 Replay").clicked() {
+                        replay = true;
+                    }
+                    if ui.button("🏢 Back to Lobby").clicked() {
+                        back_to_lobby = true;
+                    }
+                });
+
+                if let Some(error) = &self.game_over_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
+
+        if vote_rematch {
+            match self
+                .api_client
+                .vote_rematch_sync(session.room_id, session.player_id)
+            {
+                Ok(response) => match response.starting_airport {
+                    Some(airport_id) if response.restarted => self.start_next_round(airport_id),
+                    _ => {
+                        self.game_over_error = None;
+                        self.rematch_votes.push(session.player_name.clone());
+                    },
                 },
-                fuel_price: 50,
-                cargo_prices: {
-                    let mut prices = std::collections::HashMap::new();
-                    prices.insert("electronics".to_string(), 500);
-                    prices.insert("food".to_string(), 100);
-                    prices.insert("textiles".to_string(), 200);
-                    prices.insert("industrial".to_string(), 300);
-                    prices.insert("luxury".to_string(), 1000);
-                    prices.insert("materials".to_string(), 50);
-                    prices
+                Err(e) => self.game_over_error = Some(format!("Vote failed: {}", e)),
+            }
+        } else if rematch {
+            match self
+                .api_client
+                .rematch_sync(session.room_id, session.player_id)
+            {
+                Ok(response) => {
+                    self.reset_room_state();
+                    self.scene_state
+                        .travel_to_airport(starting_airport(response.starting_airport));
+                    self.app_state = AppState::InGame(GameSession {
+                        room_id: response.room_id,
+                        player_id: response.player_id,
+                        player_name: response.player_name,
+                    });
                 },
-                last_updated: std::time::SystemTime::now(),
-            },
-            available_destinations: vec![
-                DestinationInfo {
-                    airport_id: "LAX".to_string(),
-                    airport_name: "Los Angeles LAX".to_string(),
-                    distance: 3974.0,
-                    fuel_required: 150,
-                    can_travel: true,
-                    fuel_price: 45,
+                Err(e) => self.game_over_error = Some(format!("Rematch failed: {}", e)),
+            }
+        } else if replay {
+            match self
+                .api_client
+                .get_replay_sync(session.room_id, session.player_id)
+            {
+                Ok(response) => {
+                    self.game_over_error = None;
+                    self.replay_scene = Some(ReplayScene::new(response));
                 },
-                DestinationInfo {
-                    airport_id: "MIA".to_string(),
-                    airport_name: "Miami MIA".to_string(),
-                    distance: 1757.0,
-                    fuel_required: 80,
-                    can_travel: true,
-                    fuel_price: 55,
+                Err(e) => self.game_over_error = Some(format!("Replay unavailable: {}", e)),
+            }
+        } else if back_to_lobby {
+            self.reset_room_state();
+            self.app_state = AppState::RoomLobby;
+        }
+    }
+
+    fn render_pending_actions_tray(&mut self, ctx: &egui::Context, session: &GameSession) {
+        if self.action_queue.is_empty() {
+            return;
+        }
+
+        let mut cancelled = Vec::new();
+        let mut retry = false;
+
+        egui::TopBottomPanel::bottom("pending_actions_tray").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if self.action_queue.offline {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(220, 50, 50),
+                        "📡 Connection lost - actions will be sent when it returns",
+                    );
+                } else {
+                    ui.label("📤 Sending actions...");
+                }
+                if ui.small_button("🔄 Retry now").clicked() {
+                    retry = true;
+                }
+            });
+
+            if let Some(error) = &self.action_queue.last_error {
+                ui.colored_label(egui::Color32::GRAY, error);
+            }
+
+            for action in self.action_queue.pending() {
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "⏳ {} ({}s ago)",
+                        action.kind.describe(),
+                        action.queued_at.elapsed().as_secs()
+                    ));
+                    if ui.small_button("❌ Cancel").clicked() {
+                        cancelled.push(action.idempotency_key);
+                    }
+                });
+            }
+        });
+
+        for key in cancelled {
+            self.action_queue.cancel(key);
+        }
+        if retry {
+            self.deliver_actions(session);
+        }
+    }
+
+    /// Send queued actions. Once the server has answered any of them, toast
+    /// what it said and pick up the state they left the player in. Returns
+    /// whether the room state was fetched.
+    fn deliver_actions(&mut self, session: &GameSession) -> bool {
+        if self.action_queue.is_empty() {
+            return false;
+        }
+        let results = self.action_queue.flush(&self.api_client, session);
+        if results.is_empty() {
+            return false;
+        }
+        for result in results {
+            let kind = if result.succeeded {
+                ToastKind::Success
+            } else {
+                ToastKind::Error
+            };
+            self.toasts.push(kind, result.message);
+        }
+        self.refresh_game_state(session);
+        true
+    }
+
+    /// A bell in the corner counting unread mentions. Opening it lists them
+    /// and marks them all read.
+    /// A strip across the top while price or fee modifiers are running
+    fn render_modifier_banner(&self, ctx: &egui::Context) {
+        let modifiers = &self.scene_state.active_modifiers;
+        if modifiers.is_empty() {
+            return;
+        }
+
+        egui::TopBottomPanel::top("modifier_banner")
+            .frame(
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(235, 225, 255))
+                    .inner_margin(egui::Margin::symmetric(10.0, 4.0)),
+            )
+            .show(ctx, |ui| {
+                for modifier in modifiers {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(90, 40, 150),
+                        format!("📢 {}", modifier.describe()),
+                    );
+                }
+            });
+    }
+
+    fn render_achievement_toasts(&mut self, ctx: &egui::Context) {
+        self.achievement_toasts
+            .retain(|(_, shown_at)| shown_at.elapsed() < ACHIEVEMENT_TOAST_DURATION);
+        if self.achievement_toasts.is_empty() {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("achievement_toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -12.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (toast, _) in &self.achievement_toasts {
+                    let Notification::AchievementUnlocked { achievement } = toast else {
+                        continue;
+                    };
+                    egui::Frame::none()
+                        .fill(egui::Color32::from_rgb(255, 244, 214))
+                        .stroke(egui::Stroke::new(
+                            1.0,
+                            egui::Color32::from_rgb(220, 160, 40),
+                        ))
+                        .inner_margin(egui::Margin::same(10.0))
+                        .outer_margin(egui::Margin::symmetric(0.0, 2.0))
+                        .rounding(egui::Rounding::same(6.0))
+                        .show(ui, |ui| {
+                            ui.strong(format!(
+                                "🏅 Achievement unlocked: {} {}",
+                                achievement.icon(),
+                                achievement.title()
+                            ));
+                            ui.label(achievement.description());
+                        });
+                }
+            });
+        // Come back to take the oldest down even if nothing else happens
+        let oldest = self
+            .achievement_toasts
+            .iter()
+            .map(|(_, shown_at)| shown_at.elapsed())
+            .max()
+            .unwrap_or_default();
+        ctx.request_repaint_after(ACHIEVEMENT_TOAST_DURATION.saturating_sub(oldest));
+    }
+
+    /// The server's answers to the player's actions, green when they went
+    /// through and red when they didn't, a few at a time
+    fn render_action_toasts(&mut self, ctx: &egui::Context) {
+        let now = std::time::Instant::now();
+        self.toasts.tick(now);
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let mut dismissed = Vec::new();
+        egui::Area::new(egui::Id::new("action_toasts"))
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 40.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for toast in self.toasts.visible() {
+                    let (icon, fill, stroke) = match toast.kind {
+                        ToastKind::Success => (
+                            "✅",
+                            egui::Color32::from_rgb(225, 245, 225),
+                            egui::Color32::from_rgb(60, 160, 80),
+                        ),
+                        ToastKind::Error => (
+                            "❌",
+                            egui::Color32::from_rgb(255, 228, 225),
+                            egui::Color32::from_rgb(210, 60, 50),
+                        ),
+                    };
+                    egui::Frame::none()
+                        .fill(fill)
+                        .stroke(egui::Stroke::new(1.0, stroke))
+                        .inner_margin(egui::Margin::same(8.0))
+                        .outer_margin(egui::Margin::symmetric(0.0, 2.0))
+                        .rounding(egui::Rounding::same(6.0))
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{} {}", icon, toast.message));
+                                if ui.small_button("✖").clicked() {
+                                    dismissed.push(toast.id);
+                                }
+                            });
+                        });
+                }
+                let waiting = self.toasts.waiting();
+                if waiting > 0 {
+                    ui.weak(format!("+{} more", waiting));
+                }
+            });
+
+        for id in dismissed {
+            self.toasts.dismiss(id);
+        }
+        // Come back to take the next one down even if nothing else happens
+        if let Some(next) = self.toasts.next_expiry(now) {
+            ctx.request_repaint_after(next);
+        }
+    }
+
+    /// A switch in the corner between standard refresh and power saver,
+    /// remembered for next time
+    fn render_power_saver_toggle(&mut self, ctx: &egui::Context) {
+        egui::Area::new(egui::Id::new("power_saver_toggle"))
+            .anchor(egui::Align2::LEFT_BOTTOM, [8.0, -8.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let mut power_saver = self.gui_settings.refresh_mode == RefreshMode::PowerSaver;
+                let toggled = ui
+                    .checkbox(&mut power_saver, "🔋 Power saver")
+                    .on_hover_text(
+                        "Check the server less often and only redraw when something happens",
+                    )
+                    .changed();
+                if toggled {
+                    self.gui_settings.refresh_mode = if power_saver {
+                        RefreshMode::PowerSaver
+                    } else {
+                        RefreshMode::Standard
+                    };
+                    if let Err(e) = self.gui_settings.save() {
+                        eprintln!("{}", e);
+                    }
+                }
+                self.render_moment_controls(ui);
+            });
+    }
+
+    /// Record moments on or off, and the button that saves the last few
+    /// seconds as a GIF
+    fn render_moment_controls(&mut self, ui: &mut egui::Ui) {
+        let mut recording = self.gui_settings.record_moments;
+        let toggled = ui
+            .checkbox(&mut recording, "🎥 Record moments")
+            .on_hover_text(
+                "Keep the last few seconds of the window to share as a GIF. \
+                 Redraws several times a second while on",
+            )
+            .changed();
+        if toggled {
+            self.gui_settings.record_moments = recording;
+            self.moment_recorder = recording.then(MomentRecorder::from_env);
+            if let Err(e) = self.gui_settings.save() {
+                eprintln!("{}", e);
+            }
+        }
+
+        if let Some(recorder) = &self.moment_recorder {
+            let can_share = !recorder.is_empty() && self.moment_save.is_none();
+            if ui
+                .add_enabled(can_share, egui::Button::new("📸 Share moment"))
+                .on_hover_text("Save the last few seconds as a GIF")
+                .clicked()
+            {
+                let frames = recorder.frames();
+                let (sender, receiver) = std::sync::mpsc::channel();
+                std::thread::spawn(move || {
+                    let saved = moments_dir()
+                        .and_then(|dir| save_moment(&frames, &dir, chrono::Local::now()));
+                    let _ = sender.send(saved);
+                });
+                self.moment_save = Some(receiver);
+            }
+        }
+
+        if let Some(save) = &self.moment_save {
+            match save.try_recv() {
+                Ok(saved) => {
+                    let message = match saved {
+                        Ok(path) => format!("Saved moment to {}", path.display()),
+                        Err(e) => e,
+                    };
+                    self.moment_message = Some((message, std::time::Instant::now()));
+                    self.moment_save = None;
                 },
-                DestinationInfo {
-                    airport_id: "ORD".to_string(),
-                    airport_name: "Chicago O'Hare".to_string(),
-                    distance: 1188.0,
-                    fuel_required: 60,
-                    can_travel: true,
-                    fuel_price: 50,
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Saving moment...");
+                    });
+                    ui.ctx()
+                        .request_repaint_after(std::time::Duration::from_millis(200));
                 },
-            ],
-            statistics: StatisticsInfo {
-                total_revenue: 0,
-                total_expenses: 0,
-                net_profit: 0,
-                cargo_trades: 0,
-                fuel_purchased: 0,
-                distances_traveled: 0.0,
-                airports_visited: vec![],
-                best_single_trade: 0,
-                most_profitable_cargo: "".to_string(),
-                efficiency_score: 0.0,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => self.moment_save = None,
+            }
+        }
+
+        self.moment_message
+            .take_if(|(_, saved_at)| saved_at.elapsed() >= MOMENT_MESSAGE_DURATION);
+        if let Some((message, saved_at)) = &self.moment_message {
+            ui.label(message);
+            ui.ctx()
+                .request_repaint_after(MOMENT_MESSAGE_DURATION.saturating_sub(saved_at.elapsed()));
+        }
+    }
+
+    /// While recording, keep the screenshot that came in since the last
+    /// frame and ask for the next one
+    fn capture_moment_frame(&mut self, ctx: &egui::Context) {
+        let Some(recorder) = &mut self.moment_recorder else {
+            return;
+        };
+        let now = std::time::Instant::now();
+        ctx.input(|input| {
+            for event in &input.raw.events {
+                if let egui::Event::Screenshot { image, .. } = event {
+                    recorder.push(image, now);
+                }
+            }
+        });
+        if recorder.request_frame(now) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot);
+        }
+        // Keep capturing while nothing else redraws
+        ctx.request_repaint_after(MomentRecorder::frame_interval());
+    }
+
+    fn render_notification_bell(&mut self, ctx: &egui::Context, session: &GameSession) {
+        egui::Area::new(egui::Id::new("notification_bell"))
+            .anchor(egui::Align2::RIGHT_TOP, [-12.0, 8.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    // Hosts can step out to watch all their rooms; they stay
+                    // in this one
+                    let hosting = self.game_state.as_ref().is_some_and(|state| {
+                        state.room_info.host_player_name == session.player_name
+                    });
+                    if hosting && ui.button("🖥").on_hover_text("Rooms you host").clicked() {
+                        self.reset_room_state();
+                        self.host_dashboard_scene.open(&session.player_name);
+                        self.app_state = AppState::HostDashboard;
+                    }
+
+                    let bell = if self.unread_notifications > 0 {
+                        egui::RichText::new(format!("🔔 {}", self.unread_notifications))
+                            .strong()
+                            .color(egui::Color32::from_rgb(220, 120, 20))
+                    } else {
+                        egui::RichText::new("🔔")
+                    };
+                    if ui
+                        .button(bell)
+                        .on_hover_text(
+                            "Mentions, announcements, price alerts, arrivals and deadlines",
+                        )
+                        .clicked()
+                    {
+                        if self.mentions_inbox.is_some() {
+                            self.mentions_inbox = None;
+                        } else {
+                            self.open_mentions_inbox(session);
+                        }
+                    }
+                });
+            });
+
+        let Some(mentions) = &self.mentions_inbox else {
+            return;
+        };
+        let mut open = true;
+        egui::Window::new("🔔 Notifications")
+            .open(&mut open)
+            .default_width(360.0)
+            .anchor(egui::Align2::RIGHT_TOP, [-12.0, 40.0])
+            .show(ctx, |ui| {
+                if mentions.is_empty() {
+                    ui.label(
+                        "Nothing yet. Pilots can reach you with @name, and price alerts, \
+                         arrivals and turn deadlines show up here too.",
+                    );
+                    return;
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for mention in mentions {
+                            let fill = if mention.read {
+                                egui::Color32::from_rgb(245, 245, 250)
+                            } else {
+                                egui::Color32::from_rgb(255, 244, 214)
+                            };
+                            egui::Frame::none()
+                                .fill(fill)
+                                .inner_margin(egui::Margin::same(6.0))
+                                .outer_margin(egui::Margin::symmetric(0.0, 2.0))
+                                .rounding(egui::Rounding::same(4.0))
+                                .show(ui, |ui| {
+                                    let place = if mention.kind == NotificationKind::Announcement {
+                                        "to the room · ".to_string()
+                                    } else if mention.airport_id.is_empty() {
+                                        String::new()
+                                    } else {
+                                        format!("at {} · ", mention.airport_id)
+                                    };
+                                    ui.horizontal(|ui| {
+                                        ui.strong(&mention.author_name);
+                                        ui.label(
+                                            egui::RichText::new(format!(
+                                                "{}{}",
+                                                place,
+                                                mention
+                                                    .created_at
+                                                    .with_timezone(&chrono::Local)
+                                                    .format("%H:%M")
+                                            ))
+                                            .small()
+                                            .color(egui::Color32::GRAY),
+                                        );
+                                    });
+                                    ui.label(&mention.content);
+                                });
+                        }
+                    });
+            });
+        if !open {
+            self.mentions_inbox = None;
+        }
+    }
+
+    /// Show the player's notifications, unread ones highlighted, then mark
+    /// them read
+    fn open_mentions_inbox(&mut self, session: &GameSession) {
+        match self
+            .api_client
+            .get_notifications_sync(session.room_id, session.player_id)
+        {
+            Ok(response) => {
+                if response.unread > 0
+                    && self
+                        .api_client
+                        .mark_notifications_read_sync(session.room_id, session.player_id, vec![])
+                        .is_ok()
+                {
+                    self.unread_notifications = 0;
+                }
+                self.mentions_inbox = Some(response.notifications);
             },
-            turn_number: 1,
-            world_time: Utc::now(),
+            Err(e) => eprintln!("Failed to load notifications: {}", e),
+        }
+    }
+
+    /// Check the server for a new turn, new board messages or a winner and,
+    /// if the window is in the background, let the player know. The room
+    /// state fetched here also refreshes the player's known market prices.
+    fn poll_notifications(&mut self, ctx: &egui::Context, session: &GameSession) {
+        // No point asking while the connection is down
+        if self.action_queue.offline {
+            return;
+        }
+
+        let mut notifications = Vec::new();
+
+        // The room state refresh_game_state just fetched
+        if let Some(state) = self.game_state.clone() {
+            notifications.extend(
+                self.notification_watcher
+                    .observe_turn(state.world_tick, state.turn_number),
+            );
+            notifications.extend(
+                self.notification_watcher
+                    .observe_winner(state.winner.as_ref()),
+            );
+            // Only posts get the "you were mentioned" popup; alerts and
+            // arrivals wait in the inbox
+            let unread_posts = state
+                .notifications
+                .iter()
+                .filter(|notification| {
+                    matches!(
+                        notification.kind,
+                        NotificationKind::Mention | NotificationKind::Announcement
+                    )
+                })
+                .count();
+            notifications.extend(self.notification_watcher.observe_mentions(unread_posts));
+            // Unlocks get a toast in the window as well as the usual popup
+            let unlocked = self
+                .notification_watcher
+                .observe_achievements(&state.achievements);
+            let now = std::time::Instant::now();
+            self.achievement_toasts
+                .extend(unlocked.iter().cloned().map(|toast| (toast, now)));
+            notifications.extend(unlocked);
+            self.unread_notifications = state.unread_notifications;
+            // Everyone else voted us into the next round
+            if self.room_winner.is_some() && state.winner.is_none() {
+                self.start_next_round(state.current_market.airport_id.clone());
+            }
+            self.room_winner = state.winner;
+            self.room_awards = state.awards;
+            self.rematch_votes = state.rematch_votes;
+            self.scene_state.known_markets = state.known_markets;
+            self.scene_state.active_events = state.active_events;
+            self.scene_state.active_modifiers = state.active_modifiers;
+            self.scene_state.destinations = state
+                .available_destinations
+                .into_iter()
+                .map(|destination| (destination.airport_id.clone(), destination))
+                .collect();
+            self.scene_state.flight_range = Some(state.flight_range);
+        }
+
+        if let Ok(response) =
+            self.api_client
+                .get_messages_sync(session.room_id, session.player_id, None)
+        {
+            notifications.extend(self.notification_watcher.observe_messages(
+                &response.airport_id,
+                &response.messages,
+                session.player_id,
+            ));
+        }
+
+        // Only interrupt the player when they're looking at something else
+        let focused = ctx.input(|i| i.viewport().focused).unwrap_or(true);
+        if notifications.is_empty() || focused {
+            return;
+        }
+
+        ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+            egui::UserAttentionType::Informational,
+        ));
+        for notification in &notifications {
+            self.notifier.notify(notification);
+        }
+    }
+
+    /// Fetch the room from the server, which has the final say on the
+    /// player's money, fuel, cargo and whereabouts
+    fn refresh_game_state(&mut self, session: &GameSession) {
+        self.last_state_refresh = std::time::Instant::now();
+
+        // Keep showing the last state we had until the server answers again
+        let Ok(state) = self
+            .api_client
+            .get_room_state_sync(session.room_id, session.player_id)
+        else {
+            return;
         };
 
-        self.game_state = Some(mock_state);
-        // Only clear cache if we don't have one yet - keep it stable for UI consistency
-        // Cache will be updated in place through the update_converted_state_player_static method
+        if let Some(converted_state) = &mut self.converted_game_state {
+            Self::update_converted_state_player_static(converted_state, &state, session);
+        } else {
+            self.converted_game_state = self.convert_multiplayer_to_game_state(&state, session);
+        }
+        // A flight or transfer the server let through lands us somewhere new
+        if let Some(converted_state) = &self.converted_game_state {
+            let airport_id = &converted_state.player.current_airport;
+            if self.scene_state.current_scene != Scene::Airport(airport_id.clone()) {
+                self.scene_state.travel_to_airport(airport_id.clone());
+            }
+        }
+        self.game_state = Some(state);
     }
 
     fn convert_multiplayer_to_game_state(
@@ -294,7 +1004,9 @@ impl KzrkEguiApp {
         multiplayer_state: &MultiplayerGameStateResponse,
         session: &GameSession,
     ) -> Option<crate::systems::game::GameState> {
-        use crate::models::{Player, cargo::CargoInventory};
+        use crate::models::{
+            Player, cargo::CargoInventory, quota::TurnTrades, streak::TradeStreak,
+        };
         use crate::systems::game::GameState;
         use std::collections::HashMap;
 
@@ -319,11 +1031,102 @@ impl KzrkEguiApp {
             cargo_inventory,
             max_cargo_weight: my_player.max_cargo_weight,
             fuel_efficiency: my_player.fuel_efficiency,
+            // The server only tells us our standing at the current airport
+            broker_volume: multiplayer_state
+                .current_market
+                .broker
+                .iter()
+                .map(|broker| (my_player.current_airport.clone(), broker.volume))
+                .collect(),
+            // Overnight security and hangar upgrades are settled on the server
+            hangar_parking: false,
+            security_upgrade: false,
+            aircraft: Default::default(),
+            loans: Default::default(),
+            streak: TradeStreak {
+                current: my_player.streak,
+                best: multiplayer_state.statistics.best_streak,
+            },
+            // Worked back from what the broker says is left of this turn's caps
+            turn_trades: multiplayer_state
+                .current_market
+                .broker
+                .as_ref()
+                .and_then(|broker| {
+                    let cap = broker.units_per_turn?;
+                    let used = |left: &HashMap<String, u32>| {
+                        left.iter()
+                            .map(|(cargo_id, left)| (cargo_id.clone(), cap.saturating_sub(*left)))
+                            .collect()
+                    };
+                    Some(TurnTrades {
+                        bought: used(&broker.buys_left),
+                        sold: used(&broker.sales_left),
+                    })
+                })
+                .unwrap_or_default(),
         };
 
-        // Load the default airports and cargo types (same as single-player)
-        let airports = crate::data::airports::get_default_airports();
-        let cargo_types = crate::data::cargo_types::get_default_cargo_types();
+        // Use the room's own airports and cargo types, falling back to the
+        // server's reference data (cached between polls) and then the
+        // defaults for servers that don't send them
+        let airports: HashMap<String, crate::models::Airport> =
+            if multiplayer_state.airports.is_empty() {
+                self.api_client
+                    .get_airports_sync()
+                    .map(|airports| {
+                        airports
+                            .into_iter()
+                            .map(|airport| {
+                                let converted = crate::models::Airport::new(
+                                    &airport.id,
+                                    &airport.name,
+                                    airport.coordinates,
+                                    airport.base_fuel_price,
+                                    Vec::new(),
+                                    Vec::new(),
+                                    1.0,
+                                )
+                                .with_fuel_economy(airport.fuel_economy);
+                                (airport.id, converted)
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_else(|_| crate::data::airports::get_default_airports())
+            } else {
+                multiplayer_state
+                    .airports
+                    .iter()
+                    .map(|airport| (airport.id.clone(), airport.clone()))
+                    .collect()
+            };
+        let cargo_types: HashMap<String, crate::models::CargoType> =
+            if multiplayer_state.cargo_types.is_empty() {
+                self.api_client
+                    .get_cargo_sync()
+                    .map(|cargo_types| {
+                        cargo_types
+                            .into_iter()
+                            .map(|cargo| {
+                                let converted = crate::models::CargoType::new(
+                                    &cargo.id,
+                                    &cargo.name,
+                                    cargo.base_price,
+                                    cargo.weight_per_unit,
+                                    cargo.volatility,
+                                );
+                                (cargo.id, converted)
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_else(|_| crate::data::cargo_types::get_default_cargo_types())
+            } else {
+                multiplayer_state
+                    .cargo_types
+                    .iter()
+                    .map(|cargo_type| (cargo_type.id.clone(), cargo_type.clone()))
+                    .collect()
+            };
 
         // Create markets for each airport with current multiplayer prices
         let mut markets = HashMap::new();
@@ -333,10 +1136,7 @@ impl KzrkEguiApp {
             // Set cargo prices from multiplayer data if available
             if airport_id == &multiplayer_state.current_market.airport_id {
                 // Use the current market prices from multiplayer state
-                market.update_fuel_price(multiplayer_state.current_market.fuel_price);
-                for (cargo_type, price) in &multiplayer_state.current_market.cargo_prices {
-                    market.set_cargo_price(cargo_type, *price);
-                }
+                apply_market_info(&mut market, &multiplayer_state.current_market);
             } else {
                 // Set default prices for other airports
                 for (cargo_type_id, cargo_type) in &cargo_types {
@@ -346,32 +1146,21 @@ impl KzrkEguiApp {
             markets.insert(airport_id.clone(), market);
         }
 
-        // Create distance cache with pre-calculated distances between all airports
-        let mut distance_cache = HashMap::new();
-        for (from_id, from_airport) in &airports {
-            for (to_id, to_airport) in &airports {
-                if from_id != to_id {
-                    // Calculate distance using the airport coordinates
-                    let distance =
-                        Self::calculate_distance(from_airport.coordinates, to_airport.coordinates);
-                    let key = format!("{}-{}", from_id, to_id);
-                    distance_cache.insert(key, distance);
-                }
-            }
-        }
-
         Some(GameState {
             player,
             airports,
             cargo_types,
             markets,
-            distance_cache,
+            // Great-circle, so fuel needs match what the server charges
+            distances: DistanceCache::new(),
             turn_number: multiplayer_state.turn_number,
             cheat_mode: false,
             stats: crate::models::GameStats::new(5000), // Default starting money
             win_condition_money: 100000,                // Default win condition
             active_events: Vec::new(),
             message_board: crate::models::MessageBoard::new(50),
+            price_alerts: Default::default(),
+            calendar: multiplayer_state.calendar,
         })
     }
 
@@ -380,6 +1169,19 @@ impl KzrkEguiApp {
         multiplayer_state: &MultiplayerGameStateResponse,
         session: &GameSession,
     ) {
+        // Pick up airports the room added, moved or dropped
+        if !multiplayer_state.airports.is_empty() {
+            let airports: std::collections::HashMap<String, crate::models::Airport> =
+                multiplayer_state
+                    .airports
+                    .iter()
+                    .map(|airport| (airport.id.clone(), airport.clone()))
+                    .collect();
+            if airports != converted_state.airports {
+                converted_state.set_airports(airports);
+            }
+        }
+
         // Find the current player in multiplayer state
         if let Some(my_player) = multiplayer_state
             .players
@@ -393,6 +1195,7 @@ impl KzrkEguiApp {
             if converted_state.player.current_airport != my_player.current_airport {
                 converted_state.player.current_airport = my_player.current_airport.clone();
             }
+            converted_state.player.streak.current = my_player.streak;
             if converted_state.player.fuel != my_player.fuel {
                 converted_state.player.fuel = my_player.fuel;
             }
@@ -414,215 +1217,36 @@ impl KzrkEguiApp {
                 }
             }
 
+            converted_state.calendar = multiplayer_state.calendar;
             // Only update turn number if it changed
             if converted_state.turn_number != multiplayer_state.turn_number {
                 converted_state.turn_number = multiplayer_state.turn_number;
             }
-        }
-    }
 
-    fn calculate_distance(coord1: (f64, f64), coord2: (f64, f64)) -> f64 {
-        // Simple Euclidean distance calculation (in a real app, you'd use great circle distance)
-        let dx = coord1.0 - coord2.0;
-        let dy = coord1.1 - coord2.1;
-        // Convert to approximate kilometers (rough approximation)
-        let distance_deg = (dx * dx + dy * dy).sqrt();
-        distance_deg * 111.0 // Rough conversion from degrees to km
-    }
+            if let Some(broker) = &multiplayer_state.current_market.broker {
+                converted_state
+                    .player
+                    .broker_volume
+                    .insert(my_player.current_airport.clone(), broker.volume);
+            }
+        }
 
-    fn render_multiplayer_airport_scene_static(
-        converted_state: &mut crate::systems::game::GameState,
-        scene_state: &mut SceneState,
-        session: &GameSession,
-        ctx: &egui::Context,
-        api_client: &GameApiClient,
-    ) -> Option<std::time::Instant> {
-        // Store the original state to detect changes
-        let original_money = converted_state.player.money;
-        let original_fuel = converted_state.player.fuel;
-        let original_location = converted_state.player.current_airport.clone();
-        let original_cargo = converted_state.player.cargo_inventory.clone();
-
-        // Render the airport scene (always uses API for message persistence)
-        crate::ui::scenes::airport::AirportScene::render(
-            converted_state,
-            scene_state,
-            ctx,
-            api_client,
-            session,
-        );
-
-        // Track changes for action detection (API calls would go here in full implementation)
-
-        // Return timestamp if any action occurred
-        let action_occurred = converted_state.player.money != original_money
-            || converted_state.player.fuel != original_fuel
-            || converted_state.player.current_airport != original_location
-            || converted_state.player.cargo_inventory != original_cargo;
-
-        if action_occurred {
-            // Action detected - would make appropriate API calls in full implementation
-            Some(std::time::Instant::now())
-        } else {
-            None
+        // Prices where the player is standing, after their own trades too
+        let current_market = &multiplayer_state.current_market;
+        if let Some(market) = converted_state.markets.get_mut(&current_market.airport_id) {
+            apply_market_info(market, current_market);
         }
     }
+}
 
-    #[allow(dead_code)]
-    fn render_multiplayer_airport(
-        &mut self,
-        ctx: &egui::Context,
-        session: &GameSession,
-        game_state: &MultiplayerGameStateResponse,
-    ) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("✈️ KZRK Aviation Trading Game");
-            ui.heading(format!("🏢 Room: {}", game_state.room_info.name));
-            ui.label(format!(
-                "📍 Current Location: {}",
-                game_state.current_market.airport_name
-            ));
-            ui.separator();
-
-            // Player list
-            ui.collapsing("👥 Players in Room", |ui| {
-                for player in &game_state.players {
-                    ui.horizontal(|ui| {
-                        if player.id == Some(session.player_id) {
-                            ui.label("➤");
-                        } else {
-                            ui.label("  ");
-                        }
-                        ui.label(&player.name);
-                        ui.label(format!("@{}", player.current_airport));
-                        ui.label(format!("${}", player.money));
-                        if player.is_host == Some(true) {
-                            ui.label("👑 Host");
-                        }
-                        if player.is_online == Some(false) {
-                            ui.colored_label(egui::Color32::GRAY, "Offline");
-                        }
-                    });
-                }
-            });
-
-            ui.add_space(10.0);
-
-            // Current player info
-            if let Some(my_player) = game_state
-                .players
-                .iter()
-                .find(|p| p.id == Some(session.player_id))
-            {
-                ui.horizontal(|ui| {
-                    ui.label(format!("💰 Money: ${}", my_player.money));
-                    ui.label(format!(
-                        "⛽ Fuel: {}/{}",
-                        my_player.fuel, my_player.max_fuel
-                    ));
-                    ui.label(format!(
-                        "📦 Cargo: {}/{} kg",
-                        my_player.cargo_weight, my_player.max_cargo_weight
-                    ));
-                });
-                ui.separator();
-                ui.add_space(10.0);
-            }
-
-            // Market info
-            ui.heading(format!(
-                "🏪 {} Market",
-                game_state.current_market.airport_name
-            ));
-            ui.label(format!(
-                "⛽ Fuel Price: ${}/unit",
-                game_state.current_market.fuel_price
-            ));
-
-            ui.collapsing("📈 Cargo Trading", |ui| {
-                for (cargo_type, price) in &game_state.current_market.cargo_prices {
-                    ui.horizontal(|ui| {
-                        ui.label(cargo_type);
-                        ui.label(format!("${}/unit", price));
-
-                        // Buy cargo
-                        if ui.button("📈 Buy 1").clicked() {
-                            // TODO: Implement buy cargo via API
-                            println!("Buy 1 unit of {} for ${}", cargo_type, price);
-                        }
-
-                        // Sell cargo (if player has some)
-                        if let Some(my_player) = game_state
-                            .players
-                            .iter()
-                            .find(|p| p.id == Some(session.player_id))
-                            && let Some(&quantity) = my_player.cargo_inventory.get(cargo_type)
-                            && quantity > 0
-                        {
-                            ui.label(format!("Have: {}", quantity));
-                            if ui.button("📉 Sell 1").clicked() {
-                                // TODO: Implement sell cargo via API
-                                println!("Sell 1 unit of {} for ${}", cargo_type, price);
-                            }
-                        }
-                    });
-                }
-
-                ui.add_space(5.0);
-
-                // Fuel purchase section
-                ui.horizontal(|ui| {
-                    ui.label("⛽ Fuel:");
-                    ui.label(format!("${}/unit", game_state.current_market.fuel_price));
-                    if ui.button("⛽ Buy 10 units").clicked() {
-                        // TODO: Implement fuel purchase via API
-                        println!(
-                            "Buy 10 units of fuel for ${}",
-                            game_state.current_market.fuel_price * 10
-                        );
-                    }
-                    if ui.button("⛽ Fill tank").clicked() {
-                        // TODO: Implement fill tank via API
-                        println!("Fill fuel tank");
-                    }
-                });
-            });
-
-            ui.add_space(10.0);
-
-            // Available destinations
-            ui.collapsing("✈️ Available Destinations", |ui| {
-                for dest in &game_state.available_destinations {
-                    ui.horizontal(|ui| {
-                        ui.label(&dest.airport_name);
-                        ui.label(format!("{:.0} km", dest.distance));
-                        ui.label(format!("⛽ {}", dest.fuel_required));
-                        if dest.can_travel {
-                            if ui.button("✈️ Fly").clicked() {
-                                // TODO: Implement travel action via API
-                                println!(
-                                    "Flying to {} (fuel cost: {})",
-                                    dest.airport_name, dest.fuel_required
-                                );
-                            }
-                        } else {
-                            ui.colored_label(egui::Color32::GRAY, "Not enough fuel");
-                        }
-                    });
-                }
-            });
-
-            ui.add_space(20.0);
-
-            // Disconnect button
-            if ui.button("🔌 Disconnect").clicked() {
-                // TODO: Leave room via API - for now just disconnect
-                println!("Leaving room: {}", game_state.room_info.name);
-                self.app_state = AppState::ServerConnection;
-                // Keep API client active for reconnection
-                self.game_state = None;
-                self.converted_game_state = None; // Clear cached state
-            }
-        });
+/// Bring a local market in line with what the server says about it
+fn apply_market_info(market: &mut crate::models::Market, info: &MarketInfo) {
+    market.update_fuel_price(info.fuel_price);
+    for (cargo_type, price) in &info.cargo_prices {
+        market.set_cargo_price(cargo_type, *price);
+    }
+    if let Some(stock) = info.fuel_stock {
+        market.fuel_stock = stock;
     }
+    market.fuel_outage = info.fuel_outage;
 }
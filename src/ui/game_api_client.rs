@@ -1,9 +1,45 @@
-#[cfg(feature = "gui")]
+#[cfg(any(feature = "gui", feature = "tui"))]
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
 use reqwest;
 use serde_json;
 use uuid::Uuid;
 
-use crate::api::models::*;
+#[cfg(any(feature = "gui", feature = "tui"))]
+use crate::models::{MessageTopic, aircraft::AircraftUpgrade};
+#[cfg(any(feature = "gui", feature = "tui"))]
+use crate::systems::{
+    TravelQuote, auto_refuel::AutoRefuel, events::EventSpec, insurance::InsuranceClaim,
+    loadouts::CargoLoadout, matchmaking::MatchPreferences, multiplayer::GameSpeed,
+    offers::OfferTerms,
+};
+use crate::{
+    api::{errors::ErrorCode, models::*},
+    models::locale::{Locale, Localized},
+    systems::RoomTemplate,
+};
+
+/// Asks for the server's messages in the player's language
+fn accept_language() -> String {
+    format!("Accept-Language: {}", Locale::current().tag())
+}
+
+/// How often the client asks the server whether its airports and cargo
+/// have changed
+pub const SERVER_INFO_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Airports and cargo as last fetched, and the reference version they came
+/// from. Dropped whenever the server reports a different version.
+#[derive(Debug, Default)]
+struct ReferenceCache {
+    reference_version: Option<String>,
+    checked_at: Option<Instant>,
+    airports: Option<Vec<AirportInfo>>,
+    cargo: Option<Vec<CargoInfo>>,
+}
 
 #[derive(Clone)]
 pub struct GameApiClient {
@@ -11,6 +47,8 @@ pub struct GameApiClient {
     client: reqwest::Client,
     #[allow(dead_code)]
     base_url: String,
+    #[allow(dead_code)]
+    reference_cache: Arc<Mutex<ReferenceCache>>,
 }
 
 #[derive(Debug, Clone)]
@@ -20,6 +58,13 @@ pub enum ApiError {
     NetworkError(String),
     ParseError(String),
     ServerError(String),
+    /// The room's action cooldown turned the request away for now
+    Cooldown(String),
+    /// The action has to be sent again with force, giving up these
+    ConfirmationRequired {
+        message: String,
+        obligations: Vec<String>,
+    },
 }
 
 impl From<reqwest::Error> for ApiError {
@@ -39,7 +84,33 @@ impl std::fmt::Display for ApiError {
         match self {
             ApiError::NetworkError(msg) => write!(f, "Network error: {}", msg),
             ApiError::ParseError(msg) => write!(f, "Parse error: {}", msg),
-            ApiError::ServerError(msg) => write!(f, "Server error: {}", msg),
+            ApiError::ServerError(msg)
+            | ApiError::Cooldown(msg)
+            | ApiError::ConfirmationRequired { message: msg, .. } => {
+                write!(f, "Server error: {}", msg)
+            },
+        }
+    }
+}
+
+/// The server's error in the player's language, telling cooldowns and
+/// confirmations apart by their code
+impl From<ErrorResponse> for ApiError {
+    fn from(error: ErrorResponse) -> Self {
+        let message = error.message_in(Locale::current());
+        if error.error == ErrorCode::ActionCooldown.as_str() {
+            ApiError::Cooldown(message)
+        } else if error.error == ErrorCode::ConfirmationRequired.as_str() {
+            let obligations = error
+                .details
+                .and_then(|details| serde_json::from_value(details["obligations"].clone()).ok())
+                .unwrap_or_default();
+            ApiError::ConfirmationRequired {
+                message,
+                obligations,
+            }
+        } else {
+            ApiError::ServerError(message)
         }
     }
 }
@@ -60,6 +131,7 @@ impl GameApiClient {
         Self {
             client: reqwest::Client::new(),
             base_url,
+            reference_cache: Arc::default(),
         }
     }
 
@@ -79,11 +151,14 @@ impl GameApiClient {
         name: String,
         host_player_name: String,
         max_players: Option<usize>,
+        template_id: Option<Uuid>,
     ) -> Result<CreateRoomResponse, ApiError> {
         let request = CreateRoomRequest {
             name,
             host_player_name,
             max_players,
+            template_id,
+            settings: None,
         };
 
         let response = self
@@ -95,13 +170,29 @@ impl GameApiClient {
 
         if !response.status().is_success() {
             let error: ErrorResponse = response.json().await?;
-            return Err(ApiError::ServerError(error.message));
+            return Err(ApiError::from(error));
         }
 
         let result: CreateRoomResponse = response.json().await?;
         Ok(result)
     }
 
+    pub async fn list_templates(&self) -> Result<Vec<RoomTemplate>, ApiError> {
+        let response = self
+            .client
+            .get(format!("{}/templates", self.base_url))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error: ErrorResponse = response.json().await?;
+            return Err(ApiError::from(error));
+        }
+
+        let result: Vec<RoomTemplate> = response.json().await?;
+        Ok(result)
+    }
+
     pub async fn list_rooms(&self) -> Result<Vec<RoomInfo>, ApiError> {
         let response = self
             .client
@@ -111,7 +202,7 @@ impl GameApiClient {
 
         if !response.status().is_success() {
             let error: ErrorResponse = response.json().await?;
-            return Err(ApiError::ServerError(error.message));
+            return Err(ApiError::from(error));
         }
 
         let result: Vec<RoomInfo> = response.json().await?;
@@ -138,7 +229,7 @@ impl GameApiClient {
 
         if !response.status().is_success() {
             let error: ErrorResponse = response.json().await?;
-            return Err(ApiError::ServerError(error.message));
+            return Err(ApiError::from(error));
         }
 
         let result: JoinRoomResponse = response.json().await?;
@@ -149,19 +240,20 @@ impl GameApiClient {
         &self,
         room_id: Uuid,
         player_id: Uuid,
+        force: bool,
     ) -> Result<LeaveRoomResponse, ApiError> {
         let response = self
             .client
             .post(format!(
-                "{}/rooms/{}/players/{}/leave",
-                self.base_url, room_id, player_id
+                "{}/rooms/{}/players/{}/leave?force={}",
+                self.base_url, room_id, player_id, force
             ))
             .send()
             .await?;
 
         if !response.status().is_success() {
             let error: ErrorResponse = response.json().await?;
-            return Err(ApiError::ServerError(error.message));
+            return Err(ApiError::from(error));
         }
 
         let result: LeaveRoomResponse = response.json().await?;
@@ -185,7 +277,7 @@ impl GameApiClient {
 
         if !response.status().is_success() {
             let error: ErrorResponse = response.json().await?;
-            return Err(ApiError::ServerError(error.message));
+            return Err(ApiError::from(error));
         }
 
         let result: MultiplayerGameStateResponse = response.json().await?;
@@ -213,7 +305,7 @@ impl GameApiClient {
 
         if !response.status().is_success() {
             let error: ErrorResponse = response.json().await?;
-            return Err(ApiError::ServerError(error.message));
+            return Err(ApiError::from(error));
         }
 
         let result: PlayerTravelResponse = response.json().await?;
@@ -247,7 +339,7 @@ impl GameApiClient {
 
         if !response.status().is_success() {
             let error: ErrorResponse = response.json().await?;
-            return Err(ApiError::ServerError(error.message));
+            return Err(ApiError::from(error));
         }
 
         let result: PlayerTradeResponse = response.json().await?;
@@ -274,7 +366,7 @@ impl GameApiClient {
 
         if !response.status().is_success() {
             let error: ErrorResponse = response.json().await?;
-            return Err(ApiError::ServerError(error.message));
+            return Err(ApiError::from(error));
         }
 
         let result: PlayerFuelResponse = response.json().await?;
@@ -282,7 +374,7 @@ impl GameApiClient {
     }
 
     // Reference data
-    pub async fn get_available_airports(&self) -> Result<serde_json::Value, ApiError> {
+    pub async fn get_available_airports(&self) -> Result<Vec<AirportInfo>, ApiError> {
         let response = self
             .client
             .get(format!("{}/airports", self.base_url))
@@ -291,14 +383,14 @@ impl GameApiClient {
 
         if !response.status().is_success() {
             let error: ErrorResponse = response.json().await?;
-            return Err(ApiError::ServerError(error.message));
+            return Err(ApiError::from(error));
         }
 
-        let result: serde_json::Value = response.json().await?;
+        let result: Vec<AirportInfo> = response.json().await?;
         Ok(result)
     }
 
-    pub async fn get_available_cargo(&self) -> Result<serde_json::Value, ApiError> {
+    pub async fn get_available_cargo(&self) -> Result<Vec<CargoInfo>, ApiError> {
         let response = self
             .client
             .get(format!("{}/cargo", self.base_url))
@@ -307,74 +399,56 @@ impl GameApiClient {
 
         if !response.status().is_success() {
             let error: ErrorResponse = response.json().await?;
-            return Err(ApiError::ServerError(error.message));
+            return Err(ApiError::from(error));
         }
 
-        let result: serde_json::Value = response.json().await?;
+        let result: Vec<CargoInfo> = response.json().await?;
         Ok(result)
     }
 
     // Synchronous versions for GUI using curl (when gui feature is enabled)
-    #[cfg(feature = "gui")]
+    #[cfg(any(feature = "gui", feature = "tui"))]
     pub fn list_rooms_sync(&self) -> Result<Vec<RoomInfo>, ApiError> {
-        let output = std::process::Command::new("curl")
-            .arg("-s") // silent
-            .arg("-X")
-            .arg("GET")
-            .arg(format!("{}/rooms", self.base_url))
-            .output()
-            .map_err(|e| ApiError::NetworkError(format!("Failed to execute curl: {}", e)))?;
-
-        if !output.status.success() {
-            return Err(ApiError::NetworkError("Curl command failed".to_string()));
-        }
-
-        let response_text = String::from_utf8(output.stdout)
-            .map_err(|e| ApiError::ParseError(format!("Invalid UTF-8 response: {}", e)))?;
+        self.get_sync("/rooms")
+    }
 
-        let result: Vec<RoomInfo> = serde_json::from_str(&response_text)?;
-        Ok(result)
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn list_templates_sync(&self) -> Result<Vec<RoomTemplate>, ApiError> {
+        self.get_sync("/templates")
     }
 
-    #[cfg(feature = "gui")]
+    #[cfg(any(feature = "gui", feature = "tui"))]
     pub fn create_room_sync(
         &self,
         name: String,
         host_player_name: String,
         max_players: Option<usize>,
+        template_id: Option<Uuid>,
     ) -> Result<CreateRoomResponse, ApiError> {
         let request = CreateRoomRequest {
             name,
             host_player_name,
             max_players,
+            template_id,
+            settings: None,
         };
+        self.post_sync("/rooms", &request)
+    }
 
-        let request_json = serde_json::to_string(&request)?;
-
-        let output = std::process::Command::new("curl")
-            .arg("-s") // silent
-            .arg("-X")
-            .arg("POST")
-            .arg("-H")
-            .arg("Content-Type: application/json")
-            .arg("-d")
-            .arg(request_json)
-            .arg(format!("{}/rooms", self.base_url))
-            .output()
-            .map_err(|e| ApiError::NetworkError(format!("Failed to execute curl: {}", e)))?;
-
-        if !output.status.success() {
-            return Err(ApiError::NetworkError("Curl command failed".to_string()));
-        }
-
-        let response_text = String::from_utf8(output.stdout)
-            .map_err(|e| ApiError::ParseError(format!("Invalid UTF-8 response: {}", e)))?;
-
-        let result: CreateRoomResponse = serde_json::from_str(&response_text)?;
-        Ok(result)
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn quick_match_sync(
+        &self,
+        player_name: String,
+        preferences: MatchPreferences,
+    ) -> Result<QuickMatchResponse, ApiError> {
+        let request = QuickMatchRequest {
+            player_name,
+            preferences,
+        };
+        self.post_sync("/matchmaking/quick-match", &request)
     }
 
-    #[cfg(feature = "gui")]
+    #[cfg(any(feature = "gui", feature = "tui"))]
     pub fn join_room_sync(
         &self,
         room_id: Uuid,
@@ -385,71 +459,246 @@ impl GameApiClient {
             player_name,
             starting_airport,
         };
+        self.post_sync(&format!("/rooms/{}/join", room_id), &request)
+    }
 
-        let request_json = serde_json::to_string(&request)?;
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn post_message_sync(
+        &self,
+        room_id: uuid::Uuid,
+        player_id: uuid::Uuid,
+        content: String,
+        topic: MessageTopic,
+    ) -> Result<PostMessageResponse, ApiError> {
+        let request = PostMessageRequest { content, topic };
+        self.post_sync(
+            &format!("/rooms/{}/players/{}/messages", room_id, player_id),
+            &request,
+        )
+    }
 
-        let output = std::process::Command::new("curl")
-            .arg("-s") // silent
-            .arg("-X")
-            .arg("POST")
-            .arg("-H")
-            .arg("Content-Type: application/json")
-            .arg("-d")
-            .arg(request_json)
-            .arg(format!("{}/rooms/{}/join", self.base_url, room_id))
-            .output()
-            .map_err(|e| ApiError::NetworkError(format!("Failed to execute curl: {}", e)))?;
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    /// The board at the player's airport: one topic, or all of them when
+    /// `topic` is None
+    pub fn get_messages_sync(
+        &self,
+        room_id: uuid::Uuid,
+        player_id: uuid::Uuid,
+        topic: Option<MessageTopic>,
+    ) -> Result<GetMessagesResponse, ApiError> {
+        let query = topic
+            .map(|topic| format!("?topic={:?}", topic))
+            .unwrap_or_default();
+        self.get_sync(&format!(
+            "/rooms/{}/players/{}/messages{}",
+            room_id, player_id, query
+        ))
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ApiError::NetworkError(format!(
-                "Curl command failed: {}",
-                stderr
-            )));
-        }
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn delete_message_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<DeleteMessageResponse, ApiError> {
+        self.send_sync(
+            "DELETE",
+            &format!(
+                "/rooms/{}/players/{}/messages/{}",
+                room_id, player_id, message_id
+            ),
+        )
+    }
 
-        let response_text = String::from_utf8(output.stdout)
-            .map_err(|e| ApiError::ParseError(format!("Invalid UTF-8 response: {}", e)))?;
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_room_state_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<MultiplayerGameStateResponse, ApiError> {
+        self.get_sync(&format!("/rooms/{}/players/{}/state", room_id, player_id))
+    }
 
-        // Log the response for debugging
-        eprintln!("Join room response: {}", response_text);
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_statistics_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<StatisticsInfo, ApiError> {
+        self.get_sync(&format!(
+            "/rooms/{}/players/{}/statistics",
+            room_id, player_id
+        ))
+    }
 
-        // Try to parse as JoinRoomResponse first
-        if let Ok(result) = serde_json::from_str::<JoinRoomResponse>(&response_text) {
-            Ok(result)
-        } else {
-            // If that fails, try to parse as ErrorResponse
-            if let Ok(error) = serde_json::from_str::<ErrorResponse>(&response_text) {
-                Err(ApiError::ServerError(error.message))
-            } else {
-                Err(ApiError::ParseError(format!(
-                    "Failed to parse JSON response as either success or error: '{}'",
-                    response_text
-                )))
-            }
-        }
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_replay_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<ReplayResponse, ApiError> {
+        self.get_sync(&format!("/rooms/{}/players/{}/replay", room_id, player_id))
     }
 
-    #[cfg(feature = "gui")]
-    pub fn post_message_sync(
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_notifications_sync(
         &self,
-        room_id: uuid::Uuid,
-        player_id: uuid::Uuid,
-        content: String,
-    ) -> Result<PostMessageResponse, ApiError> {
-        let request = PostMessageRequest { content };
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<NotificationsResponse, ApiError> {
+        self.get_sync(&format!(
+            "/rooms/{}/players/{}/notifications",
+            room_id, player_id
+        ))
+    }
+
+    /// Marks every notification read when `ids` is empty
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn mark_notifications_read_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        ids: Vec<Uuid>,
+    ) -> Result<NotificationsResponse, ApiError> {
+        self.post_sync(
+            &format!(
+                "/rooms/{}/players/{}/notifications/read",
+                room_id, player_id
+            ),
+            &MarkNotificationsReadRequest { ids },
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn travel_preview_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        destination: &str,
+    ) -> Result<TravelQuote, ApiError> {
+        self.get_sync(&format!(
+            "/rooms/{}/players/{}/travel-preview?dest={}",
+            room_id, player_id, destination
+        ))
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_insurance_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<InsuranceResponse, ApiError> {
+        self.get_sync(&format!(
+            "/rooms/{}/players/{}/insurance",
+            room_id, player_id
+        ))
+    }
 
-        let request_json = serde_json::to_string(&request)?;
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn set_insurance_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        insured: bool,
+    ) -> Result<InsuranceResponse, ApiError> {
+        self.post_sync(
+            &format!("/rooms/{}/players/{}/insurance", room_id, player_id),
+            &SetInsuranceRequest { insured },
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn file_claim_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        loss_id: Uuid,
+    ) -> Result<InsuranceClaim, ApiError> {
+        self.post_sync(
+            &format!("/rooms/{}/players/{}/claims", room_id, player_id),
+            &FileClaimRequest { loss_id },
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn vote_rematch_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<RematchVoteResponse, ApiError> {
+        self.send_sync(
+            "POST",
+            &format!("/rooms/{}/players/{}/rematch-vote", room_id, player_id),
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn rematch_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<JoinRoomResponse, ApiError> {
+        self.send_sync(
+            "POST",
+            &format!("/rooms/{}/players/{}/rematch", room_id, player_id),
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn leave_room_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        force: bool,
+    ) -> Result<LeaveRoomResponse, ApiError> {
+        self.send_sync(
+            "POST",
+            &format!(
+                "/rooms/{}/players/{}/leave?force={}",
+                room_id, player_id, force
+            ),
+        )
+    }
 
-        let output = std::process::Command::new("curl")
+    /// Send a request with curl and parse the JSON response, or the
+    /// server's error in its place. The synchronous calls go through here so
+    /// they all send the same headers and read errors the same way.
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    fn request_sync<Resp>(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<String>,
+        idempotency_key: Option<Uuid>,
+    ) -> Result<Resp, ApiError>
+    where
+        Resp: serde::de::DeserializeOwned,
+    {
+        let mut command = std::process::Command::new("curl");
+        command
             .arg("-s") // silent
-            .arg("-X")
-            .arg("POST")
             .arg("-H")
-            .arg("Content-Type: application/json")
-            .arg("-d")
-            .arg(request_json)
-            .arg(format!("{}/rooms/{}/players/{}/messages", self.base_url, room_id, player_id))
+            .arg(accept_language())
+            .arg("-L") // follow redirects to the instance that owns the room
+            .arg("-X")
+            .arg(method);
+        if let Some(idempotency_key) = idempotency_key {
+            command.arg("-H").arg(format!(
+                "{}: {}",
+                crate::api::multiplayer_handlers::IDEMPOTENCY_KEY_HEADER,
+                idempotency_key
+            ));
+        }
+        if let Some(body) = body {
+            command
+                .arg("-H")
+                .arg("Content-Type: application/json")
+                .arg("-d")
+                .arg(body);
+        }
+        let output = command
+            .arg(format!("{}{}", self.base_url, path))
             .output()
             .map_err(|e| ApiError::NetworkError(format!("Failed to execute curl: {}", e)))?;
 
@@ -464,13 +713,13 @@ impl GameApiClient {
         let response_text = String::from_utf8(output.stdout)
             .map_err(|e| ApiError::ParseError(format!("Invalid UTF-8 response: {}", e)))?;
 
-        // Try to parse as PostMessageResponse first
-        if let Ok(result) = serde_json::from_str::<PostMessageResponse>(&response_text) {
+        // Try to parse as the expected response first
+        if let Ok(result) = serde_json::from_str::<Resp>(&response_text) {
             Ok(result)
         } else {
             // If that fails, try to parse as ErrorResponse
             if let Ok(error) = serde_json::from_str::<ErrorResponse>(&response_text) {
-                Err(ApiError::ServerError(error.message))
+                Err(ApiError::from(error))
             } else {
                 Err(ApiError::ParseError(format!(
                     "Failed to parse JSON response as either success or error: '{}'",
@@ -480,44 +729,530 @@ impl GameApiClient {
         }
     }
 
-    #[cfg(feature = "gui")]
-    pub fn get_messages_sync(
+    /// POST a JSON body with curl, tagging it with an idempotency key so the
+    /// server ignores replays of a request it already applied.
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    fn post_action_sync<Req, Resp>(
         &self,
-        room_id: uuid::Uuid,
-        player_id: uuid::Uuid,
-    ) -> Result<GetMessagesResponse, ApiError> {
-        let output = std::process::Command::new("curl")
-            .arg("-s") // silent
-            .arg("-X")
-            .arg("GET")
-            .arg(format!("{}/rooms/{}/players/{}/messages", self.base_url, room_id, player_id))
-            .output()
-            .map_err(|e| ApiError::NetworkError(format!("Failed to execute curl: {}", e)))?;
+        path: &str,
+        request: &Req,
+        idempotency_key: Uuid,
+    ) -> Result<Resp, ApiError>
+    where
+        Req: serde::Serialize,
+        Resp: serde::de::DeserializeOwned,
+    {
+        let request_json = serde_json::to_string(request)?;
+        self.request_sync("POST", path, Some(request_json), Some(idempotency_key))
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ApiError::NetworkError(format!(
-                "Curl command failed: {}",
-                stderr
-            )));
+    /// POST a JSON body with curl, for requests that are safe to repeat
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    fn post_sync<Req, Resp>(&self, path: &str, request: &Req) -> Result<Resp, ApiError>
+    where
+        Req: serde::Serialize,
+        Resp: serde::de::DeserializeOwned,
+    {
+        let request_json = serde_json::to_string(request)?;
+        self.request_sync("POST", path, Some(request_json), None)
+    }
+
+    /// Send a request with no body, e.g. a DELETE
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    fn send_sync<Resp>(&self, method: &str, path: &str) -> Result<Resp, ApiError>
+    where
+        Resp: serde::de::DeserializeOwned,
+    {
+        self.request_sync(method, path, None, None)
+    }
+
+    /// GET a JSON response with curl
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    fn get_sync<Resp>(&self, path: &str) -> Result<Resp, ApiError>
+    where
+        Resp: serde::de::DeserializeOwned,
+    {
+        self.send_sync("GET", path)
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_server_info_sync(&self) -> Result<ServerInfo, ApiError> {
+        self.get_sync("/server-info")
+    }
+
+    /// The server's airports, fetched once and kept until the server's
+    /// reference data changes
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_airports_sync(&self) -> Result<Vec<AirportInfo>, ApiError> {
+        self.revalidate_reference_cache();
+        if let Some(airports) = self.reference_cache().airports.clone() {
+            return Ok(airports);
         }
+        let airports: Vec<AirportInfo> = self.get_sync("/airports")?;
+        self.reference_cache().airports = Some(airports.clone());
+        Ok(airports)
+    }
 
-        let response_text = String::from_utf8(output.stdout)
-            .map_err(|e| ApiError::ParseError(format!("Invalid UTF-8 response: {}", e)))?;
+    /// The server's cargo types, fetched once and kept until the server's
+    /// reference data changes
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_cargo_sync(&self) -> Result<Vec<CargoInfo>, ApiError> {
+        self.revalidate_reference_cache();
+        if let Some(cargo) = self.reference_cache().cargo.clone() {
+            return Ok(cargo);
+        }
+        let cargo: Vec<CargoInfo> = self.get_sync("/cargo")?;
+        self.reference_cache().cargo = Some(cargo.clone());
+        Ok(cargo)
+    }
 
-        // Try to parse as GetMessagesResponse first
-        if let Ok(result) = serde_json::from_str::<GetMessagesResponse>(&response_text) {
-            Ok(result)
-        } else {
-            // If that fails, try to parse as ErrorResponse
-            if let Ok(error) = serde_json::from_str::<ErrorResponse>(&response_text) {
-                Err(ApiError::ServerError(error.message))
-            } else {
-                Err(ApiError::ParseError(format!(
-                    "Failed to parse JSON response as either success or error: '{}'",
-                    response_text
-                )))
-            }
+    /// Forget the cached airports and cargo so the next call fetches them
+    pub fn clear_reference_cache(&self) {
+        *self.reference_cache() = ReferenceCache::default();
+    }
+
+    /// Ask the server for its reference version at most once a
+    /// `SERVER_INFO_CHECK_INTERVAL`, dropping the cache when it's moved on.
+    /// An unreachable server leaves the cache as it is.
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    fn revalidate_reference_cache(&self) {
+        let due = self
+            .reference_cache()
+            .checked_at
+            .is_none_or(|checked_at| checked_at.elapsed() >= SERVER_INFO_CHECK_INTERVAL);
+        if !due {
+            return;
         }
+        let Ok(info) = self.get_server_info_sync() else {
+            return;
+        };
+
+        let mut cache = self.reference_cache();
+        if cache.reference_version.as_ref() != Some(&info.reference_version) {
+            *cache = ReferenceCache {
+                reference_version: Some(info.reference_version),
+                ..ReferenceCache::default()
+            };
+        }
+        cache.checked_at = Some(Instant::now());
+    }
+
+    fn reference_cache(&self) -> std::sync::MutexGuard<'_, ReferenceCache> {
+        self.reference_cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn player_travel_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: &TravelRequest,
+        idempotency_key: Uuid,
+    ) -> Result<PlayerTravelResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/travel", room_id, player_id),
+            request,
+            idempotency_key,
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn player_ground_transfer_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: &TravelRequest,
+        idempotency_key: Uuid,
+    ) -> Result<PlayerTravelResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/ground-transfer", room_id, player_id),
+            request,
+            idempotency_key,
+        )
     }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn player_trade_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: &TradeRequest,
+        idempotency_key: Uuid,
+    ) -> Result<PlayerTradeResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/trade", room_id, player_id),
+            request,
+            idempotency_key,
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn apply_loadout_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: &ApplyLoadoutRequest,
+        idempotency_key: Uuid,
+    ) -> Result<PlayerTradeBatchResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/loadout", room_id, player_id),
+            request,
+            idempotency_key,
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn player_buy_fuel_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        request: &FuelRequest,
+        idempotency_key: Uuid,
+    ) -> Result<PlayerFuelResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/fuel", room_id, player_id),
+            request,
+            idempotency_key,
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_auto_refuel_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<AutoRefuel, ApiError> {
+        self.get_sync(&format!(
+            "/rooms/{}/players/{}/auto-refuel",
+            room_id, player_id
+        ))
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn set_auto_refuel_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        auto_refuel: &AutoRefuel,
+    ) -> Result<AutoRefuel, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/auto-refuel", room_id, player_id),
+            auto_refuel,
+            Uuid::new_v4(),
+        )
+    }
+
+    /// Host only; everyone else in the room is notified of the change
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn set_game_speed_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        speed: GameSpeed,
+    ) -> Result<GameSpeedResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/speed", room_id, player_id),
+            &SetGameSpeedRequest { speed },
+            Uuid::new_v4(),
+        )
+    }
+
+    /// Host only; the event is labelled as theirs in everyone's event feed
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn inject_event_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        spec: &EventSpec,
+    ) -> Result<InjectEventResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/events", room_id, player_id),
+            spec,
+            Uuid::new_v4(),
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_offers_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<OffersResponse, ApiError> {
+        self.get_sync(&format!("/rooms/{}/players/{}/offers", room_id, player_id))
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_courier_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<CourierResponse, ApiError> {
+        self.get_sync(&format!("/rooms/{}/players/{}/courier", room_id, player_id))
+    }
+
+    /// Each call is a fresh click, so it gets its own idempotency key
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn send_courier_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        destination: String,
+        content: String,
+    ) -> Result<SendCourierResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/courier", room_id, player_id),
+            &SendCourierRequest {
+                destination,
+                content,
+            },
+            Uuid::new_v4(),
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_hangar_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<HangarResponse, ApiError> {
+        self.get_sync(&format!("/rooms/{}/players/{}/hangar", room_id, player_id))
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_route_profits_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<RouteProfitsResponse, ApiError> {
+        self.get_sync(&format!(
+            "/rooms/{}/players/{}/route-profits",
+            room_id, player_id
+        ))
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_room_settings_sync(&self, room_id: Uuid) -> Result<RoomSettingsResponse, ApiError> {
+        self.get_sync(&format!("/rooms/{}/settings", room_id))
+    }
+
+    /// Each call is a fresh click, so it gets its own idempotency key
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn buy_upgrade_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        upgrade: AircraftUpgrade,
+    ) -> Result<HangarResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/upgrade", room_id, player_id),
+            &BuyUpgradeRequest { upgrade },
+            Uuid::new_v4(),
+        )
+    }
+
+    /// An airport's recent prices, as the player is allowed to see them
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_price_history_sync(
+        &self,
+        room_id: Uuid,
+        airport_id: &str,
+        player_id: Uuid,
+    ) -> Result<PriceHistoryResponse, ApiError> {
+        self.get_sync(&format!(
+            "/rooms/{}/markets/{}/history?player_id={}",
+            room_id,
+            path_segment(airport_id),
+            player_id
+        ))
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_stalls_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<VendorStallsResponse, ApiError> {
+        self.get_sync(&format!("/rooms/{}/players/{}/stalls", room_id, player_id))
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn buy_from_stall_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        stall_id: Uuid,
+    ) -> Result<StallPurchaseResponse, ApiError> {
+        self.post_action_sync(
+            &format!(
+                "/rooms/{}/players/{}/stalls/{}/buy",
+                room_id, player_id, stall_id
+            ),
+            &(),
+            Uuid::new_v4(),
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_loans_sync(&self, room_id: Uuid, player_id: Uuid) -> Result<LoanResponse, ApiError> {
+        self.get_sync(&format!("/rooms/{}/players/{}/loan", room_id, player_id))
+    }
+
+    /// Each call is a fresh click, so it gets its own idempotency key
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn borrow_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        amount: u32,
+    ) -> Result<LoanResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/loan", room_id, player_id),
+            &BorrowRequest { amount },
+            Uuid::new_v4(),
+        )
+    }
+
+    /// Each call is a fresh click, so it gets its own idempotency key
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn repay_loan_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        loan_id: u32,
+        amount: u32,
+    ) -> Result<LoanResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/loan/repay", room_id, player_id),
+            &RepayLoanRequest { loan_id, amount },
+            Uuid::new_v4(),
+        )
+    }
+
+    /// Each call is a fresh click, so it gets its own idempotency key
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn place_offer_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        terms: &OfferTerms,
+    ) -> Result<OfferResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/rooms/{}/players/{}/offers", room_id, player_id),
+            terms,
+            Uuid::new_v4(),
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn accept_offer_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        offer_id: Uuid,
+        quantity: u32,
+    ) -> Result<OfferResponse, ApiError> {
+        self.post_action_sync(
+            &format!(
+                "/rooms/{}/players/{}/offers/{}/accept",
+                room_id, player_id, offer_id
+            ),
+            &AcceptOfferRequest {
+                quantity: Some(quantity),
+            },
+            Uuid::new_v4(),
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn cancel_offer_sync(
+        &self,
+        room_id: Uuid,
+        player_id: Uuid,
+        offer_id: Uuid,
+    ) -> Result<OfferResponse, ApiError> {
+        self.send_sync(
+            "DELETE",
+            &format!(
+                "/rooms/{}/players/{}/offers/{}",
+                room_id, player_id, offer_id
+            ),
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_pilot_profile_sync(
+        &self,
+        player_name: &str,
+    ) -> Result<PilotProfileResponse, ApiError> {
+        self.get_sync(&format!("/players/{}/profile", path_segment(player_name)))
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_hosted_rooms_sync(
+        &self,
+        player_name: &str,
+    ) -> Result<Vec<HostedRoomSummary>, ApiError> {
+        self.get_sync(&format!(
+            "/players/{}/hosted-rooms",
+            path_segment(player_name)
+        ))
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn save_loadout_sync(
+        &self,
+        player_name: &str,
+        loadout: &CargoLoadout,
+    ) -> Result<PilotProfileResponse, ApiError> {
+        self.post_action_sync(
+            &format!("/players/{}/profile/loadouts", path_segment(player_name)),
+            loadout,
+            Uuid::new_v4(),
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn delete_loadout_sync(
+        &self,
+        player_name: &str,
+        name: &str,
+    ) -> Result<PilotProfileResponse, ApiError> {
+        self.send_sync(
+            "DELETE",
+            &format!(
+                "/players/{}/profile/loadouts/{}",
+                path_segment(player_name),
+                path_segment(name)
+            ),
+        )
+    }
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    pub fn get_rankings_sync(
+        &self,
+        season: Option<&str>,
+        page: usize,
+    ) -> Result<RankingsResponse, ApiError> {
+        let mut path = format!("/rankings?page={}", page);
+        if let Some(season) = season {
+            path.push_str(&format!("&season={}", path_segment(season)));
+        }
+        self.get_sync(&path)
+    }
+}
+
+/// Percent-encode a player name so it can go in a URL path
+#[cfg(any(feature = "gui", feature = "tui"))]
+fn path_segment(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            },
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
 }
@@ -1,12 +1,29 @@
+pub mod notifications;
+pub mod style;
 pub mod terminal;
 
 #[cfg(feature = "gui")]
-pub mod egui_app;
+pub mod action_queue;
 
 #[cfg(feature = "gui")]
+pub mod egui_app;
+
+#[cfg(any(feature = "gui", feature = "tui"))]
 pub mod game_api_client;
 
+#[cfg(feature = "gui")]
+pub mod recorder;
+
+#[cfg(feature = "gui")]
+pub mod refresh;
+
 #[cfg(feature = "gui")]
 pub mod scenes;
 
+#[cfg(feature = "gui")]
+pub mod toasts;
+
+#[cfg(feature = "tui")]
+pub mod tui;
+
 pub use terminal::TerminalUI;
@@ -0,0 +1,394 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use uuid::Uuid;
+
+use crate::{
+    api::models::MessageInfo,
+    systems::{
+        RoomWinner,
+        achievements::{Achievement, UnlockedAchievement},
+    },
+};
+
+/// Something worth pulling the player back to the game for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notification {
+    TurnReady {
+        world_tick: u32,
+    },
+    NewMessage {
+        author_name: String,
+        airport_id: String,
+    },
+    MarketEvent {
+        description: String,
+    },
+    GameOver {
+        winner_name: String,
+        money: u32,
+    },
+    Mentioned {
+        new_mentions: usize,
+    },
+    AchievementUnlocked {
+        achievement: Achievement,
+    },
+}
+
+impl Notification {
+    pub fn title(&self) -> String {
+        match self {
+            Notification::TurnReady { .. } => "KZRK - Your turn".to_string(),
+            Notification::NewMessage { .. } => "KZRK - New message".to_string(),
+            Notification::MarketEvent { .. } => "KZRK - Market news".to_string(),
+            Notification::GameOver { .. } => "KZRK - Game over".to_string(),
+            Notification::Mentioned { .. } => "KZRK - You were mentioned".to_string(),
+            Notification::AchievementUnlocked { .. } => "KZRK - Achievement unlocked".to_string(),
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match self {
+            Notification::TurnReady { world_tick } => {
+                format!(
+                    "Other pilots have moved - the world is at tick {}.",
+                    world_tick
+                )
+            },
+            Notification::NewMessage {
+                author_name,
+                airport_id,
+            } => format!(
+                "{} posted on the {} message board.",
+                author_name, airport_id
+            ),
+            Notification::MarketEvent { description } => description.clone(),
+            Notification::GameOver { winner_name, money } => {
+                format!("{} won the game with ${}.", winner_name, money)
+            },
+            Notification::Mentioned { new_mentions } => format!(
+                "{} new mention{} on the message boards.",
+                new_mentions,
+                if *new_mentions == 1 { "" } else { "s" }
+            ),
+            Notification::AchievementUnlocked { achievement } => {
+                format!("{}: {}.", achievement.title(), achievement.description())
+            },
+        }
+    }
+}
+
+/// Delivers notifications through whatever the platform offers. Desktop
+/// notifications go through the native helper (notify-send, osascript or
+/// PowerShell) so we don't need a notification library; failures are ignored
+/// since a missed popup should never interrupt the game.
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    pub desktop: bool,
+    pub bell: bool,
+}
+
+impl Notifier {
+    /// Desktop notifier for the GUI; set KZRK_NOTIFY=off to silence it
+    #[allow(dead_code)]
+    pub fn desktop_from_env() -> Self {
+        let desktop = std::env::var("KZRK_NOTIFY")
+            .map(|v| !matches!(v.to_lowercase().as_str(), "0" | "off" | "false"))
+            .unwrap_or(true);
+
+        Self {
+            desktop,
+            bell: false,
+        }
+    }
+
+    /// Terminal-only notifier, enabled via the KZRK_BELL environment variable
+    pub fn terminal_from_env() -> Self {
+        let bell = std::env::var("KZRK_BELL")
+            .map(|v| v == "1" || v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        Self {
+            desktop: false,
+            bell,
+        }
+    }
+
+    pub fn notify(&self, notification: &Notification) {
+        if self.bell {
+            Self::ring_bell();
+        }
+        if self.desktop {
+            Self::send_desktop(&notification.title(), &notification.body());
+        }
+    }
+
+    fn ring_bell() {
+        print!("\x07");
+        let _ = io::stdout().flush();
+    }
+
+    fn send_desktop(title: &str, body: &str) {
+        let mut command = if cfg!(target_os = "macos") {
+            let mut command = Command::new("osascript");
+            command.arg("-e").arg(format!(
+                "display notification \"{}\" with title \"{}\"",
+                body.replace('"', "'"),
+                title.replace('"', "'")
+            ));
+            command
+        } else if cfg!(target_os = "windows") {
+            let mut command = Command::new("powershell");
+            command.arg("-NoProfile").arg("-Command").arg(format!(
+                "[reflection.assembly]::loadwithpartialname('System.Windows.Forms') | Out-Null; \
+                 $n = New-Object System.Windows.Forms.NotifyIcon; \
+                 $n.Icon = [System.Drawing.SystemIcons]::Information; $n.Visible = $true; \
+                 $n.ShowBalloonTip(5000, '{}', '{}', 'Info')",
+                title.replace('\'', "''"),
+                body.replace('\'', "''")
+            ));
+            command
+        } else {
+            let mut command = Command::new("notify-send");
+            command.arg("--app-name=KZRK").arg(title).arg(body);
+            command
+        };
+
+        // Fire and forget - we don't wait for the helper to exit
+        let _ = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+    }
+}
+
+/// Remembers what the player has already seen so each poll only reports
+/// what changed since the last one. The first look at a turn counter or an
+/// airport's board just records a baseline; otherwise joining a room or
+/// landing somewhere new would announce its whole history.
+#[allow(dead_code)]
+#[derive(Debug, Default)]
+pub struct NotificationWatcher {
+    last_turns: Option<(u32, u32)>,
+    seen_messages: HashSet<Uuid>,
+    primed_boards: HashSet<String>,
+    winner_seen: bool,
+    last_unread: Option<usize>,
+    seen_achievements: Option<HashSet<Achievement>>,
+}
+
+#[allow(dead_code)]
+impl NotificationWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The world tick moves whenever anyone acts; it's only news when it
+    /// moved further than our own turn counter did.
+    pub fn observe_turn(&mut self, world_tick: u32, my_turn: u32) -> Option<Notification> {
+        let previous = self.last_turns.replace((world_tick, my_turn));
+        match previous {
+            Some((previous_tick, previous_turn))
+                if world_tick.saturating_sub(previous_tick)
+                    > my_turn.saturating_sub(previous_turn) =>
+            {
+                Some(Notification::TurnReady { world_tick })
+            },
+            _ => None,
+        }
+    }
+
+    pub fn observe_winner(&mut self, winner: Option<&RoomWinner>) -> Option<Notification> {
+        let winner = winner?;
+        if std::mem::replace(&mut self.winner_seen, true) {
+            return None;
+        }
+        Some(Notification::GameOver {
+            winner_name: winner.player_name.clone(),
+            money: winner.money,
+        })
+    }
+
+    /// Only mentions that arrived since the last poll are news
+    pub fn observe_mentions(&mut self, unread: usize) -> Option<Notification> {
+        let previous = self.last_unread.replace(unread)?;
+        (unread > previous).then(|| Notification::Mentioned {
+            new_mentions: unread - previous,
+        })
+    }
+
+    /// What's on the pilot's profile when we first look was earned before
+    pub fn observe_achievements(&mut self, unlocked: &[UnlockedAchievement]) -> Vec<Notification> {
+        let current: HashSet<Achievement> = unlocked
+            .iter()
+            .map(|unlocked| unlocked.achievement)
+            .collect();
+        let Some(seen) = self.seen_achievements.replace(current) else {
+            return Vec::new();
+        };
+
+        unlocked
+            .iter()
+            .filter(|unlocked| !seen.contains(&unlocked.achievement))
+            .map(|unlocked| Notification::AchievementUnlocked {
+                achievement: unlocked.achievement,
+            })
+            .collect()
+    }
+
+    pub fn observe_messages(
+        &mut self,
+        airport_id: &str,
+        messages: &[MessageInfo],
+        my_player_id: Uuid,
+    ) -> Vec<Notification> {
+        let primed = !self.primed_boards.insert(airport_id.to_string());
+
+        messages
+            .iter()
+            .filter(|message| self.seen_messages.insert(message.id))
+            // Locals chatting isn't worth a popup
+            .filter(|message| primed && !message.is_npc && message.author_id != my_player_id)
+            .map(|message| Notification::NewMessage {
+                author_name: message.author_name.clone(),
+                airport_id: message.airport_id.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageTopic;
+
+    fn message(author_id: Uuid, author_name: &str) -> MessageInfo {
+        MessageInfo {
+            id: Uuid::new_v4(),
+            author_id,
+            author_name: author_name.to_string(),
+            content: "Fuel is cheap here".to_string(),
+            airport_id: "JFK".to_string(),
+            created_at: chrono::Utc::now(),
+            is_npc: false,
+            mentions_you: false,
+            couriered_from: None,
+            topic: MessageTopic::General,
+        }
+    }
+
+    #[test]
+    fn test_turn_ready_only_when_others_move() {
+        let mut watcher = NotificationWatcher::new();
+
+        assert_eq!(watcher.observe_turn(3, 2), None);
+        assert_eq!(watcher.observe_turn(3, 2), None);
+
+        // Our own flight moves both counters - nothing to announce
+        assert_eq!(watcher.observe_turn(4, 3), None);
+
+        // Someone else flew
+        assert_eq!(
+            watcher.observe_turn(5, 3),
+            Some(Notification::TurnReady { world_tick: 5 })
+        );
+    }
+
+    #[test]
+    fn test_new_messages_from_other_pilots() {
+        let me = Uuid::new_v4();
+        let mut watcher = NotificationWatcher::new();
+
+        let old = message(Uuid::new_v4(), "Alice");
+        assert!(
+            watcher
+                .observe_messages("JFK", std::slice::from_ref(&old), me)
+                .is_empty()
+        );
+
+        let mine = message(me, "Me");
+        let theirs = message(Uuid::new_v4(), "Bob");
+        let notifications = watcher.observe_messages("JFK", &[old, mine, theirs.clone()], me);
+        assert_eq!(notifications.len(), 1);
+        assert!(notifications[0].body().contains("Bob"));
+
+        // Already-seen messages are not announced twice
+        assert!(watcher.observe_messages("JFK", &[theirs], me).is_empty());
+
+        // Nor is the locals' chatter
+        let npc = MessageInfo {
+            is_npc: true,
+            ..message(Uuid::nil(), "Gus (ramp agent)")
+        };
+        assert!(watcher.observe_messages("JFK", &[npc], me).is_empty());
+
+        // Arriving at a new airport doesn't replay its backlog
+        let backlog = message(Uuid::new_v4(), "Carol");
+        assert!(watcher.observe_messages("LAX", &[backlog], me).is_empty());
+    }
+
+    #[test]
+    fn test_new_mentions_are_announced() {
+        let mut watcher = NotificationWatcher::new();
+        assert_eq!(watcher.observe_mentions(2), None);
+        assert_eq!(watcher.observe_mentions(2), None);
+        assert_eq!(
+            watcher.observe_mentions(3),
+            Some(Notification::Mentioned { new_mentions: 1 })
+        );
+        // Reading them isn't news
+        assert_eq!(watcher.observe_mentions(0), None);
+    }
+
+    #[test]
+    fn test_new_achievements_are_announced() {
+        let unlocked = |achievement| UnlockedAchievement {
+            achievement,
+            room_name: "Test Room".to_string(),
+            unlocked_at: chrono::Utc::now(),
+        };
+        let mut watcher = NotificationWatcher::new();
+
+        // Earned in an earlier game
+        let old = vec![unlocked(Achievement::Globetrotter)];
+        assert!(watcher.observe_achievements(&old).is_empty());
+        assert!(watcher.observe_achievements(&old).is_empty());
+
+        let new = vec![
+            unlocked(Achievement::Globetrotter),
+            unlocked(Achievement::FirstMillion),
+        ];
+        assert_eq!(
+            watcher.observe_achievements(&new),
+            vec![Notification::AchievementUnlocked {
+                achievement: Achievement::FirstMillion,
+            }]
+        );
+        assert!(watcher.observe_achievements(&new).is_empty());
+    }
+
+    #[test]
+    fn test_game_over_is_announced_once() {
+        let mut watcher = NotificationWatcher::new();
+        assert_eq!(watcher.observe_winner(None), None);
+
+        let winner = RoomWinner {
+            player_id: Uuid::new_v4(),
+            player_name: "Alice".to_string(),
+            money: 100_000,
+            turn_number: 42,
+            won_at: chrono::Utc::now(),
+        };
+        assert_eq!(
+            watcher.observe_winner(Some(&winner)),
+            Some(Notification::GameOver {
+                winner_name: "Alice".to_string(),
+                money: 100_000,
+            })
+        );
+        assert_eq!(watcher.observe_winner(Some(&winner)), None);
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use eframe::egui::{Color32, ColorImage};
+
+use crate::systems::SaveSystem;
+
+/// Seconds a shared moment covers unless KZRK_MOMENT_SECONDS says otherwise
+pub const DEFAULT_MOMENT_SECONDS: u64 = 10;
+
+/// Most seconds KZRK_MOMENT_SECONDS may ask for
+pub const MAX_MOMENT_SECONDS: u64 = 30;
+
+/// Frames captured each second while recording
+pub const MOMENT_FPS: u64 = 5;
+
+/// Widest a captured frame is kept, so moments stay small enough to share
+pub const MOMENT_MAX_WIDTH: usize = 480;
+
+/// One scaled-down capture of the window
+#[derive(Debug, Clone)]
+pub struct MomentFrame {
+    pub captured_at: Instant,
+    pub image: ColorImage,
+}
+
+/// Keeps the last few seconds of the window so a trade or a win can be
+/// saved as a GIF after it happened
+#[derive(Debug)]
+pub struct MomentRecorder {
+    length: Duration,
+    frames: VecDeque<MomentFrame>,
+    last_request: Option<Instant>,
+}
+
+impl MomentRecorder {
+    pub fn new(length: Duration) -> Self {
+        Self {
+            length,
+            frames: VecDeque::new(),
+            last_request: None,
+        }
+    }
+
+    /// A recorder keeping KZRK_MOMENT_SECONDS (default 10, at most 30)
+    pub fn from_env() -> Self {
+        let seconds = std::env::var("KZRK_MOMENT_SECONDS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|seconds| *seconds > 0)
+            .unwrap_or(DEFAULT_MOMENT_SECONDS)
+            .min(MAX_MOMENT_SECONDS);
+        Self::new(Duration::from_secs(seconds))
+    }
+
+    pub fn frame_interval() -> Duration {
+        Duration::from_millis(1000 / MOMENT_FPS)
+    }
+
+    /// Whether it's time to ask for the next screenshot; counts as asking
+    /// when it is
+    pub fn request_frame(&mut self, now: Instant) -> bool {
+        let due = self
+            .last_request
+            .is_none_or(|asked| now.duration_since(asked) >= Self::frame_interval());
+        if due {
+            self.last_request = Some(now);
+        }
+        due
+    }
+
+    /// Keep a screenshot, forgetting frames older than the moment length.
+    /// A frame of a different size means the window was resized, which
+    /// starts the moment over.
+    pub fn push(&mut self, screenshot: &ColorImage, now: Instant) {
+        let image = downscale(screenshot, MOMENT_MAX_WIDTH);
+        if self
+            .frames
+            .back()
+            .is_some_and(|frame| frame.image.size != image.size)
+        {
+            self.frames.clear();
+        }
+        self.frames.push_back(MomentFrame {
+            captured_at: now,
+            image,
+        });
+        while self
+            .frames
+            .front()
+            .is_some_and(|frame| now.duration_since(frame.captured_at) > self.length)
+        {
+            self.frames.pop_front();
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The frames captured so far, oldest first
+    pub fn frames(&self) -> Vec<MomentFrame> {
+        self.frames.iter().cloned().collect()
+    }
+}
+
+/// Shrink an image to at most `max_width` pixels wide, keeping its shape
+pub fn downscale(image: &ColorImage, max_width: usize) -> ColorImage {
+    let [width, height] = image.size;
+    if width <= max_width || width == 0 {
+        return image.clone();
+    }
+    let new_width = max_width;
+    let new_height = (height * max_width / width).max(1);
+    let mut pixels = Vec::with_capacity(new_width * new_height);
+    for y in 0..new_height {
+        let source_row = y * height / new_height * width;
+        for x in 0..new_width {
+            pixels.push(image.pixels[source_row + x * width / new_width]);
+        }
+    }
+    ColorImage {
+        size: [new_width, new_height],
+        pixels,
+    }
+}
+
+/// An endlessly looping GIF of `frames`, each shown for as long as it was
+/// on screen
+pub fn encode_gif(frames: &[MomentFrame]) -> Result<Vec<u8>, String> {
+    let first = frames.first().ok_or("Nothing recorded yet")?;
+    let [width, height] = first.image.size;
+    let (width, height) = (
+        u16::try_from(width).map_err(|_| "Frame too wide for a GIF")?,
+        u16::try_from(height).map_err(|_| "Frame too tall for a GIF")?,
+    );
+    let default_delay = (100 / MOMENT_FPS) as u16;
+
+    let mut gif = Vec::new();
+    {
+        let mut encoder = gif::Encoder::new(&mut gif, width, height, &[])
+            .map_err(|e| format!("Failed to start GIF: {}", e))?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(|e| format!("Failed to start GIF: {}", e))?;
+        for (index, frame) in frames.iter().enumerate() {
+            let mut rgba: Vec<u8> = frame
+                .image
+                .pixels
+                .iter()
+                .flat_map(Color32::to_srgba_unmultiplied)
+                .collect();
+            let mut gif_frame = gif::Frame::from_rgba_speed(width, height, &mut rgba, 10);
+            gif_frame.delay = frames
+                .get(index + 1)
+                .map(|next| {
+                    let shown = next.captured_at.duration_since(frame.captured_at);
+                    (shown.as_millis() / 10).clamp(1, u16::MAX as u128) as u16
+                })
+                .unwrap_or(default_delay);
+            encoder
+                .write_frame(&gif_frame)
+                .map_err(|e| format!("Failed to write GIF frame: {}", e))?;
+        }
+    }
+    Ok(gif)
+}
+
+/// Where shared moments are saved: `moments/` in the save directory
+pub fn moments_dir() -> Result<PathBuf, String> {
+    SaveSystem::get_save_directory()
+        .map(|dir| dir.join("moments"))
+        .map_err(|e| e.to_string())
+}
+
+/// Write `frames` to a new GIF in `dir`, named for when it was saved
+pub fn save_moment(
+    frames: &[MomentFrame],
+    dir: &Path,
+    saved_at: chrono::DateTime<chrono::Local>,
+) -> Result<PathBuf, String> {
+    let gif = encode_gif(frames)?;
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(format!(
+        "kzrk-moment-{}.gif",
+        saved_at.format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, gif).map_err(|e| format!("Failed to save moment: {}", e))?;
+    Ok(path)
+}
@@ -0,0 +1,94 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::systems::SaveSystem;
+
+/// How hard the GUI works to stay current
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RefreshMode {
+    /// Polls the server every couple of seconds
+    #[default]
+    Standard,
+    /// Polls rarely, and even less while the window is in the background.
+    /// Between polls the screen only redraws on input, without animations.
+    PowerSaver,
+}
+
+impl RefreshMode {
+    /// How often the room is fetched while playing
+    pub fn state_poll_interval(self, focused: bool) -> Duration {
+        match (self, focused) {
+            (RefreshMode::Standard, _) => Duration::from_secs(2),
+            (RefreshMode::PowerSaver, true) => Duration::from_secs(10),
+            (RefreshMode::PowerSaver, false) => Duration::from_secs(60),
+        }
+    }
+
+    /// How often the lobby's room list is fetched
+    pub fn lobby_poll_interval(self) -> Duration {
+        match self {
+            RefreshMode::Standard => Duration::from_secs(5),
+            RefreshMode::PowerSaver => Duration::from_secs(30),
+        }
+    }
+
+    /// Whether egui should animate, which repaints every frame until the
+    /// animation ends
+    pub fn animates(self) -> bool {
+        self == RefreshMode::Standard
+    }
+}
+
+/// GUI preferences kept between runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GuiSettings {
+    #[serde(default)]
+    pub refresh_mode: RefreshMode,
+    /// Keep the last few seconds of the window for sharing as a GIF
+    #[serde(default)]
+    pub record_moments: bool,
+}
+
+impl GuiSettings {
+    fn default_path() -> Option<PathBuf> {
+        SaveSystem::get_save_directory()
+            .ok()
+            .map(|dir| dir.join("gui_settings.json"))
+    }
+
+    /// The saved settings, or the defaults when there are none yet.
+    /// KZRK_POWER_SAVER=1 turns power saver on regardless.
+    pub fn load() -> Self {
+        let mut settings = Self::default_path()
+            .map(|path| Self::load_from(&path))
+            .unwrap_or_default();
+        if std::env::var("KZRK_POWER_SAVER")
+            .is_ok_and(|value| matches!(value.trim(), "1" | "true" | "on"))
+        {
+            settings.refresh_mode = RefreshMode::PowerSaver;
+        }
+        settings
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::default_path().ok_or("No save directory")?;
+        self.save_to(&path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to save settings: {}", e))
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{
+    api::models::EventInfo,
+    models::{CargoCategory, CargoType, FuelEconomy, FuelPriceLevel},
+    systems::game::GameState,
+    ui::scenes::{SceneState, airport::locations::LocationRegistry},
+};
+
+/// The current airport's fuel economy, for price hints
+pub fn current_fuel_economy(game_state: &GameState) -> Option<FuelEconomy> {
+    game_state
+        .get_current_airport()
+        .map(|airport| airport.fuel_economy())
+}
+
+/// The fuel outage at an airport, if the server has reported one
+pub fn fuel_outage_at<'a>(events: &'a [EventInfo], airport_id: &str) -> Option<&'a EventInfo> {
+    events
+        .iter()
+        .find(|event| event.event_type == "FuelOutage" && event.affected_airport == airport_id)
+}
+
+pub fn fuel_level_color(level: FuelPriceLevel) -> eframe::egui::Color32 {
+    match level {
+        FuelPriceLevel::Cheap => eframe::egui::Color32::from_rgb(50, 150, 50),
+        FuelPriceLevel::Normal => eframe::egui::Color32::from_gray(120),
+        FuelPriceLevel::Expensive => eframe::egui::Color32::from_rgb(220, 50, 50),
+    }
+}
+
+/// Money, fuel, cargo weight and position - shown above every location.
+pub fn status_bar(game_state: &GameState, ui: &mut eframe::egui::Ui) {
+    eframe::egui::Frame::none()
+        .fill(eframe::egui::Color32::from_gray(240))
+        .inner_margin(eframe::egui::Margin::same(8.0))
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("💰 ${}", game_state.player.money));
+                ui.separator();
+                ui.label(format!(
+                    "⛽ {}/{}",
+                    game_state.player.fuel, game_state.player.max_fuel
+                ));
+                ui.separator();
+
+                let current_weight = game_state
+                    .player
+                    .current_cargo_weight(&game_state.cargo_types);
+                ui.label(format!(
+                    "📦 {}kg/{}kg",
+                    current_weight, game_state.player.max_cargo_weight
+                ));
+                ui.separator();
+
+                ui.label(format!(
+                    "📍 {}",
+                    game_state
+                        .airports
+                        .get(&game_state.player.current_airport)
+                        .map(|a| &a.name)
+                        .unwrap_or(&game_state.player.current_airport)
+                ));
+
+                let streak = game_state.player.streak;
+                if streak.current > 0 {
+                    ui.separator();
+                    ui.colored_label(
+                        eframe::egui::Color32::from_rgb(220, 120, 0),
+                        format!(
+                            "🔥 {} streak (+{}%)",
+                            streak.current,
+                            streak.bonus_percent()
+                        ),
+                    )
+                    .on_hover_text("Profitable sales in a row earn a bonus on the next sale");
+                }
+            });
+        });
+}
+
+/// Navigation between the FBO locations the registry knows about.
+pub fn location_buttons(
+    registry: &LocationRegistry,
+    scene_state: &mut SceneState,
+    ui: &mut eframe::egui::Ui,
+) {
+    ui.heading("🏢 FBO Locations");
+
+    ui.horizontal_wrapped(|ui| {
+        for view in registry.views() {
+            let location = view.location();
+            let label = view.label();
+            let is_current = scene_state.current_location == location;
+
+            let button = if is_current {
+                eframe::egui::Button::new(format!("▶ {}", label))
+                    .fill(eframe::egui::Color32::from_rgb(100, 150, 255))
+            } else {
+                eframe::egui::Button::new(label)
+            };
+
+            if ui.add_sized([120.0, 32.0], button).clicked() && !is_current {
+                scene_state.go_to_location(location);
+            }
+        }
+    });
+}
+
+/// The cargo on board with its weight and a base-price estimate of its value
+pub fn cargo_inventory_grid(game_state: &GameState, id_salt: &str, ui: &mut eframe::egui::Ui) {
+    eframe::egui::Grid::new(id_salt)
+        .num_columns(4)
+        .spacing([20.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("Cargo Type");
+            ui.strong("Quantity");
+            ui.strong("Weight");
+            ui.strong("Estimated Value");
+            ui.end_row();
+
+            for (cargo_id, quantity) in game_state.player.cargo_inventory.get_all_cargo() {
+                if let Some(cargo_type) = game_state.cargo_types.get(cargo_id) {
+                    let icon = cargo_icon(&cargo_type.name);
+                    ui.label(format!("{} {}", icon, cargo_type.name));
+                    ui.label(format!("{}", quantity));
+                    ui.label(format!(
+                        "{}kg",
+                        cargo_type.weight_per_unit.saturating_mul(*quantity)
+                    ));
+
+                    // Estimate value based on base price
+                    let est_value = cargo_type.base_price.saturating_mul(*quantity);
+                    ui.label(format!("~${}", est_value));
+                    ui.end_row();
+                }
+            }
+        });
+}
+
+/// A small line chart of prices, oldest on the left, coloured by whether the
+/// latest is up or down on the first
+pub fn sparkline(prices: &[u32], ui: &mut eframe::egui::Ui) -> eframe::egui::Response {
+    let size = eframe::egui::vec2(80.0, 18.0);
+    let (rect, response) = ui.allocate_exact_size(size, eframe::egui::Sense::hover());
+    if prices.len() < 2 {
+        return response.on_hover_text("Not enough history yet");
+    }
+
+    let low = prices.iter().copied().min().unwrap_or_default() as f32;
+    let high = prices.iter().copied().max().unwrap_or_default() as f32;
+    let range = (high - low).max(1.0);
+    let step = rect.width() / (prices.len() - 1) as f32;
+    let points: Vec<eframe::egui::Pos2> = prices
+        .iter()
+        .enumerate()
+        .map(|(index, price)| {
+            eframe::egui::pos2(
+                rect.left() + step * index as f32,
+                rect.bottom() - (*price as f32 - low) / range * rect.height(),
+            )
+        })
+        .collect();
+    let color = match prices.last().cmp(&prices.first()) {
+        std::cmp::Ordering::Greater => eframe::egui::Color32::from_rgb(220, 50, 50),
+        std::cmp::Ordering::Less => eframe::egui::Color32::from_rgb(50, 150, 50),
+        std::cmp::Ordering::Equal => eframe::egui::Color32::from_gray(120),
+    };
+    ui.painter().add(eframe::egui::Shape::line(
+        points,
+        eframe::egui::Stroke::new(1.5, color),
+    ));
+
+    response.on_hover_text(format!(
+        "${} to ${} over the last {} turns",
+        low as u32,
+        high as u32,
+        prices.len()
+    ))
+}
+
+pub fn cargo_icon(cargo_name: &str) -> &'static str {
+    match cargo_name {
+        "Electronics" => "💻",
+        "Food & Beverages" => "🍎",
+        "Textiles" => "👔",
+        "Industrial Parts" => "🔧",
+        "Luxury Goods" => "💎",
+        "Raw Materials" => "🏗️",
+        _ => "📦",
+    }
+}
+
+/// How the market board and trading desk order their cargo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CargoSort {
+    #[default]
+    Name,
+    PriceLowToHigh,
+    PriceHighToLow,
+    Category,
+}
+
+impl CargoSort {
+    pub const ALL: [CargoSort; 4] = [
+        CargoSort::Name,
+        CargoSort::PriceLowToHigh,
+        CargoSort::PriceHighToLow,
+        CargoSort::Category,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CargoSort::Name => "Name",
+            CargoSort::PriceLowToHigh => "Price: low to high",
+            CargoSort::PriceHighToLow => "Price: high to low",
+            CargoSort::Category => "Category",
+        }
+    }
+}
+
+/// A cargo on sale at the current airport
+pub struct MarketCargo<'a> {
+    pub id: &'a String,
+    pub cargo_type: &'a CargoType,
+    pub price: u32,
+}
+
+/// The current market's cargo that passes the category filter, in the
+/// chosen order
+pub fn market_cargo<'a>(
+    game_state: &'a GameState,
+    scene_state: &SceneState,
+) -> Vec<MarketCargo<'a>> {
+    let Some(market) = game_state.get_current_market() else {
+        return Vec::new();
+    };
+    let mut cargo: Vec<MarketCargo<'a>> = market
+        .cargo_prices
+        .iter()
+        .filter_map(|(id, price)| {
+            let cargo_type = game_state.cargo_types.get(id)?;
+            Some(MarketCargo {
+                id,
+                cargo_type,
+                price: *price,
+            })
+        })
+        .filter(|cargo| {
+            scene_state
+                .cargo_category_filter
+                .is_none_or(|category| cargo.cargo_type.category == category)
+        })
+        .collect();
+
+    cargo.sort_by(|a, b| a.cargo_type.name.cmp(&b.cargo_type.name));
+    match scene_state.cargo_sort {
+        CargoSort::Name => {},
+        CargoSort::PriceLowToHigh => cargo.sort_by_key(|cargo| cargo.price),
+        CargoSort::PriceHighToLow => cargo.sort_by_key(|cargo| std::cmp::Reverse(cargo.price)),
+        CargoSort::Category => cargo.sort_by_key(|cargo| cargo.cargo_type.category),
+    }
+    cargo
+}
+
+/// Category filter and sort pickers shared by the market board and trading desk
+pub fn cargo_filter_bar(scene_state: &mut SceneState, id_salt: &str, ui: &mut eframe::egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.label("🏷 Category:");
+        eframe::egui::ComboBox::from_id_salt(format!("{id_salt}_category"))
+            .selected_text(
+                scene_state
+                    .cargo_category_filter
+                    .map(|category| category.label())
+                    .unwrap_or("All"),
+            )
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut scene_state.cargo_category_filter, None, "All");
+                for category in CargoCategory::ALL {
+                    ui.selectable_value(
+                        &mut scene_state.cargo_category_filter,
+                        Some(category),
+                        category.label(),
+                    );
+                }
+            });
+
+        ui.add_space(10.0);
+        ui.label("↕ Sort:");
+        eframe::egui::ComboBox::from_id_salt(format!("{id_salt}_sort"))
+            .selected_text(scene_state.cargo_sort.label())
+            .show_ui(ui, |ui| {
+                for sort in CargoSort::ALL {
+                    ui.selectable_value(&mut scene_state.cargo_sort, sort, sort.label());
+                }
+            });
+    });
+}
@@ -0,0 +1,145 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    models::MessageTopic,
+    systems::{SaveSystem, offers::OfferSide},
+    ui::scenes::{SceneState, room_lobby::GameSession},
+};
+
+const DRAFTS_FILE: &str = "gui_drafts.json";
+
+/// How often half-finished input is written out while it keeps changing
+pub const DRAFT_SAVE_INTERVAL: Duration = Duration::from_secs(3);
+
+/// What the player was in the middle of typing or picking, kept on disk so
+/// a crash or a closed window doesn't lose it. Only restored into the same
+/// player's seat in the same room.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SceneDraft {
+    pub room_id: Uuid,
+    pub player_id: Uuid,
+
+    // Message board
+    pub message_input: String,
+    pub show_message_compose: bool,
+    pub courier_destination: Option<String>,
+    pub post_topic: MessageTopic,
+
+    // Trading desk and order book
+    pub selected_cargo: Option<String>,
+    pub trade_quantity: u32,
+    pub offer_side: OfferSide,
+    pub offer_quantity: u32,
+    pub offer_price: u32,
+    pub loadout_name: String,
+
+    // Fuel pump, flight planning and bank
+    pub fuel_quantity: u32,
+    pub selected_destination: Option<String>,
+    pub loan_amount: u32,
+}
+
+impl SceneDraft {
+    pub fn capture(scene_state: &SceneState, session: &GameSession) -> Self {
+        Self {
+            room_id: session.room_id,
+            player_id: session.player_id,
+            message_input: scene_state.message_input.clone(),
+            show_message_compose: scene_state.show_message_compose,
+            courier_destination: scene_state.courier_destination.clone(),
+            post_topic: scene_state.post_topic,
+            selected_cargo: scene_state.selected_cargo.clone(),
+            trade_quantity: scene_state.trade_quantity,
+            offer_side: scene_state.offer_side,
+            offer_quantity: scene_state.offer_quantity,
+            offer_price: scene_state.offer_price,
+            loadout_name: scene_state.loadout_name.clone(),
+            fuel_quantity: scene_state.fuel_quantity,
+            selected_destination: scene_state.selected_destination.clone(),
+            loan_amount: scene_state.loan_amount,
+        }
+    }
+
+    pub fn belongs_to(&self, session: &GameSession) -> bool {
+        self.room_id == session.room_id && self.player_id == session.player_id
+    }
+
+    /// Put the draft back, unless it was saved in someone else's session.
+    /// Returns whether it was restored.
+    pub fn restore(&self, scene_state: &mut SceneState, session: &GameSession) -> bool {
+        if !self.belongs_to(session) {
+            return false;
+        }
+        scene_state.message_input = self.message_input.clone();
+        scene_state.show_message_compose = self.show_message_compose;
+        scene_state.courier_destination = self.courier_destination.clone();
+        scene_state.post_topic = self.post_topic;
+        scene_state.selected_cargo = self.selected_cargo.clone();
+        scene_state.trade_quantity = self.trade_quantity;
+        scene_state.offer_side = self.offer_side;
+        scene_state.offer_quantity = self.offer_quantity;
+        scene_state.offer_price = self.offer_price;
+        scene_state.loadout_name = self.loadout_name.clone();
+        // The pump has its preset to start at; a saved amount wins over it
+        scene_state.fuel_quantity = self.fuel_quantity;
+        scene_state.fuel_preset_pending = false;
+        scene_state.selected_destination = self.selected_destination.clone();
+        scene_state.loan_amount = self.loan_amount;
+        true
+    }
+}
+
+/// The saved draft, if there is one that can be read
+pub fn load() -> Option<SceneDraft> {
+    file_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+pub fn save(draft: &SceneDraft) -> Result<(), String> {
+    let path = file_path().ok_or("Could not determine where to save the drafts")?;
+    let json = serde_json::to_string_pretty(draft)
+        .map_err(|e| format!("Failed to serialize drafts: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to save drafts: {}", e))
+}
+
+/// Forget the saved draft, once the player has left the room
+pub fn clear() {
+    if let Some(path) = file_path() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Write the draft out if it's changed and the last write was long enough
+/// ago
+pub fn save_if_due(scene_state: &mut SceneState, session: &GameSession) {
+    let due = scene_state
+        .draft_saved_at
+        .is_none_or(|saved_at| saved_at.elapsed() >= DRAFT_SAVE_INTERVAL);
+    if !due {
+        return;
+    }
+    let draft = SceneDraft::capture(scene_state, session);
+    scene_state.draft_saved_at = Some(Instant::now());
+    if scene_state.saved_draft.as_ref() == Some(&draft) {
+        return;
+    }
+    if let Err(e) = save(&draft) {
+        eprintln!("{}", e);
+    }
+    scene_state.saved_draft = Some(draft);
+}
+
+/// Next to the panel layout, above the save games
+fn file_path() -> Option<PathBuf> {
+    let save_dir = SaveSystem::get_save_directory().ok()?;
+    Some(save_dir.parent()?.join(DRAFTS_FILE))
+}
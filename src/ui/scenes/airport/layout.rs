@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::systems::SaveSystem;
+
+const LAYOUT_FILE: &str = "gui_layout.json";
+
+/// Where a panel sits around the current FBO location
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Dock {
+    Hidden,
+    Left,
+    Right,
+    Bottom,
+}
+
+impl Dock {
+    pub const ALL: [Dock; 4] = [Dock::Hidden, Dock::Left, Dock::Right, Dock::Bottom];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Dock::Hidden => "Hidden",
+            Dock::Left => "Left",
+            Dock::Right => "Right",
+            Dock::Bottom => "Bottom",
+        }
+    }
+}
+
+/// The panels that can be docked next to the current location
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DockedPanel {
+    Market,
+    Inventory,
+    Chat,
+}
+
+impl DockedPanel {
+    pub const ALL: [DockedPanel; 3] = [
+        DockedPanel::Market,
+        DockedPanel::Inventory,
+        DockedPanel::Chat,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DockedPanel::Market => "📊 Market",
+            DockedPanel::Inventory => "📦 Inventory",
+            DockedPanel::Chat => "💬 Chat",
+        }
+    }
+}
+
+/// A panel's dock and its width, or height when docked at the bottom
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PanelPlacement {
+    pub dock: Dock,
+    pub size: f32,
+}
+
+impl PanelPlacement {
+    fn hidden(size: f32) -> Self {
+        Self {
+            dock: Dock::Hidden,
+            size,
+        }
+    }
+}
+
+/// Which panels the airport scene shows around the current location.
+/// Everything is hidden by default, giving the single-view layout; players
+/// with room to spare can dock the market, their inventory and the chat
+/// alongside it. Saved next to the save games so it survives restarts.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PanelLayout {
+    pub market: PanelPlacement,
+    pub inventory: PanelPlacement,
+    pub chat: PanelPlacement,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            market: PanelPlacement::hidden(360.0),
+            inventory: PanelPlacement::hidden(280.0),
+            chat: PanelPlacement::hidden(320.0),
+        }
+    }
+}
+
+impl PanelLayout {
+    pub fn placement(&self, panel: DockedPanel) -> PanelPlacement {
+        match panel {
+            DockedPanel::Market => self.market,
+            DockedPanel::Inventory => self.inventory,
+            DockedPanel::Chat => self.chat,
+        }
+    }
+
+    pub fn placement_mut(&mut self, panel: DockedPanel) -> &mut PanelPlacement {
+        match panel {
+            DockedPanel::Market => &mut self.market,
+            DockedPanel::Inventory => &mut self.inventory,
+            DockedPanel::Chat => &mut self.chat,
+        }
+    }
+
+    /// The saved layout, or the default when there is none or it can't be read
+    pub fn load() -> Self {
+        Self::file_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::file_path().ok_or("Could not determine where to save the layout")?;
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize layout: {}", e))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to save layout: {}", e))
+    }
+
+    fn file_path() -> Option<PathBuf> {
+        let save_dir = SaveSystem::get_save_directory().ok()?;
+        Some(save_dir.parent()?.join(LAYOUT_FILE))
+    }
+}
@@ -0,0 +1,144 @@
+use crate::ui::scenes::{
+    Location,
+    airport::locations::{LocationContext, LocationView},
+};
+
+pub struct Bank;
+
+impl LocationView for Bank {
+    fn location(&self) -> Location {
+        Location::Bank
+    }
+
+    fn label(&self) -> &'static str {
+        "🏦 Bank"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            scene_state,
+            api_client,
+            session,
+            ..
+        } = context;
+
+        ui.heading("🏦 Bank - Loans");
+
+        if scene_state.bank.is_none() {
+            match api_client.get_loans_sync(session.room_id, session.player_id) {
+                Ok(bank) => scene_state.bank = Some(bank),
+                Err(err) => {
+                    ui.colored_label(
+                        eframe::egui::Color32::RED,
+                        format!("Error loading the bank: {}", err),
+                    );
+                    return;
+                },
+            }
+        }
+        let Some(bank) = scene_state.bank.clone() else {
+            return;
+        };
+
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_gray(245))
+            .inner_margin(eframe::egui::Margin::same(12.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("💰 Cash: ${}", bank.money));
+                    ui.separator();
+                    ui.label(format!("📉 Owed: ${}", bank.debt));
+                    ui.separator();
+                    ui.label(format!("💳 Credit available: ${}", bank.credit_available));
+                });
+                ui.weak(format!(
+                    "{}% interest a turn, repaid over {} turns. Owing over ${} means bankruptcy: \
+                     the bank takes your cash and cargo.",
+                    bank.interest_percent, bank.term_turns, bank.bankruptcy_debt
+                ));
+            });
+
+        if let Some(message) = &scene_state.bank_message {
+            ui.label(message);
+        }
+        ui.add_space(8.0);
+
+        if bank.bankrupt {
+            ui.colored_label(
+                eframe::egui::Color32::from_rgb(200, 50, 50),
+                "You've been declared bankrupt. The bank won't lend to you again.",
+            );
+        } else {
+            ui.horizontal(|ui| {
+                ui.label("Borrow:");
+                ui.add(
+                    eframe::egui::DragValue::new(&mut scene_state.loan_amount)
+                        .range(bank.min_loan..=bank.credit_available.max(bank.min_loan))
+                        .speed(100)
+                        .prefix("$"),
+                );
+                let button = ui.add_enabled(
+                    bank.credit_available >= bank.min_loan,
+                    eframe::egui::Button::new("✍️ Take out loan"),
+                );
+                if button.clicked() {
+                    let amount = scene_state.loan_amount;
+                    match api_client.borrow_sync(session.room_id, session.player_id, amount) {
+                        Ok(bank) => {
+                            scene_state.bank_message = Some(format!("Borrowed ${}", amount));
+                            scene_state.bank = Some(bank);
+                        },
+                        Err(err) => scene_state.bank_message = Some(err.to_string()),
+                    }
+                }
+            });
+        }
+
+        ui.add_space(8.0);
+        ui.strong("Loans:");
+        if bank.loans.is_empty() {
+            ui.label("You don't owe the bank anything.");
+            return;
+        }
+
+        eframe::egui::Grid::new("bank_loans")
+            .striped(true)
+            .num_columns(5)
+            .show(ui, |ui| {
+                for header in ["Borrowed", "Owed", "Per turn", "Missed", ""] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+
+                for loan in &bank.loans {
+                    ui.label(format!(
+                        "${} on turn {}",
+                        loan.principal, loan.taken_at_turn
+                    ));
+                    ui.label(format!("${}", loan.balance));
+                    ui.label(format!("${}", loan.installment));
+                    ui.label(loan.missed_payments.to_string());
+                    let payment = loan.balance.min(bank.money);
+                    let button = ui.add_enabled(
+                        payment > 0,
+                        eframe::egui::Button::new(format!("💵 Repay ${}", payment)),
+                    );
+                    if button.clicked() {
+                        match api_client.repay_loan_sync(
+                            session.room_id,
+                            session.player_id,
+                            loan.id,
+                            payment,
+                        ) {
+                            Ok(bank) => {
+                                scene_state.bank_message = Some(format!("Repaid ${}", payment));
+                                scene_state.bank = Some(bank);
+                            },
+                            Err(err) => scene_state.bank_message = Some(err.to_string()),
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+
+use crate::{
+    api::models::{DestinationInfo, TravelRequest},
+    models::locale::{Locale, Localized},
+    systems::{
+        slots::SlotStatus,
+        travel::{FlightRange, TravelQuote, TravelSystem},
+    },
+    ui::{
+        action_queue::ActionKind,
+        scenes::{
+            Location,
+            airport::{
+                components::fuel_outage_at,
+                locations::{LocationContext, LocationView},
+            },
+        },
+    },
+};
+
+/// Rows for airports close enough to reach by road
+const GROUND_TRANSFER_COLOR: eframe::egui::Color32 = eframe::egui::Color32::from_rgb(70, 110, 200);
+
+/// In range on the fuel aboard
+const IN_RANGE_COLOR: eframe::egui::Color32 = eframe::egui::Color32::from_rgb(50, 150, 50);
+
+/// Out of range until the tank is filled
+const FULL_TANK_COLOR: eframe::egui::Color32 = eframe::egui::Color32::from_rgb(255, 140, 0);
+
+/// Beyond even a full tank
+const OUT_OF_RANGE_COLOR: eframe::egui::Color32 = eframe::egui::Color32::from_rgb(220, 50, 50);
+
+pub struct FlightPlanning;
+
+impl LocationView for FlightPlanning {
+    fn location(&self) -> Location {
+        Location::FlightPlanning
+    }
+
+    fn label(&self) -> &'static str {
+        "✈️ Flight Planning"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            game_state,
+            scene_state,
+            api_client,
+            session,
+        } = context;
+
+        ui.heading("✈️ Flight Planning - Choose Your Destination");
+
+        // Flight planning header
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_rgb(240, 250, 255))
+            .inner_margin(eframe::egui::Margin::same(8.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("\"Where would you like to fly today? I'll calculate fuel requirements for you.\"");
+                    if game_state.cheat_mode {
+                        ui.with_layout(eframe::egui::Layout::right_to_left(eframe::egui::Align::Center), |ui| {
+                            ui.colored_label(eframe::egui::Color32::from_rgb(255, 140, 0), "⚡ CHEAT MODE: Unlimited Fuel");
+                        });
+                    }
+                });
+            });
+
+        ui.separator();
+
+        // Current flight status
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_gray(248))
+            .stroke(eframe::egui::Stroke::new(
+                1.0,
+                eframe::egui::Color32::from_gray(200),
+            ))
+            .inner_margin(eframe::egui::Margin::same(8.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("🛩️ Aircraft Status:");
+                    ui.separator();
+                    ui.label(format!(
+                        "Fuel: {}/{} units",
+                        game_state.player.fuel, game_state.player.max_fuel
+                    ));
+                    ui.separator();
+                    match &scene_state.flight_range {
+                        Some(range) => {
+                            ui.label(format!(
+                                "Range: ~{:.0}km now, ~{:.0}km on a full tank",
+                                range.current_fuel_km, range.full_tank_km
+                            ));
+                        },
+                        None => {
+                            ui.weak("Range: waiting for the tower");
+                        },
+                    }
+                    ui.separator();
+                    let fuel_percent =
+                        (game_state.player.fuel as f32 / game_state.player.max_fuel as f32) * 100.0;
+                    let fuel_color = if fuel_percent > 75.0 {
+                        eframe::egui::Color32::from_rgb(50, 150, 50)
+                    } else if fuel_percent > 25.0 {
+                        eframe::egui::Color32::from_rgb(255, 140, 0)
+                    } else {
+                        eframe::egui::Color32::from_rgb(220, 50, 50)
+                    };
+                    ui.colored_label(fuel_color, format!("Fuel: {:.0}%", fuel_percent));
+                });
+            });
+
+        ui.separator();
+
+        let destinations: Vec<_> = game_state
+            .get_available_destinations()
+            .into_iter()
+            .cloned()
+            .collect();
+        let current_airport = game_state.get_current_airport().cloned();
+        let fuel_price = game_state
+            .get_current_market()
+            .map(|market| market.fuel_price)
+            .unwrap_or(80); // Default estimate
+
+        if let Some(range) = &scene_state.flight_range {
+            Self::render_range_strip(ui, range, &scene_state.destinations);
+        }
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut scene_state.show_only_reachable, "Show only reachable")
+                .on_hover_text(
+                    "Hide airports the fuel aboard won't get you to, unless they're a drive away",
+                );
+            if scene_state.show_only_reachable {
+                let hidden = scene_state
+                    .destinations
+                    .values()
+                    .filter(|destination| !Self::reachable(destination, game_state.cheat_mode))
+                    .count();
+                if hidden > 0 {
+                    ui.weak(format!("{} out of range hidden", hidden));
+                }
+            }
+        });
+
+        // Enhanced destinations grid
+        eframe::egui::Grid::new("destinations_grid")
+            .num_columns(7)
+            .spacing([15.0, 8.0])
+            .striped(true)
+            .show(ui, |ui| {
+                // Enhanced header row
+                ui.strong("🏢 Airport");
+                ui.strong("📏 Distance");
+                ui.strong("⛽ Fuel Needed");
+                ui.strong("💰 Est. Fuel Cost");
+                ui.strong("✈️ Can Fly?");
+                ui.strong("📊 Market Info");
+                ui.strong("🎯 Action");
+                ui.end_row();
+
+                for airport in destinations {
+                    if let Some(current_airport) = &current_airport {
+                        let quote = TravelSystem::quote(
+                            &game_state.player,
+                            current_airport,
+                            &airport,
+                            fuel_price,
+                            game_state.flight_conditions(&current_airport.id, &airport.id),
+                        );
+                        let distance = quote.distance;
+                        // The server's figures when it has sent them
+                        let server = scene_state.destinations.get(&airport.id);
+                        if scene_state.show_only_reachable
+                            && server.is_some_and(|destination| {
+                                !Self::reachable(destination, game_state.cheat_mode)
+                            })
+                        {
+                            continue;
+                        }
+                        let slot_status = server
+                            .map(|destination| destination.slot_status)
+                            .unwrap_or_default();
+                        let fuel_required = server
+                            .map_or(quote.total_fuel, |destination| destination.fuel_required);
+                        let can_travel = (server
+                            .map_or(quote.can_travel, |destination| destination.can_travel)
+                            || game_state.cheat_mode)
+                            && slot_status.can_land();
+                        let ground_fare =
+                            TravelSystem::ground_transfer_fare(current_airport, &airport);
+
+                        // Airport name with region indicator
+                        let region_icon = match airport.id.as_str() {
+                            "JFK" => "🗽",
+                            "LAX" => "🌴",
+                            "MIA" => "🏖️",
+                            "ORD" => "🏙️",
+                            "DEN" => "🏔️",
+                            "SEA" => "🌲",
+                            _ => "🏢",
+                        };
+                        if ground_fare.is_some() {
+                            ui.colored_label(
+                                GROUND_TRANSFER_COLOR,
+                                format!("🚌 {} (same metro)", airport.name),
+                            );
+                        } else {
+                            ui.label(format!("{} {}", region_icon, airport.name));
+                        }
+
+                        // Distance with color coding
+                        let distance_color = if ground_fare.is_some() {
+                            GROUND_TRANSFER_COLOR
+                        } else if distance > 3000.0 {
+                            eframe::egui::Color32::from_rgb(220, 50, 50) // Red for long distance
+                        } else if distance > 1500.0 {
+                            eframe::egui::Color32::from_rgb(255, 140, 0) // Orange for medium
+                        } else {
+                            eframe::egui::Color32::from_rgb(50, 150, 50) // Green for short
+                        };
+                        ui.colored_label(distance_color, format!("{:.0} km", distance));
+
+                        // Fuel needed with efficiency indicator
+                        ui.label(format!("{} units", fuel_required));
+
+                        // Estimated fuel cost (assuming current market price), or the
+                        // road fare when there's no need to fly
+                        match ground_fare {
+                            Some(fare) => ui.colored_label(
+                                GROUND_TRANSFER_COLOR,
+                                format!("~${} or ${} by road", quote.refill_cost, fare),
+                            ),
+                            None => ui.label(format!("~${}", quote.refill_cost)),
+                        };
+
+                        // Can travel status with better feedback
+                        if !slot_status.can_land() {
+                            ui.colored_label(
+                                eframe::egui::Color32::from_rgb(220, 50, 50),
+                                "🛑 Slots full",
+                            )
+                            .on_hover_text(slot_status.label());
+                        } else if can_travel {
+                            if game_state.cheat_mode {
+                                ui.colored_label(
+                                    eframe::egui::Color32::from_rgb(255, 140, 0),
+                                    "⚡ Cheat",
+                                );
+                            } else if slot_status == SlotStatus::Holding {
+                                ui.colored_label(
+                                    eframe::egui::Color32::from_rgb(255, 140, 0),
+                                    "⏳ Hold 1T",
+                                )
+                                .on_hover_text(slot_status.label());
+                            } else {
+                                ui.colored_label(
+                                    eframe::egui::Color32::from_rgb(50, 150, 50),
+                                    "✅ Yes",
+                                );
+                            }
+                        } else if server
+                            .is_none_or(|destination| destination.reachable_on_full_tank)
+                        {
+                            let fuel_deficit = fuel_required.saturating_sub(game_state.player.fuel);
+                            ui.colored_label(FULL_TANK_COLOR, format!("⛽ Need +{}", fuel_deficit))
+                                .on_hover_text("In range once you fill up");
+                        } else {
+                            ui.colored_label(OUT_OF_RANGE_COLOR, "❌ Out of range")
+                                .on_hover_text("Too far even on a full tank");
+                        }
+
+                        // Market intelligence preview, unless there's no fuel to be had
+                        if let Some(outage) =
+                            fuel_outage_at(&scene_state.active_events, &airport.id)
+                        {
+                            ui.colored_label(
+                                eframe::egui::Color32::from_rgb(220, 50, 50),
+                                format!("⛽ No fuel for {}T", outage.turns_remaining),
+                            )
+                            .on_hover_text(&outage.description);
+                        } else {
+                            let market_hint = match airport.id.as_str() {
+                                "JFK" => "💻 Tech Hub",
+                                "LAX" => "🎬 Entertainment",
+                                "MIA" => "🍎 Agriculture",
+                                "ORD" => "🔧 Industrial",
+                                "DEN" => "🏔️ Regional",
+                                "SEA" => "☕ Pacific",
+                                _ => "📊 Mixed",
+                            };
+                            ui.label(market_hint);
+                        }
+
+                        // Enhanced action buttons
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button("🔍")
+                                .on_hover_text("Itemized cost from the server")
+                                .clicked()
+                            {
+                                scene_state.travel_preview = Some(
+                                    api_client
+                                        .travel_preview_sync(
+                                            session.room_id,
+                                            session.player_id,
+                                            &airport.id,
+                                        )
+                                        .map_err(|e| e.to_string()),
+                                );
+                            }
+
+                            if let Some(fare) = ground_fare {
+                                let can_pay = game_state.player.money >= fare;
+                                let button = ui
+                                    .add_enabled(
+                                        can_pay,
+                                        eframe::egui::Button::new(
+                                            eframe::egui::RichText::new("🚌 Transfer")
+                                                .color(GROUND_TRANSFER_COLOR),
+                                        ),
+                                    )
+                                    .on_hover_text(format!(
+                                        "Go by road for ${} - no fuel, takes a turn",
+                                        fare
+                                    ));
+                                if button.clicked() {
+                                    scene_state.outbox.push(ActionKind::GroundTransfer(
+                                        TravelRequest {
+                                            destination: airport.id.clone(),
+                                        },
+                                    ));
+                                }
+                            }
+
+                            ui.add_enabled_ui(can_travel, |ui| {
+                                let button_text = if game_state.cheat_mode {
+                                    "⚡ Instant Fly"
+                                } else {
+                                    "🛫 Fly"
+                                };
+
+                                if ui.button(button_text).clicked() {
+                                    scene_state.outbox.push(ActionKind::Travel(TravelRequest {
+                                        destination: airport.id.clone(),
+                                    }));
+                                }
+                            });
+                        });
+
+                        ui.end_row();
+                    }
+                }
+            });
+
+        Self::render_travel_preview(scene_state.travel_preview.as_ref(), ui);
+
+        ui.separator();
+
+        // Flight planning tips
+        ui.collapsing("💡 Flight Planning Tips", |ui| {
+            ui.label("• Short flights (< 1500km) are more fuel efficient for cargo runs");
+            ui.label("• Check market prices at destination before flying");
+            ui.label("• Keep emergency fuel reserves for unexpected opportunities");
+            ui.label("• Consider fuel costs when calculating trade profits");
+            ui.label(
+                "• Airports in the same metro area can be reached by road: no fuel, just a fare",
+            );
+            ui.label(
+                "• Busy hubs only take so many landings at a time: a full one costs a turn in a holding pattern",
+            );
+            if game_state.cheat_mode {
+                ui.colored_label(
+                    eframe::egui::Color32::from_rgb(255, 140, 0),
+                    "• Cheat mode active: Unlimited fuel available",
+                );
+            }
+        });
+    }
+}
+
+impl FlightPlanning {
+    /// Whether a destination stays listed under "Show only reachable":
+    /// flyable on the fuel aboard, or close enough to drive to
+    pub fn reachable(destination: &DestinationInfo, cheat_mode: bool) -> bool {
+        destination.can_travel || cheat_mode || destination.ground_transfer_fare.is_some()
+    }
+
+    /// Every destination along a line by distance, under the range on the
+    /// fuel aboard and on a full tank
+    fn render_range_strip(
+        ui: &mut eframe::egui::Ui,
+        range: &FlightRange,
+        destinations: &HashMap<String, DestinationInfo>,
+    ) {
+        let size = eframe::egui::vec2(ui.available_width(), 44.0);
+        let (response, painter) = ui.allocate_painter(size, eframe::egui::Sense::hover());
+        let rect = response.rect;
+        painter.rect_filled(rect, 4.0, eframe::egui::Color32::from_gray(245));
+
+        let furthest = destinations
+            .values()
+            .map(|destination| destination.distance)
+            .fold(range.full_tank_km, f64::max)
+            .max(1.0);
+        let x = |km: f64| rect.left() + rect.width() * (km / furthest).min(1.0) as f32;
+
+        // The reach of a full tank, then of the fuel aboard on top of it
+        let band = |km: f64, color: eframe::egui::Color32| {
+            let band = eframe::egui::Rect::from_min_max(
+                eframe::egui::pos2(rect.left(), rect.center().y - 6.0),
+                eframe::egui::pos2(x(km), rect.center().y + 6.0),
+            );
+            painter.rect_filled(band, 3.0, color);
+        };
+        band(range.full_tank_km, FULL_TANK_COLOR.gamma_multiply(0.3));
+        band(range.current_fuel_km, IN_RANGE_COLOR.gamma_multiply(0.4));
+
+        let mut ids: Vec<&String> = destinations.keys().collect();
+        ids.sort();
+        for id in ids {
+            let destination = &destinations[id];
+            let color = if destination.can_travel {
+                IN_RANGE_COLOR
+            } else if destination.reachable_on_full_tank {
+                FULL_TANK_COLOR
+            } else {
+                OUT_OF_RANGE_COLOR
+            };
+            let center = eframe::egui::pos2(x(destination.distance), rect.center().y);
+            painter.circle_filled(center, 4.0, color);
+            painter.text(
+                center - eframe::egui::vec2(0.0, 8.0),
+                eframe::egui::Align2::CENTER_BOTTOM,
+                id,
+                eframe::egui::FontId::proportional(10.0),
+                eframe::egui::Color32::DARK_GRAY,
+            );
+        }
+        response.on_hover_text(format!(
+            "🟢 {:.0} km on the fuel aboard, 🟠 {:.0} km on a full tank",
+            range.current_fuel_km, range.full_tank_km
+        ));
+    }
+
+    /// Show the server's breakdown line for line - the server charges exactly
+    /// this when the flight goes ahead.
+    fn render_travel_preview(
+        preview: Option<&Result<TravelQuote, String>>,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        let Some(preview) = preview else {
+            return;
+        };
+
+        ui.separator();
+        match preview {
+            Ok(quote) => {
+                ui.strong(format!(
+                    "🧾 {} → {} ({:.0} km)",
+                    quote.from, quote.to, quote.distance
+                ));
+                eframe::egui::Grid::new("travel_preview_grid")
+                    .num_columns(3)
+                    .spacing([15.0, 4.0])
+                    .show(ui, |ui| {
+                        for item in &quote.items {
+                            ui.label(&item.label);
+                            ui.label(format!("{} fuel", item.fuel));
+                            ui.label(format!("${}", item.money));
+                            ui.end_row();
+                        }
+                        ui.strong("Total");
+                        ui.strong(format!("{} fuel", quote.total_fuel));
+                        ui.strong(format!("${}", quote.total_money));
+                        ui.end_row();
+                    });
+                ui.label(format!(
+                    "Refilling afterwards costs ~${}",
+                    quote.refill_cost
+                ));
+
+                let color = if quote.can_travel {
+                    eframe::egui::Color32::from_rgb(50, 150, 50)
+                } else {
+                    eframe::egui::Color32::from_rgb(220, 50, 50)
+                };
+                ui.colored_label(color, quote.message_in(Locale::current()));
+            },
+            Err(error) => {
+                ui.colored_label(
+                    eframe::egui::Color32::from_rgb(220, 50, 50),
+                    format!("Couldn't get a travel preview: {}", error),
+                );
+            },
+        }
+    }
+}
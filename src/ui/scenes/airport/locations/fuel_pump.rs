@@ -0,0 +1,357 @@
+use crate::{
+    api::models::FuelRequest,
+    ui::{
+        action_queue::ActionKind,
+        game_api_client::GameApiClient,
+        scenes::{
+            Location, SceneState,
+            airport::{
+                components::{current_fuel_economy, fuel_level_color, fuel_outage_at},
+                locations::{LocationContext, LocationView},
+            },
+            room_lobby::GameSession,
+        },
+    },
+};
+
+pub struct FuelPump;
+
+impl LocationView for FuelPump {
+    fn location(&self) -> Location {
+        Location::FuelPump
+    }
+
+    fn label(&self) -> &'static str {
+        "⛽ Fuel Pump"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            game_state,
+            scene_state,
+            api_client,
+            session,
+        } = context;
+
+        ui.heading("⛽ Fuel Pump - Fill Up Your Tank");
+
+        // Fuel pump header
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_rgb(255, 248, 220))
+            .inner_margin(eframe::egui::Margin::same(8.0))
+            .show(ui, |ui| {
+                ui.label("\"Need fuel for your next flight? We've got premium aviation fuel ready to pump!\"");
+            });
+
+        ui.separator();
+
+        render_auto_refuel(scene_state, api_client, session, ui);
+
+        if let Some(outage) = fuel_outage_at(
+            &scene_state.active_events,
+            &game_state.player.current_airport,
+        ) {
+            ui.colored_label(
+                eframe::egui::Color32::from_rgb(220, 50, 50),
+                format!(
+                    "\"Sorry, we're out of fuel. The next delivery is {} turn(s) away.\"",
+                    outage.turns_remaining
+                ),
+            );
+            return;
+        }
+
+        // Start at the player's preset, or a full tank just after landing
+        if scene_state.fuel_preset_pending {
+            let space = game_state
+                .player
+                .max_fuel
+                .saturating_sub(game_state.player.fuel);
+            scene_state.fuel_quantity = scene_state
+                .quantity_defaults
+                .fuel_quantity_for(space, scene_state.just_landed)
+                .max(1);
+            scene_state.fuel_preset_pending = false;
+            scene_state.just_landed = false;
+        }
+
+        if let Some(market) = game_state.get_current_market() {
+            // Fuel status display
+            eframe::egui::Frame::none()
+                .fill(eframe::egui::Color32::from_gray(245))
+                .stroke(eframe::egui::Stroke::new(
+                    1.0,
+                    eframe::egui::Color32::from_gray(200),
+                ))
+                .inner_margin(eframe::egui::Margin::same(12.0))
+                .show(ui, |ui| {
+                    ui.strong("🛩️ Aircraft Fuel Status");
+                    ui.separator();
+
+                    eframe::egui::Grid::new("fuel_status")
+                        .num_columns(2)
+                        .spacing([20.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.label("Current Fuel:");
+                            let fuel_percent = (game_state.player.fuel as f32
+                                / game_state.player.max_fuel as f32)
+                                * 100.0;
+                            ui.horizontal(|ui| {
+                                ui.label(format!(
+                                    "{}/{} units",
+                                    game_state.player.fuel, game_state.player.max_fuel
+                                ));
+                                let fuel_color = if fuel_percent > 75.0 {
+                                    eframe::egui::Color32::from_rgb(50, 150, 50)
+                                } else if fuel_percent > 25.0 {
+                                    eframe::egui::Color32::from_rgb(255, 140, 0)
+                                } else {
+                                    eframe::egui::Color32::from_rgb(220, 50, 50)
+                                };
+                                ui.colored_label(fuel_color, format!("({:.0}%)", fuel_percent));
+                            });
+                            ui.end_row();
+
+                            ui.label("Current Range:");
+                            let range =
+                                (game_state.player.fuel as f32) * game_state.player.fuel_efficiency;
+                            ui.label(format!("~{:.0} km", range));
+                            ui.end_row();
+
+                            ui.label("Fuel Price Today:");
+                            match current_fuel_economy(game_state) {
+                                Some(economy) => {
+                                    let level = economy.level(market.fuel_price);
+                                    ui.colored_label(
+                                        fuel_level_color(level),
+                                        format!(
+                                            "${}/unit ({}, range {})",
+                                            market.fuel_price,
+                                            level.label().to_lowercase(),
+                                            economy.range_label()
+                                        ),
+                                    );
+                                },
+                                None => {
+                                    ui.label(format!("${}/unit", market.fuel_price));
+                                },
+                            }
+                            ui.end_row();
+                        });
+                });
+
+            ui.separator();
+
+            // Enhanced fuel quantity selection
+            ui.horizontal(|ui| {
+                ui.label("⛽ Fuel Quantity:");
+                ui.add_space(10.0);
+
+                let max_fuel_can_add = game_state.player.max_fuel - game_state.player.fuel;
+                let max_slider = max_fuel_can_add.max(scene_state.fuel_quantity);
+
+                ui.add(
+                    eframe::egui::Slider::new(&mut scene_state.fuel_quantity, 1..=max_slider)
+                        .text("units"),
+                );
+
+                // Quick fuel buttons
+                ui.separator();
+                if ui.small_button("10").clicked() {
+                    scene_state.fuel_quantity = 10.min(max_fuel_can_add);
+                }
+                if ui.small_button("25").clicked() {
+                    scene_state.fuel_quantity = 25.min(max_fuel_can_add);
+                }
+                if ui.small_button("50").clicked() {
+                    scene_state.fuel_quantity = 50.min(max_fuel_can_add);
+                }
+                if ui.small_button("Fill").clicked() {
+                    scene_state.fuel_quantity = max_fuel_can_add;
+                }
+                let preset = scene_state.quantity_defaults.fuel_quantity;
+                if ui.small_button(format!("Preset ({})", preset)).clicked() {
+                    scene_state.fuel_quantity = preset.min(max_fuel_can_add);
+                }
+            });
+
+            let max_fuel_can_add = game_state
+                .player
+                .max_fuel
+                .saturating_sub(game_state.player.fuel);
+            let actual_fuel_to_add = scene_state.fuel_quantity.min(max_fuel_can_add);
+            let total_cost = market.fuel_price.saturating_mul(actual_fuel_to_add);
+
+            ui.separator();
+
+            // Transaction preview
+            if actual_fuel_to_add > 0 {
+                eframe::egui::Frame::none()
+                    .fill(eframe::egui::Color32::from_rgb(240, 255, 240))
+                    .stroke(eframe::egui::Stroke::new(
+                        1.0,
+                        eframe::egui::Color32::from_rgb(100, 200, 100),
+                    ))
+                    .inner_margin(eframe::egui::Margin::same(8.0))
+                    .show(ui, |ui| {
+                        ui.strong("🧾 Fuel Purchase Preview");
+                        ui.separator();
+
+                        eframe::egui::Grid::new("fuel_preview")
+                            .num_columns(2)
+                            .spacing([20.0, 4.0])
+                            .show(ui, |ui| {
+                                ui.label("Fuel to Add:");
+                                ui.label(format!("{} units", actual_fuel_to_add));
+                                ui.end_row();
+
+                                ui.label("Total Cost:");
+                                ui.label(format!("${}", total_cost));
+                                ui.end_row();
+
+                                ui.label("After Purchase:");
+                                ui.label(format!(
+                                    "{}/{} units ({:.0}%)",
+                                    game_state.player.fuel + actual_fuel_to_add,
+                                    game_state.player.max_fuel,
+                                    ((game_state.player.fuel + actual_fuel_to_add) as f32
+                                        / game_state.player.max_fuel as f32)
+                                        * 100.0
+                                ));
+                                ui.end_row();
+
+                                ui.label("New Range:");
+                                let new_range = (game_state.player.fuel + actual_fuel_to_add)
+                                    as f32
+                                    * game_state.player.fuel_efficiency;
+                                ui.label(format!("~{:.0} km", new_range));
+                                ui.end_row();
+
+                                ui.label("Money After:");
+                                ui.label(format!(
+                                    "${}",
+                                    game_state.player.money.saturating_sub(total_cost)
+                                ));
+                                ui.end_row();
+                            });
+                    });
+
+                ui.separator();
+
+                let can_buy = game_state.player.can_afford(total_cost) && actual_fuel_to_add > 0;
+
+                // Purchase button
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(can_buy, |ui| {
+                        if ui
+                            .button(format!(
+                                "⛽ PURCHASE {} units for ${}",
+                                actual_fuel_to_add, total_cost
+                            ))
+                            .clicked()
+                        {
+                            scene_state.outbox.push(ActionKind::BuyFuel(FuelRequest {
+                                quantity: actual_fuel_to_add,
+                            }));
+                        }
+                    });
+
+                    ui.separator();
+
+                    if !can_buy {
+                        if total_cost > game_state.player.money {
+                            ui.colored_label(
+                                eframe::egui::Color32::from_rgb(220, 50, 50),
+                                "💸 Not enough money",
+                            );
+                        }
+                    } else {
+                        ui.label("💡 Tip: Fill up before long flights!");
+                    }
+                });
+            } else {
+                // Tank is full
+                eframe::egui::Frame::none()
+                    .fill(eframe::egui::Color32::from_rgb(255, 255, 240))
+                    .inner_margin(eframe::egui::Margin::same(16.0))
+                    .show(ui, |ui| {
+                        ui.vertical_centered(|ui| {
+                            ui.label("⛽ Tank is Already Full!");
+                            ui.add_space(8.0);
+                            ui.label("🎉 You're ready for any flight with maximum fuel capacity.");
+                        });
+                    });
+            }
+        } else {
+            ui.label("❌ Fuel pumps are not operational at this time.");
+        }
+    }
+}
+
+/// The standing order carried out on every landing, edited here and saved
+/// to the server
+fn render_auto_refuel(
+    scene_state: &mut SceneState,
+    api_client: &GameApiClient,
+    session: &GameSession,
+    ui: &mut eframe::egui::Ui,
+) {
+    eframe::egui::CollapsingHeader::new("🔁 Auto-refuel on arrival").show(ui, |ui| {
+        if scene_state.auto_refuel.is_none() {
+            match api_client.get_auto_refuel_sync(session.room_id, session.player_id) {
+                Ok(auto_refuel) => scene_state.auto_refuel = Some(auto_refuel),
+                Err(err) => {
+                    ui.colored_label(
+                        eframe::egui::Color32::RED,
+                        format!("Error loading auto-refuel: {}", err),
+                    );
+                    return;
+                },
+            }
+        }
+        let Some(auto_refuel) = scene_state.auto_refuel.as_mut() else {
+            return;
+        };
+
+        ui.checkbox(
+            &mut auto_refuel.enabled,
+            "Top up the tank as soon as I land",
+        );
+        ui.add_enabled_ui(auto_refuel.enabled, |ui| {
+            ui.add(
+                eframe::egui::Slider::new(&mut auto_refuel.target_percent, 1..=100)
+                    .text("% of the tank"),
+            );
+            ui.horizontal(|ui| {
+                let mut limited = auto_refuel.max_price.is_some();
+                ui.checkbox(&mut limited, "Only when fuel is under");
+                let mut limit = auto_refuel.max_price.unwrap_or(80);
+                ui.add_enabled(
+                    limited,
+                    eframe::egui::DragValue::new(&mut limit)
+                        .prefix("$")
+                        .range(1..=1000),
+                );
+                auto_refuel.max_price = limited.then_some(limit);
+            });
+        });
+
+        if ui.button("💾 Save").clicked() {
+            let request = *auto_refuel;
+            scene_state.auto_refuel_message = Some(
+                match api_client.set_auto_refuel_sync(session.room_id, session.player_id, &request)
+                {
+                    Ok(saved) => {
+                        scene_state.auto_refuel = Some(saved);
+                        "Saved. It's bought, and shown in the flight report, when you land."
+                            .to_string()
+                    },
+                    Err(err) => format!("Couldn't save: {}", err),
+                },
+            );
+        }
+        if let Some(message) = &scene_state.auto_refuel_message {
+            ui.label(message);
+        }
+    });
+}
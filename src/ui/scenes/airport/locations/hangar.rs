@@ -0,0 +1,111 @@
+use crate::ui::scenes::{
+    Location,
+    airport::locations::{LocationContext, LocationView},
+};
+
+pub struct Hangar;
+
+impl LocationView for Hangar {
+    fn location(&self) -> Location {
+        Location::Hangar
+    }
+
+    fn label(&self) -> &'static str {
+        "🛠️ Hangar"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            scene_state,
+            api_client,
+            session,
+            ..
+        } = context;
+
+        ui.heading("🛠️ Hangar - Aircraft Upgrades");
+
+        if scene_state.hangar.is_none() {
+            match api_client.get_hangar_sync(session.room_id, session.player_id) {
+                Ok(hangar) => scene_state.hangar = Some(hangar),
+                Err(err) => {
+                    ui.colored_label(
+                        eframe::egui::Color32::RED,
+                        format!("Error loading the hangar: {}", err),
+                    );
+                    return;
+                },
+            }
+        }
+        let Some(hangar) = scene_state.hangar.clone() else {
+            return;
+        };
+
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_gray(245))
+            .inner_margin(eframe::egui::Margin::same(12.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("⛽ Tank: {} units", hangar.max_fuel));
+                    ui.separator();
+                    ui.label(format!("📦 Hold: {} kg", hangar.max_cargo_weight));
+                    ui.separator();
+                    ui.label(format!(
+                        "🌱 {:.1} km per unit of fuel",
+                        hangar.fuel_efficiency
+                    ));
+                    ui.separator();
+                    ui.label(format!(
+                        "⚠️ {:.1}% chance of losing cargo each flight",
+                        hangar.cargo_loss_chance * 100.0
+                    ));
+                });
+            });
+
+        if let Some(message) = &scene_state.hangar_message {
+            ui.label(message);
+        }
+        ui.add_space(8.0);
+
+        eframe::egui::Grid::new("hangar_upgrades")
+            .striped(true)
+            .num_columns(4)
+            .show(ui, |ui| {
+                for header in ["Upgrade", "Effect", "Level", ""] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+
+                for upgrade in &hangar.upgrades {
+                    ui.label(&upgrade.label);
+                    ui.label(&upgrade.description);
+                    ui.label(format!("{} / {}", upgrade.level, upgrade.max_level));
+                    match upgrade.next_cost {
+                        Some(cost) => {
+                            let button = ui.add_enabled(
+                                hangar.money >= cost,
+                                eframe::egui::Button::new(format!("🔧 Fit for ${}", cost)),
+                            );
+                            if button.clicked() {
+                                match api_client.buy_upgrade_sync(
+                                    session.room_id,
+                                    session.player_id,
+                                    upgrade.upgrade,
+                                ) {
+                                    Ok(hangar) => {
+                                        scene_state.hangar_message =
+                                            Some(format!("{} fitted for ${}", upgrade.label, cost));
+                                        scene_state.hangar = Some(hangar);
+                                    },
+                                    Err(err) => scene_state.hangar_message = Some(err.to_string()),
+                                }
+                            }
+                        },
+                        None => {
+                            ui.weak("Fully upgraded");
+                        },
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    systems::insurance::ClaimStatus,
+    ui::scenes::{
+        Location,
+        airport::locations::{LocationContext, LocationView},
+    },
+};
+
+pub struct InsuranceOffice;
+
+impl LocationView for InsuranceOffice {
+    fn location(&self) -> Location {
+        Location::InsuranceOffice
+    }
+
+    fn label(&self) -> &'static str {
+        "🛡️ Insurance"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            scene_state,
+            api_client,
+            session,
+            ..
+        } = context;
+
+        ui.heading("🛡️ Insurance Office - Cargo Cover & Claims");
+
+        let insurance = match api_client.get_insurance_sync(session.room_id, session.player_id) {
+            Ok(insurance) => insurance,
+            Err(err) => {
+                ui.colored_label(
+                    eframe::egui::Color32::RED,
+                    format!("Error loading insurance: {}", err),
+                );
+                return;
+            },
+        };
+
+        if let Some(error) = &scene_state.insurance_error {
+            ui.colored_label(eframe::egui::Color32::RED, error);
+        }
+
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_gray(245))
+            .inner_margin(eframe::egui::Margin::same(12.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if insurance.insured {
+                        ui.colored_label(
+                            eframe::egui::Color32::from_rgb(0, 150, 0),
+                            "✅ Your cargo is insured",
+                        );
+                    } else {
+                        ui.colored_label(
+                            eframe::egui::Color32::from_rgb(200, 50, 50),
+                            "⚠️ Your cargo is not insured",
+                        );
+                    }
+                    ui.separator();
+                    ui.label(format!(
+                        "Premium: {}% of cargo value per flight (next flight: ${})",
+                        insurance.premium_percent, insurance.next_flight_premium
+                    ));
+                });
+
+                let (label, insured) = if insurance.insured {
+                    ("Cancel cover", false)
+                } else {
+                    ("Take out cover", true)
+                };
+                if ui.button(label).clicked() {
+                    scene_state.insurance_error = api_client
+                        .set_insurance_sync(session.room_id, session.player_id, insured)
+                        .err()
+                        .map(|e| e.to_string());
+                }
+            });
+
+        ui.add_space(8.0);
+        ui.strong("Unclaimed losses:");
+        if insurance.unclaimed_losses.is_empty() {
+            ui.label("Nothing lost in transit. Safe flying!");
+        }
+        for loss in &insurance.unclaimed_losses {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Turn {} at {}: {} (${})",
+                    loss.turn, loss.airport_id, loss.description, loss.value
+                ));
+                if !loss.insured {
+                    ui.weak("Uninsured");
+                } else if ui.button("📝 File claim").clicked() {
+                    scene_state.insurance_error = api_client
+                        .file_claim_sync(session.room_id, session.player_id, loss.id)
+                        .err()
+                        .map(|e| e.to_string());
+                }
+            });
+        }
+
+        ui.add_space(8.0);
+        ui.strong("Claims:");
+        if insurance.claims.is_empty() {
+            ui.label("No claims filed.");
+            return;
+        }
+
+        eframe::egui::ScrollArea::vertical()
+            .max_height(250.0)
+            .show(ui, |ui| {
+                eframe::egui::Grid::new("insurance_claims")
+                    .striped(true)
+                    .num_columns(5)
+                    .show(ui, |ui| {
+                        for header in ["Loss", "Claimed", "Status", "Payout", "Notes"] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+
+                        for claim in &insurance.claims {
+                            ui.label(format!(
+                                "{}: {}",
+                                claim.loss.cause.label(),
+                                claim.loss.description
+                            ));
+                            ui.label(format!("${}", claim.loss.value));
+
+                            let color = match claim.status {
+                                ClaimStatus::Pending => {
+                                    eframe::egui::Color32::from_rgb(200, 150, 0)
+                                },
+                                ClaimStatus::Paid => eframe::egui::Color32::from_rgb(0, 150, 0),
+                                ClaimStatus::PartiallyPaid => {
+                                    eframe::egui::Color32::from_rgb(100, 150, 0)
+                                },
+                                ClaimStatus::Denied => eframe::egui::Color32::from_rgb(200, 50, 50),
+                            };
+                            ui.colored_label(color, claim.status.label());
+
+                            if claim.status == ClaimStatus::Pending {
+                                ui.label("-");
+                            } else {
+                                ui.label(format!("${}", claim.payout));
+                            }
+                            ui.label(&claim.note);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
@@ -0,0 +1,280 @@
+use crate::ui::{
+    game_api_client::GameApiClient,
+    scenes::{
+        Location, SceneState,
+        airport::{
+            components::cargo_inventory_grid,
+            locations::{LocationContext, LocationView},
+        },
+        room_lobby::GameSession,
+    },
+};
+
+pub struct MainDesk;
+
+impl MainDesk {
+    /// Vendors' one-off items at this airport, if any have set up here
+    fn vendor_stalls(
+        scene_state: &mut SceneState,
+        api_client: &GameApiClient,
+        session: &GameSession,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        if scene_state.stalls.is_none() {
+            match api_client.get_stalls_sync(session.room_id, session.player_id) {
+                Ok(stalls) => scene_state.stalls = Some(stalls),
+                Err(err) => {
+                    ui.colored_label(
+                        eframe::egui::Color32::RED,
+                        format!("Error loading the vendor stalls: {}", err),
+                    );
+                    return;
+                },
+            }
+        }
+        let Some(stalls) = scene_state.stalls.clone() else {
+            return;
+        };
+        if !stalls.vendors_enabled {
+            return;
+        }
+
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_rgb(255, 250, 240))
+            .inner_margin(eframe::egui::Margin::same(8.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.strong("🛒 Vendor Stalls");
+                    if ui.small_button("🔄").on_hover_text("Refresh").clicked() {
+                        scene_state.stalls = None;
+                    }
+                });
+                if let Some(message) = &scene_state.stall_message {
+                    ui.label(message);
+                }
+                if stalls.stalls.is_empty() {
+                    ui.weak("No vendors have set up here right now. They move on every few turns.");
+                    return;
+                }
+
+                eframe::egui::Grid::new("vendor_stalls")
+                    .striped(true)
+                    .num_columns(4)
+                    .show(ui, |ui| {
+                        for stall in &stalls.stalls {
+                            ui.label(&stall.vendor);
+                            ui.label(&stall.description);
+                            ui.weak(format!("leaving in {} turns", stall.closes_in_turns));
+                            let button = ui.add_enabled(
+                                stalls.money >= stall.price,
+                                eframe::egui::Button::new(format!("Buy ${}", stall.price)),
+                            );
+                            if button.clicked() {
+                                match api_client.buy_from_stall_sync(
+                                    session.room_id,
+                                    session.player_id,
+                                    stall.id,
+                                ) {
+                                    Ok(purchase) => {
+                                        scene_state.stall_message = Some(purchase.message);
+                                        scene_state.stalls = None;
+                                    },
+                                    Err(err) => scene_state.stall_message = Some(err.to_string()),
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        ui.separator();
+    }
+}
+
+impl LocationView for MainDesk {
+    fn location(&self) -> Location {
+        Location::MainDesk
+    }
+
+    fn label(&self) -> &'static str {
+        "🏠 Main Desk"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            game_state,
+            scene_state,
+            api_client,
+            session,
+        } = context;
+
+        ui.heading("🏠 Main Desk - Welcome, Pilot!");
+
+        // Welcome message with airport info
+        let current_airport = game_state.airports.get(&game_state.player.current_airport);
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_rgb(245, 250, 255))
+            .inner_margin(eframe::egui::Margin::same(8.0))
+            .show(ui, |ui| {
+                if let Some(airport) = current_airport {
+                    ui.label(format!("\"Welcome to {} Fixed Base Operation! Good to see you, pilot. What can we help you with today?\"", airport.name));
+                } else {
+                    ui.label("\"Good to see you at our Fixed Base Operation. What can we help you with today?\"");
+                }
+            });
+
+        ui.separator();
+
+        // Game status overview with color coding
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_gray(248))
+            .stroke(eframe::egui::Stroke::new(
+                1.0,
+                eframe::egui::Color32::from_gray(200),
+            ))
+            .inner_margin(eframe::egui::Margin::same(12.0))
+            .show(ui, |ui| {
+                ui.strong("📊 Flight Status Overview");
+                ui.separator();
+
+                eframe::egui::Grid::new("status_overview")
+                    .num_columns(2)
+                    .spacing([30.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label("Pilot Status:");
+                        let (status_text, status_color) = if game_state.is_game_won() {
+                            (
+                                "🏆 WINNER! You've made $100,000!",
+                                eframe::egui::Color32::from_rgb(255, 215, 0),
+                            )
+                        } else if game_state.can_player_continue() {
+                            (
+                                "✅ Active pilot - ready for business",
+                                eframe::egui::Color32::from_rgb(50, 150, 50),
+                            )
+                        } else {
+                            (
+                                "⚠️ Low on fuel and funds - need assistance",
+                                eframe::egui::Color32::from_rgb(220, 50, 50),
+                            )
+                        };
+                        ui.colored_label(status_color, status_text);
+                        ui.end_row();
+
+                        ui.label("Current Funds:");
+                        let money_color = if game_state.player.money > 50000 {
+                            eframe::egui::Color32::from_rgb(50, 150, 50)
+                        } else if game_state.player.money > 10000 {
+                            eframe::egui::Color32::from_rgb(255, 140, 0)
+                        } else if game_state.player.money > 1000 {
+                            eframe::egui::Color32::from_rgb(200, 100, 50)
+                        } else {
+                            eframe::egui::Color32::from_rgb(220, 50, 50)
+                        };
+                        ui.colored_label(money_color, format!("${}", game_state.player.money));
+                        ui.end_row();
+
+                        ui.label("Cargo Manifest:");
+                        let cargo_count = game_state.player.cargo_inventory.get_all_cargo().len();
+                        let current_weight = game_state
+                            .player
+                            .current_cargo_weight(&game_state.cargo_types);
+                        ui.label(format!(
+                            "{} types | {}kg/{} max",
+                            cargo_count, current_weight, game_state.player.max_cargo_weight
+                        ));
+                        ui.end_row();
+
+                        ui.label("Fuel Status:");
+                        let fuel_percent = (game_state.player.fuel as f32
+                            / game_state.player.max_fuel as f32)
+                            * 100.0;
+                        let fuel_color = if fuel_percent > 75.0 {
+                            eframe::egui::Color32::from_rgb(50, 150, 50)
+                        } else if fuel_percent > 25.0 {
+                            eframe::egui::Color32::from_rgb(255, 140, 0)
+                        } else {
+                            eframe::egui::Color32::from_rgb(220, 50, 50)
+                        };
+                        ui.colored_label(
+                            fuel_color,
+                            format!(
+                                "{:.0}% capacity ({}/{})",
+                                fuel_percent, game_state.player.fuel, game_state.player.max_fuel
+                            ),
+                        );
+                        ui.end_row();
+
+                        ui.label("Game Progress:");
+                        ui.label(format!(
+                            "Turn {} ({}) | Goal: $100,000",
+                            game_state.turn_number,
+                            game_state.date()
+                        ));
+                        ui.end_row();
+                    });
+            });
+
+        ui.separator();
+
+        // Current cargo inventory (if any)
+        let inventory = game_state.player.cargo_inventory.get_all_cargo();
+        if !inventory.is_empty() {
+            ui.collapsing("📦 Current Cargo Inventory", |ui| {
+                cargo_inventory_grid(game_state, "inventory_display", ui);
+            });
+            ui.separator();
+        }
+
+        Self::vendor_stalls(scene_state, api_client, session, ui);
+
+        // Quick action buttons
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_rgb(250, 255, 250))
+            .inner_margin(eframe::egui::Margin::same(8.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("🚀 Quick Actions:");
+                    ui.separator();
+                    if ui.small_button("📊 Check Prices").clicked() {
+                        // This would switch to Market Board in a real implementation
+                    }
+                    if ui.small_button("⛽ Fuel Status").clicked() {
+                        // This would switch to Fuel Pump in a real implementation
+                    }
+                    if ui.small_button("✈️ Plan Flight").clicked() {
+                        // This would switch to Flight Planning in a real implementation
+                    }
+                });
+            });
+
+        ui.separator();
+
+        // Sierra-style tips and information
+        ui.collapsing("💡 Pilot's Handbook", |ui| {
+            ui.label("📈 Trading Tips:");
+            ui.label("  • Buy low at production centers, sell high at consumption markets");
+            ui.label("  • Check market trends - volatile goods offer higher profits but more risk");
+            ui.label("  • Consider cargo weight vs. fuel efficiency for longer routes");
+            ui.separator();
+
+            ui.label("✈️ Flight Operations:");
+            ui.label("  • Monitor fuel levels - emergency landings are expensive!");
+            ui.label("  • Shorter routes are more fuel-efficient for frequent trading");
+            ui.label("  • Each airport specializes in different cargo types");
+            ui.separator();
+
+            ui.label("🎯 Strategy:");
+            ui.label("  • Start with short, profitable routes to build capital");
+            ui.label("  • Expand to longer routes as you gain experience and funds");
+            ui.label("  • Goal: Reach $100,000 to become a successful aviation trader!");
+
+            if game_state.cheat_mode {
+                ui.separator();
+                ui.colored_label(
+                    eframe::egui::Color32::from_rgb(255, 140, 0),
+                    "⚡ Cheat mode is active - unlimited fuel available!",
+                );
+            }
+        });
+    }
+}
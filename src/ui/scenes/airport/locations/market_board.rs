@@ -0,0 +1,307 @@
+use crate::{
+    api::models::KnownMarketInfo,
+    ui::scenes::{
+        Location,
+        airport::{
+            components::{
+                cargo_filter_bar, cargo_icon, current_fuel_economy, fuel_level_color,
+                fuel_outage_at, market_cargo, sparkline,
+            },
+            locations::{LocationContext, LocationView},
+        },
+    },
+};
+
+pub struct MarketBoard;
+
+impl LocationView for MarketBoard {
+    fn location(&self) -> Location {
+        Location::MarketBoard
+    }
+
+    fn label(&self) -> &'static str {
+        "📊 Market Board"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            game_state,
+            scene_state,
+            api_client,
+            session,
+        } = context;
+
+        ui.heading("📊 Market Board - Current Prices");
+
+        // Fetched once a turn; the board shows it as a sparkline per cargo
+        if scene_state.price_history_turn != Some(game_state.turn_number) {
+            scene_state.price_history = api_client
+                .get_price_history_sync(
+                    session.room_id,
+                    &game_state.player.current_airport,
+                    session.player_id,
+                )
+                .ok();
+            scene_state.price_history_turn = Some(game_state.turn_number);
+        }
+        let history = scene_state.price_history.clone();
+
+        if let Some(market) = game_state.get_current_market() {
+            // Market board header with timestamp
+            eframe::egui::Frame::none()
+                .fill(eframe::egui::Color32::from_gray(250))
+                .inner_margin(eframe::egui::Margin::same(8.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("\"Here are today's market prices, updated hourly.\"");
+                        ui.with_layout(
+                            eframe::egui::Layout::right_to_left(eframe::egui::Align::Center),
+                            |ui| {
+                                ui.label(format!("🕒 {}", game_state.date()));
+                            },
+                        );
+                    });
+                });
+
+            ui.separator();
+
+            cargo_filter_bar(scene_state, "market_board", ui);
+            let listed = market_cargo(game_state, scene_state);
+
+            // Enhanced market grid with more information
+            eframe::egui::Grid::new("market_prices_grid")
+                .num_columns(7)
+                .spacing([25.0, 8.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    // Header row with better styling
+                    ui.strong("Cargo Type");
+                    ui.strong("Current Price");
+                    ui.strong("Base Price");
+                    ui.strong("Change");
+                    ui.strong("Weight");
+                    ui.strong("Market Trend");
+                    ui.strong("History");
+                    ui.end_row();
+
+                    for cargo in &listed {
+                        let (cargo_type, price) = (cargo.cargo_type, &cargo.price);
+                        // Cargo name with icon
+                        let cargo_icon = cargo_icon(&cargo_type.name);
+                        ui.label(format!("{} {}", cargo_icon, cargo_type.name));
+
+                        // Current price with color coding
+                        let price_color =
+                            if *price > cargo_type.base_price + (cargo_type.base_price / 4) {
+                                eframe::egui::Color32::from_rgb(220, 50, 50) // Red for high prices
+                            } else if *price < cargo_type.base_price - (cargo_type.base_price / 4) {
+                                eframe::egui::Color32::from_rgb(50, 150, 50) // Green for low prices
+                            } else {
+                                eframe::egui::Color32::from_gray(120) // Gray for normal
+                            };
+                        ui.colored_label(price_color, format!("${}", price));
+
+                        // Base price for reference
+                        ui.label(format!("${}", cargo_type.base_price));
+
+                        // Price change percentage
+                        let change_percent = (((*price as f32) - (cargo_type.base_price as f32))
+                            / (cargo_type.base_price as f32))
+                            * 100.0;
+                        let change_text = if change_percent > 0.0 {
+                            format!("+{:.1}%", change_percent)
+                        } else {
+                            format!("{:.1}%", change_percent)
+                        };
+                        let change_color = if change_percent > 0.0 {
+                            eframe::egui::Color32::from_rgb(220, 50, 50)
+                        } else if change_percent < 0.0 {
+                            eframe::egui::Color32::from_rgb(50, 150, 50)
+                        } else {
+                            eframe::egui::Color32::from_gray(120)
+                        };
+                        ui.colored_label(change_color, change_text);
+
+                        // Weight per unit
+                        ui.label(format!("{}kg", cargo_type.weight_per_unit));
+
+                        // Enhanced trend indicator
+                        let (trend_text, trend_color) = if cargo_type.volatility > 0.4 {
+                            (
+                                "📈 Very Volatile",
+                                eframe::egui::Color32::from_rgb(255, 140, 0),
+                            )
+                        } else if cargo_type.volatility > 0.3 {
+                            ("📊 Volatile", eframe::egui::Color32::from_rgb(255, 165, 0))
+                        } else if *price > cargo_type.base_price {
+                            (
+                                "📈 Above Average",
+                                eframe::egui::Color32::from_rgb(220, 50, 50),
+                            )
+                        } else if *price < cargo_type.base_price {
+                            (
+                                "📉 Below Average",
+                                eframe::egui::Color32::from_rgb(50, 150, 50),
+                            )
+                        } else {
+                            ("➖ Stable", eframe::egui::Color32::from_gray(120))
+                        };
+                        ui.colored_label(trend_color, trend_text);
+
+                        match &history {
+                            Some(history) => {
+                                sparkline(&history.cargo_prices(&cargo_type.id), ui);
+                            },
+                            None => {
+                                ui.weak("—");
+                            },
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+
+            // Enhanced fuel information
+            eframe::egui::Frame::none()
+                .fill(eframe::egui::Color32::from_rgb(255, 255, 200))
+                .inner_margin(eframe::egui::Margin::same(8.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("⛽ Fuel Information:");
+                        ui.separator();
+                        if let Some(outage) = fuel_outage_at(
+                            &scene_state.active_events,
+                            &game_state.player.current_airport,
+                        ) {
+                            ui.colored_label(
+                                eframe::egui::Color32::from_rgb(220, 50, 50),
+                                format!(
+                                    "🚫 OUT OF STOCK for {} more turn(s)",
+                                    outage.turns_remaining
+                                ),
+                            );
+                            return;
+                        }
+                        ui.label(format!("Current: ${}/unit", market.fuel_price));
+                        if let Some(history) = &history {
+                            sparkline(&history.fuel_prices(), ui);
+                        }
+                        if let Some(economy) = current_fuel_economy(game_state) {
+                            ui.separator();
+                            ui.label(format!("💡 Range here: {}", economy.range_label()));
+                            ui.separator();
+                            let level = economy.level(market.fuel_price);
+                            ui.colored_label(fuel_level_color(level), level.label());
+                        }
+                    });
+                });
+
+            ui.separator();
+
+            // Market analysis summary
+            ui.collapsing("📈 Market Analysis", |ui| {
+                let mut high_prices = Vec::new();
+                let mut low_prices = Vec::new();
+
+                for (cargo_id, price) in &market.cargo_prices {
+                    if let Some(cargo_type) = game_state.cargo_types.get(cargo_id) {
+                        let change_percent = (((*price as f32) - (cargo_type.base_price as f32))
+                            / (cargo_type.base_price as f32))
+                            * 100.0;
+                        if change_percent > 20.0 {
+                            high_prices.push((cargo_type.name.clone(), change_percent));
+                        } else if change_percent < -20.0 {
+                            low_prices.push((cargo_type.name.clone(), change_percent));
+                        }
+                    }
+                }
+
+                if !high_prices.is_empty() {
+                    ui.label("🔥 High Prices (Good for Selling):");
+                    for (name, change) in &high_prices {
+                        ui.label(format!("  • {}: +{:.1}%", name, change));
+                    }
+                }
+
+                if !low_prices.is_empty() {
+                    ui.label("💰 Low Prices (Good for Buying):");
+                    for (name, change) in &low_prices {
+                        ui.label(format!("  • {}: {:.1}%", name, change));
+                    }
+                }
+
+                if high_prices.is_empty() && low_prices.is_empty() {
+                    ui.label("📊 All prices are within normal ranges today.");
+                }
+            });
+        } else {
+            ui.label("❌ Market data not available at this location.");
+        }
+
+        Self::render_known_markets(
+            &scene_state.known_markets,
+            &game_state.player.current_airport,
+            ui,
+        );
+    }
+}
+
+impl MarketBoard {
+    /// Prices elsewhere, as of when we last saw them. In fog-of-war rooms
+    /// old sightings are greyed out and flagged so nobody trades on them
+    /// by accident.
+    fn render_known_markets(
+        known_markets: &[KnownMarketInfo],
+        current_airport: &str,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        let elsewhere: Vec<&KnownMarketInfo> = known_markets
+            .iter()
+            .filter(|market| market.airport_id != current_airport)
+            .collect();
+
+        ui.separator();
+        ui.collapsing("🗺️ Prices at Other Airports", |ui| {
+            if elsewhere.is_empty() {
+                ui.label("You haven't scouted any other airports yet.");
+                return;
+            }
+
+            eframe::egui::Grid::new("known_markets_grid")
+                .num_columns(3)
+                .spacing([25.0, 6.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Airport");
+                    ui.strong("Fuel");
+                    ui.strong("Seen");
+                    ui.end_row();
+
+                    for market in elsewhere {
+                        let color = if market.stale {
+                            eframe::egui::Color32::GRAY
+                        } else {
+                            ui.visuals().text_color()
+                        };
+                        ui.colored_label(color, &market.airport_name);
+                        ui.colored_label(color, format!("${}", market.fuel_price));
+                        let seen = match market.turns_ago {
+                            0 => "this turn".to_string(),
+                            1 => "1 turn ago".to_string(),
+                            turns => format!("{} turns ago", turns),
+                        };
+                        if market.stale {
+                            ui.colored_label(
+                                eframe::egui::Color32::from_rgb(220, 140, 0),
+                                format!("⚠️ {} (stale)", seen),
+                            );
+                        } else {
+                            ui.label(seen);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+}
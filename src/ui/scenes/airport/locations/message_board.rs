@@ -0,0 +1,441 @@
+use crate::{
+    models::{BoardLimits, MessageTopic},
+    systems::courier::CourierStatus,
+    ui::scenes::{
+        Location,
+        airport::locations::{LocationContext, LocationView},
+    },
+};
+
+pub struct MessageBoard;
+
+impl LocationView for MessageBoard {
+    fn location(&self) -> Location {
+        Location::MessageBoard
+    }
+
+    fn label(&self) -> &'static str {
+        "💬 Message Board"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            game_state,
+            scene_state,
+            api_client,
+            session,
+        } = context;
+
+        ui.heading("💬 Message Board - Pilot Communications");
+
+        let current_airport = &game_state.player.current_airport;
+        let mut limits = BoardLimits::default();
+        let board = api_client.get_messages_sync(
+            session.room_id,
+            session.player_id,
+            scene_state.board_topic,
+        );
+
+        // Topic tabs, with how many messages each one holds
+        ui.horizontal(|ui| {
+            let counts = board.as_ref().map(|response| &response.topic_counts).ok();
+            let all = counts.map(|counts| counts.values().sum::<usize>());
+            let tab = |topic: Option<MessageTopic>, count: Option<usize>| {
+                let name = topic.map_or("All", MessageTopic::label);
+                match count {
+                    Some(count) => format!("{} ({})", name, count),
+                    None => name.to_string(),
+                }
+            };
+            ui.selectable_value(&mut scene_state.board_topic, None, tab(None, all));
+            for topic in MessageTopic::ALL {
+                let count = counts.and_then(|counts| counts.get(&topic).copied());
+                ui.selectable_value(
+                    &mut scene_state.board_topic,
+                    Some(topic),
+                    tab(Some(topic), count),
+                );
+            }
+        });
+        ui.add_space(4.0);
+
+        // Display recent messages from API
+        eframe::egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                match board {
+                    Ok(response) => {
+                        limits = response.limits.clone();
+                        if response.messages.is_empty() {
+                            eframe::egui::Frame::none()
+                                .fill(eframe::egui::Color32::from_rgb(250, 250, 250))
+                                .inner_margin(eframe::egui::Margin::same(16.0))
+                                .show(ui, |ui| {
+                                    ui.vertical_centered(|ui| {
+                                        ui.label(match scene_state.board_topic {
+                                            Some(topic) => format!(
+                                                "📭 Nothing under {} at this airport yet.",
+                                                topic.label()
+                                            ),
+                                            None => {
+                                                "📭 No messages at this airport yet.".to_string()
+                                            },
+                                        });
+                                        ui.add_space(8.0);
+                                        ui.label(
+                                            "Be the first to leave a message for other pilots!",
+                                        );
+                                    });
+                                });
+                        } else {
+                            ui.label(format!("📋 Recent messages at {}:", current_airport));
+                            ui.add_space(4.0);
+
+                            for message in &response.messages {
+                                // Messages that @mention the player stand out
+                                let fill = if message.mentions_you {
+                                    eframe::egui::Color32::from_rgb(255, 244, 214)
+                                } else {
+                                    eframe::egui::Color32::from_rgb(245, 245, 250)
+                                };
+                                eframe::egui::Frame::none()
+                                    .fill(fill)
+                                    .inner_margin(eframe::egui::Margin::same(8.0))
+                                    .outer_margin(eframe::egui::Margin::symmetric(0.0, 4.0))
+                                    .rounding(eframe::egui::Rounding::same(6.0))
+                                    .show(ui, |ui| {
+                                        ui.horizontal_top(|ui| {
+                                            ui.vertical(|ui| {
+                                                // Locals are greyed and tagged so
+                                                // nobody mistakes them for pilots
+                                                let (author, color) = if message.is_npc {
+                                                    (
+                                                        format!("🏷 {} · NPC", message.author_name),
+                                                        eframe::egui::Color32::from_gray(130),
+                                                    )
+                                                } else {
+                                                    (
+                                                        message.author_name.clone(),
+                                                        eframe::egui::Color32::from_rgb(
+                                                            70, 130, 180,
+                                                        ),
+                                                    )
+                                                };
+                                                ui.label(
+                                                    eframe::egui::RichText::new(author)
+                                                        .strong()
+                                                        .color(color),
+                                                );
+
+                                                // Format the timestamp
+                                                let local_time = message
+                                                    .created_at
+                                                    .with_timezone(&chrono::Local);
+                                                ui.label(
+                                                    eframe::egui::RichText::new(
+                                                        local_time.format("%H:%M").to_string(),
+                                                    )
+                                                    .small()
+                                                    .color(eframe::egui::Color32::GRAY),
+                                                );
+                                                if let Some(origin) = &message.couriered_from {
+                                                    ui.label(
+                                                        eframe::egui::RichText::new(format!(
+                                                            "📮 by courier from {}",
+                                                            origin
+                                                        ))
+                                                        .small()
+                                                        .color(eframe::egui::Color32::from_rgb(
+                                                            120, 90, 160,
+                                                        )),
+                                                    );
+                                                }
+                                                if scene_state.board_topic.is_none()
+                                                    && message.topic != MessageTopic::General
+                                                {
+                                                    ui.label(
+                                                        eframe::egui::RichText::new(format!(
+                                                            "🏷 {}",
+                                                            message.topic.label()
+                                                        ))
+                                                        .small()
+                                                        .color(eframe::egui::Color32::from_rgb(
+                                                            60, 120, 90,
+                                                        )),
+                                                    );
+                                                }
+                                                if message.mentions_you {
+                                                    ui.label(
+                                                        eframe::egui::RichText::new(
+                                                            "📣 mentions you",
+                                                        )
+                                                        .small()
+                                                        .color(eframe::egui::Color32::from_rgb(
+                                                            200, 110, 20,
+                                                        )),
+                                                    );
+                                                }
+                                            });
+
+                                            ui.separator();
+                                            ui.label(&message.content);
+
+                                            // Pilots can take down their own posts
+                                            if message.author_id == session.player_id
+                                                && ui
+                                                    .small_button("🗑")
+                                                    .on_hover_text("Delete this message")
+                                                    .clicked()
+                                            {
+                                                scene_state.message_error = api_client
+                                                    .delete_message_sync(
+                                                        session.room_id,
+                                                        session.player_id,
+                                                        message.id,
+                                                    )
+                                                    .err()
+                                                    .map(|e| e.to_string());
+                                            }
+                                        });
+                                    });
+                            }
+                        }
+                    },
+                    Err(err) => {
+                        ui.colored_label(
+                            eframe::egui::Color32::RED,
+                            format!("Error loading messages: {}", err),
+                        );
+                    },
+                }
+            });
+
+        if !scene_state.show_message_compose
+            && let Some(error) = &scene_state.message_error
+        {
+            ui.colored_label(
+                eframe::egui::Color32::from_rgb(220, 50, 50),
+                format!("⚠️ {}", error),
+            );
+        }
+
+        ui.add_space(8.0);
+        ui.separator();
+
+        // Message composition area
+        ui.heading("✍️ Post a Message");
+
+        if !scene_state.show_message_compose {
+            if ui.button("📝 Write a message").clicked() {
+                scene_state.show_message_compose = true;
+                scene_state.message_input.clear();
+                scene_state.post_topic = scene_state.board_topic.unwrap_or_default();
+            }
+        } else {
+            // Text input for message
+            ui.label(format!(
+                "Message content (max {} characters):",
+                limits.max_message_length
+            ));
+            let text_edit = eframe::egui::TextEdit::multiline(&mut scene_state.message_input)
+                .desired_width(f32::INFINITY)
+                .desired_rows(3);
+            ui.add(text_edit);
+
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Characters: {}/{}",
+                    scene_state.message_input.chars().count(),
+                    limits.max_message_length
+                ));
+            });
+
+            if scene_state.courier_destination.is_none() {
+                ui.horizontal(|ui| {
+                    ui.label("Topic:");
+                    eframe::egui::ComboBox::from_id_salt("message_topic")
+                        .selected_text(scene_state.post_topic.label())
+                        .show_ui(ui, |ui| {
+                            for topic in MessageTopic::ALL {
+                                ui.selectable_value(
+                                    &mut scene_state.post_topic,
+                                    topic,
+                                    topic.label(),
+                                );
+                            }
+                        });
+                });
+            }
+
+            // Post here, or pay the courier to post it somewhere else
+            let courier = api_client
+                .get_courier_sync(session.room_id, session.player_id)
+                .ok();
+            if let Some(courier) = &courier {
+                let describe = |destination: &Option<String>| match destination {
+                    None => "This board".to_string(),
+                    Some(id) => courier
+                        .rates
+                        .iter()
+                        .find(|rate| &rate.airport_id == id)
+                        .map(|rate| {
+                            format!(
+                                "📮 {} ({}) - ${}, {} turn{}",
+                                rate.airport_name,
+                                rate.airport_id,
+                                rate.fee,
+                                rate.turns,
+                                if rate.turns == 1 { "" } else { "s" }
+                            )
+                        })
+                        .unwrap_or_else(|| id.clone()),
+                };
+                ui.horizontal(|ui| {
+                    ui.label("Deliver to:");
+                    eframe::egui::ComboBox::from_id_salt("courier_destination")
+                        .selected_text(describe(&scene_state.courier_destination))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut scene_state.courier_destination,
+                                None,
+                                describe(&None),
+                            );
+                            for rate in &courier.rates {
+                                let destination = Some(rate.airport_id.clone());
+                                let text = describe(&destination);
+                                ui.selectable_value(
+                                    &mut scene_state.courier_destination,
+                                    destination,
+                                    text,
+                                );
+                            }
+                        });
+                });
+            }
+
+            ui.add_space(4.0);
+
+            // Post/Cancel buttons
+            ui.horizontal(|ui| {
+                let can_post = !scene_state.message_input.trim().is_empty()
+                    && scene_state.message_input.chars().count() <= limits.max_message_length;
+
+                ui.add_enabled_ui(can_post, |ui| {
+                    let label = if scene_state.courier_destination.is_some() {
+                        "📮 Send by Courier"
+                    } else {
+                        "📤 Post Message"
+                    };
+                    if !ui.button(label).clicked() {
+                        return;
+                    }
+                    if let Some(destination) = scene_state.courier_destination.clone() {
+                        match api_client.send_courier_sync(
+                            session.room_id,
+                            session.player_id,
+                            destination,
+                            scene_state.message_input.clone(),
+                        ) {
+                            Ok(_) => {
+                                scene_state.message_input.clear();
+                                scene_state.show_message_compose = false;
+                                scene_state.message_error = None;
+                                scene_state.courier_destination = None;
+                            },
+                            // Keep the draft; the fee or the mail limit may have been the problem
+                            Err(e) => scene_state.message_error = Some(e.to_string()),
+                        }
+                    } else {
+                        // Post message to API
+                        match api_client.post_message_sync(
+                            session.room_id,
+                            session.player_id,
+                            scene_state.message_input.clone(),
+                            scene_state.post_topic,
+                        ) {
+                            Ok(response) => {
+                                if response.success {
+                                    scene_state.message_input.clear();
+                                    scene_state.show_message_compose = false;
+                                    scene_state.message_error = None;
+                                } else {
+                                    // Keep the draft so the pilot can fix it or wait out the cooldown
+                                    scene_state.message_error = Some(response.message);
+                                }
+                            },
+                            Err(e) => {
+                                eprintln!("Failed to post message: {}", e);
+                                scene_state.message_input.clear();
+                                scene_state.show_message_compose = false;
+                            },
+                        }
+                    }
+                });
+
+                if ui.button("❌ Cancel").clicked() {
+                    scene_state.show_message_compose = false;
+                    scene_state.message_input.clear();
+                    scene_state.message_error = None;
+                }
+
+                if !can_post && !scene_state.message_input.trim().is_empty() {
+                    ui.colored_label(
+                        eframe::egui::Color32::from_rgb(220, 50, 50),
+                        "⚠️ Message too long",
+                    );
+                } else if let Some(error) = &scene_state.message_error {
+                    ui.colored_label(
+                        eframe::egui::Color32::from_rgb(220, 50, 50),
+                        format!("⚠️ {}", error),
+                    );
+                }
+            });
+        }
+
+        // Letters this pilot has sent, and where they've got to
+        if let Ok(courier) = api_client.get_courier_sync(session.room_id, session.player_id)
+            && !courier.letters.is_empty()
+        {
+            ui.add_space(8.0);
+            ui.collapsing("📮 Courier letters", |ui| {
+                for info in &courier.letters {
+                    let status = if info.letter.status == CourierStatus::Delivered {
+                        "✅ delivered".to_string()
+                    } else {
+                        format!(
+                            "✈ in the mail, {} turn{} to go",
+                            info.turns_remaining,
+                            if info.turns_remaining == 1 { "" } else { "s" }
+                        )
+                    };
+                    ui.label(format!(
+                        "{} → {} (${}): {}",
+                        info.letter.from_airport, info.letter.to_airport, info.letter.fee, status
+                    ));
+                    ui.label(
+                        eframe::egui::RichText::new(&info.letter.content)
+                            .small()
+                            .color(eframe::egui::Color32::GRAY),
+                    );
+                }
+            });
+        }
+
+        ui.add_space(8.0);
+
+        // Instructions
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_rgb(255, 252, 240))
+            .inner_margin(eframe::egui::Margin::same(8.0))
+            .show(ui, |ui| {
+                ui.label(
+                    "💡 Messages are location-specific - only pilots at this airport can see them.",
+                );
+                ui.label(
+                    "📝 Share trade tips, post warnings, or just say hello to fellow aviators under General!",
+                );
+                ui.label("📣 Write @name to notify a pilot wherever they are.");
+                ui.label("📮 Pay the courier to have a message posted at another airport.");
+            });
+    }
+}
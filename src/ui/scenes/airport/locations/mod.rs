@@ -0,0 +1,99 @@
+mod bank;
+mod flight_planning;
+mod fuel_pump;
+mod hangar;
+mod insurance_office;
+mod main_desk;
+mod market_board;
+mod message_board;
+mod route_map;
+mod statistics;
+mod trading_desk;
+
+use crate::{
+    systems::game::GameState,
+    ui::{
+        game_api_client::GameApiClient,
+        scenes::{Location, SceneState, room_lobby::GameSession},
+    },
+};
+
+pub use bank::Bank;
+pub use flight_planning::FlightPlanning;
+pub use fuel_pump::FuelPump;
+pub use hangar::Hangar;
+pub use insurance_office::InsuranceOffice;
+pub use main_desk::MainDesk;
+pub use market_board::MarketBoard;
+pub use message_board::MessageBoard;
+pub use route_map::RouteMap;
+pub use statistics::Statistics;
+pub use trading_desk::TradingDesk;
+
+/// Everything a location needs to draw itself and act on the player's behalf.
+pub struct LocationContext<'a> {
+    pub game_state: &'a mut GameState,
+    pub scene_state: &'a mut SceneState,
+    pub api_client: &'a GameApiClient,
+    pub session: &'a GameSession,
+}
+
+/// One FBO location. Implement this and register it with the
+/// `LocationRegistry` to add a new place the pilot can visit.
+pub trait LocationView {
+    fn location(&self) -> Location;
+
+    /// Button label, icon included
+    fn label(&self) -> &'static str;
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui);
+}
+
+/// The locations shown at every airport, in button order.
+pub struct LocationRegistry {
+    views: Vec<Box<dyn LocationView>>,
+}
+
+impl Default for LocationRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(MainDesk));
+        registry.register(Box::new(MarketBoard));
+        registry.register(Box::new(TradingDesk));
+        registry.register(Box::new(FlightPlanning));
+        registry.register(Box::new(FuelPump));
+        registry.register(Box::new(MessageBoard));
+        registry.register(Box::new(Statistics));
+        registry.register(Box::new(InsuranceOffice));
+        registry.register(Box::new(Hangar));
+        registry.register(Box::new(Bank));
+        registry.register(Box::new(RouteMap));
+        registry
+    }
+}
+
+impl LocationRegistry {
+    pub fn new() -> Self {
+        Self { views: Vec::new() }
+    }
+
+    /// Add a location, replacing any view already registered for it
+    pub fn register(&mut self, view: Box<dyn LocationView>) {
+        let location = view.location();
+        match self.views.iter().position(|v| v.location() == location) {
+            Some(index) => self.views[index] = view,
+            None => self.views.push(view),
+        }
+    }
+
+    pub fn get(&self, location: &Location) -> Option<&dyn LocationView> {
+        self.views
+            .iter()
+            .find(|view| &view.location() == location)
+            .map(|view| view.as_ref())
+    }
+
+    pub fn views(&self) -> impl Iterator<Item = &dyn LocationView> {
+        self.views.iter().map(|view| view.as_ref())
+    }
+}
@@ -0,0 +1,136 @@
+use eframe::egui::{Color32, RichText};
+
+use crate::ui::scenes::{
+    Location,
+    airport::locations::{LocationContext, LocationView},
+};
+
+/// Routes listed under the heatmap
+const TOP_ROUTES: usize = 10;
+
+pub struct RouteMap;
+
+impl LocationView for RouteMap {
+    fn location(&self) -> Location {
+        Location::RouteMap
+    }
+
+    fn label(&self) -> &'static str {
+        "🗺️ Route Map"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            scene_state,
+            api_client,
+            session,
+            ..
+        } = context;
+
+        ui.horizontal(|ui| {
+            ui.heading("🗺️ Route Map - Profit per Unit of Fuel");
+            if ui.button("🔄 Refresh").clicked() {
+                scene_state.route_profits = None;
+            }
+        });
+
+        if scene_state.route_profits.is_none() {
+            match api_client.get_route_profits_sync(session.room_id, session.player_id) {
+                Ok(route_profits) => scene_state.route_profits = Some(route_profits),
+                Err(err) => {
+                    ui.colored_label(Color32::RED, format!("Error loading routes: {}", err));
+                    return;
+                },
+            }
+        }
+        let Some(route_profits) = &scene_state.route_profits else {
+            return;
+        };
+
+        if route_profits.routes.is_empty() {
+            ui.label("You don't know prices at two airports yet. Fly somewhere to compare.");
+            return;
+        }
+        ui.label("Best profit per unit of fuel for a full hold, buying at the row and selling at the column.");
+        ui.add_space(8.0);
+
+        let matrix = &route_profits.matrix;
+        let best = matrix
+            .cells
+            .iter()
+            .flatten()
+            .flatten()
+            .fold(0.0_f64, |best, value| best.max(value.abs()));
+        eframe::egui::Grid::new("route_heatmap")
+            .spacing([2.0, 2.0])
+            .show(ui, |ui| {
+                ui.strong("Buy \\ Sell");
+                for airport_id in &matrix.airports {
+                    ui.strong(airport_id);
+                }
+                ui.end_row();
+
+                for (row, airport_id) in matrix.airports.iter().enumerate() {
+                    if *airport_id == route_profits.airport_id {
+                        ui.strong(format!("📍 {}", airport_id));
+                    } else {
+                        ui.strong(airport_id);
+                    }
+                    for value in &matrix.cells[row] {
+                        let text = match value {
+                            Some(value) => RichText::new(format!("{:>6.0}", value)),
+                            None => RichText::new("     -"),
+                        };
+                        ui.label(text.monospace().background_color(heat(*value, best)));
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(12.0);
+        ui.strong("Best routes");
+        eframe::egui::Grid::new("route_ranking")
+            .striped(true)
+            .num_columns(6)
+            .show(ui, |ui| {
+                for header in ["Cargo", "Buy at", "Sell at", "Profit", "Fuel", "Per fuel"] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+
+                for route in route_profits.routes.iter().take(TOP_ROUTES) {
+                    ui.label(&route.cargo_type);
+                    ui.label(format!("{} (${})", route.buy_at, route.buy_price));
+                    ui.label(format!("{} (${})", route.sell_at, route.sell_price));
+                    ui.label(format!("${}", route.profit));
+                    ui.label(route.fuel_needed.to_string());
+                    let per_fuel = format!("${:.1}", route.profit_per_fuel);
+                    if route.stale {
+                        ui.weak(format!("{} (old prices)", per_fuel));
+                    } else {
+                        ui.label(per_fuel);
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+/// Green for the most profitable cells, red for the worst losses, fading to
+/// white in between
+fn heat(value: Option<f64>, best: f64) -> Color32 {
+    let Some(value) = value else {
+        return Color32::from_gray(220);
+    };
+    let strength = if best > 0.0 {
+        (value.abs() / best).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let fade = (255.0 - 135.0 * strength) as u8;
+    if value >= 0.0 {
+        Color32::from_rgb(fade, 255, fade)
+    } else {
+        Color32::from_rgb(255, fade, fade)
+    }
+}
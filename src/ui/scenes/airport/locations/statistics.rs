@@ -0,0 +1,203 @@
+use crate::{
+    api::models::NetWorthPoint,
+    systems::multiplayer::FUEL_EFFICIENCY_PROFIT_UNIT,
+    ui::scenes::{
+        Location,
+        airport::locations::{LocationContext, LocationView},
+    },
+};
+
+pub struct Statistics;
+
+impl LocationView for Statistics {
+    fn location(&self) -> Location {
+        Location::Statistics
+    }
+
+    fn label(&self) -> &'static str {
+        "📈 Statistics"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            api_client,
+            session,
+            ..
+        } = context;
+
+        ui.heading("📈 Statistics - Fuel Economy Report");
+
+        let stats = match api_client.get_statistics_sync(session.room_id, session.player_id) {
+            Ok(stats) => stats,
+            Err(err) => {
+                ui.colored_label(
+                    eframe::egui::Color32::RED,
+                    format!("Error loading statistics: {}", err),
+                );
+                return;
+            },
+        };
+
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_gray(245))
+            .inner_margin(eframe::egui::Margin::same(12.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("💰 Revenue: ${}", stats.total_revenue));
+                    ui.separator();
+                    ui.label(format!("⛽ Fuel spend: ${}", stats.fuel_spend));
+                    ui.separator();
+                    ui.label(format!("🤝 Broker fees: ${}", stats.trading_fees));
+                    ui.separator();
+                    ui.label(format!("🛢️ Fuel bought: {} units", stats.fuel_purchased));
+                    ui.separator();
+                    ui.label(format!("📏 Distance: {:.0} km", stats.distances_traveled));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(format!("🔥 Fuel burned: {} units", stats.fuel_burned));
+                    ui.separator();
+                    match stats.fuel_per_profit_dollar {
+                        Some(ratio) => ui.label(format!(
+                            "🌱 {:.1} fuel per ${} of profit",
+                            ratio * FUEL_EFFICIENCY_PROFIT_UNIT as f32,
+                            FUEL_EFFICIENCY_PROFIT_UNIT
+                        )),
+                        None => ui.label("🌱 Fuel efficiency: not in profit yet"),
+                    };
+                });
+                ui.horizontal(|ui| {
+                    let worth = &stats.net_worth;
+                    ui.strong(format!("🏦 Net worth: ${}", worth.total()));
+                    ui.separator();
+                    ui.label(format!(
+                        "${} cash + ${} cargo + ${} aircraft",
+                        worth.cash, worth.cargo, worth.aircraft
+                    ));
+                });
+                if stats.net_worth_history.len() > 1 {
+                    net_worth_chart(ui, &stats.net_worth_history);
+                }
+            });
+
+        ui.add_space(8.0);
+
+        if stats.routes.is_empty() {
+            ui.label("No flights logged yet. Fly a route to start building your report.");
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            if let Some(best) = &stats.best_route {
+                ui.colored_label(
+                    eframe::egui::Color32::from_rgb(0, 150, 0),
+                    format!(
+                        "🏆 Best route: {} → {} (${:+})",
+                        best.from,
+                        best.to,
+                        best.net_result()
+                    ),
+                );
+            }
+            if let Some(worst) = &stats.worst_route {
+                ui.separator();
+                ui.colored_label(
+                    eframe::egui::Color32::from_rgb(200, 50, 50),
+                    format!(
+                        "📉 Worst route: {} → {} (${:+})",
+                        worst.from,
+                        worst.to,
+                        worst.net_result()
+                    ),
+                );
+            }
+        });
+
+        ui.add_space(8.0);
+        ui.strong("Per-route breakdown (fuel valued at the departure airport's price):");
+
+        eframe::egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                eframe::egui::Grid::new("route_fuel_report")
+                    .striped(true)
+                    .num_columns(8)
+                    .show(ui, |ui| {
+                        for header in [
+                            "Route",
+                            "Legs",
+                            "Distance",
+                            "Fuel",
+                            "Fuel Cost",
+                            "Revenue",
+                            "Net",
+                            "Rev/$ Fuel",
+                        ] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+
+                        for route in &stats.routes {
+                            ui.label(format!("{} → {}", route.from, route.to));
+                            ui.label(route.legs_flown.to_string());
+                            ui.label(format!("{:.0} km", route.distance));
+                            ui.label(format!("{} units", route.fuel_used));
+                            ui.label(format!("${}", route.fuel_cost));
+                            ui.label(format!("${}", route.revenue));
+
+                            let net = route.net_result();
+                            let color = if net >= 0 {
+                                eframe::egui::Color32::from_rgb(0, 150, 0)
+                            } else {
+                                eframe::egui::Color32::from_rgb(200, 50, 50)
+                            };
+                            ui.colored_label(color, format!("${:+}", net));
+
+                            ui.label(
+                                route
+                                    .revenue_per_fuel_dollar()
+                                    .map(|ratio| format!("{:.1}x", ratio))
+                                    .unwrap_or_else(|| "-".to_string()),
+                            );
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+/// Net worth turn by turn, as a line
+fn net_worth_chart(ui: &mut eframe::egui::Ui, history: &[NetWorthPoint]) {
+    let size = eframe::egui::vec2(ui.available_width(), 70.0);
+    let (response, painter) = ui.allocate_painter(size, eframe::egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 4.0, eframe::egui::Color32::WHITE);
+
+    let max_worth = history
+        .iter()
+        .map(|point| point.net_worth)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let last = (history.len() - 1) as f32;
+    let line: Vec<eframe::egui::Pos2> = history
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            eframe::egui::pos2(
+                rect.left() + rect.width() * index as f32 / last,
+                rect.bottom() - rect.height() * point.net_worth as f32 / max_worth as f32,
+            )
+        })
+        .collect();
+    painter.add(eframe::egui::Shape::line(
+        line,
+        eframe::egui::Stroke::new(2.0, eframe::egui::Color32::from_rgb(50, 100, 200)),
+    ));
+
+    if let (Some(first), Some(latest)) = (history.first(), history.last()) {
+        response.on_hover_text(format!(
+            "Turn {}: ${} → turn {}: ${}",
+            first.turn_number, first.net_worth, latest.turn_number, latest.net_worth
+        ));
+    }
+}
@@ -0,0 +1,709 @@
+use crate::{
+    api::models::{
+        ApplyLoadoutRequest, OfferResponse, PilotProfileResponse, TradeAction, TradeRequest,
+    },
+    models::cargo::CargoInventory,
+    systems::{
+        game::GameState,
+        loadouts::{CargoLoadout, MAX_LOADOUT_NAME_LENGTH},
+        offers::{OfferSide, OfferTerms},
+        trading::TradingSystem,
+    },
+    ui::{
+        action_queue::ActionKind,
+        game_api_client::{ApiError, GameApiClient},
+        scenes::{
+            Location, SceneState,
+            airport::{
+                components::{cargo_filter_bar, cargo_icon, market_cargo},
+                locations::{LocationContext, LocationView},
+            },
+            room_lobby::GameSession,
+        },
+    },
+};
+
+pub struct TradingDesk;
+
+impl LocationView for TradingDesk {
+    fn location(&self) -> Location {
+        Location::TradingDesk
+    }
+
+    fn label(&self) -> &'static str {
+        "💼 Trading Desk"
+    }
+
+    fn render(&self, context: LocationContext<'_>, ui: &mut eframe::egui::Ui) {
+        let LocationContext {
+            game_state,
+            scene_state,
+            api_client,
+            session,
+        } = context;
+
+        ui.heading("💼 Trading Desk - Buy & Sell Cargo");
+
+        // Trading desk header
+        eframe::egui::Frame::none()
+            .fill(eframe::egui::Color32::from_rgb(240, 248, 255))
+            .inner_margin(eframe::egui::Margin::same(8.0))
+            .show(ui, |ui| {
+                ui.label("\"Looking to do some business? We handle all cargo transactions here.\"");
+            });
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut scene_state.show_offers, false, "📈 Market");
+            ui.selectable_value(&mut scene_state.show_offers, true, "🤝 Player Offers");
+        });
+
+        ui.separator();
+
+        if scene_state.show_offers {
+            render_offers(game_state, scene_state, api_client, session, ui);
+            return;
+        }
+
+        cargo_filter_bar(scene_state, "trading_desk", ui);
+        let listed = market_cargo(game_state, scene_state);
+
+        // Enhanced cargo selection with icons and details
+        ui.horizontal(|ui| {
+            ui.label("📦 Select Cargo:");
+            ui.add_space(10.0);
+
+            eframe::egui::ComboBox::from_id_salt("cargo_selection")
+                .width(200.0)
+                .selected_text(
+                    scene_state
+                        .selected_cargo
+                        .as_ref()
+                        .and_then(|id| game_state.cargo_types.get(id))
+                        .map(|ct| {
+                            let icon = cargo_icon(&ct.name);
+                            format!("{} {}", icon, ct.name)
+                        })
+                        .unwrap_or("Choose cargo type...".to_string()),
+                )
+                .show_ui(ui, |ui| {
+                    for cargo in &listed {
+                        let icon = cargo_icon(&cargo.cargo_type.name);
+                        ui.selectable_value(
+                            &mut scene_state.selected_cargo,
+                            Some(cargo.id.clone()),
+                            format!(
+                                "{} {} · {} ({}kg/unit)",
+                                icon,
+                                cargo.cargo_type.name,
+                                cargo.cargo_type.category.label(),
+                                cargo.cargo_type.weight_per_unit
+                            ),
+                        );
+                    }
+                });
+        });
+
+        // Enhanced quantity selection with smart suggestions
+        ui.horizontal(|ui| {
+            ui.label("📊 Quantity:");
+            ui.add_space(10.0);
+
+            // Smart max calculation
+            let max_quantity = if let Some(selected_cargo_id) = &scene_state.selected_cargo {
+                if let (Some(cargo_type), Some(market)) = (
+                    game_state.cargo_types.get(selected_cargo_id),
+                    game_state.get_current_market(),
+                ) {
+                    if let Some(price) = market.cargo_prices.get(selected_cargo_id) {
+                        let max_by_money = game_state.player.money / price;
+                        let current_weight = game_state
+                            .player
+                            .current_cargo_weight(&game_state.cargo_types);
+                        let available_weight = game_state
+                            .player
+                            .max_cargo_weight
+                            .saturating_sub(current_weight);
+                        let max_by_weight = available_weight / cargo_type.weight_per_unit;
+                        let max_by_broker = TradingSystem::purchases_left(
+                            &game_state.player,
+                            market,
+                            selected_cargo_id,
+                        );
+                        max_by_money.min(max_by_weight).min(max_by_broker).max(1)
+                    } else {
+                        20
+                    }
+                } else {
+                    20
+                }
+            } else {
+                20
+            };
+
+            let current_quantity = scene_state.trade_quantity;
+            ui.add(
+                eframe::egui::Slider::new(
+                    &mut scene_state.trade_quantity,
+                    1..=max_quantity.max(current_quantity),
+                )
+                .text("units"),
+            );
+
+            // Quick quantity buttons
+            ui.separator();
+            if ui.small_button("1").clicked() {
+                scene_state.trade_quantity = 1;
+            }
+            if ui.small_button("5").clicked() {
+                scene_state.trade_quantity = 5.min(max_quantity);
+            }
+            if ui.small_button("Max").clicked() {
+                scene_state.trade_quantity = max_quantity;
+            }
+            let preset = scene_state.quantity_defaults.trade_quantity;
+            if ui.small_button(format!("Preset ({})", preset)).clicked() {
+                scene_state.trade_quantity = preset.min(max_quantity);
+            }
+        });
+
+        ui.separator();
+
+        // Enhanced transaction details for selected cargo
+        if let Some(selected_cargo_id) = &scene_state.selected_cargo {
+            let cargo_type = game_state.cargo_types.get(selected_cargo_id).cloned();
+            let market = game_state.get_current_market().cloned();
+
+            if let (Some(cargo_type), Some(market)) = (cargo_type, market)
+                && let Some(current_price) = market.cargo_prices.get(selected_cargo_id)
+            {
+                // Transaction details frame
+                eframe::egui::Frame::none()
+                    .fill(eframe::egui::Color32::from_gray(248))
+                    .stroke(eframe::egui::Stroke::new(
+                        1.0,
+                        eframe::egui::Color32::from_gray(200),
+                    ))
+                    .inner_margin(eframe::egui::Margin::same(12.0))
+                    .show(ui, |ui| {
+                        let icon = cargo_icon(&cargo_type.name);
+
+                        ui.strong(format!("{} {} Transaction Details", icon, cargo_type.name));
+                        ui.separator();
+
+                        eframe::egui::Grid::new("transaction_details")
+                            .num_columns(2)
+                            .spacing([20.0, 4.0])
+                            .show(ui, |ui| {
+                                ui.label("Current Market Price:");
+                                ui.label(format!("${}/unit", current_price));
+                                ui.end_row();
+
+                                ui.label("You Currently Own:");
+                                let owned = game_state
+                                    .player
+                                    .cargo_inventory
+                                    .get_quantity(selected_cargo_id);
+                                ui.label(format!("{} units", owned));
+                                ui.end_row();
+
+                                ui.label("Weight per Unit:");
+                                ui.label(format!("{}kg", cargo_type.weight_per_unit));
+                                ui.end_row();
+
+                                ui.label("Transaction Quantity:");
+                                ui.label(format!("{} units", scene_state.trade_quantity));
+                                ui.end_row();
+
+                                ui.label("Broker:");
+                                let volume = game_state.player.broker_volume_at(&market.airport_id);
+                                let tier = TradingSystem::broker_tier(volume);
+                                let label = format!("{} ({}% fee)", tier.name, tier.fee_percent());
+                                match TradingSystem::next_broker_tier(volume) {
+                                    Some(next) => {
+                                        ui.label(label).on_hover_text(format!(
+                                            "Trade ${} more here for {} ({}%)",
+                                            next.min_volume - volume,
+                                            next.name,
+                                            next.fee_percent()
+                                        ));
+                                    },
+                                    None => {
+                                        ui.label(label);
+                                    },
+                                }
+                                ui.end_row();
+
+                                ui.label("Left This Turn:");
+                                ui.label(format!(
+                                    "Buy {} · Sell {} (of {} each)",
+                                    TradingSystem::purchases_left(
+                                        &game_state.player,
+                                        &market,
+                                        selected_cargo_id
+                                    ),
+                                    TradingSystem::sales_left(
+                                        &game_state.player,
+                                        &market,
+                                        selected_cargo_id
+                                    ),
+                                    tier.units_per_turn
+                                ))
+                                .on_hover_text(
+                                    "The broker caps how much of one cargo you can trade per turn",
+                                );
+                                ui.end_row();
+                            });
+                    });
+
+                ui.add_space(8.0);
+
+                // Prices with the broker's fee, as the server will charge them
+                let buy_fee = TradingSystem::quote_trade(
+                    &game_state.player,
+                    &market,
+                    selected_cargo_id,
+                    scene_state.trade_quantity,
+                )
+                .map(|quote| quote.fee)
+                .unwrap_or(0);
+                let total_cost = current_price
+                    .saturating_mul(scene_state.trade_quantity)
+                    .saturating_add(buy_fee);
+                let total_weight = cargo_type
+                    .weight_per_unit
+                    .saturating_mul(scene_state.trade_quantity);
+
+                // Buy transaction
+                eframe::egui::Frame::none()
+                    .fill(eframe::egui::Color32::from_rgb(240, 255, 240))
+                    .stroke(eframe::egui::Stroke::new(
+                        1.0,
+                        eframe::egui::Color32::from_rgb(100, 200, 100),
+                    ))
+                    .inner_margin(eframe::egui::Margin::same(8.0))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            let buys_left = TradingSystem::purchases_left(
+                                &game_state.player,
+                                &market,
+                                selected_cargo_id,
+                            );
+                            let can_buy = game_state.player.can_afford(total_cost)
+                                && game_state
+                                    .player
+                                    .can_carry_more_weight(total_weight, &game_state.cargo_types)
+                                && scene_state.trade_quantity <= buys_left;
+
+                            ui.add_enabled_ui(can_buy, |ui| {
+                                if ui
+                                    .button(format!("💰 BUY {} units", scene_state.trade_quantity))
+                                    .clicked()
+                                {
+                                    scene_state.outbox.push(ActionKind::Trade(TradeRequest {
+                                        cargo_type: selected_cargo_id.clone(),
+                                        quantity: scene_state.trade_quantity,
+                                        action: TradeAction::Buy,
+                                    }));
+                                }
+                            });
+
+                            ui.separator();
+
+                            if can_buy {
+                                ui.label(format!(
+                                    "Cost: ${} (incl. ${} fee) | Weight: {}kg",
+                                    total_cost, buy_fee, total_weight
+                                ));
+                                ui.separator();
+                                ui.label(format!(
+                                    "After: ${} remaining",
+                                    game_state.player.money.saturating_sub(total_cost)
+                                ));
+                            } else if scene_state.trade_quantity > buys_left {
+                                ui.colored_label(
+                                    eframe::egui::Color32::from_rgb(200, 50, 50),
+                                    format!(
+                                        "🚦 The broker takes only {} more this turn",
+                                        buys_left
+                                    ),
+                                );
+                            } else if !game_state.player.can_afford(total_cost) {
+                                ui.colored_label(
+                                    eframe::egui::Color32::from_rgb(200, 50, 50),
+                                    "💸 Not enough money",
+                                );
+                            } else {
+                                ui.colored_label(
+                                    eframe::egui::Color32::from_rgb(200, 50, 50),
+                                    "📦 Not enough cargo space",
+                                );
+                            }
+                        });
+                    });
+
+                ui.add_space(4.0);
+
+                // Sell transaction
+                eframe::egui::Frame::none()
+                    .fill(eframe::egui::Color32::from_rgb(255, 248, 240))
+                    .stroke(eframe::egui::Stroke::new(
+                        1.0,
+                        eframe::egui::Color32::from_rgb(200, 150, 100),
+                    ))
+                    .inner_margin(eframe::egui::Margin::same(8.0))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            let owned_quantity = game_state
+                                .player
+                                .cargo_inventory
+                                .get_quantity(selected_cargo_id);
+                            let sales_left = TradingSystem::sales_left(
+                                &game_state.player,
+                                &market,
+                                selected_cargo_id,
+                            );
+                            let sell_quantity = scene_state
+                                .trade_quantity
+                                .min(owned_quantity)
+                                .min(sales_left);
+                            let can_sell = sell_quantity > 0;
+
+                            ui.add_enabled_ui(can_sell, |ui| {
+                                if ui
+                                    .button(format!("💵 SELL {} units", sell_quantity))
+                                    .clicked()
+                                {
+                                    scene_state.outbox.push(ActionKind::Trade(TradeRequest {
+                                        cargo_type: selected_cargo_id.clone(),
+                                        quantity: sell_quantity,
+                                        action: TradeAction::Sell,
+                                    }));
+                                }
+                            });
+
+                            ui.separator();
+
+                            if can_sell {
+                                let sell_quote = TradingSystem::quote_sale(
+                                    &game_state.player,
+                                    &market,
+                                    selected_cargo_id,
+                                    sell_quantity,
+                                );
+                                let (sell_value, sell_fee, sell_bonus) = sell_quote
+                                    .map(|quote| (quote.proceeds(), quote.fee, quote.bonus))
+                                    .unwrap_or((0, 0, 0));
+                                ui.label(format!(
+                                    "Revenue: ${} (after ${} fee) | Units: {}",
+                                    sell_value, sell_fee, sell_quantity
+                                ));
+                                if sell_bonus > 0 {
+                                    ui.label(format!("🔥 +${} streak bonus", sell_bonus));
+                                }
+                                ui.separator();
+                                ui.label(format!(
+                                    "After: ${} total",
+                                    game_state.player.money + sell_value
+                                ));
+                            } else if owned_quantity > 0 {
+                                ui.colored_label(
+                                    eframe::egui::Color32::from_rgb(200, 50, 50),
+                                    "🚦 The broker won't take more of this until next turn",
+                                );
+                            } else {
+                                ui.colored_label(
+                                    eframe::egui::Color32::from_rgb(200, 50, 50),
+                                    "❌ No cargo to sell",
+                                );
+                            }
+                        });
+                    });
+            }
+        } else {
+            // No cargo selected
+            eframe::egui::Frame::none()
+                .fill(eframe::egui::Color32::from_gray(250))
+                .inner_margin(eframe::egui::Margin::same(16.0))
+                .show(ui, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.label("👆 Please select a cargo type above to begin trading");
+                        ui.add_space(8.0);
+                        ui.label("💡 Tip: Check the Market Board first to see the best deals!");
+                    });
+                });
+        }
+
+        ui.add_space(8.0);
+        render_loadouts(game_state, scene_state, api_client, session, ui);
+    }
+}
+
+/// Saved cargo holds: one click sells what the hold has too much of and
+/// buys what it's short of, here and now
+fn render_loadouts(
+    game_state: &mut GameState,
+    scene_state: &mut SceneState,
+    api_client: &GameApiClient,
+    session: &GameSession,
+    ui: &mut eframe::egui::Ui,
+) {
+    eframe::egui::CollapsingHeader::new("🎒 Load-outs")
+        .id_salt("trading_desk_loadouts")
+        .show(ui, |ui| {
+            if scene_state.loadouts.is_none() {
+                match api_client.get_pilot_profile_sync(&session.player_name) {
+                    Ok(profile) => scene_state.loadouts = Some(profile.loadouts),
+                    Err(err) => {
+                        ui.colored_label(
+                            eframe::egui::Color32::RED,
+                            format!("Error loading load-outs: {}", err),
+                        );
+                        return;
+                    },
+                }
+            }
+
+            if let Some(message) = &scene_state.loadout_message {
+                ui.label(message);
+            }
+
+            let loadouts = scene_state.loadouts.clone().unwrap_or_default();
+            if loadouts.is_empty() {
+                ui.weak("No load-outs yet. Fill your hold, then save it here.");
+            }
+            for loadout in &loadouts {
+                ui.horizontal(|ui| {
+                    let contents = loadout
+                        .cargo
+                        .iter()
+                        .map(|(cargo_id, quantity)| format!("{} {}", quantity, cargo_id))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    if ui
+                        .button(format!("📦 {}", loadout.name))
+                        .on_hover_text(if contents.is_empty() {
+                            "An empty hold".to_string()
+                        } else {
+                            contents
+                        })
+                        .clicked()
+                    {
+                        if loadout.plan(&game_state.player.cargo_inventory).is_empty() {
+                            scene_state.loadout_message =
+                                Some(format!("Your hold already matches '{}'", loadout.name));
+                        } else {
+                            scene_state.outbox.push(ActionKind::ApplyLoadout(
+                                ApplyLoadoutRequest {
+                                    name: loadout.name.clone(),
+                                },
+                            ));
+                            scene_state.loadout_message =
+                                Some(format!("Loading '{}'...", loadout.name));
+                        }
+                    }
+                    if ui.small_button("🗑").on_hover_text("Forget").clicked() {
+                        let result =
+                            api_client.delete_loadout_sync(&session.player_name, &loadout.name);
+                        apply_loadouts_result(scene_state, result);
+                    }
+                });
+            }
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    eframe::egui::TextEdit::singleline(&mut scene_state.loadout_name)
+                        .hint_text("ORD electronics run")
+                        .char_limit(MAX_LOADOUT_NAME_LENGTH),
+                );
+                if ui.button("💾 Save current hold").clicked() {
+                    let loadout = CargoLoadout::from_hold(
+                        scene_state.loadout_name.clone(),
+                        &game_state.player.cargo_inventory,
+                    );
+                    if let Err(err) = loadout.validate() {
+                        scene_state.loadout_message = Some(err);
+                    } else if apply_loadouts_result(
+                        scene_state,
+                        api_client.save_loadout_sync(&session.player_name, &loadout),
+                    ) {
+                        scene_state.loadout_name.clear();
+                    }
+                }
+            });
+        });
+}
+
+/// Take the load-outs from the server's answer; false if it said no
+fn apply_loadouts_result(
+    scene_state: &mut SceneState,
+    result: Result<PilotProfileResponse, ApiError>,
+) -> bool {
+    match result {
+        Ok(profile) => {
+            scene_state.loadouts = Some(profile.loadouts);
+            scene_state.loadout_message = None;
+            true
+        },
+        Err(err) => {
+            scene_state.loadout_message = Some(err.to_string());
+            false
+        },
+    }
+}
+
+/// The order book: post offers for other pilots here, take theirs, and
+/// cancel your own. The server holds whatever's on offer in escrow.
+fn render_offers(
+    game_state: &mut GameState,
+    scene_state: &mut SceneState,
+    api_client: &GameApiClient,
+    session: &GameSession,
+    ui: &mut eframe::egui::Ui,
+) {
+    let book = match api_client.get_offers_sync(session.room_id, session.player_id) {
+        Ok(book) => book,
+        Err(err) => {
+            ui.colored_label(
+                eframe::egui::Color32::RED,
+                format!("Error loading offers: {}", err),
+            );
+            return;
+        },
+    };
+
+    if let Some(error) = &scene_state.offer_error {
+        ui.colored_label(eframe::egui::Color32::RED, error);
+    }
+
+    // Post a new offer
+    eframe::egui::Frame::none()
+        .fill(eframe::egui::Color32::from_gray(248))
+        .inner_margin(eframe::egui::Margin::same(12.0))
+        .show(ui, |ui| {
+            ui.strong(format!("📌 Post an offer at {}", book.airport_id));
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut scene_state.offer_side, OfferSide::Sell, "Sell");
+                ui.radio_value(&mut scene_state.offer_side, OfferSide::Buy, "Buy");
+
+                eframe::egui::ComboBox::from_id_salt("offer_cargo")
+                    .selected_text(
+                        scene_state
+                            .selected_cargo
+                            .as_ref()
+                            .and_then(|id| game_state.cargo_types.get(id))
+                            .map(|ct| format!("{} {}", cargo_icon(&ct.name), ct.name))
+                            .unwrap_or("Choose cargo type...".to_string()),
+                    )
+                    .show_ui(ui, |ui| {
+                        for (cargo_id, cargo_type) in &game_state.cargo_types {
+                            ui.selectable_value(
+                                &mut scene_state.selected_cargo,
+                                Some(cargo_id.clone()),
+                                format!("{} {}", cargo_icon(&cargo_type.name), cargo_type.name),
+                            );
+                        }
+                    });
+
+                ui.add(
+                    eframe::egui::DragValue::new(&mut scene_state.offer_quantity)
+                        .range(1..=u32::MAX)
+                        .suffix(" units"),
+                );
+                ui.add(
+                    eframe::egui::DragValue::new(&mut scene_state.offer_price)
+                        .range(1..=u32::MAX)
+                        .prefix("$")
+                        .suffix("/unit"),
+                );
+            });
+
+            let Some(cargo_id) = scene_state.selected_cargo.clone() else {
+                return;
+            };
+            if let Some(price) = game_state
+                .get_current_market()
+                .and_then(|market| market.cargo_prices.get(&cargo_id))
+            {
+                ui.weak(format!("The market here pays ${}/unit", price));
+            }
+            if ui.button("📌 Post offer").clicked() {
+                let terms = OfferTerms {
+                    side: scene_state.offer_side,
+                    cargo_type: cargo_id,
+                    quantity: scene_state.offer_quantity,
+                    unit_price: scene_state.offer_price,
+                };
+                let result =
+                    api_client.place_offer_sync(session.room_id, session.player_id, &terms);
+                scene_state.offer_error = apply_offer_result(game_state, result);
+            }
+        });
+
+    ui.add_space(8.0);
+    ui.strong("Offers from other pilots here:");
+    if book.offers.is_empty() {
+        ui.label("Nobody has anything on offer here right now.");
+    }
+    for offer in &book.offers {
+        ui.horizontal(|ui| {
+            ui.label(offer.describe());
+            let quantity = scene_state.offer_quantity.min(offer.quantity);
+            let label = match offer.side {
+                OfferSide::Sell => format!("💰 Buy {}", quantity),
+                OfferSide::Buy => format!("💵 Sell {}", quantity),
+            };
+            if ui.button(label).clicked() {
+                let result = api_client.accept_offer_sync(
+                    session.room_id,
+                    session.player_id,
+                    offer.id,
+                    quantity,
+                );
+                scene_state.offer_error = apply_offer_result(game_state, result);
+            }
+        });
+    }
+
+    ui.add_space(8.0);
+    ui.strong("Your open offers:");
+    if book.my_offers.is_empty() {
+        ui.label("You have no open offers.");
+    }
+    for offer in &book.my_offers {
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "{} {} {} at ${} each ({})",
+                offer.side.verb(),
+                offer.quantity,
+                offer.cargo_type,
+                offer.unit_price,
+                offer.airport_id
+            ));
+            if ui.button("✖ Cancel").clicked() {
+                let result =
+                    api_client.cancel_offer_sync(session.room_id, session.player_id, offer.id);
+                scene_state.offer_error = apply_offer_result(game_state, result);
+            }
+        });
+    }
+}
+
+/// Take the server's word for the player's money and hold after an offer
+/// changes hands, returning the error to show if it didn't go through.
+fn apply_offer_result(
+    game_state: &mut GameState,
+    result: Result<OfferResponse, ApiError>,
+) -> Option<String> {
+    match result {
+        Ok(response) => {
+            game_state.player.money = response.new_money;
+            let mut inventory = CargoInventory::new();
+            for (cargo_id, quantity) in &response.new_inventory {
+                inventory.add_cargo(cargo_id, *quantity);
+            }
+            game_state.player.cargo_inventory = inventory;
+            None
+        },
+        Err(err) => Some(err.to_string()),
+    }
+}
@@ -0,0 +1,334 @@
+pub mod components;
+pub mod drafts;
+pub mod layout;
+pub mod locations;
+pub mod presets;
+
+use crate::{
+    systems::{
+        game::GameState,
+        profile::{MAX_DEFAULT_QUANTITY, QuantityDefaults},
+    },
+    ui::{
+        game_api_client::{ApiError, GameApiClient},
+        scenes::{Location, SceneState, room_lobby::GameSession},
+    },
+};
+use layout::{Dock, DockedPanel};
+
+pub use locations::{LocationContext, LocationRegistry, LocationView};
+
+pub struct AirportScene;
+
+impl AirportScene {
+    pub fn render(
+        game_state: &mut GameState,
+        scene_state: &mut SceneState,
+        ctx: &eframe::egui::Context,
+        api_client: &GameApiClient,
+        session: &GameSession,
+    ) {
+        // Get current airport info
+        let current_airport = game_state
+            .airports
+            .get(&game_state.player.current_airport)
+            .cloned();
+
+        let airport_name = current_airport
+            .as_ref()
+            .map(|a| a.name.as_str())
+            .unwrap_or("Unknown Airport");
+
+        let registry = LocationRegistry::default();
+
+        // Docked panels have to claim their space before the central panel
+        for panel in DockedPanel::ALL {
+            let placement = scene_state.panel_layout.placement(panel);
+            let id = format!("docked_{:?}_{:?}", panel, placement.dock);
+            let mut render = |ui: &mut eframe::egui::Ui| {
+                eframe::egui::ScrollArea::vertical()
+                    .id_salt(&id)
+                    .show(ui, |ui| {
+                        Self::render_docked_panel(
+                            panel,
+                            &registry,
+                            LocationContext {
+                                game_state: &mut *game_state,
+                                scene_state: &mut *scene_state,
+                                api_client,
+                                session,
+                            },
+                            ui,
+                        );
+                    });
+            };
+
+            let size = match placement.dock {
+                Dock::Hidden => continue,
+                Dock::Left => eframe::egui::SidePanel::left(id.clone())
+                    .resizable(true)
+                    .default_width(placement.size)
+                    .min_width(200.0)
+                    .show(ctx, |ui| render(ui))
+                    .response
+                    .rect
+                    .width(),
+                Dock::Right => eframe::egui::SidePanel::right(id.clone())
+                    .resizable(true)
+                    .default_width(placement.size)
+                    .min_width(200.0)
+                    .show(ctx, |ui| render(ui))
+                    .response
+                    .rect
+                    .width(),
+                Dock::Bottom => eframe::egui::TopBottomPanel::bottom(id.clone())
+                    .resizable(true)
+                    .default_height(placement.size)
+                    .min_height(120.0)
+                    .show(ctx, |ui| render(ui))
+                    .response
+                    .rect
+                    .height(),
+            };
+
+            let size = size.round();
+            let placement = scene_state.panel_layout.placement_mut(panel);
+            if placement.size != size {
+                placement.size = size;
+                scene_state.panel_layout_changed = true;
+            }
+        }
+
+        eframe::egui::CentralPanel::default().show(ctx, |ui| {
+            // Airport header
+            ui.horizontal(|ui| {
+                ui.heading(format!("🛩️ {} - Fixed Base Operation", airport_name));
+                ui.with_layout(
+                    eframe::egui::Layout::right_to_left(eframe::egui::Align::Center),
+                    |ui| {
+                        if ui.button("🚪 Leave Room").clicked() {
+                            Self::request_leave(scene_state, api_client, session, false);
+                        }
+                        Self::render_layout_menu(scene_state, ui);
+                        Self::render_presets_menu(scene_state, ui);
+                        ui.label(format!(
+                            "Turn: {} · {}",
+                            game_state.turn_number,
+                            game_state.date()
+                        ));
+                    },
+                );
+            });
+
+            ui.separator();
+
+            // Player status bar - always visible
+            components::status_bar(game_state, ui);
+
+            ui.separator();
+
+            // FBO location buttons
+            components::location_buttons(&registry, scene_state, ui);
+
+            ui.separator();
+
+            // Current location content
+            if let Some(view) = registry.get(&scene_state.current_location) {
+                view.render(
+                    LocationContext {
+                        game_state,
+                        scene_state,
+                        api_client,
+                        session,
+                    },
+                    ui,
+                );
+            }
+        });
+
+        Self::render_leave_confirmation(scene_state, ctx, api_client, session);
+
+        // Wait for the player to let go before saving a resized panel
+        if scene_state.panel_layout_changed && !ctx.input(|i| i.pointer.any_down()) {
+            scene_state.panel_layout_changed = false;
+            if let Err(e) = scene_state.panel_layout.save() {
+                eprintln!("{}", e);
+            }
+        }
+        if scene_state.quantity_defaults_changed && !ctx.input(|i| i.pointer.any_down()) {
+            scene_state.quantity_defaults_changed = false;
+            if let Err(e) = presets::save(&scene_state.quantity_defaults) {
+                eprintln!("{}", e);
+            }
+        }
+        drafts::save_if_due(scene_state, session);
+    }
+
+    fn render_docked_panel(
+        panel: DockedPanel,
+        registry: &LocationRegistry,
+        context: LocationContext<'_>,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        let location = match panel {
+            DockedPanel::Market => Location::MarketBoard,
+            DockedPanel::Chat => Location::MessageBoard,
+            DockedPanel::Inventory => {
+                ui.heading(panel.label());
+                ui.separator();
+                if context.game_state.player.cargo_inventory.is_empty() {
+                    ui.label("No cargo on board");
+                } else {
+                    components::cargo_inventory_grid(context.game_state, "docked_inventory", ui);
+                }
+                return;
+            },
+        };
+
+        // The same location twice would fight over its widgets
+        if context.scene_state.current_location == location {
+            ui.heading(panel.label());
+            ui.separator();
+            ui.label("Open in the main view");
+            return;
+        }
+
+        if let Some(view) = registry.get(&location) {
+            view.render(context, ui);
+        }
+    }
+
+    fn render_layout_menu(scene_state: &mut SceneState, ui: &mut eframe::egui::Ui) {
+        ui.menu_button("🪟 Panels", |ui| {
+            eframe::egui::Grid::new("panel_layout_menu")
+                .num_columns(Dock::ALL.len() + 1)
+                .show(ui, |ui| {
+                    for panel in DockedPanel::ALL {
+                        ui.label(panel.label());
+                        let placement = scene_state.panel_layout.placement_mut(panel);
+                        for dock in Dock::ALL {
+                            if ui
+                                .radio_value(&mut placement.dock, dock, dock.label())
+                                .changed()
+                            {
+                                scene_state.panel_layout_changed = true;
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+            if ui.button("↺ Reset layout").clicked() {
+                scene_state.panel_layout = layout::PanelLayout::default();
+                scene_state.panel_layout_changed = true;
+                ui.close_menu();
+            }
+        });
+    }
+
+    fn render_presets_menu(scene_state: &mut SceneState, ui: &mut eframe::egui::Ui) {
+        ui.menu_button("🎚 Presets", |ui| {
+            let defaults = &mut scene_state.quantity_defaults;
+            let mut changed = false;
+            eframe::egui::Grid::new("quantity_presets_menu")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("⛽ Fuel to buy:");
+                    changed |= ui
+                        .add(
+                            eframe::egui::DragValue::new(&mut defaults.fuel_quantity)
+                                .range(1..=MAX_DEFAULT_QUANTITY)
+                                .suffix(" units"),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("📦 Cargo to trade:");
+                    changed |= ui
+                        .add(
+                            eframe::egui::DragValue::new(&mut defaults.trade_quantity)
+                                .range(1..=MAX_DEFAULT_QUANTITY)
+                                .suffix(" units"),
+                        )
+                        .changed();
+                    ui.end_row();
+                });
+            changed |= ui
+                .checkbox(
+                    &mut defaults.auto_fill_on_arrival,
+                    "Fill the tank at the pump after landing",
+                )
+                .changed();
+
+            ui.separator();
+            if ui.button("↺ Reset presets").clicked() {
+                *defaults = QuantityDefaults::default();
+                changed = true;
+                ui.close_menu();
+            }
+
+            if changed {
+                scene_state.quantity_defaults_changed = true;
+                scene_state.apply_quantity_defaults();
+            }
+        });
+    }
+
+    fn request_leave(
+        scene_state: &mut SceneState,
+        api_client: &GameApiClient,
+        session: &GameSession,
+        force: bool,
+    ) {
+        match api_client.leave_room_sync(session.room_id, session.player_id, force) {
+            Ok(response) => {
+                if response.success {
+                    scene_state.leave_obligations = None;
+                    scene_state.left_room = true;
+                } else {
+                    eprintln!("Failed to leave room: {}", response.message);
+                }
+            },
+            Err(ApiError::ConfirmationRequired { obligations, .. }) => {
+                scene_state.leave_obligations = Some(obligations);
+            },
+            Err(e) => {
+                eprintln!("Failed to leave room: {}", e);
+            },
+        }
+    }
+
+    fn render_leave_confirmation(
+        scene_state: &mut SceneState,
+        ctx: &eframe::egui::Context,
+        api_client: &GameApiClient,
+        session: &GameSession,
+    ) {
+        let Some(obligations) = scene_state.leave_obligations.clone() else {
+            return;
+        };
+
+        eframe::egui::Window::new("⚠️ Leave Room?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(eframe::egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("You still have assets in this room:");
+                ui.add_space(4.0);
+                for obligation in &obligations {
+                    ui.label(format!("  • {}", obligation));
+                }
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("🚪 Leave Anyway").clicked() {
+                        Self::request_leave(scene_state, api_client, session, true);
+                    }
+                    if ui.button("❌ Stay").clicked() {
+                        scene_state.leave_obligations = None;
+                    }
+                });
+            });
+    }
+}
@@ -0,0 +1,28 @@
+use std::{fs, path::PathBuf};
+
+use crate::systems::{SaveSystem, profile::QuantityDefaults};
+
+const PRESETS_FILE: &str = "gui_presets.json";
+
+/// The saved quantity defaults, or the stock ones when there are none or
+/// they can't be read
+pub fn load() -> QuantityDefaults {
+    file_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str::<QuantityDefaults>(&json).ok())
+        .filter(|defaults| defaults.validate().is_ok())
+        .unwrap_or_default()
+}
+
+pub fn save(defaults: &QuantityDefaults) -> Result<(), String> {
+    let path = file_path().ok_or("Could not determine where to save the presets")?;
+    let json = serde_json::to_string_pretty(defaults)
+        .map_err(|e| format!("Failed to serialize presets: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to save presets: {}", e))
+}
+
+/// Next to the panel layout, above the save games
+fn file_path() -> Option<PathBuf> {
+    let save_dir = SaveSystem::get_save_directory().ok()?;
+    Some(save_dir.parent()?.join(PRESETS_FILE))
+}
@@ -0,0 +1,209 @@
+use eframe::egui;
+
+use crate::{
+    api::models::{HostedRoomSummary, RoomAlert},
+    ui::{
+        game_api_client::GameApiClient,
+        scenes::{
+            Scene,
+            room_lobby::{GameSession, idle_time},
+        },
+    },
+};
+
+/// What the host picked on the dashboard
+pub enum DashboardAction {
+    /// Back to the lobby
+    Close,
+    /// Play as the host in one of their rooms, opening where they are
+    Switch(Scene, GameSession),
+}
+
+/// Every room a player hosts at a glance: who's playing, how far they've
+/// got, and which games have stalled or finished
+pub struct HostDashboardScene {
+    pub host_name: String,
+    pub rooms: Vec<HostedRoomSummary>,
+    pub error_message: Option<String>,
+    pub last_refresh: Option<std::time::Instant>,
+    /// How often the rooms refresh by themselves
+    pub refresh_every: std::time::Duration,
+}
+
+impl Default for HostDashboardScene {
+    fn default() -> Self {
+        Self {
+            host_name: String::new(),
+            rooms: Vec::new(),
+            error_message: None,
+            last_refresh: None,
+            refresh_every: std::time::Duration::from_secs(5),
+        }
+    }
+}
+
+impl HostDashboardScene {
+    /// Show the rooms `host_name` hosts, fetching them on the next frame
+    pub fn open(&mut self, host_name: &str) {
+        if self.host_name != host_name {
+            self.rooms.clear();
+        }
+        self.host_name = host_name.to_string();
+        self.error_message = None;
+        self.last_refresh = None;
+    }
+
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        client: &GameApiClient,
+    ) -> Option<DashboardAction> {
+        let mut close = false;
+        let mut switch_to = None;
+
+        let due = self
+            .last_refresh
+            .is_none_or(|refreshed| refreshed.elapsed() >= self.refresh_every);
+        if due {
+            self.refresh(client);
+        }
+        let since_refresh = self.last_refresh.map(|r| r.elapsed()).unwrap_or_default();
+        ctx.request_repaint_after(self.refresh_every.saturating_sub(since_refresh));
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(format!("🖥 Rooms hosted by {}", self.host_name));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("🏢 Back to Lobby").clicked() {
+                        close = true;
+                    }
+                    if ui.button("🔄 Refresh").clicked() {
+                        self.refresh(client);
+                    }
+                });
+            });
+            ui.separator();
+
+            if let Some(error) = &self.error_message {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+
+            let alerts: usize = self.rooms.iter().map(|room| room.alerts.len()).sum();
+            ui.label(format!(
+                "{} room(s), {} pilot(s) online, {} alert(s)",
+                self.rooms.len(),
+                self.rooms
+                    .iter()
+                    .map(|room| room.players_online)
+                    .sum::<u32>(),
+                alerts
+            ));
+            ui.add_space(8.0);
+
+            if self.rooms.is_empty() {
+                ui.label("You aren't hosting any rooms on this server.");
+                return;
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for room in &self.rooms {
+                    if let Some(session) = self.render_room(ui, room) {
+                        switch_to = Some(session);
+                    }
+                    ui.add_space(6.0);
+                }
+            });
+        });
+
+        if close {
+            return Some(DashboardAction::Close);
+        }
+        switch_to.and_then(|session| self.enter(client, session))
+    }
+
+    /// One room's card. Returns the host's session in it when they switch
+    /// in.
+    fn render_room(&self, ui: &mut egui::Ui, room: &HostedRoomSummary) -> Option<GameSession> {
+        let mut switch_to = None;
+
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong(&room.room_name);
+                ui.label(format!("{:?}", room.game_status));
+                if room.round > 1 {
+                    ui.weak(format!("round {}", room.round));
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("▶ Switch").clicked() {
+                        switch_to = Some(GameSession {
+                            room_id: room.room_id,
+                            player_id: room.host_player_id,
+                            player_name: self.host_name.clone(),
+                        });
+                    }
+                });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "👥 {}/{} online ({} max)",
+                    room.players_online, room.players_total, room.max_players
+                ));
+                ui.label(format!("⏱ Turn {}", room.turn_number));
+                ui.weak(format!("last played {}", idle_time(room.idle_seconds)));
+            });
+
+            for alert in &room.alerts {
+                let (color, text) = match alert {
+                    RoomAlert::Stalled { idle_minutes } => (
+                        egui::Color32::from_rgb(220, 120, 20),
+                        format!("⚠ Stalled: nobody has played for {} min", idle_minutes),
+                    ),
+                    RoomAlert::Finished { winner } => (
+                        egui::Color32::from_rgb(40, 160, 60),
+                        format!("🏆 Finished: {} won", winner),
+                    ),
+                };
+                ui.colored_label(color, text);
+            }
+
+            ui.collapsing(format!("Pilots ({})", room.players.len()), |ui| {
+                for player in &room.players {
+                    ui.horizontal(|ui| {
+                        ui.label(if player.is_online { "🟢" } else { "⚫" });
+                        ui.label(&player.player_name);
+                        ui.weak(format!("turn {}", player.turn_number));
+                    });
+                }
+            });
+        });
+
+        switch_to
+    }
+
+    fn refresh(&mut self, client: &GameApiClient) {
+        self.last_refresh = Some(std::time::Instant::now());
+        match client.get_hosted_rooms_sync(&self.host_name) {
+            Ok(rooms) => {
+                self.rooms = rooms;
+                self.error_message = None;
+            },
+            // Keep showing the last rooms we had until the server answers again
+            Err(e) => self.error_message = Some(format!("Failed to load rooms: {}", e)),
+        }
+    }
+
+    /// Switch into a room where the host's plane is
+    fn enter(&mut self, client: &GameApiClient, session: GameSession) -> Option<DashboardAction> {
+        match client.get_room_state_sync(session.room_id, session.player_id) {
+            Ok(state) => Some(DashboardAction::Switch(
+                Scene::Airport(state.current_market.airport_id),
+                session,
+            )),
+            Err(e) => {
+                self.error_message = Some(format!("Failed to open room: {}", e));
+                None
+            },
+        }
+    }
+}
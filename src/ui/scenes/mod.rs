@@ -1,38 +1,57 @@
+use std::collections::HashMap;
+
+use crate::{
+    api::models::{
+        DestinationInfo, EventInfo, HangarResponse, KnownMarketInfo, LoanResponse,
+        PriceHistoryResponse, RouteProfitsResponse, VendorStallsResponse,
+    },
+    models::{CargoCategory, MessageTopic},
+    systems::{
+        TravelQuote, auto_refuel::AutoRefuel, loadouts::CargoLoadout, modifiers::GlobalModifier,
+        offers::OfferSide, profile::QuantityDefaults, travel::FlightRange,
+    },
+    ui::{
+        action_queue::ActionKind,
+        scenes::airport::{
+            components::CargoSort, drafts::SceneDraft, layout::PanelLayout, presets,
+        },
+    },
+};
+
 pub mod airport;
+pub mod host_dashboard;
+pub mod profile_manager;
+pub mod replay;
+pub mod room_info;
 pub mod room_lobby;
 pub mod server_connection;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum Scene {
+    #[default]
     ServerConnection,
     RoomLobby,
+    ProfileManager,
     Airport(String), // airport_id
 }
 
-impl Default for Scene {
-    fn default() -> Self {
-        Self::ServerConnection
-    }
-}
-
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum Location {
-    MainDesk,       // General info, fuel status, overview
-    MarketBoard,    // View current prices
-    TradingDesk,    // Buy/sell cargo
-    FlightPlanning, // Travel to other airports
-    FuelPump,       // Buy fuel
-    MessageBoard,   // Read and post messages
-                    // Future locations:
-                    // Hangar,       // Plane upgrades
-                    // WeatherStation, // Weather info
-                    // RadioRoom,    // Communications
-}
-
-impl Default for Location {
-    fn default() -> Self {
-        Self::MainDesk
-    }
+    #[default]
+    MainDesk, // General info, fuel status, overview
+    MarketBoard,     // View current prices
+    TradingDesk,     // Buy/sell cargo
+    FlightPlanning,  // Travel to other airports
+    FuelPump,        // Buy fuel
+    MessageBoard,    // Read and post messages
+    Statistics,      // Fuel economy and route report
+    InsuranceOffice, // Cargo cover and claims
+    Hangar,          // Plane upgrades
+    Bank,            // Loans
+    RouteMap,        // Profit per fuel heatmap
+                     // Future locations:
+                     // WeatherStation, // Weather info
+                     // RadioRoom,    // Communications
 }
 
 #[derive(Debug, Clone, Default)]
@@ -44,28 +63,157 @@ pub struct SceneState {
     pub selected_cargo: Option<String>,
     pub trade_quantity: u32,
 
+    // How the market board and trading desk list cargo
+    pub cargo_category_filter: Option<CargoCategory>,
+    pub cargo_sort: CargoSort,
+
+    // UI-only state for the order book at the trading desk
+    pub show_offers: bool,
+    pub offer_side: OfferSide,
+    pub offer_quantity: u32,
+    pub offer_price: u32,
+    pub offer_error: Option<String>, // Why the server turned an offer down
+
+    // Cargo load-outs saved on the pilot's profile, fetched at the trading desk
+    pub loadouts: Option<Vec<CargoLoadout>>,
+    pub loadout_name: String,
+    pub loadout_message: Option<String>,
+
     // UI-only state for travel
     pub selected_destination: Option<String>,
+    pub travel_preview: Option<Result<TravelQuote, String>>, // Server's breakdown for a destination
+    pub show_only_reachable: bool, // Hide destinations out of range on the fuel aboard
+
+    // Last prices the server says we've seen at each airport
+    pub known_markets: Vec<KnownMarketInfo>,
+
+    // Market events in the room, fuel outages included
+    pub active_events: Vec<EventInfo>,
+
+    // Room-wide price and fee modifiers, shown in a banner
+    pub active_modifiers: Vec<GlobalModifier>,
+
+    // The server's word on each destination: fuel, reach and landing slots
+    pub destinations: HashMap<String, DestinationInfo>,
+    // How far the aircraft can fly, from the server
+    pub flight_range: Option<FlightRange>,
 
     // UI state for fuel purchase
     pub fuel_quantity: u32,
+    pub fuel_preset_pending: bool, // Start the pump at the preset on the next visit
+    pub just_landed: bool,         // No visit to the pump since arriving
+    // The player's auto-refuel order, fetched when the pilot walks in
+    pub auto_refuel: Option<AutoRefuel>,
+    pub auto_refuel_message: Option<String>,
 
     // UI state for message board
     pub message_input: String,
     pub show_message_compose: bool,
+    pub message_error: Option<String>, // Why the server turned a post down
+    pub courier_destination: Option<String>, // Send the draft by courier rather than post it here
+    pub board_topic: Option<MessageTopic>, // Topic being read; None shows them all
+    pub post_topic: MessageTopic,      // Topic the draft goes up under
+
+    // UI state for the insurance office
+    pub insurance_error: Option<String>,
+
+    // Upgrades on offer at the hangar, fetched when the pilot walks in
+    pub hangar: Option<HangarResponse>,
+    pub hangar_message: Option<String>,
+
+    // Loans at the bank, fetched when the pilot walks in
+    pub bank: Option<LoanResponse>,
+    pub bank_message: Option<String>,
+    pub loan_amount: u32,
+
+    // Vendor stalls at the main desk, fetched when the pilot walks in
+    pub stalls: Option<VendorStallsResponse>,
+    pub stall_message: Option<String>,
+    // The current airport's price history, unless the room keeps it from
+    // us, and the turn it was last fetched on
+    pub price_history: Option<PriceHistoryResponse>,
+    pub price_history_turn: Option<u32>,
+
+    // Route profitability, fetched when the pilot walks into the route map
+    pub route_profits: Option<RouteProfitsResponse>,
+
+    // UI state for leaving the room
+    pub leave_obligations: Option<Vec<String>>,
+    pub left_room: bool,
+
+    // Actions taken this frame that still need to reach the server
+    pub outbox: Vec<ActionKind>,
+
+    // Panels docked around the current location, saved between sessions
+    pub panel_layout: PanelLayout,
+    pub panel_layout_changed: bool, // Not saved yet
+
+    // Quantities the pump and trading desk start at, saved between sessions
+    pub quantity_defaults: QuantityDefaults,
+    pub quantity_defaults_changed: bool, // Not saved yet
+
+    // Half-finished input as last written to disk, and when
+    pub saved_draft: Option<SceneDraft>,
+    pub draft_saved_at: Option<std::time::Instant>,
 }
 
 impl SceneState {
     pub fn new() -> Self {
+        let quantity_defaults = presets::load();
         Self {
             current_scene: Scene::ServerConnection, // Start at server connection
             current_location: Location::MainDesk,
             selected_cargo: None,
-            trade_quantity: 1,
+            trade_quantity: quantity_defaults.trade_quantity,
+            cargo_category_filter: None,
+            cargo_sort: CargoSort::default(),
+            show_offers: false,
+            offer_side: OfferSide::Sell,
+            offer_quantity: 1,
+            offer_price: 100,
+            offer_error: None,
+            loadouts: None,
+            loadout_name: String::new(),
+            loadout_message: None,
             selected_destination: None,
-            fuel_quantity: 10,
+            travel_preview: None,
+            show_only_reachable: false,
+            known_markets: Vec::new(),
+            active_events: Vec::new(),
+            active_modifiers: Vec::new(),
+            destinations: HashMap::new(),
+            flight_range: None,
+            fuel_quantity: quantity_defaults.fuel_quantity,
+            fuel_preset_pending: true,
+            just_landed: false,
             message_input: String::new(),
             show_message_compose: false,
+            message_error: None,
+            courier_destination: None,
+            board_topic: None,
+            post_topic: MessageTopic::General,
+            insurance_error: None,
+            auto_refuel: None,
+            auto_refuel_message: None,
+            hangar: None,
+            hangar_message: None,
+            bank: None,
+            bank_message: None,
+            loan_amount: 1000,
+            stalls: None,
+            stall_message: None,
+            price_history: None,
+            price_history_turn: None,
+            route_profits: None,
+            leave_obligations: None,
+            left_room: false,
+            outbox: Vec::new(),
+            panel_layout: PanelLayout::load(),
+            panel_layout_changed: false,
+            quantity_defaults,
+            quantity_defaults_changed: false,
+            saved_draft: None,
+            draft_saved_at: None,
         }
     }
 
@@ -74,8 +222,24 @@ impl SceneState {
         // Reset UI state when changing locations
         self.selected_cargo = None;
         self.selected_destination = None;
+        self.travel_preview = None;
         self.message_input.clear();
         self.show_message_compose = false;
+        self.message_error = None;
+        self.courier_destination = None;
+        self.insurance_error = None;
+        self.auto_refuel = None;
+        self.auto_refuel_message = None;
+        self.offer_error = None;
+        self.loadout_message = None;
+        self.hangar = None;
+        self.hangar_message = None;
+        self.bank = None;
+        self.bank_message = None;
+        self.stalls = None;
+        self.stall_message = None;
+        self.route_profits = None;
+        self.apply_quantity_defaults();
     }
 
     pub fn travel_to_airport(&mut self, airport_id: String) {
@@ -84,5 +248,19 @@ impl SceneState {
         // Reset UI state
         self.selected_cargo = None;
         self.selected_destination = None;
+        self.travel_preview = None;
+        self.stalls = None;
+        self.stall_message = None;
+        self.price_history = None;
+        self.price_history_turn = None;
+        self.just_landed = true;
+        self.apply_quantity_defaults();
+    }
+
+    /// Put the trade slider back at the player's default, and have the
+    /// fuel pump do the same once it knows how much room is in the tank
+    pub fn apply_quantity_defaults(&mut self) {
+        self.trade_quantity = self.quantity_defaults.trade_quantity;
+        self.fuel_preset_pending = true;
     }
 }
@@ -0,0 +1,183 @@
+use crate::{
+    config::Difficulty,
+    systems::{Profile, ProfileSettings, SaveSystem},
+};
+use eframe::egui;
+
+/// Manage single-player profiles. Games under a profile are played with
+/// `cargo run` in the terminal; this screen creates, inspects and removes them.
+pub struct ProfileManagerScene {
+    profiles: Option<Vec<Profile>>,
+    new_profile_name: String,
+    new_profile_difficulty: Difficulty,
+    new_profile_ironman: bool,
+    pending_delete: Option<String>,
+    error_message: Option<String>,
+}
+
+impl Default for ProfileManagerScene {
+    fn default() -> Self {
+        Self {
+            profiles: None,
+            new_profile_name: String::new(),
+            new_profile_difficulty: Difficulty::Normal,
+            new_profile_ironman: false,
+            pending_delete: None,
+            error_message: None,
+        }
+    }
+}
+
+impl ProfileManagerScene {
+    /// Returns true when the player wants to go back
+    pub fn render(&mut self, ctx: &egui::Context) -> bool {
+        let mut go_back = false;
+
+        if self.profiles.is_none() {
+            self.refresh();
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("👤 Single-player Profiles");
+            ui.label("Each profile keeps its own saves, difficulty and statistics.");
+            ui.label("Pick a profile when starting the terminal game (cargo run).");
+            ui.add_space(10.0);
+
+            if let Some(error) = &self.error_message {
+                ui.colored_label(egui::Color32::RED, error);
+                ui.add_space(5.0);
+            }
+
+            ui.group(|ui| {
+                ui.label("Create a profile:");
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut self.new_profile_name);
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Difficulty:");
+                    for difficulty in Difficulty::ALL {
+                        ui.radio_value(
+                            &mut self.new_profile_difficulty,
+                            difficulty,
+                            difficulty.label(),
+                        );
+                    }
+                });
+                ui.checkbox(
+                    &mut self.new_profile_ironman,
+                    "🛡️ Ironman (no manual saves, bankruptcy deletes the save)",
+                );
+                if ui.button("➕ Create").clicked() {
+                    self.create();
+                }
+            });
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            let profiles = self.profiles.clone().unwrap_or_default();
+            if profiles.is_empty() {
+                ui.label("No profiles yet.");
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for profile in &profiles {
+                        self.render_profile(ui, profile);
+                    }
+                });
+
+            ui.add_space(10.0);
+            if ui.button("⬅ Back").clicked() {
+                go_back = true;
+            }
+        });
+
+        if go_back {
+            // Re-read from disk next time in case the terminal game changed things
+            self.profiles = None;
+            self.pending_delete = None;
+            self.error_message = None;
+        }
+
+        go_back
+    }
+
+    fn render_profile(&mut self, ui: &mut egui::Ui, profile: &Profile) {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong(&profile.name);
+                ui.label(format!("({})", profile.settings.difficulty.label()));
+                if profile.settings.ironman {
+                    ui.colored_label(egui::Color32::GOLD, "🛡️ IRONMAN");
+                }
+            });
+            ui.label(format!(
+                "Games: {} started, {} won, {} lost · {} turns flown · best ${}",
+                profile.stats.games_started,
+                profile.stats.games_won,
+                profile.stats.games_lost,
+                profile.stats.turns_played,
+                profile.stats.best_money
+            ));
+            let last_played = profile
+                .last_played
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "never".to_string());
+            ui.label(format!("Last played: {}", last_played));
+
+            if self.pending_delete.as_deref() == Some(profile.name.as_str()) {
+                ui.horizontal(|ui| {
+                    ui.colored_label(egui::Color32::YELLOW, "Delete this profile and its saves?");
+                    if ui.button("Delete").clicked() {
+                        self.delete(&profile.name);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.pending_delete = None;
+                    }
+                });
+            } else if ui.button("🗑 Delete").clicked() {
+                self.pending_delete = Some(profile.name.clone());
+            }
+        });
+    }
+
+    fn refresh(&mut self) {
+        match SaveSystem::list_profiles() {
+            Ok(profiles) => self.profiles = Some(profiles),
+            Err(e) => {
+                self.profiles = Some(Vec::new());
+                self.error_message = Some(format!("Couldn't read profiles: {}", e));
+            },
+        }
+    }
+
+    fn create(&mut self) {
+        let settings = ProfileSettings {
+            difficulty: self.new_profile_difficulty,
+            ironman: self.new_profile_ironman,
+        };
+
+        match SaveSystem::create_profile(&self.new_profile_name, settings) {
+            Ok(_) => {
+                self.new_profile_name.clear();
+                self.error_message = None;
+                self.refresh();
+            },
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    fn delete(&mut self, name: &str) {
+        self.pending_delete = None;
+        match SaveSystem::delete_profile(name) {
+            Ok(()) => {
+                self.error_message = None;
+                self.refresh();
+            },
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use eframe::egui;
+
+use crate::{api::models::ReplayResponse, systems::TurnRecord};
+
+/// Post-game analysis: scrub through each pilot's turns and see their money,
+/// position and the market they were looking at.
+pub struct ReplayScene {
+    replay: ReplayResponse,
+    pilot: usize,
+    turn: usize,
+}
+
+impl ReplayScene {
+    pub fn new(replay: ReplayResponse) -> Self {
+        let mut scene = Self {
+            replay,
+            pilot: 0,
+            turn: 0,
+        };
+        scene.turn = scene.last_turn();
+        scene
+    }
+
+    /// Returns true when the player closes the replay
+    pub fn render(&mut self, ctx: &egui::Context) -> bool {
+        let mut open = true;
+
+        egui::Window::new("📼 Replay")
+            .open(&mut open)
+            .default_width(520.0)
+            .default_pos([40.0, 40.0])
+            .show(ctx, |ui| {
+                if self.replay.pilots.is_empty() {
+                    ui.label("No turns were recorded in this room.");
+                    return;
+                }
+                if !self.replay.finished {
+                    ui.label("Other pilots' turns are shown once the game is over.");
+                }
+
+                self.pilot_picker(ui);
+                ui.separator();
+
+                let turns = &self.replay.pilots[self.pilot].turns;
+                if turns.is_empty() {
+                    ui.label("No turns recorded for this pilot.");
+                    return;
+                }
+                self.turn = self.turn.min(turns.len() - 1);
+
+                if let Some(turn) = Self::money_chart(ui, turns, self.turn) {
+                    self.turn = turn;
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("◀").clicked() {
+                        self.turn = self.turn.saturating_sub(1);
+                    }
+                    ui.add(
+                        egui::Slider::new(&mut self.turn, 0..=turns.len() - 1).show_value(false),
+                    );
+                    if ui.button("▶").clicked() {
+                        self.turn = (self.turn + 1).min(turns.len() - 1);
+                    }
+                });
+                ui.separator();
+
+                let previous = self.turn.checked_sub(1).map(|index| &turns[index]);
+                Self::turn_details(ui, &turns[self.turn], previous);
+            });
+
+        !open
+    }
+
+    fn last_turn(&self) -> usize {
+        self.replay
+            .pilots
+            .get(self.pilot)
+            .map(|pilot| pilot.turns.len().saturating_sub(1))
+            .unwrap_or(0)
+    }
+
+    fn pilot_picker(&mut self, ui: &mut egui::Ui) {
+        let mut pilot = self.pilot;
+        ui.horizontal(|ui| {
+            ui.label("Pilot:");
+            egui::ComboBox::from_id_salt("replay_pilot")
+                .selected_text(&self.replay.pilots[pilot].player_name)
+                .show_ui(ui, |ui| {
+                    for (index, replay) in self.replay.pilots.iter().enumerate() {
+                        ui.selectable_value(&mut pilot, index, &replay.player_name);
+                    }
+                });
+        });
+
+        if pilot != self.pilot {
+            self.pilot = pilot;
+            self.turn = self.last_turn();
+        }
+    }
+
+    /// Money over the game with the selected turn marked. Clicking or
+    /// dragging on the chart picks a turn, which is returned.
+    fn money_chart(ui: &mut egui::Ui, turns: &[TurnRecord], selected: usize) -> Option<usize> {
+        let size = egui::vec2(ui.available_width(), 90.0);
+        let (response, painter) = ui.allocate_painter(size, egui::Sense::click_and_drag());
+        let rect = response.rect;
+        painter.rect_filled(rect, 4.0, egui::Color32::from_gray(245));
+
+        let max_money = turns
+            .iter()
+            .map(|turn| turn.money)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let last = turns.len().saturating_sub(1).max(1) as f32;
+        let point = |index: usize, money: u32| {
+            egui::pos2(
+                rect.left() + rect.width() * index as f32 / last,
+                rect.bottom() - rect.height() * money as f32 / max_money as f32,
+            )
+        };
+
+        let line: Vec<egui::Pos2> = turns
+            .iter()
+            .enumerate()
+            .map(|(index, turn)| point(index, turn.money))
+            .collect();
+        painter.add(egui::Shape::line(
+            line,
+            egui::Stroke::new(2.0, egui::Color32::from_rgb(50, 150, 50)),
+        ));
+
+        let marker = point(selected, turns[selected].money);
+        painter.vline(
+            marker.x,
+            rect.y_range(),
+            egui::Stroke::new(1.0, egui::Color32::from_rgb(100, 150, 255)),
+        );
+        painter.circle_filled(marker, 4.0, egui::Color32::from_rgb(100, 150, 255));
+
+        let pointer = response.interact_pointer_pos()?;
+        let fraction = ((pointer.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+        Some(((fraction * last).round() as usize).min(turns.len() - 1))
+    }
+
+    fn turn_details(ui: &mut egui::Ui, record: &TurnRecord, previous: Option<&TurnRecord>) {
+        egui::Grid::new("replay_turn")
+            .num_columns(2)
+            .spacing([20.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Turn:");
+                ui.strong(record.turn_number.to_string());
+                ui.end_row();
+
+                ui.label("Money:");
+                ui.horizontal(|ui| {
+                    ui.strong(format!("${}", record.money));
+                    if let Some(previous) = previous {
+                        let change = record.money as i64 - previous.money as i64;
+                        if change > 0 {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(50, 150, 50),
+                                format!("+${}", change),
+                            );
+                        } else if change < 0 {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(220, 50, 50),
+                                format!("-${}", -change),
+                            );
+                        }
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Location:");
+                ui.label(format!("📍 {}", record.airport_id));
+                ui.end_row();
+
+                ui.label("Fuel:");
+                ui.label(format!("⛽ {}", record.fuel));
+                ui.end_row();
+
+                ui.label("Cargo:");
+                if record.cargo.is_empty() {
+                    ui.label("None");
+                } else {
+                    let mut cargo: Vec<_> = record.cargo.iter().collect();
+                    cargo.sort();
+                    let cargo: Vec<String> = cargo
+                        .into_iter()
+                        .map(|(cargo_id, quantity)| format!("{} x{}", cargo_id, quantity))
+                        .collect();
+                    ui.label(cargo.join(", "));
+                }
+                ui.end_row();
+            });
+
+        ui.add_space(6.0);
+        ui.label(format!(
+            "📊 Market at {} (fuel ${})",
+            record.airport_id, record.market.fuel_price
+        ));
+        let mut prices: Vec<_> = record.market.cargo_prices.iter().collect();
+        prices.sort();
+        egui::Grid::new("replay_market")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (cargo_id, price) in prices {
+                    ui.label(cargo_id);
+                    ui.label(format!("${}", price));
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -0,0 +1,154 @@
+use eframe::egui;
+
+use crate::{api::models::RoomSettingsResponse, systems::multiplayer::SpawnRule};
+
+/// Everything a room plays with, fetched from the server. Shown from the
+/// lobby before joining and once on entering a room.
+pub struct RoomInfoDialog {
+    info: RoomSettingsResponse,
+}
+
+impl RoomInfoDialog {
+    pub fn new(info: RoomSettingsResponse) -> Self {
+        Self { info }
+    }
+
+    /// Returns true when the player closes the dialog
+    pub fn render(&self, ctx: &egui::Context) -> bool {
+        let info = &self.info;
+        let settings = &info.settings;
+        let mut open = true;
+        let mut dismissed = false;
+
+        egui::Window::new(format!("ℹ️ {}", info.room_name))
+            .id(egui::Id::new(("room_info", info.room_id)))
+            .open(&mut open)
+            .default_width(380.0)
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                egui::Grid::new("room_info_rules")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        let mut row = |label: &str, value: String| {
+                            ui.label(label);
+                            ui.label(value);
+                            ui.end_row();
+                        };
+                        row("Status", format!("{:?}", info.game_status));
+                        row("Players", format!("up to {}", info.max_players));
+                        row("Starting money", format!("${}", settings.starting_money));
+                        row(
+                            "Starting fuel",
+                            match settings.starting_fuel {
+                                Some(fuel) => format!("{} units", fuel),
+                                None => "two thirds of a tank".to_string(),
+                            },
+                        );
+                        row("Price swings", format!("x{}", settings.price_volatility));
+                        row(
+                            "Sell spread",
+                            format!("{}% under list", settings.trade_guards.sell_spread_percent),
+                        );
+                        row(
+                            "Resale cooldown",
+                            match settings.trade_guards.resale_cooldown_turns {
+                                0 => "none".to_string(),
+                                turns => format!("{} turn(s) before selling back", turns),
+                            },
+                        );
+                        row("Win at", format!("${}", settings.win_condition_money));
+                        row("Start at", spawn_label(&settings.spawn));
+                        row(
+                            "Fog of war",
+                            if settings.fog_of_war {
+                                format!(
+                                    "on, prices stale after {} turns",
+                                    settings.fog_stale_after_turns
+                                )
+                            } else {
+                                "off".to_string()
+                            },
+                        );
+                        row("Game speed", settings.game_speed.label().to_string());
+                        row(
+                            "Calendar",
+                            format!(
+                                "{} hours a turn from {}",
+                                settings.calendar.hours_per_turn,
+                                settings.calendar.date_label(1)
+                            ),
+                        );
+                        row("Landing slots", on_off(settings.landing_slots));
+                        row(
+                            "Turn deadline",
+                            match info.turn_deadline_hours {
+                                Some(hours) => format!("{} hours", hours),
+                                None => "none".to_string(),
+                            },
+                        );
+                        row(
+                            "Action cooldown",
+                            if settings.action_cooldown_ms == 0 {
+                                "none".to_string()
+                            } else {
+                                format!("{} ms", settings.action_cooldown_ms)
+                            },
+                        );
+                        row("Local chatter", on_off(settings.npc_chatter));
+                    });
+
+                ui.separator();
+                ui.strong("Events");
+                let rules = &info.event_rules;
+                ui.label(rules.summary());
+                if !rules.enabled_market_events.is_empty() {
+                    ui.weak(format!(
+                        "Can happen: {}",
+                        rules.enabled_market_events.join(", ")
+                    ));
+                }
+
+                if !settings.scenario_modifiers.is_empty() {
+                    ui.separator();
+                    ui.strong("Every round starts with");
+                    for spec in &settings.scenario_modifiers {
+                        ui.label(format!(
+                            "{}: {} {:+}% for {} turns",
+                            spec.name,
+                            spec.target.label(),
+                            spec.percent,
+                            spec.turns
+                        ));
+                    }
+                }
+
+                ui.separator();
+                ui.strong(format!("Airports ({})", info.airports.len()));
+                ui.label(info.airports.join(", "));
+                ui.strong(format!("Cargo ({})", info.cargo_types.len()));
+                ui.label(info.cargo_types.join(", "));
+
+                ui.add_space(6.0);
+                if ui.button("OK").clicked() {
+                    dismissed = true;
+                }
+            });
+
+        !open || dismissed
+    }
+}
+
+fn on_off(on: bool) -> String {
+    if on { "on" } else { "off" }.to_string()
+}
+
+fn spawn_label(spawn: &SpawnRule) -> String {
+    match spawn {
+        SpawnRule::DefaultAirport => "the default airport".to_string(),
+        SpawnRule::Random => "a random airport".to_string(),
+        SpawnRule::SpreadOut => "away from other pilots".to_string(),
+        SpawnRule::HostChosen(airport_id) => airport_id.clone(),
+    }
+}
@@ -1,13 +1,22 @@
 use crate::{
-    api::models::{PlayerSessionInfo, RoomInfo},
+    api::models::{
+        AirportInfo, PilotProfileResponse, PlayerSessionInfo, RankingsResponse, RoomInfo,
+    },
+    systems::{
+        RoomTemplate,
+        matchmaking::{GameMode, MatchPreferences},
+    },
     ui::{
         game_api_client::{ApiError, GameApiClient},
-        scenes::Scene,
+        scenes::{Scene, room_info::RoomInfoDialog},
     },
 };
 use eframe::egui;
 use uuid::Uuid;
 
+/// Where a freshly created or joined game opens, and the session to play it with
+type Entered = (Scene, GameSession);
+
 #[derive(Debug, Clone)]
 pub enum LobbyState {
     Loading,
@@ -17,30 +26,70 @@ pub enum LobbyState {
     Error(String),
 }
 
+/// The lobby's tabs: rooms to play in, or how pilots stand this season
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LobbyTab {
+    Rooms,
+    Rankings,
+}
+
 pub struct RoomLobbyScene {
     pub lobby_state: LobbyState,
+    pub tab: LobbyTab,
     pub available_rooms: Vec<RoomInfo>,
     pub player_name: String,
     pub previous_player_name: String,
     pub existing_sessions: Vec<PlayerSessionInfo>,
+    pub pilot_profile: Option<PilotProfileResponse>, // For the name typed in
     pub create_room_name: String,
     pub create_room_max_players: usize,
+    pub templates: Vec<RoomTemplate>,
+    pub selected_template: Option<Uuid>,
+    pub quick_match_mode: Option<GameMode>,
+    pub quick_match_max_players: Option<usize>,
+    /// Airports a joining player can ask to start at
+    pub airports: Vec<AirportInfo>,
+    /// Where to ask to start when joining; None leaves it to the room
+    pub starting_airport: Option<String>,
+    pub rankings: Option<RankingsResponse>,
+    pub rankings_season: Option<String>,
+    pub rankings_page: usize,
     pub error_message: Option<String>,
+    /// A room's full ruleset, opened from its Info button
+    pub room_info: Option<RoomInfoDialog>,
     pub last_refresh: std::time::Instant,
+    /// How often the room list refreshes by itself
+    pub refresh_every: std::time::Duration,
+    /// Set when the player asks for the dashboard of rooms they host
+    pub open_host_dashboard: bool,
 }
 
 impl Default for RoomLobbyScene {
     fn default() -> Self {
         Self {
             lobby_state: LobbyState::Loading,
+            tab: LobbyTab::Rooms,
             available_rooms: Vec::new(),
             player_name: "Player".to_string(),
             previous_player_name: String::new(),
             existing_sessions: Vec::new(),
+            pilot_profile: None,
             create_room_name: "My Game Room".to_string(),
             create_room_max_players: 4,
+            templates: Vec::new(),
+            selected_template: None,
+            quick_match_mode: None,
+            quick_match_max_players: None,
+            airports: Vec::new(),
+            starting_airport: None,
+            rankings: None,
+            rankings_season: None,
+            rankings_page: 1,
             error_message: None,
+            room_info: None,
             last_refresh: std::time::Instant::now(),
+            refresh_every: std::time::Duration::from_secs(5),
+            open_host_dashboard: false,
         }
     }
 }
@@ -60,16 +109,33 @@ impl RoomLobbyScene {
     ) -> Option<(Scene, GameSession)> {
         let mut transition = None;
 
-        // Auto-refresh rooms every 5 seconds
-        if self.last_refresh.elapsed().as_secs() >= 5 {
+        // Auto-refresh rooms, coming back for the next one without input
+        if self.last_refresh.elapsed() >= self.refresh_every {
             self.refresh_rooms(client);
         }
+        ctx.request_repaint_after(
+            self.refresh_every
+                .saturating_sub(self.last_refresh.elapsed()),
+        );
 
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("🏢 Game Rooms");
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.tab, LobbyTab::Rooms, "🛫 Rooms");
+                let rankings_tab =
+                    ui.selectable_value(&mut self.tab, LobbyTab::Rankings, "🏆 Rankings");
+                if rankings_tab.clicked() {
+                    self.load_rankings(client);
+                }
+            });
             ui.separator();
             ui.add_space(10.0);
 
+            if self.tab == LobbyTab::Rankings {
+                self.render_rankings(ui, client);
+                return;
+            }
+
             // Player name input
             ui.horizontal(|ui| {
                 ui.label("Your name:");
@@ -83,11 +149,23 @@ impl RoomLobbyScene {
                     } else {
                         self.existing_sessions.clear();
                     }
+                    self.load_pilot_profile(client);
                 }
 
                 if ui.button("🔄 Refresh Rooms").clicked() {
                     self.refresh_rooms(client);
                 }
+                if ui
+                    .button("🖥 My Hosted Rooms")
+                    .on_hover_text("Keep an eye on every room you host")
+                    .clicked()
+                {
+                    if self.player_name.trim().is_empty() {
+                        self.error_message = Some("Please enter your name".to_string());
+                    } else {
+                        self.open_host_dashboard = true;
+                    }
+                }
             });
 
             // Show existing sessions if any
@@ -99,6 +177,7 @@ impl RoomLobbyScene {
                     for session in &self.existing_sessions {
                         ui.horizontal(|ui| {
                             ui.label(format!("📍 {}", session.room_name));
+                            ui.weak(format!("last played {}", idle_time(session.idle_seconds)));
                             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                                 if ui.small_button("Resume").clicked() {
                                     // Note: This would need proper async handling in a real app
@@ -111,6 +190,82 @@ impl RoomLobbyScene {
                 });
             }
 
+            // The pilot's logbook, earned across every room they've played
+            if let Some(profile) = &self.pilot_profile {
+                ui.add_space(5.0);
+                ui.group(|ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.strong(format!(
+                            "🏅 Logbook ({}/{}):",
+                            profile.unlocked,
+                            profile.achievements.len()
+                        ));
+                        for info in &profile.achievements {
+                            let badge = format!("{} {}", info.achievement.icon(), info.title);
+                            match &info.room_name {
+                                Some(room_name) => {
+                                    ui.label(badge).on_hover_text(format!(
+                                        "{} - earned in {}",
+                                        info.description, room_name
+                                    ));
+                                },
+                                None => {
+                                    ui.weak(badge).on_hover_text(format!(
+                                        "{} - not earned yet",
+                                        info.description
+                                    ));
+                                },
+                            }
+                        }
+                    });
+                });
+            }
+
+            ui.add_space(10.0);
+
+            // Quick match: let the server pick (or open) a room
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Mode:");
+                    egui::ComboBox::from_id_salt("quick_match_mode")
+                        .selected_text(self.quick_match_mode.map_or("Any", |mode| mode.label()))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.quick_match_mode, None, "Any");
+                            for mode in GameMode::ALL {
+                                ui.selectable_value(&mut self.quick_match_mode, Some(mode), mode.label());
+                            }
+                        });
+
+                    ui.label("Players:");
+                    egui::ComboBox::from_id_salt("quick_match_players")
+                        .selected_text(
+                            self.quick_match_max_players
+                                .map_or("Any".to_string(), |max_players| max_players.to_string()),
+                        )
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.quick_match_max_players, None, "Any");
+                            for max_players in 1..=8 {
+                                ui.selectable_value(
+                                    &mut self.quick_match_max_players,
+                                    Some(max_players),
+                                    max_players.to_string(),
+                                );
+                            }
+                        });
+
+                    if ui.button("⚡ Quick Match").clicked() {
+                        if self.player_name.trim().is_empty() {
+                            self.error_message = Some("Please enter your name".to_string());
+                        } else {
+                            match self.quick_match_sync(client) {
+                                Ok(joined) => transition = Some(joined),
+                                Err(e) => self.error_message = Some(format!("Quick match failed: {}", e)),
+                            }
+                        }
+                    }
+                });
+            });
+
             ui.add_space(10.0);
 
             // Create room section
@@ -121,17 +276,41 @@ impl RoomLobbyScene {
                 });
 
                 ui.horizontal(|ui| {
-                    ui.label("Max Players:");
-                    ui.add(egui::Slider::new(&mut self.create_room_max_players, 1..=8));
+                    ui.label("Template:");
+                    let selected_name = self
+                        .selected_template
+                        .and_then(|id| self.templates.iter().find(|t| t.id == id))
+                        .map(|t| t.name.clone())
+                        .unwrap_or_else(|| "None (default rules)".to_string());
+                    egui::ComboBox::from_id_salt("room_template")
+                        .selected_text(selected_name)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.selected_template, None, "None (default rules)");
+                            for template in &self.templates {
+                                ui.selectable_value(
+                                    &mut self.selected_template,
+                                    Some(template.id),
+                                    format!("{} ({} players)", template.name, template.max_players),
+                                );
+                            }
+                        });
                 });
 
+                // Templates carry their own player limit
+                if self.selected_template.is_none() {
+                    ui.horizontal(|ui| {
+                        ui.label("Max Players:");
+                        ui.add(egui::Slider::new(&mut self.create_room_max_players, 1..=8));
+                    });
+                }
+
                 if ui.button("Create Room").clicked() {
                     if !self.player_name.trim().is_empty() && !self.create_room_name.trim().is_empty() {
                         self.lobby_state = LobbyState::CreatingRoom;
                         // In a real async app, you'd spawn a task here
                         // For now, we'll simulate immediate response
                         match self.create_room_sync(client) {
-                            Ok(session) => transition = Some((Scene::Airport("JFK".to_string()), session)),
+                            Ok(joined) => transition = Some(joined),
                             Err(e) => {
                                 self.error_message = Some(format!("Failed to create room: {:?}", e));
                                 self.lobby_state = LobbyState::Error(format!("{:?}", e));
@@ -148,7 +327,12 @@ impl RoomLobbyScene {
             ui.add_space(10.0);
 
             // Room list
-            ui.label("Available Rooms:");
+            ui.horizontal(|ui| {
+                ui.label("Available Rooms:");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    self.render_starting_airport(ui);
+                });
+            });
             ui.add_space(5.0);
 
             match &self.lobby_state {
@@ -170,12 +354,15 @@ impl RoomLobbyScene {
                                         ui.vertical(|ui| {
                                             ui.horizontal(|ui| {
                                                 ui.heading(&room.name);
+                                                if room.is_demo {
+                                                    ui.label("Demo with bot traders - try the game here");
+                                                }
                                                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                                                     if room.is_joinable && ui.button("Join").clicked() {
                                                         if !self.player_name.trim().is_empty() {
                                                             self.lobby_state = LobbyState::JoiningRoom(room.id);
                                                             match self.join_room_sync(client, room.id) {
-                                                                Ok(session) => transition = Some((Scene::Airport("JFK".to_string()), session)),
+                                                                Ok(joined) => transition = Some(joined),
                                                                 Err(e) => {
                                                                     let error_msg = format!("Failed to join room: {:?}", e);
                                                                     eprintln!("Room join error: {}", error_msg);
@@ -190,6 +377,9 @@ impl RoomLobbyScene {
                                                     if !room.is_joinable {
                                                         ui.label("🚫 Full/In Progress");
                                                     }
+                                                    if ui.button("ℹ️ Info").clicked() {
+                                                        self.open_room_info(client, room.id);
+                                                    }
                                                 });
                                             });
 
@@ -198,6 +388,7 @@ impl RoomLobbyScene {
                                                 ui.label(format!("Players: {}/{}", room.current_players, room.max_players));
                                                 ui.label(format!("Status: {:?}", room.game_status));
                                             });
+                                            ui.weak(room.event_rules.summary());
                                         });
                                     });
                                 });
@@ -237,13 +428,64 @@ impl RoomLobbyScene {
             }
         });
 
+        if let Some(dialog) = &self.room_info
+            && dialog.render(ctx)
+        {
+            self.room_info = None;
+        }
+
         transition
     }
 
+    /// Where to start in the next room joined. The server has the final
+    /// say: it refuses airports the room doesn't have, and a pilot rejoining
+    /// picks up where they left off.
+    fn render_starting_airport(&mut self, ui: &mut egui::Ui) {
+        let selected = self
+            .starting_airport
+            .as_ref()
+            .map_or("Room decides".to_string(), |airport_id| {
+                airport_label(&self.airports, airport_id)
+            });
+        egui::ComboBox::from_id_salt("starting_airport")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.starting_airport, None, "Room decides");
+                for airport in &self.airports {
+                    ui.selectable_value(
+                        &mut self.starting_airport,
+                        Some(airport.id.clone()),
+                        format!("{} ({})", airport.name, airport.id),
+                    );
+                }
+            })
+            .response
+            .on_hover_text("Rejoining a room puts you back where you left off");
+        ui.label("Start at:");
+    }
+
+    fn open_room_info(&mut self, client: &GameApiClient, room_id: Uuid) {
+        match client.get_room_settings_sync(room_id) {
+            Ok(info) => self.room_info = Some(RoomInfoDialog::new(info)),
+            Err(e) => self.error_message = Some(format!("Failed to load room info: {}", e)),
+        }
+    }
+
     fn refresh_rooms(&mut self, client: &GameApiClient) {
         self.lobby_state = LobbyState::Loading;
         self.last_refresh = std::time::Instant::now();
 
+        // Templates are optional - keep whatever we had if the fetch fails
+        if let Ok(templates) = client.list_templates_sync() {
+            self.templates = templates;
+        }
+        // Pick up anything earned since we last looked
+        self.load_pilot_profile(client);
+        // Cached by the client, so this only goes to the server now and then
+        if let Ok(airports) = client.get_airports_sync() {
+            self.airports = airports;
+        }
+
         match client.list_rooms_sync() {
             Ok(rooms) => {
                 self.available_rooms = rooms;
@@ -256,25 +498,48 @@ impl RoomLobbyScene {
         }
     }
 
-    fn create_room_sync(&mut self, client: &GameApiClient) -> Result<GameSession, ApiError> {
+    fn create_room_sync(&mut self, client: &GameApiClient) -> Result<Entered, ApiError> {
         let response = client.create_room_sync(
             self.create_room_name.clone(),
             self.player_name.clone(),
             Some(self.create_room_max_players), // Use configured max players
+            self.selected_template,
         )?;
 
-        Ok(GameSession {
-            room_id: response.room_id,
-            player_id: response.host_player_id,
-            player_name: response.host_player_name,
-        })
+        Ok((
+            Scene::Airport(starting_airport(response.starting_airport)),
+            GameSession {
+                room_id: response.room_id,
+                player_id: response.host_player_id,
+                player_name: response.host_player_name,
+            },
+        ))
+    }
+
+    fn quick_match_sync(&mut self, client: &GameApiClient) -> Result<Entered, ApiError> {
+        let response = client.quick_match_sync(
+            self.player_name.clone(),
+            MatchPreferences {
+                max_players: self.quick_match_max_players,
+                mode: self.quick_match_mode,
+            },
+        )?;
+
+        Ok((
+            Scene::Airport(starting_airport(response.starting_airport)),
+            GameSession {
+                room_id: response.room_id,
+                player_id: response.player_id,
+                player_name: response.player_name,
+            },
+        ))
     }
 
     fn join_room_sync(
         &mut self,
         client: &GameApiClient,
         room_id: Uuid,
-    ) -> Result<GameSession, ApiError> {
+    ) -> Result<Entered, ApiError> {
         eprintln!(
             "Attempting to join room {} as player '{}'",
             room_id, self.player_name
@@ -284,7 +549,7 @@ impl RoomLobbyScene {
             .join_room_sync(
                 room_id,
                 self.player_name.clone(),
-                Some("JFK".to_string()), // Default starting airport
+                self.starting_airport.clone(),
             )
             .map_err(|e| {
                 eprintln!("Join room failed with error: {:?}", e);
@@ -292,11 +557,115 @@ impl RoomLobbyScene {
             })?;
 
         eprintln!("Successfully joined room: {:?}", response);
-        Ok(GameSession {
-            room_id: response.room_id,
-            player_id: response.player_id,
-            player_name: response.player_name,
-        })
+        Ok((
+            Scene::Airport(starting_airport(response.starting_airport)),
+            GameSession {
+                room_id: response.room_id,
+                player_id: response.player_id,
+                player_name: response.player_name,
+            },
+        ))
+    }
+
+    /// The season's table, a page at a time, with the typed-in pilot picked out
+    fn render_rankings(&mut self, ui: &mut egui::Ui, client: &GameApiClient) {
+        let Some(rankings) = self.rankings.clone() else {
+            ui.label("Rankings are unavailable right now.");
+            if ui.button("🔄 Retry").clicked() {
+                self.load_rankings(client);
+            }
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Season:");
+            let mut season = rankings.season.clone();
+            egui::ComboBox::from_id_salt("rankings_season")
+                .selected_text(&season)
+                .show_ui(ui, |ui| {
+                    for listed in &rankings.seasons {
+                        ui.selectable_value(&mut season, listed.clone(), listed);
+                    }
+                });
+            if season != rankings.season {
+                self.rankings_season = Some(season);
+                self.rankings_page = 1;
+                self.load_rankings(client);
+            }
+
+            if ui.button("🔄 Refresh").clicked() {
+                self.load_rankings(client);
+            }
+        });
+        ui.add_space(5.0);
+
+        if rankings.entries.is_empty() {
+            ui.label("No games have finished this season yet.");
+            return;
+        }
+
+        egui::Grid::new("rankings_table")
+            .striped(true)
+            .num_columns(6)
+            .show(ui, |ui| {
+                ui.strong("#");
+                ui.strong("Pilot");
+                ui.strong("Points");
+                ui.strong("Games");
+                ui.strong("Wins");
+                ui.strong("Profit");
+                ui.end_row();
+
+                for entry in &rankings.entries {
+                    ui.label(entry.rank.to_string());
+                    if entry.pilot_name == self.player_name.trim() {
+                        ui.strong(format!("✈ {}", entry.pilot_name));
+                    } else {
+                        ui.label(&entry.pilot_name);
+                    }
+                    ui.label(entry.points.to_string());
+                    ui.label(entry.games_played.to_string());
+                    ui.label(entry.wins.to_string());
+                    ui.label(format!("${}", entry.total_profit));
+                    ui.end_row();
+                }
+            });
+
+        let pages = rankings.total.div_ceil(rankings.per_page).max(1);
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(rankings.page > 1, egui::Button::new("◀ Previous"))
+                .clicked()
+            {
+                self.rankings_page = rankings.page - 1;
+                self.load_rankings(client);
+            }
+            ui.label(format!("Page {} of {}", rankings.page, pages));
+            if ui
+                .add_enabled(rankings.page < pages, egui::Button::new("Next ▶"))
+                .clicked()
+            {
+                self.rankings_page = rankings.page + 1;
+                self.load_rankings(client);
+            }
+        });
+    }
+
+    fn load_rankings(&mut self, client: &GameApiClient) {
+        self.rankings = client
+            .get_rankings_sync(self.rankings_season.as_deref(), self.rankings_page)
+            .ok();
+    }
+
+    /// The logbook showcase follows whatever name is typed in
+    fn load_pilot_profile(&mut self, client: &GameApiClient) {
+        let pilot_name = self.player_name.trim();
+        self.pilot_profile = if pilot_name.is_empty() {
+            None
+        } else {
+            client.get_pilot_profile_sync(pilot_name).ok()
+        };
     }
 
     fn check_existing_sessions(&mut self, _client: &GameApiClient) {
@@ -311,3 +680,31 @@ impl RoomLobbyScene {
         // This would make an HTTP GET request to /players/{player_name}/sessions
     }
 }
+
+/// The airport the server put the player at. Older servers don't say, and
+/// always used JFK.
+pub fn starting_airport(airport_id: String) -> String {
+    if airport_id.is_empty() {
+        "JFK".to_string()
+    } else {
+        airport_id
+    }
+}
+
+fn airport_label(airports: &[AirportInfo], airport_id: &str) -> String {
+    airports
+        .iter()
+        .find(|airport| airport.id == airport_id)
+        .map_or(airport_id.to_string(), |airport| {
+            format!("{} ({})", airport.name, airport.id)
+        })
+}
+
+pub fn idle_time(idle_seconds: i64) -> String {
+    match idle_seconds {
+        s if s < 60 => "just now".to_string(),
+        s if s < 3600 => format!("{} min ago", s / 60),
+        s if s < 86400 => format!("{} h ago", s / 3600),
+        s => format!("{} days ago", s / 86400),
+    }
+}
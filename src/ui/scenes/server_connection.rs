@@ -1,21 +1,16 @@
 use crate::ui::{game_api_client::GameApiClient, scenes::Scene};
 use eframe::egui;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 #[allow(dead_code)]
 pub enum ConnectionState {
+    #[default]
     Disconnected,
     Connecting,
     Connected,
     Error(String),
 }
 
-impl Default for ConnectionState {
-    fn default() -> Self {
-        Self::Disconnected
-    }
-}
-
 pub struct ServerConnectionScene {
     pub server_address: String,
     pub connection_state: ConnectionState,
@@ -99,6 +94,12 @@ impl ServerConnectionScene {
                     ui.colored_label(egui::Color32::RED, error);
                 }
 
+                ui.add_space(20.0);
+                if ui.button("👤 Single-player Profiles").clicked() {
+                    let client = GameApiClient::new(self.server_address.clone());
+                    transition = Some((Scene::ProfileManager, client));
+                }
+
                 ui.add_space(30.0);
 
                 // Instructions
@@ -110,6 +111,7 @@ impl ServerConnectionScene {
                     "• For ngrok: Use the HTTPS URL from ngrok (e.g., https://your-name.ngrok.app)",
                 );
                 ui.label("• Supports both HTTP and HTTPS connections");
+                ui.label("• For a server built with --features tls: use its https:// address");
                 ui.label("• Multiple clients can connect to the same server");
                 ui.add_space(10.0);
                 ui.separator();
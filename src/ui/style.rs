@@ -0,0 +1,239 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub use nu_ansi_term::{Color, Style};
+
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Turn colored output on unless NO_COLOR is set, `--no-color` was passed or
+/// stdout isn't a terminal
+pub fn init(args: &[String]) {
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let no_color_flag = args.iter().any(|arg| arg == "--no-color");
+    set_color(!no_color_env && !no_color_flag && std::io::stdout().is_terminal());
+}
+
+pub fn set_color(enabled: bool) {
+    COLOR.store(enabled, Ordering::Relaxed);
+}
+
+pub fn color_enabled() -> bool {
+    COLOR.load(Ordering::Relaxed)
+}
+
+/// `text` in `style`, or as-is when color is off
+pub fn paint(style: Style, text: impl AsRef<str>) -> String {
+    if color_enabled() {
+        style.paint(text.as_ref()).to_string()
+    } else {
+        text.as_ref().to_string()
+    }
+}
+
+pub fn heading(title: &str) -> String {
+    paint(Color::Cyan.bold(), format!("=== {} ===", title))
+}
+
+pub fn good(text: impl AsRef<str>) -> String {
+    paint(Color::Green.normal(), text)
+}
+
+pub fn bad(text: impl AsRef<str>) -> String {
+    paint(Color::Red.normal(), text)
+}
+
+pub fn warning(text: impl AsRef<str>) -> String {
+    paint(Color::Yellow.normal(), text)
+}
+
+pub fn muted() -> Style {
+    Style::new().dimmed()
+}
+
+pub fn dim(text: impl AsRef<str>) -> String {
+    paint(muted(), text)
+}
+
+pub fn money(amount: u32) -> String {
+    paint(Color::Green.bold(), format!("${}", amount))
+}
+
+/// For buying: green below the usual price, red above it
+pub fn price_style(price: u32, usual_price: u32) -> Style {
+    match price.cmp(&usual_price) {
+        std::cmp::Ordering::Less => Color::Green.normal(),
+        std::cmp::Ordering::Greater => Color::Red.normal(),
+        std::cmp::Ordering::Equal => Style::new(),
+    }
+}
+
+/// For selling: green above the usual price, red below it
+pub fn sale_price_style(price: u32, usual_price: u32) -> Style {
+    price_style(usual_price, price)
+}
+
+/// Bars for the fuel and cargo gauges
+pub const BAR_WIDTH: usize = 20;
+
+/// A gauge like `[#######-------------]  35%`. With `fuller_is_better` the bar
+/// goes red as it empties (fuel); otherwise as it fills up (cargo).
+pub fn bar(current: u32, max: u32, fuller_is_better: bool) -> String {
+    let fraction = if max == 0 {
+        0.0
+    } else {
+        (current as f64 / max as f64).clamp(0.0, 1.0)
+    };
+    let filled = (fraction * BAR_WIDTH as f64).round() as usize;
+    let percent = (fraction * 100.0).round() as u32;
+
+    let level = if fuller_is_better {
+        fraction
+    } else {
+        1.0 - fraction
+    };
+    let color = if level > 0.5 {
+        Color::Green
+    } else if level > 0.25 {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+
+    format!(
+        "[{}{}] {:>3}%",
+        paint(color.normal(), "#".repeat(filled)),
+        dim("-".repeat(BAR_WIDTH - filled)),
+        percent
+    )
+}
+
+/// A table cell: the text, and how to paint it once it's padded
+pub struct Cell {
+    text: String,
+    style: Style,
+    right_aligned: bool,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            style: Style::new(),
+            right_aligned: false,
+        }
+    }
+
+    /// Numbers line up on the right
+    pub fn number(text: impl Into<String>) -> Self {
+        Self {
+            right_aligned: true,
+            ..Self::new(text)
+        }
+    }
+
+    pub fn styled(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Rows printed in aligned columns. Widths are measured before any color is
+/// added, so escape codes don't throw the columns out.
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<Cell>) {
+        self.rows.push(cells);
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .headers
+            .iter()
+            .map(|header| header.chars().count())
+            .collect();
+        for row in &self.rows {
+            for (column, cell) in row.iter().enumerate() {
+                let width = cell.text.chars().count();
+                match widths.get_mut(column) {
+                    Some(current) => *current = (*current).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+        widths
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        let widths = self.widths();
+        let mut lines = Vec::with_capacity(self.rows.len() + 2);
+
+        let header: Vec<String> = self
+            .headers
+            .iter()
+            .zip(&widths)
+            .map(|(header, width)| format!("{:<width$}", header, width = *width))
+            .collect();
+        lines.push(paint(Style::new().bold(), header.join("  ").trim_end()));
+        let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        lines.push(dim(rule.join("  ")));
+
+        for row in &self.rows {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| {
+                    let padded = if cell.right_aligned {
+                        format!("{:>width$}", cell.text, width = *width)
+                    } else {
+                        format!("{:<width$}", cell.text, width = *width)
+                    };
+                    paint(cell.style, padded)
+                })
+                .collect();
+            lines.push(cells.join("  ").trim_end().to_string());
+        }
+        lines
+    }
+
+    pub fn print(&self) {
+        for line in self.lines() {
+            println!("{}", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_and_table_without_color() {
+        set_color(false);
+        assert_eq!(bar(50, 100, true), "[##########----------]  50%");
+        assert_eq!(bar(0, 0, true), "[--------------------]   0%");
+
+        let mut table = Table::new(&["Cargo", "Price"]);
+        table.row(vec![Cell::new("Food"), Cell::number("$90")]);
+        table.row(vec![Cell::new("Electronics"), Cell::number("$1200")]);
+        assert_eq!(
+            table.lines(),
+            vec![
+                "Cargo        Price",
+                "-----------  -----",
+                "Food           $90",
+                "Electronics  $1200",
+            ]
+        );
+    }
+}
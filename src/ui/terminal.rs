@@ -1,40 +1,77 @@
 use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
 
 use crate::{
-    config::GameConfig,
-    systems::{GameState, SaveSystem, TradingSystem, TravelSystem},
+    config::Difficulty,
+    models::SecurityRating,
+    systems::{
+        AutosavePolicy, AutosaveTimer, GameState, Profile, ProfileSettings, SaveSystem,
+        TradingSystem, TravelSystem,
+        advisor::Advisor,
+        bank::BankStatement,
+        game::Completed,
+        price_alerts::{self, PriceAlert},
+        realtime::{self, TurnClock},
+        reconcile::WorldAdjustment,
+        save::SaveInfo,
+        security::{HANGAR_FEE, Night, SECURITY_UPGRADE_COST, SecuritySystem},
+        travel::TravelError,
+    },
+    ui::{
+        notifications::{Notification, Notifier},
+        style::{self, Cell, Color, Table},
+    },
 };
 
 pub struct TerminalUI;
 
 impl TerminalUI {
     pub fn run_game_loop() {
-        println!("=== KZRK Aviation Trading Game ===");
+        println!("{}", style::heading("KZRK Aviation Trading Game"));
         println!("Welcome, pilot! Build your aviation trading empire.");
         println!();
 
+        // Each profile keeps its own saves; without one we use the shared directory
+        let mut profile = Self::select_profile();
+        let save_dir = match &profile {
+            Some(profile) => SaveSystem::profile_directory(&profile.name),
+            None => SaveSystem::get_save_directory(),
+        }
+        .unwrap_or_else(|e| {
+            println!(
+                "⚠️  Save directory unavailable ({}), saving to a temporary folder",
+                e
+            );
+            std::env::temp_dir().join("kzrk_saves")
+        });
+        println!();
+
         // Check for autosave and offer to load
-        let mut game_state = if SaveSystem::has_autosave() {
+        let mut resumed_autosave = None;
+        let mut game_state = if SaveSystem::has_autosave_in_dir(&save_dir) {
             print!("Autosave detected. Would you like to continue your previous game? (y/n): ");
             io::stdout().flush().unwrap();
             let choice = Self::get_user_input();
 
             if choice.trim().to_lowercase() == "y" {
-                match SaveSystem::load_autosave() {
-                    Ok(state) => {
+                match SaveSystem::load_game_from_dir_reporting("autosave", &save_dir) {
+                    Ok(loaded) => {
                         println!("Autosave loaded successfully!");
-                        state
+                        resumed_autosave = Some(SaveSystem::autosave_path_in_dir(&save_dir));
+                        Self::report_adjustments(&loaded.adjustments);
+                        loaded.game_state
                     },
                     Err(_) => {
                         println!("Failed to load autosave. Starting new game...");
-                        Self::create_new_game()
+                        Self::create_new_game(&mut profile)
                     },
                 }
             } else {
-                Self::create_new_game()
+                Self::create_new_game(&mut profile)
             }
         } else {
-            Self::create_new_game()
+            Self::create_new_game(&mut profile)
         };
 
         println!();
@@ -44,26 +81,69 @@ impl TerminalUI {
             println!("🔧 CHEAT MODE ENABLED: Unlimited fuel for travel!");
             println!("   (Set via KZRK_CHEAT environment variable)");
         }
+        if Notifier::terminal_from_env().bell {
+            println!("🔔 Terminal bell enabled for market news (KZRK_BELL)");
+        }
         println!();
 
+        let autosave_policy = if game_state.stats.ironman {
+            AutosavePolicy::ironman()
+        } else {
+            AutosavePolicy::from_env()
+        };
+        let new_autosave_timer = |turn_number: u32| {
+            AutosaveTimer::new(autosave_policy.clone(), turn_number).with_save_dir(save_dir.clone())
+        };
+        let mut autosave = match resumed_autosave {
+            Some(path) => new_autosave_timer(game_state.turn_number).resumed_from(path),
+            None => new_autosave_timer(game_state.turn_number),
+        };
+        let mut clock = TurnClock::for_game(&game_state, Instant::now());
+        // On for pilots who haven't finished a game yet
+        let mut advisor = profile
+            .as_ref()
+            .is_none_or(|profile| profile.stats.games_won + profile.stats.games_lost == 0);
+
         // Main game loop
         loop {
+            // Real-time games catch up on the turns that ran out while we waited
+            if let Some(clock) = &mut clock {
+                Self::pass_idle_turns(&mut game_state, clock);
+            }
+
             // Check win/lose conditions
             if game_state.is_game_won() {
                 Self::display_victory(&game_state);
+                Self::record_profile_result(&mut profile, &game_state, true);
                 break;
             }
 
             if !game_state.can_player_continue() {
                 Self::display_game_over(&game_state);
+                Self::record_profile_result(&mut profile, &game_state, false);
+                if game_state.stats.ironman
+                    && let Some(path) = autosave.last_save()
+                {
+                    match SaveSystem::delete_save_file(path) {
+                        Ok(()) => println!("🛡️  Ironman: this career's save has been deleted."),
+                        Err(e) => println!("⚠️  Couldn't delete the ironman save: {}", e),
+                    }
+                }
                 break;
             }
 
             // Display current status
             Self::display_status(&game_state);
+            if let Some(clock) = &clock {
+                println!("{}", clock.status(Instant::now()));
+            }
+            if advisor {
+                Self::display_advice(&game_state);
+            }
 
             // Main menu
-            match Self::display_main_menu() {
+            let turn_before = game_state.turn_number;
+            match Self::display_main_menu(clock.is_some(), advisor) {
                 MainMenuChoice::ViewMarket => {
                     Self::display_market_info(&game_state);
                 },
@@ -72,37 +152,104 @@ impl TerminalUI {
                 },
                 MainMenuChoice::Travel => {
                     Self::handle_travel(&mut game_state);
-                    // Autosave after travel
-                    let _ = SaveSystem::autosave(&game_state);
                 },
                 MainMenuChoice::MessageBoard => {
                     Self::handle_message_board(&mut game_state);
                 },
+                MainMenuChoice::SaveGame | MainMenuChoice::LoadGame if game_state.stats.ironman => {
+                    println!("🛡️  Ironman games can't be saved or loaded by hand.");
+                    println!("   Your progress is saved after every action.");
+                },
                 MainMenuChoice::SaveGame => {
-                    Self::handle_save_game(&game_state);
+                    Self::handle_save_game(&game_state, &save_dir);
                 },
                 MainMenuChoice::LoadGame => {
-                    if let Some(loaded_state) = Self::handle_load_game() {
+                    if let Some(loaded_state) = Self::handle_load_game(&save_dir) {
                         game_state = loaded_state;
+                        autosave = new_autosave_timer(game_state.turn_number);
+                        clock = TurnClock::for_game(&game_state, Instant::now());
                         println!("Game loaded successfully!");
                     }
                 },
                 MainMenuChoice::Help => {
                     Self::display_help();
                 },
+                MainMenuChoice::PauseClock => {
+                    if let Some(clock) = &mut clock {
+                        if clock.toggle_pause(Instant::now()) {
+                            println!("⏸️  Clock paused. Turns won't pass until you resume.");
+                        } else {
+                            println!("▶️  Clock running again.");
+                        }
+                    }
+                },
+                MainMenuChoice::ToggleAdvisor => {
+                    advisor = !advisor;
+                    if advisor {
+                        println!("🧭 Advisor on: a suggested move each turn.");
+                    } else {
+                        println!("🧭 Advisor off.");
+                    }
+                },
                 MainMenuChoice::Quit => {
-                    Self::prompt_save_before_quit(&game_state);
+                    if !game_state.stats.ironman {
+                        Self::prompt_save_before_quit(&game_state, &save_dir);
+                    }
                     println!("Thanks for playing KZRK! Safe travels, pilot.");
                     break;
                 },
             }
 
+            // Flying somewhere is a turn of its own, so the countdown starts over
+            if game_state.turn_number != turn_before
+                && let Some(clock) = &mut clock
+            {
+                clock.restart(Instant::now());
+            }
+
+            let autosaved = if game_state.stats.ironman {
+                Some(autosave.save_now(&game_state))
+            } else {
+                autosave.tick(&game_state)
+            };
+            if let Some(Err(e)) = autosaved {
+                println!("⚠️  Autosave failed: {}", e);
+            }
+
             println!(); // Add spacing between turns
         }
     }
 
+    fn pass_idle_turns(game_state: &mut GameState, clock: &mut TurnClock) {
+        let reports = clock.pass_due_turns(game_state, Instant::now());
+        if reports.is_empty() {
+            return;
+        }
+        println!(
+            "{}",
+            style::warning(format!(
+                "⏱️  {} turn(s) passed while you were on the ground",
+                reports.len()
+            ))
+        );
+        for report in &reports {
+            Self::print_night(&report.night);
+            Self::print_bank(&report.bank);
+        }
+        if let Some(last) = reports.last() {
+            Self::print_price_alerts(&last.price_alerts);
+        }
+        println!();
+    }
+
     fn display_status(game_state: &GameState) {
-        println!("=== STATUS ===");
+        println!("{}", style::heading("STATUS"));
+        if game_state.stats.ironman {
+            println!("🛡️  IRONMAN");
+        }
+        if let Some(seconds) = game_state.stats.real_time_turn_secs {
+            println!("⏱️  REAL-TIME: a turn every {}s", seconds);
+        }
 
         if let Some(current_airport) = game_state.get_current_airport() {
             println!(
@@ -111,19 +258,28 @@ impl TerminalUI {
             );
         }
 
-        println!("Turn: {}", game_state.turn_number);
-        println!("Money: ${}", game_state.player.money);
         println!(
-            "Fuel: {}/{}",
-            game_state.player.fuel, game_state.player.max_fuel
+            "Turn:  {} ({}, {})",
+            game_state.turn_number,
+            game_state.date(),
+            game_state.season().name()
+        );
+        println!("Money: {}", style::money(game_state.player.money));
+        println!(
+            "Fuel:  {} {}/{}",
+            style::bar(game_state.player.fuel, game_state.player.max_fuel, true),
+            game_state.player.fuel,
+            game_state.player.max_fuel
         );
 
         let current_weight = game_state
             .player
             .current_cargo_weight(&game_state.cargo_types);
         println!(
-            "Cargo: {}kg / {}kg",
-            current_weight, game_state.player.max_cargo_weight
+            "Cargo: {} {}kg / {}kg",
+            style::bar(current_weight, game_state.player.max_cargo_weight, false),
+            current_weight,
+            game_state.player.max_cargo_weight
         );
 
         // Display active market events
@@ -131,8 +287,9 @@ impl TerminalUI {
             println!("\n📰 MARKET EVENTS:");
             for event in &game_state.active_events {
                 println!(
-                    "  {} ({}T remaining)",
-                    event.description, event.turns_remaining
+                    "  {} {}",
+                    style::warning(&event.description),
+                    style::dim(format!("({}T remaining)", event.turns_remaining))
                 );
             }
         }
@@ -152,9 +309,23 @@ impl TerminalUI {
         println!();
     }
 
-    fn display_main_menu() -> MainMenuChoice {
+    fn display_advice(game_state: &GameState) {
+        match Advisor::advise(game_state) {
+            Some(advice) => {
+                println!("🧭 Advisor: {}", style::good(&advice.summary));
+                println!("   {}", style::dim(&advice.reason));
+            },
+            None => println!(
+                "🧭 Advisor: {}",
+                style::dim("no trade you know of turns a profit right now")
+            ),
+        }
+        println!();
+    }
+
+    fn display_main_menu(real_time: bool, advisor: bool) -> MainMenuChoice {
         loop {
-            println!("=== MAIN MENU ===");
+            println!("{}", style::heading("MAIN MENU"));
             println!("1. View Market");
             println!("2. Trade");
             println!("3. Travel");
@@ -163,7 +334,14 @@ impl TerminalUI {
             println!("6. Load Game");
             println!("7. Help");
             println!("8. Quit");
-            print!("Choose an option (1-8): ");
+            if real_time {
+                println!("9. Pause/Resume Clock");
+            }
+            println!("A. Turn Advisor {}", if advisor { "Off" } else { "On" });
+            print!(
+                "Choose an option (1-{} or A): ",
+                if real_time { 9 } else { 8 }
+            );
             io::stdout().flush().unwrap();
 
             let choice = Self::get_user_input();
@@ -176,6 +354,8 @@ impl TerminalUI {
                 "6" => return MainMenuChoice::LoadGame,
                 "7" => return MainMenuChoice::Help,
                 "8" => return MainMenuChoice::Quit,
+                "9" if real_time => return MainMenuChoice::PauseClock,
+                "a" | "A" => return MainMenuChoice::ToggleAdvisor,
                 _ => {
                     println!("Invalid choice. Please try again.");
                     println!();
@@ -185,16 +365,40 @@ impl TerminalUI {
     }
 
     fn display_market_info(game_state: &GameState) {
-        println!("=== MARKET PRICES ===");
+        println!("{}", style::heading("MARKET PRICES"));
 
         if let Some(market) = game_state.get_current_market() {
-            println!("Fuel: ${}/unit", market.fuel_price);
+            match game_state.fuel_outage_at(&market.airport_id) {
+                Some(outage) => println!(
+                    "Fuel: ⛽ OUT OF STOCK for {} more turn(s)",
+                    outage.turns_remaining
+                ),
+                None => println!(
+                    "Fuel: ${}/unit{} ({} in stock)",
+                    market.fuel_price,
+                    Self::fuel_price_hint(game_state, market.fuel_price),
+                    market.fuel_available()
+                ),
+            }
+            let volume = game_state.player.broker_volume_at(&market.airport_id);
+            let tier = TradingSystem::broker_tier(volume);
+            match TradingSystem::next_broker_tier(volume) {
+                Some(next) => println!(
+                    "Broker: {} ({}% fee) - trade ${} more here for {} ({}%)",
+                    tier.name,
+                    tier.fee_percent(),
+                    next.min_volume - volume,
+                    next.name,
+                    next.fee_percent()
+                ),
+                None => println!("Broker: {} ({}% fee)", tier.name, tier.fee_percent()),
+            }
             println!();
-            println!("Cargo Prices:");
 
             let mut cargo_list: Vec<_> = market.get_all_cargo_prices().iter().collect();
             cargo_list.sort_by(|a, b| a.0.cmp(b.0)); // Sort by cargo ID
 
+            let mut table = Table::new(&["Cargo", "Price", "Usual", "Can buy", "News"]);
             for (cargo_id, price) in cargo_list {
                 if let Some(cargo_type) = game_state.cargo_types.get(cargo_id) {
                     let max_buyable = TradingSystem::get_max_buyable_quantity(
@@ -209,27 +413,33 @@ impl TerminalUI {
                         .active_events
                         .iter()
                         .find(|e| {
-                            e.affected_cargo == *cargo_id && e.affected_airport == market.airport_id
+                            e.affects_cargo(cargo_id, &game_state.cargo_types)
+                                && e.affected_airport == market.airport_id
                         })
                         .map(|e| {
                             if e.price_multiplier > 1.5 {
-                                " 🔥" // Hot/spike
+                                "🔥" // Hot/spike
                             } else if e.price_multiplier < 0.7 {
-                                " 📉" // Crash
+                                "📉" // Crash
                             } else if e.price_multiplier > 1.0 {
-                                " ⬆️" // Up
+                                "⬆️" // Up
                             } else {
-                                " ⬇️" // Down
+                                "⬇️" // Down
                             }
                         })
                         .unwrap_or("");
 
-                    println!(
-                        "  {}: ${}/unit (can buy: {}){}",
-                        cargo_type.name, price, max_buyable, event_indicator
-                    );
+                    table.row(vec![
+                        Cell::new(&cargo_type.name),
+                        Cell::number(format!("${}", price))
+                            .styled(style::price_style(*price, cargo_type.base_price)),
+                        Cell::number(format!("${}", cargo_type.base_price)).styled(style::muted()),
+                        Cell::number(max_buyable.to_string()),
+                        Cell::new(event_indicator),
+                    ]);
                 }
             }
+            table.print();
         }
 
         Self::press_enter_to_continue();
@@ -237,12 +447,14 @@ impl TerminalUI {
 
     fn handle_trading(game_state: &mut GameState) {
         loop {
-            println!("=== TRADING ===");
+            println!("{}", style::heading("TRADING"));
             println!("1. Buy Cargo");
             println!("2. Sell Cargo");
             println!("3. Buy Fuel");
-            println!("4. Back to Main Menu");
-            print!("Choose an option (1-4): ");
+            println!("4. Hangar & Security");
+            println!("5. Price Alerts");
+            println!("6. Back to Main Menu");
+            print!("Choose an option (1-6): ");
             io::stdout().flush().unwrap();
 
             let choice = Self::get_user_input();
@@ -250,19 +462,170 @@ impl TerminalUI {
                 "1" => Self::handle_buy_cargo(game_state),
                 "2" => Self::handle_sell_cargo(game_state),
                 "3" => Self::handle_buy_fuel(game_state),
-                "4" => break,
+                "4" => Self::handle_security(game_state),
+                "5" => Self::handle_price_alerts(game_state),
+                "6" => break,
                 _ => println!("Invalid choice. Please try again."),
             }
         }
     }
 
+    fn handle_security(game_state: &mut GameState) {
+        println!("{}", style::heading("HANGAR & SECURITY"));
+        if let Some(airport) = game_state.get_current_airport() {
+            let chance = SecuritySystem::theft_chance(&game_state.player, airport);
+            let rating = match airport.security {
+                SecurityRating::Low => style::bad(airport.security.label()),
+                SecurityRating::Standard => style::good(airport.security.label()),
+            };
+            println!(
+                "{} security: {} ({:.0}% chance of a break-in parked outside tonight)",
+                airport.name,
+                rating,
+                chance * 100.0
+            );
+        }
+        let player = &game_state.player;
+        println!(
+            "Hangar parking: {} (${} a night, wherever you stop)",
+            if player.hangar_parking { "on" } else { "off" },
+            HANGAR_FEE
+        );
+        println!(
+            "Security upgrade: {}",
+            if player.security_upgrade {
+                "fitted".to_string()
+            } else {
+                format!("not fitted (${})", SECURITY_UPGRADE_COST)
+            }
+        );
+        println!();
+        println!(
+            "1. Turn hangar parking {}",
+            if player.hangar_parking { "off" } else { "on" }
+        );
+        if !player.security_upgrade {
+            println!("2. Buy the security upgrade");
+        }
+        println!("0. Back");
+        print!("Choose an option: ");
+        io::stdout().flush().unwrap();
+
+        match Self::get_user_input().trim() {
+            "1" => {
+                let player = &mut game_state.player;
+                player.hangar_parking = !player.hangar_parking;
+                if player.hangar_parking {
+                    println!(
+                        "{}",
+                        style::good("✓ Your aircraft will be hangared each night.")
+                    );
+                } else {
+                    println!("Your aircraft will park outside from now on.");
+                }
+            },
+            "2" if !game_state.player.security_upgrade => {
+                match SecuritySystem::buy_security_upgrade(&mut game_state.player) {
+                    Ok(cost) => {
+                        game_state.stats.update_money_stats(game_state.player.money);
+                        println!(
+                            "{}",
+                            style::good(format!("✓ Security upgrade fitted for ${}", cost))
+                        );
+                    },
+                    Err(e) => println!("{}", style::bad(format!("✗ {}", e))),
+                }
+            },
+            _ => return,
+        }
+        Self::press_enter_to_continue();
+    }
+
+    /// Gain over cost basis at `price`: green for a profit, red for a loss
+    fn gain_cell(average_cost: Option<u32>, price: u32) -> Cell {
+        match average_cost.and_then(|cost| price_alerts::gain_percent(cost, price)) {
+            Some(gain) if gain > 0 => {
+                Cell::number(format!("+{}%", gain)).styled(Color::Green.normal())
+            },
+            Some(gain) if gain < 0 => {
+                Cell::number(format!("{}%", gain)).styled(Color::Red.normal())
+            },
+            Some(_) => Cell::number("0%"),
+            None => Cell::number("-"),
+        }
+    }
+
+    fn handle_price_alerts(game_state: &mut GameState) {
+        println!("{}", style::heading("PRICE ALERTS"));
+        let threshold = game_state.price_alerts.threshold_percent;
+        if threshold == 0 {
+            println!("Price alerts are off.");
+        } else {
+            println!(
+                "You're alerted when cargo you hold is worth {}% or more above what you paid.",
+                threshold
+            );
+        }
+        print!(
+            "New threshold in percent (0 turns alerts off, Enter keeps {}): ",
+            threshold
+        );
+        io::stdout().flush().unwrap();
+
+        let input = Self::get_user_input();
+        if input.trim().is_empty() {
+            return;
+        }
+        match input.trim().trim_end_matches('%').parse::<u32>() {
+            Ok(percent) => match game_state.price_alerts.set_threshold(percent) {
+                Ok(()) if percent == 0 => println!("Price alerts turned off."),
+                Ok(()) => println!(
+                    "{}",
+                    style::good(format!("✓ Alerting at +{}% over cost", percent))
+                ),
+                Err(e) => println!("{}", style::bad(format!("✗ {}", e))),
+            },
+            Err(_) => println!("{}", style::bad("Please enter a whole number.")),
+        }
+        Self::press_enter_to_continue();
+    }
+
+    fn print_price_alerts(alerts: &[PriceAlert]) {
+        for alert in alerts {
+            println!("📈 {}", style::good(alert.message()));
+        }
+    }
+
+    fn print_night(night: &Night) {
+        match night {
+            Night::Quiet => {},
+            Night::Hangar { .. } => {
+                if let Some(report) = night.report() {
+                    println!("🏠 {}", style::dim(report));
+                }
+            },
+            Night::Robbed(loss) => println!("🚨 {}", style::bad(&loss.description)),
+        }
+    }
+
+    fn print_bank(statement: &Option<BankStatement>) {
+        match statement {
+            Some(statement) if statement.bankruptcy.is_some() => {
+                println!("🏦 {}", style::bad(statement.report()))
+            },
+            Some(statement) => println!("🏦 {}", style::dim(statement.report())),
+            None => {},
+        }
+    }
+
     fn handle_buy_cargo(game_state: &mut GameState) {
         if let Some(market) = game_state.get_current_market().cloned() {
-            println!("=== BUY CARGO ===");
+            println!("{}", style::heading("BUY CARGO"));
 
             let mut available_cargo: Vec<_> = market.get_all_cargo_prices().iter().collect();
             available_cargo.sort_by(|a, b| a.0.cmp(b.0));
 
+            let mut table = Table::new(&["#", "Cargo", "Price", "Max"]);
             for (i, (cargo_id, price)) in available_cargo.iter().enumerate() {
                 if let Some(cargo_type) = game_state.cargo_types.get(*cargo_id) {
                     let max_buyable = TradingSystem::get_max_buyable_quantity(
@@ -272,15 +635,16 @@ impl TerminalUI {
                         cargo_id,
                     );
 
-                    println!(
-                        "{}. {} - ${}/unit (max: {})",
-                        i + 1,
-                        cargo_type.name,
-                        price,
-                        max_buyable
-                    );
+                    table.row(vec![
+                        Cell::number(format!("{}.", i + 1)),
+                        Cell::new(&cargo_type.name),
+                        Cell::number(format!("${}", price))
+                            .styled(style::price_style(**price, cargo_type.base_price)),
+                        Cell::number(max_buyable.to_string()),
+                    ]);
                 }
             }
+            table.print();
 
             println!("0. Back");
             print!("Choose cargo to buy (0-{}): ", available_cargo.len());
@@ -312,33 +676,26 @@ impl TerminalUI {
                     let quantity_input = Self::get_user_input();
                     if let Ok(quantity) = quantity_input.trim().parse::<u32>() {
                         if quantity > 0 && quantity <= max_buyable {
-                            match TradingSystem::buy_cargo(
-                                &mut game_state.player,
-                                &market,
-                                &game_state.cargo_types,
-                                cargo_id,
-                                quantity,
-                            ) {
-                                Ok(cost) => {
+                            match game_state.buy_cargo(cargo_id, quantity) {
+                                Ok(Completed { result: quote, .. }) => {
                                     if let Some(cargo_type) = game_state.cargo_types.get(*cargo_id)
                                     {
                                         println!(
-                                            "✓ Bought {} {} for ${}",
-                                            quantity, cargo_type.name, cost
-                                        );
-                                        // Record the trade in statistics
-                                        game_state.stats.record_trade(
-                                            -(cost as i64),
-                                            cargo_id.to_string(),
-                                            quantity,
-                                            true,
+                                            "{}",
+                                            style::good(format!(
+                                                "✓ Bought {} {} for ${} (incl. ${} broker fee)",
+                                                quantity,
+                                                cargo_type.name,
+                                                quote.total_cost(),
+                                                quote.fee
+                                            )),
                                         );
-                                        game_state
-                                            .stats
-                                            .update_money_stats(game_state.player.money);
                                     }
                                 },
-                                Err(e) => println!("✗ Purchase failed: {:?}", e),
+                                Err(e) => println!(
+                                    "{}",
+                                    style::bad(format!("✗ Purchase failed: {:?}", e))
+                                ),
                             }
                         } else {
                             println!("Invalid quantity.");
@@ -354,7 +711,7 @@ impl TerminalUI {
     }
 
     fn handle_sell_cargo(game_state: &mut GameState) {
-        println!("=== SELL CARGO ===");
+        println!("{}", style::heading("SELL CARGO"));
 
         let carried_cargo = game_state.player.cargo_inventory.get_all_cargo().clone();
         if carried_cargo.is_empty() || carried_cargo.values().all(|&q| q == 0) {
@@ -378,20 +735,30 @@ impl TerminalUI {
             },
         };
 
+        let inventory = &game_state.player.cargo_inventory;
+        let mut table = Table::new(&["#", "Cargo", "Qty", "Paid", "Price", "Gain", "Total"]);
         for (i, (cargo_id, quantity)) in sellable_cargo.iter().enumerate() {
             if let Some(cargo_type) = game_state.cargo_types.get(*cargo_id)
                 && let Some(price) = market.get_cargo_price(cargo_id)
             {
-                println!(
-                    "{}. {} x{} - ${}/unit (total: ${})",
-                    i + 1,
-                    cargo_type.name,
-                    quantity,
-                    price,
-                    price * *quantity
-                );
+                let average_cost = inventory.average_cost(cargo_id);
+                table.row(vec![
+                    Cell::number(format!("{}.", i + 1)),
+                    Cell::new(&cargo_type.name),
+                    Cell::number(quantity.to_string()),
+                    Cell::number(
+                        average_cost
+                            .map(|cost| format!("${}", cost))
+                            .unwrap_or_else(|| "-".to_string()),
+                    ),
+                    Cell::number(format!("${}", price))
+                        .styled(style::sale_price_style(price, cargo_type.base_price)),
+                    Self::gain_cell(average_cost, price),
+                    Cell::number(format!("${}", price.saturating_mul(**quantity))),
+                ]);
             }
         }
+        table.print();
 
         println!("0. Back");
         print!("Choose cargo to sell (0-{}): ", sellable_cargo.len());
@@ -410,32 +777,31 @@ impl TerminalUI {
                 let quantity_input = Self::get_user_input();
                 if let Ok(quantity) = quantity_input.trim().parse::<u32>() {
                     if quantity > 0 && quantity <= **max_quantity {
-                        match TradingSystem::sell_cargo(
-                            &mut game_state.player,
-                            &market,
-                            cargo_id,
-                            quantity,
-                        ) {
-                            Ok(revenue) => {
+                        match game_state.sell_cargo(cargo_id, quantity) {
+                            Ok(Completed { result: quote, .. }) => {
                                 if let Some(cargo_type) = game_state.cargo_types.get(*cargo_id) {
                                     println!(
-                                        "✓ Sold {} {} for ${}",
-                                        quantity, cargo_type.name, revenue
-                                    );
-                                    // Record the trade in statistics
-                                    // Since we don't track purchase price, we'll approximate profit
-                                    let estimated_cost = cargo_type.base_price * quantity;
-                                    let profit = revenue as i64 - estimated_cost as i64;
-                                    game_state.stats.record_trade(
-                                        profit,
-                                        cargo_id.to_string(),
-                                        quantity,
-                                        false,
+                                        "{}",
+                                        style::good(format!(
+                                            "✓ Sold {} {} for ${} (after ${} broker fee)",
+                                            quantity,
+                                            cargo_type.name,
+                                            quote.proceeds(),
+                                            quote.fee
+                                        )),
                                     );
-                                    game_state.stats.update_money_stats(game_state.player.money);
+                                    if quote.bonus > 0 {
+                                        println!(
+                                            "{}",
+                                            style::good(format!(
+                                                "  Includes a ${} streak bonus ({} in a row)",
+                                                quote.bonus, game_state.player.streak.current
+                                            )),
+                                        );
+                                    }
                                 }
                             },
-                            Err(e) => println!("✗ Sale failed: {:?}", e),
+                            Err(e) => println!("{}", style::bad(format!("✗ Sale failed: {:?}", e))),
                         }
                     } else {
                         println!("Invalid quantity.");
@@ -449,20 +815,53 @@ impl TerminalUI {
         Self::press_enter_to_continue();
     }
 
+    /// " (cheap here; usually $55-$80)" for the current airport
+    fn fuel_price_hint(game_state: &GameState, fuel_price: u32) -> String {
+        match game_state.get_current_airport() {
+            Some(airport) => {
+                let economy = airport.fuel_economy();
+                format!(
+                    " ({} here; usually {})",
+                    economy.level(fuel_price).label().to_lowercase(),
+                    economy.range_label()
+                )
+            },
+            None => String::new(),
+        }
+    }
+
     fn handle_buy_fuel(game_state: &mut GameState) {
         if let Some(market) = game_state.get_current_market().cloned() {
-            println!("=== BUY FUEL ===");
+            println!("{}", style::heading("BUY FUEL"));
+
+            if let Some(outage) = game_state.fuel_outage_at(&market.airport_id) {
+                println!("{}", outage.description);
+                println!(
+                    "No fuel for sale here for another {} turn(s).",
+                    outage.turns_remaining
+                );
+                Self::press_enter_to_continue();
+                return;
+            }
 
             let max_fuel = TradingSystem::get_max_fuel_buyable(&game_state.player, &market);
-            println!("Fuel price: ${}/unit", market.fuel_price);
+            println!(
+                "Fuel price: ${}/unit{}",
+                market.fuel_price,
+                Self::fuel_price_hint(game_state, market.fuel_price)
+            );
             println!(
                 "Current fuel: {}/{}",
                 game_state.player.fuel, game_state.player.max_fuel
             );
-            println!("Max you can buy: {}", max_fuel);
+            println!(
+                "In stock: {} | Max you can buy: {}",
+                market.fuel_available(),
+                max_fuel
+            );
 
             if max_fuel == 0 {
-                println!("Your tank is full or you cannot afford fuel.");
+                println!("Your tank is full, the pumps are dry or you cannot afford fuel.");
                 Self::press_enter_to_continue();
                 return;
             }
@@ -477,14 +876,21 @@ impl TerminalUI {
                 }
 
                 if quantity <= max_fuel {
-                    match TradingSystem::buy_fuel(&mut game_state.player, &market, quantity) {
-                        Ok(cost) => {
-                            println!("✓ Bought {} fuel for ${}", quantity, cost);
-                            // Record fuel purchase in statistics
-                            game_state.stats.record_fuel_purchase(quantity);
-                            game_state.stats.update_money_stats(game_state.player.money);
+                    match game_state.buy_fuel(quantity) {
+                        Ok(Completed {
+                            result: purchase, ..
+                        }) => {
+                            println!(
+                                "{}",
+                                style::good(format!(
+                                    "✓ Bought {} fuel for ${}",
+                                    purchase.quantity, purchase.cost
+                                ))
+                            );
+                        },
+                        Err(e) => {
+                            println!("{}", style::bad(format!("✗ Fuel purchase failed: {:?}", e)))
                         },
-                        Err(e) => println!("✗ Fuel purchase failed: {:?}", e),
                     }
                 } else {
                     println!("Cannot buy that much fuel.");
@@ -497,8 +903,44 @@ impl TerminalUI {
         }
     }
 
+    fn handle_ground_transfer(game_state: &mut GameState, destination_id: &str) {
+        if let (Some(from), Some(to)) = (
+            game_state.get_current_airport(),
+            game_state.airports.get(destination_id),
+        ) && let Ok(quote) = TravelSystem::ground_transfer_quote(&game_state.player, from, to)
+        {
+            for line in quote.lines() {
+                println!("  {}", line);
+            }
+        }
+
+        match TravelSystem::ground_transfer(game_state, destination_id) {
+            Ok(travel_info) => {
+                println!("{}", style::good("✓ Ground transfer complete!"));
+                println!(
+                    "Route: {} → {} ({:.0}km by road, no fuel used)",
+                    travel_info.from, travel_info.to, travel_info.distance_km
+                );
+                Self::print_night(&travel_info.night);
+                Self::print_bank(&travel_info.bank);
+                Self::print_price_alerts(&travel_info.price_alerts);
+            },
+            Err(TravelError::InsufficientFunds) => {
+                println!(
+                    "{}",
+                    style::bad("✗ You can't afford the ground transfer fare.")
+                )
+            },
+            Err(e) => println!(
+                "{}",
+                style::bad(format!("✗ Ground transfer failed: {:?}", e))
+            ),
+        }
+        Self::press_enter_to_continue();
+    }
+
     fn handle_travel(game_state: &mut GameState) {
-        println!("=== TRAVEL ===");
+        println!("{}", style::heading("TRAVEL"));
 
         let destinations = TravelSystem::get_reachable_destinations(game_state);
 
@@ -508,26 +950,40 @@ impl TerminalUI {
             return;
         }
 
-        println!("Available destinations:");
+        let mut table = Table::new(&["#", "", "Destination", "Code", "Distance", "Fuel", "Notes"]);
         for (i, dest) in destinations.iter().enumerate() {
-            let status = if dest.can_afford { "✓" } else { "✗" };
-            let cheat_indicator =
-                if game_state.cheat_mode && !game_state.player.fuel >= dest.fuel_needed {
-                    " 🔧"
-                } else {
-                    ""
-                };
-            println!(
-                "{}. {} {} - {:.0}km, {} fuel needed ({}){}",
-                i + 1,
+            let status = if dest.can_afford {
+                Cell::new("✓").styled(style::Color::Green.normal())
+            } else {
+                Cell::new("✗").styled(style::Color::Red.normal())
+            };
+            let mut notes = Vec::new();
+            if game_state.cheat_mode && !game_state.player.fuel >= dest.fuel_needed {
+                notes.push("🔧".to_string());
+            }
+            // You can land there, but you can't refuel for the way out
+            if let Some(outage) = game_state.fuel_outage_at(&dest.airport_id) {
+                notes.push(format!("⛽ no fuel for {}T", outage.turns_remaining));
+            }
+            if let Some(fare) = dest.ground_transfer_fare {
+                notes.push(format!("🚌 or ${} by road", fare));
+            }
+            let fuel_style = if dest.fuel_needed > game_state.player.fuel {
+                style::Color::Red.normal()
+            } else {
+                style::Style::new()
+            };
+            table.row(vec![
+                Cell::number(format!("{}.", i + 1)),
                 status,
-                dest.airport_name,
-                dest.distance_km,
-                dest.fuel_needed,
-                dest.airport_id,
-                cheat_indicator
-            );
+                Cell::new(&dest.airport_name),
+                Cell::new(&dest.airport_id).styled(style::muted()),
+                Cell::number(format!("{:.0}km", dest.distance_km)),
+                Cell::number(dest.fuel_needed.to_string()).styled(fuel_style),
+                Cell::new(notes.join(" ")),
+            ]);
         }
+        table.print();
 
         println!("0. Back");
         print!("Choose destination (0-{}): ", destinations.len());
@@ -540,6 +996,15 @@ impl TerminalUI {
             }
 
             if let Some(destination) = destinations.get(index - 1) {
+                if destination.ground_transfer_fare.is_some() {
+                    print!("Fly there (f) or take the ground transfer (r)? ");
+                    io::stdout().flush().unwrap();
+                    if Self::get_user_input().trim().eq_ignore_ascii_case("r") {
+                        Self::handle_ground_transfer(game_state, &destination.airport_id);
+                        return;
+                    }
+                }
+
                 if !destination.can_afford {
                     println!("You don't have enough fuel for this trip.");
                     println!(
@@ -550,29 +1015,58 @@ impl TerminalUI {
                     return;
                 }
 
+                if let (Some(from), Some(to)) = (
+                    game_state.get_current_airport(),
+                    game_state.airports.get(&destination.airport_id),
+                ) {
+                    let fuel_price = game_state
+                        .get_current_market()
+                        .map(|market| market.fuel_price)
+                        .unwrap_or(0);
+                    let conditions = game_state.flight_conditions(&from.id, &to.id);
+                    let quote =
+                        TravelSystem::quote(&game_state.player, from, to, fuel_price, conditions);
+                    for line in quote.lines() {
+                        println!("  {}", line);
+                    }
+                }
+
                 print!("Confirm travel to {} (y/n): ", destination.airport_name);
                 io::stdout().flush().unwrap();
 
                 let confirm = Self::get_user_input();
                 if confirm.trim().to_lowercase() == "y" {
-                    match TravelSystem::travel_to(game_state, &destination.airport_id) {
+                    let known_events: Vec<String> = game_state
+                        .active_events
+                        .iter()
+                        .map(|event| event.description.clone())
+                        .collect();
+
+                    match game_state.fly_to(&destination.airport_id) {
                         Ok(travel_info) => {
-                            println!("✓ Travel successful!");
+                            println!("{}", style::good("✓ Travel successful!"));
                             println!("Route: {} → {}", travel_info.from, travel_info.to);
                             println!(
                                 "Distance: {:.0}km, Fuel consumed: {}",
                                 travel_info.distance_km, travel_info.fuel_consumed
                             );
                             println!("Arrived at {}! New market prices await.", travel_info.to);
-                            // Record travel in statistics
-                            game_state.stats.record_travel(
-                                travel_info.distance_km,
-                                travel_info.fuel_consumed,
-                                travel_info.from.clone(),
-                                travel_info.to.clone(),
-                            );
+                            Self::print_night(&travel_info.night);
+                            Self::print_bank(&travel_info.bank);
+                            Self::print_price_alerts(&travel_info.price_alerts);
+
+                            // Ring the bell for news that broke while we were in the air
+                            let notifier = Notifier::terminal_from_env();
+                            for event in &game_state.active_events {
+                                if !known_events.contains(&event.description) {
+                                    println!("📰 {}", event.description);
+                                    notifier.notify(&Notification::MarketEvent {
+                                        description: event.description.clone(),
+                                    });
+                                }
+                            }
                         },
-                        Err(e) => println!("✗ Travel failed: {:?}", e),
+                        Err(e) => println!("{}", style::bad(format!("✗ Travel failed: {:?}", e))),
                     }
                 }
             }
@@ -582,7 +1076,7 @@ impl TerminalUI {
     }
 
     fn display_help() {
-        println!("=== HELP ===");
+        println!("{}", style::heading("HELP"));
         println!("KZRK is an aviation trading game. Your goal is to reach $100,000.");
         println!();
         println!("Game Mechanics:");
@@ -591,6 +1085,8 @@ impl TerminalUI {
         println!("• Fuel is needed for travel - manage it carefully");
         println!("• Market prices change when you travel");
         println!("• Your plane has limited cargo capacity (weight-based)");
+        println!("• In real-time games turns also pass on their own; option 9 pauses the clock");
+        println!("• The advisor (option A) suggests a move each turn and says why");
         println!();
         println!("Tips:");
         println!("• Look for airports that produce goods (lower prices)");
@@ -605,16 +1101,25 @@ impl TerminalUI {
 
     fn display_victory(game_state: &GameState) {
         println!("🎉 CONGRATULATIONS! 🎉");
+        if game_state.stats.ironman {
+            println!("🛡️  IRONMAN VICTORY - won without a single reload");
+        }
         println!(
             "You've reached ${} and won the game!",
             game_state.win_condition_money
         );
         println!();
-        println!("=== FINAL STATISTICS ===");
+        println!("{}", style::heading("FINAL STATISTICS"));
         println!("📊 Performance:");
         println!("  Final Money: ${}", game_state.player.money);
         println!("  Peak Money: ${}", game_state.stats.peak_money);
         println!("  Turns Played: {}", game_state.turn_number);
+        let hours = game_state.calendar.hours_between(1, game_state.turn_number);
+        println!(
+            "  Time in Business: {} days {} hours",
+            hours / 24,
+            hours % 24
+        );
         println!();
         println!("📈 Trading:");
         println!("  Total Trades: {}", game_state.stats.total_trades);
@@ -624,6 +1129,10 @@ impl TerminalUI {
         );
         println!("  Net Profit: ${}", game_state.stats.get_net_profit());
         println!("  Best Trade: ${}", game_state.stats.best_trade_profit);
+        println!(
+            "  Best Streak: {} profitable sales in a row",
+            game_state.stats.best_streak
+        );
         if game_state.stats.worst_trade_loss < 0 {
             println!(
                 "  Worst Trade: ${}",
@@ -657,7 +1166,7 @@ impl TerminalUI {
         println!("💸 GAME OVER 💸");
         println!("You've run out of money and fuel. Your trading career has ended.");
         println!();
-        println!("=== FINAL STATISTICS ===");
+        println!("{}", style::heading("FINAL STATISTICS"));
         println!("📊 Performance:");
         println!("  Final Money: ${}", game_state.player.money);
         println!("  Peak Money: ${}", game_state.stats.peak_money);
@@ -698,8 +1207,8 @@ impl TerminalUI {
         }
     }
 
-    fn select_difficulty() -> GameConfig {
-        println!("=== Select Difficulty ===");
+    fn select_difficulty() -> Difficulty {
+        println!("{}", style::heading("Select Difficulty"));
         println!("1. Easy   - $8000 start, full fuel, $50k to win");
         println!("2. Normal - $5000 start, 2/3 fuel, $100k to win");
         println!("3. Hard   - $3000 start, 1/2 fuel, $150k to win");
@@ -712,15 +1221,15 @@ impl TerminalUI {
             match input.as_str() {
                 "1" => {
                     println!("Easy mode selected. Good luck, pilot!");
-                    return GameConfig::easy();
+                    return Difficulty::Easy;
                 },
                 "2" => {
                     println!("Normal mode selected. The skies await!");
-                    return GameConfig::normal();
+                    return Difficulty::Normal;
                 },
                 "3" => {
                     println!("Hard mode selected. Brave choice, pilot!");
-                    return GameConfig::hard();
+                    return Difficulty::Hard;
                 },
                 _ => {
                     print!("Invalid choice. Please enter 1, 2, or 3: ");
@@ -730,24 +1239,83 @@ impl TerminalUI {
         }
     }
 
-    fn create_new_game() -> GameState {
-        // Select difficulty
-        let config = Self::select_difficulty();
+    fn create_new_game(profile: &mut Option<Profile>) -> GameState {
+        // Profiles carry their own difficulty and mode; otherwise ask
+        let (config, ironman) = match profile {
+            Some(profile) => {
+                profile.stats.record_game_started();
+                if let Err(e) = SaveSystem::save_profile(profile) {
+                    println!("⚠️  Couldn't update profile: {}", e);
+                }
+                println!(
+                    "{} difficulty (from profile {})",
+                    profile.settings.difficulty.label(),
+                    profile.name
+                );
+                (
+                    profile.settings.difficulty.config(),
+                    profile.settings.ironman,
+                )
+            },
+            None => (Self::select_difficulty().config(), Self::ask_ironman()),
+        };
 
         // Initialize game
         let airports = crate::data::get_default_airports();
         let cargo_types = crate::data::get_default_cargo_types();
-        let game_state = GameState::new_with_config(airports, cargo_types, config.clone());
+        let mut game_state = GameState::new_with_config(airports, cargo_types, config.clone());
+        game_state.stats.ironman = ironman;
+        game_state.stats.real_time_turn_secs = Self::ask_real_time();
 
         // Display selected difficulty settings
         println!("\n=== Game Settings ===");
         println!("Starting Money: ${}", config.starting_money);
         println!("Win Condition: ${}", config.win_condition_money);
         println!("Starting Airport: {}", config.starting_airport);
+        if ironman {
+            println!("🛡️  Ironman: saved after every action, no manual saves, bankruptcy is final");
+        }
+        if let Some(seconds) = game_state.stats.real_time_turn_secs {
+            println!(
+                "⏱️  Real-time: a turn passes every {}s even if you stay put",
+                seconds
+            );
+        }
 
         game_state
     }
 
+    fn ask_ironman() -> bool {
+        print!("Play in ironman mode? No manual saves, and bankruptcy deletes the save (y/n): ");
+        io::stdout().flush().unwrap();
+        Self::get_user_input().trim().eq_ignore_ascii_case("y")
+    }
+
+    fn ask_real_time() -> Option<u32> {
+        print!("Play in real time, with turns passing on their own? (y/n): ");
+        io::stdout().flush().unwrap();
+        if !Self::get_user_input().trim().eq_ignore_ascii_case("y") {
+            return None;
+        }
+        loop {
+            print!(
+                "Seconds per turn ({}-{}, Enter for {}): ",
+                realtime::MIN_TURN_SECONDS,
+                realtime::MAX_TURN_SECONDS,
+                realtime::DEFAULT_TURN_SECONDS
+            );
+            io::stdout().flush().unwrap();
+            let input = Self::get_user_input();
+            if input.is_empty() {
+                return Some(realtime::DEFAULT_TURN_SECONDS);
+            }
+            match realtime::parse_turn_seconds(&input) {
+                Ok(seconds) => return Some(seconds),
+                Err(e) => println!("{}", e),
+            }
+        }
+    }
+
     fn press_enter_to_continue() {
         print!("Press Enter to continue...");
         io::stdout().flush().unwrap();
@@ -784,7 +1352,11 @@ impl TerminalUI {
                         "Just now".to_string()
                     };
 
-                    println!("[{}] - {}", time_str, msg.author_name);
+                    if msg.is_npc {
+                        println!("[{}] - {} (NPC)", time_str, msg.author_name);
+                    } else {
+                        println!("[{}] - {}", time_str, msg.author_name);
+                    }
                     println!("  {}", msg.content);
                     println!();
                 }
@@ -801,7 +1373,10 @@ impl TerminalUI {
             let choice = Self::get_user_input();
             match choice.trim() {
                 "1" => {
-                    print!("Enter your message (max 500 characters): ");
+                    print!(
+                        "Enter your message (max {} characters): ",
+                        game_state.message_board.limits().max_message_length
+                    );
                     io::stdout().flush().unwrap();
                     let content = Self::get_user_input().trim().to_string();
 
@@ -837,8 +1412,8 @@ impl TerminalUI {
         }
     }
 
-    fn handle_save_game(game_state: &GameState) {
-        println!("=== SAVE GAME ===");
+    fn handle_save_game(game_state: &GameState, save_dir: &Path) {
+        println!("{}", style::heading("SAVE GAME"));
         print!("Enter save name (or press Enter for auto-generated): ");
         io::stdout().flush().unwrap();
 
@@ -849,24 +1424,63 @@ impl TerminalUI {
             Some(save_name.trim().to_string())
         };
 
-        match SaveSystem::save_game(game_state, save_name) {
+        match SaveSystem::save_game_to_dir(game_state, save_name, save_dir) {
             Ok(path) => {
-                println!("✓ Game saved successfully!");
+                println!("{}", style::good("✓ Game saved successfully!"));
                 println!("Save location: {}", path.display());
             },
             Err(e) => {
-                println!("✗ Failed to save game: {}", e);
+                println!("{}", style::bad(format!("✗ Failed to save game: {}", e)));
             },
         }
 
         Self::press_enter_to_continue();
     }
 
-    fn handle_load_game() -> Option<GameState> {
-        println!("=== LOAD GAME ===");
+    /// If an autosave was written after the chosen save, the player has
+    /// probably lost progress - offer to pick it up from there instead.
+    fn offer_newer_autosave(save: &SaveInfo, save_dir: &Path) -> SaveInfo {
+        if save.is_autosave() {
+            return save.clone();
+        }
+
+        let Some(newer) = SaveSystem::newer_autosave_in_dir(save.timestamp, save_dir) else {
+            return save.clone();
+        };
+
+        println!(
+            "An autosave from {} is newer than this save (turn {}, ${}, {}).",
+            newer.timestamp.format("%Y-%m-%d %H:%M"),
+            newer.turn,
+            newer.money,
+            newer.location
+        );
+        print!("Load the autosave instead? (y/n): ");
+        io::stdout().flush().unwrap();
+
+        if Self::get_user_input().trim().to_lowercase() == "y" {
+            newer
+        } else {
+            save.clone()
+        }
+    }
+
+    /// Tell the player what changed since their game was saved, e.g. an
+    /// airport that has closed
+    fn report_adjustments(adjustments: &[WorldAdjustment]) {
+        for adjustment in adjustments {
+            println!(
+                "{}",
+                style::warning(format!("⚠️  {}", adjustment.describe()))
+            );
+        }
+    }
+
+    fn handle_load_game(save_dir: &Path) -> Option<GameState> {
+        println!("{}", style::heading("LOAD GAME"));
 
         // List available saves
-        match SaveSystem::list_saves() {
+        match SaveSystem::list_saves_in_dir(save_dir) {
             Ok(saves) => {
                 if saves.is_empty() {
                     println!("No saved games found.");
@@ -877,9 +1491,10 @@ impl TerminalUI {
                 println!("Available saves:");
                 for (i, save) in saves.iter().enumerate() {
                     println!(
-                        "{}. {} - Turn {}, ${}, {} - {}",
+                        "{}. {}{} - Turn {}, ${}, {} - {}",
                         i + 1,
                         save.name,
+                        if save.ironman { " [IRONMAN]" } else { "" },
                         save.turn,
                         save.money,
                         save.location,
@@ -896,13 +1511,14 @@ impl TerminalUI {
                         return None;
                     }
                     if num > 0 && num <= saves.len() {
-                        let save = &saves[num - 1];
-                        match SaveSystem::load_game(&save.file_name) {
-                            Ok(state) => {
-                                return Some(state);
+                        let save = Self::offer_newer_autosave(&saves[num - 1], save_dir);
+                        match SaveSystem::load_game_from_dir_reporting(&save.file_name, save_dir) {
+                            Ok(loaded) => {
+                                Self::report_adjustments(&loaded.adjustments);
+                                return Some(loaded.game_state);
                             },
                             Err(e) => {
-                                println!("✗ Failed to load game: {}", e);
+                                println!("{}", style::bad(format!("✗ Failed to load game: {}", e)));
                                 Self::press_enter_to_continue();
                             },
                         }
@@ -916,7 +1532,7 @@ impl TerminalUI {
                 }
             },
             Err(e) => {
-                println!("✗ Failed to list saves: {}", e);
+                println!("{}", style::bad(format!("✗ Failed to list saves: {}", e)));
                 Self::press_enter_to_continue();
             },
         }
@@ -924,13 +1540,92 @@ impl TerminalUI {
         None
     }
 
-    fn prompt_save_before_quit(game_state: &GameState) {
+    fn prompt_save_before_quit(game_state: &GameState, save_dir: &Path) {
         print!("Would you like to save before quitting? (y/n): ");
         io::stdout().flush().unwrap();
 
         let choice = Self::get_user_input();
         if choice.trim().to_lowercase() == "y" {
-            Self::handle_save_game(game_state);
+            Self::handle_save_game(game_state, save_dir);
+        }
+    }
+
+    /// Pick a pilot profile, or create one. None plays without a profile,
+    /// using the shared save directory.
+    fn select_profile() -> Option<Profile> {
+        let profiles = SaveSystem::list_profiles().unwrap_or_default();
+        let create_choice = profiles.len() + 1;
+
+        println!("{}", style::heading("Pilot Profiles"));
+        for (i, profile) in profiles.iter().enumerate() {
+            println!("{}. {}", i + 1, profile.summary());
+        }
+        println!("{}. Create a new profile", create_choice);
+        println!("0. Play without a profile");
+        print!("Choose a profile (0-{}): ", create_choice);
+        io::stdout().flush().unwrap();
+
+        loop {
+            match Self::get_user_input().parse::<usize>() {
+                Ok(0) => return None,
+                Ok(choice) if choice < create_choice => {
+                    let mut profile = profiles[choice - 1].clone();
+                    profile.last_played = Some(chrono::Local::now());
+                    let _ = SaveSystem::save_profile(&profile);
+                    println!("Welcome back, {}!", profile.name);
+                    return Some(profile);
+                },
+                Ok(choice) if choice == create_choice => {
+                    if let Some(profile) = Self::create_profile() {
+                        return Some(profile);
+                    }
+                    print!("Choose a profile (0-{}): ", create_choice);
+                },
+                _ => print!("Invalid choice. Please enter 0-{}: ", create_choice),
+            }
+            io::stdout().flush().unwrap();
+        }
+    }
+
+    fn create_profile() -> Option<Profile> {
+        print!("Profile name (e.g. Casual, Ironman run): ");
+        io::stdout().flush().unwrap();
+        let name = Self::get_user_input();
+
+        let settings = ProfileSettings {
+            difficulty: Self::select_difficulty(),
+            ironman: Self::ask_ironman(),
+        };
+
+        match SaveSystem::create_profile(&name, settings) {
+            Ok(mut profile) => {
+                profile.last_played = Some(chrono::Local::now());
+                let _ = SaveSystem::save_profile(&profile);
+                println!(
+                    "{}",
+                    style::good(format!("✓ Created profile {}", profile.name))
+                );
+                Some(profile)
+            },
+            Err(e) => {
+                println!(
+                    "{}",
+                    style::bad(format!("✗ Couldn't create profile: {}", e))
+                );
+                None
+            },
+        }
+    }
+
+    fn record_profile_result(profile: &mut Option<Profile>, game_state: &GameState, won: bool) {
+        let Some(profile) = profile else {
+            return;
+        };
+
+        profile.stats.record_game_finished(game_state, won);
+        match SaveSystem::save_profile(profile) {
+            Ok(()) => println!("📒 {}", profile.summary()),
+            Err(e) => println!("⚠️  Couldn't update profile: {}", e),
         }
     }
 }
@@ -945,4 +1640,6 @@ enum MainMenuChoice {
     LoadGame,
     Help,
     Quit,
+    PauseClock,
+    ToggleAdvisor,
 }
@@ -0,0 +1,123 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// How long word of an action that went through stays on screen
+pub const SUCCESS_TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Errors stay up longer, so there's time to read why
+pub const ERROR_TOAST_DURATION: Duration = Duration::from_secs(8);
+
+/// Toasts on screen at once; the rest wait their turn
+pub const MAX_VISIBLE_TOASTS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Success,
+    Error,
+}
+
+impl ToastKind {
+    pub fn duration(self) -> Duration {
+        match self {
+            ToastKind::Success => SUCCESS_TOAST_DURATION,
+            ToastKind::Error => ERROR_TOAST_DURATION,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub kind: ToastKind,
+    pub message: String,
+    /// None while it waits for room on screen
+    shown_at: Option<Instant>,
+}
+
+impl Toast {
+    /// Time left on screen, or None if it isn't up yet
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.shown_at.map(|shown_at| {
+            self.kind
+                .duration()
+                .saturating_sub(now.saturating_duration_since(shown_at))
+        })
+    }
+}
+
+/// What the server made of the player's actions, a few toasts at a time.
+/// Each comes down by itself once its time is up, and a toast waiting in
+/// the queue doesn't start its timer until it's on screen.
+#[derive(Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+    next_id: u64,
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, kind: ToastKind, message: impl Into<String>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.queue.push_back(Toast {
+            id,
+            kind,
+            message: message.into(),
+            shown_at: None,
+        });
+        id
+    }
+
+    /// Take a toast down before its time is up
+    pub fn dismiss(&mut self, id: u64) -> bool {
+        let before = self.queue.len();
+        self.queue.retain(|toast| toast.id != id);
+        self.queue.len() != before
+    }
+
+    /// Take down toasts whose time is up as of `now`, and put waiting ones
+    /// up in their place
+    pub fn tick(&mut self, now: Instant) {
+        self.queue
+            .retain(|toast| toast.remaining(now) != Some(Duration::ZERO));
+        for toast in self.queue.iter_mut().take(MAX_VISIBLE_TOASTS) {
+            toast.shown_at.get_or_insert(now);
+        }
+    }
+
+    /// The toasts on screen, oldest first
+    pub fn visible(&self) -> impl Iterator<Item = &Toast> {
+        self.queue
+            .iter()
+            .take_while(|toast| toast.shown_at.is_some())
+    }
+
+    /// Toasts still waiting for room on screen
+    pub fn waiting(&self) -> usize {
+        self.queue
+            .iter()
+            .filter(|toast| toast.shown_at.is_none())
+            .count()
+    }
+
+    /// How long until the next toast on screen comes down
+    pub fn next_expiry(&self, now: Instant) -> Option<Duration> {
+        self.queue
+            .iter()
+            .filter_map(|toast| toast.remaining(now))
+            .min()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+}
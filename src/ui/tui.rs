@@ -0,0 +1,1288 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Gauge, List, ListItem, Paragraph, Row, Table, TableState},
+};
+use uuid::Uuid;
+
+use crate::{
+    api::models::{
+        EventInfo, FuelRequest, MultiplayerGameStateResponse, TradeAction, TradeRequest,
+        TravelRequest,
+    },
+    models::{
+        MessageTopic,
+        locale::{Locale, Localized},
+        streak::TradeStreak,
+    },
+    systems::{
+        AutosavePolicy, AutosaveTimer, GameState, SaveSystem, TradingSystem, TravelSystem,
+        realtime::TurnClock,
+        route_profits::{RouteAnalytics, RouteProfit},
+    },
+    ui::game_api_client::GameApiClient,
+};
+
+/// How often a connected TUI asks the server for the room
+const SERVER_REFRESH: Duration = Duration::from_secs(2);
+
+/// How often a real-time game's countdown is redrawn
+const CLOCK_REFRESH: Duration = Duration::from_secs(1);
+
+/// Messages shown on the board panel
+const BOARD_MESSAGES: usize = 20;
+
+/// Rows in the routes table
+const ROUTE_ROWS: usize = 30;
+
+/// Everything the panels show, from either backend
+#[derive(Debug, Clone, Default)]
+pub struct TuiSnapshot {
+    pub location_id: String,
+    pub location_name: String,
+    pub turn: u32,
+    pub date: String,
+    pub money: u32,
+    pub streak: TradeStreak,
+    pub fuel: u32,
+    pub max_fuel: u32,
+    pub cargo_weight: u32,
+    pub max_cargo_weight: u32,
+    pub fuel_price: u32,
+    pub market: Vec<MarketRow>,
+    pub destinations: Vec<DestinationRow>,
+    pub messages: Vec<MessageRow>, // Newest first
+    pub news: Vec<String>,
+    pub modifiers: Vec<String>, // Room-wide price and fee modifiers, served games only
+    pub clock: Option<String>,  // Countdown to the next turn in real-time games
+}
+
+#[derive(Debug, Clone)]
+pub struct MarketRow {
+    pub cargo_id: String,
+    pub name: String,
+    pub price: u32,
+    pub usual_price: u32,
+    pub held: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct DestinationRow {
+    pub airport_id: String,
+    pub name: String,
+    pub distance: f64,
+    pub fuel_needed: u32,
+    pub can_travel: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct MessageRow {
+    pub author: String,
+    pub content: String,
+    pub is_npc: bool,
+}
+
+/// Where the TUI's game lives: in this process, or on a server. Actions
+/// answer with a line for the status bar.
+pub trait TuiBackend {
+    fn title(&self) -> String;
+    fn snapshot(&mut self) -> Result<TuiSnapshot, String>;
+    fn trade(
+        &mut self,
+        cargo_id: &str,
+        quantity: u32,
+        action: TradeAction,
+    ) -> Result<String, String>;
+    fn buy_fuel(&mut self, quantity: u32) -> Result<String, String>;
+    fn travel(&mut self, destination: &str) -> Result<String, String>;
+    fn post_message(&mut self, content: String) -> Result<String, String>;
+    /// Routes the pilot can price, best profit per unit of fuel first
+    fn route_profits(&mut self) -> Result<Vec<RouteProfit>, String>;
+
+    /// How often to pull changes made by someone else; None when nobody else can
+    fn refresh_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Let turns pass on their own in a real-time game. Answers with a line
+    /// for the status bar when any did.
+    fn pass_time(&mut self) -> Option<String> {
+        None
+    }
+
+    /// Pause or resume a real-time game's clock
+    fn toggle_clock(&mut self) -> Result<String, String> {
+        Err("Only real-time single-player games have a clock".to_string())
+    }
+
+    /// Anything the player should hear about before they start, e.g. an
+    /// airport that closed since their game was saved
+    fn take_notice(&mut self) -> Option<String> {
+        None
+    }
+}
+
+/// A single-player game, autosaved the same way as the plain CLI
+pub struct LocalBackend {
+    game_state: GameState,
+    autosave: AutosaveTimer,
+    clock: Option<TurnClock>,
+    /// What changed in the autosave to fit today's airports
+    notice: Option<String>,
+}
+
+impl LocalBackend {
+    /// Pick up the latest autosave in `save_dir`, or start a new game there
+    pub fn resume_or_new(save_dir: PathBuf) -> Self {
+        let Ok(loaded) = SaveSystem::load_game_from_dir_reporting("autosave", &save_dir) else {
+            let game_state = GameState::new(
+                crate::data::get_default_airports(),
+                crate::data::get_default_cargo_types(),
+            );
+            return Self::new(game_state).with_save_dir(save_dir);
+        };
+
+        let mut backend = Self::new(loaded.game_state).with_save_dir(save_dir);
+        if !loaded.adjustments.is_empty() {
+            let changes: Vec<String> = loaded
+                .adjustments
+                .iter()
+                .map(|adjustment| adjustment.describe())
+                .collect();
+            backend.notice = Some(changes.join("; "));
+        }
+        backend
+    }
+
+    pub fn new(game_state: GameState) -> Self {
+        let policy = if game_state.stats.ironman {
+            AutosavePolicy::ironman()
+        } else {
+            AutosavePolicy::from_env()
+        };
+        let autosave = AutosaveTimer::new(policy, game_state.turn_number);
+        let clock = TurnClock::for_game(&game_state, Instant::now());
+        Self {
+            game_state,
+            autosave,
+            clock,
+            notice: None,
+        }
+    }
+
+    /// Have turns pass on their own every `seconds`
+    pub fn with_real_time(mut self, seconds: u32) -> Self {
+        self.game_state.stats.real_time_turn_secs = Some(seconds);
+        self.clock = TurnClock::for_game(&self.game_state, Instant::now());
+        self
+    }
+
+    /// Autosave somewhere other than the shared save directory
+    pub fn with_save_dir(mut self, save_dir: PathBuf) -> Self {
+        self.autosave = self.autosave.with_save_dir(save_dir);
+        self
+    }
+
+    fn ensure_playing(&self) -> Result<(), String> {
+        if self.game_state.is_game_won() {
+            Err("You've won! Start a new game from the CLI to play again".to_string())
+        } else if !self.game_state.can_player_continue() {
+            Err("Game over: no way to make money or move on".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn autosave(&mut self) {
+        let _ = if self.game_state.stats.ironman {
+            Some(self.autosave.save_now(&self.game_state))
+        } else {
+            self.autosave.tick(&self.game_state)
+        };
+    }
+}
+
+impl TuiBackend for LocalBackend {
+    fn title(&self) -> String {
+        "Single player".to_string()
+    }
+
+    fn snapshot(&mut self) -> Result<TuiSnapshot, String> {
+        let game_state = &self.game_state;
+        let player = &game_state.player;
+        let airport = game_state
+            .get_current_airport()
+            .ok_or("Current airport not found")?;
+        let market = game_state
+            .get_current_market()
+            .ok_or("No market at this airport")?;
+
+        let mut market_rows: Vec<MarketRow> = market
+            .get_all_cargo_prices()
+            .iter()
+            .filter_map(|(cargo_id, price)| {
+                let cargo_type = game_state.cargo_types.get(cargo_id)?;
+                Some(MarketRow {
+                    cargo_id: cargo_id.clone(),
+                    name: cargo_type.name.clone(),
+                    price: *price,
+                    usual_price: cargo_type.base_price,
+                    held: player.cargo_inventory.get_quantity(cargo_id),
+                })
+            })
+            .collect();
+        market_rows.sort_by(|a, b| a.cargo_id.cmp(&b.cargo_id));
+
+        let mut destinations: Vec<DestinationRow> =
+            TravelSystem::get_reachable_destinations(game_state)
+                .into_iter()
+                .map(|destination| DestinationRow {
+                    airport_id: destination.airport_id,
+                    name: destination.airport_name,
+                    distance: destination.distance_km,
+                    fuel_needed: destination.fuel_needed,
+                    can_travel: destination.can_afford,
+                })
+                .collect();
+        destinations.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+        let messages = game_state
+            .message_board
+            .get_messages(&airport.id, Some(BOARD_MESSAGES))
+            .into_iter()
+            .map(|message| MessageRow {
+                author: message.author_name.clone(),
+                content: message.content.clone(),
+                is_npc: message.is_npc,
+            })
+            .collect();
+
+        Ok(TuiSnapshot {
+            location_id: airport.id.clone(),
+            location_name: airport.name.clone(),
+            turn: game_state.turn_number,
+            date: game_state.date(),
+            money: player.money,
+            streak: player.streak,
+            fuel: player.fuel,
+            max_fuel: player.max_fuel,
+            cargo_weight: player.current_cargo_weight(&game_state.cargo_types),
+            max_cargo_weight: player.max_cargo_weight,
+            fuel_price: market.fuel_price,
+            market: market_rows,
+            destinations,
+            messages,
+            news: game_state
+                .active_events
+                .iter()
+                .map(|event| event.description.clone())
+                .collect(),
+            modifiers: Vec::new(),
+            clock: self
+                .clock
+                .as_ref()
+                .map(|clock| clock.status(Instant::now())),
+        })
+    }
+
+    fn trade(
+        &mut self,
+        cargo_id: &str,
+        quantity: u32,
+        action: TradeAction,
+    ) -> Result<String, String> {
+        self.ensure_playing()?;
+        let game_state = &mut self.game_state;
+        let cargo_name = game_state
+            .cargo_types
+            .get(cargo_id)
+            .map(|cargo_type| cargo_type.name.clone())
+            .unwrap_or_else(|| cargo_id.to_string());
+
+        let message = match action {
+            TradeAction::Buy => {
+                let quote = game_state
+                    .buy_cargo(cargo_id, quantity)
+                    .map_err(|e| format!("Purchase failed: {:?}", e))?
+                    .result;
+                format!(
+                    "Bought {} {} for ${}",
+                    quantity,
+                    cargo_name,
+                    quote.total_cost()
+                )
+            },
+            TradeAction::Sell => {
+                let quote = game_state
+                    .sell_cargo(cargo_id, quantity)
+                    .map_err(|e| format!("Sale failed: {:?}", e))?
+                    .result;
+                format!("Sold {} {} for ${}", quantity, cargo_name, quote.proceeds())
+            },
+        };
+        self.autosave();
+        Ok(message)
+    }
+
+    fn buy_fuel(&mut self, quantity: u32) -> Result<String, String> {
+        self.ensure_playing()?;
+        let game_state = &mut self.game_state;
+        let market = game_state
+            .markets
+            .get_mut(&game_state.player.current_airport)
+            .ok_or("No market at this airport")?;
+        let quantity = quantity.min(TradingSystem::get_max_fuel_buyable(
+            &game_state.player,
+            market,
+        ));
+        let purchase = game_state
+            .buy_fuel(quantity)
+            .map_err(|e| format!("Fuel purchase failed: {:?}", e))?
+            .result;
+        self.autosave();
+        Ok(format!(
+            "Bought {} fuel for ${}",
+            purchase.quantity, purchase.cost
+        ))
+    }
+
+    fn travel(&mut self, destination: &str) -> Result<String, String> {
+        self.ensure_playing()?;
+        let travel_info = self
+            .game_state
+            .fly_to(destination)
+            .map_err(|e| format!("Travel failed: {:?}", e))?;
+        if let Some(clock) = &mut self.clock {
+            clock.restart(Instant::now());
+        }
+        self.autosave();
+        let mut message = format!(
+            "Flew {} → {}: {:.0}km, {} fuel",
+            travel_info.from, travel_info.to, travel_info.distance_km, travel_info.fuel_consumed
+        );
+        if let Some(report) = travel_info.night.report() {
+            message.push_str(&format!(". {}", report));
+        }
+        if let Some(statement) = &travel_info.bank {
+            message.push_str(&format!(". {}", statement.report()));
+        }
+        for alert in &travel_info.price_alerts {
+            message.push_str(&format!(". {}", alert.message()));
+        }
+        Ok(message)
+    }
+
+    fn post_message(&mut self, content: String) -> Result<String, String> {
+        // For single-player, use a pseudo player ID and name
+        let game_state = &mut self.game_state;
+        game_state.message_board.post_message(
+            Uuid::new_v4(),
+            format!("Pilot#{}", game_state.turn_number),
+            content,
+            game_state.player.current_airport.clone(),
+        )?;
+        Ok("Message posted".to_string())
+    }
+
+    fn route_profits(&mut self) -> Result<Vec<RouteProfit>, String> {
+        Ok(RouteAnalytics::for_game(&self.game_state))
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.clock.as_ref().map(|_| CLOCK_REFRESH)
+    }
+
+    fn pass_time(&mut self) -> Option<String> {
+        self.ensure_playing().ok()?;
+        let reports = self
+            .clock
+            .as_mut()?
+            .pass_due_turns(&mut self.game_state, Instant::now());
+        if reports.is_empty() {
+            return None;
+        }
+        self.autosave();
+        let mut message = format!("⏱️ {} turn(s) passed on the ground", reports.len());
+        for report in &reports {
+            if let Some(night) = report.night.report() {
+                message.push_str(&format!(". {}", night));
+            }
+            if let Some(statement) = &report.bank {
+                message.push_str(&format!(". {}", statement.report()));
+            }
+        }
+        Some(message)
+    }
+
+    fn toggle_clock(&mut self) -> Result<String, String> {
+        let clock = self
+            .clock
+            .as_mut()
+            .ok_or("This game isn't played in real time")?;
+        if clock.toggle_pause(Instant::now()) {
+            Ok("Clock paused".to_string())
+        } else {
+            Ok("Clock running".to_string())
+        }
+    }
+
+    fn take_notice(&mut self) -> Option<String> {
+        self.notice.take()
+    }
+}
+
+/// A seat in a multiplayer room, through the same API the GUI uses
+pub struct ServerBackend {
+    client: GameApiClient,
+    room_id: Uuid,
+    player_id: Uuid,
+    room_name: String,
+}
+
+impl ServerBackend {
+    pub fn new(server_address: String, room_id: Uuid, player_id: Uuid) -> Self {
+        Self {
+            client: GameApiClient::new(server_address),
+            room_id,
+            player_id,
+            room_name: String::new(),
+        }
+    }
+
+    fn room_state(&self) -> Result<MultiplayerGameStateResponse, String> {
+        self.client
+            .get_room_state_sync(self.room_id, self.player_id)
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl TuiBackend for ServerBackend {
+    fn title(&self) -> String {
+        if self.room_name.is_empty() {
+            format!("Room {}", self.room_id)
+        } else {
+            self.room_name.clone()
+        }
+    }
+
+    fn snapshot(&mut self) -> Result<TuiSnapshot, String> {
+        let state = self.room_state()?;
+        self.room_name = state.room_info.name.clone();
+        let me = state
+            .players
+            .iter()
+            .find(|player| player.id == Some(state.my_player_id))
+            .ok_or("You're not in this room")?;
+
+        let mut market: Vec<MarketRow> = state
+            .current_market
+            .cargo_prices
+            .iter()
+            .map(|(cargo_id, price)| {
+                let cargo_type = state
+                    .cargo_types
+                    .iter()
+                    .find(|cargo_type| &cargo_type.id == cargo_id);
+                MarketRow {
+                    cargo_id: cargo_id.clone(),
+                    name: cargo_type
+                        .map(|cargo_type| cargo_type.name.clone())
+                        .unwrap_or_else(|| cargo_id.clone()),
+                    price: *price,
+                    usual_price: cargo_type.map_or(*price, |cargo_type| cargo_type.base_price),
+                    held: me.cargo_inventory.get(cargo_id).copied().unwrap_or(0),
+                }
+            })
+            .collect();
+        market.sort_by(|a, b| a.cargo_id.cmp(&b.cargo_id));
+
+        let mut destinations: Vec<DestinationRow> = state
+            .available_destinations
+            .iter()
+            .map(|destination| DestinationRow {
+                airport_id: destination.airport_id.clone(),
+                name: destination.airport_name.clone(),
+                distance: destination.distance,
+                fuel_needed: destination.fuel_required,
+                can_travel: destination.can_travel,
+            })
+            .collect();
+        destinations.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+        // The board is a nice-to-have; keep playing if it doesn't load
+        let messages = self
+            .client
+            .get_messages_sync(self.room_id, self.player_id, None)
+            .map(|board| {
+                board
+                    .messages
+                    .into_iter()
+                    .take(BOARD_MESSAGES)
+                    .map(|message| MessageRow {
+                        author: message.author_name,
+                        content: message.content,
+                        is_npc: message.is_npc,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(TuiSnapshot {
+            location_id: state.current_market.airport_id.clone(),
+            location_name: state.current_market.airport_name.clone(),
+            turn: state.turn_number,
+            date: state.date.clone(),
+            money: me.money,
+            streak: TradeStreak {
+                current: me.streak,
+                best: state.statistics.best_streak,
+            },
+            fuel: me.fuel,
+            max_fuel: me.max_fuel,
+            cargo_weight: me.cargo_weight,
+            max_cargo_weight: me.max_cargo_weight,
+            fuel_price: state.current_market.fuel_price,
+            market,
+            destinations,
+            messages,
+            news: state
+                .active_events
+                .iter()
+                .map(EventInfo::headline)
+                .collect(),
+            modifiers: state
+                .active_modifiers
+                .iter()
+                .map(|modifier| modifier.describe())
+                .collect(),
+            clock: None,
+        })
+    }
+
+    fn trade(
+        &mut self,
+        cargo_id: &str,
+        quantity: u32,
+        action: TradeAction,
+    ) -> Result<String, String> {
+        let request = TradeRequest {
+            cargo_type: cargo_id.to_string(),
+            quantity,
+            action,
+        };
+        let response = self
+            .client
+            .player_trade_sync(self.room_id, self.player_id, &request, Uuid::new_v4())
+            .map_err(|e| e.to_string())?;
+        if response.success {
+            Ok(response.message_in(Locale::current()))
+        } else {
+            Err(response.message_in(Locale::current()))
+        }
+    }
+
+    fn buy_fuel(&mut self, quantity: u32) -> Result<String, String> {
+        let response = self
+            .client
+            .player_buy_fuel_sync(
+                self.room_id,
+                self.player_id,
+                &FuelRequest { quantity },
+                Uuid::new_v4(),
+            )
+            .map_err(|e| e.to_string())?;
+        if response.success {
+            Ok(response.message_in(Locale::current()))
+        } else {
+            Err(response.message_in(Locale::current()))
+        }
+    }
+
+    fn travel(&mut self, destination: &str) -> Result<String, String> {
+        let request = TravelRequest {
+            destination: destination.to_string(),
+        };
+        let response = self
+            .client
+            .player_travel_sync(self.room_id, self.player_id, &request, Uuid::new_v4())
+            .map_err(|e| e.to_string())?;
+        if response.success {
+            Ok(response.message_in(Locale::current()))
+        } else {
+            Err(response.message_in(Locale::current()))
+        }
+    }
+
+    fn post_message(&mut self, content: String) -> Result<String, String> {
+        let response = self
+            .client
+            .post_message_sync(self.room_id, self.player_id, content, MessageTopic::General)
+            .map_err(|e| e.to_string())?;
+        if response.success {
+            Ok("Message posted".to_string())
+        } else {
+            Err(response.message)
+        }
+    }
+
+    fn route_profits(&mut self) -> Result<Vec<RouteProfit>, String> {
+        self.client
+            .get_route_profits_sync(self.room_id, self.player_id)
+            .map(|response| response.routes)
+            .map_err(|e| e.to_string())
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        Some(SERVER_REFRESH)
+    }
+}
+
+/// The panel the arrow keys move around in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Panel {
+    Market,
+    Destinations,
+    Messages,
+}
+
+impl Panel {
+    fn next(self) -> Self {
+        match self {
+            Panel::Market => Panel::Destinations,
+            Panel::Destinations => Panel::Messages,
+            Panel::Messages => Panel::Market,
+        }
+    }
+
+    fn previous(self) -> Self {
+        self.next().next()
+    }
+}
+
+pub struct TuiApp<B: TuiBackend> {
+    backend: B,
+    snapshot: TuiSnapshot,
+    pub focus: Panel,
+    market: TableState,
+    destinations: TableState,
+    pub status: Result<String, String>,
+    /// The message being typed, while the board has the keyboard
+    pub composing: Option<String>,
+    /// Ranked routes, shown in place of the bottom panels while open
+    pub routes: Option<Vec<RouteProfit>>,
+    last_refresh: Instant,
+    pub quit: bool,
+}
+
+impl<B: TuiBackend> TuiApp<B> {
+    pub fn new(backend: B) -> Self {
+        let mut app = Self {
+            backend,
+            snapshot: TuiSnapshot::default(),
+            focus: Panel::Market,
+            market: TableState::default().with_selected(Some(0)),
+            destinations: TableState::default().with_selected(Some(0)),
+            status: Ok("Tab switches panels, ? for keys".to_string()),
+            composing: None,
+            routes: None,
+            last_refresh: Instant::now(),
+            quit: false,
+        };
+        app.refresh();
+        if let Some(notice) = app.backend.take_notice() {
+            app.status = Ok(notice);
+        }
+        app
+    }
+
+    pub fn refresh(&mut self) {
+        match self.backend.snapshot() {
+            Ok(snapshot) => self.snapshot = snapshot,
+            Err(e) => self.status = Err(e),
+        }
+        if self.routes.is_some() {
+            self.load_routes();
+        }
+        self.last_refresh = Instant::now();
+    }
+
+    /// Refresh if the backend wants live updates and it's been long enough
+    pub fn tick(&mut self) {
+        if let Some(status) = self.backend.pass_time() {
+            self.act(Ok(status));
+            return;
+        }
+        if let Some(interval) = self.backend.refresh_interval()
+            && self.last_refresh.elapsed() >= interval
+        {
+            self.refresh();
+        }
+    }
+
+    fn load_routes(&mut self) {
+        match self.backend.route_profits() {
+            Ok(routes) => self.routes = Some(routes),
+            Err(e) => self.status = Err(e),
+        }
+    }
+
+    fn selected_cargo(&self) -> Option<&MarketRow> {
+        self.snapshot.market.get(self.market.selected()?)
+    }
+
+    fn selected_destination(&self) -> Option<&DestinationRow> {
+        self.snapshot
+            .destinations
+            .get(self.destinations.selected()?)
+    }
+
+    fn act(&mut self, result: Result<String, String>) {
+        self.status = result;
+        self.refresh();
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        if let Some(draft) = &mut self.composing {
+            match key.code {
+                KeyCode::Esc => self.composing = None,
+                KeyCode::Enter => {
+                    let content = draft.trim().to_string();
+                    self.composing = None;
+                    if !content.is_empty() {
+                        let result = self.backend.post_message(content);
+                        self.act(result);
+                    }
+                },
+                KeyCode::Backspace => {
+                    draft.pop();
+                },
+                KeyCode::Char(c) => draft.push(c),
+                _ => {},
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Tab => self.focus = self.focus.next(),
+            KeyCode::BackTab => self.focus = self.focus.previous(),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Char('r') => {
+                self.refresh();
+                self.status = Ok("Refreshed".to_string());
+            },
+            KeyCode::Char('f') => {
+                let room = self.snapshot.max_fuel.saturating_sub(self.snapshot.fuel);
+                let result = if room == 0 {
+                    Err("The tank is already full".to_string())
+                } else {
+                    self.backend.buy_fuel(room)
+                };
+                self.act(result);
+            },
+            KeyCode::Char('m') => self.composing = Some(String::new()),
+            // Closes the routes table if it's open
+            KeyCode::Char('o') => match self.routes.take() {
+                Some(_) => {},
+                None => self.load_routes(),
+            },
+            KeyCode::Char('p') => {
+                let result = self.backend.toggle_clock();
+                self.act(result);
+            },
+            KeyCode::Char('?') => self.status = Ok(
+                "b/B buy 1/10  s/S sell 1/all  f fill tank  Enter fly  m post  o routes  p pause  r refresh  q quit"
+                    .to_string(),
+            ),
+            KeyCode::Char(c @ ('b' | 'B' | 's' | 'S')) if self.focus == Panel::Market => {
+                let Some(row) = self.selected_cargo() else {
+                    return;
+                };
+                let cargo_id = row.cargo_id.clone();
+                let (action, quantity) = match c {
+                    'b' => (TradeAction::Buy, 1),
+                    'B' => (TradeAction::Buy, 10),
+                    's' => (TradeAction::Sell, 1),
+                    _ => (TradeAction::Sell, row.held.max(1)),
+                };
+                let result = self.backend.trade(&cargo_id, quantity, action);
+                self.act(result);
+            },
+            KeyCode::Enter if self.focus == Panel::Destinations => {
+                let Some(destination) = self.selected_destination() else {
+                    return;
+                };
+                let airport_id = destination.airport_id.clone();
+                let result = self.backend.travel(&airport_id);
+                // New airport, new lists
+                self.market.select(Some(0));
+                self.destinations.select(Some(0));
+                self.act(result);
+            },
+            _ => {},
+        }
+    }
+
+    fn move_selection(&mut self, step: isize) {
+        let (state, len) = match self.focus {
+            Panel::Market => (&mut self.market, self.snapshot.market.len()),
+            Panel::Destinations => (&mut self.destinations, self.snapshot.destinations.len()),
+            Panel::Messages => return,
+        };
+        if len == 0 {
+            return;
+        }
+        let current = state.selected().unwrap_or(0) as isize;
+        state.select(Some((current + step).rem_euclid(len as isize) as usize));
+    }
+
+    fn panel_block(&self, title: &str, panel: Option<Panel>) -> Block<'static> {
+        let block = Block::bordered().title(format!(" {} ", title));
+        if panel.is_some() && panel == Some(self.focus) {
+            block.border_style(Style::new().fg(Color::Cyan))
+        } else {
+            block
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let [header, top, bottom, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Percentage(50),
+            Constraint::Min(6),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [status_area, market_area] =
+            Layout::horizontal([Constraint::Length(36), Constraint::Min(30)]).areas(top);
+        let [destinations_area, messages_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(bottom);
+
+        frame.render_widget(
+            Line::from(vec![
+                Span::from(" KZRK ").bold().reversed(),
+                Span::from(format!(
+                    " {} · {} ({})",
+                    self.backend.title(),
+                    self.snapshot.location_name,
+                    self.snapshot.location_id
+                )),
+            ]),
+            header,
+        );
+        self.render_status(frame, status_area);
+        self.render_market(frame, market_area);
+        if self.routes.is_some() {
+            self.render_routes(frame, bottom);
+        } else {
+            self.render_destinations(frame, destinations_area);
+            self.render_messages(frame, messages_area);
+        }
+
+        let footer_line = match (&self.composing, &self.status) {
+            (Some(draft), _) => {
+                Line::from(format!(" Message: {}▏ (Enter posts, Esc cancels)", draft))
+            },
+            (None, Ok(status)) => Line::from(format!(" {}", status)).fg(Color::Green),
+            (None, Err(error)) => Line::from(format!(" {}", error)).fg(Color::Red),
+        };
+        frame.render_widget(footer_line, footer);
+    }
+
+    fn render_status(&self, frame: &mut Frame, area: Rect) {
+        let block = self.panel_block("Status", None);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let snapshot = &self.snapshot;
+        let mut lines = vec![
+            Line::from(vec![
+                Span::from("Money: "),
+                Span::from(format!("${}", snapshot.money)).green().bold(),
+            ]),
+            Line::from(format!(
+                "Turn {}   Fuel ${}/unit",
+                snapshot.turn, snapshot.fuel_price
+            )),
+            Line::from(snapshot.date.clone()).dim(),
+        ];
+        if snapshot.streak.current > 0 {
+            lines.push(
+                Line::from(format!(
+                    "🔥 {} sale streak (+{}%)",
+                    snapshot.streak.current,
+                    snapshot.streak.bonus_percent()
+                ))
+                .yellow(),
+            );
+        }
+        if let Some(clock) = &snapshot.clock {
+            lines.push(Line::from(clock.clone()).yellow());
+        }
+        for modifier in &snapshot.modifiers {
+            lines.push(Line::from(format!("📢 {}", modifier)).magenta().bold());
+        }
+        let [text, fuel, cargo, news_area] = Layout::vertical([
+            Constraint::Length(lines.len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+        frame.render_widget(Paragraph::new(lines), text);
+        frame.render_widget(gauge("Fuel", snapshot.fuel, snapshot.max_fuel, true), fuel);
+        frame.render_widget(
+            gauge(
+                "Cargo",
+                snapshot.cargo_weight,
+                snapshot.max_cargo_weight,
+                false,
+            ),
+            cargo,
+        );
+        let news: Vec<ListItem> = snapshot
+            .news
+            .iter()
+            .map(|news| ListItem::new(format!("📰 {}", news)).yellow())
+            .collect();
+        frame.render_widget(List::new(news), news_area);
+    }
+
+    fn render_market(&mut self, frame: &mut Frame, area: Rect) {
+        let rows: Vec<Row> = self
+            .snapshot
+            .market
+            .iter()
+            .map(|row| {
+                let price_color = match row.price.cmp(&row.usual_price) {
+                    std::cmp::Ordering::Less => Color::Green,
+                    std::cmp::Ordering::Greater => Color::Red,
+                    std::cmp::Ordering::Equal => Color::Reset,
+                };
+                Row::new(vec![
+                    Line::from(row.name.clone()),
+                    Line::from(format!("${}", row.price))
+                        .fg(price_color)
+                        .right_aligned(),
+                    Line::from(format!("${}", row.usual_price))
+                        .dim()
+                        .right_aligned(),
+                    Line::from(row.held.to_string()).right_aligned(),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(16),
+                Constraint::Length(7),
+                Constraint::Length(7),
+                Constraint::Length(5),
+            ],
+        )
+        .header(Row::new(["Cargo", "  Price", "  Usual", " Held"]).bold())
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .block(self.panel_block("Market · b/s trade", Some(Panel::Market)));
+        frame.render_stateful_widget(table, area, &mut self.market);
+    }
+
+    fn render_destinations(&mut self, frame: &mut Frame, area: Rect) {
+        let rows: Vec<Row> = self
+            .snapshot
+            .destinations
+            .iter()
+            .map(|destination| {
+                let fuel_color = if destination.can_travel {
+                    Color::Reset
+                } else {
+                    Color::Red
+                };
+                Row::new(vec![
+                    Line::from(format!("{} ({})", destination.name, destination.airport_id)),
+                    Line::from(format!("{:.0}km", destination.distance)).right_aligned(),
+                    Line::from(destination.fuel_needed.to_string())
+                        .fg(fuel_color)
+                        .right_aligned(),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(20),
+                Constraint::Length(8),
+                Constraint::Length(5),
+            ],
+        )
+        .header(Row::new(["Destination", "Distance", " Fuel"]).bold())
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .block(self.panel_block("Fly · Enter", Some(Panel::Destinations)));
+        frame.render_stateful_widget(table, area, &mut self.destinations);
+    }
+
+    fn render_routes(&self, frame: &mut Frame, area: Rect) {
+        let routes = self.routes.as_deref().unwrap_or_default();
+        let rows: Vec<Row> = routes
+            .iter()
+            .take(ROUTE_ROWS)
+            .map(|route| {
+                let profit_color = if route.profit > 0 {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+                let row = Row::new(vec![
+                    Line::from(route.cargo_type.clone()),
+                    Line::from(format!("{} ${}", route.buy_at, route.buy_price)),
+                    Line::from(format!("{} ${}", route.sell_at, route.sell_price)),
+                    Line::from(format!("${}", route.profit))
+                        .fg(profit_color)
+                        .right_aligned(),
+                    Line::from(route.fuel_needed.to_string()).right_aligned(),
+                    Line::from(format!("${:.1}", route.profit_per_fuel))
+                        .fg(profit_color)
+                        .right_aligned(),
+                ]);
+                if route.stale { row.dim() } else { row }
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(12),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(9),
+                Constraint::Length(5),
+                Constraint::Length(9),
+            ],
+        )
+        .header(
+            Row::new([
+                "Cargo",
+                "Buy at",
+                "Sell at",
+                "   Profit",
+                " Fuel",
+                " Per fuel",
+            ])
+            .bold(),
+        )
+        .block(self.panel_block("Routes by profit per fuel, full hold · o to close", None));
+        frame.render_widget(table, area);
+    }
+
+    fn render_messages(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .snapshot
+            .messages
+            .iter()
+            .map(|message| {
+                let author = if message.is_npc {
+                    Span::from(format!("{} (NPC): ", message.author)).dim()
+                } else {
+                    Span::from(format!("{}: ", message.author)).bold()
+                };
+                ListItem::new(Line::from(vec![
+                    author,
+                    Span::from(message.content.clone()),
+                ]))
+            })
+            .collect();
+        let list = if items.is_empty() {
+            List::new([ListItem::new("No messages at this airport yet").dim()])
+        } else {
+            List::new(items)
+        };
+        frame.render_widget(
+            list.block(self.panel_block("Message Board · m to post", Some(Panel::Messages))),
+            area,
+        );
+    }
+}
+
+/// A one-line gauge that goes red as it runs out (fuel) or fills up (cargo)
+fn gauge(label: &str, current: u32, max: u32, fuller_is_better: bool) -> Gauge<'static> {
+    let ratio = if max == 0 {
+        0.0
+    } else {
+        (current as f64 / max as f64).clamp(0.0, 1.0)
+    };
+    let level = if fuller_is_better { ratio } else { 1.0 - ratio };
+    let color = if level > 0.5 {
+        Color::Green
+    } else if level > 0.25 {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+    Gauge::default()
+        .ratio(ratio)
+        .label(format!("{} {}/{}", label, current, max))
+        .gauge_style(Style::new().fg(color).bg(Color::DarkGray))
+}
+
+/// Run the TUI until the player quits, putting the terminal back afterwards
+pub fn run<B: TuiBackend>(backend: B) -> std::io::Result<()> {
+    let mut app = TuiApp::new(backend);
+    let mut terminal = ratatui::init();
+    let result = run_app(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn run_app<B: TuiBackend>(
+    terminal: &mut DefaultTerminal,
+    app: &mut TuiApp<B>,
+) -> std::io::Result<()> {
+    while !app.quit {
+        terminal.draw(|frame| app.render(frame))?;
+        if event::poll(Duration::from_millis(250))?
+            && let Event::Key(key) = event::read()?
+        {
+            app.handle_key(key);
+        }
+        app.tick();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn local_app() -> TuiApp<LocalBackend> {
+        let game_state = GameState::new(
+            crate::data::get_default_airports(),
+            crate::data::get_default_cargo_types(),
+        );
+        let save_dir = std::env::temp_dir().join("kzrk_tui_test_saves");
+        TuiApp::new(LocalBackend::new(game_state).with_save_dir(save_dir))
+    }
+
+    fn press(app: &mut TuiApp<LocalBackend>, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_keys_trade_fly_and_post() {
+        let mut app = local_app();
+        let start = app.snapshot.clone();
+        assert!(!start.market.is_empty());
+
+        // Buy one of the second cargo type on the board
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char('b'));
+        assert!(app.status.is_ok(), "{:?}", app.status);
+        assert_eq!(app.snapshot.market[1].held, 1);
+        assert!(app.snapshot.money < start.money);
+
+        press(&mut app, KeyCode::Char('f'));
+        assert_eq!(app.snapshot.fuel, app.snapshot.max_fuel);
+
+        // Destinations only take Enter once they have the focus
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.snapshot.location_id, start.location_id);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.focus, Panel::Destinations);
+        press(&mut app, KeyCode::Enter);
+        assert_ne!(
+            app.snapshot.location_id, start.location_id,
+            "{:?}",
+            app.status
+        );
+
+        press(&mut app, KeyCode::Char('m'));
+        for c in "hello".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.composing, None);
+        assert_eq!(app.snapshot.messages[0].content, "hello");
+
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn test_panels_render() {
+        let mut app = local_app();
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        for panel in ["Status", "Market", "Fly", "Message Board"] {
+            assert!(screen.contains(panel), "{} panel missing", panel);
+        }
+    }
+    #[test]
+    fn test_routes_are_listed_best_first() {
+        let mut app = local_app();
+        press(&mut app, KeyCode::Char('o'));
+        let routes = app.routes.clone().unwrap();
+        assert!(!routes.is_empty());
+        assert!(
+            routes
+                .windows(2)
+                .all(|pair| pair[0].profit_per_fuel >= pair[1].profit_per_fuel)
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Routes by profit per fuel"));
+
+        press(&mut app, KeyCode::Char('o'));
+        assert!(app.routes.is_none());
+    }
+
+    #[test]
+    fn test_real_time_games_show_and_pause_their_clock() {
+        let mut app = local_app();
+        press(&mut app, KeyCode::Char('p'));
+        assert!(app.status.is_err(), "only real-time games have a clock");
+        assert!(app.snapshot.clock.is_none());
+
+        let game_state = GameState::new(
+            crate::data::get_default_airports(),
+            crate::data::get_default_cargo_types(),
+        );
+        let save_dir = std::env::temp_dir().join("kzrk_tui_test_saves");
+        let backend = LocalBackend::new(game_state)
+            .with_save_dir(save_dir)
+            .with_real_time(60);
+        let mut app = TuiApp::new(backend);
+        assert!(app.snapshot.clock.as_ref().unwrap().contains("Next turn"));
+
+        press(&mut app, KeyCode::Char('p'));
+        assert_eq!(app.status, Ok("Clock paused".to_string()));
+        assert!(app.snapshot.clock.as_ref().unwrap().contains("paused"));
+    }
+}
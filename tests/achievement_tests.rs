@@ -0,0 +1,173 @@
+use uuid::Uuid;
+
+use kzrk::api::models::{TradeAction, TradeRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::models::Airport;
+use kzrk::systems::{
+    RoomSettings,
+    achievements::{Achievement, FIRST_MILLION},
+};
+
+fn airport(id: &str, name: &str, coordinates: (f64, f64)) -> Airport {
+    Airport::new(id, name, coordinates, 80, vec![], vec![], 1.0)
+}
+
+/// New York's three airports, all a short hop apart
+fn new_york() -> Vec<Airport> {
+    vec![
+        airport("JFK", "New York JFK", (40.6413, -73.7781)),
+        airport("EWR", "Newark", (40.6895, -74.1745)),
+        airport("LGA", "LaGuardia", (40.7769, -73.8740)),
+    ]
+}
+
+fn new_york_room(
+    service: &MultiplayerGameService,
+    pilot_name: &str,
+    settings: RoomSettings,
+) -> (Uuid, Uuid) {
+    let room = service
+        .create_room_with_settings(
+            format!("{}'s room", pilot_name),
+            pilot_name.to_string(),
+            None,
+            RoomSettings {
+                custom_airports: new_york(),
+                fuel_outage_chance: 0.0,
+                ..settings
+            },
+        )
+        .unwrap();
+    (room.room_id, room.host_player_id)
+}
+
+fn unlocked(service: &MultiplayerGameService, pilot_name: &str) -> Vec<Achievement> {
+    service
+        .get_pilot_profile(pilot_name)
+        .unwrap()
+        .achievements
+        .into_iter()
+        .filter(|info| info.unlocked_at.is_some())
+        .map(|info| info.achievement)
+        .collect()
+}
+
+fn cheap_food() -> TradeRequest {
+    TradeRequest {
+        cargo_type: "food".to_string(),
+        quantity: 1,
+        action: TradeAction::Buy,
+    }
+}
+
+#[test]
+fn test_globetrotter_goes_on_the_profile() {
+    let service = MultiplayerGameService::new_in_memory();
+    let (room_id, player_id) = new_york_room(
+        &service,
+        "Amelia",
+        RoomSettings {
+            cargo_loss_chance: 0.0,
+            ..RoomSettings::default()
+        },
+    );
+
+    let profile = service.get_pilot_profile("Amelia").unwrap();
+    assert_eq!(profile.unlocked, 0);
+    assert_eq!(profile.achievements.len(), Achievement::ALL.len());
+
+    // The starting airport counts, so two more to go
+    for destination in ["EWR", "LGA"] {
+        let transfer = service
+            .player_ground_transfer(room_id, player_id, destination.to_string())
+            .unwrap();
+        assert!(transfer.success, "{}", transfer.message);
+    }
+
+    let profile = service.get_pilot_profile("Amelia").unwrap();
+    assert_eq!(profile.unlocked, 1);
+    let globetrotter = &profile.achievements[0];
+    assert_eq!(globetrotter.achievement, Achievement::Globetrotter);
+    assert_eq!(globetrotter.room_name.as_deref(), Some("Amelia's room"));
+    assert!(
+        profile.achievements[1..]
+            .iter()
+            .all(|info| info.unlocked_at.is_none())
+    );
+
+    // The room state carries it too, for the GUI's unlock toasts
+    let state = service.get_room_state(room_id, player_id).unwrap();
+    assert_eq!(state.achievements.len(), 1);
+    assert_eq!(state.achievements[0].achievement, Achievement::Globetrotter);
+}
+
+#[test]
+fn test_surviving_a_cargo_loss_is_a_storm() {
+    let service = MultiplayerGameService::new_in_memory();
+    let (room_id, player_id) = new_york_room(
+        &service,
+        "Bessie",
+        RoomSettings {
+            cargo_loss_chance: 1.0,
+            ..RoomSettings::default()
+        },
+    );
+    let bought = service
+        .player_trade(
+            room_id,
+            player_id,
+            TradeRequest {
+                quantity: 10,
+                ..cheap_food()
+            },
+        )
+        .unwrap();
+    assert!(bought.success, "{}", bought.message);
+    assert!(unlocked(&service, "Bessie").is_empty());
+
+    let flight = service
+        .player_travel(room_id, player_id, "EWR".to_string())
+        .unwrap();
+    assert!(flight.success, "{}", flight.message);
+    assert_eq!(
+        unlocked(&service, "Bessie"),
+        vec![Achievement::StormSurvivor]
+    );
+}
+
+#[test]
+fn test_achievements_carry_across_rooms() {
+    let service = MultiplayerGameService::new_in_memory();
+    let rich = RoomSettings {
+        starting_money: FIRST_MILLION + 10_000,
+        win_condition_money: 2 * FIRST_MILLION,
+        cargo_loss_chance: 0.0,
+        ..RoomSettings::default()
+    };
+    let (room_id, player_id) = new_york_room(&service, "Jackie", rich.clone());
+
+    // Checked once something has happened in the room
+    assert!(unlocked(&service, "Jackie").is_empty());
+    service
+        .player_trade(room_id, player_id, cheap_food())
+        .unwrap();
+    assert_eq!(
+        unlocked(&service, "Jackie"),
+        vec![Achievement::FirstMillion]
+    );
+
+    // Earning it again in another room doesn't add a second entry
+    let (room_id, player_id) = new_york_room(&service, "Jackie", rich);
+    service
+        .player_trade(room_id, player_id, cheap_food())
+        .unwrap();
+    let profile = service.get_pilot_profile("Jackie").unwrap();
+    assert_eq!(profile.unlocked, 1);
+    assert_eq!(
+        profile.achievements[0].room_name.as_deref(),
+        Some("Jackie's room")
+    );
+
+    // Profiles belong to the pilot name
+    assert!(unlocked(&service, "Somebody Else").is_empty());
+}
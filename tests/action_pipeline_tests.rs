@@ -0,0 +1,109 @@
+use kzrk::api::{
+    models::{CreateGameRequest, FuelRequest, TradeAction, TradeRequest, TravelRequest},
+    service::GameService,
+};
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{GameState, trading::TradingError};
+
+fn game() -> GameState {
+    let mut game_state = GameState::new(get_default_airports(), get_default_cargo_types());
+    game_state.cheat_mode = false;
+    game_state.player.current_airport = "JFK".to_string();
+    game_state
+}
+
+#[test]
+fn test_buying_fuel_records_what_went_into_the_tank() {
+    let mut game_state = game();
+    game_state.player.fuel = game_state.player.max_fuel - 5;
+    let money = game_state.player.money;
+
+    let completed = game_state.buy_fuel(20).unwrap();
+    assert_eq!(completed.result.quantity, 5, "only 5 units fit");
+    assert_eq!(completed.turn, None, "refueling doesn't cost a turn");
+    assert_eq!(game_state.player.money, money - completed.result.cost);
+    assert_eq!(game_state.stats.total_fuel_purchased, 5);
+    assert_eq!(game_state.turn_number, 1);
+
+    assert!(matches!(
+        game_state.buy_fuel(10),
+        Err(TradingError::InsufficientCapacity)
+    ));
+    assert_eq!(game_state.stats.total_fuel_purchased, 5);
+}
+
+#[test]
+fn test_trades_and_flights_record_statistics() {
+    let mut game_state = game();
+    game_state.player.fuel = game_state.player.max_fuel;
+
+    let bought = game_state.buy_cargo("food", 2).unwrap();
+    assert_eq!(bought.turn, None);
+    let sold = game_state.sell_cargo("food", 1).unwrap();
+    assert_eq!(sold.result.quantity, 1);
+    assert_eq!(game_state.stats.total_trades, 2);
+    assert!(game_state.sell_cargo("food", 5).is_err());
+    assert_eq!(game_state.stats.total_trades, 2);
+
+    let travel_info = game_state.fly_to("ORD").unwrap();
+    assert_eq!(game_state.turn_number, 2);
+    assert_eq!(
+        game_state.stats.total_fuel_consumed,
+        travel_info.fuel_consumed
+    );
+    assert!(
+        game_state
+            .stats
+            .airports_visited
+            .contains(&"ORD".to_string())
+    );
+}
+
+#[test]
+fn test_the_api_takes_turns_like_the_terminal() {
+    let service = GameService::new();
+    let created = service
+        .create_game(CreateGameRequest {
+            player_name: "Solo".to_string(),
+            starting_money: None,
+            starting_airport: Some("JFK".to_string()),
+        })
+        .unwrap();
+    let session_id = created.session_id;
+
+    let fuel = service
+        .buy_fuel(session_id, FuelRequest { quantity: 10 })
+        .unwrap();
+    assert!(fuel.success, "{}", fuel.message);
+    let trade = service
+        .trade(
+            session_id,
+            TradeRequest {
+                cargo_type: "food".to_string(),
+                quantity: 1,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(trade.success, "{}", trade.message);
+    let travel = service
+        .travel(
+            session_id,
+            TravelRequest {
+                destination: "ORD".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+
+    let mut terminal = game();
+    terminal.player.fuel = 0;
+    terminal.buy_fuel(10).unwrap();
+    terminal.player.fuel = terminal.player.max_fuel;
+    terminal.buy_cargo("food", 1).unwrap();
+    terminal.fly_to("ORD").unwrap();
+
+    let game_state = service.get_game_state(session_id).unwrap();
+    assert_eq!(game_state.turn_number, terminal.turn_number);
+    assert_eq!(terminal.stats.total_fuel_purchased, 10);
+}
@@ -0,0 +1,112 @@
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{
+    GameState,
+    advisor::{AdvisedAction, Advisor},
+};
+
+/// Airport, cargo and price
+type Price = (&'static str, &'static str, u32);
+
+/// A game at JFK where only the given prices are known
+fn game_with_prices(prices: &[Price]) -> GameState {
+    let mut game_state = GameState::new(get_default_airports(), get_default_cargo_types());
+    game_state.cheat_mode = false;
+    game_state.player.current_airport = "JFK".to_string();
+    for market in game_state.markets.values_mut() {
+        market.cargo_prices.clear();
+    }
+    for (airport_id, cargo_id, price) in prices {
+        game_state
+            .markets
+            .get_mut(*airport_id)
+            .unwrap()
+            .cargo_prices
+            .insert(cargo_id.to_string(), *price);
+    }
+    game_state
+}
+
+#[test]
+fn test_empty_hold_buys_what_pays_best_from_here() {
+    let mut game_state = game_with_prices(&[
+        ("JFK", "electronics", 100),
+        ("ORD", "electronics", 300),
+        ("JFK", "food", 50),
+        ("ORD", "food", 40),
+    ]);
+    game_state.player.money = 1_000;
+
+    let advice = Advisor::advise(&game_state).unwrap();
+    assert_eq!(
+        advice.action,
+        AdvisedAction::Buy {
+            cargo_type: "electronics".to_string(),
+            units: 10,
+        }
+    );
+    assert!(advice.reason.contains("$300 at ORD"), "{}", advice.reason);
+}
+
+#[test]
+fn test_a_full_hold_flies_to_the_best_sale_fueling_up_first() {
+    let mut game_state =
+        game_with_prices(&[("JFK", "electronics", 100), ("ORD", "electronics", 300)]);
+    game_state
+        .player
+        .cargo_inventory
+        .add_cargo("electronics", 5);
+    game_state.player.fuel = game_state.player.max_fuel;
+
+    let advice = Advisor::advise(&game_state).unwrap();
+    assert_eq!(
+        advice.action,
+        AdvisedAction::Fly {
+            airport_id: "ORD".to_string()
+        }
+    );
+    assert!(advice.reason.contains("$1000 more"), "{}", advice.reason);
+
+    game_state.player.fuel = 0;
+    let advice = Advisor::advise(&game_state).unwrap();
+    assert!(
+        matches!(advice.action, AdvisedAction::BuyFuel { units } if units > 0),
+        "{:?}",
+        advice
+    );
+}
+
+#[test]
+fn test_cargo_nobody_pays_more_for_is_sold_here() {
+    let mut game_state =
+        game_with_prices(&[("JFK", "electronics", 300), ("ORD", "electronics", 100)]);
+    game_state
+        .player
+        .cargo_inventory
+        .add_cargo("electronics", 5);
+
+    let advice = Advisor::advise(&game_state).unwrap();
+    assert_eq!(
+        advice.action,
+        AdvisedAction::Sell {
+            cargo_type: "electronics".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_nothing_here_points_to_where_the_best_run_starts() {
+    let mut game_state =
+        game_with_prices(&[("ORD", "electronics", 100), ("LAX", "electronics", 400)]);
+    game_state.player.fuel = game_state.player.max_fuel;
+
+    let advice = Advisor::advise(&game_state).unwrap();
+    assert_eq!(
+        advice.action,
+        AdvisedAction::Fly {
+            airport_id: "ORD".to_string()
+        }
+    );
+
+    let flat = game_with_prices(&[("JFK", "electronics", 100), ("ORD", "electronics", 100)]);
+    assert!(Advisor::advise(&flat).is_none());
+}
@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::get_default_cargo_types;
+use kzrk::data::import::{ImportOptions, airports_from_csv};
+use kzrk::systems::RoomSettings;
+
+const OUR_AIRPORTS: &str = r#""id","ident","type","name","latitude_deg","longitude_deg","iata_code"
+3622,"KJFK","large_airport","John F Kennedy International Airport",40.639447,-73.779317,"JFK"
+1,"00A","heliport","Total RF Heliport",40.070985,-74.933689,""
+3484,"KLAX","large_airport","Los Angeles International Airport",33.942501,-118.407997,"LAX"
+3754,"KORD","large_airport","Chicago O'Hare International Airport",41.9786,-87.9048,"ORD"
+2,"PANC","large_airport","Ted Stevens Anchorage International Airport, ""Alaska""",61.1744,-149.996002,"ANC"
+3622,"KJFK","large_airport","John F Kennedy International Airport (again)",40.639447,-73.779317,"JFK"
+"#;
+
+fn large_airports() -> ImportOptions {
+    ImportOptions {
+        types: vec!["large_airport".to_string()],
+        limit: None,
+    }
+}
+
+#[test]
+fn test_imports_our_airports_export() {
+    let cargo_types = get_default_cargo_types();
+    let airports = airports_from_csv(OUR_AIRPORTS, &large_airports(), &cargo_types).unwrap();
+
+    let ids: Vec<&str> = airports.iter().map(|airport| airport.id.as_str()).collect();
+    assert_eq!(ids, vec!["KJFK", "KLAX", "KORD", "PANC"]);
+    assert_eq!(
+        airports[3].name,
+        "Ted Stevens Anchorage International Airport, \"Alaska\""
+    );
+    assert_eq!(airports[0].coordinates, (40.639447, -73.779317));
+
+    for airport in &airports {
+        let profile = &airport.market_profile;
+        assert_eq!(profile.produces.len(), 2);
+        assert_eq!(profile.consumes.len(), 2);
+        let distinct: HashSet<&String> = profile.produces.iter().chain(&profile.consumes).collect();
+        assert_eq!(distinct.len(), 4);
+        airport.validate(&cargo_types).unwrap();
+    }
+
+    // Anchorage is a long way from anywhere; JFK has neighbors
+    assert!(airports[3].base_fuel_price > airports[0].base_fuel_price);
+}
+
+#[test]
+fn test_import_is_repeatable() {
+    let cargo_types = get_default_cargo_types();
+    let first = airports_from_csv(OUR_AIRPORTS, &large_airports(), &cargo_types).unwrap();
+    let second = airports_from_csv(OUR_AIRPORTS, &large_airports(), &cargo_types).unwrap();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_plain_csv_types_and_limit() {
+    let cargo_types = get_default_cargo_types();
+    let plain = "icao,name,lat,lon\negll,London Heathrow,51.4706,-0.461941\n\
+                 LFPG,Paris Charles de Gaulle,49.012798,2.55\n";
+    let airports = airports_from_csv(plain, &ImportOptions::default(), &cargo_types).unwrap();
+    assert_eq!(airports.len(), 2);
+    assert_eq!(airports[0].id, "EGLL");
+
+    let everything =
+        airports_from_csv(OUR_AIRPORTS, &ImportOptions::default(), &cargo_types).unwrap();
+    assert_eq!(everything.len(), 5);
+
+    let limited = ImportOptions {
+        limit: Some(2),
+        ..large_airports()
+    };
+    let airports = airports_from_csv(OUR_AIRPORTS, &limited, &cargo_types).unwrap();
+    assert_eq!(airports.len(), 2);
+}
+
+#[test]
+fn test_import_errors() {
+    let cargo_types = get_default_cargo_types();
+    let options = ImportOptions::default();
+
+    let error = airports_from_csv("icao,name,lat\n", &options, &cargo_types).unwrap_err();
+    assert!(error.contains("longitude"), "{}", error);
+
+    let error = airports_from_csv(
+        "icao,name,lat,lon\nEGLL,Heathrow,north,west\n",
+        &options,
+        &cargo_types,
+    )
+    .unwrap_err();
+    assert!(error.contains("Line 2"), "{}", error);
+
+    let nothing = ImportOptions {
+        types: vec!["seaplane_base".to_string()],
+        limit: None,
+    };
+    assert!(airports_from_csv(OUR_AIRPORTS, &nothing, &cargo_types).is_err());
+    assert!(airports_from_csv("", &options, &cargo_types).is_err());
+}
+
+#[test]
+fn test_imported_airports_make_a_room() {
+    let airports =
+        airports_from_csv(OUR_AIRPORTS, &large_airports(), &get_default_cargo_types()).unwrap();
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Real World".to_string(),
+            "Host".to_string(),
+            Some(2),
+            RoomSettings {
+                custom_airports: airports,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(room.starting_airport, "KJFK");
+}
@@ -183,6 +183,7 @@ async fn test_complete_gameplay_flow() {
 
     assert_eq!(final_state["player"]["current_airport"], "ORD");
     assert_eq!(final_state["turn_number"], 2); // 2 turns: initial + 1 travel
+    assert_eq!(final_state["world_tick"], 2);
     assert!(final_state["statistics"]["cargo_trades"].as_u64().unwrap() > 0);
     assert!(
         final_state["statistics"]["distances_traveled"]
@@ -242,7 +243,7 @@ async fn test_error_scenarios() {
     assert_eq!(response.status(), 400);
 
     let travel_error: Value = response.json().await.unwrap();
-    assert_eq!(travel_error["error"], "TravelError");
+    assert_eq!(travel_error["error"], "ActionRejected");
 
     // Test insufficient fuel for travel
     let long_distance_travel = json!({
@@ -0,0 +1,165 @@
+use uuid::Uuid;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::models::{Airport, FuelEconomy};
+use kzrk::systems::{
+    RoomSettings,
+    auto_refuel::AutoRefuel,
+    events::EventConfig,
+    multiplayer::{STARTING_MAX_FUEL, SpawnRule},
+};
+
+/// Two fields with fuel at a steady $50 a unit, starting at the first
+fn steady_fuel_room() -> (MultiplayerGameService, Uuid, Uuid) {
+    let field = |id: &str, lat: f64| {
+        Airport::new(id, id, (lat, -75.0), 50, vec![], vec![], 1.0)
+            .with_fuel_economy(FuelEconomy::new(50, 0.0, 50, 50))
+    };
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Pit Stop".to_string(),
+            "Pilot".to_string(),
+            Some(2),
+            RoomSettings {
+                custom_airports: vec![field("AAA", 40.0), field("BBB", 45.0)],
+                spawn: SpawnRule::HostChosen("AAA".to_string()),
+                events: EventConfig {
+                    chance: 0.0,
+                    ..EventConfig::default()
+                },
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    (service, room.room_id, room.host_player_id)
+}
+
+fn pilot_fuel_and_money(
+    service: &MultiplayerGameService,
+    room_id: Uuid,
+    pilot: Uuid,
+) -> (u32, u32) {
+    let state = service.get_room_state(room_id, pilot).unwrap();
+    let me = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(pilot))
+        .unwrap();
+    (me.fuel, me.money)
+}
+
+#[test]
+fn test_auto_refuel_tops_up_on_landing_and_itemizes_it() {
+    let (service, room_id, pilot) = steady_fuel_room();
+    assert_eq!(
+        service.get_auto_refuel(room_id, pilot).unwrap(),
+        AutoRefuel::default()
+    );
+
+    let order = AutoRefuel {
+        enabled: true,
+        target_percent: 90,
+        max_price: Some(80),
+    };
+    assert_eq!(
+        service.set_auto_refuel(room_id, pilot, order).unwrap(),
+        order
+    );
+
+    let (_, money_before) = pilot_fuel_and_money(&service, room_id, pilot);
+    let response = service
+        .player_travel(room_id, pilot, "BBB".to_string())
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    let receipt = response.auto_refuel.unwrap();
+    let target = STARTING_MAX_FUEL * 90 / 100;
+    assert_eq!(receipt.new_fuel, target);
+    assert_eq!(receipt.price_per_unit, 50);
+    assert_eq!(receipt.cost, receipt.quantity * 50);
+    assert!(response.message.contains(&format!(
+        "Auto-refuel bought {} fuel at $50",
+        receipt.quantity
+    )));
+    // The top-up is free of a turn of its own
+    assert_eq!(
+        service.get_room_state(room_id, pilot).unwrap().turn_number,
+        2
+    );
+
+    let (fuel, money) = pilot_fuel_and_money(&service, room_id, pilot);
+    assert_eq!(fuel, target);
+    assert!(money_before - money >= receipt.cost);
+}
+
+#[test]
+fn test_auto_refuel_waits_for_a_better_price() {
+    let (service, room_id, pilot) = steady_fuel_room();
+    service
+        .set_auto_refuel(
+            room_id,
+            pilot,
+            AutoRefuel {
+                enabled: true,
+                target_percent: 100,
+                max_price: Some(50),
+            },
+        )
+        .unwrap();
+
+    let response = service
+        .player_travel(room_id, pilot, "BBB".to_string())
+        .unwrap();
+    let receipt = response.auto_refuel.unwrap();
+    assert_eq!(receipt.quantity, 0);
+    assert_eq!(receipt.cost, 0);
+    assert!(response.message.contains("Auto-refuel skipped"));
+    let (fuel, _) = pilot_fuel_and_money(&service, room_id, pilot);
+    assert_eq!(fuel, receipt.new_fuel);
+
+    // Turned off, landing buys nothing and says nothing
+    service
+        .set_auto_refuel(room_id, pilot, AutoRefuel::default())
+        .unwrap();
+    let response = service
+        .player_travel(room_id, pilot, "AAA".to_string())
+        .unwrap();
+    assert!(response.auto_refuel.is_none());
+    assert!(!response.message.contains("Auto-refuel"));
+}
+
+#[test]
+fn test_auto_refuel_orders_are_checked() {
+    let (service, room_id, pilot) = steady_fuel_room();
+    let set = |order: AutoRefuel| service.set_auto_refuel(room_id, pilot, order);
+
+    assert!(
+        set(AutoRefuel {
+            target_percent: 0,
+            ..AutoRefuel::default()
+        })
+        .is_err()
+    );
+    assert!(
+        set(AutoRefuel {
+            target_percent: 101,
+            ..AutoRefuel::default()
+        })
+        .is_err()
+    );
+    assert!(
+        set(AutoRefuel {
+            max_price: Some(0),
+            ..AutoRefuel::default()
+        })
+        .is_err()
+    );
+    assert!(
+        service
+            .set_auto_refuel(room_id, Uuid::new_v4(), AutoRefuel::default())
+            .is_err()
+    );
+}
@@ -0,0 +1,117 @@
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::models::Player;
+use kzrk::systems::bank::{BANKRUPTCY_DEBT, BankSystem, CREDIT_LIMIT, MIN_LOAN};
+use kzrk::systems::valuation::ValuationSystem;
+use kzrk::systems::{GameState, RoomSettings};
+
+#[test]
+fn test_borrowing_pays_cash_up_to_the_credit_limit() {
+    let mut player = Player::new(1_000, "JFK", 100, 500, 10.0);
+
+    assert!(BankSystem::borrow(&mut player, MIN_LOAN - 1, 1).is_err());
+    let first = BankSystem::borrow(&mut player, 6_000, 1).unwrap();
+    assert_eq!(player.money, 7_000);
+    assert_eq!(player.loans.debt(), 6_000);
+    assert_eq!(
+        BankSystem::credit_available(&player.loans),
+        CREDIT_LIMIT - 6_000
+    );
+
+    let err = BankSystem::borrow(&mut player, 5_000, 1).unwrap_err();
+    assert!(err.contains("$4000"), "{}", err);
+    let second = BankSystem::borrow(&mut player, 4_000, 2).unwrap();
+    assert_ne!(first, second);
+    assert_eq!(BankSystem::credit_available(&player.loans), 0);
+
+    // Borrowed money is owed against what the pilot is worth
+    let worth = ValuationSystem::value(&player, None, &get_default_cargo_types());
+    assert_eq!(worth.debt, 10_000);
+    assert_eq!(worth.total(), 1_000);
+}
+
+#[test]
+fn test_repaying_never_takes_more_than_is_owed() {
+    let mut player = Player::new(5_000, "JFK", 100, 500, 10.0);
+    let loan_id = BankSystem::borrow(&mut player, 1_000, 1).unwrap();
+
+    assert_eq!(BankSystem::repay(&mut player, loan_id, 400), Ok(400));
+    assert_eq!(player.loans.debt(), 600);
+    assert_eq!(BankSystem::repay(&mut player, loan_id, 10_000), Ok(600));
+    assert!(player.loans.loans.is_empty());
+    assert_eq!(player.money, 5_000);
+    assert!(BankSystem::repay(&mut player, loan_id, 1).is_err());
+}
+
+#[test]
+fn test_unpaid_debt_ends_in_bankruptcy() {
+    let mut player = Player::new(0, "JFK", 100, 500, 10.0);
+    BankSystem::borrow(&mut player, CREDIT_LIMIT, 1).unwrap();
+    player.money = 0;
+    player.cargo_inventory.add_cargo("food", 12);
+
+    let mut statement = None;
+    for _ in 0..50 {
+        statement = BankSystem::charge_turn(&mut player);
+        if player.loans.bankrupt {
+            break;
+        }
+    }
+    let bankruptcy = statement.unwrap().bankruptcy.expect("should go bankrupt");
+    assert!(bankruptcy.debt_written_off > BANKRUPTCY_DEBT);
+    assert_eq!(bankruptcy.cargo_seized, 12);
+    assert!(player.cargo_inventory.is_empty());
+    assert_eq!(player.loans.debt(), 0);
+    assert_eq!(BankSystem::charge_turn(&mut player), None);
+    assert!(BankSystem::borrow(&mut player, MIN_LOAN, 20).is_err());
+}
+
+#[test]
+fn test_a_flight_takes_the_installment() {
+    let mut game_state = GameState::new(get_default_airports(), get_default_cargo_types());
+    game_state.cheat_mode = false;
+    game_state.player.current_airport = "JFK".to_string();
+    game_state.player.fuel = game_state.player.max_fuel;
+    game_state.player.hangar_parking = false;
+    BankSystem::borrow(&mut game_state.player, 1_000, 1).unwrap();
+    let installment = game_state.player.loans.loans[0].installment;
+
+    let travel_info = game_state.fly_to("ORD").unwrap();
+    let statement = travel_info.bank.expect("a loan payment");
+    assert_eq!(statement.paid, installment);
+    assert_eq!(statement.interest, 20);
+    assert_eq!(game_state.player.loans.debt(), 1_020 - installment);
+}
+
+#[test]
+fn test_bank_endpoints_lend_and_take_repayments() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Bank Holiday".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                starting_money: 2_000,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, player_id) = (room.room_id, room.host_player_id);
+
+    let bank = service.get_loans(room_id, player_id).unwrap();
+    assert!(bank.loans.is_empty());
+    assert_eq!(bank.credit_available, CREDIT_LIMIT);
+
+    let bank = service.borrow(room_id, player_id, 3_000).unwrap();
+    assert_eq!(bank.money, 5_000);
+    assert_eq!(bank.debt, 3_000);
+    let loan_id = bank.loans[0].id;
+
+    let bank = service
+        .repay_loan(room_id, player_id, loan_id, 1_000)
+        .unwrap();
+    assert_eq!(bank.money, 4_000);
+    assert_eq!(bank.debt, 2_000);
+    assert!(service.borrow(room_id, player_id, 20_000).is_err());
+}
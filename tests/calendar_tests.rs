@@ -0,0 +1,89 @@
+use chrono::NaiveDate;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::config::GameConfig;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{
+    GameState, RoomSettings,
+    calendar::{GameCalendar, Season},
+    multiplayer::SpawnRule,
+};
+
+fn new_year_calendar() -> GameCalendar {
+    GameCalendar {
+        start: NaiveDate::from_ymd_opt(1990, 1, 1)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap(),
+        hours_per_turn: 12,
+    }
+}
+
+#[test]
+fn test_single_player_dates_follow_the_configured_calendar() {
+    let config = GameConfig {
+        calendar: new_year_calendar(),
+        ..GameConfig::default()
+    };
+    let mut game_state =
+        GameState::new_with_config(get_default_airports(), get_default_cargo_types(), config);
+    assert_eq!(game_state.date(), "Mon 1 Jan 1990, 08:00");
+    assert_eq!(game_state.season(), Season::Winter);
+
+    game_state.advance_turn();
+    game_state.advance_turn();
+    assert_eq!(game_state.date(), "Tue 2 Jan 1990, 08:00");
+}
+
+#[test]
+fn test_room_state_reports_the_date_of_the_players_turn() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "New Year".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                spawn: SpawnRule::HostChosen("JFK".to_string()),
+                landing_slots: false,
+                calendar: new_year_calendar(),
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    assert_eq!(state.date, "Mon 1 Jan 1990, 08:00");
+    assert_eq!(state.calendar, new_year_calendar());
+
+    let travel = service
+        .player_travel(room_id, host_id, "ORD".to_string())
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    assert_eq!(state.turn_number, 2);
+    assert_eq!(state.date, "Mon 1 Jan 1990, 20:00");
+}
+
+#[test]
+fn test_rooms_refuse_turns_of_no_time_or_over_a_week() {
+    let service = MultiplayerGameService::new_in_memory();
+    for hours_per_turn in [0, 24 * 7 + 1] {
+        let err = service
+            .create_room_with_settings(
+                "Timeless".to_string(),
+                "Host".to_string(),
+                None,
+                RoomSettings {
+                    calendar: GameCalendar {
+                        hours_per_turn,
+                        ..GameCalendar::default()
+                    },
+                    ..RoomSettings::default()
+                },
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("hours"), "{}", err);
+    }
+}
@@ -0,0 +1,105 @@
+use rand::{SeedableRng, rngs::StdRng};
+
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::models::{CargoCategory, CargoType, Market};
+use kzrk::systems::events::{EventConfig, EventSystem, MarketEvent, MarketEventType};
+
+fn luxury_crackdown() -> MarketEvent {
+    MarketEvent {
+        event_type: MarketEventType::Recession,
+        affected_cargo: String::new(),
+        affected_category: Some(CargoCategory::Luxury),
+        affected_airport: "JFK".to_string(),
+        price_multiplier: 0.7,
+        duration_turns: 4,
+        turns_remaining: 4,
+        description: "🚔 LUXURY CRACKDOWN".to_string(),
+        injected_by: None,
+    }
+}
+
+#[test]
+fn test_default_cargo_is_sorted_into_categories() {
+    let cargo_types = get_default_cargo_types();
+    let category = |id: &str| cargo_types[id].category;
+
+    assert_eq!(category("food"), CargoCategory::Perishable);
+    assert_eq!(category("luxury"), CargoCategory::Luxury);
+    assert_eq!(category("electronics"), CargoCategory::Luxury);
+    assert_eq!(category("industrial"), CargoCategory::Industrial);
+    assert!(
+        cargo_types
+            .values()
+            .all(|cargo_type| cargo_type.category != CargoCategory::General)
+    );
+}
+
+#[test]
+fn test_cargo_saved_without_a_category_is_general() {
+    let json = r#"{"id":"tea","name":"Tea","base_price":40,"weight_per_unit":1,"volatility":0.2}"#;
+    let cargo_type: CargoType = serde_json::from_str(json).unwrap();
+    assert_eq!(cargo_type.category, CargoCategory::General);
+}
+
+#[test]
+fn test_category_events_move_every_cargo_in_the_category() {
+    let cargo_types = get_default_cargo_types();
+    let mut market = Market::new("JFK", 50);
+    for (id, cargo_type) in &cargo_types {
+        market.set_cargo_price(id, cargo_type.base_price);
+    }
+
+    let event = luxury_crackdown();
+    EventSystem::apply_event_to_market(&event, &mut market, &cargo_types);
+
+    for (id, cargo_type) in &cargo_types {
+        let price = market.get_cargo_price(id).unwrap();
+        if cargo_type.category == CargoCategory::Luxury {
+            assert!(event.affects_cargo(id, &cargo_types));
+            let expected = (cargo_type.base_price as f64 * 0.7_f64).round() as u32;
+            assert!(price.abs_diff(expected) <= 1, "{id}: {price} vs {expected}");
+        } else {
+            assert!(!event.affects_cargo(id, &cargo_types));
+            assert_eq!(price, cargo_type.base_price, "{id} should be untouched");
+        }
+    }
+}
+
+#[test]
+fn test_single_cargo_events_still_hit_one_cargo() {
+    let cargo_types = get_default_cargo_types();
+    let event = MarketEvent {
+        affected_cargo: "food".to_string(),
+        affected_category: None,
+        ..luxury_crackdown()
+    };
+    assert!(event.affects_cargo("food", &cargo_types));
+    assert!(!event.affects_cargo("luxury", &cargo_types));
+}
+
+#[test]
+fn test_random_events_include_category_booms_and_recessions() {
+    let airports = get_default_airports();
+    let cargo_types = get_default_cargo_types();
+    let config = EventConfig::default();
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let category_events: Vec<MarketEvent> = (0..2000)
+        .filter_map(|_| {
+            EventSystem::generate_random_event(&airports, &cargo_types, &config, &mut rng)
+        })
+        .filter(|event| event.affected_category.is_some())
+        .collect();
+    assert!(!category_events.is_empty());
+
+    for event in &category_events {
+        assert!(event.affected_cargo.is_empty());
+        match event.event_type {
+            MarketEventType::Boom => assert!(event.price_multiplier > 1.0),
+            MarketEventType::Recession => assert!(event.price_multiplier < 1.0),
+            ref other => panic!("unexpected category event {other:?}"),
+        }
+        let label = event.affected_category.unwrap().label().to_lowercase();
+        assert!(event.description.to_lowercase().contains(&label));
+    }
+}
@@ -0,0 +1,209 @@
+#![cfg(feature = "chaos")]
+
+use std::time::Duration;
+
+use kzrk::api::{
+    chaos::FaultInjector, multiplayer_service::MultiplayerGameService,
+    routes::create_multiplayer_router,
+};
+use reqwest::{Client, StatusCode};
+use serde_json::{Value, json};
+use tokio::time::{sleep, timeout};
+
+const TOKEN: &str = "let-me-break-things";
+
+async fn start(faults: FaultInjector) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind to address");
+    let base_url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+
+    let service = MultiplayerGameService::new_in_memory().with_fault_injector(faults);
+    let app = create_multiplayer_router(service);
+    tokio::spawn(async move {
+        axum::serve(listener, app)
+            .await
+            .expect("Failed to start server");
+    });
+    sleep(Duration::from_millis(100)).await;
+    base_url
+}
+
+async fn create_room(client: &Client, base_url: &str) -> (String, String) {
+    let response: Value = client
+        .post(format!("{}/rooms", base_url))
+        .json(&json!({"name": "Chaos Room", "host_player_name": "Host"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    (
+        response["room_id"].as_str().unwrap().to_string(),
+        response["host_player_id"].as_str().unwrap().to_string(),
+    )
+}
+
+async fn set_faults(client: &Client, base_url: &str, room_id: &str, faults: Value) -> StatusCode {
+    client
+        .post(format!("{}/admin/rooms/{}/faults", base_url, room_id))
+        .bearer_auth(TOKEN)
+        .json(&faults)
+        .send()
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_admin_api_needs_the_token() {
+    let client = Client::new();
+    let closed = start(FaultInjector::default()).await;
+    let (room_id, _) = create_room(&client, &closed).await;
+    let faults_url = format!("{}/admin/rooms/{}/faults", closed, room_id);
+
+    let response = client
+        .get(&faults_url)
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "AdminUnauthorized");
+
+    let open = start(FaultInjector::default().with_admin_token(TOKEN)).await;
+    let (room_id, _) = create_room(&client, &open).await;
+    let faults_url = format!("{}/admin/rooms/{}/faults", open, room_id);
+    let wrong = client
+        .get(&faults_url)
+        .bearer_auth("guess")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+
+    let body: Value = client
+        .get(&faults_url)
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["enabled"], false);
+
+    let unknown = format!("{}/admin/rooms/{}/faults", open, uuid::Uuid::new_v4());
+    let response = client.get(unknown).bearer_auth(TOKEN).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    assert_eq!(
+        set_faults(&client, &open, &room_id, json!({"error_rate": 1.5})).await,
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn test_error_faults_only_hit_their_room_until_cleared() {
+    let client = Client::new();
+    let base_url = start(FaultInjector::default().with_admin_token(TOKEN)).await;
+    let (broken_room, broken_player) = create_room(&client, &base_url).await;
+    let (healthy_room, healthy_player) = create_room(&client, &base_url).await;
+    let state =
+        |room: &str, player: &str| format!("{}/rooms/{}/players/{}/state", base_url, room, player);
+
+    assert_eq!(
+        set_faults(&client, &base_url, &broken_room, json!({"error_rate": 1.0})).await,
+        StatusCode::OK
+    );
+
+    let response = client
+        .get(state(&broken_room, &broken_player))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "InjectedFault");
+
+    let response = client
+        .get(state(&healthy_room, &healthy_player))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The admin API itself keeps working in a broken room
+    let response = client
+        .delete(format!("{}/admin/rooms/{}/faults", base_url, broken_room))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = client
+        .get(state(&broken_room, &broken_player))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_delayed_requests_still_succeed() {
+    let client = Client::new();
+    let base_url = start(FaultInjector::default().with_admin_token(TOKEN)).await;
+    let (room_id, player_id) = create_room(&client, &base_url).await;
+
+    let faults = json!({"delay_rate": 1.0, "max_delay_ms": 50});
+    assert_eq!(
+        set_faults(&client, &base_url, &room_id, faults).await,
+        StatusCode::OK
+    );
+    let response = client
+        .get(format!(
+            "{}/rooms/{}/players/{}/state",
+            base_url, room_id, player_id
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_stream_events_can_be_dropped() {
+    let client = Client::new();
+    let base_url = start(FaultInjector::default().with_admin_token(TOKEN)).await;
+    let (room_id, _) = create_room(&client, &base_url).await;
+    let stream_url = format!("{}/rooms/{}/stream", base_url, room_id);
+
+    // A healthy stream opens with a snapshot
+    let mut response = client.get(&stream_url).send().await.unwrap();
+    let chunk = timeout(Duration::from_secs(2), response.chunk())
+        .await
+        .expect("no snapshot")
+        .unwrap()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&chunk).contains("snapshot"));
+
+    assert_eq!(
+        set_faults(
+            &client,
+            &base_url,
+            &room_id,
+            json!({"drop_event_rate": 1.0})
+        )
+        .await,
+        StatusCode::OK
+    );
+    let mut response = client.get(&stream_url).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        timeout(Duration::from_millis(500), response.chunk())
+            .await
+            .is_err()
+    );
+}
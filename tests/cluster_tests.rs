@@ -0,0 +1,313 @@
+use std::time::Duration;
+
+use kzrk::api::{
+    cluster::{ClusterConfig, INSTANCE_HEADER},
+    database::{Database, LeaseOutcome},
+    multiplayer_service::{MultiplayerGameService, PersistencePolicy, SaveMode},
+    routes::create_multiplayer_router,
+};
+use reqwest::{Client, StatusCode, redirect::Policy};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+use tokio::time::sleep;
+use uuid::Uuid;
+
+/// An API instance sharing `db_path` with its siblings
+struct Instance {
+    base_url: String,
+}
+
+impl Instance {
+    async fn start(name: &str, db_path: &str, lease_ttl: Duration) -> Self {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind to address");
+        let base_url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+
+        let cluster = ClusterConfig::new(name, base_url.clone()).with_lease_ttl(lease_ttl);
+        let service = MultiplayerGameService::new_with_db_path(db_path).with_cluster(cluster);
+        let app = create_multiplayer_router(service);
+
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .await
+                .expect("Failed to start server");
+        });
+        sleep(Duration::from_millis(100)).await;
+
+        Self { base_url }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+}
+
+fn shared_db() -> (TempDir, String) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cluster.db").to_str().unwrap().to_string();
+    (dir, path)
+}
+
+async fn create_room(client: &Client, instance: &Instance) -> (String, String) {
+    let response: Value = client
+        .post(instance.url("/rooms"))
+        .json(&json!({"name": "Cluster Room", "host_player_name": "Host"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    (
+        response["room_id"].as_str().unwrap().to_string(),
+        response["host_player_id"].as_str().unwrap().to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_room_requests_are_redirected_to_the_owner() {
+    let (_dir, db_path) = shared_db();
+    let alpha = Instance::start("alpha", &db_path, Duration::from_secs(30)).await;
+    let beta = Instance::start("beta", &db_path, Duration::from_secs(30)).await;
+    let client = Client::new();
+
+    let (room_id, player_id) = create_room(&client, &alpha).await;
+    let state_path = format!("/rooms/{}/players/{}/state", room_id, player_id);
+
+    // Beta knows about the room from the shared database but doesn't own it
+    let no_redirects = Client::builder().redirect(Policy::none()).build().unwrap();
+    let response = no_redirects
+        .get(beta.url(&state_path))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+    assert_eq!(
+        response.headers()["location"].to_str().unwrap(),
+        alpha.url(&state_path)
+    );
+
+    // Clients that follow redirects end up at the owner
+    let response = client.get(beta.url(&state_path)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[INSTANCE_HEADER], "alpha");
+
+    // POST bodies survive the redirect
+    let travel: Value = client
+        .post(beta.url(&format!("/rooms/{}/players/{}/travel", room_id, player_id)))
+        .json(&json!({"destination": "ORD"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(travel["success"], true);
+
+    // Both instances list the room
+    let rooms: Value = client
+        .get(beta.url("/rooms"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(
+        rooms
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|room| room["id"] == room_id.as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_expired_lease_fails_over_with_latest_state() {
+    let (_dir, db_path) = shared_db();
+    let lease_ttl = Duration::from_millis(300);
+    let alpha = Instance::start("alpha", &db_path, lease_ttl).await;
+    let beta = Instance::start("beta", &db_path, lease_ttl).await;
+    let client = Client::new();
+
+    let (room_id, player_id) = create_room(&client, &alpha).await;
+    let travel = client
+        .post(alpha.url(&format!("/rooms/{}/players/{}/travel", room_id, player_id)))
+        .json(&json!({"destination": "JFK"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(travel.headers()[INSTANCE_HEADER], "alpha");
+    let travel: Value = travel.json().await.unwrap();
+    assert_eq!(travel["success"], true);
+
+    // Alpha goes quiet and its lease runs out; beta takes the room over from
+    // the state alpha saved
+    sleep(lease_ttl * 2).await;
+    let state_path = format!("/rooms/{}/players/{}/state", room_id, player_id);
+    let response = client.get(beta.url(&state_path)).send().await.unwrap();
+    assert_eq!(response.headers()[INSTANCE_HEADER], "beta");
+    let state: Value = response.json().await.unwrap();
+    assert_eq!(state["players"][0]["current_airport"], "JFK");
+
+    // Now alpha sends the room's players to beta
+    let response = client.get(alpha.url(&state_path)).send().await.unwrap();
+    assert_eq!(response.headers()[INSTANCE_HEADER], "beta");
+}
+
+#[test]
+fn test_only_one_instance_holds_a_lease() {
+    let (_dir, db_path) = shared_db();
+    let alpha_db = Database::new(&db_path).unwrap();
+    let beta_db = Database::new(&db_path).unwrap();
+    let alpha = ClusterConfig::new("alpha", "http://alpha:3000/");
+    let beta = ClusterConfig::new("beta", "http://beta:3000");
+    let room_id = Uuid::new_v4();
+    let now = chrono::Utc::now();
+
+    assert_eq!(
+        alpha_db.acquire_room_lease(&room_id, &alpha, now).unwrap(),
+        LeaseOutcome::Acquired { renewed: false }
+    );
+    assert_eq!(
+        alpha_db.acquire_room_lease(&room_id, &alpha, now).unwrap(),
+        LeaseOutcome::Acquired { renewed: true }
+    );
+
+    match beta_db.acquire_room_lease(&room_id, &beta, now).unwrap() {
+        LeaseOutcome::HeldBy(lease) => {
+            assert_eq!(lease.instance_id, "alpha");
+            assert_eq!(lease.instance_url, "http://alpha:3000");
+        },
+        outcome => panic!("Beta shouldn't get the lease: {:?}", outcome),
+    }
+
+    // Beta takes over once the lease expires, and gives it up on release
+    let later = now + chrono::Duration::from_std(alpha.lease_ttl).unwrap();
+    assert_eq!(
+        beta_db.acquire_room_lease(&room_id, &beta, later).unwrap(),
+        LeaseOutcome::Acquired { renewed: false }
+    );
+    assert_eq!(alpha_db.release_room_leases("beta").unwrap(), 1);
+    assert!(alpha_db.room_lease(&room_id).unwrap().is_none());
+}
+
+#[test]
+fn test_renewing_the_lease_keeps_the_room_in_memory() {
+    let (_dir, db_path) = shared_db();
+    let service = MultiplayerGameService::new_with_db_path(&db_path)
+        .with_cluster(ClusterConfig::new("alpha", "http://alpha:3000"));
+    let room = service
+        .create_room("Leased".to_string(), "Host".to_string(), None)
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    service.room_owner(room_id).unwrap();
+    let money = service.get_room_state(room_id, host_id).unwrap().players[0].money;
+
+    // Someone else writes the room behind this instance's back
+    let db = Database::new(&db_path).unwrap();
+    let mut stored = db.load_room(&room_id).unwrap().unwrap();
+    stored.get_player_mut(&host_id).unwrap().player.money = money + 1;
+    db.save_room(&stored).unwrap();
+
+    // A renewal doesn't read the room again
+    service.room_owner(room_id).unwrap();
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    assert_eq!(state.players[0].money, money);
+
+    // Taking the lease over does
+    db.release_room_leases("alpha").unwrap();
+    service.room_owner(room_id).unwrap();
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    assert_eq!(state.players[0].money, money + 1);
+}
+
+#[test]
+fn test_joining_saves_the_room_for_the_next_owner() {
+    let (_dir, db_path) = shared_db();
+    let service = MultiplayerGameService::new_with_db_path(&db_path)
+        .with_cluster(ClusterConfig::new("alpha", "http://alpha:3000"));
+    let room = service
+        .create_room("Leased".to_string(), "Host".to_string(), None)
+        .unwrap();
+    let joined = service
+        .join_room(room.room_id, "Guest".to_string(), None)
+        .unwrap();
+
+    let stored = Database::new(&db_path)
+        .unwrap()
+        .load_room(&room.room_id)
+        .unwrap()
+        .unwrap();
+    assert!(stored.players.contains_key(&joined.player_id));
+}
+
+#[test]
+fn test_batched_changes_are_written_when_the_lease_is_renewed() {
+    let (_dir, db_path) = shared_db();
+    let service = MultiplayerGameService::new_with_db_path(&db_path)
+        .with_cluster(ClusterConfig::new("alpha", "http://alpha:3000"))
+        .with_persistence_policy(PersistencePolicy {
+            save_mode: SaveMode::Batched(Duration::from_secs(3600)),
+            ..PersistencePolicy::default()
+        });
+    let room = service
+        .create_room("Leased".to_string(), "Host".to_string(), None)
+        .unwrap();
+    let joined = service
+        .join_room(room.room_id, "Guest".to_string(), None)
+        .unwrap();
+    let db = Database::new(&db_path).unwrap();
+    assert!(db.load_room(&room.room_id).unwrap().is_none());
+
+    service.room_owner(room.room_id).unwrap();
+    let stored = db.load_room(&room.room_id).unwrap().unwrap();
+    assert!(stored.players.contains_key(&joined.player_id));
+    // Nothing left for the flush loop
+    assert_eq!(service.flush_dirty_rooms().unwrap(), 0);
+}
+
+#[test]
+fn test_flushes_come_before_the_lease_can_lapse() {
+    let batched = PersistencePolicy {
+        save_mode: SaveMode::Batched(Duration::from_secs(60)),
+        ..PersistencePolicy::default()
+    };
+    let service = MultiplayerGameService::new().with_persistence_policy(batched.clone());
+    assert_eq!(service.flush_interval(), Some(Duration::from_secs(60)));
+
+    let clustered = MultiplayerGameService::new()
+        .with_cluster(
+            ClusterConfig::new("alpha", "http://alpha:3000")
+                .with_lease_ttl(Duration::from_secs(30)),
+        )
+        .with_persistence_policy(batched);
+    assert_eq!(clustered.flush_interval(), Some(Duration::from_secs(15)));
+
+    assert_eq!(MultiplayerGameService::new().flush_interval(), None);
+}
+
+#[test]
+fn test_streams_end_when_the_room_moves_away() {
+    let (_dir, db_path) = shared_db();
+    let service = MultiplayerGameService::new_with_db_path(&db_path)
+        .with_cluster(ClusterConfig::new("alpha", "http://alpha:3000"));
+    let room = service
+        .create_room("Leased".to_string(), "Host".to_string(), None)
+        .unwrap();
+    service.room_owner(room.room_id).unwrap();
+    let changes = service.subscribe_room_stream(room.room_id).unwrap();
+
+    let db = Database::new(&db_path).unwrap();
+    db.release_room_leases("alpha").unwrap();
+    let beta = ClusterConfig::new("beta", "http://beta:3000");
+    db.acquire_room_lease(&room.room_id, &beta, chrono::Utc::now())
+        .unwrap();
+    service.room_owner(room.room_id).unwrap();
+
+    // The feed is gone, so the stream ends and the client reconnects
+    assert!(changes.has_changed().is_err());
+    assert!(service.room_stream_events(room.room_id, None).is_empty());
+}
@@ -0,0 +1,203 @@
+use uuid::Uuid;
+
+use kzrk::{
+    api::{models::SendCourierRequest, multiplayer_service::MultiplayerGameService},
+    data::{airports::get_default_airports, cargo_types::get_default_cargo_types},
+    systems::{
+        GameRoom,
+        courier::{
+            COURIER_BASE_FEE, COURIER_FEE_PER_TURN, CourierQuote, CourierStatus,
+            MAX_LETTERS_IN_TRANSIT,
+        },
+        retention::ArchivedEntry,
+    },
+};
+
+fn room_at_jfk() -> (GameRoom, Uuid) {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Mail Room".to_string(),
+        host_id,
+        "Sender".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.players
+        .get_mut(&host_id)
+        .unwrap()
+        .player
+        .current_airport = "JFK".to_string();
+    (room, host_id)
+}
+
+#[test]
+fn test_quote_grows_with_distance() {
+    let short = CourierQuote::for_distance(100.0);
+    assert_eq!(short.turns, 1);
+    assert_eq!(short.fee, COURIER_BASE_FEE + COURIER_FEE_PER_TURN);
+
+    let long = CourierQuote::for_distance(4000.0);
+    assert_eq!(long.turns, 3);
+    assert_eq!(long.fee, COURIER_BASE_FEE + COURIER_FEE_PER_TURN * 3);
+
+    // Even next door, the mail takes a turn
+    assert_eq!(CourierQuote::for_distance(0.0).turns, 1);
+}
+
+#[test]
+fn test_send_charges_the_fee_and_holds_the_letter() {
+    let (mut room, sender) = room_at_jfk();
+    let money = room.get_player(&sender).unwrap().player.money;
+    let quote = room.courier_quote("JFK", "LAX").unwrap();
+
+    let letter = room
+        .send_courier(&sender, "LAX", "Prices are up out west".to_string())
+        .unwrap();
+
+    assert_eq!(letter.fee, quote.fee);
+    assert_eq!(letter.status, CourierStatus::InTransit);
+    assert_eq!(
+        letter.turns_remaining(room.shared_state.world_tick),
+        quote.turns
+    );
+    assert_eq!(
+        room.get_player(&sender).unwrap().player.money,
+        money - quote.fee
+    );
+    // Nothing shows up until the mail flight lands
+    assert!(room.message_board.get_messages("LAX", None).is_empty());
+}
+
+#[test]
+fn test_letter_is_posted_after_its_turns() {
+    let (mut room, sender) = room_at_jfk();
+    let letter = room
+        .send_courier(&sender, "LAX", "See you in LA".to_string())
+        .unwrap();
+    let turns = letter.deliver_at - letter.sent_at;
+
+    for _ in 1..turns {
+        room.advance_turn(&sender);
+    }
+    assert!(room.message_board.get_messages("LAX", None).is_empty());
+    assert_eq!(
+        room.courier_letters_from(&sender)[0].turns_remaining(room.shared_state.world_tick),
+        1
+    );
+
+    room.advance_turn(&sender);
+    let posted = room.message_board.get_messages("LAX", None);
+    assert_eq!(posted.len(), 1);
+    assert_eq!(posted[0].content, "See you in LA");
+    assert_eq!(posted[0].couriered_from.as_deref(), Some("JFK"));
+
+    let sent = room.courier_letters_from(&sender)[0];
+    assert_eq!(sent.status, CourierStatus::Delivered);
+    assert_eq!(sent.message_id, Some(posted[0].id));
+    // Delivered once, not every turn after
+    room.advance_turn(&sender);
+    assert_eq!(room.message_board.get_messages("LAX", None).len(), 1);
+}
+
+#[test]
+fn test_send_rejections() {
+    let (mut room, sender) = room_at_jfk();
+
+    assert!(room.send_courier(&sender, "JFK", "Hi".to_string()).is_err());
+    assert!(
+        room.send_courier(&sender, "NOWHERE", "Hi".to_string())
+            .is_err()
+    );
+    assert!(room.send_courier(&sender, "LAX", String::new()).is_err());
+
+    room.players.get_mut(&sender).unwrap().player.money = 0;
+    assert!(room.send_courier(&sender, "LAX", "Hi".to_string()).is_err());
+    assert!(room.courier_mail.is_empty());
+}
+
+#[test]
+fn test_letters_in_transit_are_capped() {
+    let (mut room, sender) = room_at_jfk();
+    for i in 0..MAX_LETTERS_IN_TRANSIT {
+        room.send_courier(&sender, "LAX", format!("Letter {}", i))
+            .unwrap();
+    }
+    assert!(
+        room.send_courier(&sender, "LAX", "One more".to_string())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_delivered_letters_move_to_the_archive() {
+    let (mut room, sender) = room_at_jfk();
+    room.settings.retention.max_delivered_letters = 2;
+
+    for i in 0..8 {
+        room.send_courier(&sender, "LAX", format!("Letter {}", i))
+            .unwrap();
+        while room.courier_letters_from(&sender)[0].status == CourierStatus::InTransit {
+            room.advance_turn(&sender);
+        }
+        assert!(room.courier_mail.len() <= 2);
+    }
+
+    let kept: Vec<&str> = room
+        .courier_mail
+        .iter()
+        .map(|letter| letter.content.as_str())
+        .collect();
+    assert_eq!(kept, vec!["Letter 6", "Letter 7"]);
+    let archived: Vec<ArchivedEntry> = room
+        .take_overflow()
+        .into_iter()
+        .filter(|entry| entry.kind() == "letter")
+        .collect();
+    assert_eq!(archived.len(), 6);
+    assert_eq!(archived[0].owner(), sender.to_string());
+}
+
+#[test]
+fn test_service_shows_rates_and_status() {
+    let service = MultiplayerGameService::new_in_memory();
+    let created = service
+        .create_room("Mail".to_string(), "Sender".to_string(), Some(4))
+        .unwrap();
+    let room_id = created.room_id;
+    let sender = created.host_player_id;
+
+    let courier = service.get_courier(room_id, sender).unwrap();
+    assert!(!courier.rates.is_empty());
+    assert!(
+        courier
+            .rates
+            .iter()
+            .all(|rate| rate.airport_id != courier.airport_id)
+    );
+    assert!(
+        courier
+            .rates
+            .windows(2)
+            .all(|pair| pair[0].fee <= pair[1].fee)
+    );
+    assert!(courier.letters.is_empty());
+
+    let destination = courier.rates[0].airport_id.clone();
+    let sent = service
+        .send_courier(
+            room_id,
+            sender,
+            SendCourierRequest {
+                destination: destination.clone(),
+                content: "Mail call".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(sent.letter.letter.to_airport, destination);
+    assert_eq!(sent.letter.turns_remaining, courier.rates[0].turns);
+
+    let courier = service.get_courier(room_id, sender).unwrap();
+    assert_eq!(courier.letters.len(), 1);
+    assert_eq!(courier.letters[0].letter.status, CourierStatus::InTransit);
+}
@@ -1,7 +1,7 @@
 use tempfile::tempdir;
 use uuid::Uuid;
 
-use kzrk::api::database::Database;
+use kzrk::api::database::{Database, JournalSync};
 use kzrk::data::{get_default_airports, get_default_cargo_types};
 use kzrk::systems::{GameRoom, GameStatus, PlayerSession};
 
@@ -347,3 +347,19 @@ fn test_serialization_error_handling() {
     // The database should handle malformed data gracefully by skipping invalid entries
     // This is tested implicitly by the serialization/deserialization process
 }
+
+#[test]
+fn test_journal_sync_policy() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("sync.db");
+    let db = Database::new(db_path.to_str().unwrap()).unwrap();
+    assert_eq!(db.journal_sync().unwrap(), JournalSync::Full);
+
+    for sync in [JournalSync::Off, JournalSync::Normal, JournalSync::Full] {
+        db.set_journal_sync(sync).unwrap();
+        assert_eq!(db.journal_sync().unwrap(), sync);
+    }
+
+    assert_eq!(JournalSync::parse(" Normal "), Some(JournalSync::Normal));
+    assert_eq!(JournalSync::parse("sometimes"), None);
+}
@@ -0,0 +1,182 @@
+use uuid::Uuid;
+
+use kzrk::api::multiplayer_service::{DemoRoomPolicy, MultiplayerGameService};
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{
+    GameRoom,
+    bots::{BotTurn, DemoRoom},
+};
+
+fn demo_service() -> (MultiplayerGameService, Uuid) {
+    let service = MultiplayerGameService::new_in_memory();
+    let room_id = service
+        .ensure_demo_room(&DemoRoomPolicy::default())
+        .unwrap()
+        .expect("an empty server gets a demo room");
+    (service, room_id)
+}
+
+#[test]
+fn test_empty_servers_get_one_demo_room_with_bots() {
+    let (service, room_id) = demo_service();
+
+    let rooms = service.list_rooms().unwrap();
+    assert_eq!(rooms.len(), 1);
+    let lobby = &rooms[0];
+    assert_eq!(lobby.id, room_id);
+    assert!(lobby.is_demo);
+    assert!(lobby.is_joinable);
+    assert_eq!(lobby.current_players, 3);
+
+    let joined = service
+        .join_room(room_id, "Newcomer".to_string(), None)
+        .unwrap();
+    let state = service.get_room_state(room_id, joined.player_id).unwrap();
+    assert!(state.room_info.is_demo);
+    let bots = state.players.iter().filter(|player| player.is_bot).count();
+    assert_eq!(bots, 3);
+    let newcomer = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(joined.player_id))
+        .unwrap();
+    assert!(!newcomer.is_bot);
+
+    assert_eq!(
+        service.ensure_demo_room(&DemoRoomPolicy::default()),
+        Ok(None)
+    );
+}
+
+#[test]
+fn test_servers_with_rooms_get_no_demo_room() {
+    let service = MultiplayerGameService::new_in_memory();
+    service
+        .create_room("Friends".to_string(), "Host".to_string(), None)
+        .unwrap();
+
+    assert_eq!(
+        service.ensure_demo_room(&DemoRoomPolicy::default()),
+        Ok(None)
+    );
+    assert!(
+        service
+            .list_rooms()
+            .unwrap()
+            .iter()
+            .all(|room| !room.is_demo)
+    );
+}
+
+#[test]
+fn test_bots_trade_and_fly() {
+    let (service, room_id) = demo_service();
+    let joined = service
+        .join_room(room_id, "Watcher".to_string(), None)
+        .unwrap();
+
+    let mut flown = 0;
+    for _ in 0..5 {
+        flown += service.play_demo_bots().unwrap();
+    }
+    assert!(flown > 0, "no bot ever took off");
+
+    let state = service.get_room_state(room_id, joined.player_id).unwrap();
+    assert!(
+        state
+            .players
+            .iter()
+            .filter(|player| player.is_bot)
+            .any(|player| player.turn_number > Some(1))
+    );
+}
+
+#[test]
+fn test_demo_rooms_start_over_when_due() {
+    let (service, room_id) = demo_service();
+    let joined = service
+        .join_room(room_id, "Stayer".to_string(), None)
+        .unwrap();
+    for _ in 0..3 {
+        service.play_demo_bots().unwrap();
+    }
+
+    let policy = DemoRoomPolicy::default();
+    let now = chrono::Utc::now();
+    assert_eq!(service.reset_demo_rooms(policy.reset_interval, now), Ok(0));
+    assert_eq!(
+        service.reset_demo_rooms(policy.reset_interval, now + policy.reset_interval),
+        Ok(1)
+    );
+
+    let state = service.get_room_state(room_id, joined.player_id).unwrap();
+    assert_eq!(state.players.len(), 4);
+    for player in &state.players {
+        assert_eq!(player.turn_number, Some(1), "{}", player.name);
+        assert_eq!(player.money, 5000, "{}", player.name);
+    }
+}
+
+#[test]
+fn test_reset_makes_room_for_newcomers() {
+    let bot_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Demo".to_string(),
+        bot_id,
+        "Bot".to_string(),
+        2,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    let demo = DemoRoom {
+        bots: vec![bot_id],
+        reset_at: chrono::Utc::now(),
+    };
+    room.demo = Some(demo);
+    let human_id = room
+        .add_player(Uuid::new_v4(), "Gone".to_string(), None)
+        .unwrap();
+    room.players.get_mut(&human_id).unwrap().is_online = false;
+    room.players.get_mut(&bot_id).unwrap().is_online = false;
+
+    room.reset_demo(chrono::Utc::now());
+
+    assert!(!room.players.contains_key(&human_id));
+    assert!(room.players[&bot_id].is_online);
+    assert_eq!(room.host_player_id, bot_id);
+    assert!(room.is_bot(&bot_id));
+}
+
+#[test]
+fn test_bots_buy_where_another_airport_pays_more() {
+    let bot_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Demo".to_string(),
+        bot_id,
+        "Bot".to_string(),
+        2,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    let here = room.players[&bot_id].player.current_airport.clone();
+    for (airport_id, market) in room.shared_state.markets.iter_mut() {
+        let price = if *airport_id == here { 10 } else { 20 };
+        for cargo_id in get_default_cargo_types().keys() {
+            market.set_cargo_price(cargo_id, price);
+        }
+    }
+    room.shared_state
+        .markets
+        .get_mut("ORD")
+        .unwrap()
+        .set_cargo_price("electronics", 5000);
+
+    let turn = BotTurn::plan(&room, &bot_id, &mut rand::thread_rng()).unwrap();
+    let buy = turn.buy.expect("a profitable run to fly");
+    assert_ne!(turn.destination, here);
+    if here != "ORD" {
+        assert_eq!(buy.cargo_id, "electronics");
+        assert_eq!(turn.destination, "ORD");
+    }
+    assert!(buy.quantity > 0);
+}
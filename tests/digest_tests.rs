@@ -0,0 +1,231 @@
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+use kzrk::api::digests::DigestSender;
+use kzrk::api::errors::{ErrorCode, ServiceError};
+use kzrk::api::models::{DigestRequest, FuelRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::systems::{
+    RoomSettings,
+    digest::{Digest, DigestTarget},
+};
+
+type SentDigest = (DigestTarget, Digest);
+
+/// Keeps digests instead of delivering them
+#[derive(Default)]
+struct RecordingSender {
+    sent: Mutex<Vec<SentDigest>>,
+}
+
+impl DigestSender for RecordingSender {
+    fn send(&self, target: &DigestTarget, digest: &Digest) -> Result<(), String> {
+        self.sent
+            .lock()
+            .unwrap()
+            .push((target.clone(), digest.clone()));
+        Ok(())
+    }
+}
+
+struct FailingSender;
+
+impl DigestSender for FailingSender {
+    fn send(&self, _target: &DigestTarget, _digest: &Digest) -> Result<(), String> {
+        Err("unreachable".to_string())
+    }
+}
+
+fn webhook(url: &str) -> DigestRequest {
+    DigestRequest {
+        webhook_url: Some(url.to_string()),
+        email: None,
+    }
+}
+
+fn email(address: &str) -> DigestRequest {
+    DigestRequest {
+        webhook_url: None,
+        email: Some(address.to_string()),
+    }
+}
+
+fn hours_from_now(hours: i64) -> chrono::DateTime<chrono::Utc> {
+    chrono::Utc::now() + chrono::Duration::hours(hours)
+}
+
+fn setup() -> (MultiplayerGameService, Uuid, Uuid, Uuid) {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Slow Burn".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let guest = service
+        .join_room(room.room_id, "Guest".to_string(), None)
+        .unwrap();
+    (service, room.room_id, room.host_player_id, guest.player_id)
+}
+
+#[test]
+fn test_digest_summarises_the_day() {
+    let (service, room_id, host_id, guest_id) = setup();
+    let subscribed = service
+        .subscribe_digest(room_id, host_id, webhook("https://example.com/hook"))
+        .unwrap();
+    assert_eq!(
+        subscribed.target,
+        Some(DigestTarget::Webhook(
+            "https://example.com/hook".to_string()
+        ))
+    );
+    assert_eq!(subscribed.period_hours, 24);
+
+    service
+        .post_message(
+            room_id,
+            guest_id,
+            "@host want to split the ORD run?".to_string(),
+        )
+        .unwrap();
+    service
+        .post_message(room_id, guest_id, "anyone seen the hostess?".to_string())
+        .unwrap();
+    service
+        .post_message(room_id, host_id, "Host here, sure".to_string())
+        .unwrap();
+
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    let host = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(host_id))
+        .unwrap();
+    let space = host.max_fuel - host.fuel;
+    service
+        .player_buy_fuel(room_id, host_id, FuelRequest { quantity: space })
+        .unwrap();
+    let travel = service
+        .player_travel(room_id, host_id, "ORD".to_string())
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+
+    let sender = RecordingSender::default();
+    // Nothing goes out before the period is up
+    assert_eq!(service.send_digests(&sender, hours_from_now(1)).unwrap(), 0);
+    assert_eq!(
+        service.send_digests(&sender, hours_from_now(25)).unwrap(),
+        1
+    );
+
+    let sent = sender.sent.lock().unwrap();
+    let (target, digest) = &sent[0];
+    assert_eq!(
+        *target,
+        DigestTarget::Webhook("https://example.com/hook".to_string())
+    );
+    assert_eq!(digest.player_id, host_id);
+    assert_eq!(digest.room_name, "Slow Burn");
+    assert_eq!(digest.your_turns, 1);
+    assert!(digest.world_turns >= 1);
+    assert_eq!(digest.leaderboard.len(), 2);
+    // Only the guest's message that names the host counts
+    assert_eq!(digest.mentions.len(), 1);
+    assert_eq!(digest.mentions[0].author_name, "Guest");
+    assert!(
+        digest
+            .text()
+            .contains("Guest at JFK: @host want to split the ORD run?")
+    );
+    drop(sent);
+
+    // The next digest starts where this one left off; a quiet day sends nothing
+    assert_eq!(
+        service.send_digests(&sender, hours_from_now(30)).unwrap(),
+        0
+    );
+    assert_eq!(
+        service.send_digests(&sender, hours_from_now(50)).unwrap(),
+        0
+    );
+    assert_eq!(sender.sent.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_failed_delivery_waits_for_the_next_period() {
+    let (service, room_id, host_id, guest_id) = setup();
+    service
+        .subscribe_digest(room_id, host_id, email("host@example.com"))
+        .unwrap();
+    service
+        .post_message(room_id, guest_id, "Host, you there?".to_string())
+        .unwrap();
+
+    assert_eq!(
+        service
+            .send_digests(&FailingSender, hours_from_now(25))
+            .unwrap(),
+        0
+    );
+    // Not retried straight away
+    let sender = RecordingSender::default();
+    assert_eq!(
+        service.send_digests(&sender, hours_from_now(26)).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_digest_registration() {
+    let (service, room_id, host_id, _) = setup();
+
+    let neither = DigestRequest {
+        webhook_url: None,
+        email: None,
+    };
+    assert!(service.subscribe_digest(room_id, host_id, neither).is_err());
+    let both = DigestRequest {
+        webhook_url: Some("https://example.com/hook".to_string()),
+        email: Some("host@example.com".to_string()),
+    };
+    assert!(service.subscribe_digest(room_id, host_id, both).is_err());
+    assert!(
+        service
+            .subscribe_digest(room_id, host_id, email("host@example.com\nBcc: a@b.com"))
+            .is_err()
+    );
+    assert!(
+        service
+            .subscribe_digest(room_id, host_id, webhook("file:///etc/passwd"))
+            .is_err()
+    );
+    assert_eq!(
+        service
+            .subscribe_digest(room_id, Uuid::new_v4(), email("host@example.com"))
+            .unwrap_err(),
+        ServiceError::NotFound(ErrorCode::PlayerNotInRoom)
+    );
+
+    service
+        .subscribe_digest(room_id, host_id, email("host@example.com"))
+        .unwrap();
+    let stopped = service.unsubscribe_digest(room_id, host_id).unwrap();
+    assert_eq!(stopped.target, None);
+
+    service
+        .post_message(room_id, host_id, "anyone flying today?".to_string())
+        .unwrap();
+    let sender = RecordingSender::default();
+    assert_eq!(
+        service.send_digests(&sender, hours_from_now(25)).unwrap(),
+        0
+    );
+}
@@ -0,0 +1,139 @@
+use std::thread;
+
+use kzrk::data::{get_default_airports, get_default_cargo_types};
+use kzrk::models::airport::great_circle_km;
+use kzrk::systems::{GameState, TravelSystem, distance::DistanceCache};
+
+#[test]
+fn test_distances_are_measured_once_per_pair() {
+    let airports = get_default_airports();
+    let cache = DistanceCache::new();
+    assert!(cache.is_empty());
+
+    let there = cache.between(&airports["JFK"], &airports["LAX"]);
+    let back = cache.between(&airports["LAX"], &airports["JFK"]);
+    assert_eq!(there, back);
+    assert_eq!(there, airports["JFK"].distance_to(&airports["LAX"]));
+    assert_eq!(cache.len(), 1);
+
+    assert_eq!(cache.between(&airports["JFK"], &airports["JFK"]), 0.0);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_moved_airports_are_measured_again() {
+    let mut airports = get_default_airports();
+    let cache = DistanceCache::new();
+    let before = cache.between(&airports["JFK"], &airports["ORD"]);
+
+    airports.get_mut("ORD").unwrap().coordinates = airports["LAX"].coordinates;
+    let after = cache.between(&airports["JFK"], &airports["ORD"]);
+    assert_ne!(before, after);
+    assert_eq!(after, airports["JFK"].distance_to(&airports["LAX"]));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_game_state_forgets_removed_airports() {
+    let mut state = GameState::new(get_default_airports(), get_default_cargo_types());
+    assert_eq!(
+        state.get_distance("JFK", "LAX"),
+        Some(state.airports["JFK"].distance_to(&state.airports["LAX"]))
+    );
+    assert_eq!(state.get_distance("JFK", "XXX"), None);
+    assert!(state.get_distance("JFK", "ORD").is_some());
+    assert_eq!(state.distances.len(), 2);
+
+    let mut airports = state.airports.clone();
+    airports.remove("LAX");
+    state.set_airports(airports);
+    assert_eq!(state.distances.len(), 1);
+    assert_eq!(state.get_distance("JFK", "LAX"), None);
+    assert!(
+        TravelSystem::get_reachable_destinations(&state)
+            .iter()
+            .all(|destination| destination.airport_id != "LAX")
+    );
+}
+
+#[test]
+fn test_distances_survive_a_save_round_trip() {
+    let state = GameState::new(get_default_airports(), get_default_cargo_types());
+    let destinations = TravelSystem::get_reachable_destinations(&state);
+    assert!(!state.distances.is_empty());
+
+    let json = serde_json::to_string(&state).unwrap();
+    assert!(!json.contains("distance_cache"));
+    let loaded: GameState = serde_json::from_str(&json).unwrap();
+    assert!(loaded.distances.is_empty());
+    let reloaded = TravelSystem::get_reachable_destinations(&loaded);
+    assert_eq!(
+        destinations
+            .iter()
+            .map(|destination| destination.distance_km)
+            .collect::<Vec<_>>(),
+        reloaded
+            .iter()
+            .map(|destination| destination.distance_km)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_cache_is_shared_between_threads() {
+    let airports = get_default_airports();
+    let cache = DistanceCache::new();
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for from in airports.values() {
+                    for to in airports.values() {
+                        cache.between(from, to);
+                    }
+                }
+            });
+        }
+    });
+    let n = airports.len();
+    assert_eq!(cache.len(), n * (n - 1) / 2);
+}
+
+#[test]
+fn test_distances_follow_the_earths_curve() {
+    let airports = get_default_airports();
+    // Published great-circle distances between the airports. A round Earth
+    // comes within half a percent of these ellipsoid figures; a flat map
+    // of degrees would be off by more than a quarter on the long routes.
+    let known = [
+        ("JFK", "ORD", 1191.0),
+        ("LAX", "SEA", 1535.0),
+        ("JFK", "MIA", 1753.0),
+        ("ORD", "LAX", 2808.0),
+        ("JFK", "LAX", 3983.0),
+        ("MIA", "SEA", 4384.0),
+    ];
+    for (from, to, expected) in known {
+        let km = great_circle_km(airports[from].coordinates, airports[to].coordinates);
+        assert!(
+            (km - expected).abs() < expected * 0.005,
+            "{} to {}: {} km, expected about {}",
+            from,
+            to,
+            km,
+            expected
+        );
+        assert_eq!(airports[from].distance_to(&airports[to]), km);
+        assert_eq!(
+            great_circle_km(airports[to].coordinates, airports[from].coordinates),
+            km
+        );
+    }
+
+    // A game's distances, like the GUI's copy of the server's world, and
+    // the travel quotes the server charges by all agree
+    let (jfk, lax) = (&airports["JFK"], &airports["LAX"]);
+    let km = great_circle_km(jfk.coordinates, lax.coordinates);
+    let state = GameState::new(get_default_airports(), get_default_cargo_types());
+    assert_eq!(state.get_distance("JFK", "LAX"), Some(km));
+    assert_eq!(state.get_distance("LAX", "JFK"), Some(km));
+}
@@ -87,33 +87,24 @@ async fn test_invalid_session_id_errors() {
     assert_eq!(error["error"], "GameNotFound");
     assert!(error["message"].as_str().unwrap().contains("not found"));
 
-    // Test travel with invalid session
-    let travel_body = json!({"destination": "ORD"});
-    let response = server
-        .post(&format!("/game/{}/travel", fake_session_id), travel_body)
-        .await
-        .unwrap();
-    assert_eq!(response.status(), 400);
-
-    // Test trade with invalid session
-    let trade_body = json!({
-        "cargo_type": "electronics",
-        "quantity": 1,
-        "action": "Buy"
-    });
-    let response = server
-        .post(&format!("/game/{}/trade", fake_session_id), trade_body)
-        .await
-        .unwrap();
-    assert_eq!(response.status(), 400);
-
-    // Test fuel purchase with invalid session
-    let fuel_body = json!({"quantity": 50});
-    let response = server
-        .post(&format!("/game/{}/fuel", fake_session_id), fuel_body)
-        .await
-        .unwrap();
-    assert_eq!(response.status(), 400);
+    // Actions on an invalid session are a missing resource too, not a bad request
+    let actions = [
+        ("travel", json!({"destination": "ORD"})),
+        (
+            "trade",
+            json!({"cargo_type": "electronics", "quantity": 1, "action": "Buy"}),
+        ),
+        ("fuel", json!({"quantity": 50})),
+    ];
+    for (action, body) in actions {
+        let response = server
+            .post(&format!("/game/{}/{}", fake_session_id, action), body)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+        let error: Value = response.json().await.unwrap();
+        assert_eq!(error["error"], "GameNotFound");
+    }
 }
 
 #[tokio::test]
@@ -277,7 +268,7 @@ async fn test_invalid_destination_errors() {
     assert_eq!(response.status(), 400);
 
     let error: Value = response.json().await.unwrap();
-    assert_eq!(error["error"], "TravelError");
+    assert_eq!(error["error"], "ActionRejected");
 
     // Try to travel to current airport (should be handled gracefully)
     let same_destination = json!({"destination": "JFK"}); // Starting airport
@@ -391,6 +382,8 @@ async fn test_malformed_request_bodies() {
         .await
         .unwrap();
     assert_eq!(response.status(), 422);
+    let error: Value = response.json().await.unwrap();
+    assert_eq!(error["error"], "InvalidBody");
 }
 
 #[tokio::test]
@@ -419,6 +412,46 @@ async fn test_edge_case_values() {
     assert!(response.status() == 200 || response.status() == 400);
 }
 
+#[tokio::test]
+async fn test_huge_quantities_are_refused_not_overflowed() {
+    let server = TestServer::new().await;
+    let (session_id, _) = server.create_test_game(Some(u32::MAX)).await;
+
+    // Priced past what any wallet holds: refused instead of wrapping around
+    let huge_trade = json!({
+        "cargo_type": "luxury",
+        "quantity": u32::MAX,
+        "action": "Buy"
+    });
+    let response = server
+        .post(&format!("/game/{}/trade", session_id), huge_trade)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["success"], false);
+    assert_eq!(body["localized"]["code"], "AmountTooLarge");
+
+    let huge_fuel = json!({"quantity": u32::MAX});
+    let response = server
+        .post(&format!("/game/{}/fuel", session_id), huge_fuel)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["success"], false);
+
+    // Nothing was charged
+    let state: Value = server
+        .get(&format!("/game/{}", session_id))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(state["player"]["money"], u32::MAX);
+}
+
 #[tokio::test]
 async fn test_concurrent_operations_on_same_session() {
     let server = TestServer::new().await;
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::cargo_types::get_default_cargo_types;
+use kzrk::models::{Player, player::FULL_HOLD_BURN_PERCENT};
+use kzrk::systems::{
+    RoomSettings, TravelSystem,
+    events::{EventSpec, EventSystem, MarketEvent, MarketEventType},
+    multiplayer::{STARTING_MAX_FUEL, SpawnRule},
+    travel::{FlightConditions, FlightRange},
+};
+
+#[test]
+fn test_range_follows_the_fuel_burn() {
+    let mut player = Player::new(1000, "JFK", 200, 1000, 12.5);
+    player.fuel = 40;
+    assert_eq!(
+        TravelSystem::range(&player, FlightConditions::default()),
+        FlightRange {
+            current_fuel_km: 500.0,
+            full_tank_km: 2500.0,
+        }
+    );
+    // A flight exactly as long as the range burns exactly the fuel aboard
+    assert_eq!(player.fuel_needed_for_distance(500.0), 40);
+    assert!(player.can_travel_distance(500.0));
+    assert!(!player.can_travel_distance(500.1));
+}
+
+#[test]
+fn test_a_heavier_load_cuts_range() {
+    let mut player = Player::new(1000, "JFK", 200, 1000, 12.5);
+    player.fuel = 40;
+    let empty = TravelSystem::range(&player, FlightConditions::default());
+
+    let half_full = FlightConditions {
+        cargo_weight: 500,
+        ..FlightConditions::default()
+    };
+    let full = FlightConditions {
+        cargo_weight: 1000,
+        ..FlightConditions::default()
+    };
+    let half_range = TravelSystem::range(&player, half_full);
+    let full_range = TravelSystem::range(&player, full);
+    assert!(half_range.current_fuel_km < empty.current_fuel_km);
+    assert!(full_range.current_fuel_km < half_range.current_fuel_km);
+    let full_burn = 1.0 + FULL_HOLD_BURN_PERCENT as f64 / 100.0;
+    assert_eq!(full_range.full_tank_km, 2500.0 / full_burn);
+
+    // The hold counts toward the charge for a flight as well as the range
+    assert_eq!(
+        TravelSystem::calculate_fuel_needed(&player, 500.0, full),
+        (40.0 * full_burn).ceil() as u32
+    );
+}
+
+#[test]
+fn test_cargo_weight_comes_from_the_hold() {
+    let cargo_types = get_default_cargo_types();
+    let mut player = Player::new(1000, "JFK", 200, 1000, 12.5);
+    let empty = FlightConditions::for_route(&player, &cargo_types, &[], "JFK", "ORD");
+    assert_eq!(empty, FlightConditions::default());
+
+    player.cargo_inventory.add_cargo("electronics", 10);
+    let loaded = FlightConditions::for_route(&player, &cargo_types, &[], "JFK", "ORD");
+    assert_eq!(
+        loaded.cargo_weight,
+        10 * cargo_types["electronics"].weight_per_unit
+    );
+}
+
+fn storm_at(airport: &str, percent: i32) -> MarketEvent {
+    let airports = kzrk::data::airports::get_default_airports();
+    EventSpec {
+        event_type: MarketEventType::Storm,
+        airport: airport.to_string(),
+        cargo: None,
+        category: None,
+        percent: Some(percent),
+        turns: 2,
+        description: None,
+    }
+    .build(&airports, &get_default_cargo_types(), "Host")
+    .unwrap()
+}
+
+#[test]
+fn test_a_storm_at_either_end_cuts_range() {
+    let player = Player::new(1000, "JFK", 200, 1000, 12.5);
+    let events = vec![storm_at("ORD", 25), storm_at("JFK", 60)];
+    let cargo_types = HashMap::new();
+
+    // The worse of the two storms is the one the flight flies through
+    let into_storms = FlightConditions::for_route(&player, &cargo_types, &events, "JFK", "ORD");
+    assert!((into_storms.weather - 1.6).abs() < 1e-6);
+    let into_one = FlightConditions::for_route(&player, &cargo_types, &events, "LAX", "ORD");
+    assert!((into_one.weather - 1.25).abs() < 1e-6);
+    let clear = FlightConditions::for_route(&player, &cargo_types, &events, "LAX", "SFO");
+    assert_eq!(clear.weather, 1.0);
+    assert_eq!(EventSystem::weather_multiplier(&[], "JFK", "ORD"), 1.0);
+
+    let calm = TravelSystem::range(&player, clear);
+    let stormy = TravelSystem::range(&player, into_one);
+    assert!(stormy.full_tank_km < calm.full_tank_km);
+    assert!((stormy.full_tank_km - calm.full_tank_km / 1.25).abs() < 1e-6);
+}
+
+#[test]
+fn test_quote_itemizes_the_load_and_the_storm() {
+    let airports = kzrk::data::airports::get_default_airports();
+    let player = Player::new(1000, "JFK", 200, 1000, 12.5);
+    let conditions = FlightConditions {
+        cargo_weight: 1000,
+        weather: 1.5,
+    };
+    let quote = TravelSystem::quote(&player, &airports["JFK"], &airports["ORD"], 10, conditions);
+    assert_eq!(quote.items.len(), 3, "{:?}", quote.items);
+    assert!(quote.items[1].label.starts_with("Cargo weight"));
+    assert!(quote.items[2].label.starts_with("Storm"));
+    assert_eq!(
+        quote.total_fuel,
+        TravelSystem::calculate_fuel_needed(&player, quote.distance, conditions)
+    );
+
+    let clear = TravelSystem::quote(
+        &player,
+        &airports["JFK"],
+        &airports["ORD"],
+        10,
+        FlightConditions::default(),
+    );
+    assert_eq!(clear.items.len(), 1);
+    assert!(clear.total_fuel < quote.total_fuel);
+}
+
+#[test]
+fn test_state_range_shrinks_in_a_storm() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Storm".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                spawn: SpawnRule::HostChosen("JFK".to_string()),
+                landing_slots: false,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let before = service
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+    let ord_before = before
+        .available_destinations
+        .iter()
+        .find(|destination| destination.airport_id == "ORD")
+        .unwrap()
+        .fuel_required;
+
+    service
+        .host_inject_event(
+            room.room_id,
+            room.host_player_id,
+            EventSpec {
+                event_type: MarketEventType::Storm,
+                airport: "JFK".to_string(),
+                cargo: None,
+                category: None,
+                percent: Some(50),
+                turns: 3,
+                description: None,
+            },
+        )
+        .unwrap();
+    let after = service
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+    assert!(after.flight_range.full_tank_km < before.flight_range.full_tank_km);
+    assert!(after.flight_range.current_fuel_km < before.flight_range.current_fuel_km);
+    let ord_after = after
+        .available_destinations
+        .iter()
+        .find(|destination| destination.airport_id == "ORD")
+        .unwrap()
+        .fuel_required;
+    assert!(ord_after > ord_before, "{} vs {}", ord_after, ord_before);
+}
+
+#[test]
+fn test_state_says_what_a_full_tank_would_reach() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Range".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                spawn: SpawnRule::HostChosen("JFK".to_string()),
+                starting_fuel: Some(10),
+                landing_slots: false,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let state = service
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+    let me = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(room.host_player_id))
+        .unwrap();
+    let efficiency = me.fuel_efficiency as f64;
+    assert_eq!(state.flight_range.current_fuel_km, 10.0 * efficiency);
+    assert_eq!(
+        state.flight_range.full_tank_km,
+        STARTING_MAX_FUEL as f64 * efficiency
+    );
+
+    for destination in &state.available_destinations {
+        assert_eq!(
+            destination.can_travel,
+            destination.distance <= state.flight_range.current_fuel_km,
+            "{}",
+            destination.airport_id
+        );
+        assert_eq!(
+            destination.reachable_on_full_tank,
+            destination.distance <= state.flight_range.full_tank_km,
+            "{}",
+            destination.airport_id
+        );
+    }
+    let destination = |id: &str| {
+        state
+            .available_destinations
+            .iter()
+            .find(|destination| destination.airport_id == id)
+            .unwrap()
+    };
+    // Chicago needs a fill-up; Los Angeles is too far for one tank
+    assert!(!destination("ORD").can_travel);
+    assert!(destination("ORD").reachable_on_full_tank);
+    assert!(!destination("LAX").reachable_on_full_tank);
+}
+
+#[cfg(feature = "gui")]
+mod reachable_filter {
+    use kzrk::api::models::DestinationInfo;
+    use kzrk::systems::slots::SlotStatus;
+    use kzrk::ui::scenes::airport::locations::FlightPlanning;
+
+    fn destination(can_travel: bool, ground_transfer_fare: Option<u32>) -> DestinationInfo {
+        DestinationInfo {
+            airport_id: "EWR".to_string(),
+            airport_name: "Newark".to_string(),
+            distance: 30.0,
+            fuel_required: 3,
+            can_travel,
+            fuel_price: 0,
+            fuel_price_observed_at: None,
+            fuel_outage: false,
+            ground_transfer_fare,
+            slot_status: SlotStatus::Unrestricted,
+            reachable_on_full_tank: true,
+        }
+    }
+
+    #[test]
+    fn test_airports_a_drive_away_stay_listed() {
+        assert!(FlightPlanning::reachable(&destination(true, None), false));
+        assert!(!FlightPlanning::reachable(&destination(false, None), false));
+        assert!(FlightPlanning::reachable(
+            &destination(false, Some(95)),
+            false
+        ));
+        assert!(FlightPlanning::reachable(&destination(false, None), true));
+    }
+}
@@ -0,0 +1,136 @@
+use uuid::Uuid;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::models::Airport;
+use kzrk::systems::{GameRoom, GameStatistics, RoomSettings, RouteFuelStats};
+
+#[test]
+fn test_fuel_burned_counts_every_leg() {
+    let mut stats = GameStatistics::new();
+    stats.record_leg(RouteFuelStats::leg("JFK", "ORD", 1188.0, 80, 50));
+    stats.record_leg(RouteFuelStats::leg("ORD", "JFK", 1188.0, 90, 50));
+    stats.record_leg(RouteFuelStats::leg("JFK", "ORD", 1188.0, 80, 50));
+    assert_eq!(stats.fuel_burned, 250);
+    let route_total: u32 = stats.routes.iter().map(|route| route.fuel_used).sum();
+    assert_eq!(stats.fuel_burned, route_total);
+}
+
+#[test]
+fn test_efficiency_needs_a_profit() {
+    let mut stats = GameStatistics::new();
+    stats.record_leg(RouteFuelStats::leg("JFK", "ORD", 1188.0, 100, 50));
+    assert_eq!(stats.fuel_per_profit_dollar(), None);
+
+    stats.record_cargo_purchase(3000);
+    stats.record_sale("electronics", 2000);
+    assert_eq!(stats.profit(), -1000);
+    assert_eq!(stats.net_profit, 0);
+    assert_eq!(stats.fuel_per_profit_dollar(), None);
+
+    stats.record_sale("electronics", 6000);
+    assert_eq!(stats.profit(), 5000);
+    assert_eq!(stats.fuel_per_profit_dollar(), Some(100.0 / 5000.0));
+}
+
+#[test]
+fn test_ground_transfer_fares_count_as_expenses() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Tri-State".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                custom_airports: vec![
+                    Airport::new(
+                        "JFK",
+                        "New York JFK",
+                        (40.64, -73.78),
+                        80,
+                        vec![],
+                        vec![],
+                        1.0,
+                    ),
+                    Airport::new("EWR", "Newark", (40.69, -74.17), 80, vec![], vec![], 1.0),
+                ],
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, player_id) = (room.room_id, room.host_player_id);
+    let airport = service
+        .get_room_state(room_id, player_id)
+        .unwrap()
+        .current_market
+        .airport_id;
+    let destination = if airport == "JFK" { "EWR" } else { "JFK" };
+
+    let transfer = service
+        .player_ground_transfer(room_id, player_id, destination.to_string())
+        .unwrap();
+    assert!(transfer.success, "{}", transfer.message);
+
+    let stats = service.get_player_statistics(room_id, player_id).unwrap();
+    assert!(stats.total_expenses > 0);
+    assert_eq!(stats.fuel_burned, 0);
+}
+
+/// A pilot's name, fuel burned and profit
+type Pilot<'a> = (&'a str, u32, u32);
+
+/// A room where each pilot has burned their fuel and made their profit
+fn finished_room(pilots: &[Pilot]) -> (GameRoom, Vec<Uuid>) {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Green Skies".to_string(),
+        host_id,
+        pilots[0].0.to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    let mut ids = vec![host_id];
+    for (name, _, _) in &pilots[1..] {
+        let player_id = Uuid::new_v4();
+        room.add_player(player_id, name.to_string(), None).unwrap();
+        ids.push(player_id);
+    }
+    for (player_id, (_, fuel, profit)) in ids.iter().zip(pilots) {
+        let stats = room.player_statistics.get_mut(player_id).unwrap();
+        stats.record_leg(RouteFuelStats::leg("JFK", "ORD", 1188.0, *fuel, 50));
+        stats.record_sale("electronics", *profit);
+    }
+    (room, ids)
+}
+
+#[test]
+fn test_most_efficient_pilot_burns_least_per_dollar() {
+    // Bessie made less but burned far less for it; Charles never turned a profit
+    let (mut room, ids) = finished_room(&[
+        ("Amelia", 400, 20_000),
+        ("Bessie", 50, 10_000),
+        ("Charles", 10, 0),
+    ]);
+    assert!(room.awards().is_empty(), "no awards while the game is on");
+
+    room.get_player_mut(&ids[0]).unwrap().player.money = room.settings.win_condition_money;
+    assert!(room.check_for_winner(&ids[0]));
+
+    let awards = room.awards();
+    assert_eq!(awards.len(), 1);
+    assert!(awards[0].title.contains("Most Efficient Pilot"));
+    assert_eq!(awards[0].player_id, ids[1]);
+    assert_eq!(awards[0].player_name, "Bessie");
+    assert_eq!(awards[0].detail, "5.0 fuel per $1000 of profit");
+}
+
+#[test]
+fn test_no_award_when_nobody_made_a_profit() {
+    let (mut room, ids) = finished_room(&[("Amelia", 100, 0), ("Bessie", 100, 0)]);
+    room.get_player_mut(&ids[0]).unwrap().player.money = room.settings.win_condition_money;
+    assert!(room.check_for_winner(&ids[0]));
+    assert!(room.awards().is_empty());
+}
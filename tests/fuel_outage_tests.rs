@@ -0,0 +1,140 @@
+use rand::{SeedableRng, rngs::StdRng};
+use uuid::Uuid;
+
+use kzrk::api::models::FuelRequest;
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{GameRoom, RoomSettings, events::EventConfig};
+
+fn room() -> (GameRoom, Uuid) {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Outage Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.settings.fuel_outage_chance = 0.0;
+    // Only outages the tests roll themselves
+    room.settings.events.chance = 0.0;
+    (room, host_id)
+}
+
+#[test]
+fn test_outages_empty_the_market_until_they_end() {
+    let (mut room, host_id) = room();
+    let mut rng = StdRng::seed_from_u64(7);
+
+    let outage = room.roll_fuel_outage(1.0, &mut rng).unwrap().clone();
+    let airport_id = outage.affected_airport.clone();
+    assert!(room.fuel_outage_at(&airport_id).is_some());
+    assert_eq!(room.shared_state.markets[&airport_id].fuel_available(), 0);
+
+    // One outage per airport at a time
+    for _ in 0..20 {
+        room.roll_fuel_outage(1.0, &mut rng);
+    }
+    let outages_here = room
+        .shared_state
+        .active_events
+        .iter()
+        .filter(|event| event.affected_airport == airport_id)
+        .count();
+    assert_eq!(outages_here, 1);
+
+    for _ in 0..outage.turns_remaining {
+        assert_eq!(room.shared_state.markets[&airport_id].fuel_available(), 0);
+        room.advance_turn(&host_id);
+    }
+    assert!(room.fuel_outage_at(&airport_id).is_none());
+    assert!(room.shared_state.markets[&airport_id].fuel_available() > 0);
+}
+
+#[test]
+fn test_no_outages_when_the_chance_is_zero() {
+    let (mut room, host_id) = room();
+    for _ in 0..50 {
+        room.advance_turn(&host_id);
+    }
+    assert!(room.shared_state.active_events.is_empty());
+}
+
+#[test]
+fn test_fuel_cannot_be_bought_during_an_outage() {
+    let service = MultiplayerGameService::new_in_memory();
+    let settings = RoomSettings {
+        airports: vec!["JFK".to_string(), "ORD".to_string()],
+        fuel_outage_chance: 1.0,
+        events: EventConfig {
+            chance: 0.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let room = service
+        .create_room_with_settings(
+            "Dry Room".to_string(),
+            "Host".to_string(),
+            Some(2),
+            settings,
+        )
+        .unwrap();
+
+    // Fill up for the round trip while JFK still has fuel
+    let state = service
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+    let space = state.players[0].max_fuel - state.players[0].fuel;
+    let fill = service
+        .player_buy_fuel(
+            room.room_id,
+            room.host_player_id,
+            FuelRequest { quantity: space },
+        )
+        .unwrap();
+    assert!(fill.success, "{}", fill.message);
+
+    // An outage starts every turn after that, so both airports are dry
+    // once we're back
+    for destination in ["ORD", "JFK"] {
+        let travel = service
+            .player_travel(room.room_id, room.host_player_id, destination.to_string())
+            .unwrap();
+        assert!(travel.success, "{}", travel.message);
+    }
+
+    let state = service
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+    assert!(state.current_market.fuel_outage);
+    assert_eq!(state.current_market.fuel_stock, Some(0));
+    assert!(
+        state
+            .available_destinations
+            .iter()
+            .all(|destination| destination.fuel_outage)
+    );
+    assert_eq!(state.active_events.len(), 2);
+
+    let before = state.players[0].money;
+    let fuel = service
+        .player_buy_fuel(
+            room.room_id,
+            room.host_player_id,
+            FuelRequest { quantity: 10 },
+        )
+        .unwrap();
+    assert!(!fuel.success);
+    assert!(
+        fuel.message.starts_with("No fuel at JFK"),
+        "{}",
+        fuel.message
+    );
+
+    let after = service
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+    assert_eq!(after.players[0].money, before);
+}
@@ -1,6 +1,7 @@
 use kzrk::{
     data::cargo_types::get_default_cargo_types,
     models::{market::Market, player::Player},
+    systems::{TradingSystem, trading::BROKER_TIERS},
 };
 
 #[test]
@@ -102,3 +103,94 @@ fn test_complete_trading_scenario() {
     let expected_final_change = expected_profit - fuel_cost as i32;
     assert_eq!(final_money_change, expected_final_change);
 }
+
+#[test]
+fn test_route_fuel_economy_breakdown() {
+    use kzrk::systems::{GameStatistics, RouteFuelStats};
+
+    let mut stats = GameStatistics::new();
+
+    // Sales before the first flight don't belong to any route
+    stats.record_sale("food", 100);
+    assert!(stats.routes.is_empty());
+
+    stats.record_leg(RouteFuelStats::leg("JFK", "ORD", 1188.0, 80, 50));
+    stats.record_sale("electronics", 6000);
+
+    stats.record_leg(RouteFuelStats::leg("ORD", "DEN", 1430.0, 95, 40));
+    stats.record_sale("textiles", 1000);
+
+    // Flying the same route again accumulates into one entry
+    stats.record_leg(RouteFuelStats::leg("DEN", "ORD", 1430.0, 95, 60));
+    stats.record_leg(RouteFuelStats::leg("ORD", "DEN", 1430.0, 95, 40));
+    stats.record_sale("textiles", 500);
+
+    assert_eq!(stats.routes.len(), 3);
+    let ord_den = stats
+        .routes
+        .iter()
+        .find(|r| r.from == "ORD" && r.to == "DEN")
+        .unwrap();
+    assert_eq!(ord_den.legs_flown, 2);
+    assert_eq!(ord_den.fuel_used, 190);
+    assert_eq!(ord_den.fuel_cost, 7600);
+    assert_eq!(ord_den.revenue, 1500);
+    assert_eq!(ord_den.net_result(), -6100);
+
+    let best = stats.best_route().unwrap();
+    assert_eq!((best.from.as_str(), best.to.as_str()), ("JFK", "ORD"));
+    assert_eq!(best.net_result(), 2000);
+
+    let worst = stats.worst_route().unwrap();
+    assert_eq!((worst.from.as_str(), worst.to.as_str()), ("ORD", "DEN"));
+}
+
+#[test]
+fn test_broker_fees_fall_as_volume_grows() {
+    let cargo_types = get_default_cargo_types();
+    let mut player = Player::new(1_000_000, "JFK", 100, 100_000, 10.0);
+    let mut market = Market::new("JFK", 50);
+    market.set_cargo_price("electronics", 500);
+
+    // Walk-in customers pay the full 3%
+    let quote =
+        TradingSystem::buy_cargo(&mut player, &market, &cargo_types, "electronics", 10).unwrap();
+    assert_eq!(quote.gross, 5_000);
+    assert_eq!(quote.fee, 150);
+    assert_eq!(quote.tier, BROKER_TIERS[0]);
+    assert_eq!(player.money, 1_000_000 - 5_150);
+
+    // Selling also counts towards volume, and takes the fee out of the proceeds
+    let quote = TradingSystem::sell_cargo(&mut player, &market, "electronics", 10).unwrap();
+    assert_eq!(quote.proceeds(), 4_850);
+    assert_eq!(player.broker_volume_at("JFK"), 10_000);
+
+    let quote = TradingSystem::quote_trade(&player, &market, "electronics", 10).unwrap();
+    assert_eq!(quote.tier.name, "Regular");
+    assert_eq!(quote.fee, 100);
+
+    // Standing is per airport
+    let mut elsewhere = Market::new("ORD", 50);
+    elsewhere.set_cargo_price("electronics", 500);
+    let quote = TradingSystem::quote_trade(&player, &elsewhere, "electronics", 10).unwrap();
+    assert_eq!(quote.tier.name, "Walk-in");
+
+    assert_eq!(
+        TradingSystem::broker_tier(u32::MAX).name,
+        BROKER_TIERS[BROKER_TIERS.len() - 1].name
+    );
+    assert!(TradingSystem::next_broker_tier(u32::MAX).is_none());
+}
+
+#[test]
+fn test_max_buyable_leaves_room_for_the_fee() {
+    let cargo_types = get_default_cargo_types();
+    let mut player = Player::new(1_000, "JFK", 100, 100_000, 10.0);
+    let mut market = Market::new("JFK", 50);
+    market.set_cargo_price("food", 100);
+
+    // Ten units would cost $1,030 with the fee
+    let max = TradingSystem::get_max_buyable_quantity(&player, &market, &cargo_types, "food");
+    assert_eq!(max, 9);
+    assert!(TradingSystem::buy_cargo(&mut player, &market, &cargo_types, "food", max).is_ok());
+}
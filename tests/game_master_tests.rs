@@ -0,0 +1,200 @@
+use uuid::Uuid;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::models::{CargoCategory, NotificationKind};
+use kzrk::systems::{
+    RoomSettings,
+    events::{EventConfig, EventSpec, GAME_MASTER_NAME, MAX_INJECTED_EVENTS, MarketEventType},
+};
+
+// (service, room_id, host_id, guest_id)
+type StoryRoom = (MultiplayerGameService, Uuid, Uuid, Uuid);
+
+fn story_room() -> StoryRoom {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Story Night".to_string(),
+            "Host".to_string(),
+            Some(4),
+            RoomSettings {
+                events: EventConfig {
+                    chance: 0.0,
+                    ..EventConfig::default()
+                },
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let guest = service
+        .join_room(room.room_id, "Guest".to_string(), None)
+        .unwrap()
+        .player_id;
+    (service, room.room_id, room.host_player_id, guest)
+}
+
+fn storm_at_den() -> EventSpec {
+    EventSpec {
+        event_type: MarketEventType::FuelOutage,
+        airport: "DEN".to_string(),
+        cargo: None,
+        category: None,
+        percent: None,
+        turns: 3,
+        description: Some("⛈️ STORM: Thunderstorms ground the fuel trucks at Denver".to_string()),
+    }
+}
+
+#[test]
+fn test_host_injects_a_labelled_event_and_everyone_else_hears() {
+    let (service, room_id, host, guest) = story_room();
+
+    let response = service
+        .host_inject_event(room_id, host, storm_at_den())
+        .unwrap();
+    assert_eq!(response.event.event_type, "FuelOutage");
+    assert_eq!(response.event.injected_by.as_deref(), Some("Host"));
+    assert_eq!(response.notified_players, 1);
+
+    let state = service.get_room_state(room_id, guest).unwrap();
+    let storm = state
+        .active_events
+        .iter()
+        .find(|event| event.affected_airport == "DEN")
+        .unwrap();
+    assert_eq!(storm.injected_by.as_deref(), Some("Host"));
+    assert_eq!(storm.turns_remaining, 3);
+    assert_eq!(
+        storm.headline(),
+        "⛈️ STORM: Thunderstorms ground the fuel trucks at Denver [started by Host]"
+    );
+
+    let inbox = service.get_notifications(room_id, guest).unwrap();
+    let notice = &inbox.notifications[0];
+    assert_eq!(notice.kind, NotificationKind::Announcement);
+    assert_eq!(notice.author_name, "Host");
+    assert!(
+        notice
+            .content
+            .starts_with("Host started an event: ⛈️ STORM")
+    );
+    let host_state = service.get_room_state(room_id, host).unwrap();
+    assert_eq!(host_state.unread_notifications, 0);
+
+    // The same airport can't run out of fuel twice
+    let error = service
+        .host_inject_event(room_id, host, storm_at_den())
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("already out of fuel"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_only_the_host_injects_events() {
+    let (service, room_id, _host, guest) = story_room();
+
+    let error = service
+        .host_inject_event(room_id, guest, storm_at_den())
+        .unwrap_err();
+    assert!(error.to_string().contains("Only the host"), "{}", error);
+    let state = service.get_room_state(room_id, guest).unwrap();
+    assert!(state.active_events.is_empty());
+}
+
+#[test]
+fn test_admin_boom_moves_only_that_category_and_credits_the_game_master() {
+    let (service, room_id, host, _guest) = story_room();
+    let before = service.get_room_state(room_id, host).unwrap();
+    assert_eq!(before.current_market.airport_id, "JFK");
+
+    let response = service
+        .inject_room_event(room_id, EventSpec::luxury_boom("JFK"))
+        .unwrap();
+    assert_eq!(
+        response.event.injected_by.as_deref(),
+        Some(GAME_MASTER_NAME)
+    );
+    assert_eq!(
+        response.event.affected_category,
+        Some(CargoCategory::Luxury)
+    );
+    assert_eq!(response.notified_players, 2);
+
+    let after = service.get_room_state(room_id, host).unwrap();
+    for cargo in &after.cargo_types {
+        let old = before.current_market.cargo_prices[&cargo.id];
+        let new = after.current_market.cargo_prices[&cargo.id];
+        if cargo.category == CargoCategory::Luxury {
+            assert_eq!(new, (old as f64 * 1.4).round() as u32, "{}", cargo.id);
+        } else {
+            assert_eq!(new, old, "{}", cargo.id);
+        }
+    }
+}
+
+#[test]
+fn test_injected_events_are_checked_against_the_room() {
+    let (service, room_id, host, _guest) = story_room();
+    let inject = |spec: EventSpec| service.host_inject_event(room_id, host, spec);
+
+    assert!(
+        inject(EventSpec {
+            airport: "XXX".to_string(),
+            ..storm_at_den()
+        })
+        .unwrap_err()
+        .to_string()
+        .contains("not part of this room")
+    );
+    assert!(
+        inject(EventSpec {
+            turns: 0,
+            ..storm_at_den()
+        })
+        .is_err()
+    );
+    assert!(
+        inject(EventSpec {
+            event_type: MarketEventType::PriceSpike,
+            ..storm_at_den()
+        })
+        .unwrap_err()
+        .to_string()
+        .contains("needs a cargo")
+    );
+    assert!(
+        inject(EventSpec {
+            event_type: MarketEventType::PriceCrash,
+            cargo: Some("electronics".to_string()),
+            percent: Some(50),
+            ..storm_at_den()
+        })
+        .unwrap_err()
+        .to_string()
+        .contains("can't move prices that way")
+    );
+    assert!(
+        inject(EventSpec {
+            category: None,
+            ..EventSpec::luxury_boom("JFK")
+        })
+        .unwrap_err()
+        .to_string()
+        .contains("cargo category")
+    );
+
+    let airports = ["JFK", "LAX", "MIA", "ORD", "DEN", "SEA"];
+    for airport in airports.iter().take(MAX_INJECTED_EVENTS) {
+        inject(EventSpec::luxury_boom(airport)).unwrap();
+    }
+    assert!(
+        inject(storm_at_den())
+            .unwrap_err()
+            .to_string()
+            .contains("at most")
+    );
+}
@@ -0,0 +1,134 @@
+use std::time::{Duration, Instant};
+
+use tempfile::tempdir;
+use uuid::Uuid;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{get_default_airports, get_default_cargo_types};
+use kzrk::models::NotificationKind;
+use kzrk::systems::{GameRoom, multiplayer::GameSpeed};
+
+const MINUTE: Duration = Duration::from_secs(60);
+
+// (service, room_id, host_id, guest_ids)
+type SpeedRoom = (MultiplayerGameService, Uuid, Uuid, Vec<Uuid>);
+
+fn room_with_guests(guests: usize) -> SpeedRoom {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Speed Test".to_string(), "Host".to_string(), Some(4))
+        .unwrap();
+    let guests = (0..guests)
+        .map(|guest| {
+            service
+                .join_room(room.room_id, format!("Guest {}", guest), None)
+                .unwrap()
+                .player_id
+        })
+        .collect();
+    (service, room.room_id, room.host_player_id, guests)
+}
+
+#[test]
+fn test_speed_scales_intervals() {
+    assert_eq!(GameSpeed::default(), GameSpeed::Normal);
+    assert_eq!(GameSpeed::Slow.scale(MINUTE), 2 * MINUTE);
+    assert_eq!(GameSpeed::Normal.scale(MINUTE), MINUTE);
+    assert_eq!(GameSpeed::Fast.scale(MINUTE), MINUTE / 2);
+}
+
+#[test]
+fn test_room_clock_ticks_at_its_speed() {
+    let mut room = GameRoom::new(
+        "Clock".to_string(),
+        Uuid::new_v4(),
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    let start = Instant::now();
+    let at = |seconds: u64| start + Duration::from_secs(seconds);
+
+    // The first pass always ticks; then once a minute at normal speed
+    assert!(room.clock_due(MINUTE, at(0)));
+    assert!(!room.clock_due(MINUTE, at(30)));
+    assert!(room.clock_due(MINUTE, at(60)));
+
+    room.settings.game_speed = GameSpeed::Fast;
+    assert!(room.clock_due(MINUTE, at(90)));
+    assert!(room.clock_due(MINUTE, at(120)));
+
+    room.settings.game_speed = GameSpeed::Slow;
+    assert!(!room.clock_due(MINUTE, at(180)));
+    assert!(room.clock_due(MINUTE, at(240)));
+}
+
+#[test]
+fn test_host_changes_speed_and_everyone_else_hears() {
+    let (service, room_id, host, guests) = room_with_guests(2);
+
+    let response = service
+        .set_game_speed(room_id, host, GameSpeed::Fast)
+        .unwrap();
+    assert_eq!(response.game_speed, GameSpeed::Fast);
+    assert_eq!(response.notified_players, 2);
+
+    for guest in &guests {
+        let state = service.get_room_state(room_id, *guest).unwrap();
+        assert_eq!(state.game_speed, GameSpeed::Fast);
+        assert_eq!(state.unread_notifications, 1);
+
+        let inbox = service.get_notifications(room_id, *guest).unwrap();
+        let notice = &inbox.notifications[0];
+        assert_eq!(notice.kind, NotificationKind::Announcement);
+        assert_eq!(notice.author_name, "Host");
+        assert_eq!(notice.content, "Host set the game speed to fast");
+    }
+    // The host made the change; nothing to tell them
+    let host_state = service.get_room_state(room_id, host).unwrap();
+    assert_eq!(host_state.unread_notifications, 0);
+
+    // Picking the speed the room already runs at tells nobody
+    let unchanged = service
+        .set_game_speed(room_id, host, GameSpeed::Fast)
+        .unwrap();
+    assert_eq!(unchanged.notified_players, 0);
+    let state = service.get_room_state(room_id, guests[0]).unwrap();
+    assert_eq!(state.unread_notifications, 1);
+}
+
+#[test]
+fn test_only_the_host_changes_speed() {
+    let (service, room_id, host, guests) = room_with_guests(1);
+
+    let error = service
+        .set_game_speed(room_id, guests[0], GameSpeed::Slow)
+        .unwrap_err();
+    assert!(error.to_string().contains("Only the host"), "{}", error);
+
+    let state = service.get_room_state(room_id, host).unwrap();
+    assert_eq!(state.game_speed, GameSpeed::Normal);
+}
+
+#[test]
+fn test_speed_survives_a_restart() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("speed.db");
+    let db_path = db_path.to_str().unwrap();
+
+    let (room_id, host) = {
+        let service = MultiplayerGameService::new_with_db_path(db_path);
+        let room = service
+            .create_room("Persisted".to_string(), "Host".to_string(), Some(2))
+            .unwrap();
+        service
+            .set_game_speed(room.room_id, room.host_player_id, GameSpeed::Slow)
+            .unwrap();
+        (room.room_id, room.host_player_id)
+    };
+
+    let service = MultiplayerGameService::new_with_db_path(db_path);
+    let state = service.get_room_state(room_id, host).unwrap();
+    assert_eq!(state.game_speed, GameSpeed::Slow);
+}
@@ -0,0 +1,116 @@
+use uuid::Uuid;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::models::Airport;
+use kzrk::systems::{
+    RoomSettings, TravelSystem,
+    travel::{GROUND_TRANSFER_BASE_FARE, GROUND_TRANSFER_MAX_KM},
+};
+
+fn airport(id: &str, name: &str, coordinates: (f64, f64)) -> Airport {
+    Airport::new(id, name, coordinates, 80, vec![], vec![], 1.0)
+}
+
+/// New York's three airports, plus Chicago for something out of driving range
+fn metro_airports() -> Vec<Airport> {
+    vec![
+        airport("JFK", "New York JFK", (40.6413, -73.7781)),
+        airport("EWR", "Newark", (40.6895, -74.1745)),
+        airport("LGA", "LaGuardia", (40.7769, -73.8740)),
+        airport("ORD", "Chicago O'Hare", (41.9742, -87.9073)),
+    ]
+}
+
+fn metro_room(starting_money: u32) -> (MultiplayerGameService, Uuid, Uuid) {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Tri-State".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                starting_money,
+                custom_airports: metro_airports(),
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    (service, room.room_id, room.host_player_id)
+}
+
+#[test]
+fn test_ground_transfer_fares() {
+    let airports = metro_airports();
+    let (jfk, ewr, ord) = (&airports[0], &airports[1], &airports[3]);
+
+    let fare = TravelSystem::ground_transfer_fare(jfk, ewr).unwrap();
+    assert!(fare > GROUND_TRANSFER_BASE_FARE);
+    assert_eq!(TravelSystem::ground_transfer_fare(ewr, jfk), Some(fare));
+    assert!(jfk.distance_to(ord) > GROUND_TRANSFER_MAX_KM);
+    assert_eq!(TravelSystem::ground_transfer_fare(jfk, ord), None);
+    assert_eq!(TravelSystem::ground_transfer_fare(jfk, jfk), None);
+}
+
+#[test]
+fn test_ground_transfer_costs_money_not_fuel() {
+    let (service, room_id, player_id) = metro_room(5000);
+
+    let before = service.get_room_state(room_id, player_id).unwrap();
+    let ewr = before
+        .available_destinations
+        .iter()
+        .find(|destination| destination.airport_id == "EWR")
+        .unwrap();
+    let fare = ewr.ground_transfer_fare.unwrap();
+    let ord = before
+        .available_destinations
+        .iter()
+        .find(|destination| destination.airport_id == "ORD")
+        .unwrap();
+    assert_eq!(ord.ground_transfer_fare, None);
+
+    let transfer = service
+        .player_ground_transfer(room_id, player_id, "EWR".to_string())
+        .unwrap();
+    assert!(transfer.success, "{}", transfer.message);
+    assert_eq!(transfer.fuel_consumed, Some(0));
+    assert_eq!(transfer.new_location.as_deref(), Some("EWR"));
+
+    let after = service.get_room_state(room_id, player_id).unwrap();
+    assert_eq!(after.players[0].current_airport, "EWR");
+    assert_eq!(after.players[0].fuel, before.players[0].fuel);
+    assert_eq!(after.players[0].money, before.players[0].money - fare);
+    assert_eq!(after.turn_number, before.turn_number + 1);
+}
+
+#[test]
+fn test_ground_transfer_limits() {
+    let (service, room_id, player_id) = metro_room(5000);
+
+    let too_far = service
+        .player_ground_transfer(room_id, player_id, "ORD".to_string())
+        .unwrap_err();
+    assert!(too_far.to_string().contains("too far"), "{}", too_far);
+    assert!(
+        service
+            .player_ground_transfer(room_id, player_id, "JFK".to_string())
+            .is_err()
+    );
+
+    // Players who can't pay the fare stay put
+    let (service, room_id, player_id) = metro_room(10);
+    let broke = service
+        .player_ground_transfer(room_id, player_id, "LGA".to_string())
+        .unwrap();
+    assert!(!broke.success);
+    assert!(
+        broke.message.contains("Not enough money"),
+        "{}",
+        broke.message
+    );
+    let state = service.get_room_state(room_id, player_id).unwrap();
+    assert_eq!(state.players[0].current_airport, "JFK");
+    assert_eq!(state.turn_number, 1);
+}
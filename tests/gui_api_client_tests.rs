@@ -68,7 +68,12 @@ mod gui_tests {
         assert!(rooms.is_empty());
 
         let create_response = client
-            .create_room("Test Room".to_string(), "TestHost".to_string(), Some(4))
+            .create_room(
+                "Test Room".to_string(),
+                "TestHost".to_string(),
+                Some(4),
+                None,
+            )
             .await
             .unwrap();
 
@@ -78,4 +83,47 @@ mod gui_tests {
         assert_eq!(rooms[0].id, create_response.room_id);
     }
     */
+
+    #[test]
+    fn test_location_registry_covers_every_location() {
+        use kzrk::ui::scenes::{
+            Location,
+            airport::{LocationContext, LocationRegistry, LocationView},
+        };
+
+        let registry = LocationRegistry::default();
+        let locations: Vec<Location> = registry.views().map(|view| view.location()).collect();
+        assert_eq!(locations.first(), Some(&Location::MainDesk));
+        for location in [
+            Location::MainDesk,
+            Location::MarketBoard,
+            Location::TradingDesk,
+            Location::FlightPlanning,
+            Location::FuelPump,
+            Location::MessageBoard,
+            Location::Statistics,
+        ] {
+            assert!(registry.get(&location).is_some(), "{:?} missing", location);
+        }
+
+        // Registering a view for an existing location replaces it in place
+        struct QuietDesk;
+        impl LocationView for QuietDesk {
+            fn location(&self) -> Location {
+                Location::MainDesk
+            }
+            fn label(&self) -> &'static str {
+                "Quiet Desk"
+            }
+            fn render(&self, _context: LocationContext<'_>, _ui: &mut eframe::egui::Ui) {}
+        }
+
+        let mut registry = LocationRegistry::default();
+        registry.register(Box::new(QuietDesk));
+        assert_eq!(registry.views().count(), locations.len());
+        assert_eq!(
+            registry.get(&Location::MainDesk).unwrap().label(),
+            "Quiet Desk"
+        );
+    }
 }
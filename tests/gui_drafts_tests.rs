@@ -0,0 +1,77 @@
+#[cfg(feature = "gui")]
+mod gui_tests {
+    use uuid::Uuid;
+
+    use kzrk::models::MessageTopic;
+    use kzrk::systems::offers::OfferSide;
+    use kzrk::ui::scenes::{SceneState, airport::drafts::SceneDraft, room_lobby::GameSession};
+
+    fn session() -> GameSession {
+        GameSession {
+            room_id: Uuid::new_v4(),
+            player_id: Uuid::new_v4(),
+            player_name: "Drafty".to_string(),
+        }
+    }
+
+    fn half_finished() -> SceneState {
+        SceneState {
+            message_input: "Cheap electronics at @LAX, hurry".to_string(),
+            show_message_compose: true,
+            courier_destination: Some("ORD".to_string()),
+            post_topic: MessageTopic::TradeTips,
+            selected_cargo: Some("electronics".to_string()),
+            trade_quantity: 12,
+            offer_side: OfferSide::Buy,
+            offer_quantity: 4,
+            offer_price: 180,
+            fuel_quantity: 75,
+            selected_destination: Some("LAX".to_string()),
+            loan_amount: 2500,
+            ..SceneState::default()
+        }
+    }
+
+    #[test]
+    fn test_draft_survives_a_round_trip_to_disk_format() {
+        let session = session();
+        let draft = SceneDraft::capture(&half_finished(), &session);
+        let json = serde_json::to_string(&draft).unwrap();
+        let loaded: SceneDraft = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, draft);
+
+        let mut scene_state = SceneState::default();
+        assert!(loaded.restore(&mut scene_state, &session));
+        assert_eq!(SceneDraft::capture(&scene_state, &session), draft);
+        assert_eq!(
+            scene_state.message_input,
+            "Cheap electronics at @LAX, hurry"
+        );
+        assert!(scene_state.show_message_compose);
+        assert_eq!(scene_state.selected_destination.as_deref(), Some("LAX"));
+        assert!(!scene_state.fuel_preset_pending);
+    }
+
+    #[test]
+    fn test_drafts_stay_with_their_session() {
+        let draft = SceneDraft::capture(&half_finished(), &session());
+
+        let mut scene_state = SceneState::default();
+        assert!(!draft.restore(&mut scene_state, &session()));
+        assert!(scene_state.message_input.is_empty());
+        assert_eq!(scene_state.selected_cargo, None);
+    }
+
+    #[test]
+    fn test_older_draft_files_fill_in_what_they_lack() {
+        let session = session();
+        let json = format!(
+            r#"{{"room_id": "{}", "player_id": "{}", "message_input": "hello"}}"#,
+            session.room_id, session.player_id
+        );
+        let draft: SceneDraft = serde_json::from_str(&json).unwrap();
+        assert!(draft.belongs_to(&session));
+        assert_eq!(draft.message_input, "hello");
+        assert_eq!(draft.selected_cargo, None);
+    }
+}
@@ -0,0 +1,94 @@
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::models::Player;
+use kzrk::models::aircraft::{AircraftUpgrade, MAX_UPGRADE_LEVEL};
+use kzrk::systems::RoomSettings;
+use kzrk::systems::hangar::{CARGO_HOLD_STEP, FUEL_TANK_STEP, HangarSystem};
+
+#[test]
+fn test_upgrades_raise_capacity_and_cost_money() {
+    let mut player = Player::new(50_000, "JFK", 100, 500, 10.0);
+
+    assert_eq!(
+        HangarSystem::buy_upgrade(&mut player, AircraftUpgrade::FuelTank),
+        Ok(4_000)
+    );
+    assert_eq!(player.max_fuel, 100 + FUEL_TANK_STEP);
+    assert_eq!(player.money, 46_000);
+
+    assert_eq!(
+        HangarSystem::buy_upgrade(&mut player, AircraftUpgrade::CargoHold),
+        Ok(6_000)
+    );
+    assert_eq!(player.max_cargo_weight, 500 + CARGO_HOLD_STEP);
+
+    HangarSystem::buy_upgrade(&mut player, AircraftUpgrade::FuelEfficiency).unwrap();
+    assert!(player.fuel_efficiency > 10.0);
+    assert_eq!(player.aircraft.level(AircraftUpgrade::FuelEfficiency), 1);
+}
+
+#[test]
+fn test_each_level_costs_more_until_maxed_out() {
+    let mut player = Player::new(1_000_000, "JFK", 100, 500, 10.0);
+    let costs: Vec<u32> = (0..MAX_UPGRADE_LEVEL)
+        .map(|_| HangarSystem::buy_upgrade(&mut player, AircraftUpgrade::Engines).unwrap())
+        .collect();
+    assert_eq!(costs, vec![5_000, 10_000, 15_000]);
+    assert_eq!(player.aircraft.next_cost(AircraftUpgrade::Engines), None);
+
+    let money = player.money;
+    assert!(HangarSystem::buy_upgrade(&mut player, AircraftUpgrade::Engines).is_err());
+    assert_eq!(player.money, money);
+    assert_eq!(
+        player.aircraft.level(AircraftUpgrade::Engines),
+        MAX_UPGRADE_LEVEL
+    );
+}
+
+#[test]
+fn test_upgrades_must_be_affordable() {
+    let mut player = Player::new(3_000, "JFK", 100, 500, 10.0);
+    let err = HangarSystem::buy_upgrade(&mut player, AircraftUpgrade::FuelTank).unwrap_err();
+    assert!(err.contains("costs $4000"), "{}", err);
+    assert_eq!(player.max_fuel, 100);
+    assert_eq!(player.money, 3_000);
+}
+
+#[test]
+fn test_faster_engines_cut_the_chance_of_losing_cargo() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Hangar Day".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                starting_money: 20_000,
+                cargo_loss_chance: 0.2,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, player_id) = (room.room_id, room.host_player_id);
+
+    let before = service.get_hangar(room_id, player_id).unwrap();
+    assert_eq!(before.money, 20_000);
+    assert!(before.upgrades.iter().all(|upgrade| upgrade.level == 0));
+
+    let after = service
+        .buy_upgrade(room_id, player_id, AircraftUpgrade::Engines)
+        .unwrap();
+    assert_eq!(after.money, 15_000);
+    assert!(after.cargo_loss_chance < before.cargo_loss_chance);
+    let engines = after
+        .upgrades
+        .iter()
+        .find(|upgrade| upgrade.upgrade == AircraftUpgrade::Engines)
+        .unwrap();
+    assert_eq!(engines.level, 1);
+    assert_eq!(engines.next_cost, Some(10_000));
+
+    // The purchase is saved with the room
+    let saved = service.get_hangar(room_id, player_id).unwrap();
+    assert_eq!(saved.money, after.money);
+    assert_eq!(saved.cargo_loss_chance, after.cargo_loss_chance);
+}
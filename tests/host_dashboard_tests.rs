@@ -0,0 +1,148 @@
+use chrono::Duration;
+use tempfile::tempdir;
+use uuid::Uuid;
+
+use kzrk::api::database::Database;
+use kzrk::api::models::{RoomAlert, STALLED_ROOM_MINUTES};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{
+    GameRoom, RoomSettings,
+    multiplayer::{STARTING_MAX_FUEL, SpawnRule},
+};
+
+fn ready_to_fly() -> RoomSettings {
+    RoomSettings {
+        spawn: SpawnRule::HostChosen("JFK".to_string()),
+        starting_fuel: Some(STARTING_MAX_FUEL),
+        cargo_loss_chance: 0.0,
+        fuel_outage_chance: 0.0,
+        landing_slots: false,
+        ..RoomSettings::default()
+    }
+}
+
+fn host_room(service: &MultiplayerGameService, name: &str, host: &str) -> (Uuid, Uuid) {
+    let room = service
+        .create_room_with_settings(name.to_string(), host.to_string(), None, ready_to_fly())
+        .unwrap();
+    (room.room_id, room.host_player_id)
+}
+
+#[test]
+fn test_hosts_see_every_room_they_host() {
+    let service = MultiplayerGameService::new_in_memory();
+    let (alpha, host) = host_room(&service, "Alpha", "Host");
+    host_room(&service, "Beta", "Host");
+    host_room(&service, "Gamma", "Other");
+    service.join_room(alpha, "Guest".to_string(), None).unwrap();
+    let response = service
+        .player_travel(alpha, host, "ORD".to_string())
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+
+    let rooms = service.hosted_rooms("Host", chrono::Utc::now()).unwrap();
+    let mut names: Vec<&str> = rooms.iter().map(|room| room.room_name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, ["Alpha", "Beta"]);
+    assert!(rooms.iter().all(|room| room.alerts.is_empty()));
+
+    let room = rooms.iter().find(|room| room.room_id == alpha).unwrap();
+    assert_eq!(room.host_player_id, host);
+    assert_eq!((room.players_online, room.players_total), (2, 2));
+    assert_eq!(room.turn_number, 2);
+    let pilots: Vec<(&str, u32)> = room
+        .players
+        .iter()
+        .map(|player| (player.player_name.as_str(), player.turn_number))
+        .collect();
+    assert_eq!(pilots, [("Host", 2), ("Guest", 1)]);
+
+    // Joining a room doesn't make it yours
+    assert_eq!(
+        service
+            .hosted_rooms("Guest", chrono::Utc::now())
+            .unwrap()
+            .len(),
+        0
+    );
+    assert_eq!(
+        service
+            .hosted_rooms("Other", chrono::Utc::now())
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_rooms_nobody_plays_in_are_flagged_as_stalled() {
+    let service = MultiplayerGameService::new_in_memory();
+    host_room(&service, "Quiet", "Host");
+    let (busy, host) = host_room(&service, "Busy", "Host");
+
+    let last_activity = service
+        .hosted_rooms("Host", chrono::Utc::now())
+        .unwrap()
+        .iter()
+        .map(|room| room.last_activity)
+        .max()
+        .unwrap();
+    let almost = last_activity + Duration::minutes(STALLED_ROOM_MINUTES - 1);
+    let rooms = service.hosted_rooms("Host", almost).unwrap();
+    assert!(rooms.iter().all(|room| room.alerts.is_empty()));
+
+    // A pilot checking in keeps their room off the alerts; the stalled one
+    // comes first
+    service.get_room_state(busy, host).unwrap();
+    let stalled = last_activity + Duration::minutes(STALLED_ROOM_MINUTES);
+    let rooms = service.hosted_rooms("Host", stalled).unwrap();
+    assert_eq!(rooms[0].room_name, "Quiet");
+    assert_eq!(
+        rooms[0].alerts,
+        [RoomAlert::Stalled {
+            idle_minutes: STALLED_ROOM_MINUTES
+        }]
+    );
+    assert_eq!(rooms[1].room_name, "Busy");
+    assert!(rooms[1].alerts.is_empty());
+}
+
+#[test]
+fn test_finished_rooms_say_who_won() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_host_dashboard.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    let host_id = Uuid::new_v4();
+    let guest_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Done".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.add_player(guest_id, "Guest".to_string(), None)
+        .unwrap();
+    room.get_player_mut(&guest_id).unwrap().player.money = room.settings.win_condition_money;
+    assert!(room.check_for_winner(&guest_id));
+    Database::new(db_path_str)
+        .unwrap()
+        .save_room(&room)
+        .unwrap();
+
+    let service = MultiplayerGameService::new_with_db_path(db_path_str);
+    // A finished game isn't stalled, however long it's been
+    let rooms = service
+        .hosted_rooms("Host", chrono::Utc::now() + Duration::days(3))
+        .unwrap();
+    assert_eq!(rooms.len(), 1);
+    assert_eq!(
+        rooms[0].alerts,
+        [RoomAlert::Finished {
+            winner: "Guest".to_string()
+        }]
+    );
+}
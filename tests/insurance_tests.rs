@@ -0,0 +1,122 @@
+use rand::{SeedableRng, rngs::StdRng};
+use uuid::Uuid;
+
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::GameRoom;
+use kzrk::systems::insurance::{CLAIM_PROCESSING_TURNS, ClaimStatus};
+
+fn room_with_cargo(cargo_loss_chance: f32) -> (GameRoom, Uuid) {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Insurance Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.settings.cargo_loss_chance = cargo_loss_chance;
+    room.get_player_mut(&host_id)
+        .unwrap()
+        .player
+        .cargo_inventory
+        .add_cargo("electronics", 10);
+    (room, host_id)
+}
+
+#[test]
+fn test_insured_players_pay_a_premium_per_flight() {
+    let (mut room, host_id) = room_with_cargo(0.0);
+    assert!(room.insurance_premium_item(&host_id).is_none());
+
+    room.get_player_mut(&host_id).unwrap().insurance.insured = true;
+    let premium = room.insurance_premium_item(&host_id).unwrap();
+    assert!(premium.money > 0);
+    assert_eq!(premium.fuel, 0);
+
+    // Nothing to insure, nothing to pay
+    room.get_player_mut(&host_id)
+        .unwrap()
+        .player
+        .cargo_inventory
+        .remove_cargo("electronics", 10);
+    assert!(room.insurance_premium_item(&host_id).is_none());
+}
+
+#[test]
+fn test_lost_cargo_leaves_the_hold_and_can_be_claimed() {
+    let (mut room, host_id) = room_with_cargo(1.0);
+    room.get_player_mut(&host_id).unwrap().insurance.insured = true;
+    let money_before = room.get_player(&host_id).unwrap().player.money;
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let loss = room.roll_cargo_loss(&host_id, &mut rng).unwrap();
+    assert!(loss.insured);
+    assert_eq!(loss.cargo_type, "electronics");
+    assert!(loss.quantity >= 1 && loss.quantity <= 10);
+    assert!(loss.value > 0);
+
+    let player_state = room.get_player(&host_id).unwrap();
+    assert_eq!(
+        player_state
+            .player
+            .cargo_inventory
+            .get_quantity("electronics"),
+        10 - loss.quantity
+    );
+    assert_eq!(player_state.insurance.losses, vec![loss.clone()]);
+
+    let turn = player_state.turn_number;
+    let claim = room
+        .get_player_mut(&host_id)
+        .unwrap()
+        .insurance
+        .file_claim(loss.id, turn)
+        .unwrap();
+    assert_eq!(claim.resolves_at_turn, turn + CLAIM_PROCESSING_TURNS);
+
+    // Nothing settles until the claim has been processed
+    assert!(room.resolve_claims(&host_id, &mut rng).is_empty());
+    for _ in 0..CLAIM_PROCESSING_TURNS {
+        room.advance_turn(&host_id);
+    }
+
+    let settled = room.resolve_claims(&host_id, &mut rng);
+    assert_eq!(settled.len(), 1);
+    assert_ne!(settled[0].status, ClaimStatus::Pending);
+    assert_eq!(
+        room.get_player(&host_id).unwrap().player.money,
+        money_before + settled[0].payout
+    );
+}
+
+#[test]
+fn test_uninsured_losses_cannot_be_claimed() {
+    let (mut room, host_id) = room_with_cargo(1.0);
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let loss = room.roll_cargo_loss(&host_id, &mut rng).unwrap();
+    assert!(!loss.insured);
+    assert!(
+        room.get_player_mut(&host_id)
+            .unwrap()
+            .insurance
+            .file_claim(loss.id, 1)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_no_losses_without_cargo_or_bad_luck() {
+    let (mut room, host_id) = room_with_cargo(0.0);
+    let mut rng = StdRng::seed_from_u64(3);
+    assert!(room.roll_cargo_loss(&host_id, &mut rng).is_none());
+
+    let (mut room, host_id) = room_with_cargo(1.0);
+    room.get_player_mut(&host_id)
+        .unwrap()
+        .player
+        .cargo_inventory
+        .remove_cargo("electronics", 10);
+    assert!(room.roll_cargo_loss(&host_id, &mut rng).is_none());
+}
@@ -0,0 +1,163 @@
+use uuid::Uuid;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::models::Airport;
+use kzrk::systems::{
+    RoomSettings,
+    multiplayer::SpawnRule,
+    slots::{HOLDING_FUEL, SLOT_PERIOD_TICKS, SlotBook, SlotStatus},
+};
+
+/// A one-slot hub with a field a few hundred km out where everyone starts
+fn hub_room(pilots: usize) -> (MultiplayerGameService, Uuid, Vec<Uuid>) {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Slot Test".to_string(),
+            "Pilot 0".to_string(),
+            Some(pilots),
+            RoomSettings {
+                custom_airports: vec![
+                    Airport::new("HUB", "Big Hub", (40.0, -75.0), 80, vec![], vec![], 1.0)
+                        .with_landing_slots(1),
+                    Airport::new("FLD", "Outfield", (42.0, -75.0), 80, vec![], vec![], 1.0),
+                ],
+                spawn: SpawnRule::HostChosen("FLD".to_string()),
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+
+    let mut players = vec![room.host_player_id];
+    for pilot in 1..pilots {
+        let joined = service
+            .join_room(room.room_id, format!("Pilot {}", pilot), None)
+            .unwrap();
+        players.push(joined.player_id);
+    }
+    (service, room.room_id, players)
+}
+
+fn hub_slots(service: &MultiplayerGameService, room_id: Uuid, player_id: Uuid) -> SlotStatus {
+    service
+        .get_room_state(room_id, player_id)
+        .unwrap()
+        .available_destinations
+        .into_iter()
+        .find(|destination| destination.airport_id == "HUB")
+        .unwrap()
+        .slot_status
+}
+
+#[test]
+fn test_full_hub_means_a_holding_pattern() {
+    let (service, room_id, players) = hub_room(2);
+    assert_eq!(
+        hub_slots(&service, room_id, players[0]),
+        SlotStatus::Open { free: 1 }
+    );
+
+    let first = service
+        .player_travel(room_id, players[0], "HUB".to_string())
+        .unwrap();
+    assert!(first.success, "{}", first.message);
+
+    // The second pilot pays for circling in fuel and a turn
+    assert_eq!(
+        hub_slots(&service, room_id, players[1]),
+        SlotStatus::Holding
+    );
+    let quote = service
+        .travel_preview(room_id, players[1], "HUB".to_string())
+        .unwrap();
+    assert!(quote.can_travel);
+    assert_eq!(quote.items.last().unwrap().fuel, HOLDING_FUEL);
+
+    let before = service.get_room_state(room_id, players[1]).unwrap();
+    let second = service
+        .player_travel(room_id, players[1], "HUB".to_string())
+        .unwrap();
+    assert!(second.success, "{}", second.message);
+    assert!(
+        second.message.contains("landing slot"),
+        "{}",
+        second.message
+    );
+    assert_eq!(second.fuel_consumed, Some(quote.total_fuel));
+
+    let after = service.get_room_state(room_id, players[1]).unwrap();
+    assert_eq!(after.turn_number, before.turn_number + 2);
+    assert_eq!(after.world_tick, before.world_tick + 2);
+}
+
+#[test]
+fn test_hub_full_this_period_and_next_turns_flights_away() {
+    let (service, room_id, players) = hub_room(4);
+    for &pilot in &players[..3] {
+        let flight = service
+            .player_travel(room_id, pilot, "HUB".to_string())
+            .unwrap();
+        assert!(flight.success, "{}", flight.message);
+    }
+
+    assert_eq!(hub_slots(&service, room_id, players[3]), SlotStatus::Full);
+    let diverted = service
+        .player_travel(room_id, players[3], "HUB".to_string())
+        .unwrap();
+    assert!(!diverted.success);
+    assert!(diverted.message.contains("divert"), "{}", diverted.message);
+}
+
+#[test]
+fn test_rooms_can_turn_slots_off() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "No Slots".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                landing_slots: false,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let state = service
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+    assert!(
+        state
+            .available_destinations
+            .iter()
+            .all(|destination| destination.slot_status == SlotStatus::Unrestricted)
+    );
+}
+
+#[test]
+fn test_slot_book_periods() {
+    let hub = Airport::new("HUB", "Big Hub", (40.0, -75.0), 80, vec![], vec![], 1.0)
+        .with_landing_slots(2);
+    let mut book = SlotBook::default();
+
+    assert_eq!(book.status(&hub, 0), SlotStatus::Open { free: 2 });
+    book.book(&hub, 0, false);
+    book.book(&hub, 1, false);
+    assert_eq!(book.status(&hub, 2), SlotStatus::Holding);
+    book.book(&hub, 2, true);
+    book.book(&hub, 3, true);
+    assert_eq!(book.status(&hub, 3), SlotStatus::Full);
+
+    // A new period opens up what nobody booked ahead
+    assert_eq!(book.status(&hub, SLOT_PERIOD_TICKS), SlotStatus::Holding);
+    book.prune(2 * SLOT_PERIOD_TICKS);
+    assert_eq!(
+        book.status(&hub, 2 * SLOT_PERIOD_TICKS),
+        SlotStatus::Open { free: 2 }
+    );
+
+    let field = Airport::new("FLD", "Outfield", (42.0, -75.0), 80, vec![], vec![], 1.0);
+    book.book(&field, 0, false);
+    assert_eq!(book.status(&field, 0), SlotStatus::Unrestricted);
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use kzrk::api::{
+    load_shedding::LoadPolicy, multiplayer_service::MultiplayerGameService,
+    routes::create_multiplayer_router,
+};
+use reqwest::{Client, StatusCode};
+use serde_json::{Value, json};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::sleep,
+};
+
+async fn start(policy: LoadPolicy) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind to address");
+    let addr = listener.local_addr().unwrap();
+
+    let service = MultiplayerGameService::new_in_memory().with_load_policy(policy);
+    let app = create_multiplayer_router(service);
+    tokio::spawn(async move {
+        axum::serve(listener, app)
+            .await
+            .expect("Failed to start server");
+    });
+    sleep(Duration::from_millis(100)).await;
+    addr.to_string()
+}
+
+/// A request that promises a body and never finishes sending it, so it
+/// holds its place in the server until it times out
+async fn stall(addr: &str) -> TcpStream {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(
+            b"POST /rooms HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: 100\r\n\r\n{\"name\":",
+        )
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(100)).await;
+    stream
+}
+
+#[tokio::test]
+async fn test_requests_beyond_the_limit_are_shed() {
+    let addr = start(LoadPolicy {
+        max_concurrent_requests: 1,
+        request_timeout: Duration::from_secs(5),
+        ..LoadPolicy::default()
+    })
+    .await;
+    let client = Client::new();
+
+    let stalled = stall(&addr).await;
+    let response = client
+        .get(format!("http://{}/rooms", addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "ServerBusy");
+
+    drop(stalled);
+    sleep(Duration::from_millis(100)).await;
+    let response = client
+        .get(format!("http://{}/rooms", addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_slow_requests_time_out() {
+    let addr = start(LoadPolicy {
+        request_timeout: Duration::from_millis(200),
+        ..LoadPolicy::default()
+    })
+    .await;
+
+    let mut stalled = stall(&addr).await;
+    let mut response = vec![0; 1024];
+    let read = tokio::time::timeout(Duration::from_secs(5), stalled.read(&mut response))
+        .await
+        .expect("the server should answer once the request times out")
+        .unwrap();
+    let response = String::from_utf8_lossy(&response[..read]);
+    assert!(response.starts_with("HTTP/1.1 503"), "{}", response);
+    assert!(response.contains("RequestTimedOut"), "{}", response);
+}
+
+#[tokio::test]
+async fn test_oversized_bodies_are_refused() {
+    let addr = start(LoadPolicy {
+        max_body_bytes: 200,
+        ..LoadPolicy::default()
+    })
+    .await;
+    let client = Client::new();
+
+    let response = client
+        .post(format!("http://{}/rooms", addr))
+        .json(&json!({"name": "x".repeat(500), "host_player_name": "Host"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "PayloadTooLarge");
+
+    let response = client
+        .post(format!("http://{}/rooms", addr))
+        .json(&json!({"name": "Small", "host_player_name": "Host"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
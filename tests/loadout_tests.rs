@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+use kzrk::api::models::{ApplyLoadoutRequest, TradeAction, TradeBatchRequest, TradeRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::models::cargo::CargoInventory;
+use kzrk::systems::loadouts::{CargoLoadout, CargoLoadouts, LoadoutTrade, MAX_LOADOUTS};
+
+fn loadout(name: &str, cargo: &[(&str, u32)]) -> CargoLoadout {
+    CargoLoadout {
+        name: name.to_string(),
+        cargo: cargo
+            .iter()
+            .map(|(cargo_id, quantity)| (cargo_id.to_string(), *quantity))
+            .collect::<BTreeMap<_, _>>(),
+    }
+}
+
+fn trade(action: TradeAction, cargo_type: &str, quantity: u32) -> TradeRequest {
+    TradeRequest {
+        cargo_type: cargo_type.to_string(),
+        quantity,
+        action,
+    }
+}
+
+fn setup() -> (MultiplayerGameService, Uuid, Uuid) {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Load-outs".to_string(), "Host".to_string(), Some(2))
+        .unwrap();
+    (service, room.room_id, room.host_player_id)
+}
+
+fn hold(service: &MultiplayerGameService, room_id: Uuid, player_id: Uuid) -> (u32, Vec<String>) {
+    let state = service.get_room_state(room_id, player_id).unwrap();
+    let player = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(player_id))
+        .unwrap();
+    let mut cargo: Vec<String> = player
+        .cargo_inventory
+        .iter()
+        .filter(|(_, quantity)| **quantity > 0)
+        .map(|(cargo_id, quantity)| format!("{} {}", quantity, cargo_id))
+        .collect();
+    cargo.sort();
+    (player.money, cargo)
+}
+
+#[test]
+fn test_plan_sells_the_excess_and_buys_the_shortfall() {
+    let mut inventory = CargoInventory::new();
+    inventory.add_cargo("electronics", 10);
+    inventory.add_cargo("food", 4);
+    inventory.add_cargo("textiles", 3);
+
+    let plan = loadout(
+        "ORD electronics run",
+        &[("electronics", 6), ("luxury", 2), ("food", 4)],
+    )
+    .plan(&inventory);
+    assert_eq!(
+        plan.sell,
+        [
+            LoadoutTrade {
+                cargo_id: "electronics".to_string(),
+                quantity: 4,
+            },
+            LoadoutTrade {
+                cargo_id: "textiles".to_string(),
+                quantity: 3,
+            },
+        ]
+    );
+    assert_eq!(
+        plan.buy,
+        [LoadoutTrade {
+            cargo_id: "luxury".to_string(),
+            quantity: 2,
+        }]
+    );
+
+    let same = CargoLoadout::from_hold("Same".to_string(), &inventory);
+    assert!(same.plan(&inventory).is_empty());
+}
+
+#[test]
+fn test_loadouts_are_named_once_and_capped() {
+    let mut loadouts = CargoLoadouts::default();
+    assert!(loadouts.save(loadout("   ", &[])).is_err());
+    assert!(loadouts.save(loadout(&"x".repeat(41), &[])).is_err());
+
+    loadouts
+        .save(loadout(" Food run ", &[("food", 10), ("luxury", 0)]))
+        .unwrap();
+    loadouts.save(loadout("FOOD RUN", &[("food", 20)])).unwrap();
+    assert_eq!(loadouts.all().len(), 1);
+    let saved = loadouts.get("food run").unwrap();
+    assert_eq!(saved.name, "FOOD RUN");
+    assert_eq!(saved.cargo.get("food"), Some(&20));
+
+    for index in 1..MAX_LOADOUTS {
+        loadouts
+            .save(loadout(&format!("Run {}", index), &[]))
+            .unwrap();
+    }
+    assert!(loadouts.save(loadout("One too many", &[])).is_err());
+
+    assert!(loadouts.remove("Food Run"));
+    assert!(!loadouts.remove("Food Run"));
+    loadouts.save(loadout("One too many", &[])).unwrap();
+}
+
+#[test]
+fn test_batches_go_through_all_or_not_at_all() {
+    let (service, room_id, host_id) = setup();
+    let before = hold(&service, room_id, host_id);
+
+    let err = service
+        .player_trade_batch(
+            room_id,
+            host_id,
+            TradeBatchRequest {
+                trades: vec![
+                    trade(TradeAction::Buy, "food", 2),
+                    trade(TradeAction::Buy, "luxury", 1000),
+                ],
+            },
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("Trade 2"), "{err}");
+    assert!(err.to_string().contains("none were made"), "{err}");
+    assert_eq!(hold(&service, room_id, host_id), before);
+    // The food bought first comes off the statistics as well as the hold
+    let statistics = service.get_player_statistics(room_id, host_id).unwrap();
+    assert_eq!(statistics.cargo_trades, 0);
+    assert_eq!(statistics.total_expenses, 0);
+
+    let response = service
+        .player_trade_batch(
+            room_id,
+            host_id,
+            TradeBatchRequest {
+                trades: vec![
+                    trade(TradeAction::Buy, "food", 2),
+                    trade(TradeAction::Buy, "materials", 3),
+                    trade(TradeAction::Sell, "food", 1),
+                ],
+            },
+        )
+        .unwrap();
+    assert_eq!(response.trades.len(), 3);
+    assert!(response.net_amount < 0);
+    assert!(response.fees > 0);
+    let (money, cargo) = hold(&service, room_id, host_id);
+    assert_eq!(money, response.new_money);
+    assert_eq!(cargo, ["1 food", "3 materials"]);
+}
+
+#[test]
+fn test_one_click_loads_a_saved_loadout() {
+    let (service, room_id, host_id) = setup();
+    let profile = service
+        .save_loadout("Host", loadout("Starter", &[("food", 5), ("materials", 2)]))
+        .unwrap();
+    assert_eq!(profile.loadouts.len(), 1);
+    assert_eq!(
+        service.get_pilot_profile("Host").unwrap().loadouts,
+        profile.loadouts
+    );
+
+    service
+        .player_trade(room_id, host_id, trade(TradeAction::Buy, "textiles", 2))
+        .unwrap();
+
+    let response = service
+        .apply_loadout(
+            room_id,
+            host_id,
+            ApplyLoadoutRequest {
+                name: "starter".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(response.message.contains("Starter"), "{}", response.message);
+    assert_eq!(response.trades.len(), 3);
+    assert!(matches!(response.trades[0].action, TradeAction::Sell));
+    let (_, cargo) = hold(&service, room_id, host_id);
+    assert_eq!(cargo, ["2 materials", "5 food"]);
+
+    let again = service
+        .apply_loadout(
+            room_id,
+            host_id,
+            ApplyLoadoutRequest {
+                name: "Starter".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(again.trades.is_empty());
+    assert!(again.message.contains("already matches"));
+}
+
+#[test]
+fn test_unknown_loadouts_are_reported() {
+    let (service, room_id, host_id) = setup();
+    let err = service
+        .apply_loadout(
+            room_id,
+            host_id,
+            ApplyLoadoutRequest {
+                name: "Nowhere".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("No load-out called 'Nowhere'"),
+        "{err}"
+    );
+
+    service
+        .save_loadout("Host", loadout("Starter", &[("food", 1)]))
+        .unwrap();
+    let profile = service.delete_loadout("Host", "STARTER").unwrap();
+    assert!(profile.loadouts.is_empty());
+    assert!(service.delete_loadout("Host", "Starter").is_err());
+}
@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use kzrk::api::{
+    locale::localize_messages,
+    models::{FuelRequest, PlayerFuelResponse},
+    multiplayer_service::MultiplayerGameService,
+    routes::create_multiplayer_router,
+};
+use kzrk::models::locale::{Locale, Localized, LocalizedMessage, MessageCode};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+/// The `{name}` placeholders in a template
+fn placeholders(template: &str) -> Vec<String> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name.to_string()))
+        .collect()
+}
+
+/// A message for `code` with a different value for each parameter
+fn sample(code: MessageCode) -> LocalizedMessage {
+    placeholders(code.template(Locale::English))
+        .iter()
+        .enumerate()
+        .fold(LocalizedMessage::new(code), |message, (index, name)| {
+            message.with(name, format!("v{}", index + 7))
+        })
+}
+
+#[test]
+fn test_accept_language_picks_the_best_supported_language() {
+    assert_eq!(
+        Locale::negotiate("fr-CH, fr;q=0.9, es;q=0.8, *;q=0.5"),
+        Locale::Spanish
+    );
+    assert_eq!(Locale::negotiate("en;q=0.5, es-MX"), Locale::Spanish);
+    assert_eq!(Locale::negotiate("es;q=0.2, en"), Locale::English);
+    assert_eq!(Locale::negotiate("es;q=0"), Locale::English);
+    assert_eq!(Locale::negotiate("de"), Locale::English);
+    assert_eq!(Locale::negotiate(""), Locale::English);
+
+    assert_eq!(Locale::from_tag("es_ES.UTF-8"), Some(Locale::Spanish));
+    assert_eq!(Locale::from_tag("EN-gb"), Some(Locale::English));
+    assert_eq!(Locale::from_tag("C"), None);
+}
+
+#[test]
+fn test_every_language_has_every_parameter() {
+    for code in MessageCode::ALL {
+        let mut english = placeholders(code.template(Locale::English));
+        english.sort();
+        for locale in Locale::ALL {
+            let mut translated = placeholders(code.template(locale));
+            translated.sort();
+            assert_eq!(translated, english, "{:?} in {:?}", code, locale);
+        }
+    }
+}
+
+#[test]
+fn test_messages_render_every_parameter() {
+    for code in MessageCode::ALL {
+        let message = sample(code);
+        for locale in Locale::ALL {
+            let text = message.render(locale);
+            assert!(!text.contains('{'), "{:?} in {:?}: {}", code, locale, text);
+            for value in message.params.values() {
+                assert!(
+                    text.contains(value.as_str()),
+                    "{:?} in {:?}: {}",
+                    code,
+                    locale,
+                    text
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_detail_is_kept_as_sent() {
+    let headline = LocalizedMessage::new(MessageCode::Traveled)
+        .with("airport_name", "Chicago O'Hare")
+        .with("airport", "ORD");
+    let message = format!("{}. A quiet night", headline);
+    let localized = headline.leading(&message);
+
+    assert_eq!(localized.to_string(), message);
+    assert_eq!(
+        localized.render(Locale::Spanish),
+        "Volaste a Chicago O'Hare (ORD). A quiet night"
+    );
+}
+
+#[test]
+fn test_service_rejections_come_with_codes() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Babel".to_string(), "Host".to_string(), None)
+        .unwrap();
+    let response = service
+        .player_buy_fuel(
+            room.room_id,
+            room.host_player_id,
+            FuelRequest { quantity: 100_000 },
+        )
+        .unwrap();
+
+    assert!(!response.success);
+    let localized = response.localized.clone().unwrap();
+    assert_eq!(response.message, localized.to_string());
+    assert_ne!(
+        response.message_in(Locale::Spanish),
+        response.message_in(Locale::English)
+    );
+
+    let error = service
+        .player_travel(room.room_id, Uuid::new_v4(), "JFK".to_string())
+        .unwrap_err();
+    assert_eq!(
+        error.localized().map(|message| message.code),
+        Some(MessageCode::PlayerNotInRoom)
+    );
+
+    // Travel previews send their summary's code too
+    let quote = service
+        .travel_preview(room.room_id, room.host_player_id, "ORD".to_string())
+        .unwrap();
+    let localized = quote.localized.clone().unwrap();
+    assert_eq!(quote.summary, localized.to_string());
+    assert_eq!(
+        quote.message_in(Locale::Spanish),
+        localized.render(Locale::Spanish)
+    );
+}
+
+#[test]
+fn test_nested_messages_are_rendered() {
+    let localized = LocalizedMessage::new(MessageCode::FuelTankFull).with("space", 12);
+    let mut body = json!({
+        "results": [{"message": localized.to_string(), "localized": localized}],
+        "message": "No code here",
+    });
+    localize_messages(&mut body, Locale::Spanish);
+    assert_eq!(
+        body["results"][0]["message"],
+        "En el depósito solo caben 12 unidades más"
+    );
+    assert_eq!(body["message"], "No code here");
+}
+
+#[tokio::test]
+async fn test_server_answers_in_the_language_asked_for() {
+    let app = create_multiplayer_router(MultiplayerGameService::new_in_memory());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+    let room: Value = client
+        .post(format!("{}/rooms", base_url))
+        .json(&json!({"name": "Babel", "host_player_name": "Host"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let fuel_url = format!(
+        "{}/rooms/{}/players/{}/fuel",
+        base_url,
+        room["room_id"].as_str().unwrap(),
+        room["host_player_id"].as_str().unwrap()
+    );
+
+    let spanish = client
+        .post(&fuel_url)
+        .header("Accept-Language", "es-ES,es;q=0.9,en;q=0.8")
+        .json(&json!({"quantity": 100_000}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(spanish.headers()["content-language"], "es");
+    let spanish: PlayerFuelResponse = spanish.json().await.unwrap();
+    let localized = spanish.localized.clone().unwrap();
+    assert_eq!(spanish.message, localized.render(Locale::Spanish));
+
+    let english = client
+        .post(&fuel_url)
+        .json(&json!({"quantity": 100_000}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(english.headers()["content-language"], "en");
+    let english: PlayerFuelResponse = english.json().await.unwrap();
+    assert_eq!(english.message, localized.to_string());
+
+    let missing: Value = client
+        .get(format!(
+            "{}/rooms/{}/players/{}/state",
+            base_url,
+            Uuid::new_v4(),
+            Uuid::new_v4()
+        ))
+        .header("Accept-Language", "es")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(missing["error"], "RoomNotFound");
+    assert_eq!(missing["message"], "Sala no encontrada");
+    assert_eq!(missing["localized"]["code"], "RoomNotFound");
+}
@@ -1,6 +1,18 @@
+use std::time::{Duration, Instant};
+
+use rand::{SeedableRng, rngs::StdRng};
 use uuid::Uuid;
 
-use kzrk::{api::multiplayer_service::MultiplayerGameService, models::MessageBoard};
+use kzrk::{
+    api::{
+        errors::{ErrorCode, ServiceError},
+        models::{ExportFormat, MessageExportQuery, MessagesQuery},
+        multiplayer_service::MultiplayerGameService,
+    },
+    data::{airports::get_default_airports, cargo_types::get_default_cargo_types},
+    models::{BoardLimits, MessageBoard, MessageTopic},
+    systems::{GameRoom, RoomSettings},
+};
 
 #[test]
 fn test_message_board_post_and_retrieve() {
@@ -257,3 +269,739 @@ async fn test_message_board_location_based() {
     // 2. Messages at ORD are only visible at ORD
     // 3. Player location changes correctly affect message visibility
 }
+
+#[tokio::test]
+async fn test_room_message_board_limits() {
+    use kzrk::models::BoardLimits;
+
+    let service = MultiplayerGameService::new_in_memory();
+    let settings = RoomSettings {
+        message_board: BoardLimits {
+            max_message_length: 20,
+            max_messages_per_airport: 2,
+            post_cooldown_seconds: 30,
+            ..BoardLimits::default()
+        },
+        ..RoomSettings::default()
+    };
+
+    let create_response = service
+        .create_room_with_settings(
+            "Quiet Room".to_string(),
+            "Host".to_string(),
+            Some(4),
+            settings,
+        )
+        .unwrap();
+    let room_id = create_response.room_id;
+    let host_id = create_response.host_player_id;
+
+    let too_long = service
+        .post_message(
+            room_id,
+            host_id,
+            "This message is much too long".to_string(),
+        )
+        .unwrap();
+    assert!(!too_long.success);
+    assert_eq!(
+        too_long.message,
+        "Message content cannot exceed 20 characters"
+    );
+
+    assert!(
+        service
+            .post_message(room_id, host_id, "Cheap fuel".to_string())
+            .unwrap()
+            .success
+    );
+
+    let too_soon = service
+        .post_message(room_id, host_id, "Cheaper now".to_string())
+        .unwrap();
+    assert!(!too_soon.success);
+    assert!(too_soon.message.contains("You can post again in"));
+
+    // The cooldown is per pilot, and each airport keeps only the newest messages
+    for name in ["Alice", "Bob"] {
+        let joined = service.join_room(room_id, name.to_string(), None).unwrap();
+        assert!(
+            service
+                .post_message(room_id, joined.player_id, format!("Hi from {}", name))
+                .unwrap()
+                .success
+        );
+    }
+
+    let board = service.get_messages(room_id, host_id).unwrap();
+    assert_eq!(board.total_count, 2);
+    assert_eq!(board.limits.max_message_length, 20);
+    assert!(board.messages.iter().all(|m| m.author_name != "Host"));
+
+    // Nonsensical limits are rejected up front
+    let invalid = RoomSettings {
+        message_board: BoardLimits {
+            max_message_length: 0,
+            ..BoardLimits::default()
+        },
+        ..RoomSettings::default()
+    };
+    assert!(
+        service
+            .create_room_with_settings("Mute Room".to_string(), "Host".to_string(), None, invalid)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_npc_chatter_fills_quiet_boards_without_burying_players() {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Chatty Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    let mut rng = StdRng::seed_from_u64(11);
+
+    let airports = room.shared_state.airports.len();
+    assert_eq!(room.post_npc_chatter(1.0, &mut rng), airports);
+    let jfk = room.message_board.get_messages("JFK", None);
+    assert_eq!(jfk.len(), 1);
+    assert!(jfk[0].is_npc);
+    assert_eq!(jfk[0].author_id, Uuid::nil());
+
+    // The locals go quiet once they're only talking to each other
+    for _ in 0..10 {
+        room.post_npc_chatter(1.0, &mut rng);
+    }
+    assert_eq!(room.message_board.message_count(Some("JFK")), 3);
+
+    // A pilot's reply gets them going again
+    room.message_board
+        .post_message(
+            host_id,
+            "Host".to_string(),
+            "Anyone flying to ORD?".to_string(),
+            "JFK".to_string(),
+        )
+        .unwrap();
+    room.post_npc_chatter(1.0, &mut rng);
+    assert_eq!(room.message_board.message_count(Some("JFK")), 5);
+
+    // Rooms can turn them off
+    room.settings.npc_chatter = false;
+    assert_eq!(room.post_npc_chatter(1.0, &mut rng), 0);
+}
+
+#[tokio::test]
+async fn test_npc_posts_are_marked_in_the_api() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Chatty Room".to_string(), "Host".to_string(), None)
+        .unwrap();
+
+    // Chatter is random, so tick until a local has spoken at JFK. With no
+    // interval every room's clock is always due.
+    for _ in 0..500 {
+        service
+            .tick_room_clocks(Duration::ZERO, Instant::now())
+            .unwrap();
+        if !service
+            .get_messages(room.room_id, room.host_player_id)
+            .unwrap()
+            .messages
+            .is_empty()
+        {
+            break;
+        }
+    }
+
+    let board = service
+        .get_messages(room.room_id, room.host_player_id)
+        .unwrap();
+    assert!(!board.messages.is_empty());
+    assert!(board.messages.iter().all(|message| message.is_npc));
+
+    let quiet = service
+        .create_room_with_settings(
+            "Quiet Room".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                npc_chatter: false,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    for _ in 0..50 {
+        service
+            .tick_room_clocks(Duration::ZERO, Instant::now())
+            .unwrap();
+    }
+    let board = service
+        .get_messages(quiet.room_id, quiet.host_player_id)
+        .unwrap();
+    assert!(board.messages.is_empty());
+}
+
+#[test]
+fn test_mentions_notify_the_mentioned_player() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Mention Room".to_string(), "Host".to_string(), None)
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    let guest_id = service
+        .join_room(room_id, "Guest Pilot".to_string(), None)
+        .unwrap()
+        .player_id;
+
+    let posts = [
+        (guest_id, "@Guest Pilot talking to myself"),
+        (host_id, "no mention of the guest pilot here"),
+        (host_id, "@Guest Pilotage is not a name"),
+        (host_id, "@GUEST PILOT are you buying the electronics?"),
+    ];
+    for (author, content) in posts {
+        let posted = service
+            .post_message(room_id, author, content.to_string())
+            .unwrap();
+        assert!(posted.success, "{}", posted.message);
+    }
+
+    // Only the host's @ counts: authors aren't notified of their own posts
+    let guest = service.get_notifications(room_id, guest_id).unwrap();
+    assert_eq!(guest.unread, 1);
+    assert_eq!(
+        guest.notifications[0].content,
+        "@GUEST PILOT are you buying the electronics?"
+    );
+    assert_eq!(
+        service
+            .get_room_state(room_id, guest_id)
+            .unwrap()
+            .unread_notifications,
+        1
+    );
+    assert_eq!(
+        service
+            .get_room_state(room_id, host_id)
+            .unwrap()
+            .unread_notifications,
+        0
+    );
+
+    // The board highlights messages addressed to whoever is reading it
+    let board = service.get_messages(room_id, guest_id).unwrap();
+    let flagged: Vec<&str> = board
+        .messages
+        .iter()
+        .filter(|message| message.mentions_you)
+        .map(|message| message.content.as_str())
+        .collect();
+    assert_eq!(
+        flagged,
+        vec![
+            "@GUEST PILOT are you buying the electronics?",
+            "@Guest Pilot talking to myself"
+        ]
+    );
+}
+
+#[test]
+fn test_notifications_can_be_marked_read() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Inbox Room".to_string(), "Host".to_string(), None)
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    let guest_id = service
+        .join_room(room_id, "Guest".to_string(), None)
+        .unwrap()
+        .player_id;
+
+    for content in ["@host first", "@host second", "@host third"] {
+        service
+            .post_message(room_id, guest_id, content.to_string())
+            .unwrap();
+    }
+
+    let inbox = service.get_notifications(room_id, host_id).unwrap();
+    assert_eq!(inbox.unread, 3);
+    // Newest first
+    assert_eq!(inbox.notifications[0].content, "@host third");
+    assert_eq!(inbox.notifications[0].author_name, "Guest");
+    assert_eq!(inbox.notifications[0].airport_id, "JFK");
+
+    let oldest = inbox.notifications[2].id;
+    let inbox = service
+        .mark_notifications_read(room_id, host_id, vec![oldest])
+        .unwrap();
+    assert_eq!(inbox.unread, 2);
+    assert!(inbox.notifications[2].read);
+
+    let inbox = service
+        .mark_notifications_read(room_id, host_id, vec![])
+        .unwrap();
+    assert_eq!(inbox.unread, 0);
+    assert_eq!(inbox.notifications.len(), 3);
+
+    assert_eq!(
+        service
+            .get_notifications(room_id, Uuid::new_v4())
+            .unwrap_err(),
+        ServiceError::NotFound(ErrorCode::PlayerNotInRoom)
+    );
+}
+
+#[test]
+fn test_boards_save_as_one_list_and_load_back_per_airport() {
+    let mut board = MessageBoard::new(100);
+    let author = Uuid::new_v4();
+    for (content, airport) in [("one", "JFK"), ("two", "ORD"), ("three", "JFK")] {
+        board
+            .post_message(
+                author,
+                "Pilot".to_string(),
+                content.to_string(),
+                airport.to_string(),
+            )
+            .unwrap();
+    }
+
+    // Saved rooms and games keep the flat list older versions wrote
+    let json = serde_json::to_value(&board).unwrap();
+    assert_eq!(json["messages"].as_array().unwrap().len(), 3);
+
+    let loaded: MessageBoard = serde_json::from_value(json).unwrap();
+    let at_jfk: Vec<&str> = loaded
+        .get_messages("JFK", None)
+        .iter()
+        .map(|message| message.content.as_str())
+        .collect();
+    assert_eq!(at_jfk, vec!["three", "one"]);
+    assert_eq!(loaded.message_count(Some("ORD")), 1);
+    assert_eq!(loaded.message_count(None), 3);
+    assert_eq!(loaded.airport_ids().collect::<Vec<_>>(), vec!["JFK", "ORD"]);
+
+    assert!(
+        board
+            .post_message(
+                author,
+                "Pilot".to_string(),
+                "nowhere".to_string(),
+                String::new()
+            )
+            .is_err()
+    );
+}
+
+#[test]
+fn test_players_only_read_and_post_where_they_stand() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Scoped Boards".to_string(), "Host".to_string(), Some(2))
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    let host_airport = service
+        .get_room_state(room_id, host_id)
+        .unwrap()
+        .current_market
+        .airport_id;
+    let elsewhere = if host_airport == "ORD" { "DEN" } else { "ORD" };
+    let guest_id = service
+        .join_room(room_id, "Guest".to_string(), Some(elsewhere.to_string()))
+        .unwrap()
+        .player_id;
+
+    let posted = service
+        .post_message(room_id, host_id, "Cheap fuel here".to_string())
+        .unwrap();
+    assert!(posted.success, "{}", posted.message);
+    assert_eq!(posted.airport_id.as_deref(), Some(host_airport.as_str()));
+
+    // Nothing at the guest's airport, and the host's board isn't counted
+    let guest_board = service.get_messages(room_id, guest_id).unwrap();
+    assert_eq!(guest_board.airport_id, elsewhere);
+    assert!(guest_board.messages.is_empty());
+    assert_eq!(guest_board.total_count, 0);
+
+    let guest_post = service
+        .post_message(room_id, guest_id, "Quiet out here".to_string())
+        .unwrap();
+    assert_eq!(guest_post.airport_id.as_deref(), Some(elsewhere));
+
+    let host_board = service.get_messages(room_id, host_id).unwrap();
+    assert_eq!(host_board.total_count, 1);
+    assert_eq!(host_board.messages[0].content, "Cheap fuel here");
+    assert!(
+        host_board
+            .messages
+            .iter()
+            .all(|message| message.airport_id == host_airport)
+    );
+}
+
+#[test]
+fn test_topics_are_read_and_trimmed_separately() {
+    let mut board = MessageBoard::with_limits(BoardLimits {
+        max_messages_per_airport: 3,
+        topic_limits: [(MessageTopic::Warnings, 1)].into_iter().collect(),
+        ..BoardLimits::default()
+    });
+    let author = Uuid::new_v4();
+    let post = |board: &mut MessageBoard, content: &str, topic: MessageTopic| {
+        board
+            .post_to_topic(
+                author,
+                "Pilot".to_string(),
+                content.to_string(),
+                "JFK".to_string(),
+                topic,
+            )
+            .unwrap()
+    };
+
+    post(&mut board, "Hello", MessageTopic::General);
+    post(
+        &mut board,
+        "Food sells high at ORD",
+        MessageTopic::TradeTips,
+    );
+    post(&mut board, "Fog at LAX", MessageTopic::Warnings);
+    post(&mut board, "Storms at DEN", MessageTopic::Warnings);
+
+    let warnings = board.get_topic_messages("JFK", MessageTopic::Warnings, None);
+    assert_eq!(warnings.len(), 1, "Warnings keep only one message");
+    assert_eq!(warnings[0].content, "Storms at DEN");
+    assert_eq!(board.take_evicted()[0].content, "Fog at LAX");
+
+    // A busy topic doesn't push the others off the board
+    for index in 0..5 {
+        post(
+            &mut board,
+            &format!("Tip {}", index),
+            MessageTopic::TradeTips,
+        );
+    }
+    let counts = board.topic_counts("JFK");
+    assert_eq!(counts[&MessageTopic::General], 1);
+    assert_eq!(counts[&MessageTopic::TradeTips], 3);
+    assert_eq!(counts[&MessageTopic::Warnings], 1);
+    assert_eq!(board.get_messages("JFK", None).len(), 5);
+
+    assert!(
+        BoardLimits {
+            topic_limits: [(MessageTopic::TradeTips, 0)].into_iter().collect(),
+            ..BoardLimits::default()
+        }
+        .validate()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_rooms_serve_one_topic_at_a_time() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Channels".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                npc_chatter: false,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+
+    let posted = service
+        .post_message(room_id, host_id, "Hello all".to_string())
+        .unwrap();
+    assert!(posted.success, "{}", posted.message);
+    let posted = service
+        .post_to_topic(
+            room_id,
+            host_id,
+            "Pumps are slow today".to_string(),
+            MessageTopic::Warnings,
+        )
+        .unwrap();
+    assert!(posted.success, "{}", posted.message);
+
+    let everything = service.get_messages(room_id, host_id).unwrap();
+    assert_eq!(everything.topic, None);
+    assert_eq!(everything.total_count, 2);
+    assert_eq!(everything.topic_counts[&MessageTopic::Warnings], 1);
+    assert_eq!(everything.topic_counts[&MessageTopic::TradeTips], 0);
+
+    let warnings = service
+        .get_topic_messages(room_id, host_id, Some(MessageTopic::Warnings))
+        .unwrap();
+    assert_eq!(warnings.topic, Some(MessageTopic::Warnings));
+    assert_eq!(warnings.total_count, 1);
+    assert_eq!(warnings.messages[0].content, "Pumps are slow today");
+    assert_eq!(warnings.messages[0].topic, MessageTopic::Warnings);
+
+    let general = service
+        .get_topic_messages(room_id, host_id, Some(MessageTopic::General))
+        .unwrap();
+    assert_eq!(general.messages.len(), 1);
+    assert_eq!(general.messages[0].content, "Hello all");
+}
+
+#[test]
+fn test_host_exports_every_board_with_its_archive() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Memorable Chatter".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                npc_chatter: false,
+                message_board: BoardLimits {
+                    max_messages_per_airport: 2,
+                    ..BoardLimits::default()
+                },
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    let guest_id = service
+        .join_room(room_id, "Guest".to_string(), Some("LAX".to_string()))
+        .unwrap()
+        .player_id;
+
+    for index in 1..=3 {
+        service
+            .post_message(room_id, host_id, format!("JFK {}", index))
+            .unwrap();
+    }
+    service
+        .post_to_topic(
+            room_id,
+            guest_id,
+            "Sunny at LAX".to_string(),
+            MessageTopic::Warnings,
+        )
+        .unwrap();
+
+    let export = service.export_messages(room_id, Some(host_id)).unwrap();
+    assert_eq!(export.room_name, "Memorable Chatter");
+    assert_eq!(export.message_count, 4);
+    let airports: Vec<&str> = export
+        .boards
+        .iter()
+        .map(|board| board.airport_id.as_str())
+        .collect();
+    assert_eq!(airports, vec!["JFK", "LAX"]);
+    // The message trimmed off JFK's board comes back from the archive
+    let jfk: Vec<&str> = export.boards[0]
+        .messages
+        .iter()
+        .map(|message| message.content.as_str())
+        .collect();
+    assert_eq!(jfk, vec!["JFK 1", "JFK 2", "JFK 3"]);
+
+    let markdown = export.to_markdown();
+    assert!(markdown.starts_with("# Memorable Chatter message boards"));
+    assert!(markdown.contains("## Los Angeles LAX (LAX)"));
+    assert!(markdown.contains("[Warnings] Guest: Sunny at LAX"));
+
+    // Only the host may take the boards away
+    for player_id in [Some(guest_id), None] {
+        let error = service.export_messages(room_id, player_id).unwrap_err();
+        assert!(error.to_string().contains("Only the host"), "{}", error);
+    }
+    let query: MessageExportQuery = serde_json::from_str(r#"{"format": "markdown"}"#).unwrap();
+    assert_eq!(query.format, ExportFormat::Markdown);
+}
+
+#[test]
+fn test_boards_are_read_a_page_at_a_time() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Pages".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                npc_chatter: false,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    for index in 1..=5 {
+        service
+            .post_message(room_id, host_id, format!("Message {}", index))
+            .unwrap();
+    }
+
+    let page = |before_id: Option<Uuid>| {
+        service.get_message_page(
+            room_id,
+            host_id,
+            MessagesQuery {
+                before_id,
+                limit: Some(2),
+                ..MessagesQuery::default()
+            },
+        )
+    };
+    let mut contents = Vec::new();
+    let mut before_id = None;
+    loop {
+        let response = page(before_id).unwrap();
+        assert_eq!(response.total_count, 5);
+        contents.extend(response.messages.iter().map(|m| m.content.clone()));
+        before_id = response.next_before_id;
+        if before_id.is_none() {
+            break;
+        }
+    }
+    assert_eq!(
+        contents,
+        [
+            "Message 5",
+            "Message 4",
+            "Message 3",
+            "Message 2",
+            "Message 1"
+        ]
+    );
+
+    assert_eq!(
+        page(Some(Uuid::new_v4())).unwrap_err(),
+        ServiceError::NotFound(ErrorCode::MessageNotFound)
+    );
+    let too_many = service.get_message_page(
+        room_id,
+        host_id,
+        MessagesQuery {
+            limit: Some(101),
+            ..MessagesQuery::default()
+        },
+    );
+    assert!(
+        too_many
+            .unwrap_err()
+            .to_string()
+            .starts_with("limit must be between")
+    );
+}
+
+#[test]
+fn test_authors_and_the_host_can_delete_messages() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Takedowns".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                npc_chatter: false,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    let guest_id = service
+        .join_room(room_id, "Guest".to_string(), Some("JFK".to_string()))
+        .unwrap()
+        .player_id;
+
+    let hosts = service
+        .post_message(room_id, host_id, "Welcome".to_string())
+        .unwrap()
+        .message_id
+        .unwrap();
+    let first = service
+        .post_message(room_id, guest_id, "Typo".to_string())
+        .unwrap()
+        .message_id
+        .unwrap();
+    let second = service
+        .post_message(room_id, guest_id, "Spam".to_string())
+        .unwrap()
+        .message_id
+        .unwrap();
+
+    let refused = service
+        .delete_message(room_id, guest_id, hosts)
+        .unwrap_err();
+    assert!(
+        refused.to_string().contains("Only the author or the host"),
+        "{refused}"
+    );
+
+    let deleted = service.delete_message(room_id, guest_id, first).unwrap();
+    assert_eq!(deleted.airport_id, "JFK");
+    service.delete_message(room_id, host_id, second).unwrap();
+    assert_eq!(
+        service
+            .delete_message(room_id, host_id, second)
+            .unwrap_err(),
+        ServiceError::NotFound(ErrorCode::MessageNotFound)
+    );
+
+    let board = service.get_messages(room_id, host_id).unwrap();
+    assert_eq!(board.total_count, 1);
+    assert_eq!(board.messages[0].content, "Welcome");
+}
+
+#[test]
+fn test_old_messages_expire() {
+    let posted = |days_ago: i64, content: &str| {
+        serde_json::json!({
+            "id": Uuid::new_v4(),
+            "author_id": Uuid::new_v4(),
+            "author_name": "Pilot",
+            "content": content,
+            "airport_id": "JFK",
+            "created_at": chrono::Utc::now() - chrono::Duration::days(days_ago),
+        })
+    };
+    let stored = serde_json::json!({
+        "messages": [posted(10, "Ancient history"), posted(1, "Yesterday's news")],
+        "limits": {"max_message_age_days": 7},
+    });
+    let mut board: MessageBoard = serde_json::from_value(stored).unwrap();
+
+    // Hidden straight away, taken down and archived with the next post
+    let contents = |board: &MessageBoard| -> Vec<String> {
+        board
+            .get_messages("JFK", None)
+            .iter()
+            .map(|message| message.content.clone())
+            .collect()
+    };
+    assert_eq!(contents(&board), ["Yesterday's news"]);
+    assert_eq!(board.message_count(Some("JFK")), 1);
+    board
+        .post_message(
+            Uuid::new_v4(),
+            "Pilot".to_string(),
+            "Fresh".to_string(),
+            "LAX".to_string(),
+        )
+        .unwrap();
+    let evicted = board.take_evicted();
+    assert_eq!(evicted.len(), 1);
+    assert_eq!(evicted[0].content, "Ancient history");
+
+    assert!(
+        BoardLimits {
+            max_message_age_days: Some(0),
+            ..BoardLimits::default()
+        }
+        .validate()
+        .is_err()
+    );
+}
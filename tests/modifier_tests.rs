@@ -0,0 +1,193 @@
+use rand::{SeedableRng, rngs::StdRng};
+use uuid::Uuid;
+
+use kzrk::api::errors::{ErrorCode, ServiceError};
+use kzrk::api::models::{FuelRequest, TradeAction, TradeRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::models::Market;
+use kzrk::systems::{
+    RoomSettings,
+    events::EventConfig,
+    modifiers::{ModifierSource, ModifierSpec, ModifierSystem, ModifierTarget},
+};
+
+fn quiet(scenario_modifiers: Vec<ModifierSpec>) -> RoomSettings {
+    RoomSettings {
+        events: EventConfig {
+            chance: 0.0,
+            ..EventConfig::default()
+        },
+        fuel_outage_chance: 0.0,
+        cargo_loss_chance: 0.0,
+        scenario_modifiers,
+        ..RoomSettings::default()
+    }
+}
+
+fn buy_electronics(service: &MultiplayerGameService, room_id: Uuid, player_id: Uuid) -> u32 {
+    let trade = service
+        .player_trade(
+            room_id,
+            player_id,
+            TradeRequest {
+                cargo_type: "electronics".to_string(),
+                quantity: 1,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(trade.success, "{}", trade.message);
+    trade.fee.unwrap()
+}
+
+#[test]
+fn test_specs_are_validated() {
+    assert!(ModifierSpec::sales_tax_holiday(3).validate().is_ok());
+    for spec in [
+        ModifierSpec {
+            name: " ".to_string(),
+            ..ModifierSpec::sales_tax_holiday(3)
+        },
+        ModifierSpec {
+            percent: 0,
+            ..ModifierSpec::sales_tax_holiday(3)
+        },
+        ModifierSpec {
+            percent: -150,
+            ..ModifierSpec::sales_tax_holiday(3)
+        },
+        ModifierSpec::sales_tax_holiday(0),
+        ModifierSpec::sales_tax_holiday(500),
+    ] {
+        assert!(spec.validate().is_err(), "{:?}", spec);
+    }
+
+    let service = MultiplayerGameService::new_in_memory();
+    let created = service.create_room_with_settings(
+        "Bad Scenario".to_string(),
+        "Host".to_string(),
+        None,
+        quiet(vec![ModifierSpec::sales_tax_holiday(0)]),
+    );
+    assert!(created.is_err());
+}
+
+#[test]
+fn test_modifiers_scale_prices_and_fees_then_expire() {
+    let tariffs = ModifierSpec {
+        name: "Import tariffs".to_string(),
+        target: ModifierTarget::CargoPrices,
+        percent: 50,
+        turns: 2,
+    }
+    .start(ModifierSource::Admin);
+    let subsidy = ModifierSpec {
+        name: "Fuel subsidy".to_string(),
+        target: ModifierTarget::FuelPrices,
+        percent: -100,
+        turns: 1,
+    }
+    .start(ModifierSource::Admin);
+    let holiday = ModifierSpec::sales_tax_holiday(1).start(ModifierSource::Scenario);
+    assert_eq!(
+        holiday.describe(),
+        "Sales tax holiday: broker fees -100% for 1 more turn"
+    );
+
+    let mut market = Market::new("JFK", 100);
+    market.cargo_prices.insert("electronics".to_string(), 200);
+    let mut modifiers = vec![tariffs, subsidy, holiday];
+    ModifierSystem::apply_to_market(&modifiers, &mut market);
+    assert_eq!(market.cargo_prices["electronics"], 300);
+    assert_eq!(market.fuel_price, 1, "prices never drop below $1");
+    assert_eq!(market.fee_multiplier, 0.0);
+
+    let expired = ModifierSystem::expire(&mut modifiers);
+    assert_eq!(expired.len(), 2);
+    assert_eq!(modifiers.len(), 1);
+    assert_eq!(modifiers[0].turns_remaining, 1);
+    assert!(ModifierSystem::expire(&mut modifiers).len() == 1 && modifiers.is_empty());
+}
+
+#[test]
+fn test_modifier_events_follow_the_chance() {
+    let mut rng = StdRng::seed_from_u64(3);
+    assert!((0..500).all(|_| ModifierSystem::roll_event(0.0, &mut rng).is_none()));
+    for _ in 0..50 {
+        let modifier = ModifierSystem::roll_event(1.0, &mut rng).unwrap();
+        assert_eq!(modifier.source, ModifierSource::Event);
+        assert!(modifier.turns_remaining > 0);
+    }
+}
+
+#[test]
+fn test_scenario_holiday_waives_fees_until_it_runs_out() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Tax Free".to_string(),
+            "Host".to_string(),
+            None,
+            quiet(vec![ModifierSpec::sales_tax_holiday(2)]),
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+
+    let fuel = service
+        .player_buy_fuel(room_id, host_id, FuelRequest { quantity: 67 })
+        .unwrap();
+    assert!(fuel.success, "{}", fuel.message);
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    assert_eq!(state.active_modifiers.len(), 1);
+    assert_eq!(state.active_modifiers[0].source, ModifierSource::Scenario);
+    assert_eq!(buy_electronics(&service, room_id, host_id), 0);
+
+    for destination in ["ORD", "JFK"] {
+        let travel = service
+            .player_travel(room_id, host_id, destination.to_string())
+            .unwrap();
+        assert!(travel.success, "{}", travel.message);
+    }
+
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    assert!(state.active_modifiers.is_empty());
+    assert!(buy_electronics(&service, room_id, host_id) > 0);
+}
+
+#[test]
+fn test_admins_apply_and_cancel_modifiers() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Managed".to_string(),
+            "Host".to_string(),
+            None,
+            quiet(Vec::new()),
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    assert!(buy_electronics(&service, room_id, host_id) > 0);
+
+    let holiday = service
+        .apply_room_modifier(room_id, ModifierSpec::sales_tax_holiday(5))
+        .unwrap();
+    assert_eq!(holiday.source, ModifierSource::Admin);
+    assert_eq!(buy_electronics(&service, room_id, host_id), 0);
+    let listed = service.get_room_modifiers(room_id).unwrap();
+    assert_eq!(listed.modifiers, vec![holiday.clone()]);
+
+    let cancelled = service.cancel_room_modifier(room_id, holiday.id).unwrap();
+    assert_eq!(cancelled.id, holiday.id);
+    assert!(buy_electronics(&service, room_id, host_id) > 0);
+    assert_eq!(
+        service
+            .cancel_room_modifier(room_id, holiday.id)
+            .unwrap_err(),
+        ServiceError::NotFound(ErrorCode::ModifierNotFound)
+    );
+    assert!(
+        service
+            .apply_room_modifier(room_id, ModifierSpec::sales_tax_holiday(0))
+            .is_err()
+    );
+}
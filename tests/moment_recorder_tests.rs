@@ -0,0 +1,93 @@
+#[cfg(feature = "gui")]
+mod moment_recorder {
+    use std::time::{Duration, Instant};
+
+    use eframe::egui::{Color32, ColorImage};
+    use kzrk::ui::recorder::{
+        MOMENT_MAX_WIDTH, MomentRecorder, downscale, encode_gif, save_moment,
+    };
+
+    fn screenshot(width: usize, height: usize, color: Color32) -> ColorImage {
+        ColorImage::new([width, height], color)
+    }
+
+    #[test]
+    fn test_only_the_last_few_seconds_are_kept() {
+        let mut recorder = MomentRecorder::new(Duration::from_secs(2));
+        let start = Instant::now();
+        for tenth in 0..30 {
+            recorder.push(
+                &screenshot(64, 48, Color32::RED),
+                start + Duration::from_millis(tenth * 100),
+            );
+        }
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 21);
+        assert_eq!(frames[0].captured_at, start + Duration::from_millis(900));
+    }
+
+    #[test]
+    fn test_resizing_the_window_starts_over() {
+        let mut recorder = MomentRecorder::new(Duration::from_secs(10));
+        let now = Instant::now();
+        recorder.push(&screenshot(64, 48, Color32::RED), now);
+        recorder.push(&screenshot(64, 48, Color32::RED), now);
+        recorder.push(&screenshot(80, 48, Color32::BLUE), now);
+        assert_eq!(recorder.frames().len(), 1);
+    }
+
+    #[test]
+    fn test_frames_are_requested_a_few_times_a_second() {
+        let mut recorder = MomentRecorder::new(Duration::from_secs(10));
+        let now = Instant::now();
+        assert!(recorder.request_frame(now));
+        assert!(!recorder.request_frame(now + Duration::from_millis(50)));
+        assert!(recorder.request_frame(now + MomentRecorder::frame_interval()));
+    }
+
+    #[test]
+    fn test_big_windows_are_scaled_down() {
+        let image = downscale(&screenshot(1920, 1080, Color32::GREEN), MOMENT_MAX_WIDTH);
+        assert_eq!(image.size, [MOMENT_MAX_WIDTH, 270]);
+        assert_eq!(image.pixels.len(), MOMENT_MAX_WIDTH * 270);
+        assert!(image.pixels.iter().all(|pixel| *pixel == Color32::GREEN));
+
+        let small = screenshot(320, 200, Color32::GREEN);
+        assert_eq!(downscale(&small, MOMENT_MAX_WIDTH).size, small.size);
+    }
+
+    #[test]
+    fn test_moments_are_saved_as_looping_gifs() {
+        let mut recorder = MomentRecorder::new(Duration::from_secs(10));
+        let start = Instant::now();
+        recorder.push(&screenshot(32, 24, Color32::RED), start);
+        recorder.push(
+            &screenshot(32, 24, Color32::BLUE),
+            start + Duration::from_millis(200),
+        );
+
+        let gif = encode_gif(&recorder.frames()).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(u16::from_le_bytes([gif[6], gif[7]]), 32);
+        assert_eq!(u16::from_le_bytes([gif[8], gif[9]]), 24);
+        assert!(
+            gif.windows(11).any(|window| window == b"NETSCAPE2.0"),
+            "GIF doesn't loop"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = save_moment(&recorder.frames(), dir.path(), chrono::Local::now()).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), gif);
+        assert!(
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("kzrk-moment-")
+        );
+    }
+
+    #[test]
+    fn test_nothing_recorded_is_nothing_to_share() {
+        assert!(encode_gif(&[]).is_err());
+    }
+}
@@ -0,0 +1,992 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+use std::time::Duration;
+
+use kzrk::api::{
+    errors::{ErrorCode, ServiceError},
+    models::TradeRequest,
+    multiplayer_service::MultiplayerGameService,
+    routes::create_multiplayer_router,
+};
+use reqwest::Client;
+use serde_json::{Value, json};
+use tokio::time::sleep;
+use uuid::Uuid;
+
+struct TestServer {
+    base_url: String,
+    client: Client,
+}
+
+impl TestServer {
+    async fn new() -> Self {
+        let service = MultiplayerGameService::new_in_memory();
+        let app = create_multiplayer_router(service);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind to address");
+
+        let addr = listener.local_addr().unwrap();
+        let base_url = format!("http://127.0.0.1:{}", addr.port());
+
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .await
+                .expect("Failed to start server");
+        });
+
+        // Give the server time to start
+        sleep(Duration::from_millis(100)).await;
+
+        Self {
+            base_url,
+            client: Client::new(),
+        }
+    }
+
+    async fn get(&self, path: &str) -> reqwest::Result<reqwest::Response> {
+        self.client
+            .get(format!("{}{}", self.base_url, path))
+            .send()
+            .await
+    }
+
+    async fn post(&self, path: &str, body: Value) -> reqwest::Result<reqwest::Response> {
+        self.client
+            .post(format!("{}{}", self.base_url, path))
+            .json(&body)
+            .send()
+            .await
+    }
+
+    async fn post_with_key(
+        &self,
+        path: &str,
+        body: Value,
+        key: Uuid,
+    ) -> reqwest::Result<reqwest::Response> {
+        self.client
+            .post(format!("{}{}", self.base_url, path))
+            .header("Idempotency-Key", key.to_string())
+            .json(&body)
+            .send()
+            .await
+    }
+
+    /// Create a room and return (room_id, host_player_id)
+    async fn create_room(&self) -> (String, String) {
+        let response: Value = self
+            .post(
+                "/rooms",
+                json!({"name": "API Test Room", "host_player_name": "Host"}),
+            )
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+
+        (
+            response["room_id"].as_str().unwrap().to_string(),
+            response["host_player_id"].as_str().unwrap().to_string(),
+        )
+    }
+}
+
+#[tokio::test]
+async fn test_replayed_action_is_applied_once() {
+    let server = TestServer::new().await;
+    let (room_id, player_id) = server.create_room().await;
+    let travel_path = format!("/rooms/{}/players/{}/travel", room_id, player_id);
+    let key = Uuid::new_v4();
+
+    let first: Value = server
+        .post_with_key(&travel_path, json!({"destination": "ORD"}), key)
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(first["success"], true);
+
+    // Replaying the same key returns the original response without flying again
+    let replay: Value = server
+        .post_with_key(&travel_path, json!({"destination": "ORD"}), key)
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(replay, first);
+
+    let state: Value = server
+        .get(&format!("/rooms/{}/players/{}/state", room_id, player_id))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(state["turn_number"], 2);
+    assert_eq!(state["players"][0]["current_airport"], "ORD");
+
+    // A fresh key is a new action and is evaluated against the current state
+    let second: Value = server
+        .post_with_key(&travel_path, json!({"destination": "ORD"}), Uuid::new_v4())
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_ne!(second, first);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_retry_racing_the_original_is_applied_once() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Race".to_string(), "Host".to_string(), None)
+        .unwrap();
+    let (room_id, player_id) = (room.room_id, room.host_player_id);
+    let key = Uuid::new_v4();
+    let applied = Arc::new(AtomicUsize::new(0));
+
+    let request = || {
+        let (service, applied) = (service.clone(), applied.clone());
+        tokio::spawn(async move {
+            service
+                .with_idempotency_key(player_id, Some(key), || {
+                    applied.fetch_add(1, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(200));
+                    service.player_travel(room_id, player_id, "ORD".to_string())
+                })
+                .await
+        })
+    };
+
+    // A flaky client retries while its first request is still in flight,
+    // which holds one of the two worker threads
+    let original = request();
+    // The test itself runs outside the workers, so it can block to wait
+    while applied.load(Ordering::SeqCst) == 0 {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    let retry = request();
+    std::thread::sleep(Duration::from_millis(20));
+
+    // The waiting retry leaves the other worker free. Timers need a free
+    // worker too, so this is timed by the clock instead.
+    let asked = std::time::Instant::now();
+    assert_eq!(tokio::spawn(async { 42 }).await.unwrap(), 42);
+    assert!(
+        asked.elapsed() < Duration::from_millis(100),
+        "a waiting retry blocked the runtime for {:?}",
+        asked.elapsed()
+    );
+
+    let responses: Vec<Value> = [original.await, retry.await]
+        .into_iter()
+        .map(|response| serde_json::to_value(response.unwrap().unwrap()).unwrap())
+        .collect();
+    assert_eq!(applied.load(Ordering::SeqCst), 1);
+    assert_eq!(responses[0], responses[1]);
+    assert_eq!(
+        service
+            .get_room_state(room_id, player_id)
+            .unwrap()
+            .turn_number,
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_failed_action_frees_its_key_for_a_retry() {
+    let service = MultiplayerGameService::new_in_memory();
+    let player_id = Uuid::new_v4();
+    let key = Uuid::new_v4();
+
+    let failed: Result<u32, ServiceError> = service
+        .with_idempotency_key(player_id, Some(key), || {
+            Err(ServiceError::NotFound(ErrorCode::RoomNotFound))
+        })
+        .await;
+    assert!(failed.is_err());
+    let retried = service
+        .with_idempotency_key(player_id, Some(key), || Ok(7))
+        .await;
+    assert_eq!(retried, Ok(7));
+    let replayed = service
+        .with_idempotency_key(player_id, Some(key), || Ok(8))
+        .await;
+    assert_eq!(replayed, Ok(7));
+}
+
+#[tokio::test]
+async fn test_actions_without_key_are_not_deduplicated() {
+    let server = TestServer::new().await;
+    let (room_id, player_id) = server.create_room().await;
+    let travel_path = format!("/rooms/{}/players/{}/travel", room_id, player_id);
+
+    for destination in ["ORD", "ORD"] {
+        let response: Value = server
+            .post(&travel_path, json!({"destination": destination}))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(response["success"], true);
+    }
+
+    let state: Value = server
+        .get(&format!("/rooms/{}/players/{}/state", room_id, player_id))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(state["turn_number"], 3);
+}
+
+#[tokio::test]
+async fn test_statistics_endpoint_reports_route_fuel_costs() {
+    let server = TestServer::new().await;
+    let (room_id, player_id) = server.create_room().await;
+
+    let response = server
+        .post(
+            &format!("/rooms/{}/players/{}/travel", room_id, player_id),
+            json!({"destination": "ORD"}),
+        )
+        .await
+        .unwrap();
+    let travel: Value = response.json().await.unwrap();
+    let fuel_used = travel["fuel_consumed"].as_u64().unwrap();
+
+    let response = server
+        .get(&format!(
+            "/rooms/{}/players/{}/statistics",
+            room_id, player_id
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let stats: Value = response.json().await.unwrap();
+
+    let routes = stats["routes"].as_array().unwrap();
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0]["from"], "JFK");
+    assert_eq!(routes[0]["to"], "ORD");
+    assert_eq!(routes[0]["fuel_used"].as_u64().unwrap(), fuel_used);
+    assert!(routes[0]["fuel_cost"].as_u64().unwrap() > 0);
+    assert_eq!(stats["best_route"]["to"], "ORD");
+    assert!(stats["worst_route"].is_null());
+
+    // Players outside the room can't read it
+    let response = server
+        .get(&format!(
+            "/rooms/{}/players/{}/statistics",
+            room_id,
+            Uuid::new_v4()
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_trades_pay_a_broker_fee() {
+    let server = TestServer::new().await;
+    let (room_id, player_id) = server.create_room().await;
+    let state_path = format!("/rooms/{}/players/{}/state", room_id, player_id);
+
+    let state: Value = server.get(&state_path).await.unwrap().json().await.unwrap();
+    let broker = &state["current_market"]["broker"];
+    assert_eq!(broker["tier"], "Walk-in");
+    assert_eq!(broker["volume"], 0);
+    let money = state["players"][0]["money"].as_u64().unwrap();
+
+    let trade: Value = server
+        .post(
+            &format!("/rooms/{}/players/{}/trade", room_id, player_id),
+            json!({"cargo_type": "food", "quantity": 5, "action": "Buy"}),
+        )
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(trade["success"], true);
+    let gross = trade["transaction_amount"].as_u64().unwrap();
+    let fee = trade["fee"].as_u64().unwrap();
+    assert!(fee > 0);
+    assert_eq!(trade["new_money"].as_u64().unwrap(), money - gross - fee);
+
+    // The fee counts as an expense, and the trade towards the next tier
+    let stats: Value = server
+        .get(&format!(
+            "/rooms/{}/players/{}/statistics",
+            room_id, player_id
+        ))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(stats["trading_fees"].as_u64().unwrap(), fee);
+    assert_eq!(stats["total_expenses"].as_u64().unwrap(), gross + fee);
+
+    let state: Value = server.get(&state_path).await.unwrap().json().await.unwrap();
+    assert_eq!(
+        state["current_market"]["broker"]["volume"]
+            .as_u64()
+            .unwrap(),
+        gross
+    );
+}
+
+#[tokio::test]
+async fn test_turns_are_per_player_and_world_tick_is_shared() {
+    let server = TestServer::new().await;
+    let (room_id, host_id) = server.create_room().await;
+
+    let joined: Value = server
+        .post(
+            &format!("/rooms/{}/join", room_id),
+            json!({"player_name": "Wingman", "starting_airport": "JFK"}),
+        )
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let wingman_id = joined["player_id"].as_str().unwrap().to_string();
+
+    let travel: Value = server
+        .post(
+            &format!("/rooms/{}/players/{}/travel", room_id, host_id),
+            json!({"destination": "ORD"}),
+        )
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(travel["success"], true);
+
+    let host_state: Value = server
+        .get(&format!("/rooms/{}/players/{}/state", room_id, host_id))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(host_state["turn_number"], 2);
+    assert_eq!(host_state["world_tick"], 2);
+
+    // The wingman hasn't flown yet, but sees the world move on
+    let wingman_state: Value = server
+        .get(&format!("/rooms/{}/players/{}/state", room_id, wingman_id))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(wingman_state["turn_number"], 1);
+    assert_eq!(wingman_state["world_tick"], 2);
+
+    let host_info = wingman_state["players"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["id"] == host_id.as_str())
+        .unwrap();
+    assert_eq!(host_info["turn_number"], 2);
+}
+
+#[tokio::test]
+async fn test_travel_preview_matches_what_travel_charges() {
+    let server = TestServer::new().await;
+    let (room_id, player_id) = server.create_room().await;
+
+    let preview: Value = server
+        .get(&format!(
+            "/rooms/{}/players/{}/travel-preview?dest=ORD",
+            room_id, player_id
+        ))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(preview["from"], "JFK");
+    assert_eq!(preview["to"], "ORD");
+    assert_eq!(preview["can_travel"], true);
+
+    let items = preview["items"].as_array().unwrap();
+    assert!(!items.is_empty());
+    let item_fuel: u64 = items
+        .iter()
+        .map(|item| item["fuel"].as_u64().unwrap())
+        .sum();
+    assert_eq!(preview["total_fuel"].as_u64().unwrap(), item_fuel);
+
+    // Previewing doesn't move the player
+    let state: Value = server
+        .get(&format!("/rooms/{}/players/{}/state", room_id, player_id))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let me = state["players"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|player| player["id"] == player_id.as_str())
+        .unwrap();
+    assert_eq!(me["current_airport"], "JFK");
+    let ord = state["available_destinations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|dest| dest["airport_id"] == "ORD")
+        .unwrap();
+    assert_eq!(ord["fuel_required"], preview["total_fuel"]);
+
+    let travel: Value = server
+        .post(
+            &format!("/rooms/{}/players/{}/travel", room_id, player_id),
+            json!({"destination": "ORD"}),
+        )
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(travel["success"], true);
+    assert_eq!(travel["fuel_consumed"], preview["total_fuel"]);
+
+    let unknown = server
+        .get(&format!(
+            "/rooms/{}/players/{}/travel-preview?dest=XXX",
+            room_id, player_id
+        ))
+        .await
+        .unwrap();
+    assert_eq!(unknown.status(), 400);
+}
+
+#[tokio::test]
+async fn test_travel_preview_explains_a_shortfall() {
+    let server = TestServer::new().await;
+    let (room_id, player_id) = server.create_room().await;
+
+    let preview: Value = server
+        .get(&format!(
+            "/rooms/{}/players/{}/travel-preview?dest=LAX",
+            room_id, player_id
+        ))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(preview["can_travel"], false);
+    assert!(preview["summary"].as_str().unwrap().contains("units short"));
+
+    // Travel refuses with the same explanation
+    let travel: Value = server
+        .post(
+            &format!("/rooms/{}/players/{}/travel", room_id, player_id),
+            json!({"destination": "LAX"}),
+        )
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(travel["success"], false);
+    assert_eq!(travel["message"], preview["summary"]);
+}
+
+#[tokio::test]
+async fn test_insurance_cover_and_claims_endpoints() {
+    let server = TestServer::new().await;
+    let (room_id, player_id) = server.create_room().await;
+    let path = format!("/rooms/{}/players/{}/insurance", room_id, player_id);
+
+    let insurance: Value = server.get(&path).await.unwrap().json().await.unwrap();
+    assert_eq!(insurance["insured"], false);
+    assert_eq!(insurance["next_flight_premium"], 0); // Empty hold
+    assert!(insurance["claims"].as_array().unwrap().is_empty());
+
+    let insurance: Value = server
+        .post(&path, json!({"insured": true}))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(insurance["insured"], true);
+
+    // Claims need a loss to claim for
+    let claim = server
+        .post(
+            &format!("/rooms/{}/players/{}/claims", room_id, player_id),
+            json!({"loss_id": Uuid::new_v4()}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(claim.status(), 404);
+    let error: Value = claim.json().await.unwrap();
+    assert_eq!(error["error"], "LossNotFound");
+}
+
+/// Read server-sent events until `count` have arrived, returning each one's
+/// (event, id, data) lines
+async fn read_events(
+    response: &mut reqwest::Response,
+    count: usize,
+) -> Vec<(String, String, Value)> {
+    let mut buffer = String::new();
+    let mut events = Vec::new();
+
+    while events.len() < count {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+            .await
+            .expect("Timed out waiting for an event")
+            .unwrap()
+            .expect("Stream ended early");
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(end) = buffer.find("\n\n") {
+            let block: String = buffer.drain(..end + 2).collect();
+            let field = |name: &str| {
+                block
+                    .lines()
+                    .find_map(|line| line.strip_prefix(&format!("{}:", name)))
+                    .map(|value| value.trim().to_string())
+            };
+            if let (Some(event), Some(id), Some(data)) =
+                (field("event"), field("id"), field("data"))
+            {
+                events.push((event, id, serde_json::from_str(&data).unwrap()));
+            }
+        }
+    }
+
+    events
+}
+
+#[tokio::test]
+async fn test_room_stream_sends_snapshot_then_patches() {
+    let server = TestServer::new().await;
+    let (room_id, player_id) = server.create_room().await;
+    let stream_path = format!("/rooms/{}/stream", room_id);
+
+    let mut stream = server.get(&stream_path).await.unwrap();
+    assert_eq!(stream.status(), 200);
+    assert_eq!(stream.headers()["content-type"], "text/event-stream");
+
+    let snapshot = read_events(&mut stream, 1).await.remove(0);
+    assert_eq!(snapshot.0, "snapshot");
+    assert_eq!(snapshot.2["players"][&player_id]["current_airport"], "JFK");
+    assert!(snapshot.2["markets"]["ORD"]["fuel_price"].as_u64().unwrap() > 0);
+
+    let travel: Value = server
+        .post(
+            &format!("/rooms/{}/players/{}/travel", room_id, player_id),
+            json!({"destination": "ORD"}),
+        )
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(travel["success"], true);
+
+    let patch = read_events(&mut stream, 1).await.remove(0);
+    assert_eq!(patch.0, "patch");
+    let ops = patch.2.as_array().unwrap();
+    let airport_path = format!("/players/{}/current_airport", player_id);
+    assert!(ops.iter().any(|op| op["op"] == "replace"
+        && op["path"] == airport_path.as_str()
+        && op["value"] == "ORD"));
+
+    // A reconnecting client only gets what it missed
+    let mut resumed = server
+        .client
+        .get(format!("{}{}", server.base_url, stream_path))
+        .header("Last-Event-ID", &snapshot.1)
+        .send()
+        .await
+        .unwrap();
+    let replayed = read_events(&mut resumed, 1).await.remove(0);
+    assert_eq!(replayed, patch);
+
+    let missing = server
+        .get(&format!("/rooms/{}/stream", Uuid::new_v4()))
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+}
+
+#[tokio::test]
+async fn test_missing_room_is_404_for_reads_and_actions() {
+    let server = TestServer::new().await;
+    let (room_id, player_id) = server.create_room().await;
+    let missing_room = Uuid::new_v4();
+
+    let read = server
+        .get(&format!(
+            "/rooms/{}/players/{}/state",
+            missing_room, player_id
+        ))
+        .await
+        .unwrap();
+    let action = server
+        .post(
+            &format!("/rooms/{}/players/{}/travel", missing_room, player_id),
+            json!({"destination": "ORD"}),
+        )
+        .await
+        .unwrap();
+    let leave = server
+        .post(
+            &format!("/rooms/{}/players/{}/leave", missing_room, player_id),
+            json!({}),
+        )
+        .await
+        .unwrap();
+    for response in [read, action, leave] {
+        assert_eq!(response.status(), 404);
+        let error: Value = response.json().await.unwrap();
+        assert_eq!(error["error"], "RoomNotFound");
+    }
+
+    // A stranger in a real room
+    let response = server
+        .get(&format!(
+            "/rooms/{}/players/{}/state",
+            room_id,
+            Uuid::new_v4()
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    let error: Value = response.json().await.unwrap();
+    assert_eq!(error["error"], "PlayerNotInRoom");
+
+    // Game rules refusing an action are a 400
+    let response = server
+        .post(
+            &format!("/rooms/{}/players/{}/travel", room_id, player_id),
+            json!({"destination": "NOWHERE"}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let error: Value = response.json().await.unwrap();
+    assert_eq!(error["error"], "ActionRejected");
+}
+
+#[tokio::test]
+async fn test_logout_forgets_the_session() {
+    let server = TestServer::new().await;
+    let (_room_id, player_id) = server.create_room().await;
+
+    let sessions: Value = server
+        .get("/players/Host/sessions")
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(sessions[0]["idle_seconds"].is_i64());
+
+    let logout = format!("/sessions/{}/logout", player_id);
+    let response = server.post(&logout, json!({})).await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Logging out twice finds nothing to end
+    let response = server.post(&logout, json!({})).await.unwrap();
+    assert_eq!(response.status(), 404);
+    let error: Value = response.json().await.unwrap();
+    assert_eq!(error["error"], "SessionNotFound");
+}
+
+#[tokio::test]
+async fn test_openapi_spec_documents_the_error_catalog() {
+    let server = TestServer::new().await;
+    let spec: Value = server
+        .get("/openapi.json")
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(spec["openapi"], "3.1.0");
+    let catalog = spec["x-error-catalog"].as_array().unwrap();
+    let room_not_found = catalog
+        .iter()
+        .find(|entry| entry["code"] == "RoomNotFound")
+        .unwrap();
+    assert_eq!(room_not_found["status"], 404);
+
+    let travel = &spec["paths"]["/rooms/{room_id}/players/{player_id}/travel"]["post"];
+    let codes = |status: &str| {
+        travel["responses"][status]["content"]["application/json"]["schema"]["allOf"][1]
+            ["properties"]["error"]["enum"]
+            .as_array()
+            .unwrap()
+            .clone()
+    };
+    assert!(codes("404").contains(&json!("RoomNotFound")));
+    assert!(travel["responses"]["400"].is_object());
+    // Any request can be shed, whatever the endpoint
+    assert_eq!(
+        codes("503"),
+        vec![json!("ServerBusy"), json!("RequestTimedOut")]
+    );
+    assert!(spec["paths"]["/rankings"]["get"]["responses"]["503"].is_object());
+}
+
+/// The schema `schema` points at, following `$ref`s into the components and
+/// past the null of an optional field
+fn resolve<'a>(spec: &'a Value, schema: &'a Value) -> &'a Value {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let name = reference.trim_start_matches("#/components/schemas/");
+        return resolve(spec, &spec["components"]["schemas"][name]);
+    }
+    if let Some(variants) = schema["oneOf"].as_array()
+        && let Some(some) = variants.iter().find(|variant| variant["type"] != "null")
+    {
+        return resolve(spec, some);
+    }
+    schema
+}
+
+#[tokio::test]
+async fn test_openapi_spec_describes_request_bodies() {
+    let server = TestServer::new().await;
+    let spec: Value = server
+        .get("/openapi.json")
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    let trade = &spec["paths"]["/rooms/{room_id}/players/{player_id}/trade"]["post"]["requestBody"]
+        ["content"]["application/json"];
+    let properties = &resolve(&spec, &trade["schema"])["properties"];
+    assert_eq!(properties["quantity"]["type"], "integer");
+    assert_eq!(resolve(&spec, &properties["action"])["type"], "string");
+    let example: TradeRequest = serde_json::from_value(trade["example"].clone()).unwrap();
+    assert_eq!(example.cargo_type, "electronics");
+
+    // Settings are described all the way down
+    let create_room = resolve(
+        &spec,
+        &spec["paths"]["/rooms"]["post"]["requestBody"]["content"]["application/json"]["schema"],
+    );
+    let settings = resolve(&spec, &create_room["properties"]["settings"]);
+    let calendar = resolve(&spec, &settings["properties"]["calendar"]);
+    assert_eq!(calendar["properties"]["hours_per_turn"]["type"], "integer");
+
+    // Query strings are described too
+    let rankings = spec["paths"]["/rankings"]["get"]["parameters"]
+        .as_array()
+        .unwrap();
+    assert!(
+        rankings
+            .iter()
+            .any(|parameter| parameter["name"] == "season" && parameter["in"] == "query")
+    );
+
+    // Reads don't take a body
+    let state = &spec["paths"]["/rooms/{room_id}/players/{player_id}/state"]["get"];
+    assert!(state.get("requestBody").is_none());
+}
+
+#[cfg(feature = "swagger-ui")]
+#[tokio::test]
+async fn test_docs_serve_swagger_ui_for_the_spec() {
+    let server = TestServer::new().await;
+    let response = server.get("/docs").await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html")
+    );
+    let page = response.text().await.unwrap();
+    assert!(page.contains("swagger-ui"));
+
+    // The UI's assets come from this server rather than a CDN
+    assert!(!page.contains("unpkg.com"));
+    let initializer = server
+        .get("/docs/swagger-initializer.js")
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(initializer.contains("/openapi.json"));
+    let bundle = server.get("/docs/swagger-ui-bundle.js").await.unwrap();
+    assert_eq!(bundle.status(), 200);
+}
+
+#[tokio::test]
+async fn test_quick_match_fills_open_rooms_before_opening_new_ones() {
+    let server = TestServer::new().await;
+
+    let quick_match = |name: &str, body: Value| {
+        let mut body = body;
+        body["player_name"] = json!(name);
+        server.post("/matchmaking/quick-match", body)
+    };
+
+    // Nothing open yet, so the first pilot hosts a new room
+    let first: Value = quick_match("Ada", json!({"max_players": 2}))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(first["created"], true);
+    assert_eq!(first["mode"], "Classic");
+    assert_eq!(first["max_players"], 2);
+
+    // Anyone happy with any room lands in it
+    let second: Value = quick_match("Bo", json!({}))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(second["created"], false);
+    assert_eq!(second["room_id"], first["room_id"]);
+
+    // It's full now, and a fog of war game never matched it anyway
+    let third: Value = quick_match("Cy", json!({"mode": "FogOfWar"}))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(third["created"], true);
+    assert_ne!(third["room_id"], first["room_id"]);
+    assert_eq!(third["mode"], "FogOfWar");
+    assert_eq!(third["max_players"], 4);
+
+    let state: Value = server
+        .get(&format!(
+            "/rooms/{}/players/{}/state",
+            third["room_id"].as_str().unwrap(),
+            third["player_id"].as_str().unwrap()
+        ))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(state["known_markets"].as_array().unwrap().len(), 1);
+
+    let response = quick_match("Di", json!({"max_players": 12})).await.unwrap();
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn test_action_cooldown_asks_spammers_to_retry() {
+    let server = TestServer::new().await;
+    let created: Value = server
+        .post(
+            "/rooms",
+            json!({
+                "name": "Slow Hands",
+                "host_player_name": "Host",
+                "settings": {
+                    "starting_money": 5000,
+                    "win_condition_money": 100000,
+                    "airports": [],
+                    "action_cooldown_ms": 60000
+                }
+            }),
+        )
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let (room_id, player_id) = (
+        created["room_id"].as_str().unwrap(),
+        created["host_player_id"].as_str().unwrap(),
+    );
+    let fuel_path = format!("/rooms/{}/players/{}/fuel", room_id, player_id);
+
+    let first = server
+        .post(&fuel_path, json!({"quantity": 1}))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), 200);
+
+    let spam = server
+        .post(&fuel_path, json!({"quantity": 1}))
+        .await
+        .unwrap();
+    assert_eq!(spam.status(), 429);
+    let retry_after: u64 = spam.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after > 0 && retry_after <= 60);
+    let body: Value = spam.json().await.unwrap();
+    assert_eq!(body["error"], "ActionCooldown");
+    assert!(body["details"]["retry_after_ms"].as_u64().unwrap() <= 60_000);
+
+    // Reading the room isn't an action
+    let state = server
+        .get(&format!("/rooms/{}/players/{}/state", room_id, player_id))
+        .await
+        .unwrap();
+    assert_eq!(state.status(), 200);
+}
+
+#[tokio::test]
+async fn test_leaving_with_cargo_is_a_conflict_until_forced() {
+    let server = TestServer::new().await;
+    let (room_id, player_id) = server.create_room().await;
+    let trade = server
+        .post(
+            &format!("/rooms/{}/players/{}/trade", room_id, player_id),
+            json!({"cargo_type": "food", "quantity": 5, "action": "Buy"}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(trade.status(), 200);
+
+    let leave_path = format!("/rooms/{}/players/{}/leave", room_id, player_id);
+    let response = server.post(&leave_path, json!({})).await.unwrap();
+    assert_eq!(response.status(), 409);
+    let error: Value = response.json().await.unwrap();
+    assert_eq!(error["error"], "ConfirmationRequired");
+    let obligations = error["details"]["obligations"].as_array().unwrap();
+    assert_eq!(obligations.len(), 1);
+    assert!(obligations[0].as_str().unwrap().contains("5 units"));
+
+    let response = server
+        .post(&format!("{}?force=true", leave_path), json!({}))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let left: Value = response.json().await.unwrap();
+    assert_eq!(left["success"], true);
+    assert_eq!(left["obligations"].as_array().unwrap().len(), 1);
+}
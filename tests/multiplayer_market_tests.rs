@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use rand::{SeedableRng, rngs::StdRng};
+use uuid::Uuid;
+
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::GameRoom;
+
+fn room() -> (GameRoom, Uuid) {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Market Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.settings.fuel_outage_chance = 0.0;
+    room.settings.cargo_loss_chance = 0.0;
+    (room, host_id)
+}
+
+/// Cargo prices by airport
+type Prices = HashMap<String, HashMap<String, u32>>;
+
+fn prices(room: &GameRoom) -> Prices {
+    room.shared_state
+        .markets
+        .iter()
+        .map(|(airport_id, market)| (airport_id.clone(), market.get_all_cargo_prices().clone()))
+        .collect()
+}
+
+#[test]
+fn test_prices_move_between_turns() {
+    let (mut room, host_id) = room();
+    let opening = prices(&room);
+
+    room.advance_turn(&host_id);
+    let first = prices(&room);
+    assert_ne!(first, opening);
+
+    room.advance_turn(&host_id);
+    assert_ne!(prices(&room), first);
+}
+
+#[test]
+fn test_prices_follow_volatility_and_supply_and_demand() {
+    let (mut room, _host_id) = room();
+    let cargo_types = get_default_cargo_types();
+    let mut rng = StdRng::seed_from_u64(11);
+
+    for _ in 0..20 {
+        room.refresh_markets(&mut rng);
+        for (airport_id, market) in &room.shared_state.markets {
+            let profile = &room.shared_state.airports[airport_id].market_profile;
+            for (cargo_id, price) in market.get_all_cargo_prices() {
+                let cargo_type = &cargo_types[cargo_id];
+                let base = cargo_type.base_price as f64;
+                let swing = cargo_type.volatility as f64;
+                let (low, high) = if profile.produces.contains(cargo_id) {
+                    (0.7, 0.9)
+                } else if profile.consumes.contains(cargo_id) {
+                    (1.1, 1.4)
+                } else {
+                    (0.9, 1.1)
+                };
+                let price = *price as f64;
+                assert!(price >= (base * (1.0 - swing) * low).floor(), "{cargo_id}");
+                assert!(price <= (base * (1.0 + swing) * high).ceil(), "{cargo_id}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fuel_prices_differ_between_airports() {
+    let (mut room, _host_id) = room();
+    let mut rng = StdRng::seed_from_u64(3);
+    room.refresh_markets(&mut rng);
+
+    let mut fuel_prices: Vec<u32> = room
+        .shared_state
+        .markets
+        .values()
+        .map(|market| market.fuel_price)
+        .collect();
+    fuel_prices.sort_unstable();
+    fuel_prices.dedup();
+    assert!(fuel_prices.len() > 1);
+}
@@ -1,7 +1,8 @@
 use tempfile::tempdir;
 
-use kzrk::api::multiplayer_service::MultiplayerGameService;
-use kzrk::systems::GameStatus;
+use kzrk::api::models::{FuelRequest, TradeAction, TradeRequest};
+use kzrk::api::multiplayer_service::{MultiplayerGameService, PersistencePolicy, SaveMode};
+use kzrk::systems::{GameStatus, RoomSettings};
 
 #[tokio::test]
 async fn test_room_persistence_through_service_restart() {
@@ -76,10 +77,10 @@ async fn test_empty_room_persistence() {
 
         // Both players leave
         service
-            .leave_room(room_id, host_id)
+            .leave_room(room_id, host_id, false)
             .expect("Failed for host to leave");
         service
-            .leave_room(room_id, player_id)
+            .leave_room(room_id, player_id, false)
             .expect("Failed for player to leave");
 
         // Verify room still exists but is empty
@@ -247,3 +248,231 @@ async fn test_in_memory_service() {
     let empty_rooms = new_service.list_rooms().expect("Failed to list rooms");
     assert_eq!(empty_rooms.len(), 0); // Should be empty since it's in-memory
 }
+
+#[tokio::test]
+async fn test_trades_and_fuel_purchases_are_saved() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_actions.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    let (room_id, host_id, money, fuel) = {
+        let service = MultiplayerGameService::new_with_db_path(db_path_str);
+        let room = service
+            .create_room_with_settings(
+                "Trading Room".to_string(),
+                "Trader".to_string(),
+                Some(2),
+                // Keep ORD's fuel flowing for the purchase below
+                RoomSettings {
+                    fuel_outage_chance: 0.0,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let travel = service
+            .player_travel(room.room_id, room.host_player_id, "ORD".to_string())
+            .unwrap();
+        assert!(travel.success);
+
+        let trade = service
+            .player_trade(
+                room.room_id,
+                room.host_player_id,
+                TradeRequest {
+                    cargo_type: "food".to_string(),
+                    quantity: 2,
+                    action: TradeAction::Buy,
+                },
+            )
+            .unwrap();
+        assert!(trade.success);
+
+        let fuel = service
+            .player_buy_fuel(
+                room.room_id,
+                room.host_player_id,
+                FuelRequest { quantity: 10 },
+            )
+            .unwrap();
+        assert!(fuel.success);
+
+        (
+            room.room_id,
+            room.host_player_id,
+            fuel.new_money.unwrap(),
+            fuel.new_fuel.unwrap(),
+        )
+    };
+
+    // Every action made it to the database
+    let service = MultiplayerGameService::new_with_db_path(db_path_str);
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    let me = &state.players[0];
+    assert_eq!(me.current_airport, "ORD");
+    assert_eq!(me.money, money);
+    assert_eq!(me.fuel, fuel);
+    assert_eq!(me.cargo_inventory.get("food"), Some(&2));
+}
+
+#[tokio::test]
+async fn test_rejected_actions_change_nothing() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Rejections".to_string(), "Broke".to_string(), Some(2))
+        .unwrap();
+    let before = service
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+
+    let trade = service
+        .player_trade(
+            room.room_id,
+            room.host_player_id,
+            TradeRequest {
+                cargo_type: "electronics".to_string(),
+                quantity: 100_000,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(!trade.success);
+
+    let error = service
+        .player_trade(
+            room.room_id,
+            room.host_player_id,
+            TradeRequest {
+                cargo_type: "unobtainium".to_string(),
+                quantity: 1,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap_err();
+    assert!(error.to_string().contains("not available"));
+
+    let after = service
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+    assert_eq!(after.players[0].money, before.players[0].money);
+    assert!(after.players[0].cargo_inventory.values().all(|q| *q == 0));
+}
+
+fn batched() -> PersistencePolicy {
+    PersistencePolicy {
+        save_mode: SaveMode::Batched(std::time::Duration::from_secs(3600)),
+        ..PersistencePolicy::default()
+    }
+}
+
+#[tokio::test]
+async fn test_batched_saves_wait_for_a_flush() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_batched.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    let service =
+        MultiplayerGameService::new_with_db_path(db_path_str).with_persistence_policy(batched());
+    let room = service
+        .create_room("Batched Room".to_string(), "Host".to_string(), None)
+        .unwrap();
+    service
+        .player_trade(
+            room.room_id,
+            room.host_player_id,
+            TradeRequest {
+                cargo_type: "food".to_string(),
+                quantity: 3,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+
+    // Playing goes on from memory, but nothing has reached the database
+    assert_eq!(service.list_rooms().unwrap().len(), 1);
+    let restarted = MultiplayerGameService::new_with_db_path(db_path_str);
+    assert!(restarted.list_rooms().unwrap().is_empty());
+
+    assert_eq!(service.flush_dirty_rooms().unwrap(), 1);
+    assert_eq!(service.flush_dirty_rooms().unwrap(), 0);
+    let restarted = MultiplayerGameService::new_with_db_path(db_path_str);
+    let state = restarted
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+    assert_eq!(state.players[0].cargo_inventory.get("food"), Some(&3));
+}
+
+#[tokio::test]
+async fn test_snapshot_includes_pending_saves() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_live.db");
+    let snapshot_path = temp_dir.path().join("test_live.db.snapshot");
+
+    let service = MultiplayerGameService::new_with_db_path(db_path.to_str().unwrap())
+        .with_persistence_policy(batched());
+    service
+        .create_room("First".to_string(), "Host".to_string(), None)
+        .unwrap();
+    service.snapshot_database(&snapshot_path).unwrap();
+
+    // Taking another replaces the old copy
+    service
+        .create_room("Second".to_string(), "Host".to_string(), None)
+        .unwrap();
+    service.snapshot_database(&snapshot_path).unwrap();
+
+    let restored = MultiplayerGameService::new_with_db_path(snapshot_path.to_str().unwrap());
+    let mut names: Vec<String> = restored
+        .list_rooms()
+        .unwrap()
+        .into_iter()
+        .map(|room| room.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["First", "Second"]);
+}
+
+#[test]
+fn test_restoring_a_checkpoint_keeps_only_the_cooldown() {
+    use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+    use kzrk::systems::GameRoom;
+    use std::time::Instant;
+    use uuid::Uuid;
+
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Rollback Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        2,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    let player_state = room.get_player(&host_id).unwrap();
+    let (money, airport) = (
+        player_state.player.money,
+        player_state.player.current_airport.clone(),
+    );
+    let fuel_price = room.shared_state.markets[&airport].fuel_price;
+
+    // An action that gets part-way before failing
+    let checkpoint = room.checkpoint(&host_id);
+    room.start_action(&host_id, Instant::now()).unwrap();
+    room.get_player_mut(&host_id).unwrap().player.money = 0;
+    room.shared_state
+        .markets
+        .get_mut(&airport)
+        .unwrap()
+        .fuel_price += 100;
+    room.player_statistics
+        .get_mut(&host_id)
+        .unwrap()
+        .record_cargo_purchase(500);
+    room.restore(checkpoint);
+
+    let player_state = room.get_player(&host_id).unwrap();
+    assert_eq!(player_state.player.money, money);
+    assert_eq!(room.shared_state.markets[&airport].fuel_price, fuel_price);
+    assert_eq!(room.player_statistics[&host_id].cargo_trades, 0);
+    assert!(player_state.last_action_at.is_some());
+}
@@ -0,0 +1,136 @@
+use uuid::Uuid;
+
+use kzrk::api::models::{FuelRequest, TradeAction, TradeRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::models::{Market, Player, aircraft::AircraftUpgrade};
+use kzrk::systems::{
+    GameRoom, RoomSettings,
+    events::EventConfig,
+    valuation::{NetWorth, ValuationSystem},
+};
+
+#[test]
+fn test_net_worth_adds_cash_cargo_and_aircraft() {
+    let cargo_types = get_default_cargo_types();
+    let mut player = Player::new(1000, "JFK", 200, 1000, 10.0);
+    player.cargo_inventory.add_cargo("electronics", 10);
+    player.cargo_inventory.add_cargo("food", 4);
+    player.aircraft.fit(AircraftUpgrade::FuelTank);
+    player.aircraft.fit(AircraftUpgrade::FuelTank);
+
+    // Food isn't priced here, so it counts at its base price
+    let mut market = Market::new("JFK", 50);
+    market.set_cargo_price("electronics", 300);
+    let worth = ValuationSystem::value(&player, Some(&market), &cargo_types);
+
+    assert_eq!(
+        worth,
+        NetWorth {
+            cash: 1000,
+            cargo: 10 * 300 + 4 * cargo_types["food"].base_price,
+            // Half of 4,000 and 8,000
+            aircraft: 6000,
+            debt: 0,
+        }
+    );
+    assert_eq!(worth.total(), 1000 + worth.cargo + 6000);
+}
+
+#[test]
+fn test_standings_rank_by_net_worth() {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Hoarders".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    let guest_id = room
+        .add_player(Uuid::new_v4(), "Guest".to_string(), None)
+        .unwrap();
+
+    // The host has less cash but a hold worth more than the difference
+    let host = &mut room.players.get_mut(&host_id).unwrap().player;
+    host.money = 500;
+    host.cargo_inventory.add_cargo("electronics", 100);
+    room.players.get_mut(&guest_id).unwrap().player.money = 5000;
+
+    let standings: Vec<&str> = room
+        .standings()
+        .iter()
+        .map(|player_state| player_state.player_name.as_str())
+        .collect();
+    assert_eq!(standings, vec!["Host", "Guest"]);
+    assert!(room.net_worth(&host_id).unwrap().cargo > 4500);
+}
+
+#[test]
+fn test_statistics_carry_net_worth_and_its_history() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Ledger".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                overnight_theft: false,
+                events: EventConfig {
+                    chance: 0.0,
+                    ..EventConfig::default()
+                },
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+
+    let fuel = service
+        .player_buy_fuel(room_id, host_id, FuelRequest { quantity: 50 })
+        .unwrap();
+    assert!(fuel.success, "{}", fuel.message);
+    let trade = service
+        .player_trade(
+            room_id,
+            host_id,
+            TradeRequest {
+                cargo_type: "electronics".to_string(),
+                quantity: 1,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(trade.success, "{}", trade.message);
+
+    let stats = service.get_player_statistics(room_id, host_id).unwrap();
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    let host = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(host_id))
+        .unwrap();
+    assert_eq!(stats.net_worth.cash, host.money);
+    assert!(stats.net_worth.cargo > 0);
+    assert_eq!(stats.net_worth.aircraft, 0);
+
+    let travel = service
+        .player_travel(room_id, host_id, "ORD".to_string())
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+
+    let stats = service.get_player_statistics(room_id, host_id).unwrap();
+    let history = &stats.net_worth_history;
+    assert!(history.len() >= 2, "{:?}", history);
+    assert!(
+        history
+            .windows(2)
+            .all(|pair| pair[0].turn_number < pair[1].turn_number)
+    );
+    // The latest turn is valued at O'Hare's prices, as is the net worth now
+    assert_eq!(history.last().unwrap().net_worth, stats.net_worth.total());
+    assert_eq!(state.room_info.name, "Ledger");
+}
@@ -0,0 +1,205 @@
+use uuid::Uuid;
+
+use kzrk::api::models::TurnDeadlineRequest;
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::api::webhooks::WebhookSender;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::models::NotificationKind;
+use kzrk::systems::{
+    GameRoom, RoomSettings,
+    multiplayer::{STARTING_MAX_FUEL, SpawnRule},
+};
+
+/// Drops webhook posts on the floor
+struct QuietSender;
+
+impl WebhookSender for QuietSender {
+    fn post(&self, _url: &str, _body: &serde_json::Value) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+fn setup() -> (MultiplayerGameService, Uuid, Uuid, Uuid) {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Digest".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                spawn: SpawnRule::HostChosen("JFK".to_string()),
+                starting_fuel: Some(STARTING_MAX_FUEL),
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                landing_slots: false,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let guest = service
+        .join_room(room.room_id, "Guest".to_string(), None)
+        .unwrap();
+    (service, room.room_id, room.host_player_id, guest.player_id)
+}
+
+fn kinds(
+    service: &MultiplayerGameService,
+    room_id: Uuid,
+    player_id: Uuid,
+) -> Vec<NotificationKind> {
+    service
+        .get_room_state(room_id, player_id)
+        .unwrap()
+        .notifications
+        .iter()
+        .map(|notification| notification.kind)
+        .collect()
+}
+
+#[test]
+fn test_pilots_waiting_at_an_airport_hear_who_arrived() {
+    let (service, room_id, host_id, guest_id) = setup();
+    let response = service
+        .player_travel(room_id, guest_id, "ORD".to_string())
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+    // Nobody was at O'Hare to see the guest land
+    assert!(kinds(&service, room_id, host_id).is_empty());
+
+    let response = service
+        .player_travel(room_id, host_id, "ORD".to_string())
+        .unwrap();
+    assert!(response.success, "{}", response.message);
+    let state = service.get_room_state(room_id, guest_id).unwrap();
+    assert_eq!(state.unread_notifications, 1);
+    let arrival = &state.notifications[0];
+    assert_eq!(arrival.kind, NotificationKind::PlayerArrived);
+    assert_eq!(arrival.airport_id, "ORD");
+    assert!(
+        arrival.content.starts_with("Host arrived at"),
+        "{}",
+        arrival.content
+    );
+    assert_eq!(arrival.turn_number, Some(2));
+    // The pilot who flew in isn't told about themselves
+    assert!(kinds(&service, room_id, host_id).is_empty());
+}
+
+#[test]
+fn test_digest_stays_until_acknowledged() {
+    let (service, room_id, host_id, guest_id) = setup();
+    service
+        .player_travel(room_id, guest_id, "ORD".to_string())
+        .unwrap();
+    service
+        .player_travel(room_id, host_id, "ORD".to_string())
+        .unwrap();
+    service
+        .player_travel(room_id, guest_id, "JFK".to_string())
+        .unwrap();
+    service
+        .player_travel(room_id, host_id, "JFK".to_string())
+        .unwrap();
+
+    // Every fetch of the state, from any client, carries what's unread,
+    // oldest first
+    let digest = service
+        .get_room_state(room_id, guest_id)
+        .unwrap()
+        .notifications;
+    assert_eq!(digest.len(), 2);
+    assert_eq!(digest[0].airport_id, "ORD");
+    assert_eq!(digest[1].airport_id, "JFK");
+    assert_eq!(
+        service
+            .get_room_state(room_id, guest_id)
+            .unwrap()
+            .notifications,
+        digest
+    );
+
+    // Acknowledging one leaves the other
+    service
+        .mark_notifications_read(room_id, guest_id, vec![digest[0].id])
+        .unwrap();
+    let state = service.get_room_state(room_id, guest_id).unwrap();
+    assert_eq!(state.unread_notifications, 1);
+    assert_eq!(state.notifications[0].id, digest[1].id);
+
+    service
+        .mark_notifications_read(room_id, guest_id, vec![])
+        .unwrap();
+    assert!(kinds(&service, room_id, guest_id).is_empty());
+    // Still in the feed once read
+    assert_eq!(
+        service
+            .get_notifications(room_id, guest_id)
+            .unwrap()
+            .notifications
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_turn_deadlines_reach_the_late_player() {
+    let (service, room_id, host_id, guest_id) = setup();
+    service
+        .set_turn_deadline(
+            room_id,
+            host_id,
+            TurnDeadlineRequest {
+                webhook_url: Some("https://discord.example.com/api/webhooks/1/abc".to_string()),
+                hours: Some(24),
+                warning_hours: Some(2),
+            },
+        )
+        .unwrap();
+
+    let late = chrono::Utc::now() + chrono::Duration::hours(25);
+    assert_eq!(service.send_deadline_alerts(&QuietSender, late).unwrap(), 2);
+    let state = service.get_room_state(room_id, guest_id).unwrap();
+    let notice = &state.notifications[0];
+    assert_eq!(notice.kind, NotificationKind::TurnDeadline);
+    assert!(
+        notice.content.contains("You missed the"),
+        "{}",
+        notice.content
+    );
+    assert!(
+        notice.content.ends_with("deadline for turn 1"),
+        "{}",
+        notice.content
+    );
+
+    // Already told: nothing more this turn
+    service.send_deadline_alerts(&QuietSender, late).unwrap();
+    assert_eq!(kinds(&service, room_id, guest_id).len(), 1);
+}
+
+#[test]
+fn test_price_alerts_land_in_the_notifications() {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Alerts".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.get_player_mut(&host_id)
+        .unwrap()
+        .player
+        .cargo_inventory
+        .add_cargo_at("electronics", 10, 1);
+
+    let report = room.advance_turn(&host_id);
+    assert_eq!(report.price_alerts.len(), 1);
+    let player_state = room.get_player(&host_id).unwrap();
+    let notice = player_state.notifications.last().unwrap();
+    assert_eq!(notice.kind, NotificationKind::PriceAlert);
+    assert_eq!(notice.content, report.price_alerts[0].message());
+    assert_eq!(notice.airport_id, player_state.player.current_airport);
+    assert_eq!(player_state.unread_digest().len(), 1);
+}
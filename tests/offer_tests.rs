@@ -0,0 +1,272 @@
+use uuid::Uuid;
+
+use kzrk::api::errors::{ErrorCode, ServiceError};
+use kzrk::api::models::{AcceptOfferRequest, TradeAction, TradeRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::systems::{
+    RoomSettings,
+    offers::{OfferSide, OfferTerms},
+};
+
+/// A room with a host and a guest, both at JFK
+fn trading_room() -> (MultiplayerGameService, Uuid, Uuid, Uuid) {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Trading Floor".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                starting_money: 10000,
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let guest = service
+        .join_room(room.room_id, "Guest".to_string(), None)
+        .unwrap();
+    (service, room.room_id, room.host_player_id, guest.player_id)
+}
+
+fn money_and_cargo(
+    service: &MultiplayerGameService,
+    room_id: Uuid,
+    player_id: Uuid,
+    cargo_type: &str,
+) -> (u32, u32) {
+    let state = service.get_room_state(room_id, player_id).unwrap();
+    let player = state
+        .players
+        .into_iter()
+        .find(|player| player.id == Some(player_id))
+        .unwrap();
+    let quantity = player.cargo_inventory.get(cargo_type).copied().unwrap_or(0);
+    (player.money, quantity)
+}
+
+fn terms(side: OfferSide, cargo_type: &str, quantity: u32, unit_price: u32) -> OfferTerms {
+    OfferTerms {
+        side,
+        cargo_type: cargo_type.to_string(),
+        quantity,
+        unit_price,
+    }
+}
+
+#[test]
+fn test_sell_offer_holds_cargo_until_taken() {
+    let (service, room_id, host, guest) = trading_room();
+    let bought = service
+        .player_trade(
+            room_id,
+            host,
+            TradeRequest {
+                cargo_type: "electronics".to_string(),
+                quantity: 10,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(bought.success, "{}", bought.message);
+
+    let placed = service
+        .place_offer(
+            room_id,
+            host,
+            terms(OfferSide::Sell, "electronics", 10, 600),
+        )
+        .unwrap();
+    assert_eq!(placed.new_inventory.get("electronics"), None);
+    let (host_money, _) = money_and_cargo(&service, room_id, host, "electronics");
+
+    // Visible to the guest, and listed as the host's own
+    let guest_book = service.get_offers(room_id, guest).unwrap();
+    assert_eq!(guest_book.airport_id, "JFK");
+    assert_eq!(guest_book.offers.len(), 1);
+    assert!(guest_book.my_offers.is_empty());
+    let host_book = service.get_offers(room_id, host).unwrap();
+    assert!(host_book.offers.is_empty());
+    assert_eq!(host_book.my_offers.len(), 1);
+
+    let offer_id = placed.offer.id;
+    let (guest_money, _) = money_and_cargo(&service, room_id, guest, "electronics");
+    let taken = service
+        .accept_offer(
+            room_id,
+            guest,
+            offer_id,
+            AcceptOfferRequest { quantity: Some(4) },
+        )
+        .unwrap();
+    assert_eq!(taken.offer.quantity, 4);
+    assert_eq!(taken.new_money, guest_money - 2400);
+    assert_eq!(taken.new_inventory.get("electronics"), Some(&4));
+    assert_eq!(
+        money_and_cargo(&service, room_id, host, "electronics"),
+        (host_money + 2400, 0)
+    );
+    assert_eq!(
+        service.get_offers(room_id, guest).unwrap().offers[0].quantity,
+        6
+    );
+
+    // Taking the rest closes the offer
+    service
+        .accept_offer(room_id, guest, offer_id, AcceptOfferRequest::default())
+        .unwrap();
+    assert!(
+        service
+            .get_offers(room_id, host)
+            .unwrap()
+            .my_offers
+            .is_empty()
+    );
+    assert_eq!(
+        money_and_cargo(&service, room_id, guest, "electronics"),
+        (guest_money - 6000, 10)
+    );
+    let error = service
+        .accept_offer(room_id, guest, offer_id, AcceptOfferRequest::default())
+        .unwrap_err();
+    assert_eq!(error, ServiceError::NotFound(ErrorCode::OfferNotFound));
+}
+
+#[test]
+fn test_buy_offer_holds_money_until_cancelled() {
+    let (service, room_id, host, guest) = trading_room();
+    let (money, _) = money_and_cargo(&service, room_id, guest, "food");
+
+    let placed = service
+        .place_offer(room_id, guest, terms(OfferSide::Buy, "food", 5, 150))
+        .unwrap();
+    assert_eq!(placed.new_money, money - 750);
+
+    // Only the player who posted it can take it down
+    let error = service
+        .cancel_offer(room_id, host, placed.offer.id)
+        .unwrap_err();
+    assert_eq!(error, "That offer isn't yours".into());
+    let error = service
+        .accept_offer(
+            room_id,
+            guest,
+            placed.offer.id,
+            AcceptOfferRequest::default(),
+        )
+        .unwrap_err();
+    assert_eq!(error, "You can't accept your own offer".into());
+
+    let cancelled = service
+        .cancel_offer(room_id, guest, placed.offer.id)
+        .unwrap();
+    assert_eq!(cancelled.new_money, money);
+    assert!(service.get_offers(room_id, host).unwrap().offers.is_empty());
+}
+
+#[test]
+fn test_filling_a_buy_offer_delivers_the_cargo() {
+    let (service, room_id, host, guest) = trading_room();
+    let placed = service
+        .place_offer(room_id, host, terms(OfferSide::Buy, "food", 5, 150))
+        .unwrap();
+
+    // The guest has nothing to sell yet
+    let error = service
+        .accept_offer(
+            room_id,
+            guest,
+            placed.offer.id,
+            AcceptOfferRequest::default(),
+        )
+        .unwrap_err();
+    assert!(error.to_string().contains("don't have"), "{}", error);
+
+    service
+        .player_trade(
+            room_id,
+            guest,
+            TradeRequest {
+                cargo_type: "food".to_string(),
+                quantity: 5,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    let (guest_money, _) = money_and_cargo(&service, room_id, guest, "food");
+    let sold = service
+        .accept_offer(
+            room_id,
+            guest,
+            placed.offer.id,
+            AcceptOfferRequest::default(),
+        )
+        .unwrap();
+    assert_eq!(sold.new_money, guest_money + 750);
+    assert_eq!(sold.new_inventory.get("food"), None);
+    assert_eq!(money_and_cargo(&service, room_id, host, "food").1, 5);
+}
+
+#[test]
+fn test_offers_stay_at_their_airport() {
+    let (service, room_id, host, _) = trading_room();
+    let traveller = service
+        .join_room(room_id, "Traveller".to_string(), Some("ORD".to_string()))
+        .unwrap();
+    assert_eq!(traveller.starting_airport, "ORD");
+
+    let placed = service
+        .place_offer(room_id, host, terms(OfferSide::Buy, "textiles", 2, 250))
+        .unwrap();
+    assert_eq!(placed.offer.airport_id, "JFK");
+
+    let book = service.get_offers(room_id, traveller.player_id).unwrap();
+    assert_eq!(book.airport_id, "ORD");
+    assert!(book.offers.is_empty());
+    let error = service
+        .accept_offer(
+            room_id,
+            traveller.player_id,
+            placed.offer.id,
+            AcceptOfferRequest::default(),
+        )
+        .unwrap_err();
+    assert_eq!(error, "That offer can only be taken at JFK".into());
+}
+
+#[test]
+fn test_offers_are_validated_and_capped() {
+    let (service, room_id, host, _) = trading_room();
+
+    let error = service
+        .place_offer(room_id, host, terms(OfferSide::Sell, "electronics", 1, 600))
+        .unwrap_err();
+    assert!(error.to_string().contains("don't have"), "{}", error);
+    let error = service
+        .place_offer(room_id, host, terms(OfferSide::Buy, "unobtainium", 1, 10))
+        .unwrap_err();
+    assert!(
+        error.to_string().starts_with("Unknown cargo type"),
+        "{}",
+        error
+    );
+    let error = service
+        .place_offer(room_id, host, terms(OfferSide::Buy, "food", 0, 10))
+        .unwrap_err();
+    assert_eq!(error, "Quantity must be greater than zero".into());
+    let error = service
+        .place_offer(room_id, host, terms(OfferSide::Buy, "food", u32::MAX, 2))
+        .unwrap_err();
+    assert_eq!(error, "That offer is too large to price".into());
+
+    for _ in 0..kzrk::systems::offers::MAX_OPEN_OFFERS {
+        service
+            .place_offer(room_id, host, terms(OfferSide::Buy, "food", 1, 10))
+            .unwrap();
+    }
+    let error = service
+        .place_offer(room_id, host, terms(OfferSide::Buy, "food", 1, 10))
+        .unwrap_err();
+    assert!(error.to_string().contains("open offers"), "{}", error);
+}
@@ -0,0 +1,160 @@
+use kzrk::api::models::{TradeAction, TradeRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::get_default_cargo_types;
+use kzrk::models::{Market, Player, cargo::CargoInventory};
+use kzrk::systems::{
+    TradingSystem,
+    price_alerts::{DEFAULT_ALERT_THRESHOLD_PERCENT, PriceAlerts, gain_percent},
+};
+
+fn market_at(airport_id: &str, electronics_price: u32) -> Market {
+    let mut market = Market::new(airport_id, 50);
+    market.set_cargo_price("electronics", electronics_price);
+    market.set_cargo_price("food", 100);
+    market
+}
+
+#[test]
+fn test_cost_basis_averages_purchases() {
+    let mut inventory = CargoInventory::new();
+    assert_eq!(inventory.average_cost("electronics"), None);
+
+    inventory.add_cargo_at("electronics", 10, 400);
+    inventory.add_cargo_at("electronics", 30, 600);
+    assert_eq!(inventory.average_cost("electronics"), Some(550));
+
+    // Selling part of the holding leaves the average alone
+    inventory.remove_cargo("electronics", 20);
+    assert_eq!(inventory.average_cost("electronics"), Some(550));
+    // Cargo that wasn't bought doesn't move it either
+    inventory.add_cargo("electronics", 5);
+    assert_eq!(inventory.average_cost("electronics"), Some(550));
+
+    // Selling out forgets it
+    inventory.remove_cargo("electronics", 25);
+    assert_eq!(inventory.average_cost("electronics"), None);
+}
+
+#[test]
+fn test_buying_records_what_was_paid_with_fees() {
+    let cargo_types = get_default_cargo_types();
+    let market = market_at("JFK", 500);
+    let mut player = Player::new(100000, "JFK", 200, 1000, 15.0);
+
+    let quote =
+        TradingSystem::buy_cargo(&mut player, &market, &cargo_types, "electronics", 10).unwrap();
+    assert_eq!(
+        player.cargo_inventory.average_cost("electronics"),
+        Some(quote.total_cost() / 10)
+    );
+    assert!(quote.total_cost() / 10 >= 500);
+}
+
+#[test]
+fn test_gain_percent() {
+    assert_eq!(gain_percent(1000, 1320), Some(32));
+    assert_eq!(gain_percent(1000, 900), Some(-10));
+    assert_eq!(gain_percent(0, 900), None);
+}
+
+#[test]
+fn test_alert_fires_once_per_rise() {
+    let cargo_types = get_default_cargo_types();
+    let mut inventory = CargoInventory::new();
+    inventory.add_cargo_at("electronics", 10, 1000);
+    inventory.add_cargo_at("food", 10, 100);
+    let mut alerts = PriceAlerts::default();
+    assert_eq!(alerts.threshold_percent, DEFAULT_ALERT_THRESHOLD_PERCENT);
+
+    assert!(
+        alerts
+            .check(&inventory, &market_at("JFK", 1100), &cargo_types)
+            .is_empty()
+    );
+
+    let fired = alerts.check(&inventory, &market_at("LAX", 1320), &cargo_types);
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].cargo_type, "electronics");
+    assert_eq!(fired[0].gain_percent, 32);
+    assert_eq!(
+        fired[0].message(),
+        "Your electronics are now +32% vs purchase at LAX ($1320 a unit, bought at $1000)"
+    );
+
+    // Still up next turn: nothing new to say
+    assert!(
+        alerts
+            .check(&inventory, &market_at("LAX", 1400), &cargo_types)
+            .is_empty()
+    );
+    // After dipping back under, the next rise alerts again
+    alerts.check(&inventory, &market_at("DEN", 1000), &cargo_types);
+    assert_eq!(
+        alerts
+            .check(&inventory, &market_at("LAX", 1300), &cargo_types)
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_threshold_is_configurable() {
+    let cargo_types = get_default_cargo_types();
+    let mut inventory = CargoInventory::new();
+    inventory.add_cargo_at("electronics", 10, 1000);
+    let mut alerts = PriceAlerts::default();
+
+    alerts.set_threshold(50).unwrap();
+    assert!(
+        alerts
+            .check(&inventory, &market_at("LAX", 1320), &cargo_types)
+            .is_empty()
+    );
+    assert_eq!(
+        alerts
+            .check(&inventory, &market_at("LAX", 1500), &cargo_types)
+            .len(),
+        1
+    );
+
+    alerts.set_threshold(0).unwrap();
+    assert!(
+        alerts
+            .check(&inventory, &market_at("LAX", 5000), &cargo_types)
+            .is_empty()
+    );
+    assert!(alerts.set_threshold(5000).is_err());
+}
+
+#[test]
+fn test_service_reports_holdings_and_sets_threshold() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Alerts".to_string(), "Host".to_string(), Some(2))
+        .unwrap();
+    let (room_id, host) = (room.room_id, room.host_player_id);
+
+    service
+        .player_trade(
+            room_id,
+            host,
+            TradeRequest {
+                cargo_type: "electronics".to_string(),
+                quantity: 2,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    let response = service.get_price_alerts(room_id, host).unwrap();
+    assert_eq!(response.threshold_percent, DEFAULT_ALERT_THRESHOLD_PERCENT);
+    assert_eq!(response.holdings.len(), 1);
+    let holding = &response.holdings[0];
+    assert_eq!(holding.cargo_type, "electronics");
+    assert_eq!(holding.quantity, 2);
+    assert!(holding.average_cost.unwrap() >= holding.price.unwrap());
+    assert!(holding.gain_percent.unwrap() <= 0);
+
+    let response = service.set_price_alerts(room_id, host, 40).unwrap();
+    assert_eq!(response.threshold_percent, 40);
+    assert!(service.set_price_alerts(room_id, host, 5000).is_err());
+}
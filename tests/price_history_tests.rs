@@ -0,0 +1,119 @@
+use uuid::Uuid;
+
+use kzrk::{
+    api::multiplayer_service::MultiplayerGameService,
+    data::{airports::get_default_airports, cargo_types::get_default_cargo_types},
+    models::{Market, market::PRICE_HISTORY_TURNS},
+    systems::{GameRoom, RoomSettings, multiplayer::SpawnRule},
+};
+
+fn room() -> (GameRoom, Uuid) {
+    let host_id = Uuid::new_v4();
+    let room = GameRoom::new(
+        "Ticker".to_string(),
+        host_id,
+        "Watcher".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    (room, host_id)
+}
+
+#[test]
+fn test_markets_open_with_one_snapshot() {
+    let (room, _) = room();
+    for market in room.shared_state.markets.values() {
+        assert_eq!(market.price_history.len(), 1);
+        let opening = &market.price_history[0];
+        assert_eq!(opening.tick, 1);
+        assert_eq!(opening.cargo_prices, market.cargo_prices);
+    }
+}
+
+#[test]
+fn test_every_turn_adds_a_snapshot_up_to_the_limit() {
+    let (mut room, player_id) = room();
+    room.advance_turn(&player_id);
+    room.advance_turn(&player_id);
+
+    let history = room.price_history("JFK", None).unwrap();
+    let ticks: Vec<u32> = history.iter().map(|snapshot| snapshot.tick).collect();
+    assert_eq!(ticks, vec![1, 2, 3]);
+    let market = &room.shared_state.markets["JFK"];
+    assert_eq!(history.last().unwrap().fuel_price, market.fuel_price);
+
+    for _ in 0..PRICE_HISTORY_TURNS {
+        room.advance_turn(&player_id);
+    }
+    let history = room.price_history("JFK", None).unwrap();
+    assert_eq!(history.len(), PRICE_HISTORY_TURNS);
+    assert_eq!(history.last().unwrap().tick, room.shared_state.world_tick);
+}
+
+#[test]
+fn test_unknown_airports_have_no_history() {
+    let (room, _) = room();
+    let err = room.price_history("XXX", None).unwrap_err();
+    assert!(err.contains("XXX"), "{}", err);
+}
+
+#[test]
+fn test_markets_saved_before_history_load_with_none() {
+    let mut value = serde_json::to_value(Market::new("JFK", 100)).unwrap();
+    value.as_object_mut().unwrap().remove("price_history");
+    let market: Market = serde_json::from_value(value).unwrap();
+    assert!(market.price_history.is_empty());
+}
+
+#[test]
+fn test_fog_of_war_keeps_history_at_the_airport() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Foggy".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                spawn: SpawnRule::HostChosen("JFK".to_string()),
+                fog_of_war: true,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+
+    let here = service
+        .get_price_history(room_id, "JFK".to_string(), Some(host_id))
+        .unwrap();
+    assert_eq!(here.airport_id, "JFK");
+    assert_eq!(here.max_turns, PRICE_HISTORY_TURNS);
+    assert!(!here.cargo_prices("electronics").is_empty());
+
+    for viewer in [None, Some(host_id)] {
+        assert!(
+            service
+                .get_price_history(room_id, "LAX".to_string(), viewer)
+                .is_err()
+        );
+    }
+}
+
+#[test]
+fn test_open_rooms_show_anyone_any_airport() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Open".to_string(), "Host".to_string(), None)
+        .unwrap();
+
+    let history = service
+        .get_price_history(room.room_id, "LAX".to_string(), None)
+        .unwrap();
+    assert_eq!(history.fuel_prices().len(), 1);
+
+    assert!(
+        service
+            .get_price_history(Uuid::new_v4(), "LAX".to_string(), None)
+            .is_err()
+    );
+}
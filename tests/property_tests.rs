@@ -1,6 +1,7 @@
 use kzrk::{
     data::{airports::get_default_airports, cargo_types::get_default_cargo_types},
-    models::{Airport, Player},
+    models::{Airport, CargoType, FuelEconomy, FuelPriceLevel, Market, Player},
+    systems::{MarketSystem, TradingSystem, trading::TradingError},
 };
 use proptest::prelude::*;
 
@@ -11,6 +12,18 @@ mod property_tests {
     use super::*;
 
     proptest! {
+        #[test]
+        fn test_fuel_prices_stay_within_airport_bounds(seed in any::<u64>()) {
+            use rand::{SeedableRng, rngs::StdRng};
+
+            let mut rng = StdRng::seed_from_u64(seed);
+            for airport in get_default_airports().values() {
+                let economy = airport.fuel_economy();
+                let price = MarketSystem::generate_fuel_price(airport, &mut rng);
+                prop_assert!(price >= economy.min_price && price <= economy.max_price);
+            }
+        }
+
         #[test]
         fn test_fuel_consumption_is_consistent(
             distance in 0.0f64..10000.0,
@@ -215,6 +228,91 @@ mod property_tests {
             // Maximum distance on Earth should be roughly half the circumference
             prop_assert!(distance <= 20037.5); // Approximately half Earth's circumference in km
         }
+
+        #[test]
+        fn test_trade_quotes_never_overflow(
+            unit_price in 1u32..=u32::MAX,
+            quantity in 1u32..=u32::MAX
+        ) {
+            let player = Player::new(u32::MAX, "JFK", 100, u32::MAX, 10.0);
+            let mut market = Market::new("JFK", 80);
+            market.set_cargo_price("gold", unit_price);
+
+            let gross = unit_price as u64 * quantity as u64;
+            match TradingSystem::quote_trade(&player, &market, "gold", quantity) {
+                Ok(quote) => {
+                    prop_assert_eq!(quote.gross as u64, gross);
+                    prop_assert_eq!(quote.total_cost() as u64, gross + quote.fee as u64);
+                    prop_assert!(quote.proceeds() <= quote.gross);
+                },
+                Err(TradingError::AmountTooLarge) => {
+                    // Only refused when the total really is past what a u32 holds
+                    let fee = gross as u128 * 300 / 10_000;
+                    prop_assert!(gross as u128 + fee > u32::MAX as u128);
+                },
+                Err(e) => prop_assert!(false, "unexpected error: {}", e),
+            }
+        }
+
+        #[test]
+        fn test_extreme_trades_conserve_money(
+            money in prop_oneof![0u32..100_000, (u32::MAX - 100_000)..=u32::MAX],
+            unit_price in prop_oneof![1u32..1000, (u32::MAX / 2)..=u32::MAX],
+            quantity in prop_oneof![1u32..1000, (u32::MAX / 2)..=u32::MAX],
+            held in prop_oneof![0u32..1000, (u32::MAX / 2)..=u32::MAX]
+        ) {
+            let mut cargo_types = std::collections::HashMap::new();
+            cargo_types.insert("gold".to_string(), CargoType::new("gold", "Gold", 100, 1, 0.1));
+            let mut market = Market::new("JFK", 80);
+            market.set_cargo_price("gold", unit_price);
+
+            let mut player = Player::new(money, "JFK", 100, u32::MAX, 10.0);
+            player.cargo_inventory.add_cargo("gold", held);
+
+            match TradingSystem::buy_cargo(&mut player, &market, &cargo_types, "gold", quantity) {
+                Ok(quote) => {
+                    prop_assert_eq!(player.money as u64 + quote.total_cost() as u64, money as u64);
+                },
+                Err(_) => prop_assert_eq!(player.money, money),
+            }
+
+            let before = player.money;
+            let held_before = player.cargo_inventory.get_quantity("gold");
+            match TradingSystem::sell_cargo(&mut player, &market, "gold", quantity) {
+                Ok(quote) => {
+                    prop_assert_eq!(player.money as u64, before as u64 + quote.proceeds() as u64);
+                    prop_assert_eq!(player.cargo_inventory.get_quantity("gold"), held_before - quantity);
+                },
+                Err(_) => {
+                    prop_assert_eq!(player.money, before);
+                    prop_assert_eq!(player.cargo_inventory.get_quantity("gold"), held_before);
+                },
+            }
+        }
+
+        #[test]
+        fn test_extreme_fuel_purchases(
+            money in prop_oneof![0u32..100_000, (u32::MAX - 100_000)..=u32::MAX],
+            fuel_price in prop_oneof![1u32..200, (u32::MAX / 2)..=u32::MAX],
+            quantity in 1u32..=u32::MAX
+        ) {
+            let mut market = Market::new("JFK", fuel_price);
+            let mut player = Player::new(money, "JFK", 300, 1000, 10.0);
+            let fuel_before = player.fuel;
+
+            match TradingSystem::buy_fuel(&mut player, &mut market, quantity) {
+                Ok(cost) => {
+                    let bought = player.fuel - fuel_before;
+                    prop_assert_eq!(cost as u64, fuel_price as u64 * bought as u64);
+                    prop_assert_eq!(player.money, money - cost);
+                },
+                Err(_) => {
+                    prop_assert_eq!(player.money, money);
+                    prop_assert_eq!(player.fuel, fuel_before);
+                },
+            }
+            prop_assert!(TradingSystem::get_max_fuel_buyable(&player, &market) <= player.max_fuel);
+        }
     }
 
     #[test]
@@ -261,6 +359,32 @@ mod property_tests {
         }
     }
 
+    #[test]
+    fn test_fuel_economy_hints() {
+        let economy = FuelEconomy::new(90, 0.2, 60, 120);
+        assert_eq!(economy.range_label(), "$60-$120");
+        assert_eq!(economy.level(62), FuelPriceLevel::Cheap);
+        assert_eq!(economy.level(90), FuelPriceLevel::Normal);
+        assert_eq!(economy.level(115), FuelPriceLevel::Expensive);
+        assert_eq!(economy.clamp(200), 120);
+
+        // Airports without explicit settings keep the old +/-15% behaviour
+        let airport = Airport::new("TST", "Test", (0.0, 0.0), 100, vec![], vec![], 1.0);
+        let legacy = airport.fuel_economy();
+        assert_eq!(legacy.base_price, 100);
+        assert_eq!((legacy.min_price, legacy.max_price), (85, 115));
+
+        // Remote airports can be dearer than anywhere else
+        let airports = get_default_airports();
+        let sea = airports["SEA"].fuel_economy();
+        assert!(
+            airports
+                .values()
+                .filter(|airport| airport.id != "SEA")
+                .all(|airport| airport.fuel_economy().max_price < sea.max_price)
+        );
+    }
+
     #[test]
     fn test_airport_data_consistency() {
         let airports = get_default_airports();
@@ -297,6 +421,18 @@ mod property_tests {
                 airport.base_fuel_price
             );
 
+            let economy = airport.fuel_economy();
+            assert!(
+                economy.min_price > 0 && economy.min_price <= economy.base_price,
+                "Airport {} has a fuel floor above its base price",
+                id
+            );
+            assert!(
+                economy.base_price <= economy.max_price,
+                "Airport {} has a fuel ceiling below its base price",
+                id
+            );
+
             // Fuel modifier should be positive
             assert!(
                 airport.market_profile.fuel_modifier > 0.0,
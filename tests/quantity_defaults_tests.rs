@@ -0,0 +1,82 @@
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::systems::profile::{MAX_DEFAULT_QUANTITY, QuantityDefaults};
+
+#[test]
+fn test_defaults_validate_their_quantities() {
+    assert!(QuantityDefaults::default().validate().is_ok());
+
+    let no_fuel = QuantityDefaults {
+        fuel_quantity: 0,
+        ..QuantityDefaults::default()
+    };
+    let error = no_fuel.validate().unwrap_err();
+    assert!(error.contains("fuel"), "{}", error);
+
+    let too_much_cargo = QuantityDefaults {
+        trade_quantity: MAX_DEFAULT_QUANTITY + 1,
+        ..QuantityDefaults::default()
+    };
+    let error = too_much_cargo.validate().unwrap_err();
+    assert!(error.contains("trade"), "{}", error);
+}
+
+#[test]
+fn test_fuel_preset_fits_the_tank() {
+    let defaults = QuantityDefaults {
+        fuel_quantity: 40,
+        ..QuantityDefaults::default()
+    };
+    assert_eq!(defaults.fuel_quantity_for(100, true), 40);
+    assert_eq!(defaults.fuel_quantity_for(25, false), 25);
+
+    let auto_fill = QuantityDefaults {
+        auto_fill_on_arrival: true,
+        ..defaults
+    };
+    assert_eq!(auto_fill.fuel_quantity_for(100, true), 100);
+    // Later visits to the pump go back to the preset
+    assert_eq!(auto_fill.fuel_quantity_for(100, false), 40);
+}
+
+#[test]
+fn test_older_settings_fill_in_missing_defaults() {
+    let defaults: QuantityDefaults = serde_json::from_str(r#"{"fuel_quantity": 75}"#).unwrap();
+    assert_eq!(defaults.fuel_quantity, 75);
+    assert_eq!(defaults.trade_quantity, 1);
+    assert!(!defaults.auto_fill_on_arrival);
+}
+
+#[test]
+fn test_pilot_profile_keeps_quantity_defaults() {
+    let service = MultiplayerGameService::new_in_memory();
+    let profile = service.get_pilot_profile("Amelia").unwrap();
+    assert_eq!(profile.quantity_defaults, QuantityDefaults::default());
+
+    let defaults = QuantityDefaults {
+        fuel_quantity: 120,
+        trade_quantity: 15,
+        auto_fill_on_arrival: true,
+    };
+    let profile = service.set_quantity_defaults("Amelia", defaults).unwrap();
+    assert_eq!(profile.quantity_defaults, defaults);
+    assert_eq!(
+        service
+            .get_pilot_profile("Amelia")
+            .unwrap()
+            .quantity_defaults,
+        defaults
+    );
+
+    let bad = QuantityDefaults {
+        trade_quantity: 0,
+        ..defaults
+    };
+    assert!(service.set_quantity_defaults("Amelia", bad).is_err());
+    assert_eq!(
+        service
+            .get_pilot_profile("Amelia")
+            .unwrap()
+            .quantity_defaults,
+        defaults
+    );
+}
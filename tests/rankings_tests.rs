@@ -0,0 +1,149 @@
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+use kzrk::api::{
+    database::Database, models::RankingsQuery, multiplayer_service::MultiplayerGameService,
+};
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{
+    GameRoom,
+    rankings::{GameResult, PLACEMENT_POINTS, PROFIT_PER_POINT, points_for, season_for},
+};
+
+/// A room of three where `winner` has just reached the target and the
+/// others ended on the given money
+fn won_room(winner: &str, others: &[(&str, u32)]) -> GameRoom {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "League Night".to_string(),
+        host_id,
+        winner.to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    for (name, money) in others {
+        let player_id = Uuid::new_v4();
+        room.add_player(player_id, name.to_string(), None).unwrap();
+        room.get_player_mut(&player_id).unwrap().player.money = *money;
+    }
+    room.get_player_mut(&host_id).unwrap().player.money = room.settings.win_condition_money;
+    assert!(room.check_for_winner(&host_id));
+    room
+}
+
+#[test]
+fn test_seasons_run_a_quarter_each() {
+    let at = |month| Utc.with_ymd_and_hms(2026, month, 15, 12, 0, 0).unwrap();
+    assert_eq!(season_for(at(1)), "2026-Q1");
+    assert_eq!(season_for(at(3)), "2026-Q1");
+    assert_eq!(season_for(at(4)), "2026-Q2");
+    assert_eq!(season_for(at(10)), "2026-Q4");
+}
+
+#[test]
+fn test_points_come_from_placement_and_profit() {
+    assert_eq!(points_for(1, 0), PLACEMENT_POINTS[0]);
+    assert_eq!(points_for(2, -3000), PLACEMENT_POINTS[1]);
+    assert_eq!(
+        points_for(1, 3 * PROFIT_PER_POINT as i64 + 1),
+        PLACEMENT_POINTS[0] + 3
+    );
+    // Everyone past the table still gets something for finishing
+    assert_eq!(points_for(9, 0), *PLACEMENT_POINTS.last().unwrap());
+}
+
+#[test]
+fn test_results_place_the_winner_first_then_by_money() {
+    let room = won_room("Amelia", &[("Bessie", 3000), ("Charles", 8000)]);
+    let results = GameResult::for_room(&room);
+
+    let order: Vec<(&str, u32)> = results
+        .iter()
+        .map(|result| (result.pilot_name.as_str(), result.placement))
+        .collect();
+    assert_eq!(order, vec![("Amelia", 1), ("Charles", 2), ("Bessie", 3)]);
+
+    let starting_money = room.settings.starting_money as i64;
+    assert_eq!(results[2].profit, 3000 - starting_money);
+    assert_eq!(results[2].points, PLACEMENT_POINTS[2]);
+    assert!(results.iter().all(|result| result.room_id == room.id));
+}
+
+#[test]
+fn test_rooms_in_play_have_no_results() {
+    let room = GameRoom::new(
+        "Still Flying".to_string(),
+        Uuid::new_v4(),
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    assert!(GameResult::for_room(&room).is_empty());
+}
+
+#[test]
+fn test_rankings_sum_a_season_and_count_each_room_once() {
+    let db = Database::in_memory().unwrap();
+    let first = GameResult::for_room(&won_room("Amelia", &[("Bessie", 3000)]));
+    let second = GameResult::for_room(&won_room("Bessie", &[("Amelia", 6000)]));
+    let season = first[0].season.clone();
+
+    assert_eq!(db.record_game_results(&first).unwrap(), 2);
+    assert_eq!(db.record_game_results(&second).unwrap(), 2);
+    // Saving the same room again changes nothing
+    assert_eq!(db.record_game_results(&first).unwrap(), 0);
+
+    let (entries, total) = db.load_season_rankings(&season, 0, 10).unwrap();
+    assert_eq!(total, 2);
+    for entry in &entries {
+        assert_eq!(entry.games_played, 2);
+        assert_eq!(entry.wins, 1);
+        let expected: u32 = first
+            .iter()
+            .chain(&second)
+            .filter(|result| result.pilot_name == entry.pilot_name)
+            .map(|result| result.points)
+            .sum();
+        assert_eq!(entry.points, expected);
+    }
+    assert!(entries[0].points >= entries[1].points);
+    assert_eq!(entries[0].rank, 1);
+    assert_eq!(entries[1].rank, 2);
+
+    // Pages pick up where the last left off
+    let (page_two, total) = db.load_season_rankings(&season, 1, 1).unwrap();
+    assert_eq!(total, 2);
+    assert_eq!(page_two.len(), 1);
+    assert_eq!(page_two[0].rank, 2);
+    assert_eq!(page_two[0].pilot_name, entries[1].pilot_name);
+
+    assert_eq!(db.list_ranked_seasons().unwrap(), vec![season]);
+    let (other_season, total) = db.load_season_rankings("1999-Q1", 0, 10).unwrap();
+    assert!(other_season.is_empty());
+    assert_eq!(total, 0);
+}
+
+#[test]
+fn test_rankings_endpoint_checks_its_page() {
+    let service = MultiplayerGameService::new_in_memory();
+
+    let rankings = service.get_rankings(RankingsQuery::default()).unwrap();
+    assert_eq!(rankings.season, season_for(Utc::now()));
+    assert_eq!(rankings.page, 1);
+    assert_eq!(rankings.total, 0);
+    assert!(rankings.entries.is_empty());
+
+    let zeroth = RankingsQuery {
+        page: Some(0),
+        ..RankingsQuery::default()
+    };
+    assert!(service.get_rankings(zeroth).is_err());
+
+    let huge = RankingsQuery {
+        per_page: Some(10_000),
+        ..RankingsQuery::default()
+    };
+    assert!(service.get_rankings(huge).is_err());
+}
@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{
+    GameState,
+    realtime::{MAX_TURN_SECONDS, MIN_TURN_SECONDS, TurnClock, parse_turn_seconds},
+    security::HANGAR_FEE,
+};
+
+const TURN: Duration = Duration::from_secs(30);
+
+fn real_time_game() -> GameState {
+    let mut game_state = GameState::new(get_default_airports(), get_default_cargo_types());
+    game_state.stats.real_time_turn_secs = Some(30);
+    game_state
+}
+
+#[test]
+fn test_turn_lengths_are_checked() {
+    assert_eq!(parse_turn_seconds(" 30 "), Ok(30));
+    assert!(parse_turn_seconds("soon").is_err());
+    assert!(parse_turn_seconds(&(MIN_TURN_SECONDS - 1).to_string()).is_err());
+    assert!(parse_turn_seconds(&(MAX_TURN_SECONDS + 1).to_string()).is_err());
+}
+
+#[test]
+fn test_only_real_time_games_have_a_clock() {
+    let now = Instant::now();
+    let game_state = GameState::new(get_default_airports(), get_default_cargo_types());
+    assert!(TurnClock::for_game(&game_state, now).is_none());
+    assert!(TurnClock::for_game(&real_time_game(), now).is_some());
+}
+
+#[test]
+fn test_the_clock_counts_whole_turns_and_keeps_the_rest() {
+    let start = Instant::now();
+    let mut clock = TurnClock::new(TURN, start);
+
+    assert_eq!(clock.turns_due(start + Duration::from_secs(29)), 0);
+    assert_eq!(clock.turns_due(start + Duration::from_secs(70)), 2);
+    // 10 seconds of the third turn were already used up
+    assert_eq!(
+        clock.remaining(start + Duration::from_secs(70)),
+        Duration::from_secs(20)
+    );
+    assert_eq!(clock.turns_due(start + Duration::from_secs(89)), 0);
+    assert_eq!(clock.turns_due(start + Duration::from_secs(90)), 1);
+}
+
+#[test]
+fn test_pausing_freezes_the_countdown() {
+    let start = Instant::now();
+    let mut clock = TurnClock::new(TURN, start);
+
+    assert!(clock.toggle_pause(start + Duration::from_secs(20)));
+    assert!(clock.is_paused());
+    assert_eq!(clock.turns_due(start + Duration::from_secs(600)), 0);
+    assert_eq!(
+        clock.remaining(start + Duration::from_secs(600)),
+        Duration::from_secs(10)
+    );
+
+    assert!(!clock.toggle_pause(start + Duration::from_secs(600)));
+    assert_eq!(clock.turns_due(start + Duration::from_secs(609)), 0);
+    assert_eq!(clock.turns_due(start + Duration::from_secs(610)), 1);
+}
+
+#[test]
+fn test_taking_a_turn_restarts_the_countdown() {
+    let start = Instant::now();
+    let mut clock = TurnClock::new(TURN, start);
+
+    clock.restart(start + Duration::from_secs(25));
+    assert_eq!(clock.turns_due(start + Duration::from_secs(50)), 0);
+    assert_eq!(clock.turns_due(start + Duration::from_secs(55)), 1);
+}
+
+#[test]
+fn test_idle_turns_move_the_game_on_without_moving_the_player() {
+    let mut game_state = real_time_game();
+    game_state.player.hangar_parking = true;
+    let airport = game_state.player.current_airport.clone();
+    let money = game_state.player.money;
+    let turn = game_state.turn_number;
+
+    let start = Instant::now();
+    let mut clock = TurnClock::for_game(&game_state, start).unwrap();
+    let reports = clock.pass_due_turns(&mut game_state, start + TURN * 3);
+
+    assert_eq!(reports.len(), 3);
+    assert_eq!(game_state.turn_number, turn + 3);
+    assert_eq!(game_state.player.current_airport, airport);
+    assert_eq!(game_state.player.money, money - 3 * HANGAR_FEE);
+}
+
+#[test]
+fn test_idle_turns_stop_once_the_game_is_over() {
+    let mut game_state = real_time_game();
+    game_state.player.money = game_state.win_condition_money;
+    let turn = game_state.turn_number;
+
+    let start = Instant::now();
+    let mut clock = TurnClock::for_game(&game_state, start).unwrap();
+    assert!(
+        clock
+            .pass_due_turns(&mut game_state, start + TURN * 5)
+            .is_empty()
+    );
+    assert_eq!(game_state.turn_number, turn);
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use tempfile::tempdir;
+use uuid::Uuid;
+
+use kzrk::api::database::Database;
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{get_default_airports, get_default_cargo_types};
+use kzrk::models::{Airport, Market, NotificationKind};
+use kzrk::systems::{
+    GameRoom, GameState, SaveSystem,
+    multiplayer::MarketSighting,
+    reconcile::{AirportReconciler, WorldAdjustment},
+};
+
+/// An airport next door to O'Hare that the game no longer has
+fn midway() -> Airport {
+    Airport::new(
+        "MDW",
+        "Chicago Midway",
+        (41.7868, -87.7522),
+        80,
+        vec!["food".to_string()],
+        vec!["electronics".to_string()],
+        1.0,
+    )
+}
+
+fn add_airport(
+    airports: &mut HashMap<String, Airport>,
+    markets: &mut HashMap<String, Market>,
+    airport: Airport,
+) {
+    markets.insert(airport.id.clone(), Market::new(&airport.id, 80));
+    airports.insert(airport.id.clone(), airport);
+}
+
+#[test]
+fn test_players_at_removed_airports_move_to_the_nearest() {
+    let temp_dir = tempdir().unwrap();
+    let mut game_state = GameState::new(get_default_airports(), get_default_cargo_types());
+    add_airport(&mut game_state.airports, &mut game_state.markets, midway());
+    game_state.player.current_airport = "MDW".to_string();
+    SaveSystem::save_game_to_dir(&game_state, Some("old".to_string()), temp_dir.path()).unwrap();
+
+    let loaded = SaveSystem::load_game_from_dir_reporting("old", temp_dir.path()).unwrap();
+    assert_eq!(
+        loaded.adjustments,
+        vec![
+            WorldAdjustment::Removed {
+                airport_id: "MDW".to_string()
+            },
+            WorldAdjustment::Relocated {
+                player_name: "Your aircraft".to_string(),
+                from: "MDW".to_string(),
+                to: "ORD".to_string(),
+            },
+        ]
+    );
+    let game_state = loaded.game_state;
+    assert_eq!(game_state.player.current_airport, "ORD");
+    assert!(!game_state.airports.contains_key("MDW"));
+    assert!(!game_state.markets.contains_key("MDW"));
+
+    // The plain loader fits the game the same way
+    let plain = SaveSystem::load_game_from_dir("old", temp_dir.path()).unwrap();
+    assert_eq!(plain.player.current_airport, "ORD");
+}
+
+#[test]
+fn test_renamed_airports_keep_their_market_and_pilots() {
+    let mut game_state = GameState::new(get_default_airports(), get_default_cargo_types());
+    // A save from when JFK was called IDL
+    let mut idlewild = game_state.airports.remove("JFK").unwrap();
+    idlewild.id = "IDL".to_string();
+    let mut market = game_state.markets.remove("JFK").unwrap();
+    market.airport_id = "IDL".to_string();
+    market.fuel_price = 123;
+    game_state.airports.insert("IDL".to_string(), idlewild);
+    game_state.markets.insert("IDL".to_string(), market);
+    game_state.player.current_airport = "IDL".to_string();
+    game_state.stats.airports_visited = vec!["IDL".to_string(), "JFK".to_string()];
+
+    let registry = get_default_airports();
+    let reconciler = AirportReconciler::new(&registry, &[("IDL", "JFK")]);
+    let adjustments = game_state.reconcile_airports(&reconciler).unwrap();
+
+    assert_eq!(
+        adjustments,
+        vec![WorldAdjustment::Renamed {
+            from: "IDL".to_string(),
+            to: "JFK".to_string(),
+        }]
+    );
+    assert_eq!(adjustments[0].describe(), "Airport IDL is now JFK");
+    assert_eq!(game_state.player.current_airport, "JFK");
+    assert_eq!(game_state.markets["JFK"].fuel_price, 123);
+    assert_eq!(game_state.markets["JFK"].airport_id, "JFK");
+    assert_eq!(game_state.airports["JFK"].name, registry["JFK"].name);
+    assert_eq!(game_state.stats.airports_visited, vec!["JFK"]);
+}
+
+#[test]
+fn test_a_game_with_no_airports_left_is_rejected() {
+    let mut game_state = GameState::new(get_default_airports(), get_default_cargo_types());
+    game_state.airports.clear();
+    game_state.markets.clear();
+    add_airport(&mut game_state.airports, &mut game_state.markets, midway());
+
+    let registry = get_default_airports();
+    let reconciler = AirportReconciler::new(&registry, &[]);
+    assert!(game_state.reconcile_airports(&reconciler).is_err());
+}
+
+#[test]
+fn test_rooms_are_fitted_and_players_told() {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Old Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    let shared = &mut room.shared_state;
+    add_airport(&mut shared.airports, &mut shared.markets, midway());
+    room.settings.airports = vec!["JFK".to_string(), "ORD".to_string(), "MDW".to_string()];
+    let host = room.players.get_mut(&host_id).unwrap();
+    host.player.current_airport = "MDW".to_string();
+    host.market_knowledge.insert(
+        "MDW".to_string(),
+        MarketSighting {
+            fuel_price: 80,
+            cargo_prices: HashMap::new(),
+            observed_at: 1,
+        },
+    );
+
+    let adjustments = room
+        .reconcile_airports(get_default_airports(), &[])
+        .unwrap();
+    assert_eq!(adjustments.len(), 2);
+    assert_eq!(room.settings.airports, vec!["JFK", "ORD"]);
+
+    let host = &room.players[&host_id];
+    assert_eq!(host.player.current_airport, "ORD");
+    assert!(!host.market_knowledge.contains_key("MDW"));
+    let notice = host.notifications.last().unwrap();
+    assert_eq!(notice.kind, NotificationKind::Announcement);
+    assert!(notice.content.contains("Airport MDW has closed"));
+    assert!(notice.content.contains("Host was moved from MDW to ORD"));
+
+    // Nothing left to change the second time round
+    assert!(
+        room.reconcile_airports(get_default_airports(), &[])
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_rooms_in_the_database_are_fitted_on_load() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("rooms.db");
+    let db_path = db_path.to_str().unwrap();
+
+    let (room_id, host_id) = {
+        let service = MultiplayerGameService::new_with_db_path(db_path);
+        let room = service
+            .create_room("Midway".to_string(), "Host".to_string(), None)
+            .unwrap();
+        (room.room_id, room.host_player_id)
+    };
+
+    {
+        let db = Database::new(db_path).unwrap();
+        let mut room = db.load_room(&room_id).unwrap().unwrap();
+        let shared = &mut room.shared_state;
+        add_airport(&mut shared.airports, &mut shared.markets, midway());
+        room.players
+            .get_mut(&host_id)
+            .unwrap()
+            .player
+            .current_airport = "MDW".to_string();
+        db.save_room(&room).unwrap();
+    }
+
+    let service = MultiplayerGameService::new_with_db_path(db_path);
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    let host = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(host_id))
+        .unwrap();
+    assert_eq!(host.current_airport, "ORD");
+    let notifications = service.get_notifications(room_id, host_id).unwrap();
+    assert!(notifications.notifications[0].content.contains("MDW"));
+
+    // The fitted room was saved, so nobody is told twice
+    let db = Database::new(db_path).unwrap();
+    let stored = db.load_room(&room_id).unwrap().unwrap();
+    assert!(!stored.shared_state.airports.contains_key("MDW"));
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use kzrk::api::{
+    models::{AirportInfo, AirportSummary, CargoInfo, ServerInfo},
+    multiplayer_service::MultiplayerGameService,
+    routes::{create_multiplayer_router, create_router},
+    service::GameService,
+    stateless_handlers::{REFERENCE_MAX_AGE_SECS, reference_version},
+};
+
+async fn serve(app: axum::Router) -> (String, tokio::task::JoinHandle<()>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    (base_url, server)
+}
+
+#[tokio::test]
+async fn test_server_info_reports_the_reference_version() {
+    let (base_url, _server) = serve(create_multiplayer_router(
+        MultiplayerGameService::new_in_memory(),
+    ))
+    .await;
+
+    let info: ServerInfo = reqwest::get(format!("{}/server-info", base_url))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(info.reference_version, reference_version());
+}
+
+#[tokio::test]
+async fn test_reference_data_is_typed_and_cacheable() {
+    let (base_url, _server) = serve(create_multiplayer_router(
+        MultiplayerGameService::new_in_memory(),
+    ))
+    .await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("{}/airports", base_url))
+        .send()
+        .await
+        .unwrap();
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+    assert_eq!(etag, format!("\"{}\"", reference_version()));
+    assert_eq!(
+        response.headers()["cache-control"],
+        format!("public, max-age={}", REFERENCE_MAX_AGE_SECS).as_str()
+    );
+    let airports: Vec<AirportInfo> = response.json().await.unwrap();
+    assert!(airports.windows(2).all(|pair| pair[0].id < pair[1].id));
+    assert!(airports.iter().any(|airport| airport.id == "JFK"));
+
+    let cargo: Vec<CargoInfo> = client
+        .get(format!("{}/cargo", base_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(!cargo.is_empty());
+
+    // A client that already has this version gets no body
+    for path in ["airports", "cargo"] {
+        let response = client
+            .get(format!("{}/{}", base_url, path))
+            .header("If-None-Match", &etag)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 304);
+        assert!(response.bytes().await.unwrap().is_empty());
+    }
+
+    let stale = client
+        .get(format!("{}/airports", base_url))
+        .header("If-None-Match", "\"something-older\"")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(stale.status(), 200);
+}
+
+#[tokio::test]
+async fn test_single_player_reference_data_is_cacheable_too() {
+    let (base_url, _server) = serve(create_router(GameService::new())).await;
+
+    let response = reqwest::get(format!("{}/airports", base_url))
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()["etag"],
+        format!("\"{}\"", reference_version()).as_str()
+    );
+    let airports: Vec<AirportSummary> = response.json().await.unwrap();
+    assert!(airports.iter().any(|airport| airport.id == "JFK"));
+}
+
+#[cfg(feature = "gui")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_client_keeps_reference_data_until_cleared() {
+    use kzrk::ui::game_api_client::GameApiClient;
+
+    let (base_url, server) = serve(create_multiplayer_router(
+        MultiplayerGameService::new_in_memory(),
+    ))
+    .await;
+    let client = GameApiClient::new(base_url);
+
+    let airports = client.get_airports_sync().unwrap();
+    let cargo = client.get_cargo_sync().unwrap();
+    assert!(!airports.is_empty());
+
+    // With the server gone the cache still answers
+    server.abort();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(client.get_airports_sync().unwrap(), airports);
+    assert_eq!(client.get_cargo_sync().unwrap(), cargo);
+
+    client.clear_reference_cache();
+    assert!(client.get_airports_sync().is_err());
+}
@@ -0,0 +1,47 @@
+#[cfg(feature = "gui")]
+mod refresh_mode {
+    use std::time::Duration;
+
+    use kzrk::ui::refresh::{GuiSettings, RefreshMode};
+
+    #[test]
+    fn test_power_saver_polls_less_and_less_in_the_background() {
+        let standard = RefreshMode::Standard;
+        let saver = RefreshMode::PowerSaver;
+
+        assert_eq!(standard.state_poll_interval(true), Duration::from_secs(2));
+        assert_eq!(standard.state_poll_interval(false), Duration::from_secs(2));
+        assert!(saver.state_poll_interval(true) > standard.state_poll_interval(true));
+        assert!(saver.state_poll_interval(false) > saver.state_poll_interval(true));
+        assert!(saver.lobby_poll_interval() > standard.lobby_poll_interval());
+        assert!(standard.animates());
+        assert!(!saver.animates());
+    }
+
+    #[test]
+    fn test_settings_are_remembered() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gui_settings.json");
+        assert_eq!(GuiSettings::load_from(&path), GuiSettings::default());
+
+        let settings = GuiSettings {
+            refresh_mode: RefreshMode::PowerSaver,
+            record_moments: true,
+        };
+        settings.save_to(&path).unwrap();
+        assert_eq!(GuiSettings::load_from(&path), settings);
+    }
+
+    #[test]
+    fn test_unreadable_settings_fall_back_to_standard() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gui_settings.json");
+        std::fs::write(&path, "{}").unwrap();
+        assert_eq!(
+            GuiSettings::load_from(&path).refresh_mode,
+            RefreshMode::Standard
+        );
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(GuiSettings::load_from(&path), GuiSettings::default());
+    }
+}
@@ -0,0 +1,161 @@
+use tempfile::tempdir;
+use uuid::Uuid;
+
+use kzrk::api::database::Database;
+use kzrk::api::models::{FuelRequest, TradeAction, TradeRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{GameRoom, RoomSettings, retention::DEFAULT_MAX_JOURNAL_TURNS};
+
+fn calm_settings() -> RoomSettings {
+    RoomSettings {
+        cargo_loss_chance: 0.0,
+        fuel_outage_chance: 0.0,
+        ..RoomSettings::default()
+    }
+}
+
+#[test]
+fn test_journal_keeps_one_entry_per_turn() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Replay Room".to_string(),
+            "Host".to_string(),
+            None,
+            calm_settings(),
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    service
+        .join_room(room_id, "Guest".to_string(), None)
+        .unwrap();
+
+    // Fill up for the round trip first
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    let host = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(host_id))
+        .unwrap();
+    let space = host.max_fuel - host.fuel;
+    let fuel = service
+        .player_buy_fuel(room_id, host_id, FuelRequest { quantity: space })
+        .unwrap();
+    assert!(fuel.success, "{}", fuel.message);
+
+    let buy = service
+        .player_trade(
+            room_id,
+            host_id,
+            TradeRequest {
+                cargo_type: "food".to_string(),
+                quantity: 5,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(buy.success, "{}", buy.message);
+    for destination in ["ORD", "JFK"] {
+        let travel = service
+            .player_travel(room_id, host_id, destination.to_string())
+            .unwrap();
+        assert!(travel.success, "{}", travel.message);
+    }
+
+    let replay = service.get_replay(room_id, host_id).unwrap();
+    assert!(!replay.finished);
+    // Other pilots stay private while the game is on
+    assert_eq!(replay.pilots.len(), 1);
+    let turns = &replay.pilots[0].turns;
+    assert_eq!(replay.pilots[0].player_id, host_id);
+
+    let numbers: Vec<u32> = turns.iter().map(|turn| turn.turn_number).collect();
+    assert_eq!(numbers, vec![1, 2, 3]);
+    let airports: Vec<&str> = turns.iter().map(|turn| turn.airport_id.as_str()).collect();
+    assert_eq!(airports, vec!["JFK", "ORD", "JFK"]);
+
+    // Turn one ends with the purchase made before taking off
+    assert_eq!(turns[0].cargo.get("food"), Some(&5));
+    assert!(turns[0].money < 5000);
+    assert!(turns[1].fuel < turns[0].fuel);
+    assert!(turns[1].market.cargo_prices.contains_key("food"));
+
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    let host = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(host_id))
+        .unwrap();
+    assert_eq!(turns[2].money, host.money);
+}
+
+#[test]
+fn test_recording_a_turn_again_replaces_it() {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Journal Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    assert_eq!(room.players[&host_id].journal.len(), 1);
+
+    room.get_player_mut(&host_id).unwrap().player.money = 1234;
+    room.record_turn(&host_id);
+    let journal = &room.players[&host_id].journal;
+    assert_eq!(journal.len(), 1);
+    assert_eq!(journal[0].money, 1234);
+
+    for _ in 0..DEFAULT_MAX_JOURNAL_TURNS + 10 {
+        room.advance_turn(&host_id);
+        room.record_turn(&host_id);
+    }
+    let journal = &room.players[&host_id].journal;
+    assert_eq!(journal.len(), DEFAULT_MAX_JOURNAL_TURNS);
+    assert_eq!(
+        journal.last().unwrap().turn_number,
+        room.players[&host_id].turn_number
+    );
+}
+
+#[test]
+fn test_everyone_is_visible_once_the_game_is_over() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_replay.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    let host_id = Uuid::new_v4();
+    let guest_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Finished Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.add_player(guest_id, "Guest".to_string(), None)
+        .unwrap();
+    let room_id = room.id;
+    room.get_player_mut(&host_id).unwrap().player.money = room.settings.win_condition_money;
+    assert!(room.check_for_winner(&host_id));
+    Database::new(db_path_str)
+        .unwrap()
+        .save_room(&room)
+        .unwrap();
+
+    let service = MultiplayerGameService::new_with_db_path(db_path_str);
+    let replay = service.get_replay(room_id, guest_id).unwrap();
+    assert!(replay.finished);
+    let names: Vec<&str> = replay
+        .pilots
+        .iter()
+        .map(|pilot| pilot.player_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Guest", "Host"]);
+
+    assert!(service.get_replay(room_id, Uuid::new_v4()).is_err());
+}
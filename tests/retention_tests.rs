@@ -0,0 +1,176 @@
+use uuid::Uuid;
+
+use kzrk::api::models::{ArchivedMessagesQuery, FuelRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::models::{BoardLimits, MessageBoard};
+use kzrk::systems::{
+    RoomSettings,
+    retention::{RetentionLimits, evict_oldest},
+};
+
+fn settings(max_messages_per_airport: usize, retention: RetentionLimits) -> RoomSettings {
+    RoomSettings {
+        cargo_loss_chance: 0.0,
+        fuel_outage_chance: 0.0,
+        message_board: BoardLimits {
+            max_messages_per_airport,
+            ..BoardLimits::default()
+        },
+        retention,
+        ..RoomSettings::default()
+    }
+}
+
+#[test]
+fn test_oldest_entries_are_evicted_first() {
+    let mut entries = vec![1, 2, 3, 4, 5];
+    assert_eq!(evict_oldest(&mut entries, 3), vec![1, 2]);
+    assert_eq!(entries, vec![3, 4, 5]);
+    assert!(evict_oldest(&mut entries, 10).is_empty());
+
+    let mut board = MessageBoard::new(2);
+    for content in ["one", "two", "three"] {
+        board
+            .post_message(
+                Uuid::new_v4(),
+                "Pilot".to_string(),
+                content.to_string(),
+                "JFK".to_string(),
+            )
+            .unwrap();
+    }
+    let evicted = board.take_evicted();
+    assert_eq!(evicted.len(), 1);
+    assert_eq!(evicted[0].content, "one");
+    assert!(board.take_evicted().is_empty());
+}
+
+#[test]
+fn test_retention_limits_must_keep_something() {
+    assert!(RetentionLimits::default().validate().is_ok());
+    for limits in [
+        RetentionLimits {
+            max_feed_length: 0,
+            ..RetentionLimits::default()
+        },
+        RetentionLimits {
+            max_journal_turns: 0,
+            ..RetentionLimits::default()
+        },
+    ] {
+        assert!(
+            MultiplayerGameService::new_in_memory()
+                .create_room_with_settings(
+                    "Forgetful".to_string(),
+                    "Host".to_string(),
+                    None,
+                    settings(50, limits),
+                )
+                .is_err()
+        );
+    }
+}
+
+#[test]
+fn test_trimmed_messages_are_archived() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Archive Room".to_string(),
+            "Host".to_string(),
+            None,
+            settings(2, RetentionLimits::default()),
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+
+    for index in 1..=5 {
+        let posted = service
+            .post_message(room_id, host_id, format!("Message {}", index))
+            .unwrap();
+        assert!(posted.success, "{}", posted.message);
+    }
+
+    let board = service.get_messages(room_id, host_id).unwrap();
+    assert_eq!(board.total_count, 2);
+
+    let archive = service
+        .get_archived_messages(room_id, host_id, ArchivedMessagesQuery::default())
+        .unwrap();
+    assert_eq!(archive.airport_id, board.airport_id);
+    let contents: Vec<&str> = archive
+        .messages
+        .iter()
+        .map(|message| message.content.as_str())
+        .collect();
+    assert_eq!(contents, vec!["Message 3", "Message 2", "Message 1"]);
+
+    let page = service
+        .get_archived_messages(room_id, host_id, ArchivedMessagesQuery { limit: Some(1) })
+        .unwrap();
+    assert_eq!(page.messages[0].content, "Message 3");
+    assert!(
+        service
+            .get_archived_messages(room_id, host_id, ArchivedMessagesQuery { limit: Some(0) })
+            .is_err()
+    );
+}
+
+#[test]
+fn test_feeds_and_journals_stay_within_their_limits() {
+    let service = MultiplayerGameService::new_in_memory();
+    let limits = RetentionLimits {
+        max_feed_length: 2,
+        max_journal_turns: 1,
+        ..RetentionLimits::default()
+    };
+    let room = service
+        .create_room_with_settings(
+            "Short Memory".to_string(),
+            "Host".to_string(),
+            None,
+            settings(50, limits),
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    let guest = service
+        .join_room(room_id, "Guest".to_string(), None)
+        .unwrap();
+
+    for index in 0..4 {
+        let posted = service
+            .post_message(room_id, guest.player_id, format!("@Host ping {}", index))
+            .unwrap();
+        assert!(posted.success, "{}", posted.message);
+    }
+    let notifications = service.get_notifications(room_id, host_id).unwrap();
+    assert_eq!(notifications.notifications.len(), 2);
+
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    let host = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(host_id))
+        .unwrap();
+    let space = host.max_fuel - host.fuel;
+    let fuel = service
+        .player_buy_fuel(room_id, host_id, FuelRequest { quantity: space })
+        .unwrap();
+    assert!(fuel.success, "{}", fuel.message);
+    for destination in ["ORD", "JFK"] {
+        let travel = service
+            .player_travel(room_id, host_id, destination.to_string())
+            .unwrap();
+        assert!(travel.success, "{}", travel.message);
+    }
+
+    // Only the latest turn is kept in the room, but the replay still has
+    // the whole flight from the archive
+    let replay = service.get_replay(room_id, host_id).unwrap();
+    let airports: Vec<&str> = replay.pilots[0]
+        .turns
+        .iter()
+        .map(|turn| turn.airport_id.as_str())
+        .collect();
+    assert_eq!(airports, vec!["JFK", "ORD", "JFK"]);
+}
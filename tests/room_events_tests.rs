@@ -0,0 +1,175 @@
+use rand::{SeedableRng, rngs::StdRng};
+
+use kzrk::api::models::FuelRequest;
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{
+    RoomSettings,
+    events::{EventConfig, EventSystem, EventWeights, MarketEventType},
+};
+
+fn only(weights: EventWeights) -> EventConfig {
+    EventConfig {
+        chance: 1.0,
+        weights,
+    }
+}
+
+fn none() -> EventWeights {
+    EventWeights {
+        price_spike: 0.0,
+        price_crash: 0.0,
+        shortage: 0.0,
+        news: 0.0,
+        boom: 0.0,
+        recession: 0.0,
+    }
+}
+
+#[test]
+fn test_weights_pick_only_the_kinds_left_on() {
+    let mut rng = StdRng::seed_from_u64(7);
+    assert!(none().pick(&mut rng).is_none());
+
+    let crashes_only = EventWeights {
+        price_crash: 1.0,
+        ..none()
+    };
+    for _ in 0..100 {
+        assert!(matches!(
+            crashes_only.pick(&mut rng),
+            Some(MarketEventType::PriceCrash)
+        ));
+    }
+
+    // Booms weighted 3 to 1 over recessions
+    let boosted = EventWeights {
+        boom: 3.0,
+        recession: 1.0,
+        ..none()
+    };
+    let booms = (0..4000)
+        .filter(|_| matches!(boosted.pick(&mut rng), Some(MarketEventType::Boom)))
+        .count();
+    assert!((2700..3300).contains(&booms), "{} booms", booms);
+}
+
+#[test]
+fn test_generator_follows_the_config() {
+    let airports = get_default_airports();
+    let cargo_types = get_default_cargo_types();
+    let mut rng = StdRng::seed_from_u64(11);
+
+    let off = EventConfig {
+        chance: 0.0,
+        ..EventConfig::default()
+    };
+    assert!((0..500).all(|_| {
+        EventSystem::generate_random_event(&airports, &cargo_types, &off, &mut rng).is_none()
+    }));
+
+    let spikes = only(EventWeights {
+        price_spike: 1.0,
+        ..none()
+    });
+    for _ in 0..50 {
+        let event =
+            EventSystem::generate_random_event(&airports, &cargo_types, &spikes, &mut rng).unwrap();
+        assert!(matches!(event.event_type, MarketEventType::PriceSpike));
+        assert!(event.price_multiplier > 1.0);
+    }
+}
+
+#[test]
+fn test_room_rejects_bad_event_settings() {
+    let service = MultiplayerGameService::new_in_memory();
+    for events in [
+        EventConfig {
+            chance: 1.5,
+            ..EventConfig::default()
+        },
+        only(EventWeights {
+            shortage: -1.0,
+            ..EventWeights::default()
+        }),
+        only(EventWeights {
+            boom: 50.0,
+            ..EventWeights::default()
+        }),
+    ] {
+        let created = service.create_room_with_settings(
+            "Bad Events".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                events,
+                ..RoomSettings::default()
+            },
+        );
+        assert!(created.is_err());
+    }
+}
+
+#[test]
+fn test_room_events_follow_the_settings_and_are_listed() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Boom Town".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                events: only(EventWeights {
+                    boom: 2.0,
+                    ..none()
+                }),
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                overnight_theft: false,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+
+    let fuel = service
+        .player_buy_fuel(room_id, host_id, FuelRequest { quantity: 50 })
+        .unwrap();
+    assert!(fuel.success, "{}", fuel.message);
+    let travel = service
+        .player_travel(room_id, host_id, "ORD".to_string())
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    assert!(!state.active_events.is_empty());
+    assert!(
+        state
+            .active_events
+            .iter()
+            .all(|event| event.event_type == "Boom")
+    );
+
+    let rules = &state.room_info.event_rules;
+    assert_eq!(rules.enabled_market_events, vec!["Boom"]);
+    assert!(!rules.overnight_theft);
+
+    let quiet = service
+        .create_room_with_settings(
+            "Quiet".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                events: EventConfig {
+                    chance: 0.0,
+                    ..EventConfig::default()
+                },
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let listed = service.list_rooms().unwrap();
+    let quiet = listed.iter().find(|room| room.id == quiet.room_id).unwrap();
+    assert!(quiet.event_rules.enabled_market_events.is_empty());
+    assert!(quiet.event_rules.summary().starts_with("No market events"));
+}
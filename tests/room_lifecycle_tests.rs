@@ -1,7 +1,10 @@
 use tempfile::tempdir;
 
-use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::api::errors::{ErrorCode, ServiceError};
+use kzrk::api::multiplayer_service::{MultiplayerGameService, SessionPolicy};
+use kzrk::models::locale::MessageCode;
 use kzrk::systems::GameStatus;
+use kzrk::systems::errors::RoomError;
 
 /// Test the complete lifecycle of a room from creation to persistence
 #[tokio::test]
@@ -42,7 +45,7 @@ async fn test_basic_room_lifecycle() {
 
     // Phase 3: Players Leave
     service
-        .leave_room(room_id, player2_response.player_id)
+        .leave_room(room_id, player2_response.player_id, false)
         .expect("Failed for player2 to leave");
 
     let rooms = service.list_rooms().expect("Failed to list rooms");
@@ -50,7 +53,7 @@ async fn test_basic_room_lifecycle() {
 
     // Phase 4: Host Leaves (Room becomes empty)
     service
-        .leave_room(room_id, host_id)
+        .leave_room(room_id, host_id, false)
         .expect("Failed for host to leave");
 
     // Room should still exist but be empty
@@ -104,10 +107,10 @@ async fn test_multiple_empty_rooms_management() {
 
         // Both leave
         service
-            .leave_room(room_id, host_id)
+            .leave_room(room_id, host_id, false)
             .expect("Host failed to leave");
         service
-            .leave_room(room_id, player_response.player_id)
+            .leave_room(room_id, player_response.player_id, false)
             .expect("Player failed to leave");
 
         room_data.push((room_id, format!("Empty Room {}", i)));
@@ -135,7 +138,7 @@ async fn test_multiple_empty_rooms_management() {
 
         // Leave immediately to keep room empty for next test
         service
-            .leave_room(*room_id, join_response.player_id)
+            .leave_room(*room_id, join_response.player_id, false)
             .expect("Failed to leave after rejoining");
     }
 }
@@ -172,7 +175,7 @@ async fn test_rapid_leave_rejoin() {
 
         // Immediately leave
         service
-            .leave_room(room_id, join_response.player_id)
+            .leave_room(room_id, join_response.player_id, false)
             .expect("Failed to leave rapidly");
 
         // Verify room state is consistent
@@ -183,7 +186,7 @@ async fn test_rapid_leave_rejoin() {
 
     // Host leaves and rejoins
     service
-        .leave_room(room_id, host_id)
+        .leave_room(room_id, host_id, false)
         .expect("Host failed to leave");
 
     let rooms = service.list_rooms().expect("Failed to list rooms");
@@ -209,3 +212,494 @@ async fn test_rapid_leave_rejoin() {
     assert_eq!(final_state.players.len(), 1);
     assert_eq!(final_state.room_info.name, "Rapid Test Room");
 }
+
+#[test]
+fn test_leave_obligations_list_held_cargo() {
+    use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+    use kzrk::systems::GameRoom;
+    use uuid::Uuid;
+
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Obligations Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+
+    // An empty hold means the player can leave freely
+    assert!(room.leave_obligations(&host_id).is_empty());
+
+    room.get_player_mut(&host_id)
+        .unwrap()
+        .player
+        .cargo_inventory
+        .add_cargo("electronics", 3);
+
+    let obligations = room.leave_obligations(&host_id);
+    assert_eq!(obligations.len(), 1);
+    assert!(obligations[0].contains("3 units of Electronics"));
+
+    // Unknown players have nothing to confirm
+    assert!(room.leave_obligations(&Uuid::new_v4()).is_empty());
+}
+
+fn obligations_room() -> (kzrk::systems::GameRoom, uuid::Uuid) {
+    use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+    use kzrk::systems::GameRoom;
+    use uuid::Uuid;
+
+    let host_id = Uuid::new_v4();
+    let room = GameRoom::new(
+        "Obligations Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    (room, host_id)
+}
+
+#[test]
+fn test_leave_obligations_list_outstanding_loans() {
+    use kzrk::systems::bank::BankSystem;
+
+    let (mut room, host_id) = obligations_room();
+    let player = &mut room.get_player_mut(&host_id).unwrap().player;
+    let loan_id = BankSystem::borrow(player, 1000, 1).unwrap();
+    let loan = player.loans.get(loan_id).unwrap().clone();
+
+    let obligations = room.leave_obligations(&host_id);
+    assert_eq!(obligations.len(), 1);
+    assert!(
+        obligations[0].contains(&format!("Loan #{} still owes ${}", loan.id, loan.balance)),
+        "{}",
+        obligations[0]
+    );
+}
+
+#[test]
+fn test_leave_obligations_list_escrowed_offers() {
+    use kzrk::systems::offers::{OfferSide, OfferTerms};
+
+    let (mut room, host_id) = obligations_room();
+    room.get_player_mut(&host_id)
+        .unwrap()
+        .player
+        .cargo_inventory
+        .add_cargo("electronics", 3);
+    room.place_offer(
+        &host_id,
+        &OfferTerms {
+            side: OfferSide::Sell,
+            cargo_type: "electronics".to_string(),
+            quantity: 3,
+            unit_price: 400,
+        },
+    )
+    .unwrap();
+    room.place_offer(
+        &host_id,
+        &OfferTerms {
+            side: OfferSide::Buy,
+            cargo_type: "food".to_string(),
+            quantity: 5,
+            unit_price: 20,
+        },
+    )
+    .unwrap();
+
+    // The cargo left the hold for escrow, so only the offers are listed
+    let obligations = room.leave_obligations(&host_id);
+    assert_eq!(obligations.len(), 2, "{:?}", obligations);
+    assert!(
+        obligations[0].contains("selling 3 electronics")
+            && obligations[0].contains("3 units of electronics held in escrow"),
+        "{}",
+        obligations[0]
+    );
+    assert!(
+        obligations[1].contains("buying 5 food") && obligations[1].contains("$100 held in escrow"),
+        "{}",
+        obligations[1]
+    );
+}
+
+#[test]
+fn test_leave_obligations_list_pending_claims() {
+    use kzrk::systems::insurance::{CargoLoss, CargoLossCause, ClaimStatus};
+    use uuid::Uuid;
+
+    let (mut room, host_id) = obligations_room();
+    let insurance = &mut room.get_player_mut(&host_id).unwrap().insurance;
+    let loss_id = Uuid::new_v4();
+    insurance.record_loss(CargoLoss {
+        id: loss_id,
+        cause: CargoLossCause::Theft,
+        cargo_type: "electronics".to_string(),
+        quantity: 5,
+        value: 1000,
+        airport_id: "ORD".to_string(),
+        turn: 2,
+        insured: true,
+        description: "Stolen".to_string(),
+    });
+    let claim = insurance.file_claim(loss_id, 2).unwrap();
+
+    let obligations = room.leave_obligations(&host_id);
+    assert_eq!(obligations.len(), 1);
+    assert!(
+        obligations[0].contains(&format!(
+            "claim for $1000 is under review until turn {}",
+            claim.resolves_at_turn
+        )),
+        "{}",
+        obligations[0]
+    );
+
+    // Settled claims are nothing to wait for
+    room.get_player_mut(&host_id).unwrap().insurance.claims[0].status = ClaimStatus::Paid;
+    assert!(room.leave_obligations(&host_id).is_empty());
+}
+
+#[tokio::test]
+async fn test_leave_without_assets_needs_no_confirmation() {
+    let service = MultiplayerGameService::new_in_memory();
+    let create_response = service
+        .create_room("Free Leave Room".to_string(), "Host".to_string(), Some(2))
+        .expect("Failed to create room");
+
+    let response = service
+        .leave_room(
+            create_response.room_id,
+            create_response.host_player_id,
+            false,
+        )
+        .expect("Failed to leave");
+
+    assert!(response.success);
+    assert!(response.obligations.is_empty());
+}
+
+#[tokio::test]
+async fn test_leave_with_a_loan_needs_confirmation() {
+    let service = MultiplayerGameService::new_in_memory();
+    let create_response = service
+        .create_room("Debtor Room".to_string(), "Host".to_string(), Some(2))
+        .expect("Failed to create room");
+    let room_id = create_response.room_id;
+    let host_id = create_response.host_player_id;
+    service
+        .borrow(room_id, host_id, 1000)
+        .expect("Failed to borrow");
+
+    let error = service
+        .leave_room(room_id, host_id, false)
+        .expect_err("Leaving with a loan should need confirming");
+    let ServiceError::ConfirmationRequired { obligations, .. } = error else {
+        panic!("Expected a confirmation, got {:?}", error);
+    };
+    assert_eq!(obligations.len(), 1);
+    assert!(obligations[0].starts_with("Loan #"));
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    assert!(
+        state
+            .players
+            .iter()
+            .all(|player| player.is_online == Some(true))
+    );
+
+    let response = service
+        .leave_room(room_id, host_id, true)
+        .expect("Failed to leave");
+    assert!(response.success);
+    assert_eq!(response.obligations.len(), 1);
+}
+
+#[test]
+fn test_first_player_to_reach_target_wins_the_room() {
+    use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+    use kzrk::systems::GameRoom;
+    use uuid::Uuid;
+
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Race Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    let target = room.settings.win_condition_money;
+
+    // Not rich enough yet
+    assert!(!room.check_for_winner(&host_id));
+    assert!(room.ensure_in_play().is_ok());
+
+    room.get_player_mut(&host_id).unwrap().player.money = target;
+    assert!(room.check_for_winner(&host_id));
+    assert_eq!(room.game_status, GameStatus::Finished);
+
+    let winner = room.winner.clone().expect("winner should be recorded");
+    assert_eq!(winner.player_id, host_id);
+    assert_eq!(winner.money, target);
+
+    // The game is decided once - nobody else can win afterwards
+    assert!(!room.check_for_winner(&host_id));
+    let error = room.ensure_in_play().unwrap_err();
+    assert!(error.to_string().contains("Host won"));
+    let RoomError::Localized(localized) = error else {
+        panic!("expected a localized refusal, got {:?}", error);
+    };
+    assert_eq!(localized.code, MessageCode::GameWon);
+    assert_eq!(localized.params["winner"], "Host");
+}
+
+#[tokio::test]
+async fn test_finished_room_rejects_actions_and_offers_rematch() {
+    use kzrk::api::database::Database;
+    use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+    use kzrk::systems::GameRoom;
+    use uuid::Uuid;
+
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_rematch.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    // Seed a room that has already been won
+    let host_id = Uuid::new_v4();
+    let guest_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Finished Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.add_player(guest_id, "Guest".to_string(), None)
+        .unwrap();
+    let room_id = room.id;
+    room.get_player_mut(&host_id).unwrap().player.money = room.settings.win_condition_money;
+    assert!(room.check_for_winner(&host_id));
+    Database::new(db_path_str)
+        .unwrap()
+        .save_room(&room)
+        .unwrap();
+
+    let service = MultiplayerGameService::new_with_db_path(db_path_str);
+
+    let state = service.get_room_state(room_id, guest_id).unwrap();
+    assert_eq!(state.winner.as_ref().map(|w| w.player_id), Some(host_id));
+    assert!(state.rematch_room_id.is_none());
+
+    let error = service
+        .player_travel(room_id, guest_id, "ORD".to_string())
+        .unwrap_err();
+    assert!(error.to_string().contains("Game over"));
+
+    // The first player to ask hosts the rematch
+    let host_rematch = service.rematch(room_id, host_id).unwrap();
+    assert_ne!(host_rematch.room_id, room_id);
+    assert_eq!(host_rematch.player_name, "Host");
+
+    // Everyone else lands in the same rematch room
+    let guest_rematch = service.rematch(room_id, guest_id).unwrap();
+    assert_eq!(guest_rematch.room_id, host_rematch.room_id);
+    assert_eq!(guest_rematch.player_name, "Guest");
+
+    let rematch_state = service
+        .get_room_state(host_rematch.room_id, guest_rematch.player_id)
+        .unwrap();
+    assert!(rematch_state.winner.is_none());
+    assert_eq!(rematch_state.players.len(), 2);
+    assert_eq!(
+        service
+            .get_room_state(room_id, host_id)
+            .unwrap()
+            .rematch_room_id,
+        Some(host_rematch.room_id)
+    );
+
+    // Strangers can't piggyback on someone else's rematch
+    assert!(service.rematch(room_id, Uuid::new_v4()).is_err());
+}
+
+#[test]
+fn test_rematch_vote_restarts_the_room_in_place() {
+    use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+    use kzrk::systems::GameRoom;
+    use uuid::Uuid;
+
+    let host_id = Uuid::new_v4();
+    let guest_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "League Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.add_player(guest_id, "Guest".to_string(), None)
+        .unwrap();
+    room.message_board
+        .post_message(
+            guest_id,
+            "Guest".to_string(),
+            "gg".to_string(),
+            "JFK".to_string(),
+        )
+        .unwrap();
+
+    // Nobody can call a rematch mid-game
+    assert!(room.vote_rematch(&host_id).is_err());
+
+    {
+        let guest = room.get_player_mut(&guest_id).unwrap();
+        guest.player.money = 42;
+        guest.player.cargo_inventory.add_cargo("food", 5);
+        guest.turn_number = 9;
+    }
+    room.shared_state.world_tick = 30;
+    room.get_player_mut(&host_id).unwrap().player.money = room.settings.win_condition_money;
+    assert!(room.check_for_winner(&host_id));
+
+    // One vote of two isn't enough, and voting twice doesn't count double
+    assert!(!room.vote_rematch(&host_id).unwrap());
+    assert!(!room.vote_rematch(&host_id).unwrap());
+    assert!(room.is_finished());
+    assert!(room.vote_rematch(&Uuid::new_v4()).is_err());
+
+    assert!(room.vote_rematch(&guest_id).unwrap());
+    assert!(!room.is_finished());
+    assert!(room.winner.is_none());
+    assert!(room.rematch_votes.is_empty());
+    assert_eq!(room.round, 2);
+    assert_eq!(room.shared_state.world_tick, 1);
+    assert!(room.ensure_in_play().is_ok());
+
+    let guest = room.get_player(&guest_id).unwrap();
+    assert_eq!(guest.player.money, room.settings.starting_money);
+    assert!(guest.player.cargo_inventory.is_empty());
+    assert_eq!(guest.turn_number, 1);
+    assert_eq!(room.players.len(), 2);
+
+    // The chat survives the reset
+    assert_eq!(room.message_board.message_count(None), 1);
+}
+
+#[tokio::test]
+async fn test_rematch_vote_waits_for_everyone_online() {
+    use kzrk::api::database::Database;
+    use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+    use kzrk::systems::GameRoom;
+    use uuid::Uuid;
+
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_rematch_vote.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    let host_id = Uuid::new_v4();
+    let guest_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Vote Room".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.add_player(guest_id, "Guest".to_string(), None)
+        .unwrap();
+    let room_id = room.id;
+    room.get_player_mut(&host_id).unwrap().player.money = room.settings.win_condition_money;
+    assert!(room.check_for_winner(&host_id));
+    Database::new(db_path_str)
+        .unwrap()
+        .save_room(&room)
+        .unwrap();
+
+    let service = MultiplayerGameService::new_with_db_path(db_path_str);
+
+    let vote = service.vote_rematch(room_id, host_id).unwrap();
+    assert!(!vote.restarted);
+    assert_eq!((vote.votes, vote.votes_needed), (1, 2));
+    assert!(vote.starting_airport.is_none());
+    let state = service.get_room_state(room_id, guest_id).unwrap();
+    assert_eq!(state.rematch_votes, vec!["Host".to_string()]);
+    assert!(state.winner.is_some());
+
+    let vote = service.vote_rematch(room_id, guest_id).unwrap();
+    assert!(vote.restarted);
+    assert_eq!(vote.round, 2);
+    let state = service.get_room_state(room_id, guest_id).unwrap();
+    assert!(state.winner.is_none());
+    assert_eq!(state.round, 2);
+    assert_eq!(Some(state.current_market.airport_id), vote.starting_airport);
+
+    // Play carries on in the same room
+    let flight = service
+        .player_travel(room_id, guest_id, "ORD".to_string())
+        .unwrap();
+    assert!(flight.success, "{}", flight.message);
+}
+
+#[tokio::test]
+async fn test_idle_sessions_expire() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_session_expiry.db");
+    let service = MultiplayerGameService::new_with_db_path(db_path.to_str().unwrap())
+        .with_session_policy(SessionPolicy {
+            ttl: chrono::Duration::hours(1),
+            ..SessionPolicy::default()
+        });
+
+    let room = service
+        .create_room("Expiry Room".to_string(), "Host".to_string(), Some(4))
+        .unwrap();
+    service
+        .join_room(room.room_id, "Guest".to_string(), None)
+        .unwrap();
+
+    let now = chrono::Utc::now();
+    assert_eq!(service.expire_sessions(now).unwrap(), 0);
+    let sessions = service.find_player_sessions("Host").unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert!(sessions[0].idle_seconds < 60);
+
+    // Two hours on, both sessions are past the TTL
+    let later = now + chrono::Duration::hours(2);
+    assert_eq!(service.expire_sessions(later).unwrap(), 2);
+    assert!(service.find_player_sessions("Host").unwrap().is_empty());
+    assert!(service.find_player_sessions("Guest").unwrap().is_empty());
+
+    // Their players are offline but keep their place in the room
+    let rooms = service.list_rooms().unwrap();
+    assert_eq!(rooms[0].current_players, 0);
+}
+
+#[tokio::test]
+async fn test_logout_ends_the_session() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_logout.db");
+    let service = MultiplayerGameService::new_with_db_path(db_path.to_str().unwrap());
+
+    let room = service
+        .create_room("Logout Room".to_string(), "Host".to_string(), Some(4))
+        .unwrap();
+
+    let response = service.logout(room.host_player_id).unwrap();
+    assert!(response.success);
+    assert!(service.find_player_sessions("Host").unwrap().is_empty());
+    assert_eq!(service.list_rooms().unwrap()[0].current_players, 0);
+
+    assert_eq!(
+        service.logout(room.host_player_id).unwrap_err(),
+        ServiceError::NotFound(ErrorCode::SessionNotFound)
+    );
+}
@@ -0,0 +1,166 @@
+use rand::{SeedableRng, rngs::StdRng};
+use uuid::Uuid;
+
+use kzrk::api::errors::{ErrorCode, ServiceError};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{GameRoom, GameStatus, RoomSettings, deadlines::TurnDeadline};
+
+#[test]
+fn test_settings_list_the_effective_airports_and_cargo() {
+    let service = MultiplayerGameService::new_in_memory();
+    let narrowed = service
+        .create_room_with_settings(
+            "Short Hops".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                airports: vec!["ORD".to_string(), "JFK".to_string()],
+                cargo_types: vec!["food".to_string(), "electronics".to_string()],
+                starting_money: 2500,
+                fog_of_war: true,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+
+    let info = service.get_room_settings(narrowed.room_id).unwrap();
+    assert_eq!(info.room_name, "Short Hops");
+    assert_eq!(info.game_status, GameStatus::WaitingForPlayers);
+    assert_eq!(info.airports, vec!["JFK", "ORD"]);
+    assert_eq!(info.cargo_types, vec!["electronics", "food"]);
+    assert_eq!(info.settings.starting_money, 2500);
+    assert!(info.settings.fog_of_war);
+    assert_eq!(info.turn_deadline_hours, None);
+
+    // An empty list in the settings means everything
+    let open = service
+        .create_room("Everywhere".to_string(), "Host".to_string(), None)
+        .unwrap();
+    let info = service.get_room_settings(open.room_id).unwrap();
+    assert_eq!(info.airports.len(), get_default_airports().len());
+    assert_eq!(info.cargo_types.len(), get_default_cargo_types().len());
+    assert!(info.settings.airports.is_empty());
+}
+
+#[test]
+fn test_settings_hide_the_deadline_webhook() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "By Post".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                turn_deadline: Some(TurnDeadline {
+                    hours: 24,
+                    warning_hours: 2,
+                    webhook_url: "https://discord.example.com/api/webhooks/1/abc".to_string(),
+                }),
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+
+    let info = service.get_room_settings(room.room_id).unwrap();
+    assert_eq!(info.turn_deadline_hours, Some(24));
+    assert!(info.settings.turn_deadline.is_none());
+    let json = serde_json::to_string(&info).unwrap();
+    assert!(!json.contains("discord.example.com"));
+}
+
+#[test]
+fn test_unknown_rooms_have_no_settings() {
+    let service = MultiplayerGameService::new_in_memory();
+    let err = service.get_room_settings(Uuid::new_v4()).unwrap_err();
+    assert_eq!(err, ServiceError::NotFound(ErrorCode::RoomNotFound));
+}
+
+#[test]
+fn test_world_config_sets_starting_money_fuel_and_price_swings() {
+    let settings = RoomSettings {
+        airports: vec!["DEN".to_string(), "SEA".to_string()],
+        starting_money: 2000,
+        starting_fuel: Some(50),
+        price_volatility: 0.0,
+        ..RoomSettings::default()
+    };
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::from_settings(
+        "Steady".to_string(),
+        host_id,
+        "Host".to_string(),
+        4,
+        settings,
+    )
+    .unwrap();
+    let guest_id = room
+        .add_player(Uuid::new_v4(), "Guest".to_string(), None)
+        .unwrap();
+
+    let mut airports: Vec<&String> = room.shared_state.airports.keys().collect();
+    airports.sort();
+    assert_eq!(airports, ["DEN", "SEA"]);
+    for player_id in [host_id, guest_id] {
+        let player = &room.players[&player_id].player;
+        assert_eq!(player.money, 2000);
+        assert_eq!(player.fuel, 50);
+        assert!(
+            room.shared_state
+                .airports
+                .contains_key(&player.current_airport)
+        );
+    }
+
+    // With no swings, prices only move with what each airport makes and
+    // wants
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..20 {
+        room.refresh_markets(&mut rng);
+        for market in room.shared_state.markets.values() {
+            for (cargo_id, price) in &market.cargo_prices {
+                let base = room.shared_state.cargo_types[cargo_id].base_price as f64;
+                let price = *price as f64;
+                assert!(
+                    (base * 0.7 - 1.0..=base * 1.4 + 1.0).contains(&price),
+                    "{} at {}",
+                    cargo_id,
+                    price
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_world_config_is_validated() {
+    let create = |settings: RoomSettings| {
+        MultiplayerGameService::new_in_memory().create_room_with_settings(
+            "Bad World".to_string(),
+            "Host".to_string(),
+            None,
+            settings,
+        )
+    };
+
+    let err = create(RoomSettings {
+        starting_fuel: Some(201),
+        ..RoomSettings::default()
+    })
+    .unwrap_err();
+    assert!(err.to_string().contains("Starting fuel"), "{}", err);
+    let err = create(RoomSettings {
+        price_volatility: 3.5,
+        ..RoomSettings::default()
+    })
+    .unwrap_err();
+    assert!(err.to_string().contains("Price volatility"), "{}", err);
+
+    // Settings saved before the world config existed still load
+    let mut json = serde_json::to_value(RoomSettings::default()).unwrap();
+    let object = json.as_object_mut().unwrap();
+    object.remove("starting_fuel");
+    object.remove("price_volatility");
+    let settings: RoomSettings = serde_json::from_value(json).unwrap();
+    assert_eq!(settings, RoomSettings::default());
+}
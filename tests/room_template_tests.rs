@@ -0,0 +1,675 @@
+use tempfile::tempdir;
+
+use kzrk::api::{
+    models::{CreateTemplateRequest, TradeAction, TradeRequest},
+    multiplayer_service::MultiplayerGameService,
+};
+use kzrk::models::{Airport, CargoType};
+use kzrk::systems::{RoomSettings, multiplayer::SpawnRule};
+
+fn small_world_settings() -> RoomSettings {
+    RoomSettings {
+        starting_money: 8000,
+        win_condition_money: 50000,
+        airports: vec!["ORD".to_string(), "DEN".to_string(), "SEA".to_string()],
+        ..RoomSettings::default()
+    }
+}
+
+#[tokio::test]
+async fn test_create_room_with_custom_settings() {
+    let service = MultiplayerGameService::new_in_memory();
+
+    let response = service
+        .create_room_with_settings(
+            "Small World".to_string(),
+            "Host".to_string(),
+            Some(3),
+            small_world_settings(),
+        )
+        .expect("Failed to create room");
+
+    let state = service
+        .get_room_state(response.room_id, response.host_player_id)
+        .expect("Failed to get room state");
+
+    // Host starts with the configured money at a valid airport of the subset
+    let host = &state.players[0];
+    assert_eq!(host.money, 8000);
+    assert_eq!(host.current_airport, "DEN");
+    assert_eq!(state.available_destinations.len(), 2);
+
+    // Joining players get the same starting money
+    let join = service
+        .join_room(
+            response.room_id,
+            "Guest".to_string(),
+            Some("SEA".to_string()),
+        )
+        .expect("Failed to join");
+    let guest_state = service
+        .get_room_state(response.room_id, join.player_id)
+        .expect("Failed to get guest state");
+    let guest = guest_state
+        .players
+        .iter()
+        .find(|p| p.id == Some(join.player_id))
+        .unwrap();
+    assert_eq!(guest.money, 8000);
+
+    // Airports outside the room's world are rejected
+    let result = service.join_room(
+        response.room_id,
+        "Lost".to_string(),
+        Some("JFK".to_string()),
+    );
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_invalid_settings_are_rejected() {
+    let service = MultiplayerGameService::new_in_memory();
+
+    let unknown_airport = RoomSettings {
+        airports: vec!["ORD".to_string(), "XXX".to_string()],
+        ..RoomSettings::default()
+    };
+    let result = service.create_room_with_settings(
+        "Bad Room".to_string(),
+        "Host".to_string(),
+        None,
+        unknown_airport,
+    );
+    assert!(result.unwrap_err().to_string().contains("XXX"));
+
+    let unwinnable = RoomSettings {
+        starting_money: 200000,
+        ..RoomSettings::default()
+    };
+    let result = service.create_room_with_settings(
+        "Bad Room".to_string(),
+        "Host".to_string(),
+        None,
+        unwinnable,
+    );
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_create_room_from_template() {
+    let service = MultiplayerGameService::new_in_memory();
+
+    let template = service
+        .create_template(CreateTemplateRequest {
+            name: "Weekly Mountain Run".to_string(),
+            max_players: Some(6),
+            settings: Some(small_world_settings()),
+            from_room_id: None,
+        })
+        .expect("Failed to create template");
+
+    let templates = service.list_templates().expect("Failed to list templates");
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].name, "Weekly Mountain Run");
+
+    let response = service
+        .create_room_from_template(template.id, "Week 1".to_string(), "Host".to_string())
+        .expect("Failed to create room from template");
+    assert_eq!(response.max_players, 6);
+
+    let state = service
+        .get_room_state(response.room_id, response.host_player_id)
+        .expect("Failed to get room state");
+    assert_eq!(state.players[0].money, 8000);
+
+    let missing = service.create_room_from_template(
+        uuid::Uuid::new_v4(),
+        "Week 2".to_string(),
+        "Host".to_string(),
+    );
+    assert!(missing.is_err());
+}
+
+#[tokio::test]
+async fn test_clone_room_and_template_from_room() {
+    let service = MultiplayerGameService::new_in_memory();
+
+    let original = service
+        .create_room_with_settings(
+            "Friday Game".to_string(),
+            "Host".to_string(),
+            Some(5),
+            small_world_settings(),
+        )
+        .expect("Failed to create room");
+
+    let clone = service
+        .clone_room(original.room_id, None, "NewHost".to_string())
+        .expect("Failed to clone room");
+    assert_ne!(clone.room_id, original.room_id);
+    assert_eq!(clone.room_name, "Friday Game (copy)");
+    assert_eq!(clone.max_players, 5);
+    assert_eq!(clone.host_player_name, "NewHost");
+
+    let clone_state = service
+        .get_room_state(clone.room_id, clone.host_player_id)
+        .expect("Failed to get clone state");
+    assert_eq!(clone_state.players[0].money, 8000);
+    assert_eq!(clone_state.turn_number, 1);
+
+    // A room's rules can be saved as a template too
+    let template = service
+        .create_template(CreateTemplateRequest {
+            name: "Friday Rules".to_string(),
+            max_players: None,
+            settings: None,
+            from_room_id: Some(original.room_id),
+        })
+        .expect("Failed to save room as template");
+    assert_eq!(template.max_players, 5);
+    assert_eq!(template.settings, small_world_settings());
+
+    assert!(
+        service
+            .clone_room(uuid::Uuid::new_v4(), None, "Host".to_string())
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_templates_persist_through_restart() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_templates.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    let template_id = {
+        let service = MultiplayerGameService::new_with_db_path(db_path_str);
+        service
+            .create_template(CreateTemplateRequest {
+                name: "Persistent Template".to_string(),
+                max_players: Some(2),
+                settings: None,
+                from_room_id: None,
+            })
+            .expect("Failed to create template")
+            .id
+    };
+
+    let service = MultiplayerGameService::new_with_db_path(db_path_str);
+    let templates = service.list_templates().expect("Failed to list templates");
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].id, template_id);
+    assert_eq!(templates[0].settings, RoomSettings::default());
+}
+
+#[tokio::test]
+async fn test_fog_of_war_hides_unvisited_markets() {
+    let service = MultiplayerGameService::new_in_memory();
+    let settings = RoomSettings {
+        fog_of_war: true,
+        fog_stale_after_turns: 3,
+        ..RoomSettings::default()
+    };
+    let response = service
+        .create_room_with_settings("Foggy".to_string(), "Scout".to_string(), None, settings)
+        .unwrap();
+    let (room_id, player_id) = (response.room_id, response.host_player_id);
+
+    // Only the starting airport is known
+    let state = service.get_room_state(room_id, player_id).unwrap();
+    let known: Vec<&str> = state
+        .known_markets
+        .iter()
+        .map(|m| m.airport_id.as_str())
+        .collect();
+    assert_eq!(known, vec!["JFK"]);
+    let ord = state
+        .available_destinations
+        .iter()
+        .find(|d| d.airport_id == "ORD")
+        .unwrap();
+    assert_eq!(ord.fuel_price_observed_at, None);
+    assert_eq!(ord.fuel_price, 0);
+
+    service
+        .player_travel(room_id, player_id, "ORD".to_string())
+        .unwrap();
+    let state = service.get_room_state(room_id, player_id).unwrap();
+    let jfk = state
+        .known_markets
+        .iter()
+        .find(|m| m.airport_id == "JFK")
+        .unwrap();
+    assert_eq!(jfk.observed_at, 1);
+    assert_eq!(jfk.turns_ago, 1);
+    assert!(!jfk.stale);
+    assert!(state.known_markets.iter().any(|m| m.airport_id == "ORD"));
+
+    // Hang around long enough and the JFK prices go stale
+    for _ in 0..3 {
+        service
+            .player_travel(room_id, player_id, "ORD".to_string())
+            .unwrap();
+    }
+    let state = service.get_room_state(room_id, player_id).unwrap();
+    for market in &state.known_markets {
+        assert_eq!(market.stale, market.airport_id == "JFK");
+    }
+}
+
+#[tokio::test]
+async fn test_without_fog_every_market_is_known() {
+    let service = MultiplayerGameService::new_in_memory();
+    let response = service
+        .create_room("Clear Skies".to_string(), "Host".to_string(), None)
+        .unwrap();
+
+    let state = service
+        .get_room_state(response.room_id, response.host_player_id)
+        .unwrap();
+    assert_eq!(
+        state.known_markets.len(),
+        state.available_destinations.len() + 1
+    );
+    assert!(state.known_markets.iter().all(|m| !m.stale));
+    assert!(
+        state
+            .available_destinations
+            .iter()
+            .all(|d| d.fuel_price_observed_at.is_some() && d.fuel_price > 0)
+    );
+
+    let invalid = RoomSettings {
+        fog_of_war: true,
+        fog_stale_after_turns: 0,
+        ..RoomSettings::default()
+    };
+    assert!(
+        service
+            .create_room_with_settings("Bad".to_string(), "Host".to_string(), None, invalid)
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_room_with_a_subset_of_airports_and_cargo() {
+    let service = MultiplayerGameService::new_in_memory();
+    let settings = RoomSettings {
+        airports: vec!["JFK".to_string(), "ORD".to_string(), "DEN".to_string()],
+        cargo_types: vec!["electronics".to_string(), "food".to_string()],
+        ..RoomSettings::default()
+    };
+    let response = service
+        .create_room_with_settings("Quick Game".to_string(), "Host".to_string(), None, settings)
+        .unwrap();
+    let (room_id, player_id) = (response.room_id, response.host_player_id);
+
+    let state = service.get_room_state(room_id, player_id).unwrap();
+    let airport_ids: Vec<&str> = state.airports.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(airport_ids, vec!["DEN", "JFK", "ORD"]);
+    let cargo_ids: Vec<&str> = state.cargo_types.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(cargo_ids, vec!["electronics", "food"]);
+    assert_eq!(state.available_destinations.len(), 2);
+
+    let mut traded: Vec<&String> = state.current_market.cargo_prices.keys().collect();
+    traded.sort();
+    assert_eq!(traded, vec!["electronics", "food"]);
+
+    // Cargo left out of the room can't be bought
+    let result = service.player_trade(
+        room_id,
+        player_id,
+        TradeRequest {
+            cargo_type: "luxury".to_string(),
+            quantity: 1,
+            action: TradeAction::Buy,
+        },
+    );
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_room_with_an_uploaded_world() {
+    let service = MultiplayerGameService::new_in_memory();
+    let settings = RoomSettings {
+        custom_cargo_types: vec![
+            CargoType::new("mail", "Air Mail", 40, 1, 0.1),
+            CargoType::new("salmon", "Fresh Salmon", 120, 3, 0.4),
+        ],
+        custom_airports: vec![
+            Airport::new(
+                "ANC",
+                "Anchorage",
+                (61.17, -149.99),
+                60,
+                vec!["salmon".to_string()],
+                vec!["mail".to_string()],
+                1.1,
+            ),
+            Airport::new(
+                "FAI",
+                "Fairbanks",
+                (64.82, -147.86),
+                70,
+                vec![],
+                vec!["salmon".to_string()],
+                1.2,
+            ),
+            Airport::new(
+                "JNU",
+                "Juneau",
+                (58.35, -134.58),
+                65,
+                vec!["mail".to_string()],
+                vec![],
+                1.0,
+            ),
+        ],
+        ..RoomSettings::default()
+    };
+    let response = service
+        .create_room_with_settings(
+            "Bush Pilots".to_string(),
+            "Host".to_string(),
+            None,
+            settings,
+        )
+        .unwrap();
+    let (room_id, player_id) = (response.room_id, response.host_player_id);
+
+    // No JFK here, so the host starts at the first airport by id
+    let state = service.get_room_state(room_id, player_id).unwrap();
+    assert_eq!(state.players[0].current_airport, "ANC");
+    assert_eq!(state.airports.len(), 3);
+    assert_eq!(state.current_market.cargo_prices.len(), 2);
+
+    let buy = service
+        .player_trade(
+            room_id,
+            player_id,
+            TradeRequest {
+                cargo_type: "salmon".to_string(),
+                quantity: 5,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(buy.success, "{}", buy.message);
+    assert_eq!(buy.new_inventory.unwrap()["salmon"], 5);
+
+    let travel = service
+        .player_travel(room_id, player_id, "FAI".to_string())
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+}
+
+#[tokio::test]
+async fn test_invalid_worlds_are_rejected() {
+    let service = MultiplayerGameService::new_in_memory();
+    let outpost = |id: &str, produces: &str| {
+        Airport::new(
+            id,
+            "Outpost",
+            (10.0, 10.0),
+            50,
+            vec![produces.to_string()],
+            vec![],
+            1.0,
+        )
+    };
+
+    let cases = [
+        (
+            RoomSettings {
+                cargo_types: vec!["unobtainium".to_string()],
+                ..RoomSettings::default()
+            },
+            "Unknown cargo type",
+        ),
+        (
+            RoomSettings {
+                custom_cargo_types: vec![
+                    CargoType::new("mail", "Air Mail", 40, 1, 0.1),
+                    CargoType::new("mail", "More Mail", 40, 1, 0.1),
+                ],
+                ..RoomSettings::default()
+            },
+            "unique",
+        ),
+        (
+            RoomSettings {
+                custom_cargo_types: vec![CargoType::new("mail", "Air Mail", 0, 1, 0.1)],
+                ..RoomSettings::default()
+            },
+            "greater than zero",
+        ),
+        (
+            RoomSettings {
+                custom_airports: vec![outpost("AAA", "food")],
+                ..RoomSettings::default()
+            },
+            "at least two airports",
+        ),
+        (
+            RoomSettings {
+                custom_airports: vec![outpost("AAA", "food"), outpost("BBB", "spice")],
+                ..RoomSettings::default()
+            },
+            "unknown cargo type: spice",
+        ),
+        (
+            RoomSettings {
+                custom_airports: vec![outpost("AAA", "food"), outpost("BBB", "food")],
+                airports: vec!["AAA".to_string(), "JFK".to_string()],
+                ..RoomSettings::default()
+            },
+            "Unknown airport: JFK",
+        ),
+        (
+            RoomSettings {
+                custom_airports: vec![
+                    outpost("AAA", "food"),
+                    Airport::new("BBB", "Nowhere", (95.0, 0.0), 50, vec![], vec![], 1.0),
+                ],
+                ..RoomSettings::default()
+            },
+            "invalid coordinates",
+        ),
+    ];
+
+    for (settings, expected) in cases {
+        let error = service
+            .create_room_with_settings("Bad".to_string(), "Host".to_string(), None, settings)
+            .unwrap_err();
+        assert!(error.to_string().contains(expected), "{}", error);
+    }
+}
+
+fn spawn_settings(spawn: SpawnRule) -> RoomSettings {
+    RoomSettings {
+        spawn,
+        ..small_world_settings()
+    }
+}
+
+#[tokio::test]
+async fn test_host_chosen_spawn() {
+    let service = MultiplayerGameService::new_in_memory();
+    let created = service
+        .create_room_with_settings(
+            "Denver Start".to_string(),
+            "Host".to_string(),
+            Some(4),
+            spawn_settings(SpawnRule::HostChosen("DEN".to_string())),
+        )
+        .unwrap();
+    assert_eq!(created.starting_airport, "DEN");
+
+    let joined = service
+        .join_room(created.room_id, "Guest".to_string(), None)
+        .unwrap();
+    assert_eq!(joined.starting_airport, "DEN");
+    let state = service
+        .get_room_state(created.room_id, joined.player_id)
+        .unwrap();
+    assert_eq!(state.current_market.airport_id, "DEN");
+
+    // Asking for an airport still wins over the room's rule
+    let picky = service
+        .join_room(
+            created.room_id,
+            "Picky".to_string(),
+            Some("SEA".to_string()),
+        )
+        .unwrap();
+    assert_eq!(picky.starting_airport, "SEA");
+
+    // The chosen airport has to be in the room
+    let result = service.create_room_with_settings(
+        "Nowhere".to_string(),
+        "Host".to_string(),
+        None,
+        spawn_settings(SpawnRule::HostChosen("JFK".to_string())),
+    );
+    assert_eq!(
+        result.unwrap_err(),
+        "Spawn airport JFK is not part of this room".into()
+    );
+}
+
+#[tokio::test]
+async fn test_spread_out_spawn_keeps_players_apart() {
+    let service = MultiplayerGameService::new_in_memory();
+    let created = service
+        .create_room_with_settings(
+            "Spread".to_string(),
+            "Host".to_string(),
+            Some(4),
+            spawn_settings(SpawnRule::SpreadOut),
+        )
+        .unwrap();
+
+    let mut airports = vec![created.starting_airport];
+    for name in ["Second", "Third"] {
+        let joined = service
+            .join_room(created.room_id, name.to_string(), None)
+            .unwrap();
+        airports.push(joined.starting_airport);
+    }
+    airports.sort();
+    assert_eq!(airports, vec!["DEN", "ORD", "SEA"]);
+}
+
+#[tokio::test]
+async fn test_random_spawn_stays_in_the_room() {
+    let service = MultiplayerGameService::new_in_memory();
+    let created = service
+        .create_room_with_settings(
+            "Lottery".to_string(),
+            "Host".to_string(),
+            Some(8),
+            spawn_settings(SpawnRule::Random),
+        )
+        .unwrap();
+
+    let mut airports = vec![created.starting_airport];
+    for index in 0..7 {
+        let joined = service
+            .join_room(created.room_id, format!("Pilot {}", index), None)
+            .unwrap();
+        airports.push(joined.starting_airport);
+    }
+    assert!(
+        airports
+            .iter()
+            .all(|airport| ["ORD", "DEN", "SEA"].contains(&airport.as_str()))
+    );
+}
+
+#[tokio::test]
+async fn test_action_cooldown_is_per_player() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Cooldown".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                action_cooldown_ms: 50,
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let guest = service
+        .join_room(room.room_id, "Guest".to_string(), None)
+        .unwrap();
+    let buy = || TradeRequest {
+        cargo_type: "food".to_string(),
+        quantity: 1,
+        action: TradeAction::Buy,
+    };
+
+    assert!(
+        service
+            .player_trade(room.room_id, room.host_player_id, buy())
+            .unwrap()
+            .success
+    );
+    let error = service
+        .player_trade(room.room_id, room.host_player_id, buy())
+        .unwrap_err();
+    assert!(
+        error.to_string().starts_with("Too many actions"),
+        "{}",
+        error
+    );
+    // Someone else's cooldown doesn't hold this player up
+    assert!(
+        service
+            .player_trade(room.room_id, guest.player_id, buy())
+            .unwrap()
+            .success
+    );
+
+    std::thread::sleep(std::time::Duration::from_millis(60));
+    assert!(
+        service
+            .player_trade(room.room_id, room.host_player_id, buy())
+            .unwrap()
+            .success
+    );
+
+    // Refused actions count against the cooldown too, so a player can't
+    // hammer the server with trades that fail
+    std::thread::sleep(std::time::Duration::from_millis(60));
+    let too_many = || TradeRequest {
+        quantity: 1_000_000,
+        ..buy()
+    };
+    let refused = service
+        .player_trade(room.room_id, guest.player_id, too_many())
+        .unwrap();
+    assert!(!refused.success);
+    for _ in 0..3 {
+        let error = service
+            .player_trade(room.room_id, guest.player_id, too_many())
+            .unwrap_err();
+        assert!(
+            error.to_string().starts_with("Too many actions"),
+            "{}",
+            error
+        );
+    }
+
+    let too_slow = RoomSettings {
+        action_cooldown_ms: 10 * 60 * 1000,
+        ..RoomSettings::default()
+    };
+    assert!(
+        service
+            .create_room_with_settings("Glacial".to_string(), "Host".to_string(), None, too_slow)
+            .is_err()
+    );
+}
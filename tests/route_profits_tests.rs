@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use kzrk::api::models::FuelRequest;
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::models::Player;
+use kzrk::systems::{
+    RoomSettings,
+    distance::DistanceCache,
+    route_profits::{KnownPrices, ProfitMatrix, RouteAnalytics},
+};
+
+fn prices(airport_id: &str, cargo_prices: &[(&str, u32)], stale: bool) -> KnownPrices {
+    KnownPrices {
+        airport_id: airport_id.to_string(),
+        cargo_prices: cargo_prices
+            .iter()
+            .map(|(cargo, price)| (cargo.to_string(), *price))
+            .collect::<HashMap<_, _>>(),
+        stale,
+    }
+}
+
+#[test]
+fn test_routes_are_priced_for_a_full_hold_and_ranked_by_fuel() {
+    let airports = get_default_airports();
+    let cargo_types = get_default_cargo_types();
+    let distances = DistanceCache::new();
+    let player = Player::new(1000, "JFK", 200, 1000, 10.0);
+
+    let known = [
+        prices("JFK", &[("electronics", 400), ("food", 100)], false),
+        prices("ORD", &[("electronics", 600), ("food", 90)], false),
+        prices("LAX", &[("electronics", 700)], true),
+    ];
+    let routes = RouteAnalytics::rank(&player, &known, &airports, &cargo_types, &distances);
+
+    // Electronics weigh 1kg, so a full hold is 1000 of them
+    let to_ord = routes
+        .iter()
+        .find(|route| {
+            route.cargo_type == "electronics" && route.buy_at == "JFK" && route.sell_at == "ORD"
+        })
+        .unwrap();
+    assert_eq!(to_ord.units, 1000);
+    assert_eq!(to_ord.profit, 200_000);
+    let km = distances.between_ids(&airports, "JFK", "ORD").unwrap();
+    assert_eq!(to_ord.fuel_needed, (km / 10.0).ceil() as u32);
+    assert!(!to_ord.stale);
+
+    // Food is only priced at two airports, so it never goes to LAX
+    assert!(
+        !routes
+            .iter()
+            .any(|route| route.cargo_type == "food" && route.sell_at == "LAX")
+    );
+    assert!(
+        routes
+            .iter()
+            .filter(|route| route.buy_at == "LAX" || route.sell_at == "LAX")
+            .all(|route| route.stale)
+    );
+    assert!(
+        routes
+            .windows(2)
+            .all(|pair| pair[0].profit_per_fuel >= pair[1].profit_per_fuel)
+    );
+    assert!(routes.iter().any(|route| route.profit < 0));
+}
+
+#[test]
+fn test_matrix_keeps_the_best_cargo_per_pair() {
+    let airports = get_default_airports();
+    let cargo_types = get_default_cargo_types();
+    let player = Player::new(1000, "JFK", 200, 1000, 10.0);
+    let known = [
+        prices("JFK", &[("electronics", 400), ("food", 100)], false),
+        prices("ORD", &[("electronics", 600), ("food", 150)], false),
+    ];
+    let routes = RouteAnalytics::rank(
+        &player,
+        &known,
+        &airports,
+        &cargo_types,
+        &DistanceCache::new(),
+    );
+    let matrix = ProfitMatrix::from_routes(&routes);
+
+    assert_eq!(matrix.airports, vec!["JFK", "ORD"]);
+    assert_eq!(matrix.cells[0][0], None);
+    assert_eq!(matrix.cells[1][1], None);
+    let best_out = routes
+        .iter()
+        .filter(|route| route.buy_at == "JFK")
+        .map(|route| route.profit_per_fuel)
+        .fold(f64::MIN, f64::max);
+    assert_eq!(matrix.cells[0][1], Some(best_out));
+    assert!(matrix.cells[1][0].unwrap() < 0.0);
+}
+
+#[test]
+fn test_fog_of_war_limits_routes_to_markets_seen() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Foggy".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                fog_of_war: true,
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+
+    let response = service.get_route_profits(room_id, host_id).unwrap();
+    assert_eq!(response.airport_id, "JFK");
+    assert!(response.routes.is_empty(), "only JFK has been seen");
+
+    let fuel = service
+        .player_buy_fuel(room_id, host_id, FuelRequest { quantity: 50 })
+        .unwrap();
+    assert!(fuel.success, "{}", fuel.message);
+    let travel = service
+        .player_travel(room_id, host_id, "ORD".to_string())
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+
+    let response = service.get_route_profits(room_id, host_id).unwrap();
+    assert_eq!(response.airport_id, "ORD");
+    assert_eq!(response.matrix.airports, vec!["JFK", "ORD"]);
+    assert!(
+        response
+            .routes
+            .iter()
+            .all(|route| [route.buy_at.as_str(), route.sell_at.as_str()].contains(&"JFK"))
+    );
+
+    let clear = service
+        .create_room("Clear".to_string(), "Host".to_string(), None)
+        .unwrap();
+    let response = service
+        .get_route_profits(clear.room_id, clear.host_player_id)
+        .unwrap();
+    assert_eq!(response.matrix.airports.len(), get_default_airports().len());
+}
@@ -1,6 +1,10 @@
 #[cfg(test)]
 mod save_system_tests {
-    use kzrk::systems::{GameState, SaveSystem};
+    use kzrk::config::Difficulty;
+    use kzrk::systems::{
+        AutosavePolicy, AutosaveTimer, GameState, Profile, ProfileSettings, SaveSystem,
+        save::SaveError,
+    };
     use tempfile::tempdir;
 
     #[test]
@@ -122,4 +126,260 @@ mod save_system_tests {
         let result = SaveSystem::load_game_from_dir("nonexistent", &save_dir);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_autosaves_rotate_and_keep_the_newest() {
+        let temp_dir = tempdir().unwrap();
+        let save_dir = temp_dir.path().join("saves");
+
+        let airports = kzrk::data::get_default_airports();
+        let cargo_types = kzrk::data::get_default_cargo_types();
+        let mut game_state = GameState::new(airports, cargo_types);
+
+        for turn in 1..=5 {
+            game_state.turn_number = turn;
+            SaveSystem::autosave_to_dir(&game_state, 3, &save_dir).unwrap();
+        }
+
+        let saves = SaveSystem::list_saves_in_dir(&save_dir).unwrap();
+        assert_eq!(saves.len(), 3);
+        assert!(saves.iter().all(|save| save.is_autosave()));
+
+        // Newest is always plain "autosave", older ones are numbered
+        let turn_of = |name: &str| {
+            SaveSystem::load_game_from_dir(name, &save_dir)
+                .unwrap()
+                .turn_number
+        };
+        assert_eq!(turn_of("autosave"), 5);
+        assert_eq!(turn_of("autosave_1"), 4);
+        assert_eq!(turn_of("autosave_2"), 3);
+    }
+
+    #[test]
+    fn test_newer_autosave_is_offered_over_an_old_save() {
+        let temp_dir = tempdir().unwrap();
+        let save_dir = temp_dir.path().join("saves");
+
+        let airports = kzrk::data::get_default_airports();
+        let cargo_types = kzrk::data::get_default_cargo_types();
+        let mut game_state = GameState::new(airports, cargo_types);
+
+        SaveSystem::save_game_to_dir(&game_state, Some("manual".to_string()), &save_dir).unwrap();
+        let manual = SaveSystem::list_saves_in_dir(&save_dir)
+            .unwrap()
+            .into_iter()
+            .find(|save| save.file_name == "manual")
+            .unwrap();
+        assert!(!manual.is_autosave());
+        assert!(SaveSystem::newer_autosave_in_dir(manual.timestamp, &save_dir).is_none());
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        game_state.turn_number = 7;
+        SaveSystem::autosave_to_dir(&game_state, 3, &save_dir).unwrap();
+
+        let newer = SaveSystem::newer_autosave_in_dir(manual.timestamp, &save_dir).unwrap();
+        assert_eq!(newer.file_name, "autosave");
+        assert_eq!(newer.turn, 7);
+    }
+
+    #[test]
+    fn test_autosave_timer_triggers() {
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let mut timer = AutosaveTimer::new(
+            AutosavePolicy {
+                every_turns: 2,
+                every_minutes: 10,
+                keep: 3,
+            },
+            1,
+        );
+
+        assert!(!timer.is_due(2, start));
+        assert!(timer.is_due(3, start));
+
+        timer.mark_saved(3, start);
+        assert!(!timer.is_due(3, start + Duration::from_secs(60)));
+        assert!(timer.is_due(3, start + Duration::from_secs(10 * 60)));
+
+        // Zero switches a trigger off
+        let turns_only = AutosaveTimer::new(
+            AutosavePolicy {
+                every_turns: 1,
+                every_minutes: 0,
+                keep: 1,
+            },
+            1,
+        );
+        assert!(!turns_only.is_due(1, start + Duration::from_secs(3600)));
+        assert!(turns_only.is_due(2, start));
+    }
+
+    #[test]
+    fn test_create_and_list_profiles() {
+        let temp_dir = tempdir().unwrap();
+        let profiles_dir = temp_dir.path().join("profiles");
+
+        let settings = ProfileSettings {
+            difficulty: Difficulty::Hard,
+            ironman: true,
+        };
+        SaveSystem::create_profile_in_dir("Ironman run", settings.clone(), &profiles_dir).unwrap();
+        SaveSystem::create_profile_in_dir("Casual", ProfileSettings::default(), &profiles_dir)
+            .unwrap();
+
+        let profiles = SaveSystem::list_profiles_in_dir(&profiles_dir).unwrap();
+        assert_eq!(profiles.len(), 2);
+
+        let ironman = SaveSystem::load_profile_in_dir("Ironman run", &profiles_dir).unwrap();
+        assert_eq!(ironman.settings, settings);
+        assert!(
+            profiles_dir
+                .join("ironman-run")
+                .join("profile.json")
+                .exists()
+        );
+
+        // Names that map to the same directory clash
+        assert!(matches!(
+            SaveSystem::create_profile_in_dir("ironman  RUN", settings, &profiles_dir),
+            Err(SaveError::ProfileExists(_))
+        ));
+    }
+
+    #[test]
+    fn test_profile_names_must_be_usable() {
+        let temp_dir = tempdir().unwrap();
+
+        assert_eq!(Profile::slug("  My Casual Game! "), "my-casual-game");
+        assert!(matches!(
+            SaveSystem::create_profile_in_dir("!!!", ProfileSettings::default(), temp_dir.path()),
+            Err(SaveError::InvalidProfileName)
+        ));
+    }
+
+    #[test]
+    fn test_profile_stats_are_recorded() {
+        let temp_dir = tempdir().unwrap();
+        let mut profile =
+            SaveSystem::create_profile_in_dir("Stats", ProfileSettings::default(), temp_dir.path())
+                .unwrap();
+
+        let airports = kzrk::data::get_default_airports();
+        let cargo_types = kzrk::data::get_default_cargo_types();
+        let mut game_state = GameState::new(airports, cargo_types);
+        game_state.turn_number = 12;
+
+        profile.stats.record_game_started();
+        profile.stats.record_game_finished(&game_state, true);
+        SaveSystem::save_profile_in_dir(&profile, temp_dir.path()).unwrap();
+
+        let loaded = SaveSystem::load_profile_in_dir("Stats", temp_dir.path()).unwrap();
+        assert_eq!(loaded.stats.games_started, 1);
+        assert_eq!(loaded.stats.games_won, 1);
+        assert_eq!(loaded.stats.games_lost, 0);
+        assert_eq!(loaded.stats.turns_played, 12);
+        assert_eq!(loaded.stats.best_money, game_state.player.money);
+    }
+
+    #[test]
+    fn test_profiles_keep_their_own_saves() {
+        let temp_dir = tempdir().unwrap();
+        let profiles_dir = temp_dir.path();
+        SaveSystem::create_profile_in_dir("One", ProfileSettings::default(), profiles_dir).unwrap();
+        SaveSystem::create_profile_in_dir("Two", ProfileSettings::default(), profiles_dir).unwrap();
+
+        let one_dir = SaveSystem::profile_directory_in("One", profiles_dir).unwrap();
+        let two_dir = SaveSystem::profile_directory_in("Two", profiles_dir).unwrap();
+
+        let airports = kzrk::data::get_default_airports();
+        let cargo_types = kzrk::data::get_default_cargo_types();
+        let game_state = GameState::new(airports, cargo_types);
+        SaveSystem::autosave_to_dir(&game_state, 3, &one_dir).unwrap();
+
+        assert!(SaveSystem::has_autosave_in_dir(&one_dir));
+        assert!(!SaveSystem::has_autosave_in_dir(&two_dir));
+
+        // The profile file isn't listed as a save
+        let saves = SaveSystem::list_saves_in_dir(&one_dir).unwrap();
+        assert_eq!(saves.len(), 1);
+        assert!(saves[0].is_autosave());
+
+        SaveSystem::delete_profile_in_dir("One", profiles_dir).unwrap();
+        assert!(!one_dir.exists());
+        assert_eq!(
+            SaveSystem::list_profiles_in_dir(profiles_dir)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_ironman_games_only_autosave() {
+        let temp_dir = tempdir().unwrap();
+        let save_dir = temp_dir.path();
+
+        let airports = kzrk::data::get_default_airports();
+        let cargo_types = kzrk::data::get_default_cargo_types();
+        let mut game_state = GameState::new(airports, cargo_types);
+        game_state.stats.ironman = true;
+
+        assert!(matches!(
+            SaveSystem::save_game_to_dir(&game_state, Some("scum".to_string()), save_dir),
+            Err(SaveError::IronmanManualSave)
+        ));
+
+        // Every action overwrites the one autosave; nothing older is kept
+        let mut timer = AutosaveTimer::new(AutosavePolicy::ironman(), game_state.turn_number)
+            .with_save_dir(save_dir.to_path_buf());
+        timer.save_now(&game_state).unwrap();
+        game_state.player.money -= 100;
+        timer.save_now(&game_state).unwrap();
+
+        let saves = SaveSystem::list_saves_in_dir(save_dir).unwrap();
+        assert_eq!(saves.len(), 1);
+        assert!(saves[0].ironman);
+        assert_eq!(saves[0].money, game_state.player.money);
+
+        let loaded = SaveSystem::load_game_from_dir("autosave", save_dir).unwrap();
+        assert!(loaded.stats.ironman);
+    }
+
+    #[test]
+    fn test_bankrupt_ironman_save_is_deleted() {
+        let temp_dir = tempdir().unwrap();
+        let save_dir = temp_dir.path();
+
+        let airports = kzrk::data::get_default_airports();
+        let cargo_types = kzrk::data::get_default_cargo_types();
+        let game_state = GameState::new(airports, cargo_types);
+
+        // Another game's saves share the directory
+        for _ in 0..2 {
+            SaveSystem::autosave_to_dir(&game_state, 3, save_dir).unwrap();
+        }
+        SaveSystem::save_game_to_dir(&game_state, Some("other".to_string()), save_dir).unwrap();
+
+        let mut ironman = game_state.clone();
+        ironman.stats.ironman = true;
+        let mut timer = AutosaveTimer::new(AutosavePolicy::ironman(), ironman.turn_number)
+            .with_save_dir(save_dir.to_path_buf());
+        assert_eq!(timer.last_save(), None);
+        let path = timer.save_now(&ironman).unwrap();
+        assert_eq!(timer.last_save(), Some(path.as_path()));
+        assert_eq!(SaveSystem::list_saves_in_dir(save_dir).unwrap().len(), 3);
+
+        SaveSystem::delete_save_file(timer.last_save().unwrap()).unwrap();
+        assert!(!path.exists());
+        let saves = SaveSystem::list_saves_in_dir(save_dir).unwrap();
+        assert_eq!(saves.len(), 2);
+        assert!(saves.iter().all(|save| !save.ironman));
+        assert!(matches!(
+            SaveSystem::delete_save_file(&path),
+            Err(SaveError::SaveNotFound)
+        ));
+    }
 }
@@ -0,0 +1,100 @@
+use std::path::Path;
+
+use kzrk::api::{multiplayer_service::MultiplayerGameService, scenario_server::ServerTarget};
+use kzrk::systems::scenario::{self, LocalTarget, Outcome, Scenario, Snapshot};
+
+fn fixtures() -> Vec<Scenario> {
+    let mut files: Vec<_> = std::fs::read_dir(Path::new("tests/scenarios"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    files.sort();
+    files
+        .iter()
+        .map(|file| Scenario::load(file).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_fixtures_pass_on_a_local_game() {
+    for scenario in fixtures() {
+        let report = scenario::run(&scenario, &mut LocalTarget::new()).unwrap();
+        assert!(report.passed(), "{:?}", report);
+        assert_eq!(report.steps_run, scenario.steps.len());
+    }
+}
+
+#[test]
+fn test_fixtures_pass_against_a_live_server() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let listener = runtime
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .unwrap();
+    let address = listener.local_addr().unwrap();
+    let app = kzrk::api::routes::create_multiplayer_router(MultiplayerGameService::new_in_memory());
+    runtime.spawn(async move { axum::serve(listener, app).await });
+
+    for scenario in fixtures() {
+        let mut target = ServerTarget::new(&address.to_string());
+        let report = scenario::run(&scenario, &mut target).unwrap();
+        assert!(report.passed(), "{:?}", report);
+    }
+}
+
+#[test]
+fn test_a_broken_expectation_stops_the_run_at_its_step() {
+    let scenario = Scenario::from_json(
+        r#"{
+            "name": "Wrong turn",
+            "steps": [
+                { "action": "check", "expect": { "location": "JFK", "turn": 1 } },
+                { "action": { "buy": { "cargo": "food", "quantity": 1 } }, "expect": { "turn": 2 } },
+                { "action": "check" }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let report = scenario::run(&scenario, &mut LocalTarget::new()).unwrap();
+    let failure = report.failure.unwrap();
+    assert_eq!(failure.step, 2);
+    assert_eq!(failure.problems, vec!["on turn 1, not 2".to_string()]);
+    assert_eq!(report.steps_run, 2);
+}
+
+#[test]
+fn test_refusals_are_checked_against_what_was_expected() {
+    let expect = Scenario::from_json(
+        r#"{ "name": "", "steps": [
+            { "action": "check", "expect": { "fails": true, "error_contains": "fuel" } }
+        ] }"#,
+    )
+    .unwrap()
+    .steps[0]
+        .expect
+        .clone();
+    let snapshot = Snapshot::default();
+
+    assert!(
+        scenario::check(
+            &expect,
+            &Outcome::Refused("Not enough fuel".to_string()),
+            &snapshot
+        )
+        .is_empty()
+    );
+    assert_eq!(
+        scenario::check(&expect, &Outcome::Done, &snapshot),
+        vec!["expected the action to be refused".to_string()]
+    );
+    assert_eq!(
+        scenario::check(&expect, &Outcome::Refused("No".to_string()), &snapshot).len(),
+        1
+    );
+    assert!(
+        Scenario::from_json(
+            r#"{ "name": "", "steps": [{ "action": "check", "expect": { "colour": 1 } }] }"#
+        )
+        .is_err()
+    );
+}
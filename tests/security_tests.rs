@@ -0,0 +1,195 @@
+use rand::{SeedableRng, rngs::StdRng};
+use uuid::Uuid;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::{get_default_airports, get_default_cargo_types};
+use kzrk::models::{Player, SecurityRating};
+use kzrk::systems::{
+    GameRoom,
+    insurance::CargoLossCause,
+    security::{HANGAR_FEE, Night, SECURITY_UPGRADE_COST, SecuritySystem, Stolen},
+};
+
+const NIGHTS: usize = 200;
+
+fn player_at(airport: &str) -> Player {
+    let mut player = Player::new(10000, airport, 200, 1000, 15.0);
+    player.cargo_inventory.add_cargo("electronics", 20);
+    player
+}
+
+#[test]
+fn test_only_low_security_airports_see_theft() {
+    let airports = get_default_airports();
+    let cargo_types = get_default_cargo_types();
+    assert_eq!(airports["MIA"].security, SecurityRating::Low);
+    assert_eq!(airports["JFK"].security, SecurityRating::Standard);
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut safe = player_at("JFK");
+    for _ in 0..NIGHTS {
+        let night =
+            SecuritySystem::spend_night(&mut safe, &airports["JFK"], &cargo_types, &mut rng);
+        assert_eq!(night, Night::Quiet);
+    }
+    assert_eq!(safe.fuel, 133);
+    assert_eq!(safe.cargo_inventory.get_quantity("electronics"), 20);
+
+    let mut exposed = player_at("MIA");
+    let mut robberies = 0;
+    for _ in 0..NIGHTS {
+        let fuel = exposed.fuel;
+        let carried = exposed.cargo_inventory.get_quantity("electronics");
+        match SecuritySystem::spend_night(&mut exposed, &airports["MIA"], &cargo_types, &mut rng) {
+            Night::Robbed(loss) => {
+                robberies += 1;
+                assert_eq!(loss.airport_id, "MIA");
+                match loss.stolen {
+                    Stolen::Fuel { quantity } => assert_eq!(exposed.fuel, fuel - quantity),
+                    Stolen::Cargo { quantity, .. } => assert_eq!(
+                        exposed.cargo_inventory.get_quantity("electronics"),
+                        carried - quantity
+                    ),
+                }
+            },
+            night => assert_eq!(night, Night::Quiet),
+        }
+    }
+    assert!(robberies > 0);
+}
+
+#[test]
+fn test_hangar_parking_keeps_the_aircraft_safe_while_it_can_be_paid_for() {
+    let airports = get_default_airports();
+    let cargo_types = get_default_cargo_types();
+    let mut rng = StdRng::seed_from_u64(5);
+
+    let mut player = player_at("MIA");
+    player.hangar_parking = true;
+    player.money = HANGAR_FEE * 3;
+    for _ in 0..3 {
+        let night =
+            SecuritySystem::spend_night(&mut player, &airports["MIA"], &cargo_types, &mut rng);
+        assert_eq!(night, Night::Hangar { fee: HANGAR_FEE });
+        assert_eq!(night.report().unwrap(), "Hangared overnight for $120");
+    }
+    assert_eq!(player.money, 0);
+    assert_eq!(player.cargo_inventory.get_quantity("electronics"), 20);
+
+    // Broke pilots park outside and take their chances
+    let night = SecuritySystem::spend_night(&mut player, &airports["MIA"], &cargo_types, &mut rng);
+    assert_ne!(night, Night::Hangar { fee: HANGAR_FEE });
+}
+
+#[test]
+fn test_security_upgrade_cuts_the_risk_once_bought() {
+    let airports = get_default_airports();
+    let mut player = player_at("MIA");
+    let risk = SecuritySystem::theft_chance(&player, &airports["MIA"]);
+
+    assert_eq!(
+        SecuritySystem::buy_security_upgrade(&mut player),
+        Ok(SECURITY_UPGRADE_COST)
+    );
+    assert_eq!(player.money, 10000 - SECURITY_UPGRADE_COST);
+    assert!(SecuritySystem::theft_chance(&player, &airports["MIA"]) < risk);
+    assert!(SecuritySystem::buy_security_upgrade(&mut player).is_err());
+
+    let mut broke = player_at("MIA");
+    broke.money = SECURITY_UPGRADE_COST - 1;
+    assert!(SecuritySystem::buy_security_upgrade(&mut broke).is_err());
+    assert!(!broke.security_upgrade);
+}
+
+fn room_at_mia() -> (GameRoom, Uuid) {
+    let host = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Night Shift".to_string(),
+        host,
+        "Host".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    let player = &mut room.get_player_mut(&host).unwrap().player;
+    player.current_airport = "MIA".to_string();
+    player.cargo_inventory.add_cargo("electronics", 20);
+    (room, host)
+}
+
+#[test]
+fn test_cargo_stolen_in_a_room_is_recorded_for_insurance() {
+    let (mut room, host) = room_at_mia();
+    room.get_player_mut(&host).unwrap().insurance.insured = true;
+    let mut rng = StdRng::seed_from_u64(11);
+
+    let theft = (0..NIGHTS)
+        .map(|_| room.spend_night(&host, &mut rng))
+        .find_map(|night| match night {
+            Night::Robbed(loss) if matches!(loss.stolen, Stolen::Cargo { .. }) => Some(loss),
+            _ => None,
+        })
+        .expect("no cargo was stolen");
+
+    let losses = &room.get_player(&host).unwrap().insurance.losses;
+    let recorded = losses
+        .iter()
+        .find(|loss| loss.description == theft.description)
+        .unwrap();
+    assert_eq!(recorded.cause, CargoLossCause::Theft);
+    assert_eq!(recorded.airport_id, "MIA");
+    assert!(recorded.insured);
+    assert!(recorded.value > 0);
+}
+
+#[test]
+fn test_rooms_can_turn_overnight_theft_off() {
+    let (mut room, host) = room_at_mia();
+    room.settings.overnight_theft = false;
+    let mut rng = StdRng::seed_from_u64(11);
+
+    for _ in 0..NIGHTS {
+        assert_eq!(room.spend_night(&host, &mut rng), Night::Quiet);
+    }
+    let player_state = room.get_player(&host).unwrap();
+    assert_eq!(
+        player_state
+            .player
+            .cargo_inventory
+            .get_quantity("electronics"),
+        20
+    );
+    assert!(player_state.insurance.losses.is_empty());
+}
+
+#[test]
+fn test_service_hangar_and_upgrade() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Security".to_string(), "Host".to_string(), Some(2))
+        .unwrap();
+    let (room_id, host) = (room.room_id, room.host_player_id);
+
+    let security = service.get_security(room_id, host).unwrap();
+    assert_eq!(security.airport_id, "JFK");
+    assert_eq!(security.airport_security, SecurityRating::Standard);
+    assert_eq!(security.theft_chance, 0.0);
+    assert!(!security.hangar_parking);
+
+    let security = service.set_hangar_parking(room_id, host, true).unwrap();
+    assert!(security.hangar_parking);
+    assert_eq!(security.hangar_fee, HANGAR_FEE);
+
+    let security = service.buy_security_upgrade(room_id, host).unwrap();
+    assert!(security.security_upgrade);
+    let error = service.buy_security_upgrade(room_id, host).unwrap_err();
+    assert!(error.to_string().contains("already"), "{}", error);
+
+    let state = service.get_room_state(room_id, host).unwrap();
+    let me = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(host))
+        .unwrap();
+    assert_eq!(me.money, 5000 - SECURITY_UPGRADE_COST);
+}
@@ -0,0 +1,121 @@
+use uuid::Uuid;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::systems::{RoomSettings, multiplayer::SpawnRule};
+
+/// A room whose spawn rule would put everyone at Denver
+fn denver_room(service: &MultiplayerGameService) -> Uuid {
+    service
+        .create_room_with_settings(
+            "Spawn Point".to_string(),
+            "Host".to_string(),
+            Some(4),
+            RoomSettings {
+                spawn: SpawnRule::HostChosen("DEN".to_string()),
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap()
+        .room_id
+}
+
+fn airport_of(service: &MultiplayerGameService, room_id: Uuid, player_id: Uuid) -> String {
+    service
+        .get_room_state(room_id, player_id)
+        .unwrap()
+        .current_market
+        .airport_id
+}
+
+#[test]
+fn test_joining_players_start_where_they_ask() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room_id = denver_room(&service);
+
+    let joined = service
+        .join_room(room_id, "Picky".to_string(), Some("ORD".to_string()))
+        .unwrap();
+    assert_eq!(joined.starting_airport, "ORD");
+    assert_eq!(airport_of(&service, room_id, joined.player_id), "ORD");
+}
+
+#[test]
+fn test_a_blank_choice_leaves_it_to_the_room() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room_id = denver_room(&service);
+
+    for (name, airport) in [("Unsure", None), ("Blank", Some("  ".to_string()))] {
+        let joined = service
+            .join_room(room_id, name.to_string(), airport)
+            .unwrap();
+        assert_eq!(joined.starting_airport, "DEN");
+    }
+
+    let padded = service
+        .join_room(room_id, "Padded".to_string(), Some(" LAX ".to_string()))
+        .unwrap();
+    assert_eq!(padded.starting_airport, "LAX");
+}
+
+#[test]
+fn test_airports_outside_the_room_are_refused() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room_id = denver_room(&service);
+
+    let err = service
+        .join_room(room_id, "Lost".to_string(), Some("XXX".to_string()))
+        .unwrap_err();
+    assert!(err.to_string().contains("not part of this room"), "{}", err);
+}
+
+#[test]
+fn test_rejoining_reports_where_the_pilot_left_off() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room_id = denver_room(&service);
+    let joined = service
+        .join_room(room_id, "Wanderer".to_string(), Some("ORD".to_string()))
+        .unwrap();
+    service
+        .leave_room(room_id, joined.player_id, false)
+        .unwrap();
+
+    let rejoined = service
+        .join_room(room_id, "Wanderer".to_string(), Some("LAX".to_string()))
+        .unwrap();
+    assert_eq!(rejoined.player_id, joined.player_id);
+    assert_eq!(rejoined.starting_airport, "ORD");
+}
+
+#[cfg(feature = "gui")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_gui_client_joins_at_the_chosen_airport() {
+    use std::time::Duration;
+
+    use kzrk::api::routes::create_multiplayer_router;
+    use kzrk::ui::game_api_client::{ApiError, GameApiClient};
+
+    let service = MultiplayerGameService::new_in_memory();
+    let room_id = denver_room(&service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let app = create_multiplayer_router(service.clone());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = GameApiClient::new(base_url);
+    let airports = client.get_airports_sync().unwrap();
+    assert!(airports.iter().any(|airport| airport.id == "MIA"));
+
+    let joined = client
+        .join_room_sync(room_id, "Sunny".to_string(), Some("MIA".to_string()))
+        .unwrap();
+    assert_eq!(joined.starting_airport, "MIA");
+    assert_eq!(airport_of(&service, room_id, joined.player_id), "MIA");
+
+    match client.join_room_sync(room_id, "Lost".to_string(), Some("XXX".to_string())) {
+        Err(ApiError::ServerError(message)) => {
+            assert!(message.contains("not part of this room"), "{}", message)
+        },
+        other => panic!("expected the airport to be refused, got {:?}", other),
+    }
+}
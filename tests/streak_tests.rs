@@ -0,0 +1,84 @@
+use kzrk::config::GameConfig;
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::models::{Market, Player, streak::MAX_STREAK_BONUS_PERCENT};
+use kzrk::systems::{GameState, TradingSystem};
+
+fn market(price: u32) -> Market {
+    let mut market = Market::new("JFK", 50);
+    market.set_cargo_price("electronics", price);
+    market
+}
+
+fn trade(player: &mut Player, buy_at: u32, sell_at: u32) -> u32 {
+    let cargo_types = get_default_cargo_types();
+    TradingSystem::buy_cargo(player, &market(buy_at), &cargo_types, "electronics", 10).unwrap();
+    TradingSystem::sell_cargo(player, &market(sell_at), "electronics", 10)
+        .unwrap()
+        .bonus
+}
+
+#[test]
+fn test_profitable_sales_build_a_capped_bonus() {
+    let mut player = Player::new(100_000, "JFK", 200, 1000, 10.0);
+
+    // The first sale earns no bonus; each one after pays a point more
+    assert_eq!(trade(&mut player, 100, 200), 0);
+    assert_eq!(player.streak.current, 1);
+    assert_eq!(trade(&mut player, 100, 200), 20);
+    assert_eq!(trade(&mut player, 100, 200), 40);
+
+    for _ in 0..MAX_STREAK_BONUS_PERCENT {
+        trade(&mut player, 100, 200);
+    }
+    assert_eq!(player.streak.bonus_percent(), MAX_STREAK_BONUS_PERCENT);
+    assert_eq!(trade(&mut player, 100, 200), 100);
+}
+
+#[test]
+fn test_a_sale_at_a_loss_breaks_the_streak() {
+    let mut player = Player::new(100_000, "JFK", 200, 1000, 10.0);
+    trade(&mut player, 100, 200);
+    trade(&mut player, 100, 200);
+    trade(&mut player, 100, 200);
+    assert_eq!(player.streak.current, 3);
+
+    trade(&mut player, 200, 100);
+    assert_eq!(player.streak.current, 0);
+    assert_eq!(player.streak.best, 3);
+    assert_eq!(player.streak.bonus_percent(), 0);
+}
+
+#[test]
+fn test_proceeds_include_the_bonus() {
+    let mut player = Player::new(100_000, "JFK", 200, 1000, 10.0);
+    player.streak.current = 2;
+    player.cargo_inventory.add_cargo("electronics", 10);
+
+    let quote = TradingSystem::quote_sale(&player, &market(300), "electronics", 10).unwrap();
+    assert_eq!(quote.gross, 3000);
+    assert_eq!(quote.bonus, 60);
+    assert_eq!(quote.proceeds(), 3000 - quote.fee + 60);
+}
+
+#[test]
+fn test_best_streak_is_kept_in_game_stats() {
+    let config = GameConfig {
+        starting_money: 100_000,
+        ..GameConfig::default()
+    };
+    let mut game_state =
+        GameState::new_with_config(get_default_airports(), get_default_cargo_types(), config);
+    let airport = game_state.player.current_airport.clone();
+    game_state.markets.insert(airport.clone(), market(100));
+    game_state.buy_cargo("electronics", 10).unwrap();
+    game_state
+        .markets
+        .get_mut(&airport)
+        .unwrap()
+        .set_cargo_price("electronics", 200);
+    game_state.sell_cargo("electronics", 5).unwrap();
+    game_state.sell_cargo("electronics", 5).unwrap();
+
+    assert_eq!(game_state.player.streak.current, 2);
+    assert_eq!(game_state.stats.best_streak, 2);
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "tls")]
+
+use std::{net::SocketAddr, path::Path, time::Duration};
+
+use kzrk::api::{
+    multiplayer_service::MultiplayerGameService, routes::create_multiplayer_router, tls::TlsConfig,
+};
+use reqwest::{Certificate, Client, StatusCode, redirect::Policy};
+use tempfile::TempDir;
+use tokio::time::sleep;
+
+/// Writes a self-signed certificate for `hostname`, returning the cert path,
+/// key path and the certificate PEM
+fn write_certificate(dir: &Path, hostname: &str) -> (String, String, String) {
+    let generated = rcgen::generate_simple_self_signed(vec![hostname.to_string()]).unwrap();
+    let cert_pem = generated.cert.pem();
+    let cert_path = dir.join(format!("{}.pem", hostname));
+    let key_path = dir.join(format!("{}.key", hostname));
+    std::fs::write(&cert_path, &cert_pem).unwrap();
+    std::fs::write(&key_path, generated.key_pair.serialize_pem()).unwrap();
+    (
+        cert_path.to_str().unwrap().to_string(),
+        key_path.to_str().unwrap().to_string(),
+        cert_pem,
+    )
+}
+
+fn free_addr() -> SocketAddr {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap()
+}
+
+async fn start(config: TlsConfig) -> SocketAddr {
+    let addr = free_addr();
+    let app = create_multiplayer_router(MultiplayerGameService::new());
+    tokio::spawn(async move {
+        kzrk::api::tls::serve(app, addr, &config)
+            .await
+            .expect("Failed to start HTTPS server");
+    });
+    sleep(Duration::from_millis(200)).await;
+    addr
+}
+
+fn trusting(cert_pem: &str, hostname: &str, addr: SocketAddr) -> Client {
+    Client::builder()
+        .add_root_certificate(Certificate::from_pem(cert_pem.as_bytes()).unwrap())
+        .resolve(hostname, addr)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_serves_https_with_sni_certificates() {
+    let dir = TempDir::new().unwrap();
+    let (cert, key, default_pem) = write_certificate(dir.path(), "localhost");
+    let (play_cert, play_key, play_pem) = write_certificate(dir.path(), "play.kzrk.test");
+    let addr = start(TlsConfig::new(cert, key).with_sni_certificate(
+        "play.kzrk.test",
+        play_cert,
+        play_key,
+    ))
+    .await;
+
+    let response = trusting(&default_pem, "localhost", addr)
+        .get(format!("https://localhost:{}/health", addr.port()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The second hostname gets its own certificate...
+    let response = trusting(&play_pem, "play.kzrk.test", addr)
+        .get(format!("https://play.kzrk.test:{}/health", addr.port()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // ...and not the default one
+    let mismatched = trusting(&default_pem, "play.kzrk.test", addr)
+        .get(format!("https://play.kzrk.test:{}/health", addr.port()))
+        .send()
+        .await;
+    assert!(mismatched.is_err());
+}
+
+#[tokio::test]
+async fn test_plain_http_is_redirected_to_https() {
+    let dir = TempDir::new().unwrap();
+    let (cert, key, _) = write_certificate(dir.path(), "localhost");
+    let redirect_addr = free_addr();
+    let addr = start(TlsConfig::new(cert, key).with_http_redirect(redirect_addr.to_string())).await;
+
+    let client = Client::builder().redirect(Policy::none()).build().unwrap();
+    let response = client
+        .post(format!(
+            "http://localhost:{}/rooms?x=1",
+            redirect_addr.port()
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(
+        response.headers()["location"],
+        format!("https://localhost:{}/rooms?x=1", addr.port()).as_str()
+    );
+}
+
+#[test]
+fn test_missing_certificate_is_reported() {
+    let config = TlsConfig::new("/nonexistent/cert.pem", "/nonexistent/key.pem");
+    let error = config.server_config().unwrap_err();
+    assert!(error.contains("/nonexistent/cert.pem"));
+}
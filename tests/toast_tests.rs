@@ -0,0 +1,67 @@
+#[cfg(feature = "gui")]
+mod toasts {
+    use std::time::{Duration, Instant};
+
+    use kzrk::ui::toasts::{
+        ERROR_TOAST_DURATION, MAX_VISIBLE_TOASTS, SUCCESS_TOAST_DURATION, ToastKind, Toasts,
+    };
+
+    fn messages(toasts: &Toasts) -> Vec<&str> {
+        toasts
+            .visible()
+            .map(|toast| toast.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_toasts_come_down_on_their_own() {
+        let start = Instant::now();
+        let mut toasts = Toasts::new();
+        toasts.push(ToastKind::Success, "Bought 5 food");
+        toasts.push(ToastKind::Error, "Not enough money");
+        toasts.tick(start);
+        assert_eq!(messages(&toasts), ["Bought 5 food", "Not enough money"]);
+        assert_eq!(toasts.next_expiry(start), Some(SUCCESS_TOAST_DURATION));
+
+        // Errors stay up longer than successes
+        toasts.tick(start + SUCCESS_TOAST_DURATION);
+        assert_eq!(messages(&toasts), ["Not enough money"]);
+        assert_eq!(
+            toasts.next_expiry(start + SUCCESS_TOAST_DURATION),
+            Some(ERROR_TOAST_DURATION - SUCCESS_TOAST_DURATION)
+        );
+
+        toasts.tick(start + ERROR_TOAST_DURATION);
+        assert!(toasts.is_empty());
+        assert_eq!(toasts.next_expiry(start + ERROR_TOAST_DURATION), None);
+    }
+
+    #[test]
+    fn test_toasts_past_the_limit_wait_their_turn() {
+        let start = Instant::now();
+        let mut toasts = Toasts::new();
+        let first = toasts.push(ToastKind::Success, "0");
+        for n in 1..=MAX_VISIBLE_TOASTS {
+            toasts.push(ToastKind::Success, n.to_string());
+        }
+        // Not on screen until the first tick
+        assert_eq!(toasts.visible().count(), 0);
+
+        toasts.tick(start);
+        assert_eq!(toasts.visible().count(), MAX_VISIBLE_TOASTS);
+        assert_eq!(toasts.waiting(), 1);
+
+        // Dismissing one makes room, and the newcomer gets its full time
+        let later = start + Duration::from_secs(1);
+        assert!(toasts.dismiss(first));
+        assert!(!toasts.dismiss(first));
+        toasts.tick(later);
+        assert_eq!(toasts.waiting(), 0);
+        let last = toasts.visible().last().unwrap();
+        assert_eq!(last.message, MAX_VISIBLE_TOASTS.to_string());
+        assert_eq!(last.remaining(later), Some(SUCCESS_TOAST_DURATION));
+
+        toasts.clear();
+        assert!(toasts.is_empty());
+    }
+}
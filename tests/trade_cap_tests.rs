@@ -0,0 +1,177 @@
+use kzrk::api::{
+    models::{TradeAction, TradeRequest},
+    multiplayer_service::MultiplayerGameService,
+};
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::models::locale::{Locale, MessageCode};
+use kzrk::systems::{
+    GameState, RoomSettings,
+    multiplayer::SpawnRule,
+    trading::{BROKER_TIERS, BrokerTier, TradingError, TradingSystem},
+};
+
+fn game() -> GameState {
+    let mut game_state = GameState::new(get_default_airports(), get_default_cargo_types());
+    game_state.cheat_mode = false;
+    game_state.player.current_airport = "JFK".to_string();
+    game_state.player.money = 1_000_000;
+    game_state.player.max_cargo_weight = 10_000;
+    game_state
+}
+
+/// A pilot already at the top broker tier at JFK, so trading can't earn a
+/// better one and raise the cap partway through a test
+fn partner_game() -> GameState {
+    let mut game_state = game();
+    game_state
+        .player
+        .record_broker_volume("JFK", partner().min_volume);
+    game_state
+}
+
+fn partner() -> BrokerTier {
+    BROKER_TIERS[BROKER_TIERS.len() - 1]
+}
+
+#[test]
+fn test_the_broker_stops_buying_at_the_turn_cap() {
+    let mut game_state = partner_game();
+    let cap = partner().units_per_turn;
+
+    game_state.buy_cargo("materials", cap - 10).unwrap();
+    match game_state.buy_cargo("materials", 20) {
+        Err(TradingError::TurnCapReached { remaining }) => assert_eq!(remaining, 10),
+        other => panic!("expected the turn cap, got {:?}", other.map(|c| c.result)),
+    }
+    game_state.buy_cargo("materials", 10).unwrap();
+    assert_eq!(
+        game_state.player.cargo_inventory.get_quantity("materials"),
+        cap
+    );
+
+    // Other cargo has a cap of its own
+    game_state.buy_cargo("food", 10).unwrap();
+}
+
+#[test]
+fn test_sales_are_capped_apart_from_purchases() {
+    let mut game_state = partner_game();
+    let cap = partner().units_per_turn;
+    game_state
+        .player
+        .cargo_inventory
+        .add_cargo("materials", cap + 50);
+
+    game_state.sell_cargo("materials", cap).unwrap();
+    assert!(matches!(
+        game_state.sell_cargo("materials", 1),
+        Err(TradingError::TurnCapReached { remaining: 0 })
+    ));
+    game_state.buy_cargo("materials", 10).unwrap();
+}
+
+#[test]
+fn test_a_new_turn_resets_the_caps() {
+    let mut game_state = partner_game();
+    let cap = partner().units_per_turn;
+
+    game_state.buy_cargo("materials", cap).unwrap();
+    assert!(game_state.buy_cargo("materials", 1).is_err());
+
+    game_state.advance_turn();
+    game_state.buy_cargo("materials", cap).unwrap();
+}
+
+#[test]
+fn test_better_broker_tiers_handle_more_per_turn() {
+    let walk_in = BROKER_TIERS[0].units_per_turn;
+    assert!(partner().units_per_turn > walk_in);
+
+    let mut game_state = game();
+    assert!(matches!(
+        game_state.buy_cargo("electronics", walk_in + 1),
+        Err(TradingError::TurnCapReached { remaining }) if remaining == walk_in
+    ));
+
+    partner_game()
+        .buy_cargo("electronics", partner().units_per_turn)
+        .unwrap();
+}
+
+#[test]
+fn test_max_buyable_stops_at_the_cap() {
+    let mut game_state = partner_game();
+    let cap = partner().units_per_turn;
+    let market = game_state.get_current_market().unwrap().clone();
+    let max = TradingSystem::get_max_buyable_quantity(
+        &game_state.player,
+        &market,
+        &game_state.cargo_types,
+        "materials",
+    );
+    assert_eq!(max, cap);
+
+    game_state.buy_cargo("materials", 40).unwrap();
+    let max = TradingSystem::get_max_buyable_quantity(
+        &game_state.player,
+        &market,
+        &game_state.cargo_types,
+        "materials",
+    );
+    assert_eq!(max, cap - 40);
+}
+
+#[test]
+fn test_the_cap_message_has_a_code_and_translates() {
+    let localized = TradingError::TurnCapReached { remaining: 12 }.localized();
+    assert_eq!(localized.code, MessageCode::TurnCapReached);
+    assert_eq!(localized.params["remaining"], "12");
+    assert!(localized.render(Locale::Spanish).contains("12 unidades"));
+}
+
+#[test]
+fn test_room_state_shows_what_is_left_of_the_caps() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Quotas".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                spawn: SpawnRule::HostChosen("JFK".to_string()),
+                landing_slots: false,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+    let cap = BROKER_TIERS[0].units_per_turn;
+
+    let trade = service
+        .player_trade(
+            room_id,
+            host_id,
+            TradeRequest {
+                cargo_type: "materials".to_string(),
+                quantity: 10,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(trade.success, "{}", trade.message);
+
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    let broker = state.current_market.broker.unwrap();
+    assert_eq!(broker.units_per_turn, Some(cap));
+    assert_eq!(broker.buys_left["materials"], cap - 10);
+    assert_eq!(broker.sales_left["materials"], cap);
+    assert_eq!(broker.buys_left["food"], cap);
+
+    let travel = service
+        .player_travel(room_id, host_id, "ORD".to_string())
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    let broker = state.current_market.broker.unwrap();
+    assert_eq!(broker.buys_left["materials"], cap);
+}
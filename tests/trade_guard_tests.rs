@@ -0,0 +1,164 @@
+use uuid::Uuid;
+
+use kzrk::api::{
+    models::{TradeAction, TradeRequest},
+    multiplayer_service::MultiplayerGameService,
+};
+use kzrk::models::{Market, Player, locale::MessageCode};
+use kzrk::systems::{
+    RoomSettings,
+    events::EventConfig,
+    multiplayer::{STARTING_MAX_FUEL, SpawnRule},
+    trade_guards::TradeGuards,
+    trading::{TradingError, TradingSystem},
+};
+
+fn guarded_settings(trade_guards: TradeGuards) -> RoomSettings {
+    RoomSettings {
+        spawn: SpawnRule::HostChosen("JFK".to_string()),
+        starting_fuel: Some(STARTING_MAX_FUEL),
+        events: EventConfig {
+            chance: 0.0,
+            ..EventConfig::default()
+        },
+        cargo_loss_chance: 0.0,
+        fuel_outage_chance: 0.0,
+        landing_slots: false,
+        trade_guards,
+        ..RoomSettings::default()
+    }
+}
+
+fn guarded_room(trade_guards: TradeGuards) -> (MultiplayerGameService, Uuid, Uuid) {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Wash Trading".to_string(),
+            "Host".to_string(),
+            None,
+            guarded_settings(trade_guards),
+        )
+        .unwrap();
+    (service, room.room_id, room.host_player_id)
+}
+
+fn trade(action: TradeAction, quantity: u32) -> TradeRequest {
+    TradeRequest {
+        cargo_type: "food".to_string(),
+        quantity,
+        action,
+    }
+}
+
+#[test]
+fn test_cargo_cant_be_sold_back_where_it_was_just_bought() {
+    let (service, room_id, host) = guarded_room(TradeGuards {
+        sell_spread_percent: 0,
+        resale_cooldown_turns: 2,
+    });
+
+    let bought = service
+        .player_trade(room_id, host, trade(TradeAction::Buy, 10))
+        .unwrap();
+    assert!(bought.success, "{}", bought.message);
+
+    let refused = service
+        .player_trade(room_id, host, trade(TradeAction::Sell, 1))
+        .unwrap();
+    assert!(!refused.success);
+    assert_eq!(refused.localized.unwrap().code, MessageCode::ResaleCooldown);
+    let state = service.get_room_state(room_id, host).unwrap();
+    let broker = state.current_market.broker.unwrap();
+    assert_eq!(broker.resale_locked["food"], 10);
+
+    // A round trip takes two turns, and the food can go back on sale
+    for destination in ["ORD", "JFK"] {
+        let response = service
+            .player_travel(room_id, host, destination.to_string())
+            .unwrap();
+        assert!(response.success, "{}", response.message);
+    }
+    service
+        .player_trade(room_id, host, trade(TradeAction::Buy, 5))
+        .unwrap();
+    let sold = service
+        .player_trade(room_id, host, trade(TradeAction::Sell, 10))
+        .unwrap();
+    assert!(sold.success, "{}", sold.message);
+
+    // The 5 just bought are still held back
+    let refused = service
+        .player_trade(room_id, host, trade(TradeAction::Sell, 5))
+        .unwrap();
+    assert!(!refused.success);
+    let localized = refused.localized.unwrap();
+    assert_eq!(refused.message, localized.to_string());
+    assert_eq!(localized.params["sellable"], "0");
+    assert_eq!(localized.params["turns"], "2");
+}
+
+#[test]
+fn test_without_a_cooldown_cargo_sells_back_at_once() {
+    let (service, room_id, host) = guarded_room(TradeGuards::default());
+    service
+        .player_trade(room_id, host, trade(TradeAction::Buy, 10))
+        .unwrap();
+    let sold = service
+        .player_trade(room_id, host, trade(TradeAction::Sell, 10))
+        .unwrap();
+    assert!(sold.success, "{}", sold.message);
+
+    let state = service.get_room_state(room_id, host).unwrap();
+    let broker = state.current_market.broker.unwrap();
+    assert_eq!(broker.sell_spread_percent, 0);
+    assert!(broker.resale_locked.is_empty());
+}
+
+#[test]
+fn test_the_sell_spread_takes_its_cut_of_every_sale() {
+    let mut market = Market::new("JFK", 50);
+    market.set_cargo_price("food", 100);
+    let mut player = Player::new(5000, "JFK", 150, 1000, 15.0);
+    player.cargo_inventory.add_cargo("food", 20);
+
+    assert_eq!(TradingSystem::sell_price(100, 10), 90);
+    let list = TradingSystem::sell_cargo(&mut player, &market, "food", 10).unwrap();
+    let spread = TradingSystem::sell_cargo_at_spread(&mut player, &market, "food", 10, 10).unwrap();
+    assert_eq!(list.gross, 1000);
+    assert_eq!(spread.gross, 900);
+    assert_eq!(player.cargo_inventory.get_quantity("food"), 0);
+}
+
+#[test]
+fn test_trade_guards_are_checked() {
+    let service = MultiplayerGameService::new_in_memory();
+    let create = |trade_guards: TradeGuards| {
+        service.create_room_with_settings(
+            "Wash Trading".to_string(),
+            "Host".to_string(),
+            None,
+            guarded_settings(trade_guards),
+        )
+    };
+    assert!(
+        create(TradeGuards {
+            sell_spread_percent: 51,
+            ..TradeGuards::default()
+        })
+        .is_err()
+    );
+    assert!(
+        create(TradeGuards {
+            resale_cooldown_turns: 21,
+            ..TradeGuards::default()
+        })
+        .is_err()
+    );
+
+    let error = TradingError::ResaleCooldown {
+        sellable: 3,
+        turns: 1,
+    };
+    assert_eq!(error.localized().code, MessageCode::ResaleCooldown);
+    assert_eq!(error.to_string(), error.localized().to_string());
+}
@@ -0,0 +1,196 @@
+use uuid::Uuid;
+
+use kzrk::api::errors::{ErrorCode, ServiceError};
+use kzrk::api::models::{FuelRequest, TradeAction, TradeRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::models::locale::{Locale, MessageCode};
+use kzrk::systems::{RoomSettings, trading::BROKER_TIERS, transfers::TransferTerms};
+
+/// A room with a host and a guest, both at JFK
+fn transfer_room(starting_money: u32) -> (MultiplayerGameService, Uuid, Uuid, Uuid) {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Hand Over".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                starting_money,
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let guest = service
+        .join_room(room.room_id, "Guest".to_string(), None)
+        .unwrap();
+    (service, room.room_id, room.host_player_id, guest.player_id)
+}
+
+fn money_and_cargo(
+    service: &MultiplayerGameService,
+    room_id: Uuid,
+    player_id: Uuid,
+    cargo_type: &str,
+) -> (u32, u32) {
+    let state = service.get_room_state(room_id, player_id).unwrap();
+    let player = state
+        .players
+        .into_iter()
+        .find(|player| player.id == Some(player_id))
+        .unwrap();
+    let quantity = player.cargo_inventory.get(cargo_type).copied().unwrap_or(0);
+    (player.money, quantity)
+}
+
+fn buy(service: &MultiplayerGameService, room_id: Uuid, player_id: Uuid, cargo: &str, n: u32) {
+    let bought = service
+        .player_trade(
+            room_id,
+            player_id,
+            TradeRequest {
+                cargo_type: cargo.to_string(),
+                quantity: n,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(bought.success, "{}", bought.message);
+}
+
+fn send(recipient_id: Uuid, money: u32, cargo: Option<(&str, u32)>) -> TransferTerms {
+    TransferTerms {
+        recipient_id,
+        money,
+        cargo_type: cargo.map(|(cargo_type, _)| cargo_type.to_string()),
+        quantity: cargo.map(|(_, quantity)| quantity).unwrap_or(0),
+    }
+}
+
+#[test]
+fn test_money_and_cargo_change_hands() {
+    let (service, room_id, host, guest) = transfer_room(10000);
+    buy(&service, room_id, host, "electronics", 10);
+    let (host_money, _) = money_and_cargo(&service, room_id, host, "electronics");
+
+    let response = service
+        .transfer(room_id, host, send(guest, 1500, Some(("electronics", 4))))
+        .unwrap();
+    assert_eq!(response.new_money, host_money - 1500);
+    assert_eq!(response.new_inventory.get("electronics"), Some(&6));
+    assert_eq!(response.transfer.recipient_name, "Guest");
+    assert_eq!(response.transfer.airport_id, "JFK");
+    assert!(response.message.contains("$1500 and 4 electronics"));
+    let localized = response.localized.unwrap();
+    assert_eq!(localized.code, MessageCode::GaveMoneyAndCargo);
+    assert_eq!(
+        localized.render(Locale::Spanish),
+        "Diste $1500 y 4 electronics a Guest en JFK"
+    );
+
+    assert_eq!(
+        money_and_cargo(&service, room_id, host, "electronics"),
+        (host_money - 1500, 6)
+    );
+    assert_eq!(
+        money_and_cargo(&service, room_id, guest, "electronics"),
+        (11500, 4)
+    );
+}
+
+#[test]
+fn test_transfers_need_the_goods_and_the_space() {
+    let (service, room_id, host, guest) = transfer_room(40000);
+    buy(&service, room_id, host, "food", 5);
+
+    for (terms, error) in [
+        (send(guest, 0, None), "Send some money"),
+        (send(host, 100, None), "yourself"),
+        (send(guest, 1_000_000, None), "don't have $1000000"),
+        (send(guest, 0, Some(("food", 6))), "don't have 6 food"),
+        (send(guest, 0, Some(("gold", 1))), "Unknown cargo type"),
+    ] {
+        let rejected = service.transfer(room_id, host, terms).unwrap_err();
+        assert!(rejected.to_string().contains(error), "{}", rejected);
+    }
+
+    // Fill the guest's hold, then try to add more. The broker only sells
+    // so much of one cargo a turn, so it takes a few kinds.
+    let state = service.get_room_state(room_id, guest).unwrap();
+    let guest_state = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(guest))
+        .unwrap();
+    let mut space = guest_state.max_cargo_weight;
+    for (cargo, weight) in [("materials", 4), ("food", 2), ("industrial", 5)] {
+        let units = (space / weight).min(BROKER_TIERS[0].units_per_turn);
+        if units > 0 {
+            buy(&service, room_id, guest, cargo, units);
+            space -= units * weight;
+        }
+    }
+    let (host_money, host_food) = money_and_cargo(&service, room_id, host, "food");
+    let rejected = service
+        .transfer(room_id, host, send(guest, 500, Some(("food", 5))))
+        .unwrap_err();
+    assert!(
+        rejected.to_string().contains("no room left"),
+        "{}",
+        rejected
+    );
+
+    // Nothing moved, not even the money
+    assert_eq!(
+        money_and_cargo(&service, room_id, host, "food"),
+        (host_money, host_food)
+    );
+}
+
+#[test]
+fn test_transfers_only_happen_at_the_same_airport() {
+    let (service, room_id, host, guest) = transfer_room(10000);
+    let fuel = service
+        .player_buy_fuel(room_id, guest, FuelRequest { quantity: 50 })
+        .unwrap();
+    assert!(fuel.success, "{}", fuel.message);
+    let travel = service
+        .player_travel(room_id, guest, "ORD".to_string())
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+
+    let rejected = service
+        .transfer(room_id, host, send(guest, 100, None))
+        .unwrap_err();
+    assert!(
+        rejected.to_string().contains("Guest is at ORD"),
+        "{}",
+        rejected
+    );
+}
+
+#[test]
+fn test_money_received_can_win_the_game() {
+    let (service, room_id, host, guest) = transfer_room(60000);
+    let response = service
+        .transfer(room_id, host, send(guest, 50000, None))
+        .unwrap();
+    assert!(response.message.contains("Guest won the game"));
+    assert_eq!(
+        response.localized.map(|localized| localized.code),
+        Some(MessageCode::GaveMoneyAndWon)
+    );
+
+    let state = service.get_room_state(room_id, host).unwrap();
+    assert_eq!(state.winner.map(|winner| winner.player_id), Some(guest));
+}
+
+#[test]
+fn test_transfers_to_strangers_are_not_found() {
+    let (service, room_id, host, _) = transfer_room(10000);
+    let rejected = service
+        .transfer(room_id, host, send(Uuid::new_v4(), 100, None))
+        .unwrap_err();
+    assert_eq!(rejected, ServiceError::NotFound(ErrorCode::PlayerNotInRoom));
+}
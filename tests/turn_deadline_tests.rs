@@ -0,0 +1,264 @@
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+use kzrk::api::models::{CreateTemplateRequest, FuelRequest, TurnDeadlineRequest};
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::api::webhooks::WebhookSender;
+use kzrk::systems::RoomSettings;
+
+type Posted = (String, serde_json::Value);
+
+/// Keeps webhook posts instead of sending them
+#[derive(Default)]
+struct RecordingSender {
+    posted: Mutex<Vec<Posted>>,
+}
+
+impl RecordingSender {
+    fn take(&self) -> Vec<Posted> {
+        std::mem::take(&mut self.posted.lock().unwrap())
+    }
+}
+
+impl WebhookSender for RecordingSender {
+    fn post(&self, url: &str, body: &serde_json::Value) -> Result<(), String> {
+        self.posted
+            .lock()
+            .unwrap()
+            .push((url.to_string(), body.clone()));
+        Ok(())
+    }
+}
+
+struct FailingSender;
+
+impl WebhookSender for FailingSender {
+    fn post(&self, _url: &str, _body: &serde_json::Value) -> Result<(), String> {
+        Err("unreachable".to_string())
+    }
+}
+
+const HOOK: &str = "https://discord.example.com/api/webhooks/1/abc";
+
+fn deadline(hours: u32, warning_hours: u32) -> TurnDeadlineRequest {
+    TurnDeadlineRequest {
+        webhook_url: Some(HOOK.to_string()),
+        hours: Some(hours),
+        warning_hours: Some(warning_hours),
+    }
+}
+
+fn minutes_from_now(minutes: i64) -> chrono::DateTime<chrono::Utc> {
+    chrono::Utc::now() + chrono::Duration::minutes(minutes)
+}
+
+fn setup() -> (MultiplayerGameService, Uuid, Uuid, Uuid) {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "By Post".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let guest = service
+        .join_room(room.room_id, "Guest".to_string(), None)
+        .unwrap();
+    (service, room.room_id, room.host_player_id, guest.player_id)
+}
+
+fn alert_kinds(posted: &[Posted], player_name: &str) -> Vec<String> {
+    posted
+        .iter()
+        .filter(|(_, body)| body["alert"]["player_name"] == player_name)
+        .map(|(_, body)| body["alert"]["kind"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_only_the_host_sets_a_valid_deadline() {
+    let (service, room_id, host_id, guest_id) = setup();
+
+    let err = service
+        .set_turn_deadline(room_id, guest_id, deadline(24, 2))
+        .unwrap_err();
+    assert!(err.to_string().contains("Only the host"), "{err}");
+
+    let not_a_url = TurnDeadlineRequest {
+        webhook_url: Some("discord please".to_string()),
+        ..deadline(24, 2)
+    };
+    assert!(
+        service
+            .set_turn_deadline(room_id, host_id, not_a_url)
+            .is_err()
+    );
+    assert!(
+        service
+            .set_turn_deadline(room_id, host_id, deadline(24, 24))
+            .is_err()
+    );
+    assert!(
+        service
+            .set_turn_deadline(room_id, host_id, deadline(0, 0))
+            .is_err()
+    );
+
+    let set = service
+        .set_turn_deadline(room_id, host_id, deadline(24, 2))
+        .unwrap();
+    let turn_deadline = set.turn_deadline.unwrap();
+    assert_eq!(turn_deadline.hours, 24);
+    assert_eq!(turn_deadline.warning_hours, 2);
+    assert_eq!(turn_deadline.webhook_url, HOOK);
+
+    let cleared = service
+        .set_turn_deadline(
+            room_id,
+            host_id,
+            TurnDeadlineRequest {
+                webhook_url: None,
+                hours: None,
+                warning_hours: None,
+            },
+        )
+        .unwrap();
+    assert!(cleared.turn_deadline.is_none());
+}
+
+#[test]
+fn test_each_turn_gets_one_warning_and_one_miss() {
+    let (service, room_id, host_id, _guest_id) = setup();
+    service
+        .set_turn_deadline(room_id, host_id, deadline(2, 1))
+        .unwrap();
+    let sender = RecordingSender::default();
+
+    assert_eq!(
+        service.send_deadline_alerts(&sender, minutes_from_now(30)),
+        Ok(0)
+    );
+
+    assert_eq!(
+        service.send_deadline_alerts(&sender, minutes_from_now(70)),
+        Ok(2)
+    );
+    let posted = sender.take();
+    assert!(posted.iter().all(|(url, _)| url == HOOK));
+    assert_eq!(alert_kinds(&posted, "Host"), ["Approaching"]);
+    assert_eq!(alert_kinds(&posted, "Guest"), ["Approaching"]);
+    let (_, body) = &posted[0];
+    assert_eq!(body["content"], body["text"]);
+    assert!(body["content"].as_str().unwrap().contains("By Post"));
+
+    // Already warned
+    assert_eq!(
+        service.send_deadline_alerts(&sender, minutes_from_now(90)),
+        Ok(0)
+    );
+
+    assert_eq!(
+        service.send_deadline_alerts(&sender, minutes_from_now(150)),
+        Ok(2)
+    );
+    let posted = sender.take();
+    assert_eq!(alert_kinds(&posted, "Guest"), ["Missed"]);
+    assert!(posted[0].1["content"].as_str().unwrap().contains("missed"));
+
+    assert_eq!(
+        service.send_deadline_alerts(&sender, minutes_from_now(600)),
+        Ok(0)
+    );
+}
+
+#[test]
+fn test_taking_a_turn_starts_a_new_deadline() {
+    let (service, room_id, host_id, _guest_id) = setup();
+    service
+        .set_turn_deadline(room_id, host_id, deadline(2, 1))
+        .unwrap();
+    let sender = RecordingSender::default();
+    service
+        .send_deadline_alerts(&sender, minutes_from_now(70))
+        .unwrap();
+    sender.take();
+
+    let state = service.get_room_state(room_id, host_id).unwrap();
+    let host = state
+        .players
+        .iter()
+        .find(|player| player.id == Some(host_id))
+        .unwrap();
+    let space = host.max_fuel - host.fuel;
+    service
+        .player_buy_fuel(room_id, host_id, FuelRequest { quantity: space })
+        .unwrap();
+    let travel = service
+        .player_travel(room_id, host_id, "ORD".to_string())
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+
+    service
+        .send_deadline_alerts(&sender, minutes_from_now(70))
+        .unwrap();
+    let posted = sender.take();
+    assert_eq!(alert_kinds(&posted, "Host"), ["Approaching"]);
+    assert_eq!(posted[0].1["alert"]["turn_number"], 2);
+    assert!(alert_kinds(&posted, "Guest").is_empty());
+}
+
+#[test]
+fn test_failed_alerts_are_not_retried() {
+    let (service, room_id, host_id, _guest_id) = setup();
+    service
+        .set_turn_deadline(room_id, host_id, deadline(2, 1))
+        .unwrap();
+
+    assert_eq!(
+        service.send_deadline_alerts(&FailingSender, minutes_from_now(70)),
+        Ok(0)
+    );
+    let sender = RecordingSender::default();
+    assert_eq!(
+        service.send_deadline_alerts(&sender, minutes_from_now(80)),
+        Ok(0)
+    );
+}
+
+#[test]
+fn test_copied_rules_leave_the_webhook_behind() {
+    let (service, room_id, host_id, _guest_id) = setup();
+    service
+        .set_turn_deadline(room_id, host_id, deadline(24, 2))
+        .unwrap();
+
+    let clone = service
+        .clone_room(room_id, None, "Copycat".to_string())
+        .unwrap();
+    let template = service
+        .create_template(CreateTemplateRequest {
+            name: "By Post".to_string(),
+            from_room_id: Some(room_id),
+            max_players: None,
+            settings: None,
+        })
+        .unwrap();
+    assert!(template.settings.turn_deadline.is_none());
+
+    let sender = RecordingSender::default();
+    service
+        .send_deadline_alerts(&sender, minutes_from_now(24 * 60))
+        .unwrap();
+    assert!(
+        sender
+            .take()
+            .iter()
+            .all(|(_, body)| body["alert"]["room_id"] != clone.room_id.to_string())
+    );
+}
@@ -0,0 +1,136 @@
+use uuid::Uuid;
+
+use kzrk::api::models::{CreateGameRequest, FuelRequest, TradeAction, TradeRequest, TravelRequest};
+use kzrk::api::{multiplayer_service::MultiplayerGameService, service::GameService};
+use kzrk::data::{airports::get_default_airports, cargo_types::get_default_cargo_types};
+use kzrk::systems::{GameState, RoomSettings, TravelSystem, TurnAction, TurnEngine};
+
+#[test]
+fn test_only_getting_somewhere_costs_a_turn() {
+    assert!(TurnEngine::costs_turn(TurnAction::Fly));
+    assert!(TurnEngine::costs_turn(TurnAction::GroundTransfer));
+    assert!(!TurnEngine::costs_turn(TurnAction::Trade));
+    assert!(!TurnEngine::costs_turn(TurnAction::BuyFuel));
+
+    let mut game_state = GameState::new(get_default_airports(), get_default_cargo_types());
+    assert_eq!(
+        TurnEngine::finish(&mut game_state, TurnAction::BuyFuel),
+        None
+    );
+    assert_eq!(game_state.turn_number, 1);
+    assert!(TurnEngine::finish(&mut game_state, TurnAction::Fly).is_some());
+    assert_eq!(game_state.turn_number, 2);
+}
+
+#[test]
+fn test_single_player_api_charges_turns_like_the_terminal_game() {
+    let service = GameService::new();
+    let game = service
+        .create_game(CreateGameRequest {
+            player_name: "Solo".to_string(),
+            starting_money: None,
+            starting_airport: None,
+        })
+        .unwrap();
+    let session_id = game.session_id;
+    let start_turn = game.game_state.turn_number;
+
+    let fuel = service
+        .buy_fuel(session_id, FuelRequest { quantity: 10 })
+        .unwrap();
+    assert!(fuel.success, "{}", fuel.message);
+    assert!(!fuel.advanced_turn);
+    assert_eq!(fuel.game_state.unwrap().turn_number, start_turn);
+
+    let trade = service
+        .trade(
+            session_id,
+            TradeRequest {
+                cargo_type: "food".to_string(),
+                quantity: 1,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(trade.success, "{}", trade.message);
+    assert!(!trade.advanced_turn);
+    assert_eq!(trade.game_state.unwrap().turn_number, start_turn);
+
+    let travel = service
+        .travel(
+            session_id,
+            TravelRequest {
+                destination: "ORD".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+    assert!(travel.advanced_turn);
+    assert_eq!(travel.game_state.unwrap().turn_number, start_turn + 1);
+
+    let mut terminal = GameState::new(get_default_airports(), get_default_cargo_types());
+    terminal.player.fuel = terminal.player.max_fuel;
+    let terminal_start = terminal.turn_number;
+    TravelSystem::travel_to(&mut terminal, "JFK").unwrap();
+    assert_eq!(terminal.turn_number, terminal_start + 1);
+}
+
+fn player_turn(service: &MultiplayerGameService, room_id: Uuid, player_id: Uuid) -> Option<u32> {
+    service
+        .get_room_state(room_id, player_id)
+        .unwrap()
+        .players
+        .iter()
+        .find(|player| player.id == Some(player_id))
+        .and_then(|player| player.turn_number)
+}
+
+#[test]
+fn test_rooms_charge_the_same_turns_as_the_single_player_api() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Same Rules".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                cargo_loss_chance: 0.0,
+                fuel_outage_chance: 0.0,
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, player_id) = (room.room_id, room.host_player_id);
+    let start_turn = player_turn(&service, room_id, player_id);
+
+    let fuel = service
+        .player_buy_fuel(room_id, player_id, FuelRequest { quantity: 10 })
+        .unwrap();
+    assert!(fuel.success, "{}", fuel.message);
+    assert!(!fuel.advanced_turn);
+
+    let trade = service
+        .player_trade(
+            room_id,
+            player_id,
+            TradeRequest {
+                cargo_type: "food".to_string(),
+                quantity: 1,
+                action: TradeAction::Buy,
+            },
+        )
+        .unwrap();
+    assert!(trade.success, "{}", trade.message);
+    assert!(!trade.advanced_turn);
+    assert_eq!(player_turn(&service, room_id, player_id), start_turn);
+
+    let travel = service
+        .player_travel(room_id, player_id, "ORD".to_string())
+        .unwrap();
+    assert!(travel.success, "{}", travel.message);
+    assert!(travel.advanced_turn);
+    assert_eq!(
+        player_turn(&service, room_id, player_id),
+        start_turn.map(|turn| turn + 1)
+    );
+}
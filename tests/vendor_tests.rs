@@ -0,0 +1,221 @@
+use rand::{SeedableRng, rngs::StdRng};
+use uuid::Uuid;
+
+use kzrk::{
+    api::multiplayer_service::MultiplayerGameService,
+    data::{airports::get_default_airports, cargo_types::get_default_cargo_types},
+    systems::{
+        GameRoom, RoomSettings,
+        multiplayer::SpawnRule,
+        vendors::{INTEL_AIRPORTS, STALL_ROTATION_TICKS, VendorItem, VendorPurchase, VendorStall},
+    },
+};
+
+fn room_at_jfk() -> (GameRoom, Uuid) {
+    let host_id = Uuid::new_v4();
+    let mut room = GameRoom::new(
+        "Bazaar".to_string(),
+        host_id,
+        "Shopper".to_string(),
+        4,
+        get_default_airports(),
+        get_default_cargo_types(),
+    );
+    room.players
+        .get_mut(&host_id)
+        .unwrap()
+        .player
+        .current_airport = "JFK".to_string();
+    (room, host_id)
+}
+
+fn put_up_stall(room: &mut GameRoom, airport_id: &str, item: VendorItem, price: u32) -> Uuid {
+    let stall = VendorStall {
+        id: Uuid::new_v4(),
+        airport_id: airport_id.to_string(),
+        vendor: "Tarmac Tony".to_string(),
+        item,
+        price,
+        closes_at_tick: room.shared_state.world_tick + STALL_ROTATION_TICKS,
+    };
+    let id = stall.id;
+    room.shared_state.vendors.stalls.push(stall);
+    id
+}
+
+#[test]
+fn test_fuel_voucher_fills_the_tank_and_comes_down() {
+    let (mut room, player_id) = room_at_jfk();
+    let stall_id = put_up_stall(&mut room, "JFK", VendorItem::FuelVoucher { fuel: 50 }, 120);
+    let player = &mut room.get_player_mut(&player_id).unwrap().player;
+    player.fuel = player.max_fuel - 30;
+    let money = player.money;
+
+    let (stall, purchase) = room.buy_from_stall(&player_id, &stall_id).unwrap();
+    assert_eq!(stall.price, 120);
+    // Only as much fuel as the tank has room for
+    assert_eq!(purchase, VendorPurchase::Fuel { added: 30 });
+
+    let player = &room.get_player(&player_id).unwrap().player;
+    assert_eq!(player.fuel, player.max_fuel);
+    assert_eq!(player.money, money - 120);
+    assert!(room.shared_state.vendors.stalls.is_empty());
+
+    let err = room.buy_from_stall(&player_id, &stall_id).unwrap_err();
+    assert!(err.to_string().contains("packed up"), "{}", err);
+}
+
+#[test]
+fn test_stall_prices_hold_up_at_extreme_pump_prices() {
+    assert_eq!(VendorItem::voucher_price(50, 100), 3000);
+    assert_eq!(VendorItem::voucher_price(1, 1), 1);
+    // 80 units at a runaway pump price would overflow a u32 on the way
+    assert_eq!(VendorItem::voucher_price(80, 60_000_000), 2_880_000_000);
+    assert_eq!(VendorItem::voucher_price(80, u32::MAX), u32::MAX);
+
+    assert_eq!(VendorItem::crate_price(10, 100), 700);
+    assert_eq!(VendorItem::crate_price(15, u64::MAX), u32::MAX);
+}
+
+#[test]
+fn test_restock_survives_a_runaway_fuel_price() {
+    let (mut room, _) = room_at_jfk();
+    for market in room.shared_state.markets.values_mut() {
+        market.fuel_price = u32::MAX;
+    }
+    let shared = &mut room.shared_state;
+    shared.vendors.restock(
+        &shared.markets,
+        &shared.cargo_types,
+        1,
+        false,
+        &mut StdRng::seed_from_u64(7),
+    );
+    for stall in &shared.vendors.stalls {
+        if let VendorItem::FuelVoucher { .. } = stall.item {
+            assert_eq!(stall.price, u32::MAX);
+        }
+    }
+}
+
+#[test]
+fn test_cargo_crate_lands_in_the_hold() {
+    let (mut room, player_id) = room_at_jfk();
+    let stall_id = put_up_stall(&mut room, "JFK", VendorItem::CargoCrate { units: 5 }, 500);
+
+    let (_, purchase) = room.buy_from_stall(&player_id, &stall_id).unwrap();
+    let VendorPurchase::Cargo { cargo_type, units } = purchase else {
+        panic!("expected cargo, got {:?}", purchase);
+    };
+    assert_eq!(units, 5);
+    assert!(room.shared_state.cargo_types.contains_key(&cargo_type));
+    let player = &room.get_player(&player_id).unwrap().player;
+    assert_eq!(player.cargo_inventory.get_quantity(&cargo_type), 5);
+}
+
+#[test]
+fn test_stalls_only_sell_to_players_standing_at_them() {
+    let (mut room, player_id) = room_at_jfk();
+    let stall_id = put_up_stall(&mut room, "LAX", VendorItem::FuelVoucher { fuel: 50 }, 10);
+
+    let err = room.buy_from_stall(&player_id, &stall_id).unwrap_err();
+    assert!(err.to_string().contains("LAX"), "{}", err);
+    assert_eq!(room.shared_state.vendors.stalls.len(), 1);
+}
+
+#[test]
+fn test_stalls_refuse_players_who_cannot_pay() {
+    let (mut room, player_id) = room_at_jfk();
+    let stall_id = put_up_stall(&mut room, "JFK", VendorItem::MapIntel, 1_000_000);
+
+    let err = room.buy_from_stall(&player_id, &stall_id).unwrap_err();
+    assert!(err.to_string().contains("wants"), "{}", err);
+    assert_eq!(room.shared_state.vendors.stalls.len(), 1);
+}
+
+#[test]
+fn test_map_intel_reveals_prices_elsewhere() {
+    let (mut room, player_id) = room_at_jfk();
+    let stall_id = put_up_stall(&mut room, "JFK", VendorItem::MapIntel, 150);
+
+    let (_, purchase) = room.buy_from_stall(&player_id, &stall_id).unwrap();
+    let VendorPurchase::Intel { airports } = purchase else {
+        panic!("expected intel, got {:?}", purchase);
+    };
+    assert_eq!(airports.len(), INTEL_AIRPORTS);
+    assert!(!airports.contains(&"JFK".to_string()));
+
+    let player_state = room.get_player(&player_id).unwrap();
+    for airport_id in &airports {
+        let sighting = &player_state.market_knowledge[airport_id];
+        assert_eq!(
+            sighting.fuel_price,
+            room.shared_state.markets[airport_id].fuel_price
+        );
+    }
+}
+
+#[test]
+fn test_vendors_move_on_after_a_few_turns() {
+    let (mut room, _) = room_at_jfk();
+    let mut rng = StdRng::seed_from_u64(7);
+    room.shared_state.world_tick = 1;
+    room.rotate_vendor_stalls(&mut rng);
+    assert_eq!(room.shared_state.vendors.stocked_at_tick, 1);
+    let stall_id = put_up_stall(&mut room, "JFK", VendorItem::MapIntel, 150);
+
+    room.shared_state.world_tick = STALL_ROTATION_TICKS;
+    room.rotate_vendor_stalls(&mut rng);
+    assert!(room.shared_state.vendors.take(&stall_id).is_some());
+
+    room.shared_state.world_tick = 1 + STALL_ROTATION_TICKS;
+    room.rotate_vendor_stalls(&mut rng);
+    assert_eq!(
+        room.shared_state.vendors.stocked_at_tick,
+        1 + STALL_ROTATION_TICKS
+    );
+    assert!(
+        room.shared_state
+            .vendors
+            .stalls
+            .iter()
+            .all(|stall| stall.closes_at_tick == 1 + 2 * STALL_ROTATION_TICKS)
+    );
+}
+
+#[test]
+fn test_rooms_can_turn_the_vendors_away() {
+    let (mut room, player_id) = room_at_jfk();
+    room.settings.vendor_stalls = false;
+    for _ in 0..(STALL_ROTATION_TICKS * 4) {
+        room.advance_turn(&player_id);
+    }
+    assert!(room.shared_state.vendors.stalls.is_empty());
+}
+
+#[test]
+fn test_service_lists_stalls_at_the_players_airport() {
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room_with_settings(
+            "Bazaar".to_string(),
+            "Host".to_string(),
+            None,
+            RoomSettings {
+                spawn: SpawnRule::HostChosen("JFK".to_string()),
+                ..RoomSettings::default()
+            },
+        )
+        .unwrap();
+    let (room_id, host_id) = (room.room_id, room.host_player_id);
+
+    let stalls = service.get_stalls(room_id, host_id).unwrap();
+    assert_eq!(stalls.airport_id, "JFK");
+    assert!(stalls.vendors_enabled);
+    assert!(stalls.stalls.iter().all(|stall| stall.price > 0));
+
+    let err = service
+        .buy_from_stall(room_id, host_id, Uuid::new_v4())
+        .unwrap_err();
+    assert!(err.to_string().contains("packed up"), "{}", err);
+}
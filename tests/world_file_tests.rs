@@ -0,0 +1,108 @@
+use std::path::Path;
+
+use kzrk::api::multiplayer_service::MultiplayerGameService;
+use kzrk::data::world::{self, World, WorldFormat};
+use kzrk::data::{
+    airports::builtin_airports, cargo_types::builtin_cargo_types, get_default_airports,
+    get_default_cargo_types,
+};
+use kzrk::systems::GameState;
+
+const ISLANDS: &str = r#"{
+  "name": "Island Hopper",
+  "airports": [
+    {
+      "id": "HNL",
+      "name": "Honolulu",
+      "coordinates": [21.3187, -157.9225],
+      "base_fuel_price": 90,
+      "market_profile": {"produces": ["pineapples"], "consumes": ["surfboards"], "fuel_modifier": 1.0}
+    },
+    {
+      "id": "OGG",
+      "name": "Kahului",
+      "coordinates": [20.8986, -156.4305],
+      "base_fuel_price": 95,
+      "market_profile": {"produces": ["surfboards"], "consumes": ["pineapples"], "fuel_modifier": 1.1}
+    }
+  ],
+  "cargo_types": [
+    {"id": "pineapples", "name": "Pineapples", "base_price": 40, "weight_per_unit": 2, "volatility": 0.2},
+    {"id": "surfboards", "name": "Surfboards", "base_price": 300, "weight_per_unit": 3, "volatility": 0.3}
+  ]
+}"#;
+
+#[test]
+fn test_shipped_north_america_world_extends_the_builtin_one() {
+    let world = World::load(Path::new("worlds/north_america.toml")).unwrap();
+    assert_eq!(world.label(), "North America");
+    assert!(world.airports.len() > 12);
+
+    let airports = world.airport_map();
+    for (id, builtin) in builtin_airports() {
+        assert_eq!(airports[&id], builtin, "{}", id);
+    }
+    assert_eq!(world.cargo_type_map(), builtin_cargo_types());
+}
+
+#[test]
+fn test_broken_worlds_are_rejected() {
+    let parse = |json: &str| World::parse(json, WorldFormat::Json).unwrap_err();
+
+    assert!(parse("{\"airports\": [").contains("Invalid world"));
+
+    let mut world: serde_json::Value = serde_json::from_str(ISLANDS).unwrap();
+    world["airports"].as_array_mut().unwrap().pop();
+    assert!(parse(&world.to_string()).contains("at least two airports"));
+
+    let mut world: serde_json::Value = serde_json::from_str(ISLANDS).unwrap();
+    world["airports"][1]["id"] = "HNL".into();
+    assert!(parse(&world.to_string()).contains("HNL is in the world twice"));
+
+    // Without its own cargo types the world trades the built-in ones
+    let mut world: serde_json::Value = serde_json::from_str(ISLANDS).unwrap();
+    world["cargo_types"] = serde_json::json!([]);
+    assert!(parse(&world.to_string()).contains("unknown cargo type"));
+
+    assert!(
+        World::load(Path::new("worlds/islands.yaml"))
+            .unwrap_err()
+            .contains(".toml or .json")
+    );
+}
+
+#[test]
+fn test_world_flag_replaces_the_builtin_world_everywhere() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("islands.json");
+    std::fs::write(&path, ISLANDS).unwrap();
+
+    let mut args: Vec<String> = ["kzrk", "--world", path.to_str().unwrap(), "api"]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+    let loaded = world::init(&mut args).unwrap().unwrap();
+    assert_eq!(args, ["kzrk", "api"]);
+    assert_eq!(loaded.label(), "Island Hopper");
+    assert!(world::installed().is_some());
+    assert!(world::install(World::default()).is_err());
+
+    let airports = get_default_airports();
+    assert_eq!(airports.len(), 2);
+    assert!(airports.contains_key("OGG"));
+    assert!(get_default_cargo_types().contains_key("surfboards"));
+
+    // Single player starts at the first airport when the world has no O'Hare
+    let game = GameState::new(get_default_airports(), get_default_cargo_types());
+    assert_eq!(game.player.current_airport, "HNL");
+
+    let service = MultiplayerGameService::new_in_memory();
+    let room = service
+        .create_room("Luau".to_string(), "Host".to_string(), None)
+        .unwrap();
+    let state = service
+        .get_room_state(room.room_id, room.host_player_id)
+        .unwrap();
+    assert_eq!(state.current_market.airport_id, "HNL");
+    assert_eq!(state.cargo_types.len(), 2);
+}